@@ -0,0 +1,13 @@
+//! Generates the gRPC messages and service traits from
+//! `proto/twh/v1/warehouse.proto`. The proto is parsed with `protox`, so the
+//! build needs no `protoc`.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=proto");
+
+    let file_descriptors = protox::compile(["twh/v1/warehouse.proto"], ["proto"])?;
+    tonic_build::configure()
+        .build_client(false)
+        .compile_fds(file_descriptors)?;
+    Ok(())
+}
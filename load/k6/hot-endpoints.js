@@ -0,0 +1,99 @@
+// Hot read paths against a tenant loaded with `seed`. See docs/load-testing.md.
+//
+//   k6 run -e BASE_URL=http://localhost:8080 -e TENANT_ID=<id> load/k6/hot-endpoints.js
+
+import http from 'k6/http';
+import { check } from 'k6';
+
+const BASE_URL = __ENV.BASE_URL || 'http://localhost:8080';
+const TENANT_ID = __ENV.TENANT_ID;
+// Pages each paginated walk follows before starting over
+const PAGES = Number(__ENV.PAGES || 5);
+const LIMIT = Number(__ENV.LIMIT || 100);
+
+const params = (name) => ({
+  headers: { 'X-Tenant-ID': TENANT_ID },
+  tags: { name },
+});
+
+export const options = {
+  scenarios: {
+    browse: {
+      executor: 'constant-vus',
+      vus: Number(__ENV.VUS || 20),
+      duration: __ENV.DURATION || '2m',
+      exec: 'browse',
+    },
+    reports: {
+      executor: 'constant-vus',
+      vus: Number(__ENV.REPORT_VUS || 2),
+      duration: __ENV.DURATION || '2m',
+      exec: 'reports',
+    },
+  },
+  thresholds: {
+    http_req_failed: ['rate<0.01'],
+    'http_req_duration{name:items}': ['p(95)<300'],
+    'http_req_duration{name:stock_levels}': ['p(95)<300'],
+    'http_req_duration{name:stock_movements}': ['p(95)<300'],
+    'http_req_duration{name:search}': ['p(95)<300'],
+    'http_req_duration{name:stock_valuation}': ['p(95)<2000'],
+    'http_req_duration{name:low_stock}': ['p(95)<2000'],
+    'http_req_duration{name:dead_stock}': ['p(95)<2000'],
+  },
+};
+
+export function setup() {
+  if (!TENANT_ID) {
+    throw new Error('Set TENANT_ID to the tenant loaded with `seed`');
+  }
+  const locations = http.get(`${BASE_URL}/locations?limit=100`, params('setup')).json('data');
+  if (!locations || locations.length === 0) {
+    throw new Error(`Tenant ${TENANT_ID} has no locations; run \`seed\` first`);
+  }
+  return { locationIds: locations.map((location) => location.id) };
+}
+
+// Follow `cursor.next_cursor` for up to PAGES pages
+function walk(path, name) {
+  let cursor = null;
+  for (let page = 0; page < PAGES; page++) {
+    const separator = path.includes('?') ? '&' : '?';
+    const url = `${BASE_URL}${path}${separator}limit=${LIMIT}${cursor ? `&cursor=${encodeURIComponent(cursor)}` : ''}`;
+    const response = http.get(url, params(name));
+    check(response, { [`${name} 200`]: (r) => r.status === 200 });
+    if (response.status !== 200) {
+      return;
+    }
+    cursor = response.json('cursor.next_cursor');
+    if (!cursor) {
+      return;
+    }
+  }
+}
+
+function pick(values) {
+  return values[Math.floor(Math.random() * values.length)];
+}
+
+const TERMS = ['pallet', 'cable', 'bolt', 'label', 'case', 'large'];
+
+export function browse(data) {
+  const locationId = pick(data.locationIds);
+  walk('/items', 'items');
+  walk(`/stock/levels?location_id=${locationId}`, 'stock_levels');
+  walk(`/stock/movements?location_id=${locationId}`, 'stock_movements');
+  const search = http.get(`${BASE_URL}/search?q=${pick(TERMS)}`, params('search'));
+  check(search, { 'search 200': (r) => r.status === 200 });
+}
+
+export function reports() {
+  for (const [path, name] of [
+    ['/reports/stock_valuation', 'stock_valuation'],
+    ['/reports/low_stock', 'low_stock'],
+    ['/reports/dead-stock', 'dead_stock'],
+  ]) {
+    const response = http.get(`${BASE_URL}${path}`, params(name));
+    check(response, { [`${name} 200`]: (r) => r.status === 200 });
+  }
+}
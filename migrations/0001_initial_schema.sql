@@ -1,5 +1,5 @@
--- Database setup for The Warehouse Hub
--- Run this against your PostgreSQL database to set up the initial schema
+-- Initial schema for The Warehouse Hub
+-- Applied by the API on startup when database.run_migrations is enabled (see src/infrastructure/repositories/schema_migrations.rs)
 
 -- Create database (run this as superuser if needed)
 -- CREATE DATABASE twh;
@@ -13,15 +13,34 @@ CREATE TABLE IF NOT EXISTS users (
     password_hash VARCHAR(255) NOT NULL,
     first_name VARCHAR(100),
     last_name VARCHAR(100),
+    role VARCHAR(20) NOT NULL DEFAULT 'MEMBER' CHECK (role IN ('ADMIN', 'MEMBER')),
     active BOOLEAN NOT NULL DEFAULT true,
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
 );
 
+-- Role column for databases created before tenant user management
+ALTER TABLE users ADD COLUMN IF NOT EXISTS role VARCHAR(20) NOT NULL DEFAULT 'MEMBER' CHECK (role IN ('ADMIN', 'MEMBER'));
+
 -- Create indexes
 CREATE INDEX IF NOT EXISTS idx_users_email ON users(email);
 CREATE INDEX IF NOT EXISTS idx_users_active ON users(active);
 
+-- Pending invitations for users to join a tenant. Only a hash of the token is kept;
+-- the token itself is handed to the inviter once.
+CREATE TABLE IF NOT EXISTS invitations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL,
+    email VARCHAR(255) NOT NULL,
+    role VARCHAR(20) NOT NULL CHECK (role IN ('ADMIN', 'MEMBER')),
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    accepted_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_invitations_tenant_email ON invitations(tenant_id, email);
+
 -- Tenants table for multi-tenancy
 CREATE TABLE IF NOT EXISTS tenants (
     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
@@ -167,14 +186,15 @@ CREATE TABLE IF NOT EXISTS stock_movements (
     location_id UUID NOT NULL REFERENCES locations(id),
     movement_type VARCHAR(20) NOT NULL CHECK (movement_type IN ('inbound', 'outbound', 'adjustment', 'transfer', 'initial')),
     quantity INTEGER NOT NULL,
-    reference_type VARCHAR(20) NOT NULL CHECK (reference_type IN ('purchase_order', 'sales_order', 'adjustment', 'transfer', 'initial', 'return')),
+    reference_type VARCHAR(20) NOT NULL CHECK (reference_type IN ('purchase_order', 'sales_order', 'adjustment', 'transfer', 'initial', 'return', 'cycle_count')),
     reference_id UUID,
     reason TEXT,
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     created_by UUID REFERENCES users(id),
     CONSTRAINT positive_quantity CHECK (
-        (movement_type IN ('inbound', 'adjustment', 'initial') AND quantity >= 0) OR
-        (movement_type IN ('outbound', 'transfer') AND quantity <= 0)
+        (movement_type IN ('inbound', 'initial') AND quantity >= 0) OR
+        (movement_type IN ('outbound', 'transfer') AND quantity <= 0) OR
+        movement_type = 'adjustment'
     )
 );
 
@@ -183,26 +203,68 @@ CREATE INDEX IF NOT EXISTS idx_stock_movements_item_location ON stock_movements(
 CREATE INDEX IF NOT EXISTS idx_stock_movements_reference ON stock_movements(reference_type, reference_id);
 CREATE INDEX IF NOT EXISTS idx_stock_movements_created_at ON stock_movements(created_at);
 CREATE INDEX IF NOT EXISTS idx_stock_movements_movement_type ON stock_movements(movement_type);
+-- Keyset pagination of movement history (newest first, id as tie-breaker)
+CREATE INDEX IF NOT EXISTS idx_stock_movements_item_keyset ON stock_movements(item_id, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_stock_movements_location_keyset ON stock_movements(location_id, created_at DESC, id DESC);
 
 -- Stock levels table - current inventory snapshots
 CREATE TABLE IF NOT EXISTS stock_levels (
     item_id UUID NOT NULL REFERENCES items(id),
     location_id UUID NOT NULL REFERENCES locations(id),
     quantity_on_hand INTEGER NOT NULL DEFAULT 0,
+    quantity_reserved INTEGER NOT NULL DEFAULT 0,
     last_movement_id UUID REFERENCES stock_movements(id),
     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     PRIMARY KEY (item_id, location_id),
-    CONSTRAINT non_negative_stock CHECK (quantity_on_hand >= 0)
+    CONSTRAINT non_negative_stock CHECK (quantity_on_hand >= 0),
+    CONSTRAINT non_negative_reserved CHECK (quantity_reserved >= 0)
 );
 
 -- Create indexes for stock levels
 CREATE INDEX IF NOT EXISTS idx_stock_levels_item ON stock_levels(item_id);
 CREATE INDEX IF NOT EXISTS idx_stock_levels_location ON stock_levels(location_id);
+
+-- Nightly copies of stock_levels, used to answer "what was on hand on date Y"
+-- without replaying every movement since the beginning
+CREATE TABLE IF NOT EXISTS stock_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    snapshot_date DATE NOT NULL,
+    taken_at TIMESTAMPTZ NOT NULL,
+    item_id UUID NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    location_id UUID NOT NULL REFERENCES locations(id) ON DELETE CASCADE,
+    quantity_on_hand INTEGER NOT NULL,
+    quantity_reserved INTEGER NOT NULL DEFAULT 0,
+    tenant_id UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tenant_id, snapshot_date, item_id, location_id)
+);
+
+-- Create indexes for stock snapshots
+CREATE INDEX IF NOT EXISTS idx_stock_snapshots_tenant_taken_at ON stock_snapshots(tenant_id, taken_at);
+
+-- Files linked to items (product photos, spec sheets); the bytes live in blob storage
+CREATE TABLE IF NOT EXISTS item_attachments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    item_id UUID NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes > 0),
+    storage_key TEXT NOT NULL UNIQUE,
+    tenant_id UUID,
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create indexes for item attachments
+CREATE INDEX IF NOT EXISTS idx_item_attachments_item_id ON item_attachments(item_id);
+CREATE INDEX IF NOT EXISTS idx_item_attachments_tenant_id ON item_attachments(tenant_id);
 CREATE INDEX IF NOT EXISTS idx_stock_levels_quantity ON stock_levels(quantity_on_hand);
 
 -- Idempotency store table for request deduplication
 CREATE TABLE IF NOT EXISTS idempotency_keys (
     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID,
+    -- Prefixed with the tenant ID so clients only need unique keys per tenant
     idempotency_key VARCHAR(255) NOT NULL UNIQUE,
     request_path VARCHAR(500) NOT NULL,
     request_method VARCHAR(10) NOT NULL,
@@ -217,6 +279,7 @@ CREATE TABLE IF NOT EXISTS idempotency_keys (
 -- Create indexes for idempotency keys
 CREATE INDEX IF NOT EXISTS idx_idempotency_keys_key ON idempotency_keys(idempotency_key);
 CREATE INDEX IF NOT EXISTS idx_idempotency_keys_expires ON idempotency_keys(expires_at);
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_tenant_id ON idempotency_keys(tenant_id);
 
 -- Search indexes table for full-text search
 CREATE TABLE IF NOT EXISTS search_indexes (
@@ -240,7 +303,7 @@ CREATE TABLE IF NOT EXISTS purchase_orders (
     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
     po_number VARCHAR(100) NOT NULL UNIQUE,
     supplier_id UUID NOT NULL, -- References external supplier system
-    status VARCHAR(20) NOT NULL CHECK (status IN ('DRAFT', 'OPEN', 'RECEIVING', 'PARTIAL_RECEIVED', 'RECEIVED', 'CANCELLED')),
+    status VARCHAR(20) NOT NULL CHECK (status IN ('DRAFT', 'OPEN', 'RECEIVING', 'PARTIAL_RECEIVED', 'RECEIVED', 'CANCELLED', 'CLOSED')),
     expected_date TIMESTAMPTZ,
     total_amount DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (total_amount >= 0),
     created_by UUID NOT NULL REFERENCES users(id),
@@ -279,9 +342,11 @@ CREATE TABLE IF NOT EXISTS sales_orders (
     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
     so_number VARCHAR(100) NOT NULL UNIQUE,
     customer_id UUID, -- References external customer system
-    status VARCHAR(20) NOT NULL CHECK (status IN ('DRAFT', 'CONFIRMED', 'PICKING', 'SHIPPED', 'INVOICED', 'CANCELLED', 'RETURNED')),
+    status VARCHAR(20) NOT NULL CHECK (status IN ('DRAFT', 'CONFIRMED', 'PICKING', 'PARTIALLY_SHIPPED', 'SHIPPED', 'INVOICED', 'CANCELLED', 'RETURNED')),
     total_amount DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (total_amount >= 0),
     fulfillment_location_id UUID REFERENCES locations(id),
+    cancellation_reason TEXT,
+    cancelled_at TIMESTAMPTZ,
     created_by UUID NOT NULL REFERENCES users(id),
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
@@ -301,6 +366,7 @@ CREATE TABLE IF NOT EXISTS sales_order_lines (
     so_id UUID NOT NULL REFERENCES sales_orders(id) ON DELETE CASCADE,
     item_id UUID NOT NULL REFERENCES items(id),
     qty INTEGER NOT NULL CHECK (qty > 0),
+    qty_shipped INTEGER NOT NULL DEFAULT 0 CHECK (qty_shipped >= 0 AND qty_shipped <= qty),
     unit_price DOUBLE PRECISION NOT NULL CHECK (unit_price >= 0),
     tax DOUBLE PRECISION DEFAULT 0 CHECK (tax >= 0),
     reserved BOOLEAN NOT NULL DEFAULT false,
@@ -314,6 +380,59 @@ CREATE INDEX IF NOT EXISTS idx_so_lines_item_id ON sales_order_lines(item_id);
 CREATE INDEX IF NOT EXISTS idx_so_lines_reserved ON sales_order_lines(reserved);
 CREATE INDEX IF NOT EXISTS idx_so_lines_created_at ON sales_order_lines(created_at);
 
+-- Backorders link a follow-up sales order holding unshipped quantities to its original order
+CREATE TABLE IF NOT EXISTS sales_order_backorders (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    original_so_id UUID NOT NULL REFERENCES sales_orders(id) ON DELETE CASCADE,
+    backorder_so_id UUID NOT NULL UNIQUE REFERENCES sales_orders(id) ON DELETE CASCADE,
+    tenant_id UUID,
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create indexes for sales order backorders
+CREATE INDEX IF NOT EXISTS idx_so_backorders_original_so_id ON sales_order_backorders(original_so_id);
+CREATE INDEX IF NOT EXISTS idx_so_backorders_tenant_id ON sales_order_backorders(tenant_id);
+
+-- Sales order allocations table (per-line stock allocations across locations)
+CREATE TABLE IF NOT EXISTS so_allocations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    sales_order_id UUID NOT NULL REFERENCES sales_orders(id) ON DELETE CASCADE,
+    so_line_id UUID NOT NULL REFERENCES sales_order_lines(id) ON DELETE CASCADE,
+    item_id UUID NOT NULL REFERENCES items(id),
+    location_id UUID NOT NULL REFERENCES locations(id),
+    qty_allocated INTEGER NOT NULL CHECK (qty_allocated >= 0),
+    qty_shipped INTEGER NOT NULL DEFAULT 0 CHECK (qty_shipped >= 0 AND qty_shipped <= qty_allocated),
+    tenant_id UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create indexes for sales order allocations
+CREATE INDEX IF NOT EXISTS idx_so_allocations_sales_order_id ON so_allocations(sales_order_id);
+CREATE INDEX IF NOT EXISTS idx_so_allocations_item_location ON so_allocations(item_id, location_id);
+CREATE INDEX IF NOT EXISTS idx_so_allocations_tenant_id ON so_allocations(tenant_id);
+
+-- Putaway rules table (where received goods should be stored)
+CREATE TABLE IF NOT EXISTS putaway_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    item_id UUID REFERENCES items(id) ON DELETE CASCADE,
+    category VARCHAR(100),
+    location_id UUID NOT NULL REFERENCES locations(id) ON DELETE CASCADE,
+    priority INTEGER NOT NULL DEFAULT 100,
+    max_quantity INTEGER CHECK (max_quantity IS NULL OR max_quantity > 0),
+    active BOOLEAN NOT NULL DEFAULT true,
+    tenant_id UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create indexes for putaway rules
+CREATE INDEX IF NOT EXISTS idx_putaway_rules_item_id ON putaway_rules(item_id);
+CREATE INDEX IF NOT EXISTS idx_putaway_rules_category ON putaway_rules(category);
+CREATE INDEX IF NOT EXISTS idx_putaway_rules_tenant_id ON putaway_rules(tenant_id);
+
 -- Transfers table
 CREATE TABLE IF NOT EXISTS transfers (
     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
@@ -353,6 +472,44 @@ CREATE INDEX IF NOT EXISTS idx_transfer_lines_transfer_id ON transfer_lines(tran
 CREATE INDEX IF NOT EXISTS idx_transfer_lines_item_id ON transfer_lines(item_id);
 CREATE INDEX IF NOT EXISTS idx_transfer_lines_created_at ON transfer_lines(created_at);
 
+-- Shipments record carrier and tracking details for shipped sales orders and transfers
+CREATE TABLE IF NOT EXISTS shipments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    shipment_number VARCHAR(100) NOT NULL UNIQUE,
+    source_type VARCHAR(20) NOT NULL CHECK (source_type IN ('SALES_ORDER', 'TRANSFER')),
+    source_id UUID NOT NULL,
+    carrier VARCHAR(100),
+    tracking_number VARCHAR(255),
+    total_weight_kg DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (total_weight_kg >= 0),
+    shipped_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    tenant_id UUID,
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create indexes for shipments
+CREATE INDEX IF NOT EXISTS idx_shipments_source ON shipments(source_type, source_id);
+CREATE INDEX IF NOT EXISTS idx_shipments_tracking_number ON shipments(tracking_number);
+CREATE INDEX IF NOT EXISTS idx_shipments_tenant_id ON shipments(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_shipments_shipped_at ON shipments(shipped_at);
+
+-- Shipment packages table
+CREATE TABLE IF NOT EXISTS shipment_packages (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    shipment_id UUID NOT NULL REFERENCES shipments(id) ON DELETE CASCADE,
+    weight_kg DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (weight_kg >= 0),
+    length_cm DOUBLE PRECISION CHECK (length_cm > 0),
+    width_cm DOUBLE PRECISION CHECK (width_cm > 0),
+    height_cm DOUBLE PRECISION CHECK (height_cm > 0),
+    tracking_number VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create indexes for shipment packages
+CREATE INDEX IF NOT EXISTS idx_shipment_packages_shipment_id ON shipment_packages(shipment_id);
+CREATE INDEX IF NOT EXISTS idx_shipment_packages_tracking_number ON shipment_packages(tracking_number);
+
 -- Create returns table
 CREATE TABLE IF NOT EXISTS returns (
     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
@@ -393,12 +550,56 @@ CREATE INDEX IF NOT EXISTS idx_return_lines_return_id ON return_lines(return_id)
 CREATE INDEX IF NOT EXISTS idx_return_lines_item_id ON return_lines(item_id);
 CREATE INDEX IF NOT EXISTS idx_return_lines_created_at ON return_lines(created_at);
 
+-- Cycle counts table for scheduled physical inventory counts
+CREATE TABLE IF NOT EXISTS cycle_counts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    count_number VARCHAR(100) NOT NULL UNIQUE,
+    location_id UUID NOT NULL REFERENCES locations(id),
+    status VARCHAR(20) NOT NULL DEFAULT 'SCHEDULED' CHECK (status IN ('SCHEDULED', 'IN_PROGRESS', 'COMPLETED', 'CANCELLED')),
+    scheduled_for TIMESTAMPTZ,
+    notes TEXT,
+    tenant_id UUID,
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+-- Create indexes for cycle counts
+CREATE INDEX IF NOT EXISTS idx_cycle_counts_location_id ON cycle_counts(location_id);
+CREATE INDEX IF NOT EXISTS idx_cycle_counts_status ON cycle_counts(status);
+CREATE INDEX IF NOT EXISTS idx_cycle_counts_tenant_id ON cycle_counts(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_cycle_counts_created_at ON cycle_counts(created_at);
+
+-- Cycle count lines table
+CREATE TABLE IF NOT EXISTS cycle_count_lines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    cycle_count_id UUID NOT NULL REFERENCES cycle_counts(id) ON DELETE CASCADE,
+    item_id UUID NOT NULL REFERENCES items(id),
+    expected_qty INTEGER NOT NULL DEFAULT 0,
+    counted_qty INTEGER CHECK (counted_qty >= 0),
+    counted_by UUID REFERENCES users(id),
+    counted_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (cycle_count_id, item_id)
+);
+
+-- Create indexes for cycle count lines
+CREATE INDEX IF NOT EXISTS idx_cycle_count_lines_cycle_count_id ON cycle_count_lines(cycle_count_id);
+CREATE INDEX IF NOT EXISTS idx_cycle_count_lines_item_id ON cycle_count_lines(item_id);
+
 -- Webhooks table for webhook configurations
 CREATE TABLE IF NOT EXISTS webhooks (
     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
     url VARCHAR(2048) NOT NULL,
     secret VARCHAR(255) NOT NULL,
+    -- Previous secret stays valid for signing until it expires after a rotation
+    previous_secret VARCHAR(255),
+    previous_secret_expires_at TIMESTAMPTZ,
     events TEXT[] NOT NULL DEFAULT '{}',
+    -- Optional payload conditions (see WebhookFilter); NULL delivers every subscribed event
+    filter JSONB,
     status VARCHAR(50) NOT NULL DEFAULT 'ACTIVE' CHECK (status IN ('ACTIVE', 'INACTIVE', 'FAILED')),
     created_by UUID NOT NULL REFERENCES users(id),
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
@@ -430,12 +631,14 @@ CREATE TABLE IF NOT EXISTS webhook_deliveries (
     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
     webhook_id UUID NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
     event_id UUID NOT NULL REFERENCES webhook_events(id) ON DELETE CASCADE,
-    status VARCHAR(50) NOT NULL CHECK (status IN ('PENDING', 'SUCCESS', 'FAILED', 'RETRY')),
+    status VARCHAR(50) NOT NULL CHECK (status IN ('PENDING', 'SUCCESS', 'FAILED', 'RETRY', 'TIMEOUT', 'DLQ')),
     attempt_count INTEGER NOT NULL DEFAULT 0 CHECK (attempt_count >= 0),
     response_status INTEGER,
     response_body TEXT,
     error_message TEXT,
     next_retry_at TIMESTAMPTZ,
+    last_attempt_at TIMESTAMPTZ,
+    next_attempt_at TIMESTAMPTZ,
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
 );
@@ -446,6 +649,8 @@ CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_event_id ON webhook_deliveries
 CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_status ON webhook_deliveries(status);
 CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_next_retry ON webhook_deliveries(next_retry_at);
 CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_created_at ON webhook_deliveries(created_at);
+-- Lets the delivery worker find due deliveries without scanning finished ones
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries(next_attempt_at) WHERE status IN ('PENDING', 'FAILED');
 
 -- Tenants table for multi-tenancy support
 CREATE TABLE IF NOT EXISTS tenants (
@@ -483,11 +688,16 @@ CREATE TABLE IF NOT EXISTS jobs (
     job_id VARCHAR(255) NOT NULL UNIQUE,
     tenant_id UUID NOT NULL,
     type VARCHAR(100) NOT NULL,
-    status VARCHAR(50) NOT NULL DEFAULT 'QUEUED' CHECK (status IN ('QUEUED', 'RUNNING', 'SUCCESS', 'FAILED', 'PARTIAL_SUCCESS')),
+    status VARCHAR(50) NOT NULL DEFAULT 'QUEUED' CHECK (status IN ('QUEUED', 'RUNNING', 'SUCCESS', 'FAILED', 'PARTIAL_SUCCESS', 'CANCELLED')),
     progress INTEGER NOT NULL DEFAULT 0 CHECK (progress >= 0 AND progress <= 100),
     payload JSONB,
     result_url VARCHAR(500),
     errors JSONB,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 3,
+    run_after TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    heartbeat_at TIMESTAMPTZ,
+    cancel_requested BOOLEAN NOT NULL DEFAULT FALSE,
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     started_at TIMESTAMPTZ,
@@ -495,6 +705,7 @@ CREATE TABLE IF NOT EXISTS jobs (
 );
 
 -- Create indexes for jobs
+CREATE INDEX IF NOT EXISTS idx_jobs_due ON jobs(run_after) WHERE status = 'QUEUED';
 CREATE INDEX IF NOT EXISTS idx_jobs_job_id ON jobs(job_id);
 CREATE INDEX IF NOT EXISTS idx_jobs_tenant_id ON jobs(tenant_id);
 CREATE INDEX IF NOT EXISTS idx_jobs_type ON jobs(type);
@@ -0,0 +1,5 @@
+-- Per-tenant request rate overrides. NULL keeps the tier's default
+-- (requests_per_minute); the per-key limit falls back to the tenant's.
+ALTER TABLE tenant_quotas
+    ADD COLUMN IF NOT EXISTS max_requests_per_minute INTEGER,
+    ADD COLUMN IF NOT EXISTS max_requests_per_minute_per_api_key INTEGER;
@@ -0,0 +1,68 @@
+-- Return-to-vendor: defective stock shipped back to the supplier of a purchase order
+CREATE TABLE IF NOT EXISTS vendor_returns (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    rtv_number VARCHAR(50) NOT NULL UNIQUE,
+    po_id UUID NOT NULL REFERENCES purchase_orders(id),
+    supplier_id UUID NOT NULL,
+    location_id UUID NOT NULL REFERENCES locations(id),
+    status VARCHAR(20) NOT NULL DEFAULT 'DRAFT' CHECK (status IN ('DRAFT', 'SHIPPED', 'CANCELLED')),
+    total_quantity INTEGER NOT NULL DEFAULT 0 CHECK (total_quantity >= 0),
+    total_amount DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (total_amount >= 0),
+    notes TEXT,
+    shipped_at TIMESTAMPTZ,
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS vendor_return_lines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    rtv_id UUID NOT NULL REFERENCES vendor_returns(id) ON DELETE CASCADE,
+    po_line_id UUID NOT NULL REFERENCES purchase_order_lines(id),
+    item_id UUID NOT NULL REFERENCES items(id),
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    unit_cost DOUBLE PRECISION NOT NULL CHECK (unit_cost >= 0),
+    reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Credit the supplier owes for a shipped RTV
+CREATE TABLE IF NOT EXISTS vendor_credits (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    rtv_id UUID NOT NULL UNIQUE REFERENCES vendor_returns(id),
+    supplier_id UUID NOT NULL,
+    po_id UUID NOT NULL REFERENCES purchase_orders(id),
+    amount DOUBLE PRECISION NOT NULL CHECK (amount >= 0),
+    status VARCHAR(20) NOT NULL DEFAULT 'EXPECTED' CHECK (status IN ('EXPECTED', 'RECEIVED')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_vendor_returns_tenant_id ON vendor_returns(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_vendor_returns_po_id ON vendor_returns(po_id);
+CREATE INDEX IF NOT EXISTS idx_vendor_returns_supplier_id ON vendor_returns(supplier_id);
+CREATE INDEX IF NOT EXISTS idx_vendor_returns_status ON vendor_returns(status);
+CREATE INDEX IF NOT EXISTS idx_vendor_returns_created_at ON vendor_returns(created_at);
+CREATE INDEX IF NOT EXISTS idx_vendor_return_lines_rtv_id ON vendor_return_lines(rtv_id);
+CREATE INDEX IF NOT EXISTS idx_vendor_return_lines_po_line_id ON vendor_return_lines(po_line_id);
+CREATE INDEX IF NOT EXISTS idx_vendor_credits_tenant_id ON vendor_credits(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_vendor_credits_supplier_id ON vendor_credits(supplier_id);
+
+ALTER TABLE vendor_returns ENABLE ROW LEVEL SECURITY;
+ALTER TABLE vendor_return_lines ENABLE ROW LEVEL SECURITY;
+ALTER TABLE vendor_credits ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_vendor_returns_policy ON vendor_returns
+    FOR ALL USING (vendor_returns.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_vendor_return_lines_policy ON vendor_return_lines
+    FOR ALL USING (vendor_return_lines.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_vendor_credits_policy ON vendor_credits
+    FOR ALL USING (vendor_credits.tenant_id = current_setting('custom.tenant_id')::UUID);
+
+-- RTV shipments are outbound movements referencing the vendor return
+ALTER TABLE stock_movements DROP CONSTRAINT IF EXISTS stock_movements_reference_type_check;
+ALTER TABLE stock_movements ADD CONSTRAINT stock_movements_reference_type_check
+    CHECK (reference_type IN ('purchase_order', 'sales_order', 'adjustment', 'transfer', 'initial', 'return', 'cycle_count', 'vendor_return'));
@@ -0,0 +1,21 @@
+-- Stock status: on-hand stock can be held in quarantine (awaiting inspection)
+-- or marked damaged, and neither counts as available to reserve, allocate or ship
+
+ALTER TABLE stock_levels
+    ADD COLUMN IF NOT EXISTS quantity_quarantine INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS quantity_damaged INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE stock_levels
+    ADD CONSTRAINT non_negative_quarantine CHECK (quantity_quarantine >= 0),
+    ADD CONSTRAINT non_negative_damaged CHECK (quantity_damaged >= 0),
+    ADD CONSTRAINT held_within_on_hand CHECK (quantity_quarantine + quantity_damaged <= quantity_on_hand);
+
+-- The status bucket each movement lands in or is taken from
+ALTER TABLE stock_movements
+    ADD COLUMN IF NOT EXISTS stock_status VARCHAR(20) NOT NULL DEFAULT 'AVAILABLE'
+        CHECK (stock_status IN ('AVAILABLE', 'QUARANTINE', 'DAMAGED'));
+
+-- A status change is a pair of adjustments, out of one status and into another
+ALTER TABLE stock_movements DROP CONSTRAINT IF EXISTS stock_movements_reference_type_check;
+ALTER TABLE stock_movements ADD CONSTRAINT stock_movements_reference_type_check
+    CHECK (reference_type IN ('purchase_order', 'sales_order', 'adjustment', 'transfer', 'initial', 'return', 'cycle_count', 'vendor_return', 'status_change'));
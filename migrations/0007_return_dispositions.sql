@@ -0,0 +1,7 @@
+-- What happened to each processed return line: back on the shelf (RESTOCK),
+-- written off (SCRAP) or held in quarantine for refurbishment (REFURBISH)
+ALTER TABLE return_lines
+    ADD COLUMN IF NOT EXISTS disposition VARCHAR(20)
+        CHECK (disposition IN ('RESTOCK', 'SCRAP', 'REFURBISH'));
+
+CREATE INDEX IF NOT EXISTS idx_return_lines_disposition ON return_lines(disposition);
@@ -0,0 +1,19 @@
+-- In-transit stock: a shipped transfer's goods are held against the destination
+-- until received, without counting as on hand anywhere
+
+ALTER TABLE stock_levels
+    ADD COLUMN IF NOT EXISTS quantity_in_transit INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE stock_levels
+    ADD CONSTRAINT non_negative_in_transit CHECK (quantity_in_transit >= 0);
+
+ALTER TABLE stock_movements DROP CONSTRAINT IF EXISTS stock_movements_stock_status_check;
+ALTER TABLE stock_movements ADD CONSTRAINT stock_movements_stock_status_check
+    CHECK (stock_status IN ('AVAILABLE', 'QUARANTINE', 'DAMAGED', 'IN_TRANSIT'));
+
+-- Shipped units that never arrived, written off when the transfer was closed
+ALTER TABLE transfer_lines
+    ADD COLUMN IF NOT EXISTS quantity_short INTEGER NOT NULL DEFAULT 0 CHECK (quantity_short >= 0);
+
+ALTER TABLE transfer_lines
+    ADD CONSTRAINT received_within_quantity CHECK (quantity_received + quantity_short <= quantity);
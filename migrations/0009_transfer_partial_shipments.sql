@@ -0,0 +1,20 @@
+-- Partial transfer shipments: lines track how much has shipped so a transfer
+-- can go out in several shipments
+
+ALTER TABLE transfer_lines
+    ADD COLUMN IF NOT EXISTS quantity_shipped INTEGER NOT NULL DEFAULT 0 CHECK (quantity_shipped >= 0);
+
+-- Transfers already shipped went out in full
+UPDATE transfer_lines l
+SET quantity_shipped = l.quantity
+FROM transfers t
+WHERE t.id = l.transfer_id AND t.status IN ('IN_TRANSIT', 'RECEIVED');
+
+ALTER TABLE transfer_lines DROP CONSTRAINT IF EXISTS received_within_quantity;
+ALTER TABLE transfer_lines
+    ADD CONSTRAINT shipped_within_quantity CHECK (quantity_shipped <= quantity),
+    ADD CONSTRAINT received_within_shipped CHECK (quantity_received + quantity_short <= quantity_shipped);
+
+ALTER TABLE transfers DROP CONSTRAINT IF EXISTS transfers_status_check;
+ALTER TABLE transfers ADD CONSTRAINT transfers_status_check
+    CHECK (status IN ('DRAFT', 'OPEN', 'PARTIALLY_SHIPPED', 'IN_TRANSIT', 'RECEIVED', 'CANCELLED'));
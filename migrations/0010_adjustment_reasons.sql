@@ -0,0 +1,75 @@
+-- Catalog of stock adjustment reason codes. Adjustments beyond a reason's
+-- quantity or value threshold wait for approval before they touch stock.
+CREATE TABLE IF NOT EXISTS adjustment_reasons (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    code VARCHAR(50) NOT NULL,
+    description TEXT,
+    active BOOLEAN NOT NULL DEFAULT true,
+    approval_quantity_threshold INTEGER CHECK (approval_quantity_threshold IS NULL OR approval_quantity_threshold > 0),
+    approval_value_threshold DOUBLE PRECISION CHECK (approval_value_threshold IS NULL OR approval_value_threshold > 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tenant_id, code)
+);
+
+-- Every adjustment, applied straight away or held for approval
+CREATE TABLE IF NOT EXISTS stock_adjustments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    item_id UUID NOT NULL REFERENCES items(id),
+    location_id UUID NOT NULL REFERENCES locations(id),
+    qty_change INTEGER NOT NULL CHECK (qty_change <> 0),
+    reason_code VARCHAR(50) NOT NULL,
+    note TEXT,
+    value DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (value >= 0),
+    status VARCHAR(20) NOT NULL CHECK (status IN ('APPLIED', 'PENDING_APPROVAL', 'REJECTED')),
+    movement_id UUID REFERENCES stock_movements(id),
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    decided_by UUID REFERENCES users(id),
+    decided_at TIMESTAMPTZ,
+    decision_note TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_adjustment_reasons_tenant_id ON adjustment_reasons(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_stock_adjustments_tenant_id ON stock_adjustments(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_stock_adjustments_status ON stock_adjustments(status);
+CREATE INDEX IF NOT EXISTS idx_stock_adjustments_reason_code ON stock_adjustments(reason_code);
+CREATE INDEX IF NOT EXISTS idx_stock_adjustments_created_at ON stock_adjustments(created_at);
+
+ALTER TABLE adjustment_reasons ENABLE ROW LEVEL SECURITY;
+ALTER TABLE stock_adjustments ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_adjustment_reasons_policy ON adjustment_reasons
+    FOR ALL USING (adjustment_reasons.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_stock_adjustments_policy ON stock_adjustments
+    FOR ALL USING (stock_adjustments.tenant_id = current_setting('custom.tenant_id')::UUID);
+
+-- Every tenant starts with the reasons adjustments used before the catalog existed
+CREATE OR REPLACE FUNCTION seed_adjustment_reasons()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO adjustment_reasons (tenant_id, code, description)
+    VALUES (NEW.id, 'COUNT', 'Physical count correction'),
+           (NEW.id, 'DAMAGE', 'Damaged stock written off'),
+           (NEW.id, 'CORRECTION', 'Data entry correction'),
+           (NEW.id, 'OTHER', 'Other')
+    ON CONFLICT (tenant_id, code) DO NOTHING;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER seed_adjustment_reasons_on_create
+    AFTER INSERT ON tenants
+    FOR EACH ROW EXECUTE FUNCTION seed_adjustment_reasons();
+
+INSERT INTO adjustment_reasons (tenant_id, code, description)
+SELECT t.id, r.code, r.description
+FROM tenants t
+CROSS JOIN (VALUES ('COUNT', 'Physical count correction'),
+                   ('DAMAGE', 'Damaged stock written off'),
+                   ('CORRECTION', 'Data entry correction'),
+                   ('OTHER', 'Other')) AS r(code, description)
+ON CONFLICT (tenant_id, code) DO NOTHING;
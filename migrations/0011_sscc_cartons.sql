@@ -0,0 +1,32 @@
+-- GS1 SSCC numbering: each tenant allocates serial references under its own
+-- GS1 company prefix
+CREATE TABLE IF NOT EXISTS sscc_sequences (
+    tenant_id UUID PRIMARY KEY REFERENCES tenants(id),
+    company_prefix VARCHAR(10) NOT NULL CHECK (company_prefix ~ '^[0-9]{7,10}$'),
+    extension_digit SMALLINT NOT NULL DEFAULT 0 CHECK (extension_digit BETWEEN 0 AND 9),
+    next_serial BIGINT NOT NULL DEFAULT 1 CHECK (next_serial >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Cartons on a shipment, each identified by its SSCC
+CREATE TABLE IF NOT EXISTS shipment_cartons (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    shipment_id UUID NOT NULL REFERENCES shipments(id) ON DELETE CASCADE,
+    package_id UUID REFERENCES shipment_packages(id) ON DELETE SET NULL,
+    sscc CHAR(18) NOT NULL CHECK (sscc ~ '^[0-9]{18}$'),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tenant_id, sscc)
+);
+
+CREATE INDEX IF NOT EXISTS idx_shipment_cartons_shipment_id ON shipment_cartons(shipment_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_shipment_cartons_package_id ON shipment_cartons(package_id)
+    WHERE package_id IS NOT NULL;
+
+ALTER TABLE sscc_sequences ENABLE ROW LEVEL SECURITY;
+ALTER TABLE shipment_cartons ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_sscc_sequences_policy ON sscc_sequences
+    FOR ALL USING (sscc_sequences.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_shipment_cartons_policy ON shipment_cartons
+    FOR ALL USING (shipment_cartons.tenant_id = current_setting('custom.tenant_id')::UUID);
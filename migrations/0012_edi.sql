@@ -0,0 +1,67 @@
+-- EDI trading partners: interchange identities on both sides plus how partner
+-- documents map onto our items, customers and locations
+CREATE TABLE IF NOT EXISTS edi_trading_partners (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    name VARCHAR(255) NOT NULL,
+    interchange_id_qualifier VARCHAR(2) NOT NULL,
+    interchange_id VARCHAR(15) NOT NULL,
+    application_id VARCHAR(15) NOT NULL,
+    our_interchange_id_qualifier VARCHAR(2) NOT NULL,
+    our_interchange_id VARCHAR(15) NOT NULL,
+    our_application_id VARCHAR(15) NOT NULL,
+    item_id_qualifier VARCHAR(2) NOT NULL DEFAULT 'VN',
+    item_match VARCHAR(20) NOT NULL DEFAULT 'SKU' CHECK (item_match IN ('SKU', 'BARCODE')),
+    customer_id UUID,
+    fulfillment_location_id UUID REFERENCES locations(id),
+    active BOOLEAN NOT NULL DEFAULT true,
+    -- Last ISA/GS control number sent to the partner
+    control_number BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tenant_id, interchange_id)
+);
+
+-- Every document received from or sent to a partner, kept verbatim
+CREATE TABLE IF NOT EXISTS edi_documents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    partner_id UUID REFERENCES edi_trading_partners(id) ON DELETE SET NULL,
+    document_type VARCHAR(3) NOT NULL CHECK (document_type IN ('850', '856', '810')),
+    direction VARCHAR(10) NOT NULL CHECK (direction IN ('INBOUND', 'OUTBOUND')),
+    status VARCHAR(20) NOT NULL CHECK (status IN ('PROCESSED', 'FAILED', 'GENERATED')),
+    control_number VARCHAR(9),
+    reference_type VARCHAR(20) CHECK (reference_type IN ('SALES_ORDER', 'SHIPMENT')),
+    reference_ids UUID[] NOT NULL DEFAULT '{}',
+    error TEXT,
+    content TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Sales orders raised from an 850, so ship notices and invoices can quote the PO
+CREATE TABLE IF NOT EXISTS edi_sales_orders (
+    sales_order_id UUID PRIMARY KEY REFERENCES sales_orders(id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    partner_id UUID NOT NULL REFERENCES edi_trading_partners(id),
+    document_id UUID NOT NULL REFERENCES edi_documents(id),
+    po_number VARCHAR(22) NOT NULL,
+    po_date DATE,
+    UNIQUE (tenant_id, partner_id, po_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_edi_trading_partners_tenant_id ON edi_trading_partners(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_edi_documents_tenant_id ON edi_documents(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_edi_documents_partner_id ON edi_documents(partner_id);
+CREATE INDEX IF NOT EXISTS idx_edi_documents_created_at ON edi_documents(created_at);
+CREATE INDEX IF NOT EXISTS idx_edi_sales_orders_tenant_id ON edi_sales_orders(tenant_id);
+
+ALTER TABLE edi_trading_partners ENABLE ROW LEVEL SECURITY;
+ALTER TABLE edi_documents ENABLE ROW LEVEL SECURITY;
+ALTER TABLE edi_sales_orders ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_edi_trading_partners_policy ON edi_trading_partners
+    FOR ALL USING (edi_trading_partners.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_edi_documents_policy ON edi_documents
+    FOR ALL USING (edi_documents.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_edi_sales_orders_policy ON edi_sales_orders
+    FOR ALL USING (edi_sales_orders.tenant_id = current_setting('custom.tenant_id')::UUID);
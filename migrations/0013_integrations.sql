@@ -0,0 +1,72 @@
+-- Sales channel connectors: where orders are pulled from and stock is pushed to
+CREATE TABLE IF NOT EXISTS integration_connectors (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    provider VARCHAR(20) NOT NULL CHECK (provider IN ('SHOPIFY')),
+    name VARCHAR(255) NOT NULL,
+    -- e.g. acme.myshopify.com
+    shop_domain VARCHAR(255) NOT NULL,
+    -- Our location whose stock is published and which fulfils pulled orders
+    location_id UUID NOT NULL REFERENCES locations(id),
+    -- The channel's own location that receives stock levels
+    external_location_id VARCHAR(255),
+    customer_id UUID,
+    sync_interval_minutes INTEGER NOT NULL DEFAULT 15 CHECK (sync_interval_minutes > 0),
+    active BOOLEAN NOT NULL DEFAULT true,
+    access_token TEXT,
+    token_scopes TEXT,
+    token_obtained_at TIMESTAMPTZ,
+    -- Nonce of an OAuth authorization in progress
+    oauth_state VARCHAR(64),
+    -- Orders updated at or after this are pulled next time
+    orders_cursor TIMESTAMPTZ,
+    -- Stock levels changed after this are pushed next time
+    inventory_cursor TIMESTAMPTZ,
+    next_sync_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tenant_id, provider, shop_domain)
+);
+
+-- Channel orders already turned into sales orders
+CREATE TABLE IF NOT EXISTS integration_orders (
+    connector_id UUID NOT NULL REFERENCES integration_connectors(id) ON DELETE CASCADE,
+    external_order_id VARCHAR(64) NOT NULL,
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    sales_order_id UUID NOT NULL REFERENCES sales_orders(id) ON DELETE CASCADE,
+    external_order_number VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (connector_id, external_order_id)
+);
+
+-- History of order pulls and stock pushes
+CREATE TABLE IF NOT EXISTS integration_sync_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    connector_id UUID NOT NULL REFERENCES integration_connectors(id) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('ORDERS', 'INVENTORY')),
+    trigger VARCHAR(20) NOT NULL CHECK (trigger IN ('SCHEDULED', 'MANUAL')),
+    status VARCHAR(20) NOT NULL CHECK (status IN ('SUCCEEDED', 'PARTIAL', 'FAILED')),
+    records_processed INTEGER NOT NULL DEFAULT 0,
+    records_failed INTEGER NOT NULL DEFAULT 0,
+    errors JSONB NOT NULL DEFAULT '[]',
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_integration_connectors_tenant_id ON integration_connectors(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_integration_connectors_next_sync_at ON integration_connectors(next_sync_at) WHERE active;
+CREATE INDEX IF NOT EXISTS idx_integration_orders_tenant_id ON integration_orders(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_integration_sync_runs_connector_id ON integration_sync_runs(connector_id, started_at);
+
+ALTER TABLE integration_connectors ENABLE ROW LEVEL SECURITY;
+ALTER TABLE integration_orders ENABLE ROW LEVEL SECURITY;
+ALTER TABLE integration_sync_runs ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_integration_connectors_policy ON integration_connectors
+    FOR ALL USING (integration_connectors.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_integration_orders_policy ON integration_orders
+    FOR ALL USING (integration_orders.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_integration_sync_runs_policy ON integration_sync_runs
+    FOR ALL USING (integration_sync_runs.tenant_id = current_setting('custom.tenant_id')::UUID);
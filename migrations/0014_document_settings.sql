@@ -0,0 +1,20 @@
+-- Letterhead printed on purchase orders, order confirmations and packing slips
+CREATE TABLE IF NOT EXISTS document_settings (
+    tenant_id UUID PRIMARY KEY REFERENCES tenants(id),
+    company_name VARCHAR(255) NOT NULL,
+    -- Same shape as locations.address
+    address JSONB,
+    phone VARCHAR(50),
+    email VARCHAR(255),
+    tax_id VARCHAR(50),
+    -- Printed at the bottom of every page, e.g. payment terms
+    footer_text TEXT,
+    -- Blob storage key of the PNG logo
+    logo_key TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE document_settings ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_document_settings_policy ON document_settings
+    FOR ALL USING (document_settings.tenant_id = current_setting('custom.tenant_id')::UUID);
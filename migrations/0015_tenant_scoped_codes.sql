@@ -0,0 +1,18 @@
+-- SKUs, location codes and order numbers only need to be unique within a tenant:
+-- every sandbox is seeded with the same demo catalogue, and two customers may
+-- well both stock a "WIDGET-001" or number their first purchase order PO-1001.
+ALTER TABLE items DROP CONSTRAINT IF EXISTS items_sku_key;
+ALTER TABLE items ADD CONSTRAINT items_tenant_sku_key UNIQUE (tenant_id, sku);
+DROP INDEX IF EXISTS idx_items_tenant_sku;
+
+ALTER TABLE locations DROP CONSTRAINT IF EXISTS locations_code_key;
+ALTER TABLE locations ADD CONSTRAINT locations_tenant_code_key UNIQUE (tenant_id, code);
+DROP INDEX IF EXISTS idx_locations_tenant_code;
+
+ALTER TABLE purchase_orders DROP CONSTRAINT IF EXISTS purchase_orders_po_number_key;
+ALTER TABLE purchase_orders ADD CONSTRAINT purchase_orders_tenant_po_number_key UNIQUE (tenant_id, po_number);
+DROP INDEX IF EXISTS idx_purchase_orders_tenant_po_number;
+
+ALTER TABLE sales_orders DROP CONSTRAINT IF EXISTS sales_orders_so_number_key;
+ALTER TABLE sales_orders ADD CONSTRAINT sales_orders_tenant_so_number_key UNIQUE (tenant_id, so_number);
+DROP INDEX IF EXISTS idx_sales_orders_tenant_so_number;
@@ -0,0 +1,25 @@
+-- How each sandbox was last seeded, so a reset can replay the same profile and
+-- hand back the quota headroom the previous seed added
+CREATE TABLE IF NOT EXISTS sandbox_seeds (
+    tenant_id UUID PRIMARY KEY REFERENCES tenants(id) ON DELETE CASCADE,
+    profile VARCHAR(50) NOT NULL,
+    -- Count overrides and seed the request gave, same shape as the request body
+    overrides JSONB NOT NULL DEFAULT '{}',
+    -- What was written: counts of locations, items, orders, movements and webhooks
+    summary JSONB NOT NULL,
+    seeded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Administrative actions taken on a tenant as a whole. Not row-level secured:
+-- entries are written and read by tenant id, often by a user of another tenant
+-- (whoever created the sandbox).
+CREATE TABLE IF NOT EXISTS tenant_audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    action VARCHAR(50) NOT NULL,
+    actor_id UUID NOT NULL REFERENCES users(id),
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_tenant_audit_log_tenant_created ON tenant_audit_log(tenant_id, created_at DESC);
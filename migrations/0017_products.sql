@@ -0,0 +1,30 @@
+-- Parent products grouping item variants that differ by size, colour and the like
+CREATE TABLE IF NOT EXISTS products (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    -- Prefix of every variant's SKU
+    parent_sku VARCHAR(100) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    category VARCHAR(100),
+    unit VARCHAR(50) NOT NULL,
+    -- [{"name": "size", "values": ["S", "M", "L"]}, ...] in the order variant SKUs are built
+    attributes JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tenant_id, parent_sku)
+);
+
+CREATE INDEX IF NOT EXISTS idx_products_tenant_id ON products(tenant_id);
+
+-- Items without a product stay plain, stand-alone items
+ALTER TABLE items ADD COLUMN IF NOT EXISTS product_id UUID REFERENCES products(id) ON DELETE SET NULL;
+-- The variant's value for each of its product's attributes, e.g. {"size": "M", "color": "Red"}
+ALTER TABLE items ADD COLUMN IF NOT EXISTS variant_attributes JSONB;
+
+CREATE INDEX IF NOT EXISTS idx_items_product_id ON items(product_id);
+
+ALTER TABLE products ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_products_policy ON products
+    FOR ALL USING (products.tenant_id = current_setting('custom.tenant_id')::UUID);
@@ -0,0 +1,22 @@
+-- Currency that reports and billing metrics are converted into
+ALTER TABLE tenants ADD COLUMN IF NOT EXISTS base_currency VARCHAR(3) NOT NULL DEFAULT 'USD';
+
+-- Daily reference rates, shared by every tenant: 1 base_currency = rate quote_currency
+CREATE TABLE IF NOT EXISTS exchange_rates (
+    base_currency VARCHAR(3) NOT NULL,
+    quote_currency VARCHAR(3) NOT NULL,
+    rate_date DATE NOT NULL,
+    rate DOUBLE PRECISION NOT NULL CHECK (rate > 0),
+    source VARCHAR(100) NOT NULL,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (base_currency, quote_currency, rate_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_exchange_rates_rate_date ON exchange_rates(rate_date);
+
+-- Orders priced in a currency other than the tenant's base currency record it with
+-- the rate into the base currency when they were placed; NULL means the base currency
+ALTER TABLE purchase_orders ADD COLUMN IF NOT EXISTS currency VARCHAR(3);
+ALTER TABLE purchase_orders ADD COLUMN IF NOT EXISTS exchange_rate DOUBLE PRECISION CHECK (exchange_rate > 0);
+ALTER TABLE sales_orders ADD COLUMN IF NOT EXISTS currency VARCHAR(3);
+ALTER TABLE sales_orders ADD COLUMN IF NOT EXISTS exchange_rate DOUBLE PRECISION CHECK (exchange_rate > 0);
@@ -0,0 +1,34 @@
+-- Money is stored as exact decimals to four places: unit prices and costs keep
+-- that precision, line and order totals are rounded to cents by the application.
+-- Weights, dimensions and exchange rates are measurements and stay floating point.
+ALTER TABLE items
+    ALTER COLUMN cost_price TYPE NUMERIC(19, 4) USING ROUND(cost_price::NUMERIC, 4),
+    ALTER COLUMN sale_price TYPE NUMERIC(19, 4) USING ROUND(sale_price::NUMERIC, 4);
+
+ALTER TABLE purchase_orders
+    ALTER COLUMN total_amount TYPE NUMERIC(19, 4) USING ROUND(total_amount::NUMERIC, 4);
+ALTER TABLE purchase_order_lines
+    ALTER COLUMN unit_cost TYPE NUMERIC(19, 4) USING ROUND(unit_cost::NUMERIC, 4),
+    ALTER COLUMN line_total TYPE NUMERIC(19, 4) USING ROUND(line_total::NUMERIC, 4);
+
+ALTER TABLE sales_orders
+    ALTER COLUMN total_amount TYPE NUMERIC(19, 4) USING ROUND(total_amount::NUMERIC, 4);
+ALTER TABLE sales_order_lines
+    ALTER COLUMN unit_price TYPE NUMERIC(19, 4) USING ROUND(unit_price::NUMERIC, 4),
+    ALTER COLUMN tax TYPE NUMERIC(19, 4) USING ROUND(tax::NUMERIC, 4);
+
+ALTER TABLE return_lines
+    ALTER COLUMN unit_price TYPE NUMERIC(19, 4) USING ROUND(unit_price::NUMERIC, 4);
+
+ALTER TABLE vendor_returns
+    ALTER COLUMN total_amount TYPE NUMERIC(19, 4) USING ROUND(total_amount::NUMERIC, 4);
+ALTER TABLE vendor_return_lines
+    ALTER COLUMN unit_cost TYPE NUMERIC(19, 4) USING ROUND(unit_cost::NUMERIC, 4);
+ALTER TABLE vendor_credits
+    ALTER COLUMN amount TYPE NUMERIC(19, 4) USING ROUND(amount::NUMERIC, 4);
+
+ALTER TABLE stock_adjustments
+    ALTER COLUMN value TYPE NUMERIC(19, 4) USING ROUND(value::NUMERIC, 4);
+ALTER TABLE adjustment_reasons
+    ALTER COLUMN approval_value_threshold TYPE NUMERIC(19, 4)
+        USING ROUND(approval_value_threshold::NUMERIC, 4);
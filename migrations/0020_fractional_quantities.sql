@@ -0,0 +1,65 @@
+-- Quantities may be fractional for items sold by length, weight or volume.
+-- Every quantity column holds up to four decimal places; how many an item
+-- actually allows is its quantity_precision, checked by the application.
+ALTER TABLE items
+    ADD COLUMN IF NOT EXISTS quantity_precision SMALLINT NOT NULL DEFAULT 0
+        CHECK (quantity_precision BETWEEN 0 AND 4),
+    ALTER COLUMN reorder_point TYPE NUMERIC(19, 4),
+    ALTER COLUMN reorder_qty TYPE NUMERIC(19, 4);
+
+ALTER TABLE stock_movements
+    ALTER COLUMN quantity TYPE NUMERIC(19, 4);
+
+ALTER TABLE stock_levels
+    ALTER COLUMN quantity_on_hand TYPE NUMERIC(19, 4),
+    ALTER COLUMN quantity_reserved TYPE NUMERIC(19, 4),
+    ALTER COLUMN quantity_quarantine TYPE NUMERIC(19, 4),
+    ALTER COLUMN quantity_damaged TYPE NUMERIC(19, 4),
+    ALTER COLUMN quantity_in_transit TYPE NUMERIC(19, 4);
+
+ALTER TABLE stock_snapshots
+    ALTER COLUMN quantity_on_hand TYPE NUMERIC(19, 4),
+    ALTER COLUMN quantity_reserved TYPE NUMERIC(19, 4);
+
+ALTER TABLE purchase_order_lines
+    ALTER COLUMN qty_ordered TYPE NUMERIC(19, 4),
+    ALTER COLUMN qty_received TYPE NUMERIC(19, 4);
+
+ALTER TABLE sales_order_lines
+    ALTER COLUMN qty TYPE NUMERIC(19, 4),
+    ALTER COLUMN qty_shipped TYPE NUMERIC(19, 4);
+
+ALTER TABLE so_allocations
+    ALTER COLUMN qty_allocated TYPE NUMERIC(19, 4),
+    ALTER COLUMN qty_shipped TYPE NUMERIC(19, 4);
+
+ALTER TABLE transfers
+    ALTER COLUMN total_quantity TYPE NUMERIC(19, 4);
+ALTER TABLE transfer_lines
+    ALTER COLUMN quantity TYPE NUMERIC(19, 4),
+    ALTER COLUMN quantity_shipped TYPE NUMERIC(19, 4),
+    ALTER COLUMN quantity_received TYPE NUMERIC(19, 4),
+    ALTER COLUMN quantity_short TYPE NUMERIC(19, 4);
+
+ALTER TABLE returns
+    ALTER COLUMN total_quantity TYPE NUMERIC(19, 4);
+ALTER TABLE return_lines
+    ALTER COLUMN quantity TYPE NUMERIC(19, 4),
+    ALTER COLUMN quantity_received TYPE NUMERIC(19, 4);
+
+ALTER TABLE vendor_returns
+    ALTER COLUMN total_quantity TYPE NUMERIC(19, 4);
+ALTER TABLE vendor_return_lines
+    ALTER COLUMN quantity TYPE NUMERIC(19, 4);
+
+ALTER TABLE cycle_count_lines
+    ALTER COLUMN expected_qty TYPE NUMERIC(19, 4),
+    ALTER COLUMN counted_qty TYPE NUMERIC(19, 4);
+
+ALTER TABLE putaway_rules
+    ALTER COLUMN max_quantity TYPE NUMERIC(19, 4);
+
+ALTER TABLE stock_adjustments
+    ALTER COLUMN qty_change TYPE NUMERIC(19, 4);
+ALTER TABLE adjustment_reasons
+    ALTER COLUMN approval_quantity_threshold TYPE NUMERIC(19, 4);
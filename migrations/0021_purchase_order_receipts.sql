@@ -0,0 +1,32 @@
+-- Each receiving session against a purchase order, so the history of who
+-- received what, where and when survives alongside the running qty_received
+CREATE TABLE IF NOT EXISTS purchase_order_receipts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    po_id UUID NOT NULL REFERENCES purchase_orders(id) ON DELETE CASCADE,
+    location_id UUID NOT NULL REFERENCES locations(id),
+    stock_status VARCHAR(20) NOT NULL DEFAULT 'AVAILABLE',
+    received_by UUID NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS purchase_order_receipt_lines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    receipt_id UUID NOT NULL REFERENCES purchase_order_receipts(id) ON DELETE CASCADE,
+    po_line_id UUID NOT NULL REFERENCES purchase_order_lines(id) ON DELETE CASCADE,
+    item_id UUID NOT NULL REFERENCES items(id),
+    qty_received NUMERIC(19, 4) NOT NULL CHECK (qty_received > 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_po_receipts_po_id ON purchase_order_receipts(po_id, received_at);
+CREATE INDEX IF NOT EXISTS idx_po_receipt_lines_receipt_id ON purchase_order_receipt_lines(receipt_id);
+
+ALTER TABLE purchase_order_receipts ENABLE ROW LEVEL SECURITY;
+ALTER TABLE purchase_order_receipt_lines ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_purchase_order_receipts_policy ON purchase_order_receipts
+    FOR ALL USING (purchase_order_receipts.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_purchase_order_receipt_lines_policy ON purchase_order_receipt_lines
+    FOR ALL USING (purchase_order_receipt_lines.tenant_id = current_setting('custom.tenant_id')::UUID);
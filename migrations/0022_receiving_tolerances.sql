@@ -0,0 +1,26 @@
+-- How far past the ordered quantity a purchase order line may be received, as
+-- a percentage of the ordered quantity. A supplier's own tolerance overrides
+-- the tenant's; with neither set, receipts stop at the ordered quantity.
+CREATE TABLE IF NOT EXISTS receiving_settings (
+    tenant_id UUID PRIMARY KEY REFERENCES tenants(id),
+    over_receipt_percent NUMERIC(5, 2) NOT NULL DEFAULT 0
+        CHECK (over_receipt_percent BETWEEN 0 AND 100),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS supplier_receiving_tolerances (
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    supplier_id UUID NOT NULL,
+    over_receipt_percent NUMERIC(5, 2) NOT NULL
+        CHECK (over_receipt_percent BETWEEN 0 AND 100),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tenant_id, supplier_id)
+);
+
+ALTER TABLE receiving_settings ENABLE ROW LEVEL SECURITY;
+ALTER TABLE supplier_receiving_tolerances ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_receiving_settings_policy ON receiving_settings
+    FOR ALL USING (receiving_settings.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_supplier_receiving_tolerances_policy ON supplier_receiving_tolerances
+    FOR ALL USING (supplier_receiving_tolerances.tenant_id = current_setting('custom.tenant_id')::UUID);
@@ -0,0 +1,59 @@
+-- Advance shipping notices: what a supplier says is on its way against a
+-- purchase order, carton by carton. Dock staff receive the cartons by scanning
+-- their labels, and anything that differs from the notice is reported as a
+-- discrepancy.
+CREATE TABLE IF NOT EXISTS inbound_shipments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    asn_number VARCHAR(100) NOT NULL,
+    po_id UUID NOT NULL REFERENCES purchase_orders(id) ON DELETE CASCADE,
+    supplier_id UUID NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'EXPECTED'
+        CHECK (status IN ('EXPECTED', 'RECEIVING', 'CLOSED')),
+    expected_arrival TIMESTAMPTZ,
+    carrier VARCHAR(100),
+    tracking_number VARCHAR(255),
+    created_by UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    closed_at TIMESTAMPTZ,
+    UNIQUE (tenant_id, supplier_id, asn_number)
+);
+
+-- code is whatever the carton's label scans as, usually an SSCC
+CREATE TABLE IF NOT EXISTS inbound_cartons (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    shipment_id UUID NOT NULL REFERENCES inbound_shipments(id) ON DELETE CASCADE,
+    code VARCHAR(100) NOT NULL,
+    received_at TIMESTAMPTZ,
+    received_by UUID,
+    UNIQUE (shipment_id, code)
+);
+
+-- qty_expected is zero for items found in a carton the notice didn't list;
+-- qty_received stays NULL until the carton is scanned in
+CREATE TABLE IF NOT EXISTS inbound_carton_lines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    carton_id UUID NOT NULL REFERENCES inbound_cartons(id) ON DELETE CASCADE,
+    po_line_id UUID NOT NULL REFERENCES purchase_order_lines(id) ON DELETE CASCADE,
+    item_id UUID NOT NULL REFERENCES items(id),
+    qty_expected NUMERIC(19, 4) NOT NULL CHECK (qty_expected >= 0),
+    qty_received NUMERIC(19, 4) CHECK (qty_received >= 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_inbound_shipments_po_id ON inbound_shipments(po_id);
+CREATE INDEX IF NOT EXISTS idx_inbound_cartons_shipment_id ON inbound_cartons(shipment_id);
+CREATE INDEX IF NOT EXISTS idx_inbound_carton_lines_carton_id ON inbound_carton_lines(carton_id);
+
+ALTER TABLE inbound_shipments ENABLE ROW LEVEL SECURITY;
+ALTER TABLE inbound_cartons ENABLE ROW LEVEL SECURITY;
+ALTER TABLE inbound_carton_lines ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_inbound_shipments_policy ON inbound_shipments
+    FOR ALL USING (inbound_shipments.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_inbound_cartons_policy ON inbound_cartons
+    FOR ALL USING (inbound_cartons.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_inbound_carton_lines_policy ON inbound_carton_lines
+    FOR ALL USING (inbound_carton_lines.tenant_id = current_setting('custom.tenant_id')::UUID);
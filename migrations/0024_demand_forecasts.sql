@@ -0,0 +1,30 @@
+-- Demand forecasts computed from each item's outbound stock movements. One
+-- snapshot per item, method and day; recomputing the same day replaces it.
+CREATE TABLE IF NOT EXISTS demand_forecasts (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    item_id UUID NOT NULL REFERENCES items(id),
+    method VARCHAR(30) NOT NULL
+        CHECK (method IN ('MOVING_AVERAGE', 'EXPONENTIAL_SMOOTHING')),
+    history_days INTEGER NOT NULL CHECK (history_days > 0),
+    window_days INTEGER CHECK (window_days > 0),
+    alpha NUMERIC(5, 4) CHECK (alpha > 0 AND alpha <= 1),
+    history_total NUMERIC(18, 6) NOT NULL,
+    daily_demand NUMERIC(18, 6) NOT NULL,
+    horizon_days INTEGER NOT NULL CHECK (horizon_days >= 0),
+    forecast_qty NUMERIC(18, 6) NOT NULL,
+    lead_time_days INTEGER NOT NULL CHECK (lead_time_days >= 0),
+    safety_stock_days INTEGER NOT NULL CHECK (safety_stock_days >= 0),
+    reorder_point NUMERIC(18, 6) NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    computed_on DATE NOT NULL DEFAULT CURRENT_DATE,
+    UNIQUE (tenant_id, item_id, method, computed_on)
+);
+
+CREATE INDEX IF NOT EXISTS idx_demand_forecasts_item
+    ON demand_forecasts (tenant_id, item_id, computed_at DESC);
+
+ALTER TABLE demand_forecasts ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_demand_forecasts_policy ON demand_forecasts
+    FOR ALL USING (demand_forecasts.tenant_id = current_setting('custom.tenant_id')::UUID);
@@ -0,0 +1,26 @@
+-- Trigram matching over the search index, for typo-tolerant search and
+-- autocomplete. Both columns are derived from a document's metadata, so
+-- existing documents get them without a reindex.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+ALTER TABLE search_indexes
+    -- What a suggestion shows: the entity's name, or item @ location for stock levels
+    ADD COLUMN IF NOT EXISTS label TEXT GENERATED ALWAYS AS (
+        COALESCE(metadata->>'name', (metadata->>'item_name') || ' @ ' || (metadata->>'location_name'))
+    ) STORED,
+    -- Names and codes a user is likely to type, lowercased
+    ADD COLUMN IF NOT EXISTS search_text TEXT GENERATED ALWAYS AS (
+        lower(
+            COALESCE(metadata->>'name', '') || ' ' ||
+            COALESCE(metadata->>'sku', '') || ' ' ||
+            COALESCE(metadata->>'code', '') || ' ' ||
+            COALESCE(metadata->>'category', '') || ' ' ||
+            COALESCE(metadata->>'item_name', '') || ' ' ||
+            COALESCE(metadata->>'item_sku', '') || ' ' ||
+            COALESCE(metadata->>'location_name', '') || ' ' ||
+            COALESCE(metadata->>'location_code', '')
+        )
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_search_indexes_trgm
+    ON search_indexes USING gin (search_text gin_trgm_ops);
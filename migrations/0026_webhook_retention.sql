@@ -0,0 +1,43 @@
+-- How long each tenant keeps webhook history. Delivered and dead-lettered
+-- deliveries are pruned once older than their retention; events go once no
+-- deliveries are left for them. With archive_enabled, pruned rows are first
+-- written to blob storage as JSONL.
+CREATE TABLE IF NOT EXISTS webhook_retention_settings (
+    tenant_id UUID PRIMARY KEY REFERENCES tenants(id),
+    event_retention_days INTEGER NOT NULL DEFAULT 30
+        CHECK (event_retention_days BETWEEN 1 AND 3650),
+    delivery_retention_days INTEGER NOT NULL DEFAULT 7
+        CHECK (delivery_retention_days BETWEEN 1 AND 3650),
+    dlq_retention_days INTEGER NOT NULL DEFAULT 30
+        CHECK (dlq_retention_days BETWEEN 1 AND 3650),
+    archive_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    -- When the scheduled cleanup next runs for the tenant
+    next_run_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS webhook_retention_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    trigger VARCHAR(20) NOT NULL CHECK (trigger IN ('SCHEDULED', 'MANUAL')),
+    status VARCHAR(20) NOT NULL CHECK (status IN ('SUCCEEDED', 'FAILED')),
+    deliveries_pruned BIGINT NOT NULL DEFAULT 0,
+    events_pruned BIGINT NOT NULL DEFAULT 0,
+    archive_keys JSONB NOT NULL DEFAULT '[]',
+    error TEXT,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_retention_runs_tenant_id ON webhook_retention_runs(tenant_id, started_at);
+-- Lets the cleanup find expired rows without scanning live ones
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_finished ON webhook_deliveries(tenant_id, created_at) WHERE status IN ('SUCCESS', 'DLQ');
+CREATE INDEX IF NOT EXISTS idx_webhook_events_tenant_created_at ON webhook_events(tenant_id, created_at);
+
+ALTER TABLE webhook_retention_settings ENABLE ROW LEVEL SECURITY;
+ALTER TABLE webhook_retention_runs ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_webhook_retention_settings_policy ON webhook_retention_settings
+    FOR ALL USING (webhook_retention_settings.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_webhook_retention_runs_policy ON webhook_retention_runs
+    FOR ALL USING (webhook_retention_runs.tenant_id = current_setting('custom.tenant_id')::UUID);
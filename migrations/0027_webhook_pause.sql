@@ -0,0 +1,11 @@
+-- A paused webhook keeps its configuration and stops receiving deliveries;
+-- events raised meanwhile are held for it and replayed when it resumes
+ALTER TABLE webhooks DROP CONSTRAINT IF EXISTS webhooks_status_check;
+ALTER TABLE webhooks ADD CONSTRAINT webhooks_status_check
+    CHECK (status IN ('ACTIVE', 'INACTIVE', 'FAILED', 'PAUSED'));
+
+ALTER TABLE webhook_deliveries DROP CONSTRAINT IF EXISTS webhook_deliveries_status_check;
+ALTER TABLE webhook_deliveries ADD CONSTRAINT webhook_deliveries_status_check
+    CHECK (status IN ('PENDING', 'SUCCESS', 'FAILED', 'RETRY', 'TIMEOUT', 'DLQ', 'HELD'));
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_held ON webhook_deliveries(webhook_id, created_at) WHERE status = 'HELD';
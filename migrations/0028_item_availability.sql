@@ -0,0 +1,62 @@
+-- Availability read model, kept up to date by the paths that move, reserve or
+-- allocate stock and by purchase order changes, so reading an item's
+-- availability doesn't have to aggregate stock levels, allocations and
+-- purchase orders on every request. One row per stock level.
+CREATE TABLE IF NOT EXISTS item_availability (
+    item_id UUID NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    location_id UUID NOT NULL REFERENCES locations(id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    quantity_on_hand NUMERIC(19, 4) NOT NULL DEFAULT 0,
+    -- On hand but held in quarantine or as damaged
+    quantity_unavailable NUMERIC(19, 4) NOT NULL DEFAULT 0,
+    quantity_reserved NUMERIC(19, 4) NOT NULL DEFAULT 0,
+    -- Allocated to sales orders and not shipped yet
+    quantity_allocated NUMERIC(19, 4) NOT NULL DEFAULT 0,
+    quantity_in_transit NUMERIC(19, 4) NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (item_id, location_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_item_availability_tenant_item ON item_availability(tenant_id, item_id);
+
+-- Ordered on open purchase orders and not received yet; not tied to a location
+CREATE TABLE IF NOT EXISTS item_on_order (
+    item_id UUID PRIMARY KEY REFERENCES items(id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    quantity_on_order NUMERIC(19, 4) NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_item_on_order_tenant_id ON item_on_order(tenant_id);
+
+ALTER TABLE item_availability ENABLE ROW LEVEL SECURITY;
+ALTER TABLE item_on_order ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_item_availability_policy ON item_availability
+    FOR ALL USING (item_availability.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_item_on_order_policy ON item_on_order
+    FOR ALL USING (item_on_order.tenant_id = current_setting('custom.tenant_id')::UUID);
+
+INSERT INTO item_availability (
+    item_id, location_id, tenant_id, quantity_on_hand, quantity_unavailable,
+    quantity_reserved, quantity_allocated, quantity_in_transit, updated_at
+)
+SELECT sl.item_id, sl.location_id, sl.tenant_id, sl.quantity_on_hand,
+       sl.quantity_quarantine + sl.quantity_damaged, sl.quantity_reserved,
+       COALESCE((
+           SELECT SUM(a.qty_allocated - a.qty_shipped)
+           FROM so_allocations a
+           WHERE a.item_id = sl.item_id AND a.location_id = sl.location_id
+             AND a.tenant_id = sl.tenant_id
+       ), 0),
+       sl.quantity_in_transit, NOW()
+FROM stock_levels sl
+ON CONFLICT (item_id, location_id) DO NOTHING;
+
+INSERT INTO item_on_order (item_id, tenant_id, quantity_on_order, updated_at)
+SELECT l.item_id, l.tenant_id, SUM(l.qty_ordered - l.qty_received), NOW()
+FROM purchase_order_lines l
+JOIN purchase_orders po ON po.id = l.po_id
+WHERE po.status IN ('OPEN', 'RECEIVING', 'PARTIAL_RECEIVED')
+GROUP BY l.item_id, l.tenant_id
+ON CONFLICT (item_id) DO NOTHING;
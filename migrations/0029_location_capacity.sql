@@ -0,0 +1,12 @@
+-- How much a location can hold, by volume (in the cube of the unit item
+-- dimensions are recorded in), weight and pallet positions. Any limit left
+-- NULL is unbounded. Receipts that would take the location past a limit are
+-- refused when capacity is enforced, and only flagged when it warns.
+ALTER TABLE locations ADD COLUMN IF NOT EXISTS max_volume DOUBLE PRECISION
+    CHECK (max_volume > 0);
+ALTER TABLE locations ADD COLUMN IF NOT EXISTS max_weight DOUBLE PRECISION
+    CHECK (max_weight > 0);
+ALTER TABLE locations ADD COLUMN IF NOT EXISTS max_pallets INTEGER
+    CHECK (max_pallets > 0);
+ALTER TABLE locations ADD COLUMN IF NOT EXISTS capacity_enforcement VARCHAR(10) NOT NULL DEFAULT 'WARN'
+    CHECK (capacity_enforcement IN ('WARN', 'ENFORCE'));
@@ -0,0 +1,50 @@
+-- Networked label printers (Zebra and other ZPL printers listening on a raw
+-- TCP port), configured per location. A location's default printer takes
+-- print jobs sent to the location rather than to a specific printer.
+CREATE TABLE IF NOT EXISTS printers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    location_id UUID NOT NULL REFERENCES locations(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    host VARCHAR(255) NOT NULL,
+    port INTEGER NOT NULL DEFAULT 9100 CHECK (port BETWEEN 1 AND 65535),
+    is_default BOOLEAN NOT NULL DEFAULT false,
+    active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_printers_tenant_location ON printers(tenant_id, location_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_printers_location_default ON printers(location_id) WHERE is_default;
+
+-- Labels queued for a printer. Each is delivered by a background job, which
+-- retries with backoff while the printer can't be reached.
+CREATE TABLE IF NOT EXISTS print_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    printer_id UUID NOT NULL REFERENCES printers(id) ON DELETE CASCADE,
+    -- The background job delivering it (jobs.job_id)
+    job_id VARCHAR(255),
+    source_type VARCHAR(20) NOT NULL CHECK (source_type IN ('SHIPMENT_LABELS', 'RAW')),
+    source_id UUID,
+    content TEXT NOT NULL,
+    copies INTEGER NOT NULL DEFAULT 1 CHECK (copies BETWEEN 1 AND 100),
+    status VARCHAR(20) NOT NULL DEFAULT 'QUEUED'
+        CHECK (status IN ('QUEUED', 'PRINTING', 'PRINTED', 'FAILED')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    printed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_print_jobs_tenant_created ON print_jobs(tenant_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_print_jobs_printer_status ON print_jobs(printer_id, status);
+
+ALTER TABLE printers ENABLE ROW LEVEL SECURITY;
+ALTER TABLE print_jobs ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_printers_policy ON printers
+    FOR ALL USING (printers.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_print_jobs_policy ON print_jobs
+    FOR ALL USING (print_jobs.tenant_id = current_setting('custom.tenant_id')::UUID);
@@ -0,0 +1,77 @@
+-- Every status a purchase order, sales order, transfer or return has been
+-- through. Rows are written by a trigger on the entity tables, so transitions
+-- made by any path (API, jobs, integrations) are recorded. The actor is the
+-- logged-in user the application pins in `custom.actor_id`; it is NULL for
+-- changes made without one (background jobs, header-authenticated callers).
+CREATE TABLE IF NOT EXISTS entity_status_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    entity_type VARCHAR(30) NOT NULL
+        CHECK (entity_type IN ('PURCHASE_ORDER', 'SALES_ORDER', 'TRANSFER', 'RETURN')),
+    entity_id UUID NOT NULL,
+    -- NULL for the status the entity was created with
+    old_status VARCHAR(50),
+    new_status VARCHAR(50) NOT NULL,
+    actor_id UUID,
+    metadata JSONB NOT NULL DEFAULT '{}',
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+
+CREATE INDEX IF NOT EXISTS idx_entity_status_history_entity
+    ON entity_status_history(tenant_id, entity_type, entity_id, changed_at);
+
+ALTER TABLE entity_status_history ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_entity_status_history_policy ON entity_status_history
+    FOR ALL USING (entity_status_history.tenant_id = current_setting('custom.tenant_id')::UUID);
+
+-- TG_ARGV[0] is the entity type. Restores of exported history turn recording
+-- off for their transaction with `custom.status_history = 'off'`.
+CREATE OR REPLACE FUNCTION record_status_change()
+RETURNS TRIGGER AS $$
+DECLARE
+    old_status VARCHAR(50);
+BEGIN
+    IF current_setting('custom.status_history', true) = 'off' THEN
+        RETURN NEW;
+    END IF;
+    IF TG_OP = 'UPDATE' THEN
+        -- Updates rewrite the whole row; only an actual change is a transition
+        IF OLD.status IS NOT DISTINCT FROM NEW.status THEN
+            RETURN NEW;
+        END IF;
+        old_status := OLD.status;
+    END IF;
+
+    INSERT INTO entity_status_history
+        (tenant_id, entity_type, entity_id, old_status, new_status, actor_id, metadata)
+    VALUES (
+        NEW.tenant_id, TG_ARGV[0], NEW.id, old_status, NEW.status,
+        NULLIF(current_setting('custom.actor_id', true), '')::UUID,
+        jsonb_strip_nulls(jsonb_build_object(
+            'cancellation_reason', to_jsonb(NEW) -> 'cancellation_reason'
+        ))
+    );
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER purchase_orders_status_history
+    AFTER INSERT OR UPDATE OF status ON purchase_orders
+    FOR EACH ROW
+    EXECUTE FUNCTION record_status_change('PURCHASE_ORDER');
+
+CREATE TRIGGER sales_orders_status_history
+    AFTER INSERT OR UPDATE OF status ON sales_orders
+    FOR EACH ROW
+    EXECUTE FUNCTION record_status_change('SALES_ORDER');
+
+CREATE TRIGGER transfers_status_history
+    AFTER INSERT OR UPDATE OF status ON transfers
+    FOR EACH ROW
+    EXECUTE FUNCTION record_status_change('TRANSFER');
+
+CREATE TRIGGER returns_status_history
+    AFTER INSERT OR UPDATE OF status ON returns
+    FOR EACH ROW
+    EXECUTE FUNCTION record_status_change('RETURN');
@@ -0,0 +1,22 @@
+-- Webhook events written in the same transaction as the change that raised
+-- them. The delivery worker publishes them afterwards, so a change that commits
+-- always gets its events and one that rolls back never does. Rows are deleted
+-- once published.
+CREATE TABLE IF NOT EXISTS event_outbox (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    event_type VARCHAR(100) NOT NULL,
+    payload JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    -- Pushed forward while a publisher holds the row and after a failed attempt
+    available_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    attempt_count INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_outbox_available ON event_outbox(available_at);
+
+ALTER TABLE event_outbox ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_event_outbox_policy ON event_outbox
+    FOR ALL USING (event_outbox.tenant_id = current_setting('custom.tenant_id')::UUID);
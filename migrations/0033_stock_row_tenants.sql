@@ -0,0 +1,57 @@
+-- A stock movement or level belongs to the tenant that owns its item and
+-- location. Rows written before every repository recorded movements through
+-- the same path could carry another tenant's id; they take their item's.
+UPDATE stock_movements m
+SET tenant_id = i.tenant_id
+FROM items i
+WHERE i.id = m.item_id AND m.tenant_id IS DISTINCT FROM i.tenant_id;
+
+UPDATE stock_levels s
+SET tenant_id = i.tenant_id
+FROM items i
+WHERE i.id = s.item_id AND s.tenant_id IS DISTINCT FROM i.tenant_id;
+
+-- A row whose item and location belong to different tenants has no right
+-- owner, and the constraints below would reject it part way through. Stop
+-- here instead, naming the rows, so they can be corrected or removed by hand
+-- before the migration is run again.
+DO $$
+DECLARE
+    mismatched_movements BIGINT;
+    mismatched_levels BIGINT;
+BEGIN
+    SELECT COUNT(*) INTO mismatched_movements
+    FROM stock_movements m
+    JOIN locations l ON l.id = m.location_id
+    WHERE l.tenant_id IS DISTINCT FROM m.tenant_id;
+
+    SELECT COUNT(*) INTO mismatched_levels
+    FROM stock_levels s
+    JOIN locations l ON l.id = s.location_id
+    WHERE l.tenant_id IS DISTINCT FROM s.tenant_id;
+
+    IF mismatched_movements > 0 OR mismatched_levels > 0 THEN
+        RAISE EXCEPTION '% stock movement(s) and % stock level(s) pair an item with another tenant''s location',
+            mismatched_movements, mismatched_levels
+            USING HINT = 'Find them with: SELECT m.id FROM stock_movements m JOIN items i ON i.id = m.item_id '
+                || 'JOIN locations l ON l.id = m.location_id WHERE i.tenant_id <> l.tenant_id '
+                || '(and likewise for stock_levels), then move or delete them and run the migration again.';
+    END IF;
+END $$;
+
+-- From here on the database refuses a row whose tenant differs from its
+-- item's or location's
+CREATE UNIQUE INDEX IF NOT EXISTS idx_items_id_tenant ON items(id, tenant_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_locations_id_tenant ON locations(id, tenant_id);
+
+ALTER TABLE stock_movements
+    ADD CONSTRAINT stock_movements_item_tenant_fkey
+        FOREIGN KEY (item_id, tenant_id) REFERENCES items(id, tenant_id),
+    ADD CONSTRAINT stock_movements_location_tenant_fkey
+        FOREIGN KEY (location_id, tenant_id) REFERENCES locations(id, tenant_id);
+
+ALTER TABLE stock_levels
+    ADD CONSTRAINT stock_levels_item_tenant_fkey
+        FOREIGN KEY (item_id, tenant_id) REFERENCES items(id, tenant_id),
+    ADD CONSTRAINT stock_levels_location_tenant_fkey
+        FOREIGN KEY (location_id, tenant_id) REFERENCES locations(id, tenant_id);
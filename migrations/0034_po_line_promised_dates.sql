@@ -0,0 +1,18 @@
+-- Each purchase order line carries its own dates: when we asked for it and,
+-- once the supplier confirms, when they promised it. A line is due on its
+-- promised date, or its expected date until a promise is recorded.
+ALTER TABLE purchase_order_lines
+    ADD COLUMN IF NOT EXISTS expected_date TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS promised_date TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS promised_at TIMESTAMPTZ;
+
+-- Existing lines were expected when their order was
+UPDATE purchase_order_lines l
+SET expected_date = po.expected_date
+FROM purchase_orders po
+WHERE po.id = l.po_id AND l.expected_date IS NULL;
+
+-- Overdue lines are found by due date among those still outstanding
+CREATE INDEX IF NOT EXISTS idx_purchase_order_lines_due
+    ON purchase_order_lines (tenant_id, (COALESCE(promised_date, expected_date)))
+    WHERE qty_received < qty_ordered;
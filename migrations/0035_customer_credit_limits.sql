@@ -0,0 +1,28 @@
+-- How far a customer may run up invoiced sales before new orders are held.
+-- Customers live in an external system, so limits are keyed by its id. With
+-- no limit set a customer's orders are never checked.
+CREATE TABLE IF NOT EXISTS credit_settings (
+    tenant_id UUID PRIMARY KEY REFERENCES tenants(id),
+    enforcement TEXT NOT NULL DEFAULT 'BLOCK' CHECK (enforcement IN ('BLOCK', 'WARN')),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS customer_credit_limits (
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    customer_id UUID NOT NULL,
+    credit_limit NUMERIC(15, 2) NOT NULL CHECK (credit_limit >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tenant_id, customer_id)
+);
+
+-- The outstanding balance sums a customer's invoiced orders
+CREATE INDEX IF NOT EXISTS idx_sales_orders_invoiced_customer
+    ON sales_orders (tenant_id, customer_id) WHERE status = 'INVOICED';
+
+ALTER TABLE credit_settings ENABLE ROW LEVEL SECURITY;
+ALTER TABLE customer_credit_limits ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_credit_settings_policy ON credit_settings
+    FOR ALL USING (credit_settings.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_customer_credit_limits_policy ON customer_credit_limits
+    FOR ALL USING (customer_credit_limits.tenant_id = current_setting('custom.tenant_id')::UUID);
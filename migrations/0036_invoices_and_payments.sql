@@ -0,0 +1,60 @@
+-- Invoices raised for shipped sales orders, and the customer payments recorded
+-- against them. An invoice's amount_paid is the sum of its payments' applied
+-- amounts; whatever a payment brings in beyond the balance is held as credit
+-- for the customer.
+CREATE TABLE IF NOT EXISTS invoices (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    invoice_number VARCHAR(50) NOT NULL,
+    so_id UUID NOT NULL REFERENCES sales_orders(id),
+    customer_id UUID,
+    status VARCHAR(20) NOT NULL DEFAULT 'OPEN'
+        CHECK (status IN ('OPEN', 'PARTIALLY_PAID', 'PAID')),
+    total_amount NUMERIC(15, 2) NOT NULL CHECK (total_amount >= 0),
+    amount_paid NUMERIC(15, 2) NOT NULL DEFAULT 0
+        CHECK (amount_paid >= 0 AND amount_paid <= total_amount),
+    currency VARCHAR(3),
+    exchange_rate DOUBLE PRECISION CHECK (exchange_rate > 0),
+    issued_at TIMESTAMPTZ NOT NULL,
+    due_date TIMESTAMPTZ NOT NULL,
+    created_by UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tenant_id, invoice_number),
+    UNIQUE (so_id)
+);
+
+CREATE TABLE IF NOT EXISTS payments (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES tenants(id),
+    invoice_id UUID NOT NULL REFERENCES invoices(id),
+    customer_id UUID,
+    amount NUMERIC(15, 2) NOT NULL CHECK (amount > 0),
+    applied_amount NUMERIC(15, 2) NOT NULL CHECK (applied_amount >= 0),
+    credit_amount NUMERIC(15, 2) NOT NULL DEFAULT 0 CHECK (credit_amount >= 0),
+    method VARCHAR(20) NOT NULL
+        CHECK (method IN ('CASH', 'CARD', 'BANK_TRANSFER', 'CHECK', 'OTHER')),
+    reference VARCHAR(255),
+    paid_at TIMESTAMPTZ NOT NULL,
+    recorded_by UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (applied_amount + credit_amount = amount)
+);
+
+CREATE INDEX IF NOT EXISTS idx_invoices_open_customer
+    ON invoices (tenant_id, customer_id) WHERE status <> 'PAID';
+CREATE INDEX IF NOT EXISTS idx_invoices_created ON invoices (tenant_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_payments_invoice ON payments (invoice_id, paid_at);
+CREATE INDEX IF NOT EXISTS idx_payments_credit_customer
+    ON payments (tenant_id, customer_id) WHERE credit_amount > 0;
+
+ALTER TABLE invoices ENABLE ROW LEVEL SECURITY;
+ALTER TABLE payments ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY tenant_invoices_policy ON invoices
+    FOR ALL USING (invoices.tenant_id = current_setting('custom.tenant_id')::UUID);
+CREATE POLICY tenant_payments_policy ON payments
+    FOR ALL USING (payments.tenant_id = current_setting('custom.tenant_id')::UUID);
+
+-- Outstanding credit balances now come from unpaid invoices
+DROP INDEX IF EXISTS idx_sales_orders_invoiced_customer;
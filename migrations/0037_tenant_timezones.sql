@@ -0,0 +1,12 @@
+-- IANA zone the tenant's calendar days, snapshots and billing periods follow
+ALTER TABLE tenants ADD COLUMN IF NOT EXISTS timezone TEXT NOT NULL DEFAULT 'UTC';
+
+-- The current tenant's zone, for bucketing timestamps into its local days with
+-- `ts AT TIME ZONE get_current_tenant_timezone()`
+CREATE OR REPLACE FUNCTION get_current_tenant_timezone()
+RETURNS TEXT AS $$
+    SELECT COALESCE(
+        (SELECT timezone FROM tenants WHERE id = get_current_tenant_id()),
+        'UTC'
+    );
+$$ LANGUAGE sql STABLE SECURITY DEFINER;
@@ -0,0 +1,9 @@
+-- A barcode identifies one item per tenant, like a SKU. Tenants that already
+-- have items sharing a barcode must merge or relabel them before this runs.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_items_tenant_barcode
+    ON items(tenant_id, barcode)
+    WHERE barcode IS NOT NULL;
+
+-- Trigram matching over item names, for the duplicate items report
+CREATE INDEX IF NOT EXISTS idx_items_name_trgm
+    ON items USING gin (lower(name) gin_trgm_ops);
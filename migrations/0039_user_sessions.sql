@@ -0,0 +1,17 @@
+-- One row per login. Tokens carry the session id in their `sid` claim and are
+-- only accepted while the session is neither revoked nor expired. Looked up
+-- before the tenant is known, so there is no row level security here.
+CREATE TABLE IF NOT EXISTS user_sessions (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL,
+    ip_address VARCHAR(64),
+    user_agent TEXT,
+    issued_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_sessions_user_active
+    ON user_sessions(user_id, issued_at DESC)
+    WHERE revoked_at IS NULL;
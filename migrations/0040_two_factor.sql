@@ -0,0 +1,18 @@
+-- Authenticator app (TOTP) enrollments. A row with no enabled_at is an
+-- enrollment the user hasn't confirmed yet; login only asks for codes once
+-- it is enabled.
+CREATE TABLE IF NOT EXISTS user_two_factor (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    secret TEXT NOT NULL,
+    recovery_code_hashes TEXT[] NOT NULL DEFAULT '{}',
+    last_used_step BIGINT,
+    enabled_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Tenants can require admins to log in with two factors
+ALTER TABLE tenants ADD COLUMN IF NOT EXISTS require_admin_two_factor BOOLEAN NOT NULL DEFAULT false;
+
+-- Sessions of admins who logged in without two factors under that policy may
+-- only enroll until they confirm an authenticator app
+ALTER TABLE user_sessions ADD COLUMN IF NOT EXISTS two_factor_enrollment_required BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,17 @@
+-- Return lines may name the sales order line the customer bought the item on.
+-- Such lines can't return more than the order line shipped, counting every
+-- return that isn't cancelled.
+ALTER TABLE return_lines ADD COLUMN IF NOT EXISTS sales_order_id UUID REFERENCES sales_orders(id);
+-- Checked at commit, since updating a sales order rewrites its lines with the
+-- same ids
+ALTER TABLE return_lines ADD COLUMN IF NOT EXISTS so_line_id UUID
+    REFERENCES sales_order_lines(id) DEFERRABLE INITIALLY DEFERRED;
+
+ALTER TABLE return_lines DROP CONSTRAINT IF EXISTS return_lines_sales_order_link;
+ALTER TABLE return_lines ADD CONSTRAINT return_lines_sales_order_link
+    CHECK ((sales_order_id IS NULL) = (so_line_id IS NULL));
+
+CREATE INDEX IF NOT EXISTS idx_return_lines_sales_order_id ON return_lines(sales_order_id)
+    WHERE sales_order_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_return_lines_so_line_id ON return_lines(so_line_id)
+    WHERE so_line_id IS NOT NULL;
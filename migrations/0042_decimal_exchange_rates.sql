@@ -0,0 +1,9 @@
+-- Exchange rates are kept as exact decimals, like the amounts they convert, so a
+-- rate reads back as the feed published it and every conversion rounds the same way
+ALTER TABLE exchange_rates ALTER COLUMN rate TYPE NUMERIC(20, 10) USING ROUND(rate::NUMERIC, 10);
+ALTER TABLE purchase_orders ALTER COLUMN exchange_rate TYPE NUMERIC(20, 10)
+    USING ROUND(exchange_rate::NUMERIC, 10);
+ALTER TABLE sales_orders ALTER COLUMN exchange_rate TYPE NUMERIC(20, 10)
+    USING ROUND(exchange_rate::NUMERIC, 10);
+ALTER TABLE invoices ALTER COLUMN exchange_rate TYPE NUMERIC(20, 10)
+    USING ROUND(exchange_rate::NUMERIC, 10);
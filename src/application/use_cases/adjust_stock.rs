@@ -1,10 +1,14 @@
-use crate::domain::entities::inventory::{
-    Adjustment, MovementType, ReferenceType, StockAdjustmentRequest, StockMovement,
+use crate::domain::entities::adjustment::{
+    normalize_code, Adjustment, AdjustmentStatus, StockAdjustmentRequest,
 };
+use crate::domain::entities::inventory::StockMovement;
 use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::adjustment_repository::AdjustmentRepository;
+use crate::domain::services::item_repository::ItemRepository;
 use crate::domain::services::stock_repository::StockRepository;
 use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
@@ -12,62 +16,159 @@ use uuid::Uuid;
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AdjustStockResponse {
     pub adjustment: Adjustment,
-    pub new_quantity_on_hand: i32,
+    /// Unset while the adjustment awaits approval
+    pub new_quantity_on_hand: Option<Decimal>,
 }
 
-pub struct AdjustStockUseCase<R: StockRepository, D: WebhookDispatcher> {
+#[derive(Debug, Deserialize)]
+pub struct ListAdjustmentsQuery {
+    pub status: Option<AdjustmentStatus>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListAdjustmentsResponse {
+    pub adjustments: Vec<Adjustment>,
+}
+
+pub struct AdjustStockUseCase<
+    R: StockRepository,
+    I: ItemRepository,
+    A: AdjustmentRepository,
+    D: WebhookDispatcher,
+> {
     stock_repository: Arc<R>,
+    item_repository: Arc<I>,
+    adjustment_repository: Arc<A>,
     webhook_dispatcher: Arc<D>,
 }
 
-impl<R: StockRepository, D: WebhookDispatcher> AdjustStockUseCase<R, D> {
-    pub fn new(stock_repository: Arc<R>, webhook_dispatcher: Arc<D>) -> Self {
+impl<R: StockRepository, I: ItemRepository, A: AdjustmentRepository, D: WebhookDispatcher>
+    AdjustStockUseCase<R, I, A, D>
+{
+    pub fn new(
+        stock_repository: Arc<R>,
+        item_repository: Arc<I>,
+        adjustment_repository: Arc<A>,
+        webhook_dispatcher: Arc<D>,
+    ) -> Self {
         Self {
             stock_repository,
+            item_repository,
+            adjustment_repository,
             webhook_dispatcher,
         }
     }
 
+    /// Adjust stock against a catalog reason code. Adjustments past the
+    /// reason's approval thresholds are saved but leave stock untouched until
+    /// approved.
     pub async fn execute(
         &self,
         request: StockAdjustmentRequest,
         created_by: Uuid,
     ) -> Result<AdjustStockResponse, DomainError> {
-        // Create the stock movement
-        let movement = StockMovement::new(
-            request.item_id,
-            request.location_id,
-            MovementType::Adjustment,
-            request.qty_change,
-            ReferenceType::Adjustment,
-            None, // No reference ID for adjustments
-            Some(request.reason.as_str().to_string()),
-            Some(created_by),
-        )?;
-
-        // Record the movement (this will update stock levels atomically)
-        self.stock_repository.record_movement(&movement).await?;
+        let code = normalize_code(&request.reason_code);
+        let reason = self
+            .adjustment_repository
+            .find_reason_by_code(&code)
+            .await?
+            .ok_or_else(|| {
+                DomainError::ValidationError(
+                    format!("Unknown adjustment reason code: {}", code).into(),
+                )
+            })?;
+        let item = self
+            .item_repository
+            .find_by_id(request.item_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Item {} not found", request.item_id).into())
+            })?;
+        item.check_quantity(request.qty_change)?;
+
+        let mut adjustment = Adjustment::new(request, &reason, item.cost_price, created_by)?;
+        if adjustment.status == AdjustmentStatus::PendingApproval {
+            self.adjustment_repository.create(&adjustment, None).await?;
+            return Ok(AdjustStockResponse {
+                adjustment,
+                new_quantity_on_hand: None,
+            });
+        }
+
+        let movement = adjustment.movement()?;
+        self.adjustment_repository
+            .create(&adjustment, Some(&movement))
+            .await?;
+        self.applied(adjustment, movement).await
+    }
+
+    /// Apply an adjustment that was waiting for approval
+    pub async fn approve(
+        &self,
+        adjustment_id: Uuid,
+        approved_by: Uuid,
+        note: Option<String>,
+    ) -> Result<AdjustStockResponse, DomainError> {
+        let mut adjustment = self.get(adjustment_id).await?;
+        let movement = adjustment.approve(approved_by, note)?;
+        self.adjustment_repository
+            .decide(&adjustment, Some(&movement))
+            .await?;
+        self.applied(adjustment, movement).await
+    }
+
+    /// Reject an adjustment that was waiting for approval; stock is untouched
+    pub async fn reject(
+        &self,
+        adjustment_id: Uuid,
+        rejected_by: Uuid,
+        note: Option<String>,
+    ) -> Result<Adjustment, DomainError> {
+        let mut adjustment = self.get(adjustment_id).await?;
+        adjustment.reject(rejected_by, note)?;
+        self.adjustment_repository.decide(&adjustment, None).await?;
+        Ok(adjustment)
+    }
 
+    pub async fn get(&self, adjustment_id: Uuid) -> Result<Adjustment, DomainError> {
+        self.adjustment_repository
+            .find_by_id(adjustment_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Adjustment {} not found", adjustment_id).into())
+            })
+    }
+
+    pub async fn list(
+        &self,
+        query: ListAdjustmentsQuery,
+    ) -> Result<ListAdjustmentsResponse, DomainError> {
+        let limit = query.limit.unwrap_or(50).clamp(1, 100);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let adjustments = self
+            .adjustment_repository
+            .list(query.status, limit, offset)
+            .await?;
+        Ok(ListAdjustmentsResponse { adjustments })
+    }
+
+    /// Notify subscribers of an applied adjustment and check for low stock
+    async fn applied(
+        &self,
+        adjustment: Adjustment,
+        movement: StockMovement,
+    ) -> Result<AdjustStockResponse, DomainError> {
         // Get the updated stock level
         let stock_level = self
             .stock_repository
-            .get_stock_level(request.item_id, request.location_id)
+            .get_stock_level(adjustment.item_id, adjustment.location_id)
             .await?
             .ok_or_else(|| {
-                DomainError::NotFound("Stock level not found after adjustment".to_string())
+                DomainError::NotFound("Stock level not found after adjustment".into())
             })?;
 
-        let adjustment = Adjustment {
-            id: movement.id,
-            item_id: request.item_id,
-            location_id: request.location_id,
-            qty_change: request.qty_change,
-            reason: request.reason,
-            note: request.note,
-            created_by,
-            created_at: movement.created_at,
-        };
-
         // Trigger webhook event for stock adjustment
         let webhook_payload = serde_json::json!({
             "event_type": "stock_adjustment",
@@ -76,10 +177,13 @@ impl<R: StockRepository, D: WebhookDispatcher> AdjustStockUseCase<R, D> {
                 "item_id": adjustment.item_id,
                 "location_id": adjustment.location_id,
                 "qty_change": adjustment.qty_change,
-                "reason": adjustment.reason,
+                "reason": adjustment.reason_code,
                 "note": adjustment.note,
+                "value": adjustment.value,
+                "movement_id": adjustment.movement_id,
                 "created_by": adjustment.created_by,
                 "created_at": adjustment.created_at,
+                "approved_by": adjustment.decided_by,
                 "new_quantity_on_hand": stock_level.quantity_on_hand
             }
         });
@@ -89,9 +193,59 @@ impl<R: StockRepository, D: WebhookDispatcher> AdjustStockUseCase<R, D> {
         // Note: We don't fail the stock adjustment if webhook dispatch fails
         let _ = self.webhook_dispatcher.dispatch_event(&webhook_event).await;
 
+        if adjustment.qty_change < Decimal::ZERO {
+            if let Err(e) = self
+                .check_low_stock(&movement, stock_level.quantity_on_hand)
+                .await
+            {
+                eprintln!(
+                    "Failed to check low stock for item {}: {:?}",
+                    movement.item_id, e
+                );
+            }
+        }
+
         Ok(AdjustStockResponse {
             adjustment,
-            new_quantity_on_hand: stock_level.quantity_on_hand,
+            new_quantity_on_hand: Some(stock_level.quantity_on_hand),
         })
     }
+
+    /// Emit `LOW_STOCK_ALERT` when this movement took the item's total on-hand
+    /// quantity below its reorder point
+    async fn check_low_stock(
+        &self,
+        movement: &StockMovement,
+        location_quantity_on_hand: Decimal,
+    ) -> Result<(), DomainError> {
+        let Some(item) = self.item_repository.find_by_id(movement.item_id).await? else {
+            return Ok(());
+        };
+        let total_after = self
+            .stock_repository
+            .get_total_quantity_on_hand(movement.item_id)
+            .await?;
+        let total_before = total_after - movement.quantity;
+        if !item.falls_below_reorder_point(total_before, total_after) {
+            return Ok(());
+        }
+
+        let webhook_event = WebhookEvent::new(
+            WebhookEventType::LowStockAlert,
+            serde_json::json!({
+                "item": {
+                    "id": item.id,
+                    "sku": item.sku,
+                    "name": item.name,
+                    "reorder_point": item.reorder_point,
+                    "reorder_qty": item.reorder_qty,
+                },
+                "quantity_on_hand": total_after,
+                "location_id": movement.location_id,
+                "location_quantity_on_hand": location_quantity_on_hand,
+                "movement_id": movement.id,
+            }),
+        );
+        self.webhook_dispatcher.dispatch_event(&webhook_event).await
+    }
 }
@@ -0,0 +1,86 @@
+use crate::domain::entities::allocation::{AllocateSalesOrderRequest, AllocationPlan};
+use crate::domain::entities::sales_order::SalesOrderStatus;
+use crate::domain::services::allocation_repository::AllocationRepository;
+use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::shared::error::DomainError;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Splits an open sales order across locations and stores the per-line allocations
+/// that the ship flow draws from
+pub struct AllocateSalesOrderUseCase<T: SalesOrderRepository, A: AllocationRepository> {
+    sales_order_repo: Arc<T>,
+    allocation_repo: Arc<A>,
+}
+
+impl<T: SalesOrderRepository, A: AllocationRepository> AllocateSalesOrderUseCase<T, A> {
+    pub fn new(sales_order_repo: Arc<T>, allocation_repo: Arc<A>) -> Self {
+        Self {
+            sales_order_repo,
+            allocation_repo,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        so_id: Uuid,
+        request: AllocateSalesOrderRequest,
+    ) -> Result<AllocationPlan, DomainError> {
+        let (sales_order, _) = self
+            .sales_order_repo
+            .find_by_id(so_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Sales order {} not found", so_id).into())
+            })?;
+
+        match sales_order.status {
+            SalesOrderStatus::Confirmed
+            | SalesOrderStatus::Picking
+            | SalesOrderStatus::PartiallyShipped => {}
+            _ => {
+                return Err(DomainError::ValidationError(
+                    format!(
+                        "Cannot allocate sales order with status: {:?}",
+                        sales_order.status
+                    )
+                    .into(),
+                ))
+            }
+        }
+
+        let mut item_ids: Vec<Uuid> = sales_order.lines.iter().map(|l| l.item_id).collect();
+        item_ids.sort();
+        item_ids.dedup();
+
+        let stock = self
+            .allocation_repo
+            .find_available_stock(so_id, &item_ids)
+            .await?;
+
+        let plan = AllocationPlan::build(
+            &sales_order,
+            &stock,
+            request.strategy.unwrap_or_default(),
+            request.ship_to.as_ref(),
+        )?;
+
+        if !plan.unallocated.is_empty() && !request.allow_partial.unwrap_or(false) {
+            let short: Decimal = plan.unallocated.iter().map(|u| u.qty).sum();
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Insufficient stock to allocate sales order {}: {} units short",
+                    sales_order.so_number, short
+                )
+                .into(),
+            ));
+        }
+
+        self.allocation_repo
+            .replace_allocations(so_id, &plan.allocations)
+            .await?;
+
+        Ok(plan)
+    }
+}
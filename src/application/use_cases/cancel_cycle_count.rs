@@ -0,0 +1,32 @@
+use crate::domain::entities::cycle_count::CycleCount;
+use crate::domain::services::cycle_count_repository::CycleCountRepository;
+use crate::shared::error::DomainError;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct CancelCycleCountUseCase<C: CycleCountRepository> {
+    cycle_count_repository: Arc<C>,
+}
+
+impl<C: CycleCountRepository> CancelCycleCountUseCase<C> {
+    pub fn new(cycle_count_repository: Arc<C>) -> Self {
+        Self {
+            cycle_count_repository,
+        }
+    }
+
+    pub async fn execute(&self, cycle_count_id: Uuid) -> Result<CycleCount, DomainError> {
+        let mut cycle_count = self
+            .cycle_count_repository
+            .find_by_id(cycle_count_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Cycle count {} not found", cycle_count_id).into())
+            })?;
+
+        cycle_count.cancel()?;
+        self.cycle_count_repository.update(&cycle_count).await?;
+
+        Ok(cycle_count)
+    }
+}
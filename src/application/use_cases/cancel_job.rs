@@ -0,0 +1,35 @@
+use crate::application::use_cases::get_job_status::GetJobStatusResponse;
+use crate::domain::services::job_service::JobService;
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CancelJobRequest {
+    pub tenant_id: Uuid,
+    pub job_id: String,
+}
+
+/// Cancel a job: queued jobs are cancelled straight away, running jobs are
+/// flagged and stop at their next checkpoint
+pub struct CancelJobUseCase<S: JobService> {
+    job_service: Arc<S>,
+}
+
+impl<S: JobService> CancelJobUseCase<S> {
+    pub fn new(job_service: Arc<S>) -> Self {
+        Self { job_service }
+    }
+
+    pub async fn execute(
+        &self,
+        request: CancelJobRequest,
+    ) -> Result<GetJobStatusResponse, DomainError> {
+        let job = self
+            .job_service
+            .cancel_job(request.tenant_id, &request.job_id)
+            .await?;
+        Ok(job.into())
+    }
+}
@@ -0,0 +1,96 @@
+use crate::domain::entities::sales_order::{SalesOrder, StockMovement};
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use crate::shared::etag::check_if_match;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CancelSalesOrderRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CancelSalesOrderResponse {
+    pub sales_order: SalesOrder,
+    pub stock_movements: Vec<StockMovement>,
+}
+
+pub struct CancelSalesOrderUseCase<T: SalesOrderRepository, D: WebhookDispatcher + 'static> {
+    sales_order_repo: Arc<T>,
+    webhook_dispatcher: Arc<D>,
+}
+
+impl<T: SalesOrderRepository, D: WebhookDispatcher + 'static> CancelSalesOrderUseCase<T, D> {
+    pub fn new(sales_order_repo: Arc<T>, webhook_dispatcher: Arc<D>) -> Self {
+        Self {
+            sales_order_repo,
+            webhook_dispatcher,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        so_id: Uuid,
+        request: CancelSalesOrderRequest,
+        cancelled_by: Uuid,
+        if_match: Option<String>,
+    ) -> Result<CancelSalesOrderResponse, DomainError> {
+        if if_match.is_some() {
+            let (current, _) = self
+                .sales_order_repo
+                .find_by_id(so_id)
+                .await?
+                .ok_or_else(|| {
+                    DomainError::NotFound(format!("Sales order {} not found", so_id).into())
+                })?;
+            check_if_match(if_match.as_deref(), &current.etag())?;
+        }
+
+        let (sales_order, stock_movements) = self
+            .sales_order_repo
+            .cancel_sales_order(so_id, request.reason, cancelled_by)
+            .await?;
+
+        // Dispatch webhook event (non-blocking)
+        let webhook_event = WebhookEvent::new(
+            WebhookEventType::SalesOrderUpdated,
+            json!({
+                "sales_order": {
+                    "id": sales_order.id,
+                    "so_number": sales_order.so_number,
+                    "customer_id": sales_order.customer_id,
+                    "status": sales_order.status.as_str(),
+                    "total_amount": sales_order.total_amount,
+                    "fulfillment_location_id": sales_order.fulfillment_location_id,
+                    "cancellation_reason": sales_order.cancellation_reason,
+                    "cancelled_at": sales_order.cancelled_at,
+                    "updated_at": sales_order.updated_at
+                },
+                "released": stock_movements.iter().map(|movement| json!({
+                    "item_id": movement.item_id,
+                    "location_id": movement.location_id,
+                    "reason": movement.reason
+                })).collect::<Vec<_>>(),
+                "cancelled_by": cancelled_by
+            }),
+        );
+
+        // Spawn a task to dispatch the webhook asynchronously
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch sales order cancelled webhook: {:?}", e);
+            }
+        });
+
+        Ok(CancelSalesOrderResponse {
+            sales_order,
+            stock_movements,
+        })
+    }
+}
@@ -0,0 +1,45 @@
+use crate::domain::services::user_repository::UserRepository;
+use crate::domain::value_objects::password_hash::PasswordHash;
+use crate::shared::error::DomainError;
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+pub struct ChangePasswordUseCase<R: UserRepository> {
+    user_repository: Arc<R>,
+}
+
+impl<R: UserRepository> ChangePasswordUseCase<R> {
+    pub fn new(user_repository: Arc<R>) -> Self {
+        Self { user_repository }
+    }
+
+    /// Change a logged-in user's password after checking their current one
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        request: ChangePasswordRequest,
+    ) -> Result<(), DomainError> {
+        let mut user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("User {} not found", user_id).into()))?;
+
+        if !user.verify_password(&request.current_password)? {
+            return Err(DomainError::ValidationError(
+                "Current password is incorrect".into(),
+            ));
+        }
+
+        user.password_hash = PasswordHash::new(&request.new_password)?;
+        user.updated_at = chrono::Utc::now();
+        self.user_repository.update(&user).await
+    }
+}
@@ -0,0 +1,69 @@
+use crate::domain::entities::inventory::{ChangeStockStatusRequest, StockLevel, StockMovement};
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::stock_repository::StockRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use serde::Serialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct ChangeStockStatusResponse {
+    pub stock_movements: Vec<StockMovement>,
+    pub stock_level: StockLevel,
+}
+
+pub struct ChangeStockStatusUseCase<R: StockRepository, D: WebhookDispatcher> {
+    stock_repository: Arc<R>,
+    webhook_dispatcher: Arc<D>,
+}
+
+impl<R: StockRepository, D: WebhookDispatcher> ChangeStockStatusUseCase<R, D> {
+    pub fn new(stock_repository: Arc<R>, webhook_dispatcher: Arc<D>) -> Self {
+        Self {
+            stock_repository,
+            webhook_dispatcher,
+        }
+    }
+
+    /// Move stock between statuses at one location, e.g. quarantine received
+    /// goods for inspection or release them once they pass
+    pub async fn execute(
+        &self,
+        request: ChangeStockStatusRequest,
+        created_by: Uuid,
+    ) -> Result<ChangeStockStatusResponse, DomainError> {
+        let (out_of_status, into_status) = request.movements(created_by)?;
+        let stock_level = self
+            .stock_repository
+            .record_status_change(&out_of_status, &into_status)
+            .await?;
+
+        let webhook_event = WebhookEvent::new(
+            WebhookEventType::StockMovement,
+            serde_json::json!({
+                "event_type": "stock_status_change",
+                "status_change": {
+                    "id": out_of_status.reference_id,
+                    "item_id": request.item_id,
+                    "location_id": request.location_id,
+                    "from_status": request.from_status,
+                    "to_status": request.to_status,
+                    "quantity": request.quantity,
+                    "reason": out_of_status.reason,
+                    "created_by": created_by,
+                    "created_at": out_of_status.created_at,
+                },
+                "stock_level": stock_level,
+            }),
+        );
+
+        // Note: We don't fail the status change if webhook dispatch fails
+        let _ = self.webhook_dispatcher.dispatch_event(&webhook_event).await;
+
+        Ok(ChangeStockStatusResponse {
+            stock_movements: vec![out_of_status, into_status],
+            stock_level,
+        })
+    }
+}
@@ -70,7 +70,7 @@ mod tests {
             .returning(move || Ok(vec![expired_tenant.clone()]));
         mock_repo.expect_delete_tenant().returning(|_| Ok(()));
 
-        let use_case = CleanupExpiredSandboxesUseCase::new(mock_repo);
+        let use_case = CleanupExpiredSandboxesUseCase::new(Arc::new(mock_repo));
         let result = use_case.execute().await;
 
         assert!(result.is_ok());
@@ -86,7 +86,7 @@ mod tests {
             .expect_get_expired_sandboxes()
             .returning(|| Ok(vec![]));
 
-        let use_case = CleanupExpiredSandboxesUseCase::new(mock_repo);
+        let use_case = CleanupExpiredSandboxesUseCase::new(Arc::new(mock_repo));
         let result = use_case.execute().await;
 
         assert!(result.is_ok());
@@ -0,0 +1,81 @@
+use crate::application::use_cases::get_purchase_order::GetPurchaseOrderResponse;
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use crate::shared::etag::check_if_match;
+use crate::shared::i18n::Message;
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ClosePurchaseOrderRequest {
+    pub reason: Option<String>,
+}
+
+pub struct ClosePurchaseOrderUseCase<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> {
+    purchase_order_repository: Arc<R>,
+    webhook_dispatcher: Arc<D>,
+}
+
+impl<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> ClosePurchaseOrderUseCase<R, D> {
+    pub fn new(purchase_order_repository: Arc<R>, webhook_dispatcher: Arc<D>) -> Self {
+        Self {
+            purchase_order_repository,
+            webhook_dispatcher,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        po_id: Uuid,
+        request: ClosePurchaseOrderRequest,
+        user_id: Uuid,
+        if_match: Option<String>,
+    ) -> Result<GetPurchaseOrderResponse, DomainError> {
+        let mut po = self
+            .purchase_order_repository
+            .find_by_id(po_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_not_found",
+                    &[("id", &po_id)],
+                ))
+            })?;
+        check_if_match(if_match.as_deref(), &po.etag())?;
+
+        let outstanding_qty = po.outstanding_qty();
+        po.close()?;
+        self.purchase_order_repository.update(&po).await?;
+
+        // Dispatch webhook event (non-blocking)
+        let webhook_event = WebhookEvent::new(
+            WebhookEventType::PurchaseOrderClosed,
+            json!({
+                "purchase_order": {
+                    "id": po.id,
+                    "po_number": po.po_number,
+                    "supplier_id": po.supplier_id,
+                    "status": po.status.to_string(),
+                    "total_amount": po.total_amount,
+                    "outstanding_qty": outstanding_qty,
+                    "updated_at": po.updated_at
+                },
+                "reason": request.reason,
+                "closed_by": user_id
+            }),
+        );
+
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch purchase order closed webhook: {:?}", e);
+            }
+        });
+
+        Ok(po.into())
+    }
+}
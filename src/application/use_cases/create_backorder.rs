@@ -0,0 +1,83 @@
+use crate::domain::entities::sales_order::{SalesOrder, SalesOrderBackorder};
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use serde::Serialize;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct CreateBackorderResponse {
+    pub original_sales_order: SalesOrder,
+    pub backorder_sales_order: SalesOrder,
+    pub backorder: SalesOrderBackorder,
+}
+
+pub struct CreateBackorderUseCase<T: SalesOrderRepository, D: WebhookDispatcher + 'static> {
+    sales_order_repo: Arc<T>,
+    webhook_dispatcher: Arc<D>,
+}
+
+impl<T: SalesOrderRepository, D: WebhookDispatcher + 'static> CreateBackorderUseCase<T, D> {
+    pub fn new(sales_order_repo: Arc<T>, webhook_dispatcher: Arc<D>) -> Self {
+        Self {
+            sales_order_repo,
+            webhook_dispatcher,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        so_id: Uuid,
+        created_by: Uuid,
+    ) -> Result<CreateBackorderResponse, DomainError> {
+        // Generate SO number (in a real app, this might come from a sequence)
+        let so_number = format!("SO-{}", Uuid::new_v4().simple());
+
+        let (original_sales_order, backorder_sales_order, backorder) = self
+            .sales_order_repo
+            .create_backorder(so_id, so_number, created_by)
+            .await?;
+
+        // Dispatch webhook event (non-blocking)
+        let webhook_event = WebhookEvent::new(
+            WebhookEventType::SalesOrderCreated,
+            json!({
+                "sales_order": {
+                    "id": backorder_sales_order.id,
+                    "so_number": backorder_sales_order.so_number,
+                    "customer_id": backorder_sales_order.customer_id,
+                    "status": backorder_sales_order.status.as_str(),
+                    "total_amount": backorder_sales_order.total_amount,
+                    "fulfillment_location_id": backorder_sales_order.fulfillment_location_id,
+                    "created_at": backorder_sales_order.created_at,
+                    "backorder_of": original_sales_order.id,
+                    "lines": backorder_sales_order.lines.iter().map(|line| json!({
+                        "id": line.id,
+                        "item_id": line.item_id,
+                        "qty": line.qty,
+                        "unit_price": line.unit_price,
+                        "tax": line.tax,
+                        "reserved": line.reserved
+                    })).collect::<Vec<_>>()
+                }
+            }),
+        );
+
+        // Spawn a task to dispatch the webhook asynchronously
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch backorder created webhook: {:?}", e);
+            }
+        });
+
+        Ok(CreateBackorderResponse {
+            original_sales_order,
+            backorder_sales_order,
+            backorder,
+        })
+    }
+}
@@ -0,0 +1,77 @@
+use crate::domain::entities::cycle_count::{CreateCycleCountRequest, CycleCount};
+use crate::domain::services::cycle_count_repository::CycleCountRepository;
+use crate::domain::services::stock_repository::StockRepository;
+use crate::shared::error::DomainError;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct CreateCycleCountResponse {
+    pub cycle_count: CycleCount,
+}
+
+pub struct CreateCycleCountUseCase<C: CycleCountRepository, S: StockRepository> {
+    cycle_count_repository: Arc<C>,
+    stock_repository: Arc<S>,
+}
+
+impl<C: CycleCountRepository, S: StockRepository> CreateCycleCountUseCase<C, S> {
+    pub fn new(cycle_count_repository: Arc<C>, stock_repository: Arc<S>) -> Self {
+        Self {
+            cycle_count_repository,
+            stock_repository,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        request: CreateCycleCountRequest,
+        created_by: Uuid,
+    ) -> Result<CreateCycleCountResponse, DomainError> {
+        let count_number = format!("CC-{}", Uuid::new_v4().simple());
+
+        let mut cycle_count = CycleCount::new(
+            count_number,
+            request.location_id,
+            request.scheduled_for,
+            request.notes,
+            created_by,
+        )?;
+
+        // Snapshot the system quantity for each item being counted
+        match request.item_ids {
+            Some(item_ids) => {
+                for item_id in item_ids {
+                    let expected_qty = self
+                        .stock_repository
+                        .get_stock_level(item_id, request.location_id)
+                        .await?
+                        .map(|level| level.quantity_on_hand)
+                        .unwrap_or(Decimal::ZERO);
+                    cycle_count.add_line(item_id, expected_qty)?;
+                }
+            }
+            None => {
+                let stock_levels = self
+                    .stock_repository
+                    .get_location_stock_levels(request.location_id)
+                    .await?;
+                for level in stock_levels {
+                    cycle_count.add_line(level.item_id, level.quantity_on_hand)?;
+                }
+            }
+        }
+
+        if cycle_count.lines.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Cycle count must include at least one item".into(),
+            ));
+        }
+
+        self.cycle_count_repository.create(&cycle_count).await?;
+
+        Ok(CreateCycleCountResponse { cycle_count })
+    }
+}
@@ -1,7 +1,13 @@
 use crate::domain::entities::item::{Item, ItemDimensions};
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
 use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::quota_service::{QuotaResource, QuotaService};
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -13,10 +19,11 @@ pub struct CreateItemRequest {
     pub category: Option<String>,
     pub unit: String,
     pub barcode: Option<String>,
-    pub cost_price: f64,
-    pub sale_price: Option<f64>,
-    pub reorder_point: Option<i32>,
-    pub reorder_qty: Option<i32>,
+    pub cost_price: Decimal,
+    pub sale_price: Option<Decimal>,
+    pub reorder_point: Option<Decimal>,
+    pub reorder_qty: Option<Decimal>,
+    pub quantity_precision: Option<u32>,
     pub weight: Option<f64>,
     pub dimensions: Option<ItemDimensions>,
     pub metadata: Option<serde_json::Value>,
@@ -28,20 +35,29 @@ pub struct CreateItemResponse {
     pub sku: String,
     pub name: String,
     pub unit: String,
-    pub cost_price: f64,
+    pub cost_price: Decimal,
     pub active: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Clone)]
-pub struct CreateItemUseCase<R: ItemRepository> {
+pub struct CreateItemUseCase<R: ItemRepository + ?Sized, D: WebhookDispatcher + ?Sized + 'static> {
     item_repository: Arc<R>,
+    webhook_dispatcher: Arc<D>,
+    quota_service: Arc<dyn QuotaService>,
 }
 
-impl<R: ItemRepository> CreateItemUseCase<R> {
-    pub fn new(item_repository: Arc<R>) -> Self {
-        Self { item_repository }
+impl<R: ItemRepository + ?Sized, D: WebhookDispatcher + ?Sized + 'static> CreateItemUseCase<R, D> {
+    pub fn new(
+        item_repository: Arc<R>,
+        webhook_dispatcher: Arc<D>,
+        quota_service: Arc<dyn QuotaService>,
+    ) -> Self {
+        Self {
+            item_repository,
+            webhook_dispatcher,
+            quota_service,
+        }
     }
 
     pub async fn execute(
@@ -52,11 +68,19 @@ impl<R: ItemRepository> CreateItemUseCase<R> {
         // Check if SKU already exists
         let sku_exists = self.item_repository.sku_exists(&request.sku, None).await?;
         if sku_exists {
-            return Err(DomainError::ValidationError(format!(
-                "Item with SKU '{}' already exists",
-                request.sku
+            return Err(DomainError::Conflict(Message::localized(
+                "error.item_sku_exists",
+                &[("sku", &request.sku)],
             )));
         }
+        if let Some(barcode) = &request.barcode {
+            if self.item_repository.barcode_exists(barcode, None).await? {
+                return Err(DomainError::Conflict(Message::localized(
+                    "error.item_barcode_exists",
+                    &[("barcode", &barcode)],
+                )));
+            }
+        }
 
         // Create the item with required fields
         let mut item = Item::new(
@@ -79,6 +103,7 @@ impl<R: ItemRepository> CreateItemUseCase<R> {
             sale_price: request.sale_price,
             reorder_point: request.reorder_point,
             reorder_qty: request.reorder_qty,
+            quantity_precision: request.quantity_precision,
             weight: request.weight,
             dimensions: request.dimensions,
             metadata: request.metadata,
@@ -86,8 +111,7 @@ impl<R: ItemRepository> CreateItemUseCase<R> {
 
         item.update(update_request)?;
 
-        // Save to repository
-        self.item_repository.save(&item).await?;
+        let item = self.save_new(item).await?;
 
         // Return response
         Ok(CreateItemResponse {
@@ -101,4 +125,31 @@ impl<R: ItemRepository> CreateItemUseCase<R> {
             updated_at: item.updated_at,
         })
     }
+
+    /// Save an item built elsewhere, counting it against its tenant's quota and
+    /// announcing it to webhooks
+    pub async fn save_new(&self, item: Item) -> Result<Item, DomainError> {
+        // Count the item against the tenant's quota, handing the slot back if the save fails
+        self.quota_service
+            .reserve(item.tenant_id, QuotaResource::Items)
+            .await?;
+        if let Err(e) = self.item_repository.save(&item).await {
+            self.quota_service
+                .release(item.tenant_id, QuotaResource::Items)
+                .await?;
+            return Err(e);
+        }
+
+        // Dispatch webhook event (non-blocking)
+        let webhook_event =
+            WebhookEvent::new(WebhookEventType::ItemCreated, json!({ "item": item }));
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch item created webhook: {:?}", e);
+            }
+        });
+
+        Ok(item)
+    }
 }
@@ -1,7 +1,14 @@
 use crate::domain::entities::location::{Location, LocationAddress, UpdateLocationRequest};
+use crate::domain::entities::location_capacity::LocationCapacity;
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::address_validator::AddressValidator;
 use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::services::quota_service::{QuotaResource, QuotaService};
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -11,6 +18,7 @@ pub struct CreateLocationRequest {
     pub code: Option<String>,
     pub address: Option<LocationAddress>,
     pub r#type: Option<String>,
+    pub capacity: Option<LocationCapacity>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,33 +28,50 @@ pub struct CreateLocationResponse {
     pub code: Option<String>,
     pub r#type: Option<String>,
     pub active: bool,
+    pub capacity: LocationCapacity,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Clone)]
-pub struct CreateLocationUseCase<R: LocationRepository> {
+pub struct CreateLocationUseCase<
+    R: LocationRepository + ?Sized,
+    D: WebhookDispatcher + ?Sized + 'static,
+> {
     location_repository: Arc<R>,
+    webhook_dispatcher: Arc<D>,
+    quota_service: Arc<dyn QuotaService>,
+    address_validator: Arc<dyn AddressValidator>,
 }
 
-impl<R: LocationRepository> CreateLocationUseCase<R> {
-    pub fn new(location_repository: Arc<R>) -> Self {
+impl<R: LocationRepository + ?Sized, D: WebhookDispatcher + ?Sized + 'static>
+    CreateLocationUseCase<R, D>
+{
+    pub fn new(
+        location_repository: Arc<R>,
+        webhook_dispatcher: Arc<D>,
+        quota_service: Arc<dyn QuotaService>,
+        address_validator: Arc<dyn AddressValidator>,
+    ) -> Self {
         Self {
             location_repository,
+            webhook_dispatcher,
+            quota_service,
+            address_validator,
         }
     }
 
     pub async fn execute(
         &self,
         request: CreateLocationRequest,
+        tenant_id: Uuid,
     ) -> Result<CreateLocationResponse, DomainError> {
         // Check if code already exists (if provided)
         if let Some(ref code) = request.code {
             let code_exists = self.location_repository.code_exists(code, None).await?;
             if code_exists {
-                return Err(DomainError::ValidationError(format!(
-                    "Location with code '{}' already exists",
-                    code
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.location_code_exists",
+                    &[("code", &code)],
                 )));
             }
         }
@@ -54,18 +79,45 @@ impl<R: LocationRepository> CreateLocationUseCase<R> {
         // Create the location with required fields
         let mut location = Location::new(request.name)?;
 
+        // Store the address in its normalized form, refusing one that can't be right
+        let address = match request.address {
+            Some(address) => Some(self.address_validator.normalize(address).await?),
+            None => None,
+        };
+
         // Update with optional fields
         let update_request = UpdateLocationRequest {
             name: None, // Name is already set
             code: request.code,
-            address: request.address,
+            address,
             r#type: request.r#type,
+            capacity: request.capacity,
         };
 
         location.update(update_request)?;
 
-        // Save to repository
-        self.location_repository.save(&location).await?;
+        // Count the location against the tenant's quota, handing the slot back if the save fails
+        self.quota_service
+            .reserve(tenant_id, QuotaResource::Locations)
+            .await?;
+        if let Err(e) = self.location_repository.save(&location).await {
+            self.quota_service
+                .release(tenant_id, QuotaResource::Locations)
+                .await?;
+            return Err(e);
+        }
+
+        // Dispatch webhook event (non-blocking)
+        let webhook_event = WebhookEvent::new(
+            WebhookEventType::LocationCreated,
+            json!({ "location": location }),
+        );
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch location created webhook: {:?}", e);
+            }
+        });
 
         // Return response
         Ok(CreateLocationResponse {
@@ -74,6 +126,7 @@ impl<R: LocationRepository> CreateLocationUseCase<R> {
             code: location.code,
             r#type: location.r#type.map(|t| t.as_str().to_string()),
             active: location.active,
+            capacity: location.capacity,
             created_at: location.created_at,
             updated_at: location.updated_at,
         })
@@ -1,8 +1,11 @@
 use crate::domain::entities::purchase_order::{CreatePurchaseOrderLine, PurchaseOrder};
 use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::currency_service::CurrencyService;
+use crate::domain::services::item_repository::ItemRepository;
 use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
 use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
@@ -13,6 +16,9 @@ pub struct CreatePurchaseOrderUseCaseRequest {
     pub supplier_id: Uuid,
     pub expected_date: Option<chrono::DateTime<chrono::Utc>>,
     pub lines: Vec<CreatePurchaseOrderLine>,
+    /// ISO 4217 code the supplier prices in; defaults to the tenant's base currency
+    #[serde(default)]
+    pub currency: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -21,7 +27,11 @@ pub struct CreatePurchaseOrderResponse {
     pub po_number: String,
     pub supplier_id: Uuid,
     pub status: String,
-    pub total_amount: f64,
+    pub total_amount: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exchange_rate: Option<Decimal>,
     pub lines: Vec<PurchaseOrderLineResponse>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
@@ -30,21 +40,31 @@ pub struct CreatePurchaseOrderResponse {
 pub struct PurchaseOrderLineResponse {
     pub id: Uuid,
     pub item_id: Uuid,
-    pub qty_ordered: i32,
-    pub qty_received: i32,
-    pub unit_cost: f64,
-    pub line_total: f64,
+    pub qty_ordered: Decimal,
+    pub qty_received: Decimal,
+    pub unit_cost: Decimal,
+    pub line_total: Decimal,
+    pub expected_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 pub struct CreatePurchaseOrderUseCase<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> {
     purchase_order_repository: Arc<R>,
+    currency_service: Arc<dyn CurrencyService>,
+    item_repository: Arc<dyn ItemRepository>,
     webhook_dispatcher: Arc<D>,
 }
 
 impl<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> CreatePurchaseOrderUseCase<R, D> {
-    pub fn new(purchase_order_repository: Arc<R>, webhook_dispatcher: Arc<D>) -> Self {
+    pub fn new(
+        purchase_order_repository: Arc<R>,
+        currency_service: Arc<dyn CurrencyService>,
+        item_repository: Arc<dyn ItemRepository>,
+        webhook_dispatcher: Arc<D>,
+    ) -> Self {
         Self {
             purchase_order_repository,
+            currency_service,
+            item_repository,
             webhook_dispatcher,
         }
     }
@@ -54,13 +74,26 @@ impl<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> CreatePurchaseO
         request: CreatePurchaseOrderUseCaseRequest,
         created_by: Uuid,
     ) -> Result<CreatePurchaseOrderResponse, DomainError> {
+        let order_currency = self
+            .currency_service
+            .order_currency(request.currency.as_deref())
+            .await?;
+
+        let quantities: Vec<_> = request
+            .lines
+            .iter()
+            .map(|line| (line.item_id, line.qty_ordered))
+            .collect();
+        self.item_repository.check_quantities(&quantities).await?;
+
         // Create the purchase order
-        let po = PurchaseOrder::new(
+        let mut po = PurchaseOrder::new(
             request.supplier_id,
             request.lines,
             request.expected_date,
             created_by,
         )?;
+        po.set_currency(order_currency);
 
         // Save to repository
         self.purchase_order_repository.save(&po).await?;
@@ -80,8 +113,11 @@ impl<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> CreatePurchaseO
                         crate::domain::entities::purchase_order::PurchaseOrderStatus::PartialReceived => "PARTIAL_RECEIVED",
                         crate::domain::entities::purchase_order::PurchaseOrderStatus::Received => "RECEIVED",
                         crate::domain::entities::purchase_order::PurchaseOrderStatus::Cancelled => "CANCELLED",
+                        crate::domain::entities::purchase_order::PurchaseOrderStatus::Closed => "CLOSED",
                     },
                     "total_amount": po.total_amount,
+                    "currency": po.currency,
+                    "exchange_rate": po.exchange_rate,
                     "expected_date": po.expected_date,
                     "created_at": po.created_at,
                     "lines": po.lines.iter().map(|line| json!({
@@ -128,8 +164,13 @@ impl<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> CreatePurchaseO
                 crate::domain::entities::purchase_order::PurchaseOrderStatus::Cancelled => {
                     "CANCELLED".to_string()
                 }
+                crate::domain::entities::purchase_order::PurchaseOrderStatus::Closed => {
+                    "CLOSED".to_string()
+                }
             },
             total_amount: po.total_amount,
+            currency: po.currency,
+            exchange_rate: po.exchange_rate,
             lines: po
                 .lines
                 .into_iter()
@@ -140,6 +181,7 @@ impl<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> CreatePurchaseO
                     qty_received: line.qty_received,
                     unit_cost: line.unit_cost,
                     line_total: line.line_total,
+                    expected_date: line.expected_date,
                 })
                 .collect(),
             created_at: po.created_at,
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::application::use_cases::create_purchase_order::{
+    CreatePurchaseOrderResponse, CreatePurchaseOrderUseCase, CreatePurchaseOrderUseCaseRequest,
+};
+use crate::application::use_cases::get_reorder_suggestions::GetReorderSuggestionsUseCase;
+use crate::domain::entities::forecast::ForecastQuery;
+use crate::domain::entities::purchase_order::CreatePurchaseOrderLine;
+use crate::domain::entities::replenishment::ReorderSuggestion;
+use crate::domain::services::{
+    item_repository::ItemRepository, purchase_order_repository::PurchaseOrderRepository,
+    stock_repository::StockRepository, webhook_dispatcher::WebhookDispatcher,
+};
+use crate::shared::error::DomainError;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CreateReorderPurchaseOrdersRequest {
+    /// Only draft a PO for this supplier
+    pub supplier_id: Option<Uuid>,
+    pub expected_date: Option<DateTime<Utc>>,
+    /// Reorder at forecast demand rather than each item's fixed reorder point
+    #[serde(default)]
+    pub dynamic_reorder_points: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateReorderPurchaseOrdersResponse {
+    pub purchase_orders: Vec<CreatePurchaseOrderResponse>,
+    /// Suggestions left out because the item has no `metadata.supplier_id`
+    pub unassigned: Vec<ReorderSuggestion>,
+}
+
+/// Turns the current reorder suggestions into one draft purchase order per supplier
+pub struct CreateReorderPurchaseOrdersUseCase<
+    T: ItemRepository,
+    S: StockRepository,
+    P: PurchaseOrderRepository,
+    D: WebhookDispatcher + 'static,
+> {
+    get_reorder_suggestions_use_case: Arc<GetReorderSuggestionsUseCase<T, S>>,
+    create_purchase_order_use_case: Arc<CreatePurchaseOrderUseCase<P, D>>,
+}
+
+impl<T, S, P, D> CreateReorderPurchaseOrdersUseCase<T, S, P, D>
+where
+    T: ItemRepository,
+    S: StockRepository,
+    P: PurchaseOrderRepository,
+    D: WebhookDispatcher + 'static,
+{
+    pub fn new(
+        get_reorder_suggestions_use_case: Arc<GetReorderSuggestionsUseCase<T, S>>,
+        create_purchase_order_use_case: Arc<CreatePurchaseOrderUseCase<P, D>>,
+    ) -> Self {
+        Self {
+            get_reorder_suggestions_use_case,
+            create_purchase_order_use_case,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        request: CreateReorderPurchaseOrdersRequest,
+        created_by: Uuid,
+    ) -> Result<CreateReorderPurchaseOrdersResponse, DomainError> {
+        let suggestions = self
+            .get_reorder_suggestions_use_case
+            .execute(
+                request.supplier_id,
+                request
+                    .dynamic_reorder_points
+                    .then(ForecastQuery::default)
+                    .as_ref(),
+            )
+            .await?;
+
+        let mut purchase_orders = Vec::new();
+        let mut unassigned = Vec::new();
+        for group in suggestions.suppliers {
+            let Some(supplier_id) = group.supplier_id else {
+                unassigned.extend(group.lines);
+                continue;
+            };
+
+            let lines = group
+                .lines
+                .iter()
+                .map(|suggestion| CreatePurchaseOrderLine {
+                    item_id: suggestion.item_id,
+                    qty_ordered: suggestion.suggested_qty,
+                    unit_cost: suggestion.unit_cost,
+                    expected_date: None,
+                })
+                .collect();
+            let purchase_order = self
+                .create_purchase_order_use_case
+                .execute(
+                    CreatePurchaseOrderUseCaseRequest {
+                        supplier_id,
+                        expected_date: request.expected_date,
+                        lines,
+                        currency: None,
+                    },
+                    created_by,
+                )
+                .await?;
+            purchase_orders.push(purchase_order);
+        }
+
+        Ok(CreateReorderPurchaseOrdersResponse {
+            purchase_orders,
+            unassigned,
+        })
+    }
+}
@@ -3,6 +3,7 @@ use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
 use crate::domain::services::return_repository::ReturnRepository;
 use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
 use serde::Serialize;
 use serde_json::json;
 use std::sync::Arc;
@@ -33,9 +34,10 @@ impl<R: ReturnRepository, D: WebhookDispatcher + 'static> CreateReturnUseCase<R,
     ) -> Result<CreateReturnResponse, DomainError> {
         // Validate request
         if request.lines.is_empty() {
-            return Err(DomainError::ValidationError(
-                "Return must have at least one line".to_string(),
-            ));
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.return_needs_line",
+                &[],
+            )));
         }
 
         // Generate return number (in a real app, this might come from a sequence)
@@ -60,11 +62,13 @@ impl<R: ReturnRepository, D: WebhookDispatcher + 'static> CreateReturnUseCase<R,
                 line_req.quantity,
                 line_req.unit_price,
                 line_req.reason,
-            )?;
+            )?
+            .with_sales_order_line(line_req.sales_order_id, line_req.so_line_id)?;
             return_entity.add_line(line)?;
         }
 
-        // Create in repository
+        // Create in repository, which checks lines naming a sales order line
+        // against what it shipped
         self.return_repository.create(&return_entity).await?;
 
         // Dispatch webhook event (non-blocking)
@@ -91,7 +95,9 @@ impl<R: ReturnRepository, D: WebhookDispatcher + 'static> CreateReturnUseCase<R,
                         "quantity": line.quantity,
                         "quantity_received": line.quantity_received,
                         "unit_price": line.unit_price,
-                        "reason": line.reason
+                        "reason": line.reason,
+                        "sales_order_id": line.sales_order_id,
+                        "so_line_id": line.so_line_id
                     })).collect::<Vec<_>>()
                 }
             }),
@@ -1,8 +1,18 @@
+use crate::domain::entities::credit::{
+    CreditEnforcement, CreditLimitExceeded, CustomerCreditStatus,
+};
 use crate::domain::entities::sales_order::{SalesOrder, SalesOrderLine};
+use crate::domain::entities::user::UserRole;
 use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::currency_service::CurrencyService;
+use crate::domain::services::customer_credit_repository::CustomerCreditRepository;
+use crate::domain::services::item_repository::ItemRepository;
 use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::domain::services::user_repository::UserRepository;
 use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
@@ -14,30 +24,54 @@ pub struct CreateSalesOrderRequest {
     pub lines: Vec<CreateSalesOrderLineRequest>,
     pub should_reserve: Option<bool>,
     pub fulfillment_location_id: Option<Uuid>,
+    /// ISO 4217 code the customer is billed in; defaults to the tenant's base currency
+    pub currency: Option<String>,
+    /// Confirm the order even though it takes the customer past a blocking
+    /// credit limit. Only tenant admins may.
+    #[serde(default)]
+    pub override_credit_limit: bool,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct CreateSalesOrderLineRequest {
     pub item_id: Uuid,
-    pub qty: i32,
-    pub unit_price: f64,
+    pub qty: Decimal,
+    pub unit_price: Decimal,
 }
 
 #[derive(Debug, Serialize)]
 pub struct CreateSalesOrderResponse {
     pub sales_order: SalesOrder,
     pub stock_movements: Option<Vec<crate::domain::entities::sales_order::StockMovement>>,
+    /// Set when the order went through past the customer's credit limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credit_limit_exceeded: Option<CreditLimitExceeded>,
 }
 
 pub struct CreateSalesOrderUseCase<T: SalesOrderRepository, D: WebhookDispatcher + 'static> {
     sales_order_repo: Arc<T>,
+    currency_service: Arc<dyn CurrencyService>,
+    item_repository: Arc<dyn ItemRepository>,
+    credit_repository: Arc<dyn CustomerCreditRepository>,
+    user_repository: Arc<dyn UserRepository>,
     webhook_dispatcher: Arc<D>,
 }
 
 impl<T: SalesOrderRepository, D: WebhookDispatcher + 'static> CreateSalesOrderUseCase<T, D> {
-    pub fn new(sales_order_repo: Arc<T>, webhook_dispatcher: Arc<D>) -> Self {
+    pub fn new(
+        sales_order_repo: Arc<T>,
+        currency_service: Arc<dyn CurrencyService>,
+        item_repository: Arc<dyn ItemRepository>,
+        credit_repository: Arc<dyn CustomerCreditRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        webhook_dispatcher: Arc<D>,
+    ) -> Self {
         Self {
             sales_order_repo,
+            currency_service,
+            item_repository,
+            credit_repository,
+            user_repository,
             webhook_dispatcher,
         }
     }
@@ -49,11 +83,19 @@ impl<T: SalesOrderRepository, D: WebhookDispatcher + 'static> CreateSalesOrderUs
     ) -> Result<CreateSalesOrderResponse, DomainError> {
         // Validate request
         if request.lines.is_empty() {
-            return Err(DomainError::ValidationError(
-                "Sales order must have at least one line".to_string(),
-            ));
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.sales_order_needs_line",
+                &[],
+            )));
         }
 
+        let quantities: Vec<_> = request
+            .lines
+            .iter()
+            .map(|line| (line.item_id, line.qty))
+            .collect();
+        self.item_repository.check_quantities(&quantities).await?;
+
         // Generate SO number (in a real app, this might come from a sequence)
         let so_number = format!("SO-{}", Uuid::new_v4().simple());
 
@@ -64,6 +106,11 @@ impl<T: SalesOrderRepository, D: WebhookDispatcher + 'static> CreateSalesOrderUs
             request.fulfillment_location_id,
             created_by,
         )?;
+        sales_order.set_currency(
+            self.currency_service
+                .order_currency(request.currency.as_deref())
+                .await?,
+        );
 
         // Add lines
         for line_req in request.lines {
@@ -71,6 +118,10 @@ impl<T: SalesOrderRepository, D: WebhookDispatcher + 'static> CreateSalesOrderUs
             sales_order.add_line(line)?;
         }
 
+        let credit_limit_exceeded = self
+            .check_credit(&sales_order, request.override_credit_limit, created_by)
+            .await?;
+
         // Confirm the order (moves from Draft to Confirmed)
         sales_order.confirm()?;
 
@@ -79,11 +130,19 @@ impl<T: SalesOrderRepository, D: WebhookDispatcher + 'static> CreateSalesOrderUs
 
         // Handle reservation if requested
         let stock_movements = if request.should_reserve.unwrap_or(true) {
-            Some(
-                self.sales_order_repo
-                    .reserve_inventory(sales_order.id, created_by)
-                    .await?,
-            )
+            match self
+                .sales_order_repo
+                .reserve_inventory(sales_order.id, created_by)
+                .await
+            {
+                Ok(movements) => Some(movements),
+                Err(e @ DomainError::BusinessLogicError(_)) => {
+                    // Not enough stock to promise; don't leave an unreserved order behind
+                    self.sales_order_repo.delete(sales_order.id).await?;
+                    return Err(e);
+                }
+                Err(e) => return Err(e),
+            }
         } else {
             None
         };
@@ -100,12 +159,15 @@ impl<T: SalesOrderRepository, D: WebhookDispatcher + 'static> CreateSalesOrderUs
                         crate::domain::entities::sales_order::SalesOrderStatus::Draft => "DRAFT",
                         crate::domain::entities::sales_order::SalesOrderStatus::Confirmed => "CONFIRMED",
                         crate::domain::entities::sales_order::SalesOrderStatus::Picking => "PICKING",
+                        crate::domain::entities::sales_order::SalesOrderStatus::PartiallyShipped => "PARTIALLY_SHIPPED",
                         crate::domain::entities::sales_order::SalesOrderStatus::Shipped => "SHIPPED",
                         crate::domain::entities::sales_order::SalesOrderStatus::Invoiced => "INVOICED",
                         crate::domain::entities::sales_order::SalesOrderStatus::Cancelled => "CANCELLED",
                         crate::domain::entities::sales_order::SalesOrderStatus::Returned => "RETURNED",
                     },
                     "total_amount": sales_order.total_amount,
+                    "currency": sales_order.currency,
+                    "exchange_rate": sales_order.exchange_rate,
                     "fulfillment_location_id": sales_order.fulfillment_location_id,
                     "created_at": sales_order.created_at,
                     "lines": sales_order.lines.iter().map(|line| json!({
@@ -131,6 +193,57 @@ impl<T: SalesOrderRepository, D: WebhookDispatcher + 'static> CreateSalesOrderUs
         Ok(CreateSalesOrderResponse {
             sales_order,
             stock_movements,
+            credit_limit_exceeded,
         })
     }
+
+    /// Hold the order to its customer's credit limit: what it owes on unpaid
+    /// invoices plus this order may not pass it. Under `Block` only an admin's
+    /// override lets the order through; under `Warn` it always goes through.
+    async fn check_credit(
+        &self,
+        sales_order: &SalesOrder,
+        override_credit_limit: bool,
+        actor_id: Uuid,
+    ) -> Result<Option<CreditLimitExceeded>, DomainError> {
+        let Some(customer_id) = sales_order.customer_id else {
+            return Ok(None);
+        };
+        let Some(credit_limit) = self.credit_repository.find_limit(customer_id).await? else {
+            return Ok(None);
+        };
+        let status = CustomerCreditStatus::new(
+            customer_id,
+            Some(credit_limit),
+            self.credit_repository
+                .outstanding_balance(customer_id)
+                .await?,
+        );
+        let Some(mut exceeded) =
+            CreditLimitExceeded::check(&status, sales_order.base_total_amount()?)
+        else {
+            return Ok(None);
+        };
+
+        if self.credit_repository.find_enforcement().await? == CreditEnforcement::Warn {
+            return Ok(Some(exceeded));
+        }
+        if !override_credit_limit {
+            return Err(DomainError::BusinessLogicError(
+                format!("{}; an admin may override the limit", exceeded.message()).into(),
+            ));
+        }
+        let is_admin = self
+            .user_repository
+            .find_by_id(actor_id)
+            .await?
+            .is_some_and(|user| user.role == UserRole::Admin && user.active);
+        if !is_admin {
+            return Err(DomainError::Forbidden(
+                "Only tenant admins may override a customer's credit limit".into(),
+            ));
+        }
+        exceeded.overridden = true;
+        Ok(Some(exceeded))
+    }
 }
@@ -1,211 +1,228 @@
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::application::use_cases::{
-    create_item::{CreateItemRequest, CreateItemUseCase},
-    create_location::{CreateLocationRequest, CreateLocationUseCase},
+use crate::domain::entities::sandbox_seed::{
+    echo_webhook, SeedDataset, SeedProfile, SeedRecord, SeedSummary, DEFAULT_SEED,
 };
-use crate::domain::entities::tenant::{Tenant, TenantType};
-use crate::domain::services::item_repository::ItemRepository;
-use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::entities::tenant::{CreateSandboxTenantRequest, Tenant};
+use crate::domain::services::sandbox_seed_repository::SandboxSeedRepository;
 use crate::domain::services::tenant_repository::TenantRepository;
 use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::with_tenant;
+
+/// A freshly seeded sandbox
+#[derive(Debug, Clone)]
+pub struct SandboxTenant {
+    pub tenant: Tenant,
+    pub seeded: SeedSummary,
+    pub echo_url: String,
+    pub webhook_secret: String,
+}
+
+/// The data a sandbox is about to be seeded with
+pub(crate) struct SeedPlan {
+    pub dataset: SeedDataset,
+    pub record: SeedRecord,
+    pub echo_url: String,
+    pub webhook_secret: String,
+}
 
-#[derive(Clone)]
-pub struct CreateSandboxTenantUseCase<T, I, L>
+impl SeedPlan {
+    /// Generate the dataset the request asks for, plus a webhook delivering to
+    /// the sandbox's own path under `echo_base_url`
+    pub(crate) fn new(
+        request: &CreateSandboxTenantRequest,
+        default_profile: &str,
+        echo_base_url: &str,
+        tenant_id: Uuid,
+        created_by: Uuid,
+    ) -> Result<Self, DomainError> {
+        let profile = SeedProfile::named(request.profile.as_deref().unwrap_or(default_profile))?
+            .with_overrides(&request.overrides)?;
+        let seed = request.overrides.seed.unwrap_or(DEFAULT_SEED);
+
+        let echo_url = format!("{}/{}", echo_base_url.trim_end_matches('/'), tenant_id);
+        let webhook = echo_webhook(echo_url.clone(), created_by)?;
+        let webhook_secret = webhook.secret.clone();
+        let now = chrono::Utc::now();
+        let mut dataset = SeedDataset::generate(&profile, seed, tenant_id, created_by, now);
+        dataset.webhooks.push(webhook);
+        let record = SeedRecord {
+            overrides: request.overrides.clone(),
+            summary: dataset.summary(&profile),
+            seeded_at: now,
+        };
+        Ok(Self {
+            dataset,
+            record,
+            echo_url,
+            webhook_secret,
+        })
+    }
+}
+
+pub struct CreateSandboxTenantUseCase<T, S>
 where
     T: TenantRepository,
-    I: ItemRepository,
-    L: LocationRepository,
+    S: SandboxSeedRepository,
 {
     tenant_repository: Arc<T>,
-    create_item_use_case: CreateItemUseCase<I>,
-    create_location_use_case: CreateLocationUseCase<L>,
+    seed_repository: Arc<S>,
+    default_profile: String,
+    /// Base URL of the echo receiver; each sandbox's webhook gets `/{tenant_id}` appended
+    echo_url: String,
 }
 
-impl<T, I, L> CreateSandboxTenantUseCase<T, I, L>
+impl<T, S> CreateSandboxTenantUseCase<T, S>
 where
     T: TenantRepository,
-    I: ItemRepository,
-    L: LocationRepository,
+    S: SandboxSeedRepository,
 {
     pub fn new(
         tenant_repository: Arc<T>,
-        create_item_use_case: CreateItemUseCase<I>,
-        create_location_use_case: CreateLocationUseCase<L>,
+        seed_repository: Arc<S>,
+        default_profile: String,
+        echo_url: String,
     ) -> Self {
         Self {
             tenant_repository,
-            create_item_use_case,
-            create_location_use_case,
+            seed_repository,
+            default_profile,
+            echo_url,
         }
     }
 
-    pub async fn execute(&self, created_by: Option<Uuid>) -> Result<Tenant, DomainError> {
-        // Create the sandbox tenant
-        let tenant = Tenant::new_sandbox(created_by);
+    /// Create a sandbox and seed it with a demo dataset: a catalogue stocked
+    /// across several locations, order and movement history, open orders, and a
+    /// webhook delivering to the echo receiver
+    pub async fn execute(
+        &self,
+        request: CreateSandboxTenantRequest,
+        created_by: Uuid,
+    ) -> Result<SandboxTenant, DomainError> {
+        // Plan before creating the tenant, so an invalid request leaves nothing behind
+        let mut tenant = Tenant::new_sandbox(Some(created_by));
+        let plan = SeedPlan::new(
+            &request,
+            &self.default_profile,
+            &self.echo_url,
+            tenant.id,
+            created_by,
+        )?;
         self.tenant_repository.create_tenant(&tenant).await?;
-
-        // Populate with sample data
-        self.populate_sample_data(&tenant).await?;
+        with_tenant(
+            tenant.id,
+            self.seed_repository.seed(&plan.dataset, &plan.record),
+        )
+        .await?;
 
         // Mark tenant as active after sample data is loaded
         self.tenant_repository
             .update_tenant_status(tenant.id, "ACTIVE")
             .await?;
-
-        Ok(tenant)
-    }
-
-    async fn populate_sample_data(&self, _tenant: &Tenant) -> Result<(), DomainError> {
-        use crate::domain::entities::location::LocationAddress;
-
-        // Create sample locations
-        let warehouse_request = CreateLocationRequest {
-            name: "Main Warehouse".to_string(),
-            code: Some("WH-001".to_string()),
-            address: Some(LocationAddress {
-                line1: Some("123 Industrial Ave".to_string()),
-                line2: None,
-                city: Some("Industrial City".to_string()),
-                region: Some("IC".to_string()),
-                postal_code: Some("12345".to_string()),
-                country: Some("USA".to_string()),
-            }),
-            r#type: Some("warehouse".to_string()),
-        };
-        let _warehouse_location = self
-            .create_location_use_case
-            .execute(warehouse_request)
-            .await?;
-
-        let retail_request = CreateLocationRequest {
-            name: "Retail Store".to_string(),
-            code: Some("RT-001".to_string()),
-            address: Some(LocationAddress {
-                line1: Some("456 Commerce St".to_string()),
-                line2: None,
-                city: Some("Commerce City".to_string()),
-                region: Some("CC".to_string()),
-                postal_code: Some("67890".to_string()),
-                country: Some("USA".to_string()),
-            }),
-            r#type: Some("store".to_string()),
-        };
-        let _retail_store = self
-            .create_location_use_case
-            .execute(retail_request)
-            .await?;
-
-        // Create sample items
-        let laptop_request = CreateItemRequest {
-            sku: "LPT-001".to_string(),
-            name: "Gaming Laptop".to_string(),
-            description: Some("High-performance gaming laptop".to_string()),
-            category: Some("Electronics".to_string()),
-            unit: "each".to_string(),
-            barcode: Some("123456789012".to_string()),
-            cost_price: 899.99,
-            sale_price: Some(1299.99),
-            reorder_point: Some(5),
-            reorder_qty: Some(10),
-            weight: Some(2.5),
-            dimensions: None,
-            metadata: None,
-        };
-        let _laptop = self
-            .create_item_use_case
-            .execute(laptop_request, _tenant.id)
-            .await?;
-
-        let mouse_request = CreateItemRequest {
-            sku: "MSE-001".to_string(),
-            name: "Wireless Mouse".to_string(),
-            description: Some("Ergonomic wireless mouse".to_string()),
-            category: Some("Electronics".to_string()),
-            unit: "each".to_string(),
-            barcode: Some("123456789013".to_string()),
-            cost_price: 29.99,
-            sale_price: Some(49.99),
-            reorder_point: Some(20),
-            reorder_qty: Some(50),
-            weight: Some(0.1),
-            dimensions: None,
-            metadata: None,
-        };
-        let _mouse = self
-            .create_item_use_case
-            .execute(mouse_request, _tenant.id)
-            .await?;
-
-        let keyboard_request = CreateItemRequest {
-            sku: "KBD-001".to_string(),
-            name: "Mechanical Keyboard".to_string(),
-            description: Some("RGB mechanical gaming keyboard".to_string()),
-            category: Some("Electronics".to_string()),
-            unit: "each".to_string(),
-            barcode: Some("123456789014".to_string()),
-            cost_price: 79.99,
-            sale_price: Some(129.99),
-            reorder_point: Some(10),
-            reorder_qty: Some(25),
-            weight: Some(0.8),
-            dimensions: None,
-            metadata: None,
-        };
-        let _keyboard = self
-            .create_item_use_case
-            .execute(keyboard_request, _tenant.id)
-            .await?;
-
-        let tshirt_request = CreateItemRequest {
-            sku: "TSH-001".to_string(),
-            name: "Cotton T-Shirt".to_string(),
-            description: Some("Comfortable cotton t-shirt".to_string()),
-            category: Some("Apparel".to_string()),
-            unit: "each".to_string(),
-            barcode: Some("123456789015".to_string()),
-            cost_price: 9.99,
-            sale_price: Some(19.99),
-            reorder_point: Some(50),
-            reorder_qty: Some(100),
-            weight: Some(0.2),
-            dimensions: None,
-            metadata: None,
-        };
-        let _tshirt = self
-            .create_item_use_case
-            .execute(tshirt_request, _tenant.id)
-            .await?;
-
-        // TODO: Create initial stock levels for these items
-        // This would require a create_stock_adjustment use case
-
-        Ok(())
+        tenant.mark_active();
+
+        Ok(SandboxTenant {
+            tenant,
+            seeded: plan.record.summary,
+            echo_url: plan.echo_url,
+            webhook_secret: plan.webhook_secret,
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::domain::entities::sandbox_seed::SeedOverrides;
+    use crate::domain::entities::tenant::{TenantStatus, TenantType};
     use crate::domain::services::tenant_repository::MockTenantRepository;
+    use crate::shared::tenant_scope::current_tenant;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingSeeder(Mutex<Vec<(Option<Uuid>, usize, Vec<String>)>>);
+
+    #[async_trait]
+    impl SandboxSeedRepository for RecordingSeeder {
+        async fn seed(&self, dataset: &SeedDataset, _: &SeedRecord) -> Result<(), DomainError> {
+            let urls = dataset.webhooks.iter().map(|w| w.url.clone()).collect();
+            self.0
+                .lock()
+                .unwrap()
+                .push((current_tenant(), dataset.items.len(), urls));
+            Ok(())
+        }
+
+        async fn reseed(&self, _: &SeedDataset, _: &SeedRecord) -> Result<(), DomainError> {
+            unreachable!("creating a sandbox never reseeds")
+        }
+
+        async fn find_record(&self) -> Result<Option<SeedRecord>, DomainError> {
+            Ok(None)
+        }
+
+        async fn clear_transactions(&self, _: &str) -> Result<(), DomainError> {
+            unreachable!("creating a sandbox never clears transactions")
+        }
+    }
 
     #[tokio::test]
     async fn test_create_sandbox_tenant_success() {
         let mut mock_repo = MockTenantRepository::new();
         mock_repo.expect_create_tenant().returning(|_| Ok(()));
+        mock_repo
+            .expect_update_tenant_status()
+            .withf(|_, status| status == "ACTIVE")
+            .returning(|_, _| Ok(()));
+        let seeder = Arc::new(RecordingSeeder::default());
+        let use_case = CreateSandboxTenantUseCase::new(
+            Arc::new(mock_repo),
+            Arc::clone(&seeder),
+            "demo".to_string(),
+            "http://127.0.0.1:8080/sandbox/echo/".to_string(),
+        );
+
+        let request = CreateSandboxTenantRequest {
+            profile: Some("minimal".to_string()),
+            overrides: SeedOverrides {
+                items: Some(10),
+                ..Default::default()
+            },
+        };
+        let sandbox = use_case.execute(request, Uuid::new_v4()).await.unwrap();
+
+        assert_eq!(sandbox.tenant.tenant_type, TenantType::Sandbox);
+        assert_eq!(sandbox.tenant.status, TenantStatus::Active);
+        assert_eq!(sandbox.seeded.profile, "minimal");
+        assert_eq!(sandbox.seeded.items, 10);
+        assert!(sandbox.webhook_secret.starts_with("whsec_"));
+        let echo_url = format!("http://127.0.0.1:8080/sandbox/echo/{}", sandbox.tenant.id);
+        assert_eq!(sandbox.echo_url, echo_url);
+        // Seeding runs scoped to the new tenant
+        assert_eq!(
+            *seeder.0.lock().unwrap(),
+            vec![(Some(sandbox.tenant.id), 10, vec![echo_url])]
+        );
+    }
 
-        // TODO: Add mock use cases for items and locations
-        // let create_item_use_case = CreateItemUseCase::new(mock_repo.clone());
-        // let create_location_use_case = CreateLocationUseCase::new(mock_repo.clone());
-
-        // let use_case = CreateSandboxTenantUseCase::new(
-        //     mock_repo,
-        //     create_item_use_case,
-        //     create_location_use_case,
-        // );
-
-        // For now, just test tenant creation
-        // let result = use_case.execute(None).await;
-        // assert!(result.is_ok());
+    #[tokio::test]
+    async fn test_rejects_unknown_profile_before_creating_tenant() {
+        let mock_repo = MockTenantRepository::new();
+        let use_case = CreateSandboxTenantUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(RecordingSeeder::default()),
+            "demo".to_string(),
+            "http://127.0.0.1:8080/sandbox/echo".to_string(),
+        );
+        let request = CreateSandboxTenantRequest {
+            profile: Some("huge".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            use_case.execute(request, Uuid::new_v4()).await,
+            Err(DomainError::ValidationError(_))
+        ));
     }
 }
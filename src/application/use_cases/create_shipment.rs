@@ -0,0 +1,70 @@
+use crate::domain::entities::shipment::{Shipment, ShipmentDetails, ShipmentSourceType};
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::shipment_repository::ShipmentRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Records the shipment produced when a sales order or transfer ships
+pub struct CreateShipmentUseCase<S: ShipmentRepository, D: WebhookDispatcher + 'static> {
+    shipment_repository: Arc<S>,
+    webhook_dispatcher: Arc<D>,
+}
+
+impl<S: ShipmentRepository, D: WebhookDispatcher + 'static> CreateShipmentUseCase<S, D> {
+    pub fn new(shipment_repository: Arc<S>, webhook_dispatcher: Arc<D>) -> Self {
+        Self {
+            shipment_repository,
+            webhook_dispatcher,
+        }
+    }
+
+    /// Validate shipment details up front so a bad package doesn't fail after stock has moved
+    pub fn build(
+        &self,
+        source_type: ShipmentSourceType,
+        source_id: Uuid,
+        details: ShipmentDetails,
+        created_by: Uuid,
+    ) -> Result<Shipment, DomainError> {
+        // Generate shipment number (in a real app, this might come from a sequence)
+        let shipment_number = format!("SH-{}", Uuid::new_v4().simple());
+
+        let mut shipment = Shipment::new(
+            shipment_number,
+            source_type,
+            source_id,
+            details.carrier,
+            details.tracking_number,
+            details.shipped_at,
+            created_by,
+        )?;
+
+        for package in details.packages.unwrap_or_default() {
+            shipment.add_package(package)?;
+        }
+
+        Ok(shipment)
+    }
+
+    pub async fn execute(&self, shipment: Shipment) -> Result<Shipment, DomainError> {
+        self.shipment_repository.create(&shipment).await?;
+
+        // Dispatch webhook event (non-blocking)
+        let webhook_event = WebhookEvent::new(
+            WebhookEventType::ShipmentCreated,
+            json!({ "shipment": shipment }),
+        );
+
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch shipment created webhook: {:?}", e);
+            }
+        });
+
+        Ok(shipment)
+    }
+}
@@ -48,7 +48,7 @@ mod tests {
         let mut mock_repo = MockTenantRepository::new();
         mock_repo.expect_create_tenant().returning(|_| Ok(()));
 
-        let use_case = CreateTenantUseCase::new(mock_repo);
+        let use_case = CreateTenantUseCase::new(Arc::new(mock_repo));
         let result = use_case
             .execute(
                 "Test Tenant".to_string(),
@@ -1,8 +1,10 @@
 use crate::domain::entities::transfer::{CreateTransferRequest, Transfer, TransferLine};
 use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::item_repository::ItemRepository;
 use crate::domain::services::transfer_repository::TransferRepository;
 use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -16,13 +18,19 @@ pub struct CreateTransferResponse {
 
 pub struct CreateTransferUseCase<T: TransferRepository, D: WebhookDispatcher + 'static> {
     transfer_repo: Arc<T>,
+    item_repository: Arc<dyn ItemRepository>,
     webhook_dispatcher: Arc<D>,
 }
 
 impl<T: TransferRepository, D: WebhookDispatcher + 'static> CreateTransferUseCase<T, D> {
-    pub fn new(transfer_repo: Arc<T>, webhook_dispatcher: Arc<D>) -> Self {
+    pub fn new(
+        transfer_repo: Arc<T>,
+        item_repository: Arc<dyn ItemRepository>,
+        webhook_dispatcher: Arc<D>,
+    ) -> Self {
         Self {
             transfer_repo,
+            item_repository,
             webhook_dispatcher,
         }
     }
@@ -34,11 +42,19 @@ impl<T: TransferRepository, D: WebhookDispatcher + 'static> CreateTransferUseCas
     ) -> Result<CreateTransferResponse, DomainError> {
         // Validate request
         if request.lines.is_empty() {
-            return Err(DomainError::ValidationError(
-                "Transfer must have at least one line".to_string(),
-            ));
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.transfer_needs_line",
+                &[],
+            )));
         }
 
+        let quantities: Vec<_> = request
+            .lines
+            .iter()
+            .map(|line| (line.item_id, line.quantity))
+            .collect();
+        self.item_repository.check_quantities(&quantities).await?;
+
         // Generate transfer number (in a real app, this might come from a sequence)
         let transfer_number = format!("TR-{}", Uuid::new_v4().simple());
 
@@ -74,13 +90,7 @@ impl<T: TransferRepository, D: WebhookDispatcher + 'static> CreateTransferUseCas
                     "transfer_number": transfer.transfer_number,
                     "from_location_id": transfer.from_location_id,
                     "to_location_id": transfer.to_location_id,
-                    "status": match transfer.status {
-                        crate::domain::entities::transfer::TransferStatus::Draft => "DRAFT",
-                        crate::domain::entities::transfer::TransferStatus::Open => "OPEN",
-                        crate::domain::entities::transfer::TransferStatus::InTransit => "IN_TRANSIT",
-                        crate::domain::entities::transfer::TransferStatus::Received => "RECEIVED",
-                        crate::domain::entities::transfer::TransferStatus::Cancelled => "CANCELLED",
-                    },
+                    "status": transfer.status.as_str(),
                     "notes": transfer.notes,
                     "created_at": transfer.created_at,
                     "lines": transfer.lines.iter().map(|line| json!({
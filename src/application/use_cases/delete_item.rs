@@ -1,6 +1,10 @@
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
 use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -16,13 +20,17 @@ pub struct DeleteItemResponse {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-pub struct DeleteItemUseCase<R: ItemRepository> {
+pub struct DeleteItemUseCase<R: ItemRepository + ?Sized, D: WebhookDispatcher + ?Sized + 'static> {
     item_repository: Arc<R>,
+    webhook_dispatcher: Arc<D>,
 }
 
-impl<R: ItemRepository> DeleteItemUseCase<R> {
-    pub fn new(item_repository: Arc<R>) -> Self {
-        Self { item_repository }
+impl<R: ItemRepository + ?Sized, D: WebhookDispatcher + ?Sized + 'static> DeleteItemUseCase<R, D> {
+    pub fn new(item_repository: Arc<R>, webhook_dispatcher: Arc<D>) -> Self {
+        Self {
+            item_repository,
+            webhook_dispatcher,
+        }
     }
 
     pub async fn execute(
@@ -35,15 +43,17 @@ impl<R: ItemRepository> DeleteItemUseCase<R> {
             .find_by_id(request.id)
             .await?
             .ok_or_else(|| {
-                DomainError::ValidationError(format!("Item with ID {} not found", request.id))
+                DomainError::NotFound(Message::localized(
+                    "error.item_not_found",
+                    &[("id", &request.id)],
+                ))
             })?;
 
         // Check if item is already inactive
         if !item.is_active() {
-            return Err(DomainError::ValidationError(format!(
-                "Item with ID {} is already deleted",
-                request.id
-            )));
+            return Err(DomainError::ValidationError(
+                format!("Item with ID {} is already deleted", request.id).into(),
+            ));
         }
 
         // Soft delete by deactivating the item
@@ -52,6 +62,18 @@ impl<R: ItemRepository> DeleteItemUseCase<R> {
         // Save to repository
         self.item_repository.update(&item).await?;
 
+        // Dispatch webhook event (non-blocking)
+        let webhook_event = WebhookEvent::new(
+            WebhookEventType::ItemDeleted,
+            json!({ "item": { "id": item.id, "sku": item.sku, "deleted_at": item.updated_at } }),
+        );
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch item deleted webhook: {:?}", e);
+            }
+        });
+
         // Return response
         Ok(DeleteItemResponse {
             id: item.id,
@@ -1,6 +1,9 @@
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
 use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -16,14 +19,21 @@ pub struct DeleteLocationResponse {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-pub struct DeleteLocationUseCase<R: LocationRepository> {
+pub struct DeleteLocationUseCase<
+    R: LocationRepository + ?Sized,
+    D: WebhookDispatcher + ?Sized + 'static,
+> {
     location_repository: Arc<R>,
+    webhook_dispatcher: Arc<D>,
 }
 
-impl<R: LocationRepository> DeleteLocationUseCase<R> {
-    pub fn new(location_repository: Arc<R>) -> Self {
+impl<R: LocationRepository + ?Sized, D: WebhookDispatcher + ?Sized + 'static>
+    DeleteLocationUseCase<R, D>
+{
+    pub fn new(location_repository: Arc<R>, webhook_dispatcher: Arc<D>) -> Self {
         Self {
             location_repository,
+            webhook_dispatcher,
         }
     }
 
@@ -37,7 +47,7 @@ impl<R: LocationRepository> DeleteLocationUseCase<R> {
             .find_by_id(request.id)
             .await?
             .ok_or_else(|| {
-                DomainError::NotFound(format!("Location with id {} not found", request.id))
+                DomainError::NotFound(format!("Location with id {} not found", request.id).into())
             })?;
 
         // Soft delete by deactivating
@@ -46,6 +56,18 @@ impl<R: LocationRepository> DeleteLocationUseCase<R> {
         // Update in repository
         self.location_repository.update(&location).await?;
 
+        // Dispatch webhook event (non-blocking)
+        let webhook_event = WebhookEvent::new(
+            WebhookEventType::LocationDeleted,
+            json!({ "location": { "id": location.id, "code": location.code, "deleted_at": location.updated_at } }),
+        );
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch location deleted webhook: {:?}", e);
+            }
+        });
+
         Ok(DeleteLocationResponse {
             id: location.id,
             active: location.active,
@@ -19,10 +19,9 @@ impl<T: TenantRepository> DeleteTenantUseCase<T> {
         // First check if tenant exists
         let tenant = self.tenant_repository.get_tenant(tenant_id).await?;
         if tenant.is_none() {
-            return Err(DomainError::NotFound(format!(
-                "Tenant {} not found",
-                tenant_id
-            )));
+            return Err(DomainError::NotFound(
+                format!("Tenant {} not found", tenant_id).into(),
+            ));
         }
 
         // Mark tenant for deletion (soft delete)
@@ -70,7 +69,7 @@ mod tests {
             .returning(move |_| Ok(Some(tenant.clone())));
         mock_repo.expect_delete_tenant().returning(|_| Ok(()));
 
-        let use_case = DeleteTenantUseCase::new(mock_repo);
+        let use_case = DeleteTenantUseCase::new(Arc::new(mock_repo));
         let result = use_case.execute(tenant_id).await;
 
         assert!(result.is_ok());
@@ -83,7 +82,7 @@ mod tests {
         let mut mock_repo = MockTenantRepository::new();
         mock_repo.expect_get_tenant().returning(|_| Ok(None));
 
-        let use_case = DeleteTenantUseCase::new(mock_repo);
+        let use_case = DeleteTenantUseCase::new(Arc::new(mock_repo));
         let result = use_case.execute(tenant_id).await;
 
         assert!(result.is_err());
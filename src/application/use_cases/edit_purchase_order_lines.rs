@@ -0,0 +1,109 @@
+use crate::application::use_cases::get_purchase_order::GetPurchaseOrderResponse;
+use crate::domain::entities::purchase_order::{
+    CreatePurchaseOrderLine, PromisePurchaseOrderLineRequest, PurchaseOrder,
+    UpdatePurchaseOrderLineRequest,
+};
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
+use crate::shared::error::DomainError;
+use crate::shared::etag::check_if_match;
+use crate::shared::i18n::Message;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Add, change and remove lines on a draft purchase order, and record the
+/// supplier's promised dates once it is under way
+pub struct EditPurchaseOrderLinesUseCase<R: PurchaseOrderRepository> {
+    purchase_order_repository: Arc<R>,
+    item_repository: Arc<dyn ItemRepository>,
+}
+
+impl<R: PurchaseOrderRepository> EditPurchaseOrderLinesUseCase<R> {
+    pub fn new(
+        purchase_order_repository: Arc<R>,
+        item_repository: Arc<dyn ItemRepository>,
+    ) -> Self {
+        Self {
+            purchase_order_repository,
+            item_repository,
+        }
+    }
+
+    pub async fn add_line(
+        &self,
+        po_id: Uuid,
+        request: CreatePurchaseOrderLine,
+        if_match: Option<String>,
+    ) -> Result<GetPurchaseOrderResponse, DomainError> {
+        let mut po = self.load(po_id, if_match.as_deref()).await?;
+        self.item_repository
+            .check_quantities(&[(request.item_id, request.qty_ordered)])
+            .await?;
+        po.add_line(request)?;
+        self.purchase_order_repository.update(&po).await?;
+        Ok(po.into())
+    }
+
+    pub async fn update_line(
+        &self,
+        po_id: Uuid,
+        line_id: Uuid,
+        request: UpdatePurchaseOrderLineRequest,
+        if_match: Option<String>,
+    ) -> Result<GetPurchaseOrderResponse, DomainError> {
+        let mut po = self.load(po_id, if_match.as_deref()).await?;
+        let line = po.lines.iter().find(|line| line.id == line_id);
+        if let (Some(line), Some(qty)) = (line, request.qty_ordered) {
+            self.item_repository
+                .check_quantities(&[(line.item_id, qty)])
+                .await?;
+        }
+        po.update_line(line_id, request)?;
+        self.purchase_order_repository.update(&po).await?;
+        Ok(po.into())
+    }
+
+    pub async fn remove_line(
+        &self,
+        po_id: Uuid,
+        line_id: Uuid,
+        if_match: Option<String>,
+    ) -> Result<GetPurchaseOrderResponse, DomainError> {
+        let mut po = self.load(po_id, if_match.as_deref()).await?;
+        po.remove_line(line_id)?;
+        self.purchase_order_repository.update(&po).await?;
+        Ok(po.into())
+    }
+
+    pub async fn promise_line(
+        &self,
+        po_id: Uuid,
+        line_id: Uuid,
+        request: PromisePurchaseOrderLineRequest,
+        if_match: Option<String>,
+    ) -> Result<GetPurchaseOrderResponse, DomainError> {
+        let mut po = self.load(po_id, if_match.as_deref()).await?;
+        po.promise_line(line_id, request.promised_date)?;
+        self.purchase_order_repository.update(&po).await?;
+        Ok(po.into())
+    }
+
+    async fn load(
+        &self,
+        po_id: Uuid,
+        if_match: Option<&str>,
+    ) -> Result<PurchaseOrder, DomainError> {
+        let po = self
+            .purchase_order_repository
+            .find_by_id(po_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_not_found",
+                    &[("id", &po_id)],
+                ))
+            })?;
+        check_if_match(if_match, &po.etag())?;
+        Ok(po)
+    }
+}
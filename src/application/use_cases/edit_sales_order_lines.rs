@@ -0,0 +1,188 @@
+use crate::application::use_cases::create_sales_order::CreateSalesOrderLineRequest;
+use crate::application::use_cases::get_sales_order::SalesOrderWithLines;
+use crate::domain::entities::sales_order::{
+    SalesOrder, SalesOrderLine, UpdateSalesOrderLineRequest,
+};
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::shared::error::DomainError;
+use crate::shared::etag::check_if_match;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Add, change and remove lines on a draft sales order
+pub struct EditSalesOrderLinesUseCase<T: SalesOrderRepository> {
+    sales_order_repo: Arc<T>,
+    item_repository: Arc<dyn ItemRepository>,
+}
+
+impl<T: SalesOrderRepository> EditSalesOrderLinesUseCase<T> {
+    pub fn new(sales_order_repo: Arc<T>, item_repository: Arc<dyn ItemRepository>) -> Self {
+        Self {
+            sales_order_repo,
+            item_repository,
+        }
+    }
+
+    pub async fn add_line(
+        &self,
+        so_id: Uuid,
+        request: CreateSalesOrderLineRequest,
+        if_match: Option<String>,
+    ) -> Result<SalesOrderWithLines, DomainError> {
+        let mut sales_order = self.load(so_id, if_match.as_deref()).await?;
+        self.item_repository
+            .check_quantities(&[(request.item_id, request.qty)])
+            .await?;
+        let line = SalesOrderLine::new(request.item_id, request.qty, request.unit_price)?;
+        sales_order.add_line(line)?;
+        self.save(sales_order).await
+    }
+
+    pub async fn update_line(
+        &self,
+        so_id: Uuid,
+        line_id: Uuid,
+        request: UpdateSalesOrderLineRequest,
+        if_match: Option<String>,
+    ) -> Result<SalesOrderWithLines, DomainError> {
+        let mut sales_order = self.load(so_id, if_match.as_deref()).await?;
+        let line = sales_order.lines.iter().find(|line| line.id == line_id);
+        if let (Some(line), Some(qty)) = (line, request.qty) {
+            self.item_repository
+                .check_quantities(&[(line.item_id, qty)])
+                .await?;
+        }
+        sales_order.update_line(line_id, request)?;
+        self.save(sales_order).await
+    }
+
+    pub async fn remove_line(
+        &self,
+        so_id: Uuid,
+        line_id: Uuid,
+        if_match: Option<String>,
+    ) -> Result<SalesOrderWithLines, DomainError> {
+        let mut sales_order = self.load(so_id, if_match.as_deref()).await?;
+        sales_order.remove_line(line_id)?;
+        self.save(sales_order).await
+    }
+
+    async fn load(&self, so_id: Uuid, if_match: Option<&str>) -> Result<SalesOrder, DomainError> {
+        let (sales_order, _) = self
+            .sales_order_repo
+            .find_by_id(so_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Sales order {} not found", so_id).into())
+            })?;
+        check_if_match(if_match, &sales_order.etag())?;
+        Ok(sales_order)
+    }
+
+    async fn save(&self, sales_order: SalesOrder) -> Result<SalesOrderWithLines, DomainError> {
+        self.sales_order_repo.update(&sales_order).await?;
+        let lines = sales_order.lines.clone();
+        Ok(SalesOrderWithLines::new(sales_order, lines))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::postgres_item_repository::PostgresItemRepository;
+    use crate::infrastructure::repositories::postgres_sales_order_repository::PostgresSalesOrderRepository;
+    use crate::shared::tenant_scope::with_tenant;
+    use crate::test_support::database::{TestDatabase, SEEDED_USER};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn test_line_edits_respect_the_item_quantity_precision() {
+        let db = TestDatabase::connect().await;
+        let (admin, pool) = (&db.admin, &db.pool);
+        let use_case = EditSalesOrderLinesUseCase::new(
+            Arc::new(PostgresSalesOrderRepository::new(Arc::clone(pool))),
+            Arc::new(PostgresItemRepository::new(Arc::clone(pool))),
+        );
+        let user = Uuid::from_str(SEEDED_USER).unwrap();
+        let tenant_id = db.create_tenant("Line precision").await;
+        let item_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO items (sku, name, unit, cost_price, tenant_id)
+             VALUES ('WHOLE-1', 'Whole units only', 'each', 5, $1) RETURNING id",
+        )
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        let order_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO sales_orders (so_number, status, created_by, tenant_id)
+             VALUES ('SO-PREC-1', 'DRAFT', $1, $2) RETURNING id",
+        )
+        .bind(user)
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        let line_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO sales_order_lines (so_id, item_id, qty, unit_price, tenant_id)
+             VALUES ($1, $2, 5, 10, $3) RETURNING id",
+        )
+        .bind(order_id)
+        .bind(item_id)
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        let add = |qty: &str| {
+            with_tenant(
+                tenant_id,
+                use_case.add_line(
+                    order_id,
+                    CreateSalesOrderLineRequest {
+                        item_id,
+                        qty: Decimal::from_str(qty).unwrap(),
+                        unit_price: Decimal::from(10),
+                    },
+                    None,
+                ),
+            )
+        };
+        let update = |qty: &str| {
+            with_tenant(
+                tenant_id,
+                use_case.update_line(
+                    order_id,
+                    line_id,
+                    UpdateSalesOrderLineRequest {
+                        qty: Some(Decimal::from_str(qty).unwrap()),
+                        unit_price: None,
+                    },
+                    None,
+                ),
+            )
+        };
+
+        assert!(matches!(
+            add("2.5").await,
+            Err(DomainError::ValidationError(_))
+        ));
+        assert!(matches!(
+            update("2.5").await,
+            Err(DomainError::ValidationError(_))
+        ));
+        let line_qty = || async {
+            sqlx::query_scalar::<_, Decimal>("SELECT qty FROM sales_order_lines WHERE id = $1")
+                .bind(line_id)
+                .fetch_one(admin)
+                .await
+                .unwrap()
+        };
+        assert_eq!(line_qty().await, Decimal::from(5));
+
+        add("2").await.unwrap();
+        update("3").await.unwrap();
+        assert_eq!(line_qty().await, Decimal::from(3));
+    }
+}
@@ -35,6 +35,7 @@ impl<S: JobService> EnqueueJobUseCase<S> {
         let create_request = CreateJobRequest {
             job_type: request.job_type,
             payload: request.payload,
+            max_attempts: None,
         };
 
         let job = self
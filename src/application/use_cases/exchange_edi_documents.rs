@@ -0,0 +1,489 @@
+use crate::domain::entities::edi::{
+    EdiDirection, EdiDocument, EdiDocumentType, EdiItemMatch, EdiSalesOrderLink,
+    ListEdiDocumentsQuery, TradingPartner,
+};
+use crate::domain::entities::item::Item;
+use crate::domain::entities::sales_order::{SalesOrder, SalesOrderLine};
+use crate::domain::entities::shipment::{Shipment, ShipmentSourceType};
+use crate::domain::services::edi_repository::EdiRepository;
+use crate::domain::services::edi_translator::{
+    EdiDocumentLine, EdiEnvelope, EdiInvoice, EdiPurchaseOrder, EdiShipNotice, EdiTranslator,
+};
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::domain::services::shipment_repository::ShipmentRepository;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct IngestEdiDocumentQuery {
+    /// Defaults to the partner whose interchange ID is the ISA sender
+    pub partner_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListEdiDocumentsResponse {
+    pub documents: Vec<EdiDocument>,
+}
+
+pub struct ExchangeEdiDocumentsUseCase<
+    E: EdiRepository,
+    I: ItemRepository,
+    O: SalesOrderRepository,
+    S: ShipmentRepository,
+    X: EdiTranslator,
+> {
+    edi_repository: Arc<E>,
+    item_repository: Arc<I>,
+    sales_order_repository: Arc<O>,
+    shipment_repository: Arc<S>,
+    translator: Arc<X>,
+}
+
+impl<
+        E: EdiRepository,
+        I: ItemRepository,
+        O: SalesOrderRepository,
+        S: ShipmentRepository,
+        X: EdiTranslator,
+    > ExchangeEdiDocumentsUseCase<E, I, O, S, X>
+{
+    pub fn new(
+        edi_repository: Arc<E>,
+        item_repository: Arc<I>,
+        sales_order_repository: Arc<O>,
+        shipment_repository: Arc<S>,
+        translator: Arc<X>,
+    ) -> Self {
+        Self {
+            edi_repository,
+            item_repository,
+            sales_order_repository,
+            shipment_repository,
+            translator,
+        }
+    }
+
+    /// Raise a draft sales order for every 850 in the interchange. The upload
+    /// is kept either way; a rejected one is stored as FAILED with the reason.
+    pub async fn ingest(
+        &self,
+        content: String,
+        query: IngestEdiDocumentQuery,
+        created_by: Uuid,
+    ) -> Result<EdiDocument, DomainError> {
+        let mut document = EdiDocument::inbound(query.partner_id, content);
+        match self.plan_sales_orders(&mut document, created_by).await {
+            Ok((control_number, orders)) => {
+                document.processed(
+                    control_number,
+                    orders.iter().map(|(order, _)| order.id).collect(),
+                );
+                self.edi_repository.create_document(&document).await?;
+                for (order, link) in &orders {
+                    self.sales_order_repository.create(order).await?;
+                    self.edi_repository.create_sales_order_link(link).await?;
+                }
+                Ok(document)
+            }
+            Err(error) => {
+                document.failed(&error);
+                self.edi_repository.create_document(&document).await?;
+                Err(recorded_as(error, document.id))
+            }
+        }
+    }
+
+    /// Map every order before creating any, so a bad line rejects the whole upload
+    async fn plan_sales_orders(
+        &self,
+        document: &mut EdiDocument,
+        created_by: Uuid,
+    ) -> Result<(String, Vec<(SalesOrder, EdiSalesOrderLink)>), DomainError> {
+        let interchange = self.translator.read_purchase_orders(&document.content)?;
+        // Only a partner that exists is recorded against the document
+        let partner = match document.partner_id.take() {
+            Some(partner_id) => self.find_partner(partner_id).await?,
+            None => self
+                .edi_repository
+                .find_partner_by_interchange_id(&interchange.sender_id)
+                .await?
+                .ok_or_else(|| {
+                    DomainError::ValidationError(
+                        format!(
+                            "No trading partner has interchange ID '{}'",
+                            interchange.sender_id
+                        )
+                        .into(),
+                    )
+                })?,
+        };
+        document.partner_id = Some(partner.id);
+        if !partner.active {
+            return Err(DomainError::BusinessLogicError(
+                format!("Trading partner {} is inactive", partner.name).into(),
+            ));
+        }
+
+        let mut orders = Vec::with_capacity(interchange.purchase_orders.len());
+        for purchase_order in &interchange.purchase_orders {
+            if interchange
+                .purchase_orders
+                .iter()
+                .filter(|po| po.po_number == purchase_order.po_number)
+                .count()
+                > 1
+            {
+                return Err(DomainError::ValidationError(
+                    format!(
+                        "Purchase order {} appears more than once in the interchange",
+                        purchase_order.po_number
+                    )
+                    .into(),
+                ));
+            }
+            if self
+                .edi_repository
+                .find_sales_order_link_by_po(partner.id, &purchase_order.po_number)
+                .await?
+                .is_some()
+            {
+                return Err(DomainError::Conflict(
+                    format!(
+                        "Purchase order {} from {} has already been ingested",
+                        purchase_order.po_number, partner.name
+                    )
+                    .into(),
+                ));
+            }
+            let order = self
+                .sales_order(&partner, purchase_order, created_by)
+                .await?;
+            let link = EdiSalesOrderLink {
+                sales_order_id: order.id,
+                partner_id: partner.id,
+                document_id: document.id,
+                po_number: purchase_order.po_number.clone(),
+                po_date: purchase_order.po_date,
+            };
+            orders.push((order, link));
+        }
+        Ok((interchange.control_number, orders))
+    }
+
+    async fn sales_order(
+        &self,
+        partner: &TradingPartner,
+        purchase_order: &EdiPurchaseOrder,
+        created_by: Uuid,
+    ) -> Result<SalesOrder, DomainError> {
+        let mut order = SalesOrder::new(
+            format!("SO-{}", Uuid::new_v4().simple()),
+            partner.customer_id,
+            partner.fulfillment_location_id,
+            created_by,
+        )?;
+        for (index, line) in purchase_order.lines.iter().enumerate() {
+            let product_id = line.product_id(&partner.item_id_qualifier).ok_or_else(|| {
+                DomainError::ValidationError(
+                    format!(
+                        "Line {} of purchase order {} has no {} product ID",
+                        line.line_number
+                            .as_deref()
+                            .unwrap_or(&(index + 1).to_string()),
+                        purchase_order.po_number,
+                        partner.item_id_qualifier
+                    )
+                    .into(),
+                )
+            })?;
+            let item = match partner.item_match {
+                EdiItemMatch::Sku => self.item_repository.find_by_sku(product_id).await?,
+                EdiItemMatch::Barcode => self.item_repository.find_by_barcode(product_id).await?,
+            }
+            .ok_or_else(|| {
+                DomainError::ValidationError(
+                    format!(
+                        "Purchase order {} orders unknown product {} '{}'",
+                        purchase_order.po_number, partner.item_id_qualifier, product_id
+                    )
+                    .into(),
+                )
+            })?;
+            order.add_line(SalesOrderLine::new(
+                item.id,
+                line.quantity,
+                line.unit_price,
+            )?)?;
+        }
+        Ok(order)
+    }
+
+    /// Build an 856 for a sales order shipment. Shipments don't record
+    /// per-line quantities, so lines carry what has shipped on the order to date.
+    pub async fn ship_notice(&self, shipment_id: Uuid) -> Result<EdiDocument, DomainError> {
+        let (shipment, partner, link, lines) = self.shipped_lines(shipment_id).await?;
+        let notice = EdiShipNotice {
+            shipment_number: shipment.shipment_number.clone(),
+            shipped_at: shipment.shipped_at,
+            carrier: shipment.carrier.clone(),
+            tracking_number: shipment.tracking_number.clone(),
+            po_number: link.po_number,
+            sscc_codes: shipment.cartons.iter().map(|c| c.sscc.clone()).collect(),
+            lines,
+        };
+        let envelope = self.envelope(&partner).await?;
+        let content = self.translator.write_ship_notice(&envelope, &notice)?;
+        self.record_outbound(
+            &partner,
+            EdiDocumentType::ShipNotice,
+            &envelope,
+            &shipment,
+            content,
+        )
+        .await
+    }
+
+    /// Build an 810 for a sales order shipment, numbered after the shipment
+    /// and billing what has shipped on the order to date
+    pub async fn invoice(&self, shipment_id: Uuid) -> Result<EdiDocument, DomainError> {
+        let (shipment, partner, link, lines) = self.shipped_lines(shipment_id).await?;
+        let invoice = EdiInvoice {
+            invoice_number: shipment.shipment_number.clone(),
+            invoice_date: Utc::now(),
+            po_number: link.po_number,
+            po_date: link.po_date,
+            lines,
+        };
+        let envelope = self.envelope(&partner).await?;
+        let content = self.translator.write_invoice(&envelope, &invoice)?;
+        self.record_outbound(
+            &partner,
+            EdiDocumentType::Invoice,
+            &envelope,
+            &shipment,
+            content,
+        )
+        .await
+    }
+
+    async fn shipped_lines(
+        &self,
+        shipment_id: Uuid,
+    ) -> Result<
+        (
+            Shipment,
+            TradingPartner,
+            EdiSalesOrderLink,
+            Vec<EdiDocumentLine>,
+        ),
+        DomainError,
+    > {
+        let shipment = self
+            .shipment_repository
+            .find_by_id(shipment_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Shipment {} not found", shipment_id).into())
+            })?;
+        if shipment.source_type != ShipmentSourceType::SalesOrder {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Shipment {} is not for a sales order",
+                    shipment.shipment_number
+                )
+                .into(),
+            ));
+        }
+
+        let link = self
+            .edi_repository
+            .find_sales_order_link(shipment.source_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::BusinessLogicError(
+                    format!(
+                    "Shipment {} is for a sales order that did not come from an EDI purchase order",
+                    shipment.shipment_number
+                )
+                    .into(),
+                )
+            })?;
+        let partner = self.find_partner(link.partner_id).await?;
+        let (_, order_lines) = self
+            .sales_order_repository
+            .find_by_id(shipment.source_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(
+                    format!("Sales order {} not found", shipment.source_id).into(),
+                )
+            })?;
+
+        // PO1 line numbers have to be echoed back, so re-read the original 850
+        let source = self
+            .edi_repository
+            .find_document(link.document_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("EDI document {} not found", link.document_id).into())
+            })?;
+        let purchase_order = self
+            .translator
+            .read_purchase_orders(&source.content)?
+            .purchase_orders
+            .into_iter()
+            .find(|po| po.po_number == link.po_number)
+            .ok_or_else(|| {
+                DomainError::NotFound(
+                    format!(
+                        "Purchase order {} is missing from EDI document {}",
+                        link.po_number, source.id
+                    )
+                    .into(),
+                )
+            })?;
+
+        let mut lines = Vec::new();
+        for (index, order_line) in order_lines.iter().enumerate() {
+            if order_line.qty_shipped <= Decimal::ZERO {
+                continue;
+            }
+            let item = self
+                .item_repository
+                .find_by_id(order_line.item_id)
+                .await?
+                .ok_or_else(|| {
+                    DomainError::NotFound(format!("Item {} not found", order_line.item_id).into())
+                })?;
+            let product_id = product_id(&partner, &item);
+            let po_line = purchase_order
+                .lines
+                .iter()
+                .find(|line| line.product_id(&partner.item_id_qualifier) == product_id.as_deref());
+            lines.push(EdiDocumentLine {
+                line_number: po_line
+                    .and_then(|line| line.line_number.clone())
+                    .unwrap_or_else(|| (index + 1).to_string()),
+                product_qualifier: partner.item_id_qualifier.clone(),
+                product_id: product_id.unwrap_or(item.sku),
+                quantity: order_line.qty_shipped,
+                unit_price: order_line.unit_price,
+            });
+        }
+        if lines.is_empty() {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Nothing has shipped yet on the order behind shipment {}",
+                    shipment.shipment_number
+                )
+                .into(),
+            ));
+        }
+
+        Ok((shipment, partner, link, lines))
+    }
+
+    async fn envelope(&self, partner: &TradingPartner) -> Result<EdiEnvelope, DomainError> {
+        if !partner.active {
+            return Err(DomainError::BusinessLogicError(
+                format!("Trading partner {} is inactive", partner.name).into(),
+            ));
+        }
+        Ok(EdiEnvelope {
+            sender_qualifier: partner.our_interchange_id_qualifier.clone(),
+            sender_id: partner.our_interchange_id.clone(),
+            sender_application_id: partner.our_application_id.clone(),
+            receiver_qualifier: partner.interchange_id_qualifier.clone(),
+            receiver_id: partner.interchange_id.clone(),
+            receiver_application_id: partner.application_id.clone(),
+            control_number: self.edi_repository.next_control_number(partner.id).await?,
+            created_at: Utc::now(),
+        })
+    }
+
+    async fn record_outbound(
+        &self,
+        partner: &TradingPartner,
+        document_type: EdiDocumentType,
+        envelope: &EdiEnvelope,
+        shipment: &Shipment,
+        content: String,
+    ) -> Result<EdiDocument, DomainError> {
+        let document = EdiDocument::outbound(
+            partner.id,
+            document_type,
+            envelope.control_number,
+            shipment.id,
+            content,
+        );
+        self.edi_repository.create_document(&document).await?;
+        Ok(document)
+    }
+
+    pub async fn get_document(&self, document_id: Uuid) -> Result<EdiDocument, DomainError> {
+        self.edi_repository
+            .find_document(document_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("EDI document {} not found", document_id).into())
+            })
+    }
+
+    pub async fn list_documents(
+        &self,
+        query: ListEdiDocumentsQuery,
+    ) -> Result<ListEdiDocumentsResponse, DomainError> {
+        let document_type = query
+            .document_type
+            .as_deref()
+            .map(EdiDocumentType::from_str)
+            .transpose()?;
+        let direction = query
+            .direction
+            .as_deref()
+            .map(EdiDirection::from_str)
+            .transpose()?;
+        let limit = query.limit.unwrap_or(50).clamp(1, 100);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let documents = self
+            .edi_repository
+            .list_documents(query.partner_id, document_type, direction, limit, offset)
+            .await?;
+        Ok(ListEdiDocumentsResponse { documents })
+    }
+
+    async fn find_partner(&self, partner_id: Uuid) -> Result<TradingPartner, DomainError> {
+        self.edi_repository
+            .find_partner_by_id(partner_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Trading partner {} not found", partner_id).into())
+            })
+    }
+}
+
+/// The partner's ID for an item, by whichever item field the partner matches on
+fn product_id(partner: &TradingPartner, item: &Item) -> Option<String> {
+    match partner.item_match {
+        EdiItemMatch::Sku => Some(item.sku.clone()),
+        EdiItemMatch::Barcode => item.barcode.clone(),
+    }
+}
+
+/// Point the caller at the stored copy of a rejected upload
+fn recorded_as(error: DomainError, document_id: Uuid) -> DomainError {
+    let note = |msg: Message| format!("{} (recorded as EDI document {})", msg, document_id);
+    match error {
+        DomainError::ValidationError(msg) => DomainError::ValidationError(note(msg).into()),
+        DomainError::BusinessLogicError(msg) => DomainError::BusinessLogicError(note(msg).into()),
+        DomainError::NotFound(msg) => DomainError::NotFound(note(msg).into()),
+        DomainError::Conflict(msg) => DomainError::Conflict(note(msg).into()),
+        other => other,
+    }
+}
@@ -0,0 +1,102 @@
+use crate::domain::entities::cycle_count::CycleCount;
+use crate::domain::entities::inventory::StockMovement;
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::cycle_count_repository::CycleCountRepository;
+use crate::domain::services::stock_repository::StockRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct FinalizeCycleCountResponse {
+    pub cycle_count: CycleCount,
+    pub stock_movements: Vec<StockMovement>,
+}
+
+pub struct FinalizeCycleCountUseCase<
+    C: CycleCountRepository,
+    S: StockRepository,
+    D: WebhookDispatcher + 'static,
+> {
+    cycle_count_repository: Arc<C>,
+    stock_repository: Arc<S>,
+    webhook_dispatcher: Arc<D>,
+}
+
+impl<C: CycleCountRepository, S: StockRepository, D: WebhookDispatcher + 'static>
+    FinalizeCycleCountUseCase<C, S, D>
+{
+    pub fn new(
+        cycle_count_repository: Arc<C>,
+        stock_repository: Arc<S>,
+        webhook_dispatcher: Arc<D>,
+    ) -> Self {
+        Self {
+            cycle_count_repository,
+            stock_repository,
+            webhook_dispatcher,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        cycle_count_id: Uuid,
+        finalized_by: Uuid,
+    ) -> Result<FinalizeCycleCountResponse, DomainError> {
+        let mut cycle_count = self
+            .cycle_count_repository
+            .find_by_id(cycle_count_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Cycle count {} not found", cycle_count_id).into())
+            })?;
+
+        // Reconcile against current stock so movements recorded while counting
+        // don't leave the location off from the physical count
+        let item_ids: Vec<Uuid> = cycle_count.lines.iter().map(|l| l.item_id).collect();
+        for item_id in item_ids {
+            let current_qty = self
+                .stock_repository
+                .get_stock_level(item_id, cycle_count.location_id)
+                .await?
+                .map(|level| level.quantity_on_hand)
+                .unwrap_or(Decimal::ZERO);
+            cycle_count.refresh_expected(item_id, current_qty);
+        }
+
+        let stock_movements = cycle_count.finalize(finalized_by)?;
+
+        self.cycle_count_repository
+            .finalize(&cycle_count, &stock_movements)
+            .await?;
+
+        // Dispatch one stock movement event per variance (non-blocking)
+        for movement in &stock_movements {
+            let webhook_event = WebhookEvent::new(
+                WebhookEventType::StockMovement,
+                json!({
+                    "event_type": "cycle_count_adjustment",
+                    "cycle_count_id": cycle_count.id,
+                    "count_number": cycle_count.count_number,
+                    "movement": movement,
+                }),
+            );
+
+            let dispatcher = Arc::clone(&self.webhook_dispatcher);
+            tokio::spawn(async move {
+                if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                    eprintln!("Failed to dispatch cycle count webhook: {:?}", e);
+                }
+            });
+        }
+
+        Ok(FinalizeCycleCountResponse {
+            cycle_count,
+            stock_movements,
+        })
+    }
+}
@@ -0,0 +1,122 @@
+use crate::domain::entities::forecast::{
+    daily_series, DemandForecast, ForecastParams, ForecastQuery,
+};
+use crate::domain::entities::item::Item;
+use crate::domain::services::forecast_repository::ForecastRepository;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::time_zone_service::TimeZoneService;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Stored forecasts listed per item by default, and at most
+const DEFAULT_SNAPSHOT_LIMIT: i64 = 30;
+const MAX_SNAPSHOT_LIMIT: i64 = 365;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ForecastSnapshotsQuery {
+    pub limit: Option<i64>,
+}
+
+/// Forecast `items` from their outbound movements up to the day before `today`
+/// (on the tenant's calendar), one query for the lot
+pub async fn forecast_items(
+    forecast_repository: &dyn ForecastRepository,
+    items: &[&Item],
+    query: &ForecastQuery,
+    today: NaiveDate,
+) -> Result<HashMap<Uuid, DemandForecast>, DomainError> {
+    if items.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let params = items
+        .iter()
+        .map(|item| ForecastParams::resolve(query, item))
+        .collect::<Result<Vec<_>, _>>()?;
+    // Every item shares the query's history, so the first one's window does
+    let history_start = params[0].history_start(today);
+    let item_ids: Vec<Uuid> = items.iter().map(|item| item.id).collect();
+    let mut demand = forecast_repository
+        .daily_demand(&item_ids, history_start, today)
+        .await?;
+
+    Ok(items
+        .iter()
+        .zip(params)
+        .map(|(item, params)| {
+            let sales = demand.remove(&item.id).unwrap_or_default();
+            let series = daily_series(&sales, history_start, params.history_days);
+            (item.id, DemandForecast::compute(item, &params, &series))
+        })
+        .collect())
+}
+
+/// Per-item demand forecasts from historical outbound movements. Each
+/// forecast is kept as a snapshot so its accuracy can be looked back on.
+pub struct ForecastDemandUseCase<I: ItemRepository, F: ForecastRepository> {
+    item_repository: Arc<I>,
+    forecast_repository: Arc<F>,
+    time_zone_service: Arc<dyn TimeZoneService>,
+}
+
+impl<I: ItemRepository, F: ForecastRepository> ForecastDemandUseCase<I, F> {
+    pub fn new(
+        item_repository: Arc<I>,
+        forecast_repository: Arc<F>,
+        time_zone_service: Arc<dyn TimeZoneService>,
+    ) -> Self {
+        Self {
+            item_repository,
+            forecast_repository,
+            time_zone_service,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        item_id: Uuid,
+        query: ForecastQuery,
+    ) -> Result<DemandForecast, DomainError> {
+        let item = self.find_item(item_id).await?;
+        let today = self.time_zone_service.today().await?;
+        let forecast = forecast_items(&*self.forecast_repository, &[&item], &query, today)
+            .await?
+            .remove(&item.id)
+            .expect("every item gets a forecast");
+        self.forecast_repository.save(&forecast).await?;
+        Ok(forecast)
+    }
+
+    /// Forecasts stored for an item, newest first
+    pub async fn snapshots(
+        &self,
+        item_id: Uuid,
+        query: ForecastSnapshotsQuery,
+    ) -> Result<Vec<DemandForecast>, DomainError> {
+        let limit = query.limit.unwrap_or(DEFAULT_SNAPSHOT_LIMIT);
+        if !(1..=MAX_SNAPSHOT_LIMIT).contains(&limit) {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.field_between",
+                &[
+                    ("field", &"limit"),
+                    ("min", &1),
+                    ("max", &MAX_SNAPSHOT_LIMIT),
+                ],
+            )));
+        }
+        self.find_item(item_id).await?;
+        self.forecast_repository.list_by_item(item_id, limit).await
+    }
+
+    async fn find_item(&self, item_id: Uuid) -> Result<Item, DomainError> {
+        self.item_repository
+            .find_by_id(item_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Item {} not found", item_id).into()))
+    }
+}
@@ -0,0 +1,61 @@
+use crate::domain::services::barcode_service::{
+    BarcodeFormat, BarcodeService, BarcodeSymbology, RenderedBarcode,
+};
+use crate::domain::services::item_repository::ItemRepository;
+use crate::shared::error::DomainError;
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ItemBarcodeQuery {
+    pub symbology: Option<String>,
+    pub format: Option<String>,
+}
+
+pub struct GenerateItemBarcodeUseCase<I: ItemRepository, B: BarcodeService> {
+    item_repository: Arc<I>,
+    barcode_service: Arc<B>,
+}
+
+impl<I: ItemRepository, B: BarcodeService> GenerateItemBarcodeUseCase<I, B> {
+    pub fn new(item_repository: Arc<I>, barcode_service: Arc<B>) -> Self {
+        Self {
+            item_repository,
+            barcode_service,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        item_id: Uuid,
+        query: ItemBarcodeQuery,
+    ) -> Result<RenderedBarcode, DomainError> {
+        let symbology = query
+            .symbology
+            .as_deref()
+            .map(BarcodeSymbology::from_str)
+            .transpose()?
+            .unwrap_or_default();
+        let format = query
+            .format
+            .as_deref()
+            .map(BarcodeFormat::from_str)
+            .transpose()?
+            .unwrap_or_default();
+
+        let item = self
+            .item_repository
+            .find_by_id(item_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Item {} not found", item_id).into()))?;
+
+        // Labels carry the item's own barcode when it has one, otherwise the SKU
+        let data = item
+            .barcode
+            .filter(|b| !b.trim().is_empty())
+            .unwrap_or(item.sku);
+
+        self.barcode_service.render(&data, symbology, format)
+    }
+}
@@ -0,0 +1,349 @@
+use crate::domain::entities::item::Item;
+use crate::domain::entities::sales_order::{SalesOrder, SalesOrderLine, SalesOrderStatus};
+use crate::domain::services::blob_storage::BlobStorage;
+use crate::domain::services::document_renderer::{
+    BusinessDocument, DocumentColumn, DocumentKind, DocumentParty, DocumentRenderer,
+};
+use crate::domain::services::document_settings_repository::DocumentSettingsRepository;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
+use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::money::{extend, round_total};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct RenderedDocument {
+    pub file_name: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Print purchase orders for suppliers, order confirmations for customers and
+/// packing slips for the warehouse, on the tenant's letterhead
+pub struct GenerateOrderDocumentsUseCase<
+    P: PurchaseOrderRepository,
+    O: SalesOrderRepository,
+    I: ItemRepository,
+    L: LocationRepository,
+    D: DocumentSettingsRepository,
+    R: DocumentRenderer,
+> {
+    purchase_order_repository: Arc<P>,
+    sales_order_repository: Arc<O>,
+    item_repository: Arc<I>,
+    location_repository: Arc<L>,
+    settings_repository: Arc<D>,
+    document_renderer: Arc<R>,
+    blob_storage: Arc<dyn BlobStorage>,
+}
+
+impl<
+        P: PurchaseOrderRepository,
+        O: SalesOrderRepository,
+        I: ItemRepository,
+        L: LocationRepository,
+        D: DocumentSettingsRepository,
+        R: DocumentRenderer,
+    > GenerateOrderDocumentsUseCase<P, O, I, L, D, R>
+{
+    pub fn new(
+        purchase_order_repository: Arc<P>,
+        sales_order_repository: Arc<O>,
+        item_repository: Arc<I>,
+        location_repository: Arc<L>,
+        settings_repository: Arc<D>,
+        document_renderer: Arc<R>,
+        blob_storage: Arc<dyn BlobStorage>,
+    ) -> Self {
+        Self {
+            purchase_order_repository,
+            sales_order_repository,
+            item_repository,
+            location_repository,
+            settings_repository,
+            document_renderer,
+            blob_storage,
+        }
+    }
+
+    /// The purchase order as sent to the supplier
+    pub async fn purchase_order(&self, po_id: Uuid) -> Result<RenderedDocument, DomainError> {
+        let po = self
+            .purchase_order_repository
+            .find_by_id(po_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_not_found",
+                    &[("id", &po_id)],
+                ))
+            })?;
+        let items = self.items(po.lines.iter().map(|line| line.item_id)).await?;
+
+        let mut details = vec![("Status".to_string(), status_label(&po.status.to_string()))];
+        if let Some(expected_date) = po.expected_date {
+            details.push((
+                "Expected".to_string(),
+                expected_date.format("%Y-%m-%d").to_string(),
+            ));
+        }
+        let rows = po
+            .lines
+            .iter()
+            .map(|line| {
+                let (sku, name) = item_cells(&items, line.item_id);
+                vec![
+                    sku,
+                    name,
+                    line.qty_ordered.to_string(),
+                    money(line.unit_cost),
+                    money(line.line_total),
+                ]
+            })
+            .collect();
+
+        let mut document = BusinessDocument {
+            kind: DocumentKind::PurchaseOrder,
+            number: po.po_number.clone(),
+            issued_at: po.created_at,
+            letterhead: Vec::new(),
+            logo: None,
+            parties: vec![DocumentParty {
+                heading: "Supplier".to_string(),
+                lines: vec![format!("Supplier ID {}", po.supplier_id)],
+            }],
+            details,
+            columns: vec![
+                DocumentColumn::text("SKU", 2.0),
+                DocumentColumn::text("Description", 4.0),
+                DocumentColumn::numeric("Qty", 1.0),
+                DocumentColumn::numeric("Unit cost", 1.5),
+                DocumentColumn::numeric("Amount", 1.5),
+            ],
+            rows,
+            totals: vec![("Total".to_string(), money(po.total_amount))],
+            footer: None,
+        };
+        self.render(&mut document).await
+    }
+
+    /// The customer's order confirmation, with prices and tax
+    pub async fn sales_order(&self, so_id: Uuid) -> Result<RenderedDocument, DomainError> {
+        let (order, lines) = self.find_sales_order(so_id).await?;
+        let items = self.items(lines.iter().map(|line| line.item_id)).await?;
+
+        let rows = lines
+            .iter()
+            .map(|line| {
+                let (sku, name) = item_cells(&items, line.item_id);
+                vec![
+                    sku,
+                    name,
+                    line.qty.to_string(),
+                    money(line.unit_price),
+                    money(line.tax),
+                    money(line.line_total()),
+                ]
+            })
+            .collect();
+        let subtotal: Decimal = lines
+            .iter()
+            .map(|line| extend(line.qty, line.unit_price))
+            .sum();
+        let tax: Decimal = lines.iter().map(|line| line.tax).sum();
+
+        let mut document = BusinessDocument {
+            kind: DocumentKind::SalesOrderConfirmation,
+            number: order.so_number.clone(),
+            issued_at: order.created_at,
+            letterhead: Vec::new(),
+            logo: None,
+            parties: self.order_parties(&order).await?,
+            details: vec![("Status".to_string(), status_label(order.status.as_str()))],
+            columns: vec![
+                DocumentColumn::text("SKU", 2.0),
+                DocumentColumn::text("Description", 4.0),
+                DocumentColumn::numeric("Qty", 1.0),
+                DocumentColumn::numeric("Unit price", 1.5),
+                DocumentColumn::numeric("Tax", 1.2),
+                DocumentColumn::numeric("Amount", 1.5),
+            ],
+            rows,
+            totals: vec![
+                ("Subtotal".to_string(), money(subtotal)),
+                ("Tax".to_string(), money(tax)),
+                ("Total".to_string(), money(order.total_amount)),
+            ],
+            footer: None,
+        };
+        self.render(&mut document).await
+    }
+
+    /// Packing slip and pick ticket: what is left to pick and pack, without prices
+    pub async fn packing_slip(&self, so_id: Uuid) -> Result<RenderedDocument, DomainError> {
+        let (order, lines) = self.find_sales_order(so_id).await?;
+        if matches!(
+            order.status,
+            SalesOrderStatus::Draft | SalesOrderStatus::Cancelled
+        ) {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Cannot print a packing slip for sales order {} in status {}",
+                    order.so_number,
+                    order.status.as_str()
+                )
+                .into(),
+            ));
+        }
+        let items = self.items(lines.iter().map(|line| line.item_id)).await?;
+
+        let rows = lines
+            .iter()
+            .map(|line| {
+                let (sku, name) = item_cells(&items, line.item_id);
+                let unit = items
+                    .get(&line.item_id)
+                    .map(|item| item.unit.clone())
+                    .unwrap_or_default();
+                vec![
+                    sku,
+                    name,
+                    unit,
+                    line.qty.to_string(),
+                    line.qty_shipped.to_string(),
+                    line.remaining_qty().max(Decimal::ZERO).to_string(),
+                    String::new(),
+                ]
+            })
+            .collect();
+        let units: Decimal = lines
+            .iter()
+            .map(|line| line.remaining_qty().max(Decimal::ZERO))
+            .sum();
+
+        let mut document = BusinessDocument {
+            kind: DocumentKind::PackingSlip,
+            number: order.so_number.clone(),
+            issued_at: chrono::Utc::now(),
+            letterhead: Vec::new(),
+            logo: None,
+            parties: self.order_parties(&order).await?,
+            details: vec![
+                ("Status".to_string(), status_label(order.status.as_str())),
+                (
+                    "Ordered".to_string(),
+                    order.created_at.format("%Y-%m-%d").to_string(),
+                ),
+            ],
+            columns: vec![
+                DocumentColumn::text("SKU", 2.0),
+                DocumentColumn::text("Description", 4.0),
+                DocumentColumn::text("Unit", 1.0),
+                DocumentColumn::numeric("Ordered", 1.2),
+                DocumentColumn::numeric("Shipped", 1.2),
+                DocumentColumn::numeric("To ship", 1.2),
+                // Left blank for the picker to tick or write the picked quantity
+                DocumentColumn::numeric("Picked", 1.2),
+            ],
+            rows,
+            totals: vec![("Units to ship".to_string(), units.to_string())],
+            footer: None,
+        };
+        self.render(&mut document).await
+    }
+
+    async fn find_sales_order(
+        &self,
+        so_id: Uuid,
+    ) -> Result<(SalesOrder, Vec<SalesOrderLine>), DomainError> {
+        self.sales_order_repository
+            .find_by_id(so_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Sales order {} not found", so_id).into()))
+    }
+
+    async fn order_parties(&self, order: &SalesOrder) -> Result<Vec<DocumentParty>, DomainError> {
+        let mut parties = Vec::new();
+        if let Some(customer_id) = order.customer_id {
+            parties.push(DocumentParty {
+                heading: "Customer".to_string(),
+                lines: vec![format!("Customer ID {}", customer_id)],
+            });
+        }
+        if let Some(location_id) = order.fulfillment_location_id {
+            if let Some(location) = self.location_repository.find_by_id(location_id).await? {
+                let mut lines = vec![location.name.clone()];
+                if let Some(address) = &location.address {
+                    lines.extend(address.lines());
+                }
+                parties.push(DocumentParty {
+                    heading: "Ship from".to_string(),
+                    lines,
+                });
+            }
+        }
+        Ok(parties)
+    }
+
+    /// Each distinct item on the order, looked up once
+    async fn items(
+        &self,
+        item_ids: impl Iterator<Item = Uuid>,
+    ) -> Result<HashMap<Uuid, Item>, DomainError> {
+        let mut items = HashMap::new();
+        for item_id in item_ids {
+            if items.contains_key(&item_id) {
+                continue;
+            }
+            if let Some(item) = self.item_repository.find_by_id(item_id).await? {
+                items.insert(item_id, item);
+            }
+        }
+        Ok(items)
+    }
+
+    /// Put the tenant's letterhead on the document and render it
+    async fn render(
+        &self,
+        document: &mut BusinessDocument,
+    ) -> Result<RenderedDocument, DomainError> {
+        if let Some(settings) = self.settings_repository.find().await? {
+            document.letterhead = settings.letterhead_lines();
+            document.footer = settings.footer_text.clone();
+            if let Some(logo_key) = &settings.logo_key {
+                // A missing logo shouldn't keep the document from printing
+                match self.blob_storage.get(logo_key).await {
+                    Ok(logo) => document.logo = Some(logo),
+                    Err(e) => warn!("Printing without logo {}: {}", logo_key, e),
+                }
+            }
+        }
+
+        Ok(RenderedDocument {
+            file_name: document.file_name(),
+            bytes: self.document_renderer.render(document)?,
+        })
+    }
+}
+
+fn item_cells(items: &HashMap<Uuid, Item>, item_id: Uuid) -> (String, String) {
+    match items.get(&item_id) {
+        Some(item) => (item.sku.clone(), item.name.clone()),
+        None => (String::new(), format!("Item {}", item_id)),
+    }
+}
+
+fn money(amount: Decimal) -> String {
+    format!("{:.2}", round_total(amount))
+}
+
+/// `PARTIALLY_SHIPPED` as `PARTIALLY SHIPPED`
+fn status_label(status: &str) -> String {
+    status.replace('_', " ")
+}
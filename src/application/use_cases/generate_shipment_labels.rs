@@ -0,0 +1,190 @@
+use crate::domain::entities::location::Location;
+use crate::domain::entities::shipment::{
+    CreateShipmentCartonsRequest, Shipment, ShipmentSourceType,
+};
+use crate::domain::services::label_renderer::{
+    LabelFormat, LabelRenderer, RenderedLabels, ShippingLabel,
+};
+use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::domain::services::shipment_repository::ShipmentRepository;
+use crate::domain::services::transfer_repository::TransferRepository;
+use crate::shared::error::DomainError;
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ShipmentLabelsQuery {
+    pub format: Option<String>,
+}
+
+pub struct GenerateShipmentLabelsUseCase<
+    S: ShipmentRepository,
+    O: SalesOrderRepository,
+    T: TransferRepository,
+    L: LocationRepository,
+    R: LabelRenderer,
+> {
+    shipment_repository: Arc<S>,
+    sales_order_repository: Arc<O>,
+    transfer_repository: Arc<T>,
+    location_repository: Arc<L>,
+    label_renderer: Arc<R>,
+}
+
+impl<
+        S: ShipmentRepository,
+        O: SalesOrderRepository,
+        T: TransferRepository,
+        L: LocationRepository,
+        R: LabelRenderer,
+    > GenerateShipmentLabelsUseCase<S, O, T, L, R>
+{
+    pub fn new(
+        shipment_repository: Arc<S>,
+        sales_order_repository: Arc<O>,
+        transfer_repository: Arc<T>,
+        location_repository: Arc<L>,
+        label_renderer: Arc<R>,
+    ) -> Self {
+        Self {
+            shipment_repository,
+            sales_order_repository,
+            transfer_repository,
+            location_repository,
+            label_renderer,
+        }
+    }
+
+    /// Open cartons on a shipment, each numbered with the tenant's next SSCC
+    pub async fn add_cartons(
+        &self,
+        shipment_id: Uuid,
+        request: CreateShipmentCartonsRequest,
+    ) -> Result<Shipment, DomainError> {
+        let mut shipment = self.find_shipment(shipment_id).await?;
+        let package_ids = shipment.plan_cartons(&request)?;
+        let cartons = self
+            .shipment_repository
+            .create_cartons(shipment.id, &package_ids)
+            .await?;
+        shipment.cartons.extend(cartons);
+        Ok(shipment)
+    }
+
+    /// Render a GS1-128 label for every carton on the shipment
+    pub async fn execute(
+        &self,
+        shipment_id: Uuid,
+        query: ShipmentLabelsQuery,
+    ) -> Result<RenderedLabels, DomainError> {
+        let format = query
+            .format
+            .as_deref()
+            .map(LabelFormat::from_str)
+            .transpose()?
+            .unwrap_or_default();
+
+        let shipment = self.find_shipment(shipment_id).await?;
+        if shipment.cartons.is_empty() {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Shipment {} has no cartons to label",
+                    shipment.shipment_number
+                )
+                .into(),
+            ));
+        }
+
+        let (reference, from_location_id, to_location_id) = match shipment.source_type {
+            ShipmentSourceType::SalesOrder => {
+                let (order, _) = self
+                    .sales_order_repository
+                    .find_by_id(shipment.source_id)
+                    .await?
+                    .ok_or_else(|| {
+                        DomainError::NotFound(
+                            format!("Sales order {} not found", shipment.source_id).into(),
+                        )
+                    })?;
+                (order.so_number, order.fulfillment_location_id, None)
+            }
+            ShipmentSourceType::Transfer => {
+                let (transfer, _) = self
+                    .transfer_repository
+                    .find_by_id(shipment.source_id)
+                    .await?
+                    .ok_or_else(|| {
+                        DomainError::NotFound(
+                            format!("Transfer {} not found", shipment.source_id).into(),
+                        )
+                    })?;
+                (
+                    transfer.transfer_number,
+                    Some(transfer.from_location_id),
+                    Some(transfer.to_location_id),
+                )
+            }
+        };
+        let ship_from = self.address_lines(from_location_id).await?;
+        let ship_to = self.address_lines(to_location_id).await?;
+
+        let carton_count = shipment.cartons.len();
+        let labels: Vec<ShippingLabel> = shipment
+            .cartons
+            .iter()
+            .enumerate()
+            .map(|(i, carton)| {
+                let package = carton
+                    .package_id
+                    .and_then(|id| shipment.packages.iter().find(|p| p.id == id));
+                ShippingLabel {
+                    sscc: carton.sscc.clone(),
+                    ship_from: ship_from.clone(),
+                    ship_to: ship_to.clone(),
+                    carrier: shipment.carrier.clone(),
+                    tracking_number: package
+                        .and_then(|p| p.tracking_number.clone())
+                        .or_else(|| shipment.tracking_number.clone()),
+                    shipment_number: shipment.shipment_number.clone(),
+                    reference: Some(reference.clone()),
+                    carton_number: i + 1,
+                    carton_count,
+                    weight_kg: package.map(|p| p.weight_kg),
+                }
+            })
+            .collect();
+
+        self.label_renderer.render(&labels, format)
+    }
+
+    async fn find_shipment(&self, shipment_id: Uuid) -> Result<Shipment, DomainError> {
+        self.shipment_repository
+            .find_by_id(shipment_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Shipment {} not found", shipment_id).into())
+            })
+    }
+
+    async fn address_lines(&self, location_id: Option<Uuid>) -> Result<Vec<String>, DomainError> {
+        let Some(location_id) = location_id else {
+            return Ok(Vec::new());
+        };
+        Ok(self
+            .location_repository
+            .find_by_id(location_id)
+            .await?
+            .map(|location| address_lines(&location))
+            .unwrap_or_default())
+    }
+}
+
+fn address_lines(location: &Location) -> Vec<String> {
+    let mut lines = vec![location.name.clone()];
+    if let Some(address) = &location.address {
+        lines.extend(address.lines());
+    }
+    lines
+}
@@ -1,5 +1,6 @@
 use crate::domain::services::webhook_repository::WebhookRepository;
 use crate::shared::error::DomainError;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
@@ -20,7 +21,7 @@ impl<R: WebhookRepository> GetBillingMetricsUseCase<R> {
             sqlx::query!("SELECT COUNT(*) as count FROM webhook_deliveries")
                 .fetch_one(&*self.webhook_repository.get_pool())
                 .await
-                .map_err(|_| DomainError::DatabaseError("Failed to count deliveries".to_string()))?
+                .map_err(|_| DomainError::DatabaseError("Failed to count deliveries".into()))?
                 .count
                 .unwrap_or(0);
 
@@ -29,9 +30,7 @@ impl<R: WebhookRepository> GetBillingMetricsUseCase<R> {
         )
         .fetch_one(&*self.webhook_repository.get_pool())
         .await
-        .map_err(|_| {
-            DomainError::DatabaseError("Failed to count successful deliveries".to_string())
-        })?
+        .map_err(|_| DomainError::DatabaseError("Failed to count successful deliveries".into()))?
         .count
         .unwrap_or(0);
 
@@ -40,13 +39,54 @@ impl<R: WebhookRepository> GetBillingMetricsUseCase<R> {
         )
         .fetch_one(&*self.webhook_repository.get_pool())
         .await
-        .map_err(|_| DomainError::DatabaseError("Failed to count failed deliveries".to_string()))?
+        .map_err(|_| DomainError::DatabaseError("Failed to count failed deliveries".into()))?
         .count
         .unwrap_or(0);
 
+        // The last 30 days of the tenant's calendar, from local midnight; truncating in
+        // local time before converting back keeps the start on midnight across DST changes
+        let billing_period_start = sqlx::query_scalar!(
+            r#"
+            SELECT (date_trunc('day', NOW() AT TIME ZONE get_current_tenant_timezone()) - INTERVAL '30 days')
+                   AT TIME ZONE get_current_tenant_timezone() AS "start!"
+            "#
+        )
+        .fetch_one(self.webhook_repository.get_pool())
+        .await
+        .map_err(|_| DomainError::DatabaseError("Failed to compute billing period".into()))?;
+
+        // Calls metered by the usage metering middleware, as of its last flush
+        let total_api_calls = sqlx::query!(
+            "SELECT COALESCE(SUM(request_count), 0) as count FROM api_rate_limits WHERE tenant_id = get_current_tenant_id() AND window_start >= $1",
+            billing_period_start
+        )
+        .fetch_one(self.webhook_repository.get_pool())
+        .await
+        .map_err(|_| DomainError::DatabaseError("Failed to count API calls".into()))?
+        .count
+        .unwrap_or(0);
+
+        // Order values over the period in the base currency, each foreign-currency
+        // order at the rate fixed when it was placed and rounded to cents as
+        // `money::convert` does
+        let order_values = sqlx::query!(
+            r#"
+            SELECT
+                COALESCE((SELECT base_currency FROM tenants WHERE id = get_current_tenant_id()), 'USD') AS "currency!",
+                (SELECT COALESCE(SUM(ROUND(total_amount * COALESCE(exchange_rate, 1), 2)), 0) FROM purchase_orders
+                 WHERE tenant_id = get_current_tenant_id() AND status <> 'CANCELLED' AND created_at >= $1) AS "purchase_orders!",
+                (SELECT COALESCE(SUM(ROUND(total_amount * COALESCE(exchange_rate, 1), 2)), 0) FROM sales_orders
+                 WHERE tenant_id = get_current_tenant_id() AND status <> 'CANCELLED' AND created_at >= $1) AS "sales_orders!"
+            "#,
+            billing_period_start
+        )
+        .fetch_one(self.webhook_repository.get_pool())
+        .await
+        .map_err(|_| DomainError::DatabaseError("Failed to sum order values".into()))?;
+
         // Mock data for other metrics (would be collected from actual usage)
         Ok(BillingMetricsResponse {
-            total_api_calls: 1250,
+            total_api_calls,
             storage_used_gb: 2.5,
             active_tenants: 3,
             total_items: 150,
@@ -58,8 +98,13 @@ impl<R: WebhookRepository> GetBillingMetricsUseCase<R> {
                 successful: successful_deliveries,
                 failed: failed_deliveries,
             },
+            order_values: OrderValueMetrics {
+                currency: order_values.currency,
+                purchase_orders: order_values.purchase_orders,
+                sales_orders: order_values.sales_orders,
+            },
             billing_period: BillingPeriod {
-                start_date: chrono::Utc::now() - chrono::Duration::days(30),
+                start_date: billing_period_start,
                 end_date: chrono::Utc::now(),
                 days_remaining: 0,
             },
@@ -77,6 +122,7 @@ pub struct BillingMetricsResponse {
     pub total_orders: i64,
     pub total_transfers: i64,
     pub webhook_deliveries: WebhookMetrics,
+    pub order_values: OrderValueMetrics,
     pub billing_period: BillingPeriod,
 }
 
@@ -87,6 +133,14 @@ pub struct WebhookMetrics {
     pub failed: i64,
 }
 
+/// Non-cancelled orders placed in the billing period, in the tenant's base currency
+#[derive(Debug, Serialize)]
+pub struct OrderValueMetrics {
+    pub currency: String,
+    pub purchase_orders: Decimal,
+    pub sales_orders: Decimal,
+}
+
 #[derive(Debug, Serialize)]
 pub struct BillingPeriod {
     pub start_date: chrono::DateTime<chrono::Utc>,
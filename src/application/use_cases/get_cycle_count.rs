@@ -0,0 +1,60 @@
+use crate::domain::entities::cycle_count::CycleCount;
+use crate::domain::services::cycle_count_repository::CycleCountRepository;
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct ListCycleCountsQuery {
+    pub location_id: Option<Uuid>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListCycleCountsResponse {
+    pub cycle_counts: Vec<CycleCount>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+pub struct GetCycleCountUseCase<C: CycleCountRepository> {
+    cycle_count_repository: Arc<C>,
+}
+
+impl<C: CycleCountRepository> GetCycleCountUseCase<C> {
+    pub fn new(cycle_count_repository: Arc<C>) -> Self {
+        Self {
+            cycle_count_repository,
+        }
+    }
+
+    pub async fn execute(&self, cycle_count_id: Uuid) -> Result<CycleCount, DomainError> {
+        self.cycle_count_repository
+            .find_by_id(cycle_count_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Cycle count {} not found", cycle_count_id).into())
+            })
+    }
+
+    pub async fn list(
+        &self,
+        query: ListCycleCountsQuery,
+    ) -> Result<ListCycleCountsResponse, DomainError> {
+        let limit = query.limit.unwrap_or(50).clamp(1, 100);
+        let offset = query.offset.unwrap_or(0).max(0);
+
+        let cycle_counts = self
+            .cycle_count_repository
+            .list(query.location_id, limit, offset)
+            .await?;
+
+        Ok(ListCycleCountsResponse {
+            cycle_counts,
+            limit,
+            offset,
+        })
+    }
+}
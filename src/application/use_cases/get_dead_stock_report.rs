@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::entities::inventory::DeadStockItem;
+use crate::domain::services::stock_repository::StockRepository;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use rust_decimal::Decimal;
+
+/// Days without an outbound movement before stock counts as dead, by default
+/// and at most
+pub const DEFAULT_DEAD_STOCK_DAYS: i64 = 90;
+pub const MAX_DEAD_STOCK_DAYS: i64 = 3650;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadStockReportResponse {
+    pub days: i64,
+    /// Items that haven't shipped since this moment are listed
+    pub since: DateTime<Utc>,
+    pub location_id: Option<Uuid>,
+    pub items: Vec<DeadStockItem>,
+    pub total_quantity: Decimal,
+    pub total_value: Decimal,
+}
+
+/// Stock on hand that hasn't moved out in a while, and the money tied up in
+/// it, highest value first
+pub struct GetDeadStockReportUseCase<S: StockRepository> {
+    stock_repository: Arc<S>,
+}
+
+impl<S: StockRepository> GetDeadStockReportUseCase<S> {
+    pub fn new(stock_repository: Arc<S>) -> Self {
+        Self { stock_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        days: Option<i64>,
+        location_id: Option<Uuid>,
+    ) -> Result<DeadStockReportResponse, DomainError> {
+        let days = days.unwrap_or(DEFAULT_DEAD_STOCK_DAYS);
+        if !(1..=MAX_DEAD_STOCK_DAYS).contains(&days) {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.field_between",
+                &[
+                    ("field", &"days"),
+                    ("min", &1),
+                    ("max", &MAX_DEAD_STOCK_DAYS),
+                ],
+            )));
+        }
+
+        let since = Utc::now() - Duration::days(days);
+        let items = self.stock_repository.dead_stock(since, location_id).await?;
+
+        Ok(DeadStockReportResponse {
+            days,
+            since,
+            location_id,
+            total_quantity: items.iter().map(|item| item.quantity_on_hand).sum(),
+            total_value: items.iter().map(|item| item.value).sum(),
+            items,
+        })
+    }
+}
@@ -1,6 +1,12 @@
+use crate::domain::entities::item::Item;
 use crate::domain::services::item_repository::ItemRepository;
+use crate::shared::batch_get::{in_request_order, unique_keys};
 use crate::shared::error::DomainError;
+use crate::shared::etag::entity_etag;
+use crate::shared::i18n::Message;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -18,23 +24,40 @@ pub struct GetItemResponse {
     pub category: Option<String>,
     pub unit: String,
     pub barcode: Option<String>,
-    pub cost_price: f64,
-    pub sale_price: Option<f64>,
-    pub reorder_point: Option<i32>,
-    pub reorder_qty: Option<i32>,
+    pub cost_price: Decimal,
+    pub sale_price: Option<Decimal>,
+    pub reorder_point: Option<Decimal>,
+    pub reorder_qty: Option<Decimal>,
+    pub quantity_precision: u32,
     pub weight: Option<f64>,
     pub dimensions: Option<serde_json::Value>,
     pub metadata: Option<serde_json::Value>,
+    pub product_id: Option<Uuid>,
+    pub variant_attributes: Option<BTreeMap<String, String>>,
     pub active: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub etag: String,
 }
 
-pub struct GetItemUseCase<R: ItemRepository> {
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchGetItemsRequest {
+    pub ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchGetItemsResponse {
+    /// In the order asked for, once each
+    pub items: Vec<GetItemResponse>,
+    /// IDs with no item in the tenant
+    pub missing: Vec<Uuid>,
+}
+
+pub struct GetItemUseCase<R: ItemRepository + ?Sized> {
     item_repository: Arc<R>,
 }
 
-impl<R: ItemRepository> GetItemUseCase<R> {
+impl<R: ItemRepository + ?Sized> GetItemUseCase<R> {
     pub fn new(item_repository: Arc<R>) -> Self {
         Self { item_repository }
     }
@@ -46,11 +69,33 @@ impl<R: ItemRepository> GetItemUseCase<R> {
             .find_by_id(request.id)
             .await?
             .ok_or_else(|| {
-                DomainError::ValidationError(format!("Item with ID {} not found", request.id))
+                DomainError::NotFound(Message::localized(
+                    "error.item_not_found",
+                    &[("id", &request.id)],
+                ))
             })?;
 
-        // Return response
-        Ok(GetItemResponse {
+        Ok(GetItemResponse::from(item))
+    }
+
+    /// Fetch up to 500 items in one query
+    pub async fn execute_batch(
+        &self,
+        request: BatchGetItemsRequest,
+    ) -> Result<BatchGetItemsResponse, DomainError> {
+        let ids = unique_keys(request.ids)?;
+        let found = self.item_repository.find_by_ids(&ids).await?;
+        let (items, missing) = in_request_order(&ids, found, |item| item.id);
+        Ok(BatchGetItemsResponse {
+            items: items.into_iter().map(GetItemResponse::from).collect(),
+            missing,
+        })
+    }
+}
+
+impl From<Item> for GetItemResponse {
+    fn from(item: Item) -> Self {
+        Self {
             id: item.id,
             sku: item.sku,
             name: item.name,
@@ -62,14 +107,18 @@ impl<R: ItemRepository> GetItemUseCase<R> {
             sale_price: item.sale_price,
             reorder_point: item.reorder_point,
             reorder_qty: item.reorder_qty,
+            quantity_precision: item.quantity_precision,
             weight: item.weight,
             dimensions: item
                 .dimensions
                 .map(|d| serde_json::to_value(d).unwrap_or(serde_json::Value::Null)),
             metadata: item.metadata,
+            product_id: item.product_id,
+            variant_attributes: item.variant_attributes,
             active: item.active,
             created_at: item.created_at,
+            etag: entity_etag(item.id, item.updated_at),
             updated_at: item.updated_at,
-        })
+        }
     }
 }
@@ -17,6 +17,9 @@ pub struct GetJobStatusResponse {
     pub job_type: String,
     pub status: String,
     pub progress: i32,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub cancel_requested: bool,
     pub result_url: Option<String>,
     pub errors: Option<Vec<JobError>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -25,6 +28,26 @@ pub struct GetJobStatusResponse {
     pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+impl From<Job> for GetJobStatusResponse {
+    fn from(job: Job) -> Self {
+        Self {
+            job_id: job.job_id,
+            job_type: job.job_type,
+            status: job.status.to_string(),
+            progress: job.progress,
+            attempts: job.attempts,
+            max_attempts: job.max_attempts,
+            cancel_requested: job.cancel_requested,
+            result_url: job.result_url,
+            errors: job.errors,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+            started_at: job.started_at,
+            completed_at: job.completed_at,
+        }
+    }
+}
+
 pub struct GetJobStatusUseCase<S: JobService> {
     job_service: Arc<S>,
 }
@@ -43,20 +66,6 @@ impl<S: JobService> GetJobStatusUseCase<S> {
             .get_job_status(request.tenant_id, &request.job_id)
             .await?;
 
-        match job {
-            Some(job) => Ok(Some(GetJobStatusResponse {
-                job_id: job.job_id,
-                job_type: job.job_type,
-                status: job.status.to_string(),
-                progress: job.progress,
-                result_url: job.result_url,
-                errors: job.errors,
-                created_at: job.created_at,
-                updated_at: job.updated_at,
-                started_at: job.started_at,
-                completed_at: job.completed_at,
-            })),
-            None => Ok(None),
-        }
+        Ok(job.map(GetJobStatusResponse::from))
     }
 }
@@ -1,6 +1,8 @@
 use crate::domain::entities::location::{Location, LocationAddress, LocationType};
+use crate::domain::entities::location_capacity::LocationCapacity;
 use crate::domain::services::location_repository::LocationRepository;
 use crate::shared::error::DomainError;
+use crate::shared::etag::entity_etag;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
@@ -18,15 +20,17 @@ pub struct GetLocationResponse {
     pub address: Option<LocationAddress>,
     pub r#type: Option<String>,
     pub active: bool,
+    pub capacity: LocationCapacity,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub etag: String,
 }
 
-pub struct GetLocationUseCase<R: LocationRepository> {
+pub struct GetLocationUseCase<R: LocationRepository + ?Sized> {
     location_repository: Arc<R>,
 }
 
-impl<R: LocationRepository> GetLocationUseCase<R> {
+impl<R: LocationRepository + ?Sized> GetLocationUseCase<R> {
     pub fn new(location_repository: Arc<R>) -> Self {
         Self {
             location_repository,
@@ -42,7 +46,7 @@ impl<R: LocationRepository> GetLocationUseCase<R> {
             .find_by_id(request.id)
             .await?
             .ok_or_else(|| {
-                DomainError::NotFound(format!("Location with id {} not found", request.id))
+                DomainError::NotFound(format!("Location with id {} not found", request.id).into())
             })?;
 
         Ok(GetLocationResponse {
@@ -52,6 +56,8 @@ impl<R: LocationRepository> GetLocationUseCase<R> {
             address: location.address,
             r#type: location.r#type.map(|t| t.as_str().to_string()),
             active: location.active,
+            capacity: location.capacity,
+            etag: entity_etag(location.id, location.updated_at),
             created_at: location.created_at,
             updated_at: location.updated_at,
         })
@@ -0,0 +1,158 @@
+use crate::domain::entities::location_capacity::{
+    CapacityEnforcement, CapacityUsage, CapacityWarning, LocationUtilization, StockFootprint,
+};
+use crate::domain::entities::putaway::PutawayCandidate;
+use crate::domain::services::location_capacity_repository::LocationCapacityRepository;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocationUtilizationReportResponse {
+    pub locations: Vec<LocationUtilization>,
+    /// Locations holding more than one of their limits allows
+    pub over_capacity: usize,
+}
+
+/// Check that putting `incoming` item quantities away at a location keeps it
+/// within capacity. Breaches the receipt doesn't add to (the location was
+/// already over, or the items aren't measured for that limit) are ignored.
+/// An enforcing location refuses the receipt; a warning one reports it.
+pub async fn check_putaway_capacity(
+    capacity_repository: &dyn LocationCapacityRepository,
+    location_id: Uuid,
+    incoming: &[(Uuid, Decimal)],
+) -> Result<Option<CapacityWarning>, DomainError> {
+    // An unknown or inactive location is the receipt's to reject
+    let Some(location) = capacity_repository
+        .find_location_stock(Some(&[location_id]))
+        .await?
+        .into_iter()
+        .next()
+    else {
+        return Ok(None);
+    };
+    if !location.capacity.is_bounded() || incoming.is_empty() {
+        return Ok(None);
+    }
+
+    let item_ids: Vec<Uuid> = incoming.iter().map(|(item_id, _)| *item_id).collect();
+    let incoming: Vec<StockFootprint> = capacity_repository
+        .find_footprints(&item_ids)
+        .await?
+        .into_iter()
+        .map(|footprint| StockFootprint {
+            quantity: incoming
+                .iter()
+                .filter(|(item_id, _)| *item_id == footprint.item_id)
+                .map(|(_, quantity)| *quantity)
+                .sum(),
+            footprint,
+        })
+        .collect();
+
+    let before = CapacityUsage::of(&location.stock);
+    let after = CapacityUsage::of(&location.with_incoming(&incoming));
+    let mut breaches = location.capacity.breaches(&after);
+    breaches.retain(|b| b.projected > before.of_dimension(b.dimension));
+    if breaches.is_empty() {
+        return Ok(None);
+    }
+
+    match location.capacity.enforcement {
+        CapacityEnforcement::Enforce => Err(DomainError::BusinessLogicError(
+            format!(
+                "Putting this receipt away at location {} would exceed its capacity: {}",
+                location.name,
+                breaches
+                    .iter()
+                    .map(|b| format!("{:?} {} of {}", b.dimension, b.projected, b.capacity))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .into(),
+        )),
+        CapacityEnforcement::Warn => Ok(Some(CapacityWarning {
+            location_id,
+            breaches,
+        })),
+    }
+}
+
+/// Cap each putaway candidate at what its location's capacity still has room
+/// for of the item
+pub async fn limit_candidates_to_capacity(
+    capacity_repository: &dyn LocationCapacityRepository,
+    item_id: Uuid,
+    candidates: &mut [PutawayCandidate],
+) -> Result<(), DomainError> {
+    if candidates.is_empty() {
+        return Ok(());
+    }
+    let Some(footprint) = capacity_repository
+        .find_footprints(&[item_id])
+        .await?
+        .into_iter()
+        .next()
+    else {
+        return Ok(());
+    };
+
+    let location_ids: Vec<Uuid> = candidates.iter().map(|c| c.rule.location_id).collect();
+    let locations = capacity_repository
+        .find_location_stock(Some(&location_ids))
+        .await?;
+    for candidate in candidates.iter_mut() {
+        if let Some(location) = locations
+            .iter()
+            .find(|l| l.location_id == candidate.rule.location_id)
+        {
+            candidate.capacity_room = location.capacity.room_for(
+                &CapacityUsage::of(&location.stock),
+                &footprint,
+                location.item_on_hand(item_id),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Each active location's use of its volume, weight and pallet capacity
+pub struct GetLocationUtilizationReportUseCase<C: LocationCapacityRepository> {
+    capacity_repository: Arc<C>,
+}
+
+impl<C: LocationCapacityRepository> GetLocationUtilizationReportUseCase<C> {
+    pub fn new(capacity_repository: Arc<C>) -> Self {
+        Self {
+            capacity_repository,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        location_id: Option<Uuid>,
+    ) -> Result<LocationUtilizationReportResponse, DomainError> {
+        let location_ids = location_id.map(|id| vec![id]);
+        let stock = self
+            .capacity_repository
+            .find_location_stock(location_ids.as_deref())
+            .await?;
+        if let (Some(location_id), true) = (location_id, stock.is_empty()) {
+            return Err(DomainError::NotFound(Message::localized(
+                "error.location_not_found",
+                &[("id", &location_id)],
+            )));
+        }
+
+        let locations: Vec<LocationUtilization> =
+            stock.iter().map(LocationUtilization::of).collect();
+        Ok(LocationUtilizationReportResponse {
+            over_capacity: locations.iter().filter(|l| l.over_capacity).count(),
+            locations,
+        })
+    }
+}
@@ -11,10 +11,11 @@ use crate::domain::{
         stock_repository::StockRepository,
     },
 };
+use rust_decimal::Decimal;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetLowStockReportRequest {
-    pub threshold: i32,
+    pub threshold: Decimal,
     pub limit: i64,
     pub cursor: Option<String>,
 }
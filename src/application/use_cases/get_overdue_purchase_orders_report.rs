@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::entities::purchase_order::OverduePurchaseOrderLine;
+use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
+use crate::shared::error::DomainError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OverduePurchaseOrdersReportResponse {
+    /// Lines due before this moment and still short are listed
+    pub as_of: DateTime<Utc>,
+    pub supplier_id: Option<Uuid>,
+    pub lines: Vec<OverduePurchaseOrderLine>,
+    /// Distinct purchase orders with at least one overdue line
+    pub total_purchase_orders: usize,
+    pub total_qty_outstanding: Decimal,
+}
+
+/// Purchase order lines still awaited past the date the supplier promised, or
+/// the expected date where nothing was promised, most overdue first
+pub struct GetOverduePurchaseOrdersReportUseCase<R: PurchaseOrderRepository> {
+    purchase_order_repository: Arc<R>,
+}
+
+impl<R: PurchaseOrderRepository> GetOverduePurchaseOrdersReportUseCase<R> {
+    pub fn new(purchase_order_repository: Arc<R>) -> Self {
+        Self {
+            purchase_order_repository,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        as_of: Option<DateTime<Utc>>,
+        supplier_id: Option<Uuid>,
+    ) -> Result<OverduePurchaseOrdersReportResponse, DomainError> {
+        let as_of = as_of.unwrap_or_else(Utc::now);
+        let lines = self
+            .purchase_order_repository
+            .overdue_lines(as_of, supplier_id)
+            .await?;
+
+        let mut po_ids: Vec<Uuid> = lines.iter().map(|line| line.po_id).collect();
+        po_ids.sort();
+        po_ids.dedup();
+
+        Ok(OverduePurchaseOrdersReportResponse {
+            as_of,
+            supplier_id,
+            total_purchase_orders: po_ids.len(),
+            total_qty_outstanding: lines.iter().map(|line| line.qty_outstanding).sum(),
+            lines,
+        })
+    }
+}
@@ -1,6 +1,10 @@
-use crate::domain::entities::purchase_order::PurchaseOrder;
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::purchase_order::{PurchaseOrder, PurchaseOrderReceipt};
 use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::pagination::{Page, PageRequest};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
@@ -12,42 +16,36 @@ pub struct GetPurchaseOrderResponse {
     pub supplier_id: Uuid,
     pub status: String,
     pub expected_date: Option<chrono::DateTime<chrono::Utc>>,
-    pub total_amount: f64,
+    pub total_amount: Decimal,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange_rate: Option<Decimal>,
     pub lines: Vec<PurchaseOrderLineResponse>,
     pub created_by: Uuid,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub etag: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PurchaseOrderLineResponse {
     pub id: Uuid,
     pub item_id: Uuid,
-    pub qty_ordered: i32,
-    pub qty_received: i32,
-    pub unit_cost: f64,
-    pub line_total: f64,
-}
-
-pub struct GetPurchaseOrderUseCase<R: PurchaseOrderRepository> {
-    purchase_order_repository: Arc<R>,
+    pub qty_ordered: Decimal,
+    pub qty_received: Decimal,
+    pub unit_cost: Decimal,
+    pub line_total: Decimal,
+    pub expected_date: Option<chrono::DateTime<chrono::Utc>>,
+    /// The date the supplier last confirmed, and when they confirmed it
+    pub promised_date: Option<chrono::DateTime<chrono::Utc>>,
+    pub promised_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-impl<R: PurchaseOrderRepository> GetPurchaseOrderUseCase<R> {
-    pub fn new(purchase_order_repository: Arc<R>) -> Self {
-        Self {
-            purchase_order_repository,
-        }
-    }
-
-    pub async fn execute(&self, id: Uuid) -> Result<GetPurchaseOrderResponse, DomainError> {
-        let po = self
-            .purchase_order_repository
-            .find_by_id(id)
-            .await?
-            .ok_or_else(|| DomainError::ValidationError("Purchase order not found".to_string()))?;
-
-        Ok(GetPurchaseOrderResponse {
+impl From<PurchaseOrder> for GetPurchaseOrderResponse {
+    fn from(po: PurchaseOrder) -> Self {
+        let etag = po.etag();
+        GetPurchaseOrderResponse {
             id: po.id,
             po_number: po.po_number,
             supplier_id: po.supplier_id,
@@ -70,9 +68,14 @@ impl<R: PurchaseOrderRepository> GetPurchaseOrderUseCase<R> {
                 crate::domain::entities::purchase_order::PurchaseOrderStatus::Cancelled => {
                     "CANCELLED".to_string()
                 }
+                crate::domain::entities::purchase_order::PurchaseOrderStatus::Closed => {
+                    "CLOSED".to_string()
+                }
             },
             expected_date: po.expected_date,
             total_amount: po.total_amount,
+            currency: po.currency,
+            exchange_rate: po.exchange_rate,
             lines: po
                 .lines
                 .into_iter()
@@ -83,11 +86,72 @@ impl<R: PurchaseOrderRepository> GetPurchaseOrderUseCase<R> {
                     qty_received: line.qty_received,
                     unit_cost: line.unit_cost,
                     line_total: line.line_total,
+                    expected_date: line.expected_date,
+                    promised_date: line.promised_date,
+                    promised_at: line.promised_at,
                 })
                 .collect(),
             created_by: po.created_by,
             created_at: po.created_at,
             updated_at: po.updated_at,
-        })
+            etag,
+        }
+    }
+}
+
+pub struct GetPurchaseOrderUseCase<R: PurchaseOrderRepository> {
+    purchase_order_repository: Arc<R>,
+}
+
+impl<R: PurchaseOrderRepository> GetPurchaseOrderUseCase<R> {
+    pub fn new(purchase_order_repository: Arc<R>) -> Self {
+        Self {
+            purchase_order_repository,
+        }
+    }
+
+    pub async fn execute(&self, id: Uuid) -> Result<GetPurchaseOrderResponse, DomainError> {
+        let po = self
+            .purchase_order_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_not_found",
+                    &[("id", &id)],
+                ))
+            })?;
+
+        Ok(po.into())
+    }
+
+    /// Receiving history of a purchase order, oldest receipt first
+    pub async fn receipts(&self, id: Uuid) -> Result<Vec<PurchaseOrderReceipt>, DomainError> {
+        self.purchase_order_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_not_found",
+                    &[("id", &id)],
+                ))
+            })?;
+
+        self.purchase_order_repository.list_receipts(id).await
+    }
+
+    pub async fn list(
+        &self,
+        filter: &ListFilter,
+        page: PageRequest,
+    ) -> Result<Page<GetPurchaseOrderResponse>, DomainError> {
+        let (purchase_orders, total_count) = tokio::try_join!(
+            self.purchase_order_repository.list(filter, &page),
+            self.purchase_order_repository.count(filter)
+        )?;
+
+        Ok(purchase_orders
+            .map(Into::into)
+            .with_total_count(total_count))
     }
 }
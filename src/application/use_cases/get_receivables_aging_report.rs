@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::entities::invoice::ReceivablesAging;
+use crate::domain::services::invoice_repository::InvoiceRepository;
+use crate::shared::error::DomainError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReceivablesAgingReportResponse {
+    /// Invoices are aged by how far past due they are at this moment
+    pub as_of: DateTime<Utc>,
+    pub customers: Vec<ReceivablesAging>,
+    /// All customers' buckets added up
+    pub totals: ReceivablesAging,
+}
+
+/// Unpaid invoice balances per customer in current, 1-30, 31-60, 61-90 and
+/// over-90-days-past-due buckets, in the tenant's base currency, largest
+/// balance first
+pub struct GetReceivablesAgingReportUseCase<R: InvoiceRepository> {
+    invoice_repository: Arc<R>,
+}
+
+impl<R: InvoiceRepository> GetReceivablesAgingReportUseCase<R> {
+    pub fn new(invoice_repository: Arc<R>) -> Self {
+        Self { invoice_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        as_of: Option<DateTime<Utc>>,
+    ) -> Result<ReceivablesAgingReportResponse, DomainError> {
+        let as_of = as_of.unwrap_or_else(Utc::now);
+        let invoices = self.invoice_repository.open_invoices().await?;
+
+        let mut by_customer: BTreeMap<Option<Uuid>, ReceivablesAging> = BTreeMap::new();
+        let mut totals = ReceivablesAging::default();
+        for invoice in &invoices {
+            by_customer
+                .entry(invoice.customer_id)
+                .or_insert_with(|| ReceivablesAging {
+                    customer_id: invoice.customer_id,
+                    ..Default::default()
+                })
+                .add(invoice, as_of)?;
+            totals.add(invoice, as_of)?;
+        }
+
+        let mut customers: Vec<ReceivablesAging> = by_customer.into_values().collect();
+        customers.sort_by_key(|aging| std::cmp::Reverse(aging.total));
+
+        Ok(ReceivablesAgingReportResponse {
+            as_of,
+            customers,
+            totals,
+        })
+    }
+}
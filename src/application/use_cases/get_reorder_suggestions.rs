@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::application::use_cases::forecast_demand::forecast_items;
+use crate::domain::entities::forecast::ForecastQuery;
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::replenishment::{
+    item_supplier, ReorderPointSource, ReorderSuggestion, SupplierReorderGroup,
+};
+use crate::domain::services::{
+    forecast_repository::ForecastRepository, item_repository::ItemRepository,
+    stock_repository::StockRepository, time_zone_service::TimeZoneService,
+};
+use crate::shared::error::DomainError;
+use crate::shared::pagination::{PageRequest, MAX_PAGE_LIMIT};
+use rust_decimal::Decimal;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReorderSuggestionsResponse {
+    pub suppliers: Vec<SupplierReorderGroup>,
+    pub item_count: usize,
+    pub total_cost: Decimal,
+}
+
+/// Compares every item's available stock with its reorder point and proposes
+/// purchase order lines, grouped by supplier
+pub struct GetReorderSuggestionsUseCase<T: ItemRepository, S: StockRepository> {
+    item_repository: Arc<T>,
+    stock_repository: Arc<S>,
+    forecast_repository: Arc<dyn ForecastRepository>,
+    time_zone_service: Arc<dyn TimeZoneService>,
+}
+
+impl<T: ItemRepository, S: StockRepository> GetReorderSuggestionsUseCase<T, S> {
+    pub fn new(
+        item_repository: Arc<T>,
+        stock_repository: Arc<S>,
+        forecast_repository: Arc<dyn ForecastRepository>,
+        time_zone_service: Arc<dyn TimeZoneService>,
+    ) -> Self {
+        Self {
+            item_repository,
+            stock_repository,
+            forecast_repository,
+            time_zone_service,
+        }
+    }
+
+    /// `supplier_id` restricts the report to one supplier's items.
+    ///
+    /// With `forecast`, reorder points are dynamic: an item that has sold over
+    /// the forecast's history is reordered at its forecast reorder point, and
+    /// only items without sales fall back to their own `reorder_point`.
+    pub async fn execute(
+        &self,
+        supplier_id: Option<Uuid>,
+        forecast: Option<&ForecastQuery>,
+    ) -> Result<ReorderSuggestionsResponse, DomainError> {
+        let mut suggestions = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = self
+                .item_repository
+                .list(
+                    &ListFilter::default(),
+                    &PageRequest::new(Some(MAX_PAGE_LIMIT), cursor),
+                )
+                .await?;
+
+            let candidates: Vec<_> = page
+                .data
+                .iter()
+                .filter(|item| item.active && (forecast.is_some() || item.reorder_point.is_some()))
+                .filter(|item| supplier_id.is_none() || supplier_id == item_supplier(item).0)
+                .collect();
+            let forecasts = match forecast {
+                Some(query) => {
+                    let today = self.time_zone_service.today().await?;
+                    forecast_items(&*self.forecast_repository, &candidates, query, today).await?
+                }
+                None => Default::default(),
+            };
+
+            for item in candidates {
+                let (reorder_point, source) = match forecasts.get(&item.id) {
+                    Some(f) if f.history_total > Decimal::ZERO => {
+                        (f.reorder_point, ReorderPointSource::Forecast)
+                    }
+                    _ => match item.reorder_point {
+                        Some(reorder_point) => (reorder_point, ReorderPointSource::Static),
+                        None => continue,
+                    },
+                };
+                let stock_levels = self.stock_repository.get_item_stock_levels(item.id).await?;
+                if let Some(suggestion) = ReorderSuggestion::with_reorder_point(
+                    item,
+                    reorder_point,
+                    source,
+                    &stock_levels,
+                ) {
+                    let (item_supplier_id, supplier_name) = item_supplier(item);
+                    suggestions.push((item_supplier_id, supplier_name, suggestion));
+                }
+            }
+
+            match page.cursor.next_cursor {
+                Some(next) if page.cursor.has_more => cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        let suppliers = SupplierReorderGroup::group(suggestions);
+        Ok(ReorderSuggestionsResponse {
+            item_count: suppliers.iter().map(|g| g.lines.len()).sum(),
+            total_cost: suppliers.iter().map(|g| g.total_cost).sum(),
+            suppliers,
+        })
+    }
+}
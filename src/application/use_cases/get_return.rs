@@ -1,6 +1,8 @@
+use crate::domain::entities::list_filter::ListFilter;
 use crate::domain::entities::returns::{Return, ReturnLine};
 use crate::domain::services::return_repository::ReturnRepository;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -12,6 +14,12 @@ pub struct GetReturnResponse {
     pub lines: Vec<ReturnLine>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct SalesOrderReturnsResponse {
+    pub sales_order_id: Uuid,
+    pub returns: Vec<GetReturnResponse>,
+}
+
 pub struct GetReturnUseCase<R: ReturnRepository> {
     return_repository: Arc<R>,
 }
@@ -26,7 +34,9 @@ impl<R: ReturnRepository> GetReturnUseCase<R> {
             .return_repository
             .find_by_id(return_id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Return {} not found", return_id)))?;
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Return {} not found", return_id).into())
+            })?;
 
         Ok(GetReturnResponse {
             return_entity,
@@ -42,11 +52,53 @@ impl<R: ReturnRepository> GetReturnUseCase<R> {
             .return_repository
             .find_by_return_number(return_number)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Return {} not found", return_number)))?;
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Return {} not found", return_number).into())
+            })?;
 
         Ok(GetReturnResponse {
             return_entity,
             lines,
         })
     }
+
+    /// The returns made against a sales order, oldest first
+    pub async fn for_sales_order(
+        &self,
+        sales_order_id: Uuid,
+    ) -> Result<SalesOrderReturnsResponse, DomainError> {
+        let returns = self
+            .return_repository
+            .find_by_sales_order(sales_order_id)
+            .await?
+            .into_iter()
+            .map(|(return_entity, lines)| GetReturnResponse {
+                return_entity,
+                lines,
+            })
+            .collect();
+
+        Ok(SalesOrderReturnsResponse {
+            sales_order_id,
+            returns,
+        })
+    }
+
+    pub async fn list(
+        &self,
+        filter: &ListFilter,
+        page: PageRequest,
+    ) -> Result<Page<GetReturnResponse>, DomainError> {
+        let (returns, total_count) = tokio::try_join!(
+            self.return_repository.list(filter, &page),
+            self.return_repository.count(filter)
+        )?;
+
+        Ok(returns
+            .map(|(return_entity, lines)| GetReturnResponse {
+                return_entity,
+                lines,
+            })
+            .with_total_count(total_count))
+    }
 }
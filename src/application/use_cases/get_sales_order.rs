@@ -1,6 +1,8 @@
+use crate::domain::entities::list_filter::ListFilter;
 use crate::domain::entities::sales_order::{SalesOrder, SalesOrderLine};
 use crate::domain::services::sales_order_repository::SalesOrderRepository;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -9,6 +11,18 @@ use uuid::Uuid;
 pub struct SalesOrderWithLines {
     pub sales_order: SalesOrder,
     pub lines: Vec<SalesOrderLine>,
+    pub etag: String,
+}
+
+impl SalesOrderWithLines {
+    pub fn new(sales_order: SalesOrder, lines: Vec<SalesOrderLine>) -> Self {
+        let etag = sales_order.etag();
+        Self {
+            sales_order,
+            lines,
+            etag,
+        }
+    }
 }
 
 pub struct GetSalesOrderUseCase<T: SalesOrderRepository> {
@@ -21,13 +35,12 @@ impl<T: SalesOrderRepository> GetSalesOrderUseCase<T> {
     }
 
     pub async fn execute(&self, id: Uuid) -> Result<SalesOrderWithLines, DomainError> {
-        let (sales_order, lines) = self
-            .sales_order_repo
-            .find_by_id(id)
-            .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Sales order {} not found", id)))?;
+        let (sales_order, lines) =
+            self.sales_order_repo.find_by_id(id).await?.ok_or_else(|| {
+                DomainError::NotFound(format!("Sales order {} not found", id).into())
+            })?;
 
-        Ok(SalesOrderWithLines { sales_order, lines })
+        Ok(SalesOrderWithLines::new(sales_order, lines))
     }
 
     pub async fn execute_by_number(
@@ -38,8 +51,25 @@ impl<T: SalesOrderRepository> GetSalesOrderUseCase<T> {
             .sales_order_repo
             .find_by_so_number(so_number)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Sales order {} not found", so_number)))?;
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Sales order {} not found", so_number).into())
+            })?;
+
+        Ok(SalesOrderWithLines::new(sales_order, lines))
+    }
+
+    pub async fn list(
+        &self,
+        filter: &ListFilter,
+        page: PageRequest,
+    ) -> Result<Page<SalesOrderWithLines>, DomainError> {
+        let (sales_orders, total_count) = tokio::try_join!(
+            self.sales_order_repo.list(filter, &page),
+            self.sales_order_repo.count(filter)
+        )?;
 
-        Ok(SalesOrderWithLines { sales_order, lines })
+        Ok(sales_orders
+            .map(|(sales_order, lines)| SalesOrderWithLines::new(sales_order, lines))
+            .with_total_count(total_count))
     }
 }
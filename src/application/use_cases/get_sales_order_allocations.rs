@@ -0,0 +1,34 @@
+use crate::domain::entities::allocation::SalesOrderAllocation;
+use crate::domain::services::allocation_repository::AllocationRepository;
+use crate::shared::error::DomainError;
+use serde::Serialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct GetSalesOrderAllocationsResponse {
+    pub sales_order_id: Uuid,
+    pub allocations: Vec<SalesOrderAllocation>,
+}
+
+pub struct GetSalesOrderAllocationsUseCase<A: AllocationRepository> {
+    allocation_repo: Arc<A>,
+}
+
+impl<A: AllocationRepository> GetSalesOrderAllocationsUseCase<A> {
+    pub fn new(allocation_repo: Arc<A>) -> Self {
+        Self { allocation_repo }
+    }
+
+    pub async fn execute(
+        &self,
+        so_id: Uuid,
+    ) -> Result<GetSalesOrderAllocationsResponse, DomainError> {
+        let allocations = self.allocation_repo.find_by_sales_order(so_id).await?;
+
+        Ok(GetSalesOrderAllocationsResponse {
+            sales_order_id: so_id,
+            allocations,
+        })
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::entities::returns::ScrapTotal;
+use crate::domain::entities::time_zone::ReportBound;
+use crate::domain::services::return_repository::ReturnRepository;
+use crate::domain::services::time_zone_service::TimeZoneService;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use rust_decimal::Decimal;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScrapReportResponse {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub location_id: Option<Uuid>,
+    pub items: Vec<ScrapTotal>,
+    pub total_quantity: Decimal,
+    pub total_value: Decimal,
+}
+
+/// Totals of returned stock that was scrapped, per item, highest value first
+pub struct GetScrapReportUseCase<R: ReturnRepository> {
+    return_repository: Arc<R>,
+    time_zone_service: Arc<dyn TimeZoneService>,
+}
+
+impl<R: ReturnRepository> GetScrapReportUseCase<R> {
+    pub fn new(return_repository: Arc<R>, time_zone_service: Arc<dyn TimeZoneService>) -> Self {
+        Self {
+            return_repository,
+            time_zone_service,
+        }
+    }
+
+    /// Bounds given as dates cover whole days in the tenant's time zone, `to` included
+    pub async fn execute(
+        &self,
+        from: Option<ReportBound>,
+        to: Option<ReportBound>,
+        location_id: Option<Uuid>,
+    ) -> Result<ScrapReportResponse, DomainError> {
+        let time_zone = self.time_zone_service.time_zone().await?;
+        let from = from.map(|bound| bound.start(time_zone));
+        let to = to.map(|bound| bound.end(time_zone)).transpose()?;
+        if let (Some(from), Some(to)) = (from, to) {
+            if from >= to {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.from_before_to",
+                    &[],
+                )));
+            }
+        }
+
+        let items = self
+            .return_repository
+            .scrap_totals(from, to, location_id)
+            .await?;
+
+        Ok(ScrapReportResponse {
+            from,
+            to,
+            location_id,
+            total_quantity: items.iter().map(|item| item.quantity_scrapped).sum(),
+            total_value: items.iter().map(|item| item.value).sum(),
+            items,
+        })
+    }
+}
@@ -0,0 +1,72 @@
+use crate::domain::entities::shipment::{Shipment, ShipmentSourceType};
+use crate::domain::services::shipment_repository::ShipmentRepository;
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct ListShipmentsQuery {
+    pub source_type: Option<String>,
+    pub source_id: Option<Uuid>,
+    pub tracking_number: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListShipmentsResponse {
+    pub shipments: Vec<Shipment>,
+}
+
+pub struct GetShipmentUseCase<S: ShipmentRepository> {
+    shipment_repository: Arc<S>,
+}
+
+impl<S: ShipmentRepository> GetShipmentUseCase<S> {
+    pub fn new(shipment_repository: Arc<S>) -> Self {
+        Self {
+            shipment_repository,
+        }
+    }
+
+    pub async fn execute(&self, shipment_id: Uuid) -> Result<Shipment, DomainError> {
+        self.shipment_repository
+            .find_by_id(shipment_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Shipment {} not found", shipment_id).into())
+            })
+    }
+
+    pub async fn list(
+        &self,
+        query: ListShipmentsQuery,
+    ) -> Result<ListShipmentsResponse, DomainError> {
+        let shipments = match (query.source_type, query.source_id, query.tracking_number) {
+            (_, _, Some(tracking_number)) => {
+                self.shipment_repository
+                    .find_by_tracking_number(&tracking_number)
+                    .await?
+            }
+            (Some(source_type), Some(source_id), None) => {
+                let source_type = ShipmentSourceType::from_str(&source_type)?;
+                self.shipment_repository
+                    .find_by_source(&source_type, source_id)
+                    .await?
+            }
+            (None, None, None) => {
+                let limit = query.limit.unwrap_or(50).clamp(1, 100);
+                let offset = query.offset.unwrap_or(0).max(0);
+                self.shipment_repository.list(limit, offset).await?
+            }
+            _ => {
+                return Err(DomainError::ValidationError(
+                    "source_type and source_id must be provided together".into(),
+                ))
+            }
+        };
+
+        Ok(ListShipmentsResponse { shipments })
+    }
+}
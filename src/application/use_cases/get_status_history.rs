@@ -0,0 +1,38 @@
+use crate::domain::entities::status_history::{StatusHistoryEntity, StatusHistoryResponse};
+use crate::domain::services::status_history_repository::StatusHistoryRepository;
+use crate::shared::error::DomainError;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Who moved an order, transfer or return between statuses, and when
+pub struct GetStatusHistoryUseCase<R: StatusHistoryRepository> {
+    status_history_repository: Arc<R>,
+}
+
+impl<R: StatusHistoryRepository> GetStatusHistoryUseCase<R> {
+    pub fn new(status_history_repository: Arc<R>) -> Self {
+        Self {
+            status_history_repository,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        entity: StatusHistoryEntity,
+        entity_id: Uuid,
+    ) -> Result<StatusHistoryResponse, DomainError> {
+        let changes = self
+            .status_history_repository
+            .find_history(entity, entity_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("{} {} not found", entity.label(), entity_id).into())
+            })?;
+
+        Ok(StatusHistoryResponse {
+            entity_type: entity,
+            entity_id,
+            changes,
+        })
+    }
+}
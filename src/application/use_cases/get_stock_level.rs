@@ -4,17 +4,33 @@ use crate::domain::entities::location::Location;
 use crate::domain::services::item_repository::ItemRepository;
 use crate::domain::services::location_repository::LocationRepository;
 use crate::domain::services::stock_repository::StockRepository;
+use crate::shared::batch_get::{in_request_order, unique_keys};
 use crate::shared::error::DomainError;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GetStockLevelRequest {
     pub item_id: Uuid,
     pub location_id: Uuid,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchGetStockLevelsRequest {
+    pub pairs: Vec<GetStockLevelRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchGetStockLevelsResponse {
+    /// In the order asked for, once each
+    pub levels: Vec<StockLevelResponse>,
+    /// Pairs with no stock level, because nothing has been stocked there or
+    /// the item or location doesn't exist
+    pub missing: Vec<GetStockLevelRequest>,
+}
+
 pub struct GetStockLevelUseCase<SR: StockRepository, IR: ItemRepository, LR: LocationRepository> {
     stock_repository: Arc<SR>,
     item_repository: Arc<IR>,
@@ -59,14 +75,77 @@ impl<SR: StockRepository, IR: ItemRepository, LR: LocationRepository>
             .find_by_id(request.location_id)
             .await?;
 
-        Ok(Some(StockLevelResponse {
+        Ok(Some(Self::response(stock_level, item, location)))
+    }
+
+    /// Fetch up to 500 (item, location) stock levels with one query each for
+    /// the levels, their items and their locations
+    pub async fn execute_batch(
+        &self,
+        request: BatchGetStockLevelsRequest,
+    ) -> Result<BatchGetStockLevelsResponse, DomainError> {
+        let pairs = unique_keys(request.pairs)?;
+        let keys: Vec<(Uuid, Uuid)> = pairs
+            .iter()
+            .map(|pair| (pair.item_id, pair.location_id))
+            .collect();
+        let found = self
+            .stock_repository
+            .get_stock_levels_for_pairs(&keys)
+            .await?;
+        let (levels, missing) = in_request_order(&pairs, found, |level| GetStockLevelRequest {
+            item_id: level.item_id,
+            location_id: level.location_id,
+        });
+
+        let item_ids: HashSet<Uuid> = levels.iter().map(|level| level.item_id).collect();
+        let location_ids: HashSet<Uuid> = levels.iter().map(|level| level.location_id).collect();
+        let items: HashMap<Uuid, Item> = self
+            .item_repository
+            .find_by_ids(&item_ids.into_iter().collect::<Vec<_>>())
+            .await?
+            .into_iter()
+            .map(|item| (item.id, item))
+            .collect();
+        let locations: HashMap<Uuid, Location> = self
+            .location_repository
+            .find_by_ids(&location_ids.into_iter().collect::<Vec<_>>())
+            .await?
+            .into_iter()
+            .map(|location| (location.id, location))
+            .collect();
+
+        Ok(BatchGetStockLevelsResponse {
+            levels: levels
+                .into_iter()
+                .map(|level| {
+                    let item = items.get(&level.item_id).cloned();
+                    let location = locations.get(&level.location_id).cloned();
+                    Self::response(level, item, location)
+                })
+                .collect(),
+            missing,
+        })
+    }
+
+    fn response(
+        stock_level: StockLevel,
+        item: Option<Item>,
+        location: Option<Location>,
+    ) -> StockLevelResponse {
+        StockLevelResponse {
             item_id: stock_level.item_id,
             location_id: stock_level.location_id,
             quantity_on_hand: stock_level.quantity_on_hand,
+            quantity_reserved: stock_level.quantity_reserved,
+            quantity_quarantine: stock_level.quantity_quarantine,
+            quantity_damaged: stock_level.quantity_damaged,
+            quantity_in_transit: stock_level.quantity_in_transit,
+            available_to_promise: stock_level.available_to_promise(),
             last_movement_id: stock_level.last_movement_id,
             updated_at: stock_level.updated_at,
             item,
             location,
-        }))
+        }
     }
 }
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::domain::entities::stock_snapshot::{HistoricalStockLevels, StockQuantityFilter};
+use crate::domain::services::snapshot_repository::SnapshotRepository;
+use crate::domain::services::time_zone_service::TimeZoneService;
+use crate::shared::error::DomainError;
+
+#[derive(Debug, Deserialize)]
+pub struct GetStockLevelsAsOfRequest {
+    /// Levels at the end of this day in the tenant's time zone
+    pub date: NaiveDate,
+    pub item_id: Option<Uuid>,
+    pub location_id: Option<Uuid>,
+}
+
+/// Rebuilds on-hand quantities for a past date from the nearest earlier snapshot
+/// plus the movements recorded after it
+pub struct GetStockLevelsAsOfUseCase<R: SnapshotRepository> {
+    snapshot_repository: Arc<R>,
+    time_zone_service: Arc<dyn TimeZoneService>,
+}
+
+impl<R: SnapshotRepository> GetStockLevelsAsOfUseCase<R> {
+    pub fn new(snapshot_repository: Arc<R>, time_zone_service: Arc<dyn TimeZoneService>) -> Self {
+        Self {
+            snapshot_repository,
+            time_zone_service,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        request: GetStockLevelsAsOfRequest,
+    ) -> Result<HistoricalStockLevels, DomainError> {
+        let time_zone = self.time_zone_service.time_zone().await?;
+        if request.date > self.time_zone_service.today().await? {
+            return Err(DomainError::ValidationError(
+                "Date cannot be in the future".into(),
+            ));
+        }
+
+        let as_of = HistoricalStockLevels::end_of_day(request.date, time_zone)?;
+        let filter = StockQuantityFilter {
+            item_id: request.item_id,
+            location_id: request.location_id,
+        };
+
+        let snapshot_taken_at = self
+            .snapshot_repository
+            .latest_snapshot_before(as_of)
+            .await?;
+        let snapshot = match snapshot_taken_at {
+            Some(taken_at) => {
+                self.snapshot_repository
+                    .get_snapshot_quantities(taken_at, &filter)
+                    .await?
+            }
+            None => Vec::new(),
+        };
+        let movements_since = self
+            .snapshot_repository
+            .get_movement_totals(snapshot_taken_at, as_of, &filter)
+            .await?;
+
+        Ok(HistoricalStockLevels::reconstruct(
+            as_of,
+            snapshot_taken_at,
+            snapshot,
+            movements_since,
+        ))
+    }
+}
@@ -7,6 +7,8 @@ use crate::domain::services::item_repository::ItemRepository;
 use crate::domain::services::location_repository::LocationRepository;
 use crate::domain::services::stock_repository::StockRepository;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::pagination::{Page, PageRequest};
 
 #[derive(Clone)]
 pub struct GetStockMovementsUseCase<SR: StockRepository, IR: ItemRepository, LR: LocationRepository>
@@ -35,62 +37,62 @@ impl<SR: StockRepository, IR: ItemRepository, LR: LocationRepository>
         &self,
         item_id: Option<uuid::Uuid>,
         location_id: Option<uuid::Uuid>,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<StockMovementResponse>, DomainError> {
+        page: PageRequest,
+    ) -> Result<Page<StockMovementResponse>, DomainError> {
         // Validate pagination parameters
-        if limit <= 0 || limit > 1000 {
-            return Err(DomainError::ValidationError(
-                "Limit must be between 1 and 1000".to_string(),
-            ));
-        }
-        if offset < 0 {
-            return Err(DomainError::ValidationError(
-                "Offset must be non-negative".to_string(),
-            ));
+        if page.limit.is_some_and(|limit| limit <= 0 || limit > 1000) {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.limit_range",
+                &[],
+            )));
         }
 
         // Get stock movements based on filters
         let movements = match (item_id, location_id) {
             (Some(item_id), Some(location_id)) => {
                 self.stock_repository
-                    .get_stock_movements(item_id, location_id, limit, offset)
+                    .get_stock_movements(item_id, location_id, &page)
                     .await?
             }
             (Some(item_id), None) => {
                 self.stock_repository
-                    .get_item_movements(item_id, limit, offset)
+                    .get_item_movements(item_id, &page)
                     .await?
             }
             (None, Some(location_id)) => {
                 self.stock_repository
-                    .get_location_movements(location_id, limit, offset)
+                    .get_location_movements(location_id, &page)
                     .await?
             }
             (None, None) => {
                 return Err(DomainError::ValidationError(
-                    "Either item_id or location_id must be provided".to_string(),
+                    "Either item_id or location_id must be provided".into(),
                 ));
             }
         };
 
         // Enrich movements with item and location data
         let mut enriched_movements = Vec::new();
+        let Page {
+            data,
+            cursor,
+            total_count,
+        } = movements;
 
-        for movement in movements {
+        for movement in data {
             // Get item details
             let item = self
                 .item_repository
                 .find_by_id(movement.item_id)
                 .await?
-                .ok_or_else(|| DomainError::NotFound("Item not found".to_string()))?;
+                .ok_or_else(|| DomainError::NotFound("Item not found".into()))?;
 
             // Get location details
             let location = self
                 .location_repository
                 .find_by_id(movement.location_id)
                 .await?
-                .ok_or_else(|| DomainError::NotFound("Location not found".to_string()))?;
+                .ok_or_else(|| DomainError::NotFound("Location not found".into()))?;
 
             enriched_movements.push(StockMovementResponse {
                 id: movement.id,
@@ -103,12 +105,17 @@ impl<SR: StockRepository, IR: ItemRepository, LR: LocationRepository>
                 reason: movement.reason,
                 created_at: movement.created_at,
                 created_by: movement.created_by,
+                stock_status: movement.stock_status,
                 item: Some(item),
                 location: Some(location),
                 created_by_user: None, // TODO: Implement user lookup when needed
             });
         }
 
-        Ok(enriched_movements)
+        Ok(Page {
+            data: enriched_movements,
+            cursor,
+            total_count,
+        })
     }
 }
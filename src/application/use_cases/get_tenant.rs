@@ -53,7 +53,7 @@ mod tests {
             .expect_get_tenant()
             .returning(move |_| Ok(Some(tenant.clone())));
 
-        let use_case = GetTenantUseCase::new(mock_repo);
+        let use_case = GetTenantUseCase::new(Arc::new(mock_repo));
         let result = use_case.execute(tenant_id).await;
 
         assert!(result.is_ok());
@@ -69,7 +69,7 @@ mod tests {
         let mut mock_repo = MockTenantRepository::new();
         mock_repo.expect_get_tenant().returning(|_| Ok(None));
 
-        let use_case = GetTenantUseCase::new(mock_repo);
+        let use_case = GetTenantUseCase::new(Arc::new(mock_repo));
         let result = use_case.execute(tenant_id).await;
 
         assert!(result.is_ok());
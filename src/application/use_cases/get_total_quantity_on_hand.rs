@@ -1,3 +1,4 @@
+use rust_decimal::Decimal;
 use std::sync::Arc;
 
 use crate::domain::services::stock_repository::StockRepository;
@@ -13,12 +14,10 @@ impl<R: StockRepository> GetTotalQuantityOnHandUseCase<R> {
         Self { stock_repository }
     }
 
-    pub async fn execute(&self, item_id: uuid::Uuid) -> Result<i32, DomainError> {
+    pub async fn execute(&self, item_id: uuid::Uuid) -> Result<Decimal, DomainError> {
         // Validate item_id is not nil
         if item_id.is_nil() {
-            return Err(DomainError::ValidationError(
-                "Item ID cannot be nil".to_string(),
-            ));
+            return Err(DomainError::ValidationError("Item ID cannot be nil".into()));
         }
 
         self.stock_repository
@@ -1,6 +1,8 @@
+use crate::domain::entities::list_filter::ListFilter;
 use crate::domain::entities::transfer::{Transfer, TransferLine};
 use crate::domain::services::transfer_repository::TransferRepository;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -25,7 +27,9 @@ impl<T: TransferRepository> GetTransferUseCase<T> {
             .transfer_repo
             .find_by_id(transfer_id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Transfer {} not found", transfer_id)))?;
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Transfer {} not found", transfer_id).into())
+            })?;
 
         Ok(GetTransferResponse { transfer, lines })
     }
@@ -39,9 +43,24 @@ impl<T: TransferRepository> GetTransferUseCase<T> {
             .find_by_transfer_number(transfer_number)
             .await?
             .ok_or_else(|| {
-                DomainError::NotFound(format!("Transfer {} not found", transfer_number))
+                DomainError::NotFound(format!("Transfer {} not found", transfer_number).into())
             })?;
 
         Ok(GetTransferResponse { transfer, lines })
     }
+
+    pub async fn list(
+        &self,
+        filter: &ListFilter,
+        page: PageRequest,
+    ) -> Result<Page<GetTransferResponse>, DomainError> {
+        let (transfers, total_count) = tokio::try_join!(
+            self.transfer_repo.list(filter, &page),
+            self.transfer_repo.count(filter)
+        )?;
+
+        Ok(transfers
+            .map(|(transfer, lines)| GetTransferResponse { transfer, lines })
+            .with_total_count(total_count))
+    }
 }
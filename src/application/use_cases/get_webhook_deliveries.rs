@@ -25,11 +25,13 @@ impl<R: WebhookRepository> GetWebhookDeliveriesUseCase<R> {
             .webhook_repository
             .get_webhook(webhook_id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Webhook {} not found", webhook_id)))?;
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Webhook {} not found", webhook_id).into())
+            })?;
 
         if webhook.created_by != user_id {
             return Err(DomainError::BusinessLogicError(
-                "You can only view deliveries for your own webhooks".to_string(),
+                "You can only view deliveries for your own webhooks".into(),
             ));
         }
 
@@ -78,7 +80,9 @@ impl<R: WebhookRepository> GetWebhookDeliveryDetailsUseCase<R> {
             .webhook_repository
             .get_delivery(delivery_id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Delivery {} not found", delivery_id)))?;
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Delivery {} not found", delivery_id).into())
+            })?;
 
         // Verify webhook ownership
         let webhook = self
@@ -86,12 +90,12 @@ impl<R: WebhookRepository> GetWebhookDeliveryDetailsUseCase<R> {
             .get_webhook(delivery.webhook_id)
             .await?
             .ok_or_else(|| {
-                DomainError::NotFound(format!("Webhook {} not found", delivery.webhook_id))
+                DomainError::NotFound(format!("Webhook {} not found", delivery.webhook_id).into())
             })?;
 
         if webhook.created_by != user_id {
             return Err(DomainError::BusinessLogicError(
-                "You can only view deliveries for your own webhooks".to_string(),
+                "You can only view deliveries for your own webhooks".into(),
             ));
         }
 
@@ -101,7 +105,7 @@ impl<R: WebhookRepository> GetWebhookDeliveryDetailsUseCase<R> {
             .get_event(delivery.event_id)
             .await?
             .ok_or_else(|| {
-                DomainError::NotFound(format!("Event {} not found", delivery.event_id))
+                DomainError::NotFound(format!("Event {} not found", delivery.event_id).into())
             })?;
 
         Ok(WebhookDeliveryDetails {
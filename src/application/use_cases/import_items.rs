@@ -0,0 +1,508 @@
+use crate::application::use_cases::create_item::{CreateItemRequest, CreateItemUseCase};
+use crate::domain::entities::item::{Item, UpdateItemRequest};
+use crate::domain::entities::job::{CreateJobRequest, Job, JobCancellation, JobError};
+use crate::domain::services::blob_storage::BlobStorage;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::job_service::JobService;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use crate::shared::spooled_file::SpooledFile;
+use calamine::{open_workbook_from_rs, Reader, Xlsx};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufReader, Cursor, Read, Seek};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const IMPORT_ITEMS_JOB_TYPE: &str = "import_items";
+
+/// Number of rows created between job progress updates
+const IMPORT_BATCH_SIZE: usize = 500;
+
+/// Hard cap on rows accepted in a single upload
+const MAX_IMPORT_ROWS: usize = 50_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFileFormat {
+    Csv,
+    Xlsx,
+}
+
+impl ImportFileFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportFileFormat::Csv => "csv",
+            ImportFileFormat::Xlsx => "xlsx",
+        }
+    }
+
+    /// Resolve the upload format from a Content-Type header value
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next().unwrap_or("").trim();
+        match mime {
+            "text/csv" | "application/csv" | "text/plain" => Some(ImportFileFormat::Csv),
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => {
+                Some(ImportFileFormat::Xlsx)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ImportItemsRequest {
+    pub format: ImportFileFormat,
+    pub file: SpooledFile,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportItemsResponse {
+    pub job_id: String,
+    pub status: String,
+    pub total_rows: usize,
+    pub rejected_rows: usize,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// What an import job carries: the uploaded file stays in blob storage so the
+/// job can be picked up (and retried) by any worker
+#[derive(Debug, Serialize, Deserialize)]
+struct ImportJobPayload {
+    format: ImportFileFormat,
+    storage_key: String,
+    total_rows: usize,
+    rejected_rows: usize,
+}
+
+/// Result of running an import job
+#[derive(Debug)]
+pub struct ImportItemsOutcome {
+    pub created: usize,
+    /// Rejected and failed rows, in row order
+    pub errors: Vec<JobError>,
+    /// Stopped early because the job was cancelled; rows created so far are kept
+    pub cancelled: bool,
+}
+
+/// A validated row ready to be created, keyed by its spreadsheet row number
+#[derive(Debug)]
+struct ImportRow {
+    row: i32,
+    request: CreateItemRequest,
+}
+
+pub struct ImportItemsUseCase<
+    R: ItemRepository + 'static,
+    D: WebhookDispatcher + 'static,
+    S: JobService + 'static,
+> {
+    create_item_use_case: Arc<CreateItemUseCase<R, D>>,
+    job_service: Arc<S>,
+    blob_storage: Arc<dyn BlobStorage>,
+}
+
+impl<R: ItemRepository + 'static, D: WebhookDispatcher + 'static, S: JobService + 'static>
+    ImportItemsUseCase<R, D, S>
+{
+    pub fn new(
+        create_item_use_case: Arc<CreateItemUseCase<R, D>>,
+        job_service: Arc<S>,
+        blob_storage: Arc<dyn BlobStorage>,
+    ) -> Self {
+        Self {
+            create_item_use_case,
+            job_service,
+            blob_storage,
+        }
+    }
+
+    /// Validate the upload and queue it; the items are created by the job worker
+    /// so large files don't block the request
+    pub async fn execute(
+        &self,
+        request: ImportItemsRequest,
+        tenant_id: Uuid,
+    ) -> Result<ImportItemsResponse, DomainError> {
+        let records = parse_records(request.format, BufReader::new(request.file.open()?))?;
+
+        if records.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Import file contains no item rows".into(),
+            ));
+        }
+        if records.len() > MAX_IMPORT_ROWS {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Import file exceeds the maximum of {} rows",
+                    MAX_IMPORT_ROWS
+                )
+                .into(),
+            ));
+        }
+
+        let total_rows = records.len();
+        let (_, errors) = validate_records(records);
+        let rejected_rows = errors.len();
+
+        let storage_key = format!(
+            "tenants/{}/imports/{}.{}",
+            tenant_id,
+            Uuid::new_v4(),
+            request.format.as_str()
+        );
+        let content_type = match request.format {
+            ImportFileFormat::Csv => "text/csv",
+            ImportFileFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        };
+        self.blob_storage
+            .put_file(&storage_key, content_type, request.file.path())
+            .await?;
+
+        let payload = ImportJobPayload {
+            format: request.format,
+            storage_key,
+            total_rows,
+            rejected_rows,
+        };
+        let job = self
+            .job_service
+            .enqueue_job(
+                tenant_id,
+                CreateJobRequest {
+                    job_type: IMPORT_ITEMS_JOB_TYPE.to_string(),
+                    payload: serde_json::to_value(&payload).map_err(|e| {
+                        DomainError::ValidationError(
+                            format!("Failed to serialize payload: {}", e).into(),
+                        )
+                    })?,
+                    max_attempts: None,
+                },
+            )
+            .await?;
+
+        Ok(ImportItemsResponse {
+            job_id: job.job_id,
+            status: job.status.to_string(),
+            total_rows,
+            rejected_rows,
+            created_at: job.created_at,
+        })
+    }
+
+    /// Create the items of a queued import job, reporting progress on the job as
+    /// batches complete. A retried job re-reads the whole file, so rows created by
+    /// an earlier attempt come back as duplicate SKU errors.
+    pub async fn run(
+        &self,
+        job: &Job,
+        cancellation: &JobCancellation,
+    ) -> Result<ImportItemsOutcome, DomainError> {
+        let payload: ImportJobPayload =
+            serde_json::from_value(job.payload.clone().unwrap_or_default()).map_err(|e| {
+                DomainError::ValidationError(format!("Invalid import payload: {}", e).into())
+            })?;
+
+        let data = self.blob_storage.get(&payload.storage_key).await?;
+        let (rows, mut errors) =
+            validate_records(parse_records(payload.format, Cursor::new(data))?);
+
+        let total = rows.len() + errors.len();
+        let mut processed = errors.len();
+        let mut created = 0usize;
+
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            if cancellation.is_cancelled() {
+                return Ok(ImportItemsOutcome {
+                    created,
+                    errors,
+                    cancelled: true,
+                });
+            }
+            for import_row in rows.by_ref().take(IMPORT_BATCH_SIZE) {
+                match self
+                    .create_item_use_case
+                    .execute(import_row.request, job.tenant_id)
+                    .await
+                {
+                    Ok(_) => created += 1,
+                    Err(e) => errors.push(JobError {
+                        row: Some(import_row.row),
+                        message: e.to_string(),
+                    }),
+                }
+                processed += 1;
+            }
+
+            let progress = ((processed * 100) / total.max(1)) as i32;
+            self.job_service
+                .update_job_progress(&job.job_id, progress)
+                .await?;
+        }
+
+        errors.sort_by_key(|e| e.row);
+
+        // The file isn't needed once every row has been attempted
+        if let Err(e) = self.blob_storage.delete(&payload.storage_key).await {
+            eprintln!(
+                "Failed to remove import file {}: {:?}",
+                payload.storage_key, e
+            );
+        }
+
+        Ok(ImportItemsOutcome {
+            created,
+            errors,
+            cancelled: false,
+        })
+    }
+}
+
+fn parse_records(
+    format: ImportFileFormat,
+    reader: impl Read + Seek,
+) -> Result<Vec<ImportRecord>, DomainError> {
+    match format {
+        ImportFileFormat::Csv => parse_csv(reader),
+        ImportFileFormat::Xlsx => parse_xlsx(reader),
+    }
+}
+
+/// Raw import record: spreadsheet row number and column values keyed by header
+type ImportRecord = (i32, HashMap<String, String>);
+
+fn parse_csv(data: impl Read) -> Result<Vec<ImportRecord>, DomainError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(data);
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| DomainError::ValidationError(format!("Invalid CSV header: {}", e).into()))?
+        .iter()
+        .map(normalize_header)
+        .collect();
+
+    let mut records = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        // Header occupies row 1, so data rows start at 2
+        let row = index as i32 + 2;
+        let record = record.map_err(|e| {
+            DomainError::ValidationError(format!("Invalid CSV data at row {}: {}", row, e).into())
+        })?;
+
+        let values: Vec<String> = record.iter().map(|v| v.to_string()).collect();
+        if values.iter().all(|v| v.is_empty()) {
+            continue;
+        }
+        records.push((row, zip_record(&headers, values)));
+    }
+
+    Ok(records)
+}
+
+fn parse_xlsx(data: impl Read + Seek) -> Result<Vec<ImportRecord>, DomainError> {
+    let mut workbook: Xlsx<_> = open_workbook_from_rs(data)
+        .map_err(|e| DomainError::ValidationError(format!("Invalid XLSX file: {}", e).into()))?;
+
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or_else(|| DomainError::ValidationError("XLSX file has no worksheets".into()))?
+        .map_err(|e| {
+            DomainError::ValidationError(format!("Invalid XLSX worksheet: {}", e).into())
+        })?;
+
+    let mut rows = range.rows();
+    let headers: Vec<String> = match rows.next() {
+        Some(header_row) => header_row
+            .iter()
+            .map(|cell| normalize_header(&cell.to_string()))
+            .collect(),
+        None => return Ok(Vec::new()),
+    };
+
+    let mut records = Vec::new();
+    for (index, cells) in rows.enumerate() {
+        let row = index as i32 + 2;
+        let values: Vec<String> = cells
+            .iter()
+            .map(|cell| cell.to_string().trim().to_string())
+            .collect();
+        if values.iter().all(|v| v.is_empty()) {
+            continue;
+        }
+        records.push((row, zip_record(&headers, values)));
+    }
+
+    Ok(records)
+}
+
+fn normalize_header(header: &str) -> String {
+    header.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+fn zip_record(headers: &[String], values: Vec<String>) -> HashMap<String, String> {
+    headers
+        .iter()
+        .cloned()
+        .zip(values)
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+}
+
+/// Validate every record against the item domain rules, returning the rows that
+/// can be created and a per-row error list for the ones that cannot.
+fn validate_records(records: Vec<ImportRecord>) -> (Vec<ImportRow>, Vec<JobError>) {
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen_skus = HashSet::new();
+
+    for (row, record) in records {
+        match record_to_request(&record) {
+            Ok(request) => {
+                if !seen_skus.insert(request.sku.clone()) {
+                    errors.push(JobError {
+                        row: Some(row),
+                        message: format!("Duplicate SKU '{}' in import file", request.sku),
+                    });
+                    continue;
+                }
+                rows.push(ImportRow { row, request });
+            }
+            Err(e) => errors.push(JobError {
+                row: Some(row),
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    (rows, errors)
+}
+
+fn record_to_request(record: &HashMap<String, String>) -> Result<CreateItemRequest, DomainError> {
+    let required = |field: &str| {
+        record.get(field).cloned().ok_or_else(|| {
+            DomainError::ValidationError(format!("Missing required column '{}'", field).into())
+        })
+    };
+
+    let request = CreateItemRequest {
+        sku: required("sku")?,
+        name: required("name")?,
+        description: record.get("description").cloned(),
+        category: record.get("category").cloned(),
+        unit: required("unit")?,
+        barcode: record.get("barcode").cloned(),
+        cost_price: parse_field::<Decimal>(record, "cost_price")?.ok_or_else(|| {
+            DomainError::ValidationError("Missing required column 'cost_price'".into())
+        })?,
+        sale_price: parse_field(record, "sale_price")?,
+        reorder_point: parse_field(record, "reorder_point")?,
+        reorder_qty: parse_field(record, "reorder_qty")?,
+        quantity_precision: parse_field(record, "quantity_precision")?,
+        weight: parse_field(record, "weight")?,
+        dimensions: None,
+        metadata: None,
+    };
+
+    // Run the same domain validation the item would go through on creation
+    let mut item = Item::new(
+        Uuid::nil(),
+        request.sku.clone(),
+        request.name.clone(),
+        request.unit.clone(),
+        request.cost_price,
+    )?;
+    item.update(UpdateItemRequest {
+        sku: None,
+        name: None,
+        description: None,
+        category: None,
+        unit: None,
+        barcode: None,
+        cost_price: None,
+        sale_price: request.sale_price,
+        reorder_point: request.reorder_point,
+        reorder_qty: request.reorder_qty,
+        quantity_precision: request.quantity_precision,
+        weight: request.weight,
+        dimensions: None,
+        metadata: None,
+    })?;
+
+    Ok(request)
+}
+
+fn parse_field<T: std::str::FromStr>(
+    record: &HashMap<String, String>,
+    field: &str,
+) -> Result<Option<T>, DomainError> {
+    match record.get(field) {
+        Some(value) => value.parse::<T>().map(Some).map_err(|_| {
+            DomainError::ValidationError(
+                format!("Invalid value '{}' for column '{}'", value, field).into(),
+            )
+        }),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_and_validate_rows() {
+        let csv = "SKU,Name,Unit,Cost Price,Reorder Point\n\
+                   SKU-1,Widget,each,1.50,10\n\
+                   SKU-2,,each,2.00,\n\
+                   SKU-1,Widget again,each,1.50,\n\
+                   SKU-3,Gadget,each,abc,\n\
+                   SKU-4,Gizmo,box,-1,\n\
+                   SKU-5,Doohickey,each,3,\n";
+
+        let records = parse_csv(csv.as_bytes()).unwrap();
+        assert_eq!(records.len(), 6);
+
+        let (rows, errors) = validate_records(records);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].row, 2);
+        assert_eq!(rows[0].request.reorder_point, Some(Decimal::from(10)));
+        assert_eq!(rows[1].request.sku, "SKU-5");
+
+        let error_rows: Vec<Option<i32>> = errors.iter().map(|e| e.row).collect();
+        assert_eq!(error_rows, vec![Some(3), Some(4), Some(5), Some(6)]);
+        assert!(errors[1].message.contains("Duplicate SKU"));
+    }
+
+    #[test]
+    fn test_parse_csv_skips_blank_rows() {
+        let csv = "sku,name,unit,cost_price\n,,,\nA,Thing,each,1\n";
+        let records = parse_csv(csv.as_bytes()).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].0, 3);
+    }
+
+    #[test]
+    fn test_format_from_content_type() {
+        assert_eq!(
+            ImportFileFormat::from_content_type("text/csv; charset=utf-8"),
+            Some(ImportFileFormat::Csv)
+        );
+        assert_eq!(
+            ImportFileFormat::from_content_type(
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            ),
+            Some(ImportFileFormat::Xlsx)
+        );
+        assert_eq!(
+            ImportFileFormat::from_content_type("application/json"),
+            None
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use crate::domain::entities::item_availability::{AvailabilityRebuild, ItemAvailability};
+use crate::domain::services::item_availability_repository::ItemAvailabilityRepository;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::shared::error::DomainError;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Reads item availability from the maintained read model instead of
+/// aggregating stock levels, allocations and purchase orders per request
+pub struct ItemAvailabilityUseCase<I: ItemRepository, A: ItemAvailabilityRepository> {
+    item_repository: Arc<I>,
+    availability_repository: Arc<A>,
+}
+
+impl<I: ItemRepository, A: ItemAvailabilityRepository> ItemAvailabilityUseCase<I, A> {
+    pub fn new(item_repository: Arc<I>, availability_repository: Arc<A>) -> Self {
+        Self {
+            item_repository,
+            availability_repository,
+        }
+    }
+
+    pub async fn execute(&self, item_id: Uuid) -> Result<ItemAvailability, DomainError> {
+        // An item without stock anywhere still has an (empty) availability
+        self.item_repository
+            .find_by_id(item_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Item {} not found", item_id).into()))?;
+        self.availability_repository.find_by_item(item_id).await
+    }
+
+    /// Recompute the tenant's read model from the stock, allocation and
+    /// purchase order tables, for when it has drifted from them
+    pub async fn rebuild(&self) -> Result<AvailabilityRebuild, DomainError> {
+        self.availability_repository.rebuild().await
+    }
+}
@@ -70,6 +70,11 @@ impl<SR: StockRepository, IR: ItemRepository, LR: LocationRepository>
                 item_id: level.item_id,
                 location_id: level.location_id,
                 quantity_on_hand: level.quantity_on_hand,
+                quantity_reserved: level.quantity_reserved,
+                quantity_quarantine: level.quantity_quarantine,
+                quantity_damaged: level.quantity_damaged,
+                quantity_in_transit: level.quantity_in_transit,
+                available_to_promise: level.available_to_promise(),
                 last_movement_id: level.last_movement_id,
                 updated_at: level.updated_at,
                 item: item.clone(), // Same item for all levels
@@ -1,12 +1,15 @@
+use crate::domain::entities::list_filter::ListFilter;
 use crate::domain::services::item_repository::ItemRepository;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ListItemsRequest {
-    pub limit: Option<i64>,
-    pub offset: Option<i64>,
+    pub page: PageRequest,
+    pub filter: ListFilter,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,26 +19,19 @@ pub struct ItemSummary {
     pub name: String,
     pub category: Option<String>,
     pub unit: String,
-    pub cost_price: f64,
-    pub sale_price: Option<f64>,
+    pub cost_price: Decimal,
+    pub sale_price: Option<Decimal>,
+    pub product_id: Option<uuid::Uuid>,
     pub active: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ListItemsResponse {
-    pub items: Vec<ItemSummary>,
-    pub total_count: i64,
-    pub limit: i64,
-    pub offset: i64,
-}
-
-pub struct ListItemsUseCase<R: ItemRepository> {
+pub struct ListItemsUseCase<R: ItemRepository + ?Sized> {
     item_repository: Arc<R>,
 }
 
-impl<R: ItemRepository> ListItemsUseCase<R> {
+impl<R: ItemRepository + ?Sized> ListItemsUseCase<R> {
     pub fn new(item_repository: Arc<R>) -> Self {
         Self { item_repository }
     }
@@ -43,20 +39,15 @@ impl<R: ItemRepository> ListItemsUseCase<R> {
     pub async fn execute(
         &self,
         request: ListItemsRequest,
-    ) -> Result<ListItemsResponse, DomainError> {
-        // Set defaults for pagination
-        let limit = request.limit.unwrap_or(50).min(1000); // Max 1000 items per page
-        let offset = request.offset.unwrap_or(0).max(0); // Ensure non-negative offset
-
-        // Get items and total count in parallel
+    ) -> Result<Page<ItemSummary>, DomainError> {
+        // Get one page of items and the total count in parallel
         let (items, total_count) = tokio::try_join!(
-            self.item_repository.list(limit, offset),
-            self.item_repository.count()
+            self.item_repository.list(&request.filter, &request.page),
+            self.item_repository.count(&request.filter)
         )?;
 
         // Convert to summary format
-        let items_summary = items
-            .into_iter()
+        Ok(items
             .map(|item| ItemSummary {
                 id: item.id,
                 sku: item.sku,
@@ -65,17 +56,11 @@ impl<R: ItemRepository> ListItemsUseCase<R> {
                 unit: item.unit,
                 cost_price: item.cost_price,
                 sale_price: item.sale_price,
+                product_id: item.product_id,
                 active: item.active,
                 created_at: item.created_at,
                 updated_at: item.updated_at,
             })
-            .collect();
-
-        Ok(ListItemsResponse {
-            items: items_summary,
-            total_count,
-            limit,
-            offset,
-        })
+            .with_total_count(total_count))
     }
 }
@@ -29,11 +29,11 @@ pub struct ListLocationsResponse {
     pub offset: i64,
 }
 
-pub struct ListLocationsUseCase<R: LocationRepository> {
+pub struct ListLocationsUseCase<R: LocationRepository + ?Sized> {
     location_repository: Arc<R>,
 }
 
-impl<R: LocationRepository> ListLocationsUseCase<R> {
+impl<R: LocationRepository + ?Sized> ListLocationsUseCase<R> {
     pub fn new(location_repository: Arc<R>) -> Self {
         Self {
             location_repository,
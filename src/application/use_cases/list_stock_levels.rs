@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::entities::inventory::StockLevel;
+use crate::domain::services::stock_repository::StockRepository;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::pagination::{Page, PageRequest};
+
+/// Stock levels across the tenant or at one location, including stock in
+/// transit to each location on open transfers
+pub struct ListStockLevelsUseCase<SR: StockRepository> {
+    stock_repository: Arc<SR>,
+}
+
+impl<SR: StockRepository> ListStockLevelsUseCase<SR> {
+    pub fn new(stock_repository: Arc<SR>) -> Self {
+        Self { stock_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        location_id: Option<Uuid>,
+        page: PageRequest,
+    ) -> Result<Page<StockLevel>, DomainError> {
+        if page.limit.is_some_and(|limit| limit <= 0 || limit > 1000) {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.limit_range",
+                &[],
+            )));
+        }
+
+        match location_id {
+            Some(location_id) => {
+                self.stock_repository
+                    .get_stock_levels_by_location(location_id, &page)
+                    .await
+            }
+            None => self.stock_repository.get_all_stock_levels(&page).await,
+        }
+    }
+}
@@ -51,7 +51,7 @@ mod tests {
             .expect_list_tenants()
             .returning(move || Ok(vec![tenant.clone()]));
 
-        let use_case = ListTenantsUseCase::new(mock_repo);
+        let use_case = ListTenantsUseCase::new(Arc::new(mock_repo));
         let result = use_case.execute().await;
 
         assert!(result.is_ok());
@@ -65,7 +65,7 @@ mod tests {
         let mut mock_repo = MockTenantRepository::new();
         mock_repo.expect_list_tenants().returning(|| Ok(vec![]));
 
-        let use_case = ListTenantsUseCase::new(mock_repo);
+        let use_case = ListTenantsUseCase::new(Arc::new(mock_repo));
         let result = use_case.execute().await;
 
         assert!(result.is_ok());
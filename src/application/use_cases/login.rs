@@ -1,6 +1,13 @@
+use crate::application::use_cases::manage_two_factor::verify_code;
+use crate::domain::entities::session::Session;
+use crate::domain::entities::user::UserRole;
+use crate::domain::services::session_repository::SessionRepository;
+use crate::domain::services::tenant_repository::TenantRepository;
+use crate::domain::services::two_factor_repository::TwoFactorRepository;
 use crate::domain::services::user_repository::UserRepository;
 use crate::domain::value_objects::email::Email;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
@@ -8,6 +15,14 @@ use std::sync::Arc;
 pub struct LoginRequest {
     pub email: String,
     pub password: String,
+    /// Where the login came from, recorded on the session
+    #[serde(default)]
+    pub ip_address: Option<String>,
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// Authenticator or recovery code, for users with two-factor login
+    #[serde(default)]
+    pub two_factor_code: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,18 +31,42 @@ pub struct LoginResponse {
     pub user_id: String,
     pub email: String,
     pub expires_at: i64,
+    pub session_id: String,
+    /// The tenant requires two-factor login for this admin, who hasn't set it
+    /// up; the token only works for enrolling until they do
+    pub two_factor_enrollment_required: bool,
 }
 
-pub struct LoginUseCase<R: UserRepository> {
+pub struct LoginUseCase<
+    R: UserRepository,
+    S: SessionRepository,
+    F: TwoFactorRepository,
+    T: TenantRepository,
+> {
     user_repository: Arc<R>,
+    session_repository: Arc<S>,
+    two_factor_repository: Arc<F>,
+    tenant_repository: Arc<T>,
     jwt_secret: String,
     jwt_expiry_hours: i64,
 }
 
-impl<R: UserRepository> LoginUseCase<R> {
-    pub fn new(user_repository: Arc<R>, jwt_secret: String, jwt_expiry_hours: i64) -> Self {
+impl<R: UserRepository, S: SessionRepository, F: TwoFactorRepository, T: TenantRepository>
+    LoginUseCase<R, S, F, T>
+{
+    pub fn new(
+        user_repository: Arc<R>,
+        session_repository: Arc<S>,
+        two_factor_repository: Arc<F>,
+        tenant_repository: Arc<T>,
+        jwt_secret: String,
+        jwt_expiry_hours: i64,
+    ) -> Self {
         Self {
             user_repository,
+            session_repository,
+            two_factor_repository,
+            tenant_repository,
             jwt_secret,
             jwt_expiry_hours,
         }
@@ -42,55 +81,93 @@ impl<R: UserRepository> LoginUseCase<R> {
             .user_repository
             .find_by_email(&email)
             .await?
-            .ok_or_else(|| DomainError::ValidationError("Invalid credentials".to_string()))?;
+            .ok_or_else(|| {
+                DomainError::ValidationError(Message::localized("error.invalid_credentials", &[]))
+            })?;
 
         // Check if user is active
         if !user.is_active() {
-            return Err(DomainError::ValidationError(
-                "Account is deactivated".to_string(),
-            ));
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.account_deactivated",
+                &[],
+            )));
         }
 
         // Verify password
         let password_valid = user.verify_password(&request.password)?;
         if !password_valid {
-            return Err(DomainError::ValidationError(
-                "Invalid credentials".to_string(),
-            ));
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.invalid_credentials",
+                &[],
+            )));
         }
 
-        // Generate JWT token
-        let token = self.generate_jwt(&user)?;
+        // Second factor, once the user has enabled it
+        let two_factor = self
+            .two_factor_repository
+            .find_by_user(user.id)
+            .await?
+            .filter(|two_factor| two_factor.is_enabled());
+        let enrollment_required = match two_factor {
+            Some(mut two_factor) => {
+                let code = request.two_factor_code.ok_or_else(|| {
+                    DomainError::ValidationError("Two-factor code required".into())
+                })?;
+                if !verify_code(&mut two_factor, &code, true) {
+                    return Err(DomainError::ValidationError(
+                        "Invalid two-factor code".into(),
+                    ));
+                }
+                self.two_factor_repository.save(&two_factor).await?;
+                false
+            }
+            None => {
+                user.role == UserRole::Admin
+                    && self
+                        .tenant_repository
+                        .get_admin_two_factor_required(user.tenant_id)
+                        .await?
+            }
+        };
 
-        let expires_at =
-            (chrono::Utc::now() + chrono::Duration::hours(self.jwt_expiry_hours)).timestamp();
+        // Record the session the token is tied to, so it can be listed and revoked
+        let mut session = Session::new(
+            user.id,
+            user.tenant_id,
+            request.ip_address,
+            request.user_agent,
+            chrono::Duration::hours(self.jwt_expiry_hours),
+        );
+        session.two_factor_enrollment_required = enrollment_required;
+        self.session_repository.save(&session).await?;
+
+        // Generate JWT token
+        let token = self.generate_jwt(&user, &session)?;
 
         Ok(LoginResponse {
             token,
             user_id: user.id.to_string(),
             email: user.email.as_str().to_string(),
-            expires_at,
+            expires_at: session.expires_at.timestamp(),
+            session_id: session.id.to_string(),
+            two_factor_enrollment_required: enrollment_required,
         })
     }
 
     fn generate_jwt(
         &self,
         user: &crate::domain::entities::user::User,
+        session: &Session,
     ) -> Result<String, DomainError> {
         use jsonwebtoken::{encode, EncodingKey, Header};
 
-        let expiration = chrono::Utc::now()
-            .checked_add_signed(chrono::Duration::hours(self.jwt_expiry_hours))
-            .ok_or_else(|| {
-                DomainError::ValidationError("Failed to calculate token expiration".to_string())
-            })?;
-
         let claims = Claims {
             sub: user.id.to_string(),
             email: user.email.as_str().to_string(),
             tenant_id: user.tenant_id.to_string(),
-            exp: expiration.timestamp() as usize,
-            iat: chrono::Utc::now().timestamp() as usize,
+            sid: session.id.to_string(),
+            exp: session.expires_at.timestamp() as usize,
+            iat: session.issued_at.timestamp() as usize,
         };
 
         encode(
@@ -98,7 +175,7 @@ impl<R: UserRepository> LoginUseCase<R> {
             &claims,
             &EncodingKey::from_secret(self.jwt_secret.as_ref()),
         )
-        .map_err(|_| DomainError::ValidationError("Failed to generate token".to_string()))
+        .map_err(|_| DomainError::ValidationError("Failed to generate token".into()))
     }
 }
 
@@ -107,6 +184,7 @@ struct Claims {
     sub: String,       // User ID
     email: String,     // User email
     tenant_id: String, // Tenant ID
+    sid: String,       // Session ID
     exp: usize,        // Expiration time
     iat: usize,        // Issued at time
 }
@@ -114,7 +192,11 @@ struct Claims {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::entities::two_factor::TwoFactor;
     use crate::domain::entities::user::User;
+    use crate::domain::services::session_repository::MockSessionRepository;
+    use crate::domain::services::tenant_repository::MockTenantRepository;
+    use crate::domain::services::two_factor_repository::MockTwoFactorRepository;
     use crate::domain::value_objects::{email::Email, password_hash::PasswordHash};
     use async_trait::async_trait;
     use std::sync::Arc;
@@ -149,14 +231,14 @@ mod tests {
     impl UserRepository for MockUserRepository {
         async fn find_by_id(&self, _id: Uuid) -> Result<Option<User>, DomainError> {
             if self.should_fail {
-                return Err(DomainError::ValidationError("Database error".to_string()));
+                return Err(DomainError::ValidationError("Database error".into()));
             }
             Ok(None)
         }
 
         async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError> {
             if self.should_fail {
-                return Err(DomainError::ValidationError("Database error".to_string()));
+                return Err(DomainError::ValidationError("Database error".into()));
             }
             Ok(self.users.get(email.as_str()).cloned())
         }
@@ -180,6 +262,33 @@ mod tests {
         ) -> Result<bool, DomainError> {
             Ok(false)
         }
+
+        async fn list_by_tenant(&self, _tenant_id: Uuid) -> Result<Vec<User>, DomainError> {
+            Ok(self.users.values().cloned().collect())
+        }
+    }
+
+    fn session_repository() -> Arc<MockSessionRepository> {
+        let mut repository = MockSessionRepository::new();
+        repository.expect_save().returning(|_| Ok(()));
+        Arc::new(repository)
+    }
+
+    fn two_factor_repository(two_factor: Option<TwoFactor>) -> Arc<MockTwoFactorRepository> {
+        let mut repository = MockTwoFactorRepository::new();
+        repository
+            .expect_find_by_user()
+            .returning(move |_| Ok(two_factor.clone()));
+        repository.expect_save().returning(|_| Ok(()));
+        Arc::new(repository)
+    }
+
+    fn tenant_repository(require_admin_two_factor: bool) -> Arc<MockTenantRepository> {
+        let mut repository = MockTenantRepository::new();
+        repository
+            .expect_get_admin_two_factor_required()
+            .returning(move |_| Ok(require_admin_two_factor));
+        Arc::new(repository)
     }
 
     fn create_test_user() -> User {
@@ -201,11 +310,21 @@ mod tests {
     async fn test_login_success() {
         let user = create_test_user();
         let mock_repo = MockUserRepository::new().with_user(user.clone());
-        let use_case = LoginUseCase::new(Arc::new(mock_repo), "test-secret".to_string(), 24);
+        let use_case = LoginUseCase::new(
+            Arc::new(mock_repo),
+            session_repository(),
+            two_factor_repository(None),
+            tenant_repository(false),
+            "test-secret".to_string(),
+            24,
+        );
 
         let request = LoginRequest {
             email: "test@example.com".to_string(),
             password: "password".to_string(),
+            ip_address: None,
+            user_agent: None,
+            two_factor_code: None,
         };
 
         let result = use_case.execute(request).await;
@@ -221,11 +340,21 @@ mod tests {
     #[tokio::test]
     async fn test_login_invalid_email() {
         let mock_repo = MockUserRepository::new();
-        let use_case = LoginUseCase::new(Arc::new(mock_repo), "test-secret".to_string(), 24);
+        let use_case = LoginUseCase::new(
+            Arc::new(mock_repo),
+            session_repository(),
+            two_factor_repository(None),
+            tenant_repository(false),
+            "test-secret".to_string(),
+            24,
+        );
 
         let request = LoginRequest {
             email: "invalid-email".to_string(),
             password: "password".to_string(),
+            ip_address: None,
+            user_agent: None,
+            two_factor_code: None,
         };
 
         let result = use_case.execute(request).await;
@@ -239,11 +368,21 @@ mod tests {
     #[tokio::test]
     async fn test_login_user_not_found() {
         let mock_repo = MockUserRepository::new();
-        let use_case = LoginUseCase::new(Arc::new(mock_repo), "test-secret".to_string(), 24);
+        let use_case = LoginUseCase::new(
+            Arc::new(mock_repo),
+            session_repository(),
+            two_factor_repository(None),
+            tenant_repository(false),
+            "test-secret".to_string(),
+            24,
+        );
 
         let request = LoginRequest {
             email: "notfound@example.com".to_string(),
             password: "password".to_string(),
+            ip_address: None,
+            user_agent: None,
+            two_factor_code: None,
         };
 
         let result = use_case.execute(request).await;
@@ -256,11 +395,21 @@ mod tests {
     async fn test_login_wrong_password() {
         let user = create_test_user();
         let mock_repo = MockUserRepository::new().with_user(user);
-        let use_case = LoginUseCase::new(Arc::new(mock_repo), "test-secret".to_string(), 24);
+        let use_case = LoginUseCase::new(
+            Arc::new(mock_repo),
+            session_repository(),
+            two_factor_repository(None),
+            tenant_repository(false),
+            "test-secret".to_string(),
+            24,
+        );
 
         let request = LoginRequest {
             email: "test@example.com".to_string(),
             password: "wrongpassword".to_string(),
+            ip_address: None,
+            user_agent: None,
+            two_factor_code: None,
         };
 
         let result = use_case.execute(request).await;
@@ -274,11 +423,21 @@ mod tests {
         let mut user = create_test_user();
         user.deactivate();
         let mock_repo = MockUserRepository::new().with_user(user);
-        let use_case = LoginUseCase::new(Arc::new(mock_repo), "test-secret".to_string(), 24);
+        let use_case = LoginUseCase::new(
+            Arc::new(mock_repo),
+            session_repository(),
+            two_factor_repository(None),
+            tenant_repository(false),
+            "test-secret".to_string(),
+            24,
+        );
 
         let request = LoginRequest {
             email: "test@example.com".to_string(),
             password: "password".to_string(),
+            ip_address: None,
+            user_agent: None,
+            two_factor_code: None,
         };
 
         let result = use_case.execute(request).await;
@@ -292,11 +451,21 @@ mod tests {
     #[tokio::test]
     async fn test_login_database_error() {
         let mock_repo = MockUserRepository::new().with_failure();
-        let use_case = LoginUseCase::new(Arc::new(mock_repo), "test-secret".to_string(), 24);
+        let use_case = LoginUseCase::new(
+            Arc::new(mock_repo),
+            session_repository(),
+            two_factor_repository(None),
+            tenant_repository(false),
+            "test-secret".to_string(),
+            24,
+        );
 
         let request = LoginRequest {
             email: "test@example.com".to_string(),
             password: "password".to_string(),
+            ip_address: None,
+            user_agent: None,
+            two_factor_code: None,
         };
 
         let result = use_case.execute(request).await;
@@ -307,11 +476,21 @@ mod tests {
     async fn test_jwt_token_structure() {
         let user = create_test_user();
         let mock_repo = MockUserRepository::new().with_user(user.clone());
-        let use_case = LoginUseCase::new(Arc::new(mock_repo), "test-secret".to_string(), 24);
+        let use_case = LoginUseCase::new(
+            Arc::new(mock_repo),
+            session_repository(),
+            two_factor_repository(None),
+            tenant_repository(false),
+            "test-secret".to_string(),
+            24,
+        );
 
         let request = LoginRequest {
             email: "test@example.com".to_string(),
             password: "password".to_string(),
+            ip_address: None,
+            user_agent: None,
+            two_factor_code: None,
         };
 
         let result = use_case.execute(request).await.unwrap();
@@ -327,6 +506,111 @@ mod tests {
 
         assert_eq!(token_data.claims.sub, user.id.to_string());
         assert_eq!(token_data.claims.email, "test@example.com");
+        assert_eq!(token_data.claims.sid, result.session_id);
         assert!(token_data.claims.exp > token_data.claims.iat);
     }
+
+    #[tokio::test]
+    async fn test_login_records_session_with_client_details() {
+        let user = create_test_user();
+        let user_id = user.id;
+        let mut sessions = MockSessionRepository::new();
+        sessions
+            .expect_save()
+            .withf(move |session| {
+                session.user_id == user_id
+                    && session.ip_address.as_deref() == Some("203.0.113.7")
+                    && session.user_agent.as_deref() == Some("scanner/2.1")
+                    && session.is_active()
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        let use_case = LoginUseCase::new(
+            Arc::new(MockUserRepository::new().with_user(user)),
+            Arc::new(sessions),
+            two_factor_repository(None),
+            tenant_repository(false),
+            "test-secret".to_string(),
+            24,
+        );
+
+        let response = use_case
+            .execute(LoginRequest {
+                email: "test@example.com".to_string(),
+                password: "password".to_string(),
+                ip_address: Some("203.0.113.7".to_string()),
+                user_agent: Some("scanner/2.1".to_string()),
+                two_factor_code: None,
+            })
+            .await
+            .unwrap();
+        assert!(Uuid::parse_str(&response.session_id).is_ok());
+    }
+
+    fn login_with_code(code: Option<&str>) -> LoginRequest {
+        LoginRequest {
+            email: "test@example.com".to_string(),
+            password: "password".to_string(),
+            ip_address: None,
+            user_agent: None,
+            two_factor_code: code.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_asks_for_second_factor_once_enabled() {
+        let user = create_test_user();
+        let (mut two_factor, recovery_codes) =
+            TwoFactor::new(user.id, "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ".to_string());
+        two_factor.enable();
+        let use_case = LoginUseCase::new(
+            Arc::new(MockUserRepository::new().with_user(user)),
+            session_repository(),
+            two_factor_repository(Some(two_factor)),
+            tenant_repository(false),
+            "test-secret".to_string(),
+            24,
+        );
+
+        let missing = use_case.execute(login_with_code(None)).await.unwrap_err();
+        assert!(
+            matches!(missing, DomainError::ValidationError(msg) if msg == "Two-factor code required")
+        );
+        let wrong = use_case
+            .execute(login_with_code(Some("000000")))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(wrong, DomainError::ValidationError(msg) if msg == "Invalid two-factor code")
+        );
+
+        let response = use_case
+            .execute(login_with_code(Some(&recovery_codes[0])))
+            .await
+            .unwrap();
+        assert!(!response.two_factor_enrollment_required);
+    }
+
+    #[tokio::test]
+    async fn test_policy_limits_admins_without_second_factor_to_enrollment() {
+        let mut admin = create_test_user();
+        admin.change_role(UserRole::Admin);
+        let mut sessions = MockSessionRepository::new();
+        sessions
+            .expect_save()
+            .withf(|session| session.two_factor_enrollment_required)
+            .times(1)
+            .returning(|_| Ok(()));
+        let use_case = LoginUseCase::new(
+            Arc::new(MockUserRepository::new().with_user(admin)),
+            Arc::new(sessions),
+            two_factor_repository(None),
+            tenant_repository(true),
+            "test-secret".to_string(),
+            24,
+        );
+
+        let response = use_case.execute(login_with_code(None)).await.unwrap();
+        assert!(response.two_factor_enrollment_required);
+    }
 }
@@ -0,0 +1,88 @@
+use crate::domain::entities::adjustment::{
+    AdjustmentReason, CreateAdjustmentReasonRequest, UpdateAdjustmentReasonRequest,
+};
+use crate::domain::services::adjustment_repository::AdjustmentRepository;
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct ListAdjustmentReasonsQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListAdjustmentReasonsResponse {
+    pub adjustment_reasons: Vec<AdjustmentReason>,
+}
+
+pub struct ManageAdjustmentReasonsUseCase<A: AdjustmentRepository> {
+    adjustment_repository: Arc<A>,
+}
+
+impl<A: AdjustmentRepository> ManageAdjustmentReasonsUseCase<A> {
+    pub fn new(adjustment_repository: Arc<A>) -> Self {
+        Self {
+            adjustment_repository,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        request: CreateAdjustmentReasonRequest,
+    ) -> Result<AdjustmentReason, DomainError> {
+        let reason = AdjustmentReason::new(request)?;
+        if self
+            .adjustment_repository
+            .find_reason_by_code(&reason.code)
+            .await?
+            .is_some()
+        {
+            return Err(DomainError::Conflict(
+                format!("Adjustment reason '{}' already exists", reason.code).into(),
+            ));
+        }
+        self.adjustment_repository.create_reason(&reason).await?;
+        Ok(reason)
+    }
+
+    pub async fn get(&self, reason_id: Uuid) -> Result<AdjustmentReason, DomainError> {
+        self.adjustment_repository
+            .find_reason_by_id(reason_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Adjustment reason {} not found", reason_id).into())
+            })
+    }
+
+    pub async fn list(
+        &self,
+        query: ListAdjustmentReasonsQuery,
+    ) -> Result<ListAdjustmentReasonsResponse, DomainError> {
+        let limit = query.limit.unwrap_or(50).clamp(1, 100);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let adjustment_reasons = self
+            .adjustment_repository
+            .list_reasons(limit, offset)
+            .await?;
+        Ok(ListAdjustmentReasonsResponse { adjustment_reasons })
+    }
+
+    pub async fn update(
+        &self,
+        reason_id: Uuid,
+        request: UpdateAdjustmentReasonRequest,
+    ) -> Result<AdjustmentReason, DomainError> {
+        let mut reason = self.get(reason_id).await?;
+        reason.update(request)?;
+        self.adjustment_repository.update_reason(&reason).await?;
+        Ok(reason)
+    }
+
+    /// Past adjustments keep their code; deactivate a reason to stop new use
+    pub async fn delete(&self, reason_id: Uuid) -> Result<(), DomainError> {
+        self.adjustment_repository.delete_reason(reason_id).await
+    }
+}
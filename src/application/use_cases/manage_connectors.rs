@@ -0,0 +1,171 @@
+use crate::domain::entities::integration::{
+    ConnectorCredentialsRequest, CreateConnectorRequest, IntegrationConnector,
+    OAuthCallbackRequest, UpdateConnectorRequest,
+};
+use crate::domain::services::connector::ConnectorRegistry;
+use crate::domain::services::integration_repository::IntegrationRepository;
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct ListConnectorsQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListConnectorsResponse {
+    pub connectors: Vec<IntegrationConnector>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthorizeConnectorResponse {
+    pub authorize_url: String,
+}
+
+pub struct ManageConnectorsUseCase<R: IntegrationRepository> {
+    integration_repository: Arc<R>,
+    registry: Arc<ConnectorRegistry>,
+}
+
+impl<R: IntegrationRepository> ManageConnectorsUseCase<R> {
+    pub fn new(integration_repository: Arc<R>, registry: Arc<ConnectorRegistry>) -> Self {
+        Self {
+            integration_repository,
+            registry,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        request: CreateConnectorRequest,
+        created_by: Uuid,
+    ) -> Result<IntegrationConnector, DomainError> {
+        let connector = IntegrationConnector::new(request, created_by)?;
+        self.registry.get(connector.provider)?;
+        if self
+            .integration_repository
+            .find_connector_by_shop(connector.provider, &connector.shop_domain)
+            .await?
+            .is_some()
+        {
+            return Err(DomainError::Conflict(
+                format!(
+                    "A {} connector for {} already exists",
+                    connector.provider.as_str(),
+                    connector.shop_domain
+                )
+                .into(),
+            ));
+        }
+        self.integration_repository
+            .create_connector(&connector)
+            .await?;
+        Ok(connector)
+    }
+
+    pub async fn get(&self, connector_id: Uuid) -> Result<IntegrationConnector, DomainError> {
+        self.integration_repository
+            .find_connector(connector_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Connector {} not found", connector_id).into())
+            })
+    }
+
+    pub async fn list(
+        &self,
+        query: ListConnectorsQuery,
+    ) -> Result<ListConnectorsResponse, DomainError> {
+        let limit = query.limit.unwrap_or(50).clamp(1, 100);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let connectors = self
+            .integration_repository
+            .list_connectors(limit, offset)
+            .await?;
+        Ok(ListConnectorsResponse { connectors })
+    }
+
+    pub async fn update(
+        &self,
+        connector_id: Uuid,
+        request: UpdateConnectorRequest,
+    ) -> Result<IntegrationConnector, DomainError> {
+        let mut connector = self.get(connector_id).await?;
+        connector.update(request)?;
+        self.integration_repository
+            .update_connector(&connector)
+            .await?;
+        Ok(connector)
+    }
+
+    /// Sales orders pulled by the connector are kept
+    pub async fn delete(&self, connector_id: Uuid) -> Result<(), DomainError> {
+        self.integration_repository
+            .delete_connector(connector_id)
+            .await
+    }
+
+    /// Store a token the merchant issued directly, skipping the OAuth flow
+    pub async fn set_credentials(
+        &self,
+        connector_id: Uuid,
+        request: ConnectorCredentialsRequest,
+    ) -> Result<IntegrationConnector, DomainError> {
+        let access_token = request.access_token.trim().to_string();
+        if access_token.is_empty() {
+            return Err(DomainError::ValidationError(
+                "access_token cannot be empty".into(),
+            ));
+        }
+        let mut connector = self.get(connector_id).await?;
+        connector.store_token(access_token, request.scopes);
+        self.integration_repository
+            .update_connector(&connector)
+            .await?;
+        Ok(connector)
+    }
+
+    pub async fn begin_oauth(
+        &self,
+        connector_id: Uuid,
+    ) -> Result<AuthorizeConnectorResponse, DomainError> {
+        let mut connector = self.get(connector_id).await?;
+        let state = connector.begin_authorization();
+        let authorize_url = self
+            .registry
+            .get(connector.provider)?
+            .authorize_url(&connector, &state)?;
+        self.integration_repository
+            .update_connector(&connector)
+            .await?;
+        Ok(AuthorizeConnectorResponse { authorize_url })
+    }
+
+    pub async fn complete_oauth(
+        &self,
+        connector_id: Uuid,
+        request: OAuthCallbackRequest,
+    ) -> Result<IntegrationConnector, DomainError> {
+        let mut connector = self.get(connector_id).await?;
+        let checked = connector.complete_authorization(&request.state);
+        // The state is spent whether or not it matched
+        self.integration_repository
+            .update_connector(&connector)
+            .await?;
+        checked?;
+
+        let token = self
+            .registry
+            .get(connector.provider)?
+            .exchange_code(&connector, &request.code)
+            .await?;
+        connector.store_token(token.access_token, token.scopes);
+        self.integration_repository
+            .update_connector(&connector)
+            .await?;
+        Ok(connector)
+    }
+}
@@ -0,0 +1,74 @@
+use crate::domain::entities::credit::{
+    CreditSettings, CustomerCreditStatus, SetCustomerCreditLimitRequest,
+    UpdateCreditSettingsRequest,
+};
+use crate::domain::services::customer_credit_repository::CustomerCreditRepository;
+use crate::shared::error::DomainError;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Customers' credit limits, what happens to orders that break them, and how
+/// much of each limit is used
+pub struct ManageCreditSettingsUseCase<R: CustomerCreditRepository> {
+    credit_repository: Arc<R>,
+}
+
+impl<R: CustomerCreditRepository> ManageCreditSettingsUseCase<R> {
+    pub fn new(credit_repository: Arc<R>) -> Self {
+        Self { credit_repository }
+    }
+
+    pub async fn get(&self) -> Result<CreditSettings, DomainError> {
+        self.credit_repository.find().await
+    }
+
+    pub async fn update(
+        &self,
+        request: UpdateCreditSettingsRequest,
+    ) -> Result<CreditSettings, DomainError> {
+        self.credit_repository
+            .save_enforcement(request.enforcement)
+            .await?;
+        self.credit_repository.find().await
+    }
+
+    pub async fn set_customer(
+        &self,
+        customer_id: Uuid,
+        request: SetCustomerCreditLimitRequest,
+    ) -> Result<CreditSettings, DomainError> {
+        if request.credit_limit < Decimal::ZERO {
+            return Err(DomainError::ValidationError(
+                "Credit limit cannot be negative".into(),
+            ));
+        }
+        self.credit_repository
+            .save_customer(customer_id, request.credit_limit)
+            .await?;
+        self.credit_repository.find().await
+    }
+
+    /// Stop checking the customer's orders against a limit
+    pub async fn remove_customer(&self, customer_id: Uuid) -> Result<(), DomainError> {
+        if !self.credit_repository.delete_customer(customer_id).await? {
+            return Err(DomainError::NotFound(
+                format!("Customer {} has no credit limit", customer_id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn customer_status(
+        &self,
+        customer_id: Uuid,
+    ) -> Result<CustomerCreditStatus, DomainError> {
+        Ok(CustomerCreditStatus::new(
+            customer_id,
+            self.credit_repository.find_limit(customer_id).await?,
+            self.credit_repository
+                .outstanding_balance(customer_id)
+                .await?,
+        ))
+    }
+}
@@ -0,0 +1,117 @@
+use crate::domain::entities::currency::{
+    normalize_currency, CurrencyConverter, CurrencySettings, UpdateCurrencySettingsRequest,
+};
+use crate::domain::services::currency_service::CurrencyService;
+use crate::domain::services::exchange_rate_repository::ExchangeRateRepository;
+use crate::domain::services::tenant_repository::TenantRepository;
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::current_tenant;
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+pub struct ExchangeRatesQuery {
+    /// Rates as they stood on this day; defaults to today
+    pub date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExchangeRatesResponse {
+    pub base_currency: String,
+    pub date: NaiveDate,
+    /// Day the newest of the rates was published, if any are held
+    pub rate_date: Option<NaiveDate>,
+    /// Units of each currency one unit of the base currency buys
+    pub rates: BTreeMap<String, Decimal>,
+}
+
+/// The tenant's base currency and the rates orders are converted with
+pub struct ManageCurrencyUseCase<T: TenantRepository, E: ExchangeRateRepository> {
+    tenant_repository: Arc<T>,
+    exchange_rate_repository: Arc<E>,
+    currency_service: Arc<dyn CurrencyService>,
+}
+
+impl<T: TenantRepository, E: ExchangeRateRepository> ManageCurrencyUseCase<T, E> {
+    pub fn new(
+        tenant_repository: Arc<T>,
+        exchange_rate_repository: Arc<E>,
+        currency_service: Arc<dyn CurrencyService>,
+    ) -> Self {
+        Self {
+            tenant_repository,
+            exchange_rate_repository,
+            currency_service,
+        }
+    }
+
+    pub async fn get_settings(&self) -> Result<CurrencySettings, DomainError> {
+        Ok(CurrencySettings {
+            base_currency: self.currency_service.base_currency().await?,
+        })
+    }
+
+    /// Change the base currency. Orders in a foreign currency carry a rate into the
+    /// base currency they were placed under, so once there are any it stays fixed.
+    pub async fn update_settings(
+        &self,
+        request: UpdateCurrencySettingsRequest,
+    ) -> Result<CurrencySettings, DomainError> {
+        let base_currency = normalize_currency(&request.base_currency)?;
+        let tenant_id = current_tenant()
+            .ok_or_else(|| DomainError::ValidationError("No tenant is in scope".into()))?;
+        let current = self
+            .tenant_repository
+            .get_base_currency(tenant_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Tenant {} not found", tenant_id).into())
+            })?;
+
+        if current != base_currency
+            && !self
+                .tenant_repository
+                .set_base_currency(tenant_id, &base_currency)
+                .await?
+        {
+            return Err(DomainError::Conflict(
+                format!(
+                    "The base currency stays {} once orders have been placed in another currency",
+                    current
+                )
+                .into(),
+            ));
+        }
+        Ok(CurrencySettings { base_currency })
+    }
+
+    pub async fn list_rates(
+        &self,
+        query: ExchangeRatesQuery,
+    ) -> Result<ExchangeRatesResponse, DomainError> {
+        let base_currency = self.currency_service.base_currency().await?;
+        let date = query.date.unwrap_or_else(|| Utc::now().date_naive());
+        let rates = self.exchange_rate_repository.latest_rates(date).await?;
+        let rate_date = rates.iter().map(|rate| rate.rate_date).max();
+
+        let converter = CurrencyConverter::new(&rates);
+        let rates = rates
+            .iter()
+            .flat_map(|rate| [&rate.base_currency, &rate.quote_currency])
+            .filter(|currency| **currency != base_currency)
+            .filter_map(|currency| {
+                Some((currency.clone(), converter.rate(&base_currency, currency)?))
+            })
+            .collect();
+
+        Ok(ExchangeRatesResponse {
+            base_currency,
+            date,
+            rate_date,
+            rates,
+        })
+    }
+}
@@ -0,0 +1,82 @@
+use crate::domain::entities::document::{
+    logo_key, DocumentSettings, UpdateDocumentSettingsRequest,
+};
+use crate::domain::services::blob_storage::BlobStorage;
+use crate::domain::services::document_settings_repository::DocumentSettingsRepository;
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::current_tenant;
+use std::sync::Arc;
+
+/// The letterhead and logo printed on the tenant's order documents
+pub struct ManageDocumentSettingsUseCase<D: DocumentSettingsRepository> {
+    settings_repository: Arc<D>,
+    blob_storage: Arc<dyn BlobStorage>,
+}
+
+impl<D: DocumentSettingsRepository> ManageDocumentSettingsUseCase<D> {
+    pub fn new(settings_repository: Arc<D>, blob_storage: Arc<dyn BlobStorage>) -> Self {
+        Self {
+            settings_repository,
+            blob_storage,
+        }
+    }
+
+    pub async fn get(&self) -> Result<DocumentSettings, DomainError> {
+        self.settings_repository
+            .find()
+            .await?
+            .ok_or_else(|| DomainError::NotFound("No document settings are configured".into()))
+    }
+
+    pub async fn update(
+        &self,
+        request: UpdateDocumentSettingsRequest,
+    ) -> Result<DocumentSettings, DomainError> {
+        let settings = match self.settings_repository.find().await? {
+            Some(mut settings) => {
+                settings.update(request)?;
+                settings
+            }
+            None => DocumentSettings::new(request)?,
+        };
+        self.settings_repository.save(&settings).await?;
+        Ok(settings)
+    }
+
+    /// Replace the logo with a PNG image
+    pub async fn upload_logo(&self, data: Vec<u8>) -> Result<DocumentSettings, DomainError> {
+        let mut settings = self.settings_repository.find().await?.ok_or_else(|| {
+            DomainError::BusinessLogicError(
+                "Set up the letterhead at PUT /admin/document-settings before uploading a logo"
+                    .to_string()
+                    .into(),
+            )
+        })?;
+        let key = logo_key(current_tenant(), &data)?;
+        self.blob_storage.put(&key, "image/png", data).await?;
+
+        settings.logo_key = Some(key);
+        settings.updated_at = chrono::Utc::now();
+        self.settings_repository.save(&settings).await?;
+        Ok(settings)
+    }
+
+    pub async fn download_logo(&self) -> Result<Vec<u8>, DomainError> {
+        let key = self
+            .get()
+            .await?
+            .logo_key
+            .ok_or_else(|| DomainError::NotFound("No logo has been uploaded".into()))?;
+        self.blob_storage.get(&key).await
+    }
+
+    pub async fn delete_logo(&self) -> Result<(), DomainError> {
+        let mut settings = self.get().await?;
+        let Some(key) = settings.logo_key.take() else {
+            return Ok(());
+        };
+        self.blob_storage.delete(&key).await?;
+        settings.updated_at = chrono::Utc::now();
+        self.settings_repository.save(&settings).await
+    }
+}
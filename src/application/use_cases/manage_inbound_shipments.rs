@@ -0,0 +1,238 @@
+use crate::application::use_cases::receive_purchase_order::{
+    ReceivePurchaseOrderResponse, ReceivePurchaseOrderUseCase, ReceivePurchaseOrderUseCaseRequest,
+};
+use crate::domain::entities::inbound_shipment::{
+    CreateInboundShipmentRequest, InboundDiscrepancy, InboundShipment, ScannedCarton,
+};
+use crate::domain::entities::inventory::StockStatus;
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::inbound_shipment_repository::InboundShipmentRepository;
+use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
+use crate::domain::services::putaway_rule_repository::PutawayRuleRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct InboundShipmentResponse {
+    pub inbound_shipment: InboundShipment,
+    pub discrepancies: Vec<InboundDiscrepancy>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiveInboundCartonsRequest {
+    pub cartons: Vec<ScannedCarton>,
+    pub destination_location_id: Uuid,
+    #[serde(default)]
+    pub stock_status: StockStatus,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReceiveInboundCartonsResponse {
+    pub inbound_shipment: InboundShipment,
+    pub discrepancies: Vec<InboundDiscrepancy>,
+    /// The purchase order receipt, absent when every scanned carton was empty
+    pub receipt: Option<ReceivePurchaseOrderResponse>,
+}
+
+/// Advance shipping notices from suppliers, and checking their cartons in at
+/// the dock. Receiving goes through the purchase order, so tolerances, stock
+/// movements and putaway suggestions work as they do for a plain PO receipt.
+pub struct ManageInboundShipmentsUseCase<
+    A: InboundShipmentRepository,
+    P: PurchaseOrderRepository,
+    R: PutawayRuleRepository,
+    D: WebhookDispatcher + 'static,
+> {
+    inbound_shipment_repository: Arc<A>,
+    purchase_order_repository: Arc<P>,
+    receive_purchase_order_use_case: Arc<ReceivePurchaseOrderUseCase<P, R, D>>,
+    webhook_dispatcher: Arc<D>,
+}
+
+impl<A, P, R, D> ManageInboundShipmentsUseCase<A, P, R, D>
+where
+    A: InboundShipmentRepository,
+    P: PurchaseOrderRepository,
+    R: PutawayRuleRepository,
+    D: WebhookDispatcher + 'static,
+{
+    pub fn new(
+        inbound_shipment_repository: Arc<A>,
+        purchase_order_repository: Arc<P>,
+        receive_purchase_order_use_case: Arc<ReceivePurchaseOrderUseCase<P, R, D>>,
+        webhook_dispatcher: Arc<D>,
+    ) -> Self {
+        Self {
+            inbound_shipment_repository,
+            purchase_order_repository,
+            receive_purchase_order_use_case,
+            webhook_dispatcher,
+        }
+    }
+
+    /// Record a supplier's notice of what is on its way against an open PO
+    pub async fn create(
+        &self,
+        request: CreateInboundShipmentRequest,
+        created_by: Uuid,
+    ) -> Result<InboundShipmentResponse, DomainError> {
+        let po = self
+            .purchase_order_repository
+            .find_by_id(request.po_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_not_found",
+                    &[("id", &request.po_id)],
+                ))
+            })?;
+
+        let shipment = InboundShipment::new(&po, request, created_by)?;
+        if self
+            .inbound_shipment_repository
+            .find_by_asn_number(shipment.supplier_id, &shipment.asn_number)
+            .await?
+            .is_some()
+        {
+            return Err(DomainError::Conflict(
+                format!(
+                    "ASN {} from this supplier already exists",
+                    shipment.asn_number
+                )
+                .into(),
+            ));
+        }
+        self.inbound_shipment_repository.create(&shipment).await?;
+
+        self.dispatch(
+            WebhookEventType::InboundShipmentCreated,
+            json!({ "inbound_shipment": shipment }),
+        );
+
+        Ok(Self::response(shipment))
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<InboundShipmentResponse, DomainError> {
+        self.find(id).await.map(Self::response)
+    }
+
+    pub async fn list_by_po(&self, po_id: Uuid) -> Result<Vec<InboundShipment>, DomainError> {
+        self.inbound_shipment_repository.list_by_po(po_id).await
+    }
+
+    /// Check in scanned cartons and receive their contents against the PO.
+    /// Scanning the last carton closes the shipment.
+    pub async fn receive(
+        &self,
+        id: Uuid,
+        request: ReceiveInboundCartonsRequest,
+        received_by: Uuid,
+    ) -> Result<ReceiveInboundCartonsResponse, DomainError> {
+        let mut shipment = self.find(id).await?;
+        let po = self
+            .purchase_order_repository
+            .find_by_id(shipment.po_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_not_found",
+                    &[("id", &shipment.po_id)],
+                ))
+            })?;
+
+        let received_lines = shipment.receive_cartons(&po, request.cartons, received_by)?;
+        let receipt = if received_lines.is_empty() {
+            None
+        } else {
+            Some(
+                self.receive_purchase_order_use_case
+                    .execute(
+                        ReceivePurchaseOrderUseCaseRequest {
+                            po_id: po.id,
+                            received_lines,
+                            receive_date: None,
+                            destination_location_id: request.destination_location_id,
+                            stock_status: request.stock_status,
+                        },
+                        received_by,
+                    )
+                    .await?,
+            )
+        };
+        self.inbound_shipment_repository.update(&shipment).await?;
+
+        let discrepancies = shipment.discrepancies();
+        if shipment.closed_at.is_some() {
+            self.report_discrepancies(&shipment, &discrepancies);
+        }
+        Ok(ReceiveInboundCartonsResponse {
+            inbound_shipment: shipment,
+            discrepancies,
+            receipt,
+        })
+    }
+
+    /// Stop waiting for cartons that haven't arrived
+    pub async fn close(&self, id: Uuid) -> Result<InboundShipmentResponse, DomainError> {
+        let mut shipment = self.find(id).await?;
+        shipment.close()?;
+        self.inbound_shipment_repository.update(&shipment).await?;
+
+        let response = Self::response(shipment);
+        self.report_discrepancies(&response.inbound_shipment, &response.discrepancies);
+        Ok(response)
+    }
+
+    async fn find(&self, id: Uuid) -> Result<InboundShipment, DomainError> {
+        self.inbound_shipment_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Inbound shipment {} not found", id).into())
+            })
+    }
+
+    fn response(shipment: InboundShipment) -> InboundShipmentResponse {
+        InboundShipmentResponse {
+            discrepancies: shipment.discrepancies(),
+            inbound_shipment: shipment,
+        }
+    }
+
+    /// Tell subscribers when a closed shipment didn't match its notice
+    fn report_discrepancies(
+        &self,
+        shipment: &InboundShipment,
+        discrepancies: &[InboundDiscrepancy],
+    ) {
+        if discrepancies.is_empty() {
+            return;
+        }
+        self.dispatch(
+            WebhookEventType::InboundShipmentDiscrepancy,
+            json!({
+                "inbound_shipment_id": shipment.id,
+                "asn_number": shipment.asn_number,
+                "po_id": shipment.po_id,
+                "supplier_id": shipment.supplier_id,
+                "discrepancies": discrepancies,
+            }),
+        );
+    }
+
+    /// Dispatch a webhook event without blocking the request
+    fn dispatch(&self, event_type: WebhookEventType, payload: serde_json::Value) {
+        let webhook_event = WebhookEvent::new(event_type, payload);
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch inbound shipment webhook: {:?}", e);
+            }
+        });
+    }
+}
@@ -0,0 +1,153 @@
+use crate::domain::entities::invoice::{
+    CreateInvoiceRequest, Invoice, Payment, RecordPaymentRequest,
+};
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::invoice_repository::InvoiceRepository;
+use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct InvoiceResponse {
+    pub invoice: Invoice,
+    pub balance: Decimal,
+    pub payments: Vec<Payment>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecordPaymentResponse {
+    pub invoice: Invoice,
+    pub balance: Decimal,
+    pub payment: Payment,
+}
+
+pub struct ManageInvoicesUseCase<
+    I: InvoiceRepository,
+    S: SalesOrderRepository,
+    D: WebhookDispatcher + 'static,
+> {
+    invoice_repository: Arc<I>,
+    sales_order_repository: Arc<S>,
+    webhook_dispatcher: Arc<D>,
+}
+
+impl<I: InvoiceRepository, S: SalesOrderRepository, D: WebhookDispatcher + 'static>
+    ManageInvoicesUseCase<I, S, D>
+{
+    pub fn new(
+        invoice_repository: Arc<I>,
+        sales_order_repository: Arc<S>,
+        webhook_dispatcher: Arc<D>,
+    ) -> Self {
+        Self {
+            invoice_repository,
+            sales_order_repository,
+            webhook_dispatcher,
+        }
+    }
+
+    /// Invoice a shipped sales order for its total, moving it to INVOICED
+    pub async fn create_for_sales_order(
+        &self,
+        so_id: Uuid,
+        request: CreateInvoiceRequest,
+        created_by: Uuid,
+    ) -> Result<InvoiceResponse, DomainError> {
+        let (sales_order, _) = self
+            .sales_order_repository
+            .find_by_id(so_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Sales order {} not found", so_id).into())
+            })?;
+
+        let invoice = Invoice::for_sales_order(
+            format!("INV-{}", Uuid::new_v4().simple()),
+            &sales_order,
+            request.due_date,
+            created_by,
+        )?;
+        self.invoice_repository.create(&invoice).await?;
+
+        self.dispatch(
+            WebhookEventType::InvoiceCreated,
+            json!({ "invoice": invoice }),
+        );
+
+        Ok(InvoiceResponse {
+            balance: invoice.balance(),
+            invoice,
+            payments: Vec::new(),
+        })
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<InvoiceResponse, DomainError> {
+        let invoice = self
+            .invoice_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Invoice {} not found", id).into()))?;
+        let payments = self.invoice_repository.payments(id).await?;
+
+        Ok(InvoiceResponse {
+            balance: invoice.balance(),
+            invoice,
+            payments,
+        })
+    }
+
+    pub async fn list(
+        &self,
+        filter: &ListFilter,
+        page: PageRequest,
+    ) -> Result<Page<Invoice>, DomainError> {
+        let (invoices, total_count) = tokio::try_join!(
+            self.invoice_repository.list(filter, &page),
+            self.invoice_repository.count(filter)
+        )?;
+
+        Ok(invoices.with_total_count(total_count))
+    }
+
+    /// Record a customer payment against an invoice's balance
+    pub async fn record_payment(
+        &self,
+        invoice_id: Uuid,
+        request: RecordPaymentRequest,
+        recorded_by: Uuid,
+    ) -> Result<RecordPaymentResponse, DomainError> {
+        let (invoice, payment) = self
+            .invoice_repository
+            .record_payment(invoice_id, &request, recorded_by)
+            .await?;
+
+        self.dispatch(
+            WebhookEventType::PaymentRecorded,
+            json!({ "invoice": invoice, "payment": payment }),
+        );
+
+        Ok(RecordPaymentResponse {
+            balance: invoice.balance(),
+            invoice,
+            payment,
+        })
+    }
+
+    /// Dispatch a webhook event without blocking the request
+    fn dispatch(&self, event_type: WebhookEventType, payload: serde_json::Value) {
+        let webhook_event = WebhookEvent::new(event_type, payload);
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch invoice webhook: {:?}", e);
+            }
+        });
+    }
+}
@@ -0,0 +1,149 @@
+use crate::domain::entities::attachment::ItemAttachment;
+use crate::domain::services::attachment_repository::AttachmentRepository;
+use crate::domain::services::blob_storage::BlobStorage;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::shared::error::DomainError;
+use crate::shared::spooled_file::SpooledFile;
+use crate::shared::tenant_scope::current_tenant;
+use serde::Serialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct UploadAttachmentRequest {
+    pub file_name: String,
+    pub content_type: String,
+    pub file: SpooledFile,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ItemAttachmentResponse {
+    #[serde(flatten)]
+    pub attachment: ItemAttachment,
+    pub download_url: String,
+}
+
+impl From<ItemAttachment> for ItemAttachmentResponse {
+    fn from(attachment: ItemAttachment) -> Self {
+        Self {
+            download_url: attachment.download_url(),
+            attachment,
+        }
+    }
+}
+
+/// Upload, list, download and delete the files attached to an item
+pub struct ManageItemAttachmentsUseCase<A: AttachmentRepository, I: ItemRepository> {
+    attachment_repository: Arc<A>,
+    item_repository: Arc<I>,
+    blob_storage: Arc<dyn BlobStorage>,
+}
+
+impl<A: AttachmentRepository, I: ItemRepository> ManageItemAttachmentsUseCase<A, I> {
+    pub fn new(
+        attachment_repository: Arc<A>,
+        item_repository: Arc<I>,
+        blob_storage: Arc<dyn BlobStorage>,
+    ) -> Self {
+        Self {
+            attachment_repository,
+            item_repository,
+            blob_storage,
+        }
+    }
+
+    pub async fn upload(
+        &self,
+        item_id: Uuid,
+        request: UploadAttachmentRequest,
+        created_by: Uuid,
+    ) -> Result<ItemAttachmentResponse, DomainError> {
+        self.ensure_item_exists(item_id).await?;
+
+        let attachment = ItemAttachment::new(
+            current_tenant(),
+            item_id,
+            &request.file_name,
+            &request.content_type,
+            request.file.len() as usize,
+            created_by,
+        )?;
+
+        self.blob_storage
+            .put_file(
+                &attachment.storage_key,
+                &attachment.content_type,
+                request.file.path(),
+            )
+            .await?;
+
+        if let Err(e) = self.attachment_repository.save(&attachment).await {
+            // Don't leave an unreferenced object behind
+            if let Err(cleanup) = self.blob_storage.delete(&attachment.storage_key).await {
+                eprintln!(
+                    "Failed to remove orphaned attachment {}: {:?}",
+                    attachment.storage_key, cleanup
+                );
+            }
+            return Err(e);
+        }
+
+        Ok(attachment.into())
+    }
+
+    pub async fn list(&self, item_id: Uuid) -> Result<Vec<ItemAttachmentResponse>, DomainError> {
+        self.ensure_item_exists(item_id).await?;
+
+        Ok(self
+            .attachment_repository
+            .list_for_item(item_id)
+            .await?
+            .into_iter()
+            .map(ItemAttachmentResponse::from)
+            .collect())
+    }
+
+    /// The attachment's metadata and file contents
+    pub async fn download(
+        &self,
+        item_id: Uuid,
+        attachment_id: Uuid,
+    ) -> Result<(ItemAttachment, Vec<u8>), DomainError> {
+        let attachment = self.find(item_id, attachment_id).await?;
+        let data = self.blob_storage.get(&attachment.storage_key).await?;
+        Ok((attachment, data))
+    }
+
+    pub async fn delete(&self, item_id: Uuid, attachment_id: Uuid) -> Result<(), DomainError> {
+        let attachment = self.find(item_id, attachment_id).await?;
+        self.blob_storage.delete(&attachment.storage_key).await?;
+        self.attachment_repository.delete(attachment.id).await
+    }
+
+    async fn find(
+        &self,
+        item_id: Uuid,
+        attachment_id: Uuid,
+    ) -> Result<ItemAttachment, DomainError> {
+        self.attachment_repository
+            .find_by_id(attachment_id)
+            .await?
+            .filter(|attachment| attachment.item_id == item_id)
+            .ok_or_else(|| {
+                DomainError::NotFound(
+                    format!(
+                        "Attachment {} not found for item {}",
+                        attachment_id, item_id
+                    )
+                    .into(),
+                )
+            })
+    }
+
+    async fn ensure_item_exists(&self, item_id: Uuid) -> Result<(), DomainError> {
+        self.item_repository
+            .find_by_id(item_id)
+            .await?
+            .map(|_| ())
+            .ok_or_else(|| DomainError::NotFound(format!("Item {} not found", item_id).into()))
+    }
+}
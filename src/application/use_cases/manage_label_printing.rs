@@ -0,0 +1,300 @@
+use crate::domain::entities::job::{CreateJobRequest, Job};
+use crate::domain::entities::printing::{
+    CreatePrinterRequest, PrintJob, PrintJobSource, PrintJobStatus, Printer, UpdatePrinterRequest,
+    PRINT_JOB_MAX_ATTEMPTS, PRINT_LABELS_JOB_TYPE,
+};
+use crate::domain::services::job_service::JobService;
+use crate::domain::services::label_printer::LabelPrinter;
+use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::services::print_repository::PrintRepository;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct ListPrintersQuery {
+    pub location_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListPrintersResponse {
+    pub printers: Vec<Printer>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePrintJobRequest {
+    pub printer_id: Option<Uuid>,
+    /// Print on this location's default printer instead of a named one
+    pub location_id: Option<Uuid>,
+    /// Print the carton labels of this shipment
+    pub shipment_id: Option<Uuid>,
+    /// Print this ZPL as is
+    pub zpl: Option<String>,
+    pub copies: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListPrintJobsQuery {
+    pub printer_id: Option<Uuid>,
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListPrintJobsResponse {
+    pub print_jobs: Vec<PrintJob>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PrintJobPayload {
+    print_job_id: Uuid,
+}
+
+/// Printers configured per location, and the queue of label jobs sent to them.
+/// Each print job is delivered by a background job so a printer that is off
+/// line or out of labels is retried with backoff rather than failing the
+/// request that printed.
+pub struct ManageLabelPrintingUseCase<P: PrintRepository, L: LocationRepository, S: JobService> {
+    print_repository: Arc<P>,
+    location_repository: Arc<L>,
+    job_service: Arc<S>,
+    label_printer: Arc<dyn LabelPrinter>,
+}
+
+impl<P: PrintRepository, L: LocationRepository, S: JobService> ManageLabelPrintingUseCase<P, L, S> {
+    pub fn new(
+        print_repository: Arc<P>,
+        location_repository: Arc<L>,
+        job_service: Arc<S>,
+        label_printer: Arc<dyn LabelPrinter>,
+    ) -> Self {
+        Self {
+            print_repository,
+            location_repository,
+            job_service,
+            label_printer,
+        }
+    }
+
+    pub async fn create_printer(
+        &self,
+        request: CreatePrinterRequest,
+    ) -> Result<Printer, DomainError> {
+        let printer = Printer::new(request)?;
+        self.ensure_location_exists(printer.location_id).await?;
+        self.print_repository.create_printer(&printer).await?;
+        Ok(printer)
+    }
+
+    pub async fn get_printer(&self, printer_id: Uuid) -> Result<Printer, DomainError> {
+        self.print_repository
+            .find_printer(printer_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Printer {} not found", printer_id).into())
+            })
+    }
+
+    pub async fn list_printers(
+        &self,
+        query: ListPrintersQuery,
+    ) -> Result<ListPrintersResponse, DomainError> {
+        let printers = self
+            .print_repository
+            .list_printers(query.location_id)
+            .await?;
+        Ok(ListPrintersResponse { printers })
+    }
+
+    pub async fn update_printer(
+        &self,
+        printer_id: Uuid,
+        request: UpdatePrinterRequest,
+    ) -> Result<Printer, DomainError> {
+        let mut printer = self.get_printer(printer_id).await?;
+        if let Some(location_id) = request.location_id {
+            self.ensure_location_exists(location_id).await?;
+        }
+        printer.update(request)?;
+        self.print_repository.update_printer(&printer).await?;
+        Ok(printer)
+    }
+
+    pub async fn delete_printer(&self, printer_id: Uuid) -> Result<(), DomainError> {
+        self.print_repository.delete_printer(printer_id).await
+    }
+
+    /// Queue ZPL on the requested printer, or on the location's default one.
+    /// `shipment_labels` is the shipment's rendered ZPL when the request
+    /// names a shipment.
+    pub async fn create_print_job(
+        &self,
+        tenant_id: Uuid,
+        request: CreatePrintJobRequest,
+        shipment_labels: Option<String>,
+    ) -> Result<PrintJob, DomainError> {
+        let printer = match (request.printer_id, request.location_id) {
+            (Some(printer_id), None) => self.get_printer(printer_id).await?,
+            (None, Some(location_id)) => self
+                .print_repository
+                .find_default_printer(location_id)
+                .await?
+                .ok_or_else(|| {
+                    DomainError::ValidationError(
+                        format!("Location {} has no active default printer", location_id).into(),
+                    )
+                })?,
+            _ => {
+                return Err(DomainError::ValidationError(
+                    "Provide exactly one of printer_id or location_id".into(),
+                ))
+            }
+        };
+        if !printer.active {
+            return Err(DomainError::ValidationError(
+                format!("Printer {} is inactive", printer.name).into(),
+            ));
+        }
+
+        let (source_type, content) = match (request.shipment_id, request.zpl) {
+            (Some(_), None) => (
+                PrintJobSource::ShipmentLabels,
+                shipment_labels.ok_or_else(|| {
+                    DomainError::ValidationError("Shipment labels were not rendered".into())
+                })?,
+            ),
+            (None, Some(zpl)) => (PrintJobSource::Raw, zpl),
+            _ => {
+                return Err(DomainError::ValidationError(
+                    "Provide exactly one of shipment_id or zpl".into(),
+                ))
+            }
+        };
+
+        let mut print_job = PrintJob::new(
+            printer.id,
+            source_type,
+            request.shipment_id,
+            content,
+            request.copies,
+        )?;
+        self.print_repository.create_print_job(&print_job).await?;
+        self.enqueue(tenant_id, &mut print_job).await?;
+        Ok(print_job)
+    }
+
+    pub async fn get_print_job(&self, print_job_id: Uuid) -> Result<PrintJob, DomainError> {
+        self.print_repository
+            .find_print_job(print_job_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Print job {} not found", print_job_id).into())
+            })
+    }
+
+    pub async fn list_print_jobs(
+        &self,
+        query: ListPrintJobsQuery,
+    ) -> Result<ListPrintJobsResponse, DomainError> {
+        let status = query
+            .status
+            .as_deref()
+            .map(PrintJobStatus::from_str)
+            .transpose()?;
+        let limit = query.limit.unwrap_or(50).clamp(1, 100);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let print_jobs = self
+            .print_repository
+            .list_print_jobs(query.printer_id, status, limit, offset)
+            .await?;
+        Ok(ListPrintJobsResponse { print_jobs })
+    }
+
+    /// Queue a print job that ran out of attempts again, once the printer is back
+    pub async fn retry_print_job(
+        &self,
+        tenant_id: Uuid,
+        print_job_id: Uuid,
+    ) -> Result<PrintJob, DomainError> {
+        let mut print_job = self.get_print_job(print_job_id).await?;
+        print_job.requeue()?;
+        self.print_repository.update_print_job(&print_job).await?;
+        self.enqueue(tenant_id, &mut print_job).await?;
+        Ok(print_job)
+    }
+
+    /// Send a queued print job to its printer, for the background job
+    /// delivering it. A failed send is recorded on the print job and returned
+    /// so the background job retries while it has attempts left.
+    pub async fn run(&self, job: &Job) -> Result<PrintJob, DomainError> {
+        let payload: PrintJobPayload =
+            serde_json::from_value(job.payload.clone().unwrap_or_default()).map_err(|e| {
+                DomainError::ValidationError(format!("Invalid print job payload: {}", e).into())
+            })?;
+        // Deleting a printer deletes its jobs, so there is nothing left to print
+        let mut print_job = self.get_print_job(payload.print_job_id).await?;
+        if print_job.status == PrintJobStatus::Printed {
+            return Ok(print_job);
+        }
+        let printer = self.get_printer(print_job.printer_id).await?;
+
+        print_job.start_attempt();
+        self.print_repository.update_print_job(&print_job).await?;
+
+        match self
+            .label_printer
+            .send(&printer, &print_job.payload())
+            .await
+        {
+            Ok(()) => {
+                print_job.mark_printed();
+                self.print_repository.update_print_job(&print_job).await?;
+                Ok(print_job)
+            }
+            Err(e) => {
+                print_job.fail_attempt(e.to_string(), job.attempts < job.max_attempts);
+                self.print_repository.update_print_job(&print_job).await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn enqueue(&self, tenant_id: Uuid, print_job: &mut PrintJob) -> Result<(), DomainError> {
+        let payload = PrintJobPayload {
+            print_job_id: print_job.id,
+        };
+        let job = self
+            .job_service
+            .enqueue_job(
+                tenant_id,
+                CreateJobRequest {
+                    job_type: PRINT_LABELS_JOB_TYPE.to_string(),
+                    payload: serde_json::to_value(&payload).map_err(|e| {
+                        DomainError::ValidationError(
+                            format!("Failed to serialize payload: {}", e).into(),
+                        )
+                    })?,
+                    max_attempts: Some(PRINT_JOB_MAX_ATTEMPTS),
+                },
+            )
+            .await?;
+        self.print_repository
+            .set_print_job_job_id(print_job.id, &job.job_id)
+            .await?;
+        print_job.job_id = Some(job.job_id);
+        Ok(())
+    }
+
+    async fn ensure_location_exists(&self, location_id: Uuid) -> Result<(), DomainError> {
+        match self.location_repository.find_by_id(location_id).await? {
+            Some(_) => Ok(()),
+            None => Err(DomainError::ValidationError(Message::localized(
+                "error.location_not_found",
+                &[("id", &location_id)],
+            ))),
+        }
+    }
+}
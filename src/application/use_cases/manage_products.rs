@@ -0,0 +1,167 @@
+use crate::application::use_cases::create_item::CreateItemUseCase;
+use crate::domain::entities::item::Item;
+use crate::domain::entities::product::{
+    CreateProductRequest, GenerateVariantsRequest, Product, UpdateProductRequest,
+};
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::product_repository::ProductRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct ListProductsQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListProductsResponse {
+    pub products: Vec<Product>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProductResponse {
+    #[serde(flatten)]
+    pub product: Product,
+    pub variants: Vec<Item>,
+}
+
+/// A combination that was not turned into a variant
+#[derive(Debug, Serialize)]
+pub struct SkippedVariant {
+    pub sku: String,
+    pub attributes: BTreeMap<String, String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GenerateVariantsResponse {
+    pub created: Vec<Item>,
+    pub skipped: Vec<SkippedVariant>,
+}
+
+pub struct ManageProductsUseCase<
+    P: ProductRepository,
+    I: ItemRepository,
+    D: WebhookDispatcher + 'static,
+> {
+    product_repository: Arc<P>,
+    item_repository: Arc<I>,
+    create_item_use_case: Arc<CreateItemUseCase<I, D>>,
+}
+
+impl<P: ProductRepository, I: ItemRepository, D: WebhookDispatcher + 'static>
+    ManageProductsUseCase<P, I, D>
+{
+    pub fn new(
+        product_repository: Arc<P>,
+        item_repository: Arc<I>,
+        create_item_use_case: Arc<CreateItemUseCase<I, D>>,
+    ) -> Self {
+        Self {
+            product_repository,
+            item_repository,
+            create_item_use_case,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        request: CreateProductRequest,
+        tenant_id: Uuid,
+    ) -> Result<ProductResponse, DomainError> {
+        let product = Product::new(tenant_id, request)?;
+        if self
+            .product_repository
+            .parent_sku_exists(&product.parent_sku)
+            .await?
+        {
+            return Err(DomainError::Conflict(
+                format!(
+                    "Product with parent SKU '{}' already exists",
+                    product.parent_sku
+                )
+                .into(),
+            ));
+        }
+        self.product_repository.create(&product).await?;
+        Ok(ProductResponse {
+            product,
+            variants: Vec::new(),
+        })
+    }
+
+    pub async fn get(&self, product_id: Uuid) -> Result<ProductResponse, DomainError> {
+        let product = self.find(product_id).await?;
+        let variants = self.item_repository.list_by_product(product_id).await?;
+        Ok(ProductResponse { product, variants })
+    }
+
+    pub async fn list(
+        &self,
+        query: ListProductsQuery,
+    ) -> Result<ListProductsResponse, DomainError> {
+        let limit = query.limit.unwrap_or(50).clamp(1, 100);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let products = self.product_repository.list(limit, offset).await?;
+        Ok(ListProductsResponse { products })
+    }
+
+    pub async fn update(
+        &self,
+        product_id: Uuid,
+        request: UpdateProductRequest,
+    ) -> Result<ProductResponse, DomainError> {
+        let mut product = self.find(product_id).await?;
+        product.update(request)?;
+        self.product_repository.update(&product).await?;
+        let variants = self.item_repository.list_by_product(product_id).await?;
+        Ok(ProductResponse { product, variants })
+    }
+
+    pub async fn delete(&self, product_id: Uuid) -> Result<(), DomainError> {
+        self.product_repository.delete(product_id).await
+    }
+
+    /// Create a variant item for every attribute combination that doesn't have one
+    /// yet. Each counts against the item quota; running out stops the batch, and
+    /// running it again picks up where it stopped.
+    pub async fn generate_variants(
+        &self,
+        product_id: Uuid,
+        request: GenerateVariantsRequest,
+    ) -> Result<GenerateVariantsResponse, DomainError> {
+        let product = self.find(product_id).await?;
+        let existing = self.item_repository.list_by_product(product_id).await?;
+
+        let mut created = Vec::new();
+        let mut skipped = Vec::new();
+        for values in product.missing_combinations(&existing) {
+            let variant = product.build_variant(&values, &request)?;
+            if self.item_repository.sku_exists(&variant.sku, None).await? {
+                skipped.push(SkippedVariant {
+                    reason: format!("SKU '{}' is already taken by another item", variant.sku),
+                    sku: variant.sku,
+                    attributes: variant.variant_attributes.unwrap_or_default(),
+                });
+                continue;
+            }
+            created.push(self.create_item_use_case.save_new(variant).await?);
+        }
+
+        Ok(GenerateVariantsResponse { created, skipped })
+    }
+
+    async fn find(&self, product_id: Uuid) -> Result<Product, DomainError> {
+        self.product_repository
+            .find_by_id(product_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Product {} not found", product_id).into())
+            })
+    }
+}
@@ -0,0 +1,92 @@
+use crate::domain::entities::putaway::{
+    CreatePutawayRuleRequest, PutawayRule, UpdatePutawayRuleRequest,
+};
+use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::services::putaway_rule_repository::PutawayRuleRepository;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct ListPutawayRulesQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListPutawayRulesResponse {
+    pub putaway_rules: Vec<PutawayRule>,
+}
+
+pub struct ManagePutawayRulesUseCase<P: PutawayRuleRepository, L: LocationRepository> {
+    putaway_rule_repository: Arc<P>,
+    location_repository: Arc<L>,
+}
+
+impl<P: PutawayRuleRepository, L: LocationRepository> ManagePutawayRulesUseCase<P, L> {
+    pub fn new(putaway_rule_repository: Arc<P>, location_repository: Arc<L>) -> Self {
+        Self {
+            putaway_rule_repository,
+            location_repository,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        request: CreatePutawayRuleRequest,
+    ) -> Result<PutawayRule, DomainError> {
+        let rule = PutawayRule::new(request)?;
+        self.ensure_location_exists(rule.location_id).await?;
+        self.putaway_rule_repository.create(&rule).await?;
+        Ok(rule)
+    }
+
+    pub async fn get(&self, rule_id: Uuid) -> Result<PutawayRule, DomainError> {
+        self.putaway_rule_repository
+            .find_by_id(rule_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Putaway rule {} not found", rule_id).into())
+            })
+    }
+
+    pub async fn list(
+        &self,
+        query: ListPutawayRulesQuery,
+    ) -> Result<ListPutawayRulesResponse, DomainError> {
+        let limit = query.limit.unwrap_or(50).clamp(1, 100);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let putaway_rules = self.putaway_rule_repository.list(limit, offset).await?;
+        Ok(ListPutawayRulesResponse { putaway_rules })
+    }
+
+    pub async fn update(
+        &self,
+        rule_id: Uuid,
+        request: UpdatePutawayRuleRequest,
+    ) -> Result<PutawayRule, DomainError> {
+        let mut rule = self.get(rule_id).await?;
+        if let Some(location_id) = request.location_id {
+            self.ensure_location_exists(location_id).await?;
+        }
+        rule.update(request)?;
+        self.putaway_rule_repository.update(&rule).await?;
+        Ok(rule)
+    }
+
+    pub async fn delete(&self, rule_id: Uuid) -> Result<(), DomainError> {
+        self.putaway_rule_repository.delete(rule_id).await
+    }
+
+    async fn ensure_location_exists(&self, location_id: Uuid) -> Result<(), DomainError> {
+        match self.location_repository.find_by_id(location_id).await? {
+            Some(_) => Ok(()),
+            None => Err(DomainError::ValidationError(Message::localized(
+                "error.location_not_found",
+                &[("id", &location_id)],
+            ))),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::domain::entities::receiving::{
+    check_over_receipt_percent, ReceivingSettings, SetSupplierToleranceRequest,
+    UpdateReceivingSettingsRequest,
+};
+use crate::domain::services::receiving_settings_repository::ReceivingSettingsRepository;
+use crate::shared::error::DomainError;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// How far past the ordered quantity purchase orders may be received, for the
+/// tenant as a whole and for individual suppliers
+pub struct ManageReceivingSettingsUseCase<R: ReceivingSettingsRepository> {
+    settings_repository: Arc<R>,
+}
+
+impl<R: ReceivingSettingsRepository> ManageReceivingSettingsUseCase<R> {
+    pub fn new(settings_repository: Arc<R>) -> Self {
+        Self {
+            settings_repository,
+        }
+    }
+
+    pub async fn get(&self) -> Result<ReceivingSettings, DomainError> {
+        self.settings_repository.find().await
+    }
+
+    pub async fn update(
+        &self,
+        request: UpdateReceivingSettingsRequest,
+    ) -> Result<ReceivingSettings, DomainError> {
+        check_over_receipt_percent(request.over_receipt_percent)?;
+        self.settings_repository
+            .save_default(request.over_receipt_percent)
+            .await?;
+        self.settings_repository.find().await
+    }
+
+    pub async fn set_supplier(
+        &self,
+        supplier_id: Uuid,
+        request: SetSupplierToleranceRequest,
+    ) -> Result<ReceivingSettings, DomainError> {
+        check_over_receipt_percent(request.over_receipt_percent)?;
+        self.settings_repository
+            .save_supplier(supplier_id, request.over_receipt_percent)
+            .await?;
+        self.settings_repository.find().await
+    }
+
+    /// Put the supplier back on the tenant's tolerance
+    pub async fn remove_supplier(&self, supplier_id: Uuid) -> Result<(), DomainError> {
+        if !self
+            .settings_repository
+            .delete_supplier(supplier_id)
+            .await?
+        {
+            return Err(DomainError::NotFound(
+                format!(
+                    "Supplier {} has no over-receipt tolerance of its own",
+                    supplier_id
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,464 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::use_cases::create_sandbox_tenant::{SandboxTenant, SeedPlan};
+use crate::domain::entities::tenant::{
+    CreateSandboxTenantRequest, PromoteSandboxRequest, Tenant, TenantType,
+};
+use crate::domain::entities::tenant_audit::{TenantAuditAction, TenantAuditEntry};
+use crate::domain::entities::user::UserRole;
+use crate::domain::services::sandbox_seed_repository::SandboxSeedRepository;
+use crate::domain::services::tenant_audit_repository::TenantAuditRepository;
+use crate::domain::services::tenant_repository::TenantRepository;
+use crate::domain::services::user_repository::UserRepository;
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::with_tenant;
+
+/// Audit entries returned by `audit_log`
+const AUDIT_LOG_LIMIT: i64 = 100;
+
+/// Resetting and promoting sandboxes. Either is allowed to the user who created
+/// the sandbox and to active admins of the sandbox itself; both are audited.
+pub struct ManageSandboxUseCase<T, S, U, A>
+where
+    T: TenantRepository,
+    S: SandboxSeedRepository,
+    U: UserRepository,
+    A: TenantAuditRepository,
+{
+    tenant_repository: Arc<T>,
+    seed_repository: Arc<S>,
+    user_repository: Arc<U>,
+    audit_repository: Arc<A>,
+    default_profile: String,
+    /// Base URL of the echo receiver, as given to `CreateSandboxTenantUseCase`
+    echo_url: String,
+}
+
+impl<T, S, U, A> ManageSandboxUseCase<T, S, U, A>
+where
+    T: TenantRepository,
+    S: SandboxSeedRepository,
+    U: UserRepository,
+    A: TenantAuditRepository,
+{
+    pub fn new(
+        tenant_repository: Arc<T>,
+        seed_repository: Arc<S>,
+        user_repository: Arc<U>,
+        audit_repository: Arc<A>,
+        default_profile: String,
+        echo_url: String,
+    ) -> Self {
+        Self {
+            tenant_repository,
+            seed_repository,
+            user_repository,
+            audit_repository,
+            default_profile,
+            echo_url,
+        }
+    }
+
+    /// Wipe the sandbox and seed it again. Without a request the sandbox gets
+    /// the profile and overrides it was last seeded with.
+    pub async fn reset(
+        &self,
+        tenant_id: Uuid,
+        request: Option<CreateSandboxTenantRequest>,
+        actor_id: Uuid,
+    ) -> Result<SandboxTenant, DomainError> {
+        let tenant = self.authorize(tenant_id, actor_id).await?;
+        let request = match request {
+            Some(request) => request,
+            None => with_tenant(tenant_id, self.seed_repository.find_record())
+                .await?
+                .map(|record| CreateSandboxTenantRequest {
+                    profile: Some(record.summary.profile),
+                    overrides: record.overrides,
+                })
+                .unwrap_or_default(),
+        };
+
+        let plan = SeedPlan::new(
+            &request,
+            &self.default_profile,
+            &self.echo_url,
+            tenant_id,
+            actor_id,
+        )?;
+        with_tenant(
+            tenant_id,
+            self.seed_repository.reseed(&plan.dataset, &plan.record),
+        )
+        .await?;
+
+        self.audit(
+            tenant_id,
+            TenantAuditAction::SandboxReset,
+            actor_id,
+            serde_json::json!({ "seeded": plan.record.summary }),
+        )
+        .await?;
+
+        Ok(SandboxTenant {
+            tenant,
+            seeded: plan.record.summary,
+            echo_url: plan.echo_url,
+            webhook_secret: plan.webhook_secret,
+        })
+    }
+
+    /// Turn the sandbox into a production tenant. Its users, quotas, webhooks and
+    /// settings carry over; the demo transactions and the echo webhook don't.
+    pub async fn promote(
+        &self,
+        tenant_id: Uuid,
+        request: PromoteSandboxRequest,
+        actor_id: Uuid,
+    ) -> Result<Tenant, DomainError> {
+        let mut tenant = self.authorize(tenant_id, actor_id).await?;
+        let previous_name = tenant.name.clone();
+        tenant.promote_to_production(request.name)?;
+
+        with_tenant(
+            tenant_id,
+            self.seed_repository.clear_transactions(&self.echo_url),
+        )
+        .await?;
+        self.tenant_repository.update_tenant(&tenant).await?;
+
+        self.audit(
+            tenant_id,
+            TenantAuditAction::SandboxPromoted,
+            actor_id,
+            serde_json::json!({ "previous_name": previous_name, "name": tenant.name }),
+        )
+        .await?;
+
+        Ok(tenant)
+    }
+
+    /// The tenant's audit entries, newest first; visible to whoever may manage it
+    pub async fn audit_log(
+        &self,
+        tenant_id: Uuid,
+        actor_id: Uuid,
+    ) -> Result<Vec<TenantAuditEntry>, DomainError> {
+        let tenant = self.find_tenant(tenant_id).await?;
+        self.check_access(&tenant, actor_id).await?;
+        self.audit_repository
+            .list_for_tenant(tenant_id, AUDIT_LOG_LIMIT)
+            .await
+    }
+
+    /// The sandbox, once the actor is known to be allowed to manage it
+    async fn authorize(&self, tenant_id: Uuid, actor_id: Uuid) -> Result<Tenant, DomainError> {
+        let tenant = self.find_tenant(tenant_id).await?;
+        self.check_access(&tenant, actor_id).await?;
+        if tenant.tenant_type != TenantType::Sandbox {
+            return Err(DomainError::BusinessLogicError(
+                format!("Tenant {} is not a sandbox", tenant_id).into(),
+            ));
+        }
+        Ok(tenant)
+    }
+
+    async fn find_tenant(&self, tenant_id: Uuid) -> Result<Tenant, DomainError> {
+        self.tenant_repository
+            .get_tenant(tenant_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Tenant {} not found", tenant_id).into()))
+    }
+
+    async fn check_access(&self, tenant: &Tenant, actor_id: Uuid) -> Result<(), DomainError> {
+        if tenant.created_by == Some(actor_id) {
+            return Ok(());
+        }
+        let is_admin = self
+            .user_repository
+            .find_by_id(actor_id)
+            .await?
+            .is_some_and(|user| {
+                user.tenant_id == tenant.id && user.role == UserRole::Admin && user.active
+            });
+        if !is_admin {
+            return Err(DomainError::Forbidden(
+                format!(
+                    "Only the creator of tenant {} or one of its admins may manage it",
+                    tenant.id
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn audit(
+        &self,
+        tenant_id: Uuid,
+        action: TenantAuditAction,
+        actor_id: Uuid,
+        details: serde_json::Value,
+    ) -> Result<(), DomainError> {
+        self.audit_repository
+            .record(&TenantAuditEntry::new(tenant_id, action, actor_id, details))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::sandbox_seed::{SeedDataset, SeedOverrides, SeedRecord};
+    use crate::domain::entities::tenant::TenantStatus;
+    use crate::domain::entities::user::User;
+    use crate::domain::services::tenant_repository::MockTenantRepository;
+    use crate::domain::value_objects::{email::Email, password_hash::PasswordHash};
+    use crate::shared::tenant_scope::current_tenant;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    const ECHO_URL: &str = "http://127.0.0.1:8080/sandbox/echo";
+
+    /// Remembers the last seed, and which tenant each call ran under
+    #[derive(Default)]
+    struct FakeSeeder {
+        record: Mutex<Option<SeedRecord>>,
+        calls: Mutex<Vec<(&'static str, Option<Uuid>)>>,
+    }
+
+    #[async_trait]
+    impl SandboxSeedRepository for FakeSeeder {
+        async fn seed(&self, _: &SeedDataset, record: &SeedRecord) -> Result<(), DomainError> {
+            self.calls.lock().unwrap().push(("seed", current_tenant()));
+            *self.record.lock().unwrap() = Some(record.clone());
+            Ok(())
+        }
+
+        async fn reseed(&self, _: &SeedDataset, record: &SeedRecord) -> Result<(), DomainError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(("reseed", current_tenant()));
+            *self.record.lock().unwrap() = Some(record.clone());
+            Ok(())
+        }
+
+        async fn find_record(&self) -> Result<Option<SeedRecord>, DomainError> {
+            Ok(self.record.lock().unwrap().clone())
+        }
+
+        async fn clear_transactions(&self, echo_url: &str) -> Result<(), DomainError> {
+            assert_eq!(echo_url, ECHO_URL);
+            self.calls.lock().unwrap().push(("clear", current_tenant()));
+            *self.record.lock().unwrap() = None;
+            Ok(())
+        }
+    }
+
+    struct Users(Vec<User>);
+
+    #[async_trait]
+    impl UserRepository for Users {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+            Ok(self.0.iter().find(|user| user.id == id).cloned())
+        }
+
+        async fn find_by_email(&self, _email: &Email) -> Result<Option<User>, DomainError> {
+            Ok(None)
+        }
+
+        async fn save(&self, _user: &User) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn update(&self, _user: &User) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: Uuid) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn email_exists(
+            &self,
+            _email: &Email,
+            _exclude_user_id: Option<Uuid>,
+        ) -> Result<bool, DomainError> {
+            Ok(false)
+        }
+
+        async fn list_by_tenant(&self, tenant_id: Uuid) -> Result<Vec<User>, DomainError> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|user| user.tenant_id == tenant_id)
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[derive(Default)]
+    struct AuditLog(Mutex<Vec<TenantAuditEntry>>);
+
+    #[async_trait]
+    impl TenantAuditRepository for AuditLog {
+        async fn record(&self, entry: &TenantAuditEntry) -> Result<(), DomainError> {
+            self.0.lock().unwrap().push(entry.clone());
+            Ok(())
+        }
+
+        async fn list_for_tenant(
+            &self,
+            tenant_id: Uuid,
+            _limit: i64,
+        ) -> Result<Vec<TenantAuditEntry>, DomainError> {
+            let entries = self.0.lock().unwrap();
+            Ok(entries
+                .iter()
+                .rev()
+                .filter(|entry| entry.tenant_id == tenant_id)
+                .cloned()
+                .collect())
+        }
+    }
+
+    fn user(tenant_id: Uuid, role: UserRole) -> User {
+        let mut user = User::new(
+            Email::new(format!("{}@example.com", Uuid::new_v4().simple())).unwrap(),
+            PasswordHash::new("password123").unwrap(),
+            "Sandbox".to_string(),
+            "User".to_string(),
+            tenant_id,
+        )
+        .unwrap();
+        user.role = role;
+        user
+    }
+
+    fn use_case(
+        tenant: Tenant,
+        users: Vec<User>,
+        seeder: Arc<FakeSeeder>,
+        audit: Arc<AuditLog>,
+    ) -> ManageSandboxUseCase<MockTenantRepository, FakeSeeder, Users, AuditLog> {
+        let mut tenants = MockTenantRepository::new();
+        tenants
+            .expect_get_tenant()
+            .returning(move |_| Ok(Some(tenant.clone())));
+        tenants.expect_update_tenant().returning(|_| Ok(()));
+        ManageSandboxUseCase::new(
+            Arc::new(tenants),
+            seeder,
+            Arc::new(Users(users)),
+            audit,
+            "demo".to_string(),
+            ECHO_URL.to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_reset_replays_the_last_seed_and_audits() {
+        let creator = Uuid::new_v4();
+        let sandbox = Tenant::new_sandbox(Some(creator));
+        let seeder = Arc::new(FakeSeeder::default());
+        let audit = Arc::new(AuditLog::default());
+        let use_case = use_case(sandbox.clone(), vec![], seeder.clone(), audit.clone());
+
+        let first = CreateSandboxTenantRequest {
+            profile: Some("minimal".to_string()),
+            overrides: SeedOverrides {
+                items: Some(12),
+                ..Default::default()
+            },
+        };
+        use_case
+            .reset(sandbox.id, Some(first), creator)
+            .await
+            .unwrap();
+        let replayed = use_case.reset(sandbox.id, None, creator).await.unwrap();
+
+        assert_eq!(replayed.seeded.profile, "minimal");
+        assert_eq!(replayed.seeded.items, 12);
+        assert_eq!(replayed.echo_url, format!("{}/{}", ECHO_URL, sandbox.id));
+        assert_eq!(
+            *seeder.calls.lock().unwrap(),
+            vec![("reseed", Some(sandbox.id)), ("reseed", Some(sandbox.id))]
+        );
+        let entries = use_case.audit_log(sandbox.id, creator).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].action, TenantAuditAction::SandboxReset);
+        assert_eq!(entries[0].actor_id, creator);
+        assert_eq!(entries[0].details["seeded"]["items"], 12);
+    }
+
+    #[tokio::test]
+    async fn test_only_the_creator_or_a_sandbox_admin_may_promote() {
+        let sandbox = Tenant::new_sandbox(Some(Uuid::new_v4()));
+        let member = user(sandbox.id, UserRole::Member);
+        let outsider = user(Uuid::new_v4(), UserRole::Admin);
+        let admin = user(sandbox.id, UserRole::Admin);
+        let seeder = Arc::new(FakeSeeder::default());
+        let audit = Arc::new(AuditLog::default());
+        let use_case = use_case(
+            sandbox.clone(),
+            vec![member.clone(), outsider.clone(), admin.clone()],
+            seeder.clone(),
+            audit.clone(),
+        );
+
+        for actor in [member.id, outsider.id] {
+            let result = use_case
+                .promote(sandbox.id, PromoteSandboxRequest::default(), actor)
+                .await;
+            assert!(matches!(result, Err(DomainError::Forbidden(_))));
+        }
+        assert!(seeder.calls.lock().unwrap().is_empty());
+
+        let request = PromoteSandboxRequest {
+            name: Some("Acme Warehousing".to_string()),
+        };
+        let tenant = use_case
+            .promote(sandbox.id, request, admin.id)
+            .await
+            .unwrap();
+
+        assert_eq!(tenant.tenant_type, TenantType::Production);
+        assert_eq!(tenant.status, TenantStatus::Active);
+        assert_eq!(tenant.name, "Acme Warehousing");
+        assert!(tenant.expires_at.is_none());
+        assert_eq!(
+            *seeder.calls.lock().unwrap(),
+            vec![("clear", Some(sandbox.id))]
+        );
+        let entries = audit.0.lock().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, TenantAuditAction::SandboxPromoted);
+        assert_eq!(entries[0].actor_id, admin.id);
+    }
+
+    #[tokio::test]
+    async fn test_production_tenants_cannot_be_reset_or_promoted() {
+        let creator = Uuid::new_v4();
+        let mut tenant = Tenant::new_sandbox(Some(creator));
+        tenant.promote_to_production(None).unwrap();
+        let use_case = use_case(
+            tenant.clone(),
+            vec![],
+            Arc::new(FakeSeeder::default()),
+            Arc::new(AuditLog::default()),
+        );
+
+        assert!(matches!(
+            use_case.reset(tenant.id, None, creator).await,
+            Err(DomainError::BusinessLogicError(_))
+        ));
+        assert!(matches!(
+            use_case
+                .promote(tenant.id, PromoteSandboxRequest::default(), creator)
+                .await,
+            Err(DomainError::BusinessLogicError(_))
+        ));
+    }
+}
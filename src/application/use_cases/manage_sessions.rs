@@ -0,0 +1,42 @@
+use crate::domain::entities::session::SessionSummary;
+use crate::domain::services::session_repository::SessionRepository;
+use crate::shared::error::DomainError;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A user's logins: the devices holding a token, and revoking one of them
+pub struct ManageSessionsUseCase<S: SessionRepository> {
+    session_repository: Arc<S>,
+}
+
+impl<S: SessionRepository> ManageSessionsUseCase<S> {
+    pub fn new(session_repository: Arc<S>) -> Self {
+        Self { session_repository }
+    }
+
+    /// The user's active sessions, flagging the one `current_session_id` names
+    pub async fn list(
+        &self,
+        user_id: Uuid,
+        current_session_id: Option<Uuid>,
+    ) -> Result<Vec<SessionSummary>, DomainError> {
+        Ok(self
+            .session_repository
+            .list_active(user_id)
+            .await?
+            .into_iter()
+            .map(|session| SessionSummary::new(session, current_session_id))
+            .collect())
+    }
+
+    /// Revoke one of the user's sessions; its token is refused from the next
+    /// request on. Revoking the current session logs the caller out.
+    pub async fn revoke(&self, user_id: Uuid, session_id: Uuid) -> Result<(), DomainError> {
+        if !self.session_repository.revoke(user_id, session_id).await? {
+            return Err(DomainError::NotFound(
+                format!("Session {} not found", session_id).into(),
+            ));
+        }
+        Ok(())
+    }
+}
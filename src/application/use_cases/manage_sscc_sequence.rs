@@ -0,0 +1,41 @@
+use crate::domain::entities::gs1::{SsccSequence, UpdateSsccSequenceRequest};
+use crate::domain::services::shipment_repository::ShipmentRepository;
+use crate::shared::error::DomainError;
+use std::sync::Arc;
+
+pub struct ManageSsccSequenceUseCase<S: ShipmentRepository> {
+    shipment_repository: Arc<S>,
+}
+
+impl<S: ShipmentRepository> ManageSsccSequenceUseCase<S> {
+    pub fn new(shipment_repository: Arc<S>) -> Self {
+        Self {
+            shipment_repository,
+        }
+    }
+
+    pub async fn get(&self) -> Result<SsccSequence, DomainError> {
+        self.shipment_repository
+            .find_sscc_sequence()
+            .await?
+            .ok_or_else(|| DomainError::NotFound("No SSCC sequence is configured".into()))
+    }
+
+    /// Set the GS1 company prefix cartons are numbered under
+    pub async fn update(
+        &self,
+        request: UpdateSsccSequenceRequest,
+    ) -> Result<SsccSequence, DomainError> {
+        let sequence = match self.shipment_repository.find_sscc_sequence().await? {
+            Some(mut sequence) => {
+                sequence.update(request)?;
+                sequence
+            }
+            None => SsccSequence::new(request)?,
+        };
+        self.shipment_repository
+            .save_sscc_sequence(&sequence)
+            .await?;
+        Ok(sequence)
+    }
+}
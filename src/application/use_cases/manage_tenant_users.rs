@@ -0,0 +1,348 @@
+use crate::domain::entities::invitation::Invitation;
+use crate::domain::entities::user::{User, UserRole};
+use crate::domain::services::invitation_repository::InvitationRepository;
+use crate::domain::services::session_repository::SessionRepository;
+use crate::domain::services::user_repository::UserRepository;
+use crate::domain::value_objects::email::Email;
+use crate::shared::error::DomainError;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct InviteUserRequest {
+    pub email: String,
+    pub role: Option<UserRole>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InviteUserResponse {
+    pub invitation: Invitation,
+    /// Shown only here; the invitee redeems it at `POST /users/register`
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChangeUserRoleRequest {
+    pub role: UserRole,
+}
+
+/// A tenant user as exposed by the API, without credentials
+#[derive(Debug, Serialize)]
+pub struct TenantUserResponse {
+    pub id: Uuid,
+    pub email: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub role: UserRole,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<User> for TenantUserResponse {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            email: user.email.as_str().to_string(),
+            first_name: user.first_name,
+            last_name: user.last_name,
+            role: user.role,
+            active: user.active,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListTenantUsersResponse {
+    pub users: Vec<TenantUserResponse>,
+}
+
+/// Invite, deactivate and change the role of a tenant's users. Only the tenant's
+/// admins may make these changes.
+pub struct ManageTenantUsersUseCase<
+    U: UserRepository,
+    I: InvitationRepository,
+    S: SessionRepository,
+> {
+    user_repository: Arc<U>,
+    invitation_repository: Arc<I>,
+    session_repository: Arc<S>,
+    invitation_ttl: Duration,
+}
+
+impl<U: UserRepository, I: InvitationRepository, S: SessionRepository>
+    ManageTenantUsersUseCase<U, I, S>
+{
+    pub fn new(
+        user_repository: Arc<U>,
+        invitation_repository: Arc<I>,
+        session_repository: Arc<S>,
+        invitation_ttl: Duration,
+    ) -> Self {
+        Self {
+            user_repository,
+            invitation_repository,
+            session_repository,
+            invitation_ttl,
+        }
+    }
+
+    pub async fn invite(
+        &self,
+        tenant_id: Uuid,
+        actor_id: Uuid,
+        request: InviteUserRequest,
+    ) -> Result<InviteUserResponse, DomainError> {
+        self.require_admin(tenant_id, actor_id).await?;
+        let email = Email::new(request.email)?;
+        if self.user_repository.email_exists(&email, None).await? {
+            return Err(DomainError::Conflict(
+                format!("A user with email {} already exists", email.as_str()).into(),
+            ));
+        }
+
+        let (invitation, token) = Invitation::new(
+            tenant_id,
+            email,
+            request.role.unwrap_or(UserRole::Member),
+            self.invitation_ttl,
+        );
+        self.invitation_repository.save(&invitation).await?;
+
+        Ok(InviteUserResponse { invitation, token })
+    }
+
+    pub async fn list(&self, tenant_id: Uuid) -> Result<ListTenantUsersResponse, DomainError> {
+        let users = self.user_repository.list_by_tenant(tenant_id).await?;
+        Ok(ListTenantUsersResponse {
+            users: users.into_iter().map(TenantUserResponse::from).collect(),
+        })
+    }
+
+    /// Activate or deactivate a user. A deactivated user is logged out of all
+    /// their sessions.
+    pub async fn set_active(
+        &self,
+        tenant_id: Uuid,
+        actor_id: Uuid,
+        user_id: Uuid,
+        active: bool,
+    ) -> Result<TenantUserResponse, DomainError> {
+        self.require_admin(tenant_id, actor_id).await?;
+        let mut user = self.find_tenant_user(tenant_id, user_id).await?;
+        if active {
+            user.activate();
+        } else {
+            user.deactivate();
+        }
+        self.user_repository.update(&user).await?;
+        if !active {
+            self.session_repository.revoke_all(user.id).await?;
+        }
+        Ok(user.into())
+    }
+
+    pub async fn change_role(
+        &self,
+        tenant_id: Uuid,
+        actor_id: Uuid,
+        user_id: Uuid,
+        request: ChangeUserRoleRequest,
+    ) -> Result<TenantUserResponse, DomainError> {
+        self.require_admin(tenant_id, actor_id).await?;
+        let mut user = self.find_tenant_user(tenant_id, user_id).await?;
+        user.change_role(request.role);
+        self.user_repository.update(&user).await?;
+        Ok(user.into())
+    }
+
+    async fn require_admin(&self, tenant_id: Uuid, actor_id: Uuid) -> Result<(), DomainError> {
+        let is_admin = self
+            .user_repository
+            .find_by_id(actor_id)
+            .await?
+            .is_some_and(|user| {
+                user.tenant_id == tenant_id && user.role == UserRole::Admin && user.active
+            });
+        if !is_admin {
+            return Err(DomainError::Forbidden(
+                "Only tenant admins may manage users".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Users of other tenants are reported as missing rather than forbidden, so ids
+    /// can't be probed across tenants
+    async fn find_tenant_user(&self, tenant_id: Uuid, user_id: Uuid) -> Result<User, DomainError> {
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .filter(|user| user.tenant_id == tenant_id)
+            .ok_or_else(|| DomainError::NotFound(format!("User {} not found", user_id).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::session_repository::MockSessionRepository;
+    use crate::domain::value_objects::password_hash::PasswordHash;
+    use async_trait::async_trait;
+
+    struct Users(Vec<User>);
+
+    #[async_trait]
+    impl UserRepository for Users {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+            Ok(self.0.iter().find(|user| user.id == id).cloned())
+        }
+
+        async fn find_by_email(&self, _email: &Email) -> Result<Option<User>, DomainError> {
+            Ok(None)
+        }
+
+        async fn save(&self, _user: &User) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn update(&self, _user: &User) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: Uuid) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn email_exists(
+            &self,
+            _email: &Email,
+            _exclude_user_id: Option<Uuid>,
+        ) -> Result<bool, DomainError> {
+            Ok(false)
+        }
+
+        async fn list_by_tenant(&self, _tenant_id: Uuid) -> Result<Vec<User>, DomainError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    struct Invitations;
+
+    #[async_trait]
+    impl InvitationRepository for Invitations {
+        async fn save(&self, _invitation: &Invitation) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn find_by_token_hash(
+            &self,
+            _token_hash: &str,
+        ) -> Result<Option<Invitation>, DomainError> {
+            Ok(None)
+        }
+
+        async fn mark_accepted(&self, _invitation: &Invitation) -> Result<bool, DomainError> {
+            Ok(true)
+        }
+    }
+
+    fn user(tenant_id: Uuid, role: UserRole) -> User {
+        let mut user = User::new(
+            Email::new(format!("{}@example.com", Uuid::new_v4())).unwrap(),
+            PasswordHash::from_hash("hash".to_string()),
+            "Ada".to_string(),
+            "Lovelace".to_string(),
+            tenant_id,
+        )
+        .unwrap();
+        user.change_role(role);
+        user
+    }
+
+    fn use_case(
+        users: Vec<User>,
+        sessions: MockSessionRepository,
+    ) -> ManageTenantUsersUseCase<Users, Invitations, MockSessionRepository> {
+        ManageTenantUsersUseCase::new(
+            Arc::new(Users(users)),
+            Arc::new(Invitations),
+            Arc::new(sessions),
+            Duration::hours(1),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_only_admins_of_the_tenant_manage_users() {
+        let tenant_id = Uuid::new_v4();
+        let member = user(tenant_id, UserRole::Member);
+        let outside_admin = user(Uuid::new_v4(), UserRole::Admin);
+        let target = user(tenant_id, UserRole::Member);
+        let use_case = use_case(
+            vec![member.clone(), outside_admin.clone(), target.clone()],
+            MockSessionRepository::new(),
+        );
+
+        for actor in [&member, &outside_admin] {
+            let invite = use_case
+                .invite(
+                    tenant_id,
+                    actor.id,
+                    InviteUserRequest {
+                        email: "new@example.com".to_string(),
+                        role: Some(UserRole::Admin),
+                    },
+                )
+                .await;
+            assert!(matches!(invite, Err(DomainError::Forbidden(_))));
+
+            let deactivate = use_case
+                .set_active(tenant_id, actor.id, target.id, false)
+                .await;
+            assert!(matches!(deactivate, Err(DomainError::Forbidden(_))));
+
+            let promote = use_case
+                .change_role(
+                    tenant_id,
+                    actor.id,
+                    target.id,
+                    ChangeUserRoleRequest {
+                        role: UserRole::Admin,
+                    },
+                )
+                .await;
+            assert!(matches!(promote, Err(DomainError::Forbidden(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deactivating_a_user_revokes_their_sessions() {
+        let tenant_id = Uuid::new_v4();
+        let admin = user(tenant_id, UserRole::Admin);
+        let target = user(tenant_id, UserRole::Member);
+        let mut sessions = MockSessionRepository::new();
+        let target_id = target.id;
+        sessions
+            .expect_revoke_all()
+            .withf(move |user_id| *user_id == target_id)
+            .times(1)
+            .returning(|_| Ok(()));
+        let use_case = use_case(vec![admin.clone(), target.clone()], sessions);
+
+        let response = use_case
+            .set_active(tenant_id, admin.id, target.id, false)
+            .await
+            .unwrap();
+        assert!(!response.active);
+
+        // Reactivating leaves sessions alone; the user logs in again
+        use_case
+            .set_active(tenant_id, admin.id, target.id, true)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,44 @@
+use crate::domain::entities::time_zone::{
+    parse_time_zone, TimeZoneSettings, UpdateTimeZoneSettingsRequest,
+};
+use crate::domain::services::tenant_repository::TenantRepository;
+use crate::domain::services::time_zone_service::TimeZoneService;
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::current_tenant;
+use std::sync::Arc;
+
+/// The time zone the tenant's calendar days are read in: report date ranges,
+/// nightly stock snapshots and billing periods
+pub struct ManageTimeZoneUseCase<T: TenantRepository> {
+    tenant_repository: Arc<T>,
+    time_zone_service: Arc<dyn TimeZoneService>,
+}
+
+impl<T: TenantRepository> ManageTimeZoneUseCase<T> {
+    pub fn new(tenant_repository: Arc<T>, time_zone_service: Arc<dyn TimeZoneService>) -> Self {
+        Self {
+            tenant_repository,
+            time_zone_service,
+        }
+    }
+
+    pub async fn get_settings(&self) -> Result<TimeZoneSettings, DomainError> {
+        Ok(TimeZoneSettings {
+            timezone: self.time_zone_service.time_zone().await?.name().to_string(),
+        })
+    }
+
+    /// Change the time zone. Existing snapshots keep the day they were taken on.
+    pub async fn update_settings(
+        &self,
+        request: UpdateTimeZoneSettingsRequest,
+    ) -> Result<TimeZoneSettings, DomainError> {
+        let timezone = parse_time_zone(&request.timezone)?.name().to_string();
+        let tenant_id = current_tenant()
+            .ok_or_else(|| DomainError::ValidationError("No tenant is in scope".into()))?;
+        self.tenant_repository
+            .set_timezone(tenant_id, &timezone)
+            .await?;
+        Ok(TimeZoneSettings { timezone })
+    }
+}
@@ -0,0 +1,87 @@
+use crate::domain::entities::edi::{
+    CreateTradingPartnerRequest, TradingPartner, UpdateTradingPartnerRequest,
+};
+use crate::domain::services::edi_repository::EdiRepository;
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct ListTradingPartnersQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListTradingPartnersResponse {
+    pub trading_partners: Vec<TradingPartner>,
+}
+
+pub struct ManageTradingPartnersUseCase<E: EdiRepository> {
+    edi_repository: Arc<E>,
+}
+
+impl<E: EdiRepository> ManageTradingPartnersUseCase<E> {
+    pub fn new(edi_repository: Arc<E>) -> Self {
+        Self { edi_repository }
+    }
+
+    pub async fn create(
+        &self,
+        request: CreateTradingPartnerRequest,
+    ) -> Result<TradingPartner, DomainError> {
+        let partner = TradingPartner::new(request)?;
+        if self
+            .edi_repository
+            .find_partner_by_interchange_id(&partner.interchange_id)
+            .await?
+            .is_some()
+        {
+            return Err(DomainError::Conflict(
+                format!(
+                    "A trading partner with interchange ID '{}' already exists",
+                    partner.interchange_id
+                )
+                .into(),
+            ));
+        }
+        self.edi_repository.create_partner(&partner).await?;
+        Ok(partner)
+    }
+
+    pub async fn get(&self, partner_id: Uuid) -> Result<TradingPartner, DomainError> {
+        self.edi_repository
+            .find_partner_by_id(partner_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Trading partner {} not found", partner_id).into())
+            })
+    }
+
+    pub async fn list(
+        &self,
+        query: ListTradingPartnersQuery,
+    ) -> Result<ListTradingPartnersResponse, DomainError> {
+        let limit = query.limit.unwrap_or(50).clamp(1, 100);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let trading_partners = self.edi_repository.list_partners(limit, offset).await?;
+        Ok(ListTradingPartnersResponse { trading_partners })
+    }
+
+    pub async fn update(
+        &self,
+        partner_id: Uuid,
+        request: UpdateTradingPartnerRequest,
+    ) -> Result<TradingPartner, DomainError> {
+        let mut partner = self.get(partner_id).await?;
+        partner.update(request)?;
+        self.edi_repository.update_partner(&partner).await?;
+        Ok(partner)
+    }
+
+    /// Partners with exchanged documents can only be deactivated
+    pub async fn delete(&self, partner_id: Uuid) -> Result<(), DomainError> {
+        self.edi_repository.delete_partner(partner_id).await
+    }
+}
@@ -0,0 +1,373 @@
+use crate::domain::entities::two_factor::{
+    TwoFactor, TwoFactorCodeRequest, TwoFactorEnrollment, TwoFactorPolicy, TwoFactorStatus,
+};
+use crate::domain::entities::user::{User, UserRole};
+use crate::domain::services::session_repository::SessionRepository;
+use crate::domain::services::tenant_repository::TenantRepository;
+use crate::domain::services::totp;
+use crate::domain::services::two_factor_repository::TwoFactorRepository;
+use crate::domain::services::user_repository::UserRepository;
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::current_tenant;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Shown as the account's issuer in authenticator apps
+const TOTP_ISSUER: &str = "The Warehouse Hub";
+
+/// Check a code against `two_factor`: the authenticator's current code, or
+/// when `allow_recovery` an unused recovery code. An accepted code is used up,
+/// so save the enrollment afterwards.
+pub fn verify_code(two_factor: &mut TwoFactor, code: &str, allow_recovery: bool) -> bool {
+    match totp::matching_step(&two_factor.secret, code, chrono::Utc::now().timestamp()) {
+        Some(step) => two_factor.accept_step(step),
+        None => allow_recovery && two_factor.use_recovery_code(code),
+    }
+}
+
+/// Enroll in, confirm and turn off two-factor login, and the tenant's policy
+/// requiring it for admins
+pub struct ManageTwoFactorUseCase<
+    U: UserRepository,
+    F: TwoFactorRepository,
+    T: TenantRepository,
+    S: SessionRepository,
+> {
+    user_repository: Arc<U>,
+    two_factor_repository: Arc<F>,
+    tenant_repository: Arc<T>,
+    session_repository: Arc<S>,
+}
+
+impl<U: UserRepository, F: TwoFactorRepository, T: TenantRepository, S: SessionRepository>
+    ManageTwoFactorUseCase<U, F, T, S>
+{
+    pub fn new(
+        user_repository: Arc<U>,
+        two_factor_repository: Arc<F>,
+        tenant_repository: Arc<T>,
+        session_repository: Arc<S>,
+    ) -> Self {
+        Self {
+            user_repository,
+            two_factor_repository,
+            tenant_repository,
+            session_repository,
+        }
+    }
+
+    pub async fn status(&self, user_id: Uuid) -> Result<TwoFactorStatus, DomainError> {
+        let user = self.find_user(user_id).await?;
+        let enabled = self.enabled(user_id).await?;
+        Ok(TwoFactorStatus {
+            enabled: enabled.is_some(),
+            recovery_codes_remaining: enabled.map_or(0, |tf| tf.recovery_code_hashes.len()),
+            required: self.is_required(&user).await?,
+        })
+    }
+
+    /// Start enrolling an authenticator app. Starting again before confirming
+    /// replaces the pending secret and recovery codes.
+    pub async fn enroll(&self, user_id: Uuid) -> Result<TwoFactorEnrollment, DomainError> {
+        let user = self.find_user(user_id).await?;
+        if self.enabled(user_id).await?.is_some() {
+            return Err(DomainError::Conflict(
+                "Two-factor authentication is already enabled".into(),
+            ));
+        }
+
+        let (two_factor, recovery_codes) = TwoFactor::new(user_id, totp::generate_secret());
+        self.two_factor_repository.save(&two_factor).await?;
+
+        Ok(TwoFactorEnrollment {
+            provisioning_uri: totp::provisioning_uri(
+                TOTP_ISSUER,
+                user.email.as_str(),
+                &two_factor.secret,
+            ),
+            secret: two_factor.secret,
+            recovery_codes,
+        })
+    }
+
+    /// Turn two-factor login on with a code from the newly enrolled app
+    pub async fn confirm(
+        &self,
+        user_id: Uuid,
+        request: TwoFactorCodeRequest,
+    ) -> Result<TwoFactorStatus, DomainError> {
+        let mut two_factor = self
+            .two_factor_repository
+            .find_by_user(user_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::BusinessLogicError(
+                    "Start two-factor enrollment before confirming it".into(),
+                )
+            })?;
+        if two_factor.is_enabled() {
+            return Err(DomainError::Conflict(
+                "Two-factor authentication is already enabled".into(),
+            ));
+        }
+        if !verify_code(&mut two_factor, &request.code, false) {
+            return Err(DomainError::ValidationError(
+                "Invalid two-factor code".into(),
+            ));
+        }
+
+        two_factor.enable();
+        self.two_factor_repository.save(&two_factor).await?;
+        self.session_repository
+            .clear_two_factor_enrollment(user_id)
+            .await?;
+        self.status(user_id).await
+    }
+
+    /// Turn two-factor login off, proven with an authenticator or recovery code.
+    /// Admins can't while their tenant requires it.
+    pub async fn disable(
+        &self,
+        user_id: Uuid,
+        request: TwoFactorCodeRequest,
+    ) -> Result<(), DomainError> {
+        let user = self.find_user(user_id).await?;
+        let mut two_factor = self.enabled(user_id).await?.ok_or_else(|| {
+            DomainError::NotFound("Two-factor authentication is not enabled".into())
+        })?;
+        if self.is_required(&user).await? {
+            return Err(DomainError::Forbidden(
+                "Your tenant requires two-factor authentication for admins".into(),
+            ));
+        }
+        if !verify_code(&mut two_factor, &request.code, true) {
+            return Err(DomainError::ValidationError(
+                "Invalid two-factor code".into(),
+            ));
+        }
+
+        self.two_factor_repository.delete(user_id).await
+    }
+
+    pub async fn get_policy(&self) -> Result<TwoFactorPolicy, DomainError> {
+        Ok(TwoFactorPolicy {
+            require_for_admins: self
+                .tenant_repository
+                .get_admin_two_factor_required(Self::tenant_id()?)
+                .await?,
+        })
+    }
+
+    /// Change the policy. Admins already logged in keep their sessions; the
+    /// policy applies from their next login.
+    pub async fn update_policy(
+        &self,
+        actor_id: Uuid,
+        policy: TwoFactorPolicy,
+    ) -> Result<TwoFactorPolicy, DomainError> {
+        let is_admin = self
+            .user_repository
+            .find_by_id(actor_id)
+            .await?
+            .is_some_and(|user| user.role == UserRole::Admin && user.active);
+        if !is_admin {
+            return Err(DomainError::Forbidden(
+                "Only tenant admins may change the two-factor policy".into(),
+            ));
+        }
+
+        self.tenant_repository
+            .set_admin_two_factor_required(Self::tenant_id()?, policy.require_for_admins)
+            .await?;
+        Ok(policy)
+    }
+
+    async fn find_user(&self, user_id: Uuid) -> Result<User, DomainError> {
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("User {} not found", user_id).into()))
+    }
+
+    async fn enabled(&self, user_id: Uuid) -> Result<Option<TwoFactor>, DomainError> {
+        Ok(self
+            .two_factor_repository
+            .find_by_user(user_id)
+            .await?
+            .filter(TwoFactor::is_enabled))
+    }
+
+    async fn is_required(&self, user: &User) -> Result<bool, DomainError> {
+        Ok(user.role == UserRole::Admin
+            && self
+                .tenant_repository
+                .get_admin_two_factor_required(user.tenant_id)
+                .await?)
+    }
+
+    fn tenant_id() -> Result<Uuid, DomainError> {
+        current_tenant().ok_or_else(|| DomainError::ValidationError("No tenant is in scope".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::session_repository::MockSessionRepository;
+    use crate::domain::services::tenant_repository::MockTenantRepository;
+    use crate::domain::services::two_factor_repository::MockTwoFactorRepository;
+    use crate::domain::value_objects::{email::Email, password_hash::PasswordHash};
+    use async_trait::async_trait;
+
+    struct Users(Vec<User>);
+
+    #[async_trait]
+    impl UserRepository for Users {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+            Ok(self.0.iter().find(|user| user.id == id).cloned())
+        }
+
+        async fn find_by_email(&self, _email: &Email) -> Result<Option<User>, DomainError> {
+            Ok(None)
+        }
+
+        async fn save(&self, _user: &User) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn update(&self, _user: &User) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: Uuid) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn email_exists(
+            &self,
+            _email: &Email,
+            _exclude_user_id: Option<Uuid>,
+        ) -> Result<bool, DomainError> {
+            Ok(false)
+        }
+
+        async fn list_by_tenant(&self, _tenant_id: Uuid) -> Result<Vec<User>, DomainError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn user(role: UserRole) -> User {
+        let mut user = User::new(
+            Email::new("admin@example.com".to_string()).unwrap(),
+            PasswordHash::from_hash("hash".to_string()),
+            "Ada".to_string(),
+            "Admin".to_string(),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        user.change_role(role);
+        user
+    }
+
+    fn use_case(
+        user: User,
+        two_factor: Option<TwoFactor>,
+        policy: bool,
+    ) -> ManageTwoFactorUseCase<
+        Users,
+        MockTwoFactorRepository,
+        MockTenantRepository,
+        MockSessionRepository,
+    > {
+        let mut two_factors = MockTwoFactorRepository::new();
+        two_factors
+            .expect_find_by_user()
+            .returning(move |_| Ok(two_factor.clone()));
+        two_factors.expect_save().returning(|_| Ok(()));
+        two_factors.expect_delete().returning(|_| Ok(()));
+        let mut tenants = MockTenantRepository::new();
+        tenants
+            .expect_get_admin_two_factor_required()
+            .returning(move |_| Ok(policy));
+        ManageTwoFactorUseCase::new(
+            Arc::new(Users(vec![user])),
+            Arc::new(two_factors),
+            Arc::new(tenants),
+            Arc::new(MockSessionRepository::new()),
+        )
+    }
+
+    fn enabled(user: &User) -> (TwoFactor, Vec<String>) {
+        let (mut two_factor, codes) = TwoFactor::new(user.id, totp::generate_secret());
+        two_factor.enable();
+        (two_factor, codes)
+    }
+
+    #[tokio::test]
+    async fn test_enroll_returns_secret_uri_and_recovery_codes() {
+        let member = user(UserRole::Member);
+        let enrollment = use_case(member.clone(), None, false)
+            .enroll(member.id)
+            .await
+            .unwrap();
+        assert!(enrollment
+            .provisioning_uri
+            .starts_with("otpauth://totp/The%20Warehouse%20Hub:admin%40example.com?secret="));
+        assert!(enrollment.provisioning_uri.contains(&enrollment.secret));
+        assert_eq!(enrollment.recovery_codes.len(), 10);
+
+        let (two_factor, _) = enabled(&member);
+        let result = use_case(member.clone(), Some(two_factor), false)
+            .enroll(member.id)
+            .await;
+        assert!(matches!(result, Err(DomainError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_confirm_rejects_a_wrong_code() {
+        let member = user(UserRole::Member);
+        let (pending, codes) = TwoFactor::new(member.id, totp::generate_secret());
+        let result = use_case(member.clone(), Some(pending), false)
+            .confirm(
+                member.id,
+                TwoFactorCodeRequest {
+                    code: codes[0].clone(),
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_admins_cannot_disable_while_policy_requires_it() {
+        let admin = user(UserRole::Admin);
+        let (two_factor, codes) = enabled(&admin);
+        let request = || TwoFactorCodeRequest {
+            code: codes[0].clone(),
+        };
+
+        let result = use_case(admin.clone(), Some(two_factor.clone()), true)
+            .disable(admin.id, request())
+            .await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+
+        let result = use_case(admin.clone(), Some(two_factor), false)
+            .disable(admin.id, request())
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_only_admins_change_the_policy() {
+        let member = user(UserRole::Member);
+        let result = use_case(member.clone(), None, false)
+            .update_policy(
+                member.id,
+                TwoFactorPolicy {
+                    require_for_admins: true,
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+    }
+}
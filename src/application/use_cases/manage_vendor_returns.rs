@@ -0,0 +1,175 @@
+use crate::domain::entities::inventory::StockMovement;
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::vendor_return::{
+    CreateVendorReturnRequest, VendorCredit, VendorReturn,
+};
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
+use crate::domain::services::vendor_return_repository::VendorReturnRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::pagination::{Page, PageRequest};
+use serde::Serialize;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct VendorReturnResponse {
+    pub vendor_return: VendorReturn,
+    /// Set once the return has shipped
+    pub credit: Option<VendorCredit>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShipVendorReturnResponse {
+    pub vendor_return: VendorReturn,
+    pub stock_movements: Vec<StockMovement>,
+    pub credit: VendorCredit,
+}
+
+pub struct ManageVendorReturnsUseCase<
+    V: VendorReturnRepository,
+    P: PurchaseOrderRepository,
+    D: WebhookDispatcher + 'static,
+> {
+    vendor_return_repository: Arc<V>,
+    purchase_order_repository: Arc<P>,
+    webhook_dispatcher: Arc<D>,
+}
+
+impl<V: VendorReturnRepository, P: PurchaseOrderRepository, D: WebhookDispatcher + 'static>
+    ManageVendorReturnsUseCase<V, P, D>
+{
+    pub fn new(
+        vendor_return_repository: Arc<V>,
+        purchase_order_repository: Arc<P>,
+        webhook_dispatcher: Arc<D>,
+    ) -> Self {
+        Self {
+            vendor_return_repository,
+            purchase_order_repository,
+            webhook_dispatcher,
+        }
+    }
+
+    /// Draft a return of received stock to the supplier of the request's PO
+    pub async fn create(
+        &self,
+        request: CreateVendorReturnRequest,
+        created_by: Uuid,
+    ) -> Result<VendorReturnResponse, DomainError> {
+        let po = self
+            .purchase_order_repository
+            .find_by_id(request.po_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_not_found",
+                    &[("id", &request.po_id)],
+                ))
+            })?;
+        let already_returned = self
+            .vendor_return_repository
+            .returned_quantities(po.id)
+            .await?;
+
+        let vendor_return = VendorReturn::new(
+            format!("RTV-{}", Uuid::new_v4().simple()),
+            &po,
+            request.location_id,
+            request.lines,
+            &already_returned,
+            request.notes,
+            created_by,
+        )?;
+        self.vendor_return_repository.create(&vendor_return).await?;
+
+        self.dispatch(
+            WebhookEventType::VendorReturnCreated,
+            json!({ "vendor_return": vendor_return }),
+        );
+
+        Ok(VendorReturnResponse {
+            vendor_return,
+            credit: None,
+        })
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<VendorReturnResponse, DomainError> {
+        let vendor_return = self
+            .vendor_return_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Vendor return {} not found", id).into())
+            })?;
+        let credit = self.vendor_return_repository.find_credit(id).await?;
+
+        Ok(VendorReturnResponse {
+            vendor_return,
+            credit,
+        })
+    }
+
+    pub async fn list(
+        &self,
+        filter: &ListFilter,
+        page: PageRequest,
+    ) -> Result<Page<VendorReturn>, DomainError> {
+        let (vendor_returns, total_count) = tokio::try_join!(
+            self.vendor_return_repository.list(filter, &page),
+            self.vendor_return_repository.count(filter)
+        )?;
+
+        Ok(vendor_returns.with_total_count(total_count))
+    }
+
+    /// Ship a draft back to the supplier: the stock leaves its location and the
+    /// supplier owes a credit for it
+    pub async fn ship(&self, id: Uuid) -> Result<ShipVendorReturnResponse, DomainError> {
+        let (vendor_return, stock_movements, credit) =
+            self.vendor_return_repository.ship(id).await?;
+
+        self.dispatch(
+            WebhookEventType::VendorReturnShipped,
+            json!({
+                "vendor_return": vendor_return,
+                "stock_movements": stock_movements,
+                "credit": credit,
+            }),
+        );
+
+        Ok(ShipVendorReturnResponse {
+            vendor_return,
+            stock_movements,
+            credit,
+        })
+    }
+
+    pub async fn cancel(&self, id: Uuid) -> Result<VendorReturnResponse, DomainError> {
+        let vendor_return = self.vendor_return_repository.cancel(id).await?;
+
+        self.dispatch(
+            WebhookEventType::VendorReturnCancelled,
+            json!({ "vendor_return": vendor_return }),
+        );
+
+        Ok(VendorReturnResponse {
+            vendor_return,
+            credit: None,
+        })
+    }
+
+    /// Dispatch a webhook event without blocking the request
+    fn dispatch(&self, event_type: WebhookEventType, payload: serde_json::Value) {
+        let webhook_event = WebhookEvent::new(event_type, payload);
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch vendor return webhook: {:?}", e);
+            }
+        });
+    }
+}
@@ -0,0 +1,88 @@
+use crate::domain::entities::webhook::Webhook;
+use crate::domain::entities::webhook_filter::WebhookFilter;
+use crate::domain::services::webhook_repository::WebhookRepository;
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct SetWebhookFilterRequest {
+    pub filter: WebhookFilter,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookFilterResponse {
+    pub webhook_id: Uuid,
+    pub filter: Option<WebhookFilter>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<&Webhook> for WebhookFilterResponse {
+    fn from(webhook: &Webhook) -> Self {
+        Self {
+            webhook_id: webhook.id,
+            filter: webhook.filter.clone(),
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+pub struct ManageWebhookFilterUseCase<R: WebhookRepository> {
+    webhook_repository: Arc<R>,
+}
+
+impl<R: WebhookRepository> ManageWebhookFilterUseCase<R> {
+    pub fn new(webhook_repository: Arc<R>) -> Self {
+        Self { webhook_repository }
+    }
+
+    pub async fn get(
+        &self,
+        webhook_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<WebhookFilterResponse, DomainError> {
+        let webhook = self.owned_webhook(webhook_id, user_id).await?;
+        Ok(WebhookFilterResponse::from(&webhook))
+    }
+
+    pub async fn set(
+        &self,
+        webhook_id: Uuid,
+        request: SetWebhookFilterRequest,
+        user_id: Uuid,
+    ) -> Result<WebhookFilterResponse, DomainError> {
+        let mut webhook = self.owned_webhook(webhook_id, user_id).await?;
+        webhook.set_filter(Some(request.filter))?;
+        self.webhook_repository.update_webhook(&webhook).await?;
+        Ok(WebhookFilterResponse::from(&webhook))
+    }
+
+    pub async fn clear(
+        &self,
+        webhook_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<WebhookFilterResponse, DomainError> {
+        let mut webhook = self.owned_webhook(webhook_id, user_id).await?;
+        webhook.set_filter(None)?;
+        self.webhook_repository.update_webhook(&webhook).await?;
+        Ok(WebhookFilterResponse::from(&webhook))
+    }
+
+    async fn owned_webhook(&self, webhook_id: Uuid, user_id: Uuid) -> Result<Webhook, DomainError> {
+        let webhook = self
+            .webhook_repository
+            .get_webhook(webhook_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Webhook {} not found", webhook_id).into())
+            })?;
+
+        if webhook.created_by != user_id {
+            return Err(DomainError::BusinessLogicError(
+                "You can only manage filters on your own webhooks".into(),
+            ));
+        }
+        Ok(webhook)
+    }
+}
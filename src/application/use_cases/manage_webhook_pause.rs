@@ -0,0 +1,154 @@
+use crate::domain::entities::webhook::{Webhook, WebhookStatus};
+use crate::domain::services::webhook_repository::WebhookRepository;
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct WebhookPauseResponse {
+    pub webhook_id: Uuid,
+    pub status: WebhookStatus,
+    /// Held deliveries queued for replay by a resume
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub released_deliveries: Option<u64>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<&Webhook> for WebhookPauseResponse {
+    fn from(webhook: &Webhook) -> Self {
+        Self {
+            webhook_id: webhook.id,
+            status: webhook.status.clone(),
+            released_deliveries: None,
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DisableTenantWebhooksRequest {
+    /// Pause the active webhooks, holding their deliveries for replay, instead
+    /// of disabling every webhook outright
+    #[serde(default)]
+    pub pause: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DisableTenantWebhooksResponse {
+    pub tenant_id: Uuid,
+    pub status: WebhookStatus,
+    pub webhook_ids: Vec<Uuid>,
+    /// Outstanding deliveries of the disabled webhooks moved to the DLQ
+    pub dead_lettered_deliveries: u64,
+}
+
+/// Pauses and resumes webhooks. A paused webhook keeps its configuration and
+/// still gets a delivery for every event it accepts, but the delivery is held
+/// instead of sent; resuming replays them in order. Deliveries held longer
+/// than the delivery worker's replay window are dead-lettered.
+pub struct ManageWebhookPauseUseCase<R: WebhookRepository> {
+    webhook_repository: Arc<R>,
+}
+
+impl<R: WebhookRepository> ManageWebhookPauseUseCase<R> {
+    pub fn new(webhook_repository: Arc<R>) -> Self {
+        Self { webhook_repository }
+    }
+
+    pub async fn pause(
+        &self,
+        webhook_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<WebhookPauseResponse, DomainError> {
+        let mut webhook = self.owned_webhook(webhook_id, user_id).await?;
+        if !webhook.is_paused() {
+            webhook.pause()?;
+            self.webhook_repository.update_webhook(&webhook).await?;
+        }
+        Ok(WebhookPauseResponse::from(&webhook))
+    }
+
+    pub async fn resume(
+        &self,
+        webhook_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<WebhookPauseResponse, DomainError> {
+        let mut webhook = self.owned_webhook(webhook_id, user_id).await?;
+        webhook.resume()?;
+        // Active first, so a delivery the worker picks up straight away is sent
+        self.webhook_repository.update_webhook(&webhook).await?;
+        let released = self
+            .webhook_repository
+            .release_held_deliveries(webhook.id)
+            .await?;
+        Ok(WebhookPauseResponse {
+            released_deliveries: Some(released),
+            ..WebhookPauseResponse::from(&webhook)
+        })
+    }
+
+    /// Stop all of the current tenant's webhooks at once, for incident
+    /// response. Disabling dead-letters their outstanding deliveries, which can
+    /// be replayed from the DLQ later; pausing holds them for a resume instead.
+    pub async fn disable_all(
+        &self,
+        tenant_id: Uuid,
+        request: DisableTenantWebhooksRequest,
+    ) -> Result<DisableTenantWebhooksResponse, DomainError> {
+        if request.pause {
+            let webhook_ids = self
+                .webhook_repository
+                .set_tenant_webhook_status(&[WebhookStatus::Active], WebhookStatus::Paused)
+                .await?;
+            return Ok(DisableTenantWebhooksResponse {
+                tenant_id,
+                status: WebhookStatus::Paused,
+                webhook_ids,
+                dead_lettered_deliveries: 0,
+            });
+        }
+
+        let webhook_ids = self
+            .webhook_repository
+            .set_tenant_webhook_status(
+                &[
+                    WebhookStatus::Active,
+                    WebhookStatus::Paused,
+                    WebhookStatus::Failed,
+                ],
+                WebhookStatus::Inactive,
+            )
+            .await?;
+        let dead_lettered_deliveries = self
+            .webhook_repository
+            .dead_letter_outstanding_deliveries(
+                &webhook_ids,
+                "Webhook disabled by an administrator",
+            )
+            .await?;
+        Ok(DisableTenantWebhooksResponse {
+            tenant_id,
+            status: WebhookStatus::Inactive,
+            webhook_ids,
+            dead_lettered_deliveries,
+        })
+    }
+
+    async fn owned_webhook(&self, webhook_id: Uuid, user_id: Uuid) -> Result<Webhook, DomainError> {
+        let webhook = self
+            .webhook_repository
+            .get_webhook(webhook_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Webhook {} not found", webhook_id).into())
+            })?;
+
+        if webhook.created_by != user_id {
+            return Err(DomainError::BusinessLogicError(
+                "You can only pause or resume your own webhooks".into(),
+            ));
+        }
+        Ok(webhook)
+    }
+}
@@ -0,0 +1,122 @@
+use crate::domain::entities::item::Item;
+use crate::domain::entities::item_duplicate::{
+    check_mergeable, DuplicateItemsReport, FindDuplicateItemsQuery, MergeItemsRequest,
+    MergeItemsResponse, DEFAULT_NAME_SIMILARITY,
+};
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::item_merge_repository::ItemMergeRepository;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 1000;
+
+/// Find items that look like the same product and fold a duplicate into the
+/// item that stays
+pub struct MergeItemsUseCase<
+    R: ItemRepository,
+    M: ItemMergeRepository,
+    D: WebhookDispatcher + 'static,
+> {
+    item_repository: Arc<R>,
+    merge_repository: Arc<M>,
+    webhook_dispatcher: Arc<D>,
+}
+
+impl<R: ItemRepository, M: ItemMergeRepository, D: WebhookDispatcher + 'static>
+    MergeItemsUseCase<R, M, D>
+{
+    pub fn new(
+        item_repository: Arc<R>,
+        merge_repository: Arc<M>,
+        webhook_dispatcher: Arc<D>,
+    ) -> Self {
+        Self {
+            item_repository,
+            merge_repository,
+            webhook_dispatcher,
+        }
+    }
+
+    pub async fn find_duplicates(
+        &self,
+        query: FindDuplicateItemsQuery,
+    ) -> Result<DuplicateItemsReport, DomainError> {
+        let threshold = query.min_similarity.unwrap_or(DEFAULT_NAME_SIMILARITY);
+        if threshold.is_nan() || threshold <= 0.0 || threshold > 1.0 {
+            return Err(DomainError::ValidationError(
+                "min_similarity must be greater than 0 and at most 1".into(),
+            ));
+        }
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+        Ok(DuplicateItemsReport {
+            name_similarity_threshold: threshold,
+            pairs: self
+                .merge_repository
+                .find_duplicates(threshold, limit)
+                .await?,
+        })
+    }
+
+    /// Move the duplicate's stock, movements and order lines onto the survivor
+    /// and deactivate the duplicate. A survivor without a barcode takes over
+    /// the duplicate's, so scanning either label still finds the item.
+    pub async fn merge(
+        &self,
+        survivor_id: Uuid,
+        request: MergeItemsRequest,
+    ) -> Result<MergeItemsResponse, DomainError> {
+        let mut survivor = self.find(survivor_id).await?;
+        let mut duplicate = self.find(request.duplicate_id).await?;
+        check_mergeable(&duplicate, &survivor)?;
+
+        let barcode_moved = if survivor.barcode.is_none() {
+            duplicate.barcode.take()
+        } else {
+            None
+        };
+        survivor.barcode = survivor.barcode.or_else(|| barcode_moved.clone());
+        survivor.updated_at = chrono::Utc::now();
+        duplicate.deactivate();
+
+        let moved = self.merge_repository.merge(&duplicate, &survivor).await?;
+
+        let events = [
+            WebhookEvent::new(
+                WebhookEventType::ItemUpdated,
+                json!({ "item": survivor, "merged_item_id": duplicate.id }),
+            ),
+            WebhookEvent::new(
+                WebhookEventType::ItemDeleted,
+                json!({ "item": { "id": duplicate.id, "sku": duplicate.sku, "deleted_at": duplicate.updated_at }, "merged_into": survivor.id }),
+            ),
+        ];
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            for event in events {
+                if let Err(e) = dispatcher.dispatch_event(&event).await {
+                    eprintln!("Failed to dispatch item merge webhook: {:?}", e);
+                }
+            }
+        });
+
+        Ok(MergeItemsResponse {
+            survivor_id: survivor.id,
+            duplicate_id: duplicate.id,
+            barcode_moved,
+            moved,
+        })
+    }
+
+    async fn find(&self, id: Uuid) -> Result<Item, DomainError> {
+        self.item_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Item {} not found", id).into()))
+    }
+}
@@ -0,0 +1,578 @@
+use crate::application::use_cases::receive_purchase_order::{
+    ReceivePurchaseOrderUseCase, ReceivePurchaseOrderUseCaseRequest,
+};
+use crate::application::use_cases::record_count::RecordCountUseCase;
+use crate::application::use_cases::scan_lookup::find_scanned_item;
+use crate::application::use_cases::ship_sales_order::{
+    ShipSalesOrderLineRequest, ShipSalesOrderRequest, ShipSalesOrderUseCase,
+};
+use crate::domain::entities::cycle_count::{
+    CycleCountStatus, RecordCountLineRequest, RecordCountRequest,
+};
+use crate::domain::entities::inventory::StockStatus;
+use crate::domain::entities::location::Location;
+use crate::domain::entities::location_capacity::CapacityWarning;
+use crate::domain::entities::purchase_order::ReceiveLine;
+use crate::domain::entities::putaway::PutawaySuggestion;
+use crate::domain::services::cycle_count_repository::CycleCountRepository;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
+use crate::domain::services::putaway_rule_repository::PutawayRuleRepository;
+use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::domain::services::shipment_repository::ShipmentRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Open cycle counts searched at a location when a count scan doesn't name one
+const OPEN_COUNT_SEARCH_LIMIT: i64 = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct ScanReceiveRequest {
+    pub po_number: String,
+    /// Scanned item barcode or SKU
+    pub item_code: String,
+    pub qty: Decimal,
+    /// Scanned code (or id) of the location receiving the goods
+    pub location: String,
+    #[serde(default)]
+    pub stock_status: StockStatus,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScanReceiveResponse {
+    pub po_id: Uuid,
+    pub po_number: String,
+    pub po_status: String,
+    pub item_id: Uuid,
+    pub sku: String,
+    pub location_id: Uuid,
+    pub qty_received: Decimal,
+    /// Units of the item the order still expects
+    pub qty_outstanding: Decimal,
+    pub putaway_suggestions: Vec<PutawaySuggestion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capacity_warning: Option<CapacityWarning>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScanPickRequest {
+    /// Number of the sales order whose pick ticket is being worked
+    pub so_number: String,
+    /// Scanned code (or id) of the bin picked from
+    pub location: String,
+    pub item_code: String,
+    pub qty: Decimal,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScanPickResponse {
+    pub so_id: Uuid,
+    pub so_number: String,
+    pub so_status: String,
+    pub item_id: Uuid,
+    pub sku: String,
+    pub location_id: Uuid,
+    pub qty_picked: Decimal,
+    /// Units of the item still to pick on the order
+    pub qty_remaining: Decimal,
+    pub shipment_id: Uuid,
+    pub shipment_number: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScanCountRequest {
+    /// Count to record against; defaults to the newest open count at the
+    /// location that includes the item
+    pub cycle_count_id: Option<Uuid>,
+    pub location: String,
+    pub item_code: String,
+    pub qty: Decimal,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScanCountResponse {
+    pub cycle_count_id: Uuid,
+    pub count_number: String,
+    pub status: String,
+    pub item_id: Uuid,
+    pub sku: String,
+    pub location_id: Uuid,
+    pub counted_qty: Decimal,
+    /// Items on the count not counted yet
+    pub lines_remaining: usize,
+}
+
+/// Spread a scanned quantity over the lines carrying the item, filling each
+/// line's outstanding quantity in order. Returns the split and whatever none
+/// of the lines had room for.
+pub fn split_scanned_qty(
+    lines: &[(Uuid, Decimal)],
+    qty: Decimal,
+) -> (Vec<(Uuid, Decimal)>, Decimal) {
+    let mut remaining = qty;
+    let mut split = Vec::new();
+    for (line_id, outstanding) in lines {
+        if remaining <= Decimal::ZERO {
+            break;
+        }
+        let take = remaining.min(*outstanding);
+        if take > Decimal::ZERO {
+            split.push((*line_id, take));
+            remaining -= take;
+        }
+    }
+    (split, remaining)
+}
+
+/// Resolve a scanned location label, which carries the location's code or id
+pub async fn find_scanned_location<L: LocationRepository + ?Sized>(
+    location_repository: &L,
+    code: &str,
+) -> Result<Location, DomainError> {
+    let code = code.trim();
+    if code.is_empty() {
+        return Err(DomainError::ValidationError(
+            "Scanned location cannot be empty".into(),
+        ));
+    }
+
+    let location = match Uuid::parse_str(code) {
+        Ok(id) => location_repository.find_by_id(id).await?,
+        Err(_) => location_repository.find_by_code(code).await?,
+    }
+    .ok_or_else(|| {
+        DomainError::NotFound(format!("No location matches scanned code {}", code).into())
+    })?;
+
+    if !location.active {
+        return Err(DomainError::ValidationError(
+            format!("Location {} is inactive", location.name).into(),
+        ));
+    }
+    Ok(location)
+}
+
+fn ensure_positive(qty: Decimal) -> Result<(), DomainError> {
+    if qty <= Decimal::ZERO {
+        return Err(DomainError::ValidationError(
+            "Scanned quantity must be positive".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Receives a scanned item against a purchase order by number in one call
+pub struct ScanToReceiveUseCase<I, L, R, P, D>
+where
+    I: ItemRepository,
+    L: LocationRepository,
+    R: PurchaseOrderRepository,
+    P: PutawayRuleRepository,
+    D: WebhookDispatcher + 'static,
+{
+    item_repository: Arc<I>,
+    location_repository: Arc<L>,
+    purchase_order_repository: Arc<R>,
+    receive_purchase_order_use_case: Arc<ReceivePurchaseOrderUseCase<R, P, D>>,
+}
+
+impl<I, L, R, P, D> ScanToReceiveUseCase<I, L, R, P, D>
+where
+    I: ItemRepository,
+    L: LocationRepository,
+    R: PurchaseOrderRepository,
+    P: PutawayRuleRepository,
+    D: WebhookDispatcher + 'static,
+{
+    pub fn new(
+        item_repository: Arc<I>,
+        location_repository: Arc<L>,
+        purchase_order_repository: Arc<R>,
+        receive_purchase_order_use_case: Arc<ReceivePurchaseOrderUseCase<R, P, D>>,
+    ) -> Self {
+        Self {
+            item_repository,
+            location_repository,
+            purchase_order_repository,
+            receive_purchase_order_use_case,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        request: ScanReceiveRequest,
+        user_id: Uuid,
+    ) -> Result<ScanReceiveResponse, DomainError> {
+        ensure_positive(request.qty)?;
+        let (item, _) = find_scanned_item(&*self.item_repository, &request.item_code).await?;
+        item.check_quantity(request.qty)?;
+        let location = find_scanned_location(&*self.location_repository, &request.location).await?;
+        let po_number = request.po_number.trim();
+        let po = self
+            .purchase_order_repository
+            .find_by_po_number(po_number)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_not_found",
+                    &[("id", &po_number)],
+                ))
+            })?;
+
+        let item_lines: Vec<(Uuid, Decimal)> = po
+            .lines
+            .iter()
+            .filter(|line| line.item_id == item.id)
+            .map(|line| {
+                (
+                    line.id,
+                    (line.qty_ordered - line.qty_received).max(Decimal::ZERO),
+                )
+            })
+            .collect();
+        let Some(&(last_line_id, _)) = item_lines.last() else {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Item {} is not on purchase order {}",
+                    item.sku, po.po_number
+                )
+                .into(),
+            ));
+        };
+
+        // Anything past what the lines expect lands on the last one, where the
+        // supplier's over-receipt tolerance decides whether it is accepted
+        let (mut split, over) = split_scanned_qty(&item_lines, request.qty);
+        if over > Decimal::ZERO {
+            match split
+                .iter_mut()
+                .find(|(line_id, _)| *line_id == last_line_id)
+            {
+                Some((_, qty)) => *qty += over,
+                None => split.push((last_line_id, over)),
+            }
+        }
+
+        let response = self
+            .receive_purchase_order_use_case
+            .execute(
+                ReceivePurchaseOrderUseCaseRequest {
+                    po_id: po.id,
+                    received_lines: split
+                        .into_iter()
+                        .map(|(po_line_id, qty_received)| ReceiveLine {
+                            po_line_id,
+                            qty_received,
+                        })
+                        .collect(),
+                    receive_date: None,
+                    destination_location_id: location.id,
+                    stock_status: request.stock_status,
+                },
+                user_id,
+            )
+            .await?;
+
+        let qty_outstanding = response
+            .po
+            .lines
+            .iter()
+            .filter(|line| line.item_id == item.id)
+            .map(|line| (line.qty_ordered - line.qty_received).max(Decimal::ZERO))
+            .sum();
+
+        Ok(ScanReceiveResponse {
+            po_id: response.po.id,
+            po_number: response.po.po_number,
+            po_status: response.po.status,
+            item_id: item.id,
+            sku: item.sku,
+            location_id: location.id,
+            qty_received: request.qty,
+            qty_outstanding,
+            putaway_suggestions: response.putaway_suggestions,
+            capacity_warning: response.capacity_warning,
+        })
+    }
+}
+
+/// Picks a scanned item from a scanned bin for a sales order in one call,
+/// shipping the picked units from that bin
+pub struct ScanToPickUseCase<I, L, T, S, D>
+where
+    I: ItemRepository,
+    L: LocationRepository,
+    T: SalesOrderRepository,
+    S: ShipmentRepository,
+    D: WebhookDispatcher + 'static,
+{
+    item_repository: Arc<I>,
+    location_repository: Arc<L>,
+    sales_order_repository: Arc<T>,
+    ship_sales_order_use_case: Arc<ShipSalesOrderUseCase<T, S, D>>,
+}
+
+impl<I, L, T, S, D> ScanToPickUseCase<I, L, T, S, D>
+where
+    I: ItemRepository,
+    L: LocationRepository,
+    T: SalesOrderRepository,
+    S: ShipmentRepository,
+    D: WebhookDispatcher + 'static,
+{
+    pub fn new(
+        item_repository: Arc<I>,
+        location_repository: Arc<L>,
+        sales_order_repository: Arc<T>,
+        ship_sales_order_use_case: Arc<ShipSalesOrderUseCase<T, S, D>>,
+    ) -> Self {
+        Self {
+            item_repository,
+            location_repository,
+            sales_order_repository,
+            ship_sales_order_use_case,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        request: ScanPickRequest,
+        user_id: Uuid,
+    ) -> Result<ScanPickResponse, DomainError> {
+        ensure_positive(request.qty)?;
+        let (item, _) = find_scanned_item(&*self.item_repository, &request.item_code).await?;
+        item.check_quantity(request.qty)?;
+        let location = find_scanned_location(&*self.location_repository, &request.location).await?;
+        let so_number = request.so_number.trim();
+        let (sales_order, _) = self
+            .sales_order_repository
+            .find_by_so_number(so_number)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Sales order {} not found", so_number).into())
+            })?;
+
+        let item_lines: Vec<(Uuid, Decimal)> = sales_order
+            .lines
+            .iter()
+            .filter(|line| line.item_id == item.id)
+            .map(|line| (line.id, line.remaining_qty()))
+            .collect();
+        let (split, over) = split_scanned_qty(&item_lines, request.qty);
+        if split.is_empty() {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Item {} has nothing left to pick on sales order {}",
+                    item.sku, sales_order.so_number
+                )
+                .into(),
+            ));
+        }
+        if over > Decimal::ZERO {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Cannot pick {} of item {} on sales order {}, only {} left to pick",
+                    request.qty,
+                    item.sku,
+                    sales_order.so_number,
+                    request.qty - over
+                )
+                .into(),
+            ));
+        }
+
+        let response = self
+            .ship_sales_order_use_case
+            .execute(
+                sales_order.id,
+                ShipSalesOrderRequest {
+                    ship_date: None,
+                    lines: split
+                        .into_iter()
+                        .map(|(so_line_id, qty_shipped)| ShipSalesOrderLineRequest {
+                            so_line_id,
+                            qty_shipped,
+                            location_id: Some(location.id),
+                        })
+                        .collect(),
+                    tracking: None,
+                    carrier: None,
+                    allow_backorder: Some(false),
+                    packages: None,
+                },
+                user_id,
+            )
+            .await?;
+
+        let qty_remaining = response
+            .sales_order
+            .lines
+            .iter()
+            .filter(|line| line.item_id == item.id)
+            .map(|line| line.remaining_qty())
+            .sum();
+
+        Ok(ScanPickResponse {
+            so_id: response.sales_order.id,
+            so_number: response.sales_order.so_number.clone(),
+            so_status: response.sales_order.status.as_str().to_string(),
+            item_id: item.id,
+            sku: item.sku,
+            location_id: location.id,
+            qty_picked: request.qty,
+            qty_remaining,
+            shipment_id: response.shipment.id,
+            shipment_number: response.shipment.shipment_number,
+        })
+    }
+}
+
+/// Records a scanned count for an item at a scanned location in one call
+pub struct ScanToCountUseCase<I, L, C>
+where
+    I: ItemRepository,
+    L: LocationRepository,
+    C: CycleCountRepository,
+{
+    item_repository: Arc<I>,
+    location_repository: Arc<L>,
+    cycle_count_repository: Arc<C>,
+    record_count_use_case: Arc<RecordCountUseCase<C>>,
+}
+
+impl<I, L, C> ScanToCountUseCase<I, L, C>
+where
+    I: ItemRepository,
+    L: LocationRepository,
+    C: CycleCountRepository,
+{
+    pub fn new(
+        item_repository: Arc<I>,
+        location_repository: Arc<L>,
+        cycle_count_repository: Arc<C>,
+        record_count_use_case: Arc<RecordCountUseCase<C>>,
+    ) -> Self {
+        Self {
+            item_repository,
+            location_repository,
+            cycle_count_repository,
+            record_count_use_case,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        request: ScanCountRequest,
+        user_id: Uuid,
+    ) -> Result<ScanCountResponse, DomainError> {
+        let (item, _) = find_scanned_item(&*self.item_repository, &request.item_code).await?;
+        item.check_quantity(request.qty)?;
+        let location = find_scanned_location(&*self.location_repository, &request.location).await?;
+
+        let cycle_count_id = match request.cycle_count_id {
+            Some(id) => {
+                let cycle_count = self
+                    .cycle_count_repository
+                    .find_by_id(id)
+                    .await?
+                    .ok_or_else(|| {
+                        DomainError::NotFound(format!("Cycle count {} not found", id).into())
+                    })?;
+                if cycle_count.location_id != location.id {
+                    return Err(DomainError::ValidationError(
+                        format!(
+                            "Cycle count {} is not for location {}",
+                            cycle_count.count_number, location.name
+                        )
+                        .into(),
+                    ));
+                }
+                id
+            }
+            None => self
+                .cycle_count_repository
+                .list(Some(location.id), OPEN_COUNT_SEARCH_LIMIT, 0)
+                .await?
+                .into_iter()
+                .find(|count| {
+                    matches!(
+                        count.status,
+                        CycleCountStatus::Scheduled | CycleCountStatus::InProgress
+                    ) && count.lines.iter().any(|line| line.item_id == item.id)
+                })
+                .map(|count| count.id)
+                .ok_or_else(|| {
+                    DomainError::ValidationError(
+                        format!(
+                            "No open cycle count at {} includes item {}",
+                            location.name, item.sku
+                        )
+                        .into(),
+                    )
+                })?,
+        };
+
+        let cycle_count = self
+            .record_count_use_case
+            .execute(
+                cycle_count_id,
+                RecordCountRequest {
+                    lines: vec![RecordCountLineRequest {
+                        item_id: item.id,
+                        counted_qty: request.qty,
+                    }],
+                },
+                user_id,
+            )
+            .await?
+            .cycle_count;
+
+        Ok(ScanCountResponse {
+            cycle_count_id: cycle_count.id,
+            count_number: cycle_count.count_number,
+            status: cycle_count.status.as_str().to_string(),
+            item_id: item.id,
+            sku: item.sku,
+            location_id: location.id,
+            counted_qty: request.qty,
+            lines_remaining: cycle_count
+                .lines
+                .iter()
+                .filter(|line| line.counted_qty.is_none())
+                .count(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_scanned_qty_fills_lines_in_order() {
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let lines = vec![(first, Decimal::from(3)), (second, Decimal::from(5))];
+
+        let (split, over) = split_scanned_qty(&lines, Decimal::from(4));
+        assert_eq!(
+            split,
+            vec![(first, Decimal::from(3)), (second, Decimal::ONE)]
+        );
+        assert_eq!(over, Decimal::ZERO);
+
+        let (split, over) = split_scanned_qty(&lines, Decimal::from(10));
+        assert_eq!(split.len(), 2);
+        assert_eq!(over, Decimal::TWO);
+
+        // Fully received lines take nothing
+        let (split, over) = split_scanned_qty(&[(first, Decimal::ZERO)], Decimal::ONE);
+        assert!(split.is_empty());
+        assert_eq!(over, Decimal::ONE);
+    }
+}
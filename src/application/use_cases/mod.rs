@@ -1,27 +1,59 @@
 pub mod adjust_stock;
+pub mod allocate_sales_order;
+pub mod cancel_cycle_count;
+pub mod cancel_job;
+pub mod cancel_purchase_order;
+pub mod cancel_sales_order;
+pub mod change_password;
+pub mod change_stock_status;
 pub mod cleanup_expired_sandboxes;
+pub mod close_purchase_order;
+pub mod create_backorder;
+pub mod create_cycle_count;
 pub mod create_item;
 pub mod create_location;
 pub mod create_purchase_order;
+pub mod create_reorder_purchase_orders;
 pub mod create_return;
 pub mod create_sales_order;
 pub mod create_sandbox_tenant;
+pub mod create_shipment;
 pub mod create_tenant;
 pub mod create_transfer;
 pub mod delete_item;
 pub mod delete_location;
 pub mod delete_tenant;
 pub mod delete_webhook;
+pub mod edit_purchase_order_lines;
+pub mod edit_sales_order_lines;
 pub mod enqueue_job;
+pub mod exchange_edi_documents;
+pub mod export_stock_movements;
+pub mod finalize_cycle_count;
+pub mod forecast_demand;
+pub mod generate_item_barcode;
+pub mod generate_order_documents;
+pub mod generate_shipment_labels;
 pub mod get_billing_metrics;
+pub mod get_cycle_count;
+pub mod get_dead_stock_report;
 pub mod get_item;
 pub mod get_job_status;
 pub mod get_location;
+pub mod get_location_utilization_report;
 pub mod get_low_stock_report;
+pub mod get_overdue_purchase_orders_report;
 pub mod get_purchase_order;
+pub mod get_receivables_aging_report;
+pub mod get_reorder_suggestions;
 pub mod get_return;
 pub mod get_sales_order;
+pub mod get_sales_order_allocations;
+pub mod get_scrap_report;
+pub mod get_shipment;
+pub mod get_status_history;
 pub mod get_stock_level;
+pub mod get_stock_levels_as_of;
 pub mod get_stock_movements;
 pub mod get_stock_valuation_report;
 pub mod get_tenant;
@@ -29,23 +61,62 @@ pub mod get_total_quantity_on_hand;
 pub mod get_transfer;
 pub mod get_webhook_deliveries;
 pub mod idempotency;
+pub mod import_items;
+pub mod item_availability;
 pub mod list_dlq_deliveries;
 pub mod list_item_stock_levels;
 pub mod list_items;
 pub mod list_locations;
+pub mod list_stock_levels;
 pub mod list_tenants;
 pub mod login;
+pub mod manage_adjustment_reasons;
+pub mod manage_connectors;
+pub mod manage_credit_settings;
+pub mod manage_currency;
+pub mod manage_document_settings;
+pub mod manage_inbound_shipments;
+pub mod manage_invoices;
+pub mod manage_item_attachments;
+pub mod manage_label_printing;
+pub mod manage_products;
+pub mod manage_putaway_rules;
+pub mod manage_receiving_settings;
+pub mod manage_sandbox;
+pub mod manage_sessions;
+pub mod manage_sscc_sequence;
+pub mod manage_tenant_users;
+pub mod manage_time_zone;
+pub mod manage_trading_partners;
+pub mod manage_two_factor;
+pub mod manage_vendor_returns;
+pub mod manage_webhook_filter;
+pub mod manage_webhook_pause;
+pub mod merge_items;
+pub mod mobile_scanning;
+pub mod password_reset;
 pub mod process_return;
 pub mod receive_purchase_order;
 pub mod receive_transfer;
+pub mod record_count;
+pub mod register_user;
 pub mod register_webhook;
 pub mod replay_dlq_delivery;
+pub mod reserve_stock;
+pub mod retry_job;
 pub mod retry_webhook_delivery;
+pub mod scan_lookup;
 pub mod search_use_case;
 pub mod ship_sales_order;
 pub mod ship_transfer;
+pub mod sync_connector;
+pub mod tenant_snapshot;
 pub mod test_webhook;
 pub mod trigger_webhook;
 pub mod update_item;
 pub mod update_location;
+pub mod update_shipment_tracking;
 pub mod update_webhook;
+pub mod validate_address;
+pub mod verify_webhook_sample;
+pub mod webhook_retention;
@@ -0,0 +1,282 @@
+use crate::domain::entities::user::User;
+use crate::domain::services::email_sender::{EmailMessage, EmailSender};
+use crate::domain::services::user_repository::UserRepository;
+use crate::domain::value_objects::email::Email;
+use crate::domain::value_objects::password_hash::PasswordHash;
+use crate::shared::error::DomainError;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Marks a token as a reset token, so login tokens signed with the same secret
+/// can't be used to reset passwords
+const RESET_TOKEN_PURPOSE: &str = "password_reset";
+
+#[derive(Debug, Deserialize)]
+pub struct ForgotPasswordRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ResetClaims {
+    sub: String,
+    purpose: String,
+    /// Fingerprint of the password hash the token was issued against; once the
+    /// password changes the token no longer matches, which makes it single-use
+    pwd: String,
+    exp: usize,
+    iat: usize,
+}
+
+/// Forgot-password and reset-password flow. Reset tokens are JWTs signed with the
+/// API's secret, expiring after `token_ttl`.
+pub struct PasswordResetUseCase<R: UserRepository> {
+    user_repository: Arc<R>,
+    email_sender: Arc<dyn EmailSender>,
+    jwt_secret: String,
+    token_ttl: chrono::Duration,
+    /// Page the emailed link points at, receiving the token as `?token=`; without
+    /// one the email contains the bare token
+    reset_url: Option<String>,
+}
+
+impl<R: UserRepository> PasswordResetUseCase<R> {
+    pub fn new(
+        user_repository: Arc<R>,
+        email_sender: Arc<dyn EmailSender>,
+        jwt_secret: String,
+        token_ttl: chrono::Duration,
+        reset_url: Option<String>,
+    ) -> Self {
+        Self {
+            user_repository,
+            email_sender,
+            jwt_secret,
+            token_ttl,
+            reset_url,
+        }
+    }
+
+    /// Email a reset token to an active user. Succeeds whether or not the address
+    /// belongs to anyone, so the endpoint can't be used to discover accounts.
+    pub async fn forgot_password(&self, request: ForgotPasswordRequest) -> Result<(), DomainError> {
+        let email = Email::new(request.email)?;
+        let Some(user) = self.user_repository.find_by_email(&email).await? else {
+            return Ok(());
+        };
+        if !user.is_active() {
+            return Ok(());
+        }
+
+        let token = self.issue_token(&user)?;
+        let instructions = match &self.reset_url {
+            Some(url) => format!(
+                "Open this link to choose a new password:\n\n{}?token={}",
+                url, token
+            ),
+            None => format!("Use this token to choose a new password:\n\n{}", token),
+        };
+        let message = EmailMessage {
+            to: user.email.as_str().to_string(),
+            subject: "Reset your password".to_string(),
+            body: format!(
+                "Hi {},\n\nWe received a request to reset your password. {}\n\n\
+                 The link expires in {} minutes. If you didn't ask for this, you can ignore this email.",
+                user.first_name,
+                instructions,
+                self.token_ttl.num_minutes()
+            ),
+        };
+
+        // The response must not depend on whether the email went out
+        if let Err(e) = self.email_sender.send(&message).await {
+            warn!(error = %e, user_id = %user.id, "Failed to send password reset email");
+        }
+        Ok(())
+    }
+
+    pub async fn reset_password(&self, request: ResetPasswordRequest) -> Result<(), DomainError> {
+        let invalid_token =
+            || DomainError::ValidationError("Invalid or expired reset token".into());
+
+        let claims = decode::<ResetClaims>(
+            &request.token,
+            &DecodingKey::from_secret(self.jwt_secret.as_ref()),
+            &Validation::default(),
+        )
+        .map_err(|_| invalid_token())?
+        .claims;
+        if claims.purpose != RESET_TOKEN_PURPOSE {
+            return Err(invalid_token());
+        }
+
+        let user_id = Uuid::parse_str(&claims.sub).map_err(|_| invalid_token())?;
+        let mut user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .filter(|user| user.is_active() && password_fingerprint(user) == claims.pwd)
+            .ok_or_else(invalid_token)?;
+
+        user.password_hash = PasswordHash::new(&request.new_password)?;
+        user.updated_at = chrono::Utc::now();
+        self.user_repository.update(&user).await
+    }
+
+    fn issue_token(&self, user: &User) -> Result<String, DomainError> {
+        let now = chrono::Utc::now();
+        let claims = ResetClaims {
+            sub: user.id.to_string(),
+            purpose: RESET_TOKEN_PURPOSE.to_string(),
+            pwd: password_fingerprint(user),
+            exp: (now + self.token_ttl).timestamp() as usize,
+            iat: now.timestamp() as usize,
+        };
+
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.jwt_secret.as_ref()),
+        )
+        .map_err(|_| DomainError::InfrastructureError("Failed to generate token".into()))
+    }
+}
+
+fn password_fingerprint(user: &User) -> String {
+    hex::encode(&Sha256::digest(user.password_hash.as_str().as_bytes())[..8])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    struct SingleUser(Mutex<User>);
+
+    #[async_trait]
+    impl UserRepository for SingleUser {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+            let user = self.0.lock().unwrap().clone();
+            Ok((user.id == id).then_some(user))
+        }
+
+        async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError> {
+            let user = self.0.lock().unwrap().clone();
+            Ok((&user.email == email).then_some(user))
+        }
+
+        async fn save(&self, _user: &User) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn update(&self, user: &User) -> Result<(), DomainError> {
+            *self.0.lock().unwrap() = user.clone();
+            Ok(())
+        }
+
+        async fn delete(&self, _id: Uuid) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn email_exists(
+            &self,
+            _email: &Email,
+            _exclude_user_id: Option<Uuid>,
+        ) -> Result<bool, DomainError> {
+            Ok(false)
+        }
+
+        async fn list_by_tenant(&self, _tenant_id: Uuid) -> Result<Vec<User>, DomainError> {
+            Ok(vec![self.0.lock().unwrap().clone()])
+        }
+    }
+
+    #[derive(Default)]
+    struct Outbox(Mutex<Vec<EmailMessage>>);
+
+    #[async_trait]
+    impl EmailSender for Outbox {
+        async fn send(&self, message: &EmailMessage) -> Result<(), DomainError> {
+            self.0.lock().unwrap().push(message.clone());
+            Ok(())
+        }
+    }
+
+    fn setup() -> (
+        PasswordResetUseCase<SingleUser>,
+        Arc<SingleUser>,
+        Arc<Outbox>,
+    ) {
+        let user = User::new(
+            Email::new("reset@example.com".to_string()).unwrap(),
+            PasswordHash::new("old-password").unwrap(),
+            "Reset".to_string(),
+            "User".to_string(),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        let users = Arc::new(SingleUser(Mutex::new(user)));
+        let outbox = Arc::new(Outbox::default());
+        let use_case = PasswordResetUseCase::new(
+            Arc::clone(&users),
+            outbox.clone(),
+            "test-secret".to_string(),
+            chrono::Duration::minutes(60),
+            None,
+        );
+        (use_case, users, outbox)
+    }
+
+    fn emailed_token(outbox: &Outbox) -> String {
+        let body = outbox.0.lock().unwrap()[0].body.clone();
+        body.split_whitespace()
+            .find(|word| word.matches('.').count() == 2)
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_reset_token_changes_password_once() {
+        let (use_case, users, outbox) = setup();
+        use_case
+            .forgot_password(ForgotPasswordRequest {
+                email: "reset@example.com".to_string(),
+            })
+            .await
+            .unwrap();
+        let token = emailed_token(&outbox);
+
+        let reset = |token: String| ResetPasswordRequest {
+            token,
+            new_password: "new-password".to_string(),
+        };
+        use_case.reset_password(reset(token.clone())).await.unwrap();
+        let user = users.0.lock().unwrap().clone();
+        assert!(user.verify_password("new-password").unwrap());
+
+        // The password changed, so the same token no longer matches
+        assert!(use_case.reset_password(reset(token)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_email_succeeds_without_sending() {
+        let (use_case, _, outbox) = setup();
+        use_case
+            .forgot_password(ForgotPasswordRequest {
+                email: "nobody@example.com".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(outbox.0.lock().unwrap().is_empty());
+    }
+}
@@ -1,11 +1,22 @@
-use crate::domain::entities::inventory::StockMovement;
+use crate::application::use_cases::get_location_utilization_report::{
+    check_putaway_capacity, limit_candidates_to_capacity,
+};
+use crate::domain::entities::inventory::{StockMovement, StockStatus};
+use crate::domain::entities::location_capacity::CapacityWarning;
 use crate::domain::entities::purchase_order::{
     PurchaseOrder, ReceiveLine, ReceivePurchaseOrderRequest,
 };
+use crate::domain::entities::putaway::{suggest_putaway, PutawaySuggestion};
 use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::location_capacity_repository::LocationCapacityRepository;
 use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
+use crate::domain::services::putaway_rule_repository::PutawayRuleRepository;
+use crate::domain::services::receiving_settings_repository::ReceivingSettingsRepository;
 use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
@@ -17,12 +28,19 @@ pub struct ReceivePurchaseOrderUseCaseRequest {
     pub received_lines: Vec<ReceiveLine>,
     pub receive_date: Option<chrono::DateTime<chrono::Utc>>,
     pub destination_location_id: Uuid,
+    #[serde(default)]
+    pub stock_status: StockStatus,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ReceivePurchaseOrderResponse {
     pub po: PurchaseOrderResponse,
     pub stock_movements: Vec<StockMovementResponse>,
+    /// Where the received goods should be moved from the receiving location
+    pub putaway_suggestions: Vec<PutawaySuggestion>,
+    /// Set when the receipt took a location that only warns past its capacity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity_warning: Option<CapacityWarning>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -31,7 +49,7 @@ pub struct PurchaseOrderResponse {
     pub po_number: String,
     pub supplier_id: Uuid,
     pub status: String,
-    pub total_amount: f64,
+    pub total_amount: Decimal,
     pub lines: Vec<PurchaseOrderLineResponse>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
@@ -40,10 +58,10 @@ pub struct PurchaseOrderResponse {
 pub struct PurchaseOrderLineResponse {
     pub id: Uuid,
     pub item_id: Uuid,
-    pub qty_ordered: i32,
-    pub qty_received: i32,
-    pub unit_cost: f64,
-    pub line_total: f64,
+    pub qty_ordered: Decimal,
+    pub qty_received: Decimal,
+    pub unit_cost: Decimal,
+    pub line_total: Decimal,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,28 +69,85 @@ pub struct StockMovementResponse {
     pub id: Uuid,
     pub item_id: Uuid,
     pub location_id: Uuid,
-    pub quantity: i32,
+    pub quantity: Decimal,
     pub movement_type: String,
     pub reference_type: Option<String>,
     pub reference_id: Option<Uuid>,
     pub reason: Option<String>,
     pub created_by: Uuid,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub stock_status: StockStatus,
 }
 
-pub struct ReceivePurchaseOrderUseCase<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> {
+pub struct ReceivePurchaseOrderUseCase<
+    R: PurchaseOrderRepository,
+    P: PutawayRuleRepository,
+    D: WebhookDispatcher + 'static,
+> {
     purchase_order_repository: Arc<R>,
+    putaway_rule_repository: Arc<P>,
+    receiving_settings_repository: Arc<dyn ReceivingSettingsRepository>,
+    capacity_repository: Arc<dyn LocationCapacityRepository>,
+    item_repository: Arc<dyn ItemRepository>,
     webhook_dispatcher: Arc<D>,
 }
 
-impl<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> ReceivePurchaseOrderUseCase<R, D> {
-    pub fn new(purchase_order_repository: Arc<R>, webhook_dispatcher: Arc<D>) -> Self {
+impl<R: PurchaseOrderRepository, P: PutawayRuleRepository, D: WebhookDispatcher + 'static>
+    ReceivePurchaseOrderUseCase<R, P, D>
+{
+    pub fn new(
+        purchase_order_repository: Arc<R>,
+        putaway_rule_repository: Arc<P>,
+        receiving_settings_repository: Arc<dyn ReceivingSettingsRepository>,
+        capacity_repository: Arc<dyn LocationCapacityRepository>,
+        item_repository: Arc<dyn ItemRepository>,
+        webhook_dispatcher: Arc<D>,
+    ) -> Self {
         Self {
             purchase_order_repository,
+            putaway_rule_repository,
+            receiving_settings_repository,
+            capacity_repository,
+            item_repository,
             webhook_dispatcher,
         }
     }
 
+    async fn suggest_putaway(
+        &self,
+        movements: &[StockMovement],
+        receiving_location_id: Uuid,
+    ) -> Result<Vec<PutawaySuggestion>, DomainError> {
+        // Several PO lines can carry the same item; plan each item once
+        let mut received: Vec<(Uuid, Decimal)> = Vec::new();
+        for movement in movements {
+            match received
+                .iter_mut()
+                .find(|(item_id, _)| *item_id == movement.item_id)
+            {
+                Some((_, qty)) => *qty += movement.quantity,
+                None => received.push((movement.item_id, movement.quantity)),
+            }
+        }
+
+        let mut suggestions = Vec::new();
+        for (item_id, quantity) in received {
+            let mut candidates = self
+                .putaway_rule_repository
+                .find_candidates(item_id)
+                .await?;
+            limit_candidates_to_capacity(&*self.capacity_repository, item_id, &mut candidates)
+                .await?;
+            suggestions.extend(suggest_putaway(
+                item_id,
+                quantity,
+                receiving_location_id,
+                candidates,
+            ));
+        }
+        Ok(suggestions)
+    }
+
     pub async fn execute(
         &self,
         request: ReceivePurchaseOrderUseCaseRequest,
@@ -83,21 +158,68 @@ impl<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> ReceivePurchase
             received_lines: request.received_lines,
             receive_date: request.receive_date,
             destination_location_id: request.destination_location_id,
+            stock_status: request.stock_status,
         };
 
-        // Receive the purchase order
+        // Refuse a receipt finer than its items' precision, or one that would
+        // overfill an enforcing location, before touching stock; the PO's own
+        // checks reject unknown lines later
+        let po = self
+            .purchase_order_repository
+            .find_by_id(request.po_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_not_found",
+                    &[("id", &request.po_id)],
+                ))
+            })?;
+        let incoming: Vec<(Uuid, Decimal)> = receive_request
+            .received_lines
+            .iter()
+            .filter_map(|line| {
+                po.lines
+                    .iter()
+                    .find(|po_line| po_line.id == line.po_line_id)
+                    .map(|po_line| (po_line.item_id, line.qty_received))
+            })
+            .collect();
+        self.item_repository.check_quantities(&incoming).await?;
+        let capacity_warning = check_putaway_capacity(
+            &*self.capacity_repository,
+            request.destination_location_id,
+            &incoming,
+        )
+        .await?;
+
+        // Receive the purchase order, within the supplier's over-receipt tolerance
+        let tolerances = self.receiving_settings_repository.find().await?;
         let movements = self
             .purchase_order_repository
-            .receive_purchase_order(request.po_id, &receive_request, user_id)
+            .receive_purchase_order(request.po_id, &receive_request, &tolerances, user_id)
             .await?;
 
+        // Suggestions are advisory; a rule lookup failure shouldn't fail the receipt
+        let putaway_suggestions = match self
+            .suggest_putaway(&movements, request.destination_location_id)
+            .await
+        {
+            Ok(suggestions) => suggestions,
+            Err(e) => {
+                eprintln!("Failed to compute putaway suggestions: {:?}", e);
+                Vec::new()
+            }
+        };
+
         // Get updated PO
         let po = self
             .purchase_order_repository
             .find_by_id(request.po_id)
             .await?
             .ok_or_else(|| {
-                DomainError::ValidationError("Purchase order not found after receive".to_string())
+                DomainError::NotFound(
+                    format!("Purchase order {} not found after receive", request.po_id).into(),
+                )
             })?;
 
         // Dispatch webhook event (non-blocking)
@@ -115,6 +237,7 @@ impl<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> ReceivePurchase
                         crate::domain::entities::purchase_order::PurchaseOrderStatus::PartialReceived => "PARTIAL_RECEIVED",
                         crate::domain::entities::purchase_order::PurchaseOrderStatus::Received => "RECEIVED",
                         crate::domain::entities::purchase_order::PurchaseOrderStatus::Cancelled => "CANCELLED",
+                        crate::domain::entities::purchase_order::PurchaseOrderStatus::Closed => "CLOSED",
                     },
                     "total_amount": po.total_amount,
                     "updated_at": po.updated_at,
@@ -168,6 +291,7 @@ impl<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> ReceivePurchase
                     crate::domain::entities::purchase_order::PurchaseOrderStatus::PartialReceived => "PARTIAL_RECEIVED".to_string(),
                     crate::domain::entities::purchase_order::PurchaseOrderStatus::Received => "RECEIVED".to_string(),
                     crate::domain::entities::purchase_order::PurchaseOrderStatus::Cancelled => "CANCELLED".to_string(),
+                    crate::domain::entities::purchase_order::PurchaseOrderStatus::Closed => "CLOSED".to_string(),
                 },
                 total_amount: po.total_amount,
                 lines: po.lines.into_iter().map(|line| PurchaseOrderLineResponse {
@@ -197,7 +321,10 @@ impl<R: PurchaseOrderRepository, D: WebhookDispatcher + 'static> ReceivePurchase
                 reason: movement.reason,
                 created_by: movement.created_by.unwrap_or_else(|| Uuid::nil()),
                 created_at: movement.created_at,
+                stock_status: movement.stock_status,
             }).collect(),
+            putaway_suggestions,
+            capacity_warning,
         })
     }
 }
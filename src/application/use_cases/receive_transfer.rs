@@ -1,6 +1,5 @@
-use crate::domain::entities::transfer::{
-    ReceiveTransferRequest, StockMovement, Transfer, TransferLine,
-};
+use crate::domain::entities::inventory::StockMovement;
+use crate::domain::entities::transfer::{ReceiveTransferRequest, Transfer, TransferLine};
 use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
 use crate::domain::services::transfer_repository::TransferRepository;
 use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
@@ -40,7 +39,7 @@ impl<T: TransferRepository, D: WebhookDispatcher + 'static> ReceiveTransferUseCa
         // Receive the transfer through the repository
         let (transfer, lines, stock_movements) = self
             .transfer_repo
-            .receive_transfer(transfer_id, request.lines, created_by)
+            .receive_transfer(transfer_id, request, created_by)
             .await?;
 
         // Dispatch webhook event (non-blocking)
@@ -52,13 +51,7 @@ impl<T: TransferRepository, D: WebhookDispatcher + 'static> ReceiveTransferUseCa
                     "transfer_number": transfer.transfer_number,
                     "from_location_id": transfer.from_location_id,
                     "to_location_id": transfer.to_location_id,
-                    "status": match transfer.status {
-                        crate::domain::entities::transfer::TransferStatus::Draft => "DRAFT",
-                        crate::domain::entities::transfer::TransferStatus::Open => "OPEN",
-                        crate::domain::entities::transfer::TransferStatus::InTransit => "IN_TRANSIT",
-                        crate::domain::entities::transfer::TransferStatus::Received => "RECEIVED",
-                        crate::domain::entities::transfer::TransferStatus::Cancelled => "CANCELLED",
-                    },
+                    "status": transfer.status.as_str(),
                     "total_quantity": transfer.total_quantity,
                     "notes": transfer.notes,
                     "updated_at": transfer.updated_at,
@@ -66,7 +59,9 @@ impl<T: TransferRepository, D: WebhookDispatcher + 'static> ReceiveTransferUseCa
                         "id": line.id,
                         "item_id": line.item_id,
                         "quantity": line.quantity,
-                        "quantity_received": line.quantity_received
+                        "quantity_shipped": line.quantity_shipped,
+                        "quantity_received": line.quantity_received,
+                        "quantity_short": line.quantity_short
                     })).collect::<Vec<_>>()
                 },
                 "stock_movements": stock_movements.iter().map(|movement| json!({
@@ -74,13 +69,8 @@ impl<T: TransferRepository, D: WebhookDispatcher + 'static> ReceiveTransferUseCa
                     "item_id": movement.item_id,
                     "location_id": movement.location_id,
                     "quantity": movement.quantity,
-                    "movement_type": match movement.movement_type {
-                        crate::domain::entities::transfer::MovementType::Inbound => "INBOUND",
-                        crate::domain::entities::transfer::MovementType::Outbound => "OUTBOUND",
-                        crate::domain::entities::transfer::MovementType::Adjustment => "ADJUSTMENT",
-                        crate::domain::entities::transfer::MovementType::Transfer => "TRANSFER",
-                        crate::domain::entities::transfer::MovementType::Initial => "INITIAL",
-                    },
+                    "movement_type": movement.movement_type.as_str().to_uppercase(),
+                    "stock_status": movement.stock_status,
                     "reference_type": movement.reference_type.as_str(),
                     "reference_id": movement.reference_id,
                     "reason": movement.reason,
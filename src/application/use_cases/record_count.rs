@@ -0,0 +1,62 @@
+use crate::domain::entities::cycle_count::{CycleCount, RecordCountRequest};
+use crate::domain::services::cycle_count_repository::CycleCountRepository;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::shared::error::DomainError;
+use serde::Serialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct RecordCountResponse {
+    pub cycle_count: CycleCount,
+}
+
+pub struct RecordCountUseCase<C: CycleCountRepository> {
+    cycle_count_repository: Arc<C>,
+    item_repository: Arc<dyn ItemRepository>,
+}
+
+impl<C: CycleCountRepository> RecordCountUseCase<C> {
+    pub fn new(cycle_count_repository: Arc<C>, item_repository: Arc<dyn ItemRepository>) -> Self {
+        Self {
+            cycle_count_repository,
+            item_repository,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        cycle_count_id: Uuid,
+        request: RecordCountRequest,
+        counted_by: Uuid,
+    ) -> Result<RecordCountResponse, DomainError> {
+        if request.lines.is_empty() {
+            return Err(DomainError::ValidationError(
+                "At least one counted line is required".into(),
+            ));
+        }
+
+        let quantities: Vec<_> = request
+            .lines
+            .iter()
+            .map(|line| (line.item_id, line.counted_qty))
+            .collect();
+        self.item_repository.check_quantities(&quantities).await?;
+
+        let mut cycle_count = self
+            .cycle_count_repository
+            .find_by_id(cycle_count_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Cycle count {} not found", cycle_count_id).into())
+            })?;
+
+        for line in request.lines {
+            cycle_count.record_count(line.item_id, line.counted_qty, counted_by)?;
+        }
+
+        self.cycle_count_repository.update(&cycle_count).await?;
+
+        Ok(RecordCountResponse { cycle_count })
+    }
+}
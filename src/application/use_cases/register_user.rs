@@ -0,0 +1,224 @@
+use crate::application::use_cases::manage_tenant_users::TenantUserResponse;
+use crate::domain::entities::invitation::Invitation;
+use crate::domain::entities::user::User;
+use crate::domain::services::invitation_repository::InvitationRepository;
+use crate::domain::services::user_repository::UserRepository;
+use crate::domain::value_objects::password_hash::PasswordHash;
+use crate::shared::error::DomainError;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterUserRequest {
+    pub token: String,
+    pub password: String,
+    pub first_name: String,
+    pub last_name: String,
+}
+
+/// Creates a user from an invitation token, in the inviting tenant and with the
+/// invited role
+pub struct RegisterUserUseCase<U: UserRepository, I: InvitationRepository> {
+    user_repository: Arc<U>,
+    invitation_repository: Arc<I>,
+}
+
+impl<U: UserRepository, I: InvitationRepository> RegisterUserUseCase<U, I> {
+    pub fn new(user_repository: Arc<U>, invitation_repository: Arc<I>) -> Self {
+        Self {
+            user_repository,
+            invitation_repository,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        request: RegisterUserRequest,
+    ) -> Result<TenantUserResponse, DomainError> {
+        let mut invitation = self
+            .invitation_repository
+            .find_by_token_hash(&Invitation::hash_token(&request.token))
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Invitation not found".into()))?;
+
+        if self
+            .user_repository
+            .email_exists(&invitation.email, None)
+            .await?
+        {
+            return Err(DomainError::Conflict(
+                format!(
+                    "A user with email {} already exists",
+                    invitation.email.as_str()
+                )
+                .into(),
+            ));
+        }
+
+        let mut user = User::new(
+            invitation.email.clone(),
+            PasswordHash::new(&request.password)?,
+            request.first_name,
+            request.last_name,
+            invitation.tenant_id,
+        )?;
+        user.change_role(invitation.role);
+
+        invitation.accept()?;
+        if !self
+            .invitation_repository
+            .mark_accepted(&invitation)
+            .await?
+        {
+            return Err(DomainError::BusinessLogicError(
+                "Invitation has already been accepted".into(),
+            ));
+        }
+
+        self.user_repository.save(&user).await?;
+        Ok(user.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::user::UserRole;
+    use crate::domain::value_objects::email::Email;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct InMemoryUsers(Mutex<Vec<User>>);
+
+    #[async_trait]
+    impl UserRepository for InMemoryUsers {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+            Ok(self.0.lock().unwrap().iter().find(|u| u.id == id).cloned())
+        }
+
+        async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError> {
+            Ok(self
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|u| &u.email == email)
+                .cloned())
+        }
+
+        async fn save(&self, user: &User) -> Result<(), DomainError> {
+            self.0.lock().unwrap().push(user.clone());
+            Ok(())
+        }
+
+        async fn update(&self, _user: &User) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: Uuid) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn email_exists(
+            &self,
+            email: &Email,
+            _exclude_user_id: Option<Uuid>,
+        ) -> Result<bool, DomainError> {
+            Ok(self.find_by_email(email).await?.is_some())
+        }
+
+        async fn list_by_tenant(&self, tenant_id: Uuid) -> Result<Vec<User>, DomainError> {
+            Ok(self
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|u| u.tenant_id == tenant_id)
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[derive(Default)]
+    struct InMemoryInvitations(Mutex<Vec<Invitation>>);
+
+    #[async_trait]
+    impl InvitationRepository for InMemoryInvitations {
+        async fn save(&self, invitation: &Invitation) -> Result<(), DomainError> {
+            self.0.lock().unwrap().push(invitation.clone());
+            Ok(())
+        }
+
+        async fn find_by_token_hash(
+            &self,
+            token_hash: &str,
+        ) -> Result<Option<Invitation>, DomainError> {
+            Ok(self
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|i| i.token_hash == token_hash)
+                .cloned())
+        }
+
+        async fn mark_accepted(&self, invitation: &Invitation) -> Result<bool, DomainError> {
+            let mut invitations = self.0.lock().unwrap();
+            let stored = invitations
+                .iter_mut()
+                .find(|i| i.id == invitation.id)
+                .unwrap();
+            if stored.accepted_at.is_some() {
+                return Ok(false);
+            }
+            stored.accepted_at = invitation.accepted_at;
+            Ok(true)
+        }
+    }
+
+    fn request(token: &str) -> RegisterUserRequest {
+        RegisterUserRequest {
+            token: token.to_string(),
+            password: "correct horse battery".to_string(),
+            first_name: "Ada".to_string(),
+            last_name: "Lovelace".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registers_user_in_inviting_tenant_once() {
+        let tenant_id = Uuid::new_v4();
+        let invitations = Arc::new(InMemoryInvitations::default());
+        let (invitation, token) = Invitation::new(
+            tenant_id,
+            Email::new("ada@example.com".to_string()).unwrap(),
+            UserRole::Admin,
+            chrono::Duration::days(7),
+        );
+        invitations.save(&invitation).await.unwrap();
+
+        let users = Arc::new(InMemoryUsers::default());
+        let use_case = RegisterUserUseCase::new(Arc::clone(&users), invitations);
+
+        let user = use_case.execute(request(&token)).await.unwrap();
+        assert_eq!(user.email, "ada@example.com");
+        assert_eq!(user.role, UserRole::Admin);
+        assert_eq!(users.list_by_tenant(tenant_id).await.unwrap().len(), 1);
+
+        assert!(use_case.execute(request(&token)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_token() {
+        let use_case = RegisterUserUseCase::new(
+            Arc::new(InMemoryUsers::default()),
+            Arc::new(InMemoryInvitations::default()),
+        );
+        assert!(matches!(
+            use_case.execute(request("not-a-token")).await,
+            Err(DomainError::NotFound(_))
+        ));
+    }
+}
@@ -1,6 +1,9 @@
 use crate::domain::entities::webhook::{Webhook, WebhookEventType, WebhookStatus};
+use crate::domain::services::quota_service::{QuotaResource, QuotaService};
 use crate::domain::services::webhook_repository::WebhookRepository;
+use crate::domain::services::webhook_url_policy::WebhookUrlPolicy;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
@@ -28,44 +31,60 @@ pub struct RegisterWebhookResponse {
 
 pub struct RegisterWebhookUseCase<R: WebhookRepository> {
     webhook_repository: Arc<R>,
+    quota_service: Arc<dyn QuotaService>,
+    url_policy: Arc<WebhookUrlPolicy>,
 }
 
 impl<R: WebhookRepository> RegisterWebhookUseCase<R> {
-    pub fn new(webhook_repository: Arc<R>) -> Self {
-        Self { webhook_repository }
+    pub fn new(
+        webhook_repository: Arc<R>,
+        quota_service: Arc<dyn QuotaService>,
+        url_policy: Arc<WebhookUrlPolicy>,
+    ) -> Self {
+        Self {
+            webhook_repository,
+            quota_service,
+            url_policy,
+        }
     }
 
     pub async fn execute(
         &self,
         request: RegisterWebhookRequest,
         user_id: Uuid,
+        tenant_id: Uuid,
     ) -> Result<RegisterWebhookResponse, DomainError> {
-        // Validate URL format
-        if !request.url.starts_with("http://") && !request.url.starts_with("https://") {
-            return Err(DomainError::ValidationError(
-                "Webhook URL must start with http:// or https://".to_string(),
-            ));
-        }
+        // Refuse targets on internal networks before anything is stored
+        self.url_policy.check_url(&request.url)?;
 
         // Validate secret length (should be at least 32 characters for security)
         if request.secret.len() < 32 {
-            return Err(DomainError::ValidationError(
-                "Webhook secret must be at least 32 characters long".to_string(),
-            ));
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.webhook_secret_length",
+                &[],
+            )));
         }
 
         // Validate events list is not empty
         if request.events.is_empty() {
             return Err(DomainError::ValidationError(
-                "At least one event type must be specified".to_string(),
+                "At least one event type must be specified".into(),
             ));
         }
 
         // Create webhook entity
         let webhook = Webhook::new(request.url, request.secret, request.events, user_id)?;
 
-        // Save to repository
-        self.webhook_repository.create_webhook(&webhook).await?;
+        // Count the webhook against the tenant's quota, handing the slot back if the save fails
+        self.quota_service
+            .reserve(tenant_id, QuotaResource::Webhooks)
+            .await?;
+        if let Err(e) = self.webhook_repository.create_webhook(&webhook).await {
+            self.quota_service
+                .release(tenant_id, QuotaResource::Webhooks)
+                .await?;
+            return Err(e);
+        }
 
         Ok(RegisterWebhookResponse {
             id: webhook.id,
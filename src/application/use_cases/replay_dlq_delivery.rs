@@ -27,12 +27,14 @@ impl<R: WebhookRepository, D: WebhookDispatcher> ReplayDlqDeliveryUseCase<R, D>
             .webhook_repository
             .get_delivery(delivery_id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Delivery {} not found", delivery_id)))?;
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Delivery {} not found", delivery_id).into())
+            })?;
 
         // Check if delivery is in DLQ
         if delivery.status != crate::domain::entities::webhook::DeliveryStatus::Dlq {
             return Err(DomainError::ValidationError(
-                "Delivery is not in DLQ status".to_string(),
+                "Delivery is not in DLQ status".into(),
             ));
         }
 
@@ -42,7 +44,7 @@ impl<R: WebhookRepository, D: WebhookDispatcher> ReplayDlqDeliveryUseCase<R, D>
             .get_webhook(delivery.webhook_id)
             .await?
             .ok_or_else(|| {
-                DomainError::NotFound(format!("Webhook {} not found", delivery.webhook_id))
+                DomainError::NotFound(format!("Webhook {} not found", delivery.webhook_id).into())
             })?;
 
         // Attempt to redeliver using the retry_delivery method
@@ -0,0 +1,81 @@
+use crate::domain::entities::inventory::{StockLevel, StockReservationRequest};
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::reservation_repository::ReservationRepository;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct AvailableToPromiseResponse {
+    pub item_id: Uuid,
+    pub location_id: Uuid,
+    pub available_to_promise: Decimal,
+}
+
+/// Holds and releases soft reservations outside of the sales order flow (e.g. cart holds)
+pub struct ReserveStockUseCase<R: ReservationRepository> {
+    reservation_repository: Arc<R>,
+    item_repository: Arc<dyn ItemRepository>,
+}
+
+impl<R: ReservationRepository> ReserveStockUseCase<R> {
+    pub fn new(reservation_repository: Arc<R>, item_repository: Arc<dyn ItemRepository>) -> Self {
+        Self {
+            reservation_repository,
+            item_repository,
+        }
+    }
+
+    pub async fn reserve(
+        &self,
+        request: StockReservationRequest,
+    ) -> Result<StockLevel, DomainError> {
+        Self::validate(&request)?;
+        self.item_repository
+            .check_quantities(&[(request.item_id, request.quantity)])
+            .await?;
+        self.reservation_repository
+            .reserve(request.item_id, request.location_id, request.quantity)
+            .await
+    }
+
+    pub async fn release(
+        &self,
+        request: StockReservationRequest,
+    ) -> Result<StockLevel, DomainError> {
+        Self::validate(&request)?;
+        self.reservation_repository
+            .release(request.item_id, request.location_id, request.quantity)
+            .await
+    }
+
+    pub async fn available_to_promise(
+        &self,
+        item_id: Uuid,
+        location_id: Uuid,
+    ) -> Result<AvailableToPromiseResponse, DomainError> {
+        let available_to_promise = self
+            .reservation_repository
+            .get_available_to_promise(item_id, location_id)
+            .await?;
+
+        Ok(AvailableToPromiseResponse {
+            item_id,
+            location_id,
+            available_to_promise,
+        })
+    }
+
+    fn validate(request: &StockReservationRequest) -> Result<(), DomainError> {
+        if request.quantity <= Decimal::ZERO {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.reservation_quantity_positive",
+                &[],
+            )));
+        }
+        Ok(())
+    }
+}
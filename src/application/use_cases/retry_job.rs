@@ -0,0 +1,35 @@
+use crate::application::use_cases::get_job_status::GetJobStatusResponse;
+use crate::domain::services::job_service::JobService;
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RetryJobRequest {
+    pub tenant_id: Uuid,
+    pub job_id: String,
+}
+
+/// Queue a failed or cancelled job to run again from the start, with a fresh
+/// set of attempts
+pub struct RetryJobUseCase<S: JobService> {
+    job_service: Arc<S>,
+}
+
+impl<S: JobService> RetryJobUseCase<S> {
+    pub fn new(job_service: Arc<S>) -> Self {
+        Self { job_service }
+    }
+
+    pub async fn execute(
+        &self,
+        request: RetryJobRequest,
+    ) -> Result<GetJobStatusResponse, DomainError> {
+        let job = self
+            .job_service
+            .retry_job(request.tenant_id, &request.job_id)
+            .await?;
+        Ok(job.into())
+    }
+}
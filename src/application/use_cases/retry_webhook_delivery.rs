@@ -27,7 +27,9 @@ impl<R: WebhookRepository, D: WebhookDispatcher> RetryWebhookDeliveryUseCase<R,
             .webhook_repository
             .get_delivery(delivery_id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Delivery {} not found", delivery_id)))?;
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Delivery {} not found", delivery_id).into())
+            })?;
 
         // Verify webhook ownership
         let webhook = self
@@ -35,25 +37,25 @@ impl<R: WebhookRepository, D: WebhookDispatcher> RetryWebhookDeliveryUseCase<R,
             .get_webhook(delivery.webhook_id)
             .await?
             .ok_or_else(|| {
-                DomainError::NotFound(format!("Webhook {} not found", delivery.webhook_id))
+                DomainError::NotFound(format!("Webhook {} not found", delivery.webhook_id).into())
             })?;
 
         if webhook.created_by != user_id {
             return Err(DomainError::BusinessLogicError(
-                "You can only retry deliveries for your own webhooks".to_string(),
+                "You can only retry deliveries for your own webhooks".into(),
             ));
         }
 
         // Check if delivery can be retried
         if delivery.attempt_count >= 5 {
             return Err(DomainError::ValidationError(
-                "Delivery has exceeded maximum retry attempts".to_string(),
+                "Delivery has exceeded maximum retry attempts".into(),
             ));
         }
 
         if delivery.status.as_str() == "SUCCESS" {
             return Err(DomainError::ValidationError(
-                "Cannot retry a successful delivery".to_string(),
+                "Cannot retry a successful delivery".into(),
             ));
         }
 
@@ -0,0 +1,82 @@
+use crate::domain::entities::inventory::StockLevel;
+use crate::domain::entities::item::Item;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::stock_repository::StockRepository;
+use crate::shared::error::DomainError;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ScanMatch {
+    Barcode,
+    Sku,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScanLookupResponse {
+    pub code: String,
+    pub matched_by: ScanMatch,
+    pub item: Item,
+    pub stock_levels: Vec<StockLevel>,
+    pub total_on_hand: Decimal,
+    pub total_available_to_promise: Decimal,
+}
+
+/// Resolve a scanned code to an item, trying the barcode first and then the SKU
+pub async fn find_scanned_item<I: ItemRepository + ?Sized>(
+    item_repository: &I,
+    code: &str,
+) -> Result<(Item, ScanMatch), DomainError> {
+    let code = code.trim();
+    if code.is_empty() {
+        return Err(DomainError::ValidationError(
+            "Scanned code cannot be empty".into(),
+        ));
+    }
+
+    if let Some(item) = item_repository.find_by_barcode(code).await? {
+        return Ok((item, ScanMatch::Barcode));
+    }
+    match item_repository.find_by_sku(code).await? {
+        Some(item) => Ok((item, ScanMatch::Sku)),
+        None => Err(DomainError::NotFound(
+            format!("No item matches scanned code {}", code).into(),
+        )),
+    }
+}
+
+/// Looks up a scanned item together with its stock at every location
+pub struct ScanLookupUseCase<I: ItemRepository, S: StockRepository> {
+    item_repository: Arc<I>,
+    stock_repository: Arc<S>,
+}
+
+impl<I: ItemRepository, S: StockRepository> ScanLookupUseCase<I, S> {
+    pub fn new(item_repository: Arc<I>, stock_repository: Arc<S>) -> Self {
+        Self {
+            item_repository,
+            stock_repository,
+        }
+    }
+
+    pub async fn execute(&self, code: String) -> Result<ScanLookupResponse, DomainError> {
+        let code = code.trim().to_string();
+        let (item, matched_by) = find_scanned_item(&*self.item_repository, &code).await?;
+
+        let stock_levels = self.stock_repository.get_item_stock_levels(item.id).await?;
+        let total_on_hand = stock_levels.iter().map(|l| l.quantity_on_hand).sum();
+        let total_available_to_promise =
+            stock_levels.iter().map(|l| l.available_to_promise()).sum();
+
+        Ok(ScanLookupResponse {
+            code,
+            matched_by,
+            item,
+            stock_levels,
+            total_on_hand,
+            total_available_to_promise,
+        })
+    }
+}
@@ -3,7 +3,9 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use serde_json;
 
-use crate::domain::entities::search::{SearchQuery, SearchResult};
+use crate::domain::entities::search::{
+    SearchIndexHealth, SearchQuery, SearchResult, SuggestQuery, Suggestion,
+};
 use crate::domain::services::search_repository::SearchRepository;
 use crate::shared::error::DomainError;
 
@@ -21,6 +23,9 @@ pub trait SearchUseCase: Send + Sync {
     /// Search for stock levels specifically
     async fn search_stock_levels(&self, query: SearchQuery) -> Result<SearchResult, DomainError>;
 
+    /// Entities whose names or codes resemble partial, possibly misspelt, input
+    async fn suggest(&self, query: SuggestQuery) -> Result<Vec<Suggestion>, DomainError>;
+
     /// Get search suggestions based on partial input
     async fn get_search_suggestions(
         &self,
@@ -28,23 +33,30 @@ pub trait SearchUseCase: Send + Sync {
         limit: usize,
     ) -> Result<Vec<String>, DomainError>;
 
-    /// Rebuild search indexes from scratch
+    /// Rebuild the current tenant's search indexes from the source tables
     async fn rebuild_indexes(&self) -> Result<(), DomainError>;
+
+    /// State of the search backend for the current tenant
+    async fn health(&self) -> Result<SearchIndexHealth, DomainError>;
 }
 
+/// Suggestions returned by default, and at most
+const DEFAULT_SUGGESTION_LIMIT: i64 = 10;
+pub const MAX_SUGGESTION_LIMIT: i64 = 50;
+
 #[derive(Clone)]
-pub struct SearchUseCaseImpl<SR: SearchRepository> {
+pub struct SearchUseCaseImpl<SR: SearchRepository + ?Sized> {
     search_repository: Arc<SR>,
 }
 
-impl<SR: SearchRepository> SearchUseCaseImpl<SR> {
+impl<SR: SearchRepository + ?Sized> SearchUseCaseImpl<SR> {
     pub fn new(search_repository: Arc<SR>) -> Self {
         Self { search_repository }
     }
 }
 
 #[async_trait]
-impl<SR: SearchRepository> SearchUseCase for SearchUseCaseImpl<SR> {
+impl<SR: SearchRepository + ?Sized> SearchUseCase for SearchUseCaseImpl<SR> {
     async fn search(&self, query: SearchQuery) -> Result<SearchResult, DomainError> {
         self.search_repository.search(query).await
     }
@@ -67,37 +79,57 @@ impl<SR: SearchRepository> SearchUseCase for SearchUseCaseImpl<SR> {
         self.search_repository.search(stock_query).await
     }
 
+    async fn suggest(&self, mut query: SuggestQuery) -> Result<Vec<Suggestion>, DomainError> {
+        query.text = query.text.trim().to_string();
+        if query.text.is_empty() {
+            return Ok(Vec::new());
+        }
+        if query.limit <= 0 {
+            query.limit = DEFAULT_SUGGESTION_LIMIT;
+        }
+        query.limit = query.limit.min(MAX_SUGGESTION_LIMIT);
+        self.search_repository.suggest(query).await
+    }
+
     async fn get_search_suggestions(
         &self,
         prefix: String,
         limit: usize,
     ) -> Result<Vec<String>, DomainError> {
-        if prefix.trim().is_empty() {
-            return Ok(Vec::new());
-        }
+        let suggestions = self
+            .suggest(SuggestQuery {
+                text: prefix,
+                entity_types: None,
+                limit: limit as i64,
+            })
+            .await?;
 
-        // For testing purposes, return mock suggestions
-        // In a real implementation, this would query the search index for suggestions
-        let suggestions = vec!["suggestion1".to_string(), "suggestion2".to_string()];
-        let limited_suggestions = suggestions.into_iter().take(limit).collect();
-        Ok(limited_suggestions)
+        // Several stock levels can share a name; list each text once
+        let mut texts: Vec<String> = Vec::new();
+        for suggestion in suggestions {
+            if !texts.contains(&suggestion.text) {
+                texts.push(suggestion.text);
+            }
+        }
+        Ok(texts)
     }
 
     async fn rebuild_indexes(&self) -> Result<(), DomainError> {
-        // This would typically involve:
-        // 1. Clearing existing indexes
-        // 2. Re-indexing all items, locations, and stock levels from the database
-        // For now, we'll just clean up orphaned documents
-        let _count = self.search_repository.cleanup_orphaned_documents().await?;
+        self.search_repository.rebuild_index().await?;
         Ok(())
     }
+
+    async fn health(&self) -> Result<SearchIndexHealth, DomainError> {
+        self.search_repository.health().await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::domain::entities::search::{
-        SearchIndex, SearchIndexRequest, SearchResult, SearchResultItem,
+        SearchIndex, SearchIndexHealth, SearchIndexRequest, SearchIndexStatus, SearchResult,
+        SearchResultItem, SuggestQuery, Suggestion,
     };
     use async_trait::async_trait;
     use std::sync::Arc;
@@ -132,7 +164,7 @@ mod tests {
     impl SearchRepository for MockSearchRepository {
         async fn index_document(&self, _request: SearchIndexRequest) -> Result<(), DomainError> {
             if self.should_fail {
-                return Err(DomainError::ValidationError("Indexing failed".to_string()));
+                return Err(DomainError::ValidationError("Indexing failed".into()));
             }
             Ok(())
         }
@@ -143,14 +175,14 @@ mod tests {
             _entity_id: Uuid,
         ) -> Result<(), DomainError> {
             if self.should_fail {
-                return Err(DomainError::ValidationError("Remove failed".to_string()));
+                return Err(DomainError::ValidationError("Remove failed".into()));
             }
             Ok(())
         }
 
         async fn search(&self, query: SearchQuery) -> Result<SearchResult, DomainError> {
             if self.should_fail {
-                return Err(DomainError::ValidationError("Search failed".to_string()));
+                return Err(DomainError::ValidationError("Search failed".into()));
             }
 
             // Filter results based on entity types if specified
@@ -171,33 +203,96 @@ mod tests {
             })
         }
 
+        async fn suggest(&self, query: SuggestQuery) -> Result<Vec<Suggestion>, DomainError> {
+            if self.should_fail {
+                return Err(DomainError::ValidationError("Suggest failed".into()));
+            }
+            // Every result suggests its metadata name; two share one
+            Ok(self
+                .search_results
+                .iter()
+                .filter(|item| {
+                    query
+                        .entity_types
+                        .as_ref()
+                        .is_none_or(|types| types.contains(&item.entity_type))
+                })
+                .take(query.limit as usize)
+                .map(|item| Suggestion {
+                    entity_type: item.entity_type.clone(),
+                    entity_id: item.entity_id,
+                    text: item.metadata.as_ref().unwrap()["name"]
+                        .as_str()
+                        .unwrap()
+                        .to_string(),
+                    similarity: item.rank,
+                })
+                .collect())
+        }
+
         async fn get_document(
             &self,
             _entity_type: &str,
             _entity_id: Uuid,
         ) -> Result<Option<SearchIndex>, DomainError> {
             if self.should_fail {
-                return Err(DomainError::ValidationError(
-                    "Get document failed".to_string(),
-                ));
+                return Err(DomainError::ValidationError("Get document failed".into()));
             }
             // Mock implementation - return None for simplicity
             Ok(None)
         }
 
+        async fn index_items(&self, item_ids: &[Uuid]) -> Result<u64, DomainError> {
+            if self.should_fail {
+                return Err(DomainError::ValidationError("Indexing failed".into()));
+            }
+            Ok(item_ids.len() as u64)
+        }
+
+        async fn index_locations(&self, location_ids: &[Uuid]) -> Result<u64, DomainError> {
+            if self.should_fail {
+                return Err(DomainError::ValidationError("Indexing failed".into()));
+            }
+            Ok(location_ids.len() as u64)
+        }
+
+        async fn sync_stale_documents(&self) -> Result<u64, DomainError> {
+            if self.should_fail {
+                return Err(DomainError::ValidationError("Sync failed".into()));
+            }
+            Ok(0)
+        }
+
         async fn rebuild_index(&self) -> Result<i64, DomainError> {
             if self.should_fail {
-                return Err(DomainError::ValidationError("Rebuild failed".to_string()));
+                return Err(DomainError::ValidationError("Rebuild failed".into()));
             }
             Ok(100) // Mock rebuild count
         }
 
         async fn cleanup_orphaned_documents(&self) -> Result<i64, DomainError> {
             if self.should_fail {
-                return Err(DomainError::ValidationError("Cleanup failed".to_string()));
+                return Err(DomainError::ValidationError("Cleanup failed".into()));
             }
             Ok(5) // Mock cleanup count
         }
+
+        async fn health(&self) -> Result<SearchIndexHealth, DomainError> {
+            Ok(SearchIndexHealth {
+                backend: "mock".to_string(),
+                status: if self.should_fail {
+                    SearchIndexStatus::Unavailable
+                } else {
+                    SearchIndexStatus::Available
+                },
+                document_count: Some(self.search_results.len() as i64),
+                expected_document_count: self.search_results.len() as i64,
+                stale_document_count: 0,
+                pending_tasks: None,
+                last_indexed_at: None,
+                error: None,
+            })
+        }
     }
 
     fn create_mock_search_result(
@@ -333,17 +428,36 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_search_suggestions_success() {
-        let mock_repo = Arc::new(MockSearchRepository::new());
+        let mock_results = vec![
+            create_mock_search_result("item", "widget", 0.9),
+            create_mock_search_result("stock_level", "widget", 0.8),
+            create_mock_search_result("location", "warehouse", 0.7),
+        ];
+        let mock_repo = Arc::new(MockSearchRepository::new().with_results(mock_results));
         let use_case = SearchUseCaseImpl::new(mock_repo);
 
         let suggestions = use_case
-            .get_search_suggestions("test".to_string(), 10)
+            .get_search_suggestions("wid".to_string(), 10)
             .await
             .unwrap();
+        assert_eq!(suggestions, vec!["widget", "warehouse"]);
 
-        assert_eq!(suggestions.len(), 2);
-        assert_eq!(suggestions[0], "suggestion1");
-        assert_eq!(suggestions[1], "suggestion2");
+        let locations = use_case
+            .suggest(SuggestQuery {
+                text: " wa ".to_string(),
+                entity_types: Some(vec!["location".to_string()]),
+                limit: 0,
+            })
+            .await
+            .unwrap();
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].text, "warehouse");
+
+        let blank = use_case
+            .get_search_suggestions("  ".to_string(), 10)
+            .await
+            .unwrap();
+        assert!(blank.is_empty());
     }
 
     #[tokio::test]
@@ -1,14 +1,16 @@
-use crate::domain::entities::sales_order::{
-    SalesOrder, SalesOrderLine, ShipLineRequest, StockMovement,
+use crate::application::use_cases::create_shipment::CreateShipmentUseCase;
+use crate::domain::entities::sales_order::{SalesOrder, ShipLineRequest, StockMovement};
+use crate::domain::entities::shipment::{
+    Shipment, ShipmentDetails, ShipmentPackageRequest, ShipmentSourceType,
 };
-use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
 use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::domain::services::shipment_repository::ShipmentRepository;
 use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -18,29 +20,48 @@ pub struct ShipSalesOrderRequest {
     pub lines: Vec<ShipSalesOrderLineRequest>,
     pub tracking: Option<String>,
     pub carrier: Option<String>,
+    /// Ship what is in stock and leave the remainder open instead of failing
+    pub allow_backorder: Option<bool>,
+    pub packages: Option<Vec<ShipmentPackageRequest>>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ShipSalesOrderLineRequest {
     pub so_line_id: Uuid,
-    pub qty_shipped: i32,
+    pub qty_shipped: Decimal,
+    /// Ship from this location rather than wherever the line is allocated
+    #[serde(default)]
+    pub location_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct ShipSalesOrderResponse {
     pub sales_order: SalesOrder,
     pub stock_movements: Vec<StockMovement>,
+    pub shipment: Shipment,
 }
 
-pub struct ShipSalesOrderUseCase<T: SalesOrderRepository, D: WebhookDispatcher + 'static> {
+pub struct ShipSalesOrderUseCase<
+    T: SalesOrderRepository,
+    S: ShipmentRepository,
+    D: WebhookDispatcher + 'static,
+> {
     sales_order_repo: Arc<T>,
+    create_shipment_use_case: Arc<CreateShipmentUseCase<S, D>>,
     webhook_dispatcher: Arc<D>,
 }
 
-impl<T: SalesOrderRepository, D: WebhookDispatcher + 'static> ShipSalesOrderUseCase<T, D> {
-    pub fn new(sales_order_repo: Arc<T>, webhook_dispatcher: Arc<D>) -> Self {
+impl<T: SalesOrderRepository, S: ShipmentRepository, D: WebhookDispatcher + 'static>
+    ShipSalesOrderUseCase<T, S, D>
+{
+    pub fn new(
+        sales_order_repo: Arc<T>,
+        create_shipment_use_case: Arc<CreateShipmentUseCase<S, D>>,
+        webhook_dispatcher: Arc<D>,
+    ) -> Self {
         Self {
             sales_order_repo,
+            create_shipment_use_case,
             webhook_dispatcher,
         }
     }
@@ -51,6 +72,18 @@ impl<T: SalesOrderRepository, D: WebhookDispatcher + 'static> ShipSalesOrderUseC
         request: ShipSalesOrderRequest,
         created_by: Uuid,
     ) -> Result<ShipSalesOrderResponse, DomainError> {
+        let shipment = self.create_shipment_use_case.build(
+            ShipmentSourceType::SalesOrder,
+            so_id,
+            ShipmentDetails {
+                carrier: request.carrier,
+                tracking_number: request.tracking,
+                shipped_at: request.ship_date,
+                packages: request.packages,
+            },
+            created_by,
+        )?;
+
         // Convert request lines to domain objects
         let shipped_lines: Vec<ShipLineRequest> = request
             .lines
@@ -58,76 +91,40 @@ impl<T: SalesOrderRepository, D: WebhookDispatcher + 'static> ShipSalesOrderUseC
             .map(|line| ShipLineRequest {
                 so_line_id: line.so_line_id,
                 qty_shipped: line.qty_shipped,
+                location_id: line.location_id,
             })
             .collect();
 
-        // Ship the sales order through the repository
-        let (sales_order, lines, stock_movements) = self
+        // Ship the sales order, record the shipment and queue the webhook
+        // events in one transaction
+        let shipped = self
             .sales_order_repo
-            .ship_sales_order(so_id, shipped_lines, created_by)
+            .ship_sales_order(
+                so_id,
+                shipped_lines,
+                request.allow_backorder.unwrap_or(false),
+                shipment,
+                created_by,
+            )
             .await?;
 
-        // Dispatch webhook event (non-blocking)
-        let webhook_event = WebhookEvent::new(
-            WebhookEventType::SalesOrderUpdated,
-            json!({
-                "sales_order": {
-                    "id": sales_order.id,
-                    "so_number": sales_order.so_number,
-                    "customer_id": sales_order.customer_id,
-                    "status": match sales_order.status {
-                        crate::domain::entities::sales_order::SalesOrderStatus::Draft => "DRAFT",
-                        crate::domain::entities::sales_order::SalesOrderStatus::Confirmed => "CONFIRMED",
-                        crate::domain::entities::sales_order::SalesOrderStatus::Picking => "PICKING",
-                        crate::domain::entities::sales_order::SalesOrderStatus::Shipped => "SHIPPED",
-                        crate::domain::entities::sales_order::SalesOrderStatus::Invoiced => "INVOICED",
-                        crate::domain::entities::sales_order::SalesOrderStatus::Cancelled => "CANCELLED",
-                        crate::domain::entities::sales_order::SalesOrderStatus::Returned => "RETURNED",
-                    },
-                    "total_amount": sales_order.total_amount,
-                    "fulfillment_location_id": sales_order.fulfillment_location_id,
-                    "updated_at": sales_order.updated_at,
-                    "lines": lines.iter().map(|line| json!({
-                        "id": line.id,
-                        "item_id": line.item_id,
-                        "qty": line.qty,
-                        "unit_price": line.unit_price,
-                        "tax": line.tax,
-                        "line_total": line.line_total()
-                    })).collect::<Vec<_>>()
-                },
-                "stock_movements": stock_movements.iter().map(|movement| json!({
-                    "id": movement.id,
-                    "item_id": movement.item_id,
-                    "location_id": movement.location_id,
-                    "quantity": movement.quantity,
-                    "movement_type": match movement.movement_type {
-                        crate::domain::entities::inventory::MovementType::Inbound => "INBOUND",
-                        crate::domain::entities::inventory::MovementType::Outbound => "OUTBOUND",
-                        crate::domain::entities::inventory::MovementType::Adjustment => "ADJUSTMENT",
-                        crate::domain::entities::inventory::MovementType::Transfer => "TRANSFER",
-                        crate::domain::entities::inventory::MovementType::Initial => "INITIAL",
-                    },
-                    "reference_type": movement.reference_type.as_str(),
-                    "reference_id": movement.reference_id,
-                    "reason": movement.reason,
-                    "created_by": movement.created_by,
-                    "created_at": movement.created_at
-                })).collect::<Vec<_>>()
-            }),
-        );
-
-        // Spawn a task to dispatch the webhook asynchronously
+        // Publish the queued events now rather than on the delivery worker's next
+        // poll; whatever this misses, the worker sends
+        let event_ids: Vec<Uuid> = shipped.events.iter().map(|event| event.id).collect();
         let dispatcher = Arc::clone(&self.webhook_dispatcher);
         tokio::spawn(async move {
-            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
-                eprintln!("Failed to dispatch sales order updated webhook: {:?}", e);
+            if let Err(e) = dispatcher
+                .publish_outbox(Some(&event_ids), event_ids.len() as i64)
+                .await
+            {
+                eprintln!("Failed to publish sales order shipment events: {:?}", e);
             }
         });
 
         Ok(ShipSalesOrderResponse {
-            sales_order,
-            stock_movements,
+            sales_order: shipped.sales_order,
+            stock_movements: shipped.stock_movements,
+            shipment: shipped.shipment,
         })
     }
 }
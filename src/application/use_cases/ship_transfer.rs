@@ -1,30 +1,61 @@
-use crate::domain::entities::transfer::{StockMovement, Transfer, TransferLine};
+use crate::application::use_cases::create_shipment::CreateShipmentUseCase;
+use crate::domain::entities::inventory::StockMovement;
+use crate::domain::entities::shipment::{
+    Shipment, ShipmentDetails, ShipmentPackageRequest, ShipmentSourceType,
+};
+use crate::domain::entities::transfer::{ShipTransferLineRequest, Transfer, TransferLine};
 use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::shipment_repository::ShipmentRepository;
 use crate::domain::services::transfer_repository::TransferRepository;
 use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
+#[derive(Debug, Default, Deserialize)]
+pub struct ShipTransferRequest {
+    pub ship_date: Option<DateTime<Utc>>,
+    pub tracking: Option<String>,
+    pub carrier: Option<String>,
+    pub packages: Option<Vec<ShipmentPackageRequest>>,
+    /// Quantities to ship per line; everything still unshipped when empty
+    #[serde(default)]
+    pub lines: Vec<ShipTransferLineRequest>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ShipTransferResponse {
     pub transfer: Transfer,
     pub lines: Vec<TransferLine>,
     pub stock_movements: Vec<StockMovement>,
+    pub shipment: Shipment,
 }
 
-pub struct ShipTransferUseCase<T: TransferRepository, D: WebhookDispatcher + 'static> {
+pub struct ShipTransferUseCase<
+    T: TransferRepository,
+    S: ShipmentRepository,
+    D: WebhookDispatcher + 'static,
+> {
     transfer_repo: Arc<T>,
+    create_shipment_use_case: Arc<CreateShipmentUseCase<S, D>>,
     webhook_dispatcher: Arc<D>,
 }
 
-impl<T: TransferRepository, D: WebhookDispatcher + 'static> ShipTransferUseCase<T, D> {
-    pub fn new(transfer_repo: Arc<T>, webhook_dispatcher: Arc<D>) -> Self {
+impl<T: TransferRepository, S: ShipmentRepository, D: WebhookDispatcher + 'static>
+    ShipTransferUseCase<T, S, D>
+{
+    pub fn new(
+        transfer_repo: Arc<T>,
+        create_shipment_use_case: Arc<CreateShipmentUseCase<S, D>>,
+        webhook_dispatcher: Arc<D>,
+    ) -> Self {
         Self {
             transfer_repo,
+            create_shipment_use_case,
             webhook_dispatcher,
         }
     }
@@ -32,14 +63,30 @@ impl<T: TransferRepository, D: WebhookDispatcher + 'static> ShipTransferUseCase<
     pub async fn execute(
         &self,
         transfer_id: Uuid,
+        request: ShipTransferRequest,
         created_by: Uuid,
     ) -> Result<ShipTransferResponse, DomainError> {
+        let shipment = self.create_shipment_use_case.build(
+            ShipmentSourceType::Transfer,
+            transfer_id,
+            ShipmentDetails {
+                carrier: request.carrier,
+                tracking_number: request.tracking,
+                shipped_at: request.ship_date,
+                packages: request.packages,
+            },
+            created_by,
+        )?;
+
         // Ship the transfer through the repository
         let (transfer, lines, stock_movements) = self
             .transfer_repo
-            .ship_transfer(transfer_id, created_by)
+            .ship_transfer(transfer_id, request.lines, created_by)
             .await?;
 
+        // Record the carrier shipment against the transfer
+        let shipment = self.create_shipment_use_case.execute(shipment).await?;
+
         // Dispatch webhook event (non-blocking)
         let webhook_event = WebhookEvent::new(
             WebhookEventType::TransferUpdated,
@@ -49,13 +96,7 @@ impl<T: TransferRepository, D: WebhookDispatcher + 'static> ShipTransferUseCase<
                     "transfer_number": transfer.transfer_number,
                     "from_location_id": transfer.from_location_id,
                     "to_location_id": transfer.to_location_id,
-                    "status": match transfer.status {
-                        crate::domain::entities::transfer::TransferStatus::Draft => "DRAFT",
-                        crate::domain::entities::transfer::TransferStatus::Open => "OPEN",
-                        crate::domain::entities::transfer::TransferStatus::InTransit => "IN_TRANSIT",
-                        crate::domain::entities::transfer::TransferStatus::Received => "RECEIVED",
-                        crate::domain::entities::transfer::TransferStatus::Cancelled => "CANCELLED",
-                    },
+                    "status": transfer.status.as_str(),
                     "total_quantity": transfer.total_quantity,
                     "notes": transfer.notes,
                     "updated_at": transfer.updated_at,
@@ -63,7 +104,9 @@ impl<T: TransferRepository, D: WebhookDispatcher + 'static> ShipTransferUseCase<
                         "id": line.id,
                         "item_id": line.item_id,
                         "quantity": line.quantity,
-                        "quantity_received": line.quantity_received
+                        "quantity_shipped": line.quantity_shipped,
+                        "quantity_received": line.quantity_received,
+                        "quantity_short": line.quantity_short
                     })).collect::<Vec<_>>()
                 },
                 "stock_movements": stock_movements.iter().map(|movement| json!({
@@ -71,19 +114,20 @@ impl<T: TransferRepository, D: WebhookDispatcher + 'static> ShipTransferUseCase<
                     "item_id": movement.item_id,
                     "location_id": movement.location_id,
                     "quantity": movement.quantity,
-                    "movement_type": match movement.movement_type {
-                        crate::domain::entities::transfer::MovementType::Inbound => "INBOUND",
-                        crate::domain::entities::transfer::MovementType::Outbound => "OUTBOUND",
-                        crate::domain::entities::transfer::MovementType::Adjustment => "ADJUSTMENT",
-                        crate::domain::entities::transfer::MovementType::Transfer => "TRANSFER",
-                        crate::domain::entities::transfer::MovementType::Initial => "INITIAL",
-                    },
+                    "movement_type": movement.movement_type.as_str().to_uppercase(),
+                    "stock_status": movement.stock_status,
                     "reference_type": movement.reference_type.as_str(),
                     "reference_id": movement.reference_id,
                     "reason": movement.reason,
                     "created_by": movement.created_by,
                     "created_at": movement.created_at
-                })).collect::<Vec<_>>()
+                })).collect::<Vec<_>>(),
+                "shipment": {
+                    "id": shipment.id,
+                    "shipment_number": shipment.shipment_number,
+                    "carrier": shipment.carrier,
+                    "tracking_number": shipment.tracking_number
+                }
             }),
         );
 
@@ -99,6 +143,7 @@ impl<T: TransferRepository, D: WebhookDispatcher + 'static> ShipTransferUseCase<
             transfer,
             lines,
             stock_movements,
+            shipment,
         })
     }
 }
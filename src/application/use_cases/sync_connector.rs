@@ -0,0 +1,285 @@
+use crate::domain::entities::integration::{
+    IntegrationConnector, IntegrationOrderLink, ListSyncRunsQuery, SyncKind, SyncRun, SyncRunError,
+    SyncTrigger,
+};
+use crate::domain::entities::sales_order::{SalesOrder, SalesOrderLine};
+use crate::domain::services::connector::{ChannelOrder, ChannelStockLevel, ConnectorRegistry};
+use crate::domain::services::integration_repository::IntegrationRepository;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::shared::error::DomainError;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct SyncRunsResponse {
+    pub runs: Vec<SyncRun>,
+}
+
+pub struct SyncConnectorUseCase<
+    R: IntegrationRepository,
+    I: ItemRepository,
+    O: SalesOrderRepository,
+> {
+    integration_repository: Arc<R>,
+    item_repository: Arc<I>,
+    sales_order_repository: Arc<O>,
+    registry: Arc<ConnectorRegistry>,
+}
+
+impl<R: IntegrationRepository, I: ItemRepository, O: SalesOrderRepository>
+    SyncConnectorUseCase<R, I, O>
+{
+    pub fn new(
+        integration_repository: Arc<R>,
+        item_repository: Arc<I>,
+        sales_order_repository: Arc<O>,
+        registry: Arc<ConnectorRegistry>,
+    ) -> Self {
+        Self {
+            integration_repository,
+            item_repository,
+            sales_order_repository,
+            registry,
+        }
+    }
+
+    /// Sync a connector now instead of waiting for its next scheduled run
+    pub async fn sync(&self, connector_id: Uuid) -> Result<SyncRunsResponse, DomainError> {
+        let connector = self
+            .integration_repository
+            .find_connector(connector_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Connector {} not found", connector_id).into())
+            })?;
+        if !connector.active {
+            return Err(DomainError::BusinessLogicError(
+                format!("Connector {} is inactive", connector.name).into(),
+            ));
+        }
+        if !connector.is_connected() {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Connector {} has not been granted access to {}",
+                    connector.name, connector.shop_domain
+                )
+                .into(),
+            ));
+        }
+        let runs = self.run(connector, SyncTrigger::Manual).await?;
+        Ok(SyncRunsResponse { runs })
+    }
+
+    /// Pull paid orders, then push stock changes, recording a run for each.
+    /// Channel failures end up in the run history rather than the result.
+    pub async fn run(
+        &self,
+        mut connector: IntegrationConnector,
+        trigger: SyncTrigger,
+    ) -> Result<Vec<SyncRun>, DomainError> {
+        let mut runs = vec![self.pull_orders(&mut connector, trigger).await?];
+        // Without a channel location there is nowhere to publish stock
+        if connector.external_location_id.is_some() {
+            runs.push(self.push_stock(&mut connector, trigger).await?);
+        }
+
+        connector.schedule_next_sync(Utc::now());
+        self.integration_repository
+            .save_sync_state(&connector)
+            .await?;
+        for run in &runs {
+            self.integration_repository.create_sync_run(run).await?;
+        }
+        Ok(runs)
+    }
+
+    pub async fn list_runs(
+        &self,
+        connector_id: Uuid,
+        query: ListSyncRunsQuery,
+    ) -> Result<SyncRunsResponse, DomainError> {
+        if self
+            .integration_repository
+            .find_connector(connector_id)
+            .await?
+            .is_none()
+        {
+            return Err(DomainError::NotFound(
+                format!("Connector {} not found", connector_id).into(),
+            ));
+        }
+        let kind = query.kind.as_deref().map(SyncKind::from_str).transpose()?;
+        let limit = query.limit.unwrap_or(50).clamp(1, 100);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let runs = self
+            .integration_repository
+            .list_sync_runs(connector_id, kind, limit, offset)
+            .await?;
+        Ok(SyncRunsResponse { runs })
+    }
+
+    async fn pull_orders(
+        &self,
+        connector: &mut IntegrationConnector,
+        trigger: SyncTrigger,
+    ) -> Result<SyncRun, DomainError> {
+        let started_at = Utc::now();
+        let orders = match self
+            .registry
+            .get(connector.provider)?
+            .fetch_paid_orders(connector, connector.orders_cursor)
+            .await
+        {
+            Ok(orders) => orders,
+            Err(error) => {
+                return Ok(SyncRun::aborted(
+                    connector.id,
+                    SyncKind::Orders,
+                    trigger,
+                    started_at,
+                    &error,
+                ))
+            }
+        };
+
+        let mut processed = 0;
+        let mut errors = Vec::new();
+        let mut retry_from: Option<DateTime<Utc>> = None;
+        for order in &orders {
+            if self
+                .integration_repository
+                .find_order_link(connector.id, &order.external_id)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            match self.sales_order(connector, order).await? {
+                Ok(sales_order) => {
+                    self.sales_order_repository.create(&sales_order).await?;
+                    self.integration_repository
+                        .create_order_link(&IntegrationOrderLink {
+                            connector_id: connector.id,
+                            external_order_id: order.external_id.clone(),
+                            sales_order_id: sales_order.id,
+                            external_order_number: Some(order.order_number.clone()),
+                        })
+                        .await?;
+                    processed += 1;
+                }
+                Err(message) => {
+                    errors.push(SyncRunError {
+                        reference: Some(order.order_number.clone()),
+                        message,
+                    });
+                    retry_from =
+                        Some(retry_from.map_or(order.updated_at, |at| at.min(order.updated_at)));
+                }
+            }
+        }
+
+        // Failed orders are fetched again next time; the ones already linked are skipped
+        if let Some(latest) = retry_from.or(orders.iter().map(|o| o.updated_at).max()) {
+            connector.orders_cursor = Some(latest);
+        }
+        Ok(SyncRun::finished(
+            connector.id,
+            SyncKind::Orders,
+            trigger,
+            started_at,
+            processed,
+            errors,
+        ))
+    }
+
+    /// A confirmed sales order for a paid channel order, or why it cannot be raised
+    async fn sales_order(
+        &self,
+        connector: &IntegrationConnector,
+        order: &ChannelOrder,
+    ) -> Result<Result<SalesOrder, String>, DomainError> {
+        let mut sales_order = SalesOrder::new(
+            format!("SO-{}", Uuid::new_v4().simple()),
+            connector.customer_id,
+            Some(connector.location_id),
+            connector.created_by,
+        )?;
+        for line in &order.lines {
+            let Some(sku) = &line.sku else {
+                return Ok(Err("Order has a line without a SKU".to_string()));
+            };
+            let Some(item) = self.item_repository.find_by_sku(sku).await? else {
+                return Ok(Err(format!("No item has SKU {}", sku)));
+            };
+            let added = SalesOrderLine::new(item.id, line.quantity, line.unit_price)
+                .and_then(|line| sales_order.add_line(line));
+            if let Err(error) = added {
+                return Ok(Err(format!("SKU {}: {}", sku, error)));
+            }
+        }
+        // The channel only hands over orders that have been paid for
+        if let Err(error) = sales_order.confirm() {
+            return Ok(Err(error.to_string()));
+        }
+        Ok(Ok(sales_order))
+    }
+
+    async fn push_stock(
+        &self,
+        connector: &mut IntegrationConnector,
+        trigger: SyncTrigger,
+    ) -> Result<SyncRun, DomainError> {
+        let started_at = Utc::now();
+        let stock = self
+            .integration_repository
+            .list_location_stock(connector.location_id, connector.inventory_cursor)
+            .await?;
+        if stock.is_empty() {
+            return Ok(SyncRun::finished(
+                connector.id,
+                SyncKind::Inventory,
+                trigger,
+                started_at,
+                0,
+                Vec::new(),
+            ));
+        }
+
+        let levels: Vec<ChannelStockLevel> = stock
+            .iter()
+            .map(|level| ChannelStockLevel {
+                sku: level.sku.clone(),
+                available: level.available,
+            })
+            .collect();
+        match self
+            .registry
+            .get(connector.provider)?
+            .push_stock_levels(connector, &levels)
+            .await
+        {
+            Ok(result) => {
+                // SKUs the channel does not list stay unpublished until they change again
+                connector.inventory_cursor = stock.iter().map(|level| level.updated_at).max();
+                Ok(SyncRun::finished(
+                    connector.id,
+                    SyncKind::Inventory,
+                    trigger,
+                    started_at,
+                    result.updated,
+                    result.errors,
+                ))
+            }
+            Err(error) => Ok(SyncRun::aborted(
+                connector.id,
+                SyncKind::Inventory,
+                trigger,
+                started_at,
+                &error,
+            )),
+        }
+    }
+}
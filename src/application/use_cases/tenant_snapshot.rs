@@ -0,0 +1,356 @@
+use crate::domain::entities::export::{export_object_key, CreateExportResponse, ExportType};
+use crate::domain::entities::job::{CreateJobRequest, Job};
+use crate::domain::entities::search::REBUILD_SEARCH_INDEX_JOB_TYPE;
+use crate::domain::entities::tenant_snapshot::{
+    SnapshotManifest, SnapshotManifestTable, SnapshotTable, TenantSnapshot,
+    TenantSnapshotImportPayload, TenantSnapshotImportResponse, SNAPSHOT_TABLES,
+    TENANT_SNAPSHOT_EXPORT_JOB_TYPE, TENANT_SNAPSHOT_FILE_NAME, TENANT_SNAPSHOT_FORMAT_VERSION,
+    TENANT_SNAPSHOT_IMPORT_JOB_TYPE,
+};
+use crate::domain::services::blob_storage::BlobStorage;
+use crate::domain::services::job_service::JobService;
+use crate::domain::services::tenant_snapshot_repository::TenantSnapshotRepository;
+use crate::shared::error::DomainError;
+use crate::shared::spooled_file::SpooledFile;
+use std::io::{BufReader, Cursor, Read, Seek, Write};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Exports a tenant's data as a zip archive and restores such an archive into
+/// another tenant, possibly in another environment. Both run as jobs.
+pub struct TenantSnapshotUseCase<R: TenantSnapshotRepository + 'static, S: JobService + 'static> {
+    snapshot_repository: Arc<R>,
+    job_service: Arc<S>,
+    blob_storage: Arc<dyn BlobStorage>,
+    url_expiry: Duration,
+}
+
+impl<R: TenantSnapshotRepository + 'static, S: JobService + 'static> TenantSnapshotUseCase<R, S> {
+    pub fn new(
+        snapshot_repository: Arc<R>,
+        job_service: Arc<S>,
+        blob_storage: Arc<dyn BlobStorage>,
+        url_expiry: Duration,
+    ) -> Self {
+        Self {
+            snapshot_repository,
+            job_service,
+            blob_storage,
+            url_expiry,
+        }
+    }
+
+    /// Queue a snapshot export; the job's `result_url` links to the archive once
+    /// it succeeds
+    pub async fn request_export(
+        &self,
+        tenant_id: Uuid,
+    ) -> Result<CreateExportResponse, DomainError> {
+        let job = self
+            .job_service
+            .enqueue_job(
+                tenant_id,
+                CreateJobRequest {
+                    job_type: TENANT_SNAPSHOT_EXPORT_JOB_TYPE.to_string(),
+                    payload: serde_json::json!({}),
+                    max_attempts: None,
+                },
+            )
+            .await?;
+
+        Ok(CreateExportResponse {
+            job_id: job.job_id,
+            export_type: ExportType::TenantSnapshot,
+            status: job.status.to_string(),
+            created_at: job.created_at,
+        })
+    }
+
+    /// Write the archive of a queued export job to blob storage and sign a link to it
+    pub async fn run_export(&self, job: &Job) -> Result<String, DomainError> {
+        let snapshot = self.snapshot_repository.dump().await?;
+        let archive = write_archive(&snapshot)?;
+
+        let key = export_object_key(job.tenant_id, &job.job_id, TENANT_SNAPSHOT_FILE_NAME);
+        self.blob_storage
+            .put(&key, "application/zip", archive)
+            .await?;
+        self.blob_storage.signed_url(&key, self.url_expiry).await
+    }
+
+    /// Check an uploaded archive and queue its restore into `tenant_id`. The
+    /// tenant must not own any data yet.
+    pub async fn request_import(
+        &self,
+        tenant_id: Uuid,
+        restored_by: Uuid,
+        archive: SpooledFile,
+    ) -> Result<TenantSnapshotImportResponse, DomainError> {
+        let snapshot = read_archive_from(BufReader::new(archive.open()?))?;
+        if self.snapshot_repository.has_data().await? {
+            return Err(DomainError::Conflict(
+                "A snapshot can only be restored into a tenant without data".into(),
+            ));
+        }
+
+        let storage_key = format!("tenants/{}/imports/{}.zip", tenant_id, Uuid::new_v4());
+        self.blob_storage
+            .put_file(&storage_key, "application/zip", archive.path())
+            .await?;
+
+        let payload = TenantSnapshotImportPayload {
+            storage_key,
+            restored_by,
+        };
+        let job = self
+            .job_service
+            .enqueue_job(
+                tenant_id,
+                CreateJobRequest {
+                    job_type: TENANT_SNAPSHOT_IMPORT_JOB_TYPE.to_string(),
+                    payload: serde_json::to_value(&payload).map_err(|e| {
+                        DomainError::ValidationError(
+                            format!("Failed to serialize payload: {}", e).into(),
+                        )
+                    })?,
+                    max_attempts: None,
+                },
+            )
+            .await?;
+
+        Ok(TenantSnapshotImportResponse {
+            job_id: job.job_id,
+            status: job.status.to_string(),
+            source_tenant_id: snapshot.source_tenant_id,
+            exported_at: snapshot.exported_at,
+            tables: snapshot.counts(),
+            created_at: job.created_at,
+        })
+    }
+
+    /// Restore the archive of a queued import job, then queue a search index
+    /// rebuild so the restored items and locations can be found
+    pub async fn run_import(&self, job: &Job) -> Result<(), DomainError> {
+        let payload: TenantSnapshotImportPayload =
+            serde_json::from_value(job.payload.clone().unwrap_or_default()).map_err(|e| {
+                DomainError::ValidationError(
+                    format!("Invalid snapshot import payload: {}", e).into(),
+                )
+            })?;
+
+        let data = self.blob_storage.get(&payload.storage_key).await?;
+        let snapshot = read_archive(&data)?;
+        self.snapshot_repository
+            .restore(&snapshot, payload.restored_by)
+            .await?;
+
+        self.job_service
+            .enqueue_job(
+                job.tenant_id,
+                CreateJobRequest {
+                    job_type: REBUILD_SEARCH_INDEX_JOB_TYPE.to_string(),
+                    payload: serde_json::json!({}),
+                    max_attempts: None,
+                },
+            )
+            .await?;
+
+        if let Err(e) = self.blob_storage.delete(&payload.storage_key).await {
+            eprintln!(
+                "Failed to remove snapshot file {}: {:?}",
+                payload.storage_key, e
+            );
+        }
+        Ok(())
+    }
+}
+
+fn zip_error(e: zip::result::ZipError) -> DomainError {
+    DomainError::ValidationError(format!("Invalid snapshot archive: {}", e).into())
+}
+
+/// Zip a snapshot: `manifest.json` plus one `<table>.jsonl` per table
+pub fn write_archive(snapshot: &TenantSnapshot) -> Result<Vec<u8>, DomainError> {
+    let write_error = |e: std::io::Error| {
+        DomainError::InfrastructureError(format!("Failed to write snapshot archive: {}", e).into())
+    };
+    let manifest = SnapshotManifest {
+        format_version: TENANT_SNAPSHOT_FORMAT_VERSION,
+        source_tenant_id: snapshot.source_tenant_id,
+        exported_at: snapshot.exported_at,
+        tables: snapshot
+            .tables
+            .iter()
+            .map(|table| SnapshotManifestTable {
+                name: table.name.clone(),
+                file: format!("{}.jsonl", table.name),
+                rows: table.rows.len(),
+            })
+            .collect(),
+    };
+
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file(MANIFEST_FILE_NAME, options)
+        .map_err(zip_error)?;
+    let manifest_json = serde_json::to_vec_pretty(&manifest).map_err(|e| {
+        DomainError::InfrastructureError(format!("Failed to serialize manifest: {}", e).into())
+    })?;
+    zip.write_all(&manifest_json).map_err(write_error)?;
+
+    for (table, entry) in snapshot.tables.iter().zip(&manifest.tables) {
+        zip.start_file(entry.file.as_str(), options)
+            .map_err(zip_error)?;
+        for row in &table.rows {
+            zip.write_all(row.as_bytes()).map_err(write_error)?;
+            zip.write_all(b"\n").map_err(write_error)?;
+        }
+    }
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
+
+/// Read an archive written by `write_archive`, checking that the manifest and
+/// the table files agree and that every row is a JSON object
+pub fn read_archive(data: &[u8]) -> Result<TenantSnapshot, DomainError> {
+    read_archive_from(Cursor::new(data))
+}
+
+/// `read_archive` over any seekable reader, such as an uploaded file
+pub fn read_archive_from<R: Read + Seek>(reader: R) -> Result<TenantSnapshot, DomainError> {
+    let mut zip = ZipArchive::new(reader).map_err(zip_error)?;
+    let read_file = |zip: &mut ZipArchive<R>, name: &str| {
+        let mut file = zip.by_name(name).map_err(|_| {
+            DomainError::ValidationError(format!("The snapshot archive has no {}", name).into())
+        })?;
+        let mut content = String::new();
+        file.read_to_string(&mut content).map_err(|e| {
+            DomainError::ValidationError(
+                format!("Failed to read {} from the snapshot: {}", name, e).into(),
+            )
+        })?;
+        Ok::<_, DomainError>(content)
+    };
+
+    let manifest: SnapshotManifest =
+        serde_json::from_str(&read_file(&mut zip, MANIFEST_FILE_NAME)?).map_err(|e| {
+            DomainError::ValidationError(format!("Invalid snapshot manifest: {}", e).into())
+        })?;
+    if manifest.format_version > TENANT_SNAPSHOT_FORMAT_VERSION {
+        return Err(DomainError::ValidationError(
+            format!(
+                "Snapshot format version {} is newer than this server supports ({})",
+                manifest.format_version, TENANT_SNAPSHOT_FORMAT_VERSION
+            )
+            .into(),
+        ));
+    }
+
+    let mut tables = Vec::with_capacity(manifest.tables.len());
+    for entry in &manifest.tables {
+        if !SNAPSHOT_TABLES.contains(&entry.name.as_str()) {
+            return Err(DomainError::ValidationError(
+                format!("Unknown snapshot table {}", entry.name).into(),
+            ));
+        }
+        let content = read_file(&mut zip, &entry.file)?;
+        let mut rows = Vec::with_capacity(entry.rows);
+        for (index, line) in content.lines().filter(|l| !l.trim().is_empty()).enumerate() {
+            match serde_json::from_str::<serde_json::Value>(line) {
+                Ok(serde_json::Value::Object(_)) => rows.push(line.to_string()),
+                _ => {
+                    return Err(DomainError::ValidationError(
+                        format!("Line {} of {} is not a JSON object", index + 1, entry.file).into(),
+                    ))
+                }
+            }
+        }
+        if rows.len() != entry.rows {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "{} holds {} rows but the manifest lists {}",
+                    entry.file,
+                    rows.len(),
+                    entry.rows
+                )
+                .into(),
+            ));
+        }
+        tables.push(SnapshotTable {
+            name: entry.name.clone(),
+            rows,
+        });
+    }
+
+    Ok(TenantSnapshot {
+        source_tenant_id: manifest.source_tenant_id,
+        exported_at: manifest.exported_at,
+        tables,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn snapshot() -> TenantSnapshot {
+        TenantSnapshot {
+            source_tenant_id: Uuid::new_v4(),
+            exported_at: Utc::now(),
+            tables: vec![
+                SnapshotTable {
+                    name: "items".to_string(),
+                    rows: vec![
+                        r#"{"id": "7f1c", "sku": "A-1", "cost_price": 12345678901234.5678}"#
+                            .to_string(),
+                        r#"{"id": "8e2d", "sku": "B-2", "metadata": {"line\nbreak": true}}"#
+                            .to_string(),
+                    ],
+                },
+                SnapshotTable {
+                    name: "webhooks".to_string(),
+                    rows: Vec::new(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_archive_round_trips_rows_verbatim() {
+        let snapshot = snapshot();
+        let archive = write_archive(&snapshot).unwrap();
+        assert_eq!(read_archive(&archive).unwrap(), snapshot);
+
+        let mut zip = ZipArchive::new(Cursor::new(archive.as_slice())).unwrap();
+        let names: Vec<_> = zip.file_names().map(str::to_string).collect();
+        assert!(names.contains(&"items.jsonl".to_string()));
+        let mut manifest = String::new();
+        zip.by_name(MANIFEST_FILE_NAME)
+            .unwrap()
+            .read_to_string(&mut manifest)
+            .unwrap();
+        assert!(manifest.contains("\"format_version\": 1"));
+    }
+
+    #[test]
+    fn test_archive_is_checked_against_its_manifest() {
+        let mut snapshot = snapshot();
+        snapshot.tables[0].rows.push("[1, 2]".to_string());
+        let error = read_archive(&write_archive(&snapshot).unwrap()).unwrap_err();
+        assert!(
+            error.to_string().contains("Line 3 of items.jsonl"),
+            "{error}"
+        );
+
+        snapshot.tables[0].name = "users".to_string();
+        let error = read_archive(&write_archive(&snapshot).unwrap()).unwrap_err();
+        assert!(error.to_string().contains("Unknown snapshot table users"));
+
+        assert!(read_archive(b"not a zip").is_err());
+    }
+}
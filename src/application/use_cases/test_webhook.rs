@@ -2,6 +2,8 @@ use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
 use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::domain::services::webhook_repository::WebhookRepository;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::{t, Message};
+use crate::shared::tenant_scope::current_tenant;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -28,12 +30,15 @@ impl<R: WebhookRepository, D: WebhookDispatcher> TestWebhookUseCase<R, D> {
             .webhook_repository
             .get_webhook(webhook_id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Webhook {} not found", webhook_id)))?;
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Webhook {} not found", webhook_id).into())
+            })?;
 
         if webhook.created_by != user_id {
-            return Err(DomainError::BusinessLogicError(
-                "You can only test your own webhooks".to_string(),
-            ));
+            return Err(DomainError::BusinessLogicError(Message::localized(
+                "error.webhook_not_owned",
+                &[],
+            )));
         }
 
         // Create a test event
@@ -42,11 +47,12 @@ impl<R: WebhookRepository, D: WebhookDispatcher> TestWebhookUseCase<R, D> {
             event_type: WebhookEventType::StockMovement,
             payload: serde_json::json!({
                 "test": true,
-                "message": "This is a test webhook delivery",
+                "message": t("webhook.test.message", &[]),
                 "timestamp": chrono::Utc::now().to_rfc3339(),
                 "webhook_id": webhook_id
             }),
             created_at: chrono::Utc::now(),
+            tenant_id: current_tenant(),
         };
 
         // Store the test event
@@ -68,12 +74,12 @@ impl<R: WebhookRepository, D: WebhookDispatcher> TestWebhookUseCase<R, D> {
                     .get_delivery(delivery.id)
                     .await?
                     .ok_or_else(|| {
-                        DomainError::NotFound(format!("Delivery {} not found", delivery.id))
+                        DomainError::NotFound(format!("Delivery {} not found", delivery.id).into())
                     })?;
 
                 Ok(TestWebhookResponse {
                     success: true,
-                    message: "Test webhook delivered successfully".to_string(),
+                    message: t("webhook.test.delivered", &[]),
                     delivery_id: Some(delivery.id),
                     response_status: delivery.response_status,
                     response_body: delivery.response_body,
@@ -82,7 +88,7 @@ impl<R: WebhookRepository, D: WebhookDispatcher> TestWebhookUseCase<R, D> {
             }
             Err(e) => Ok(TestWebhookResponse {
                 success: false,
-                message: format!("Test webhook delivery failed: {}", e),
+                message: t("webhook.test.failed", &[("error", &e)]),
                 delivery_id: Some(delivery.id),
                 response_status: None,
                 response_body: None,
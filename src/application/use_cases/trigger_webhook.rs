@@ -21,8 +21,10 @@ impl<D: WebhookDispatcher> TriggerWebhookUseCase<D> {
 
     pub async fn execute(&self, request: TriggerWebhookRequest) -> Result<(), DomainError> {
         // Parse the event type from string
-        let event_type = serde_json::from_value(serde_json::json!(request.event_type))
-            .map_err(|e| DomainError::ValidationError(format!("Invalid event type: {}", e)))?;
+        let event_type =
+            serde_json::from_value(serde_json::json!(request.event_type)).map_err(|e| {
+                DomainError::ValidationError(format!("Invalid event type: {}", e).into())
+            })?;
 
         // Create webhook event
         let event = WebhookEvent::new(event_type, request.payload);
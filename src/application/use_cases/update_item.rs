@@ -1,7 +1,14 @@
 use crate::domain::entities::item::{Item, UpdateItemRequest as DomainUpdateRequest};
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
 use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
+use crate::shared::etag::entity_etag;
+use crate::shared::i18n::Message;
+use crate::shared::merge_patch;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -14,10 +21,11 @@ pub struct UpdateItemRequest {
     pub category: Option<String>,
     pub unit: Option<String>,
     pub barcode: Option<String>,
-    pub cost_price: Option<f64>,
-    pub sale_price: Option<f64>,
-    pub reorder_point: Option<i32>,
-    pub reorder_qty: Option<i32>,
+    pub cost_price: Option<Decimal>,
+    pub sale_price: Option<Decimal>,
+    pub reorder_point: Option<Decimal>,
+    pub reorder_qty: Option<Decimal>,
+    pub quantity_precision: Option<u32>,
     pub weight: Option<f64>,
     pub dimensions: Option<serde_json::Value>,
     pub metadata: Option<serde_json::Value>,
@@ -30,68 +38,44 @@ pub struct UpdateItemResponse {
     pub sku: String,
     pub name: String,
     pub unit: String,
-    pub cost_price: f64,
+    pub cost_price: Decimal,
     pub active: bool,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub etag: String, // New ETag for the updated item
 }
 
-pub struct UpdateItemUseCase<R: ItemRepository> {
+pub struct UpdateItemUseCase<R: ItemRepository + ?Sized, D: WebhookDispatcher + ?Sized + 'static> {
     item_repository: Arc<R>,
+    webhook_dispatcher: Arc<D>,
 }
 
-impl<R: ItemRepository> UpdateItemUseCase<R> {
-    pub fn new(item_repository: Arc<R>) -> Self {
-        Self { item_repository }
+impl<R: ItemRepository + ?Sized, D: WebhookDispatcher + ?Sized + 'static> UpdateItemUseCase<R, D> {
+    pub fn new(item_repository: Arc<R>, webhook_dispatcher: Arc<D>) -> Self {
+        Self {
+            item_repository,
+            webhook_dispatcher,
+        }
     }
 
     pub async fn execute(
         &self,
         request: UpdateItemRequest,
     ) -> Result<UpdateItemResponse, DomainError> {
-        // Find the existing item
         let mut item = self
-            .item_repository
-            .find_by_id(request.id)
-            .await?
-            .ok_or_else(|| {
-                DomainError::ValidationError(format!("Item with ID {} not found", request.id))
-            })?;
-
-        // Check optimistic concurrency if If-Match header is provided
-        if let Some(if_match) = &request.if_match {
-            let current_etag = Self::generate_etag(&item);
-            if &current_etag != if_match {
-                return Err(DomainError::ValidationError(
-                    "ETag mismatch: item has been modified by another request".to_string(),
-                ));
-            }
-        }
-
-        // Check SKU uniqueness if SKU is being updated
-        if let Some(ref new_sku) = request.sku {
-            if new_sku != &item.sku {
-                let sku_exists = self
-                    .item_repository
-                    .sku_exists(new_sku, Some(item.id))
-                    .await?;
-                if sku_exists {
-                    return Err(DomainError::ValidationError(format!(
-                        "Item with SKU '{}' already exists",
-                        new_sku
-                    )));
-                }
-            }
-        }
+            .find_checked(request.id, request.if_match.as_deref())
+            .await?;
+        self.check_unique(&item, request.sku.as_ref(), request.barcode.as_ref())
+            .await?;
 
         // Parse dimensions if provided
-        let dimensions = if let Some(dimensions_json) = request.dimensions {
-            Some(serde_json::from_value(dimensions_json).map_err(|_| {
-                DomainError::ValidationError("Invalid dimensions format".to_string())
-            })?)
-        } else {
-            None // Don't move item.dimensions yet
-        };
+        let dimensions =
+            if let Some(dimensions_json) = request.dimensions {
+                Some(serde_json::from_value(dimensions_json).map_err(|_| {
+                    DomainError::ValidationError("Invalid dimensions format".into())
+                })?)
+            } else {
+                None // Don't move item.dimensions yet
+            };
 
         // Create update request
         let update_request = DomainUpdateRequest {
@@ -105,6 +89,7 @@ impl<R: ItemRepository> UpdateItemUseCase<R> {
             sale_price: request.sale_price,
             reorder_point: request.reorder_point,
             reorder_qty: request.reorder_qty,
+            quantity_precision: request.quantity_precision,
             weight: request.weight,
             dimensions,
             metadata: request.metadata,
@@ -112,12 +97,96 @@ impl<R: ItemRepository> UpdateItemUseCase<R> {
 
         // Update the item
         item.update(update_request)?;
+        self.save(item).await
+    }
+
+    /// Apply an RFC 7396 JSON Merge Patch to the item's details. Unlike a PUT,
+    /// a patch can clear an optional field by setting it to null.
+    pub async fn patch(
+        &self,
+        id: Uuid,
+        patch: serde_json::Value,
+        if_match: Option<String>,
+    ) -> Result<UpdateItemResponse, DomainError> {
+        let mut item = self.find_checked(id, if_match.as_deref()).await?;
+        let details = merge_patch::apply(&item.details(), &patch)?;
+        self.check_unique(&item, Some(&details.sku), details.barcode.as_ref())
+            .await?;
+        item.replace_details(details)?;
+        self.save(item).await
+    }
+
+    /// The item, as long as the caller's If-Match is still current
+    async fn find_checked(&self, id: Uuid, if_match: Option<&str>) -> Result<Item, DomainError> {
+        let item = self.item_repository.find_by_id(id).await?.ok_or_else(|| {
+            DomainError::NotFound(Message::localized("error.item_not_found", &[("id", &id)]))
+        })?;
+
+        // Check optimistic concurrency if If-Match header is provided
+        if let Some(if_match) = if_match {
+            let current_etag = entity_etag(item.id, item.updated_at);
+            if current_etag != if_match {
+                return Err(DomainError::PreconditionFailed(
+                    "ETag mismatch: item has been modified by another request".into(),
+                ));
+            }
+        }
+        Ok(item)
+    }
+
+    /// Refuse a new SKU or barcode another item already has
+    async fn check_unique(
+        &self,
+        item: &Item,
+        new_sku: Option<&String>,
+        new_barcode: Option<&String>,
+    ) -> Result<(), DomainError> {
+        if let Some(new_sku) = new_sku {
+            if new_sku != &item.sku
+                && self
+                    .item_repository
+                    .sku_exists(new_sku, Some(item.id))
+                    .await?
+            {
+                return Err(DomainError::Conflict(Message::localized(
+                    "error.item_sku_exists",
+                    &[("sku", &new_sku)],
+                )));
+            }
+        }
 
+        if let Some(new_barcode) = new_barcode {
+            if item.barcode.as_ref() != Some(new_barcode)
+                && self
+                    .item_repository
+                    .barcode_exists(new_barcode, Some(item.id))
+                    .await?
+            {
+                return Err(DomainError::Conflict(Message::localized(
+                    "error.item_barcode_exists",
+                    &[("barcode", &new_barcode)],
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    async fn save(&self, item: Item) -> Result<UpdateItemResponse, DomainError> {
         // Save to repository
         self.item_repository.update(&item).await?;
 
+        // Dispatch webhook event (non-blocking)
+        let webhook_event =
+            WebhookEvent::new(WebhookEventType::ItemUpdated, json!({ "item": item }));
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch item updated webhook: {:?}", e);
+            }
+        });
+
         // Generate new ETag
-        let etag = Self::generate_etag(&item);
+        let etag = entity_etag(item.id, item.updated_at);
 
         // Return response
         Ok(UpdateItemResponse {
@@ -131,15 +200,4 @@ impl<R: ItemRepository> UpdateItemUseCase<R> {
             etag,
         })
     }
-
-    // Generate ETag based on item ID and updated_at timestamp
-    fn generate_etag(item: &Item) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        item.id.hash(&mut hasher);
-        item.updated_at.hash(&mut hasher);
-        format!("\"{:x}\"", hasher.finish())
-    }
 }
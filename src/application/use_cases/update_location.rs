@@ -1,9 +1,17 @@
 use crate::domain::entities::location::{
     Location, LocationAddress, LocationType, UpdateLocationRequest,
 };
+use crate::domain::entities::location_capacity::LocationCapacity;
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::address_validator::AddressValidator;
 use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
 use crate::shared::error::DomainError;
+use crate::shared::etag::{check_if_match, entity_etag};
+use crate::shared::i18n::Message;
+use crate::shared::merge_patch;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -13,6 +21,7 @@ pub struct UpdateLocationRequestDto {
     pub code: Option<String>,
     pub address: Option<LocationAddress>,
     pub r#type: Option<String>,
+    pub capacity: Option<LocationCapacity>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,18 +31,32 @@ pub struct UpdateLocationResponse {
     pub code: Option<String>,
     pub r#type: Option<String>,
     pub active: bool,
+    pub capacity: LocationCapacity,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub etag: String,
 }
 
-pub struct UpdateLocationUseCase<R: LocationRepository> {
+pub struct UpdateLocationUseCase<
+    R: LocationRepository + ?Sized,
+    D: WebhookDispatcher + ?Sized + 'static,
+> {
     location_repository: Arc<R>,
+    webhook_dispatcher: Arc<D>,
+    address_validator: Arc<dyn AddressValidator>,
 }
 
-impl<R: LocationRepository> UpdateLocationUseCase<R> {
-    pub fn new(location_repository: Arc<R>) -> Self {
+impl<R: LocationRepository + ?Sized, D: WebhookDispatcher + ?Sized + 'static>
+    UpdateLocationUseCase<R, D>
+{
+    pub fn new(
+        location_repository: Arc<R>,
+        webhook_dispatcher: Arc<D>,
+        address_validator: Arc<dyn AddressValidator>,
+    ) -> Self {
         Self {
             location_repository,
+            webhook_dispatcher,
+            address_validator,
         }
     }
 
@@ -41,45 +64,112 @@ impl<R: LocationRepository> UpdateLocationUseCase<R> {
         &self,
         id: Uuid,
         request: UpdateLocationRequestDto,
+        if_match: Option<String>,
     ) -> Result<UpdateLocationResponse, DomainError> {
-        // Find the existing location
-        let mut location = self
-            .location_repository
-            .find_by_id(id)
-            .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Location with id {} not found", id)))?;
+        let mut location = self.find_checked(id, if_match.as_deref()).await?;
+        self.check_code(&location, request.code.as_ref()).await?;
 
-        // Check if code is being changed and if it conflicts
-        if let Some(ref new_code) = request.code {
-            if location.code.as_ref() != Some(new_code) {
-                let code_exists = self
-                    .location_repository
-                    .code_exists(new_code, Some(id))
-                    .await?;
-                if code_exists {
-                    return Err(DomainError::ValidationError(format!(
-                        "Location with code '{}' already exists",
-                        new_code
-                    )));
-                }
-            }
-        }
+        // Store the address in its normalized form, refusing one that can't be right
+        let address = match request.address {
+            Some(address) => Some(self.address_validator.normalize(address).await?),
+            None => None,
+        };
 
         // Update the location
         let update_request = UpdateLocationRequest {
             name: request.name,
             code: request.code,
-            address: request.address,
+            address,
             r#type: request.r#type,
+            capacity: request.capacity,
         };
 
         location.update(update_request)?;
+        self.save(location).await
+    }
+
+    /// Apply an RFC 7396 JSON Merge Patch to the location's details. Unlike a
+    /// PUT, a patch can clear an optional field by setting it to null.
+    pub async fn patch(
+        &self,
+        id: Uuid,
+        patch: serde_json::Value,
+        if_match: Option<String>,
+    ) -> Result<UpdateLocationResponse, DomainError> {
+        let mut location = self.find_checked(id, if_match.as_deref()).await?;
+        let mut details = merge_patch::apply(&location.details(), &patch)?;
+        self.check_code(&location, details.code.as_ref()).await?;
+
+        // Normalize the address only when the patch touches it, so unrelated
+        // edits don't depend on the address provider
+        if patch.get("address").is_some() {
+            details.address = match details.address {
+                Some(address) => Some(self.address_validator.normalize(address).await?),
+                None => None,
+            };
+        }
+
+        location.replace_details(details)?;
+        self.save(location).await
+    }
 
+    /// The location, as long as the caller's If-Match is still current
+    async fn find_checked(
+        &self,
+        id: Uuid,
+        if_match: Option<&str>,
+    ) -> Result<Location, DomainError> {
+        let location = self
+            .location_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Location with id {} not found", id).into())
+            })?;
+
+        check_if_match(if_match, &entity_etag(location.id, location.updated_at))?;
+        Ok(location)
+    }
+
+    /// Refuse a new code another location already has
+    async fn check_code(
+        &self,
+        location: &Location,
+        new_code: Option<&String>,
+    ) -> Result<(), DomainError> {
+        if let Some(new_code) = new_code {
+            if location.code.as_ref() != Some(new_code)
+                && self
+                    .location_repository
+                    .code_exists(new_code, Some(location.id))
+                    .await?
+            {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.location_code_exists",
+                    &[("code", &new_code)],
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    async fn save(&self, location: Location) -> Result<UpdateLocationResponse, DomainError> {
         // Save to repository
         self.location_repository.update(&location).await?;
 
-        // Generate ETag from updated_at timestamp
-        let etag = format!("\"{}\"", location.updated_at.timestamp());
+        // Dispatch webhook event (non-blocking)
+        let webhook_event = WebhookEvent::new(
+            WebhookEventType::LocationUpdated,
+            json!({ "location": location }),
+        );
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch location updated webhook: {:?}", e);
+            }
+        });
+
+        let etag = entity_etag(location.id, location.updated_at);
 
         Ok(UpdateLocationResponse {
             id: location.id,
@@ -87,6 +177,7 @@ impl<R: LocationRepository> UpdateLocationUseCase<R> {
             code: location.code,
             r#type: location.r#type.map(|t| t.as_str().to_string()),
             active: location.active,
+            capacity: location.capacity,
             updated_at: location.updated_at,
             etag,
         })
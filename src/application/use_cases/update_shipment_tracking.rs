@@ -0,0 +1,60 @@
+use crate::domain::entities::shipment::{Shipment, UpdateShipmentTrackingRequest};
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::shipment_repository::ShipmentRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::shared::error::DomainError;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct UpdateShipmentTrackingUseCase<S: ShipmentRepository, D: WebhookDispatcher + 'static> {
+    shipment_repository: Arc<S>,
+    webhook_dispatcher: Arc<D>,
+}
+
+impl<S: ShipmentRepository, D: WebhookDispatcher + 'static> UpdateShipmentTrackingUseCase<S, D> {
+    pub fn new(shipment_repository: Arc<S>, webhook_dispatcher: Arc<D>) -> Self {
+        Self {
+            shipment_repository,
+            webhook_dispatcher,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        shipment_id: Uuid,
+        request: UpdateShipmentTrackingRequest,
+    ) -> Result<Shipment, DomainError> {
+        if request.carrier.is_none() && request.tracking_number.is_none() {
+            return Err(DomainError::ValidationError(
+                "carrier or tracking_number must be provided".into(),
+            ));
+        }
+
+        let mut shipment = self
+            .shipment_repository
+            .find_by_id(shipment_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Shipment {} not found", shipment_id).into())
+            })?;
+
+        shipment.update_tracking(request.carrier, request.tracking_number);
+        self.shipment_repository.update_tracking(&shipment).await?;
+
+        // Dispatch webhook event (non-blocking)
+        let webhook_event = WebhookEvent::new(
+            WebhookEventType::ShipmentUpdated,
+            json!({ "shipment": shipment }),
+        );
+
+        let dispatcher = Arc::clone(&self.webhook_dispatcher);
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.dispatch_event(&webhook_event).await {
+                eprintln!("Failed to dispatch shipment updated webhook: {:?}", e);
+            }
+        });
+
+        Ok(shipment)
+    }
+}
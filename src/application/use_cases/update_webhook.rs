@@ -1,10 +1,15 @@
-use crate::domain::entities::webhook::{Webhook, WebhookEventType, WebhookStatus};
+use crate::domain::entities::webhook::{validate_url, Webhook, WebhookEventType, WebhookStatus};
 use crate::domain::services::webhook_repository::WebhookRepository;
+use crate::domain::services::webhook_url_policy::WebhookUrlPolicy;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// How long the replaced secret keeps signing deliveries after a rotation
+const SECRET_ROTATION_GRACE_HOURS: i64 = 24;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpdateWebhookRequest {
     pub url: Option<String>,
@@ -27,11 +32,15 @@ pub struct UpdateWebhookResponse {
 
 pub struct UpdateWebhookUseCase<R: WebhookRepository> {
     webhook_repository: Arc<R>,
+    url_policy: Arc<WebhookUrlPolicy>,
 }
 
 impl<R: WebhookRepository> UpdateWebhookUseCase<R> {
-    pub fn new(webhook_repository: Arc<R>) -> Self {
-        Self { webhook_repository }
+    pub fn new(webhook_repository: Arc<R>, url_policy: Arc<WebhookUrlPolicy>) -> Self {
+        Self {
+            webhook_repository,
+            url_policy,
+        }
     }
 
     pub async fn execute(
@@ -43,41 +52,42 @@ impl<R: WebhookRepository> UpdateWebhookUseCase<R> {
         // Get existing webhook
         let webhook_option = self.webhook_repository.get_webhook(webhook_id).await?;
         let mut webhook = webhook_option.ok_or_else(|| {
-            DomainError::NotFound(format!("Webhook with id {} not found", webhook_id))
+            DomainError::NotFound(format!("Webhook with id {} not found", webhook_id).into())
         })?;
 
         // Verify ownership
         if webhook.created_by != user_id {
             return Err(DomainError::BusinessLogicError(
-                "You can only update your own webhooks".to_string(),
+                "You can only update your own webhooks".into(),
             ));
         }
 
         // Validate URL if provided
         if let Some(ref url) = request.url {
-            if !url.starts_with("http://") && !url.starts_with("https://") {
-                return Err(DomainError::ValidationError(
-                    "Webhook URL must start with http:// or https://".to_string(),
-                ));
-            }
+            validate_url(url)?;
+            self.url_policy.check_url(url)?;
             webhook.url = url.clone();
         }
 
         // Validate secret if provided
         if let Some(ref secret) = request.secret {
             if secret.len() < 32 {
-                return Err(DomainError::ValidationError(
-                    "Webhook secret must be at least 32 characters long".to_string(),
-                ));
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.webhook_secret_length",
+                    &[],
+                )));
             }
-            webhook.secret = secret.clone();
+            webhook.rotate_secret(
+                secret.clone(),
+                chrono::Duration::hours(SECRET_ROTATION_GRACE_HOURS),
+            );
         }
 
         // Validate events if provided
         if let Some(ref events) = request.events {
             if events.is_empty() {
                 return Err(DomainError::ValidationError(
-                    "At least one event type must be specified".to_string(),
+                    "At least one event type must be specified".into(),
                 ));
             }
             webhook.events = events.clone();
@@ -87,6 +97,12 @@ impl<R: WebhookRepository> UpdateWebhookUseCase<R> {
 
         // Update status if active flag provided
         if let Some(active) = request.active {
+            // Resuming goes through /resume so the held deliveries are released
+            if webhook.is_paused() {
+                return Err(DomainError::ValidationError(
+                    "Webhook is paused; use /webhooks/{id}/resume to reactivate it".into(),
+                ));
+            }
             webhook.update_status(if active {
                 WebhookStatus::Active
             } else {
@@ -0,0 +1,24 @@
+use crate::domain::entities::address::AddressValidation;
+use crate::domain::entities::location::LocationAddress;
+use crate::domain::services::address_validator::AddressValidator;
+use crate::shared::error::DomainError;
+use std::sync::Arc;
+
+/// Check an address without saving anything, so a form can offer the
+/// normalized version and point at problems before the address is used
+pub struct ValidateAddressUseCase {
+    address_validator: Arc<dyn AddressValidator>,
+}
+
+impl ValidateAddressUseCase {
+    pub fn new(address_validator: Arc<dyn AddressValidator>) -> Self {
+        Self { address_validator }
+    }
+
+    pub async fn execute(
+        &self,
+        address: LocationAddress,
+    ) -> Result<AddressValidation, DomainError> {
+        self.address_validator.validate(&address).await
+    }
+}
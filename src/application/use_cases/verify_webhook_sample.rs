@@ -0,0 +1,95 @@
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
+use crate::domain::services::webhook_dispatcher::delivery_payload;
+use crate::domain::services::webhook_repository::WebhookRepository;
+use crate::domain::services::webhook_signature::{
+    signature_header, DEFAULT_TOLERANCE_SECONDS, SIGNATURE_HEADER,
+};
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::current_tenant;
+use chrono::Utc;
+use serde::Serialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Builds a signed sample delivery without sending it, so webhook consumers can
+/// check their verification code against a known-good signature
+pub struct VerifyWebhookSampleUseCase<R: WebhookRepository> {
+    webhook_repository: Arc<R>,
+}
+
+impl<R: WebhookRepository> VerifyWebhookSampleUseCase<R> {
+    pub fn new(webhook_repository: Arc<R>) -> Self {
+        Self { webhook_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        webhook_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<WebhookSignatureSample, DomainError> {
+        let webhook = self
+            .webhook_repository
+            .get_webhook(webhook_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Webhook {} not found", webhook_id).into())
+            })?;
+
+        if webhook.created_by != user_id {
+            return Err(DomainError::BusinessLogicError(
+                "You can only verify your own webhooks".into(),
+            ));
+        }
+
+        let now = Utc::now();
+        let event = WebhookEvent {
+            id: Uuid::new_v4(),
+            event_type: webhook
+                .events
+                .first()
+                .cloned()
+                .unwrap_or(WebhookEventType::StockMovement),
+            payload: serde_json::json!({
+                "sample": true,
+                "message": "This is a signed sample payload for verification testing",
+                "webhook_id": webhook.id
+            }),
+            created_at: now,
+            tenant_id: current_tenant(),
+        };
+
+        let body = serde_json::to_string(&delivery_payload(&event)).map_err(|e| {
+            DomainError::ValidationError(format!("Failed to serialize payload: {}", e).into())
+        })?;
+        let timestamp = now.timestamp();
+        let secrets = webhook.signing_secrets(now);
+        let signature = signature_header(&secrets, timestamp, body.as_bytes())?;
+
+        Ok(WebhookSignatureSample {
+            header_name: SIGNATURE_HEADER.to_string(),
+            signature,
+            timestamp,
+            signed_content: format!("{}.{}", timestamp, body),
+            body,
+            algorithm: "HMAC-SHA256".to_string(),
+            active_secrets: secrets.len(),
+            tolerance_seconds: DEFAULT_TOLERANCE_SECONDS,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookSignatureSample {
+    pub header_name: String,
+    /// Header value exactly as a real delivery would carry it
+    pub signature: String,
+    pub timestamp: i64,
+    /// Raw request body; signatures cover these exact bytes
+    pub body: String,
+    /// `<timestamp>.<body>`, the string each `v1` HMAC is computed over
+    pub signed_content: String,
+    pub algorithm: String,
+    /// 2 while a rotated-out secret is still in its grace period
+    pub active_secrets: usize,
+    pub tolerance_seconds: i64,
+}
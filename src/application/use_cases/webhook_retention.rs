@@ -0,0 +1,336 @@
+use crate::domain::entities::webhook_retention::{
+    archive_key, to_jsonl, ExpiredRecord, RetentionRun, RetentionRunStatus, RetentionTrigger,
+    UpdateWebhookRetentionRequest, WebhookRetentionSettings,
+};
+use crate::domain::services::blob_storage::BlobStorage;
+use crate::domain::services::webhook_retention_repository::WebhookRetentionRepository;
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::current_tenant;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Rows read, archived and deleted at a time
+const PRUNE_BATCH_SIZE: i64 = 1000;
+
+/// Prunes a tenant's webhook deliveries and events once they outlive the
+/// tenant's retention settings, archiving them to blob storage first when
+/// the tenant asks for it. Each batch is archived before it is deleted, so a
+/// failed run never loses rows, though a retried or overlapping run may
+/// archive some twice.
+pub struct WebhookRetentionUseCase<R: WebhookRetentionRepository> {
+    retention_repository: Arc<R>,
+    blob_storage: Arc<dyn BlobStorage>,
+}
+
+impl<R: WebhookRetentionRepository> WebhookRetentionUseCase<R> {
+    pub fn new(retention_repository: Arc<R>, blob_storage: Arc<dyn BlobStorage>) -> Self {
+        Self {
+            retention_repository,
+            blob_storage,
+        }
+    }
+
+    pub async fn get_settings(&self) -> Result<WebhookRetentionSettings, DomainError> {
+        self.retention_repository.find_settings().await
+    }
+
+    pub async fn update_settings(
+        &self,
+        request: UpdateWebhookRetentionRequest,
+    ) -> Result<WebhookRetentionSettings, DomainError> {
+        let settings = request.apply(self.retention_repository.find_settings().await?);
+        settings.validate()?;
+        self.retention_repository.save_settings(&settings).await?;
+        Ok(settings)
+    }
+
+    /// The tenant's most recent cleanup
+    pub async fn latest_run(&self) -> Result<RetentionRun, DomainError> {
+        self.retention_repository
+            .latest_run()
+            .await?
+            .ok_or_else(|| DomainError::NotFound("No webhook cleanup has run yet".into()))
+    }
+
+    /// Prune the current tenant's expired webhook history. The run is
+    /// recorded whether or not it gets through.
+    pub async fn run(&self, trigger: RetentionTrigger) -> Result<RetentionRun, DomainError> {
+        let tenant_id = current_tenant()
+            .ok_or_else(|| DomainError::ValidationError("Webhook cleanup needs a tenant".into()))?;
+        let settings = self.retention_repository.find_settings().await?;
+        let started_at = Utc::now();
+        let mut run = RetentionRun {
+            id: Uuid::new_v4(),
+            trigger,
+            status: RetentionRunStatus::Succeeded,
+            deliveries_pruned: 0,
+            events_pruned: 0,
+            archive_keys: Vec::new(),
+            error: None,
+            started_at,
+            finished_at: started_at,
+        };
+
+        let result = self.prune(tenant_id, &settings, started_at, &mut run).await;
+        run.finished_at = Utc::now();
+        if let Err(e) = &result {
+            run.status = RetentionRunStatus::Failed;
+            run.error = Some(e.to_string());
+        }
+        self.retention_repository.save_run(&run).await?;
+        result.map(|()| run)
+    }
+
+    async fn prune(
+        &self,
+        tenant_id: Uuid,
+        settings: &WebhookRetentionSettings,
+        now: DateTime<Utc>,
+        run: &mut RetentionRun,
+    ) -> Result<(), DomainError> {
+        // Deliveries first, so events they held on to can go in the same run
+        let cutoffs = settings.delivery_cutoffs(now);
+        loop {
+            let batch = self
+                .retention_repository
+                .expired_deliveries(cutoffs, PRUNE_BATCH_SIZE)
+                .await?;
+            if batch.is_empty() {
+                break;
+            }
+            self.archive(tenant_id, settings, run, "deliveries", &batch)
+                .await?;
+            run.deliveries_pruned += self
+                .retention_repository
+                .delete_deliveries(&ids(&batch))
+                .await? as i64;
+            if (batch.len() as i64) < PRUNE_BATCH_SIZE {
+                break;
+            }
+        }
+
+        let event_cutoff = settings.event_cutoff(now);
+        loop {
+            let batch = self
+                .retention_repository
+                .expired_events(event_cutoff, PRUNE_BATCH_SIZE)
+                .await?;
+            if batch.is_empty() {
+                break;
+            }
+            self.archive(tenant_id, settings, run, "events", &batch)
+                .await?;
+            run.events_pruned += self
+                .retention_repository
+                .delete_events(&ids(&batch))
+                .await? as i64;
+            if (batch.len() as i64) < PRUNE_BATCH_SIZE {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    async fn archive(
+        &self,
+        tenant_id: Uuid,
+        settings: &WebhookRetentionSettings,
+        run: &mut RetentionRun,
+        table: &str,
+        batch: &[ExpiredRecord],
+    ) -> Result<(), DomainError> {
+        if !settings.archive_enabled {
+            return Ok(());
+        }
+        let key = archive_key(tenant_id, run.id, table, run.archive_keys.len() + 1);
+        self.blob_storage
+            .put(&key, "application/x-ndjson", to_jsonl(batch))
+            .await?;
+        run.archive_keys.push(key);
+        Ok(())
+    }
+}
+
+fn ids(batch: &[ExpiredRecord]) -> Vec<Uuid> {
+    batch.iter().map(|record| record.id).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::webhook_retention::DeliveryCutoffs;
+    use crate::shared::tenant_scope::with_tenant;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct InMemoryRetentionRepository {
+        settings: Mutex<WebhookRetentionSettings>,
+        deliveries: Mutex<Vec<ExpiredRecord>>,
+        events: Mutex<Vec<ExpiredRecord>>,
+        runs: Mutex<Vec<RetentionRun>>,
+    }
+
+    #[async_trait]
+    impl WebhookRetentionRepository for InMemoryRetentionRepository {
+        async fn find_settings(&self) -> Result<WebhookRetentionSettings, DomainError> {
+            Ok(self.settings.lock().unwrap().clone())
+        }
+
+        async fn save_settings(
+            &self,
+            settings: &WebhookRetentionSettings,
+        ) -> Result<(), DomainError> {
+            *self.settings.lock().unwrap() = settings.clone();
+            Ok(())
+        }
+
+        async fn claim_due_tenants(&self, _limit: i64) -> Result<Vec<Uuid>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn expired_deliveries(
+            &self,
+            _cutoffs: DeliveryCutoffs,
+            limit: i64,
+        ) -> Result<Vec<ExpiredRecord>, DomainError> {
+            let deliveries = self.deliveries.lock().unwrap();
+            Ok(deliveries.iter().take(limit as usize).cloned().collect())
+        }
+
+        async fn expired_events(
+            &self,
+            _before: DateTime<Utc>,
+            limit: i64,
+        ) -> Result<Vec<ExpiredRecord>, DomainError> {
+            let events = self.events.lock().unwrap();
+            Ok(events.iter().take(limit as usize).cloned().collect())
+        }
+
+        async fn delete_deliveries(&self, ids: &[Uuid]) -> Result<u64, DomainError> {
+            let mut deliveries = self.deliveries.lock().unwrap();
+            let before = deliveries.len();
+            deliveries.retain(|record| !ids.contains(&record.id));
+            Ok((before - deliveries.len()) as u64)
+        }
+
+        async fn delete_events(&self, ids: &[Uuid]) -> Result<u64, DomainError> {
+            let mut events = self.events.lock().unwrap();
+            let before = events.len();
+            events.retain(|record| !ids.contains(&record.id));
+            Ok((before - events.len()) as u64)
+        }
+
+        async fn save_run(&self, run: &RetentionRun) -> Result<(), DomainError> {
+            self.runs.lock().unwrap().push(run.clone());
+            Ok(())
+        }
+
+        async fn latest_run(&self) -> Result<Option<RetentionRun>, DomainError> {
+            Ok(self.runs.lock().unwrap().last().cloned())
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryBlobStorage {
+        objects: Mutex<HashMap<String, Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl BlobStorage for MemoryBlobStorage {
+        async fn put(
+            &self,
+            key: &str,
+            _content_type: &str,
+            data: Vec<u8>,
+        ) -> Result<(), DomainError> {
+            self.objects.lock().unwrap().insert(key.to_string(), data);
+            Ok(())
+        }
+
+        async fn get(&self, key: &str) -> Result<Vec<u8>, DomainError> {
+            self.objects
+                .lock()
+                .unwrap()
+                .get(key)
+                .cloned()
+                .ok_or_else(|| DomainError::NotFound(key.to_string().into()))
+        }
+
+        async fn delete(&self, key: &str) -> Result<(), DomainError> {
+            self.objects.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        async fn signed_url(
+            &self,
+            key: &str,
+            _expires_in: Duration,
+        ) -> Result<String, DomainError> {
+            Ok(key.to_string())
+        }
+    }
+
+    fn records(count: usize) -> Vec<ExpiredRecord> {
+        (0..count)
+            .map(|n| {
+                let id = Uuid::new_v4();
+                ExpiredRecord {
+                    id,
+                    record: json!({ "id": id, "n": n }),
+                }
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_run_archives_each_batch_before_pruning_it() {
+        let repository = Arc::new(InMemoryRetentionRepository::default());
+        *repository.deliveries.lock().unwrap() = records(PRUNE_BATCH_SIZE as usize + 1);
+        *repository.events.lock().unwrap() = records(2);
+        repository.settings.lock().unwrap().archive_enabled = true;
+        let storage = Arc::new(MemoryBlobStorage::default());
+        let use_case = WebhookRetentionUseCase::new(Arc::clone(&repository), storage.clone());
+        let tenant_id = Uuid::new_v4();
+
+        let run = with_tenant(tenant_id, use_case.run(RetentionTrigger::Manual))
+            .await
+            .unwrap();
+
+        assert_eq!(run.status, RetentionRunStatus::Succeeded);
+        assert_eq!(run.deliveries_pruned, PRUNE_BATCH_SIZE + 1);
+        assert_eq!(run.events_pruned, 2);
+        assert_eq!(
+            run.archive_keys,
+            vec![
+                archive_key(tenant_id, run.id, "deliveries", 1),
+                archive_key(tenant_id, run.id, "deliveries", 2),
+                archive_key(tenant_id, run.id, "events", 3),
+            ]
+        );
+        let last_batch = storage.get(&run.archive_keys[1]).await.unwrap();
+        assert_eq!(String::from_utf8(last_batch).unwrap().lines().count(), 1);
+        assert!(repository.deliveries.lock().unwrap().is_empty());
+        assert_eq!(use_case.latest_run().await.unwrap(), run);
+    }
+
+    #[tokio::test]
+    async fn test_run_without_archiving_writes_nothing() {
+        let repository = Arc::new(InMemoryRetentionRepository::default());
+        *repository.events.lock().unwrap() = records(3);
+        let storage = Arc::new(MemoryBlobStorage::default());
+        let use_case = WebhookRetentionUseCase::new(Arc::clone(&repository), storage.clone());
+
+        let run = with_tenant(Uuid::new_v4(), use_case.run(RetentionTrigger::Scheduled))
+            .await
+            .unwrap();
+
+        assert_eq!(run.events_pruned, 3);
+        assert!(run.archive_keys.is_empty());
+        assert!(storage.objects.lock().unwrap().is_empty());
+    }
+}
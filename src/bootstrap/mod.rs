@@ -0,0 +1,1333 @@
+//! Builds the application from its configuration: the repositories and
+//! services over one connection pool, the use cases on top of them, the REST
+//! and gRPC routers, and the background workers. `main` serves what it
+//! returns, and the end-to-end test harness builds the same app over
+//! throwaway containers.
+
+mod router;
+mod state;
+
+pub use state::AppState;
+
+use crate::application::use_cases::{
+    adjust_stock::AdjustStockUseCase,
+    allocate_sales_order::AllocateSalesOrderUseCase,
+    cancel_cycle_count::CancelCycleCountUseCase,
+    cancel_job::CancelJobUseCase,
+    cancel_purchase_order::CancelPurchaseOrderUseCase,
+    cancel_sales_order::CancelSalesOrderUseCase,
+    change_password::ChangePasswordUseCase,
+    change_stock_status::ChangeStockStatusUseCase,
+    cleanup_expired_sandboxes::CleanupExpiredSandboxesUseCase,
+    close_purchase_order::ClosePurchaseOrderUseCase,
+    create_backorder::CreateBackorderUseCase,
+    create_cycle_count::CreateCycleCountUseCase,
+    create_item::CreateItemUseCase,
+    create_location::CreateLocationUseCase,
+    create_purchase_order::CreatePurchaseOrderUseCase,
+    create_reorder_purchase_orders::CreateReorderPurchaseOrdersUseCase,
+    create_return::CreateReturnUseCase,
+    create_sales_order::CreateSalesOrderUseCase,
+    create_sandbox_tenant::CreateSandboxTenantUseCase,
+    create_shipment::CreateShipmentUseCase,
+    create_tenant::CreateTenantUseCase,
+    create_transfer::CreateTransferUseCase,
+    delete_item::DeleteItemUseCase,
+    delete_location::DeleteLocationUseCase,
+    delete_tenant::DeleteTenantUseCase,
+    enqueue_job::EnqueueJobUseCase,
+    exchange_edi_documents::ExchangeEdiDocumentsUseCase,
+    export_stock_movements::ExportStockMovementsUseCase,
+    finalize_cycle_count::FinalizeCycleCountUseCase,
+    forecast_demand::ForecastDemandUseCase,
+    generate_item_barcode::GenerateItemBarcodeUseCase,
+    generate_order_documents::GenerateOrderDocumentsUseCase,
+    generate_shipment_labels::GenerateShipmentLabelsUseCase,
+    get_cycle_count::GetCycleCountUseCase,
+    get_dead_stock_report::GetDeadStockReportUseCase,
+    get_item::GetItemUseCase,
+    get_job_status::GetJobStatusUseCase,
+    get_location::GetLocationUseCase,
+    get_location_utilization_report::GetLocationUtilizationReportUseCase,
+    get_low_stock_report::GetLowStockReportUseCase,
+    get_overdue_purchase_orders_report::GetOverduePurchaseOrdersReportUseCase,
+    get_purchase_order::GetPurchaseOrderUseCase,
+    get_receivables_aging_report::GetReceivablesAgingReportUseCase,
+    get_reorder_suggestions::GetReorderSuggestionsUseCase,
+    get_return::GetReturnUseCase,
+    get_sales_order_allocations::GetSalesOrderAllocationsUseCase,
+    get_scrap_report::GetScrapReportUseCase,
+    get_shipment::GetShipmentUseCase,
+    get_stock_level::GetStockLevelUseCase,
+    get_stock_levels_as_of::GetStockLevelsAsOfUseCase,
+    get_stock_movements::GetStockMovementsUseCase,
+    get_stock_valuation_report::GetStockValuationReportUseCase,
+    get_tenant::GetTenantUseCase,
+    idempotency::IdempotencyUseCase,
+    import_items::ImportItemsUseCase,
+    item_availability::ItemAvailabilityUseCase,
+    list_item_stock_levels::ListItemStockLevelsUseCase,
+    list_items::ListItemsUseCase,
+    list_locations::ListLocationsUseCase,
+    list_stock_levels::ListStockLevelsUseCase,
+    list_tenants::ListTenantsUseCase,
+    login::LoginUseCase,
+    manage_adjustment_reasons::ManageAdjustmentReasonsUseCase,
+    manage_connectors::ManageConnectorsUseCase,
+    manage_credit_settings::ManageCreditSettingsUseCase,
+    manage_currency::ManageCurrencyUseCase,
+    manage_document_settings::ManageDocumentSettingsUseCase,
+    manage_inbound_shipments::ManageInboundShipmentsUseCase,
+    manage_invoices::ManageInvoicesUseCase,
+    manage_item_attachments::ManageItemAttachmentsUseCase,
+    manage_label_printing::ManageLabelPrintingUseCase,
+    manage_products::ManageProductsUseCase,
+    manage_putaway_rules::ManagePutawayRulesUseCase,
+    manage_receiving_settings::ManageReceivingSettingsUseCase,
+    manage_sandbox::ManageSandboxUseCase,
+    manage_sessions::ManageSessionsUseCase,
+    manage_sscc_sequence::ManageSsccSequenceUseCase,
+    manage_tenant_users::ManageTenantUsersUseCase,
+    manage_time_zone::ManageTimeZoneUseCase,
+    manage_trading_partners::ManageTradingPartnersUseCase,
+    manage_two_factor::ManageTwoFactorUseCase,
+    manage_vendor_returns::ManageVendorReturnsUseCase,
+    merge_items::MergeItemsUseCase,
+    mobile_scanning::{ScanToCountUseCase, ScanToPickUseCase, ScanToReceiveUseCase},
+    password_reset::PasswordResetUseCase,
+    process_return::ProcessReturnUseCase,
+    receive_purchase_order::ReceivePurchaseOrderUseCase,
+    receive_transfer::ReceiveTransferUseCase,
+    record_count::RecordCountUseCase,
+    register_user::RegisterUserUseCase,
+    reserve_stock::ReserveStockUseCase,
+    retry_job::RetryJobUseCase,
+    scan_lookup::ScanLookupUseCase,
+    search_use_case::SearchUseCaseImpl,
+    ship_sales_order::ShipSalesOrderUseCase,
+    ship_transfer::ShipTransferUseCase,
+    sync_connector::SyncConnectorUseCase,
+    tenant_snapshot::TenantSnapshotUseCase,
+    update_item::UpdateItemUseCase,
+    update_location::UpdateLocationUseCase,
+    update_shipment_tracking::UpdateShipmentTrackingUseCase,
+    validate_address::ValidateAddressUseCase,
+    webhook_retention::WebhookRetentionUseCase,
+};
+use crate::domain::services::address_validator::AddressValidator;
+use crate::domain::services::blob_storage::BlobStorage;
+use crate::domain::services::connector::ConnectorRegistry;
+use crate::domain::services::currency_service::{CurrencyService, CurrencyServiceImpl};
+use crate::domain::services::email_sender::EmailSender;
+use crate::domain::services::event_broadcaster::EventBroadcaster;
+use crate::domain::services::export_service::ExportServiceImpl;
+use crate::domain::services::export_source::ExportSourceRegistry;
+use crate::domain::services::quota_service::QuotaService;
+use crate::domain::services::sandbox_echo_inbox::SandboxEchoInbox;
+use crate::domain::services::search_repository::SearchRepository;
+use crate::domain::services::time_zone_service::{TimeZoneService, TimeZoneServiceImpl};
+use crate::domain::services::webhook_dispatcher::WebhookDispatcherImpl;
+use crate::infrastructure::config::app_config::{AppConfig, BlobStorageBackend, SearchBackend};
+use crate::infrastructure::controllers::catalog_state::CatalogState;
+use crate::infrastructure::middleware::idempotency::Idempotency;
+use crate::infrastructure::middleware::rate_limit_middleware::RateLimitMiddleware;
+use crate::infrastructure::middleware::tenant_middleware::TenantMiddleware;
+use crate::infrastructure::middleware::usage_metering::UsageMetering;
+use crate::infrastructure::repositories::{
+    meilisearch_search_repository::{MeilisearchConfig, MeilisearchSearchRepository},
+    postgres_adjustment_repository::PostgresAdjustmentRepository,
+    postgres_allocation_repository::PostgresAllocationRepository,
+    postgres_attachment_repository::PostgresAttachmentRepository,
+    postgres_customer_credit_repository::PostgresCustomerCreditRepository,
+    postgres_cycle_count_repository::PostgresCycleCountRepository,
+    postgres_document_settings_repository::PostgresDocumentSettingsRepository,
+    postgres_edi_repository::PostgresEdiRepository,
+    postgres_exchange_rate_repository::PostgresExchangeRateRepository,
+    postgres_forecast_repository::PostgresForecastRepository,
+    postgres_idempotency_repository::PostgresIdempotencyRepository,
+    postgres_inbound_shipment_repository::PostgresInboundShipmentRepository,
+    postgres_integration_repository::PostgresIntegrationRepository,
+    postgres_invitation_repository::PostgresInvitationRepository,
+    postgres_invoice_repository::PostgresInvoiceRepository,
+    postgres_item_availability_repository::PostgresItemAvailabilityRepository,
+    postgres_item_merge_repository::PostgresItemMergeRepository,
+    postgres_item_repository::PostgresItemRepository,
+    postgres_job_repository::PostgresJobRepository,
+    postgres_location_capacity_repository::PostgresLocationCapacityRepository,
+    postgres_location_repository::PostgresLocationRepository,
+    postgres_print_repository::PostgresPrintRepository,
+    postgres_product_repository::PostgresProductRepository,
+    postgres_purchase_order_repository::PostgresPurchaseOrderRepository,
+    postgres_putaway_rule_repository::PostgresPutawayRuleRepository,
+    postgres_receiving_settings_repository::PostgresReceivingSettingsRepository,
+    postgres_reservation_repository::PostgresReservationRepository,
+    postgres_return_repository::PostgresReturnRepository,
+    postgres_sales_order_repository::PostgresSalesOrderRepository,
+    postgres_sandbox_seed_repository::PostgresSandboxSeedRepository,
+    postgres_search_repository::PostgresSearchRepository,
+    postgres_session_repository::PostgresSessionRepository,
+    postgres_shipment_repository::PostgresShipmentRepository,
+    postgres_snapshot_repository::PostgresSnapshotRepository,
+    postgres_stock_repository::PostgresStockRepository,
+    postgres_tenant_audit_repository::PostgresTenantAuditRepository,
+    postgres_tenant_repository::PostgresTenantRepository,
+    postgres_tenant_snapshot_repository::PostgresTenantSnapshotRepository,
+    postgres_transfer_repository::PostgresTransferRepository,
+    postgres_two_factor_repository::PostgresTwoFactorRepository,
+    postgres_user_repository::PostgresUserRepository,
+    postgres_vendor_return_repository::PostgresVendorReturnRepository,
+    postgres_webhook_repository::PostgresWebhookRepository,
+    postgres_webhook_retention_repository::PostgresWebhookRetentionRepository,
+    schema_migrations::{migrations_report, run_migrations},
+    tenant_pool::connect_tenant_pool,
+};
+use crate::infrastructure::services::http_address_validator::{
+    AddressValidationConfig, HttpAddressValidator,
+};
+use crate::infrastructure::services::http_exchange_rate_provider::{
+    ExchangeRateConfig, HttpExchangeRateProvider,
+};
+use crate::infrastructure::services::local_blob_storage::LocalBlobStorage;
+use crate::infrastructure::services::log_email_sender::LogEmailSender;
+use crate::infrastructure::services::offline_address_validator::OfflineAddressValidator;
+use crate::infrastructure::services::postgres_quota_service::PostgresQuotaService;
+use crate::infrastructure::services::s3_blob_storage::{S3BlobStorage, S3Config};
+use crate::infrastructure::services::shopify_connector::{ShopifyConfig, ShopifyConnector};
+use crate::infrastructure::services::smtp_email_sender::{SmtpConfig, SmtpEmailSender};
+use crate::infrastructure::services::{
+    barcode_service_impl::BarcodeServiceImpl, document_renderer_impl::DocumentRendererImpl,
+    job_service_impl::JobServiceImpl, label_renderer_impl::LabelRendererImpl,
+    report_service_impl::ReportServiceImpl, tcp_label_printer::TcpLabelPrinter,
+    x12_translator::X12Translator,
+};
+use crate::presentation::grpc::grpc_router;
+use crate::shared::error::DomainError;
+use axum::Router;
+use std::{env, future::Future, sync::Arc, time::Duration};
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+/// The REST and gRPC apps, with the background workers spawned for them
+pub struct App {
+    pub router: Router,
+    pub grpc: tonic::transport::server::Router,
+    /// Flushed once more after the server has stopped
+    pub usage_metering: Arc<UsageMetering>,
+    /// Cancelling it stops the background workers at their next tick
+    pub shutdown: CancellationToken,
+    /// The background workers, to be drained on shutdown
+    pub background: JoinSet<()>,
+}
+
+/// Builds an [`App`] from an [`AppConfig`]
+pub struct AppBuilder {
+    config: Arc<AppConfig>,
+}
+
+impl AppBuilder {
+    pub fn new(config: Arc<AppConfig>) -> Self {
+        Self { config }
+    }
+
+    /// Connect to the database and bring its schema up to date (or report
+    /// pending migrations when `database.run_migrations` is off), wire the
+    /// repositories, services and use cases over it into the REST and gRPC
+    /// apps, and spawn their background workers
+    pub async fn build(self) -> Result<App, DomainError> {
+        let config = self.config;
+        let pool = Arc::new(connect_tenant_pool(&config.database).await?);
+
+        // Bring the schema up to date before anything queries it
+        if config.database.run_migrations {
+            run_migrations(&pool).await?;
+        } else {
+            match migrations_report(&pool).await {
+                Ok(report) if !report.pending.is_empty() => warn!(
+                    "{} database migrations are pending; set RUN_MIGRATIONS=true to apply them",
+                    report.pending.len()
+                ),
+                Ok(_) => {}
+                Err(e) => warn!("Failed to check database migrations: {}", e),
+            }
+        }
+
+        let shutdown = CancellationToken::new();
+        let mut background = JoinSet::new();
+
+        let user_repository = Arc::new(PostgresUserRepository::new(Arc::clone(&pool)));
+        let invitation_repository = Arc::new(PostgresInvitationRepository::new(Arc::clone(&pool)));
+        let item_repository = Arc::new(PostgresItemRepository::new(Arc::clone(&pool)));
+        let adjustment_repository = Arc::new(PostgresAdjustmentRepository::new(Arc::clone(&pool)));
+        let location_repository = Arc::new(PostgresLocationRepository::new(Arc::clone(&pool)));
+        let purchase_order_repository =
+            Arc::new(PostgresPurchaseOrderRepository::new(Arc::clone(&pool)));
+        let putaway_rule_repository =
+            Arc::new(PostgresPutawayRuleRepository::new(Arc::clone(&pool)));
+        let location_capacity_repository =
+            Arc::new(PostgresLocationCapacityRepository::new(Arc::clone(&pool)));
+        let return_repository = Arc::new(PostgresReturnRepository::new(Arc::clone(&pool)));
+        let sales_order_repository = Arc::new(PostgresSalesOrderRepository::new(Arc::clone(&pool)));
+        let allocation_repository = Arc::new(PostgresAllocationRepository::new(Arc::clone(&pool)));
+        let transfer_repository = Arc::new(PostgresTransferRepository::new(Arc::clone(&pool)));
+        let statement_timeout = config.database.statement_timeout();
+        let stock_repository = Arc::new(
+            PostgresStockRepository::new(Arc::clone(&pool))
+                .with_statement_timeout(statement_timeout),
+        );
+        let snapshot_repository = Arc::new(
+            PostgresSnapshotRepository::new(Arc::clone(&pool))
+                .with_statement_timeout(statement_timeout),
+        );
+        let reservation_repository =
+            Arc::new(PostgresReservationRepository::new(Arc::clone(&pool)));
+        let cycle_count_repository = Arc::new(PostgresCycleCountRepository::new(Arc::clone(&pool)));
+        let shipment_repository = Arc::new(PostgresShipmentRepository::new(Arc::clone(&pool)));
+        let tenant_repository = Arc::new(PostgresTenantRepository::new((*pool).clone()));
+        let attachment_repository = Arc::new(PostgresAttachmentRepository::new(Arc::clone(&pool)));
+        let quota_service: Arc<dyn QuotaService> =
+            Arc::new(PostgresQuotaService::new(Arc::clone(&pool)));
+        // Addresses are checked by an external provider when ADDRESS_VALIDATION_API_URL
+        // is set (see AddressValidationConfig::from_env), otherwise offline only
+        let address_validator: Arc<dyn AddressValidator> = match AddressValidationConfig::from_env()
+        {
+            Some(config) => Arc::new(HttpAddressValidator::new(&config)),
+            None => Arc::new(OfflineAddressValidator),
+        };
+        let exchange_rate_repository =
+            Arc::new(PostgresExchangeRateRepository::new(Arc::clone(&pool)));
+        let currency_service: Arc<dyn CurrencyService> = Arc::new(CurrencyServiceImpl::new(
+            tenant_repository.clone(),
+            exchange_rate_repository.clone(),
+        ));
+        let time_zone_service: Arc<dyn TimeZoneService> =
+            Arc::new(TimeZoneServiceImpl::new(tenant_repository.clone()));
+
+        // Object storage for uploaded files: the s3 backend uses the S3_* settings,
+        // otherwise files are kept on local disk
+        let blob_storage: Arc<dyn BlobStorage> = match config.storage.backend {
+            BlobStorageBackend::S3 => Arc::new(S3BlobStorage::new(
+                S3Config::from_env().expect("Invalid S3 blob storage configuration"),
+            )),
+            BlobStorageBackend::Local => Arc::new(LocalBlobStorage::new(
+                config.storage.path.clone(),
+                // Signs the /blobs download links this backend hands out
+                config.blob_url_secret().to_string(),
+            )),
+        };
+
+        // Search is served from Postgres unless the meilisearch backend is configured;
+        // either way search_indexes records what has been indexed
+        let search_repository: Arc<dyn SearchRepository> = match config.search.backend {
+            SearchBackend::Meilisearch => {
+                let repository = MeilisearchSearchRepository::new(
+                    Arc::clone(&pool),
+                    MeilisearchConfig {
+                        url: config.search.meilisearch_url.clone().unwrap_or_default(),
+                        api_key: config.search.meilisearch_api_key.clone(),
+                        index: config.search.meilisearch_index.clone(),
+                    },
+                );
+                if let Err(e) = repository.ensure_index().await {
+                    tracing::error!("Failed to set up Meilisearch index: {}", e);
+                }
+                Arc::new(repository)
+            }
+            SearchBackend::Postgres => Arc::new(PostgresSearchRepository::new(Arc::clone(&pool))),
+        };
+
+        let webhook_repository = Arc::new(PostgresWebhookRepository::new(Arc::clone(&pool)));
+        let event_broadcaster = Arc::new(EventBroadcaster::default());
+        let webhook_url_policy = Arc::new(config.webhook_url_policy());
+        let webhook_dispatcher = Arc::new(WebhookDispatcherImpl::new(
+            Arc::clone(&webhook_repository),
+            Arc::clone(&event_broadcaster),
+            Arc::clone(&webhook_url_policy),
+        ));
+
+        let get_webhook_deliveries_use_case = Arc::new(
+            crate::application::use_cases::get_webhook_deliveries::GetWebhookDeliveriesUseCase::new(
+                Arc::clone(&webhook_repository),
+            ),
+        );
+        let get_webhook_delivery_details_use_case = Arc::new(crate::application::use_cases::get_webhook_deliveries::GetWebhookDeliveryDetailsUseCase::new(Arc::clone(&webhook_repository)));
+        let test_webhook_use_case = Arc::new(
+            crate::application::use_cases::test_webhook::TestWebhookUseCase::new(
+                Arc::clone(&webhook_repository),
+                Arc::clone(&webhook_dispatcher),
+            ),
+        );
+        let retry_webhook_delivery_use_case = Arc::new(
+            crate::application::use_cases::retry_webhook_delivery::RetryWebhookDeliveryUseCase::new(
+                Arc::clone(&webhook_dispatcher),
+                Arc::clone(&webhook_repository),
+            ),
+        );
+        let list_dlq_deliveries_use_case = Arc::new(
+            crate::application::use_cases::list_dlq_deliveries::ListDlqDeliveriesUseCase::new(
+                Arc::clone(&webhook_repository),
+            ),
+        );
+        let replay_dlq_delivery_use_case = Arc::new(
+            crate::application::use_cases::replay_dlq_delivery::ReplayDlqDeliveryUseCase::new(
+                Arc::clone(&webhook_dispatcher),
+                Arc::clone(&webhook_repository),
+            ),
+        );
+        let get_billing_metrics_use_case = Arc::new(
+            crate::application::use_cases::get_billing_metrics::GetBillingMetricsUseCase::new(
+                Arc::clone(&webhook_repository),
+            ),
+        );
+
+        let jwt_secret = config.jwt_secret().to_string();
+        let session_repository = Arc::new(PostgresSessionRepository::new(Arc::clone(&pool)));
+        let two_factor_repository = Arc::new(PostgresTwoFactorRepository::new(Arc::clone(&pool)));
+        let login_use_case = Arc::new(LoginUseCase::new(
+            Arc::clone(&user_repository),
+            Arc::clone(&session_repository),
+            Arc::clone(&two_factor_repository),
+            Arc::clone(&tenant_repository),
+            jwt_secret.clone(),
+            config.auth.jwt_expiry_hours,
+        ));
+
+        // Account emails go through SMTP when SMTP_HOST is set (see SmtpConfig::from_env),
+        // otherwise they are only logged
+        let email_sender: Arc<dyn EmailSender> = match env::var("SMTP_HOST") {
+            Ok(_) => Arc::new(SmtpEmailSender::new(
+                SmtpConfig::from_env().expect("Invalid SMTP configuration"),
+            )),
+            Err(_) => Arc::new(LogEmailSender),
+        };
+        let password_reset_use_case = Arc::new(PasswordResetUseCase::new(
+            Arc::clone(&user_repository),
+            email_sender,
+            jwt_secret.clone(),
+            chrono::Duration::minutes(config.auth.password_reset_ttl_minutes),
+            config.auth.password_reset_url.clone(),
+        ));
+        let change_password_use_case =
+            Arc::new(ChangePasswordUseCase::new(Arc::clone(&user_repository)));
+        let manage_sessions_use_case =
+            Arc::new(ManageSessionsUseCase::new(Arc::clone(&session_repository)));
+        let manage_two_factor_use_case = Arc::new(ManageTwoFactorUseCase::new(
+            Arc::clone(&user_repository),
+            two_factor_repository,
+            Arc::clone(&tenant_repository),
+            Arc::clone(&session_repository),
+        ));
+
+        let create_item_use_case = Arc::new(CreateItemUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::clone(&webhook_dispatcher),
+            Arc::clone(&quota_service),
+        ));
+        let get_item_use_case = Arc::new(GetItemUseCase::new(Arc::clone(&item_repository)));
+        let update_item_use_case = Arc::new(UpdateItemUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+        let list_items_use_case = Arc::new(ListItemsUseCase::new(Arc::clone(&item_repository)));
+        let delete_item_use_case = Arc::new(DeleteItemUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+        let merge_items_use_case = Arc::new(MergeItemsUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::new(PostgresItemMergeRepository::new(Arc::clone(&pool))),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        let create_location_use_case = Arc::new(CreateLocationUseCase::new(
+            Arc::clone(&location_repository),
+            Arc::clone(&webhook_dispatcher),
+            Arc::clone(&quota_service),
+            Arc::clone(&address_validator),
+        ));
+        let get_location_use_case =
+            Arc::new(GetLocationUseCase::new(Arc::clone(&location_repository)));
+        let update_location_use_case = Arc::new(UpdateLocationUseCase::new(
+            Arc::clone(&location_repository),
+            Arc::clone(&webhook_dispatcher),
+            Arc::clone(&address_validator),
+        ));
+        let validate_address_use_case =
+            Arc::new(ValidateAddressUseCase::new(Arc::clone(&address_validator)));
+        let list_locations_use_case =
+            Arc::new(ListLocationsUseCase::new(Arc::clone(&location_repository)));
+        let delete_location_use_case = Arc::new(DeleteLocationUseCase::new(
+            Arc::clone(&location_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        // Initialize tenant use cases
+        let create_tenant_use_case =
+            Arc::new(CreateTenantUseCase::new(Arc::clone(&tenant_repository)));
+        let sandbox_seed_repository =
+            Arc::new(PostgresSandboxSeedRepository::new(Arc::clone(&pool)));
+        let create_sandbox_tenant_use_case = Arc::new(CreateSandboxTenantUseCase::new(
+            Arc::clone(&tenant_repository),
+            Arc::clone(&sandbox_seed_repository),
+            config.sandbox.seed_profile.clone(),
+            config.sandbox_echo_url(),
+        ));
+        let manage_sandbox_use_case = Arc::new(ManageSandboxUseCase::new(
+            Arc::clone(&tenant_repository),
+            sandbox_seed_repository,
+            Arc::clone(&user_repository),
+            Arc::new(PostgresTenantAuditRepository::new(Arc::clone(&pool))),
+            config.sandbox.seed_profile.clone(),
+            config.sandbox_echo_url(),
+        ));
+        let get_tenant_use_case = Arc::new(GetTenantUseCase::new(Arc::clone(&tenant_repository)));
+        let list_tenants_use_case =
+            Arc::new(ListTenantsUseCase::new(Arc::clone(&tenant_repository)));
+        let delete_tenant_use_case =
+            Arc::new(DeleteTenantUseCase::new(Arc::clone(&tenant_repository)));
+        let cleanup_expired_sandboxes_use_case = Arc::new(CleanupExpiredSandboxesUseCase::new(
+            Arc::clone(&tenant_repository),
+        ));
+        let create_purchase_order_use_case = Arc::new(CreatePurchaseOrderUseCase::new(
+            Arc::clone(&purchase_order_repository),
+            Arc::clone(&currency_service),
+            item_repository.clone(),
+            Arc::clone(&webhook_dispatcher),
+        ));
+        let get_purchase_order_use_case = Arc::new(GetPurchaseOrderUseCase::new(Arc::clone(
+            &purchase_order_repository,
+        )));
+        let cancel_purchase_order_use_case = Arc::new(CancelPurchaseOrderUseCase::new(
+            Arc::clone(&purchase_order_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+        let close_purchase_order_use_case = Arc::new(ClosePurchaseOrderUseCase::new(
+            Arc::clone(&purchase_order_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+        let receiving_settings_repository =
+            Arc::new(PostgresReceivingSettingsRepository::new(Arc::clone(&pool)));
+        let receive_purchase_order_use_case = Arc::new(ReceivePurchaseOrderUseCase::new(
+            Arc::clone(&purchase_order_repository),
+            Arc::clone(&putaway_rule_repository),
+            receiving_settings_repository.clone(),
+            location_capacity_repository.clone(),
+            item_repository.clone(),
+            Arc::clone(&webhook_dispatcher),
+        ));
+        let manage_receiving_settings_use_case = Arc::new(ManageReceivingSettingsUseCase::new(
+            Arc::clone(&receiving_settings_repository),
+        ));
+        let customer_credit_repository =
+            Arc::new(PostgresCustomerCreditRepository::new(Arc::clone(&pool)));
+        let manage_credit_settings_use_case = Arc::new(ManageCreditSettingsUseCase::new(
+            Arc::clone(&customer_credit_repository),
+        ));
+
+        let create_return_use_case = Arc::new(CreateReturnUseCase::new(
+            Arc::clone(&return_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+        let get_return_use_case = Arc::new(GetReturnUseCase::new(Arc::clone(&return_repository)));
+        let process_return_use_case =
+            Arc::new(ProcessReturnUseCase::new(Arc::clone(&return_repository)));
+        let manage_vendor_returns_use_case = Arc::new(ManageVendorReturnsUseCase::new(
+            Arc::new(PostgresVendorReturnRepository::new(Arc::clone(&pool))),
+            Arc::clone(&purchase_order_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+        let invoice_repository = Arc::new(PostgresInvoiceRepository::new(Arc::clone(&pool)));
+        let manage_invoices_use_case = Arc::new(ManageInvoicesUseCase::new(
+            Arc::clone(&invoice_repository),
+            Arc::clone(&sales_order_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+        let get_receivables_aging_report_use_case = Arc::new(
+            GetReceivablesAgingReportUseCase::new(Arc::clone(&invoice_repository)),
+        );
+        let manage_inbound_shipments_use_case = Arc::new(ManageInboundShipmentsUseCase::new(
+            Arc::new(PostgresInboundShipmentRepository::new(Arc::clone(&pool))),
+            Arc::clone(&purchase_order_repository),
+            Arc::clone(&receive_purchase_order_use_case),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        let create_sales_order_use_case = Arc::new(CreateSalesOrderUseCase::new(
+            Arc::clone(&sales_order_repository),
+            Arc::clone(&currency_service),
+            item_repository.clone(),
+            customer_credit_repository.clone(),
+            user_repository.clone(),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        let create_shipment_use_case = Arc::new(CreateShipmentUseCase::new(
+            Arc::clone(&shipment_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        let get_shipment_use_case =
+            Arc::new(GetShipmentUseCase::new(Arc::clone(&shipment_repository)));
+        let generate_shipment_labels_use_case = Arc::new(GenerateShipmentLabelsUseCase::new(
+            Arc::clone(&shipment_repository),
+            Arc::clone(&sales_order_repository),
+            Arc::clone(&transfer_repository),
+            Arc::clone(&location_repository),
+            Arc::new(LabelRendererImpl::new()),
+        ));
+        let manage_sscc_sequence_use_case = Arc::new(ManageSsccSequenceUseCase::new(Arc::clone(
+            &shipment_repository,
+        )));
+
+        let document_settings_repository =
+            Arc::new(PostgresDocumentSettingsRepository::new(Arc::clone(&pool)));
+        let generate_order_documents_use_case = Arc::new(GenerateOrderDocumentsUseCase::new(
+            Arc::clone(&purchase_order_repository),
+            Arc::clone(&sales_order_repository),
+            Arc::clone(&item_repository),
+            Arc::clone(&location_repository),
+            Arc::clone(&document_settings_repository),
+            Arc::new(DocumentRendererImpl::new()),
+            Arc::clone(&blob_storage),
+        ));
+        let manage_currency_use_case = Arc::new(ManageCurrencyUseCase::new(
+            Arc::clone(&tenant_repository),
+            Arc::clone(&exchange_rate_repository),
+            Arc::clone(&currency_service),
+        ));
+        let manage_time_zone_use_case = Arc::new(ManageTimeZoneUseCase::new(
+            Arc::clone(&tenant_repository),
+            Arc::clone(&time_zone_service),
+        ));
+        let manage_document_settings_use_case = Arc::new(ManageDocumentSettingsUseCase::new(
+            document_settings_repository,
+            Arc::clone(&blob_storage),
+        ));
+
+        let edi_repository = Arc::new(PostgresEdiRepository::new(Arc::clone(&pool)));
+        let manage_trading_partners_use_case = Arc::new(ManageTradingPartnersUseCase::new(
+            Arc::clone(&edi_repository),
+        ));
+        let exchange_edi_documents_use_case = Arc::new(ExchangeEdiDocumentsUseCase::new(
+            Arc::clone(&edi_repository),
+            Arc::clone(&item_repository),
+            Arc::clone(&sales_order_repository),
+            Arc::clone(&shipment_repository),
+            Arc::new(X12Translator::new()),
+        ));
+
+        let integration_repository =
+            Arc::new(PostgresIntegrationRepository::new(Arc::clone(&pool)));
+        let connector_registry = Arc::new(
+            ConnectorRegistry::new()
+                .register(Arc::new(ShopifyConnector::new(ShopifyConfig::from_env()))),
+        );
+        let manage_connectors_use_case = Arc::new(ManageConnectorsUseCase::new(
+            Arc::clone(&integration_repository),
+            Arc::clone(&connector_registry),
+        ));
+        let sync_connector_use_case = Arc::new(SyncConnectorUseCase::new(
+            Arc::clone(&integration_repository),
+            Arc::clone(&item_repository),
+            Arc::clone(&sales_order_repository),
+            connector_registry,
+        ));
+
+        let update_shipment_tracking_use_case = Arc::new(UpdateShipmentTrackingUseCase::new(
+            Arc::clone(&shipment_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        let ship_sales_order_use_case = Arc::new(ShipSalesOrderUseCase::new(
+            Arc::clone(&sales_order_repository),
+            Arc::clone(&create_shipment_use_case),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        let allocate_sales_order_use_case = Arc::new(AllocateSalesOrderUseCase::new(
+            Arc::clone(&sales_order_repository),
+            Arc::clone(&allocation_repository),
+        ));
+
+        let get_sales_order_allocations_use_case = Arc::new(GetSalesOrderAllocationsUseCase::new(
+            Arc::clone(&allocation_repository),
+        ));
+
+        let create_backorder_use_case = Arc::new(CreateBackorderUseCase::new(
+            Arc::clone(&sales_order_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        let cancel_sales_order_use_case = Arc::new(CancelSalesOrderUseCase::new(
+            Arc::clone(&sales_order_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        let create_transfer_use_case = Arc::new(CreateTransferUseCase::new(
+            Arc::clone(&transfer_repository),
+            item_repository.clone(),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        let receive_transfer_use_case = Arc::new(ReceiveTransferUseCase::new(
+            Arc::clone(&transfer_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        let ship_transfer_use_case = Arc::new(ShipTransferUseCase::new(
+            Arc::clone(&transfer_repository),
+            Arc::clone(&create_shipment_use_case),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        let create_cycle_count_use_case = Arc::new(CreateCycleCountUseCase::new(
+            Arc::clone(&cycle_count_repository),
+            Arc::clone(&stock_repository),
+        ));
+
+        let get_cycle_count_use_case = Arc::new(GetCycleCountUseCase::new(Arc::clone(
+            &cycle_count_repository,
+        )));
+
+        let record_count_use_case = Arc::new(RecordCountUseCase::new(
+            Arc::clone(&cycle_count_repository),
+            item_repository.clone(),
+        ));
+
+        let cancel_cycle_count_use_case = Arc::new(CancelCycleCountUseCase::new(Arc::clone(
+            &cycle_count_repository,
+        )));
+
+        let finalize_cycle_count_use_case = Arc::new(FinalizeCycleCountUseCase::new(
+            Arc::clone(&cycle_count_repository),
+            Arc::clone(&stock_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+
+        let search_use_case = Arc::new(SearchUseCaseImpl::new(Arc::clone(&search_repository)));
+
+        let get_stock_level_use_case = Arc::new(GetStockLevelUseCase::new(
+            Arc::clone(&stock_repository),
+            Arc::clone(&item_repository),
+            Arc::clone(&location_repository),
+        ));
+        let list_item_stock_levels_use_case = Arc::new(ListItemStockLevelsUseCase::new(
+            Arc::clone(&stock_repository),
+            Arc::clone(&item_repository),
+            Arc::clone(&location_repository),
+        ));
+        let list_stock_levels_use_case =
+            Arc::new(ListStockLevelsUseCase::new(Arc::clone(&stock_repository)));
+        let get_stock_movements_use_case = Arc::new(GetStockMovementsUseCase::new(
+            Arc::clone(&stock_repository),
+            Arc::clone(&item_repository),
+            Arc::clone(&location_repository),
+        ));
+        let export_stock_movements_use_case = Arc::new(ExportStockMovementsUseCase::new(
+            Arc::clone(&stock_repository),
+        ));
+        let manage_item_attachments_use_case = Arc::new(ManageItemAttachmentsUseCase::new(
+            Arc::clone(&attachment_repository),
+            Arc::clone(&item_repository),
+            Arc::clone(&blob_storage),
+        ));
+        let get_stock_levels_as_of_use_case = Arc::new(GetStockLevelsAsOfUseCase::new(
+            Arc::clone(&snapshot_repository),
+            Arc::clone(&time_zone_service),
+        ));
+        let adjust_stock_use_case = Arc::new(AdjustStockUseCase::new(
+            Arc::clone(&stock_repository),
+            Arc::clone(&item_repository),
+            Arc::clone(&adjustment_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+        let manage_adjustment_reasons_use_case = Arc::new(ManageAdjustmentReasonsUseCase::new(
+            Arc::clone(&adjustment_repository),
+        ));
+        let change_stock_status_use_case = Arc::new(ChangeStockStatusUseCase::new(
+            Arc::clone(&stock_repository),
+            Arc::clone(&webhook_dispatcher),
+        ));
+        let reserve_stock_use_case = Arc::new(ReserveStockUseCase::new(
+            Arc::clone(&reservation_repository),
+            item_repository.clone(),
+        ));
+
+        let barcode_service = Arc::new(BarcodeServiceImpl::new());
+        let generate_item_barcode_use_case = Arc::new(GenerateItemBarcodeUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::clone(&barcode_service),
+        ));
+        // Tenant user invitations; tokens stay redeemable for auth.invitation_ttl_hours
+        let manage_tenant_users_use_case = Arc::new(ManageTenantUsersUseCase::new(
+            Arc::clone(&user_repository),
+            Arc::clone(&invitation_repository),
+            Arc::clone(&session_repository),
+            chrono::Duration::hours(config.auth.invitation_ttl_hours),
+        ));
+        let register_user_use_case = Arc::new(RegisterUserUseCase::new(
+            Arc::clone(&user_repository),
+            Arc::clone(&invitation_repository),
+        ));
+        let manage_putaway_rules_use_case = Arc::new(ManagePutawayRulesUseCase::new(
+            Arc::clone(&putaway_rule_repository),
+            Arc::clone(&location_repository),
+        ));
+        let manage_products_use_case = Arc::new(ManageProductsUseCase::new(
+            Arc::new(PostgresProductRepository::new(Arc::clone(&pool))),
+            Arc::clone(&item_repository),
+            Arc::clone(&create_item_use_case),
+        ));
+        let scan_lookup_use_case = Arc::new(ScanLookupUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::clone(&stock_repository),
+        ));
+        // One-round-trip receive, pick and count for RF scanners
+        let scan_to_receive_use_case = Arc::new(ScanToReceiveUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::clone(&location_repository),
+            Arc::clone(&purchase_order_repository),
+            Arc::clone(&receive_purchase_order_use_case),
+        ));
+        let scan_to_pick_use_case = Arc::new(ScanToPickUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::clone(&location_repository),
+            Arc::clone(&sales_order_repository),
+            Arc::clone(&ship_sales_order_use_case),
+        ));
+        let scan_to_count_use_case = Arc::new(ScanToCountUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::clone(&location_repository),
+            Arc::clone(&cycle_count_repository),
+            Arc::clone(&record_count_use_case),
+        ));
+
+        // Initialize report service and use cases
+        let report_service = Arc::new(ReportServiceImpl::new(
+            Arc::clone(&item_repository),
+            Arc::clone(&stock_repository),
+            purchase_order_repository.clone(),
+        ));
+        let get_low_stock_report_use_case = Arc::new(GetLowStockReportUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::clone(&stock_repository),
+            Arc::clone(&report_service),
+        ));
+        let get_stock_valuation_report_use_case = Arc::new(GetStockValuationReportUseCase::new(
+            Arc::clone(&report_service),
+        ));
+        let forecast_repository = Arc::new(PostgresForecastRepository::new(Arc::clone(&pool)));
+        let get_reorder_suggestions_use_case = Arc::new(GetReorderSuggestionsUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::clone(&stock_repository),
+            forecast_repository.clone(),
+            Arc::clone(&time_zone_service),
+        ));
+        let forecast_demand_use_case = Arc::new(ForecastDemandUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::clone(&forecast_repository),
+            Arc::clone(&time_zone_service),
+        ));
+        let item_availability_use_case = Arc::new(ItemAvailabilityUseCase::new(
+            Arc::clone(&item_repository),
+            Arc::new(PostgresItemAvailabilityRepository::new(Arc::clone(&pool))),
+        ));
+        let get_scrap_report_use_case = Arc::new(GetScrapReportUseCase::new(
+            Arc::clone(&return_repository),
+            Arc::clone(&time_zone_service),
+        ));
+        let get_dead_stock_report_use_case = Arc::new(GetDeadStockReportUseCase::new(Arc::clone(
+            &stock_repository,
+        )));
+        let get_overdue_purchase_orders_report_use_case = Arc::new(
+            GetOverduePurchaseOrdersReportUseCase::new(Arc::clone(&purchase_order_repository)),
+        );
+        let get_location_utilization_report_use_case = Arc::new(
+            GetLocationUtilizationReportUseCase::new(Arc::clone(&location_capacity_repository)),
+        );
+        let create_reorder_purchase_orders_use_case =
+            Arc::new(CreateReorderPurchaseOrdersUseCase::new(
+                Arc::clone(&get_reorder_suggestions_use_case),
+                Arc::clone(&create_purchase_order_use_case),
+            ));
+
+        // Initialize job repository and service
+        let job_repository = Arc::new(PostgresJobRepository::new(Arc::clone(&pool)));
+        let job_service = Arc::new(JobServiceImpl::new(Arc::clone(&job_repository)));
+        let enqueue_job_use_case = Arc::new(EnqueueJobUseCase::new(Arc::clone(&job_service)));
+        let get_job_status_use_case = Arc::new(GetJobStatusUseCase::new(Arc::clone(&job_service)));
+        let cancel_job_use_case = Arc::new(CancelJobUseCase::new(Arc::clone(&job_service)));
+        let retry_job_use_case = Arc::new(RetryJobUseCase::new(Arc::clone(&job_service)));
+        let import_items_use_case = Arc::new(ImportItemsUseCase::new(
+            Arc::clone(&create_item_use_case),
+            Arc::clone(&job_service),
+            Arc::clone(&blob_storage),
+        ));
+        let manage_label_printing_use_case = Arc::new(ManageLabelPrintingUseCase::new(
+            Arc::new(PostgresPrintRepository::new(Arc::clone(&pool))),
+            Arc::clone(&location_repository),
+            Arc::clone(&job_service),
+            Arc::new(TcpLabelPrinter::new()),
+        ));
+
+        // Initialize export service
+        // Reports and collections that can be exported as files
+        let export_sources = {
+            use crate::infrastructure::services::export_sources::{
+                DeadStockExportSource, ItemsExportSource, LocationsExportSource,
+                LowStockExportSource, PurchaseOrdersExportSource, ReorderSuggestionsExportSource,
+                SalesOrdersExportSource, ScrapExportSource, StockLevelsExportSource,
+                StockValuationExportSource,
+            };
+            Arc::new(
+                ExportSourceRegistry::new()
+                    .register(Arc::new(LowStockExportSource::new(Arc::clone(
+                        &report_service,
+                    ))))
+                    .register(Arc::new(StockValuationExportSource::new(Arc::clone(
+                        &report_service,
+                    ))))
+                    .register(Arc::new(ScrapExportSource::new(Arc::clone(
+                        &get_scrap_report_use_case,
+                    ))))
+                    .register(Arc::new(DeadStockExportSource::new(Arc::clone(
+                        &get_dead_stock_report_use_case,
+                    ))))
+                    .register(Arc::new(ReorderSuggestionsExportSource::new(Arc::clone(
+                        &get_reorder_suggestions_use_case,
+                    ))))
+                    .register(Arc::new(ItemsExportSource::new(Arc::clone(
+                        &item_repository,
+                    ))))
+                    .register(Arc::new(LocationsExportSource::new(Arc::clone(
+                        &location_repository,
+                    ))))
+                    .register(Arc::new(StockLevelsExportSource::new(Arc::clone(
+                        &stock_repository,
+                    ))))
+                    .register(Arc::new(SalesOrdersExportSource::new(Arc::clone(
+                        &sales_order_repository,
+                    ))))
+                    .register(Arc::new(PurchaseOrdersExportSource::new(Arc::clone(
+                        &purchase_order_repository,
+                    )))),
+            )
+        };
+        let export_service = Arc::new(ExportServiceImpl::new(
+            Arc::clone(&job_service),
+            Arc::clone(&stock_repository),
+            Arc::clone(&export_sources),
+            Arc::clone(&blob_storage),
+            std::time::Duration::from_secs(config.storage.export_url_expiry_secs),
+        ));
+        let tenant_snapshot_use_case = Arc::new(TenantSnapshotUseCase::new(
+            Arc::new(PostgresTenantSnapshotRepository::new(Arc::clone(&pool))),
+            Arc::clone(&job_service),
+            Arc::clone(&blob_storage),
+            std::time::Duration::from_secs(config.storage.export_url_expiry_secs),
+        ));
+
+        // Initialize rate limiting middleware
+        let redis_url = &config.redis.url;
+        let rate_limit_middleware = Arc::new(
+            RateLimitMiddleware::new(redis_url, Arc::clone(&quota_service))
+                .expect("Failed to create rate limit middleware"),
+        );
+
+        // Per-tenant API call metering, flushed to Postgres for billing (flusher spawned below)
+        let usage_metering = Arc::new(
+            UsageMetering::new(redis_url, Arc::clone(&pool), Arc::clone(&quota_service))
+                .expect("Failed to create usage metering"),
+        );
+
+        // Idempotency-Key support for POST/PUT/PATCH requests
+        let idempotency_repository =
+            Arc::new(PostgresIdempotencyRepository::new(Arc::clone(&pool)));
+        let idempotency = Arc::new(Idempotency::new(
+            Arc::clone(&idempotency_repository),
+            config.idempotency.ttl_secs,
+            config.body_limits.json_bytes,
+            config.body_limits.largest(),
+        ));
+        let idempotency_use_case =
+            Arc::new(IdempotencyUseCase::new(Arc::clone(&idempotency_repository)));
+
+        // Initialize tenant middleware
+        let tenant_middleware = Arc::new(TenantMiddleware::new(
+            jwt_secret,
+            config.auth.allow_tenant_header,
+            Arc::clone(&tenant_repository)
+                as Arc<dyn crate::domain::services::tenant_repository::TenantRepository>,
+            Arc::clone(&session_repository)
+                as Arc<dyn crate::domain::services::session_repository::SessionRepository>,
+        ));
+
+        // Background worker that sends due webhook deliveries and retries (spawned below)
+        let webhook_worker =
+            crate::infrastructure::services::webhook_worker::WebhookDeliveryWorker::new(
+                Arc::clone(&webhook_repository),
+                Arc::clone(&webhook_dispatcher),
+                crate::infrastructure::services::webhook_worker::WebhookWorkerConfig::from_env(),
+            );
+
+        // Nightly stock level snapshots for historical lookups (spawned below)
+        let stock_snapshot_worker =
+            crate::infrastructure::services::stock_snapshot_worker::StockSnapshotWorker::from_env(
+                Arc::clone(&snapshot_repository),
+            );
+
+        // Daily exchange rates for orders priced in other currencies (spawned below)
+        let exchange_rate_config = ExchangeRateConfig::from_env();
+        let exchange_rate_worker =
+            crate::infrastructure::services::exchange_rate_worker::ExchangeRateWorker::new(
+                Arc::clone(&exchange_rate_repository),
+                Arc::new(HttpExchangeRateProvider::new(&exchange_rate_config)),
+                exchange_rate_config.base_currency,
+                exchange_rate_config.fetch_hour_utc,
+            );
+
+        // Pulls channel orders and pushes stock for connectors that are due (spawned below)
+        let integration_sync_worker =
+            crate::infrastructure::services::integration_sync_worker::IntegrationSyncWorker::from_env(
+                Arc::clone(&integration_repository),
+                Arc::clone(&sync_connector_use_case),
+            );
+
+        // Prunes and archives webhook history past each tenant's retention; the
+        // worker queues a job per tenant daily (spawned below)
+        let webhook_retention_repository =
+            Arc::new(PostgresWebhookRetentionRepository::new(Arc::clone(&pool)));
+        let webhook_retention_use_case = Arc::new(WebhookRetentionUseCase::new(
+            Arc::clone(&webhook_retention_repository),
+            Arc::clone(&blob_storage),
+        ));
+        let webhook_retention_worker =
+            crate::infrastructure::services::webhook_retention_worker::WebhookRetentionWorker::from_env(
+                webhook_retention_repository,
+                Arc::clone(&job_service),
+            );
+
+        // Keeps search documents in step with item, location and stock changes (spawned below)
+        let search_index_worker =
+            crate::infrastructure::services::search_index_worker::SearchIndexWorker::from_env(
+                Arc::clone(&search_repository),
+                &event_broadcaster,
+            );
+
+        // Background worker that runs queued jobs through their registered handlers (spawned below)
+        let job_worker = {
+            use crate::infrastructure::services::job_handlers::{
+                GenerateReportJobHandler, ImportItemsJobHandler, PrintLabelsJobHandler,
+                RebuildItemAvailabilityJobHandler, RebuildSearchIndexJobHandler,
+                StockCsvExportJobHandler, TenantSnapshotExportJobHandler,
+                TenantSnapshotImportJobHandler, WebhookRetentionJobHandler,
+                GENERATE_REPORT_JOB_TYPE, PRINT_LABELS_JOB_TYPE,
+                REBUILD_ITEM_AVAILABILITY_JOB_TYPE, REBUILD_SEARCH_INDEX_JOB_TYPE,
+                TENANT_SNAPSHOT_EXPORT_JOB_TYPE, TENANT_SNAPSHOT_IMPORT_JOB_TYPE,
+                WEBHOOK_RETENTION_JOB_TYPE,
+            };
+            use crate::infrastructure::services::job_worker::{
+                JobHandlerRegistry, JobWorker, JobWorkerConfig,
+            };
+
+            let registry = JobHandlerRegistry::new()
+                .register(
+                    crate::domain::entities::export::STOCK_CSV_EXPORT_JOB_TYPE,
+                    Arc::new(StockCsvExportJobHandler::new(Arc::clone(&export_service))),
+                )
+                .register(
+                    crate::application::use_cases::import_items::IMPORT_ITEMS_JOB_TYPE,
+                    Arc::new(ImportItemsJobHandler::new(Arc::clone(
+                        &import_items_use_case,
+                    ))),
+                )
+                .register(
+                    REBUILD_SEARCH_INDEX_JOB_TYPE,
+                    Arc::new(RebuildSearchIndexJobHandler::new(Arc::clone(
+                        &search_use_case,
+                    ))),
+                )
+                .register(
+                    REBUILD_ITEM_AVAILABILITY_JOB_TYPE,
+                    Arc::new(RebuildItemAvailabilityJobHandler::new(Arc::clone(
+                        &item_availability_use_case,
+                    ))),
+                )
+                .register(
+                    PRINT_LABELS_JOB_TYPE,
+                    Arc::new(PrintLabelsJobHandler::new(Arc::clone(
+                        &manage_label_printing_use_case,
+                    ))),
+                )
+                .register(
+                    WEBHOOK_RETENTION_JOB_TYPE,
+                    Arc::new(WebhookRetentionJobHandler::new(Arc::clone(
+                        &webhook_retention_use_case,
+                    ))),
+                )
+                .register(
+                    GENERATE_REPORT_JOB_TYPE,
+                    Arc::new(GenerateReportJobHandler::new(
+                        export_sources,
+                        Arc::clone(&blob_storage),
+                        std::time::Duration::from_secs(config.storage.export_url_expiry_secs),
+                    )),
+                )
+                .register(
+                    TENANT_SNAPSHOT_EXPORT_JOB_TYPE,
+                    Arc::new(TenantSnapshotExportJobHandler::new(Arc::clone(
+                        &tenant_snapshot_use_case,
+                    ))),
+                )
+                .register(
+                    TENANT_SNAPSHOT_IMPORT_JOB_TYPE,
+                    Arc::new(TenantSnapshotImportJobHandler::new(Arc::clone(
+                        &tenant_snapshot_use_case,
+                    ))),
+                );
+            JobWorker::new(
+                Arc::clone(&job_service),
+                registry,
+                JobWorkerConfig::from_env(),
+            )
+        };
+
+        let app_state = AppState {
+            config: Arc::clone(&config),
+            pool: Arc::clone(&pool),
+            user_repository: Arc::clone(&user_repository),
+            item_repository: Arc::clone(&item_repository),
+            location_repository: Arc::clone(&location_repository),
+            purchase_order_repository: Arc::clone(&purchase_order_repository),
+            putaway_rule_repository: Arc::clone(&putaway_rule_repository),
+            return_repository: Arc::clone(&return_repository),
+            sales_order_repository: Arc::clone(&sales_order_repository),
+            allocation_repository: Arc::clone(&allocation_repository),
+            transfer_repository: Arc::clone(&transfer_repository),
+            stock_repository: Arc::clone(&stock_repository),
+            reservation_repository: Arc::clone(&reservation_repository),
+            cycle_count_repository: Arc::clone(&cycle_count_repository),
+            shipment_repository: Arc::clone(&shipment_repository),
+            search_repository: Arc::clone(&search_repository),
+            tenant_repository: Arc::clone(&tenant_repository),
+            quota_service: Arc::clone(&quota_service),
+            catalog: CatalogState {
+                item_repository: item_repository.clone(),
+                location_repository: location_repository.clone(),
+                webhook_dispatcher: webhook_dispatcher.clone(),
+                quota_service,
+                address_validator,
+            },
+            rate_limit_middleware: Arc::clone(&rate_limit_middleware),
+            tenant_middleware: Arc::clone(&tenant_middleware),
+            login_use_case,
+            manage_sessions_use_case,
+            manage_two_factor_use_case,
+            password_reset_use_case,
+            change_password_use_case,
+            create_item_use_case,
+            get_item_use_case,
+            update_item_use_case,
+            list_items_use_case,
+            delete_item_use_case,
+            import_items_use_case,
+            merge_items_use_case,
+            create_location_use_case,
+            get_location_use_case,
+            update_location_use_case,
+            list_locations_use_case,
+            delete_location_use_case,
+            validate_address_use_case,
+            create_purchase_order_use_case,
+            get_purchase_order_use_case,
+            cancel_purchase_order_use_case,
+            close_purchase_order_use_case,
+            receive_purchase_order_use_case,
+            create_return_use_case,
+            get_return_use_case,
+            process_return_use_case,
+            create_sales_order_use_case,
+            ship_sales_order_use_case,
+            create_backorder_use_case,
+            cancel_sales_order_use_case,
+            allocate_sales_order_use_case,
+            get_sales_order_allocations_use_case,
+            create_transfer_use_case,
+            receive_transfer_use_case,
+            ship_transfer_use_case,
+            get_shipment_use_case,
+            generate_shipment_labels_use_case,
+            manage_sscc_sequence_use_case,
+            generate_order_documents_use_case,
+            manage_document_settings_use_case,
+            manage_receiving_settings_use_case,
+            manage_credit_settings_use_case,
+            webhook_retention_use_case,
+            manage_currency_use_case,
+            manage_time_zone_use_case,
+            manage_trading_partners_use_case,
+            exchange_edi_documents_use_case,
+            manage_connectors_use_case,
+            sync_connector_use_case,
+            update_shipment_tracking_use_case,
+            create_cycle_count_use_case,
+            get_cycle_count_use_case,
+            record_count_use_case,
+            cancel_cycle_count_use_case,
+            finalize_cycle_count_use_case,
+            search_use_case,
+            get_stock_level_use_case,
+            list_item_stock_levels_use_case,
+            get_stock_movements_use_case,
+            export_stock_movements_use_case,
+            list_stock_levels_use_case,
+            get_stock_levels_as_of_use_case,
+            manage_item_attachments_use_case,
+            blob_storage: Arc::clone(&blob_storage),
+            adjust_stock_use_case,
+            manage_adjustment_reasons_use_case,
+            change_stock_status_use_case,
+            reserve_stock_use_case,
+            generate_item_barcode_use_case,
+            forecast_demand_use_case,
+            item_availability_use_case,
+            manage_putaway_rules_use_case,
+            manage_label_printing_use_case,
+            manage_products_use_case,
+            scan_lookup_use_case,
+            scan_to_receive_use_case,
+            scan_to_pick_use_case,
+            scan_to_count_use_case,
+            manage_tenant_users_use_case,
+            manage_vendor_returns_use_case,
+            manage_invoices_use_case,
+            manage_inbound_shipments_use_case,
+            register_user_use_case,
+            webhook_repository,
+            webhook_dispatcher,
+            webhook_url_policy,
+            event_broadcaster,
+            sandbox_echo_inbox: SandboxEchoInbox::default(),
+            get_webhook_deliveries_use_case: Arc::clone(&get_webhook_deliveries_use_case),
+            get_webhook_delivery_details_use_case: Arc::clone(
+                &get_webhook_delivery_details_use_case,
+            ),
+            test_webhook_use_case: Arc::clone(&test_webhook_use_case),
+            retry_webhook_delivery_use_case: Arc::clone(&retry_webhook_delivery_use_case),
+            list_dlq_deliveries_use_case: Arc::clone(&list_dlq_deliveries_use_case),
+            replay_dlq_delivery_use_case: Arc::clone(&replay_dlq_delivery_use_case),
+            get_billing_metrics_use_case: Arc::clone(&get_billing_metrics_use_case),
+            create_tenant_use_case: Arc::clone(&create_tenant_use_case),
+            create_sandbox_tenant_use_case: Arc::clone(&create_sandbox_tenant_use_case),
+            manage_sandbox_use_case,
+            get_tenant_use_case: Arc::clone(&get_tenant_use_case),
+            list_tenants_use_case: Arc::clone(&list_tenants_use_case),
+            delete_tenant_use_case: Arc::clone(&delete_tenant_use_case),
+            cleanup_expired_sandboxes_use_case: Arc::clone(&cleanup_expired_sandboxes_use_case),
+            report_service,
+            get_low_stock_report_use_case,
+            get_stock_valuation_report_use_case,
+            get_reorder_suggestions_use_case,
+            get_scrap_report_use_case,
+            get_dead_stock_report_use_case,
+            get_overdue_purchase_orders_report_use_case,
+            get_receivables_aging_report_use_case,
+            get_location_utilization_report_use_case,
+            create_reorder_purchase_orders_use_case,
+            job_repository: Arc::clone(&job_repository),
+            job_service: Arc::clone(&job_service),
+            enqueue_job_use_case: Arc::clone(&enqueue_job_use_case),
+            get_job_status_use_case: Arc::clone(&get_job_status_use_case),
+            cancel_job_use_case: Arc::clone(&cancel_job_use_case),
+            retry_job_use_case: Arc::clone(&retry_job_use_case),
+            export_service: Arc::clone(&export_service),
+            tenant_snapshot_use_case,
+        };
+
+        // Served on its own port by the same use cases as the REST routes
+        let grpc = grpc_router(&app_state);
+
+        let app = router::build_router(app_state, idempotency, Arc::clone(&usage_metering));
+
+        // Expired sandboxes are removed hourly
+        spawn_periodic(
+            &mut background,
+            &shutdown,
+            Duration::from_secs(3600),
+            "sandbox cleanup",
+            move || {
+                let use_case = Arc::clone(&cleanup_expired_sandboxes_use_case);
+                async move { use_case.execute().await }
+            },
+        );
+
+        // Drop expired idempotency keys
+        spawn_periodic(
+            &mut background,
+            &shutdown,
+            Duration::from_secs(3600),
+            "idempotency key cleanup",
+            move || {
+                let use_case = Arc::clone(&idempotency_use_case);
+                async move { use_case.cleanup_expired_keys().await }
+            },
+        );
+
+        // Write metered API calls to Postgres
+        let flusher = Arc::clone(&usage_metering);
+        spawn_periodic(
+            &mut background,
+            &shutdown,
+            Duration::from_secs(config.usage.flush_interval_secs),
+            "API usage flush",
+            move || {
+                let flusher = Arc::clone(&flusher);
+                async move { flusher.flush().await }
+            },
+        );
+
+        background.spawn(webhook_worker.run(shutdown.clone()));
+        background.spawn(stock_snapshot_worker.run(shutdown.clone()));
+        background.spawn(exchange_rate_worker.run(shutdown.clone()));
+        background.spawn(job_worker.run(shutdown.clone()));
+        background.spawn(integration_sync_worker.run(shutdown.clone()));
+        background.spawn(search_index_worker.run(shutdown.clone()));
+        background.spawn(webhook_retention_worker.run(shutdown.clone()));
+
+        Ok(App {
+            router: app,
+            grpc,
+            usage_metering,
+            shutdown,
+            background,
+        })
+    }
+}
+
+/// Run `task` every `period`, starting straight away, until `shutdown` is
+/// cancelled, logging the runs that fail
+fn spawn_periodic<F, Fut, T, E>(
+    background: &mut JoinSet<()>,
+    shutdown: &CancellationToken,
+    period: Duration,
+    name: &'static str,
+    task: F,
+) where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, E>> + Send,
+    E: std::fmt::Debug,
+{
+    let token = shutdown.clone();
+    background.spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            if let Err(e) = task().await {
+                eprintln!("Error during {}: {:?}", name, e);
+            }
+        }
+    });
+}
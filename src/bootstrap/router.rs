@@ -0,0 +1,193 @@
+//! The REST routes and the middleware every request passes through
+
+use super::AppState;
+use crate::infrastructure::controllers::{
+    auth_controller::{
+        change_password_handler, confirm_two_factor_handler, disable_two_factor_handler,
+        enroll_two_factor_handler, forgot_password_handler, get_two_factor_policy_handler,
+        list_sessions_handler, login_handler, reset_password_handler, revoke_session_handler,
+        two_factor_status_handler, update_two_factor_policy_handler,
+    },
+    items_controller::*,
+    locations_controller::*,
+};
+use crate::infrastructure::http::routes::export_routes;
+use crate::infrastructure::middleware::body_limit::body_limit;
+use crate::infrastructure::middleware::idempotency::{idempotency_middleware, Idempotency};
+use crate::infrastructure::middleware::locale_middleware::locale_middleware;
+use crate::infrastructure::middleware::usage_metering::{usage_metering_middleware, UsageMetering};
+use crate::infrastructure::observability::tracing_middleware;
+use crate::infrastructure::repositories::postgres_idempotency_repository::PostgresIdempotencyRepository;
+use crate::presentation::routes::{
+    address_routes, adjustment_routes, attachment_routes, availability_routes, barcode_routes,
+    blob_routes, create_admin_router, create_jobs_routes, create_metrics_router,
+    create_purchase_order_routes, create_reports_routes, create_stock_routes,
+    create_webhook_routes, credit_routes, currency_routes, cycle_count_routes, document_routes,
+    edi_routes, event_stream_routes, forecast_routes, graphql_routes, inbound_shipment_routes,
+    integration_routes, invoice_routes, mobile_routes, printing_routes, product_routes,
+    putaway_routes, receiving_routes, returns::return_routes, sales_order::sales_order_routes,
+    search::create_search_routes, shipment_routes, tenant::tenant_routes,
+    transfer::transfer_routes, user_routes, vendor_return_routes, webhook_retention_routes,
+};
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{delete, get, patch, post, put},
+    Json, Router,
+};
+use serde::Serialize;
+use std::{env, sync::Arc};
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{Any, CorsLayer};
+
+#[derive(Serialize)]
+struct HealthResponse {
+    status: String,
+    version: String,
+    db: String,
+}
+
+/// Every REST route over `app_state`, behind the tenant, rate limit, usage
+/// metering, tracing and idempotency middleware
+pub(super) fn build_router(
+    app_state: AppState,
+    idempotency: Arc<Idempotency<PostgresIdempotencyRepository>>,
+    usage_metering: Arc<UsageMetering>,
+) -> Router {
+    let rate_limit_middleware = Arc::clone(&app_state.rate_limit_middleware);
+    let body_limits = app_state.config.body_limits.clone();
+    let tenant_middleware = Arc::clone(&app_state.tenant_middleware);
+    Router::new()
+        .route("/healthz", get(health_handler))
+        .route("/auth/login", post(login_handler))
+        .route("/auth/forgot-password", post(forgot_password_handler))
+        .route("/auth/reset-password", post(reset_password_handler))
+        .route("/auth/change-password", post(change_password_handler))
+        .route("/auth/sessions", get(list_sessions_handler))
+        .route("/auth/sessions/{id}", delete(revoke_session_handler))
+        .route("/auth/2fa", get(two_factor_status_handler))
+        .route("/auth/2fa/enroll", post(enroll_two_factor_handler))
+        .route("/auth/2fa/confirm", post(confirm_two_factor_handler))
+        .route("/auth/2fa/disable", post(disable_two_factor_handler))
+        .route(
+            "/admin/two-factor-policy",
+            get(get_two_factor_policy_handler).put(update_two_factor_policy_handler),
+        )
+        .route("/items", post(create_item_handler))
+        .route("/items", get(list_items_handler))
+        .route(
+            "/items/import",
+            post(import_items_handler).layer(body_limit(body_limits.import_bytes)),
+        )
+        .route("/items/duplicates", get(find_duplicate_items_handler))
+        .route("/items/batch-get", post(batch_get_items_handler))
+        .route("/items/{id}", get(get_item_handler))
+        .route("/items/{id}", put(update_item_handler))
+        .route("/items/{id}", patch(patch_item_handler))
+        .route("/items/{id}", delete(delete_item_handler))
+        .route("/items/{id}/merge", post(merge_items_handler))
+        .route("/locations", post(create_location_handler))
+        .route("/locations", get(list_locations_handler))
+        .route("/locations/{id}", get(get_location_handler))
+        .route("/locations/{id}", put(update_location_handler))
+        .route("/locations/{id}", patch(patch_location_handler))
+        .route("/locations/{id}", delete(delete_location_handler))
+        .merge(attachment_routes(&body_limits))
+        .merge(availability_routes())
+        .merge(barcode_routes())
+        .merge(forecast_routes())
+        .merge(graphql_routes())
+        .merge(blob_routes())
+        .merge(document_routes(&body_limits))
+        .merge(currency_routes())
+        .merge(receiving_routes())
+        .merge(credit_routes())
+        .merge(address_routes())
+        .merge(webhook_retention_routes())
+        .merge(create_search_routes())
+        .merge(create_stock_routes())
+        .merge(adjustment_routes())
+        .merge(create_reports_routes())
+        .merge(create_jobs_routes())
+        .merge(create_purchase_order_routes())
+        .merge(putaway_routes())
+        .merge(printing_routes())
+        .merge(mobile_routes())
+        .merge(product_routes())
+        .merge(sales_order_routes())
+        .merge(transfer_routes())
+        .merge(user_routes())
+        .merge(cycle_count_routes())
+        .merge(shipment_routes())
+        .merge(edi_routes())
+        .merge(integration_routes())
+        .merge(return_routes())
+        .merge(vendor_return_routes())
+        .merge(invoice_routes())
+        .merge(inbound_shipment_routes())
+        .merge(create_webhook_routes())
+        .merge(event_stream_routes())
+        .merge(tenant_routes())
+        .merge(create_admin_router())
+        .merge(create_metrics_router())
+        .merge(export_routes::create_exports_router(&body_limits))
+        // JSON API calls; upload and import routes set larger limits of their own
+        .layer(DefaultBodyLimit::max(body_limits.json_bytes))
+        // Inside the tenant middleware so keys can be scoped to the tenant
+        .layer(axum::middleware::from_fn_with_state(
+            idempotency,
+            idempotency_middleware::<PostgresIdempotencyRepository>,
+        ))
+        .layer(axum::middleware::from_fn(
+            tracing_middleware::tracing_middleware,
+        ))
+        // Inside the tenant middleware so calls are counted against the tenant
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&usage_metering),
+            usage_metering_middleware,
+        ))
+        // Inside the tenant middleware so each tenant gets its own buckets
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&rate_limit_middleware),
+            crate::infrastructure::middleware::rate_limit_middleware::rate_limit_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&tenant_middleware),
+            |state: axum::extract::State<
+                Arc<crate::infrastructure::middleware::tenant_middleware::TenantMiddleware>,
+            >,
+             headers,
+             request,
+             next| async move { state.handle(headers, request, next).await },
+        ))
+        // Inside the trace ID so tenant and auth errors are localized too
+        .layer(axum::middleware::from_fn(locale_middleware))
+        .layer(axum::middleware::from_fn(
+            tracing_middleware::trace_id_middleware,
+        ))
+        // gzip or brotli when the client accepts it; event streams, images
+        // and tiny bodies are sent as they are
+        .layer(CompressionLayer::new())
+        .layer(
+            CorsLayer::new()
+                .allow_origin(Any)
+                .allow_methods(Any)
+                .allow_headers(Any),
+        )
+        .with_state(app_state)
+}
+
+async fn health_handler(
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> Json<HealthResponse> {
+    // Check database connectivity
+    let db_status = match sqlx::query("SELECT 1").fetch_one(&*state.pool).await {
+        Ok(_) => "ok".to_string(),
+        Err(_) => "down".to_string(),
+    };
+
+    Json(HealthResponse {
+        status: "ok".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        db: db_status,
+    })
+}
@@ -0,0 +1,636 @@
+//! The state every REST handler and the gRPC services are served with
+
+use crate::application::use_cases::{
+    adjust_stock::AdjustStockUseCase,
+    allocate_sales_order::AllocateSalesOrderUseCase,
+    cancel_cycle_count::CancelCycleCountUseCase,
+    cancel_job::CancelJobUseCase,
+    cancel_purchase_order::CancelPurchaseOrderUseCase,
+    cancel_sales_order::CancelSalesOrderUseCase,
+    change_password::ChangePasswordUseCase,
+    change_stock_status::ChangeStockStatusUseCase,
+    cleanup_expired_sandboxes::CleanupExpiredSandboxesUseCase,
+    close_purchase_order::ClosePurchaseOrderUseCase,
+    create_backorder::CreateBackorderUseCase,
+    create_cycle_count::CreateCycleCountUseCase,
+    create_item::CreateItemUseCase,
+    create_location::CreateLocationUseCase,
+    create_purchase_order::CreatePurchaseOrderUseCase,
+    create_reorder_purchase_orders::CreateReorderPurchaseOrdersUseCase,
+    create_return::CreateReturnUseCase,
+    create_sales_order::CreateSalesOrderUseCase,
+    create_sandbox_tenant::CreateSandboxTenantUseCase,
+    create_tenant::CreateTenantUseCase,
+    create_transfer::CreateTransferUseCase,
+    delete_item::DeleteItemUseCase,
+    delete_location::DeleteLocationUseCase,
+    delete_tenant::DeleteTenantUseCase,
+    enqueue_job::EnqueueJobUseCase,
+    exchange_edi_documents::ExchangeEdiDocumentsUseCase,
+    export_stock_movements::ExportStockMovementsUseCase,
+    finalize_cycle_count::FinalizeCycleCountUseCase,
+    forecast_demand::ForecastDemandUseCase,
+    generate_item_barcode::GenerateItemBarcodeUseCase,
+    generate_order_documents::GenerateOrderDocumentsUseCase,
+    generate_shipment_labels::GenerateShipmentLabelsUseCase,
+    get_cycle_count::GetCycleCountUseCase,
+    get_dead_stock_report::GetDeadStockReportUseCase,
+    get_item::GetItemUseCase,
+    get_job_status::GetJobStatusUseCase,
+    get_location::GetLocationUseCase,
+    get_location_utilization_report::GetLocationUtilizationReportUseCase,
+    get_low_stock_report::GetLowStockReportUseCase,
+    get_overdue_purchase_orders_report::GetOverduePurchaseOrdersReportUseCase,
+    get_purchase_order::GetPurchaseOrderUseCase,
+    get_receivables_aging_report::GetReceivablesAgingReportUseCase,
+    get_reorder_suggestions::GetReorderSuggestionsUseCase,
+    get_return::GetReturnUseCase,
+    get_sales_order_allocations::GetSalesOrderAllocationsUseCase,
+    get_scrap_report::GetScrapReportUseCase,
+    get_shipment::GetShipmentUseCase,
+    get_stock_level::GetStockLevelUseCase,
+    get_stock_levels_as_of::GetStockLevelsAsOfUseCase,
+    get_stock_movements::GetStockMovementsUseCase,
+    get_stock_valuation_report::GetStockValuationReportUseCase,
+    get_tenant::GetTenantUseCase,
+    import_items::ImportItemsUseCase,
+    item_availability::ItemAvailabilityUseCase,
+    list_item_stock_levels::ListItemStockLevelsUseCase,
+    list_items::ListItemsUseCase,
+    list_locations::ListLocationsUseCase,
+    list_stock_levels::ListStockLevelsUseCase,
+    list_tenants::ListTenantsUseCase,
+    login::LoginUseCase,
+    manage_adjustment_reasons::ManageAdjustmentReasonsUseCase,
+    manage_connectors::ManageConnectorsUseCase,
+    manage_credit_settings::ManageCreditSettingsUseCase,
+    manage_currency::ManageCurrencyUseCase,
+    manage_document_settings::ManageDocumentSettingsUseCase,
+    manage_inbound_shipments::ManageInboundShipmentsUseCase,
+    manage_invoices::ManageInvoicesUseCase,
+    manage_item_attachments::ManageItemAttachmentsUseCase,
+    manage_label_printing::ManageLabelPrintingUseCase,
+    manage_products::ManageProductsUseCase,
+    manage_putaway_rules::ManagePutawayRulesUseCase,
+    manage_receiving_settings::ManageReceivingSettingsUseCase,
+    manage_sandbox::ManageSandboxUseCase,
+    manage_sessions::ManageSessionsUseCase,
+    manage_sscc_sequence::ManageSsccSequenceUseCase,
+    manage_tenant_users::ManageTenantUsersUseCase,
+    manage_time_zone::ManageTimeZoneUseCase,
+    manage_trading_partners::ManageTradingPartnersUseCase,
+    manage_two_factor::ManageTwoFactorUseCase,
+    manage_vendor_returns::ManageVendorReturnsUseCase,
+    merge_items::MergeItemsUseCase,
+    mobile_scanning::{ScanToCountUseCase, ScanToPickUseCase, ScanToReceiveUseCase},
+    password_reset::PasswordResetUseCase,
+    process_return::ProcessReturnUseCase,
+    receive_purchase_order::ReceivePurchaseOrderUseCase,
+    receive_transfer::ReceiveTransferUseCase,
+    record_count::RecordCountUseCase,
+    register_user::RegisterUserUseCase,
+    reserve_stock::ReserveStockUseCase,
+    retry_job::RetryJobUseCase,
+    scan_lookup::ScanLookupUseCase,
+    search_use_case::SearchUseCaseImpl,
+    ship_sales_order::ShipSalesOrderUseCase,
+    ship_transfer::ShipTransferUseCase,
+    sync_connector::SyncConnectorUseCase,
+    tenant_snapshot::TenantSnapshotUseCase,
+    update_item::UpdateItemUseCase,
+    update_location::UpdateLocationUseCase,
+    update_shipment_tracking::UpdateShipmentTrackingUseCase,
+    validate_address::ValidateAddressUseCase,
+    webhook_retention::WebhookRetentionUseCase,
+};
+use crate::domain::services::blob_storage::BlobStorage;
+use crate::domain::services::event_broadcaster::EventBroadcaster;
+use crate::domain::services::export_service::ExportServiceImpl;
+use crate::domain::services::quota_service::QuotaService;
+use crate::domain::services::sandbox_echo_inbox::SandboxEchoInbox;
+use crate::domain::services::search_repository::SearchRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcherImpl;
+use crate::domain::services::webhook_url_policy::WebhookUrlPolicy;
+use crate::infrastructure::config::app_config::AppConfig;
+use crate::infrastructure::controllers::catalog_state::CatalogState;
+use crate::infrastructure::middleware::rate_limit_middleware::RateLimitMiddleware;
+use crate::infrastructure::repositories::{
+    postgres_adjustment_repository::PostgresAdjustmentRepository,
+    postgres_allocation_repository::PostgresAllocationRepository,
+    postgres_attachment_repository::PostgresAttachmentRepository,
+    postgres_customer_credit_repository::PostgresCustomerCreditRepository,
+    postgres_cycle_count_repository::PostgresCycleCountRepository,
+    postgres_document_settings_repository::PostgresDocumentSettingsRepository,
+    postgres_edi_repository::PostgresEdiRepository,
+    postgres_exchange_rate_repository::PostgresExchangeRateRepository,
+    postgres_forecast_repository::PostgresForecastRepository,
+    postgres_inbound_shipment_repository::PostgresInboundShipmentRepository,
+    postgres_integration_repository::PostgresIntegrationRepository,
+    postgres_invitation_repository::PostgresInvitationRepository,
+    postgres_invoice_repository::PostgresInvoiceRepository,
+    postgres_item_availability_repository::PostgresItemAvailabilityRepository,
+    postgres_item_merge_repository::PostgresItemMergeRepository,
+    postgres_item_repository::PostgresItemRepository,
+    postgres_job_repository::PostgresJobRepository,
+    postgres_location_capacity_repository::PostgresLocationCapacityRepository,
+    postgres_location_repository::PostgresLocationRepository,
+    postgres_print_repository::PostgresPrintRepository,
+    postgres_product_repository::PostgresProductRepository,
+    postgres_purchase_order_repository::PostgresPurchaseOrderRepository,
+    postgres_putaway_rule_repository::PostgresPutawayRuleRepository,
+    postgres_receiving_settings_repository::PostgresReceivingSettingsRepository,
+    postgres_reservation_repository::PostgresReservationRepository,
+    postgres_return_repository::PostgresReturnRepository,
+    postgres_sales_order_repository::PostgresSalesOrderRepository,
+    postgres_sandbox_seed_repository::PostgresSandboxSeedRepository,
+    postgres_session_repository::PostgresSessionRepository,
+    postgres_shipment_repository::PostgresShipmentRepository,
+    postgres_snapshot_repository::PostgresSnapshotRepository,
+    postgres_stock_repository::PostgresStockRepository,
+    postgres_tenant_audit_repository::PostgresTenantAuditRepository,
+    postgres_tenant_repository::PostgresTenantRepository,
+    postgres_tenant_snapshot_repository::PostgresTenantSnapshotRepository,
+    postgres_transfer_repository::PostgresTransferRepository,
+    postgres_two_factor_repository::PostgresTwoFactorRepository,
+    postgres_user_repository::PostgresUserRepository,
+    postgres_vendor_return_repository::PostgresVendorReturnRepository,
+    postgres_webhook_repository::PostgresWebhookRepository,
+    postgres_webhook_retention_repository::PostgresWebhookRetentionRepository,
+};
+use crate::infrastructure::services::{
+    barcode_service_impl::BarcodeServiceImpl, document_renderer_impl::DocumentRendererImpl,
+    job_service_impl::JobServiceImpl, label_renderer_impl::LabelRendererImpl,
+    report_service_impl::ReportServiceImpl, x12_translator::X12Translator,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub config: Arc<AppConfig>,
+    pub pool: Arc<PgPool>,
+    pub user_repository: Arc<PostgresUserRepository>,
+    pub item_repository: Arc<PostgresItemRepository>,
+    pub location_repository: Arc<PostgresLocationRepository>,
+    pub purchase_order_repository: Arc<PostgresPurchaseOrderRepository>,
+    pub putaway_rule_repository: Arc<PostgresPutawayRuleRepository>,
+    pub return_repository: Arc<PostgresReturnRepository>,
+    pub sales_order_repository: Arc<PostgresSalesOrderRepository>,
+    pub allocation_repository: Arc<PostgresAllocationRepository>,
+    pub transfer_repository: Arc<PostgresTransferRepository>,
+    pub stock_repository: Arc<PostgresStockRepository>,
+    pub reservation_repository: Arc<PostgresReservationRepository>,
+    pub cycle_count_repository: Arc<PostgresCycleCountRepository>,
+    pub shipment_repository: Arc<PostgresShipmentRepository>,
+    pub search_repository: Arc<dyn SearchRepository>,
+    pub tenant_repository: Arc<PostgresTenantRepository>,
+    pub quota_service: Arc<dyn QuotaService>,
+    pub catalog: CatalogState,
+    pub rate_limit_middleware: Arc<RateLimitMiddleware>,
+    pub tenant_middleware:
+        Arc<crate::infrastructure::middleware::tenant_middleware::TenantMiddleware>,
+    pub login_use_case: Arc<
+        LoginUseCase<
+            PostgresUserRepository,
+            PostgresSessionRepository,
+            PostgresTwoFactorRepository,
+            PostgresTenantRepository,
+        >,
+    >,
+    pub manage_sessions_use_case: Arc<ManageSessionsUseCase<PostgresSessionRepository>>,
+    pub manage_two_factor_use_case: Arc<
+        ManageTwoFactorUseCase<
+            PostgresUserRepository,
+            PostgresTwoFactorRepository,
+            PostgresTenantRepository,
+            PostgresSessionRepository,
+        >,
+    >,
+    pub password_reset_use_case: Arc<PasswordResetUseCase<PostgresUserRepository>>,
+    pub change_password_use_case: Arc<ChangePasswordUseCase<PostgresUserRepository>>,
+    pub create_item_use_case: Arc<
+        CreateItemUseCase<PostgresItemRepository, WebhookDispatcherImpl<PostgresWebhookRepository>>,
+    >,
+    pub get_item_use_case: Arc<GetItemUseCase<PostgresItemRepository>>,
+    pub update_item_use_case: Arc<
+        UpdateItemUseCase<PostgresItemRepository, WebhookDispatcherImpl<PostgresWebhookRepository>>,
+    >,
+    pub list_items_use_case: Arc<ListItemsUseCase<PostgresItemRepository>>,
+    pub delete_item_use_case: Arc<
+        DeleteItemUseCase<PostgresItemRepository, WebhookDispatcherImpl<PostgresWebhookRepository>>,
+    >,
+    pub import_items_use_case: Arc<
+        ImportItemsUseCase<
+            PostgresItemRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+            JobServiceImpl<PostgresJobRepository>,
+        >,
+    >,
+    pub merge_items_use_case: Arc<
+        MergeItemsUseCase<
+            PostgresItemRepository,
+            PostgresItemMergeRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub create_location_use_case: Arc<
+        CreateLocationUseCase<
+            PostgresLocationRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub get_location_use_case: Arc<GetLocationUseCase<PostgresLocationRepository>>,
+    pub update_location_use_case: Arc<
+        UpdateLocationUseCase<
+            PostgresLocationRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub list_locations_use_case: Arc<ListLocationsUseCase<PostgresLocationRepository>>,
+    pub delete_location_use_case: Arc<
+        DeleteLocationUseCase<
+            PostgresLocationRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub validate_address_use_case: Arc<ValidateAddressUseCase>,
+    pub create_purchase_order_use_case: Arc<
+        CreatePurchaseOrderUseCase<
+            PostgresPurchaseOrderRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub get_purchase_order_use_case: Arc<GetPurchaseOrderUseCase<PostgresPurchaseOrderRepository>>,
+    pub cancel_purchase_order_use_case: Arc<
+        CancelPurchaseOrderUseCase<
+            PostgresPurchaseOrderRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub close_purchase_order_use_case: Arc<
+        ClosePurchaseOrderUseCase<
+            PostgresPurchaseOrderRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub receive_purchase_order_use_case: Arc<
+        ReceivePurchaseOrderUseCase<
+            PostgresPurchaseOrderRepository,
+            PostgresPutawayRuleRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub create_return_use_case: Arc<
+        CreateReturnUseCase<
+            PostgresReturnRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub get_return_use_case: Arc<GetReturnUseCase<PostgresReturnRepository>>,
+    pub process_return_use_case: Arc<ProcessReturnUseCase<PostgresReturnRepository>>,
+    pub create_sales_order_use_case: Arc<
+        CreateSalesOrderUseCase<
+            PostgresSalesOrderRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub create_backorder_use_case: Arc<
+        CreateBackorderUseCase<
+            PostgresSalesOrderRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub cancel_sales_order_use_case: Arc<
+        CancelSalesOrderUseCase<
+            PostgresSalesOrderRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub allocate_sales_order_use_case:
+        Arc<AllocateSalesOrderUseCase<PostgresSalesOrderRepository, PostgresAllocationRepository>>,
+    pub get_sales_order_allocations_use_case:
+        Arc<GetSalesOrderAllocationsUseCase<PostgresAllocationRepository>>,
+    pub ship_sales_order_use_case: Arc<
+        ShipSalesOrderUseCase<
+            PostgresSalesOrderRepository,
+            PostgresShipmentRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub create_transfer_use_case: Arc<
+        CreateTransferUseCase<
+            PostgresTransferRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub receive_transfer_use_case: Arc<
+        ReceiveTransferUseCase<
+            PostgresTransferRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub ship_transfer_use_case: Arc<
+        ShipTransferUseCase<
+            PostgresTransferRepository,
+            PostgresShipmentRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub get_shipment_use_case: Arc<GetShipmentUseCase<PostgresShipmentRepository>>,
+    pub generate_shipment_labels_use_case: Arc<
+        GenerateShipmentLabelsUseCase<
+            PostgresShipmentRepository,
+            PostgresSalesOrderRepository,
+            PostgresTransferRepository,
+            PostgresLocationRepository,
+            LabelRendererImpl,
+        >,
+    >,
+    pub manage_sscc_sequence_use_case: Arc<ManageSsccSequenceUseCase<PostgresShipmentRepository>>,
+    pub generate_order_documents_use_case: Arc<
+        GenerateOrderDocumentsUseCase<
+            PostgresPurchaseOrderRepository,
+            PostgresSalesOrderRepository,
+            PostgresItemRepository,
+            PostgresLocationRepository,
+            PostgresDocumentSettingsRepository,
+            DocumentRendererImpl,
+        >,
+    >,
+    pub manage_document_settings_use_case:
+        Arc<ManageDocumentSettingsUseCase<PostgresDocumentSettingsRepository>>,
+    pub manage_receiving_settings_use_case:
+        Arc<ManageReceivingSettingsUseCase<PostgresReceivingSettingsRepository>>,
+    pub manage_credit_settings_use_case:
+        Arc<ManageCreditSettingsUseCase<PostgresCustomerCreditRepository>>,
+    pub webhook_retention_use_case:
+        Arc<WebhookRetentionUseCase<PostgresWebhookRetentionRepository>>,
+    pub manage_currency_use_case:
+        Arc<ManageCurrencyUseCase<PostgresTenantRepository, PostgresExchangeRateRepository>>,
+    pub manage_time_zone_use_case: Arc<ManageTimeZoneUseCase<PostgresTenantRepository>>,
+    pub manage_trading_partners_use_case: Arc<ManageTradingPartnersUseCase<PostgresEdiRepository>>,
+    pub exchange_edi_documents_use_case: Arc<
+        ExchangeEdiDocumentsUseCase<
+            PostgresEdiRepository,
+            PostgresItemRepository,
+            PostgresSalesOrderRepository,
+            PostgresShipmentRepository,
+            X12Translator,
+        >,
+    >,
+    pub manage_connectors_use_case: Arc<ManageConnectorsUseCase<PostgresIntegrationRepository>>,
+    pub sync_connector_use_case: Arc<
+        SyncConnectorUseCase<
+            PostgresIntegrationRepository,
+            PostgresItemRepository,
+            PostgresSalesOrderRepository,
+        >,
+    >,
+    pub update_shipment_tracking_use_case: Arc<
+        UpdateShipmentTrackingUseCase<
+            PostgresShipmentRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub create_cycle_count_use_case:
+        Arc<CreateCycleCountUseCase<PostgresCycleCountRepository, PostgresStockRepository>>,
+    pub get_cycle_count_use_case: Arc<GetCycleCountUseCase<PostgresCycleCountRepository>>,
+    pub record_count_use_case: Arc<RecordCountUseCase<PostgresCycleCountRepository>>,
+    pub cancel_cycle_count_use_case: Arc<CancelCycleCountUseCase<PostgresCycleCountRepository>>,
+    pub finalize_cycle_count_use_case: Arc<
+        FinalizeCycleCountUseCase<
+            PostgresCycleCountRepository,
+            PostgresStockRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub search_use_case: Arc<SearchUseCaseImpl<dyn SearchRepository>>,
+    pub get_stock_level_use_case: Arc<
+        GetStockLevelUseCase<
+            PostgresStockRepository,
+            PostgresItemRepository,
+            PostgresLocationRepository,
+        >,
+    >,
+    pub list_item_stock_levels_use_case: Arc<
+        ListItemStockLevelsUseCase<
+            PostgresStockRepository,
+            PostgresItemRepository,
+            PostgresLocationRepository,
+        >,
+    >,
+    pub list_stock_levels_use_case: Arc<ListStockLevelsUseCase<PostgresStockRepository>>,
+    pub get_stock_movements_use_case: Arc<
+        GetStockMovementsUseCase<
+            PostgresStockRepository,
+            PostgresItemRepository,
+            PostgresLocationRepository,
+        >,
+    >,
+    pub export_stock_movements_use_case: Arc<ExportStockMovementsUseCase<PostgresStockRepository>>,
+    pub get_stock_levels_as_of_use_case: Arc<GetStockLevelsAsOfUseCase<PostgresSnapshotRepository>>,
+    pub adjust_stock_use_case: Arc<
+        AdjustStockUseCase<
+            PostgresStockRepository,
+            PostgresItemRepository,
+            PostgresAdjustmentRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub manage_adjustment_reasons_use_case:
+        Arc<ManageAdjustmentReasonsUseCase<PostgresAdjustmentRepository>>,
+    pub change_stock_status_use_case: Arc<
+        ChangeStockStatusUseCase<
+            PostgresStockRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub reserve_stock_use_case: Arc<ReserveStockUseCase<PostgresReservationRepository>>,
+    pub generate_item_barcode_use_case:
+        Arc<GenerateItemBarcodeUseCase<PostgresItemRepository, BarcodeServiceImpl>>,
+    pub forecast_demand_use_case:
+        Arc<ForecastDemandUseCase<PostgresItemRepository, PostgresForecastRepository>>,
+    pub item_availability_use_case:
+        Arc<ItemAvailabilityUseCase<PostgresItemRepository, PostgresItemAvailabilityRepository>>,
+    pub manage_putaway_rules_use_case:
+        Arc<ManagePutawayRulesUseCase<PostgresPutawayRuleRepository, PostgresLocationRepository>>,
+    pub manage_label_printing_use_case: Arc<
+        ManageLabelPrintingUseCase<
+            PostgresPrintRepository,
+            PostgresLocationRepository,
+            JobServiceImpl<PostgresJobRepository>,
+        >,
+    >,
+    pub manage_products_use_case: Arc<
+        ManageProductsUseCase<
+            PostgresProductRepository,
+            PostgresItemRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub scan_lookup_use_case:
+        Arc<ScanLookupUseCase<PostgresItemRepository, PostgresStockRepository>>,
+    pub scan_to_receive_use_case: Arc<
+        ScanToReceiveUseCase<
+            PostgresItemRepository,
+            PostgresLocationRepository,
+            PostgresPurchaseOrderRepository,
+            PostgresPutawayRuleRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub scan_to_pick_use_case: Arc<
+        ScanToPickUseCase<
+            PostgresItemRepository,
+            PostgresLocationRepository,
+            PostgresSalesOrderRepository,
+            PostgresShipmentRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub scan_to_count_use_case: Arc<
+        ScanToCountUseCase<
+            PostgresItemRepository,
+            PostgresLocationRepository,
+            PostgresCycleCountRepository,
+        >,
+    >,
+    pub manage_tenant_users_use_case: Arc<
+        ManageTenantUsersUseCase<
+            PostgresUserRepository,
+            PostgresInvitationRepository,
+            PostgresSessionRepository,
+        >,
+    >,
+    pub manage_vendor_returns_use_case: Arc<
+        ManageVendorReturnsUseCase<
+            PostgresVendorReturnRepository,
+            PostgresPurchaseOrderRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub manage_invoices_use_case: Arc<
+        ManageInvoicesUseCase<
+            PostgresInvoiceRepository,
+            PostgresSalesOrderRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub manage_inbound_shipments_use_case: Arc<
+        ManageInboundShipmentsUseCase<
+            PostgresInboundShipmentRepository,
+            PostgresPurchaseOrderRepository,
+            PostgresPutawayRuleRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub register_user_use_case:
+        Arc<RegisterUserUseCase<PostgresUserRepository, PostgresInvitationRepository>>,
+    pub manage_item_attachments_use_case:
+        Arc<ManageItemAttachmentsUseCase<PostgresAttachmentRepository, PostgresItemRepository>>,
+    pub blob_storage: Arc<dyn BlobStorage>,
+    pub webhook_repository: Arc<PostgresWebhookRepository>,
+    pub webhook_dispatcher: Arc<WebhookDispatcherImpl<PostgresWebhookRepository>>,
+    pub webhook_url_policy: Arc<WebhookUrlPolicy>,
+    pub event_broadcaster: Arc<EventBroadcaster>,
+    pub sandbox_echo_inbox: SandboxEchoInbox,
+    pub get_webhook_deliveries_use_case: Arc<
+        crate::application::use_cases::get_webhook_deliveries::GetWebhookDeliveriesUseCase<
+            PostgresWebhookRepository,
+        >,
+    >,
+    pub get_webhook_delivery_details_use_case: Arc<
+        crate::application::use_cases::get_webhook_deliveries::GetWebhookDeliveryDetailsUseCase<
+            PostgresWebhookRepository,
+        >,
+    >,
+    pub test_webhook_use_case: Arc<
+        crate::application::use_cases::test_webhook::TestWebhookUseCase<
+            PostgresWebhookRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub retry_webhook_delivery_use_case: Arc<
+        crate::application::use_cases::retry_webhook_delivery::RetryWebhookDeliveryUseCase<
+            PostgresWebhookRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub list_dlq_deliveries_use_case: Arc<
+        crate::application::use_cases::list_dlq_deliveries::ListDlqDeliveriesUseCase<
+            PostgresWebhookRepository,
+        >,
+    >,
+    pub replay_dlq_delivery_use_case: Arc<
+        crate::application::use_cases::replay_dlq_delivery::ReplayDlqDeliveryUseCase<
+            PostgresWebhookRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub get_billing_metrics_use_case: Arc<
+        crate::application::use_cases::get_billing_metrics::GetBillingMetricsUseCase<
+            PostgresWebhookRepository,
+        >,
+    >,
+    pub create_tenant_use_case: Arc<CreateTenantUseCase<PostgresTenantRepository>>,
+    pub create_sandbox_tenant_use_case:
+        Arc<CreateSandboxTenantUseCase<PostgresTenantRepository, PostgresSandboxSeedRepository>>,
+    pub manage_sandbox_use_case: Arc<
+        ManageSandboxUseCase<
+            PostgresTenantRepository,
+            PostgresSandboxSeedRepository,
+            PostgresUserRepository,
+            PostgresTenantAuditRepository,
+        >,
+    >,
+    pub get_tenant_use_case: Arc<GetTenantUseCase<PostgresTenantRepository>>,
+    pub list_tenants_use_case: Arc<ListTenantsUseCase<PostgresTenantRepository>>,
+    pub delete_tenant_use_case: Arc<DeleteTenantUseCase<PostgresTenantRepository>>,
+    pub cleanup_expired_sandboxes_use_case:
+        Arc<CleanupExpiredSandboxesUseCase<PostgresTenantRepository>>,
+    pub report_service: Arc<ReportServiceImpl<PostgresItemRepository, PostgresStockRepository>>,
+    pub get_low_stock_report_use_case: Arc<
+        GetLowStockReportUseCase<
+            PostgresItemRepository,
+            PostgresStockRepository,
+            ReportServiceImpl<PostgresItemRepository, PostgresStockRepository>,
+        >,
+    >,
+    pub get_stock_valuation_report_use_case: Arc<
+        GetStockValuationReportUseCase<
+            ReportServiceImpl<PostgresItemRepository, PostgresStockRepository>,
+        >,
+    >,
+    pub get_reorder_suggestions_use_case:
+        Arc<GetReorderSuggestionsUseCase<PostgresItemRepository, PostgresStockRepository>>,
+    pub get_scrap_report_use_case: Arc<GetScrapReportUseCase<PostgresReturnRepository>>,
+    pub get_dead_stock_report_use_case: Arc<GetDeadStockReportUseCase<PostgresStockRepository>>,
+    pub get_overdue_purchase_orders_report_use_case:
+        Arc<GetOverduePurchaseOrdersReportUseCase<PostgresPurchaseOrderRepository>>,
+    pub get_receivables_aging_report_use_case:
+        Arc<GetReceivablesAgingReportUseCase<PostgresInvoiceRepository>>,
+    pub get_location_utilization_report_use_case:
+        Arc<GetLocationUtilizationReportUseCase<PostgresLocationCapacityRepository>>,
+    pub create_reorder_purchase_orders_use_case: Arc<
+        CreateReorderPurchaseOrdersUseCase<
+            PostgresItemRepository,
+            PostgresStockRepository,
+            PostgresPurchaseOrderRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+    pub job_repository: Arc<PostgresJobRepository>,
+    pub job_service: Arc<JobServiceImpl<PostgresJobRepository>>,
+    pub enqueue_job_use_case: Arc<EnqueueJobUseCase<JobServiceImpl<PostgresJobRepository>>>,
+    pub get_job_status_use_case: Arc<GetJobStatusUseCase<JobServiceImpl<PostgresJobRepository>>>,
+    pub cancel_job_use_case: Arc<CancelJobUseCase<JobServiceImpl<PostgresJobRepository>>>,
+    pub retry_job_use_case: Arc<RetryJobUseCase<JobServiceImpl<PostgresJobRepository>>>,
+    pub export_service:
+        Arc<ExportServiceImpl<JobServiceImpl<PostgresJobRepository>, PostgresStockRepository>>,
+    pub tenant_snapshot_use_case: Arc<
+        TenantSnapshotUseCase<
+            PostgresTenantSnapshotRepository,
+            JobServiceImpl<PostgresJobRepository>,
+        >,
+    >,
+}
@@ -0,0 +1,466 @@
+use crate::domain::entities::location::LocationAddress;
+use serde::{Deserialize, Serialize};
+
+/// ISO 3166-1 alpha-2 codes
+const COUNTRY_CODES: &str = "AD AE AF AG AI AL AM AO AQ AR AS AT AU AW AX AZ BA BB BD BE BF BG \
+    BH BI BJ BL BM BN BO BQ BR BS BT BV BW BY BZ CA CC CD CF CG CH CI CK CL CM CN CO CR CU CV CW \
+    CX CY CZ DE DJ DK DM DO DZ EC EE EG EH ER ES ET FI FJ FK FM FO FR GA GB GD GE GF GG GH GI GL \
+    GM GN GP GQ GR GS GT GU GW GY HK HM HN HR HT HU ID IE IL IM IN IO IQ IR IS IT JE JM JO JP KE \
+    KG KH KI KM KN KP KR KW KY KZ LA LB LC LI LK LR LS LT LU LV LY MA MC MD ME MF MG MH MK ML MM \
+    MN MO MP MQ MR MS MT MU MV MW MX MY MZ NA NC NE NF NG NI NL NO NP NR NU NZ OM PA PE PF PG PH \
+    PK PL PM PN PR PS PT PW PY QA RE RO RS RU RW SA SB SC SD SE SG SH SI SJ SK SL SM SN SO SR SS \
+    ST SV SX SY SZ TC TD TF TG TH TJ TK TL TM TN TO TR TT TV TW TZ UA UG UM US UY UZ VA VC VE VG \
+    VI VN VU WF WS YE YT ZA ZM ZW";
+
+/// Country names and alpha-3 codes people commonly type instead of the alpha-2 code
+const COUNTRY_ALIASES: &[(&str, &str)] = &[
+    ("USA", "US"),
+    ("UNITED STATES", "US"),
+    ("UNITED STATES OF AMERICA", "US"),
+    ("CAN", "CA"),
+    ("CANADA", "CA"),
+    ("MEX", "MX"),
+    ("MEXICO", "MX"),
+    ("BRA", "BR"),
+    ("BRAZIL", "BR"),
+    ("BRASIL", "BR"),
+    ("UK", "GB"),
+    ("GBR", "GB"),
+    ("UNITED KINGDOM", "GB"),
+    ("GREAT BRITAIN", "GB"),
+    ("DEU", "DE"),
+    ("GERMANY", "DE"),
+    ("DEUTSCHLAND", "DE"),
+    ("FRA", "FR"),
+    ("FRANCE", "FR"),
+    ("ESP", "ES"),
+    ("SPAIN", "ES"),
+    ("ITA", "IT"),
+    ("ITALY", "IT"),
+    ("NLD", "NL"),
+    ("NETHERLANDS", "NL"),
+    ("PRT", "PT"),
+    ("PORTUGAL", "PT"),
+    ("AUS", "AU"),
+    ("AUSTRALIA", "AU"),
+    ("JPN", "JP"),
+    ("JAPAN", "JP"),
+    ("CHN", "CN"),
+    ("CHINA", "CN"),
+    ("IND", "IN"),
+    ("INDIA", "IN"),
+];
+
+/// Postal code shapes by country: `9` is a digit, `A` a letter, anything else
+/// is written as-is
+const POSTAL_CODE_FORMATS: &[(&str, &[&str])] = &[
+    ("US", &["99999", "99999-9999"]),
+    ("CA", &["A9A 9A9"]),
+    ("MX", &["99999"]),
+    ("BR", &["99999-999"]),
+    (
+        "GB",
+        &[
+            "A9 9AA", "A99 9AA", "AA9 9AA", "AA99 9AA", "A9A 9AA", "AA9A 9AA",
+        ],
+    ),
+    ("DE", &["99999"]),
+    ("FR", &["99999"]),
+    ("ES", &["99999"]),
+    ("IT", &["99999"]),
+    ("NL", &["9999 AA"]),
+    ("PT", &["9999-999"]),
+    ("AU", &["9999"]),
+    ("JP", &["999-9999"]),
+    ("IN", &["999999"]),
+];
+
+const US_STATES: &[(&str, &str)] = &[
+    ("AL", "ALABAMA"),
+    ("AK", "ALASKA"),
+    ("AZ", "ARIZONA"),
+    ("AR", "ARKANSAS"),
+    ("CA", "CALIFORNIA"),
+    ("CO", "COLORADO"),
+    ("CT", "CONNECTICUT"),
+    ("DE", "DELAWARE"),
+    ("DC", "DISTRICT OF COLUMBIA"),
+    ("FL", "FLORIDA"),
+    ("GA", "GEORGIA"),
+    ("HI", "HAWAII"),
+    ("ID", "IDAHO"),
+    ("IL", "ILLINOIS"),
+    ("IN", "INDIANA"),
+    ("IA", "IOWA"),
+    ("KS", "KANSAS"),
+    ("KY", "KENTUCKY"),
+    ("LA", "LOUISIANA"),
+    ("ME", "MAINE"),
+    ("MD", "MARYLAND"),
+    ("MA", "MASSACHUSETTS"),
+    ("MI", "MICHIGAN"),
+    ("MN", "MINNESOTA"),
+    ("MS", "MISSISSIPPI"),
+    ("MO", "MISSOURI"),
+    ("MT", "MONTANA"),
+    ("NE", "NEBRASKA"),
+    ("NV", "NEVADA"),
+    ("NH", "NEW HAMPSHIRE"),
+    ("NJ", "NEW JERSEY"),
+    ("NM", "NEW MEXICO"),
+    ("NY", "NEW YORK"),
+    ("NC", "NORTH CAROLINA"),
+    ("ND", "NORTH DAKOTA"),
+    ("OH", "OHIO"),
+    ("OK", "OKLAHOMA"),
+    ("OR", "OREGON"),
+    ("PA", "PENNSYLVANIA"),
+    ("RI", "RHODE ISLAND"),
+    ("SC", "SOUTH CAROLINA"),
+    ("SD", "SOUTH DAKOTA"),
+    ("TN", "TENNESSEE"),
+    ("TX", "TEXAS"),
+    ("UT", "UTAH"),
+    ("VT", "VERMONT"),
+    ("VA", "VIRGINIA"),
+    ("WA", "WASHINGTON"),
+    ("WV", "WEST VIRGINIA"),
+    ("WI", "WISCONSIN"),
+    ("WY", "WYOMING"),
+    ("PR", "PUERTO RICO"),
+];
+
+const CA_PROVINCES: &[(&str, &str)] = &[
+    ("AB", "ALBERTA"),
+    ("BC", "BRITISH COLUMBIA"),
+    ("MB", "MANITOBA"),
+    ("NB", "NEW BRUNSWICK"),
+    ("NL", "NEWFOUNDLAND AND LABRADOR"),
+    ("NS", "NOVA SCOTIA"),
+    ("NT", "NORTHWEST TERRITORIES"),
+    ("NU", "NUNAVUT"),
+    ("ON", "ONTARIO"),
+    ("PE", "PRINCE EDWARD ISLAND"),
+    ("QC", "QUEBEC"),
+    ("SK", "SASKATCHEWAN"),
+    ("YT", "YUKON"),
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AddressIssueSeverity {
+    /// The address can't be used as given
+    Error,
+    /// Worth a second look, but the address is accepted
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AddressIssue {
+    /// `line1`, `city`, `region`, `postal_code` or `country`
+    pub field: String,
+    /// Machine-readable, e.g. `MISSING_FIELD`, `UNKNOWN_COUNTRY`, `INVALID_POSTAL_CODE`
+    pub code: String,
+    pub severity: AddressIssueSeverity,
+    pub message: String,
+}
+
+impl AddressIssue {
+    fn error(field: &str, code: &str, message: String) -> Self {
+        Self {
+            field: field.to_string(),
+            code: code.to_string(),
+            severity: AddressIssueSeverity::Error,
+            message,
+        }
+    }
+
+    fn warning(field: &str, code: &str, message: String) -> Self {
+        Self {
+            field: field.to_string(),
+            code: code.to_string(),
+            severity: AddressIssueSeverity::Warning,
+            message,
+        }
+    }
+}
+
+/// The outcome of checking an address: the normalized form to store, and what
+/// was wrong with or changed in the original
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressValidation {
+    /// False when any issue is an error
+    pub valid: bool,
+    pub address: LocationAddress,
+    pub issues: Vec<AddressIssue>,
+    /// Fields whose value normalization changed, for the UI to confirm
+    pub corrected_fields: Vec<String>,
+    /// Who checked it: `offline`, or the external provider's host
+    pub provider: String,
+}
+
+impl AddressValidation {
+    pub fn new(
+        original: &LocationAddress,
+        address: LocationAddress,
+        issues: Vec<AddressIssue>,
+        provider: String,
+    ) -> Self {
+        let fields = [
+            ("line1", &original.line1, &address.line1),
+            ("line2", &original.line2, &address.line2),
+            ("city", &original.city, &address.city),
+            ("region", &original.region, &address.region),
+            ("postal_code", &original.postal_code, &address.postal_code),
+            ("country", &original.country, &address.country),
+        ];
+        let corrected_fields = fields
+            .into_iter()
+            .filter(|(_, before, after)| before != after)
+            .map(|(field, _, _)| field.to_string())
+            .collect();
+
+        Self {
+            valid: !issues
+                .iter()
+                .any(|issue| issue.severity == AddressIssueSeverity::Error),
+            address,
+            issues,
+            corrected_fields,
+            provider,
+        }
+    }
+
+    /// Check and normalize an address without calling out anywhere: tidy the
+    /// whitespace, turn country names into ISO codes, and check postal codes and
+    /// US/Canadian regions against the country's formats
+    pub fn offline(original: &LocationAddress) -> Self {
+        let mut issues = Vec::new();
+        let mut address = LocationAddress {
+            line1: tidy(&original.line1),
+            line2: tidy(&original.line2),
+            city: tidy(&original.city),
+            region: tidy(&original.region),
+            postal_code: tidy(&original.postal_code).map(|code| code.to_uppercase()),
+            country: tidy(&original.country),
+        };
+
+        for (field, value) in [("line1", &address.line1), ("city", &address.city)] {
+            if value.is_none() {
+                issues.push(AddressIssue::warning(
+                    field,
+                    "MISSING_FIELD",
+                    format!("{} is missing", field),
+                ));
+            }
+        }
+
+        let country = match &address.country {
+            None => {
+                issues.push(AddressIssue::warning(
+                    "country",
+                    "MISSING_FIELD",
+                    "country is missing, so the postal code and region can't be checked"
+                        .to_string(),
+                ));
+                None
+            }
+            Some(country) => match country_code(country) {
+                Some(code) => Some(code),
+                None => {
+                    issues.push(AddressIssue::error(
+                        "country",
+                        "UNKNOWN_COUNTRY",
+                        format!("'{}' is not a known country or ISO 3166 code", country),
+                    ));
+                    None
+                }
+            },
+        };
+        address.country = country.map(str::to_string).or(address.country);
+
+        if let Some(country) = country {
+            let formats = POSTAL_CODE_FORMATS
+                .iter()
+                .find(|(code, _)| *code == country)
+                .map(|(_, formats)| *formats);
+            match (&address.postal_code, formats) {
+                (Some(postal_code), Some(formats)) => {
+                    match format_postal_code(postal_code, formats) {
+                        Some(formatted) => address.postal_code = Some(formatted),
+                        None => issues.push(AddressIssue::error(
+                            "postal_code",
+                            "INVALID_POSTAL_CODE",
+                            format!(
+                                "'{}' is not a valid {} postal code (expected {})",
+                                postal_code,
+                                country,
+                                formats.join(" or ")
+                            ),
+                        )),
+                    }
+                }
+                (None, Some(_)) => issues.push(AddressIssue::warning(
+                    "postal_code",
+                    "MISSING_FIELD",
+                    "postal_code is missing".to_string(),
+                )),
+                _ => {}
+            }
+
+            let regions = match country {
+                "US" => Some(US_STATES),
+                "CA" => Some(CA_PROVINCES),
+                _ => None,
+            };
+            if let (Some(region), Some(regions)) = (&address.region, regions) {
+                let upper = region.to_uppercase();
+                match regions
+                    .iter()
+                    .find(|(code, name)| *code == upper || *name == upper)
+                {
+                    Some((code, _)) => address.region = Some(code.to_string()),
+                    None => issues.push(AddressIssue::warning(
+                        "region",
+                        "UNKNOWN_REGION",
+                        format!("'{}' is not a known {} region", region, country),
+                    )),
+                }
+            }
+        }
+
+        Self::new(original, address, issues, "offline".to_string())
+    }
+
+    /// The errors as one message, for rejecting a save
+    pub fn error_message(&self) -> String {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == AddressIssueSeverity::Error)
+            .map(|issue| issue.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Trim and collapse inner whitespace; blank becomes `None`
+fn tidy(value: &Option<String>) -> Option<String> {
+    let value = value.as_deref()?.split_whitespace().collect::<Vec<_>>();
+    (!value.is_empty()).then(|| value.join(" "))
+}
+
+fn country_code(country: &str) -> Option<&'static str> {
+    let upper = country.to_uppercase();
+    COUNTRY_CODES
+        .split_whitespace()
+        .find(|code| *code == upper)
+        .or_else(|| {
+            COUNTRY_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == upper)
+                .map(|(_, code)| *code)
+        })
+}
+
+/// Fit a postal code to the first format its letters and digits match,
+/// inserting the format's separators
+fn format_postal_code(postal_code: &str, formats: &[&str]) -> Option<String> {
+    if postal_code
+        .chars()
+        .any(|c| !(c.is_ascii_alphanumeric() || c == ' ' || c == '-'))
+    {
+        return None;
+    }
+    let compact: Vec<char> = postal_code
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect();
+
+    formats.iter().find_map(|format| {
+        let slots = format.chars().filter(|c| *c == '9' || *c == 'A').count();
+        if slots != compact.len() {
+            return None;
+        }
+        let mut chars = compact.iter();
+        let mut formatted = String::new();
+        for slot in format.chars() {
+            match slot {
+                '9' | 'A' => {
+                    let c = *chars.next()?;
+                    let fits = if slot == '9' {
+                        c.is_ascii_digit()
+                    } else {
+                        c.is_ascii_alphabetic()
+                    };
+                    if !fits {
+                        return None;
+                    }
+                    formatted.push(c);
+                }
+                separator => formatted.push(separator),
+            }
+        }
+        Some(formatted)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(region: &str, postal_code: &str, country: &str) -> LocationAddress {
+        LocationAddress {
+            line1: Some("  850   Harbor Blvd ".to_string()),
+            line2: Some(" ".to_string()),
+            city: Some("Oakland".to_string()),
+            region: Some(region.to_string()),
+            postal_code: Some(postal_code.to_string()),
+            country: Some(country.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_offline_normalizes_fields() {
+        let result = AddressValidation::offline(&address("california", "946071234", "USA"));
+
+        assert!(result.valid);
+        assert!(result.issues.is_empty());
+        assert_eq!(result.address.line1.as_deref(), Some("850 Harbor Blvd"));
+        assert_eq!(result.address.line2, None);
+        assert_eq!(result.address.region.as_deref(), Some("CA"));
+        assert_eq!(result.address.postal_code.as_deref(), Some("94607-1234"));
+        assert_eq!(result.address.country.as_deref(), Some("US"));
+        assert_eq!(
+            result.corrected_fields,
+            vec!["line1", "line2", "region", "postal_code", "country"]
+        );
+
+        let canada = AddressValidation::offline(&address("ON", "k1a0b1", "ca"));
+        assert!(canada.valid);
+        assert_eq!(canada.address.postal_code.as_deref(), Some("K1A 0B1"));
+    }
+
+    #[test]
+    fn test_offline_flags_bad_postal_code_and_country() {
+        let result = AddressValidation::offline(&address("CA", "9460", "US"));
+        assert!(!result.valid);
+        assert_eq!(result.issues[0].code, "INVALID_POSTAL_CODE");
+        assert_eq!(result.issues[0].field, "postal_code");
+
+        let unknown = AddressValidation::offline(&address("X", "123", "Atlantis"));
+        assert!(!unknown.valid);
+        assert_eq!(unknown.issues[0].code, "UNKNOWN_COUNTRY");
+
+        let partial = AddressValidation::offline(&LocationAddress {
+            city: Some("Lisbon".to_string()),
+            country: Some("PT".to_string()),
+            ..Default::default()
+        });
+        assert!(partial.valid);
+        assert_eq!(partial.issues.len(), 2);
+        assert!(partial
+            .issues
+            .iter()
+            .all(|issue| issue.severity == AddressIssueSeverity::Warning));
+    }
+}
@@ -0,0 +1,366 @@
+use crate::domain::entities::inventory::{MovementType, ReferenceType, StockMovement};
+use crate::shared::error::DomainError;
+use crate::shared::money::extend;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A reason code adjustments are booked against. Adjustments past either
+/// threshold wait for approval before they change stock.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdjustmentReason {
+    pub id: Uuid,
+    pub code: String,
+    pub description: Option<String>,
+    pub active: bool,
+    /// Units (either direction) above which an adjustment needs approval
+    pub approval_quantity_threshold: Option<Decimal>,
+    /// Cost value above which an adjustment needs approval
+    pub approval_value_threshold: Option<Decimal>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AdjustmentReason {
+    pub fn new(request: CreateAdjustmentReasonRequest) -> Result<Self, DomainError> {
+        let now = Utc::now();
+        let reason = Self {
+            id: Uuid::new_v4(),
+            code: normalize_code(&request.code),
+            description: request.description,
+            active: request.active.unwrap_or(true),
+            approval_quantity_threshold: request.approval_quantity_threshold,
+            approval_value_threshold: request.approval_value_threshold,
+            created_at: now,
+            updated_at: now,
+        };
+        reason.validate()?;
+        Ok(reason)
+    }
+
+    /// The code is fixed once created, since past adjustments refer to it
+    pub fn update(&mut self, request: UpdateAdjustmentReasonRequest) -> Result<(), DomainError> {
+        if let Some(description) = request.description {
+            self.description = Some(description);
+        }
+        if let Some(active) = request.active {
+            self.active = active;
+        }
+        if let Some(threshold) = request.approval_quantity_threshold {
+            self.approval_quantity_threshold = Some(threshold);
+        }
+        if let Some(threshold) = request.approval_value_threshold {
+            self.approval_value_threshold = Some(threshold);
+        }
+        self.validate()?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn requires_approval(&self, qty_change: Decimal, value: Decimal) -> bool {
+        self.approval_quantity_threshold
+            .is_some_and(|threshold| qty_change.abs() > threshold)
+            || self
+                .approval_value_threshold
+                .is_some_and(|threshold| value > threshold)
+    }
+
+    fn validate(&self) -> Result<(), DomainError> {
+        if self.code.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Adjustment reason code cannot be empty".into(),
+            ));
+        }
+        if self
+            .approval_quantity_threshold
+            .is_some_and(|t| t <= Decimal::ZERO)
+        {
+            return Err(DomainError::ValidationError(
+                "approval_quantity_threshold must be positive".into(),
+            ));
+        }
+        if self
+            .approval_value_threshold
+            .is_some_and(|t| t <= Decimal::ZERO)
+        {
+            return Err(DomainError::ValidationError(
+                "approval_value_threshold must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAdjustmentReasonRequest {
+    pub code: String,
+    pub description: Option<String>,
+    pub active: Option<bool>,
+    pub approval_quantity_threshold: Option<Decimal>,
+    pub approval_value_threshold: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateAdjustmentReasonRequest {
+    pub description: Option<String>,
+    pub active: Option<bool>,
+    pub approval_quantity_threshold: Option<Decimal>,
+    pub approval_value_threshold: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AdjustmentStatus {
+    Applied,
+    PendingApproval,
+    Rejected,
+}
+
+impl AdjustmentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdjustmentStatus::Applied => "APPLIED",
+            AdjustmentStatus::PendingApproval => "PENDING_APPROVAL",
+            AdjustmentStatus::Rejected => "REJECTED",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "APPLIED" => Ok(AdjustmentStatus::Applied),
+            "PENDING_APPROVAL" => Ok(AdjustmentStatus::PendingApproval),
+            "REJECTED" => Ok(AdjustmentStatus::Rejected),
+            _ => Err(DomainError::ValidationError(format!(
+                "Invalid adjustment status: {}. Must be one of: APPLIED, PENDING_APPROVAL, REJECTED",
+                s
+            ).into())),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StockAdjustmentRequest {
+    pub item_id: Uuid,
+    pub location_id: Uuid,
+    pub qty_change: Decimal,
+    /// A code from the tenant's adjustment reason catalog
+    #[serde(alias = "reason")]
+    pub reason_code: String,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Adjustment {
+    pub id: Uuid,
+    pub item_id: Uuid,
+    pub location_id: Uuid,
+    pub qty_change: Decimal,
+    pub reason_code: String,
+    pub note: Option<String>,
+    /// Cost value of the change at the item's cost price
+    pub value: Decimal,
+    pub status: AdjustmentStatus,
+    /// The stock movement, once applied
+    pub movement_id: Option<Uuid>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub decided_by: Option<Uuid>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub decision_note: Option<String>,
+}
+
+impl Adjustment {
+    /// Book an adjustment against a catalog reason; it is held for approval
+    /// when it exceeds one of the reason's thresholds
+    pub fn new(
+        request: StockAdjustmentRequest,
+        reason: &AdjustmentReason,
+        unit_cost: Decimal,
+        created_by: Uuid,
+    ) -> Result<Self, DomainError> {
+        if request.qty_change == Decimal::ZERO {
+            return Err(DomainError::ValidationError(
+                "Adjustment quantity cannot be zero".into(),
+            ));
+        }
+        if !reason.active {
+            return Err(DomainError::ValidationError(
+                format!("Adjustment reason {} is inactive", reason.code).into(),
+            ));
+        }
+
+        let value = extend(request.qty_change.abs(), unit_cost);
+        let status = if reason.requires_approval(request.qty_change, value) {
+            AdjustmentStatus::PendingApproval
+        } else {
+            AdjustmentStatus::Applied
+        };
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            item_id: request.item_id,
+            location_id: request.location_id,
+            qty_change: request.qty_change,
+            reason_code: reason.code.clone(),
+            note: request.note,
+            value,
+            status,
+            movement_id: None,
+            created_by,
+            created_at: Utc::now(),
+            decided_by: None,
+            decided_at: None,
+            decision_note: None,
+        })
+    }
+
+    /// The stock movement that applies this adjustment
+    pub fn movement(&mut self) -> Result<StockMovement, DomainError> {
+        if self.status != AdjustmentStatus::Applied {
+            return Err(DomainError::BusinessLogicError(
+                format!("Adjustment {} is {}", self.id, self.status.as_str()).into(),
+            ));
+        }
+
+        let movement = StockMovement::new(
+            self.item_id,
+            self.location_id,
+            MovementType::Adjustment,
+            self.qty_change,
+            ReferenceType::Adjustment,
+            Some(self.id),
+            Some(self.reason_code.clone()),
+            Some(self.created_by),
+        )?;
+        self.movement_id = Some(movement.id);
+        Ok(movement)
+    }
+
+    /// Approve a held adjustment, returning the movement that applies it
+    pub fn approve(
+        &mut self,
+        approved_by: Uuid,
+        note: Option<String>,
+    ) -> Result<StockMovement, DomainError> {
+        self.decide(AdjustmentStatus::Applied, approved_by, note)?;
+        self.movement()
+    }
+
+    pub fn reject(&mut self, rejected_by: Uuid, note: Option<String>) -> Result<(), DomainError> {
+        self.decide(AdjustmentStatus::Rejected, rejected_by, note)
+    }
+
+    fn decide(
+        &mut self,
+        status: AdjustmentStatus,
+        decided_by: Uuid,
+        note: Option<String>,
+    ) -> Result<(), DomainError> {
+        if self.status != AdjustmentStatus::PendingApproval {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Adjustment {} is {}, not awaiting approval",
+                    self.id,
+                    self.status.as_str()
+                )
+                .into(),
+            ));
+        }
+
+        self.status = status;
+        self.decided_by = Some(decided_by);
+        self.decided_at = Some(Utc::now());
+        self.decision_note = note;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReviewAdjustmentRequest {
+    pub note: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reason(quantity: Option<Decimal>, value: Option<Decimal>) -> AdjustmentReason {
+        AdjustmentReason::new(CreateAdjustmentReasonRequest {
+            code: " damage ".to_string(),
+            description: None,
+            active: None,
+            approval_quantity_threshold: quantity,
+            approval_value_threshold: value,
+        })
+        .unwrap()
+    }
+
+    fn request(qty_change: Decimal) -> StockAdjustmentRequest {
+        StockAdjustmentRequest {
+            item_id: Uuid::new_v4(),
+            location_id: Uuid::new_v4(),
+            qty_change,
+            reason_code: "DAMAGE".to_string(),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_adjustments_past_a_threshold_wait_for_approval() {
+        let reason = reason(Some(Decimal::from(10)), Some(Decimal::from(500)));
+        assert_eq!(reason.code, "DAMAGE");
+
+        let small = Adjustment::new(
+            request(Decimal::from(-5)),
+            &reason,
+            Decimal::from(20),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        let many_units = Adjustment::new(
+            request(Decimal::from(-11)),
+            &reason,
+            Decimal::ONE,
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        let high_value = Adjustment::new(
+            request(Decimal::from(6)),
+            &reason,
+            Decimal::from(100),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+
+        assert_eq!(small.status, AdjustmentStatus::Applied);
+        assert_eq!(many_units.status, AdjustmentStatus::PendingApproval);
+        assert_eq!(high_value.status, AdjustmentStatus::PendingApproval);
+        assert_eq!(high_value.value, Decimal::from(600));
+    }
+
+    #[test]
+    fn test_approval_applies_a_held_adjustment_once() {
+        let reason = reason(Some(Decimal::ONE), None);
+        let mut adjustment = Adjustment::new(
+            request(Decimal::from(-3)),
+            &reason,
+            Decimal::from(2),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        assert!(adjustment.movement().is_err());
+
+        let movement = adjustment.approve(Uuid::new_v4(), None).unwrap();
+        assert_eq!(movement.quantity, Decimal::from(-3));
+        assert_eq!(movement.reference_id, Some(adjustment.id));
+        assert_eq!(adjustment.movement_id, Some(movement.id));
+
+        assert!(adjustment.approve(Uuid::new_v4(), None).is_err());
+        assert!(adjustment.reject(Uuid::new_v4(), None).is_err());
+    }
+}
@@ -0,0 +1,476 @@
+use crate::domain::entities::location::LocationAddress;
+use crate::domain::entities::sales_order::{SalesOrder, ShipLineRequest};
+use crate::shared::error::DomainError;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AllocationStrategy {
+    /// Prefer locations whose address is closest to the ship-to address
+    Nearest,
+    /// Prefer locations holding the most available stock for each line
+    MostStock,
+    /// Fulfil the whole order from one location when possible, splitting only as a fallback
+    #[default]
+    SingleLocationPreferred,
+}
+
+impl AllocationStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AllocationStrategy::Nearest => "NEAREST",
+            AllocationStrategy::MostStock => "MOST_STOCK",
+            AllocationStrategy::SingleLocationPreferred => "SINGLE_LOCATION_PREFERRED",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "NEAREST" => Ok(AllocationStrategy::Nearest),
+            "MOST_STOCK" => Ok(AllocationStrategy::MostStock),
+            "SINGLE_LOCATION_PREFERRED" => Ok(AllocationStrategy::SingleLocationPreferred),
+            _ => Err(DomainError::ValidationError(format!(
+                "Invalid allocation strategy: {}. Must be one of: NEAREST, MOST_STOCK, SINGLE_LOCATION_PREFERRED",
+                s
+            ).into())),
+        }
+    }
+}
+
+/// Stock of one item at one location that is free to allocate
+#[derive(Debug, Clone)]
+pub struct LocationStock {
+    pub item_id: Uuid,
+    pub location_id: Uuid,
+    pub available: Decimal,
+    pub address: Option<LocationAddress>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SalesOrderAllocation {
+    pub id: Uuid,
+    pub sales_order_id: Uuid,
+    pub so_line_id: Uuid,
+    pub item_id: Uuid,
+    pub location_id: Uuid,
+    pub qty_allocated: Decimal,
+    pub qty_shipped: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SalesOrderAllocation {
+    pub fn new(
+        sales_order_id: Uuid,
+        so_line_id: Uuid,
+        item_id: Uuid,
+        location_id: Uuid,
+        qty_allocated: Decimal,
+    ) -> Result<Self, DomainError> {
+        if qty_allocated <= Decimal::ZERO {
+            return Err(DomainError::ValidationError(
+                "Allocated quantity must be positive".into(),
+            ));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            sales_order_id,
+            so_line_id,
+            item_id,
+            location_id,
+            qty_allocated,
+            qty_shipped: Decimal::ZERO,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn remaining_qty(&self) -> Decimal {
+        self.qty_allocated - self.qty_shipped
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnallocatedLine {
+    pub so_line_id: Uuid,
+    pub item_id: Uuid,
+    pub qty: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllocationPlan {
+    pub strategy: AllocationStrategy,
+    pub allocations: Vec<SalesOrderAllocation>,
+    pub unallocated: Vec<UnallocatedLine>,
+}
+
+impl AllocationPlan {
+    /// Split the unshipped quantity of every line across the candidate locations
+    pub fn build(
+        sales_order: &SalesOrder,
+        stock: &[LocationStock],
+        strategy: AllocationStrategy,
+        ship_to: Option<&LocationAddress>,
+    ) -> Result<Self, DomainError> {
+        let mut available: HashMap<(Uuid, Uuid), Decimal> = HashMap::new();
+        for s in stock.iter().filter(|s| s.available > Decimal::ZERO) {
+            *available
+                .entry((s.item_id, s.location_id))
+                .or_insert(Decimal::ZERO) += s.available;
+        }
+
+        // A single location that covers every line wins outright under the preferred strategy
+        let single_location = match strategy {
+            AllocationStrategy::SingleLocationPreferred => {
+                Self::single_location(sales_order, stock, &available)
+            }
+            _ => None,
+        };
+
+        let mut allocations = Vec::new();
+        let mut unallocated = Vec::new();
+
+        for line in sales_order
+            .lines
+            .iter()
+            .filter(|l| l.remaining_qty() > Decimal::ZERO)
+        {
+            let mut candidates: Vec<&LocationStock> = stock
+                .iter()
+                .filter(|s| s.item_id == line.item_id)
+                .filter(|s| single_location.is_none_or(|id| s.location_id == id))
+                .collect();
+
+            match strategy {
+                AllocationStrategy::Nearest => candidates.sort_by_key(|s| {
+                    (
+                        proximity(ship_to, s.address.as_ref()),
+                        Some(s.location_id) != sales_order.fulfillment_location_id,
+                        -s.available,
+                    )
+                }),
+                AllocationStrategy::MostStock | AllocationStrategy::SingleLocationPreferred => {
+                    candidates.sort_by_key(|s| {
+                        (
+                            Some(s.location_id) != sales_order.fulfillment_location_id
+                                && strategy == AllocationStrategy::SingleLocationPreferred,
+                            -s.available,
+                        )
+                    })
+                }
+            }
+
+            let mut needed = line.remaining_qty();
+            for candidate in candidates {
+                if needed.is_zero() {
+                    break;
+                }
+                let free = available
+                    .get_mut(&(line.item_id, candidate.location_id))
+                    .filter(|free| **free > Decimal::ZERO);
+                if let Some(free) = free {
+                    let qty = needed.min(*free);
+                    *free -= qty;
+                    needed -= qty;
+                    allocations.push(SalesOrderAllocation::new(
+                        sales_order.id,
+                        line.id,
+                        line.item_id,
+                        candidate.location_id,
+                        qty,
+                    )?);
+                }
+            }
+
+            if needed > Decimal::ZERO {
+                unallocated.push(UnallocatedLine {
+                    so_line_id: line.id,
+                    item_id: line.item_id,
+                    qty: needed,
+                });
+            }
+        }
+
+        Ok(Self {
+            strategy,
+            allocations,
+            unallocated,
+        })
+    }
+
+    fn single_location(
+        sales_order: &SalesOrder,
+        stock: &[LocationStock],
+        available: &HashMap<(Uuid, Uuid), Decimal>,
+    ) -> Option<Uuid> {
+        let mut required: HashMap<Uuid, Decimal> = HashMap::new();
+        for line in sales_order
+            .lines
+            .iter()
+            .filter(|l| l.remaining_qty() > Decimal::ZERO)
+        {
+            *required.entry(line.item_id).or_insert(Decimal::ZERO) += line.remaining_qty();
+        }
+
+        let covers = |location_id: Uuid| {
+            required.iter().all(|(item_id, qty)| {
+                available
+                    .get(&(*item_id, location_id))
+                    .copied()
+                    .unwrap_or_default()
+                    >= *qty
+            })
+        };
+
+        if let Some(location_id) = sales_order.fulfillment_location_id.filter(|id| covers(*id)) {
+            return Some(location_id);
+        }
+
+        let mut locations: Vec<Uuid> = stock.iter().map(|s| s.location_id).collect();
+        locations.sort();
+        locations.dedup();
+
+        // Among locations that can cover the order, pick the one with the most total stock
+        locations
+            .into_iter()
+            .filter(|id| covers(*id))
+            .max_by_key(|id| {
+                stock
+                    .iter()
+                    .filter(|s| s.location_id == *id)
+                    .map(|s| s.available)
+                    .sum::<Decimal>()
+            })
+    }
+}
+
+/// Draw a line shipment from its allocations, bounded by what is on hand at each location.
+/// A request naming a location draws only from that location's allocations.
+/// Returns one request per source location; the total may fall short of the requested quantity.
+pub fn split_shipment(
+    allocations: &mut [SalesOrderAllocation],
+    request: &ShipLineRequest,
+    on_hand: &mut HashMap<(Uuid, Uuid), Decimal>,
+) -> Vec<ShipLineRequest> {
+    let mut needed = request.qty_shipped;
+    let mut split = Vec::new();
+
+    for allocation in allocations.iter_mut().filter(|a| {
+        a.so_line_id == request.so_line_id
+            && a.remaining_qty() > Decimal::ZERO
+            && request.location_id.is_none_or(|l| l == a.location_id)
+    }) {
+        if needed == Decimal::ZERO {
+            break;
+        }
+        let stock = on_hand
+            .entry((allocation.item_id, allocation.location_id))
+            .or_insert(Decimal::ZERO);
+        let qty = needed.min(allocation.remaining_qty()).min(*stock);
+        if qty <= Decimal::ZERO {
+            continue;
+        }
+
+        *stock -= qty;
+        needed -= qty;
+        allocation.qty_shipped += qty;
+        allocation.updated_at = Utc::now();
+        split.push(ShipLineRequest {
+            so_line_id: request.so_line_id,
+            qty_shipped: qty,
+            location_id: Some(allocation.location_id),
+        });
+    }
+
+    split
+}
+
+/// Rank how close a location is to the ship-to address; lower is nearer
+fn proximity(ship_to: Option<&LocationAddress>, address: Option<&LocationAddress>) -> u8 {
+    let (Some(ship_to), Some(address)) = (ship_to, address) else {
+        return 4;
+    };
+
+    let same = |a: &Option<String>, b: &Option<String>| match (a, b) {
+        (Some(a), Some(b)) => a.trim().eq_ignore_ascii_case(b.trim()),
+        _ => false,
+    };
+
+    if same(&ship_to.postal_code, &address.postal_code) {
+        0
+    } else if same(&ship_to.city, &address.city) {
+        1
+    } else if same(&ship_to.region, &address.region) {
+        2
+    } else if same(&ship_to.country, &address.country) {
+        3
+    } else {
+        4
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AllocateSalesOrderRequest {
+    pub strategy: Option<AllocationStrategy>,
+    pub ship_to: Option<LocationAddress>,
+    /// Keep whatever could be allocated instead of failing when stock is short
+    pub allow_partial: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::sales_order::SalesOrderLine;
+    use rust_decimal::Decimal;
+
+    fn order(item_id: Uuid, qty: Decimal, fulfillment_location_id: Option<Uuid>) -> SalesOrder {
+        let mut so = SalesOrder::new(
+            "SO-TEST".to_string(),
+            None,
+            fulfillment_location_id,
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        so.add_line(SalesOrderLine::new(item_id, qty, Decimal::new(10, 0)).unwrap())
+            .unwrap();
+        so
+    }
+
+    fn stock(item_id: Uuid, location_id: Uuid, available: Decimal, city: &str) -> LocationStock {
+        LocationStock {
+            item_id,
+            location_id,
+            available,
+            address: Some(LocationAddress {
+                city: Some(city.to_string()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_most_stock_splits_across_locations() {
+        let item_id = Uuid::new_v4();
+        let (small, large) = (Uuid::new_v4(), Uuid::new_v4());
+        let so = order(item_id, Decimal::from(10), None);
+        let candidates = [
+            stock(item_id, small, Decimal::from(4), "Lisbon"),
+            stock(item_id, large, Decimal::from(7), "Porto"),
+        ];
+
+        let plan =
+            AllocationPlan::build(&so, &candidates, AllocationStrategy::MostStock, None).unwrap();
+
+        assert_eq!(plan.allocations.len(), 2);
+        assert_eq!(plan.allocations[0].location_id, large);
+        assert_eq!(plan.allocations[0].qty_allocated, Decimal::from(7));
+        assert_eq!(plan.allocations[1].qty_allocated, Decimal::from(3));
+        assert!(plan.unallocated.is_empty());
+    }
+
+    #[test]
+    fn test_single_location_preferred_avoids_split() {
+        let item_id = Uuid::new_v4();
+        let (partial, full) = (Uuid::new_v4(), Uuid::new_v4());
+        let so = order(item_id, Decimal::from(5), Some(partial));
+        let candidates = [
+            stock(item_id, partial, Decimal::from(3), "Lisbon"),
+            stock(item_id, full, Decimal::from(5), "Porto"),
+        ];
+
+        let plan = AllocationPlan::build(
+            &so,
+            &candidates,
+            AllocationStrategy::SingleLocationPreferred,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(plan.allocations.len(), 1);
+        assert_eq!(plan.allocations[0].location_id, full);
+    }
+
+    #[test]
+    fn test_nearest_prefers_matching_city_and_reports_shortfall() {
+        let item_id = Uuid::new_v4();
+        let (far, near) = (Uuid::new_v4(), Uuid::new_v4());
+        let so = order(item_id, Decimal::from(10), None);
+        let candidates = [
+            stock(item_id, far, Decimal::from(5), "Lisbon"),
+            stock(item_id, near, Decimal::from(2), "Porto"),
+        ];
+        let ship_to = LocationAddress {
+            city: Some("porto".to_string()),
+            ..Default::default()
+        };
+
+        let plan = AllocationPlan::build(
+            &so,
+            &candidates,
+            AllocationStrategy::Nearest,
+            Some(&ship_to),
+        )
+        .unwrap();
+
+        assert_eq!(plan.allocations[0].location_id, near);
+        assert_eq!(plan.unallocated[0].qty, Decimal::from(3));
+    }
+
+    #[test]
+    fn test_split_shipment_draws_from_allocations() {
+        let item_id = Uuid::new_v4();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let so = order(item_id, Decimal::from(6), None);
+        let line_id = so.lines[0].id;
+        let mut allocations = vec![
+            SalesOrderAllocation::new(so.id, line_id, item_id, first, Decimal::from(4)).unwrap(),
+            SalesOrderAllocation::new(so.id, line_id, item_id, second, Decimal::from(2)).unwrap(),
+        ];
+        let mut on_hand = HashMap::from([
+            ((item_id, first), Decimal::from(3)),
+            ((item_id, second), Decimal::TWO),
+        ]);
+
+        let split = split_shipment(
+            &mut allocations,
+            &ShipLineRequest {
+                so_line_id: line_id,
+                qty_shipped: Decimal::from(6),
+                location_id: None,
+            },
+            &mut on_hand,
+        );
+
+        let shipped: Vec<(Option<Uuid>, Decimal)> = split
+            .iter()
+            .map(|s| (s.location_id, s.qty_shipped))
+            .collect();
+        assert_eq!(
+            shipped,
+            vec![
+                (Some(first), Decimal::from(3)),
+                (Some(second), Decimal::TWO)
+            ]
+        );
+        assert_eq!(allocations[0].remaining_qty(), Decimal::ONE);
+
+        // Naming a location skips the other allocations, even with stock left there
+        on_hand.insert((item_id, first), Decimal::from(5));
+        let split = split_shipment(
+            &mut allocations,
+            &ShipLineRequest {
+                so_line_id: line_id,
+                qty_shipped: Decimal::ONE,
+                location_id: Some(second),
+            },
+            &mut on_hand,
+        );
+        assert!(split.is_empty());
+    }
+}
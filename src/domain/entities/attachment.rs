@@ -0,0 +1,142 @@
+use crate::shared::error::DomainError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Largest file accepted as an item attachment
+pub const MAX_ATTACHMENT_BYTES: usize = 20 * 1024 * 1024;
+
+/// A file (product photo, spec sheet, ...) linked to an item. The bytes live in
+/// blob storage under `storage_key`; only the metadata is kept in Postgres.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemAttachment {
+    pub id: Uuid,
+    pub item_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ItemAttachment {
+    pub fn new(
+        tenant_id: Option<Uuid>,
+        item_id: Uuid,
+        file_name: &str,
+        content_type: &str,
+        size_bytes: usize,
+        created_by: Uuid,
+    ) -> Result<Self, DomainError> {
+        if size_bytes == 0 {
+            return Err(DomainError::ValidationError(
+                "Attachment cannot be empty".into(),
+            ));
+        }
+        if size_bytes > MAX_ATTACHMENT_BYTES {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Attachment exceeds the {} MB limit",
+                    MAX_ATTACHMENT_BYTES / (1024 * 1024)
+                )
+                .into(),
+            ));
+        }
+
+        let file_name = sanitize_file_name(file_name);
+        let id = Uuid::new_v4();
+        let tenant = tenant_id.map_or_else(|| "shared".to_string(), |id| id.to_string());
+
+        Ok(Self {
+            id,
+            item_id,
+            storage_key: format!(
+                "tenants/{}/items/{}/attachments/{}/{}",
+                tenant, item_id, id, file_name
+            ),
+            file_name,
+            content_type: content_type.trim().to_lowercase(),
+            size_bytes: size_bytes as i64,
+            created_by,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Path the file can be downloaded from
+    pub fn download_url(&self) -> String {
+        format!("/items/{}/attachments/{}/content", self.item_id, self.id)
+    }
+}
+
+/// Keep the client's file name readable but safe to use as a storage key segment
+fn sanitize_file_name(file_name: &str) -> String {
+    // Browsers may send a full path; only the last component is the name
+    let base = file_name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(200)
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.');
+    if cleaned.is_empty() {
+        "file".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_attachment_sanitizes_name_into_key() {
+        let tenant = Uuid::new_v4();
+        let item = Uuid::new_v4();
+        let attachment = ItemAttachment::new(
+            Some(tenant),
+            item,
+            "C:\\photos\\front view (1).JPG",
+            "Image/JPEG",
+            1024,
+            Uuid::new_v4(),
+        )
+        .unwrap();
+
+        assert_eq!(attachment.file_name, "front_view__1_.JPG");
+        assert_eq!(attachment.content_type, "image/jpeg");
+        assert_eq!(
+            attachment.storage_key,
+            format!(
+                "tenants/{}/items/{}/attachments/{}/front_view__1_.JPG",
+                tenant, item, attachment.id
+            )
+        );
+        assert_eq!(sanitize_file_name("../.."), "file");
+    }
+
+    #[test]
+    fn test_rejects_empty_and_oversized_files() {
+        let new = |size| {
+            ItemAttachment::new(
+                None,
+                Uuid::new_v4(),
+                "a.pdf",
+                "application/pdf",
+                size,
+                Uuid::nil(),
+            )
+        };
+        assert!(new(0).is_err());
+        assert!(new(MAX_ATTACHMENT_BYTES + 1).is_err());
+        assert!(new(MAX_ATTACHMENT_BYTES).is_ok());
+    }
+}
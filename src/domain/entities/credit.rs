@@ -0,0 +1,169 @@
+use crate::shared::error::DomainError;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// What confirming an order past a customer's credit limit does
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CreditEnforcement {
+    /// Refuse the order unless an admin overrides the limit
+    #[default]
+    Block,
+    /// Confirm the order and report the overrun alongside it
+    Warn,
+}
+
+impl CreditEnforcement {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CreditEnforcement::Block => "BLOCK",
+            CreditEnforcement::Warn => "WARN",
+        }
+    }
+}
+
+impl fmt::Display for CreditEnforcement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CreditEnforcement {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "BLOCK" => Ok(CreditEnforcement::Block),
+            "WARN" => Ok(CreditEnforcement::Warn),
+            _ => Err(DomainError::ValidationError(
+                format!("Unknown credit enforcement: {}", s).into(),
+            )),
+        }
+    }
+}
+
+/// How the tenant holds customers to their credit limits
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreditSettings {
+    pub enforcement: CreditEnforcement,
+    pub customers: Vec<CustomerCreditLimit>,
+}
+
+impl CreditSettings {
+    /// The limit orders for `customer_id` are held to, if it has one
+    pub fn limit_for(&self, customer_id: Uuid) -> Option<Decimal> {
+        self.customers
+            .iter()
+            .find(|c| c.customer_id == customer_id)
+            .map(|c| c.credit_limit)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomerCreditLimit {
+    pub customer_id: Uuid,
+    /// In the tenant's base currency
+    pub credit_limit: Decimal,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A customer's limit against what it owes on unpaid invoices, in the
+/// tenant's base currency
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomerCreditStatus {
+    pub customer_id: Uuid,
+    pub credit_limit: Option<Decimal>,
+    pub outstanding_balance: Decimal,
+    /// What is left before the limit; `None` without a limit
+    pub available_credit: Option<Decimal>,
+}
+
+impl CustomerCreditStatus {
+    pub fn new(
+        customer_id: Uuid,
+        credit_limit: Option<Decimal>,
+        outstanding_balance: Decimal,
+    ) -> Self {
+        Self {
+            customer_id,
+            credit_limit,
+            outstanding_balance,
+            available_credit: credit_limit.map(|limit| limit - outstanding_balance),
+        }
+    }
+}
+
+/// An order that takes its customer past their credit limit
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CreditLimitExceeded {
+    pub customer_id: Uuid,
+    pub credit_limit: Decimal,
+    pub outstanding_balance: Decimal,
+    /// The order's total in the tenant's base currency
+    pub order_amount: Decimal,
+    pub exceeded_by: Decimal,
+    /// An admin confirmed the order over a blocking limit
+    pub overridden: bool,
+}
+
+impl CreditLimitExceeded {
+    /// `None` when the order fits within the limit
+    pub fn check(status: &CustomerCreditStatus, order_amount: Decimal) -> Option<Self> {
+        let credit_limit = status.credit_limit?;
+        let exceeded_by = status.outstanding_balance + order_amount - credit_limit;
+        (exceeded_by > Decimal::ZERO).then_some(Self {
+            customer_id: status.customer_id,
+            credit_limit,
+            outstanding_balance: status.outstanding_balance,
+            order_amount,
+            exceeded_by,
+            overridden: false,
+        })
+    }
+
+    pub fn message(&self) -> String {
+        format!(
+            "Order of {} would take customer {} to {} against a credit limit of {}",
+            self.order_amount,
+            self.customer_id,
+            self.outstanding_balance + self.order_amount,
+            self.credit_limit
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCreditSettingsRequest {
+    pub enforcement: CreditEnforcement,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetCustomerCreditLimitRequest {
+    pub credit_limit: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credit_check_only_flags_orders_past_the_limit() {
+        let customer_id = Uuid::new_v4();
+        let status =
+            CustomerCreditStatus::new(customer_id, Some(Decimal::from(1000)), Decimal::from(700));
+        assert_eq!(status.available_credit, Some(Decimal::from(300)));
+
+        assert!(CreditLimitExceeded::check(&status, Decimal::from(300)).is_none());
+        let exceeded = CreditLimitExceeded::check(&status, Decimal::from(450)).unwrap();
+        assert_eq!(exceeded.exceeded_by, Decimal::from(150));
+        assert!(!exceeded.overridden);
+
+        let unlimited = CustomerCreditStatus::new(customer_id, None, Decimal::from(700));
+        assert!(CreditLimitExceeded::check(&unlimited, Decimal::from(1_000_000)).is_none());
+        assert_eq!(unlimited.available_credit, None);
+    }
+}
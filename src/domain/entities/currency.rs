@@ -0,0 +1,161 @@
+use crate::shared::error::DomainError;
+use crate::shared::money;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Base currency of tenants that haven't chosen one
+pub const DEFAULT_BASE_CURRENCY: &str = "USD";
+
+/// Upper-case an ISO 4217 code, rejecting anything that isn't three letters
+pub fn normalize_currency(code: &str) -> Result<String, DomainError> {
+    let code = code.trim().to_ascii_uppercase();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(DomainError::ValidationError(
+            format!(
+                "Invalid currency code '{}': expected a three-letter ISO 4217 code",
+                code
+            )
+            .into(),
+        ));
+    }
+    Ok(code)
+}
+
+/// One unit of `base_currency` buys `rate` units of `quote_currency`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExchangeRate {
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub rate: Decimal,
+    pub rate_date: NaiveDate,
+    /// Where the rate came from, e.g. the feed's host
+    pub source: String,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// Converts amounts using a set of rates, directly, inverted, or across a
+/// currency both sides have a rate against
+#[derive(Debug, Clone, Default)]
+pub struct CurrencyConverter {
+    rates: HashMap<(String, String), Decimal>,
+}
+
+impl CurrencyConverter {
+    pub fn new(rates: &[ExchangeRate]) -> Self {
+        Self {
+            rates: rates
+                .iter()
+                .filter(|rate| rate.rate > Decimal::ZERO)
+                .map(|rate| {
+                    (
+                        (rate.base_currency.clone(), rate.quote_currency.clone()),
+                        rate.rate,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Units of `to` one unit of `from` is worth, if the rates say, to the
+    /// precision rates are stored at
+    pub fn rate(&self, from: &str, to: &str) -> Option<Decimal> {
+        if from == to {
+            return Some(Decimal::ONE);
+        }
+        if let Some(rate) = self.direct(from, to) {
+            return Some(money::round_rate(rate));
+        }
+        // Cross through any currency with a rate against both
+        self.rates
+            .keys()
+            .map(|(base, _)| base.as_str())
+            .chain(self.rates.keys().map(|(_, quote)| quote.as_str()))
+            .find_map(|via| self.direct(from, via)?.checked_mul(self.direct(via, to)?))
+            .map(money::round_rate)
+    }
+
+    /// Convert `amount`, rounded to cents
+    pub fn convert(&self, amount: Decimal, from: &str, to: &str) -> Result<Decimal, DomainError> {
+        let rate = self.rate(from, to).ok_or_else(|| {
+            DomainError::ValidationError(
+                format!("No exchange rate from {} to {} is available", from, to).into(),
+            )
+        })?;
+        money::convert(amount, rate)
+    }
+
+    fn direct(&self, from: &str, to: &str) -> Option<Decimal> {
+        if from == to {
+            return Some(Decimal::ONE);
+        }
+        let key = |a: &str, b: &str| (a.to_string(), b.to_string());
+        self.rates.get(&key(from, to)).copied().or_else(|| {
+            self.rates
+                .get(&key(to, from))
+                .and_then(|rate| Decimal::ONE.checked_div(*rate))
+        })
+    }
+}
+
+/// The currency an order is priced in when it isn't the tenant's base currency
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderCurrency {
+    pub currency: String,
+    /// Units of base currency one unit of `currency` was worth when the order was placed
+    pub exchange_rate: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrencySettings {
+    pub base_currency: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCurrencySettingsRequest {
+    pub base_currency: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(base: &str, quote: &str, rate: Decimal) -> ExchangeRate {
+        ExchangeRate {
+            base_currency: base.to_string(),
+            quote_currency: quote.to_string(),
+            rate,
+            rate_date: NaiveDate::from_ymd_opt(2026, 10, 14).unwrap(),
+            source: "test".to_string(),
+            fetched_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_currency() {
+        assert_eq!(normalize_currency(" eur ").unwrap(), "EUR");
+        assert!(normalize_currency("EURO").is_err());
+        assert!(normalize_currency("E1R").is_err());
+    }
+
+    #[test]
+    fn test_converter_direct_inverse_and_cross() {
+        let converter = CurrencyConverter::new(&[
+            rate("USD", "EUR", Decimal::new(8, 1)),
+            rate("USD", "GBP", Decimal::new(5, 1)),
+        ]);
+        assert_eq!(converter.rate("USD", "USD"), Some(Decimal::ONE));
+        assert_eq!(converter.rate("USD", "EUR"), Some(Decimal::new(8, 1)));
+        assert_eq!(converter.rate("EUR", "USD"), Some(Decimal::new(125, 2)));
+        assert_eq!(converter.rate("EUR", "GBP"), Some(Decimal::new(625, 3)));
+        assert_eq!(
+            converter
+                .convert(Decimal::new(100, 0), "GBP", "USD")
+                .unwrap(),
+            Decimal::new(200, 0)
+        );
+        assert!(converter.rate("EUR", "JPY").is_none());
+        assert!(converter.convert(Decimal::ONE, "JPY", "USD").is_err());
+    }
+}
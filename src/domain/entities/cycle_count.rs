@@ -0,0 +1,350 @@
+use crate::domain::entities::inventory::{MovementType, ReferenceType, StockMovement};
+use crate::shared::error::DomainError;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum CycleCountStatus {
+    Scheduled,
+    InProgress,
+    Completed,
+    Cancelled,
+}
+
+impl CycleCountStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CycleCountStatus::Scheduled => "SCHEDULED",
+            CycleCountStatus::InProgress => "IN_PROGRESS",
+            CycleCountStatus::Completed => "COMPLETED",
+            CycleCountStatus::Cancelled => "CANCELLED",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s {
+            "SCHEDULED" => Ok(CycleCountStatus::Scheduled),
+            "IN_PROGRESS" => Ok(CycleCountStatus::InProgress),
+            "COMPLETED" => Ok(CycleCountStatus::Completed),
+            "CANCELLED" => Ok(CycleCountStatus::Cancelled),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid cycle count status: {}", s).into(),
+            )),
+        }
+    }
+
+    pub fn can_transition_to(&self, new_status: &CycleCountStatus) -> bool {
+        match self {
+            CycleCountStatus::Scheduled => matches!(
+                new_status,
+                CycleCountStatus::InProgress | CycleCountStatus::Cancelled
+            ),
+            CycleCountStatus::InProgress => matches!(
+                new_status,
+                CycleCountStatus::Completed | CycleCountStatus::Cancelled
+            ),
+            CycleCountStatus::Completed => false,
+            CycleCountStatus::Cancelled => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleCountLine {
+    pub id: Uuid,
+    pub cycle_count_id: Uuid,
+    pub item_id: Uuid,
+    pub expected_qty: Decimal,
+    pub counted_qty: Option<Decimal>,
+    pub counted_by: Option<Uuid>,
+    pub counted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CycleCountLine {
+    pub fn new(cycle_count_id: Uuid, item_id: Uuid, expected_qty: Decimal) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            cycle_count_id,
+            item_id,
+            expected_qty,
+            counted_qty: None,
+            counted_by: None,
+            counted_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Difference between the physical count and the system quantity
+    pub fn variance(&self) -> Option<Decimal> {
+        self.counted_qty.map(|counted| counted - self.expected_qty)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleCount {
+    pub id: Uuid,
+    pub count_number: String,
+    pub location_id: Uuid,
+    pub status: CycleCountStatus,
+    pub scheduled_for: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+    pub lines: Vec<CycleCountLine>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl CycleCount {
+    pub fn new(
+        count_number: String,
+        location_id: Uuid,
+        scheduled_for: Option<DateTime<Utc>>,
+        notes: Option<String>,
+        created_by: Uuid,
+    ) -> Result<Self, DomainError> {
+        if count_number.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Cycle count number cannot be empty".into(),
+            ));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            count_number,
+            location_id,
+            status: CycleCountStatus::Scheduled,
+            scheduled_for,
+            notes,
+            lines: Vec::new(),
+            created_by,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        })
+    }
+
+    pub fn add_line(&mut self, item_id: Uuid, expected_qty: Decimal) -> Result<(), DomainError> {
+        if self.status != CycleCountStatus::Scheduled {
+            return Err(DomainError::ValidationError(
+                "Cannot add lines to a cycle count that has already started".into(),
+            ));
+        }
+
+        if self.lines.iter().any(|l| l.item_id == item_id) {
+            return Err(DomainError::ValidationError(
+                format!("Item {} is already part of this cycle count", item_id).into(),
+            ));
+        }
+
+        self.lines
+            .push(CycleCountLine::new(self.id, item_id, expected_qty));
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Record the physically counted quantity for an item, starting the count if needed
+    pub fn record_count(
+        &mut self,
+        item_id: Uuid,
+        counted_qty: Decimal,
+        counted_by: Uuid,
+    ) -> Result<(), DomainError> {
+        if self.status == CycleCountStatus::Scheduled {
+            self.status = CycleCountStatus::InProgress;
+        }
+
+        if self.status != CycleCountStatus::InProgress {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Cannot record counts for cycle count with status: {:?}",
+                    self.status
+                )
+                .into(),
+            ));
+        }
+
+        if counted_qty < Decimal::ZERO {
+            return Err(DomainError::ValidationError(
+                "Counted quantity cannot be negative".into(),
+            ));
+        }
+
+        let line = self
+            .lines
+            .iter_mut()
+            .find(|l| l.item_id == item_id)
+            .ok_or_else(|| {
+                DomainError::ValidationError(
+                    format!("Item {} is not part of this cycle count", item_id).into(),
+                )
+            })?;
+
+        let now = Utc::now();
+        line.counted_qty = Some(counted_qty);
+        line.counted_by = Some(counted_by);
+        line.counted_at = Some(now);
+        line.updated_at = now;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Update the system quantity a line is reconciled against
+    pub fn refresh_expected(&mut self, item_id: Uuid, expected_qty: Decimal) {
+        if let Some(line) = self.lines.iter_mut().find(|l| l.item_id == item_id) {
+            line.expected_qty = expected_qty;
+        }
+    }
+
+    /// Complete the count and produce adjustment movements for every non-zero variance
+    pub fn finalize(&mut self, finalized_by: Uuid) -> Result<Vec<StockMovement>, DomainError> {
+        if !self.status.can_transition_to(&CycleCountStatus::Completed) {
+            return Err(DomainError::ValidationError(
+                format!("Cannot finalize cycle count with status: {:?}", self.status).into(),
+            ));
+        }
+
+        if let Some(line) = self.lines.iter().find(|l| l.counted_qty.is_none()) {
+            return Err(DomainError::ValidationError(
+                format!("Item {} has not been counted yet", line.item_id).into(),
+            ));
+        }
+
+        let mut stock_movements = Vec::new();
+        for line in &self.lines {
+            let variance = line.variance().unwrap_or_default();
+            if variance.is_zero() {
+                continue;
+            }
+
+            let movement = StockMovement::new(
+                line.item_id,
+                self.location_id,
+                MovementType::Adjustment,
+                variance,
+                ReferenceType::CycleCount,
+                Some(self.id),
+                Some(format!(
+                    "Cycle count {}: expected {}, counted {}",
+                    self.count_number,
+                    line.expected_qty,
+                    line.counted_qty.unwrap_or(Decimal::ZERO)
+                )),
+                Some(finalized_by),
+            )?;
+            stock_movements.push(movement);
+        }
+
+        let now = Utc::now();
+        self.status = CycleCountStatus::Completed;
+        self.completed_at = Some(now);
+        self.updated_at = now;
+        Ok(stock_movements)
+    }
+
+    pub fn cancel(&mut self) -> Result<(), DomainError> {
+        if !self.status.can_transition_to(&CycleCountStatus::Cancelled) {
+            return Err(DomainError::ValidationError(
+                format!("Cannot cancel cycle count with status: {:?}", self.status).into(),
+            ));
+        }
+
+        self.status = CycleCountStatus::Cancelled;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCycleCountRequest {
+    pub location_id: Uuid,
+    /// Items to count; when omitted every item stocked at the location is counted
+    pub item_ids: Option<Vec<Uuid>>,
+    pub scheduled_for: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordCountRequest {
+    pub lines: Vec<RecordCountLineRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordCountLineRequest {
+    pub item_id: Uuid,
+    pub counted_qty: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduled_count() -> CycleCount {
+        CycleCount::new(
+            "CC-TEST".to_string(),
+            Uuid::new_v4(),
+            None,
+            None,
+            Uuid::new_v4(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_finalize_generates_adjustments_for_variances() {
+        let mut count = scheduled_count();
+        let (over, short, exact) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        count.add_line(over, Decimal::from(10)).unwrap();
+        count.add_line(short, Decimal::from(10)).unwrap();
+        count.add_line(exact, Decimal::from(5)).unwrap();
+
+        let user = Uuid::new_v4();
+        count.record_count(over, Decimal::from(12), user).unwrap();
+        count.record_count(short, Decimal::from(7), user).unwrap();
+        count.record_count(exact, Decimal::from(5), user).unwrap();
+        assert_eq!(count.status, CycleCountStatus::InProgress);
+
+        let movements = count.finalize(user).unwrap();
+        assert_eq!(count.status, CycleCountStatus::Completed);
+        assert_eq!(movements.len(), 2);
+
+        let over_movement = movements.iter().find(|m| m.item_id == over).unwrap();
+        assert_eq!(over_movement.quantity, Decimal::from(2));
+        let short_movement = movements.iter().find(|m| m.item_id == short).unwrap();
+        assert_eq!(short_movement.quantity, Decimal::from(-3));
+        assert!(movements.iter().all(
+            |m| m.movement_type == MovementType::Adjustment && m.reference_id == Some(count.id)
+        ));
+    }
+
+    #[test]
+    fn test_finalize_requires_all_lines_counted() {
+        let mut count = scheduled_count();
+        let (counted, uncounted) = (Uuid::new_v4(), Uuid::new_v4());
+        count.add_line(counted, Decimal::ONE).unwrap();
+        count.add_line(uncounted, Decimal::ONE).unwrap();
+        count
+            .record_count(counted, Decimal::ONE, Uuid::new_v4())
+            .unwrap();
+
+        assert!(count.finalize(Uuid::new_v4()).is_err());
+        assert_eq!(count.status, CycleCountStatus::InProgress);
+    }
+
+    #[test]
+    fn test_record_count_rejects_unknown_item() {
+        let mut count = scheduled_count();
+        count.add_line(Uuid::new_v4(), Decimal::ONE).unwrap();
+
+        assert!(count
+            .record_count(Uuid::new_v4(), Decimal::ONE, Uuid::new_v4())
+            .is_err());
+    }
+}
@@ -0,0 +1,197 @@
+use crate::domain::entities::location::LocationAddress;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const MAX_LOGO_BYTES: usize = 1024 * 1024;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// A tenant's letterhead: the company block, logo and footer printed on
+/// purchase orders, order confirmations and packing slips
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentSettings {
+    pub company_name: String,
+    pub address: Option<LocationAddress>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub tax_id: Option<String>,
+    pub footer_text: Option<String>,
+    /// Blob storage key of the PNG logo, if one was uploaded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo_key: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DocumentSettings {
+    pub fn new(request: UpdateDocumentSettingsRequest) -> Result<Self, DomainError> {
+        let mut settings = Self {
+            company_name: String::new(),
+            address: None,
+            phone: None,
+            email: None,
+            tax_id: None,
+            footer_text: None,
+            logo_key: None,
+            updated_at: Utc::now(),
+        };
+        settings.update(request)?;
+        Ok(settings)
+    }
+
+    /// Replace the letterhead text; the logo is managed separately
+    pub fn update(&mut self, request: UpdateDocumentSettingsRequest) -> Result<(), DomainError> {
+        let company_name = request.company_name.trim();
+        if company_name.is_empty() || company_name.len() > 255 {
+            return Err(DomainError::ValidationError(
+                "company_name is required and must be at most 255 characters".into(),
+            ));
+        }
+        let trimmed = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let phone = trimmed(request.phone);
+        let email = trimmed(request.email);
+        let tax_id = trimmed(request.tax_id);
+        if phone.as_ref().is_some_and(|p| p.len() > 50) {
+            return Err(DomainError::ValidationError(
+                "phone must be at most 50 characters".into(),
+            ));
+        }
+        if email
+            .as_ref()
+            .is_some_and(|e| e.len() > 255 || !e.contains('@'))
+        {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.email_invalid",
+                &[],
+            )));
+        }
+        if tax_id.as_ref().is_some_and(|t| t.len() > 50) {
+            return Err(DomainError::ValidationError(
+                "tax_id must be at most 50 characters".into(),
+            ));
+        }
+
+        self.company_name = company_name.to_string();
+        self.address = request.address;
+        self.phone = phone;
+        self.email = email;
+        self.tax_id = tax_id;
+        self.footer_text = trimmed(request.footer_text);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Lines of the company block under the logo
+    pub fn letterhead_lines(&self) -> Vec<String> {
+        let mut lines = vec![self.company_name.clone()];
+        if let Some(address) = &self.address {
+            lines.extend(address.lines());
+        }
+        let contact = [&self.phone, &self.email]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("  ");
+        if !contact.is_empty() {
+            lines.push(contact);
+        }
+        if let Some(tax_id) = &self.tax_id {
+            lines.push(format!("Tax ID: {}", tax_id));
+        }
+        lines
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateDocumentSettingsRequest {
+    pub company_name: String,
+    pub address: Option<LocationAddress>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub tax_id: Option<String>,
+    pub footer_text: Option<String>,
+}
+
+/// Check an uploaded logo is a PNG within the size limit and return its storage key
+pub fn logo_key(tenant_id: Option<Uuid>, data: &[u8]) -> Result<String, DomainError> {
+    if !data.starts_with(PNG_SIGNATURE) {
+        return Err(DomainError::ValidationError(
+            "Logo must be a PNG image".into(),
+        ));
+    }
+    if data.len() > MAX_LOGO_BYTES {
+        return Err(DomainError::ValidationError(
+            format!(
+                "Logo exceeds the {} MB limit",
+                MAX_LOGO_BYTES / (1024 * 1024)
+            )
+            .into(),
+        ));
+    }
+    let tenant = tenant_id.map_or_else(|| "shared".to_string(), |id| id.to_string());
+    Ok(format!("tenants/{}/documents/logo.png", tenant))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> UpdateDocumentSettingsRequest {
+        UpdateDocumentSettingsRequest {
+            company_name: " Acme Supply ".to_string(),
+            address: Some(LocationAddress {
+                line1: Some("1 Dock Road".to_string()),
+                city: Some("Springfield".to_string()),
+                postal_code: Some("12345".to_string()),
+                country: Some("US".to_string()),
+                ..Default::default()
+            }),
+            phone: Some("555-0100".to_string()),
+            email: Some("  ".to_string()),
+            tax_id: Some("US-99".to_string()),
+            footer_text: None,
+        }
+    }
+
+    #[test]
+    fn test_letterhead_lines() {
+        let settings = DocumentSettings::new(request()).unwrap();
+        assert_eq!(settings.email, None);
+        assert_eq!(
+            settings.letterhead_lines(),
+            vec![
+                "Acme Supply",
+                "1 Dock Road",
+                "Springfield 12345",
+                "US",
+                "555-0100",
+                "Tax ID: US-99"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_settings_and_logos() {
+        let mut invalid = request();
+        invalid.company_name = " ".to_string();
+        assert!(DocumentSettings::new(invalid).is_err());
+
+        let mut invalid = request();
+        invalid.email = Some("not-an-address".to_string());
+        assert!(DocumentSettings::new(invalid).is_err());
+
+        assert!(logo_key(None, b"GIF89a").is_err());
+        let tenant_id = Uuid::new_v4();
+        assert_eq!(
+            logo_key(Some(tenant_id), PNG_SIGNATURE).unwrap(),
+            format!("tenants/{}/documents/logo.png", tenant_id)
+        );
+    }
+}
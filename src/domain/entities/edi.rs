@@ -0,0 +1,468 @@
+use crate::shared::error::DomainError;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Which of our item identifiers a partner quotes in its documents
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EdiItemMatch {
+    #[default]
+    Sku,
+    Barcode,
+}
+
+impl EdiItemMatch {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdiItemMatch::Sku => "SKU",
+            EdiItemMatch::Barcode => "BARCODE",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "SKU" => Ok(EdiItemMatch::Sku),
+            "BARCODE" => Ok(EdiItemMatch::Barcode),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid item match: {}. Must be one of: SKU, BARCODE", s).into(),
+            )),
+        }
+    }
+}
+
+/// A partner we exchange X12 documents with, and how its documents map onto ours
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradingPartner {
+    pub id: Uuid,
+    pub name: String,
+    /// The partner's ISA qualifier and ID, which identify its inbound interchanges
+    pub interchange_id_qualifier: String,
+    pub interchange_id: String,
+    /// The partner's GS application code
+    pub application_id: String,
+    pub our_interchange_id_qualifier: String,
+    pub our_interchange_id: String,
+    pub our_application_id: String,
+    /// PO1/LIN product qualifier that carries our item identifier, e.g. VN or UP
+    pub item_id_qualifier: String,
+    pub item_match: EdiItemMatch,
+    /// Customer and location given to sales orders raised from this partner's 850s
+    pub customer_id: Option<Uuid>,
+    pub fulfillment_location_id: Option<Uuid>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TradingPartner {
+    pub fn new(request: CreateTradingPartnerRequest) -> Result<Self, DomainError> {
+        let now = Utc::now();
+        let interchange_id = request.interchange_id.trim().to_string();
+        let our_interchange_id = request.our_interchange_id.trim().to_string();
+        let partner = Self {
+            id: Uuid::new_v4(),
+            name: request.name.trim().to_string(),
+            interchange_id_qualifier: request.interchange_id_qualifier.trim().to_uppercase(),
+            application_id: request
+                .application_id
+                .map(|id| id.trim().to_string())
+                .unwrap_or_else(|| interchange_id.clone()),
+            interchange_id,
+            our_interchange_id_qualifier: request
+                .our_interchange_id_qualifier
+                .trim()
+                .to_uppercase(),
+            our_application_id: request
+                .our_application_id
+                .map(|id| id.trim().to_string())
+                .unwrap_or_else(|| our_interchange_id.clone()),
+            our_interchange_id,
+            item_id_qualifier: request
+                .item_id_qualifier
+                .map(|q| q.trim().to_uppercase())
+                .unwrap_or_else(|| "VN".to_string()),
+            item_match: request.item_match.unwrap_or_default(),
+            customer_id: request.customer_id,
+            fulfillment_location_id: request.fulfillment_location_id,
+            active: request.active.unwrap_or(true),
+            created_at: now,
+            updated_at: now,
+        };
+        partner.validate()?;
+        Ok(partner)
+    }
+
+    /// The partner's interchange ID is fixed, since it identifies inbound documents
+    pub fn update(&mut self, request: UpdateTradingPartnerRequest) -> Result<(), DomainError> {
+        if let Some(name) = request.name {
+            self.name = name.trim().to_string();
+        }
+        if let Some(application_id) = request.application_id {
+            self.application_id = application_id.trim().to_string();
+        }
+        if let Some(qualifier) = request.our_interchange_id_qualifier {
+            self.our_interchange_id_qualifier = qualifier.trim().to_uppercase();
+        }
+        if let Some(our_interchange_id) = request.our_interchange_id {
+            self.our_interchange_id = our_interchange_id.trim().to_string();
+        }
+        if let Some(our_application_id) = request.our_application_id {
+            self.our_application_id = our_application_id.trim().to_string();
+        }
+        if let Some(qualifier) = request.item_id_qualifier {
+            self.item_id_qualifier = qualifier.trim().to_uppercase();
+        }
+        if let Some(item_match) = request.item_match {
+            self.item_match = item_match;
+        }
+        if request.customer_id.is_some() {
+            self.customer_id = request.customer_id;
+        }
+        if request.fulfillment_location_id.is_some() {
+            self.fulfillment_location_id = request.fulfillment_location_id;
+        }
+        if let Some(active) = request.active {
+            self.active = active;
+        }
+        self.validate()?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), DomainError> {
+        if self.name.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Trading partner name cannot be empty".into(),
+            ));
+        }
+        for (field, value, max) in [
+            (
+                "interchange_id_qualifier",
+                &self.interchange_id_qualifier,
+                2,
+            ),
+            ("interchange_id", &self.interchange_id, 15),
+            ("application_id", &self.application_id, 15),
+            (
+                "our_interchange_id_qualifier",
+                &self.our_interchange_id_qualifier,
+                2,
+            ),
+            ("our_interchange_id", &self.our_interchange_id, 15),
+            ("our_application_id", &self.our_application_id, 15),
+            ("item_id_qualifier", &self.item_id_qualifier, 2),
+        ] {
+            if value.is_empty() || value.len() > max {
+                return Err(DomainError::ValidationError(
+                    format!("{} must be 1 to {} characters", field, max).into(),
+                ));
+            }
+            // These end up inside X12 segments, so they cannot carry delimiters
+            if !value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '-')
+            {
+                return Err(DomainError::ValidationError(
+                    format!(
+                        "{} may only contain letters, digits, spaces and hyphens",
+                        field
+                    )
+                    .into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTradingPartnerRequest {
+    pub name: String,
+    pub interchange_id_qualifier: String,
+    pub interchange_id: String,
+    pub application_id: Option<String>,
+    pub our_interchange_id_qualifier: String,
+    pub our_interchange_id: String,
+    pub our_application_id: Option<String>,
+    pub item_id_qualifier: Option<String>,
+    pub item_match: Option<EdiItemMatch>,
+    pub customer_id: Option<Uuid>,
+    pub fulfillment_location_id: Option<Uuid>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateTradingPartnerRequest {
+    pub name: Option<String>,
+    pub application_id: Option<String>,
+    pub our_interchange_id_qualifier: Option<String>,
+    pub our_interchange_id: Option<String>,
+    pub our_application_id: Option<String>,
+    pub item_id_qualifier: Option<String>,
+    pub item_match: Option<EdiItemMatch>,
+    pub customer_id: Option<Uuid>,
+    pub fulfillment_location_id: Option<Uuid>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum EdiDocumentType {
+    #[serde(rename = "850")]
+    PurchaseOrder,
+    #[serde(rename = "856")]
+    ShipNotice,
+    #[serde(rename = "810")]
+    Invoice,
+}
+
+impl EdiDocumentType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdiDocumentType::PurchaseOrder => "850",
+            EdiDocumentType::ShipNotice => "856",
+            EdiDocumentType::Invoice => "810",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s {
+            "850" => Ok(EdiDocumentType::PurchaseOrder),
+            "856" => Ok(EdiDocumentType::ShipNotice),
+            "810" => Ok(EdiDocumentType::Invoice),
+            _ => Err(DomainError::ValidationError(
+                format!(
+                    "Invalid EDI document type: {}. Must be one of: 850, 856, 810",
+                    s
+                )
+                .into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EdiDirection {
+    Inbound,
+    Outbound,
+}
+
+impl EdiDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdiDirection::Inbound => "INBOUND",
+            EdiDirection::Outbound => "OUTBOUND",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "INBOUND" => Ok(EdiDirection::Inbound),
+            "OUTBOUND" => Ok(EdiDirection::Outbound),
+            _ => Err(DomainError::ValidationError(
+                format!(
+                    "Invalid EDI direction: {}. Must be one of: INBOUND, OUTBOUND",
+                    s
+                )
+                .into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EdiDocumentStatus {
+    /// An inbound document whose transactions were applied
+    Processed,
+    /// An inbound document that was rejected; nothing was applied
+    Failed,
+    /// An outbound document ready for the partner
+    Generated,
+}
+
+impl EdiDocumentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdiDocumentStatus::Processed => "PROCESSED",
+            EdiDocumentStatus::Failed => "FAILED",
+            EdiDocumentStatus::Generated => "GENERATED",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "PROCESSED" => Ok(EdiDocumentStatus::Processed),
+            "FAILED" => Ok(EdiDocumentStatus::Failed),
+            "GENERATED" => Ok(EdiDocumentStatus::Generated),
+            _ => Err(DomainError::ValidationError(
+                format!(
+                    "Invalid EDI document status: {}. Must be one of: PROCESSED, FAILED, GENERATED",
+                    s
+                )
+                .into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EdiReferenceType {
+    SalesOrder,
+    Shipment,
+}
+
+impl EdiReferenceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdiReferenceType::SalesOrder => "SALES_ORDER",
+            EdiReferenceType::Shipment => "SHIPMENT",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "SALES_ORDER" => Ok(EdiReferenceType::SalesOrder),
+            "SHIPMENT" => Ok(EdiReferenceType::Shipment),
+            _ => Err(DomainError::ValidationError(
+                format!(
+                    "Invalid EDI reference type: {}. Must be one of: SALES_ORDER, SHIPMENT",
+                    s
+                )
+                .into(),
+            )),
+        }
+    }
+}
+
+/// An X12 interchange received from or sent to a partner, kept verbatim
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdiDocument {
+    pub id: Uuid,
+    pub partner_id: Option<Uuid>,
+    pub document_type: EdiDocumentType,
+    pub direction: EdiDirection,
+    pub status: EdiDocumentStatus,
+    /// ISA13 interchange control number
+    pub control_number: Option<String>,
+    pub reference_type: Option<EdiReferenceType>,
+    /// Sales orders raised from an 850, or the shipment an 856/810 describes
+    pub reference_ids: Vec<Uuid>,
+    pub error: Option<String>,
+    /// Served separately from the document's metadata
+    #[serde(skip)]
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl EdiDocument {
+    pub fn inbound(partner_id: Option<Uuid>, content: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            partner_id,
+            document_type: EdiDocumentType::PurchaseOrder,
+            direction: EdiDirection::Inbound,
+            status: EdiDocumentStatus::Processed,
+            control_number: None,
+            reference_type: None,
+            reference_ids: Vec::new(),
+            error: None,
+            content,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn outbound(
+        partner_id: Uuid,
+        document_type: EdiDocumentType,
+        control_number: i64,
+        shipment_id: Uuid,
+        content: String,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            partner_id: Some(partner_id),
+            document_type,
+            direction: EdiDirection::Outbound,
+            status: EdiDocumentStatus::Generated,
+            control_number: Some(format!("{:09}", control_number)),
+            reference_type: Some(EdiReferenceType::Shipment),
+            reference_ids: vec![shipment_id],
+            error: None,
+            content,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn processed(&mut self, control_number: String, sales_order_ids: Vec<Uuid>) {
+        self.status = EdiDocumentStatus::Processed;
+        self.control_number = Some(control_number);
+        self.reference_type = Some(EdiReferenceType::SalesOrder);
+        self.reference_ids = sales_order_ids;
+    }
+
+    pub fn failed(&mut self, error: &DomainError) {
+        self.status = EdiDocumentStatus::Failed;
+        self.error = Some(error.to_string());
+    }
+}
+
+/// Ties a sales order to the 850 it was raised from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdiSalesOrderLink {
+    pub sales_order_id: Uuid,
+    pub partner_id: Uuid,
+    pub document_id: Uuid,
+    pub po_number: String,
+    pub po_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ListEdiDocumentsQuery {
+    pub partner_id: Option<Uuid>,
+    pub document_type: Option<String>,
+    pub direction: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> CreateTradingPartnerRequest {
+        CreateTradingPartnerRequest {
+            name: "Acme Retail".to_string(),
+            interchange_id_qualifier: "zz".to_string(),
+            interchange_id: "ACMERETAIL".to_string(),
+            application_id: None,
+            our_interchange_id_qualifier: "ZZ".to_string(),
+            our_interchange_id: "TWH".to_string(),
+            our_application_id: None,
+            item_id_qualifier: None,
+            item_match: None,
+            customer_id: None,
+            fulfillment_location_id: None,
+            active: None,
+        }
+    }
+
+    #[test]
+    fn test_partner_defaults_application_ids_and_item_mapping() {
+        let partner = TradingPartner::new(request()).unwrap();
+        assert_eq!(partner.interchange_id_qualifier, "ZZ");
+        assert_eq!(partner.application_id, "ACMERETAIL");
+        assert_eq!(partner.our_application_id, "TWH");
+        assert_eq!(partner.item_id_qualifier, "VN");
+        assert_eq!(partner.item_match, EdiItemMatch::Sku);
+    }
+
+    #[test]
+    fn test_partner_ids_cannot_carry_x12_delimiters() {
+        let mut request = request();
+        request.interchange_id = "ACME*RETAIL".to_string();
+        assert!(TradingPartner::new(request).is_err());
+    }
+}
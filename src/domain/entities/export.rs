@@ -1,11 +1,18 @@
+use crate::domain::entities::inventory::MovementType;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// Export job types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ExportType {
     StockCsv,
+    Report,
+    TenantSnapshot,
 }
 
 /// Request to create a stock CSV export
@@ -24,6 +31,17 @@ pub struct CreateExportResponse {
     pub created_at: DateTime<Utc>,
 }
 
+/// Job type under which stock CSV exports are enqueued
+pub const STOCK_CSV_EXPORT_JOB_TYPE: &str = "stock_csv_export";
+
+/// Job type under which report exports are enqueued
+pub const GENERATE_REPORT_JOB_TYPE: &str = "generate_report";
+
+/// Where a job's finished export file is stored
+pub fn export_object_key(tenant_id: Uuid, job_id: &str, file_name: &str) -> String {
+    format!("tenants/{}/exports/{}/{}", tenant_id, job_id, file_name)
+}
+
 /// Export job payload for stock CSV
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StockCsvExportPayload {
@@ -36,4 +54,573 @@ pub struct CsvExportResult {
     pub filename: String,
     pub record_count: i32,
     pub file_size_bytes: i64,
+    /// Signed link to the stored file; stops working once it expires
+    pub download_url: String,
+}
+
+/// A fresh signed link to a finished export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportDownloadResponse {
+    pub job_id: String,
+    pub download_url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Filters for the streaming stock movement CSV export
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StockMovementExportFilter {
+    /// Inclusive lower bound on `created_at`
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `created_at`, so consecutive ranges don't overlap
+    pub to: Option<DateTime<Utc>>,
+    pub item_id: Option<Uuid>,
+    pub location_id: Option<Uuid>,
+    /// One of the movement type names, e.g. `inbound`
+    pub movement_type: Option<String>,
+}
+
+impl StockMovementExportFilter {
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from >= to {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.from_before_to",
+                    &[],
+                )));
+            }
+        }
+        if let Some(movement_type) = &self.movement_type {
+            MovementType::from_str(movement_type)?;
+        }
+        Ok(())
+    }
+}
+
+/// File format of a report export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+    Xlsx,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+}
+
+/// Languages report headers can be translated into
+pub const SUPPORTED_EXPORT_LOCALES: &[&str] = &["en", "es", "pt", "fr", "de"];
+
+/// Payload of a `generate_report` job, also the body of `POST /exports/reports`.
+/// Anything besides the fields below is handed to the report as its parameters,
+/// e.g. `{"report": "low_stock", "threshold": 5, "format": "csv"}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportExportRequest {
+    /// A report or collection, e.g. `low_stock`, `stock_valuation` or `items`
+    pub report: String,
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Flattened field paths to include, in order, e.g. `["item.sku", "stock.quantity_on_hand"]`.
+    /// Defaults to every field of the rows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<String>>,
+    /// Language of the column headers; defaults to English
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Header overrides by column path, applied over the localized ones
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    #[serde(flatten)]
+    pub params: Map<String, Value>,
+}
+
+impl ReportExportRequest {
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if let Some(locale) = &self.locale {
+            if !SUPPORTED_EXPORT_LOCALES.contains(&locale.as_str()) {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.unsupported_locale",
+                    &[
+                        ("locale", &locale),
+                        ("expected", &SUPPORTED_EXPORT_LOCALES.join(", ")),
+                    ],
+                )));
+            }
+        }
+        if let Some(columns) = &self.columns {
+            if columns.is_empty() || columns.iter().any(|column| column.trim().is_empty()) {
+                return Err(DomainError::ValidationError(
+                    "columns must list at least one non-empty field path".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Name of the file the finished export is stored under
+    pub fn file_name(&self) -> String {
+        format!("{}.{}", self.report, self.format.extension())
+    }
+
+    pub fn params(&self) -> Value {
+        Value::Object(self.params.clone())
+    }
+}
+
+/// Report rows laid out as a grid of cells under headed columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportTable {
+    pub columns: Vec<ExportColumn>,
+    /// One cell per column; missing fields are `Null`
+    pub rows: Vec<Vec<Value>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportColumn {
+    /// Dotted path of the field in the report row, e.g. `item.sku`
+    pub path: String,
+    pub header: String,
+}
+
+impl ExportTable {
+    /// Flatten nested objects into dotted paths and pick the requested columns.
+    /// Arrays are kept whole in a single cell.
+    pub fn build(rows: &[Value], request: &ReportExportRequest) -> Result<Self, DomainError> {
+        let flattened: Vec<Map<String, Value>> = rows
+            .iter()
+            .map(|row| {
+                let mut cells = Map::new();
+                flatten_into(&mut cells, None, row);
+                cells
+            })
+            .collect();
+
+        let paths = match &request.columns {
+            Some(columns) => {
+                // An empty report has no fields to check the selection against
+                if let Some(unknown) = columns.iter().find(|column| {
+                    !flattened.is_empty() && !flattened.iter().any(|row| row.contains_key(*column))
+                }) {
+                    return Err(DomainError::ValidationError(Message::localized(
+                        "error.report_missing_field",
+                        &[("report", &request.report), ("field", &unknown)],
+                    )));
+                }
+                columns.clone()
+            }
+            None => {
+                let mut paths: Vec<String> = Vec::new();
+                for row in &flattened {
+                    for key in row.keys() {
+                        if !paths.contains(key) {
+                            paths.push(key.clone());
+                        }
+                    }
+                }
+                paths
+            }
+        };
+
+        let locale = request.locale.as_deref().unwrap_or("en");
+        let mut columns: Vec<ExportColumn> = Vec::with_capacity(paths.len());
+        for path in paths {
+            let header = match request.headers.get(&path) {
+                Some(header) => header.clone(),
+                None => {
+                    let (parent, field) = match path.rsplit_once('.') {
+                        Some((parent, field)) => (Some(parent), field),
+                        None => (None, path.as_str()),
+                    };
+                    let header = localized_header(field, locale);
+                    // Tell apart e.g. item.id and stock.id
+                    match parent {
+                        Some(parent) if columns.iter().any(|c| c.header == header) => {
+                            format!("{} ({})", header, parent)
+                        }
+                        _ => header,
+                    }
+                }
+            };
+            columns.push(ExportColumn { path, header });
+        }
+
+        let rows = flattened
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| row.get(&column.path).cloned().unwrap_or(Value::Null))
+                    .collect()
+            })
+            .collect();
+        Ok(Self { columns, rows })
+    }
+}
+
+fn flatten_into(cells: &mut Map<String, Value>, prefix: Option<&str>, value: &Value) {
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                let path = match prefix {
+                    Some(prefix) => format!("{}.{}", prefix, key),
+                    None => key.clone(),
+                };
+                flatten_into(cells, Some(&path), value);
+            }
+        }
+        _ => {
+            cells.insert(prefix.unwrap_or("value").to_string(), value.clone());
+        }
+    }
+}
+
+/// Header for a field name in `locale`, falling back to the field name spelled out
+pub fn localized_header(field: &str, locale: &str) -> String {
+    // (field, en, es, pt, fr, de)
+    const HEADERS: &[(&str, &str, &str, &str, &str, &str)] = &[
+        ("id", "ID", "ID", "ID", "ID", "ID"),
+        ("sku", "SKU", "SKU", "SKU", "SKU", "SKU"),
+        ("name", "Name", "Nombre", "Nome", "Nom", "Name"),
+        (
+            "description",
+            "Description",
+            "Descripción",
+            "Descrição",
+            "Description",
+            "Beschreibung",
+        ),
+        (
+            "category",
+            "Category",
+            "Categoría",
+            "Categoria",
+            "Catégorie",
+            "Kategorie",
+        ),
+        (
+            "barcode",
+            "Barcode",
+            "Código de barras",
+            "Código de barras",
+            "Code-barres",
+            "Barcode",
+        ),
+        (
+            "unit_of_measure",
+            "Unit of measure",
+            "Unidad de medida",
+            "Unidade de medida",
+            "Unité de mesure",
+            "Maßeinheit",
+        ),
+        (
+            "cost_price",
+            "Cost price",
+            "Precio de coste",
+            "Preço de custo",
+            "Prix de revient",
+            "Einstandspreis",
+        ),
+        (
+            "sale_price",
+            "Sale price",
+            "Precio de venta",
+            "Preço de venda",
+            "Prix de vente",
+            "Verkaufspreis",
+        ),
+        (
+            "unit_cost",
+            "Unit cost",
+            "Coste unitario",
+            "Custo unitário",
+            "Coût unitaire",
+            "Stückkosten",
+        ),
+        (
+            "unit_price",
+            "Unit price",
+            "Precio unitario",
+            "Preço unitário",
+            "Prix unitaire",
+            "Stückpreis",
+        ),
+        (
+            "reorder_point",
+            "Reorder point",
+            "Punto de pedido",
+            "Ponto de reposição",
+            "Seuil de réapprovisionnement",
+            "Meldebestand",
+        ),
+        (
+            "reorder_qty",
+            "Reorder quantity",
+            "Cantidad de pedido",
+            "Quantidade de reposição",
+            "Quantité de réapprovisionnement",
+            "Bestellmenge",
+        ),
+        (
+            "suggested_qty",
+            "Suggested quantity",
+            "Cantidad sugerida",
+            "Quantidade sugerida",
+            "Quantité suggérée",
+            "Vorgeschlagene Menge",
+        ),
+        (
+            "quantity_on_hand",
+            "On hand",
+            "Existencias",
+            "Em estoque",
+            "En stock",
+            "Bestand",
+        ),
+        (
+            "quantity_reserved",
+            "Reserved",
+            "Reservado",
+            "Reservado",
+            "Réservé",
+            "Reserviert",
+        ),
+        (
+            "quantity_available",
+            "Available",
+            "Disponible",
+            "Disponível",
+            "Disponible",
+            "Verfügbar",
+        ),
+        (
+            "quantity_quarantine",
+            "Quarantined",
+            "En cuarentena",
+            "Em quarentena",
+            "En quarantaine",
+            "In Quarantäne",
+        ),
+        (
+            "quantity_damaged",
+            "Damaged",
+            "Dañado",
+            "Danificado",
+            "Endommagé",
+            "Beschädigt",
+        ),
+        (
+            "quantity_in_transit",
+            "In transit",
+            "En tránsito",
+            "Em trânsito",
+            "En transit",
+            "Unterwegs",
+        ),
+        (
+            "quantity_scrapped",
+            "Scrapped",
+            "Desechado",
+            "Descartado",
+            "Mis au rebut",
+            "Verschrottet",
+        ),
+        (
+            "valuation",
+            "Valuation",
+            "Valoración",
+            "Valorização",
+            "Valorisation",
+            "Bewertung",
+        ),
+        ("value", "Value", "Valor", "Valor", "Valeur", "Wert"),
+        (
+            "line_total",
+            "Line total",
+            "Total de línea",
+            "Total da linha",
+            "Total de la ligne",
+            "Zeilensumme",
+        ),
+        (
+            "total_amount",
+            "Total amount",
+            "Importe total",
+            "Valor total",
+            "Montant total",
+            "Gesamtbetrag",
+        ),
+        ("status", "Status", "Estado", "Status", "Statut", "Status"),
+        ("active", "Active", "Activo", "Ativo", "Actif", "Aktiv"),
+        ("code", "Code", "Código", "Código", "Code", "Code"),
+        (
+            "item_id",
+            "Item ID",
+            "ID de artículo",
+            "ID do item",
+            "ID d'article",
+            "Artikel-ID",
+        ),
+        (
+            "location_id",
+            "Location ID",
+            "ID de ubicación",
+            "ID do local",
+            "ID d'emplacement",
+            "Lagerort-ID",
+        ),
+        (
+            "supplier_id",
+            "Supplier ID",
+            "ID de proveedor",
+            "ID do fornecedor",
+            "ID fournisseur",
+            "Lieferanten-ID",
+        ),
+        (
+            "supplier_name",
+            "Supplier",
+            "Proveedor",
+            "Fornecedor",
+            "Fournisseur",
+            "Lieferant",
+        ),
+        (
+            "customer_id",
+            "Customer ID",
+            "ID de cliente",
+            "ID do cliente",
+            "ID client",
+            "Kunden-ID",
+        ),
+        (
+            "po_number",
+            "PO number",
+            "Número de pedido de compra",
+            "Número do pedido de compra",
+            "N° de commande d'achat",
+            "Bestellnummer",
+        ),
+        (
+            "so_number",
+            "SO number",
+            "Número de pedido de venta",
+            "Número do pedido de venda",
+            "N° de commande client",
+            "Auftragsnummer",
+        ),
+        (
+            "created_at",
+            "Created at",
+            "Creado el",
+            "Criado em",
+            "Créé le",
+            "Erstellt am",
+        ),
+        (
+            "updated_at",
+            "Updated at",
+            "Actualizado el",
+            "Atualizado em",
+            "Mis à jour le",
+            "Aktualisiert am",
+        ),
+    ];
+    let index = match locale {
+        "es" => 2,
+        "pt" => 3,
+        "fr" => 4,
+        "de" => 5,
+        _ => 1,
+    };
+    match HEADERS.iter().find(|entry| entry.0 == field) {
+        Some(entry) => [entry.1, entry.2, entry.3, entry.4, entry.5][index - 1].to_string(),
+        None => {
+            let spelled = field.replace('_', " ");
+            let mut chars = spelled.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => spelled,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(value: Value) -> ReportExportRequest {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_report_request_keeps_unknown_fields_as_params() {
+        let request = request(json!({
+            "report": "low_stock",
+            "threshold": 5,
+            "format": "xlsx",
+            "locale": "es"
+        }));
+        assert_eq!(request.format, ExportFormat::Xlsx);
+        assert_eq!(request.params(), json!({ "threshold": 5 }));
+        assert_eq!(request.file_name(), "low_stock.xlsx");
+        assert!(request.validate().is_ok());
+
+        let mut unsupported = request.clone();
+        unsupported.locale = Some("xx".to_string());
+        assert!(unsupported.validate().is_err());
+    }
+
+    #[test]
+    fn test_table_flattens_and_selects_columns() {
+        let rows = vec![
+            json!({ "item": { "id": 1, "sku": "A" }, "stock": { "id": 7, "quantity_on_hand": 3 } }),
+            json!({ "item": { "id": 2, "sku": "B" }, "stock": null }),
+        ];
+
+        let all = ExportTable::build(&rows, &request(json!({ "report": "low_stock" }))).unwrap();
+        let headers: Vec<_> = all.columns.iter().map(|c| c.header.as_str()).collect();
+        assert_eq!(headers, ["ID", "SKU", "ID (stock)", "On hand", "Stock"]);
+        assert_eq!(all.rows[1][2], Value::Null);
+
+        let picked = ExportTable::build(
+            &rows,
+            &request(json!({
+                "report": "low_stock",
+                "columns": ["item.sku", "stock.quantity_on_hand"],
+                "locale": "de",
+                "headers": { "item.sku": "Artikelnummer" }
+            })),
+        )
+        .unwrap();
+        let headers: Vec<_> = picked.columns.iter().map(|c| c.header.as_str()).collect();
+        assert_eq!(headers, ["Artikelnummer", "Bestand"]);
+        assert_eq!(picked.rows[0], vec![json!("A"), json!(3)]);
+
+        let unknown = ExportTable::build(
+            &rows,
+            &request(json!({ "report": "low_stock", "columns": ["item.colour"] })),
+        );
+        assert!(unknown.is_err());
+    }
 }
@@ -0,0 +1,320 @@
+use crate::domain::entities::item::Item;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Days of sales history a forecast looks back over by default, and at most
+pub const DEFAULT_HISTORY_DAYS: u32 = 90;
+pub const MAX_HISTORY_DAYS: u32 = 365;
+/// Most recent days a moving average covers by default
+pub const DEFAULT_WINDOW_DAYS: u32 = 28;
+pub const DEFAULT_HORIZON_DAYS: u32 = 30;
+/// Supplier lead time for items without `metadata.lead_time_days`
+pub const DEFAULT_LEAD_TIME_DAYS: u32 = 7;
+
+/// Weight exponential smoothing gives the latest day by default
+pub fn default_alpha() -> Decimal {
+    Decimal::new(3, 1)
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ForecastMethod {
+    #[default]
+    MovingAverage,
+    ExponentialSmoothing,
+}
+
+impl ForecastMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ForecastMethod::MovingAverage => "MOVING_AVERAGE",
+            ForecastMethod::ExponentialSmoothing => "EXPONENTIAL_SMOOTHING",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "MOVING_AVERAGE" => Ok(ForecastMethod::MovingAverage),
+            "EXPONENTIAL_SMOOTHING" => Ok(ForecastMethod::ExponentialSmoothing),
+            _ => Err(DomainError::ValidationError(format!(
+                "Invalid forecast method: {}. Must be one of: MOVING_AVERAGE, EXPONENTIAL_SMOOTHING",
+                s
+            ).into())),
+        }
+    }
+}
+
+/// How to forecast; anything left out takes its default
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ForecastQuery {
+    /// `MOVING_AVERAGE` (the default) or `EXPONENTIAL_SMOOTHING`
+    pub method: Option<String>,
+    pub history_days: Option<u32>,
+    /// Moving average only
+    pub window_days: Option<u32>,
+    /// Exponential smoothing only, between 0 (exclusive) and 1
+    pub alpha: Option<Decimal>,
+    pub horizon_days: Option<u32>,
+    /// Overrides the item's `metadata.lead_time_days`
+    pub lead_time_days: Option<u32>,
+    /// Days of demand held back as safety stock in the reorder point
+    pub safety_stock_days: Option<u32>,
+}
+
+/// A `ForecastQuery` with its defaults filled in and checked
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForecastParams {
+    pub method: ForecastMethod,
+    pub history_days: u32,
+    pub window_days: Option<u32>,
+    pub alpha: Option<Decimal>,
+    pub horizon_days: u32,
+    pub lead_time_days: u32,
+    pub safety_stock_days: u32,
+}
+
+impl ForecastParams {
+    pub fn resolve(query: &ForecastQuery, item: &Item) -> Result<Self, DomainError> {
+        let method = query
+            .method
+            .as_deref()
+            .map(ForecastMethod::from_str)
+            .transpose()?
+            .unwrap_or_default();
+        let history_days = query.history_days.unwrap_or(DEFAULT_HISTORY_DAYS);
+        if !(1..=MAX_HISTORY_DAYS).contains(&history_days) {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.field_between",
+                &[
+                    ("field", &"history_days"),
+                    ("min", &1),
+                    ("max", &MAX_HISTORY_DAYS),
+                ],
+            )));
+        }
+
+        let (window_days, alpha) = match method {
+            ForecastMethod::MovingAverage => {
+                let window = query
+                    .window_days
+                    .unwrap_or(DEFAULT_WINDOW_DAYS)
+                    .min(history_days);
+                if window == 0 {
+                    return Err(DomainError::ValidationError(
+                        "window_days must be positive".into(),
+                    ));
+                }
+                (Some(window), None)
+            }
+            ForecastMethod::ExponentialSmoothing => {
+                let alpha = query.alpha.unwrap_or_else(default_alpha);
+                if alpha <= Decimal::ZERO || alpha > Decimal::ONE {
+                    return Err(DomainError::ValidationError(
+                        "alpha must be greater than 0 and at most 1".into(),
+                    ));
+                }
+                (None, Some(alpha))
+            }
+        };
+
+        Ok(Self {
+            method,
+            history_days,
+            window_days,
+            alpha,
+            horizon_days: query.horizon_days.unwrap_or(DEFAULT_HORIZON_DAYS),
+            lead_time_days: query
+                .lead_time_days
+                .or_else(|| item_lead_time_days(item))
+                .unwrap_or(DEFAULT_LEAD_TIME_DAYS),
+            safety_stock_days: query.safety_stock_days.unwrap_or(0),
+        })
+    }
+
+    /// First day of history, for a forecast made on `today`. Today itself is
+    /// left out since its sales aren't in yet.
+    pub fn history_start(&self, today: NaiveDate) -> NaiveDate {
+        today - Duration::days(i64::from(self.history_days))
+    }
+}
+
+/// Supplier lead time an item's `metadata` records (`{"lead_time_days": 14}`)
+pub fn item_lead_time_days(item: &Item) -> Option<u32> {
+    item.metadata
+        .as_ref()?
+        .get("lead_time_days")?
+        .as_u64()
+        .and_then(|days| u32::try_from(days).ok())
+}
+
+/// One value per day from `start`, `days` long, with days that had no sales at zero
+pub fn daily_series(sales: &[(NaiveDate, Decimal)], start: NaiveDate, days: u32) -> Vec<Decimal> {
+    let mut series = vec![Decimal::ZERO; days as usize];
+    for (date, qty) in sales {
+        let offset = (*date - start).num_days();
+        if let Some(slot) = usize::try_from(offset)
+            .ok()
+            .and_then(|offset| series.get_mut(offset))
+        {
+            *slot += *qty;
+        }
+    }
+    series
+}
+
+/// Mean of the last `window` days
+pub fn moving_average(series: &[Decimal], window: u32) -> Decimal {
+    let window = (window as usize).min(series.len());
+    if window == 0 {
+        return Decimal::ZERO;
+    }
+    series[series.len() - window..].iter().sum::<Decimal>() / Decimal::from(window)
+}
+
+/// Simple exponential smoothing, seeded with the first day
+pub fn exponential_smoothing(series: &[Decimal], alpha: Decimal) -> Decimal {
+    let Some((first, rest)) = series.split_first() else {
+        return Decimal::ZERO;
+    };
+    rest.iter().fold(*first, |level, qty| {
+        alpha * *qty + (Decimal::ONE - alpha) * level
+    })
+}
+
+/// Expected demand for an item, and the reorder point that covers it over the
+/// supplier's lead time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DemandForecast {
+    pub id: Uuid,
+    pub item_id: Uuid,
+    pub method: ForecastMethod,
+    pub history_days: u32,
+    pub window_days: Option<u32>,
+    pub alpha: Option<Decimal>,
+    /// Units sold over the history
+    pub history_total: Decimal,
+    pub daily_demand: Decimal,
+    pub horizon_days: u32,
+    /// Units expected to sell over the horizon
+    pub forecast_qty: Decimal,
+    pub lead_time_days: u32,
+    pub safety_stock_days: u32,
+    /// Demand over the lead time plus safety stock, rounded up to what the
+    /// item can be counted in
+    pub reorder_point: Decimal,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl DemandForecast {
+    /// Forecast from `series`, one value per day of sales, oldest first
+    pub fn compute(item: &Item, params: &ForecastParams, series: &[Decimal]) -> Self {
+        let daily_demand = match params.method {
+            ForecastMethod::MovingAverage => {
+                moving_average(series, params.window_days.unwrap_or(DEFAULT_WINDOW_DAYS))
+            }
+            ForecastMethod::ExponentialSmoothing => {
+                exponential_smoothing(series, params.alpha.unwrap_or_else(default_alpha))
+            }
+        }
+        .round_dp(4);
+        let precision = item.quantity_precision;
+        let cover_days = Decimal::from(params.lead_time_days + params.safety_stock_days);
+
+        Self {
+            id: Uuid::new_v4(),
+            item_id: item.id,
+            method: params.method,
+            history_days: params.history_days,
+            window_days: params.window_days,
+            alpha: params.alpha,
+            history_total: series.iter().sum(),
+            daily_demand,
+            horizon_days: params.horizon_days,
+            forecast_qty: (daily_demand * Decimal::from(params.horizon_days))
+                .round_dp_with_strategy(precision, RoundingStrategy::AwayFromZero),
+            lead_time_days: params.lead_time_days,
+            safety_stock_days: params.safety_stock_days,
+            reorder_point: (daily_demand * cover_days)
+                .round_dp_with_strategy(precision, RoundingStrategy::AwayFromZero),
+            computed_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn item() -> Item {
+        Item::new(
+            Uuid::new_v4(),
+            "SKU-1".to_string(),
+            "Widget".to_string(),
+            "each".to_string(),
+            Decimal::ONE,
+        )
+        .unwrap()
+    }
+
+    fn series(values: &[i64]) -> Vec<Decimal> {
+        values.iter().map(|v| Decimal::from(*v)).collect()
+    }
+
+    #[test]
+    fn test_daily_series_fills_gaps() {
+        let start = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let sales = [
+            (start, Decimal::from(2)),
+            (start + Duration::days(2), Decimal::from(5)),
+            (start + Duration::days(9), Decimal::from(1)),
+        ];
+        assert_eq!(daily_series(&sales, start, 4), series(&[2, 0, 5, 0]));
+    }
+
+    #[test]
+    fn test_moving_average_and_smoothing() {
+        let history = series(&[10, 0, 4, 8]);
+        assert_eq!(moving_average(&history, 2), Decimal::from(6));
+        assert_eq!(moving_average(&history, 30), Decimal::new(55, 1));
+        // 10 -> 5 -> 4.5 -> 6.25
+        assert_eq!(
+            exponential_smoothing(&history, Decimal::new(5, 1)),
+            Decimal::new(625, 2)
+        );
+        assert_eq!(exponential_smoothing(&[], Decimal::ONE), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_reorder_point_covers_lead_time() {
+        let mut widget = item();
+        widget.metadata = Some(json!({ "lead_time_days": 10 }));
+        let params = ForecastParams::resolve(
+            &ForecastQuery {
+                window_days: Some(4),
+                safety_stock_days: Some(2),
+                ..ForecastQuery::default()
+            },
+            &widget,
+        )
+        .unwrap();
+        assert_eq!(params.lead_time_days, 10);
+
+        let forecast = DemandForecast::compute(&widget, &params, &series(&[1, 2, 1, 1]));
+        assert_eq!(forecast.daily_demand, Decimal::new(125, 2));
+        // 1.25 a day over 12 days, in whole units
+        assert_eq!(forecast.reorder_point, Decimal::from(15));
+        assert_eq!(forecast.forecast_qty, Decimal::new(38, 0));
+
+        let bad_alpha = ForecastQuery {
+            method: Some("exponential_smoothing".to_string()),
+            alpha: Some(Decimal::from(2)),
+            ..ForecastQuery::default()
+        };
+        assert!(ForecastParams::resolve(&bad_alpha, &widget).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A tenant's SSCC numbering: extension digit, GS1 company prefix, then a
+/// serial reference filling the remaining digits before the check digit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsccSequence {
+    pub company_prefix: String,
+    pub extension_digit: i16,
+    /// Serial reference handed to the next carton
+    pub next_serial: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SsccSequence {
+    pub fn new(request: UpdateSsccSequenceRequest) -> Result<Self, DomainError> {
+        let sequence = Self {
+            company_prefix: request.company_prefix.trim().to_string(),
+            extension_digit: request.extension_digit.unwrap_or(0),
+            next_serial: request.next_serial.unwrap_or(1),
+            updated_at: Utc::now(),
+        };
+        sequence.validate()?;
+        Ok(sequence)
+    }
+
+    /// Change the prefix or extension digit; the serial carries on unless reset
+    pub fn update(&mut self, request: UpdateSsccSequenceRequest) -> Result<(), DomainError> {
+        self.company_prefix = request.company_prefix.trim().to_string();
+        if let Some(extension_digit) = request.extension_digit {
+            self.extension_digit = extension_digit;
+        }
+        if let Some(next_serial) = request.next_serial {
+            self.next_serial = next_serial;
+        }
+        self.validate()?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Largest serial reference that fits beside the company prefix
+    pub fn max_serial(&self) -> i64 {
+        10_i64.pow(16 - self.company_prefix.len() as u32) - 1
+    }
+
+    /// The 18-digit SSCC for a serial reference, check digit included
+    pub fn sscc(&self, serial: i64) -> Result<String, DomainError> {
+        if serial < 0 || serial > self.max_serial() {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "SSCC serial references under company prefix {} are exhausted",
+                    self.company_prefix
+                )
+                .into(),
+            ));
+        }
+
+        let body = format!(
+            "{}{}{:0width$}",
+            self.extension_digit,
+            self.company_prefix,
+            serial,
+            width = 16 - self.company_prefix.len()
+        );
+        Ok(format!("{}{}", body, gs1_check_digit(&body)))
+    }
+
+    fn validate(&self) -> Result<(), DomainError> {
+        if !(7..=10).contains(&self.company_prefix.len())
+            || !self.company_prefix.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(DomainError::ValidationError(
+                "company_prefix must be a GS1 company prefix of 7 to 10 digits".into(),
+            ));
+        }
+        if !(0..=9).contains(&self.extension_digit) {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.field_between",
+                &[("field", &"extension_digit"), ("min", &0), ("max", &9)],
+            )));
+        }
+        if self.next_serial < 0 || self.next_serial > self.max_serial() {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.field_between",
+                &[
+                    ("field", &"next_serial"),
+                    ("min", &0),
+                    ("max", &self.max_serial()),
+                ],
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateSsccSequenceRequest {
+    pub company_prefix: String,
+    pub extension_digit: Option<i16>,
+    pub next_serial: Option<i64>,
+}
+
+/// GS1 mod-10 check digit: weights 3 and 1 alternate from the rightmost digit
+pub fn gs1_check_digit(digits: &str) -> u32 {
+    let sum: u32 = digits
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 0 { d * 3 } else { d })
+        .sum();
+    (10 - sum % 10) % 10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(company_prefix: &str) -> SsccSequence {
+        SsccSequence::new(UpdateSsccSequenceRequest {
+            company_prefix: company_prefix.to_string(),
+            extension_digit: Some(1),
+            next_serial: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sscc_matches_gs1_example() {
+        // Worked example from the GS1 General Specifications
+        assert_eq!(
+            sequence("0614141").sscc(234567890).unwrap(),
+            "106141412345678908"
+        );
+        assert_eq!(gs1_check_digit("10614141234567890"), 8);
+    }
+
+    #[test]
+    fn test_sscc_serial_range_depends_on_prefix_length() {
+        let long_prefix = sequence("0614141000");
+        assert_eq!(long_prefix.max_serial(), 999_999);
+        assert_eq!(long_prefix.sscc(42).unwrap().len(), 18);
+        assert!(long_prefix.sscc(1_000_000).is_err());
+
+        assert!(SsccSequence::new(UpdateSsccSequenceRequest {
+            company_prefix: "12AB567".to_string(),
+            extension_digit: None,
+            next_serial: None,
+        })
+        .is_err());
+    }
+}
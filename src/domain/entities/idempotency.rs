@@ -6,6 +6,7 @@ use uuid::Uuid;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IdempotencyKey {
     pub id: Uuid,
+    pub tenant_id: Option<Uuid>,
     pub idempotency_key: String,
     pub request_path: String,
     pub request_method: String,
@@ -19,6 +20,7 @@ pub struct IdempotencyKey {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IdempotencyKeyRequest {
+    pub tenant_id: Option<Uuid>,
     pub idempotency_key: String,
     pub request_path: String,
     pub request_method: String,
@@ -29,6 +31,7 @@ pub struct IdempotencyKeyRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IdempotencyKeyResponse {
     pub id: Uuid,
+    pub tenant_id: Option<Uuid>,
     pub idempotency_key: String,
     pub request_path: String,
     pub request_method: String,
@@ -48,30 +51,31 @@ impl IdempotencyKey {
 
         if request.idempotency_key.trim().is_empty() {
             return Err(DomainError::ValidationError(
-                "Idempotency key cannot be empty".to_string(),
+                "Idempotency key cannot be empty".into(),
             ));
         }
 
         if request.idempotency_key.len() > 255 {
             return Err(DomainError::ValidationError(
-                "Idempotency key cannot exceed 255 characters".to_string(),
+                "Idempotency key cannot exceed 255 characters".into(),
             ));
         }
 
         if request.request_path.trim().is_empty() {
             return Err(DomainError::ValidationError(
-                "Request path cannot be empty".to_string(),
+                "Request path cannot be empty".into(),
             ));
         }
 
         if request.request_method.trim().is_empty() {
             return Err(DomainError::ValidationError(
-                "Request method cannot be empty".to_string(),
+                "Request method cannot be empty".into(),
             ));
         }
 
         Ok(Self {
             id: Uuid::new_v4(),
+            tenant_id: request.tenant_id,
             idempotency_key: request.idempotency_key,
             request_path: request.request_path,
             request_method: request.request_method,
@@ -91,7 +95,7 @@ impl IdempotencyKey {
     pub fn complete(&mut self, status: i32, body: Option<String>) -> Result<(), DomainError> {
         if self.response_status.is_some() {
             return Err(DomainError::ValidationError(
-                "Idempotency key has already been completed".to_string(),
+                "Idempotency key has already been completed".into(),
             ));
         }
 
@@ -106,6 +110,7 @@ impl From<IdempotencyKey> for IdempotencyKeyResponse {
     fn from(key: IdempotencyKey) -> Self {
         Self {
             id: key.id,
+            tenant_id: key.tenant_id,
             idempotency_key: key.idempotency_key,
             request_path: key.request_path,
             request_method: key.request_method,
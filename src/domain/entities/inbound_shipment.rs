@@ -0,0 +1,576 @@
+use crate::domain::entities::purchase_order::{PurchaseOrder, PurchaseOrderStatus, ReceiveLine};
+use crate::shared::error::DomainError;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+const MAX_CARTON_CODE_LENGTH: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InboundShipmentStatus {
+    /// Announced, nothing scanned in yet
+    Expected,
+    Receiving,
+    /// Every carton was scanned in, or the dock closed it with some missing
+    Closed,
+}
+
+impl InboundShipmentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InboundShipmentStatus::Expected => "EXPECTED",
+            InboundShipmentStatus::Receiving => "RECEIVING",
+            InboundShipmentStatus::Closed => "CLOSED",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s {
+            "EXPECTED" => Ok(InboundShipmentStatus::Expected),
+            "RECEIVING" => Ok(InboundShipmentStatus::Receiving),
+            "CLOSED" => Ok(InboundShipmentStatus::Closed),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid inbound shipment status: {}", s).into(),
+            )),
+        }
+    }
+}
+
+/// Strip what a scanner adds around a carton label: whitespace and the GS1-128
+/// `(00)` application identifier in front of an SSCC
+pub fn normalize_carton_code(code: &str) -> String {
+    let code = code.trim();
+    let code = code.strip_prefix("(00)").unwrap_or(code);
+    match code.strip_prefix("00") {
+        Some(sscc) if sscc.len() == 18 && sscc.chars().all(|c| c.is_ascii_digit()) => {
+            sscc.to_string()
+        }
+        _ => code.to_string(),
+    }
+}
+
+/// A supplier's advance shipping notice (ASN) against a purchase order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboundShipment {
+    pub id: Uuid,
+    pub asn_number: String,
+    pub po_id: Uuid,
+    pub supplier_id: Uuid,
+    pub status: InboundShipmentStatus,
+    pub expected_arrival: Option<DateTime<Utc>>,
+    pub carrier: Option<String>,
+    pub tracking_number: Option<String>,
+    pub cartons: Vec<InboundCarton>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboundCarton {
+    pub id: Uuid,
+    /// What the carton's label scans as, usually an SSCC
+    pub code: String,
+    pub lines: Vec<InboundCartonLine>,
+    pub received_at: Option<DateTime<Utc>>,
+    pub received_by: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboundCartonLine {
+    pub id: Uuid,
+    pub po_line_id: Uuid,
+    pub item_id: Uuid,
+    /// Zero for an item found in the carton that the notice didn't list
+    pub qty_expected: Decimal,
+    /// Set once the carton is scanned in
+    pub qty_received: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DiscrepancyKind {
+    Short,
+    Over,
+    /// The carton never arrived
+    MissingCarton,
+}
+
+/// Where what was scanned in differs from what the notice announced
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InboundDiscrepancy {
+    pub carton_code: String,
+    pub kind: DiscrepancyKind,
+    pub po_line_id: Uuid,
+    pub item_id: Uuid,
+    pub qty_expected: Decimal,
+    pub qty_received: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateInboundShipmentRequest {
+    pub po_id: Uuid,
+    pub asn_number: String,
+    pub expected_arrival: Option<DateTime<Utc>>,
+    pub carrier: Option<String>,
+    pub tracking_number: Option<String>,
+    pub cartons: Vec<CreateInboundCartonRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateInboundCartonRequest {
+    pub code: String,
+    pub lines: Vec<CreateInboundCartonLineRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateInboundCartonLineRequest {
+    pub po_line_id: Uuid,
+    pub quantity: Decimal,
+}
+
+/// A carton scanned in at the dock. Without `lines` it held exactly what the
+/// notice said; with them, those are the quantities counted per PO line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScannedCarton {
+    pub code: String,
+    pub lines: Option<Vec<ReceiveLine>>,
+}
+
+impl InboundShipment {
+    pub fn new(
+        po: &PurchaseOrder,
+        request: CreateInboundShipmentRequest,
+        created_by: Uuid,
+    ) -> Result<Self, DomainError> {
+        if !matches!(
+            po.status,
+            PurchaseOrderStatus::Open
+                | PurchaseOrderStatus::Receiving
+                | PurchaseOrderStatus::PartialReceived
+        ) {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Cannot announce a shipment against purchase order {} while it is {:?}",
+                    po.po_number, po.status
+                )
+                .into(),
+            ));
+        }
+
+        let asn_number = request.asn_number.trim().to_string();
+        if asn_number.is_empty() {
+            return Err(DomainError::ValidationError(
+                "ASN number cannot be empty".into(),
+            ));
+        }
+        if request.cartons.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Inbound shipment must have at least one carton".into(),
+            ));
+        }
+
+        let mut codes = HashSet::new();
+        let mut cartons = Vec::with_capacity(request.cartons.len());
+        for carton in request.cartons {
+            let code = normalize_carton_code(&carton.code);
+            if code.is_empty() || code.len() > MAX_CARTON_CODE_LENGTH {
+                return Err(DomainError::ValidationError(
+                    format!(
+                        "Carton code must be between 1 and {} characters",
+                        MAX_CARTON_CODE_LENGTH
+                    )
+                    .into(),
+                ));
+            }
+            if !codes.insert(code.clone()) {
+                return Err(DomainError::ValidationError(
+                    format!("Carton {} appears more than once", code).into(),
+                ));
+            }
+            if carton.lines.is_empty() {
+                return Err(DomainError::ValidationError(
+                    format!("Carton {} must have at least one line", code).into(),
+                ));
+            }
+
+            let lines = carton
+                .lines
+                .into_iter()
+                .map(|line| {
+                    if line.quantity <= Decimal::ZERO {
+                        return Err(DomainError::ValidationError(
+                            format!("Quantity in carton {} must be positive", code).into(),
+                        ));
+                    }
+                    Ok(InboundCartonLine {
+                        id: Uuid::new_v4(),
+                        po_line_id: line.po_line_id,
+                        item_id: po_line_item(po, line.po_line_id)?,
+                        qty_expected: line.quantity,
+                        qty_received: None,
+                    })
+                })
+                .collect::<Result<Vec<_>, DomainError>>()?;
+
+            cartons.push(InboundCarton {
+                id: Uuid::new_v4(),
+                code,
+                lines,
+                received_at: None,
+                received_by: None,
+            });
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            asn_number,
+            po_id: po.id,
+            supplier_id: po.supplier_id,
+            status: InboundShipmentStatus::Expected,
+            expected_arrival: request.expected_arrival,
+            carrier: normalize(request.carrier),
+            tracking_number: normalize(request.tracking_number),
+            cartons,
+            created_by,
+            created_at: now,
+            updated_at: now,
+            closed_at: None,
+        })
+    }
+
+    /// Check in scanned cartons, returning what to receive against the PO:
+    /// one line per PO line, summed across the cartons
+    pub fn receive_cartons(
+        &mut self,
+        po: &PurchaseOrder,
+        scans: Vec<ScannedCarton>,
+        received_by: Uuid,
+    ) -> Result<Vec<ReceiveLine>, DomainError> {
+        if self.status == InboundShipmentStatus::Closed {
+            return Err(DomainError::BusinessLogicError(
+                format!("Inbound shipment {} is closed", self.asn_number).into(),
+            ));
+        }
+        if scans.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Scan at least one carton".into(),
+            ));
+        }
+
+        let now = Utc::now();
+        let mut scanned = HashSet::new();
+        let mut received: Vec<ReceiveLine> = Vec::new();
+        for scan in scans {
+            let code = normalize_carton_code(&scan.code);
+            let carton = self
+                .cartons
+                .iter_mut()
+                .find(|c| c.code == code)
+                .ok_or_else(|| {
+                    DomainError::ValidationError(
+                        format!(
+                            "Carton {} is not on inbound shipment {}",
+                            code, self.asn_number
+                        )
+                        .into(),
+                    )
+                })?;
+            if carton.received_at.is_some() || !scanned.insert(code.clone()) {
+                return Err(DomainError::Conflict(
+                    format!("Carton {} was already received", code).into(),
+                ));
+            }
+
+            match scan.lines {
+                None => {
+                    for line in &mut carton.lines {
+                        line.qty_received = Some(line.qty_expected);
+                    }
+                }
+                Some(counted) => {
+                    let mut counts: HashMap<Uuid, Decimal> = HashMap::new();
+                    for line in counted {
+                        if line.qty_received < Decimal::ZERO {
+                            return Err(DomainError::ValidationError(
+                                format!("Counted quantity in carton {} cannot be negative", code)
+                                    .into(),
+                            ));
+                        }
+                        *counts.entry(line.po_line_id).or_default() += line.qty_received;
+                    }
+                    for line in &mut carton.lines {
+                        line.qty_received =
+                            Some(counts.remove(&line.po_line_id).unwrap_or_default());
+                    }
+                    // Whatever is left was in the carton without being announced
+                    let mut unexpected: Vec<(Uuid, Decimal)> = counts.into_iter().collect();
+                    unexpected.sort();
+                    for (po_line_id, qty) in unexpected {
+                        carton.lines.push(InboundCartonLine {
+                            id: Uuid::new_v4(),
+                            po_line_id,
+                            item_id: po_line_item(po, po_line_id)?,
+                            qty_expected: Decimal::ZERO,
+                            qty_received: Some(qty),
+                        });
+                    }
+                }
+            }
+            carton.received_at = Some(now);
+            carton.received_by = Some(received_by);
+
+            for line in &carton.lines {
+                let qty = line.qty_received.unwrap_or_default();
+                match received
+                    .iter_mut()
+                    .find(|r| r.po_line_id == line.po_line_id)
+                {
+                    Some(total) => total.qty_received += qty,
+                    None => received.push(ReceiveLine {
+                        po_line_id: line.po_line_id,
+                        qty_received: qty,
+                    }),
+                }
+            }
+        }
+
+        self.status = if self.is_fully_received() {
+            self.closed_at = Some(now);
+            InboundShipmentStatus::Closed
+        } else {
+            InboundShipmentStatus::Receiving
+        };
+        self.updated_at = now;
+        received.retain(|r| r.qty_received > Decimal::ZERO);
+        Ok(received)
+    }
+
+    pub fn is_fully_received(&self) -> bool {
+        self.cartons.iter().all(|c| c.received_at.is_some())
+    }
+
+    /// Stop waiting for the cartons that haven't arrived; they are reported
+    /// as missing
+    pub fn close(&mut self) -> Result<(), DomainError> {
+        if self.status == InboundShipmentStatus::Closed {
+            return Err(DomainError::BusinessLogicError(
+                format!("Inbound shipment {} is already closed", self.asn_number).into(),
+            ));
+        }
+        let now = Utc::now();
+        self.status = InboundShipmentStatus::Closed;
+        self.closed_at = Some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Differences between the notice and what was scanned in. Cartons not
+    /// scanned in yet only count as missing once the shipment is closed.
+    pub fn discrepancies(&self) -> Vec<InboundDiscrepancy> {
+        let closed = self.status == InboundShipmentStatus::Closed;
+        self.cartons
+            .iter()
+            .flat_map(|carton| {
+                carton.lines.iter().filter_map(move |line| {
+                    let kind = match line.qty_received {
+                        None if closed => DiscrepancyKind::MissingCarton,
+                        None => return None,
+                        Some(qty) if qty < line.qty_expected => DiscrepancyKind::Short,
+                        Some(qty) if qty > line.qty_expected => DiscrepancyKind::Over,
+                        Some(_) => return None,
+                    };
+                    Some(InboundDiscrepancy {
+                        carton_code: carton.code.clone(),
+                        kind,
+                        po_line_id: line.po_line_id,
+                        item_id: line.item_id,
+                        qty_expected: line.qty_expected,
+                        qty_received: line.qty_received.unwrap_or_default(),
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+fn po_line_item(po: &PurchaseOrder, po_line_id: Uuid) -> Result<Uuid, DomainError> {
+    po.lines
+        .iter()
+        .find(|l| l.id == po_line_id)
+        .map(|l| l.item_id)
+        .ok_or_else(|| {
+            DomainError::ValidationError(
+                format!(
+                    "Line {} is not on purchase order {}",
+                    po_line_id, po.po_number
+                )
+                .into(),
+            )
+        })
+}
+
+fn normalize(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::purchase_order::CreatePurchaseOrderLine;
+
+    const SSCC_A: &str = "000123450000000017";
+    const SSCC_B: &str = "000123450000000024";
+
+    fn open_po() -> PurchaseOrder {
+        let line = |qty: i64| CreatePurchaseOrderLine {
+            item_id: Uuid::new_v4(),
+            qty_ordered: Decimal::from(qty),
+            unit_cost: Decimal::ONE,
+            expected_date: None,
+        };
+        let mut po = PurchaseOrder::new(
+            Uuid::new_v4(),
+            vec![line(10), line(4)],
+            None,
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        po.open().unwrap();
+        po
+    }
+
+    fn asn(po: &PurchaseOrder) -> InboundShipment {
+        let carton = |code: &str, po_line_id: Uuid, qty: i64| CreateInboundCartonRequest {
+            code: code.to_string(),
+            lines: vec![CreateInboundCartonLineRequest {
+                po_line_id,
+                quantity: Decimal::from(qty),
+            }],
+        };
+        InboundShipment::new(
+            po,
+            CreateInboundShipmentRequest {
+                po_id: po.id,
+                asn_number: " ASN-1 ".to_string(),
+                expected_arrival: None,
+                carrier: None,
+                tracking_number: None,
+                cartons: vec![
+                    carton(SSCC_A, po.lines[0].id, 10),
+                    carton(&format!("(00){}", SSCC_B), po.lines[1].id, 4),
+                ],
+            },
+            Uuid::new_v4(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_normalize_carton_code() {
+        assert_eq!(normalize_carton_code(&format!(" (00){} ", SSCC_A)), SSCC_A);
+        assert_eq!(normalize_carton_code(&format!("00{}", SSCC_A)), SSCC_A);
+        assert_eq!(normalize_carton_code("CTN-0042"), "CTN-0042");
+    }
+
+    #[test]
+    fn test_new_rejects_lines_off_the_po() {
+        let po = open_po();
+        let request = CreateInboundShipmentRequest {
+            po_id: po.id,
+            asn_number: "ASN-2".to_string(),
+            expected_arrival: None,
+            carrier: None,
+            tracking_number: None,
+            cartons: vec![CreateInboundCartonRequest {
+                code: SSCC_A.to_string(),
+                lines: vec![CreateInboundCartonLineRequest {
+                    po_line_id: Uuid::new_v4(),
+                    quantity: Decimal::ONE,
+                }],
+            }],
+        };
+        assert!(InboundShipment::new(&po, request, Uuid::new_v4()).is_err());
+    }
+
+    #[test]
+    fn test_scanning_cartons_reports_discrepancies() {
+        let po = open_po();
+        let mut shipment = asn(&po);
+        assert_eq!(shipment.asn_number, "ASN-1");
+        assert_eq!(shipment.cartons[1].code, SSCC_B);
+
+        // The first carton came in two short, with one of the second line's
+        // items packed in it too
+        let received = shipment
+            .receive_cartons(
+                &po,
+                vec![ScannedCarton {
+                    code: format!("00{}", SSCC_A),
+                    lines: Some(vec![
+                        ReceiveLine {
+                            po_line_id: po.lines[0].id,
+                            qty_received: Decimal::from(8),
+                        },
+                        ReceiveLine {
+                            po_line_id: po.lines[1].id,
+                            qty_received: Decimal::ONE,
+                        },
+                    ]),
+                }],
+                Uuid::new_v4(),
+            )
+            .unwrap();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].qty_received, Decimal::from(8));
+        assert_eq!(shipment.status, InboundShipmentStatus::Receiving);
+
+        let rescan = ScannedCarton {
+            code: SSCC_A.to_string(),
+            lines: None,
+        };
+        assert!(shipment
+            .receive_cartons(&po, vec![rescan], Uuid::new_v4())
+            .is_err());
+
+        let kinds: Vec<DiscrepancyKind> = shipment
+            .discrepancies()
+            .into_iter()
+            .map(|d| d.kind)
+            .collect();
+        assert_eq!(kinds, vec![DiscrepancyKind::Short, DiscrepancyKind::Over]);
+
+        shipment.close().unwrap();
+        let missing = shipment.discrepancies().pop().unwrap();
+        assert_eq!(missing.kind, DiscrepancyKind::MissingCarton);
+        assert_eq!(missing.carton_code, SSCC_B);
+        assert!(shipment.close().is_err());
+    }
+
+    #[test]
+    fn test_last_carton_closes_the_shipment() {
+        let po = open_po();
+        let mut shipment = asn(&po);
+        let scans = [SSCC_A, SSCC_B]
+            .iter()
+            .map(|code| ScannedCarton {
+                code: code.to_string(),
+                lines: None,
+            })
+            .collect();
+
+        let received = shipment
+            .receive_cartons(&po, scans, Uuid::new_v4())
+            .unwrap();
+        assert_eq!(received.len(), 2);
+        assert_eq!(shipment.status, InboundShipmentStatus::Closed);
+        assert!(shipment.discrepancies().is_empty());
+    }
+}
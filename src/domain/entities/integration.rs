@@ -0,0 +1,496 @@
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Sales channels we have a connector for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ConnectorProvider {
+    Shopify,
+}
+
+impl ConnectorProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConnectorProvider::Shopify => "SHOPIFY",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "SHOPIFY" => Ok(ConnectorProvider::Shopify),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid connector provider: {}. Must be one of: SHOPIFY", s).into(),
+            )),
+        }
+    }
+
+    /// Shop domains are requested server-side, so only the provider's own hosts are allowed
+    fn validate_shop_domain(&self, shop_domain: &str) -> Result<(), DomainError> {
+        match self {
+            ConnectorProvider::Shopify => {
+                let shop = shop_domain.strip_suffix(".myshopify.com").unwrap_or("");
+                if shop.is_empty()
+                    || shop.starts_with('-')
+                    || !shop
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                {
+                    return Err(DomainError::ValidationError(
+                        "shop_domain must be a store domain like acme.myshopify.com".into(),
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A tenant's link to one store on a sales channel. Paid orders are pulled into
+/// sales orders for `location_id`, and that location's stock is pushed back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationConnector {
+    pub id: Uuid,
+    pub provider: ConnectorProvider,
+    pub name: String,
+    pub shop_domain: String,
+    pub location_id: Uuid,
+    /// The channel's location that receives our stock levels
+    pub external_location_id: Option<String>,
+    /// Customer given to sales orders pulled from the channel
+    pub customer_id: Option<Uuid>,
+    pub sync_interval_minutes: i32,
+    pub active: bool,
+    #[serde(skip)]
+    pub access_token: Option<String>,
+    pub token_scopes: Option<String>,
+    /// Set once the channel has granted access
+    pub token_obtained_at: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    pub oauth_state: Option<String>,
+    pub orders_cursor: Option<DateTime<Utc>>,
+    pub inventory_cursor: Option<DateTime<Utc>>,
+    pub next_sync_at: DateTime<Utc>,
+    /// Recorded as the creator of pulled sales orders
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl IntegrationConnector {
+    pub fn new(request: CreateConnectorRequest, created_by: Uuid) -> Result<Self, DomainError> {
+        let now = Utc::now();
+        let connector = Self {
+            id: Uuid::new_v4(),
+            provider: request.provider,
+            name: request.name.trim().to_string(),
+            shop_domain: request.shop_domain.trim().to_lowercase(),
+            location_id: request.location_id,
+            external_location_id: request.external_location_id.map(|id| id.trim().to_string()),
+            customer_id: request.customer_id,
+            sync_interval_minutes: request.sync_interval_minutes.unwrap_or(15),
+            active: request.active.unwrap_or(true),
+            access_token: None,
+            token_scopes: None,
+            token_obtained_at: None,
+            oauth_state: None,
+            orders_cursor: None,
+            inventory_cursor: None,
+            next_sync_at: now,
+            created_by,
+            created_at: now,
+            updated_at: now,
+        };
+        connector.validate()?;
+        Ok(connector)
+    }
+
+    /// The provider and shop are fixed; connect another store with a new connector
+    pub fn update(&mut self, request: UpdateConnectorRequest) -> Result<(), DomainError> {
+        if let Some(name) = request.name {
+            self.name = name.trim().to_string();
+        }
+        if let Some(location_id) = request.location_id {
+            self.location_id = location_id;
+            // Everything must be published again for the new location
+            self.inventory_cursor = None;
+        }
+        if let Some(external_location_id) = request.external_location_id {
+            self.external_location_id = Some(external_location_id.trim().to_string());
+            self.inventory_cursor = None;
+        }
+        if request.customer_id.is_some() {
+            self.customer_id = request.customer_id;
+        }
+        if let Some(sync_interval_minutes) = request.sync_interval_minutes {
+            self.sync_interval_minutes = sync_interval_minutes;
+        }
+        if let Some(active) = request.active {
+            self.active = active;
+        }
+        self.validate()?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.access_token.is_some()
+    }
+
+    /// Start an OAuth authorization, returning the nonce the channel must echo back
+    pub fn begin_authorization(&mut self) -> String {
+        let state = Uuid::new_v4().simple().to_string();
+        self.oauth_state = Some(state.clone());
+        self.updated_at = Utc::now();
+        state
+    }
+
+    /// Check the nonce of an OAuth callback; it can only be used once
+    pub fn complete_authorization(&mut self, state: &str) -> Result<(), DomainError> {
+        match self.oauth_state.take() {
+            Some(expected) if expected == state => Ok(()),
+            _ => Err(DomainError::ValidationError(
+                "OAuth state does not match an authorization in progress".into(),
+            )),
+        }
+    }
+
+    pub fn store_token(&mut self, access_token: String, scopes: Option<String>) {
+        let now = Utc::now();
+        self.access_token = Some(access_token);
+        self.token_scopes = scopes;
+        self.token_obtained_at = Some(now);
+        self.next_sync_at = now;
+        self.updated_at = now;
+    }
+
+    pub fn schedule_next_sync(&mut self, after: DateTime<Utc>) {
+        self.next_sync_at = after + Duration::minutes(self.sync_interval_minutes as i64);
+    }
+
+    fn validate(&self) -> Result<(), DomainError> {
+        if self.name.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Connector name cannot be empty".into(),
+            ));
+        }
+        self.provider.validate_shop_domain(&self.shop_domain)?;
+        if !(5..=1440).contains(&self.sync_interval_minutes) {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.field_between",
+                &[
+                    ("field", &"sync_interval_minutes"),
+                    ("min", &5),
+                    ("max", &1440),
+                ],
+            )));
+        }
+        if self
+            .external_location_id
+            .as_deref()
+            .is_some_and(|id| id.is_empty() || id.len() > 255)
+        {
+            return Err(DomainError::ValidationError(
+                "external_location_id must be 1 to 255 characters".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateConnectorRequest {
+    pub provider: ConnectorProvider,
+    pub name: String,
+    pub shop_domain: String,
+    pub location_id: Uuid,
+    pub external_location_id: Option<String>,
+    pub customer_id: Option<Uuid>,
+    pub sync_interval_minutes: Option<i32>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateConnectorRequest {
+    pub name: Option<String>,
+    pub location_id: Option<Uuid>,
+    pub external_location_id: Option<String>,
+    pub customer_id: Option<Uuid>,
+    pub sync_interval_minutes: Option<i32>,
+    pub active: Option<bool>,
+}
+
+/// Store a token issued outside the OAuth flow, e.g. by a custom app
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConnectorCredentialsRequest {
+    pub access_token: String,
+    pub scopes: Option<String>,
+}
+
+/// The query the channel redirects the merchant back with
+#[derive(Debug, Clone, Deserialize)]
+pub struct OAuthCallbackRequest {
+    pub code: String,
+    pub state: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SyncKind {
+    /// Paid orders pulled from the channel
+    Orders,
+    /// Stock levels pushed to the channel
+    Inventory,
+}
+
+impl SyncKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncKind::Orders => "ORDERS",
+            SyncKind::Inventory => "INVENTORY",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "ORDERS" => Ok(SyncKind::Orders),
+            "INVENTORY" => Ok(SyncKind::Inventory),
+            _ => Err(DomainError::ValidationError(
+                format!(
+                    "Invalid sync kind: {}. Must be one of: ORDERS, INVENTORY",
+                    s
+                )
+                .into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SyncTrigger {
+    Scheduled,
+    Manual,
+}
+
+impl SyncTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncTrigger::Scheduled => "SCHEDULED",
+            SyncTrigger::Manual => "MANUAL",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "SCHEDULED" => Ok(SyncTrigger::Scheduled),
+            "MANUAL" => Ok(SyncTrigger::Manual),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid sync trigger: {}", s).into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SyncRunStatus {
+    Succeeded,
+    /// Some records failed; the rest were synced
+    Partial,
+    Failed,
+}
+
+impl SyncRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncRunStatus::Succeeded => "SUCCEEDED",
+            SyncRunStatus::Partial => "PARTIAL",
+            SyncRunStatus::Failed => "FAILED",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "SUCCEEDED" => Ok(SyncRunStatus::Succeeded),
+            "PARTIAL" => Ok(SyncRunStatus::Partial),
+            "FAILED" => Ok(SyncRunStatus::Failed),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid sync run status: {}", s).into(),
+            )),
+        }
+    }
+}
+
+/// A record the sync could not handle, e.g. an order line with an unknown SKU
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SyncRunError {
+    /// Channel order number or SKU the error concerns
+    pub reference: Option<String>,
+    pub message: String,
+}
+
+/// One order pull or stock push
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncRun {
+    pub id: Uuid,
+    pub connector_id: Uuid,
+    pub kind: SyncKind,
+    pub trigger: SyncTrigger,
+    pub status: SyncRunStatus,
+    pub records_processed: i32,
+    pub records_failed: i32,
+    pub errors: Vec<SyncRunError>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
+impl SyncRun {
+    /// A run that got through, with `processed` records synced and `errors` skipped
+    pub fn finished(
+        connector_id: Uuid,
+        kind: SyncKind,
+        trigger: SyncTrigger,
+        started_at: DateTime<Utc>,
+        processed: usize,
+        errors: Vec<SyncRunError>,
+    ) -> Self {
+        let status = if errors.is_empty() {
+            SyncRunStatus::Succeeded
+        } else if processed > 0 {
+            SyncRunStatus::Partial
+        } else {
+            SyncRunStatus::Failed
+        };
+        Self {
+            id: Uuid::new_v4(),
+            connector_id,
+            kind,
+            trigger,
+            status,
+            records_processed: processed as i32,
+            records_failed: errors.len() as i32,
+            errors,
+            started_at,
+            finished_at: Utc::now(),
+        }
+    }
+
+    /// A run that could not complete at all, e.g. because the channel was unreachable
+    pub fn aborted(
+        connector_id: Uuid,
+        kind: SyncKind,
+        trigger: SyncTrigger,
+        started_at: DateTime<Utc>,
+        error: &DomainError,
+    ) -> Self {
+        let mut run = Self::finished(
+            connector_id,
+            kind,
+            trigger,
+            started_at,
+            0,
+            vec![SyncRunError {
+                reference: None,
+                message: error.to_string(),
+            }],
+        );
+        run.records_failed = 0;
+        run
+    }
+}
+
+/// Ties a sales order to the channel order it was pulled from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationOrderLink {
+    pub connector_id: Uuid,
+    pub external_order_id: String,
+    pub sales_order_id: Uuid,
+    pub external_order_number: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ListSyncRunsQuery {
+    pub kind: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(shop_domain: &str) -> CreateConnectorRequest {
+        CreateConnectorRequest {
+            provider: ConnectorProvider::Shopify,
+            name: "Web store".to_string(),
+            shop_domain: shop_domain.to_string(),
+            location_id: Uuid::new_v4(),
+            external_location_id: None,
+            customer_id: None,
+            sync_interval_minutes: None,
+            active: None,
+        }
+    }
+
+    #[test]
+    fn test_shop_domain_must_be_a_shopify_store() {
+        let connector =
+            IntegrationConnector::new(request(" Acme-Store.myshopify.com "), Uuid::nil()).unwrap();
+        assert_eq!(connector.shop_domain, "acme-store.myshopify.com");
+        assert!(!connector.is_connected());
+
+        for shop_domain in [
+            "myshopify.com",
+            "acme.example.com",
+            "evil.com/.myshopify.com",
+            "10.0.0.1:80#.myshopify.com",
+        ] {
+            assert!(
+                IntegrationConnector::new(request(shop_domain), Uuid::nil()).is_err(),
+                "{} was accepted",
+                shop_domain
+            );
+        }
+    }
+
+    #[test]
+    fn test_oauth_state_is_single_use() {
+        let mut connector =
+            IntegrationConnector::new(request("acme.myshopify.com"), Uuid::nil()).unwrap();
+        let state = connector.begin_authorization();
+
+        assert!(connector.complete_authorization("forged").is_err());
+        // A mismatch burns the nonce too
+        assert!(connector.complete_authorization(&state).is_err());
+
+        let state = connector.begin_authorization();
+        assert!(connector.complete_authorization(&state).is_ok());
+        assert!(connector.complete_authorization(&state).is_err());
+    }
+
+    #[test]
+    fn test_sync_run_status() {
+        let id = Uuid::new_v4();
+        let error = SyncRunError {
+            reference: Some("#1001".to_string()),
+            message: "Unknown SKU".to_string(),
+        };
+        let run = |processed, errors| {
+            SyncRun::finished(
+                id,
+                SyncKind::Orders,
+                SyncTrigger::Manual,
+                Utc::now(),
+                processed,
+                errors,
+            )
+            .status
+        };
+        assert_eq!(run(3, vec![]), SyncRunStatus::Succeeded);
+        assert_eq!(run(3, vec![error.clone()]), SyncRunStatus::Partial);
+        assert_eq!(run(0, vec![error]), SyncRunStatus::Failed);
+    }
+}
@@ -6,6 +6,7 @@ use crate::domain::entities::item::Item;
 use crate::domain::entities::location::Location;
 use crate::domain::entities::user::User;
 use crate::shared::error::DomainError;
+use rust_decimal::Decimal;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum MovementType {
@@ -34,10 +35,9 @@ impl MovementType {
             "adjustment" => Ok(MovementType::Adjustment),
             "transfer" => Ok(MovementType::Transfer),
             "initial" => Ok(MovementType::Initial),
-            _ => Err(DomainError::ValidationError(format!(
-                "Invalid movement type: {}",
-                s
-            ))),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid movement type: {}", s).into(),
+            )),
         }
     }
 }
@@ -50,6 +50,9 @@ pub enum ReferenceType {
     Transfer,
     Return,
     Initial,
+    CycleCount,
+    VendorReturn,
+    StatusChange,
 }
 
 impl ReferenceType {
@@ -61,6 +64,9 @@ impl ReferenceType {
             ReferenceType::Transfer => "transfer",
             ReferenceType::Return => "return",
             ReferenceType::Initial => "initial",
+            ReferenceType::CycleCount => "cycle_count",
+            ReferenceType::VendorReturn => "vendor_return",
+            ReferenceType::StatusChange => "status_change",
         }
     }
 
@@ -72,10 +78,50 @@ impl ReferenceType {
             "transfer" => Ok(ReferenceType::Transfer),
             "return" => Ok(ReferenceType::Return),
             "initial" => Ok(ReferenceType::Initial),
+            "cycle_count" => Ok(ReferenceType::CycleCount),
+            "vendor_return" => Ok(ReferenceType::VendorReturn),
+            "status_change" => Ok(ReferenceType::StatusChange),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid reference type: {}", s).into(),
+            )),
+        }
+    }
+}
+
+/// Whether on-hand stock can be sold. Quarantined and damaged stock stays on
+/// hand but is never reserved, allocated or shipped until released. In-transit
+/// stock has left a transfer's source and is held against its destination, but
+/// is not on hand at either until received.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StockStatus {
+    #[default]
+    Available,
+    Quarantine,
+    Damaged,
+    InTransit,
+}
+
+impl StockStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StockStatus::Available => "AVAILABLE",
+            StockStatus::Quarantine => "QUARANTINE",
+            StockStatus::Damaged => "DAMAGED",
+            StockStatus::InTransit => "IN_TRANSIT",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "AVAILABLE" => Ok(StockStatus::Available),
+            "QUARANTINE" => Ok(StockStatus::Quarantine),
+            "DAMAGED" => Ok(StockStatus::Damaged),
+            "IN_TRANSIT" => Ok(StockStatus::InTransit),
             _ => Err(DomainError::ValidationError(format!(
-                "Invalid reference type: {}",
+                "Invalid stock status: {}. Must be one of: AVAILABLE, QUARANTINE, DAMAGED, IN_TRANSIT",
                 s
-            ))),
+            ).into())),
         }
     }
 }
@@ -86,12 +132,15 @@ pub struct StockMovement {
     pub item_id: Uuid,
     pub location_id: Uuid,
     pub movement_type: MovementType,
-    pub quantity: i32,
+    pub quantity: Decimal,
     pub reference_type: ReferenceType,
     pub reference_id: Option<Uuid>,
     pub reason: Option<String>,
     pub created_at: DateTime<Utc>,
     pub created_by: Option<Uuid>,
+    /// The status bucket the quantity lands in or is taken from
+    #[serde(default)]
+    pub stock_status: StockStatus,
 }
 
 impl StockMovement {
@@ -99,26 +148,26 @@ impl StockMovement {
         item_id: Uuid,
         location_id: Uuid,
         movement_type: MovementType,
-        quantity: i32,
+        quantity: Decimal,
         reference_type: ReferenceType,
         reference_id: Option<Uuid>,
         reason: Option<String>,
         created_by: Option<Uuid>,
     ) -> Result<Self, DomainError> {
-        // Validate quantity based on movement type
+        // Validate quantity based on movement type; adjustments may go either way
         match movement_type {
-            MovementType::Inbound | MovementType::Adjustment | MovementType::Initial => {
-                if quantity < 0 {
+            MovementType::Inbound | MovementType::Initial => {
+                if quantity < Decimal::ZERO {
                     return Err(DomainError::ValidationError(
-                        "Inbound, adjustment, and initial movements must have positive quantity"
-                            .to_string(),
+                        "Inbound and initial movements must have positive quantity".into(),
                     ));
                 }
             }
+            MovementType::Adjustment => {}
             MovementType::Outbound | MovementType::Transfer => {
-                if quantity > 0 {
+                if quantity > Decimal::ZERO {
                     return Err(DomainError::ValidationError(
-                        "Outbound and transfer movements must have negative quantity".to_string(),
+                        "Outbound and transfer movements must have negative quantity".into(),
                     ));
                 }
             }
@@ -135,15 +184,28 @@ impl StockMovement {
             reason,
             created_at: Utc::now(),
             created_by,
+            stock_status: StockStatus::Available,
         })
     }
+
+    pub fn with_stock_status(mut self, stock_status: StockStatus) -> Self {
+        self.stock_status = stock_status;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StockLevel {
     pub item_id: Uuid,
     pub location_id: Uuid,
-    pub quantity_on_hand: i32,
+    pub quantity_on_hand: Decimal,
+    pub quantity_reserved: Decimal,
+    /// On hand but held for inspection
+    pub quantity_quarantine: Decimal,
+    /// On hand but not sellable
+    pub quantity_damaged: Decimal,
+    /// Shipped here on a transfer but not yet received; not on hand
+    pub quantity_in_transit: Decimal,
     pub last_movement_id: Option<Uuid>,
     pub updated_at: DateTime<Utc>,
 }
@@ -153,31 +215,72 @@ impl StockLevel {
         Self {
             item_id,
             location_id,
-            quantity_on_hand: 0,
+            quantity_on_hand: Decimal::ZERO,
+            quantity_reserved: Decimal::ZERO,
+            quantity_quarantine: Decimal::ZERO,
+            quantity_damaged: Decimal::ZERO,
+            quantity_in_transit: Decimal::ZERO,
             last_movement_id: None,
             updated_at: Utc::now(),
         }
     }
 
+    /// Stock in the given status; everything but in-transit is on hand
+    pub fn quantity_in(&self, stock_status: StockStatus) -> Decimal {
+        match stock_status {
+            StockStatus::Available => {
+                self.quantity_on_hand - self.quantity_quarantine - self.quantity_damaged
+            }
+            StockStatus::Quarantine => self.quantity_quarantine,
+            StockStatus::Damaged => self.quantity_damaged,
+            StockStatus::InTransit => self.quantity_in_transit,
+        }
+    }
+
+    /// Available stock that is not promised to an open order
+    pub fn available_to_promise(&self) -> Decimal {
+        (self.quantity_in(StockStatus::Available) - self.quantity_reserved).max(Decimal::ZERO)
+    }
+
     pub fn apply_movement(&mut self, movement: &StockMovement) -> Result<(), DomainError> {
         // Validate that the movement applies to this stock level
         if movement.item_id != self.item_id || movement.location_id != self.location_id {
             return Err(DomainError::ValidationError(
-                "Movement does not apply to this stock level".to_string(),
+                "Movement does not apply to this stock level".into(),
             ));
         }
 
-        self.quantity_on_hand += movement.quantity;
-        self.last_movement_id = Some(movement.id);
-        self.updated_at = Utc::now();
-
-        // Ensure stock level doesn't go negative (except for adjustments)
-        if self.quantity_on_hand < 0 && movement.movement_type != MovementType::Adjustment {
-            return Err(DomainError::ValidationError(
-                "Stock level cannot go negative".to_string(),
+        // Stock can only leave the status it is held in, which also keeps the
+        // level from going negative
+        if movement.quantity < Decimal::ZERO
+            && self.quantity_in(movement.stock_status) < -movement.quantity
+        {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Only {} units of item {} are {}",
+                    self.quantity_in(movement.stock_status).max(Decimal::ZERO),
+                    self.item_id,
+                    movement.stock_status.as_str()
+                )
+                .into(),
             ));
         }
 
+        match movement.stock_status {
+            StockStatus::Available => self.quantity_on_hand += movement.quantity,
+            StockStatus::Quarantine => {
+                self.quantity_on_hand += movement.quantity;
+                self.quantity_quarantine += movement.quantity;
+            }
+            StockStatus::Damaged => {
+                self.quantity_on_hand += movement.quantity;
+                self.quantity_damaged += movement.quantity;
+            }
+            StockStatus::InTransit => self.quantity_in_transit += movement.quantity,
+        }
+        self.last_movement_id = Some(movement.id);
+        self.updated_at = Utc::now();
+
         Ok(())
     }
 }
@@ -187,7 +290,7 @@ pub struct CreateStockMovementRequest {
     pub item_id: Uuid,
     pub location_id: Uuid,
     pub movement_type: String,
-    pub quantity: i32,
+    pub quantity: Decimal,
     pub reference_type: String,
     pub reference_id: Option<Uuid>,
     pub reason: Option<String>,
@@ -199,12 +302,13 @@ pub struct StockMovementResponse {
     pub item_id: Uuid,
     pub location_id: Uuid,
     pub movement_type: String,
-    pub quantity: i32,
+    pub quantity: Decimal,
     pub reference_type: String,
     pub reference_id: Option<Uuid>,
     pub reason: Option<String>,
     pub created_at: DateTime<Utc>,
     pub created_by: Option<Uuid>,
+    pub stock_status: StockStatus,
     pub item: Option<Item>,
     pub location: Option<Location>,
     pub created_by_user: Option<User>,
@@ -214,7 +318,12 @@ pub struct StockMovementResponse {
 pub struct StockLevelResponse {
     pub item_id: Uuid,
     pub location_id: Uuid,
-    pub quantity_on_hand: i32,
+    pub quantity_on_hand: Decimal,
+    pub quantity_reserved: Decimal,
+    pub quantity_quarantine: Decimal,
+    pub quantity_damaged: Decimal,
+    pub quantity_in_transit: Decimal,
+    pub available_to_promise: Decimal,
     pub last_movement_id: Option<Uuid>,
     pub updated_at: DateTime<Utc>,
     pub item: Option<Item>,
@@ -222,55 +331,186 @@ pub struct StockLevelResponse {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum AdjustmentReason {
-    Count,
-    Damage,
-    Correction,
-    Other,
+pub struct StockReservationRequest {
+    pub item_id: Uuid,
+    pub location_id: Uuid,
+    pub quantity: Decimal,
 }
 
-impl AdjustmentReason {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            AdjustmentReason::Count => "COUNT",
-            AdjustmentReason::Damage => "DAMAGE",
-            AdjustmentReason::Correction => "CORRECTION",
-            AdjustmentReason::Other => "OTHER",
-        }
-    }
+/// Move on-hand stock between statuses, e.g. release inspected returns from
+/// QUARANTINE to AVAILABLE
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangeStockStatusRequest {
+    pub item_id: Uuid,
+    pub location_id: Uuid,
+    pub from_status: StockStatus,
+    pub to_status: StockStatus,
+    pub quantity: Decimal,
+    pub reason: Option<String>,
+}
 
-    pub fn from_str(s: &str) -> Result<Self, DomainError> {
-        match s.to_uppercase().as_str() {
-            "COUNT" => Ok(AdjustmentReason::Count),
-            "DAMAGE" => Ok(AdjustmentReason::Damage),
-            "CORRECTION" => Ok(AdjustmentReason::Correction),
-            "OTHER" => Ok(AdjustmentReason::Other),
-            _ => Err(DomainError::ValidationError(format!(
-                "Invalid adjustment reason: {}. Must be one of: COUNT, DAMAGE, CORRECTION, OTHER",
-                s
-            ))),
+impl ChangeStockStatusRequest {
+    /// The pair of adjustments recording the change: one taking the quantity
+    /// out of `from_status` and one putting it into `to_status`. They share a
+    /// reference ID and leave the quantity on hand unchanged.
+    pub fn movements(
+        &self,
+        created_by: Uuid,
+    ) -> Result<(StockMovement, StockMovement), DomainError> {
+        if self.quantity <= Decimal::ZERO {
+            return Err(DomainError::ValidationError(
+                "Status change quantity must be positive".into(),
+            ));
         }
+        if self.from_status == self.to_status {
+            return Err(DomainError::ValidationError(
+                format!("Stock is already {}", self.to_status.as_str()).into(),
+            ));
+        }
+        // Stock only enters or leaves transit by shipping or receiving a transfer
+        if self.from_status == StockStatus::InTransit || self.to_status == StockStatus::InTransit {
+            return Err(DomainError::ValidationError(
+                "In-transit stock can only change through transfers".into(),
+            ));
+        }
+
+        let change_id = Uuid::new_v4();
+        let reason = self.reason.clone().unwrap_or_else(|| {
+            format!(
+                "{} to {}",
+                self.from_status.as_str(),
+                self.to_status.as_str()
+            )
+        });
+        let movement = |quantity: Decimal, stock_status: StockStatus| {
+            StockMovement::new(
+                self.item_id,
+                self.location_id,
+                MovementType::Adjustment,
+                quantity,
+                ReferenceType::StatusChange,
+                Some(change_id),
+                Some(reason.clone()),
+                Some(created_by),
+            )
+            .map(|movement| movement.with_stock_status(stock_status))
+        };
+
+        Ok((
+            movement(-self.quantity, self.from_status)?,
+            movement(self.quantity, self.to_status)?,
+        ))
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Adjustment {
-    pub id: Uuid,
+/// An item with stock on hand that hasn't shipped out within the dead stock
+/// report's window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadStockItem {
     pub item_id: Uuid,
-    pub location_id: Uuid,
-    pub qty_change: i32,
-    pub reason: AdjustmentReason,
-    pub note: Option<String>,
-    pub created_by: Uuid,
-    pub created_at: DateTime<Utc>,
+    pub sku: String,
+    pub name: String,
+    pub quantity_on_hand: Decimal,
+    pub unit_cost: Decimal,
+    /// On-hand units at cost
+    pub value: Decimal,
+    /// Latest movement of any kind; `None` if the stock predates movement history
+    pub last_movement_at: Option<DateTime<Utc>>,
+    /// Latest outbound movement, before the window or never
+    pub last_outbound_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct StockAdjustmentRequest {
-    pub item_id: Uuid,
-    pub location_id: Uuid,
-    pub qty_change: i32,
-    pub reason: AdjustmentReason,
-    pub note: Option<String>,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_change(
+        from_status: StockStatus,
+        to_status: StockStatus,
+        quantity: Decimal,
+    ) -> ChangeStockStatusRequest {
+        ChangeStockStatusRequest {
+            item_id: Uuid::new_v4(),
+            location_id: Uuid::new_v4(),
+            from_status,
+            to_status,
+            quantity,
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn test_held_stock_is_not_available_to_promise() {
+        let mut level = StockLevel::new(Uuid::new_v4(), Uuid::new_v4());
+        level.quantity_on_hand = Decimal::from(10);
+        level.quantity_quarantine = Decimal::from(3);
+        level.quantity_damaged = Decimal::from(2);
+        level.quantity_reserved = Decimal::from(4);
+
+        assert_eq!(level.quantity_in(StockStatus::Available), Decimal::from(5));
+        assert_eq!(level.available_to_promise(), Decimal::ONE);
+    }
+
+    #[test]
+    fn test_status_change_moves_stock_between_buckets() {
+        let request = status_change(
+            StockStatus::Available,
+            StockStatus::Quarantine,
+            Decimal::from(4),
+        );
+        let mut level = StockLevel::new(request.item_id, request.location_id);
+        level.quantity_on_hand = Decimal::from(6);
+
+        let (out, into) = request.movements(Uuid::new_v4()).unwrap();
+        assert_eq!(out.reference_id, into.reference_id);
+        level.apply_movement(&out).unwrap();
+        level.apply_movement(&into).unwrap();
+
+        assert_eq!(level.quantity_on_hand, Decimal::from(6));
+        assert_eq!(level.quantity_quarantine, Decimal::from(4));
+        assert_eq!(level.quantity_in(StockStatus::Available), Decimal::from(2));
+    }
+
+    #[test]
+    fn test_stock_only_leaves_the_status_it_is_held_in() {
+        let request = status_change(
+            StockStatus::Quarantine,
+            StockStatus::Available,
+            Decimal::from(3),
+        );
+        let mut level = StockLevel::new(request.item_id, request.location_id);
+        level.quantity_on_hand = Decimal::from(5);
+        level.quantity_quarantine = Decimal::from(2);
+
+        let (out, _) = request.movements(Uuid::new_v4()).unwrap();
+        assert!(level.apply_movement(&out).is_err());
+
+        // Outbound stock comes from the available bucket only
+        let shipment = StockMovement::new(
+            request.item_id,
+            request.location_id,
+            MovementType::Outbound,
+            Decimal::from(-4),
+            ReferenceType::SalesOrder,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(level.apply_movement(&shipment).is_err());
+    }
+
+    #[test]
+    fn test_status_change_rejects_same_status_and_non_positive_quantity() {
+        assert!(
+            status_change(StockStatus::Damaged, StockStatus::Damaged, Decimal::ONE)
+                .movements(Uuid::new_v4())
+                .is_err()
+        );
+        assert!(
+            status_change(StockStatus::Available, StockStatus::Damaged, Decimal::ZERO)
+                .movements(Uuid::new_v4())
+                .is_err()
+        );
+    }
 }
@@ -0,0 +1,104 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::domain::entities::user::UserRole;
+use crate::domain::value_objects::email::Email;
+use crate::shared::error::DomainError;
+
+/// An invitation for `email` to join a tenant with `role`. The invitee redeems the
+/// token issued alongside it; only the token's hash is stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Invitation {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub email: Email,
+    pub role: UserRole,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Invitation {
+    /// Create an invitation valid for `ttl`, returning it with the token to hand to
+    /// the invitee
+    pub fn new(tenant_id: Uuid, email: Email, role: UserRole, ttl: Duration) -> (Self, String) {
+        let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let now = Utc::now();
+
+        let invitation = Self {
+            id: Uuid::new_v4(),
+            tenant_id,
+            email,
+            role,
+            token_hash: Self::hash_token(&token),
+            expires_at: now + ttl,
+            accepted_at: None,
+            created_at: now,
+        };
+        (invitation, token)
+    }
+
+    pub fn hash_token(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+
+    /// Mark the invitation used, failing if it was already accepted or has expired
+    pub fn accept(&mut self) -> Result<(), DomainError> {
+        if self.accepted_at.is_some() {
+            return Err(DomainError::BusinessLogicError(
+                "Invitation has already been accepted".into(),
+            ));
+        }
+        if self.is_expired() {
+            return Err(DomainError::BusinessLogicError(
+                "Invitation has expired".into(),
+            ));
+        }
+        self.accepted_at = Some(Utc::now());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invitation(ttl: Duration) -> (Invitation, String) {
+        Invitation::new(
+            Uuid::new_v4(),
+            Email::new("invitee@example.com".to_string()).unwrap(),
+            UserRole::Member,
+            ttl,
+        )
+    }
+
+    #[test]
+    fn test_only_the_token_hash_is_kept() {
+        let (invitation, token) = invitation(Duration::days(7));
+        assert_eq!(token.len(), 64);
+        assert_ne!(invitation.token_hash, token);
+        assert_eq!(invitation.token_hash, Invitation::hash_token(&token));
+    }
+
+    #[test]
+    fn test_invitation_can_be_accepted_once() {
+        let (mut invitation, _) = invitation(Duration::days(7));
+        assert!(invitation.accept().is_ok());
+        assert!(invitation.accept().is_err());
+    }
+
+    #[test]
+    fn test_expired_invitation_cannot_be_accepted() {
+        let (mut invitation, _) = invitation(Duration::seconds(-1));
+        assert!(invitation.is_expired());
+        assert!(invitation.accept().is_err());
+    }
+}
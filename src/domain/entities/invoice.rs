@@ -0,0 +1,377 @@
+use crate::domain::entities::sales_order::{SalesOrder, SalesOrderStatus};
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::money;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Days an invoice has to be paid in when no due date is given
+pub const DEFAULT_PAYMENT_TERMS_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InvoiceStatus {
+    Open,
+    PartiallyPaid,
+    Paid,
+}
+
+impl InvoiceStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InvoiceStatus::Open => "OPEN",
+            InvoiceStatus::PartiallyPaid => "PARTIALLY_PAID",
+            InvoiceStatus::Paid => "PAID",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s {
+            "OPEN" => Ok(InvoiceStatus::Open),
+            "PARTIALLY_PAID" => Ok(InvoiceStatus::PartiallyPaid),
+            "PAID" => Ok(InvoiceStatus::Paid),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid invoice status: {}", s).into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PaymentMethod {
+    Cash,
+    Card,
+    BankTransfer,
+    Check,
+    Other,
+}
+
+impl PaymentMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentMethod::Cash => "CASH",
+            PaymentMethod::Card => "CARD",
+            PaymentMethod::BankTransfer => "BANK_TRANSFER",
+            PaymentMethod::Check => "CHECK",
+            PaymentMethod::Other => "OTHER",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s {
+            "CASH" => Ok(PaymentMethod::Cash),
+            "CARD" => Ok(PaymentMethod::Card),
+            "BANK_TRANSFER" => Ok(PaymentMethod::BankTransfer),
+            "CHECK" => Ok(PaymentMethod::Check),
+            "OTHER" => Ok(PaymentMethod::Other),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid payment method: {}", s).into(),
+            )),
+        }
+    }
+}
+
+/// What a customer owes for a shipped sales order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Invoice {
+    pub id: Uuid,
+    pub invoice_number: String,
+    pub so_id: Uuid,
+    pub customer_id: Option<Uuid>,
+    pub status: InvoiceStatus,
+    /// The sales order's total, in the order's currency
+    pub total_amount: Decimal,
+    pub amount_paid: Decimal,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Base currency units per unit of `currency`, taken from the sales order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange_rate: Option<Decimal>,
+    pub issued_at: DateTime<Utc>,
+    pub due_date: DateTime<Utc>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Money received from a customer against an invoice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Payment {
+    pub id: Uuid,
+    pub invoice_id: Uuid,
+    pub customer_id: Option<Uuid>,
+    pub amount: Decimal,
+    /// The part of `amount` that went to the invoice's balance
+    pub applied_amount: Decimal,
+    /// The part beyond the balance, kept as credit for the customer
+    pub credit_amount: Decimal,
+    pub method: PaymentMethod,
+    pub reference: Option<String>,
+    pub paid_at: DateTime<Utc>,
+    pub recorded_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateInvoiceRequest {
+    /// Defaults to `DEFAULT_PAYMENT_TERMS_DAYS` after issue
+    pub due_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordPaymentRequest {
+    pub amount: Decimal,
+    pub method: PaymentMethod,
+    /// Cheque number, transfer reference and the like
+    pub reference: Option<String>,
+    /// Defaults to now
+    pub paid_at: Option<DateTime<Utc>>,
+    /// Accept more than the balance and keep the rest as customer credit
+    #[serde(default)]
+    pub apply_excess_as_credit: bool,
+}
+
+impl Invoice {
+    /// Invoice a shipped sales order for its total
+    pub fn for_sales_order(
+        invoice_number: String,
+        sales_order: &SalesOrder,
+        due_date: Option<DateTime<Utc>>,
+        created_by: Uuid,
+    ) -> Result<Self, DomainError> {
+        if !sales_order
+            .status
+            .can_transition_to(&SalesOrderStatus::Invoiced)
+        {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Cannot invoice sales order with status: {}",
+                    sales_order.status.as_str()
+                )
+                .into(),
+            ));
+        }
+
+        let now = Utc::now();
+        let due_date = due_date.unwrap_or(now + Duration::days(DEFAULT_PAYMENT_TERMS_DAYS));
+        if due_date < now - Duration::days(1) {
+            return Err(DomainError::ValidationError(
+                "Due date cannot be in the past".into(),
+            ));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            invoice_number,
+            so_id: sales_order.id,
+            customer_id: sales_order.customer_id,
+            status: InvoiceStatus::Open,
+            total_amount: sales_order.total_amount,
+            amount_paid: Decimal::ZERO,
+            currency: sales_order.currency.clone(),
+            exchange_rate: sales_order.exchange_rate,
+            issued_at: now,
+            due_date,
+            created_by,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// What is still to be paid
+    pub fn balance(&self) -> Decimal {
+        self.total_amount - self.amount_paid
+    }
+
+    /// Whole days past the due date at `as_of`, zero when not yet due
+    pub fn days_overdue(&self, as_of: DateTime<Utc>) -> i64 {
+        (as_of - self.due_date).num_days().max(0)
+    }
+
+    /// `amount` in the tenant's base currency, at the rate fixed on the order
+    pub fn to_base(&self, amount: Decimal) -> Result<Decimal, DomainError> {
+        match self.exchange_rate {
+            Some(rate) => money::convert(amount, rate),
+            None => Ok(amount),
+        }
+    }
+
+    /// Apply a payment to the balance. Paying more than the balance is refused
+    /// unless the request asks for the excess to be kept as credit.
+    pub fn record_payment(
+        &mut self,
+        request: &RecordPaymentRequest,
+        recorded_by: Uuid,
+    ) -> Result<Payment, DomainError> {
+        if request.amount <= Decimal::ZERO {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.payment_positive",
+                &[],
+            )));
+        }
+        if request.amount.scale() > 2 {
+            return Err(DomainError::ValidationError(
+                "Payment amount cannot have more than 2 decimal places".into(),
+            ));
+        }
+
+        let balance = self.balance();
+        let credit_amount = (request.amount - balance).max(Decimal::ZERO);
+        if credit_amount > Decimal::ZERO && !request.apply_excess_as_credit {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.payment_exceeds_balance",
+                &[
+                    ("amount", &request.amount),
+                    ("balance", &balance),
+                    ("invoice", &self.invoice_number),
+                ],
+            )));
+        }
+        let applied_amount = request.amount - credit_amount;
+
+        let now = Utc::now();
+        self.amount_paid += applied_amount;
+        self.status = if self.balance() == Decimal::ZERO {
+            InvoiceStatus::Paid
+        } else {
+            InvoiceStatus::PartiallyPaid
+        };
+        self.updated_at = now;
+
+        Ok(Payment {
+            id: Uuid::new_v4(),
+            invoice_id: self.id,
+            customer_id: self.customer_id,
+            amount: request.amount,
+            applied_amount,
+            credit_amount,
+            method: request.method,
+            reference: request.reference.clone(),
+            paid_at: request.paid_at.unwrap_or(now),
+            recorded_by,
+            created_at: now,
+        })
+    }
+}
+
+/// One customer's unpaid invoice balances by how overdue they are, in the
+/// tenant's base currency
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ReceivablesAging {
+    /// `None` gathers invoices for orders without a customer
+    pub customer_id: Option<Uuid>,
+    /// Not yet due
+    pub current: Decimal,
+    pub days_1_30: Decimal,
+    pub days_31_60: Decimal,
+    pub days_61_90: Decimal,
+    pub over_90: Decimal,
+    pub total: Decimal,
+    pub invoice_count: usize,
+}
+
+impl ReceivablesAging {
+    /// Add an invoice's balance to the bucket for how overdue it is at `as_of`
+    pub fn add(&mut self, invoice: &Invoice, as_of: DateTime<Utc>) -> Result<(), DomainError> {
+        let balance = invoice.to_base(invoice.balance())?;
+        let bucket = match invoice.days_overdue(as_of) {
+            0 => &mut self.current,
+            1..=30 => &mut self.days_1_30,
+            31..=60 => &mut self.days_31_60,
+            61..=90 => &mut self.days_61_90,
+            _ => &mut self.over_90,
+        };
+        *bucket += balance;
+        self.total += balance;
+        self.invoice_count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::sales_order::SalesOrderLine;
+
+    fn invoice(total: Decimal) -> Invoice {
+        let mut order = SalesOrder::new(
+            "SO-1".to_string(),
+            Some(Uuid::new_v4()),
+            None,
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        order
+            .add_line(SalesOrderLine::new(Uuid::new_v4(), Decimal::ONE, total).unwrap())
+            .unwrap();
+        assert!(
+            Invoice::for_sales_order("INV-1".to_string(), &order, None, Uuid::new_v4()).is_err()
+        );
+        order.status = SalesOrderStatus::Shipped;
+        Invoice::for_sales_order("INV-1".to_string(), &order, None, Uuid::new_v4()).unwrap()
+    }
+
+    fn payment(amount: i64, apply_excess_as_credit: bool) -> RecordPaymentRequest {
+        RecordPaymentRequest {
+            amount: Decimal::from(amount),
+            method: PaymentMethod::BankTransfer,
+            reference: Some("TRX-1".to_string()),
+            paid_at: None,
+            apply_excess_as_credit,
+        }
+    }
+
+    #[test]
+    fn test_payments_move_invoice_to_paid_and_refuse_overpayment() {
+        let mut invoice = invoice(Decimal::from(100));
+        assert_eq!(invoice.status, InvoiceStatus::Open);
+
+        let first = invoice
+            .record_payment(&payment(40, false), Uuid::new_v4())
+            .unwrap();
+        assert_eq!(first.applied_amount, Decimal::from(40));
+        assert_eq!(invoice.status, InvoiceStatus::PartiallyPaid);
+        assert_eq!(invoice.balance(), Decimal::from(60));
+
+        assert!(invoice
+            .record_payment(&payment(75, false), Uuid::new_v4())
+            .is_err());
+        assert_eq!(invoice.balance(), Decimal::from(60));
+        assert!(invoice
+            .record_payment(&payment(0, false), Uuid::new_v4())
+            .is_err());
+
+        let last = invoice
+            .record_payment(&payment(75, true), Uuid::new_v4())
+            .unwrap();
+        assert_eq!(last.applied_amount, Decimal::from(60));
+        assert_eq!(last.credit_amount, Decimal::from(15));
+        assert_eq!(invoice.status, InvoiceStatus::Paid);
+        assert_eq!(invoice.balance(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_aging_buckets_by_days_past_due() {
+        let mut invoice = invoice(Decimal::from(100));
+        invoice.amount_paid = Decimal::from(25);
+        let mut aging = ReceivablesAging::default();
+
+        aging.add(&invoice, invoice.due_date).unwrap();
+        aging
+            .add(&invoice, invoice.due_date + Duration::days(45))
+            .unwrap();
+        aging
+            .add(&invoice, invoice.due_date + Duration::days(120))
+            .unwrap();
+
+        assert_eq!(aging.current, Decimal::from(75));
+        assert_eq!(aging.days_1_30, Decimal::ZERO);
+        assert_eq!(aging.days_31_60, Decimal::from(75));
+        assert_eq!(aging.over_90, Decimal::from(75));
+        assert_eq!(aging.total, Decimal::from(225));
+        assert_eq!(aging.invoice_count, 3);
+    }
+}
@@ -1,6 +1,11 @@
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::money::round_unit;
+use crate::shared::quantity;
 use chrono::Utc;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -18,10 +23,32 @@ pub struct UpdateItemRequest {
     pub category: Option<String>,
     pub unit: Option<String>,
     pub barcode: Option<String>,
-    pub cost_price: Option<f64>,
-    pub sale_price: Option<f64>,
-    pub reorder_point: Option<i32>,
-    pub reorder_qty: Option<i32>,
+    pub cost_price: Option<Decimal>,
+    pub sale_price: Option<Decimal>,
+    pub reorder_point: Option<Decimal>,
+    pub reorder_qty: Option<Decimal>,
+    pub quantity_precision: Option<u32>,
+    pub weight: Option<f64>,
+    pub dimensions: Option<ItemDimensions>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// The fields of an item a client edits, as one document. A merge patch is
+/// applied to this, so fields it leaves out are cleared.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ItemDetails {
+    pub sku: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub unit: String,
+    pub barcode: Option<String>,
+    pub cost_price: Decimal,
+    pub sale_price: Option<Decimal>,
+    pub reorder_point: Option<Decimal>,
+    pub reorder_qty: Option<Decimal>,
+    pub quantity_precision: u32,
     pub weight: Option<f64>,
     pub dimensions: Option<ItemDimensions>,
     pub metadata: Option<serde_json::Value>,
@@ -37,13 +64,23 @@ pub struct Item {
     pub category: Option<String>,
     pub unit: String,
     pub barcode: Option<String>,
-    pub cost_price: f64,
-    pub sale_price: Option<f64>,
-    pub reorder_point: Option<i32>,
-    pub reorder_qty: Option<i32>,
+    pub cost_price: Decimal,
+    pub sale_price: Option<Decimal>,
+    pub reorder_point: Option<Decimal>,
+    pub reorder_qty: Option<Decimal>,
+    /// Decimal places quantities of this item may have: 0 for items counted in
+    /// whole units, up to 4 for items sold by length, weight or volume
+    #[serde(default)]
+    pub quantity_precision: u32,
     pub weight: Option<f64>,
     pub dimensions: Option<ItemDimensions>,
     pub metadata: Option<serde_json::Value>,
+    /// The product this item is a variant of; stand-alone items have none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<Uuid>,
+    /// The variant's value for each of its product's attributes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant_attributes: Option<BTreeMap<String, String>>,
     pub active: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
@@ -55,30 +92,34 @@ impl Item {
         sku: String,
         name: String,
         unit: String,
-        cost_price: f64,
+        cost_price: Decimal,
     ) -> Result<Self, DomainError> {
         if sku.trim().is_empty() {
-            return Err(DomainError::ValidationError(
-                "SKU cannot be empty".to_string(),
-            ));
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.sku_empty",
+                &[],
+            )));
         }
 
         if name.trim().is_empty() {
-            return Err(DomainError::ValidationError(
-                "Name cannot be empty".to_string(),
-            ));
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.name_empty",
+                &[],
+            )));
         }
 
         if unit.trim().is_empty() {
-            return Err(DomainError::ValidationError(
-                "Unit cannot be empty".to_string(),
-            ));
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.unit_empty",
+                &[],
+            )));
         }
 
-        if cost_price < 0.0 {
-            return Err(DomainError::ValidationError(
-                "Cost price cannot be negative".to_string(),
-            ));
+        if cost_price < Decimal::ZERO {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.cost_price_negative",
+                &[],
+            )));
         }
 
         let now = chrono::Utc::now();
@@ -92,13 +133,16 @@ impl Item {
             category: None,
             unit,
             barcode: None,
-            cost_price,
+            cost_price: round_unit(cost_price),
             sale_price: None,
             reorder_point: None,
             reorder_qty: None,
+            quantity_precision: 0,
             weight: None,
             dimensions: None,
             metadata: None,
+            product_id: None,
+            variant_attributes: None,
             active: true,
             created_at: now,
             updated_at: now,
@@ -108,18 +152,20 @@ impl Item {
     pub fn update(&mut self, request: UpdateItemRequest) -> Result<(), DomainError> {
         if let Some(sku) = request.sku {
             if sku.trim().is_empty() {
-                return Err(DomainError::ValidationError(
-                    "SKU cannot be empty".to_string(),
-                ));
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.sku_empty",
+                    &[],
+                )));
             }
             self.sku = sku;
         }
 
         if let Some(name) = request.name {
             if name.trim().is_empty() {
-                return Err(DomainError::ValidationError(
-                    "Name cannot be empty".to_string(),
-                ));
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.name_empty",
+                    &[],
+                )));
             }
             self.name = name;
         }
@@ -134,9 +180,10 @@ impl Item {
 
         if let Some(unit) = request.unit {
             if unit.trim().is_empty() {
-                return Err(DomainError::ValidationError(
-                    "Unit cannot be empty".to_string(),
-                ));
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.unit_empty",
+                    &[],
+                )));
             }
             self.unit = unit;
         }
@@ -146,46 +193,55 @@ impl Item {
         }
 
         if let Some(cost_price) = request.cost_price {
-            if cost_price < 0.0 {
-                return Err(DomainError::ValidationError(
-                    "Cost price cannot be negative".to_string(),
-                ));
+            if cost_price < Decimal::ZERO {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.cost_price_negative",
+                    &[],
+                )));
             }
-            self.cost_price = cost_price;
+            self.cost_price = round_unit(cost_price);
         }
 
         if let Some(sale_price) = request.sale_price {
-            if sale_price < 0.0 {
-                return Err(DomainError::ValidationError(
-                    "Sale price cannot be negative".to_string(),
-                ));
+            if sale_price < Decimal::ZERO {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.sale_price_negative",
+                    &[],
+                )));
             }
-            self.sale_price = Some(sale_price);
+            self.sale_price = Some(round_unit(sale_price));
         }
 
         if let Some(reorder_point) = request.reorder_point {
-            if reorder_point < 0 {
-                return Err(DomainError::ValidationError(
-                    "Reorder point cannot be negative".to_string(),
-                ));
+            if reorder_point < Decimal::ZERO {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.reorder_point_negative",
+                    &[],
+                )));
             }
             self.reorder_point = Some(reorder_point);
         }
 
         if let Some(reorder_qty) = request.reorder_qty {
-            if reorder_qty < 0 {
-                return Err(DomainError::ValidationError(
-                    "Reorder quantity cannot be negative".to_string(),
-                ));
+            if reorder_qty < Decimal::ZERO {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.reorder_quantity_negative",
+                    &[],
+                )));
             }
             self.reorder_qty = Some(reorder_qty);
         }
 
+        if let Some(precision) = request.quantity_precision {
+            self.set_quantity_precision(precision)?;
+        }
+
         if let Some(weight) = request.weight {
             if weight < 0.0 {
-                return Err(DomainError::ValidationError(
-                    "Weight cannot be negative".to_string(),
-                ));
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.weight_negative",
+                    &[],
+                )));
             }
             self.weight = Some(weight);
         }
@@ -202,6 +258,60 @@ impl Item {
         Ok(())
     }
 
+    pub fn details(&self) -> ItemDetails {
+        ItemDetails {
+            sku: self.sku.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            category: self.category.clone(),
+            unit: self.unit.clone(),
+            barcode: self.barcode.clone(),
+            cost_price: self.cost_price,
+            sale_price: self.sale_price,
+            reorder_point: self.reorder_point,
+            reorder_qty: self.reorder_qty,
+            quantity_precision: self.quantity_precision,
+            weight: self.weight,
+            dimensions: self.dimensions.clone(),
+            metadata: self.metadata.clone(),
+        }
+    }
+
+    /// Replace the editable fields with `details`, checked as `update` checks
+    /// them. Optional fields `details` leaves unset are cleared.
+    pub fn replace_details(&mut self, details: ItemDetails) -> Result<(), DomainError> {
+        let mut replaced = Item {
+            description: None,
+            category: None,
+            barcode: None,
+            sale_price: None,
+            reorder_point: None,
+            reorder_qty: None,
+            weight: None,
+            dimensions: None,
+            metadata: None,
+            ..self.clone()
+        };
+        replaced.update(UpdateItemRequest {
+            sku: Some(details.sku),
+            name: Some(details.name),
+            description: details.description,
+            category: details.category,
+            unit: Some(details.unit),
+            barcode: details.barcode,
+            cost_price: Some(details.cost_price),
+            sale_price: details.sale_price,
+            reorder_point: details.reorder_point,
+            reorder_qty: details.reorder_qty,
+            quantity_precision: Some(details.quantity_precision),
+            weight: details.weight,
+            dimensions: details.dimensions,
+            metadata: details.metadata,
+        })?;
+        *self = replaced;
+        Ok(())
+    }
+
     pub fn deactivate(&mut self) {
         self.active = false;
         self.updated_at = chrono::Utc::now();
@@ -219,4 +329,102 @@ impl Item {
     pub fn full_name(&self) -> String {
         format!("{} ({})", self.name, self.sku)
     }
+
+    /// Whether a movement taking total on-hand stock from `before` to `after` drops
+    /// it below the reorder point. Only the crossing counts, so further movements
+    /// while already low don't raise the alert again.
+    pub fn falls_below_reorder_point(&self, before: Decimal, after: Decimal) -> bool {
+        self.reorder_point
+            .is_some_and(|reorder_point| before >= reorder_point && after < reorder_point)
+    }
+
+    pub fn set_quantity_precision(&mut self, precision: u32) -> Result<(), DomainError> {
+        if precision > quantity::MAX_PRECISION {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Quantity precision cannot exceed {} decimal places",
+                    quantity::MAX_PRECISION
+                )
+                .into(),
+            ));
+        }
+        self.quantity_precision = precision;
+        Ok(())
+    }
+
+    /// Reject a quantity with more decimal places than this item allows
+    pub fn check_quantity(&self, qty: Decimal) -> Result<(), DomainError> {
+        quantity::check_precision(qty, self.quantity_precision, &self.sku, &self.unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reorder_point_crossing() {
+        let mut item = Item::new(
+            Uuid::new_v4(),
+            "SKU-1".to_string(),
+            "Widget".to_string(),
+            "each".to_string(),
+            Decimal::ONE,
+        )
+        .unwrap();
+        let qty = Decimal::from;
+        assert!(!item.falls_below_reorder_point(qty(10), qty(0)));
+
+        item.reorder_point = Some(qty(5));
+        assert!(item.falls_below_reorder_point(qty(6), qty(4)));
+        assert!(item.falls_below_reorder_point(qty(5), Decimal::new(49, 1)));
+        assert!(!item.falls_below_reorder_point(qty(4), qty(3)));
+        assert!(!item.falls_below_reorder_point(qty(3), qty(8)));
+    }
+
+    #[test]
+    fn test_quantity_precision() {
+        let mut item = Item::new(
+            Uuid::new_v4(),
+            "CABLE-1".to_string(),
+            "Cable".to_string(),
+            "m".to_string(),
+            Decimal::ONE,
+        )
+        .unwrap();
+        assert!(item.check_quantity(Decimal::new(25, 1)).is_err());
+
+        item.set_quantity_precision(2).unwrap();
+        assert!(item.check_quantity(Decimal::new(25, 1)).is_ok());
+        assert!(item.check_quantity(Decimal::new(2505, 3)).is_err());
+        assert!(item.set_quantity_precision(5).is_err());
+    }
+
+    #[test]
+    fn test_replace_details_clears_unset_fields_or_changes_nothing() {
+        let mut item = Item::new(
+            Uuid::new_v4(),
+            "SKU-1".to_string(),
+            "Widget".to_string(),
+            "each".to_string(),
+            Decimal::ONE,
+        )
+        .unwrap();
+        item.barcode = Some("012345678905".to_string());
+        item.reorder_point = Some(Decimal::TEN);
+
+        let mut details = item.details();
+        details.barcode = None;
+        details.name = "Blue widget".to_string();
+        item.replace_details(details).unwrap();
+        assert_eq!(item.barcode, None);
+        assert_eq!(item.reorder_point, Some(Decimal::TEN));
+        assert_eq!(item.name, "Blue widget");
+
+        let mut details = item.details();
+        details.reorder_point = None;
+        details.cost_price = Decimal::NEGATIVE_ONE;
+        assert!(item.replace_details(details).is_err());
+        assert_eq!(item.reorder_point, Some(Decimal::TEN));
+    }
 }
@@ -0,0 +1,144 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const REBUILD_ITEM_AVAILABILITY_JOB_TYPE: &str = "rebuild_item_availability";
+
+/// An item's stock at one location, as kept in the availability read model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LocationAvailability {
+    pub location_id: Uuid,
+    pub quantity_on_hand: Decimal,
+    /// On hand but held in quarantine or as damaged
+    pub quantity_unavailable: Decimal,
+    pub quantity_reserved: Decimal,
+    /// Allocated to sales orders and not shipped yet
+    pub quantity_allocated: Decimal,
+    pub quantity_in_transit: Decimal,
+    pub quantity_available: Decimal,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl LocationAvailability {
+    /// On hand and sellable, less what is reserved or allocated, never below zero
+    pub fn available(
+        on_hand: Decimal,
+        unavailable: Decimal,
+        reserved: Decimal,
+        allocated: Decimal,
+    ) -> Decimal {
+        (on_hand - unavailable - reserved - allocated).max(Decimal::ZERO)
+    }
+}
+
+/// Quantities summed over every location
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AvailabilityTotals {
+    pub quantity_on_hand: Decimal,
+    pub quantity_unavailable: Decimal,
+    pub quantity_reserved: Decimal,
+    pub quantity_allocated: Decimal,
+    pub quantity_in_transit: Decimal,
+    pub quantity_available: Decimal,
+    /// Ordered on open purchase orders and not received yet
+    pub quantity_on_order: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItemAvailability {
+    pub item_id: Uuid,
+    pub totals: AvailabilityTotals,
+    pub locations: Vec<LocationAvailability>,
+}
+
+impl ItemAvailability {
+    pub fn new(item_id: Uuid, locations: Vec<LocationAvailability>, on_order: Decimal) -> Self {
+        let mut totals = AvailabilityTotals {
+            quantity_on_order: on_order,
+            ..Default::default()
+        };
+        for location in &locations {
+            totals.quantity_on_hand += location.quantity_on_hand;
+            totals.quantity_unavailable += location.quantity_unavailable;
+            totals.quantity_reserved += location.quantity_reserved;
+            totals.quantity_allocated += location.quantity_allocated;
+            totals.quantity_in_transit += location.quantity_in_transit;
+            totals.quantity_available += location.quantity_available;
+        }
+        Self {
+            item_id,
+            totals,
+            locations,
+        }
+    }
+}
+
+/// Rows written by a rebuild of the read model
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AvailabilityRebuild {
+    pub locations: u64,
+    pub on_order_items: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(on_hand: Decimal, unavailable: Decimal, reserved: Decimal) -> LocationAvailability {
+        LocationAvailability {
+            location_id: Uuid::new_v4(),
+            quantity_on_hand: on_hand,
+            quantity_unavailable: unavailable,
+            quantity_reserved: reserved,
+            quantity_allocated: Decimal::ZERO,
+            quantity_in_transit: Decimal::from(1),
+            quantity_available: LocationAvailability::available(
+                on_hand,
+                unavailable,
+                reserved,
+                Decimal::ZERO,
+            ),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_available_excludes_held_and_promised_stock_and_floors_at_zero() {
+        assert_eq!(
+            LocationAvailability::available(
+                Decimal::from(10),
+                Decimal::from(2),
+                Decimal::from(3),
+                Decimal::from(1)
+            ),
+            Decimal::from(4)
+        );
+        assert_eq!(
+            LocationAvailability::available(
+                Decimal::from(5),
+                Decimal::from(0),
+                Decimal::from(4),
+                Decimal::from(3)
+            ),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn test_totals_sum_locations_and_carry_on_order() {
+        let availability = ItemAvailability::new(
+            Uuid::new_v4(),
+            vec![
+                location(Decimal::from(10), Decimal::from(2), Decimal::from(3)),
+                location(Decimal::from(1), Decimal::from(0), Decimal::from(4)),
+            ],
+            Decimal::from(25),
+        );
+        assert_eq!(availability.totals.quantity_on_hand, Decimal::from(11));
+        assert_eq!(availability.totals.quantity_reserved, Decimal::from(7));
+        assert_eq!(availability.totals.quantity_in_transit, Decimal::from(2));
+        assert_eq!(availability.totals.quantity_available, Decimal::from(5));
+        assert_eq!(availability.totals.quantity_on_order, Decimal::from(25));
+    }
+}
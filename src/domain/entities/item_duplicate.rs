@@ -0,0 +1,150 @@
+use crate::domain::entities::item::Item;
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How similar two names must be, by trigram similarity from 0 to 1, for the
+/// items to be reported as likely duplicates
+pub const DEFAULT_NAME_SIMILARITY: f64 = 0.6;
+
+/// Why two items were reported as duplicates of each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DuplicateReason {
+    /// The names are close enough to be the same product typed twice
+    SimilarName,
+    /// The barcodes are the same once separators and leading zeros are
+    /// dropped, e.g. a UPC-A and the EAN-13 it is printed as
+    SameBarcode,
+}
+
+/// One side of a duplicate pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateItemRef {
+    pub id: Uuid,
+    pub sku: String,
+    pub name: String,
+    pub barcode: Option<String>,
+}
+
+/// Two active items that look like the same product
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateItemPair {
+    pub item: DuplicateItemRef,
+    pub duplicate: DuplicateItemRef,
+    pub reasons: Vec<DuplicateReason>,
+    /// Trigram similarity of the two names
+    pub name_similarity: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateItemsReport {
+    pub name_similarity_threshold: f64,
+    pub pairs: Vec<DuplicateItemPair>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FindDuplicateItemsQuery {
+    /// Minimum name similarity, 0 to 1; defaults to [`DEFAULT_NAME_SIMILARITY`]
+    pub min_similarity: Option<f64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MergeItemsRequest {
+    /// The item folded into the one in the path; it is deactivated afterwards
+    pub duplicate_id: Uuid,
+}
+
+/// How many rows a merge moved from the duplicate onto the survivor
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ItemMergeCounts {
+    pub stock_levels: u64,
+    pub stock_movements: u64,
+    pub order_lines: u64,
+    pub other_rows: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeItemsResponse {
+    pub survivor_id: Uuid,
+    pub duplicate_id: Uuid,
+    /// The duplicate's barcode when the survivor had none and took it over
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub barcode_moved: Option<String>,
+    pub moved: ItemMergeCounts,
+}
+
+/// Check that `duplicate`'s stock and order lines can be carried by
+/// `survivor` unchanged: same unit, and quantities no finer than the
+/// survivor allows
+pub fn check_mergeable(duplicate: &Item, survivor: &Item) -> Result<(), DomainError> {
+    if duplicate.id == survivor.id {
+        return Err(DomainError::ValidationError(
+            "An item cannot be merged into itself".into(),
+        ));
+    }
+    if !survivor.active {
+        return Err(DomainError::BusinessLogicError(
+            format!(
+                "Item {} is inactive and cannot take over another item",
+                survivor.sku
+            )
+            .into(),
+        ));
+    }
+    if !duplicate.unit.eq_ignore_ascii_case(&survivor.unit) {
+        return Err(DomainError::ValidationError(
+            format!(
+            "Item {} is counted in {} and {} in {}; only items with the same unit can be merged",
+            duplicate.sku, duplicate.unit, survivor.sku, survivor.unit
+        )
+            .into(),
+        ));
+    }
+    if duplicate.quantity_precision > survivor.quantity_precision {
+        return Err(DomainError::ValidationError(format!(
+            "Item {} allows {} decimal places but {} only {}; raise the survivor's quantity precision first",
+            duplicate.sku,
+            duplicate.quantity_precision,
+            survivor.sku,
+            survivor.quantity_precision
+        ).into()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn item(sku: &str, unit: &str) -> Item {
+        Item::new(
+            Uuid::new_v4(),
+            sku.to_string(),
+            "Widget".to_string(),
+            unit.to_string(),
+            Decimal::ONE,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge_requires_matching_unit_and_precision() {
+        let survivor = item("W-1", "each");
+        let duplicate = item("W-1-OLD", "EACH");
+        assert!(check_mergeable(&duplicate, &survivor).is_ok());
+        assert!(check_mergeable(&survivor, &survivor).is_err());
+        assert!(check_mergeable(&item("W-2", "m"), &survivor).is_err());
+
+        let mut finer = item("W-3", "each");
+        finer.set_quantity_precision(2).unwrap();
+        assert!(check_mergeable(&finer, &survivor).is_err());
+        assert!(check_mergeable(&survivor, &finer).is_ok());
+
+        let mut inactive = item("W-4", "each");
+        inactive.deactivate();
+        assert!(check_mergeable(&duplicate, &inactive).is_err());
+    }
+}
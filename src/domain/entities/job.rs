@@ -1,6 +1,8 @@
 use crate::shared::error::DomainError;
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,10 +11,16 @@ pub struct JobError {
     pub message: String,
 }
 
+/// Attempts a job gets before a failure becomes final
+pub const DEFAULT_MAX_ATTEMPTS: i32 = 3;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateJobRequest {
     pub job_type: String,
     pub payload: serde_json::Value,
+    /// Overrides [`DEFAULT_MAX_ATTEMPTS`] for jobs that should keep retrying longer
+    #[serde(default)]
+    pub max_attempts: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -22,6 +30,14 @@ pub enum JobStatus {
     Success,
     Failed,
     PartialSuccess,
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Finished jobs never run again unless retried
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, JobStatus::Queued | JobStatus::Running)
+    }
 }
 
 impl std::fmt::Display for JobStatus {
@@ -32,6 +48,7 @@ impl std::fmt::Display for JobStatus {
             JobStatus::Success => write!(f, "SUCCESS"),
             JobStatus::Failed => write!(f, "FAILED"),
             JobStatus::PartialSuccess => write!(f, "PARTIAL_SUCCESS"),
+            JobStatus::Cancelled => write!(f, "CANCELLED"),
         }
     }
 }
@@ -47,6 +64,15 @@ pub struct Job {
     pub payload: Option<serde_json::Value>,
     pub result_url: Option<String>,
     pub errors: Option<Vec<JobError>>,
+    /// Attempts started so far, including the one running now
+    pub attempts: i32,
+    pub max_attempts: i32,
+    /// Earliest time a queued job may be picked up; pushed back between retries
+    pub run_after: DateTime<Utc>,
+    /// Last sign of life from the worker running the job
+    pub heartbeat_at: Option<DateTime<Utc>>,
+    /// Set when a running job is asked to stop; its handler checks between batches
+    pub cancel_requested: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub started_at: Option<chrono::DateTime<chrono::Utc>>,
@@ -61,7 +87,7 @@ impl Job {
     ) -> Result<Self, DomainError> {
         if job_type.trim().is_empty() {
             return Err(DomainError::ValidationError(
-                "Job type cannot be empty".to_string(),
+                "Job type cannot be empty".into(),
             ));
         }
 
@@ -78,6 +104,11 @@ impl Job {
             payload,
             result_url: None,
             errors: None,
+            attempts: 0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            run_after: now,
+            heartbeat_at: None,
+            cancel_requested: false,
             created_at: now,
             updated_at: now,
             started_at: None,
@@ -87,7 +118,29 @@ impl Job {
 
     pub fn start(&mut self) {
         self.status = JobStatus::Running;
+        self.attempts += 1;
         self.started_at = Some(Utc::now());
+        self.heartbeat_at = self.started_at;
+        self.updated_at = Utc::now();
+    }
+
+    /// Record a failed attempt: requeue the job after `retry_delay` while it has
+    /// attempts left, otherwise fail it for good
+    pub fn fail_attempt(&mut self, errors: Vec<JobError>, retry_delay: Duration) {
+        if self.cancel_requested {
+            self.errors = Some(errors);
+            self.mark_cancelled();
+            return;
+        }
+        if self.attempts >= self.max_attempts {
+            self.complete_failure(errors);
+            return;
+        }
+        self.status = JobStatus::Queued;
+        self.progress = 0;
+        self.errors = Some(errors);
+        self.run_after = Utc::now() + retry_delay;
+        self.heartbeat_at = None;
         self.updated_at = Utc::now();
     }
 
@@ -115,8 +168,138 @@ impl Job {
         self.updated_at = Utc::now();
     }
 
+    /// Cancel a queued job at once; a running job is only flagged, and stops
+    /// when its handler next checks
+    pub fn request_cancel(&mut self) -> Result<(), DomainError> {
+        match self.status {
+            JobStatus::Queued => self.mark_cancelled(),
+            JobStatus::Running => {
+                self.cancel_requested = true;
+                self.updated_at = Utc::now();
+            }
+            _ => {
+                return Err(DomainError::Conflict(
+                    format!(
+                        "Job {} has already finished with status {}",
+                        self.job_id, self.status
+                    )
+                    .into(),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    pub fn mark_cancelled(&mut self) {
+        self.status = JobStatus::Cancelled;
+        self.cancel_requested = false;
+        self.heartbeat_at = None;
+        self.completed_at = Some(Utc::now());
+        self.updated_at = Utc::now();
+    }
+
+    /// Queue a failed or cancelled job to run again from scratch, with a fresh
+    /// set of attempts
+    pub fn retry(&mut self) -> Result<(), DomainError> {
+        if !matches!(self.status, JobStatus::Failed | JobStatus::Cancelled) {
+            return Err(DomainError::Conflict(
+                format!(
+                    "Only failed or cancelled jobs can be retried; job {} is {}",
+                    self.job_id, self.status
+                )
+                .into(),
+            ));
+        }
+        let now = Utc::now();
+        self.status = JobStatus::Queued;
+        self.progress = 0;
+        self.result_url = None;
+        self.errors = None;
+        self.attempts = 0;
+        self.run_after = now;
+        self.heartbeat_at = None;
+        self.cancel_requested = false;
+        self.started_at = None;
+        self.completed_at = None;
+        self.updated_at = now;
+        Ok(())
+    }
+
     pub fn update_progress(&mut self, progress: i32) {
         self.progress = progress.clamp(0, 100);
+        self.heartbeat_at = Some(Utc::now());
         self.updated_at = Utc::now();
     }
 }
+
+/// Shared flag a job worker raises when the running job has been asked to stop.
+/// Long-running job code checks it between batches and returns early.
+#[derive(Debug, Clone, Default)]
+pub struct JobCancellation(Arc<AtomicBool>);
+
+impl JobCancellation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_attempts_requeue_until_exhausted() {
+        let mut job = Job::new(Uuid::new_v4(), "stock_csv_export".to_string(), None).unwrap();
+        let error = || {
+            vec![JobError {
+                row: None,
+                message: "storage unavailable".to_string(),
+            }]
+        };
+
+        for attempt in 1..DEFAULT_MAX_ATTEMPTS {
+            job.start();
+            assert_eq!(job.attempts, attempt);
+            job.fail_attempt(error(), Duration::seconds(30));
+            assert_eq!(job.status, JobStatus::Queued);
+            assert!(job.run_after > Utc::now());
+        }
+
+        job.start();
+        job.fail_attempt(error(), Duration::seconds(30));
+        assert_eq!(job.status, JobStatus::Failed);
+        assert!(job.completed_at.is_some());
+    }
+
+    #[test]
+    fn test_cancel_and_retry() {
+        let mut queued = Job::new(Uuid::new_v4(), "import_items".to_string(), None).unwrap();
+        queued.request_cancel().unwrap();
+        assert_eq!(queued.status, JobStatus::Cancelled);
+        assert!(queued.request_cancel().is_err());
+
+        let mut running = Job::new(Uuid::new_v4(), "import_items".to_string(), None).unwrap();
+        running.start();
+        running.request_cancel().unwrap();
+        assert_eq!(running.status, JobStatus::Running);
+        assert!(running.cancel_requested);
+
+        // A failed attempt of a job being cancelled isn't requeued
+        running.fail_attempt(Vec::new(), Duration::seconds(30));
+        assert_eq!(running.status, JobStatus::Cancelled);
+
+        running.retry().unwrap();
+        assert_eq!(running.status, JobStatus::Queued);
+        assert_eq!(running.attempts, 0);
+        assert!(!running.cancel_requested);
+        assert!(running.retry().is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Query-string filters and sort order shared by the collection endpoints.
+/// Each resource supports a subset; repositories reject the rest rather than ignoring them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListFilter {
+    /// One status or a comma-separated list, e.g. `OPEN,RECEIVING`
+    pub status: Option<String>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    pub supplier_id: Option<Uuid>,
+    pub customer_id: Option<Uuid>,
+    pub sku: Option<String>,
+    pub category: Option<String>,
+    /// Case-insensitive free-text match on the resource's number/name fields
+    pub q: Option<String>,
+    /// Field to sort by, prefixed with `-` for descending, e.g. `-created_at`
+    pub sort: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortOrder {
+    pub field: String,
+    pub descending: bool,
+}
+
+impl SortOrder {
+    pub fn parse(value: &str) -> Result<Self, DomainError> {
+        let value = value.trim();
+        let (field, descending) = match value.strip_prefix('-') {
+            Some(field) => (field, true),
+            None => (value.strip_prefix('+').unwrap_or(value), false),
+        };
+        if field.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Sort field cannot be empty".into(),
+            ));
+        }
+        Ok(Self {
+            field: field.to_lowercase(),
+            descending,
+        })
+    }
+}
+
+impl ListFilter {
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if let (Some(from), Some(to)) = (self.created_from, self.created_to) {
+            if from > to {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.created_range",
+                    &[],
+                )));
+            }
+        }
+        self.sort_order()?;
+        Ok(())
+    }
+
+    /// Requested statuses, upper-cased to match the stored values
+    pub fn statuses(&self) -> Vec<String> {
+        self.status
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().to_uppercase())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    pub fn search_text(&self) -> Option<&str> {
+        self.q.as_deref().map(str::trim).filter(|q| !q.is_empty())
+    }
+
+    pub fn sort_order(&self) -> Result<Option<SortOrder>, DomainError> {
+        match self.sort.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(sort) => SortOrder::parse(sort).map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_and_status_parsing() {
+        let filter = ListFilter {
+            status: Some("open, receiving,,".to_string()),
+            sort: Some("-Created_At".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(filter.statuses(), vec!["OPEN", "RECEIVING"]);
+        assert_eq!(
+            filter.sort_order().unwrap(),
+            Some(SortOrder {
+                field: "created_at".to_string(),
+                descending: true,
+            })
+        );
+        assert!(!SortOrder::parse("sku").unwrap().descending);
+        assert!(SortOrder::parse("-").is_err());
+    }
+
+    #[test]
+    fn test_rejects_inverted_date_range() {
+        let filter = ListFilter {
+            created_from: Some(Utc::now()),
+            created_to: Some(Utc::now() - chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        assert!(filter.validate().is_err());
+    }
+}
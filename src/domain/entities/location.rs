@@ -1,4 +1,6 @@
+use crate::domain::entities::location_capacity::LocationCapacity;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -13,12 +15,35 @@ pub struct LocationAddress {
     pub country: Option<String>,
 }
 
+impl LocationAddress {
+    /// Postal lines as printed on labels and documents
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        lines.extend(self.line1.clone());
+        lines.extend(self.line2.clone());
+        let locality = [&self.city, &self.region, &self.postal_code]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !locality.is_empty() {
+            lines.push(locality);
+        }
+        lines.extend(self.country.clone());
+        lines
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateLocationRequest {
     pub name: Option<String>,
     pub code: Option<String>,
     pub address: Option<LocationAddress>,
     pub r#type: Option<String>,
+    /// Replaces the location's capacity limits as a whole
+    #[serde(default)]
+    pub capacity: Option<LocationCapacity>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,14 +70,30 @@ impl LocationType {
             "warehouse" => Ok(LocationType::Warehouse),
             "store" => Ok(LocationType::Store),
             "drop-ship" => Ok(LocationType::DropShip),
-            _ => Err(DomainError::ValidationError(format!(
-                "Invalid location type: {}. Must be one of: warehouse, store, drop-ship",
-                s.as_ref()
-            ))),
+            _ => Err(DomainError::ValidationError(
+                format!(
+                    "Invalid location type: {}. Must be one of: warehouse, store, drop-ship",
+                    s.as_ref()
+                )
+                .into(),
+            )),
         }
     }
 }
 
+/// The fields of a location a client edits, as one document. A merge patch
+/// is applied to this, so fields it leaves out are cleared.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocationDetails {
+    pub name: String,
+    pub code: Option<String>,
+    pub address: Option<LocationAddress>,
+    pub r#type: Option<LocationType>,
+    #[serde(default)]
+    pub capacity: LocationCapacity,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Location {
     pub id: Uuid,
@@ -61,6 +102,8 @@ pub struct Location {
     pub address: Option<LocationAddress>,
     pub r#type: Option<LocationType>,
     pub active: bool,
+    #[serde(default)]
+    pub capacity: LocationCapacity,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
@@ -68,9 +111,10 @@ pub struct Location {
 impl Location {
     pub fn new(name: String) -> Result<Self, DomainError> {
         if name.trim().is_empty() {
-            return Err(DomainError::ValidationError(
-                "Name cannot be empty".to_string(),
-            ));
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.name_empty",
+                &[],
+            )));
         }
 
         let now = chrono::Utc::now();
@@ -82,6 +126,7 @@ impl Location {
             address: None,
             r#type: None,
             active: true,
+            capacity: LocationCapacity::default(),
             created_at: now,
             updated_at: now,
         })
@@ -90,9 +135,10 @@ impl Location {
     pub fn update(&mut self, request: UpdateLocationRequest) -> Result<(), DomainError> {
         if let Some(name) = request.name {
             if name.trim().is_empty() {
-                return Err(DomainError::ValidationError(
-                    "Name cannot be empty".to_string(),
-                ));
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.name_empty",
+                    &[],
+                )));
             }
             self.name = name;
         }
@@ -109,10 +155,45 @@ impl Location {
             self.r#type = Some(LocationType::from_str(&type_str)?);
         }
 
+        if let Some(capacity) = request.capacity {
+            capacity.validate()?;
+            self.capacity = capacity;
+        }
+
         self.updated_at = Utc::now();
         Ok(())
     }
 
+    pub fn details(&self) -> LocationDetails {
+        LocationDetails {
+            name: self.name.clone(),
+            code: self.code.clone(),
+            address: self.address.clone(),
+            r#type: self.r#type.clone(),
+            capacity: self.capacity.clone(),
+        }
+    }
+
+    /// Replace the editable fields with `details`, checked as `update` checks
+    /// them. Optional fields `details` leaves unset are cleared.
+    pub fn replace_details(&mut self, details: LocationDetails) -> Result<(), DomainError> {
+        let mut replaced = Location {
+            code: None,
+            address: None,
+            r#type: None,
+            ..self.clone()
+        };
+        replaced.update(UpdateLocationRequest {
+            name: Some(details.name),
+            code: details.code,
+            address: details.address,
+            r#type: details.r#type.map(|t| t.as_str().to_string()),
+            capacity: Some(details.capacity),
+        })?;
+        *self = replaced;
+        Ok(())
+    }
+
     pub fn deactivate(&mut self) {
         self.active = false;
         self.updated_at = chrono::Utc::now();
@@ -0,0 +1,412 @@
+use crate::domain::entities::item::ItemDimensions;
+use crate::shared::error::DomainError;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What happens to a receipt that would take a location past its capacity
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CapacityEnforcement {
+    /// Accept it and report the breach alongside the receipt
+    #[default]
+    Warn,
+    /// Refuse it
+    Enforce,
+}
+
+impl CapacityEnforcement {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CapacityEnforcement::Warn => "WARN",
+            CapacityEnforcement::Enforce => "ENFORCE",
+        }
+    }
+
+    pub fn from_str<S: AsRef<str>>(s: S) -> Result<Self, DomainError> {
+        match s.as_ref() {
+            "WARN" => Ok(CapacityEnforcement::Warn),
+            "ENFORCE" => Ok(CapacityEnforcement::Enforce),
+            _ => Err(DomainError::ValidationError(
+                format!(
+                    "Invalid capacity enforcement: {}. Must be one of: WARN, ENFORCE",
+                    s.as_ref()
+                )
+                .into(),
+            )),
+        }
+    }
+}
+
+/// How much a location can hold. Volume is in the cube of whatever unit item
+/// dimensions are recorded in; a limit left unset is unbounded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LocationCapacity {
+    pub max_volume: Option<f64>,
+    pub max_weight: Option<f64>,
+    pub max_pallets: Option<i32>,
+    #[serde(default)]
+    pub enforcement: CapacityEnforcement,
+}
+
+impl LocationCapacity {
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.max_volume.is_some_and(|v| v <= 0.0)
+            || self.max_weight.is_some_and(|w| w <= 0.0)
+            || self.max_pallets.is_some_and(|p| p <= 0)
+        {
+            return Err(DomainError::ValidationError(
+                "Location capacity limits must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn is_bounded(&self) -> bool {
+        self.max_volume.is_some() || self.max_weight.is_some() || self.max_pallets.is_some()
+    }
+
+    /// The limits the usage goes past
+    pub fn breaches(&self, usage: &CapacityUsage) -> Vec<CapacityBreach> {
+        let mut breaches = Vec::new();
+        if let Some(max) = self.max_volume.filter(|max| usage.volume > *max) {
+            breaches.push(CapacityBreach::new(
+                CapacityDimension::Volume,
+                max,
+                usage.volume,
+            ));
+        }
+        if let Some(max) = self.max_weight.filter(|max| usage.weight > *max) {
+            breaches.push(CapacityBreach::new(
+                CapacityDimension::Weight,
+                max,
+                usage.weight,
+            ));
+        }
+        if let Some(max) = self
+            .max_pallets
+            .filter(|max| usage.pallets > i64::from(*max))
+        {
+            breaches.push(CapacityBreach::new(
+                CapacityDimension::Pallets,
+                f64::from(max),
+                usage.pallets as f64,
+            ));
+        }
+        breaches
+    }
+
+    /// How many more units of an item fit, given what the location already
+    /// holds and how much of that is this item; `None` when no limit applies
+    /// to the item because it is unbounded or the item isn't measured for it
+    pub fn room_for(
+        &self,
+        usage: &CapacityUsage,
+        footprint: &ItemFootprint,
+        item_on_hand: Decimal,
+    ) -> Option<Decimal> {
+        let mut room: Option<Decimal> = None;
+        let mut limit = |units: f64| {
+            let units = Decimal::from_f64_retain(units.max(0.0).floor()).unwrap_or_default();
+            room = Some(room.map_or(units, |r| r.min(units)));
+        };
+
+        if let (Some(max), Some(volume)) = (self.max_volume, footprint.volume) {
+            limit((max - usage.volume) / volume);
+        }
+        if let (Some(max), Some(weight)) = (self.max_weight, footprint.weight) {
+            limit((max - usage.weight) / weight);
+        }
+        if let (Some(max), Some(per_pallet)) = (self.max_pallets, footprint.units_per_pallet) {
+            // Free pallet positions, plus whatever is left on the item's last
+            // part-filled pallet
+            let on_hand = item_on_hand.to_f64().unwrap_or_default().max(0.0);
+            let part_filled = (on_hand / per_pallet).ceil() * per_pallet - on_hand;
+            let free = (i64::from(max) - usage.pallets).max(0) as f64;
+            limit(free * per_pallet + part_filled);
+        }
+        room
+    }
+}
+
+/// The space one unit of an item takes up, as far as the item records it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemFootprint {
+    pub item_id: Uuid,
+    /// Length × width × height, when all three are recorded
+    pub volume: Option<f64>,
+    pub weight: Option<f64>,
+    /// From the item's `units_per_pallet` metadata
+    pub units_per_pallet: Option<f64>,
+}
+
+impl ItemFootprint {
+    pub fn from_parts(
+        item_id: Uuid,
+        weight: Option<f64>,
+        dimensions: Option<&ItemDimensions>,
+        metadata: Option<&serde_json::Value>,
+    ) -> Self {
+        let volume = dimensions
+            .and_then(|d| Some(d.length? * d.width? * d.height?))
+            .filter(|volume| *volume > 0.0);
+        let units_per_pallet = metadata
+            .and_then(|m| m.get("units_per_pallet")?.as_f64())
+            .filter(|units| *units > 0.0);
+        Self {
+            item_id,
+            volume,
+            weight: weight.filter(|weight| *weight > 0.0),
+            units_per_pallet,
+        }
+    }
+
+    /// Missing any of volume, weight or pallet size, so it can't count fully
+    /// against a location's capacity
+    pub fn is_unmeasured(&self) -> bool {
+        self.volume.is_none() || self.weight.is_none() || self.units_per_pallet.is_none()
+    }
+}
+
+/// A quantity of one item held at, or headed for, a location
+#[derive(Debug, Clone)]
+pub struct StockFootprint {
+    pub footprint: ItemFootprint,
+    pub quantity: Decimal,
+}
+
+/// What a location's stock takes up. Each item is palletised on its own, so
+/// pallets round up per item.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CapacityUsage {
+    pub volume: f64,
+    pub weight: f64,
+    pub pallets: i64,
+    /// Items held that lack volume, weight or pallet size and so count only
+    /// towards the limits they are measured for
+    pub unmeasured_items: usize,
+}
+
+impl CapacityUsage {
+    pub fn of(stock: &[StockFootprint]) -> Self {
+        let mut usage = Self::default();
+        for line in stock {
+            let quantity = line.quantity.to_f64().unwrap_or_default();
+            if quantity <= 0.0 {
+                continue;
+            }
+            let footprint = &line.footprint;
+            usage.volume += footprint.volume.unwrap_or_default() * quantity;
+            usage.weight += footprint.weight.unwrap_or_default() * quantity;
+            if let Some(per_pallet) = footprint.units_per_pallet {
+                usage.pallets += (quantity / per_pallet).ceil() as i64;
+            }
+            if footprint.is_unmeasured() {
+                usage.unmeasured_items += 1;
+            }
+        }
+        usage
+    }
+
+    pub fn of_dimension(&self, dimension: CapacityDimension) -> f64 {
+        match dimension {
+            CapacityDimension::Volume => self.volume,
+            CapacityDimension::Weight => self.weight,
+            CapacityDimension::Pallets => self.pallets as f64,
+        }
+    }
+}
+
+/// A location's capacity with the stock it holds
+#[derive(Debug, Clone)]
+pub struct LocationStock {
+    pub location_id: Uuid,
+    pub name: String,
+    pub code: Option<String>,
+    pub capacity: LocationCapacity,
+    pub stock: Vec<StockFootprint>,
+}
+
+impl LocationStock {
+    /// The stock with `incoming` added to it, merged per item
+    pub fn with_incoming(&self, incoming: &[StockFootprint]) -> Vec<StockFootprint> {
+        let mut stock = self.stock.clone();
+        for line in incoming {
+            match stock
+                .iter_mut()
+                .find(|s| s.footprint.item_id == line.footprint.item_id)
+            {
+                Some(existing) => existing.quantity += line.quantity,
+                None => stock.push(line.clone()),
+            }
+        }
+        stock
+    }
+
+    pub fn item_on_hand(&self, item_id: Uuid) -> Decimal {
+        self.stock
+            .iter()
+            .filter(|s| s.footprint.item_id == item_id)
+            .map(|s| s.quantity)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CapacityDimension {
+    Volume,
+    Weight,
+    Pallets,
+}
+
+/// A capacity limit a location is, or would be, over
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CapacityBreach {
+    pub dimension: CapacityDimension,
+    pub capacity: f64,
+    pub projected: f64,
+}
+
+impl CapacityBreach {
+    fn new(dimension: CapacityDimension, capacity: f64, projected: f64) -> Self {
+        Self {
+            dimension,
+            capacity,
+            projected,
+        }
+    }
+}
+
+/// A receipt accepted into a location it takes over capacity, for a location
+/// that warns rather than enforces
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapacityWarning {
+    pub location_id: Uuid,
+    pub breaches: Vec<CapacityBreach>,
+}
+
+/// One location's row of the utilization report. Percentages are `None` for
+/// limits the location doesn't set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationUtilization {
+    pub location_id: Uuid,
+    pub name: String,
+    pub code: Option<String>,
+    pub capacity: LocationCapacity,
+    pub used_volume: f64,
+    pub used_weight: f64,
+    pub used_pallets: i64,
+    pub volume_utilization_pct: Option<f64>,
+    pub weight_utilization_pct: Option<f64>,
+    pub pallet_utilization_pct: Option<f64>,
+    pub unmeasured_items: usize,
+    pub over_capacity: bool,
+}
+
+impl LocationUtilization {
+    pub fn of(location: &LocationStock) -> Self {
+        let usage = CapacityUsage::of(&location.stock);
+        let capacity = &location.capacity;
+        let pct = |used: f64, max: f64| (used / max * 10000.0).round() / 100.0;
+        Self {
+            location_id: location.location_id,
+            name: location.name.clone(),
+            code: location.code.clone(),
+            volume_utilization_pct: capacity.max_volume.map(|max| pct(usage.volume, max)),
+            weight_utilization_pct: capacity.max_weight.map(|max| pct(usage.weight, max)),
+            pallet_utilization_pct: capacity
+                .max_pallets
+                .map(|max| pct(usage.pallets as f64, f64::from(max))),
+            over_capacity: !capacity.breaches(&usage).is_empty(),
+            capacity: capacity.clone(),
+            used_volume: usage.volume,
+            used_weight: usage.weight,
+            used_pallets: usage.pallets,
+            unmeasured_items: usage.unmeasured_items,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn footprint(volume: f64, weight: f64, units_per_pallet: Option<f64>) -> ItemFootprint {
+        ItemFootprint {
+            item_id: Uuid::new_v4(),
+            volume: Some(volume),
+            weight: Some(weight),
+            units_per_pallet,
+        }
+    }
+
+    fn line(footprint: &ItemFootprint, quantity: i64) -> StockFootprint {
+        StockFootprint {
+            footprint: footprint.clone(),
+            quantity: Decimal::from(quantity),
+        }
+    }
+
+    #[test]
+    fn test_usage_rounds_pallets_up_per_item() {
+        let boxes = footprint(2.0, 1.5, Some(10.0));
+        let crates = footprint(4.0, 3.0, Some(4.0));
+        let usage = CapacityUsage::of(&[line(&boxes, 15), line(&crates, 4)]);
+
+        assert_eq!(usage.volume, 46.0);
+        assert_eq!(usage.weight, 34.5);
+        assert_eq!(usage.pallets, 3);
+        assert_eq!(usage.unmeasured_items, 0);
+    }
+
+    #[test]
+    fn test_breaches_and_room() {
+        let capacity = LocationCapacity {
+            max_volume: Some(100.0),
+            max_weight: None,
+            max_pallets: Some(3),
+            enforcement: CapacityEnforcement::Enforce,
+        };
+        let boxes = footprint(2.0, 1.0, Some(10.0));
+        let usage = CapacityUsage::of(&[line(&boxes, 15)]);
+        assert!(capacity.breaches(&usage).is_empty());
+
+        // 35 more by volume, but only the 5 left on the second pallet plus one
+        // free pallet position
+        assert_eq!(
+            capacity.room_for(&usage, &boxes, Decimal::from(15)),
+            Some(Decimal::from(15))
+        );
+
+        let over = CapacityUsage::of(&[line(&boxes, 55)]);
+        let breaches = capacity.breaches(&over);
+        assert_eq!(breaches.len(), 2);
+        assert_eq!(breaches[0].dimension, CapacityDimension::Volume);
+        assert_eq!(breaches[1].dimension, CapacityDimension::Pallets);
+        assert_eq!(
+            capacity.room_for(&over, &boxes, Decimal::from(55)),
+            Some(Decimal::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_unmeasured_items_are_unbounded() {
+        let capacity = LocationCapacity {
+            max_volume: Some(10.0),
+            ..Default::default()
+        };
+        let loose = ItemFootprint {
+            item_id: Uuid::new_v4(),
+            volume: None,
+            weight: Some(1.0),
+            units_per_pallet: None,
+        };
+        let usage = CapacityUsage::of(&[line(&loose, 500)]);
+
+        assert_eq!(usage.unmeasured_items, 1);
+        assert!(capacity.breaches(&usage).is_empty());
+        assert_eq!(capacity.room_for(&usage, &loose, Decimal::from(500)), None);
+    }
+}
@@ -1,14 +1,50 @@
+pub mod address;
+pub mod adjustment;
+pub mod allocation;
+pub mod attachment;
+pub mod credit;
+pub mod currency;
+pub mod cycle_count;
+pub mod document;
+pub mod edi;
 pub mod export;
+pub mod forecast;
+pub mod gs1;
 pub mod idempotency;
+pub mod inbound_shipment;
+pub mod integration;
 pub mod inventory;
+pub mod invitation;
+pub mod invoice;
 pub mod item;
+pub mod item_availability;
+pub mod item_duplicate;
 pub mod job;
+pub mod list_filter;
 pub mod location;
+pub mod location_capacity;
+pub mod printing;
+pub mod product;
 pub mod purchase_order;
+pub mod putaway;
+pub mod receiving;
+pub mod replenishment;
 pub mod returns;
 pub mod sales_order;
+pub mod sandbox_seed;
 pub mod search;
+pub mod session;
+pub mod shipment;
+pub mod status_history;
+pub mod stock_snapshot;
 pub mod tenant;
+pub mod tenant_audit;
+pub mod tenant_snapshot;
+pub mod time_zone;
 pub mod transfer;
+pub mod two_factor;
 pub mod user;
+pub mod vendor_return;
 pub mod webhook;
+pub mod webhook_filter;
+pub mod webhook_retention;
@@ -0,0 +1,370 @@
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const PRINT_LABELS_JOB_TYPE: &str = "print_labels";
+
+/// Attempts a print job gets before it is failed. With the worker's doubling
+/// backoff this rides out a printer that is off line for about an hour.
+pub const PRINT_JOB_MAX_ATTEMPTS: i32 = 8;
+
+/// Raw ZPL port Zebra printers listen on
+pub const DEFAULT_PRINTER_PORT: i32 = 9100;
+
+pub const MAX_PRINT_COPIES: i32 = 100;
+
+/// A networked ZPL printer at a location, reached over raw TCP
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Printer {
+    pub id: Uuid,
+    pub location_id: Uuid,
+    pub name: String,
+    pub host: String,
+    pub port: i32,
+    /// Takes the print jobs sent to its location rather than to a printer
+    pub is_default: bool,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Printer {
+    pub fn new(request: CreatePrinterRequest) -> Result<Self, DomainError> {
+        let now = Utc::now();
+        let printer = Self {
+            id: Uuid::new_v4(),
+            location_id: request.location_id,
+            name: request.name.trim().to_string(),
+            host: request.host.trim().to_string(),
+            port: request.port.unwrap_or(DEFAULT_PRINTER_PORT),
+            is_default: request.is_default.unwrap_or(false),
+            active: request.active.unwrap_or(true),
+            created_at: now,
+            updated_at: now,
+        };
+        printer.validate()?;
+        Ok(printer)
+    }
+
+    pub fn update(&mut self, request: UpdatePrinterRequest) -> Result<(), DomainError> {
+        if let Some(location_id) = request.location_id {
+            self.location_id = location_id;
+        }
+        if let Some(name) = request.name {
+            self.name = name.trim().to_string();
+        }
+        if let Some(host) = request.host {
+            self.host = host.trim().to_string();
+        }
+        if let Some(port) = request.port {
+            self.port = port;
+        }
+        if let Some(is_default) = request.is_default {
+            self.is_default = is_default;
+        }
+        if let Some(active) = request.active {
+            self.active = active;
+        }
+        self.validate()?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), DomainError> {
+        if self.name.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Printer name cannot be empty".into(),
+            ));
+        }
+        if self.host.is_empty() || self.host.contains(char::is_whitespace) {
+            return Err(DomainError::ValidationError(
+                "Printer host must be a host name or IP address".into(),
+            ));
+        }
+        if !(1..=65535).contains(&self.port) {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.field_between",
+                &[("field", &"Printer port"), ("min", &1), ("max", &65535)],
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePrinterRequest {
+    pub location_id: Uuid,
+    pub name: String,
+    pub host: String,
+    pub port: Option<i32>,
+    pub is_default: Option<bool>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdatePrinterRequest {
+    pub location_id: Option<Uuid>,
+    pub name: Option<String>,
+    pub host: Option<String>,
+    pub port: Option<i32>,
+    pub is_default: Option<bool>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PrintJobStatus {
+    /// Waiting for its first or next attempt
+    Queued,
+    Printing,
+    Printed,
+    /// Out of attempts, or the printer is gone
+    Failed,
+}
+
+impl PrintJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrintJobStatus::Queued => "QUEUED",
+            PrintJobStatus::Printing => "PRINTING",
+            PrintJobStatus::Printed => "PRINTED",
+            PrintJobStatus::Failed => "FAILED",
+        }
+    }
+
+    pub fn from_str<S: AsRef<str>>(s: S) -> Result<Self, DomainError> {
+        match s.as_ref() {
+            "QUEUED" => Ok(PrintJobStatus::Queued),
+            "PRINTING" => Ok(PrintJobStatus::Printing),
+            "PRINTED" => Ok(PrintJobStatus::Printed),
+            "FAILED" => Ok(PrintJobStatus::Failed),
+            _ => Err(DomainError::ValidationError(
+                format!(
+                "Invalid print job status: {}. Must be one of: QUEUED, PRINTING, PRINTED, FAILED",
+                s.as_ref()
+            )
+                .into(),
+            )),
+        }
+    }
+}
+
+/// What a print job prints
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PrintJobSource {
+    /// The GS1-128 carton labels of a shipment
+    ShipmentLabels,
+    /// ZPL supplied by the caller
+    Raw,
+}
+
+impl PrintJobSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrintJobSource::ShipmentLabels => "SHIPMENT_LABELS",
+            PrintJobSource::Raw => "RAW",
+        }
+    }
+
+    pub fn from_str<S: AsRef<str>>(s: S) -> Result<Self, DomainError> {
+        match s.as_ref() {
+            "SHIPMENT_LABELS" => Ok(PrintJobSource::ShipmentLabels),
+            "RAW" => Ok(PrintJobSource::Raw),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid print job source: {}", s.as_ref()).into(),
+            )),
+        }
+    }
+}
+
+/// ZPL queued for a printer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrintJob {
+    pub id: Uuid,
+    pub printer_id: Uuid,
+    /// The background job delivering it
+    pub job_id: Option<String>,
+    pub source_type: PrintJobSource,
+    pub source_id: Option<Uuid>,
+    #[serde(skip_serializing)]
+    pub content: String,
+    pub copies: i32,
+    pub status: PrintJobStatus,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub printed_at: Option<DateTime<Utc>>,
+}
+
+impl PrintJob {
+    pub fn new(
+        printer_id: Uuid,
+        source_type: PrintJobSource,
+        source_id: Option<Uuid>,
+        content: String,
+        copies: Option<i32>,
+    ) -> Result<Self, DomainError> {
+        if !content.trim_start().starts_with("^XA") {
+            return Err(DomainError::ValidationError(
+                "Print content must be ZPL starting with ^XA".into(),
+            ));
+        }
+        let copies = copies.unwrap_or(1);
+        if !(1..=MAX_PRINT_COPIES).contains(&copies) {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.field_between",
+                &[
+                    ("field", &"copies"),
+                    ("min", &1),
+                    ("max", &MAX_PRINT_COPIES),
+                ],
+            )));
+        }
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            printer_id,
+            job_id: None,
+            source_type,
+            source_id,
+            content,
+            copies,
+            status: PrintJobStatus::Queued,
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+            printed_at: None,
+        })
+    }
+
+    /// The bytes sent to the printer: the content once per copy
+    pub fn payload(&self) -> Vec<u8> {
+        self.content.repeat(self.copies as usize).into_bytes()
+    }
+
+    pub fn start_attempt(&mut self) {
+        self.status = PrintJobStatus::Printing;
+        self.attempts += 1;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn mark_printed(&mut self) {
+        self.status = PrintJobStatus::Printed;
+        self.last_error = None;
+        self.updated_at = Utc::now();
+        self.printed_at = Some(self.updated_at);
+    }
+
+    /// Record a failed attempt: back to the queue while its background job
+    /// will try again, failed once that was the last attempt
+    pub fn fail_attempt(&mut self, error: String, will_retry: bool) {
+        self.status = if will_retry {
+            PrintJobStatus::Queued
+        } else {
+            PrintJobStatus::Failed
+        };
+        self.last_error = Some(error);
+        self.updated_at = Utc::now();
+    }
+
+    /// Queue a failed job again under a new background job
+    pub fn requeue(&mut self) -> Result<(), DomainError> {
+        if self.status != PrintJobStatus::Failed {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Only failed print jobs can be retried; this one is {}",
+                    self.status.as_str()
+                )
+                .into(),
+            ));
+        }
+        self.status = PrintJobStatus::Queued;
+        self.job_id = None;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_request() -> CreatePrinterRequest {
+        CreatePrinterRequest {
+            location_id: Uuid::new_v4(),
+            name: " Dock 1 Zebra ".to_string(),
+            host: "10.0.4.21".to_string(),
+            port: None,
+            is_default: Some(true),
+            active: None,
+        }
+    }
+
+    #[test]
+    fn test_printer_defaults_and_validation() {
+        let printer = Printer::new(create_request()).unwrap();
+        assert_eq!(printer.name, "Dock 1 Zebra");
+        assert_eq!(printer.port, DEFAULT_PRINTER_PORT);
+        assert!(printer.active);
+
+        let mut bad_port = create_request();
+        bad_port.port = Some(70000);
+        assert!(Printer::new(bad_port).is_err());
+
+        let mut bad_host = create_request();
+        bad_host.host = "printer one".to_string();
+        assert!(Printer::new(bad_host).is_err());
+    }
+
+    #[test]
+    fn test_print_job_lifecycle() {
+        let zpl = "^XA^FDHello^FS^XZ".to_string();
+        assert!(PrintJob::new(
+            Uuid::new_v4(),
+            PrintJobSource::Raw,
+            None,
+            "hello".into(),
+            None
+        )
+        .is_err());
+        assert!(PrintJob::new(
+            Uuid::new_v4(),
+            PrintJobSource::Raw,
+            None,
+            zpl.clone(),
+            Some(0)
+        )
+        .is_err());
+
+        let mut job = PrintJob::new(
+            Uuid::new_v4(),
+            PrintJobSource::Raw,
+            None,
+            zpl.clone(),
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(job.payload(), format!("{}{}", zpl, zpl).into_bytes());
+        assert!(job.requeue().is_err());
+
+        job.start_attempt();
+        job.fail_attempt("connection refused".to_string(), true);
+        assert_eq!(job.status, PrintJobStatus::Queued);
+        job.start_attempt();
+        job.fail_attempt("connection refused".to_string(), false);
+        assert_eq!(job.status, PrintJobStatus::Failed);
+        assert_eq!(job.attempts, 2);
+
+        job.requeue().unwrap();
+        job.start_attempt();
+        job.mark_printed();
+        assert_eq!(job.status, PrintJobStatus::Printed);
+        assert!(job.last_error.is_none());
+        assert!(job.printed_at.is_some());
+    }
+}
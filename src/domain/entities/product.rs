@@ -0,0 +1,438 @@
+use crate::domain::entities::item::Item;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+/// Most variants one product may have, i.e. the largest product of its
+/// attributes' value counts
+pub const MAX_VARIANTS_PER_PRODUCT: usize = 500;
+
+/// A dimension a product's variants differ along, e.g. size or colour
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProductAttribute {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+/// A parent grouping item variants that share a SKU prefix. Each variant is an
+/// ordinary item carrying the product's id and its value for every attribute;
+/// items without a product are unaffected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Product {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub parent_sku: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub unit: String,
+    /// In the order their values appear in variant SKUs and names
+    pub attributes: Vec<ProductAttribute>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Product {
+    pub fn new(tenant_id: Uuid, request: CreateProductRequest) -> Result<Self, DomainError> {
+        let now = Utc::now();
+        let product = Self {
+            id: Uuid::new_v4(),
+            tenant_id,
+            parent_sku: request.parent_sku.trim().to_string(),
+            name: request.name.trim().to_string(),
+            description: normalize(request.description),
+            category: normalize(request.category),
+            unit: request.unit.trim().to_string(),
+            attributes: normalize_attributes(request.attributes),
+            created_at: now,
+            updated_at: now,
+        };
+        product.validate()?;
+        Ok(product)
+    }
+
+    /// Changes apply to variants generated afterwards; existing variants keep
+    /// their SKU, name and attribute values
+    pub fn update(&mut self, request: UpdateProductRequest) -> Result<(), DomainError> {
+        if let Some(name) = request.name {
+            self.name = name.trim().to_string();
+        }
+        if let Some(description) = request.description {
+            self.description = normalize(Some(description));
+        }
+        if let Some(category) = request.category {
+            self.category = normalize(Some(category));
+        }
+        if let Some(unit) = request.unit {
+            self.unit = unit.trim().to_string();
+        }
+        if let Some(attributes) = request.attributes {
+            self.attributes = normalize_attributes(attributes);
+        }
+        self.validate()?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), DomainError> {
+        if self.parent_sku.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Parent SKU cannot be empty".into(),
+            ));
+        }
+        if self.name.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Product name cannot be empty".into(),
+            ));
+        }
+        if self.unit.is_empty() {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.unit_empty",
+                &[],
+            )));
+        }
+        if self.attributes.is_empty() {
+            return Err(DomainError::ValidationError(
+                "A product needs at least one attribute".into(),
+            ));
+        }
+
+        let mut names = HashSet::new();
+        for attribute in &self.attributes {
+            if attribute.name.is_empty() {
+                return Err(DomainError::ValidationError(
+                    "Attribute name cannot be empty".into(),
+                ));
+            }
+            if !names.insert(attribute.name.to_lowercase()) {
+                return Err(DomainError::ValidationError(
+                    format!("Attribute '{}' is defined twice", attribute.name).into(),
+                ));
+            }
+            if attribute.values.is_empty() {
+                return Err(DomainError::ValidationError(
+                    format!("Attribute '{}' needs at least one value", attribute.name).into(),
+                ));
+            }
+
+            // Values must stay apart once squeezed into a SKU segment
+            let mut codes = HashSet::new();
+            for value in &attribute.values {
+                let code = sku_segment(value);
+                if code.is_empty() {
+                    return Err(DomainError::ValidationError(
+                        format!(
+                            "Value '{}' of attribute '{}' needs at least one letter or digit",
+                            value, attribute.name
+                        )
+                        .into(),
+                    ));
+                }
+                if !codes.insert(code) {
+                    return Err(DomainError::ValidationError(
+                        format!(
+                            "Value '{}' of attribute '{}' gives the same SKU as another value",
+                            value, attribute.name
+                        )
+                        .into(),
+                    ));
+                }
+            }
+        }
+
+        let variants = self
+            .attributes
+            .iter()
+            .try_fold(1usize, |count, a| count.checked_mul(a.values.len()))
+            .unwrap_or(usize::MAX);
+        if variants > MAX_VARIANTS_PER_PRODUCT {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Attributes would give {} variants; at most {} are allowed",
+                    variants, MAX_VARIANTS_PER_PRODUCT
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Every combination of attribute values, one value per attribute in
+    /// attribute order, with the last attribute varying fastest
+    pub fn combinations(&self) -> Vec<Vec<&str>> {
+        self.attributes
+            .iter()
+            .fold(vec![Vec::new()], |combinations, attribute| {
+                combinations
+                    .iter()
+                    .flat_map(|prefix| {
+                        attribute.values.iter().map(move |value| {
+                            let mut combination = prefix.clone();
+                            combination.push(value.as_str());
+                            combination
+                        })
+                    })
+                    .collect()
+            })
+    }
+
+    /// The combinations none of `variants` was generated from
+    pub fn missing_combinations(&self, variants: &[Item]) -> Vec<Vec<&str>> {
+        let existing: HashSet<&BTreeMap<String, String>> = variants
+            .iter()
+            .filter_map(|item| item.variant_attributes.as_ref())
+            .collect();
+        self.combinations()
+            .into_iter()
+            .filter(|values| !existing.contains(&self.variant_attributes(values)))
+            .collect()
+    }
+
+    /// e.g. `TSHIRT-M-RED` for values `["M", "Red"]`
+    pub fn variant_sku(&self, values: &[&str]) -> String {
+        std::iter::once(self.parent_sku.clone())
+            .chain(values.iter().map(|value| sku_segment(value)))
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// e.g. `T-Shirt (M / Red)` for values `["M", "Red"]`
+    pub fn variant_name(&self, values: &[&str]) -> String {
+        format!("{} ({})", self.name, values.join(" / "))
+    }
+
+    pub fn variant_attributes(&self, values: &[&str]) -> BTreeMap<String, String> {
+        self.attributes
+            .iter()
+            .zip(values)
+            .map(|(attribute, value)| (attribute.name.clone(), value.to_string()))
+            .collect()
+    }
+
+    /// A new, unsaved variant item for one combination of values
+    pub fn build_variant(
+        &self,
+        values: &[&str],
+        defaults: &GenerateVariantsRequest,
+    ) -> Result<Item, DomainError> {
+        let mut item = Item::new(
+            self.tenant_id,
+            self.variant_sku(values),
+            self.variant_name(values),
+            self.unit.clone(),
+            defaults.cost_price.unwrap_or_default(),
+        )?;
+        item.update(crate::domain::entities::item::UpdateItemRequest {
+            sku: None,
+            name: None,
+            description: self.description.clone(),
+            category: self.category.clone(),
+            unit: None,
+            barcode: None,
+            cost_price: None,
+            sale_price: defaults.sale_price,
+            reorder_point: defaults.reorder_point,
+            reorder_qty: defaults.reorder_qty,
+            quantity_precision: defaults.quantity_precision,
+            weight: defaults.weight,
+            dimensions: None,
+            metadata: None,
+        })?;
+        item.product_id = Some(self.id);
+        item.variant_attributes = Some(self.variant_attributes(values));
+        Ok(item)
+    }
+}
+
+/// Upper-case letters and digits of a value, as used in variant SKUs
+fn sku_segment(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+fn normalize(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn normalize_attributes(attributes: Vec<ProductAttribute>) -> Vec<ProductAttribute> {
+    attributes
+        .into_iter()
+        .map(|attribute| ProductAttribute {
+            name: attribute.name.trim().to_string(),
+            values: attribute
+                .values
+                .into_iter()
+                .map(|value| value.trim().to_string())
+                .collect(),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateProductRequest {
+    pub parent_sku: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub unit: String,
+    pub attributes: Vec<ProductAttribute>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateProductRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub unit: Option<String>,
+    pub attributes: Option<Vec<ProductAttribute>>,
+}
+
+/// Values every generated variant starts with; each can be changed per variant
+/// afterwards through the items API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GenerateVariantsRequest {
+    pub cost_price: Option<Decimal>,
+    pub sale_price: Option<Decimal>,
+    pub reorder_point: Option<Decimal>,
+    pub reorder_qty: Option<Decimal>,
+    pub quantity_precision: Option<u32>,
+    pub weight: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tshirt() -> Product {
+        Product::new(
+            Uuid::new_v4(),
+            CreateProductRequest {
+                parent_sku: "TSHIRT".to_string(),
+                name: "T-Shirt".to_string(),
+                description: None,
+                category: Some("Apparel".to_string()),
+                unit: "each".to_string(),
+                attributes: vec![
+                    ProductAttribute {
+                        name: "size".to_string(),
+                        values: vec!["S".to_string(), "M".to_string(), "X Large".to_string()],
+                    },
+                    ProductAttribute {
+                        name: "color".to_string(),
+                        values: vec!["Red".to_string(), "Navy-Blue".to_string()],
+                    },
+                ],
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_combinations_cover_every_value_pair() {
+        let product = tshirt();
+        let combinations = product.combinations();
+        assert_eq!(combinations.len(), 6);
+        assert_eq!(combinations[0], vec!["S", "Red"]);
+        assert_eq!(combinations[1], vec!["S", "Navy-Blue"]);
+        assert_eq!(combinations[5], vec!["X Large", "Navy-Blue"]);
+    }
+
+    #[test]
+    fn test_variant_sku_name_and_attributes() {
+        let product = tshirt();
+        let values = ["X Large", "Navy-Blue"];
+        assert_eq!(product.variant_sku(&values), "TSHIRT-XLARGE-NAVYBLUE");
+        assert_eq!(
+            product.variant_name(&values),
+            "T-Shirt (X Large / Navy-Blue)"
+        );
+
+        let variant = product
+            .build_variant(
+                &values,
+                &GenerateVariantsRequest {
+                    cost_price: Some(Decimal::from(4)),
+                    sale_price: Some(Decimal::new(125, 1)),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(variant.product_id, Some(product.id));
+        assert_eq!(variant.category.as_deref(), Some("Apparel"));
+        assert_eq!(variant.sale_price, Some(Decimal::new(125, 1)));
+        let attributes = variant.variant_attributes.unwrap();
+        assert_eq!(attributes["size"], "X Large");
+        assert_eq!(attributes["color"], "Navy-Blue");
+    }
+
+    #[test]
+    fn test_missing_combinations_skip_existing_variants() {
+        let mut product = tshirt();
+        let defaults = GenerateVariantsRequest::default();
+        let existing = vec![
+            product.build_variant(&["S", "Red"], &defaults).unwrap(),
+            product
+                .build_variant(&["M", "Navy-Blue"], &defaults)
+                .unwrap(),
+        ];
+        assert_eq!(product.missing_combinations(&existing).len(), 4);
+
+        product
+            .update(UpdateProductRequest {
+                attributes: Some(vec![
+                    ProductAttribute {
+                        name: "size".to_string(),
+                        values: vec!["S".to_string(), "M".to_string()],
+                    },
+                    ProductAttribute {
+                        name: "color".to_string(),
+                        values: vec!["Red".to_string(), "Navy-Blue".to_string()],
+                    },
+                ]),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            product.missing_combinations(&existing),
+            vec![vec!["S", "Navy-Blue"], vec!["M", "Red"]]
+        );
+    }
+
+    #[test]
+    fn test_rejects_ambiguous_or_oversized_attributes() {
+        let mut product = tshirt();
+        let duplicate_codes = UpdateProductRequest {
+            attributes: Some(vec![ProductAttribute {
+                name: "size".to_string(),
+                values: vec!["XL".to_string(), "X-L".to_string()],
+            }]),
+            ..Default::default()
+        };
+        assert!(product.update(duplicate_codes).is_err());
+
+        let values: Vec<String> = (0..30).map(|i| i.to_string()).collect();
+        let oversized = UpdateProductRequest {
+            attributes: Some(vec![
+                ProductAttribute {
+                    name: "length".to_string(),
+                    values: values.clone(),
+                },
+                ProductAttribute {
+                    name: "width".to_string(),
+                    values,
+                },
+            ]),
+            ..Default::default()
+        };
+        assert!(product.update(oversized).is_err());
+    }
+}
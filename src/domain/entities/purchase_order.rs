@@ -1,5 +1,11 @@
+use crate::domain::entities::currency::OrderCurrency;
+use crate::domain::entities::inventory::StockStatus;
+use crate::domain::entities::receiving::receivable_qty;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::money::{extend, round_unit};
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -11,6 +17,7 @@ pub enum PurchaseOrderStatus {
     PartialReceived,
     Received,
     Cancelled,
+    Closed,
 }
 
 impl std::fmt::Display for PurchaseOrderStatus {
@@ -22,6 +29,7 @@ impl std::fmt::Display for PurchaseOrderStatus {
             PurchaseOrderStatus::PartialReceived => write!(f, "PARTIAL_RECEIVED"),
             PurchaseOrderStatus::Received => write!(f, "RECEIVED"),
             PurchaseOrderStatus::Cancelled => write!(f, "CANCELLED"),
+            PurchaseOrderStatus::Closed => write!(f, "CLOSED"),
         }
     }
 }
@@ -31,50 +39,85 @@ pub struct PurchaseOrderLine {
     pub id: Uuid,
     pub po_id: Uuid,
     pub item_id: Uuid,
-    pub qty_ordered: i32,
-    pub qty_received: i32,
-    pub unit_cost: f64,
-    pub line_total: f64,
+    pub qty_ordered: Decimal,
+    pub qty_received: Decimal,
+    pub unit_cost: Decimal,
+    /// Quantity ordered at the unit cost, rounded to cents
+    pub line_total: Decimal,
+    /// When we asked for the line to arrive; the order's expected date unless
+    /// the line was given its own
+    #[serde(default)]
+    pub expected_date: Option<DateTime<Utc>>,
+    /// When the supplier has promised the line, once they confirm it
+    #[serde(default)]
+    pub promised_date: Option<DateTime<Utc>>,
+    /// When the supplier's latest promise was recorded
+    #[serde(default)]
+    pub promised_at: Option<DateTime<Utc>>,
 }
 
 impl PurchaseOrderLine {
-    pub fn new(item_id: Uuid, qty_ordered: i32, unit_cost: f64) -> Result<Self, DomainError> {
-        if qty_ordered <= 0 {
+    pub fn new(
+        item_id: Uuid,
+        qty_ordered: Decimal,
+        unit_cost: Decimal,
+    ) -> Result<Self, DomainError> {
+        if qty_ordered <= Decimal::ZERO {
             return Err(DomainError::ValidationError(
-                "Quantity ordered must be positive".to_string(),
+                "Quantity ordered must be positive".into(),
             ));
         }
 
-        if unit_cost < 0.0 {
-            return Err(DomainError::ValidationError(
-                "Unit cost cannot be negative".to_string(),
-            ));
+        if unit_cost < Decimal::ZERO {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.unit_cost_negative",
+                &[],
+            )));
         }
 
-        let line_total = qty_ordered as f64 * unit_cost;
+        let unit_cost = round_unit(unit_cost);
+        let line_total = extend(qty_ordered, unit_cost);
 
         Ok(Self {
             id: Uuid::new_v4(),
             po_id: Uuid::nil(), // Will be set when added to PO
             item_id,
             qty_ordered,
-            qty_received: 0,
+            qty_received: Decimal::ZERO,
             unit_cost,
             line_total,
+            expected_date: None,
+            promised_date: None,
+            promised_at: None,
         })
     }
 
-    pub fn receive(&mut self, qty: i32) -> Result<(), DomainError> {
-        if qty <= 0 {
+    /// Receive `qty`, allowing the line to end up to `over_receipt_percent` past
+    /// the ordered quantity
+    pub fn receive(
+        &mut self,
+        qty: Decimal,
+        over_receipt_percent: Decimal,
+    ) -> Result<(), DomainError> {
+        if qty <= Decimal::ZERO {
             return Err(DomainError::ValidationError(
-                "Receive quantity must be positive".to_string(),
+                "Receive quantity must be positive".into(),
             ));
         }
 
-        if self.qty_received + qty > self.qty_ordered {
-            return Err(DomainError::ValidationError(
-                "Cannot receive more than ordered".to_string(),
-            ));
+        let limit = receivable_qty(self.qty_ordered, over_receipt_percent);
+        if self.qty_received + qty > limit {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.over_receipt_tolerance",
+                &[
+                    ("qty", &qty.normalize()),
+                    ("line", &self.id),
+                    ("percent", &over_receipt_percent.normalize()),
+                    ("ordered", &self.qty_ordered.normalize()),
+                    ("received", &self.qty_received.normalize()),
+                    ("limit", &limit.normalize()),
+                ],
+            )));
         }
 
         self.qty_received += qty;
@@ -84,6 +127,22 @@ impl PurchaseOrderLine {
     pub fn is_fully_received(&self) -> bool {
         self.qty_received >= self.qty_ordered
     }
+
+    /// The promised date once the supplier has confirmed one, the expected
+    /// date until then
+    pub fn due_date(&self) -> Option<DateTime<Utc>> {
+        self.promised_date.or(self.expected_date)
+    }
+
+    /// Whole days the outstanding quantity is past its due date at `as_of`;
+    /// `None` when the line is received in full, has no due date or isn't due yet
+    pub fn days_late(&self, as_of: DateTime<Utc>) -> Option<i64> {
+        let due = self.due_date()?;
+        if self.is_fully_received() || due >= as_of {
+            return None;
+        }
+        Some((as_of - due).num_days())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,8 +155,11 @@ pub struct CreatePurchaseOrderRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreatePurchaseOrderLine {
     pub item_id: Uuid,
-    pub qty_ordered: i32,
-    pub unit_cost: f64,
+    pub qty_ordered: Decimal,
+    pub unit_cost: Decimal,
+    /// Defaults to the order's expected date
+    #[serde(default)]
+    pub expected_date: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,7 +169,14 @@ pub struct PurchaseOrder {
     pub supplier_id: Uuid,
     pub status: PurchaseOrderStatus,
     pub expected_date: Option<DateTime<Utc>>,
-    pub total_amount: f64,
+    /// Sum of the line totals
+    pub total_amount: Decimal,
+    /// Currency the supplier prices in; `None` means the tenant's base currency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Base currency units per unit of `currency`, fixed when the order was placed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange_rate: Option<Decimal>,
     pub lines: Vec<PurchaseOrderLine>,
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
@@ -122,24 +191,26 @@ impl PurchaseOrder {
         created_by: Uuid,
     ) -> Result<Self, DomainError> {
         if lines.is_empty() {
-            return Err(DomainError::ValidationError(
-                "Purchase order must have at least one line".to_string(),
-            ));
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.purchase_order_needs_line",
+                &[],
+            )));
         }
 
         let mut po_lines = Vec::new();
-        let mut total_amount = 0.0;
+        let mut total_amount = Decimal::ZERO;
 
         for line_req in lines {
             let mut line =
                 PurchaseOrderLine::new(line_req.item_id, line_req.qty_ordered, line_req.unit_cost)?;
             line.po_id = Uuid::nil(); // Will be set after PO creation
+            line.expected_date = line_req.expected_date.or(expected_date);
             total_amount += line.line_total;
             po_lines.push(line);
         }
 
         let now = Utc::now();
-        let po_number = format!("PO-{}", now.timestamp());
+        let po_number = format!("PO-{}", Uuid::new_v4().simple());
 
         let mut po = Self {
             id: Uuid::new_v4(),
@@ -148,6 +219,8 @@ impl PurchaseOrder {
             status: PurchaseOrderStatus::Draft,
             expected_date,
             total_amount,
+            currency: None,
+            exchange_rate: None,
             lines: po_lines,
             created_by,
             created_at: now,
@@ -162,10 +235,16 @@ impl PurchaseOrder {
         Ok(po)
     }
 
+    /// Price the order in a foreign currency, or in the base currency with `None`
+    pub fn set_currency(&mut self, currency: Option<OrderCurrency>) {
+        self.exchange_rate = currency.as_ref().map(|c| c.exchange_rate);
+        self.currency = currency.map(|c| c.currency);
+    }
+
     pub fn open(&mut self) -> Result<(), DomainError> {
         if self.status != PurchaseOrderStatus::Draft {
             return Err(DomainError::ValidationError(
-                "Only draft purchase orders can be opened".to_string(),
+                "Only draft purchase orders can be opened".into(),
             ));
         }
         self.status = PurchaseOrderStatus::Open;
@@ -173,12 +252,156 @@ impl PurchaseOrder {
         Ok(())
     }
 
+    fn ensure_draft(&self) -> Result<(), DomainError> {
+        if self.status != PurchaseOrderStatus::Draft {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Lines can only be edited on draft purchase orders (status: {})",
+                    self.status
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn add_line(&mut self, request: CreatePurchaseOrderLine) -> Result<Uuid, DomainError> {
+        self.ensure_draft()?;
+        let mut line =
+            PurchaseOrderLine::new(request.item_id, request.qty_ordered, request.unit_cost)?;
+        line.po_id = self.id;
+        line.expected_date = request.expected_date.or(self.expected_date);
+        let line_id = line.id;
+        self.lines.push(line);
+        self.recalculate_total();
+        Ok(line_id)
+    }
+
+    pub fn update_line(
+        &mut self,
+        line_id: Uuid,
+        request: UpdatePurchaseOrderLineRequest,
+    ) -> Result<(), DomainError> {
+        self.ensure_draft()?;
+        let line = self
+            .lines
+            .iter_mut()
+            .find(|l| l.id == line_id)
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_line_not_found",
+                    &[("id", &line_id)],
+                ))
+            })?;
+
+        let updated = PurchaseOrderLine::new(
+            line.item_id,
+            request.qty_ordered.unwrap_or(line.qty_ordered),
+            request.unit_cost.unwrap_or(line.unit_cost),
+        )?;
+        line.qty_ordered = updated.qty_ordered;
+        line.unit_cost = updated.unit_cost;
+        line.line_total = updated.line_total;
+        if request.expected_date.is_some() {
+            line.expected_date = request.expected_date;
+        }
+        self.recalculate_total();
+        Ok(())
+    }
+
+    /// Record the date the supplier has promised a line for, replacing any
+    /// earlier promise. Only lines still expected on an order that is under
+    /// way can be promised.
+    pub fn promise_line(
+        &mut self,
+        line_id: Uuid,
+        promised_date: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        if matches!(
+            self.status,
+            PurchaseOrderStatus::Received
+                | PurchaseOrderStatus::Cancelled
+                | PurchaseOrderStatus::Closed
+        ) {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Cannot record a promised date on a {} purchase order",
+                    self.status
+                )
+                .into(),
+            ));
+        }
+        let line = self
+            .lines
+            .iter_mut()
+            .find(|l| l.id == line_id)
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_line_not_found",
+                    &[("id", &line_id)],
+                ))
+            })?;
+        if line.is_fully_received() {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Purchase order line {} is already received in full",
+                    line_id
+                )
+                .into(),
+            ));
+        }
+
+        let now = Utc::now();
+        line.promised_date = Some(promised_date);
+        line.promised_at = Some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    pub fn remove_line(&mut self, line_id: Uuid) -> Result<(), DomainError> {
+        self.ensure_draft()?;
+        let index = self
+            .lines
+            .iter()
+            .position(|l| l.id == line_id)
+            .ok_or_else(|| {
+                DomainError::NotFound(Message::localized(
+                    "error.purchase_order_line_not_found",
+                    &[("id", &line_id)],
+                ))
+            })?;
+        if self.lines.len() == 1 {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.purchase_order_needs_line",
+                &[],
+            )));
+        }
+        self.lines.remove(index);
+        self.recalculate_total();
+        Ok(())
+    }
+
+    fn recalculate_total(&mut self) {
+        self.total_amount = self.lines.iter().map(|l| l.line_total).sum();
+        self.updated_at = Utc::now();
+    }
+
+    pub fn etag(&self) -> String {
+        crate::shared::etag::entity_etag(self.id, self.updated_at)
+    }
+
     pub fn cancel(&mut self) -> Result<(), DomainError> {
         if self.status == PurchaseOrderStatus::Received
             || self.status == PurchaseOrderStatus::Cancelled
+            || self.status == PurchaseOrderStatus::Closed
         {
             return Err(DomainError::ValidationError(
-                "Cannot cancel a received or already cancelled purchase order".to_string(),
+                "Cannot cancel a received, closed or already cancelled purchase order".into(),
+            ));
+        }
+        if self.total_received_qty() > Decimal::ZERO {
+            return Err(DomainError::ValidationError(
+                "Cannot cancel a purchase order with received goods; close it instead".into(),
             ));
         }
         self.status = PurchaseOrderStatus::Cancelled;
@@ -186,12 +409,51 @@ impl PurchaseOrder {
         Ok(())
     }
 
-    pub fn receive_lines(&mut self, received_lines: Vec<ReceiveLine>) -> Result<(), DomainError> {
+    /// Stop expecting the outstanding quantity on a partially received order
+    pub fn close(&mut self) -> Result<(), DomainError> {
+        if self.status == PurchaseOrderStatus::Received
+            || self.status == PurchaseOrderStatus::Cancelled
+            || self.status == PurchaseOrderStatus::Closed
+        {
+            return Err(DomainError::ValidationError(
+                "Cannot close a received, cancelled or already closed purchase order".into(),
+            ));
+        }
+        if self.total_received_qty().is_zero() {
+            return Err(DomainError::ValidationError(
+                "Cannot close a purchase order with nothing received; cancel it instead"
+                    .to_string()
+                    .into(),
+            ));
+        }
+        self.status = PurchaseOrderStatus::Closed;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Quantity still expected from the supplier across all lines
+    pub fn outstanding_qty(&self) -> Decimal {
+        self.lines
+            .iter()
+            .map(|l| (l.qty_ordered - l.qty_received).max(Decimal::ZERO))
+            .sum()
+    }
+
+    /// Receive against the lines, each held to `over_receipt_percent` past its
+    /// ordered quantity
+    pub fn receive_lines(
+        &mut self,
+        received_lines: Vec<ReceiveLine>,
+        over_receipt_percent: Decimal,
+    ) -> Result<(), DomainError> {
         if self.status == PurchaseOrderStatus::Cancelled
             || self.status == PurchaseOrderStatus::Received
+            || self.status == PurchaseOrderStatus::Closed
         {
             return Err(DomainError::ValidationError(
-                "Cannot receive lines on cancelled or fully received purchase order".to_string(),
+                "Cannot receive lines on cancelled, closed or fully received purchase order"
+                    .to_string()
+                    .into(),
             ));
         }
 
@@ -203,18 +465,18 @@ impl PurchaseOrder {
                 .iter_mut()
                 .find(|l| l.id == receive_req.po_line_id)
                 .ok_or_else(|| {
-                    DomainError::ValidationError(format!(
-                        "Purchase order line {} not found",
-                        receive_req.po_line_id
+                    DomainError::ValidationError(Message::localized(
+                        "error.purchase_order_line_not_found",
+                        &[("id", &receive_req.po_line_id)],
                     ))
                 })?;
 
-            line.receive(receive_req.qty_received)?;
+            line.receive(receive_req.qty_received, over_receipt_percent)?;
         }
 
         // Update status based on receipt
         let all_received = self.lines.iter().all(|l| l.is_fully_received());
-        let any_received = self.lines.iter().any(|l| l.qty_received > 0);
+        let any_received = self.lines.iter().any(|l| l.qty_received > Decimal::ZERO);
 
         if all_received {
             self.status = PurchaseOrderStatus::Received;
@@ -232,19 +494,56 @@ impl PurchaseOrder {
         self.lines.iter().all(|l| l.is_fully_received())
     }
 
-    pub fn total_received_qty(&self) -> i32 {
+    pub fn total_received_qty(&self) -> Decimal {
         self.lines.iter().map(|l| l.qty_received).sum()
     }
 
-    pub fn total_ordered_qty(&self) -> i32 {
+    pub fn total_ordered_qty(&self) -> Decimal {
         self.lines.iter().map(|l| l.qty_ordered).sum()
     }
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdatePurchaseOrderLineRequest {
+    pub qty_ordered: Option<Decimal>,
+    pub unit_cost: Option<Decimal>,
+    #[serde(default)]
+    pub expected_date: Option<DateTime<Utc>>,
+}
+
+/// A supplier's confirmation of when a line will arrive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromisePurchaseOrderLineRequest {
+    pub promised_date: DateTime<Utc>,
+}
+
+/// An outstanding purchase order line past its due date, for the overdue
+/// purchase orders report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverduePurchaseOrderLine {
+    pub po_id: Uuid,
+    pub po_number: String,
+    pub supplier_id: Uuid,
+    pub line_id: Uuid,
+    pub item_id: Uuid,
+    pub sku: String,
+    pub name: String,
+    pub qty_ordered: Decimal,
+    pub qty_received: Decimal,
+    /// Still to arrive
+    pub qty_outstanding: Decimal,
+    pub expected_date: Option<DateTime<Utc>>,
+    pub promised_date: Option<DateTime<Utc>>,
+    /// The promised date, or the expected date if nothing was promised
+    pub due_date: DateTime<Utc>,
+    /// Whole days past the due date
+    pub days_late: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReceiveLine {
     pub po_line_id: Uuid,
-    pub qty_received: i32,
+    pub qty_received: Decimal,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -252,4 +551,287 @@ pub struct ReceivePurchaseOrderRequest {
     pub received_lines: Vec<ReceiveLine>,
     pub receive_date: Option<DateTime<Utc>>,
     pub destination_location_id: Uuid,
+    /// Receive into QUARANTINE to hold the goods for inspection
+    #[serde(default)]
+    pub stock_status: StockStatus,
+}
+
+/// One receiving session against a purchase order: who received what, into
+/// which location and when
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurchaseOrderReceipt {
+    pub id: Uuid,
+    pub po_id: Uuid,
+    pub location_id: Uuid,
+    pub stock_status: StockStatus,
+    pub received_by: Uuid,
+    /// Email of the receiving user, if they still exist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub received_by_email: Option<String>,
+    pub received_at: DateTime<Utc>,
+    pub lines: Vec<PurchaseOrderReceiptLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurchaseOrderReceiptLine {
+    pub id: Uuid,
+    pub po_line_id: Uuid,
+    pub item_id: Uuid,
+    pub qty_received: Decimal,
+}
+
+impl PurchaseOrderReceipt {
+    /// Record `request` against `po`, leaving out lines that received nothing
+    pub fn new(
+        po: &PurchaseOrder,
+        request: &ReceivePurchaseOrderRequest,
+        received_by: Uuid,
+    ) -> Result<Self, DomainError> {
+        let lines = request
+            .received_lines
+            .iter()
+            .filter(|received| received.qty_received > Decimal::ZERO)
+            .map(|received| {
+                let line = po
+                    .lines
+                    .iter()
+                    .find(|l| l.id == received.po_line_id)
+                    .ok_or_else(|| {
+                        DomainError::ValidationError(Message::localized(
+                            "error.purchase_order_line_not_found",
+                            &[("id", &received.po_line_id)],
+                        ))
+                    })?;
+                Ok(PurchaseOrderReceiptLine {
+                    id: Uuid::new_v4(),
+                    po_line_id: line.id,
+                    item_id: line.item_id,
+                    qty_received: received.qty_received,
+                })
+            })
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            po_id: po.id,
+            location_id: request.destination_location_id,
+            stock_status: request.stock_status,
+            received_by,
+            received_by_email: None,
+            received_at: request.receive_date.unwrap_or_else(Utc::now),
+            lines,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_po() -> PurchaseOrder {
+        let mut po = PurchaseOrder::new(
+            Uuid::new_v4(),
+            vec![CreatePurchaseOrderLine {
+                item_id: Uuid::new_v4(),
+                qty_ordered: Decimal::from(10),
+                unit_cost: Decimal::new(25, 1),
+                expected_date: None,
+            }],
+            None,
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        po.open().unwrap();
+        po
+    }
+
+    #[test]
+    fn test_edit_lines_on_draft_recalculates_total() {
+        let mut po = PurchaseOrder::new(
+            Uuid::new_v4(),
+            vec![CreatePurchaseOrderLine {
+                item_id: Uuid::new_v4(),
+                qty_ordered: Decimal::from(10),
+                unit_cost: Decimal::new(25, 1),
+                expected_date: None,
+            }],
+            None,
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        let first_line = po.lines[0].id;
+
+        let added = po
+            .add_line(CreatePurchaseOrderLine {
+                item_id: Uuid::new_v4(),
+                qty_ordered: Decimal::from(4),
+                unit_cost: Decimal::ONE,
+                expected_date: None,
+            })
+            .unwrap();
+        assert_eq!(po.total_amount, Decimal::from(29));
+
+        po.update_line(
+            first_line,
+            UpdatePurchaseOrderLineRequest {
+                qty_ordered: Some(Decimal::from(2)),
+                unit_cost: None,
+                expected_date: None,
+            },
+        )
+        .unwrap();
+        po.remove_line(added).unwrap();
+        assert_eq!(po.total_amount, Decimal::from(5));
+        assert!(po.remove_line(first_line).is_err());
+
+        po.open().unwrap();
+        assert!(po.remove_line(first_line).is_err());
+    }
+
+    #[test]
+    fn test_cancel_requires_nothing_received() {
+        let mut po = open_po();
+        let line_id = po.lines[0].id;
+        po.receive_lines(
+            vec![ReceiveLine {
+                po_line_id: line_id,
+                qty_received: Decimal::from(4),
+            }],
+            Decimal::ZERO,
+        )
+        .unwrap();
+
+        assert!(po.cancel().is_err());
+        assert!(open_po().cancel().is_ok());
+    }
+
+    #[test]
+    fn test_close_stops_further_receipts() {
+        let mut po = open_po();
+        assert!(po.close().is_err());
+
+        let line_id = po.lines[0].id;
+        po.receive_lines(
+            vec![ReceiveLine {
+                po_line_id: line_id,
+                qty_received: Decimal::from(4),
+            }],
+            Decimal::ZERO,
+        )
+        .unwrap();
+        assert_eq!(po.outstanding_qty(), Decimal::from(6));
+
+        po.close().unwrap();
+        assert_eq!(po.status, PurchaseOrderStatus::Closed);
+        assert!(po
+            .receive_lines(
+                vec![ReceiveLine {
+                    po_line_id: line_id,
+                    qty_received: Decimal::ONE,
+                }],
+                Decimal::ZERO
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_over_receipt_tolerance() {
+        let mut po = open_po();
+        let line_id = po.lines[0].id;
+        let receive = |qty: Decimal| {
+            vec![ReceiveLine {
+                po_line_id: line_id,
+                qty_received: qty,
+            }]
+        };
+
+        let err = po
+            .receive_lines(receive(Decimal::new(106, 1)), Decimal::from(5))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::ValidationError(msg) if msg.key() == Some("error.over_receipt_tolerance")
+        ));
+        assert!(po
+            .receive_lines(receive(Decimal::new(101, 1)), Decimal::ZERO)
+            .is_err());
+
+        po.receive_lines(receive(Decimal::new(105, 1)), Decimal::from(5))
+            .unwrap();
+        assert_eq!(po.lines[0].qty_received, Decimal::new(105, 1));
+        assert_eq!(po.status, PurchaseOrderStatus::Received);
+        assert_eq!(po.outstanding_qty(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_receipt_keeps_only_received_lines() {
+        let po = open_po();
+        let request = ReceivePurchaseOrderRequest {
+            received_lines: vec![ReceiveLine {
+                po_line_id: po.lines[0].id,
+                qty_received: Decimal::new(45, 1),
+            }],
+            receive_date: None,
+            destination_location_id: Uuid::new_v4(),
+            stock_status: StockStatus::Quarantine,
+        };
+        let receipt = PurchaseOrderReceipt::new(&po, &request, Uuid::new_v4()).unwrap();
+        assert_eq!(receipt.po_id, po.id);
+        assert_eq!(receipt.lines.len(), 1);
+        assert_eq!(receipt.lines[0].item_id, po.lines[0].item_id);
+        assert_eq!(receipt.lines[0].qty_received, Decimal::new(45, 1));
+
+        let nothing = ReceivePurchaseOrderRequest {
+            received_lines: vec![ReceiveLine {
+                po_line_id: po.lines[0].id,
+                qty_received: Decimal::ZERO,
+            }],
+            ..request.clone()
+        };
+        assert!(PurchaseOrderReceipt::new(&po, &nothing, Uuid::new_v4())
+            .unwrap()
+            .lines
+            .is_empty());
+
+        let unknown = ReceivePurchaseOrderRequest {
+            received_lines: vec![ReceiveLine {
+                po_line_id: Uuid::new_v4(),
+                qty_received: Decimal::ONE,
+            }],
+            ..request
+        };
+        assert!(PurchaseOrderReceipt::new(&po, &unknown, Uuid::new_v4()).is_err());
+    }
+
+    #[test]
+    fn test_promise_line_moves_due_date_and_days_late() {
+        let mut po = open_po();
+        let line_id = po.lines[0].id;
+        let now = Utc::now();
+        assert_eq!(po.lines[0].days_late(now), None);
+
+        po.lines[0].expected_date = Some(now - chrono::Duration::days(5));
+        assert_eq!(po.lines[0].days_late(now), Some(5));
+
+        po.promise_line(line_id, now + chrono::Duration::days(2))
+            .unwrap();
+        assert_eq!(
+            po.lines[0].due_date(),
+            Some(now + chrono::Duration::days(2))
+        );
+        assert!(po.lines[0].promised_at.is_some());
+        assert_eq!(po.lines[0].days_late(now), None);
+        assert_eq!(
+            po.lines[0].days_late(now + chrono::Duration::days(3)),
+            Some(1)
+        );
+
+        po.lines[0].qty_received = po.lines[0].qty_ordered;
+        assert_eq!(po.lines[0].days_late(now + chrono::Duration::days(3)), None);
+        assert!(po.promise_line(line_id, now).is_err());
+        assert!(matches!(
+            po.promise_line(Uuid::new_v4(), now),
+            Err(DomainError::NotFound(_))
+        ));
+    }
 }
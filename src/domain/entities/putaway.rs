@@ -0,0 +1,344 @@
+use crate::shared::error::DomainError;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Directs received goods to a storage location. A rule matches a specific item,
+/// every item in a category, or (with neither set) anything received.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PutawayRule {
+    pub id: Uuid,
+    pub name: String,
+    pub item_id: Option<Uuid>,
+    pub category: Option<String>,
+    pub location_id: Uuid,
+    pub priority: i32,
+    /// Maximum units the location should hold; `None` means unbounded
+    pub max_quantity: Option<Decimal>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PutawayRule {
+    pub fn new(request: CreatePutawayRuleRequest) -> Result<Self, DomainError> {
+        let now = Utc::now();
+        let rule = Self {
+            id: Uuid::new_v4(),
+            name: request.name.trim().to_string(),
+            item_id: request.item_id,
+            category: normalize(request.category),
+            location_id: request.location_id,
+            priority: request.priority.unwrap_or(100),
+            max_quantity: request.max_quantity,
+            active: request.active.unwrap_or(true),
+            created_at: now,
+            updated_at: now,
+        };
+        rule.validate()?;
+        Ok(rule)
+    }
+
+    pub fn update(&mut self, request: UpdatePutawayRuleRequest) -> Result<(), DomainError> {
+        if let Some(name) = request.name {
+            self.name = name.trim().to_string();
+        }
+        if let Some(item_id) = request.item_id {
+            self.item_id = Some(item_id);
+        }
+        if let Some(category) = request.category {
+            self.category = normalize(Some(category));
+        }
+        if let Some(location_id) = request.location_id {
+            self.location_id = location_id;
+        }
+        if let Some(priority) = request.priority {
+            self.priority = priority;
+        }
+        if let Some(max_quantity) = request.max_quantity {
+            self.max_quantity = Some(max_quantity);
+        }
+        if let Some(active) = request.active {
+            self.active = active;
+        }
+        self.validate()?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), DomainError> {
+        if self.name.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Putaway rule name cannot be empty".into(),
+            ));
+        }
+        if self.max_quantity.is_some_and(|max| max <= Decimal::ZERO) {
+            return Err(DomainError::ValidationError(
+                "Putaway rule max_quantity must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Item rules beat category rules, which beat catch-all rules
+    fn specificity(&self) -> u8 {
+        match (&self.item_id, &self.category) {
+            (Some(_), _) => 2,
+            (None, Some(_)) => 1,
+            (None, None) => 0,
+        }
+    }
+}
+
+fn normalize(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePutawayRuleRequest {
+    pub name: String,
+    pub item_id: Option<Uuid>,
+    pub category: Option<String>,
+    pub location_id: Uuid,
+    pub priority: Option<i32>,
+    pub max_quantity: Option<Decimal>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdatePutawayRuleRequest {
+    pub name: Option<String>,
+    pub item_id: Option<Uuid>,
+    pub category: Option<String>,
+    pub location_id: Option<Uuid>,
+    pub priority: Option<i32>,
+    pub max_quantity: Option<Decimal>,
+    pub active: Option<bool>,
+}
+
+/// A rule that applies to a received item, with the stock currently held at its location
+#[derive(Debug, Clone)]
+pub struct PutawayCandidate {
+    pub rule: PutawayRule,
+    pub item_on_hand: Decimal,
+    pub location_on_hand: Decimal,
+    /// Units of the item the location's volume, weight and pallet capacity
+    /// still has room for; `None` when none of its limits apply to the item
+    pub capacity_room: Option<Decimal>,
+}
+
+impl PutawayCandidate {
+    fn remaining_capacity(&self) -> Option<Decimal> {
+        let rule_room = self
+            .rule
+            .max_quantity
+            .map(|max| (max - self.location_on_hand).max(Decimal::ZERO));
+        match (rule_room, self.capacity_room) {
+            (Some(rule_room), Some(capacity_room)) => Some(rule_room.min(capacity_room)),
+            (rule_room, capacity_room) => rule_room.or(capacity_room),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PutawaySuggestion {
+    pub item_id: Uuid,
+    pub location_id: Uuid,
+    pub quantity: Decimal,
+    pub rule_id: Option<Uuid>,
+    pub reason: String,
+}
+
+/// Spread a received quantity over the matching rules' locations.
+///
+/// Candidates are ranked by rule priority (lower first), then rule specificity, then
+/// how much of the item each location already holds so like stock stays together.
+/// Whatever no location has room for stays at the receiving location.
+pub fn suggest_putaway(
+    item_id: Uuid,
+    quantity: Decimal,
+    receiving_location_id: Uuid,
+    mut candidates: Vec<PutawayCandidate>,
+) -> Vec<PutawaySuggestion> {
+    candidates.retain(|c| c.rule.active && c.rule.location_id != receiving_location_id);
+    candidates.sort_by(|a, b| {
+        a.rule
+            .priority
+            .cmp(&b.rule.priority)
+            .then_with(|| b.rule.specificity().cmp(&a.rule.specificity()))
+            .then_with(|| b.item_on_hand.cmp(&a.item_on_hand))
+    });
+
+    let mut suggestions: Vec<PutawaySuggestion> = Vec::new();
+    let mut remaining = quantity;
+
+    for candidate in &candidates {
+        if remaining <= Decimal::ZERO {
+            break;
+        }
+        // Several rules may point at the same location; count what we've already sent there
+        let already_planned: Decimal = suggestions
+            .iter()
+            .filter(|s| s.location_id == candidate.rule.location_id)
+            .map(|s| s.quantity)
+            .sum();
+        let room = match candidate.remaining_capacity() {
+            Some(capacity) => (capacity - already_planned).max(Decimal::ZERO),
+            None => remaining,
+        };
+        let qty = room.min(remaining);
+        if qty.is_zero() {
+            continue;
+        }
+
+        let reason = match (
+            candidate.rule.specificity(),
+            candidate.item_on_hand > Decimal::ZERO,
+        ) {
+            (2, _) => "ITEM_RULE",
+            (_, true) => "EXISTING_STOCK",
+            (1, false) => "CATEGORY_RULE",
+            _ => "DEFAULT_RULE",
+        };
+        suggestions.push(PutawaySuggestion {
+            item_id,
+            location_id: candidate.rule.location_id,
+            quantity: qty,
+            rule_id: Some(candidate.rule.id),
+            reason: reason.to_string(),
+        });
+        remaining -= qty;
+    }
+
+    if remaining > Decimal::ZERO {
+        suggestions.push(PutawaySuggestion {
+            item_id,
+            location_id: receiving_location_id,
+            quantity: remaining,
+            rule_id: None,
+            reason: if candidates.is_empty() {
+                "NO_MATCHING_RULE".to_string()
+            } else {
+                "NO_CAPACITY".to_string()
+            },
+        });
+    }
+
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(
+        location_id: Uuid,
+        priority: i32,
+        item_id: Option<Uuid>,
+        max_quantity: Option<Decimal>,
+        item_on_hand: i64,
+        location_on_hand: i64,
+    ) -> PutawayCandidate {
+        let rule = PutawayRule::new(CreatePutawayRuleRequest {
+            name: "rule".to_string(),
+            item_id,
+            category: Some("Hardware".to_string()),
+            location_id,
+            priority: Some(priority),
+            max_quantity,
+            active: None,
+        })
+        .unwrap();
+        PutawayCandidate {
+            rule,
+            item_on_hand: Decimal::from(item_on_hand),
+            location_on_hand: Decimal::from(location_on_hand),
+            capacity_room: None,
+        }
+    }
+
+    #[test]
+    fn test_fills_by_priority_and_capacity() {
+        let item = Uuid::new_v4();
+        let dock = Uuid::new_v4();
+        let (shelf_a, shelf_b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let suggestions = suggest_putaway(
+            item,
+            Decimal::from(30),
+            dock,
+            vec![
+                candidate(shelf_b, 20, None, None, 0, 0),
+                candidate(shelf_a, 10, None, Some(Decimal::from(50)), 0, 40),
+            ],
+        );
+
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].location_id, shelf_a);
+        assert_eq!(suggestions[0].quantity, Decimal::from(10));
+        assert_eq!(suggestions[1].location_id, shelf_b);
+        assert_eq!(suggestions[1].quantity, Decimal::from(20));
+    }
+
+    #[test]
+    fn test_location_capacity_caps_rule() {
+        let item = Uuid::new_v4();
+        let dock = Uuid::new_v4();
+        let (shelf_a, shelf_b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let mut tight = candidate(shelf_a, 10, None, Some(Decimal::from(50)), 0, 0);
+        tight.capacity_room = Some(Decimal::from(12));
+        let mut roomy = candidate(shelf_b, 20, None, None, 0, 0);
+        roomy.capacity_room = Some(Decimal::from(100));
+
+        let suggestions = suggest_putaway(item, Decimal::from(30), dock, vec![tight, roomy]);
+
+        assert_eq!(suggestions[0].location_id, shelf_a);
+        assert_eq!(suggestions[0].quantity, Decimal::from(12));
+        assert_eq!(suggestions[1].location_id, shelf_b);
+        assert_eq!(suggestions[1].quantity, Decimal::from(18));
+    }
+
+    #[test]
+    fn test_prefers_item_rule_then_existing_stock() {
+        let item = Uuid::new_v4();
+        let dock = Uuid::new_v4();
+        let (bin, shelf, other) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let suggestions = suggest_putaway(
+            item,
+            Decimal::from(5),
+            dock,
+            vec![
+                candidate(other, 10, None, None, 0, 0),
+                candidate(shelf, 10, None, None, 12, 12),
+                candidate(bin, 10, Some(item), Some(Decimal::from(3)), 0, 0),
+            ],
+        );
+
+        assert_eq!(suggestions[0].location_id, bin);
+        assert_eq!(suggestions[0].reason, "ITEM_RULE");
+        assert_eq!(suggestions[1].location_id, shelf);
+        assert_eq!(suggestions[1].quantity, Decimal::from(2));
+        assert_eq!(suggestions[1].reason, "EXISTING_STOCK");
+    }
+
+    #[test]
+    fn test_overflow_stays_at_receiving_location() {
+        let item = Uuid::new_v4();
+        let dock = Uuid::new_v4();
+
+        let suggestions = suggest_putaway(item, Decimal::from(8), dock, Vec::new());
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].location_id, dock);
+        assert_eq!(suggestions[0].reason, "NO_MATCHING_RULE");
+
+        let full = candidate(Uuid::new_v4(), 1, None, Some(Decimal::from(10)), 0, 10);
+        let suggestions = suggest_putaway(item, Decimal::from(8), dock, vec![full]);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].reason, "NO_CAPACITY");
+    }
+}
@@ -0,0 +1,100 @@
+use crate::shared::error::DomainError;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Largest over-receipt tolerance, as a percentage of the ordered quantity
+pub const MAX_OVER_RECEIPT_PERCENT: i64 = 100;
+
+/// Reject a tolerance outside 0–100%
+pub fn check_over_receipt_percent(percent: Decimal) -> Result<(), DomainError> {
+    if percent < Decimal::ZERO || percent > Decimal::from(MAX_OVER_RECEIPT_PERCENT) {
+        return Err(DomainError::ValidationError(
+            format!(
+                "Over-receipt tolerance must be between 0 and {}%",
+                MAX_OVER_RECEIPT_PERCENT
+            )
+            .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Most of `qty_ordered` a line may receive in total when `percent` over is
+/// tolerated
+pub fn receivable_qty(qty_ordered: Decimal, percent: Decimal) -> Decimal {
+    qty_ordered + qty_ordered * percent / Decimal::ONE_HUNDRED
+}
+
+/// How far past the ordered quantity the tenant accepts deliveries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceivingSettings {
+    /// Applies to suppliers without a tolerance of their own
+    pub over_receipt_percent: Decimal,
+    pub suppliers: Vec<SupplierTolerance>,
+}
+
+impl Default for ReceivingSettings {
+    fn default() -> Self {
+        Self {
+            over_receipt_percent: Decimal::ZERO,
+            suppliers: Vec::new(),
+        }
+    }
+}
+
+impl ReceivingSettings {
+    /// The tolerance receipts from `supplier_id` are held to
+    pub fn tolerance_for(&self, supplier_id: Uuid) -> Decimal {
+        self.suppliers
+            .iter()
+            .find(|s| s.supplier_id == supplier_id)
+            .map_or(self.over_receipt_percent, |s| s.over_receipt_percent)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SupplierTolerance {
+    pub supplier_id: Uuid,
+    pub over_receipt_percent: Decimal,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateReceivingSettingsRequest {
+    pub over_receipt_percent: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetSupplierToleranceRequest {
+    pub over_receipt_percent: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supplier_tolerance_overrides_tenant_default() {
+        let supplier_id = Uuid::new_v4();
+        let settings = ReceivingSettings {
+            over_receipt_percent: Decimal::from(5),
+            suppliers: vec![SupplierTolerance {
+                supplier_id,
+                over_receipt_percent: Decimal::from(10),
+                updated_at: Utc::now(),
+            }],
+        };
+
+        assert_eq!(settings.tolerance_for(supplier_id), Decimal::from(10));
+        assert_eq!(settings.tolerance_for(Uuid::new_v4()), Decimal::from(5));
+        assert_eq!(
+            receivable_qty(Decimal::from(10), Decimal::from(5)),
+            Decimal::new(105, 1)
+        );
+        assert!(check_over_receipt_percent(Decimal::new(25, 1)).is_ok());
+        assert!(check_over_receipt_percent(Decimal::from(101)).is_err());
+        assert!(check_over_receipt_percent(Decimal::NEGATIVE_ONE).is_err());
+    }
+}
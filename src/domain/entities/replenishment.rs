@@ -0,0 +1,223 @@
+use crate::domain::entities::inventory::{StockLevel, StockStatus};
+use crate::domain::entities::item::Item;
+use crate::shared::money::extend;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// One item whose available stock (on hand, less quarantined, damaged and
+/// reserved stock, across all locations)
+/// has dropped to or below its reorder point
+#[derive(Debug, Clone, Serialize)]
+pub struct ReorderSuggestion {
+    pub item_id: Uuid,
+    pub sku: String,
+    pub name: String,
+    pub reorder_point: Decimal,
+    pub reorder_point_source: ReorderPointSource,
+    pub reorder_qty: Option<Decimal>,
+    pub quantity_on_hand: Decimal,
+    pub quantity_reserved: Decimal,
+    pub quantity_available: Decimal,
+    pub suggested_qty: Decimal,
+    pub unit_cost: Decimal,
+    pub line_total: Decimal,
+}
+
+impl ReorderSuggestion {
+    /// `None` when the item has no reorder point, is inactive, or still has
+    /// more available than its reorder point.
+    ///
+    /// The suggested quantity is the item's `reorder_qty`, raised if needed so the
+    /// order at least brings availability back up to the reorder point.
+    pub fn for_item(item: &Item, stock_levels: &[StockLevel]) -> Option<Self> {
+        let reorder_point = item.reorder_point?;
+        Self::with_reorder_point(
+            item,
+            reorder_point,
+            ReorderPointSource::Static,
+            stock_levels,
+        )
+    }
+
+    /// As `for_item`, against a reorder point worked out elsewhere, e.g. from
+    /// a demand forecast
+    pub fn with_reorder_point(
+        item: &Item,
+        reorder_point: Decimal,
+        reorder_point_source: ReorderPointSource,
+        stock_levels: &[StockLevel],
+    ) -> Option<Self> {
+        if !item.active {
+            return None;
+        }
+        let quantity_on_hand: Decimal = stock_levels.iter().map(|s| s.quantity_on_hand).sum();
+        let quantity_reserved: Decimal = stock_levels.iter().map(|s| s.quantity_reserved).sum();
+        let quantity_available = stock_levels
+            .iter()
+            .map(|s| s.quantity_in(StockStatus::Available))
+            .sum::<Decimal>()
+            - quantity_reserved;
+        if quantity_available > reorder_point {
+            return None;
+        }
+
+        let shortfall = reorder_point - quantity_available;
+        let suggested_qty = item
+            .reorder_qty
+            .unwrap_or(Decimal::ZERO)
+            .max(shortfall)
+            .max(Decimal::ONE);
+
+        Some(Self {
+            item_id: item.id,
+            sku: item.sku.clone(),
+            name: item.name.clone(),
+            reorder_point,
+            reorder_point_source,
+            reorder_qty: item.reorder_qty,
+            quantity_on_hand,
+            quantity_reserved,
+            quantity_available,
+            suggested_qty,
+            unit_cost: item.cost_price,
+            line_total: extend(suggested_qty, item.cost_price),
+        })
+    }
+}
+
+/// Where a suggestion's reorder point came from
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReorderPointSource {
+    /// The item's own `reorder_point`
+    Static,
+    /// Forecast demand over the supplier lead time
+    Forecast,
+}
+
+/// Suggestions for one supplier, i.e. one purchase order worth of lines.
+/// Items are matched to suppliers through `metadata.supplier_id`; those without
+/// one are grouped under `supplier_id: null` and cannot be drafted into a PO.
+#[derive(Debug, Clone, Serialize)]
+pub struct SupplierReorderGroup {
+    pub supplier_id: Option<Uuid>,
+    pub supplier_name: Option<String>,
+    pub lines: Vec<ReorderSuggestion>,
+    pub total_cost: Decimal,
+}
+
+impl SupplierReorderGroup {
+    /// Group suggestions by supplier, suppliers in first-seen order with the
+    /// unassigned group last
+    pub fn group(suggestions: Vec<(Option<Uuid>, Option<String>, ReorderSuggestion)>) -> Vec<Self> {
+        let mut groups: Vec<Self> = Vec::new();
+        for (supplier_id, supplier_name, suggestion) in suggestions {
+            let group = match groups.iter_mut().position(|g| g.supplier_id == supplier_id) {
+                Some(index) => &mut groups[index],
+                None => {
+                    groups.push(Self {
+                        supplier_id,
+                        supplier_name: None,
+                        lines: Vec::new(),
+                        total_cost: Decimal::ZERO,
+                    });
+                    groups.last_mut().expect("group was just pushed")
+                }
+            };
+            if group.supplier_name.is_none() {
+                group.supplier_name = supplier_name;
+            }
+            group.total_cost += suggestion.line_total;
+            group.lines.push(suggestion);
+        }
+        groups.sort_by_key(|g| g.supplier_id.is_none());
+        groups
+    }
+}
+
+/// Supplier an item is bought from, read from its `metadata`
+/// (`{"supplier_id": "<uuid>", "supplier": "<name>"}`)
+pub fn item_supplier(item: &Item) -> (Option<Uuid>, Option<String>) {
+    let Some(metadata) = item.metadata.as_ref() else {
+        return (None, None);
+    };
+    let supplier_id = metadata
+        .get("supplier_id")
+        .and_then(|v| v.as_str())
+        .and_then(|v| Uuid::parse_str(v).ok());
+    let supplier_name = metadata
+        .get("supplier")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    (supplier_id, supplier_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn item(reorder_point: Option<Decimal>, reorder_qty: Option<Decimal>) -> Item {
+        let mut item = Item::new(
+            Uuid::new_v4(),
+            "SKU-1".to_string(),
+            "Widget".to_string(),
+            "each".to_string(),
+            Decimal::new(25, 1),
+        )
+        .unwrap();
+        item.reorder_point = reorder_point;
+        item.reorder_qty = reorder_qty;
+        item
+    }
+
+    fn level(on_hand: i64, reserved: i64) -> StockLevel {
+        let mut level = StockLevel::new(Uuid::new_v4(), Uuid::new_v4());
+        level.quantity_on_hand = Decimal::from(on_hand);
+        level.quantity_reserved = Decimal::from(reserved);
+        level
+    }
+
+    #[test]
+    fn test_suggestion_uses_available_across_locations() {
+        let widget = item(Some(Decimal::from(10)), Some(Decimal::from(25)));
+        assert!(ReorderSuggestion::for_item(&widget, &[level(8, 0), level(6, 0)]).is_none());
+
+        let suggestion = ReorderSuggestion::for_item(&widget, &[level(8, 3), level(6, 2)]).unwrap();
+        assert_eq!(suggestion.quantity_available, Decimal::from(9));
+        assert_eq!(suggestion.reorder_point_source, ReorderPointSource::Static);
+        assert_eq!(suggestion.suggested_qty, Decimal::from(25));
+        assert_eq!(suggestion.line_total, Decimal::new(625, 1));
+
+        // reorder_qty alone would leave it below the reorder point
+        let deficit = ReorderSuggestion::for_item(&widget, &[level(0, 20)]).unwrap();
+        assert_eq!(deficit.suggested_qty, Decimal::from(30));
+
+        assert!(ReorderSuggestion::for_item(&item(None, Some(Decimal::from(5))), &[]).is_none());
+    }
+
+    #[test]
+    fn test_groups_by_metadata_supplier() {
+        let supplier = Uuid::new_v4();
+        let mut supplied = item(Some(Decimal::from(5)), Some(Decimal::from(10)));
+        supplied.metadata = Some(json!({"supplier_id": supplier.to_string(), "supplier": "Acme"}));
+        let unsupplied = item(Some(Decimal::from(5)), None);
+
+        let suggestions = [&unsupplied, &supplied, &supplied]
+            .into_iter()
+            .map(|i| {
+                let (id, name) = item_supplier(i);
+                (id, name, ReorderSuggestion::for_item(i, &[]).unwrap())
+            })
+            .collect();
+        let groups = SupplierReorderGroup::group(suggestions);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].supplier_id, Some(supplier));
+        assert_eq!(groups[0].supplier_name.as_deref(), Some("Acme"));
+        assert_eq!(groups[0].lines.len(), 2);
+        assert_eq!(groups[0].total_cost, Decimal::from(50));
+        assert_eq!(groups[1].supplier_id, None);
+    }
+}
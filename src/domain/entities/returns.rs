@@ -1,6 +1,11 @@
+use crate::domain::entities::inventory::{MovementType, ReferenceType, StockMovement, StockStatus};
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::money::round_unit;
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -27,10 +32,9 @@ impl ReturnStatus {
             "OPEN" => Ok(ReturnStatus::Open),
             "RECEIVED" => Ok(ReturnStatus::Received),
             "CANCELLED" => Ok(ReturnStatus::Cancelled),
-            _ => Err(DomainError::ValidationError(format!(
-                "Invalid return status: {}",
-                s
-            ))),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid return status: {}", s).into(),
+            )),
         }
     }
 
@@ -48,6 +52,49 @@ impl ReturnStatus {
     }
 }
 
+/// What happens to the stock of a processed return line
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReturnDisposition {
+    /// Back into sellable stock
+    Restock,
+    /// Received and written off
+    Scrap,
+    /// Held in quarantine until refurbished and released
+    #[default]
+    Refurbish,
+}
+
+impl ReturnDisposition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReturnDisposition::Restock => "RESTOCK",
+            ReturnDisposition::Scrap => "SCRAP",
+            ReturnDisposition::Refurbish => "REFURBISH",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s {
+            "RESTOCK" => Ok(ReturnDisposition::Restock),
+            "SCRAP" => Ok(ReturnDisposition::Scrap),
+            "REFURBISH" => Ok(ReturnDisposition::Refurbish),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid return disposition: {}", s).into(),
+            )),
+        }
+    }
+
+    /// The status the received units land in
+    pub fn stock_status(&self) -> StockStatus {
+        match self {
+            ReturnDisposition::Restock => StockStatus::Available,
+            ReturnDisposition::Scrap => StockStatus::Damaged,
+            ReturnDisposition::Refurbish => StockStatus::Quarantine,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Return {
     pub id: Uuid,
@@ -55,7 +102,7 @@ pub struct Return {
     pub customer_id: Option<Uuid>,
     pub location_id: Uuid,
     pub status: ReturnStatus,
-    pub total_quantity: i32,
+    pub total_quantity: Decimal,
     pub notes: Option<String>,
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
@@ -68,10 +115,16 @@ pub struct ReturnLine {
     pub id: Uuid,
     pub return_id: Uuid,
     pub item_id: Uuid,
-    pub quantity: i32,
-    pub quantity_received: i32,
-    pub unit_price: f64,
+    pub quantity: Decimal,
+    pub quantity_received: Decimal,
+    pub unit_price: Decimal,
     pub reason: Option<String>,
+    /// Set once the line is processed
+    pub disposition: Option<ReturnDisposition>,
+    /// The sales order the units were bought on, when the return names it
+    pub sales_order_id: Option<Uuid>,
+    /// The line of that order the units are returned against
+    pub so_line_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -87,9 +140,12 @@ pub struct CreateReturnRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateReturnLineRequest {
     pub item_id: Uuid,
-    pub quantity: i32,
-    pub unit_price: f64,
+    pub quantity: Decimal,
+    pub unit_price: Decimal,
     pub reason: Option<String>,
+    /// Given together with `so_line_id` to return units bought on that order
+    pub sales_order_id: Option<Uuid>,
+    pub so_line_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,7 +156,34 @@ pub struct ProcessReturnRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessReturnLineRequest {
     pub return_line_id: Uuid,
-    pub quantity_received: i32,
+    pub quantity_received: Decimal,
+    /// Defaults to REFURBISH, holding the units in quarantine for inspection
+    #[serde(default)]
+    pub disposition: ReturnDisposition,
+}
+
+/// A sales order line as returns against it see it
+#[derive(Debug, Clone)]
+pub struct ReturnableLine {
+    pub so_line_id: Uuid,
+    pub sales_order_id: Uuid,
+    pub customer_id: Option<Uuid>,
+    pub item_id: Uuid,
+    pub qty_shipped: Decimal,
+    /// Claimed by returns that aren't cancelled
+    pub qty_returned: Decimal,
+}
+
+/// Scrapped return quantities of one item, for the scrap report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrapTotal {
+    pub item_id: Uuid,
+    pub sku: String,
+    pub name: String,
+    pub quantity_scrapped: Decimal,
+    /// Scrapped units at the price they were returned for
+    pub value: Decimal,
+    pub return_count: i64,
 }
 
 impl Return {
@@ -116,7 +199,7 @@ impl Return {
             customer_id,
             location_id,
             status: ReturnStatus::Draft,
-            total_quantity: 0,
+            total_quantity: Decimal::ZERO,
             notes: None,
             created_by,
             created_at: Utc::now(),
@@ -126,15 +209,16 @@ impl Return {
     }
 
     pub fn add_line(&mut self, line: ReturnLine) -> Result<(), DomainError> {
-        if line.quantity <= 0 {
-            return Err(DomainError::ValidationError(
-                "Return line quantity must be positive".to_string(),
-            ));
+        if line.quantity <= Decimal::ZERO {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.return_line_quantity_positive",
+                &[],
+            )));
         }
 
-        if line.unit_price < 0.0 {
+        if line.unit_price < Decimal::ZERO {
             return Err(DomainError::ValidationError(
-                "Return line unit price cannot be negative".to_string(),
+                "Return line unit price cannot be negative".into(),
             ));
         }
 
@@ -144,12 +228,65 @@ impl Return {
         Ok(())
     }
 
+    /// Check the lines that name a sales order line against it. The order,
+    /// item and customer must match, and together with earlier returns no
+    /// more can come back than the order line shipped.
+    pub fn check_sales_order_lines(
+        &self,
+        returnable: &[ReturnableLine],
+    ) -> Result<(), DomainError> {
+        let mut claimed: HashMap<Uuid, Decimal> = HashMap::new();
+        for line in &self.lines {
+            let (Some(sales_order_id), Some(so_line_id)) = (line.sales_order_id, line.so_line_id)
+            else {
+                continue;
+            };
+            let order_line = returnable
+                .iter()
+                .find(|r| r.so_line_id == so_line_id && r.sales_order_id == sales_order_id)
+                .ok_or_else(|| {
+                    DomainError::ValidationError(
+                        format!("Sales order {} has no line {}", sales_order_id, so_line_id).into(),
+                    )
+                })?;
+
+            if order_line.item_id != line.item_id {
+                return Err(DomainError::ValidationError(
+                    format!(
+                        "Sales order line {} is for item {}, not {}",
+                        so_line_id, order_line.item_id, line.item_id
+                    )
+                    .into(),
+                ));
+            }
+            if let (Some(customer_id), Some(ordered_by)) =
+                (self.customer_id, order_line.customer_id)
+            {
+                if customer_id != ordered_by {
+                    return Err(DomainError::ValidationError(
+                        format!("Sales order {} belongs to another customer", sales_order_id)
+                            .into(),
+                    ));
+                }
+            }
+
+            let returned = claimed.entry(so_line_id).or_insert(order_line.qty_returned);
+            if *returned + line.quantity > order_line.qty_shipped {
+                return Err(DomainError::ValidationError(format!(
+                    "Cannot return {} units against sales order line {}: {} shipped, {} already returned",
+                    line.quantity, so_line_id, order_line.qty_shipped, returned
+                ).into()));
+            }
+            *returned += line.quantity;
+        }
+        Ok(())
+    }
+
     pub fn open(&mut self) -> Result<(), DomainError> {
         if !self.status.can_transition_to(&ReturnStatus::Open) {
-            return Err(DomainError::ValidationError(format!(
-                "Cannot open return with status: {:?}",
-                self.status
-            )));
+            return Err(DomainError::ValidationError(
+                format!("Cannot open return with status: {:?}", self.status).into(),
+            ));
         }
 
         self.status = ReturnStatus::Open;
@@ -162,15 +299,14 @@ impl Return {
         processed_lines: Vec<ProcessReturnLineRequest>,
     ) -> Result<Vec<crate::domain::entities::inventory::StockMovement>, DomainError> {
         if self.status != ReturnStatus::Open {
-            return Err(DomainError::ValidationError(format!(
-                "Cannot process return with status: {:?}",
-                self.status
-            )));
+            return Err(DomainError::ValidationError(
+                format!("Cannot process return with status: {:?}", self.status).into(),
+            ));
         }
 
         if processed_lines.is_empty() {
             return Err(DomainError::ValidationError(
-                "No lines specified for processing".to_string(),
+                "No lines specified for processing".into(),
             ));
         }
 
@@ -182,48 +318,72 @@ impl Return {
                 .iter_mut()
                 .find(|l| l.id == process_request.return_line_id)
                 .ok_or_else(|| {
-                    DomainError::ValidationError(format!(
-                        "Line {} not found",
-                        process_request.return_line_id
-                    ))
+                    DomainError::ValidationError(
+                        format!("Line {} not found", process_request.return_line_id).into(),
+                    )
                 })?;
 
             if process_request.quantity_received > line.quantity {
-                return Err(DomainError::ValidationError(format!(
-                    "Cannot receive {} units of line {}, only {} returned",
-                    process_request.quantity_received, line.id, line.quantity
-                )));
-            }
-
-            if process_request.quantity_received <= 0 {
                 return Err(DomainError::ValidationError(
-                    "Received quantity must be positive".to_string(),
+                    format!(
+                        "Cannot receive {} units of line {}, only {} returned",
+                        process_request.quantity_received, line.id, line.quantity
+                    )
+                    .into(),
                 ));
             }
 
+            if process_request.quantity_received <= Decimal::ZERO {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.received_quantity_positive",
+                    &[],
+                )));
+            }
+
             line.quantity_received = process_request.quantity_received;
+            line.disposition = Some(process_request.disposition);
             line.updated_at = Utc::now();
 
-            // Create inbound movement to location (items coming back into inventory)
-            let movement = crate::domain::entities::inventory::StockMovement::new(
+            // Create inbound movement to location, into the status the disposition calls for
+            let movement = StockMovement::new(
                 line.item_id,
                 self.location_id,
-                crate::domain::entities::inventory::MovementType::Inbound,
+                MovementType::Inbound,
                 process_request.quantity_received,
-                crate::domain::entities::inventory::ReferenceType::Return,
+                ReferenceType::Return,
                 Some(self.id),
                 Some(format!(
                     "Return inbound: {} units of item {}",
                     process_request.quantity_received, line.item_id
                 )),
                 Some(self.created_by),
-            )?;
+            )?
+            .with_stock_status(process_request.disposition.stock_status());
             stock_movements.push(movement);
+
+            // Scrapped units are received and written straight off again
+            if process_request.disposition == ReturnDisposition::Scrap {
+                let write_off = StockMovement::new(
+                    line.item_id,
+                    self.location_id,
+                    MovementType::Adjustment,
+                    -process_request.quantity_received,
+                    ReferenceType::Return,
+                    Some(self.id),
+                    Some(format!(
+                        "Return scrapped: {} units of item {}",
+                        process_request.quantity_received, line.item_id
+                    )),
+                    Some(self.created_by),
+                )?
+                .with_stock_status(StockStatus::Damaged);
+                stock_movements.push(write_off);
+            }
         }
 
         // Check if all lines are fully received
-        let total_received: i32 = self.lines.iter().map(|l| l.quantity_received).sum();
-        let total_returned: i32 = self.lines.iter().map(|l| l.quantity).sum();
+        let total_received: Decimal = self.lines.iter().map(|l| l.quantity_received).sum();
+        let total_returned: Decimal = self.lines.iter().map(|l| l.quantity).sum();
 
         if total_received >= total_returned {
             self.status = ReturnStatus::Received;
@@ -238,19 +398,20 @@ impl ReturnLine {
     pub fn new(
         return_id: Uuid,
         item_id: Uuid,
-        quantity: i32,
-        unit_price: f64,
+        quantity: Decimal,
+        unit_price: Decimal,
         reason: Option<String>,
     ) -> Result<Self, DomainError> {
-        if quantity <= 0 {
-            return Err(DomainError::ValidationError(
-                "Return line quantity must be positive".to_string(),
-            ));
+        if quantity <= Decimal::ZERO {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.return_line_quantity_positive",
+                &[],
+            )));
         }
 
-        if unit_price < 0.0 {
+        if unit_price < Decimal::ZERO {
             return Err(DomainError::ValidationError(
-                "Return line unit price cannot be negative".to_string(),
+                "Return line unit price cannot be negative".into(),
             ));
         }
 
@@ -259,11 +420,172 @@ impl ReturnLine {
             return_id,
             item_id,
             quantity,
-            quantity_received: 0,
-            unit_price,
+            quantity_received: Decimal::ZERO,
+            unit_price: round_unit(unit_price),
             reason,
+            disposition: None,
+            sales_order_id: None,
+            so_line_id: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         })
     }
+
+    /// Return the units against a line of the sales order they were bought on
+    pub fn with_sales_order_line(
+        mut self,
+        sales_order_id: Option<Uuid>,
+        so_line_id: Option<Uuid>,
+    ) -> Result<Self, DomainError> {
+        if sales_order_id.is_some() != so_line_id.is_some() {
+            return Err(DomainError::ValidationError(
+                "sales_order_id and so_line_id must be given together".into(),
+            ));
+        }
+        self.sales_order_id = sales_order_id;
+        self.so_line_id = so_line_id;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_return() -> Return {
+        let mut return_entity =
+            Return::new("RET-1".to_string(), None, Uuid::new_v4(), Uuid::new_v4()).unwrap();
+        let line = ReturnLine::new(
+            return_entity.id,
+            Uuid::new_v4(),
+            Decimal::from(5),
+            Decimal::from(4),
+            Some("Broken".to_string()),
+        )
+        .unwrap();
+        return_entity.add_line(line).unwrap();
+        return_entity.open().unwrap();
+        return_entity
+    }
+
+    fn process(return_entity: &mut Return, disposition: ReturnDisposition) -> Vec<StockMovement> {
+        let line_id = return_entity.lines[0].id;
+        return_entity
+            .process(vec![ProcessReturnLineRequest {
+                return_line_id: line_id,
+                quantity_received: Decimal::from(3),
+                disposition,
+            }])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_restock_and_refurbish_receive_into_their_status() {
+        let mut restocked = open_return();
+        let movements = process(&mut restocked, ReturnDisposition::Restock);
+        assert_eq!(movements.len(), 1);
+        assert_eq!(movements[0].quantity, Decimal::from(3));
+        assert_eq!(movements[0].stock_status, StockStatus::Available);
+        assert_eq!(
+            restocked.lines[0].disposition,
+            Some(ReturnDisposition::Restock)
+        );
+
+        let mut refurbished = open_return();
+        let movements = process(&mut refurbished, ReturnDisposition::Refurbish);
+        assert_eq!(movements.len(), 1);
+        assert_eq!(movements[0].stock_status, StockStatus::Quarantine);
+    }
+
+    #[test]
+    fn test_scrap_writes_received_units_off() {
+        let mut return_entity = open_return();
+        let movements = process(&mut return_entity, ReturnDisposition::Scrap);
+
+        assert_eq!(movements.len(), 2);
+        assert_eq!(
+            movements.iter().map(|m| m.quantity).sum::<Decimal>(),
+            Decimal::ZERO
+        );
+        assert!(movements
+            .iter()
+            .all(|m| m.stock_status == StockStatus::Damaged));
+        assert_eq!(movements[1].movement_type, MovementType::Adjustment);
+    }
+
+    #[test]
+    fn test_sales_order_lines_limit_returns_to_shipped_quantity() {
+        let customer_id = Uuid::new_v4();
+        let order_line = ReturnableLine {
+            so_line_id: Uuid::new_v4(),
+            sales_order_id: Uuid::new_v4(),
+            customer_id: Some(customer_id),
+            item_id: Uuid::new_v4(),
+            qty_shipped: Decimal::from(10),
+            qty_returned: Decimal::from(4),
+        };
+        let return_of = |quantities: &[i64], customer: Option<Uuid>| {
+            let mut return_entity = Return::new(
+                "RET-1".to_string(),
+                customer,
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+            )
+            .unwrap();
+            for quantity in quantities {
+                let line = ReturnLine::new(
+                    return_entity.id,
+                    order_line.item_id,
+                    Decimal::from(*quantity),
+                    Decimal::ONE,
+                    None,
+                )
+                .unwrap()
+                .with_sales_order_line(Some(order_line.sales_order_id), Some(order_line.so_line_id))
+                .unwrap();
+                return_entity.add_line(line).unwrap();
+            }
+            return_entity
+        };
+        let returnable = [order_line.clone()];
+
+        assert!(return_of(&[6], Some(customer_id))
+            .check_sales_order_lines(&returnable)
+            .is_ok());
+        assert!(return_of(&[7], None)
+            .check_sales_order_lines(&returnable)
+            .is_err());
+        // Lines of the same return count together
+        assert!(return_of(&[3, 3], None)
+            .check_sales_order_lines(&returnable)
+            .is_ok());
+        assert!(return_of(&[3, 4], None)
+            .check_sales_order_lines(&returnable)
+            .is_err());
+        assert!(return_of(&[1], Some(Uuid::new_v4()))
+            .check_sales_order_lines(&returnable)
+            .is_err());
+        assert!(return_of(&[1], None).check_sales_order_lines(&[]).is_err());
+    }
+
+    #[test]
+    fn test_sales_order_and_line_are_given_together() {
+        let line = || {
+            ReturnLine::new(
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                Decimal::ONE,
+                Decimal::ONE,
+                None,
+            )
+            .unwrap()
+        };
+        assert!(line()
+            .with_sales_order_line(Some(Uuid::new_v4()), None)
+            .is_err());
+        assert!(line()
+            .with_sales_order_line(None, Some(Uuid::new_v4()))
+            .is_err());
+        assert!(line().with_sales_order_line(None, None).is_ok());
+    }
 }
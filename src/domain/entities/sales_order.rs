@@ -1,6 +1,13 @@
+use crate::domain::entities::currency::OrderCurrency;
+use crate::domain::entities::shipment::Shipment;
+use crate::domain::entities::webhook::{WebhookEvent, WebhookEventType};
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::money::{self, extend, round_total, round_unit};
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -8,6 +15,7 @@ pub enum SalesOrderStatus {
     Draft,
     Confirmed,
     Picking,
+    PartiallyShipped,
     Shipped,
     Invoiced,
     Cancelled,
@@ -20,6 +28,7 @@ impl SalesOrderStatus {
             SalesOrderStatus::Draft => "DRAFT",
             SalesOrderStatus::Confirmed => "CONFIRMED",
             SalesOrderStatus::Picking => "PICKING",
+            SalesOrderStatus::PartiallyShipped => "PARTIALLY_SHIPPED",
             SalesOrderStatus::Shipped => "SHIPPED",
             SalesOrderStatus::Invoiced => "INVOICED",
             SalesOrderStatus::Cancelled => "CANCELLED",
@@ -32,14 +41,14 @@ impl SalesOrderStatus {
             "DRAFT" => Ok(SalesOrderStatus::Draft),
             "CONFIRMED" => Ok(SalesOrderStatus::Confirmed),
             "PICKING" => Ok(SalesOrderStatus::Picking),
+            "PARTIALLY_SHIPPED" => Ok(SalesOrderStatus::PartiallyShipped),
             "SHIPPED" => Ok(SalesOrderStatus::Shipped),
             "INVOICED" => Ok(SalesOrderStatus::Invoiced),
             "CANCELLED" => Ok(SalesOrderStatus::Cancelled),
             "RETURNED" => Ok(SalesOrderStatus::Returned),
-            _ => Err(DomainError::ValidationError(format!(
-                "Invalid sales order status: {}",
-                s
-            ))),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid sales order status: {}", s).into(),
+            )),
         }
     }
 
@@ -55,7 +64,13 @@ impl SalesOrderStatus {
             ),
             SalesOrderStatus::Picking => matches!(
                 new_status,
-                SalesOrderStatus::Shipped | SalesOrderStatus::Cancelled
+                SalesOrderStatus::PartiallyShipped
+                    | SalesOrderStatus::Shipped
+                    | SalesOrderStatus::Cancelled
+            ),
+            SalesOrderStatus::PartiallyShipped => matches!(
+                new_status,
+                SalesOrderStatus::PartiallyShipped | SalesOrderStatus::Shipped
             ),
             SalesOrderStatus::Shipped => matches!(
                 new_status,
@@ -73,25 +88,27 @@ pub struct SalesOrderLine {
     pub id: Uuid,
     pub so_id: Uuid,
     pub item_id: Uuid,
-    pub qty: i32,
-    pub unit_price: f64,
-    pub tax: f64,
+    pub qty: Decimal,
+    pub qty_shipped: Decimal,
+    pub unit_price: Decimal,
+    pub tax: Decimal,
     pub reserved: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 impl SalesOrderLine {
-    pub fn new(item_id: Uuid, qty: i32, unit_price: f64) -> Result<Self, DomainError> {
-        if qty <= 0 {
+    pub fn new(item_id: Uuid, qty: Decimal, unit_price: Decimal) -> Result<Self, DomainError> {
+        if qty <= Decimal::ZERO {
             return Err(DomainError::ValidationError(
-                "Quantity must be positive".to_string(),
+                "Quantity must be positive".into(),
             ));
         }
-        if unit_price < 0.0 {
-            return Err(DomainError::ValidationError(
-                "Unit price cannot be negative".to_string(),
-            ));
+        if unit_price < Decimal::ZERO {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.unit_price_negative",
+                &[],
+            )));
         }
 
         let now = Utc::now();
@@ -100,22 +117,28 @@ impl SalesOrderLine {
             so_id: Uuid::new_v4(), // Will be set when added to order
             item_id,
             qty,
-            unit_price,
-            tax: 0.0,
+            qty_shipped: Decimal::ZERO,
+            unit_price: round_unit(unit_price),
+            tax: Decimal::ZERO,
             reserved: false,
             created_at: now,
             updated_at: now,
         })
     }
 
-    pub fn line_total(&self) -> f64 {
-        (self.qty as f64 * self.unit_price) + self.tax
+    /// Quantity at the unit price plus tax, rounded to cents
+    pub fn line_total(&self) -> Decimal {
+        extend(self.qty, self.unit_price) + round_total(self.tax)
+    }
+
+    pub fn remaining_qty(&self) -> Decimal {
+        self.qty - self.qty_shipped
     }
 
     pub fn reserve(&mut self) -> Result<(), DomainError> {
         if self.reserved {
             return Err(DomainError::ValidationError(
-                "Line is already reserved".to_string(),
+                "Line is already reserved".into(),
             ));
         }
         self.reserved = true;
@@ -125,9 +148,7 @@ impl SalesOrderLine {
 
     pub fn unreserve(&mut self) -> Result<(), DomainError> {
         if !self.reserved {
-            return Err(DomainError::ValidationError(
-                "Line is not reserved".to_string(),
-            ));
+            return Err(DomainError::ValidationError("Line is not reserved".into()));
         }
         self.reserved = false;
         self.updated_at = Utc::now();
@@ -141,9 +162,18 @@ pub struct SalesOrder {
     pub so_number: String,
     pub customer_id: Option<Uuid>,
     pub status: SalesOrderStatus,
-    pub total_amount: f64,
+    /// Sum of the line totals
+    pub total_amount: Decimal,
+    /// Currency the customer is billed in; `None` means the tenant's base currency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Base currency units per unit of `currency`, fixed when the order was placed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange_rate: Option<Decimal>,
     pub fulfillment_location_id: Option<Uuid>,
     pub lines: Vec<SalesOrderLine>,
+    pub cancellation_reason: Option<String>,
+    pub cancelled_at: Option<DateTime<Utc>>,
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -157,9 +187,10 @@ impl SalesOrder {
         created_by: Uuid,
     ) -> Result<Self, DomainError> {
         if so_number.trim().is_empty() {
-            return Err(DomainError::ValidationError(
-                "SO number cannot be empty".to_string(),
-            ));
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.so_number_empty",
+                &[],
+            )));
         }
 
         let now = Utc::now();
@@ -168,19 +199,38 @@ impl SalesOrder {
             so_number,
             customer_id,
             status: SalesOrderStatus::Draft,
-            total_amount: 0.0,
+            total_amount: Decimal::ZERO,
+            currency: None,
+            exchange_rate: None,
             fulfillment_location_id,
             lines: Vec::new(),
+            cancellation_reason: None,
+            cancelled_at: None,
             created_by,
             created_at: now,
             updated_at: now,
         })
     }
 
+    /// Price the order in a foreign currency, or in the base currency with `None`
+    pub fn set_currency(&mut self, currency: Option<OrderCurrency>) {
+        self.exchange_rate = currency.as_ref().map(|c| c.exchange_rate);
+        self.currency = currency.map(|c| c.currency);
+    }
+
+    /// The order's total in the tenant's base currency, at the rate fixed when
+    /// it was placed
+    pub fn base_total_amount(&self) -> Result<Decimal, DomainError> {
+        match self.exchange_rate {
+            Some(rate) => money::convert(self.total_amount, rate),
+            None => Ok(self.total_amount),
+        }
+    }
+
     pub fn add_line(&mut self, mut line: SalesOrderLine) -> Result<(), DomainError> {
         if self.status != SalesOrderStatus::Draft {
             return Err(DomainError::ValidationError(
-                "Cannot add lines to non-draft sales order".to_string(),
+                "Cannot add lines to non-draft sales order".into(),
             ));
         }
 
@@ -191,17 +241,75 @@ impl SalesOrder {
         Ok(())
     }
 
+    pub fn update_line(
+        &mut self,
+        line_id: Uuid,
+        request: UpdateSalesOrderLineRequest,
+    ) -> Result<(), DomainError> {
+        if self.status != SalesOrderStatus::Draft {
+            return Err(DomainError::ValidationError(
+                "Cannot edit lines on non-draft sales order".into(),
+            ));
+        }
+
+        let line = self
+            .lines
+            .iter_mut()
+            .find(|l| l.id == line_id)
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Sales order line {} not found", line_id).into())
+            })?;
+
+        // Run the new values through the same validation as a fresh line
+        let updated = SalesOrderLine::new(
+            line.item_id,
+            request.qty.unwrap_or(line.qty),
+            request.unit_price.unwrap_or(line.unit_price),
+        )?;
+        line.qty = updated.qty;
+        line.unit_price = updated.unit_price;
+        line.updated_at = updated.updated_at;
+
+        self.recalculate_total();
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn remove_line(&mut self, line_id: Uuid) -> Result<(), DomainError> {
+        if self.status != SalesOrderStatus::Draft {
+            return Err(DomainError::ValidationError(
+                "Cannot remove lines from non-draft sales order".into(),
+            ));
+        }
+
+        let index = self
+            .lines
+            .iter()
+            .position(|l| l.id == line_id)
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Sales order line {} not found", line_id).into())
+            })?;
+        self.lines.remove(index);
+
+        self.recalculate_total();
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn etag(&self) -> String {
+        crate::shared::etag::entity_etag(self.id, self.updated_at)
+    }
+
     pub fn confirm(&mut self) -> Result<(), DomainError> {
         if !self.status.can_transition_to(&SalesOrderStatus::Confirmed) {
-            return Err(DomainError::ValidationError(format!(
-                "Cannot confirm sales order with status: {:?}",
-                self.status
-            )));
+            return Err(DomainError::ValidationError(
+                format!("Cannot confirm sales order with status: {:?}", self.status).into(),
+            ));
         }
 
         if self.lines.is_empty() {
             return Err(DomainError::ValidationError(
-                "Cannot confirm sales order without lines".to_string(),
+                "Cannot confirm sales order without lines".into(),
             ));
         }
 
@@ -212,10 +320,13 @@ impl SalesOrder {
 
     pub fn start_picking(&mut self) -> Result<(), DomainError> {
         if !self.status.can_transition_to(&SalesOrderStatus::Picking) {
-            return Err(DomainError::ValidationError(format!(
-                "Cannot start picking for sales order with status: {:?}",
-                self.status
-            )));
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Cannot start picking for sales order with status: {:?}",
+                    self.status
+                )
+                .into(),
+            ));
         }
 
         self.status = SalesOrderStatus::Picking;
@@ -232,55 +343,69 @@ impl SalesOrder {
             self.start_picking()?;
         }
 
-        if !self.status.can_transition_to(&SalesOrderStatus::Shipped) {
-            return Err(DomainError::ValidationError(format!(
-                "Cannot ship sales order with status: {:?}",
-                self.status
-            )));
+        if !self
+            .status
+            .can_transition_to(&SalesOrderStatus::PartiallyShipped)
+        {
+            return Err(DomainError::ValidationError(
+                format!("Cannot ship sales order with status: {:?}", self.status).into(),
+            ));
         }
 
         if shipped_lines.is_empty() {
             return Err(DomainError::ValidationError(
-                "No lines specified for shipping".to_string(),
+                "No lines specified for shipping".into(),
             ));
         }
 
         let mut stock_movements = Vec::new();
-        let fulfillment_location_id = self.fulfillment_location_id.ok_or_else(|| {
-            DomainError::ValidationError("Fulfillment location required for shipping".to_string())
-        })?;
 
         for ship_request in shipped_lines {
+            // Allocated shipments name their source location; otherwise ship from the order's location
+            let location_id = ship_request
+                .location_id
+                .or(self.fulfillment_location_id)
+                .ok_or_else(|| {
+                    DomainError::ValidationError(
+                        "Fulfillment location required for shipping".into(),
+                    )
+                })?;
+
             let line = self
                 .lines
                 .iter_mut()
                 .find(|l| l.id == ship_request.so_line_id)
                 .ok_or_else(|| {
-                    DomainError::ValidationError(format!(
-                        "Line {} not found",
-                        ship_request.so_line_id
-                    ))
+                    DomainError::ValidationError(
+                        format!("Line {} not found", ship_request.so_line_id).into(),
+                    )
                 })?;
 
-            if ship_request.qty_shipped > line.qty {
-                return Err(DomainError::ValidationError(format!(
-                    "Cannot ship {} units of line {}, only {} ordered",
-                    ship_request.qty_shipped, line.id, line.qty
-                )));
-            }
-
-            if ship_request.qty_shipped <= 0 {
+            if ship_request.qty_shipped > line.remaining_qty() {
                 return Err(DomainError::ValidationError(
-                    "Shipped quantity must be positive".to_string(),
+                    format!(
+                        "Cannot ship {} units of line {}, only {} remaining",
+                        ship_request.qty_shipped,
+                        line.id,
+                        line.remaining_qty()
+                    )
+                    .into(),
                 ));
             }
 
+            if ship_request.qty_shipped <= Decimal::ZERO {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.shipped_quantity_positive",
+                    &[],
+                )));
+            }
+
             // Create stock movement for the shipment (outbound)
             let movement = StockMovement::new(
                 line.item_id,
-                fulfillment_location_id,
+                location_id,
                 MovementType::Outbound,
-                -(ship_request.qty_shipped as i32), // Negative for outbound
+                -ship_request.qty_shipped, // Negative for outbound
                 ReferenceType::SalesOrder,
                 Some(self.id),
                 Some(format!(
@@ -292,41 +417,144 @@ impl SalesOrder {
 
             stock_movements.push(movement?);
 
-            // If line was reserved, unreserve it (assuming full shipment)
-            if line.reserved {
+            line.qty_shipped += ship_request.qty_shipped;
+            line.updated_at = Utc::now();
+
+            // Release the reservation once the line is fully shipped
+            if line.reserved && line.remaining_qty().is_zero() {
                 line.unreserve()?;
             }
         }
 
-        self.status = SalesOrderStatus::Shipped;
+        self.status = if self.lines.iter().all(|l| l.remaining_qty().is_zero()) {
+            SalesOrderStatus::Shipped
+        } else {
+            SalesOrderStatus::PartiallyShipped
+        };
         self.updated_at = Utc::now();
         Ok(stock_movements)
     }
 
-    pub fn cancel(&mut self) -> Result<(), DomainError> {
+    /// Split the unshipped remainder of a partially shipped order into a follow-up order.
+    /// The original order is closed as shipped and the returned order is confirmed.
+    pub fn create_backorder(
+        &mut self,
+        so_number: String,
+        created_by: Uuid,
+    ) -> Result<(SalesOrder, SalesOrderBackorder), DomainError> {
+        if self.status != SalesOrderStatus::PartiallyShipped {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Cannot backorder sales order with status: {:?}",
+                    self.status
+                )
+                .into(),
+            ));
+        }
+
+        let mut backorder_so = SalesOrder::new(
+            so_number,
+            self.customer_id,
+            self.fulfillment_location_id,
+            created_by,
+        )?;
+        // The remainder is billed the way the original order was
+        backorder_so.currency = self.currency.clone();
+        backorder_so.exchange_rate = self.exchange_rate;
+
+        for line in self
+            .lines
+            .iter()
+            .filter(|l| l.remaining_qty() > Decimal::ZERO)
+        {
+            let mut backorder_line =
+                SalesOrderLine::new(line.item_id, line.remaining_qty(), line.unit_price)?;
+            backorder_line.reserved = line.reserved;
+            backorder_so.add_line(backorder_line)?;
+        }
+
+        backorder_so.confirm()?;
+
+        let now = Utc::now();
+        for line in self
+            .lines
+            .iter_mut()
+            .filter(|l| l.remaining_qty() > Decimal::ZERO)
+        {
+            // The remainder now lives on the backorder, so the reservation moves with it
+            line.reserved = false;
+            line.updated_at = now;
+        }
+
+        self.status = SalesOrderStatus::Shipped;
+        self.updated_at = now;
+
+        let backorder = SalesOrderBackorder {
+            id: Uuid::new_v4(),
+            original_so_id: self.id,
+            backorder_so_id: backorder_so.id,
+            created_by,
+            created_at: now,
+        };
+
+        Ok((backorder_so, backorder))
+    }
+
+    /// Cancel the order and drop its reservations, returning a release movement per reserved line
+    pub fn cancel(
+        &mut self,
+        reason: Option<String>,
+        cancelled_by: Uuid,
+    ) -> Result<Vec<StockMovement>, DomainError> {
         if !self.status.can_transition_to(&SalesOrderStatus::Cancelled) {
-            return Err(DomainError::ValidationError(format!(
-                "Cannot cancel sales order with status: {:?}",
-                self.status
-            )));
+            return Err(DomainError::ValidationError(
+                format!("Cannot cancel sales order with status: {:?}", self.status).into(),
+            ));
         }
 
+        let mut stock_movements = Vec::new();
+        for line in &mut self.lines {
+            if !line.reserved {
+                continue;
+            }
+            if let Some(location_id) = self.fulfillment_location_id {
+                stock_movements.push(StockMovement::new(
+                    line.item_id,
+                    location_id,
+                    MovementType::Adjustment,
+                    Decimal::ZERO, // Releasing a soft reservation doesn't change on-hand
+                    ReferenceType::SalesOrder,
+                    Some(self.id),
+                    Some(format!(
+                        "Released {} units for cancelled sales order {}",
+                        line.remaining_qty(),
+                        self.so_number
+                    )),
+                    Some(cancelled_by),
+                )?);
+            }
+            line.unreserve()?;
+        }
+
+        let now = Utc::now();
         self.status = SalesOrderStatus::Cancelled;
-        self.updated_at = Utc::now();
-        Ok(())
+        self.cancellation_reason = reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty());
+        self.cancelled_at = Some(now);
+        self.updated_at = now;
+        Ok(stock_movements)
     }
 
     pub fn reserve_inventory(&mut self) -> Result<Vec<StockMovement>, DomainError> {
         if self.status != SalesOrderStatus::Confirmed {
             return Err(DomainError::ValidationError(
-                "Can only reserve inventory for confirmed sales orders".to_string(),
+                "Can only reserve inventory for confirmed sales orders".into(),
             ));
         }
 
         let fulfillment_location_id = self.fulfillment_location_id.ok_or_else(|| {
-            DomainError::ValidationError(
-                "Fulfillment location required for reservation".to_string(),
-            )
+            DomainError::ValidationError("Fulfillment location required for reservation".into())
         })?;
 
         let mut stock_movements = Vec::new();
@@ -339,7 +567,7 @@ impl SalesOrder {
                     line.item_id,
                     fulfillment_location_id,
                     MovementType::Adjustment,
-                    0, // No actual quantity change for reservation
+                    Decimal::ZERO, // No actual quantity change for reservation
                     ReferenceType::SalesOrder,
                     Some(self.id),
                     Some(format!(
@@ -357,16 +585,323 @@ impl SalesOrder {
         Ok(stock_movements)
     }
 
+    /// Quantity this order holds in soft reservation for an item at a location
+    pub fn reserved_qty(&self, item_id: Uuid, location_id: Uuid) -> Decimal {
+        if self.fulfillment_location_id != Some(location_id) {
+            return Decimal::ZERO;
+        }
+
+        self.lines
+            .iter()
+            .filter(|l| l.reserved && l.item_id == item_id)
+            .map(|l| l.remaining_qty())
+            .sum()
+    }
+
     fn recalculate_total(&mut self) {
         self.total_amount = self.lines.iter().map(|line| line.line_total()).sum();
     }
 }
 
+/// Links a follow-up order carrying unshipped quantities to the order it was split from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SalesOrderBackorder {
+    pub id: Uuid,
+    pub original_so_id: Uuid,
+    pub backorder_so_id: Uuid,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateSalesOrderLineRequest {
+    pub qty: Option<Decimal>,
+    pub unit_price: Option<Decimal>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShipLineRequest {
     pub so_line_id: Uuid,
-    pub qty_shipped: i32,
+    pub qty_shipped: Decimal,
+    pub location_id: Option<Uuid>,
+}
+
+/// Everything one shipment of a sales order changed, committed together
+#[derive(Debug, Clone)]
+pub struct ShippedSalesOrder {
+    pub sales_order: SalesOrder,
+    pub stock_movements: Vec<StockMovement>,
+    pub shipment: Shipment,
+    /// Webhook events announcing the shipment, in the order they are published
+    pub events: Vec<WebhookEvent>,
+}
+
+impl ShippedSalesOrder {
+    pub fn new(
+        sales_order: SalesOrder,
+        stock_movements: Vec<StockMovement>,
+        shipment: Shipment,
+    ) -> Self {
+        let events = Self::announce(&sales_order, &stock_movements, &shipment);
+        Self {
+            sales_order,
+            stock_movements,
+            shipment,
+            events,
+        }
+    }
+
+    fn announce(
+        sales_order: &SalesOrder,
+        stock_movements: &[StockMovement],
+        shipment: &Shipment,
+    ) -> Vec<WebhookEvent> {
+        vec![
+            WebhookEvent::new(
+                WebhookEventType::SalesOrderUpdated,
+                json!({
+                    "sales_order": {
+                        "id": sales_order.id,
+                        "so_number": sales_order.so_number,
+                        "customer_id": sales_order.customer_id,
+                        "status": sales_order.status.as_str(),
+                        "total_amount": sales_order.total_amount,
+                        "fulfillment_location_id": sales_order.fulfillment_location_id,
+                        "updated_at": sales_order.updated_at,
+                        "lines": sales_order.lines.iter().map(|line| json!({
+                            "id": line.id,
+                            "item_id": line.item_id,
+                            "qty": line.qty,
+                            "qty_shipped": line.qty_shipped,
+                            "unit_price": line.unit_price,
+                            "tax": line.tax,
+                            "line_total": line.line_total()
+                        })).collect::<Vec<_>>()
+                    },
+                    "stock_movements": stock_movements.iter().map(|movement| json!({
+                        "id": movement.id,
+                        "item_id": movement.item_id,
+                        "location_id": movement.location_id,
+                        "quantity": movement.quantity,
+                        "movement_type": movement.movement_type.as_str().to_uppercase(),
+                        "reference_type": movement.reference_type.as_str(),
+                        "reference_id": movement.reference_id,
+                        "reason": movement.reason,
+                        "created_by": movement.created_by,
+                        "created_at": movement.created_at
+                    })).collect::<Vec<_>>(),
+                    "shipment": {
+                        "id": shipment.id,
+                        "shipment_number": shipment.shipment_number,
+                        "carrier": shipment.carrier,
+                        "tracking_number": shipment.tracking_number
+                    }
+                }),
+            ),
+            WebhookEvent::new(
+                WebhookEventType::ShipmentCreated,
+                json!({ "shipment": shipment }),
+            ),
+        ]
+    }
 }
 
 // Re-export for convenience
 pub use crate::domain::entities::inventory::{MovementType, ReferenceType, StockMovement};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confirmed_order(qty: Decimal) -> SalesOrder {
+        let mut order = SalesOrder::new(
+            "SO-TEST".to_string(),
+            None,
+            Some(Uuid::new_v4()),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        order
+            .add_line(SalesOrderLine::new(Uuid::new_v4(), qty, Decimal::new(25, 1)).unwrap())
+            .unwrap();
+        order.confirm().unwrap();
+        order
+    }
+
+    #[test]
+    fn test_partial_shipment_leaves_order_partially_shipped() {
+        let mut order = confirmed_order(Decimal::from(10));
+        let line_id = order.lines[0].id;
+
+        order
+            .ship(vec![ShipLineRequest {
+                so_line_id: line_id,
+                qty_shipped: Decimal::from(4),
+                location_id: None,
+            }])
+            .unwrap();
+        assert_eq!(order.status, SalesOrderStatus::PartiallyShipped);
+        assert_eq!(order.lines[0].remaining_qty(), Decimal::from(6));
+
+        order
+            .ship(vec![ShipLineRequest {
+                so_line_id: line_id,
+                qty_shipped: Decimal::from(6),
+                location_id: None,
+            }])
+            .unwrap();
+        assert_eq!(order.status, SalesOrderStatus::Shipped);
+    }
+
+    #[test]
+    fn test_shipment_is_announced_with_order_and_shipment_events() {
+        let mut order = confirmed_order(Decimal::from(10));
+        let stock_movements = order
+            .ship(vec![ShipLineRequest {
+                so_line_id: order.lines[0].id,
+                qty_shipped: Decimal::from(4),
+                location_id: None,
+            }])
+            .unwrap();
+        let shipment = Shipment::new(
+            "SH-TEST".to_string(),
+            crate::domain::entities::shipment::ShipmentSourceType::SalesOrder,
+            order.id,
+            None,
+            None,
+            None,
+            order.created_by,
+        )
+        .unwrap();
+
+        let shipped = ShippedSalesOrder::new(order, stock_movements, shipment);
+        let types: Vec<_> = shipped
+            .events
+            .iter()
+            .map(|e| e.event_type.as_str())
+            .collect();
+        assert_eq!(types, ["SALES_ORDER_UPDATED", "SHIPMENT_CREATED"]);
+        let payload = &shipped.events[0].payload;
+        assert_eq!(payload["sales_order"]["status"], "PARTIALLY_SHIPPED");
+        assert_eq!(payload["stock_movements"][0]["movement_type"], "OUTBOUND");
+        assert_eq!(payload["shipment"]["shipment_number"], "SH-TEST");
+    }
+
+    #[test]
+    fn test_create_backorder_moves_remaining_quantity() {
+        let mut order = confirmed_order(Decimal::from(10));
+        order.set_currency(Some(OrderCurrency {
+            currency: "EUR".to_string(),
+            exchange_rate: Decimal::new(11, 1),
+        }));
+        let line_id = order.lines[0].id;
+        order
+            .ship(vec![ShipLineRequest {
+                so_line_id: line_id,
+                qty_shipped: Decimal::from(3),
+                location_id: None,
+            }])
+            .unwrap();
+
+        let (backorder_so, backorder) = order
+            .create_backorder("SO-BACK".to_string(), Uuid::new_v4())
+            .unwrap();
+
+        assert_eq!(order.status, SalesOrderStatus::Shipped);
+        assert_eq!(backorder_so.status, SalesOrderStatus::Confirmed);
+        assert_eq!(backorder_so.lines.len(), 1);
+        assert_eq!(backorder_so.lines[0].qty, Decimal::from(7));
+        assert_eq!(backorder_so.currency.as_deref(), Some("EUR"));
+        assert_eq!(backorder_so.exchange_rate, Some(Decimal::new(11, 1)));
+        assert_eq!(backorder.original_so_id, order.id);
+        assert_eq!(backorder.backorder_so_id, backorder_so.id);
+    }
+
+    #[test]
+    fn test_reserved_qty_shrinks_as_lines_ship() {
+        let mut order = confirmed_order(Decimal::from(10));
+        let (item_id, location_id) = (
+            order.lines[0].item_id,
+            order.fulfillment_location_id.unwrap(),
+        );
+        order.reserve_inventory().unwrap();
+        assert_eq!(order.reserved_qty(item_id, location_id), Decimal::from(10));
+        assert_eq!(order.reserved_qty(item_id, Uuid::new_v4()), Decimal::ZERO);
+
+        let line_id = order.lines[0].id;
+        order
+            .ship(vec![ShipLineRequest {
+                so_line_id: line_id,
+                qty_shipped: Decimal::from(4),
+                location_id: None,
+            }])
+            .unwrap();
+        assert_eq!(order.reserved_qty(item_id, location_id), Decimal::from(6));
+    }
+
+    #[test]
+    fn test_create_backorder_requires_partial_shipment() {
+        let mut order = confirmed_order(Decimal::from(5));
+        assert!(order
+            .create_backorder("SO-BACK".to_string(), Uuid::new_v4())
+            .is_err());
+    }
+
+    #[test]
+    fn test_cancel_releases_reservations_and_records_reason() {
+        let mut order = confirmed_order(Decimal::from(5));
+        order.reserve_inventory().unwrap();
+
+        let movements = order
+            .cancel(Some(" customer changed mind ".to_string()), Uuid::new_v4())
+            .unwrap();
+
+        assert_eq!(order.status, SalesOrderStatus::Cancelled);
+        assert_eq!(
+            order.cancellation_reason.as_deref(),
+            Some("customer changed mind")
+        );
+        assert!(order.cancelled_at.is_some());
+        assert_eq!(movements.len(), 1);
+        assert!(!order.lines[0].reserved);
+        assert!(order.cancel(None, Uuid::new_v4()).is_err());
+    }
+
+    #[test]
+    fn test_draft_line_edits_recalculate_total() {
+        let mut order = SalesOrder::new("SO-TEST".to_string(), None, None, Uuid::new_v4()).unwrap();
+        order
+            .add_line(
+                SalesOrderLine::new(Uuid::new_v4(), Decimal::from(2), Decimal::from(10)).unwrap(),
+            )
+            .unwrap();
+        order
+            .add_line(SalesOrderLine::new(Uuid::new_v4(), Decimal::ONE, Decimal::from(5)).unwrap())
+            .unwrap();
+        let (first, second) = (order.lines[0].id, order.lines[1].id);
+
+        order
+            .update_line(
+                first,
+                UpdateSalesOrderLineRequest {
+                    qty: Some(Decimal::from(3)),
+                    unit_price: None,
+                },
+            )
+            .unwrap();
+        order.remove_line(second).unwrap();
+        assert_eq!(order.total_amount, Decimal::from(30));
+        assert!(order
+            .update_line(
+                first,
+                UpdateSalesOrderLineRequest {
+                    qty: Some(Decimal::ZERO),
+                    unit_price: None
+                }
+            )
+            .is_err());
+
+        order.confirm().unwrap();
+        assert!(order.remove_line(first).is_err());
+    }
+}
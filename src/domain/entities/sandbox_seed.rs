@@ -0,0 +1,1214 @@
+use crate::domain::entities::inventory::{
+    MovementType, ReferenceType, StockLevel, StockMovement, StockStatus,
+};
+use crate::domain::entities::item::Item;
+use crate::domain::entities::location::{Location, LocationAddress, LocationType};
+use crate::domain::entities::location_capacity::LocationCapacity;
+use crate::domain::entities::purchase_order::{
+    PurchaseOrder, PurchaseOrderLine, PurchaseOrderStatus,
+};
+use crate::domain::entities::sales_order::{SalesOrder, SalesOrderLine, SalesOrderStatus};
+use crate::domain::entities::webhook::Webhook;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::money::{extend, round_total};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub const DEFAULT_SEED_PROFILE: &str = "demo";
+
+/// Seed used when the request doesn't pick one, so every sandbox starts from the
+/// same catalogue and the docs can refer to its SKUs
+pub const DEFAULT_SEED: u64 = 20240601;
+
+const MAX_LOCATIONS: usize = 25;
+const MAX_ITEMS: usize = 5000;
+const MAX_OPEN_ORDERS: usize = 500;
+const MAX_HISTORY_DAYS: i64 = 730;
+const MAX_ORDERS_PER_DAY: usize = 50;
+
+/// Most lines a replenishment purchase order carries; the rest wait for the next week
+const MAX_REPLENISHMENT_LINES: usize = 30;
+/// 8% sales tax
+const TAX_RATE: Decimal = Decimal::from_parts(8, 0, 0, false, 2);
+
+/// How much data a sandbox is seeded with
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeedProfile {
+    pub name: String,
+    pub locations: usize,
+    pub items: usize,
+    /// Purchase orders left in DRAFT, OPEN or PARTIAL_RECEIVED
+    pub open_purchase_orders: usize,
+    /// Sales orders left in DRAFT or CONFIRMED
+    pub open_sales_orders: usize,
+    /// Days of received purchase orders, shipped sales orders and their movements
+    pub history_days: i64,
+    /// Average number of sales orders shipped per day of history
+    pub orders_per_day: usize,
+}
+
+impl SeedProfile {
+    pub const NAMES: [&'static str; 3] = ["minimal", "demo", "large"];
+
+    pub fn named(name: &str) -> Result<Self, DomainError> {
+        let (
+            locations,
+            items,
+            open_purchase_orders,
+            open_sales_orders,
+            history_days,
+            orders_per_day,
+        ) = match name {
+            "minimal" => (2, 25, 3, 5, 14, 2),
+            "demo" => (5, 250, 12, 30, 90, 6),
+            "large" => (10, 1500, 40, 150, 365, 20),
+            other => {
+                return Err(DomainError::ValidationError(
+                    format!(
+                        "Unknown seed profile: {}. Must be one of: {}",
+                        other,
+                        Self::NAMES.join(", ")
+                    )
+                    .into(),
+                ))
+            }
+        };
+        Ok(Self {
+            name: name.to_string(),
+            locations,
+            items,
+            open_purchase_orders,
+            open_sales_orders,
+            history_days,
+            orders_per_day,
+        })
+    }
+
+    /// Adjust the profile's counts, within the limits a single request may seed
+    pub fn with_overrides(mut self, overrides: &SeedOverrides) -> Result<Self, DomainError> {
+        fn within<T: PartialOrd + std::fmt::Display>(
+            name: &str,
+            value: T,
+            min: T,
+            max: T,
+        ) -> Result<T, DomainError> {
+            if value < min || value > max {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.field_between",
+                    &[("field", &name), ("min", &min), ("max", &max)],
+                )));
+            }
+            Ok(value)
+        }
+
+        if let Some(locations) = overrides.locations {
+            self.locations = within("locations", locations, 1, MAX_LOCATIONS)?;
+        }
+        if let Some(items) = overrides.items {
+            self.items = within("items", items, 1, MAX_ITEMS)?;
+        }
+        if let Some(count) = overrides.open_purchase_orders {
+            self.open_purchase_orders = within("open_purchase_orders", count, 0, MAX_OPEN_ORDERS)?;
+        }
+        if let Some(count) = overrides.open_sales_orders {
+            self.open_sales_orders = within("open_sales_orders", count, 0, MAX_OPEN_ORDERS)?;
+        }
+        if let Some(days) = overrides.history_days {
+            self.history_days = within("history_days", days, 0, MAX_HISTORY_DAYS)?;
+        }
+        if let Some(count) = overrides.orders_per_day {
+            self.orders_per_day = within("orders_per_day", count, 0, MAX_ORDERS_PER_DAY)?;
+        }
+        Ok(self)
+    }
+}
+
+/// Per-request changes to a seed profile's counts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SeedOverrides {
+    pub locations: Option<usize>,
+    pub items: Option<usize>,
+    pub open_purchase_orders: Option<usize>,
+    pub open_sales_orders: Option<usize>,
+    pub history_days: Option<i64>,
+    pub orders_per_day: Option<usize>,
+    /// Generate a different catalogue and history; defaults to `DEFAULT_SEED`
+    pub seed: Option<u64>,
+}
+
+/// What a sandbox was seeded with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedSummary {
+    pub profile: String,
+    pub seed: u64,
+    pub locations: usize,
+    pub items: usize,
+    pub purchase_orders: usize,
+    pub sales_orders: usize,
+    pub stock_movements: usize,
+    pub webhooks: usize,
+}
+
+/// How a sandbox was last seeded: replayed by a reset that doesn't pick a
+/// profile of its own
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedRecord {
+    pub overrides: SeedOverrides,
+    pub summary: SeedSummary,
+    pub seeded_at: DateTime<Utc>,
+}
+
+/// A sandbox's demo data: a catalogue stocked across several locations, the
+/// purchase and sales orders that moved it over the profile's history, orders
+/// still in progress, and the resulting stock levels
+#[derive(Debug, Clone)]
+pub struct SeedDataset {
+    pub seed: u64,
+    pub locations: Vec<Location>,
+    pub items: Vec<Item>,
+    pub purchase_orders: Vec<PurchaseOrder>,
+    pub sales_orders: Vec<SalesOrder>,
+    pub movements: Vec<StockMovement>,
+    pub stock_levels: Vec<StockLevel>,
+    pub webhooks: Vec<Webhook>,
+}
+
+impl SeedDataset {
+    /// Generate the dataset. The same profile and seed always produce the same
+    /// catalogue, orders and quantities; only ids differ between sandboxes.
+    pub fn generate(
+        profile: &SeedProfile,
+        seed: u64,
+        tenant_id: Uuid,
+        created_by: Uuid,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut generator = Generator {
+            rng: SeedRng::new(seed),
+            created_by,
+            now,
+            levels: HashMap::new(),
+            movements: Vec::new(),
+            purchase_orders: Vec::new(),
+            sales_orders: Vec::new(),
+        };
+        let locations = generator.locations(profile.locations);
+        let items = generator.items(profile.items, tenant_id);
+        generator.history(profile, &items, &locations);
+        generator.open_purchase_orders(profile.open_purchase_orders, &items, &locations);
+        generator.open_sales_orders(profile.open_sales_orders, &items, &locations);
+
+        let mut stock_levels: Vec<StockLevel> = generator.levels.into_values().collect();
+        stock_levels.sort_by_key(|level| (level.item_id, level.location_id));
+        Self {
+            seed,
+            locations,
+            items,
+            purchase_orders: generator.purchase_orders,
+            sales_orders: generator.sales_orders,
+            movements: generator.movements,
+            stock_levels,
+            webhooks: Vec::new(),
+        }
+    }
+
+    pub fn summary(&self, profile: &SeedProfile) -> SeedSummary {
+        SeedSummary {
+            profile: profile.name.clone(),
+            seed: self.seed,
+            locations: self.locations.len(),
+            items: self.items.len(),
+            purchase_orders: self.purchase_orders.len(),
+            sales_orders: self.sales_orders.len(),
+            stock_movements: self.movements.len(),
+            webhooks: self.webhooks.len(),
+        }
+    }
+}
+
+const MAX_LOAD_LOCATIONS: usize = 1000;
+const MAX_LOAD_ITEMS: usize = 1_000_000;
+const MAX_LOAD_MOVEMENTS: usize = 100_000_000;
+
+/// Volumes for a load test: far past what a sandbox may be seeded with, so
+/// pagination and reports can be measured at production scale
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadProfile {
+    pub locations: usize,
+    pub items: usize,
+    pub movements: usize,
+    /// Days the movements are spread over, ending now
+    pub history_days: i64,
+}
+
+impl Default for LoadProfile {
+    fn default() -> Self {
+        Self {
+            locations: 10,
+            items: 10_000,
+            movements: 1_000_000,
+            history_days: 365,
+        }
+    }
+}
+
+impl LoadProfile {
+    pub fn validate(&self) -> Result<(), DomainError> {
+        let checks = [
+            ("locations", self.locations, 1, MAX_LOAD_LOCATIONS),
+            ("items", self.items, 1, MAX_LOAD_ITEMS),
+            ("movements", self.movements, 0, MAX_LOAD_MOVEMENTS),
+            (
+                "history_days",
+                self.history_days as usize,
+                1,
+                MAX_HISTORY_DAYS as usize,
+            ),
+        ];
+        for (name, value, min, max) in checks {
+            if value < min || value > max {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.field_between",
+                    &[("field", &name), ("min", &min), ("max", &max)],
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A load-test dataset: the sandbox catalogue at the profile's size and a run of
+/// receipts and shipments across it, produced a batch at a time so millions of
+/// movements never sit in memory together. Shipments only take what is on hand.
+pub struct LoadDataset {
+    generator: Generator,
+    pub locations: Vec<Location>,
+    pub items: Vec<Item>,
+    start: DateTime<Utc>,
+    span_ms: i64,
+    total: usize,
+    produced: usize,
+}
+
+impl LoadDataset {
+    pub fn generate(
+        profile: &LoadProfile,
+        seed: u64,
+        tenant_id: Uuid,
+        created_by: Uuid,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut generator = Generator {
+            rng: SeedRng::new(seed),
+            created_by,
+            now,
+            levels: HashMap::new(),
+            movements: Vec::new(),
+            purchase_orders: Vec::new(),
+            sales_orders: Vec::new(),
+        };
+        let locations = generator.locations(profile.locations);
+        let items = generator.items(profile.items, tenant_id);
+        Self {
+            generator,
+            locations,
+            items,
+            start: now - Duration::days(profile.history_days),
+            span_ms: Duration::days(profile.history_days).num_milliseconds(),
+            total: profile.movements,
+            produced: 0,
+        }
+    }
+
+    /// The next `limit` movements, oldest first; empty once all have been produced
+    pub fn next_movements(&mut self, limit: usize) -> Vec<StockMovement> {
+        let end = (self.produced + limit).min(self.total);
+        for index in self.produced..end {
+            let at = self.start
+                + Duration::milliseconds(
+                    (self.span_ms as i128 * index as i128 / self.total as i128) as i64,
+                );
+            let item = &self.items[self.generator.rng.skewed(self.items.len())];
+            let location =
+                &self.locations[self.generator.rng.below(self.locations.len() as u64) as usize];
+            let on_hand = self.generator.on_hand(item.id, location.id);
+            if on_hand < Decimal::ONE || self.generator.rng.chance(35) {
+                let qty = item
+                    .reorder_qty
+                    .unwrap_or(Decimal::from(10))
+                    .max(Decimal::ONE);
+                self.generator.move_stock(
+                    item.id,
+                    location.id,
+                    MovementType::Inbound,
+                    qty,
+                    ReferenceType::PurchaseOrder,
+                    None,
+                    None,
+                    at,
+                );
+            } else {
+                let most = on_hand.to_u64().unwrap_or(1).min(6);
+                let qty = Decimal::from(self.generator.rng.range(1, most));
+                self.generator.move_stock(
+                    item.id,
+                    location.id,
+                    MovementType::Outbound,
+                    -qty,
+                    ReferenceType::SalesOrder,
+                    None,
+                    None,
+                    at,
+                );
+            }
+        }
+        self.produced = end;
+        std::mem::take(&mut self.generator.movements)
+    }
+
+    /// Every item and location's level after the movements produced so far
+    pub fn stock_levels(&self) -> Vec<StockLevel> {
+        let mut levels: Vec<StockLevel> = self.generator.levels.values().cloned().collect();
+        levels.sort_by_key(|level| (level.item_id, level.location_id));
+        levels
+    }
+}
+
+struct Category {
+    name: &'static str,
+    code: &'static str,
+    unit: &'static str,
+    /// Cost price range in whole currency units
+    cost: (u64, u64),
+    products: &'static [&'static str],
+    variants: &'static [&'static str],
+}
+
+const CATEGORIES: &[Category] = &[
+    Category {
+        name: "Electronics",
+        code: "ELE",
+        unit: "each",
+        cost: (8, 400),
+        products: &[
+            "Wireless Mouse",
+            "Mechanical Keyboard",
+            "USB-C Cable",
+            "27in Monitor",
+            "Laptop Stand",
+            "HD Webcam",
+            "Noise Cancelling Headset",
+            "Power Bank",
+            "HDMI Adapter",
+            "Bluetooth Speaker",
+            "Barcode Scanner",
+            "Label Printer",
+        ],
+        variants: &["Black", "White", "Grey", "Pro", "Compact"],
+    },
+    Category {
+        name: "Office Supplies",
+        code: "OFF",
+        unit: "box",
+        cost: (2, 40),
+        products: &[
+            "Copy Paper",
+            "Ballpoint Pens",
+            "Stapler",
+            "Sticky Notes",
+            "Ring Binder",
+            "Desk Organizer",
+            "Whiteboard Markers",
+            "Envelopes",
+            "File Folders",
+        ],
+        variants: &["Standard", "Premium", "Recycled", "Assorted"],
+    },
+    Category {
+        name: "Apparel",
+        code: "APP",
+        unit: "each",
+        cost: (3, 60),
+        products: &[
+            "Cotton T-Shirt",
+            "Hoodie",
+            "Work Gloves",
+            "Safety Vest",
+            "Beanie",
+            "Work Socks",
+            "Rain Jacket",
+        ],
+        variants: &["Small", "Medium", "Large", "XL"],
+    },
+    Category {
+        name: "Hardware",
+        code: "HRD",
+        unit: "pack",
+        cost: (1, 80),
+        products: &[
+            "Hex Bolts M8",
+            "Wood Screws",
+            "Wall Anchors",
+            "Door Hinge",
+            "Padlock",
+            "Tape Measure",
+            "Utility Knife",
+            "Cable Ties",
+            "Shelf Brackets",
+        ],
+        variants: &["Zinc", "Stainless", "Heavy Duty", "Small", "Large"],
+    },
+    Category {
+        name: "Packaging",
+        code: "PKG",
+        unit: "case",
+        cost: (4, 90),
+        products: &[
+            "Shipping Box",
+            "Bubble Wrap",
+            "Packing Tape",
+            "Pallet Wrap",
+            "Poly Mailer",
+            "Void Fill",
+            "Fragile Labels",
+        ],
+        variants: &["Small", "Medium", "Large", "Clear", "Brown"],
+    },
+    Category {
+        name: "Cleaning",
+        code: "CLN",
+        unit: "each",
+        cost: (2, 35),
+        products: &[
+            "Hand Soap",
+            "Disinfectant Spray",
+            "Paper Towels",
+            "Trash Bags",
+            "Microfiber Cloths",
+            "Floor Cleaner",
+        ],
+        variants: &["Unscented", "Citrus", "Bulk", "Refill"],
+    },
+    Category {
+        name: "Kitchen",
+        code: "KIT",
+        unit: "each",
+        cost: (3, 120),
+        products: &[
+            "Coffee Beans",
+            "Travel Mug",
+            "Water Bottle",
+            "Chef Knife",
+            "Cutting Board",
+            "Storage Containers",
+            "Kettle",
+        ],
+        variants: &["Steel", "Bamboo", "Blue", "Red", "Family Size"],
+    },
+];
+
+/// City, region, postal code, street, country
+const SITES: &[(&str, &str, &str, &str, &str)] = &[
+    ("Columbus", "OH", "43215", "1200 Logistics Pkwy", "US"),
+    ("Oakland", "CA", "94607", "850 Harbor Blvd", "US"),
+    ("Austin", "TX", "78701", "401 Congress Ave", "US"),
+    ("Atlanta", "GA", "30303", "55 Peachtree St", "US"),
+    ("Denver", "CO", "80202", "1600 Wynkoop St", "US"),
+    ("Chicago", "IL", "60607", "900 W Fulton Market", "US"),
+    ("Seattle", "WA", "98104", "300 Occidental Ave S", "US"),
+    ("Newark", "NJ", "07114", "10 Port St", "US"),
+    ("Phoenix", "AZ", "85004", "222 N Central Ave", "US"),
+    ("Memphis", "TN", "38118", "3400 Airways Blvd", "US"),
+];
+
+/// Location types cycle through this pattern, starting with the main warehouse
+const LOCATION_PATTERN: &[LocationType] = &[
+    LocationType::Warehouse,
+    LocationType::Store,
+    LocationType::Warehouse,
+    LocationType::Store,
+    LocationType::DropShip,
+];
+
+struct Generator {
+    rng: SeedRng,
+    created_by: Uuid,
+    now: DateTime<Utc>,
+    levels: HashMap<(Uuid, Uuid), StockLevel>,
+    movements: Vec<StockMovement>,
+    purchase_orders: Vec<PurchaseOrder>,
+    sales_orders: Vec<SalesOrder>,
+}
+
+impl Generator {
+    fn locations(&mut self, count: usize) -> Vec<Location> {
+        let mut counters: HashMap<&'static str, usize> = HashMap::new();
+        (0..count)
+            .map(|i| {
+                let location_type = LOCATION_PATTERN[i % LOCATION_PATTERN.len()].clone();
+                let (city, region, postal_code, street, country) = SITES[i % SITES.len()];
+                let (prefix, name) = match location_type {
+                    LocationType::Warehouse => ("WH", format!("{} Distribution Center", city)),
+                    LocationType::Store => ("ST", format!("{} Store", city)),
+                    LocationType::DropShip => ("DS", format!("{} Drop-ship Partner", city)),
+                };
+                let counter = counters.entry(prefix).or_default();
+                *counter += 1;
+                Location {
+                    id: Uuid::new_v4(),
+                    name,
+                    code: Some(format!("{}-{:03}", prefix, counter)),
+                    address: Some(LocationAddress {
+                        line1: Some(street.to_string()),
+                        line2: None,
+                        city: Some(city.to_string()),
+                        region: Some(region.to_string()),
+                        postal_code: Some(postal_code.to_string()),
+                        country: Some(country.to_string()),
+                    }),
+                    r#type: Some(location_type),
+                    active: true,
+                    capacity: LocationCapacity::default(),
+                    created_at: self.now,
+                    updated_at: self.now,
+                }
+            })
+            .collect()
+    }
+
+    fn items(&mut self, count: usize, tenant_id: Uuid) -> Vec<Item> {
+        let mut per_category: HashMap<&'static str, usize> = HashMap::new();
+        (0..count)
+            .map(|i| {
+                let category = &CATEGORIES[i % CATEGORIES.len()];
+                let product = *self.rng.pick(category.products);
+                let variant = *self.rng.pick(category.variants);
+                let number = per_category.entry(category.code).or_default();
+                *number += 1;
+
+                let cost_price = Decimal::new(
+                    self.rng.range(category.cost.0 * 100, category.cost.1 * 100) as i64,
+                    2,
+                );
+                let markup = Decimal::new(130 + self.rng.below(90) as i64, 2);
+                let reorder_point = self.rng.range(5, 40) as i64;
+                // GS1 "restricted circulation" prefix 200, reserved for in-house numbering
+                let digits = format!("200{:09}", i + 1);
+                let barcode = format!(
+                    "{}{}",
+                    digits,
+                    crate::domain::entities::gs1::gs1_check_digit(&digits)
+                );
+
+                Item {
+                    id: Uuid::new_v4(),
+                    tenant_id,
+                    sku: format!("{}-{:04}", category.code, number),
+                    name: format!("{} {}", product, variant),
+                    description: Some(format!(
+                        "{} {}, sold per {}",
+                        variant,
+                        product.to_lowercase(),
+                        category.unit
+                    )),
+                    category: Some(category.name.to_string()),
+                    unit: category.unit.to_string(),
+                    barcode: Some(barcode),
+                    cost_price,
+                    sale_price: Some(round_total(cost_price * markup)),
+                    reorder_point: Some(Decimal::from(reorder_point)),
+                    reorder_qty: Some(Decimal::from(reorder_point * self.rng.range(2, 4) as i64)),
+                    quantity_precision: 0,
+                    weight: Some(self.rng.range(5, 5000) as f64 / 1000.0),
+                    dimensions: None,
+                    metadata: None,
+                    product_id: None,
+                    variant_attributes: None,
+                    active: true,
+                    created_at: self.now,
+                    updated_at: self.now,
+                }
+            })
+            .collect()
+    }
+
+    /// Stock the catalogue, then replay the profile's history day by day: sales
+    /// orders ship from whatever is on hand and a weekly purchase order per
+    /// location restocks everything at or below its reorder point
+    fn history(&mut self, profile: &SeedProfile, items: &[Item], locations: &[Location]) {
+        let start = self.now - Duration::days(profile.history_days);
+
+        let mut stocked: Vec<Vec<usize>> = vec![Vec::new(); locations.len()];
+        for (item_index, item) in items.iter().enumerate() {
+            let reorder_point = item
+                .reorder_point
+                .and_then(|point| point.to_u64())
+                .unwrap_or(10);
+            for (location_index, location) in locations.iter().enumerate() {
+                let chance = match location.r#type {
+                    Some(LocationType::Warehouse) => 90,
+                    Some(LocationType::Store) => 45,
+                    _ => 20,
+                };
+                // The main warehouse carries the whole catalogue
+                if location_index > 0 && !self.rng.chance(chance) {
+                    continue;
+                }
+                stocked[location_index].push(item_index);
+                let quantity = Decimal::from(self.rng.range(reorder_point / 2, reorder_point * 4));
+                self.move_stock(
+                    item.id,
+                    location.id,
+                    MovementType::Initial,
+                    quantity,
+                    ReferenceType::Initial,
+                    None,
+                    Some("Opening balance".to_string()),
+                    start,
+                );
+            }
+        }
+
+        for day in 0..profile.history_days {
+            let day_start = start + Duration::days(day);
+            let orders = self.rng.range(0, profile.orders_per_day as u64 * 2);
+            for _ in 0..orders {
+                let at = day_start + Duration::minutes(self.rng.range(8 * 60, 18 * 60) as i64);
+                let location_index = self.rng.below(locations.len() as u64) as usize;
+                self.shipped_sales_order(
+                    items,
+                    &locations[location_index],
+                    &stocked[location_index],
+                    at,
+                );
+            }
+            if day % 7 == 6 {
+                let at = day_start + Duration::hours(7);
+                for (location_index, location) in locations.iter().enumerate() {
+                    self.replenishment(items, location, &stocked[location_index], at);
+                }
+            }
+        }
+    }
+
+    fn shipped_sales_order(
+        &mut self,
+        items: &[Item],
+        location: &Location,
+        stocked: &[usize],
+        at: DateTime<Utc>,
+    ) {
+        if stocked.is_empty() {
+            return;
+        }
+        let mut order = self.sales_order(SalesOrderStatus::Shipped, Some(location.id), at);
+        for _ in 0..self.rng.range(1, 4) {
+            let item = &items[stocked[self.rng.skewed(stocked.len())]];
+            let on_hand = self.on_hand(item.id, location.id);
+            if on_hand <= Decimal::ZERO || order.lines.iter().any(|line| line.item_id == item.id) {
+                continue;
+            }
+            let qty = Decimal::from(self.rng.range(1, 6)).min(on_hand);
+            let mut line = self.sales_order_line(&order, item, qty);
+            line.qty_shipped = qty;
+            order.lines.push(line);
+            self.move_stock(
+                item.id,
+                location.id,
+                MovementType::Outbound,
+                -qty,
+                ReferenceType::SalesOrder,
+                Some(order.id),
+                Some(format!("Shipped {}", order.so_number)),
+                at + Duration::hours(2),
+            );
+        }
+        self.push_sales_order(order);
+    }
+
+    fn replenishment(
+        &mut self,
+        items: &[Item],
+        location: &Location,
+        stocked: &[usize],
+        at: DateTime<Utc>,
+    ) {
+        let low: Vec<&Item> = stocked
+            .iter()
+            .map(|&index| &items[index])
+            .filter(|item| {
+                self.on_hand(item.id, location.id) <= item.reorder_point.unwrap_or(Decimal::ZERO)
+            })
+            .take(MAX_REPLENISHMENT_LINES)
+            .collect();
+        if low.is_empty() {
+            return;
+        }
+        let ordered_at = at - Duration::days(5);
+        let mut po = self.purchase_order(PurchaseOrderStatus::Received, ordered_at);
+        po.expected_date = Some(at);
+        po.updated_at = at;
+        for item in low {
+            let qty = item
+                .reorder_qty
+                .unwrap_or(Decimal::from(10))
+                .max(Decimal::ONE);
+            let mut line = purchase_order_line(&po, item, qty);
+            line.qty_received = qty;
+            po.lines.push(line);
+            self.move_stock(
+                item.id,
+                location.id,
+                MovementType::Inbound,
+                qty,
+                ReferenceType::PurchaseOrder,
+                Some(po.id),
+                Some(format!("Received {}", po.po_number)),
+                at,
+            );
+        }
+        self.push_purchase_order(po);
+    }
+
+    /// Purchase orders still in progress, for the items running lowest;
+    /// partially received ones were put away at the main warehouse
+    fn open_purchase_orders(&mut self, count: usize, items: &[Item], locations: &[Location]) {
+        let main = locations[0].id;
+        let mut by_stock: Vec<&Item> = items.iter().collect();
+        by_stock.sort_by_key(|item| {
+            self.on_hand(item.id, main) - item.reorder_point.unwrap_or(Decimal::ZERO)
+        });
+        let mut candidates = by_stock.into_iter().cycle();
+
+        for i in 0..count {
+            let status = match i % 3 {
+                0 => PurchaseOrderStatus::Open,
+                1 => PurchaseOrderStatus::Draft,
+                _ => PurchaseOrderStatus::PartialReceived,
+            };
+            let ordered_at = self.now - Duration::hours(self.rng.range(1, 10 * 24) as i64);
+            let mut po = self.purchase_order(status.clone(), ordered_at);
+            po.expected_date = Some(self.now + Duration::days(self.rng.range(3, 21) as i64));
+
+            let lines = self.rng.range(2, 6) as usize;
+            for item in candidates.by_ref().take(lines.min(items.len())) {
+                let qty = item
+                    .reorder_qty
+                    .unwrap_or(Decimal::from(10))
+                    .max(Decimal::from(2));
+                let mut line = purchase_order_line(&po, item, qty);
+                if status == PurchaseOrderStatus::PartialReceived && po.lines.is_empty() {
+                    line.qty_received = (qty / Decimal::TWO).floor();
+                    self.move_stock(
+                        item.id,
+                        main,
+                        MovementType::Inbound,
+                        line.qty_received,
+                        ReferenceType::PurchaseOrder,
+                        Some(po.id),
+                        Some(format!("Received {}", po.po_number)),
+                        self.now - Duration::hours(1),
+                    );
+                }
+                po.lines.push(line);
+            }
+            self.push_purchase_order(po);
+        }
+    }
+
+    /// Sales orders waiting to ship. Confirmed orders hold their quantities
+    /// against available stock, as they would after `POST /sales_orders`; one
+    /// that can't be covered is left in draft instead.
+    fn open_sales_orders(&mut self, count: usize, items: &[Item], locations: &[Location]) {
+        for i in 0..count {
+            let location = &locations[self.rng.below(locations.len() as u64) as usize];
+            let at = self.now - Duration::minutes(self.rng.range(5, 3 * 24 * 60) as i64);
+            let mut order = self.sales_order(SalesOrderStatus::Draft, Some(location.id), at);
+            for _ in 0..self.rng.range(1, 4) {
+                let item = &items[self.rng.skewed(items.len())];
+                if order.lines.iter().any(|line| line.item_id == item.id) {
+                    continue;
+                }
+                let qty = Decimal::from(self.rng.range(1, 8));
+                let line = self.sales_order_line(&order, item, qty);
+                order.lines.push(line);
+            }
+
+            let covered = order.lines.iter().all(|line| {
+                self.levels
+                    .get(&(line.item_id, location.id))
+                    .is_some_and(|level| {
+                        level.quantity_on_hand - level.quantity_reserved >= line.qty
+                    })
+            });
+            if i % 3 != 0 && covered {
+                order.status = SalesOrderStatus::Confirmed;
+                for line in &mut order.lines {
+                    line.reserved = true;
+                    if let Some(level) = self.levels.get_mut(&(line.item_id, location.id)) {
+                        level.quantity_reserved += line.qty;
+                    }
+                }
+            }
+            self.push_sales_order(order);
+        }
+    }
+
+    fn purchase_order(&mut self, status: PurchaseOrderStatus, at: DateTime<Utc>) -> PurchaseOrder {
+        PurchaseOrder {
+            id: Uuid::new_v4(),
+            po_number: format!("PO-{}", 100001 + self.purchase_orders.len()),
+            // Suppliers aren't modelled yet; a handful of ids stand in for them
+            supplier_id: supplier_id(self.rng.below(6)),
+            status,
+            expected_date: None,
+            total_amount: Decimal::ZERO,
+            currency: None,
+            exchange_rate: None,
+            lines: Vec::new(),
+            created_by: self.created_by,
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    fn push_purchase_order(&mut self, mut po: PurchaseOrder) {
+        po.total_amount = po.lines.iter().map(|line| line.line_total).sum();
+        self.purchase_orders.push(po);
+    }
+
+    fn sales_order(
+        &mut self,
+        status: SalesOrderStatus,
+        fulfillment_location_id: Option<Uuid>,
+        at: DateTime<Utc>,
+    ) -> SalesOrder {
+        SalesOrder {
+            id: Uuid::new_v4(),
+            so_number: format!("SO-{}", 100001 + self.sales_orders.len()),
+            customer_id: Some(customer_id(self.rng.below(40))),
+            status,
+            total_amount: Decimal::ZERO,
+            currency: None,
+            exchange_rate: None,
+            fulfillment_location_id,
+            lines: Vec::new(),
+            cancellation_reason: None,
+            cancelled_at: None,
+            created_by: self.created_by,
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    fn sales_order_line(&self, order: &SalesOrder, item: &Item, qty: Decimal) -> SalesOrderLine {
+        let unit_price = item.sale_price.unwrap_or(item.cost_price);
+        SalesOrderLine {
+            id: Uuid::new_v4(),
+            so_id: order.id,
+            item_id: item.id,
+            qty,
+            qty_shipped: Decimal::ZERO,
+            unit_price,
+            tax: round_total(qty * unit_price * TAX_RATE),
+            reserved: false,
+            created_at: order.created_at,
+            updated_at: order.created_at,
+        }
+    }
+
+    /// Keep orders that ended up with lines; a shipped order whose picks all
+    /// came up empty is dropped
+    fn push_sales_order(&mut self, mut order: SalesOrder) {
+        if order.lines.is_empty() {
+            return;
+        }
+        order.total_amount = order.lines.iter().map(|line| line.line_total()).sum();
+        self.sales_orders.push(order);
+    }
+
+    fn on_hand(&self, item_id: Uuid, location_id: Uuid) -> Decimal {
+        self.levels
+            .get(&(item_id, location_id))
+            .map_or(Decimal::ZERO, |level| level.quantity_on_hand)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn move_stock(
+        &mut self,
+        item_id: Uuid,
+        location_id: Uuid,
+        movement_type: MovementType,
+        quantity: Decimal,
+        reference_type: ReferenceType,
+        reference_id: Option<Uuid>,
+        reason: Option<String>,
+        at: DateTime<Utc>,
+    ) {
+        let movement = StockMovement {
+            id: Uuid::new_v4(),
+            item_id,
+            location_id,
+            movement_type,
+            quantity,
+            reference_type,
+            reference_id,
+            reason,
+            created_at: at,
+            created_by: Some(self.created_by),
+            stock_status: StockStatus::Available,
+        };
+        let level = self
+            .levels
+            .entry((item_id, location_id))
+            .or_insert_with(|| StockLevel::new(item_id, location_id));
+        level.quantity_on_hand += quantity;
+        level.last_movement_id = Some(movement.id);
+        level.updated_at = at;
+        self.movements.push(movement);
+    }
+}
+
+fn purchase_order_line(po: &PurchaseOrder, item: &Item, qty: Decimal) -> PurchaseOrderLine {
+    PurchaseOrderLine {
+        id: Uuid::new_v4(),
+        po_id: po.id,
+        item_id: item.id,
+        qty_ordered: qty,
+        qty_received: Decimal::ZERO,
+        unit_cost: item.cost_price,
+        line_total: extend(qty, item.cost_price),
+        expected_date: po.expected_date,
+        promised_date: None,
+        promised_at: None,
+    }
+}
+
+/// Stable stand-in ids, the same in every sandbox, so the supplier and customer
+/// filters on the order listings have something to match
+fn supplier_id(index: u64) -> Uuid {
+    Uuid::from_u128(0x5a4d_b0c5_0000_4000_8000_0000_0000_0000 | (index as u128 + 1))
+}
+
+fn customer_id(index: u64) -> Uuid {
+    Uuid::from_u128(0x5a4d_b0c5_0000_4000_8000_0001_0000_0000 | (index as u128 + 1))
+}
+
+/// SplitMix64: small, fast and the same on every platform, which is all the
+/// seeding needs
+struct SeedRng(u64);
+
+impl SeedRng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`
+    fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            return 0;
+        }
+        self.next_u64() % n
+    }
+
+    /// Uniform in `lo..=hi`
+    fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        lo + self.below(hi.saturating_sub(lo) + 1)
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    fn pick<'a, T>(&mut self, values: &'a [T]) -> &'a T {
+        &values[self.below(values.len() as u64) as usize]
+    }
+
+    /// An index in `0..n` favouring the low end, so a few items sell far more
+    /// than the rest and slow movers show up in the reports
+    fn skewed(&mut self, n: usize) -> usize {
+        let r = self.below(1 << 20) as f64 / (1 << 20) as f64;
+        ((r * r * n as f64) as usize).min(n - 1)
+    }
+}
+
+/// The subscription a sandbox starts with: every event, delivered to the
+/// built-in echo receiver at `echo_url`
+pub fn echo_webhook(echo_url: String, created_by: Uuid) -> Result<Webhook, DomainError> {
+    use crate::domain::entities::webhook::WebhookEventType;
+
+    Webhook::new(
+        echo_url,
+        format!("whsec_{}", Uuid::new_v4().simple()),
+        vec![
+            WebhookEventType::StockMovement,
+            WebhookEventType::PurchaseOrderCreated,
+            WebhookEventType::PurchaseOrderUpdated,
+            WebhookEventType::SalesOrderCreated,
+            WebhookEventType::SalesOrderUpdated,
+            WebhookEventType::ItemCreated,
+            WebhookEventType::ItemUpdated,
+            WebhookEventType::LowStockAlert,
+        ],
+        created_by,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset(profile: &str, seed: u64) -> SeedDataset {
+        SeedDataset::generate(
+            &SeedProfile::named(profile).unwrap(),
+            seed,
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn test_profiles_and_overrides() {
+        assert!(SeedProfile::named("huge").is_err());
+        let profile = SeedProfile::named("demo")
+            .unwrap()
+            .with_overrides(&SeedOverrides {
+                items: Some(40),
+                history_days: Some(0),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!((profile.items, profile.history_days), (40, 0));
+        assert!(SeedProfile::named("demo")
+            .unwrap()
+            .with_overrides(&SeedOverrides {
+                items: Some(MAX_ITEMS + 1),
+                ..Default::default()
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_same_seed_same_catalogue() {
+        let first = dataset("minimal", 7);
+        let second = dataset("minimal", 7);
+        let skus = |d: &SeedDataset| {
+            d.items
+                .iter()
+                .map(|item| (item.sku.clone(), item.name.clone(), item.barcode.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(skus(&first), skus(&second));
+        assert_eq!(first.sales_orders.len(), second.sales_orders.len());
+        assert_ne!(skus(&first), skus(&dataset("minimal", 8)));
+        assert_eq!(first.items.len(), 25);
+        assert_eq!(first.locations[0].code.as_deref(), Some("WH-001"));
+    }
+
+    #[test]
+    fn test_stock_levels_match_movements() {
+        let data = dataset("demo", DEFAULT_SEED);
+        let mut totals: HashMap<(Uuid, Uuid), Decimal> = HashMap::new();
+        for movement in &data.movements {
+            *totals
+                .entry((movement.item_id, movement.location_id))
+                .or_default() += movement.quantity;
+        }
+        assert_eq!(totals.len(), data.stock_levels.len());
+        for level in &data.stock_levels {
+            assert_eq!(
+                totals[&(level.item_id, level.location_id)],
+                level.quantity_on_hand
+            );
+            assert!(level.quantity_on_hand >= Decimal::ZERO);
+            assert!(level.quantity_reserved <= level.quantity_on_hand);
+        }
+
+        let open = |status: &str| {
+            data.sales_orders
+                .iter()
+                .filter(|order| order.status.as_str() == status)
+                .count()
+        };
+        assert!(open("SHIPPED") > 100);
+        assert_eq!(open("DRAFT") + open("CONFIRMED"), 30);
+        assert_eq!(
+            data.purchase_orders
+                .iter()
+                .filter(|po| po.status != PurchaseOrderStatus::Received)
+                .count(),
+            12
+        );
+        for po in &data.purchase_orders {
+            assert!(po
+                .lines
+                .iter()
+                .all(|line| line.qty_received <= line.qty_ordered));
+        }
+    }
+
+    #[test]
+    fn test_load_dataset_streams_movements_that_never_overdraw() {
+        let profile = LoadProfile {
+            locations: 3,
+            items: 40,
+            movements: 1000,
+            history_days: 30,
+        };
+        assert!(LoadProfile {
+            items: 0,
+            ..profile.clone()
+        }
+        .validate()
+        .is_err());
+        profile.validate().unwrap();
+
+        let mut data =
+            LoadDataset::generate(&profile, 7, Uuid::new_v4(), Uuid::new_v4(), Utc::now());
+        assert_eq!((data.locations.len(), data.items.len()), (3, 40));
+        let mut movements = Vec::new();
+        loop {
+            let batch = data.next_movements(128);
+            if batch.is_empty() {
+                break;
+            }
+            assert!(batch.len() <= 128);
+            movements.extend(batch);
+        }
+        assert_eq!(movements.len(), 1000);
+        assert!(movements
+            .windows(2)
+            .all(|pair| pair[0].created_at <= pair[1].created_at));
+
+        let mut totals: HashMap<(Uuid, Uuid), Decimal> = HashMap::new();
+        for movement in &movements {
+            let total = totals
+                .entry((movement.item_id, movement.location_id))
+                .or_default();
+            *total += movement.quantity;
+            assert!(*total >= Decimal::ZERO);
+        }
+        let levels = data.stock_levels();
+        assert_eq!(levels.len(), totals.len());
+        for level in levels {
+            assert_eq!(
+                totals[&(level.item_id, level.location_id)],
+                level.quantity_on_hand
+            );
+        }
+    }
+}
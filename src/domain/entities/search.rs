@@ -3,6 +3,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Job that drops a tenant's search documents and indexes its source tables again
+pub const REBUILD_SEARCH_INDEX_JOB_TYPE: &str = "rebuild_search_index";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchIndex {
     pub id: Uuid,
@@ -45,19 +48,66 @@ pub struct SearchQuery {
     pub offset: Option<i64>,
 }
 
+/// Autocomplete request: `text` is what the user has typed so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestQuery {
+    pub text: String,
+    pub entity_types: Option<Vec<String>>,
+    pub limit: i64,
+}
+
+/// An indexed entity whose names or codes resemble the typed text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    /// The entity's name, or `item @ location` for a stock level
+    pub text: String,
+    /// Trigram word similarity to the typed text, 0 to 1
+    pub similarity: f32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SearchIndexStatus {
+    /// Serving searches with nothing queued
+    Available,
+    /// Serving searches while writes are still being applied
+    Indexing,
+    Unavailable,
+}
+
+/// How the search backend is doing, for the current tenant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchIndexHealth {
+    /// `postgres` or `meilisearch`
+    pub backend: String,
+    pub status: SearchIndexStatus,
+    /// Documents the backend serves; unknown when it can't be reached
+    pub document_count: Option<i64>,
+    /// Documents the item, location and stock tables call for
+    pub expected_document_count: i64,
+    /// Of those, documents missing from the index or older than their source
+    pub stale_document_count: i64,
+    /// Writes the backend has accepted but not yet applied
+    pub pending_tasks: Option<i64>,
+    pub last_indexed_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
 impl SearchIndex {
     pub fn new(request: SearchIndexRequest) -> Result<Self, DomainError> {
         let now = Utc::now();
 
         if request.entity_type.trim().is_empty() {
             return Err(DomainError::ValidationError(
-                "Entity type cannot be empty".to_string(),
+                "Entity type cannot be empty".into(),
             ));
         }
 
         if request.searchable_content.trim().is_empty() {
             return Err(DomainError::ValidationError(
-                "Searchable content cannot be empty".to_string(),
+                "Searchable content cannot be empty".into(),
             ));
         }
 
@@ -79,7 +129,7 @@ impl SearchIndex {
     ) -> Result<(), DomainError> {
         if content.trim().is_empty() {
             return Err(DomainError::ValidationError(
-                "Searchable content cannot be empty".to_string(),
+                "Searchable content cannot be empty".into(),
             ));
         }
 
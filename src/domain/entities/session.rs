@@ -0,0 +1,101 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A login: the token issued to one device. The token names the session in
+/// its `sid` claim and is refused once the session is revoked or expires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub tenant_id: Uuid,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Set when an admin logged in without two factors under a policy that
+    /// requires them; the session may only be used to enroll
+    pub two_factor_enrollment_required: bool,
+}
+
+impl Session {
+    pub fn new(
+        user_id: Uuid,
+        tenant_id: Uuid,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+        ttl: Duration,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            tenant_id,
+            ip_address,
+            user_agent,
+            issued_at: now,
+            expires_at: now + ttl,
+            revoked_at: None,
+            two_factor_enrollment_required: false,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && Utc::now() < self.expires_at
+    }
+}
+
+/// A session as listed to its user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub id: Uuid,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Whether this is the session the request was made with
+    pub current: bool,
+}
+
+impl SessionSummary {
+    pub fn new(session: Session, current_session_id: Option<Uuid>) -> Self {
+        Self {
+            current: current_session_id == Some(session.id),
+            id: session.id,
+            ip_address: session.ip_address,
+            user_agent: session.user_agent,
+            issued_at: session.issued_at,
+            expires_at: session.expires_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_is_inactive_once_revoked_or_expired() {
+        let mut session = Session::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Some("203.0.113.7".to_string()),
+            None,
+            Duration::hours(1),
+        );
+        assert!(session.is_active());
+
+        session.revoked_at = Some(Utc::now());
+        assert!(!session.is_active());
+
+        let expired = Session::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            None,
+            None,
+            Duration::seconds(-1),
+        );
+        assert!(!expired.is_active());
+    }
+}
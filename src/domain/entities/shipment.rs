@@ -0,0 +1,383 @@
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ShipmentSourceType {
+    SalesOrder,
+    Transfer,
+}
+
+impl ShipmentSourceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShipmentSourceType::SalesOrder => "SALES_ORDER",
+            ShipmentSourceType::Transfer => "TRANSFER",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "SALES_ORDER" => Ok(ShipmentSourceType::SalesOrder),
+            "TRANSFER" => Ok(ShipmentSourceType::Transfer),
+            _ => Err(DomainError::ValidationError(
+                format!(
+                    "Invalid shipment source type: {}. Must be one of: SALES_ORDER, TRANSFER",
+                    s
+                )
+                .into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShipmentPackage {
+    pub id: Uuid,
+    pub shipment_id: Uuid,
+    pub weight_kg: f64,
+    pub length_cm: Option<f64>,
+    pub width_cm: Option<f64>,
+    pub height_cm: Option<f64>,
+    pub tracking_number: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ShipmentPackage {
+    pub fn new(
+        shipment_id: Uuid,
+        weight_kg: f64,
+        length_cm: Option<f64>,
+        width_cm: Option<f64>,
+        height_cm: Option<f64>,
+        tracking_number: Option<String>,
+    ) -> Result<Self, DomainError> {
+        if weight_kg < 0.0 {
+            return Err(DomainError::ValidationError(
+                "Package weight cannot be negative".into(),
+            ));
+        }
+
+        if [length_cm, width_cm, height_cm]
+            .iter()
+            .flatten()
+            .any(|d| *d <= 0.0)
+        {
+            return Err(DomainError::ValidationError(
+                "Package dimensions must be positive".into(),
+            ));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            shipment_id,
+            weight_kg,
+            length_cm,
+            width_cm,
+            height_cm,
+            tracking_number,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// A labelled carton on a shipment, identified by its SSCC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShipmentCarton {
+    pub id: Uuid,
+    pub shipment_id: Uuid,
+    /// The package the carton label goes on, when packages were recorded
+    pub package_id: Option<Uuid>,
+    pub sscc: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ShipmentCarton {
+    pub fn new(shipment_id: Uuid, package_id: Option<Uuid>, sscc: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            shipment_id,
+            package_id,
+            sscc,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Shipment {
+    pub id: Uuid,
+    pub shipment_number: String,
+    pub source_type: ShipmentSourceType,
+    pub source_id: Uuid,
+    pub carrier: Option<String>,
+    pub tracking_number: Option<String>,
+    pub packages: Vec<ShipmentPackage>,
+    #[serde(default)]
+    pub cartons: Vec<ShipmentCarton>,
+    pub total_weight_kg: f64,
+    pub shipped_at: DateTime<Utc>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Shipment {
+    pub fn new(
+        shipment_number: String,
+        source_type: ShipmentSourceType,
+        source_id: Uuid,
+        carrier: Option<String>,
+        tracking_number: Option<String>,
+        shipped_at: Option<DateTime<Utc>>,
+        created_by: Uuid,
+    ) -> Result<Self, DomainError> {
+        if shipment_number.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Shipment number cannot be empty".into(),
+            ));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            shipment_number,
+            source_type,
+            source_id,
+            carrier: normalize(carrier),
+            tracking_number: normalize(tracking_number),
+            packages: Vec::new(),
+            cartons: Vec::new(),
+            total_weight_kg: 0.0,
+            shipped_at: shipped_at.unwrap_or(now),
+            created_by,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn add_package(&mut self, request: ShipmentPackageRequest) -> Result<(), DomainError> {
+        let package = ShipmentPackage::new(
+            self.id,
+            request.weight_kg,
+            request.length_cm,
+            request.width_cm,
+            request.height_cm,
+            normalize(request.tracking_number),
+        )?;
+
+        self.total_weight_kg += package.weight_kg;
+        self.packages.push(package);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// The packages to open cartons for: those requested, `count` cartons
+    /// without a package, or by default each package that has no carton yet
+    /// (a single carton when no packages were recorded)
+    pub fn plan_cartons(
+        &self,
+        request: &CreateShipmentCartonsRequest,
+    ) -> Result<Vec<Option<Uuid>>, DomainError> {
+        let has_carton = |package_id: Uuid| {
+            self.cartons
+                .iter()
+                .any(|c| c.package_id == Some(package_id))
+        };
+
+        if let Some(package_ids) = &request.package_ids {
+            if request.count.is_some() {
+                return Err(DomainError::ValidationError(
+                    "Specify either package_ids or count, not both".into(),
+                ));
+            }
+            let mut planned = Vec::with_capacity(package_ids.len());
+            for package_id in package_ids {
+                if !self.packages.iter().any(|p| p.id == *package_id) {
+                    return Err(DomainError::ValidationError(
+                        format!(
+                            "Package {} is not part of shipment {}",
+                            package_id, self.shipment_number
+                        )
+                        .into(),
+                    ));
+                }
+                if has_carton(*package_id) || planned.contains(&Some(*package_id)) {
+                    return Err(DomainError::Conflict(
+                        format!("Package {} already has a carton", package_id).into(),
+                    ));
+                }
+                planned.push(Some(*package_id));
+            }
+            return Ok(planned);
+        }
+
+        if let Some(count) = request.count {
+            if !(1..=MAX_CARTONS_PER_REQUEST).contains(&count) {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.field_between",
+                    &[
+                        ("field", &"count"),
+                        ("min", &1),
+                        ("max", &MAX_CARTONS_PER_REQUEST),
+                    ],
+                )));
+            }
+            return Ok(vec![None; count as usize]);
+        }
+
+        let planned: Vec<Option<Uuid>> = self
+            .packages
+            .iter()
+            .filter(|p| !has_carton(p.id))
+            .map(|p| Some(p.id))
+            .collect();
+        if !planned.is_empty() {
+            return Ok(planned);
+        }
+        if self.packages.is_empty() && self.cartons.is_empty() {
+            return Ok(vec![None]);
+        }
+        Err(DomainError::Conflict(
+            format!(
+                "Every package on shipment {} already has a carton",
+                self.shipment_number
+            )
+            .into(),
+        ))
+    }
+
+    pub fn update_tracking(&mut self, carrier: Option<String>, tracking_number: Option<String>) {
+        if carrier.is_some() {
+            self.carrier = normalize(carrier);
+        }
+        if tracking_number.is_some() {
+            self.tracking_number = normalize(tracking_number);
+        }
+        self.updated_at = Utc::now();
+    }
+}
+
+fn normalize(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Carrier and package details captured when an order or transfer ships
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShipmentDetails {
+    pub carrier: Option<String>,
+    pub tracking_number: Option<String>,
+    pub shipped_at: Option<DateTime<Utc>>,
+    pub packages: Option<Vec<ShipmentPackageRequest>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShipmentPackageRequest {
+    pub weight_kg: f64,
+    pub length_cm: Option<f64>,
+    pub width_cm: Option<f64>,
+    pub height_cm: Option<f64>,
+    pub tracking_number: Option<String>,
+}
+
+const MAX_CARTONS_PER_REQUEST: u32 = 500;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateShipmentCartonsRequest {
+    pub package_ids: Option<Vec<Uuid>>,
+    pub count: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateShipmentTrackingRequest {
+    pub carrier: Option<String>,
+    pub tracking_number: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_package_accumulates_weight() {
+        let mut shipment = Shipment::new(
+            "SH-TEST".to_string(),
+            ShipmentSourceType::SalesOrder,
+            Uuid::new_v4(),
+            Some(" UPS ".to_string()),
+            Some("1Z999".to_string()),
+            None,
+            Uuid::new_v4(),
+        )
+        .unwrap();
+
+        for weight_kg in [1.5, 2.25] {
+            shipment
+                .add_package(ShipmentPackageRequest {
+                    weight_kg,
+                    length_cm: Some(30.0),
+                    width_cm: Some(20.0),
+                    height_cm: Some(10.0),
+                    tracking_number: None,
+                })
+                .unwrap();
+        }
+
+        assert_eq!(shipment.carrier.as_deref(), Some("UPS"));
+        assert_eq!(shipment.packages.len(), 2);
+        assert!((shipment.total_weight_kg - 3.75).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_plan_cartons_covers_packages_without_one() {
+        let mut shipment = Shipment::new(
+            "SH-CARTONS".to_string(),
+            ShipmentSourceType::Transfer,
+            Uuid::new_v4(),
+            None,
+            None,
+            None,
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        let default = CreateShipmentCartonsRequest::default();
+        assert_eq!(shipment.plan_cartons(&default).unwrap(), vec![None]);
+
+        for weight_kg in [1.0, 2.0] {
+            shipment
+                .add_package(ShipmentPackageRequest {
+                    weight_kg,
+                    length_cm: None,
+                    width_cm: None,
+                    height_cm: None,
+                    tracking_number: None,
+                })
+                .unwrap();
+        }
+        let first = shipment.packages[0].id;
+        let second = shipment.packages[1].id;
+        shipment.cartons.push(ShipmentCarton::new(
+            shipment.id,
+            Some(first),
+            "0".repeat(18),
+        ));
+
+        assert_eq!(shipment.plan_cartons(&default).unwrap(), vec![Some(second)]);
+        let repeat = CreateShipmentCartonsRequest {
+            package_ids: Some(vec![first]),
+            count: None,
+        };
+        assert!(shipment.plan_cartons(&repeat).is_err());
+    }
+
+    #[test]
+    fn test_package_rejects_invalid_dimensions() {
+        assert!(ShipmentPackage::new(Uuid::new_v4(), 1.0, Some(0.0), None, None, None).is_err());
+        assert!(ShipmentPackage::new(Uuid::new_v4(), -1.0, None, None, None, None).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::shared::error::DomainError;
+
+/// Entities whose status transitions are recorded
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StatusHistoryEntity {
+    PurchaseOrder,
+    SalesOrder,
+    Transfer,
+    Return,
+}
+
+impl StatusHistoryEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusHistoryEntity::PurchaseOrder => "PURCHASE_ORDER",
+            StatusHistoryEntity::SalesOrder => "SALES_ORDER",
+            StatusHistoryEntity::Transfer => "TRANSFER",
+            StatusHistoryEntity::Return => "RETURN",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s {
+            "PURCHASE_ORDER" => Ok(StatusHistoryEntity::PurchaseOrder),
+            "SALES_ORDER" => Ok(StatusHistoryEntity::SalesOrder),
+            "TRANSFER" => Ok(StatusHistoryEntity::Transfer),
+            "RETURN" => Ok(StatusHistoryEntity::Return),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid status history entity: {}", s).into(),
+            )),
+        }
+    }
+
+    /// Table holding the entities
+    pub fn table(&self) -> &'static str {
+        match self {
+            StatusHistoryEntity::PurchaseOrder => "purchase_orders",
+            StatusHistoryEntity::SalesOrder => "sales_orders",
+            StatusHistoryEntity::Transfer => "transfers",
+            StatusHistoryEntity::Return => "returns",
+        }
+    }
+
+    /// How the entity is named in messages
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatusHistoryEntity::PurchaseOrder => "Purchase order",
+            StatusHistoryEntity::SalesOrder => "Sales order",
+            StatusHistoryEntity::Transfer => "Transfer",
+            StatusHistoryEntity::Return => "Return",
+        }
+    }
+}
+
+/// One status transition, written by the database whenever the entity's status
+/// changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub id: Uuid,
+    pub entity_type: StatusHistoryEntity,
+    pub entity_id: Uuid,
+    /// `None` for the status the entity was created with
+    pub old_status: Option<String>,
+    pub new_status: String,
+    /// The logged-in user who made the change; `None` for changes made by
+    /// background jobs or callers without a login
+    pub actor_id: Option<Uuid>,
+    /// Context kept with the change, e.g. a sales order's cancellation reason
+    pub metadata: serde_json::Value,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusHistoryResponse {
+    pub entity_type: StatusHistoryEntity,
+    pub entity_id: Uuid,
+    /// Oldest first
+    pub changes: Vec<StatusChange>,
+}
@@ -0,0 +1,111 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::domain::entities::time_zone::ReportBound;
+use crate::shared::error::DomainError;
+use chrono_tz::Tz;
+use rust_decimal::Decimal;
+
+/// On-hand quantity for one item at one location, either as captured by a
+/// snapshot or as the net of a range of movements
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StockQuantity {
+    pub item_id: Uuid,
+    pub location_id: Uuid,
+    pub quantity_on_hand: Decimal,
+}
+
+/// Narrows historical lookups to one item and/or location
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StockQuantityFilter {
+    pub item_id: Option<Uuid>,
+    pub location_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoricalStockLevels {
+    /// Levels reflect every movement strictly before this instant
+    pub as_of: DateTime<Utc>,
+    /// Snapshot the levels were rebuilt from; `None` means replayed from the first movement
+    pub snapshot_taken_at: Option<DateTime<Utc>>,
+    pub levels: Vec<StockQuantity>,
+}
+
+impl HistoricalStockLevels {
+    /// End of `date` in `time_zone`, the instant "on hand on date Y" refers to
+    pub fn end_of_day(date: NaiveDate, time_zone: Tz) -> Result<DateTime<Utc>, DomainError> {
+        ReportBound::Date(date).end(time_zone)
+    }
+
+    /// Apply the net movements since the snapshot to the snapshot's quantities
+    pub fn reconstruct(
+        as_of: DateTime<Utc>,
+        snapshot_taken_at: Option<DateTime<Utc>>,
+        snapshot: Vec<StockQuantity>,
+        movements_since: Vec<StockQuantity>,
+    ) -> Self {
+        let mut levels: BTreeMap<(Uuid, Uuid), Decimal> = BTreeMap::new();
+        for quantity in snapshot.into_iter().chain(movements_since) {
+            *levels
+                .entry((quantity.item_id, quantity.location_id))
+                .or_default() += quantity.quantity_on_hand;
+        }
+
+        Self {
+            as_of,
+            snapshot_taken_at,
+            levels: levels
+                .into_iter()
+                .map(|((item_id, location_id), quantity_on_hand)| StockQuantity {
+                    item_id,
+                    location_id,
+                    quantity_on_hand,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconstruct_applies_movements_to_snapshot() {
+        let (item, location, other_location) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let quantity = |location_id, quantity_on_hand| StockQuantity {
+            item_id: item,
+            location_id,
+            quantity_on_hand,
+        };
+
+        let leap_day = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(
+            HistoricalStockLevels::end_of_day(leap_day, Tz::America__Phoenix)
+                .unwrap()
+                .to_rfc3339(),
+            "2024-03-01T07:00:00+00:00"
+        );
+        let as_of = HistoricalStockLevels::end_of_day(leap_day, Tz::UTC).unwrap();
+        assert_eq!(as_of.to_rfc3339(), "2024-03-01T00:00:00+00:00");
+
+        let levels = HistoricalStockLevels::reconstruct(
+            as_of,
+            None,
+            vec![quantity(location, Decimal::from(10))],
+            vec![
+                quantity(location, Decimal::from(-4)),
+                quantity(other_location, Decimal::from(7)),
+            ],
+        );
+        assert_eq!(levels.levels.len(), 2);
+        assert!(levels
+            .levels
+            .contains(&quantity(location, Decimal::from(6))));
+        assert!(levels
+            .levels
+            .contains(&quantity(other_location, Decimal::from(7))));
+    }
+}
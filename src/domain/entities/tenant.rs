@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::entities::sandbox_seed::{SeedOverrides, SeedSummary};
 use crate::shared::error::DomainError;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -38,7 +39,7 @@ impl TenantTier {
             _ => Err(DomainError::ValidationError(format!(
                 "Invalid tenant tier: {}. Must be one of: FREE, DEVELOPER, STARTUP, GROWTH, SCALE, ENTERPRISE",
                 s
-            ))),
+            ).into())),
         }
     }
 
@@ -73,10 +74,13 @@ impl TenantType {
         match s.to_uppercase().as_str() {
             "PRODUCTION" => Ok(TenantType::Production),
             "SANDBOX" => Ok(TenantType::Sandbox),
-            _ => Err(DomainError::ValidationError(format!(
-                "Invalid tenant type: {}. Must be one of: PRODUCTION, SANDBOX",
-                s
-            ))),
+            _ => Err(DomainError::ValidationError(
+                format!(
+                    "Invalid tenant type: {}. Must be one of: PRODUCTION, SANDBOX",
+                    s
+                )
+                .into(),
+            )),
         }
     }
 }
@@ -109,7 +113,7 @@ impl TenantStatus {
             _ => Err(DomainError::ValidationError(format!(
                 "Invalid tenant status: {}. Must be one of: PROVISIONING, ACTIVE, SUSPENDED, DELETING",
                 s
-            ))),
+            ).into())),
         }
     }
 }
@@ -138,13 +142,13 @@ impl Tenant {
     ) -> Result<Self, DomainError> {
         if name.trim().is_empty() {
             return Err(DomainError::ValidationError(
-                "Tenant name cannot be empty".to_string(),
+                "Tenant name cannot be empty".into(),
             ));
         }
 
         if database_schema.trim().is_empty() {
             return Err(DomainError::ValidationError(
-                "Database schema cannot be empty".to_string(),
+                "Database schema cannot be empty".into(),
             ));
         }
 
@@ -207,13 +211,38 @@ impl Tenant {
         self.status = TenantStatus::Deleting;
         self.updated_at = Utc::now();
     }
+
+    /// Turn a sandbox into a production tenant that no longer expires
+    pub fn promote_to_production(&mut self, name: Option<String>) -> Result<(), DomainError> {
+        if self.tenant_type != TenantType::Sandbox {
+            return Err(DomainError::BusinessLogicError(
+                format!("Tenant {} is not a sandbox", self.id).into(),
+            ));
+        }
+        if let Some(name) = name {
+            if name.trim().is_empty() {
+                return Err(DomainError::ValidationError(
+                    "Tenant name cannot be empty".into(),
+                ));
+            }
+            self.name = name;
+        }
+        self.tenant_type = TenantType::Production;
+        self.status = TenantStatus::Active;
+        self.expires_at = None;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
 }
 
 // Request/Response DTOs for API
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CreateSandboxTenantRequest {
-    // No fields needed for basic sandbox creation
+    /// Seed profile: minimal, demo or large; defaults to the configured one
+    pub profile: Option<String>,
+    #[serde(flatten)]
+    pub overrides: SeedOverrides,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -222,6 +251,16 @@ pub struct CreateSandboxTenantResponse {
     pub status: String,
     pub expires_at: DateTime<Utc>,
     pub message: String,
+    pub seeded: SeedSummary,
+    /// Where the seeded webhook delivers, and the secret it signs with
+    pub echo_url: String,
+    pub webhook_secret: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PromoteSandboxRequest {
+    /// Replaces the generated `sandbox-…` name
+    pub name: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
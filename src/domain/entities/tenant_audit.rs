@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::shared::error::DomainError;
+
+/// Administrative actions recorded against a tenant
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TenantAuditAction {
+    SandboxReset,
+    SandboxPromoted,
+}
+
+impl TenantAuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TenantAuditAction::SandboxReset => "SANDBOX_RESET",
+            TenantAuditAction::SandboxPromoted => "SANDBOX_PROMOTED",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s {
+            "SANDBOX_RESET" => Ok(TenantAuditAction::SandboxReset),
+            "SANDBOX_PROMOTED" => Ok(TenantAuditAction::SandboxPromoted),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid tenant audit action: {}", s).into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantAuditEntry {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub action: TenantAuditAction,
+    /// The logged-in user who took the action
+    pub actor_id: Uuid,
+    /// Action-specific context, e.g. the seed profile a reset replayed
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TenantAuditEntry {
+    pub fn new(
+        tenant_id: Uuid,
+        action: TenantAuditAction,
+        actor_id: Uuid,
+        details: serde_json::Value,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            tenant_id,
+            action,
+            actor_id,
+            details,
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Job that writes a zip of every snapshot table the tenant owns to blob storage
+pub const TENANT_SNAPSHOT_EXPORT_JOB_TYPE: &str = "tenant_snapshot_export";
+
+/// Job that restores an uploaded tenant snapshot into the tenant that queued it
+pub const TENANT_SNAPSHOT_IMPORT_JOB_TYPE: &str = "tenant_snapshot_import";
+
+/// Name of the archive a snapshot export job stores
+pub const TENANT_SNAPSHOT_FILE_NAME: &str = "tenant-snapshot.zip";
+
+/// Version of the archive layout; imports refuse archives written by a newer one
+pub const TENANT_SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Tables a snapshot carries, in an order that inserts rows after the rows they
+/// reference
+pub const SNAPSHOT_TABLES: &[&str] = &[
+    "products",
+    "items",
+    "locations",
+    "stock_movements",
+    "stock_levels",
+    "sales_orders",
+    "sales_order_lines",
+    "purchase_orders",
+    "purchase_order_lines",
+    "webhooks",
+    "entity_status_history",
+];
+
+/// One table of a snapshot. Rows are kept as the JSON text the database wrote,
+/// without the `tenant_id` column, so decimals keep their full precision.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotTable {
+    pub name: String,
+    pub rows: Vec<String>,
+}
+
+/// Everything a tenant owns in the snapshot tables
+#[derive(Debug, Clone, PartialEq)]
+pub struct TenantSnapshot {
+    pub source_tenant_id: Uuid,
+    pub exported_at: DateTime<Utc>,
+    pub tables: Vec<SnapshotTable>,
+}
+
+impl TenantSnapshot {
+    pub fn table(&self, name: &str) -> Option<&SnapshotTable> {
+        self.tables.iter().find(|table| table.name == name)
+    }
+
+    /// Rows per table, in snapshot order
+    pub fn counts(&self) -> Vec<SnapshotTableCount> {
+        self.tables
+            .iter()
+            .map(|table| SnapshotTableCount {
+                table: table.name.clone(),
+                rows: table.rows.len(),
+            })
+            .collect()
+    }
+}
+
+/// `manifest.json` at the root of a snapshot archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub format_version: u32,
+    pub source_tenant_id: Uuid,
+    pub exported_at: DateTime<Utc>,
+    pub tables: Vec<SnapshotManifestTable>,
+}
+
+/// A table's file in the archive: one JSON object per line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifestTable {
+    pub name: String,
+    pub file: String,
+    pub rows: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotTableCount {
+    pub table: String,
+    pub rows: usize,
+}
+
+/// What a snapshot import job carries: the uploaded archive stays in blob
+/// storage so any worker can pick the job up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantSnapshotImportPayload {
+    pub storage_key: String,
+    /// Stands in for users the snapshot's rows were created by that don't
+    /// exist in this environment
+    pub restored_by: Uuid,
+}
+
+/// Response from queueing a snapshot import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantSnapshotImportResponse {
+    pub job_id: String,
+    pub status: String,
+    pub source_tenant_id: Uuid,
+    pub exported_at: DateTime<Utc>,
+    pub tables: Vec<SnapshotTableCount>,
+    pub created_at: DateTime<Utc>,
+}
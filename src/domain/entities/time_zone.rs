@@ -0,0 +1,142 @@
+use crate::shared::error::DomainError;
+use chrono::{DateTime, LocalResult, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// Time zone of tenants that haven't chosen one
+pub const DEFAULT_TIME_ZONE: &str = "UTC";
+
+/// Look up an IANA zone name such as `America/Phoenix`
+pub fn parse_time_zone(name: &str) -> Result<Tz, DomainError> {
+    name.trim().parse::<Tz>().map_err(|_| {
+        DomainError::ValidationError(
+            format!(
+                "Unknown time zone '{}': expected an IANA name such as America/Los_Angeles",
+                name
+            )
+            .into(),
+        )
+    })
+}
+
+/// The instant `date` begins in `tz`. Where a DST change skips midnight the day
+/// starts when the clocks resume; where midnight repeats, at its first occurrence.
+pub fn start_of_day(tz: Tz, date: NaiveDate) -> DateTime<Utc> {
+    let mut local = date.and_time(NaiveTime::MIN);
+    loop {
+        match tz.from_local_datetime(&local) {
+            LocalResult::Single(start) | LocalResult::Ambiguous(start, _) => {
+                return start.with_timezone(&Utc)
+            }
+            LocalResult::None => local += chrono::Duration::minutes(15),
+        }
+    }
+}
+
+/// The calendar day `instant` falls on in `tz`
+pub fn local_date(tz: Tz, instant: DateTime<Utc>) -> NaiveDate {
+    instant.with_timezone(&tz).date_naive()
+}
+
+/// A report's date-range bound: an exact instant, or a calendar day read in the
+/// tenant's time zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ReportBound {
+    Instant(DateTime<Utc>),
+    Date(NaiveDate),
+}
+
+impl ReportBound {
+    /// Inclusive lower bound; a date counts from its start
+    pub fn start(self, tz: Tz) -> DateTime<Utc> {
+        match self {
+            ReportBound::Instant(instant) => instant,
+            ReportBound::Date(date) => start_of_day(tz, date),
+        }
+    }
+
+    /// Exclusive upper bound; a date includes the whole day
+    pub fn end(self, tz: Tz) -> Result<DateTime<Utc>, DomainError> {
+        match self {
+            ReportBound::Instant(instant) => Ok(instant),
+            ReportBound::Date(date) => date
+                .succ_opt()
+                .map(|next| start_of_day(tz, next))
+                .ok_or_else(|| {
+                    DomainError::ValidationError(format!("Invalid date: {}", date).into())
+                }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeZoneSettings {
+    pub timezone: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateTimeZoneSettingsRequest {
+    pub timezone: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_time_zone() {
+        assert_eq!(
+            parse_time_zone(" America/Phoenix ").unwrap(),
+            Tz::America__Phoenix
+        );
+        assert!(parse_time_zone("Mars/Olympus_Mons").is_err());
+    }
+
+    #[test]
+    fn test_days_follow_dst_changes() {
+        let tz = Tz::America__Los_Angeles;
+        // Clocks went forward on 2024-03-10, so that day is 23 hours long
+        assert_eq!(
+            start_of_day(tz, date("2024-03-10")).to_rfc3339(),
+            "2024-03-10T08:00:00+00:00"
+        );
+        assert_eq!(
+            start_of_day(tz, date("2024-03-11")).to_rfc3339(),
+            "2024-03-11T07:00:00+00:00"
+        );
+        assert_eq!(
+            local_date(tz, "2024-03-11T06:59:59Z".parse().unwrap()),
+            date("2024-03-10")
+        );
+    }
+
+    #[test]
+    fn test_skipped_midnight_starts_the_day_when_clocks_resume() {
+        // Santiago moved from 00:00 to 01:00 on 2024-09-08
+        assert_eq!(
+            start_of_day(Tz::America__Santiago, date("2024-09-08")).to_rfc3339(),
+            "2024-09-08T04:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_report_bounds() {
+        let tz = Tz::America__Phoenix;
+        let day = ReportBound::Date(date("2024-05-01"));
+        assert_eq!(day.start(tz).to_rfc3339(), "2024-05-01T07:00:00+00:00");
+        assert_eq!(
+            day.end(tz).unwrap().to_rfc3339(),
+            "2024-05-02T07:00:00+00:00"
+        );
+
+        let instant: ReportBound = serde_json::from_str("\"2024-05-01T12:00:00Z\"").unwrap();
+        assert_eq!(instant.start(tz).to_rfc3339(), "2024-05-01T12:00:00+00:00");
+        let parsed: ReportBound = serde_json::from_str("\"2024-05-01\"").unwrap();
+        assert_eq!(parsed, day);
+    }
+}
@@ -1,5 +1,8 @@
+use crate::domain::entities::inventory::{MovementType, ReferenceType, StockMovement, StockStatus};
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -7,6 +10,7 @@ use uuid::Uuid;
 pub enum TransferStatus {
     Draft,
     Open,
+    PartiallyShipped,
     InTransit,
     Received,
     Cancelled,
@@ -17,6 +21,7 @@ impl TransferStatus {
         match self {
             TransferStatus::Draft => "DRAFT",
             TransferStatus::Open => "OPEN",
+            TransferStatus::PartiallyShipped => "PARTIALLY_SHIPPED",
             TransferStatus::InTransit => "IN_TRANSIT",
             TransferStatus::Received => "RECEIVED",
             TransferStatus::Cancelled => "CANCELLED",
@@ -27,13 +32,13 @@ impl TransferStatus {
         match s {
             "DRAFT" => Ok(TransferStatus::Draft),
             "OPEN" => Ok(TransferStatus::Open),
+            "PARTIALLY_SHIPPED" => Ok(TransferStatus::PartiallyShipped),
             "IN_TRANSIT" => Ok(TransferStatus::InTransit),
             "RECEIVED" => Ok(TransferStatus::Received),
             "CANCELLED" => Ok(TransferStatus::Cancelled),
-            _ => Err(DomainError::ValidationError(format!(
-                "Invalid transfer status: {}",
-                s
-            ))),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid transfer status: {}", s).into(),
+            )),
         }
     }
 
@@ -44,7 +49,16 @@ impl TransferStatus {
             }
             TransferStatus::Open => matches!(
                 new_status,
-                TransferStatus::InTransit | TransferStatus::Cancelled
+                TransferStatus::PartiallyShipped
+                    | TransferStatus::InTransit
+                    | TransferStatus::Cancelled
+            ),
+            TransferStatus::PartiallyShipped => matches!(
+                new_status,
+                TransferStatus::PartiallyShipped
+                    | TransferStatus::InTransit
+                    | TransferStatus::Received
+                    | TransferStatus::Cancelled
             ),
             TransferStatus::InTransit => matches!(
                 new_status,
@@ -63,7 +77,7 @@ pub struct Transfer {
     pub from_location_id: Uuid,
     pub to_location_id: Uuid,
     pub status: TransferStatus,
-    pub total_quantity: i32,
+    pub total_quantity: Decimal,
     pub notes: Option<String>,
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
@@ -76,8 +90,13 @@ pub struct TransferLine {
     pub id: Uuid,
     pub transfer_id: Uuid,
     pub item_id: Uuid,
-    pub quantity: i32,
-    pub quantity_received: i32,
+    pub quantity: Decimal,
+    #[serde(default)]
+    pub quantity_shipped: Decimal,
+    pub quantity_received: Decimal,
+    /// Shipped but written off as never received when the transfer was closed
+    #[serde(default)]
+    pub quantity_short: Decimal,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -93,80 +112,35 @@ pub struct CreateTransferRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateTransferLineRequest {
     pub item_id: Uuid,
-    pub quantity: i32,
+    pub quantity: Decimal,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReceiveTransferRequest {
+    #[serde(default)]
     pub lines: Vec<ReceiveTransferLineRequest>,
+    /// Close the transfer after this receipt, writing off anything still in
+    /// transit as short; anything never shipped stays at the source
+    #[serde(default)]
+    pub close: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReceiveTransferLineRequest {
     pub transfer_line_id: Uuid,
-    pub quantity_received: i32,
+    pub quantity_received: Decimal,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ShipLineRequest {
-    pub so_line_id: Uuid,
-    pub qty_shipped: i32,
+pub struct ShipTransferLineRequest {
+    pub transfer_line_id: Uuid,
+    pub quantity: Decimal,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct StockMovement {
-    pub id: Uuid,
-    pub item_id: Uuid,
-    pub location_id: Uuid,
-    pub movement_type: MovementType,
-    pub quantity: i32,
-    pub reference_type: ReferenceType,
-    pub reference_id: Option<Uuid>,
-    pub reason: String,
-    pub created_at: DateTime<Utc>,
-    pub created_by: Uuid,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum MovementType {
-    Inbound,
-    Outbound,
-    Adjustment,
-    Transfer,
-    Initial,
-}
-
-impl MovementType {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            MovementType::Inbound => "inbound",
-            MovementType::Outbound => "outbound",
-            MovementType::Adjustment => "adjustment",
-            MovementType::Transfer => "transfer",
-            MovementType::Initial => "initial",
-        }
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum ReferenceType {
-    PurchaseOrder,
-    SalesOrder,
-    Transfer,
-    Adjustment,
-    Initial,
-}
-
-impl ReferenceType {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            ReferenceType::PurchaseOrder => "purchase_order",
-            ReferenceType::SalesOrder => "sales_order",
-            ReferenceType::Transfer => "transfer",
-            ReferenceType::Adjustment => "adjustment",
-            ReferenceType::Initial => "initial",
-        }
-    }
+pub struct ShipLineRequest {
+    pub so_line_id: Uuid,
+    pub qty_shipped: Decimal,
 }
 
 impl Transfer {
@@ -178,7 +152,7 @@ impl Transfer {
     ) -> Result<Self, DomainError> {
         if from_location_id == to_location_id {
             return Err(DomainError::ValidationError(
-                "From and to locations cannot be the same".to_string(),
+                "From and to locations cannot be the same".into(),
             ));
         }
 
@@ -188,7 +162,7 @@ impl Transfer {
             from_location_id,
             to_location_id,
             status: TransferStatus::Draft,
-            total_quantity: 0,
+            total_quantity: Decimal::ZERO,
             notes: None,
             created_by,
             created_at: Utc::now(),
@@ -198,10 +172,11 @@ impl Transfer {
     }
 
     pub fn add_line(&mut self, line: TransferLine) -> Result<(), DomainError> {
-        if line.quantity <= 0 {
-            return Err(DomainError::ValidationError(
-                "Transfer line quantity must be positive".to_string(),
-            ));
+        if line.quantity <= Decimal::ZERO {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.transfer_line_quantity_positive",
+                &[],
+            )));
         }
 
         self.lines.push(line);
@@ -212,10 +187,9 @@ impl Transfer {
 
     pub fn open(&mut self) -> Result<(), DomainError> {
         if !self.status.can_transition_to(&TransferStatus::Open) {
-            return Err(DomainError::ValidationError(format!(
-                "Cannot open transfer with status: {:?}",
-                self.status
-            )));
+            return Err(DomainError::ValidationError(
+                format!("Cannot open transfer with status: {:?}", self.status).into(),
+            ));
         }
 
         self.status = TransferStatus::Open;
@@ -223,116 +197,234 @@ impl Transfer {
         Ok(())
     }
 
-    pub fn ship(&mut self) -> Result<Vec<StockMovement>, DomainError> {
+    /// Ship some or all of the unshipped stock: it leaves the source location
+    /// and is held in transit against the destination until received. With no
+    /// lines given, everything still unshipped goes out.
+    pub fn ship(
+        &mut self,
+        shipped_lines: Vec<ShipTransferLineRequest>,
+        created_by: Uuid,
+    ) -> Result<Vec<StockMovement>, DomainError> {
         if !self.status.can_transition_to(&TransferStatus::InTransit) {
-            return Err(DomainError::ValidationError(format!(
-                "Cannot ship transfer with status: {:?}",
-                self.status
-            )));
+            return Err(DomainError::ValidationError(
+                format!("Cannot ship transfer with status: {:?}", self.status).into(),
+            ));
         }
 
         if self.lines.is_empty() {
             return Err(DomainError::ValidationError(
-                "Transfer must have lines to ship".to_string(),
+                "Transfer must have lines to ship".into(),
             ));
         }
 
+        let shipped_lines = if shipped_lines.is_empty() {
+            self.lines
+                .iter()
+                .filter(|line| line.quantity_unshipped() > Decimal::ZERO)
+                .map(|line| ShipTransferLineRequest {
+                    transfer_line_id: line.id,
+                    quantity: line.quantity_unshipped(),
+                })
+                .collect()
+        } else {
+            shipped_lines
+        };
+
         let mut stock_movements = Vec::new();
 
-        // Create outbound movements from source location
-        for line in &self.lines {
-            let movement = StockMovement::new(
+        for ship_request in shipped_lines {
+            let line = self
+                .lines
+                .iter_mut()
+                .find(|l| l.id == ship_request.transfer_line_id)
+                .ok_or_else(|| {
+                    DomainError::ValidationError(
+                        format!("Line {} not found", ship_request.transfer_line_id).into(),
+                    )
+                })?;
+
+            if ship_request.quantity <= Decimal::ZERO {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.shipped_quantity_positive",
+                    &[],
+                )));
+            }
+
+            if ship_request.quantity > line.quantity_unshipped() {
+                return Err(DomainError::ValidationError(
+                    format!(
+                        "Cannot ship {} units of line {}, only {} left to ship",
+                        ship_request.quantity,
+                        line.id,
+                        line.quantity_unshipped()
+                    )
+                    .into(),
+                ));
+            }
+
+            line.quantity_shipped += ship_request.quantity;
+            line.updated_at = Utc::now();
+
+            let reason = format!(
+                "Transfer outbound: {} units of item {}",
+                ship_request.quantity, line.item_id
+            );
+            stock_movements.push(StockMovement::new(
                 line.item_id,
                 self.from_location_id,
                 MovementType::Outbound,
-                -(line.quantity as i32), // Negative for outbound
+                -ship_request.quantity,
                 ReferenceType::Transfer,
                 Some(self.id),
-                format!(
-                    "Transfer outbound: {} units of item {}",
-                    line.quantity, line.item_id
-                ),
-                Utc::now(),
-                self.created_by,
+                Some(reason.clone()),
+                Some(created_by),
+            )?);
+            stock_movements.push(
+                StockMovement::new(
+                    line.item_id,
+                    self.to_location_id,
+                    MovementType::Inbound,
+                    ship_request.quantity,
+                    ReferenceType::Transfer,
+                    Some(self.id),
+                    Some(reason),
+                    Some(created_by),
+                )?
+                .with_stock_status(StockStatus::InTransit),
             );
-            stock_movements.push(movement);
         }
 
-        self.status = TransferStatus::InTransit;
+        self.status = if self.lines.iter().all(|l| l.quantity_unshipped().is_zero()) {
+            TransferStatus::InTransit
+        } else {
+            TransferStatus::PartiallyShipped
+        };
         self.updated_at = Utc::now();
         Ok(stock_movements)
     }
 
+    /// Receive stock at the destination, moving it out of transit into
+    /// available stock. Receipts add up across calls and may start before the
+    /// last shipment; closing the transfer writes off whatever is still in
+    /// transit as a short-receipt variance.
     pub fn receive(
         &mut self,
-        received_lines: Vec<ReceiveTransferLineRequest>,
+        request: ReceiveTransferRequest,
+        created_by: Uuid,
     ) -> Result<Vec<StockMovement>, DomainError> {
-        if self.status != TransferStatus::InTransit {
-            return Err(DomainError::ValidationError(format!(
-                "Cannot receive transfer with status: {:?}",
-                self.status
-            )));
+        if !matches!(
+            self.status,
+            TransferStatus::PartiallyShipped | TransferStatus::InTransit
+        ) {
+            return Err(DomainError::ValidationError(
+                format!("Cannot receive transfer with status: {:?}", self.status).into(),
+            ));
         }
 
-        if received_lines.is_empty() {
+        if request.lines.is_empty() && !request.close {
             return Err(DomainError::ValidationError(
-                "No lines specified for receiving".to_string(),
+                "No lines specified for receiving".into(),
             ));
         }
 
         let mut stock_movements = Vec::new();
 
-        for receive_request in received_lines {
+        for receive_request in request.lines {
             let line = self
                 .lines
                 .iter_mut()
                 .find(|l| l.id == receive_request.transfer_line_id)
                 .ok_or_else(|| {
-                    DomainError::ValidationError(format!(
-                        "Line {} not found",
-                        receive_request.transfer_line_id
-                    ))
+                    DomainError::ValidationError(
+                        format!("Line {} not found", receive_request.transfer_line_id).into(),
+                    )
                 })?;
 
-            if receive_request.quantity_received > line.quantity {
-                return Err(DomainError::ValidationError(format!(
-                    "Cannot receive {} units of line {}, only {} ordered",
-                    receive_request.quantity_received, line.id, line.quantity
+            if receive_request.quantity_received <= Decimal::ZERO {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.received_quantity_positive",
+                    &[],
                 )));
             }
 
-            if receive_request.quantity_received <= 0 {
+            if receive_request.quantity_received > line.quantity_in_transit() {
                 return Err(DomainError::ValidationError(
-                    "Received quantity must be positive".to_string(),
+                    format!(
+                        "Cannot receive {} units of line {}, only {} in transit",
+                        receive_request.quantity_received,
+                        line.id,
+                        line.quantity_in_transit()
+                    )
+                    .into(),
                 ));
             }
 
-            line.quantity_received = receive_request.quantity_received;
+            line.quantity_received += receive_request.quantity_received;
             line.updated_at = Utc::now();
 
-            // Create inbound movement to destination location
-            let movement = StockMovement::new(
+            let reason = format!(
+                "Transfer inbound: {} units of item {}",
+                receive_request.quantity_received, line.item_id
+            );
+            stock_movements.push(
+                StockMovement::new(
+                    line.item_id,
+                    self.to_location_id,
+                    MovementType::Transfer,
+                    -receive_request.quantity_received,
+                    ReferenceType::Transfer,
+                    Some(self.id),
+                    Some(reason.clone()),
+                    Some(created_by),
+                )?
+                .with_stock_status(StockStatus::InTransit),
+            );
+            stock_movements.push(StockMovement::new(
                 line.item_id,
                 self.to_location_id,
                 MovementType::Inbound,
                 receive_request.quantity_received,
                 ReferenceType::Transfer,
                 Some(self.id),
-                format!(
-                    "Transfer inbound: {} units of item {}",
-                    receive_request.quantity_received, line.item_id
-                ),
-                Utc::now(),
-                self.created_by,
-            );
-            stock_movements.push(movement);
+                Some(reason),
+                Some(created_by),
+            )?);
         }
 
-        // Check if all lines are fully received
-        let total_received: i32 = self.lines.iter().map(|l| l.quantity_received).sum();
-        let total_ordered: i32 = self.lines.iter().map(|l| l.quantity).sum();
+        if request.close {
+            for line in self.lines.iter_mut() {
+                let short = line.quantity_in_transit();
+                if short.is_zero() {
+                    continue;
+                }
+
+                line.quantity_short += short;
+                line.updated_at = Utc::now();
+
+                stock_movements.push(
+                    StockMovement::new(
+                        line.item_id,
+                        self.to_location_id,
+                        MovementType::Adjustment,
+                        -short,
+                        ReferenceType::Transfer,
+                        Some(self.id),
+                        Some(format!(
+                            "Transfer short receipt: {} units of item {} not received",
+                            short, line.item_id
+                        )),
+                        Some(created_by),
+                    )?
+                    .with_stock_status(StockStatus::InTransit),
+                );
+            }
+        }
 
-        if total_received >= total_ordered {
+        let fully_received = self
+            .lines
+            .iter()
+            .all(|l| l.quantity_unshipped().is_zero() && l.quantity_in_transit().is_zero());
+        if request.close || fully_received {
             self.status = TransferStatus::Received;
         }
 
@@ -342,11 +434,12 @@ impl Transfer {
 }
 
 impl TransferLine {
-    pub fn new(transfer_id: Uuid, item_id: Uuid, quantity: i32) -> Result<Self, DomainError> {
-        if quantity <= 0 {
-            return Err(DomainError::ValidationError(
-                "Transfer line quantity must be positive".to_string(),
-            ));
+    pub fn new(transfer_id: Uuid, item_id: Uuid, quantity: Decimal) -> Result<Self, DomainError> {
+        if quantity <= Decimal::ZERO {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.transfer_line_quantity_positive",
+                &[],
+            )));
         }
 
         Ok(Self {
@@ -354,36 +447,169 @@ impl TransferLine {
             transfer_id,
             item_id,
             quantity,
-            quantity_received: 0,
+            quantity_shipped: Decimal::ZERO,
+            quantity_received: Decimal::ZERO,
+            quantity_short: Decimal::ZERO,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         })
     }
+
+    /// Units not shipped yet
+    pub fn quantity_unshipped(&self) -> Decimal {
+        self.quantity - self.quantity_shipped
+    }
+
+    /// Shipped units neither received nor written off yet
+    pub fn quantity_in_transit(&self) -> Decimal {
+        self.quantity_shipped - self.quantity_received - self.quantity_short
+    }
 }
 
-impl StockMovement {
-    pub fn new(
-        item_id: Uuid,
-        location_id: Uuid,
-        movement_type: MovementType,
-        quantity: i32,
-        reference_type: ReferenceType,
-        reference_id: Option<Uuid>,
-        reason: String,
-        created_at: DateTime<Utc>,
-        created_by: Uuid,
-    ) -> Self {
-        Self {
-            id: Uuid::new_v4(),
-            item_id,
-            location_id,
-            movement_type,
-            quantity,
-            reference_type,
-            reference_id,
-            reason,
-            created_at,
-            created_by,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::inventory::StockLevel;
+
+    fn open_transfer(quantity: Decimal) -> Transfer {
+        let mut transfer = Transfer::new(
+            "TRF-1".to_string(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        let line = TransferLine::new(transfer.id, Uuid::new_v4(), quantity).unwrap();
+        transfer.add_line(line).unwrap();
+        transfer.open().unwrap();
+        transfer
+    }
+
+    fn shipped_transfer(quantity: Decimal) -> (Transfer, Uuid) {
+        let mut transfer = open_transfer(quantity);
+        transfer.ship(Vec::new(), Uuid::new_v4()).unwrap();
+        let line_id = transfer.lines[0].id;
+        (transfer, line_id)
+    }
+
+    #[test]
+    fn test_shipped_stock_is_in_transit_not_on_hand() {
+        let mut transfer = open_transfer(Decimal::from(5));
+        let item_id = transfer.lines[0].item_id;
+
+        let movements = transfer.ship(Vec::new(), Uuid::new_v4()).unwrap();
+        let mut destination = StockLevel::new(item_id, transfer.to_location_id);
+        for movement in movements
+            .iter()
+            .filter(|m| m.location_id == transfer.to_location_id)
+        {
+            destination.apply_movement(movement).unwrap();
         }
+
+        assert_eq!(transfer.status, TransferStatus::InTransit);
+        assert_eq!(destination.quantity_in_transit, Decimal::from(5));
+        assert_eq!(destination.quantity_on_hand, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_closing_a_short_receipt_writes_off_the_variance() {
+        let (mut transfer, line_id) = shipped_transfer(Decimal::from(10));
+
+        let movements = transfer
+            .receive(
+                ReceiveTransferRequest {
+                    lines: vec![ReceiveTransferLineRequest {
+                        transfer_line_id: line_id,
+                        quantity_received: Decimal::from(7),
+                    }],
+                    close: true,
+                },
+                Uuid::new_v4(),
+            )
+            .unwrap();
+
+        let line = &transfer.lines[0];
+        assert_eq!(line.quantity_received, Decimal::from(7));
+        assert_eq!(line.quantity_short, Decimal::from(3));
+        assert_eq!(line.quantity_in_transit(), Decimal::ZERO);
+        assert_eq!(transfer.status, TransferStatus::Received);
+
+        let out_of_transit: Decimal = movements
+            .iter()
+            .filter(|m| m.stock_status == StockStatus::InTransit)
+            .map(|m| m.quantity)
+            .sum();
+        let variance = movements
+            .iter()
+            .find(|m| m.movement_type == MovementType::Adjustment)
+            .unwrap();
+        assert_eq!(out_of_transit, Decimal::from(-10));
+        assert_eq!(variance.quantity, Decimal::from(-3));
+    }
+
+    #[test]
+    fn test_receipts_accumulate_up_to_the_quantity_in_transit() {
+        let (mut transfer, line_id) = shipped_transfer(Decimal::from(10));
+        let receipt = |quantity_received| ReceiveTransferRequest {
+            lines: vec![ReceiveTransferLineRequest {
+                transfer_line_id: line_id,
+                quantity_received,
+            }],
+            close: false,
+        };
+
+        transfer
+            .receive(receipt(Decimal::from(4)), Uuid::new_v4())
+            .unwrap();
+        assert_eq!(transfer.status, TransferStatus::InTransit);
+        assert!(transfer
+            .receive(receipt(Decimal::from(7)), Uuid::new_v4())
+            .is_err());
+
+        transfer
+            .receive(receipt(Decimal::from(6)), Uuid::new_v4())
+            .unwrap();
+        assert_eq!(transfer.lines[0].quantity_received, Decimal::from(10));
+        assert_eq!(transfer.status, TransferStatus::Received);
+    }
+
+    #[test]
+    fn test_transfer_ships_in_parts() {
+        let mut transfer = open_transfer(Decimal::from(10));
+        let line_id = transfer.lines[0].id;
+        let shipment = |quantity| {
+            vec![ShipTransferLineRequest {
+                transfer_line_id: line_id,
+                quantity,
+            }]
+        };
+
+        transfer
+            .ship(shipment(Decimal::from(4)), Uuid::new_v4())
+            .unwrap();
+        assert_eq!(transfer.status, TransferStatus::PartiallyShipped);
+        assert_eq!(transfer.lines[0].quantity_in_transit(), Decimal::from(4));
+
+        // The first shipment can be received before the rest ships
+        transfer
+            .receive(
+                ReceiveTransferRequest {
+                    lines: vec![ReceiveTransferLineRequest {
+                        transfer_line_id: line_id,
+                        quantity_received: Decimal::from(4),
+                    }],
+                    close: false,
+                },
+                Uuid::new_v4(),
+            )
+            .unwrap();
+        assert_eq!(transfer.status, TransferStatus::PartiallyShipped);
+
+        assert!(transfer
+            .ship(shipment(Decimal::from(7)), Uuid::new_v4())
+            .is_err());
+        transfer.ship(Vec::new(), Uuid::new_v4()).unwrap();
+        assert_eq!(transfer.lines[0].quantity_shipped, Decimal::from(10));
+        assert_eq!(transfer.status, TransferStatus::InTransit);
     }
 }
@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// How many single-use recovery codes an enrollment hands out
+pub const RECOVERY_CODE_COUNT: usize = 10;
+
+/// A user's authenticator app enrollment. It is pending until the user proves
+/// the app works by confirming a code; only then does login ask for codes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoFactor {
+    pub user_id: Uuid,
+    /// Base32 TOTP secret shared with the authenticator app
+    #[serde(skip_serializing)]
+    pub secret: String,
+    /// Hashes of the recovery codes not yet used
+    #[serde(skip_serializing)]
+    pub recovery_code_hashes: Vec<String>,
+    /// Time step of the last code accepted, so a code can't be used twice
+    pub last_used_step: Option<i64>,
+    pub enabled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TwoFactor {
+    /// Start a pending enrollment for `secret`, returning it with the recovery
+    /// codes to show the user once
+    pub fn new(user_id: Uuid, secret: String) -> (Self, Vec<String>) {
+        let recovery_codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+            .map(|_| {
+                let digits = Uuid::new_v4().simple().to_string();
+                format!("{}-{}", &digits[..5], &digits[5..10])
+            })
+            .collect();
+
+        let two_factor = Self {
+            user_id,
+            secret,
+            recovery_code_hashes: recovery_codes
+                .iter()
+                .map(|code| Self::hash_recovery_code(code))
+                .collect(),
+            last_used_step: None,
+            enabled_at: None,
+            created_at: Utc::now(),
+        };
+        (two_factor, recovery_codes)
+    }
+
+    /// Hash of a recovery code, ignoring case and the dash it is shown with
+    pub fn hash_recovery_code(code: &str) -> String {
+        let normalized: String = code
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        hex::encode(Sha256::digest(normalized.as_bytes()))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+
+    pub fn enable(&mut self) {
+        self.enabled_at = Some(Utc::now());
+    }
+
+    /// Record that a code for `step` was accepted. Returns `false` for a step at
+    /// or before the last one used, which would be a replayed code.
+    pub fn accept_step(&mut self, step: i64) -> bool {
+        if self.last_used_step.is_some_and(|last| step <= last) {
+            return false;
+        }
+        self.last_used_step = Some(step);
+        true
+    }
+
+    /// Use up a recovery code. Returns `false` if it isn't one of the unused codes.
+    pub fn use_recovery_code(&mut self, code: &str) -> bool {
+        let hash = Self::hash_recovery_code(code);
+        let before = self.recovery_code_hashes.len();
+        self.recovery_code_hashes.retain(|unused| *unused != hash);
+        self.recovery_code_hashes.len() < before
+    }
+}
+
+/// Returned once by enrollment; neither the secret nor the recovery codes can
+/// be read back later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoFactorEnrollment {
+    pub secret: String,
+    pub provisioning_uri: String,
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TwoFactorCodeRequest {
+    /// A code from the authenticator app, or where allowed a recovery code
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoFactorStatus {
+    pub enabled: bool,
+    pub recovery_codes_remaining: usize,
+    /// Whether the tenant's policy requires this user to use two-factor login
+    pub required: bool,
+}
+
+/// The tenant's two-factor policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoFactorPolicy {
+    /// Admins without two-factor login must enroll before doing anything else
+    pub require_for_admins: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovery_codes_are_single_use() {
+        let (mut two_factor, codes) = TwoFactor::new(Uuid::new_v4(), "JBSWY3DP".to_string());
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+        assert!(!two_factor.is_enabled());
+
+        assert!(two_factor.use_recovery_code(&codes[0].to_uppercase()));
+        assert!(!two_factor.use_recovery_code(&codes[0]));
+        assert!(!two_factor.use_recovery_code("00000-00000"));
+        assert_eq!(
+            two_factor.recovery_code_hashes.len(),
+            RECOVERY_CODE_COUNT - 1
+        );
+    }
+
+    #[test]
+    fn test_codes_cannot_be_replayed() {
+        let (mut two_factor, _) = TwoFactor::new(Uuid::new_v4(), "JBSWY3DP".to_string());
+        assert!(two_factor.accept_step(100));
+        assert!(!two_factor.accept_step(100));
+        assert!(!two_factor.accept_step(99));
+        assert!(two_factor.accept_step(101));
+    }
+}
@@ -3,6 +3,32 @@ use crate::shared::error::DomainError;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum UserRole {
+    Admin,
+    Member,
+}
+
+impl UserRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::Admin => "ADMIN",
+            UserRole::Member => "MEMBER",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "ADMIN" => Ok(UserRole::Admin),
+            "MEMBER" => Ok(UserRole::Member),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid user role: {}. Must be one of: ADMIN, MEMBER", s).into(),
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub id: Uuid,
@@ -11,6 +37,7 @@ pub struct User {
     pub first_name: String,
     pub last_name: String,
     pub tenant_id: Uuid,
+    pub role: UserRole,
     pub active: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
@@ -26,13 +53,13 @@ impl User {
     ) -> Result<Self, DomainError> {
         if first_name.trim().is_empty() {
             return Err(DomainError::ValidationError(
-                "First name cannot be empty".to_string(),
+                "First name cannot be empty".into(),
             ));
         }
 
         if last_name.trim().is_empty() {
             return Err(DomainError::ValidationError(
-                "Last name cannot be empty".to_string(),
+                "Last name cannot be empty".into(),
             ));
         }
 
@@ -45,6 +72,7 @@ impl User {
             first_name,
             last_name,
             tenant_id,
+            role: UserRole::Member,
             active: true,
             created_at: now,
             updated_at: now,
@@ -65,6 +93,11 @@ impl User {
         self.updated_at = chrono::Utc::now();
     }
 
+    pub fn change_role(&mut self, role: UserRole) {
+        self.role = role;
+        self.updated_at = chrono::Utc::now();
+    }
+
     pub fn is_active(&self) -> bool {
         self.active
     }
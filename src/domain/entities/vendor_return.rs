@@ -0,0 +1,374 @@
+use crate::domain::entities::inventory::{MovementType, ReferenceType, StockMovement};
+use crate::domain::entities::purchase_order::PurchaseOrder;
+use crate::shared::error::DomainError;
+use crate::shared::money::extend;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VendorReturnStatus {
+    Draft,
+    Shipped,
+    Cancelled,
+}
+
+impl VendorReturnStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VendorReturnStatus::Draft => "DRAFT",
+            VendorReturnStatus::Shipped => "SHIPPED",
+            VendorReturnStatus::Cancelled => "CANCELLED",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s {
+            "DRAFT" => Ok(VendorReturnStatus::Draft),
+            "SHIPPED" => Ok(VendorReturnStatus::Shipped),
+            "CANCELLED" => Ok(VendorReturnStatus::Cancelled),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid vendor return status: {}", s).into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VendorCreditStatus {
+    Expected,
+    Received,
+}
+
+impl VendorCreditStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VendorCreditStatus::Expected => "EXPECTED",
+            VendorCreditStatus::Received => "RECEIVED",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s {
+            "EXPECTED" => Ok(VendorCreditStatus::Expected),
+            "RECEIVED" => Ok(VendorCreditStatus::Received),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid vendor credit status: {}", s).into(),
+            )),
+        }
+    }
+}
+
+/// Defective stock shipped back to the supplier of a purchase order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VendorReturn {
+    pub id: Uuid,
+    pub rtv_number: String,
+    pub po_id: Uuid,
+    pub supplier_id: Uuid,
+    /// Where the defective stock is shipped from
+    pub location_id: Uuid,
+    pub status: VendorReturnStatus,
+    pub total_quantity: Decimal,
+    /// Value of the returned stock at the PO's unit costs
+    pub total_amount: Decimal,
+    pub notes: Option<String>,
+    pub shipped_at: Option<DateTime<Utc>>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub lines: Vec<VendorReturnLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VendorReturnLine {
+    pub id: Uuid,
+    pub rtv_id: Uuid,
+    pub po_line_id: Uuid,
+    pub item_id: Uuid,
+    pub quantity: Decimal,
+    /// The PO line's unit cost, which the supplier credits back
+    pub unit_cost: Decimal,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Credit the supplier owes for a shipped vendor return
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VendorCredit {
+    pub id: Uuid,
+    pub rtv_id: Uuid,
+    pub supplier_id: Uuid,
+    pub po_id: Uuid,
+    pub amount: Decimal,
+    pub status: VendorCreditStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateVendorReturnRequest {
+    pub po_id: Uuid,
+    pub location_id: Uuid,
+    pub lines: Vec<CreateVendorReturnLineRequest>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateVendorReturnLineRequest {
+    pub po_line_id: Uuid,
+    pub quantity: Decimal,
+    pub reason: Option<String>,
+}
+
+impl VendorReturn {
+    /// Draft a return of `lines` against `po`. `already_returned` holds the
+    /// quantities of each PO line on other open or shipped returns, since only
+    /// stock that was received and not yet returned can go back.
+    pub fn new(
+        rtv_number: String,
+        po: &PurchaseOrder,
+        location_id: Uuid,
+        lines: Vec<CreateVendorReturnLineRequest>,
+        already_returned: &HashMap<Uuid, Decimal>,
+        notes: Option<String>,
+        created_by: Uuid,
+    ) -> Result<Self, DomainError> {
+        if lines.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Vendor return must have at least one line".into(),
+            ));
+        }
+
+        let now = Utc::now();
+        let mut rtv = Self {
+            id: Uuid::new_v4(),
+            rtv_number,
+            po_id: po.id,
+            supplier_id: po.supplier_id,
+            location_id,
+            status: VendorReturnStatus::Draft,
+            total_quantity: Decimal::ZERO,
+            total_amount: Decimal::ZERO,
+            notes,
+            shipped_at: None,
+            created_by,
+            created_at: now,
+            updated_at: now,
+            lines: Vec::new(),
+        };
+
+        let mut requested: HashMap<Uuid, Decimal> = HashMap::new();
+        for line in lines {
+            if line.quantity <= Decimal::ZERO {
+                return Err(DomainError::ValidationError(
+                    "Vendor return line quantity must be positive".into(),
+                ));
+            }
+
+            let po_line = po
+                .lines
+                .iter()
+                .find(|po_line| po_line.id == line.po_line_id)
+                .ok_or_else(|| {
+                    DomainError::ValidationError(
+                        format!(
+                            "Line {} is not on purchase order {}",
+                            line.po_line_id, po.po_number
+                        )
+                        .into(),
+                    )
+                })?;
+
+            let total = requested.entry(po_line.id).or_insert(Decimal::ZERO);
+            *total += line.quantity;
+            let returnable = po_line.qty_received
+                - already_returned
+                    .get(&po_line.id)
+                    .copied()
+                    .unwrap_or_default();
+            if *total > returnable {
+                return Err(DomainError::ValidationError(
+                    format!(
+                    "Cannot return {} units of PO line {}, only {} received and not yet returned",
+                    total,
+                    po_line.id,
+                    returnable.max(Decimal::ZERO)
+                )
+                    .into(),
+                ));
+            }
+
+            rtv.lines.push(VendorReturnLine {
+                id: Uuid::new_v4(),
+                rtv_id: rtv.id,
+                po_line_id: po_line.id,
+                item_id: po_line.item_id,
+                quantity: line.quantity,
+                unit_cost: po_line.unit_cost,
+                reason: line.reason,
+                created_at: now,
+            });
+        }
+
+        rtv.total_quantity = rtv.lines.iter().map(|line| line.quantity).sum();
+        rtv.total_amount = rtv
+            .lines
+            .iter()
+            .map(|line| extend(line.quantity, line.unit_cost))
+            .sum();
+        Ok(rtv)
+    }
+
+    /// Ship the stock back: one outbound movement per line, and the credit the
+    /// supplier now owes
+    pub fn ship(&mut self) -> Result<(Vec<StockMovement>, VendorCredit), DomainError> {
+        if self.status != VendorReturnStatus::Draft {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Cannot ship vendor return with status: {}",
+                    self.status.as_str()
+                )
+                .into(),
+            ));
+        }
+
+        let movements = self
+            .lines
+            .iter()
+            .map(|line| {
+                StockMovement::new(
+                    line.item_id,
+                    self.location_id,
+                    MovementType::Outbound,
+                    -line.quantity,
+                    ReferenceType::VendorReturn,
+                    Some(self.id),
+                    Some(format!("RTV {}", self.rtv_number)),
+                    Some(self.created_by),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let now = Utc::now();
+        self.status = VendorReturnStatus::Shipped;
+        self.shipped_at = Some(now);
+        self.updated_at = now;
+
+        let credit = VendorCredit {
+            id: Uuid::new_v4(),
+            rtv_id: self.id,
+            supplier_id: self.supplier_id,
+            po_id: self.po_id,
+            amount: self.total_amount,
+            status: VendorCreditStatus::Expected,
+            created_at: now,
+            updated_at: now,
+        };
+        Ok((movements, credit))
+    }
+
+    pub fn cancel(&mut self) -> Result<(), DomainError> {
+        if self.status != VendorReturnStatus::Draft {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Cannot cancel vendor return with status: {}",
+                    self.status.as_str()
+                )
+                .into(),
+            ));
+        }
+
+        self.status = VendorReturnStatus::Cancelled;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::purchase_order::CreatePurchaseOrderLine;
+
+    fn received_po(qty_received: Decimal) -> PurchaseOrder {
+        let line = CreatePurchaseOrderLine {
+            item_id: Uuid::new_v4(),
+            qty_ordered: Decimal::from(10),
+            unit_cost: Decimal::new(25, 1),
+            expected_date: None,
+        };
+        let mut po = PurchaseOrder::new(Uuid::new_v4(), vec![line], None, Uuid::new_v4()).unwrap();
+        po.lines[0].receive(qty_received, Decimal::ZERO).unwrap();
+        po
+    }
+
+    fn line(po: &PurchaseOrder, quantity: Decimal) -> CreateVendorReturnLineRequest {
+        CreateVendorReturnLineRequest {
+            po_line_id: po.lines[0].id,
+            quantity,
+            reason: Some("Damaged".to_string()),
+        }
+    }
+
+    fn draft(po: &PurchaseOrder, quantity: Decimal) -> Result<VendorReturn, DomainError> {
+        VendorReturn::new(
+            "RTV-1".to_string(),
+            po,
+            Uuid::new_v4(),
+            vec![line(po, quantity)],
+            &HashMap::new(),
+            None,
+            Uuid::new_v4(),
+        )
+    }
+
+    #[test]
+    fn test_new_prices_lines_at_po_unit_cost() {
+        let po = received_po(Decimal::from(6));
+        let rtv = draft(&po, Decimal::from(4)).unwrap();
+
+        assert_eq!(rtv.supplier_id, po.supplier_id);
+        assert_eq!(rtv.lines[0].item_id, po.lines[0].item_id);
+        assert_eq!(rtv.total_quantity, Decimal::from(4));
+        assert_eq!(rtv.total_amount, Decimal::from(10));
+    }
+
+    #[test]
+    fn test_new_rejects_more_than_received_and_not_yet_returned() {
+        let po = received_po(Decimal::from(6));
+        assert!(draft(&po, Decimal::from(7)).is_err());
+
+        let already_returned = HashMap::from([(po.lines[0].id, Decimal::from(4))]);
+        let result = VendorReturn::new(
+            "RTV-2".to_string(),
+            &po,
+            Uuid::new_v4(),
+            vec![line(&po, Decimal::from(2)), line(&po, Decimal::ONE)],
+            &already_returned,
+            None,
+            Uuid::new_v4(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_ship_creates_outbound_movements_and_expected_credit() {
+        let po = received_po(Decimal::from(6));
+        let mut rtv = draft(&po, Decimal::from(4)).unwrap();
+
+        let (movements, credit) = rtv.ship().unwrap();
+        assert_eq!(rtv.status, VendorReturnStatus::Shipped);
+        assert_eq!(movements.len(), 1);
+        assert_eq!(movements[0].quantity, Decimal::from(-4));
+        assert_eq!(movements[0].reference_type.as_str(), "vendor_return");
+        assert_eq!(credit.amount, Decimal::from(10));
+        assert_eq!(credit.status, VendorCreditStatus::Expected);
+
+        assert!(rtv.ship().is_err());
+        assert!(rtv.cancel().is_err());
+    }
+}
@@ -0,0 +1,214 @@
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Deepest nesting of `all`/`any`/`not` accepted in a filter
+const MAX_FILTER_DEPTH: usize = 8;
+
+/// Condition a webhook subscription applies to an event's payload before a
+/// delivery is created, e.g.
+/// `{"all": [{"condition": {"path": "sales_order.total_amount", "op": "gt", "value": 500}}]}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFilter {
+    All(Vec<WebhookFilter>),
+    Any(Vec<WebhookFilter>),
+    Not(Box<WebhookFilter>),
+    Condition(FilterCondition),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterCondition {
+    /// Dot-separated path into the event payload; array elements by index (`lines.0.item_id`)
+    pub path: String,
+    pub op: FilterOperator,
+    #[serde(default)]
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOperator {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    In,
+    Exists,
+}
+
+impl WebhookFilter {
+    pub fn validate(&self) -> Result<(), DomainError> {
+        self.validate_at(1)
+    }
+
+    fn validate_at(&self, depth: usize) -> Result<(), DomainError> {
+        if depth > MAX_FILTER_DEPTH {
+            return Err(DomainError::ValidationError(
+                format!(
+                    "Webhook filter cannot be nested more than {} levels deep",
+                    MAX_FILTER_DEPTH
+                )
+                .into(),
+            ));
+        }
+        match self {
+            WebhookFilter::All(filters) | WebhookFilter::Any(filters) => {
+                if filters.is_empty() {
+                    return Err(DomainError::ValidationError(
+                        "Webhook filter 'all'/'any' needs at least one condition".into(),
+                    ));
+                }
+                filters.iter().try_for_each(|f| f.validate_at(depth + 1))
+            }
+            WebhookFilter::Not(filter) => filter.validate_at(depth + 1),
+            WebhookFilter::Condition(condition) => condition.validate(),
+        }
+    }
+
+    pub fn matches(&self, payload: &Value) -> bool {
+        match self {
+            WebhookFilter::All(filters) => filters.iter().all(|f| f.matches(payload)),
+            WebhookFilter::Any(filters) => filters.iter().any(|f| f.matches(payload)),
+            WebhookFilter::Not(filter) => !filter.matches(payload),
+            WebhookFilter::Condition(condition) => condition.matches(payload),
+        }
+    }
+}
+
+impl FilterCondition {
+    fn validate(&self) -> Result<(), DomainError> {
+        if self.path.trim().is_empty() || self.path.split('.').any(str::is_empty) {
+            return Err(DomainError::ValidationError(
+                format!("Invalid webhook filter path '{}'", self.path).into(),
+            ));
+        }
+        match self.op {
+            FilterOperator::Gt | FilterOperator::Gte | FilterOperator::Lt | FilterOperator::Lte
+                if as_number(&self.value).is_none() =>
+            {
+                Err(DomainError::ValidationError(
+                    format!(
+                        "Webhook filter on '{}' compares against a number",
+                        self.path
+                    )
+                    .into(),
+                ))
+            }
+            FilterOperator::In if !self.value.is_array() => Err(DomainError::ValidationError(
+                format!(
+                    "Webhook filter 'in' on '{}' needs an array value",
+                    self.path
+                )
+                .into(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn matches(&self, payload: &Value) -> bool {
+        let actual = lookup(payload, &self.path);
+        match self.op {
+            FilterOperator::Exists => {
+                let exists = actual.is_some_and(|v| !v.is_null());
+                // `"value": false` asks for the field to be absent
+                exists == self.value.as_bool().unwrap_or(true)
+            }
+            FilterOperator::Eq => actual.is_some_and(|v| values_equal(v, &self.value)),
+            FilterOperator::Ne => !actual.is_some_and(|v| values_equal(v, &self.value)),
+            FilterOperator::In => actual.is_some_and(|v| {
+                self.value
+                    .as_array()
+                    .is_some_and(|options| options.iter().any(|o| values_equal(v, o)))
+            }),
+            FilterOperator::Gt | FilterOperator::Gte | FilterOperator::Lt | FilterOperator::Lte => {
+                match (actual.and_then(as_number), as_number(&self.value)) {
+                    (Some(actual), Some(expected)) => match self.op {
+                        FilterOperator::Gt => actual > expected,
+                        FilterOperator::Gte => actual >= expected,
+                        FilterOperator::Lt => actual < expected,
+                        _ => actual <= expected,
+                    },
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+fn lookup<'a>(payload: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(payload, |value, segment| match value {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+/// Amounts are serialized as strings in some payloads, so numbers compare by value
+/// whichever way they were encoded
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn values_equal(actual: &Value, expected: &Value) -> bool {
+    if actual == expected {
+        return true;
+    }
+    match (actual, expected) {
+        (Value::String(a), Value::String(b)) => a.eq_ignore_ascii_case(b),
+        _ => matches!((as_number(actual), as_number(expected)), (Some(a), Some(b)) if a == b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_matches_nested_conditions() {
+        let filter: WebhookFilter = serde_json::from_value(json!({
+            "all": [
+                {"condition": {"path": "sales_order.total_amount", "op": "gt", "value": 500}},
+                {"any": [
+                    {"condition": {"path": "sales_order.status", "op": "eq", "value": "confirmed"}},
+                    {"condition": {"path": "sales_order.status", "op": "in", "value": ["SHIPPED"]}}
+                ]}
+            ]
+        }))
+        .unwrap();
+        filter.validate().unwrap();
+
+        let big = json!({"sales_order": {"total_amount": "750.00", "status": "CONFIRMED"}});
+        let small = json!({"sales_order": {"total_amount": "20.00", "status": "CONFIRMED"}});
+        assert!(filter.matches(&big));
+        assert!(!filter.matches(&small));
+        assert!(!filter.matches(&json!({})));
+    }
+
+    #[test]
+    fn test_rejects_invalid_filters() {
+        let not_a_number = WebhookFilter::Condition(FilterCondition {
+            path: "movement.quantity".to_string(),
+            op: FilterOperator::Gt,
+            value: json!("many"),
+        });
+        assert!(not_a_number.validate().is_err());
+        assert!(WebhookFilter::Any(Vec::new()).validate().is_err());
+
+        let location = WebhookFilter::Not(Box::new(WebhookFilter::Condition(FilterCondition {
+            path: "movement.location_id".to_string(),
+            op: FilterOperator::Exists,
+            value: Value::Null,
+        })));
+        assert!(location.validate().is_ok());
+        assert!(location.matches(&json!({"movement": {}})));
+    }
+}
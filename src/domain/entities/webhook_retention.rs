@@ -0,0 +1,246 @@
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const WEBHOOK_RETENTION_JOB_TYPE: &str = "webhook_retention";
+
+/// Longest any webhook history may be kept, in days
+pub const MAX_RETENTION_DAYS: i32 = 3650;
+
+/// How long the tenant keeps webhook history before the cleanup prunes it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookRetentionSettings {
+    /// Events are pruned past this age once none of their deliveries are left
+    pub event_retention_days: i32,
+    /// Successful deliveries
+    pub delivery_retention_days: i32,
+    /// Deliveries that ran out of attempts
+    pub dlq_retention_days: i32,
+    /// Write pruned rows to blob storage as JSONL before deleting them
+    pub archive_enabled: bool,
+}
+
+impl Default for WebhookRetentionSettings {
+    fn default() -> Self {
+        Self {
+            event_retention_days: 30,
+            delivery_retention_days: 7,
+            dlq_retention_days: 30,
+            archive_enabled: false,
+        }
+    }
+}
+
+impl WebhookRetentionSettings {
+    pub fn validate(&self) -> Result<(), DomainError> {
+        for (name, days) in [
+            ("event_retention_days", self.event_retention_days),
+            ("delivery_retention_days", self.delivery_retention_days),
+            ("dlq_retention_days", self.dlq_retention_days),
+        ] {
+            if !(1..=MAX_RETENTION_DAYS).contains(&days) {
+                return Err(DomainError::ValidationError(Message::localized(
+                    "error.field_between",
+                    &[("field", &name), ("min", &1), ("max", &MAX_RETENTION_DAYS)],
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn delivery_cutoffs(&self, now: DateTime<Utc>) -> DeliveryCutoffs {
+        DeliveryCutoffs {
+            delivered_before: now - Duration::days(self.delivery_retention_days.into()),
+            dead_lettered_before: now - Duration::days(self.dlq_retention_days.into()),
+        }
+    }
+
+    pub fn event_cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - Duration::days(self.event_retention_days.into())
+    }
+}
+
+/// Any field left out keeps its current value
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateWebhookRetentionRequest {
+    pub event_retention_days: Option<i32>,
+    pub delivery_retention_days: Option<i32>,
+    pub dlq_retention_days: Option<i32>,
+    pub archive_enabled: Option<bool>,
+}
+
+impl UpdateWebhookRetentionRequest {
+    pub fn apply(self, settings: WebhookRetentionSettings) -> WebhookRetentionSettings {
+        WebhookRetentionSettings {
+            event_retention_days: self
+                .event_retention_days
+                .unwrap_or(settings.event_retention_days),
+            delivery_retention_days: self
+                .delivery_retention_days
+                .unwrap_or(settings.delivery_retention_days),
+            dlq_retention_days: self
+                .dlq_retention_days
+                .unwrap_or(settings.dlq_retention_days),
+            archive_enabled: self.archive_enabled.unwrap_or(settings.archive_enabled),
+        }
+    }
+}
+
+/// Deliveries created before their status's cutoff are pruned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeliveryCutoffs {
+    pub delivered_before: DateTime<Utc>,
+    pub dead_lettered_before: DateTime<Utc>,
+}
+
+/// A row about to be pruned, as it will be archived
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiredRecord {
+    pub id: Uuid,
+    pub record: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RetentionTrigger {
+    Scheduled,
+    Manual,
+}
+
+impl RetentionTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetentionTrigger::Scheduled => "SCHEDULED",
+            RetentionTrigger::Manual => "MANUAL",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "SCHEDULED" => Ok(RetentionTrigger::Scheduled),
+            "MANUAL" => Ok(RetentionTrigger::Manual),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid retention trigger: {}", s).into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RetentionRunStatus {
+    Succeeded,
+    /// Stopped part way; what was pruned before the failure stays pruned
+    Failed,
+}
+
+impl RetentionRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetentionRunStatus::Succeeded => "SUCCEEDED",
+            RetentionRunStatus::Failed => "FAILED",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_uppercase().as_str() {
+            "SUCCEEDED" => Ok(RetentionRunStatus::Succeeded),
+            "FAILED" => Ok(RetentionRunStatus::Failed),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid retention run status: {}", s).into(),
+            )),
+        }
+    }
+}
+
+/// Payload of a webhook retention job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookRetentionJobPayload {
+    pub trigger: RetentionTrigger,
+}
+
+/// One pass of the webhook cleanup over a tenant
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RetentionRun {
+    pub id: Uuid,
+    pub trigger: RetentionTrigger,
+    pub status: RetentionRunStatus,
+    pub deliveries_pruned: i64,
+    pub events_pruned: i64,
+    /// Blob storage keys of the JSONL archives written
+    pub archive_keys: Vec<String>,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
+/// Where one batch of a run's pruned rows is archived, e.g.
+/// `tenants/<id>/archives/webhooks/<run>/deliveries-0001.jsonl`
+pub fn archive_key(tenant_id: Uuid, run_id: Uuid, table: &str, batch: usize) -> String {
+    format!(
+        "tenants/{}/archives/webhooks/{}/{}-{:04}.jsonl",
+        tenant_id, run_id, table, batch
+    )
+}
+
+/// One JSON document per line
+pub fn to_jsonl(records: &[ExpiredRecord]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for record in records {
+        out.extend_from_slice(record.record.to_string().as_bytes());
+        out.push(b'\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_update_keeps_unset_fields_and_validates_range() {
+        let settings = UpdateWebhookRetentionRequest {
+            delivery_retention_days: Some(14),
+            archive_enabled: Some(true),
+            ..Default::default()
+        }
+        .apply(WebhookRetentionSettings::default());
+        assert_eq!(settings.event_retention_days, 30);
+        assert_eq!(settings.delivery_retention_days, 14);
+        assert!(settings.archive_enabled);
+        assert!(settings.validate().is_ok());
+
+        let settings = UpdateWebhookRetentionRequest {
+            dlq_retention_days: Some(0),
+            ..Default::default()
+        }
+        .apply(settings);
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_jsonl_writes_a_record_per_line() {
+        let records = vec![
+            ExpiredRecord {
+                id: Uuid::nil(),
+                record: json!({ "id": 1, "body": "a\nb" }),
+            },
+            ExpiredRecord {
+                id: Uuid::nil(),
+                record: json!({ "id": 2 }),
+            },
+        ];
+        let jsonl = String::from_utf8(to_jsonl(&records)).unwrap();
+        assert_eq!(jsonl, "{\"body\":\"a\\nb\",\"id\":1}\n{\"id\":2}\n");
+        assert_eq!(
+            archive_key(Uuid::nil(), Uuid::nil(), "events", 3),
+            format!(
+                "tenants/{0}/archives/webhooks/{0}/events-0003.jsonl",
+                Uuid::nil()
+            )
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use crate::domain::entities::address::AddressValidation;
+use crate::domain::entities::location::LocationAddress;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use async_trait::async_trait;
+
+/// Checks postal addresses and proposes their normalized form
+#[async_trait]
+pub trait AddressValidator: Send + Sync {
+    async fn validate(&self, address: &LocationAddress) -> Result<AddressValidation, DomainError>;
+
+    /// The normalized address to store, or a `ValidationError` listing what is wrong
+    async fn normalize(&self, address: LocationAddress) -> Result<LocationAddress, DomainError> {
+        let validation = self.validate(&address).await?;
+        if !validation.valid {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.invalid_address",
+                &[("issues", &validation.error_message())],
+            )));
+        }
+        Ok(validation.address)
+    }
+}
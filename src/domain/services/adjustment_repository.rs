@@ -0,0 +1,44 @@
+use crate::domain::entities::adjustment::{Adjustment, AdjustmentReason, AdjustmentStatus};
+use crate::domain::entities::inventory::StockMovement;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait AdjustmentRepository: Send + Sync {
+    async fn create_reason(&self, reason: &AdjustmentReason) -> Result<(), DomainError>;
+    async fn find_reason_by_id(&self, id: Uuid) -> Result<Option<AdjustmentReason>, DomainError>;
+    async fn find_reason_by_code(
+        &self,
+        code: &str,
+    ) -> Result<Option<AdjustmentReason>, DomainError>;
+    async fn list_reasons(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AdjustmentReason>, DomainError>;
+    async fn update_reason(&self, reason: &AdjustmentReason) -> Result<(), DomainError>;
+    async fn delete_reason(&self, id: Uuid) -> Result<(), DomainError>;
+
+    /// Save a new adjustment, recording its movement in the same transaction
+    /// when it applies straight away
+    async fn create(
+        &self,
+        adjustment: &Adjustment,
+        movement: Option<&StockMovement>,
+    ) -> Result<(), DomainError>;
+    /// Save the decision on an adjustment awaiting approval, recording the
+    /// movement when approved. Fails if it was decided in the meantime.
+    async fn decide(
+        &self,
+        adjustment: &Adjustment,
+        movement: Option<&StockMovement>,
+    ) -> Result<(), DomainError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Adjustment>, DomainError>;
+    async fn list(
+        &self,
+        status: Option<AdjustmentStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Adjustment>, DomainError>;
+}
@@ -0,0 +1,24 @@
+use crate::domain::entities::allocation::{LocationStock, SalesOrderAllocation};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait AllocationRepository: Send + Sync {
+    /// On-hand stock per active location, less what other orders have reserved or allocated
+    async fn find_available_stock(
+        &self,
+        sales_order_id: Uuid,
+        item_ids: &[Uuid],
+    ) -> Result<Vec<LocationStock>, DomainError>;
+    async fn find_by_sales_order(
+        &self,
+        sales_order_id: Uuid,
+    ) -> Result<Vec<SalesOrderAllocation>, DomainError>;
+    /// Drop the order's unshipped allocations and store the new plan in their place
+    async fn replace_allocations(
+        &self,
+        sales_order_id: Uuid,
+        allocations: &[SalesOrderAllocation],
+    ) -> Result<(), DomainError>;
+}
@@ -0,0 +1,16 @@
+use crate::domain::entities::attachment::ItemAttachment;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait AttachmentRepository: Send + Sync {
+    async fn save(&self, attachment: &ItemAttachment) -> Result<(), DomainError>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ItemAttachment>, DomainError>;
+
+    /// Attachments of an item, oldest first
+    async fn list_for_item(&self, item_id: Uuid) -> Result<Vec<ItemAttachment>, DomainError>;
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
+}
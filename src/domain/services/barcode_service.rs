@@ -0,0 +1,68 @@
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum BarcodeSymbology {
+    #[default]
+    Code128,
+    Qr,
+}
+
+impl BarcodeSymbology {
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_lowercase().as_str() {
+            "code128" => Ok(BarcodeSymbology::Code128),
+            "qr" => Ok(BarcodeSymbology::Qr),
+            _ => Err(DomainError::ValidationError(
+                format!(
+                    "Invalid barcode symbology: {}. Must be one of: code128, qr",
+                    s
+                )
+                .into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum BarcodeFormat {
+    Png,
+    #[default]
+    Svg,
+}
+
+impl BarcodeFormat {
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_lowercase().as_str() {
+            "png" => Ok(BarcodeFormat::Png),
+            "svg" => Ok(BarcodeFormat::Svg),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid barcode format: {}. Must be one of: png, svg", s).into(),
+            )),
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            BarcodeFormat::Png => "image/png",
+            BarcodeFormat::Svg => "image/svg+xml",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderedBarcode {
+    pub content_type: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+pub trait BarcodeService: Send + Sync {
+    fn render(
+        &self,
+        data: &str,
+        symbology: BarcodeSymbology,
+        format: BarcodeFormat,
+    ) -> Result<RenderedBarcode, DomainError>;
+}
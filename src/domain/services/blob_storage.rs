@@ -0,0 +1,67 @@
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use std::path::Path;
+use std::time::Duration;
+
+/// Object storage for uploaded files and generated artifacts. Keys are
+/// `/`-separated paths such as `tenants/<id>/items/<id>/attachments/<id>/spec.pdf`.
+#[async_trait]
+pub trait BlobStorage: Send + Sync {
+    /// Store `data` under `key`, replacing any existing object
+    async fn put(&self, key: &str, content_type: &str, data: Vec<u8>) -> Result<(), DomainError>;
+
+    /// Store the contents of the file at `path` under `key`. Backends that can
+    /// should stream the file rather than read it into memory.
+    async fn put_file(
+        &self,
+        key: &str,
+        content_type: &str,
+        path: &Path,
+    ) -> Result<(), DomainError> {
+        let data = tokio::fs::read(path).await.map_err(|e| {
+            DomainError::InfrastructureError(
+                format!("Failed to read {}: {}", path.display(), e).into(),
+            )
+        })?;
+        self.put(key, content_type, data).await
+    }
+
+    /// Read the object stored under `key`; `NotFound` if there is none
+    async fn get(&self, key: &str) -> Result<Vec<u8>, DomainError>;
+
+    /// Remove the object under `key`; deleting a missing object is not an error
+    async fn delete(&self, key: &str) -> Result<(), DomainError>;
+
+    /// A URL anyone holding it can download the object from until `expires_in` elapses
+    async fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, DomainError>;
+
+    /// Check a signature from a URL issued by `signed_url`, for backends whose URLs
+    /// point back at this API rather than at the storage service itself
+    fn verify_signed_url(
+        &self,
+        key: &str,
+        expires: i64,
+        signature: &str,
+    ) -> Result<(), DomainError> {
+        let _ = (key, expires, signature);
+        Err(DomainError::NotFound(
+            format!("Object {} not found", key).into(),
+        ))
+    }
+}
+
+/// Reject keys that could escape the storage root or address a "directory"
+pub fn validate_key(key: &str) -> Result<(), DomainError> {
+    if key.is_empty()
+        || key.starts_with('/')
+        || key.ends_with('/')
+        || key
+            .split('/')
+            .any(|segment| segment.is_empty() || segment == "." || segment == "..")
+    {
+        return Err(DomainError::ValidationError(
+            format!("Invalid storage key '{}'", key).into(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::domain::entities::integration::{ConnectorProvider, IntegrationConnector, SyncRunError};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A paid order as the channel reports it
+#[derive(Debug, Clone)]
+pub struct ChannelOrder {
+    pub external_id: String,
+    /// The number shown to the merchant, e.g. #1001
+    pub order_number: String,
+    pub updated_at: DateTime<Utc>,
+    pub lines: Vec<ChannelOrderLine>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChannelOrderLine {
+    pub sku: Option<String>,
+    pub quantity: Decimal,
+    pub unit_price: Decimal,
+}
+
+/// Quantity a channel may sell of one SKU
+#[derive(Debug, Clone)]
+pub struct ChannelStockLevel {
+    pub sku: String,
+    pub available: Decimal,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StockPushResult {
+    pub updated: usize,
+    /// SKUs the channel rejected or does not list
+    pub errors: Vec<SyncRunError>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OAuthToken {
+    pub access_token: String,
+    pub scopes: Option<String>,
+}
+
+/// Talks to one sales channel on behalf of a connector
+#[async_trait]
+pub trait Connector: Send + Sync {
+    fn provider(&self) -> ConnectorProvider;
+    /// Where to send the merchant to grant access; `state` comes back on the callback
+    fn authorize_url(
+        &self,
+        connector: &IntegrationConnector,
+        state: &str,
+    ) -> Result<String, DomainError>;
+    async fn exchange_code(
+        &self,
+        connector: &IntegrationConnector,
+        code: &str,
+    ) -> Result<OAuthToken, DomainError>;
+    /// Paid orders updated at or after `updated_since`, oldest first
+    async fn fetch_paid_orders(
+        &self,
+        connector: &IntegrationConnector,
+        updated_since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<ChannelOrder>, DomainError>;
+    async fn push_stock_levels(
+        &self,
+        connector: &IntegrationConnector,
+        levels: &[ChannelStockLevel],
+    ) -> Result<StockPushResult, DomainError>;
+}
+
+/// The connector for each provider we support
+#[derive(Default)]
+pub struct ConnectorRegistry {
+    connectors: HashMap<ConnectorProvider, Arc<dyn Connector>>,
+}
+
+impl ConnectorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(mut self, connector: Arc<dyn Connector>) -> Self {
+        self.connectors.insert(connector.provider(), connector);
+        self
+    }
+
+    pub fn get(&self, provider: ConnectorProvider) -> Result<Arc<dyn Connector>, DomainError> {
+        self.connectors.get(&provider).cloned().ok_or_else(|| {
+            DomainError::BusinessLogicError(
+                format!("The {} connector is not configured", provider.as_str()).into(),
+            )
+        })
+    }
+}
@@ -0,0 +1,161 @@
+use crate::domain::entities::currency::{
+    normalize_currency, CurrencyConverter, OrderCurrency, DEFAULT_BASE_CURRENCY,
+};
+use crate::domain::services::exchange_rate_repository::ExchangeRateRepository;
+use crate::domain::services::tenant_repository::TenantRepository;
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::current_tenant;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait CurrencyService: Send + Sync {
+    /// The current tenant's base currency
+    async fn base_currency(&self) -> Result<String, DomainError>;
+
+    /// Converter holding the latest rates as of today
+    async fn converter(&self) -> Result<CurrencyConverter, DomainError>;
+
+    /// Check the currency an order asks for and fix today's rate into the base
+    /// currency. `None` when the order is in the base currency.
+    async fn order_currency(
+        &self,
+        currency: Option<&str>,
+    ) -> Result<Option<OrderCurrency>, DomainError>;
+}
+
+pub struct CurrencyServiceImpl {
+    tenant_repository: Arc<dyn TenantRepository>,
+    exchange_rate_repository: Arc<dyn ExchangeRateRepository>,
+}
+
+impl CurrencyServiceImpl {
+    pub fn new(
+        tenant_repository: Arc<dyn TenantRepository>,
+        exchange_rate_repository: Arc<dyn ExchangeRateRepository>,
+    ) -> Self {
+        Self {
+            tenant_repository,
+            exchange_rate_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl CurrencyService for CurrencyServiceImpl {
+    async fn base_currency(&self) -> Result<String, DomainError> {
+        let tenant_id = current_tenant()
+            .ok_or_else(|| DomainError::ValidationError("No tenant is in scope".into()))?;
+        Ok(self
+            .tenant_repository
+            .get_base_currency(tenant_id)
+            .await?
+            .unwrap_or_else(|| DEFAULT_BASE_CURRENCY.to_string()))
+    }
+
+    async fn converter(&self) -> Result<CurrencyConverter, DomainError> {
+        let rates = self
+            .exchange_rate_repository
+            .latest_rates(Utc::now().date_naive())
+            .await?;
+        Ok(CurrencyConverter::new(&rates))
+    }
+
+    async fn order_currency(
+        &self,
+        currency: Option<&str>,
+    ) -> Result<Option<OrderCurrency>, DomainError> {
+        let Some(currency) = currency else {
+            return Ok(None);
+        };
+        let currency = normalize_currency(currency)?;
+        let base_currency = self.base_currency().await?;
+        if currency == base_currency {
+            return Ok(None);
+        }
+
+        let exchange_rate = self
+            .converter()
+            .await?
+            .rate(&currency, &base_currency)
+            .ok_or_else(|| {
+                DomainError::ValidationError(
+                    format!(
+                        "No exchange rate from {} to {} is available yet",
+                        currency, base_currency
+                    )
+                    .into(),
+                )
+            })?;
+        Ok(Some(OrderCurrency {
+            currency,
+            exchange_rate,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::currency::ExchangeRate;
+    use crate::domain::services::tenant_repository::MockTenantRepository;
+    use crate::shared::tenant_scope::with_tenant;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    struct FixedRates(Vec<ExchangeRate>);
+
+    #[async_trait]
+    impl ExchangeRateRepository for FixedRates {
+        async fn save_rates(&self, rates: &[ExchangeRate]) -> Result<u64, DomainError> {
+            Ok(rates.len() as u64)
+        }
+
+        async fn latest_rates(&self, _on: NaiveDate) -> Result<Vec<ExchangeRate>, DomainError> {
+            Ok(self.0.clone())
+        }
+
+        async fn latest_rate_date(&self) -> Result<Option<NaiveDate>, DomainError> {
+            Ok(self.0.first().map(|rate| rate.rate_date))
+        }
+    }
+
+    fn service(base_currency: &str) -> CurrencyServiceImpl {
+        let base_currency = base_currency.to_string();
+        let mut tenants = MockTenantRepository::new();
+        tenants
+            .expect_get_base_currency()
+            .returning(move |_| Ok(Some(base_currency.clone())));
+        let rates = FixedRates(vec![ExchangeRate {
+            base_currency: "EUR".to_string(),
+            quote_currency: "USD".to_string(),
+            rate: Decimal::new(11, 1),
+            rate_date: Utc::now().date_naive(),
+            source: "test".to_string(),
+            fetched_at: Utc::now(),
+        }]);
+        CurrencyServiceImpl::new(Arc::new(tenants), Arc::new(rates))
+    }
+
+    #[tokio::test]
+    async fn test_order_currency_fixes_rate_into_base() {
+        let service = service("USD");
+        let order_currency = |currency: Option<&'static str>| {
+            with_tenant(Uuid::new_v4(), service.order_currency(currency))
+        };
+
+        assert_eq!(order_currency(None).await.unwrap(), None);
+        assert_eq!(order_currency(Some("usd")).await.unwrap(), None);
+        assert_eq!(
+            order_currency(Some("eur")).await.unwrap(),
+            Some(OrderCurrency {
+                currency: "EUR".to_string(),
+                exchange_rate: Decimal::new(11, 1),
+            })
+        );
+        assert!(order_currency(Some("GBP")).await.is_err());
+        assert!(order_currency(Some("EURO")).await.is_err());
+    }
+}
@@ -0,0 +1,24 @@
+use crate::domain::entities::credit::{CreditEnforcement, CreditSettings};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait CustomerCreditRepository: Send + Sync {
+    /// The current tenant's enforcement and limits, blocking where none is set
+    async fn find(&self) -> Result<CreditSettings, DomainError>;
+    async fn find_enforcement(&self) -> Result<CreditEnforcement, DomainError>;
+    async fn find_limit(&self, customer_id: Uuid) -> Result<Option<Decimal>, DomainError>;
+    async fn save_enforcement(&self, enforcement: CreditEnforcement) -> Result<(), DomainError>;
+    async fn save_customer(
+        &self,
+        customer_id: Uuid,
+        credit_limit: Decimal,
+    ) -> Result<(), DomainError>;
+    /// Whether the customer had a limit to remove
+    async fn delete_customer(&self, customer_id: Uuid) -> Result<bool, DomainError>;
+    /// What the customer owes on unpaid invoices less any credit from overpayments,
+    /// in the tenant's base currency
+    async fn outstanding_balance(&self, customer_id: Uuid) -> Result<Decimal, DomainError>;
+}
@@ -0,0 +1,24 @@
+use crate::domain::entities::cycle_count::CycleCount;
+use crate::domain::entities::inventory::StockMovement;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait CycleCountRepository: Send + Sync {
+    async fn create(&self, cycle_count: &CycleCount) -> Result<(), DomainError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<CycleCount>, DomainError>;
+    async fn update(&self, cycle_count: &CycleCount) -> Result<(), DomainError>;
+    async fn list(
+        &self,
+        location_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<CycleCount>, DomainError>;
+    /// Persist the completed count together with its variance movements and stock level updates
+    async fn finalize(
+        &self,
+        cycle_count: &CycleCount,
+        stock_movements: &[StockMovement],
+    ) -> Result<(), DomainError>;
+}
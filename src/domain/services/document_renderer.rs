@@ -0,0 +1,93 @@
+use crate::shared::error::DomainError;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocumentKind {
+    PurchaseOrder,
+    SalesOrderConfirmation,
+    PackingSlip,
+}
+
+impl DocumentKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            DocumentKind::PurchaseOrder => "Purchase Order",
+            DocumentKind::SalesOrderConfirmation => "Order Confirmation",
+            DocumentKind::PackingSlip => "Packing Slip",
+        }
+    }
+
+    /// File name stem used in the download's Content-Disposition
+    pub fn file_prefix(&self) -> &'static str {
+        match self {
+            DocumentKind::PurchaseOrder => "purchase-order",
+            DocumentKind::SalesOrderConfirmation => "order-confirmation",
+            DocumentKind::PackingSlip => "packing-slip",
+        }
+    }
+}
+
+/// An addressed block such as "Supplier" or "Ship from"
+#[derive(Debug, Clone)]
+pub struct DocumentParty {
+    pub heading: String,
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DocumentColumn {
+    pub header: String,
+    /// Share of the table width, relative to the other columns
+    pub weight: f64,
+    /// Right-aligned, for quantities and amounts
+    pub numeric: bool,
+}
+
+impl DocumentColumn {
+    pub fn text(header: &str, weight: f64) -> Self {
+        Self {
+            header: header.to_string(),
+            weight,
+            numeric: false,
+        }
+    }
+
+    pub fn numeric(header: &str, weight: f64) -> Self {
+        Self {
+            header: header.to_string(),
+            weight,
+            numeric: true,
+        }
+    }
+}
+
+/// A printable business document, already formatted: the renderer only lays it out
+#[derive(Debug, Clone)]
+pub struct BusinessDocument {
+    pub kind: DocumentKind,
+    pub number: String,
+    pub issued_at: DateTime<Utc>,
+    /// The tenant's company block
+    pub letterhead: Vec<String>,
+    /// PNG logo printed above the company block
+    pub logo: Option<Vec<u8>>,
+    pub parties: Vec<DocumentParty>,
+    /// Label/value pairs beside the document number, e.g. status or expected date
+    pub details: Vec<(String, String)>,
+    pub columns: Vec<DocumentColumn>,
+    pub rows: Vec<Vec<String>>,
+    /// Label/value pairs under the table, e.g. subtotal and total
+    pub totals: Vec<(String, String)>,
+    pub footer: Option<String>,
+}
+
+impl BusinessDocument {
+    pub fn file_name(&self) -> String {
+        format!("{}-{}.pdf", self.kind.file_prefix(), self.number)
+    }
+}
+
+pub trait DocumentRenderer: Send + Sync {
+    /// Render the document as a PDF, continuing the line table over as many pages as needed
+    fn render(&self, document: &BusinessDocument) -> Result<Vec<u8>, DomainError>;
+}
@@ -0,0 +1,10 @@
+use crate::domain::entities::document::DocumentSettings;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait DocumentSettingsRepository: Send + Sync {
+    /// The current tenant's letterhead, if one has been set up
+    async fn find(&self) -> Result<Option<DocumentSettings>, DomainError>;
+    async fn save(&self, settings: &DocumentSettings) -> Result<(), DomainError>;
+}
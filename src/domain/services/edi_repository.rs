@@ -0,0 +1,47 @@
+use crate::domain::entities::edi::{
+    EdiDirection, EdiDocument, EdiDocumentType, EdiSalesOrderLink, TradingPartner,
+};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait EdiRepository: Send + Sync {
+    async fn create_partner(&self, partner: &TradingPartner) -> Result<(), DomainError>;
+    async fn find_partner_by_id(&self, id: Uuid) -> Result<Option<TradingPartner>, DomainError>;
+    async fn find_partner_by_interchange_id(
+        &self,
+        interchange_id: &str,
+    ) -> Result<Option<TradingPartner>, DomainError>;
+    async fn list_partners(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<TradingPartner>, DomainError>;
+    async fn update_partner(&self, partner: &TradingPartner) -> Result<(), DomainError>;
+    async fn delete_partner(&self, id: Uuid) -> Result<(), DomainError>;
+    /// Claim the next ISA/GS control number for documents sent to the partner
+    async fn next_control_number(&self, partner_id: Uuid) -> Result<i64, DomainError>;
+
+    async fn create_document(&self, document: &EdiDocument) -> Result<(), DomainError>;
+    async fn find_document(&self, id: Uuid) -> Result<Option<EdiDocument>, DomainError>;
+    async fn list_documents(
+        &self,
+        partner_id: Option<Uuid>,
+        document_type: Option<EdiDocumentType>,
+        direction: Option<EdiDirection>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<EdiDocument>, DomainError>;
+
+    async fn create_sales_order_link(&self, link: &EdiSalesOrderLink) -> Result<(), DomainError>;
+    async fn find_sales_order_link(
+        &self,
+        sales_order_id: Uuid,
+    ) -> Result<Option<EdiSalesOrderLink>, DomainError>;
+    async fn find_sales_order_link_by_po(
+        &self,
+        partner_id: Uuid,
+        po_number: &str,
+    ) -> Result<Option<EdiSalesOrderLink>, DomainError>;
+}
@@ -0,0 +1,98 @@
+use crate::shared::error::DomainError;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+
+/// The sender and receiver identities wrapped around an outbound document
+#[derive(Debug, Clone)]
+pub struct EdiEnvelope {
+    pub sender_qualifier: String,
+    pub sender_id: String,
+    pub sender_application_id: String,
+    pub receiver_qualifier: String,
+    pub receiver_id: String,
+    pub receiver_application_id: String,
+    pub control_number: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An interchange of 850 purchase orders as received
+#[derive(Debug, Clone)]
+pub struct EdiPurchaseOrderInterchange {
+    pub sender_id: String,
+    pub receiver_id: String,
+    pub control_number: String,
+    pub purchase_orders: Vec<EdiPurchaseOrder>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EdiPurchaseOrder {
+    pub po_number: String,
+    pub po_date: Option<NaiveDate>,
+    pub lines: Vec<EdiPurchaseOrderLine>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EdiPurchaseOrderLine {
+    pub line_number: Option<String>,
+    pub quantity: Decimal,
+    pub unit_price: Decimal,
+    /// Qualifier/ID pairs, e.g. ("VN", "SKU-001") or ("UP", "012345678905")
+    pub product_ids: Vec<(String, String)>,
+}
+
+impl EdiPurchaseOrderLine {
+    pub fn product_id(&self, qualifier: &str) -> Option<&str> {
+        self.product_ids
+            .iter()
+            .find(|(q, _)| q == qualifier)
+            .map(|(_, id)| id.as_str())
+    }
+}
+
+/// A line on an outbound 856 or 810
+#[derive(Debug, Clone)]
+pub struct EdiDocumentLine {
+    /// The PO1 line number from the partner's 850
+    pub line_number: String,
+    pub product_qualifier: String,
+    pub product_id: String,
+    pub quantity: Decimal,
+    pub unit_price: Decimal,
+}
+
+#[derive(Debug, Clone)]
+pub struct EdiShipNotice {
+    pub shipment_number: String,
+    pub shipped_at: DateTime<Utc>,
+    pub carrier: Option<String>,
+    pub tracking_number: Option<String>,
+    pub po_number: String,
+    pub sscc_codes: Vec<String>,
+    pub lines: Vec<EdiDocumentLine>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EdiInvoice {
+    pub invoice_number: String,
+    pub invoice_date: DateTime<Utc>,
+    pub po_number: String,
+    pub po_date: Option<NaiveDate>,
+    pub lines: Vec<EdiDocumentLine>,
+}
+
+pub trait EdiTranslator: Send + Sync {
+    fn read_purchase_orders(
+        &self,
+        content: &str,
+    ) -> Result<EdiPurchaseOrderInterchange, DomainError>;
+    fn write_ship_notice(
+        &self,
+        envelope: &EdiEnvelope,
+        notice: &EdiShipNotice,
+    ) -> Result<String, DomainError>;
+    fn write_invoice(
+        &self,
+        envelope: &EdiEnvelope,
+        invoice: &EdiInvoice,
+    ) -> Result<String, DomainError>;
+}
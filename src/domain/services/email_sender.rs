@@ -0,0 +1,16 @@
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+
+/// A plain-text email
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Outgoing email for account notifications such as password resets
+#[async_trait]
+pub trait EmailSender: Send + Sync {
+    async fn send(&self, message: &EmailMessage) -> Result<(), DomainError>;
+}
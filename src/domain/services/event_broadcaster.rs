@@ -0,0 +1,41 @@
+use crate::domain::entities::webhook::WebhookEvent;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Events buffered per subscriber before a slow one starts missing events
+pub const DEFAULT_EVENT_BUFFER: usize = 1024;
+
+/// In-process fan-out of every event that goes through the webhook dispatcher, for
+/// live consumers such as the `/events/stream` endpoint. Publishing never blocks;
+/// subscribers that fall more than the buffer behind skip ahead and are told how
+/// many events they lost.
+#[derive(Clone)]
+pub struct EventBroadcaster {
+    sender: broadcast::Sender<Arc<WebhookEvent>>,
+}
+
+impl EventBroadcaster {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    pub fn publish(&self, event: &WebhookEvent) {
+        // An error only means nobody is listening right now
+        let _ = self.sender.send(Arc::new(event.clone()));
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<WebhookEvent>> {
+        self.sender.subscribe()
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Default for EventBroadcaster {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_BUFFER)
+    }
+}
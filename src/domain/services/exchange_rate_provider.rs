@@ -0,0 +1,10 @@
+use crate::domain::entities::currency::ExchangeRate;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+
+/// A feed of daily reference exchange rates
+#[async_trait]
+pub trait ExchangeRateProvider: Send + Sync {
+    /// Today's rates from `base_currency` into every currency the feed quotes
+    async fn fetch_latest(&self, base_currency: &str) -> Result<Vec<ExchangeRate>, DomainError>;
+}
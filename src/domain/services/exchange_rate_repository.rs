@@ -0,0 +1,17 @@
+use crate::domain::entities::currency::ExchangeRate;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+#[async_trait]
+pub trait ExchangeRateRepository: Send + Sync {
+    /// Store rates, replacing any already held for the same pair and day; returns
+    /// how many were written
+    async fn save_rates(&self, rates: &[ExchangeRate]) -> Result<u64, DomainError>;
+
+    /// The most recent rate for every pair published on or before `on`
+    async fn latest_rates(&self, on: NaiveDate) -> Result<Vec<ExchangeRate>, DomainError>;
+
+    /// Day of the newest rates held, if any
+    async fn latest_rate_date(&self) -> Result<Option<NaiveDate>, DomainError>;
+}
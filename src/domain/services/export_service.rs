@@ -1,11 +1,27 @@
 use crate::domain::entities::export::{
-    CreateExportResponse, CreateStockCsvExportRequest, ExportType, StockCsvExportPayload,
+    export_object_key, CreateExportResponse, CreateStockCsvExportRequest, CsvExportResult,
+    ExportDownloadResponse, ExportType, ReportExportRequest, StockCsvExportPayload,
+    GENERATE_REPORT_JOB_TYPE, STOCK_CSV_EXPORT_JOB_TYPE,
 };
-use crate::domain::entities::job::CreateJobRequest;
+use crate::domain::entities::inventory::{StockLevel, StockStatus};
+use crate::domain::entities::job::{CreateJobRequest, Job, JobCancellation, JobStatus};
+use crate::domain::entities::tenant_snapshot::{
+    TENANT_SNAPSHOT_EXPORT_JOB_TYPE, TENANT_SNAPSHOT_FILE_NAME,
+};
+use crate::domain::services::blob_storage::BlobStorage;
+use crate::domain::services::export_source::ExportSourceRegistry;
 use crate::domain::services::job_service::JobService;
+use crate::domain::services::stock_repository::StockRepository;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{PageRequest, MAX_PAGE_LIMIT};
+use crate::shared::tenant_scope::with_tenant;
 use async_trait::async_trait;
+use chrono::Utc;
 use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+const STOCK_CSV_FILE_NAME: &str = "stock.csv";
 
 /// Service for handling data exports
 #[async_trait]
@@ -14,23 +30,97 @@ pub trait ExportService: Send + Sync {
         &self,
         request: CreateStockCsvExportRequest,
     ) -> Result<CreateExportResponse, DomainError>;
+
+    /// Build the CSV for a queued stock export job and write it to blob storage.
+    /// Returns `None` if the job was cancelled before the file was written.
+    async fn run_stock_csv_export(
+        &self,
+        job: &Job,
+        cancellation: &JobCancellation,
+    ) -> Result<Option<CsvExportResult>, DomainError>;
+
+    /// Queue a report or collection export as a `generate_report` job; its
+    /// `result_url` links to the file once the job succeeds
+    async fn create_report_export(
+        &self,
+        tenant_id: Uuid,
+        request: ReportExportRequest,
+    ) -> Result<CreateExportResponse, DomainError>;
+
+    /// Sign a new download link for a finished export, e.g. once the one stored
+    /// on the job has expired
+    async fn get_download_url(
+        &self,
+        tenant_id: Uuid,
+        job_id: &str,
+    ) -> Result<ExportDownloadResponse, DomainError>;
 }
 
 /// Implementation of ExportService
-pub struct ExportServiceImpl<T: JobService> {
+pub struct ExportServiceImpl<T: JobService, S: StockRepository> {
     job_service: Arc<T>,
+    stock_repository: Arc<S>,
+    export_sources: Arc<ExportSourceRegistry>,
+    blob_storage: Arc<dyn BlobStorage>,
+    url_expiry: Duration,
 }
 
-impl<T: JobService> ExportServiceImpl<T> {
-    pub fn new(job_service: Arc<T>) -> Self {
-        Self { job_service }
+impl<T: JobService, S: StockRepository> ExportServiceImpl<T, S> {
+    pub fn new(
+        job_service: Arc<T>,
+        stock_repository: Arc<S>,
+        export_sources: Arc<ExportSourceRegistry>,
+        blob_storage: Arc<dyn BlobStorage>,
+        url_expiry: Duration,
+    ) -> Self {
+        Self {
+            job_service,
+            stock_repository,
+            export_sources,
+            blob_storage,
+            url_expiry,
+        }
+    }
+
+    /// Every matching stock level, or `None` if cancelled part way through
+    async fn load_stock_levels(
+        &self,
+        location_id: Option<Uuid>,
+        cancellation: &JobCancellation,
+    ) -> Result<Option<Vec<StockLevel>>, DomainError> {
+        let mut levels = Vec::new();
+        let mut cursor = None;
+        loop {
+            if cancellation.is_cancelled() {
+                return Ok(None);
+            }
+            let page_request = PageRequest::new(Some(MAX_PAGE_LIMIT), cursor);
+            let page = match location_id {
+                Some(location_id) => {
+                    self.stock_repository
+                        .get_stock_levels_by_location(location_id, &page_request)
+                        .await?
+                }
+                None => {
+                    self.stock_repository
+                        .get_all_stock_levels(&page_request)
+                        .await?
+                }
+            };
+            levels.extend(page.data);
+            match page.cursor.next_cursor {
+                Some(next) if page.cursor.has_more => cursor = Some(next),
+                _ => return Ok(Some(levels)),
+            }
+        }
     }
 }
 
 #[async_trait]
-impl<T: JobService> ExportService for ExportServiceImpl<T>
+impl<T, S> ExportService for ExportServiceImpl<T, S>
 where
     T: JobService,
+    S: StockRepository,
 {
     async fn create_stock_csv_export(
         &self,
@@ -43,10 +133,11 @@ where
 
         // Create job request
         let job_request = CreateJobRequest {
-            job_type: "stock_csv_export".to_string(),
+            job_type: STOCK_CSV_EXPORT_JOB_TYPE.to_string(),
             payload: serde_json::to_value(payload).map_err(|e| {
-                DomainError::ValidationError(format!("Failed to serialize payload: {}", e))
+                DomainError::ValidationError(format!("Failed to serialize payload: {}", e).into())
             })?,
+            max_attempts: None,
         };
 
         // Enqueue job using the Jobs API
@@ -62,4 +153,148 @@ where
             created_at: job.created_at,
         })
     }
+
+    async fn run_stock_csv_export(
+        &self,
+        job: &Job,
+        cancellation: &JobCancellation,
+    ) -> Result<Option<CsvExportResult>, DomainError> {
+        let payload: StockCsvExportPayload =
+            serde_json::from_value(job.payload.clone().unwrap_or_default()).map_err(|e| {
+                DomainError::ValidationError(format!("Invalid export payload: {}", e).into())
+            })?;
+
+        // Jobs run outside any request, so scope the reads to the job's tenant
+        let levels = with_tenant(
+            job.tenant_id,
+            self.load_stock_levels(payload.location_id, cancellation),
+        )
+        .await?;
+        let Some(levels) = levels else {
+            return Ok(None);
+        };
+        let csv = stock_levels_csv(&levels)?;
+        let file_size_bytes = csv.len() as i64;
+
+        let key = export_object_key(job.tenant_id, &job.job_id, STOCK_CSV_FILE_NAME);
+        self.blob_storage.put(&key, "text/csv", csv).await?;
+        let download_url = self.blob_storage.signed_url(&key, self.url_expiry).await?;
+
+        Ok(Some(CsvExportResult {
+            filename: STOCK_CSV_FILE_NAME.to_string(),
+            record_count: levels.len() as i32,
+            file_size_bytes,
+            download_url,
+        }))
+    }
+
+    async fn create_report_export(
+        &self,
+        tenant_id: Uuid,
+        request: ReportExportRequest,
+    ) -> Result<CreateExportResponse, DomainError> {
+        // Catch bad requests now rather than as a failed job later
+        request.validate()?;
+        self.export_sources
+            .get(&request.report)?
+            .validate(&request.params())?;
+
+        let job_request = CreateJobRequest {
+            job_type: GENERATE_REPORT_JOB_TYPE.to_string(),
+            payload: serde_json::to_value(&request).map_err(|e| {
+                DomainError::ValidationError(format!("Failed to serialize payload: {}", e).into())
+            })?,
+            max_attempts: None,
+        };
+        let job = self.job_service.enqueue_job(tenant_id, job_request).await?;
+
+        Ok(CreateExportResponse {
+            job_id: job.job_id.clone(),
+            export_type: ExportType::Report,
+            status: job.status.to_string(),
+            created_at: job.created_at,
+        })
+    }
+
+    async fn get_download_url(
+        &self,
+        tenant_id: Uuid,
+        job_id: &str,
+    ) -> Result<ExportDownloadResponse, DomainError> {
+        let job = self
+            .job_service
+            .get_job_status(tenant_id, job_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Export {} not found", job_id).into()))?;
+        let file_name = match job.job_type.as_str() {
+            STOCK_CSV_EXPORT_JOB_TYPE => STOCK_CSV_FILE_NAME.to_string(),
+            GENERATE_REPORT_JOB_TYPE => serde_json::from_value::<ReportExportRequest>(
+                job.payload.clone().unwrap_or_default(),
+            )
+            .map_err(|e| {
+                DomainError::ValidationError(format!("Invalid report payload: {}", e).into())
+            })?
+            .file_name(),
+            TENANT_SNAPSHOT_EXPORT_JOB_TYPE => TENANT_SNAPSHOT_FILE_NAME.to_string(),
+            _ => {
+                return Err(DomainError::NotFound(
+                    format!("Export {} not found", job_id).into(),
+                ))
+            }
+        };
+
+        if job.status != JobStatus::Success {
+            return Err(DomainError::BusinessLogicError(
+                format!("Export {} has not finished (status {})", job_id, job.status).into(),
+            ));
+        }
+
+        let key = export_object_key(tenant_id, job_id, &file_name);
+        let expires_at = Utc::now()
+            + chrono::Duration::from_std(self.url_expiry).unwrap_or(chrono::Duration::hours(1));
+        Ok(ExportDownloadResponse {
+            job_id: job.job_id,
+            download_url: self.blob_storage.signed_url(&key, self.url_expiry).await?,
+            expires_at,
+        })
+    }
+}
+
+fn stock_levels_csv(levels: &[StockLevel]) -> Result<Vec<u8>, DomainError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let to_error =
+        |e: csv::Error| DomainError::InfrastructureError(format!("CSV error: {}", e).into());
+
+    writer
+        .write_record([
+            "item_id",
+            "location_id",
+            "quantity_on_hand",
+            "quantity_reserved",
+            "quantity_quarantine",
+            "quantity_damaged",
+            "quantity_in_transit",
+            "available",
+            "updated_at",
+        ])
+        .map_err(to_error)?;
+    for level in levels {
+        writer
+            .write_record([
+                level.item_id.to_string(),
+                level.location_id.to_string(),
+                level.quantity_on_hand.to_string(),
+                level.quantity_reserved.to_string(),
+                level.quantity_quarantine.to_string(),
+                level.quantity_damaged.to_string(),
+                level.quantity_in_transit.to_string(),
+                (level.quantity_in(StockStatus::Available) - level.quantity_reserved).to_string(),
+                level.updated_at.to_rfc3339(),
+            ])
+            .map_err(to_error)?;
+    }
+
+    writer
+        .into_inner()
+        .map_err(|e| DomainError::InfrastructureError(format!("CSV error: {}", e).into()))
 }
@@ -0,0 +1,63 @@
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// One page of report rows
+#[derive(Debug, Clone, Default)]
+pub struct ExportPage {
+    pub rows: Vec<Value>,
+    pub next_cursor: Option<String>,
+}
+
+/// A report or collection listing that can be exported to a file
+#[async_trait]
+pub trait ExportSource: Send + Sync {
+    /// The `report` name exports ask for
+    fn name(&self) -> &'static str;
+    /// Check the parameters before a job is queued
+    fn validate(&self, params: &Value) -> Result<(), DomainError>;
+    async fn fetch_page(
+        &self,
+        params: &Value,
+        cursor: Option<String>,
+    ) -> Result<ExportPage, DomainError>;
+}
+
+/// Read a source's parameters, rejecting unknown or mistyped ones
+pub fn parse_params<T: DeserializeOwned>(params: &Value) -> Result<T, DomainError> {
+    serde_json::from_value(params.clone()).map_err(|e| {
+        DomainError::ValidationError(format!("Invalid report parameters: {}", e).into())
+    })
+}
+
+/// The exportable reports, by name
+#[derive(Default)]
+pub struct ExportSourceRegistry {
+    sources: HashMap<&'static str, Arc<dyn ExportSource>>,
+}
+
+impl ExportSourceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(mut self, source: Arc<dyn ExportSource>) -> Self {
+        self.sources.insert(source.name(), source);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Result<Arc<dyn ExportSource>, DomainError> {
+        self.sources.get(name).cloned().ok_or_else(|| {
+            let mut names: Vec<_> = self.sources.keys().copied().collect();
+            names.sort_unstable();
+            DomainError::ValidationError(Message::localized(
+                "error.unknown_report",
+                &[("report", &name), ("expected", &names.join(", "))],
+            ))
+        })
+    }
+}
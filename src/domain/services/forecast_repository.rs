@@ -0,0 +1,29 @@
+use crate::domain::entities::forecast::DemandForecast;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait ForecastRepository: Send + Sync {
+    /// Units that left stock through outbound movements, per item and day of the
+    /// tenant's calendar, from `from` up to but not including `to`. Days without
+    /// any are left out.
+    async fn daily_demand(
+        &self,
+        item_ids: &[Uuid],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<HashMap<Uuid, Vec<(NaiveDate, Decimal)>>, DomainError>;
+    /// Store a forecast, replacing the item's snapshot for the same method
+    /// and day
+    async fn save(&self, forecast: &DemandForecast) -> Result<(), DomainError>;
+    /// An item's stored forecasts, newest first
+    async fn list_by_item(
+        &self,
+        item_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<DemandForecast>, DomainError>;
+}
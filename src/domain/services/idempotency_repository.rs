@@ -18,6 +18,9 @@ pub trait IdempotencyRepository: Send + Sync {
         body: Option<String>,
     ) -> Result<(), DomainError>;
 
+    /// Forget a key, e.g. after the request it guarded failed and may be retried
+    async fn delete_key(&self, idempotency_key: &str) -> Result<(), DomainError>;
+
     /// Delete expired idempotency keys
     async fn delete_expired_keys(&self) -> Result<i64, DomainError>;
 
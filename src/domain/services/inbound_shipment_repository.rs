@@ -0,0 +1,20 @@
+use crate::domain::entities::inbound_shipment::InboundShipment;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait InboundShipmentRepository: Send + Sync {
+    async fn create(&self, shipment: &InboundShipment) -> Result<(), DomainError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<InboundShipment>, DomainError>;
+    async fn find_by_asn_number(
+        &self,
+        supplier_id: Uuid,
+        asn_number: &str,
+    ) -> Result<Option<InboundShipment>, DomainError>;
+    /// Every notice against a purchase order, oldest first
+    async fn list_by_po(&self, po_id: Uuid) -> Result<Vec<InboundShipment>, DomainError>;
+    /// Save the status and what has been scanned in, including lines found in
+    /// cartons that the notice didn't list
+    async fn update(&self, shipment: &InboundShipment) -> Result<(), DomainError>;
+}
@@ -0,0 +1,66 @@
+use crate::domain::entities::integration::{
+    ConnectorProvider, IntegrationConnector, IntegrationOrderLink, SyncKind, SyncRun,
+};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// A stock level at a connector's location, by SKU
+#[derive(Debug, Clone)]
+pub struct LocationStock {
+    pub sku: String,
+    pub available: Decimal,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait IntegrationRepository: Send + Sync {
+    async fn create_connector(&self, connector: &IntegrationConnector) -> Result<(), DomainError>;
+    async fn find_connector(&self, id: Uuid) -> Result<Option<IntegrationConnector>, DomainError>;
+    async fn find_connector_by_shop(
+        &self,
+        provider: ConnectorProvider,
+        shop_domain: &str,
+    ) -> Result<Option<IntegrationConnector>, DomainError>;
+    async fn list_connectors(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<IntegrationConnector>, DomainError>;
+    /// Save the settings, credentials and OAuth state, leaving the sync cursors alone
+    async fn update_connector(&self, connector: &IntegrationConnector) -> Result<(), DomainError>;
+    /// Save the sync cursors and next sync time
+    async fn save_sync_state(&self, connector: &IntegrationConnector) -> Result<(), DomainError>;
+    async fn delete_connector(&self, id: Uuid) -> Result<(), DomainError>;
+    /// Claim connected, active connectors that are due across all tenants,
+    /// pushing their next sync out by their interval. Returns (tenant, connector).
+    async fn claim_due_connectors(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<(Uuid, IntegrationConnector)>, DomainError>;
+
+    async fn find_order_link(
+        &self,
+        connector_id: Uuid,
+        external_order_id: &str,
+    ) -> Result<Option<IntegrationOrderLink>, DomainError>;
+    async fn create_order_link(&self, link: &IntegrationOrderLink) -> Result<(), DomainError>;
+
+    /// Stock of items with a SKU at `location_id` changed after `since`, oldest change first
+    async fn list_location_stock(
+        &self,
+        location_id: Uuid,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LocationStock>, DomainError>;
+
+    async fn create_sync_run(&self, run: &SyncRun) -> Result<(), DomainError>;
+    async fn list_sync_runs(
+        &self,
+        connector_id: Uuid,
+        kind: Option<SyncKind>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<SyncRun>, DomainError>;
+}
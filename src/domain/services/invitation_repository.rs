@@ -0,0 +1,17 @@
+use crate::domain::entities::invitation::Invitation;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait InvitationRepository: Send + Sync {
+    /// Save a new invitation, replacing any pending one for the same tenant and email
+    async fn save(&self, invitation: &Invitation) -> Result<(), DomainError>;
+
+    /// Find an invitation by the hash of its token
+    async fn find_by_token_hash(&self, token_hash: &str)
+        -> Result<Option<Invitation>, DomainError>;
+
+    /// Record the invitation as accepted. Returns `false` if it was already accepted,
+    /// so a token can't be redeemed twice.
+    async fn mark_accepted(&self, invitation: &Invitation) -> Result<bool, DomainError>;
+}
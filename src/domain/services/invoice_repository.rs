@@ -0,0 +1,32 @@
+use crate::domain::entities::invoice::{Invoice, Payment, RecordPaymentRequest};
+use crate::domain::entities::list_filter::ListFilter;
+use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait InvoiceRepository: Send + Sync {
+    /// Save the invoice and move its sales order to INVOICED. Fails with
+    /// `Conflict` if the order is no longer SHIPPED or already has an invoice.
+    async fn create(&self, invoice: &Invoice) -> Result<(), DomainError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Invoice>, DomainError>;
+    /// Payments against an invoice, oldest first
+    async fn payments(&self, invoice_id: Uuid) -> Result<Vec<Payment>, DomainError>;
+    async fn list(
+        &self,
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<Invoice>, DomainError>;
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError>;
+    /// Record a payment with the invoice's row locked, so concurrent payments
+    /// see each other's effect on the balance
+    async fn record_payment(
+        &self,
+        invoice_id: Uuid,
+        request: &RecordPaymentRequest,
+        recorded_by: Uuid,
+    ) -> Result<(Invoice, Payment), DomainError>;
+    /// Invoices with a balance left, for aging
+    async fn open_invoices(&self) -> Result<Vec<Invoice>, DomainError>;
+}
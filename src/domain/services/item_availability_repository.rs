@@ -0,0 +1,16 @@
+use crate::domain::entities::item_availability::{AvailabilityRebuild, ItemAvailability};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// The maintained availability read model. The stock, reservation,
+/// allocation and purchase order repositories keep it current as they write;
+/// a rebuild recomputes it from those tables in case it has drifted.
+#[async_trait]
+pub trait ItemAvailabilityRepository: Send + Sync {
+    /// An item's availability at every location it has stock at, with what
+    /// it has on order
+    async fn find_by_item(&self, item_id: Uuid) -> Result<ItemAvailability, DomainError>;
+    /// Recompute the current tenant's read model from scratch
+    async fn rebuild(&self) -> Result<AvailabilityRebuild, DomainError>;
+}
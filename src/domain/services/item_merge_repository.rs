@@ -0,0 +1,26 @@
+use crate::domain::entities::item::Item;
+use crate::domain::entities::item_duplicate::{DuplicateItemPair, ItemMergeCounts};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait ItemMergeRepository: Send + Sync {
+    /// Pairs of the current tenant's active items whose names are at least
+    /// `min_similarity` alike or whose barcodes match once normalized, most
+    /// alike first
+    async fn find_duplicates(
+        &self,
+        min_similarity: f64,
+        limit: i64,
+    ) -> Result<Vec<DuplicateItemPair>, DomainError>;
+
+    /// In one transaction, move `duplicate`'s stock levels, movements, order
+    /// lines and other references onto `survivor`, then save both items as
+    /// given (the duplicate deactivated, the survivor possibly with the
+    /// duplicate's barcode)
+    async fn merge(
+        &self,
+        duplicate: &Item,
+        survivor: &Item,
+    ) -> Result<ItemMergeCounts, DomainError>;
+}
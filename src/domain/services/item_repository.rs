@@ -1,6 +1,9 @@
 use crate::domain::entities::item::Item;
+use crate::domain::entities::list_filter::ListFilter;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
+use rust_decimal::Decimal;
 use uuid::Uuid;
 
 #[async_trait]
@@ -8,9 +11,15 @@ pub trait ItemRepository: Send + Sync {
     /// Find an item by its ID
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Item>, DomainError>;
 
+    /// Find the items with the given IDs in one query; IDs with no item are left out
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Item>, DomainError>;
+
     /// Find an item by its SKU
     async fn find_by_sku(&self, sku: &str) -> Result<Option<Item>, DomainError>;
 
+    /// Find an item by its barcode
+    async fn find_by_barcode(&self, barcode: &str) -> Result<Option<Item>, DomainError>;
+
     /// Save a new item
     async fn save(&self, item: &Item) -> Result<(), DomainError>;
 
@@ -20,11 +29,18 @@ pub trait ItemRepository: Send + Sync {
     /// Delete an item by ID
     async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
 
-    /// List all items with pagination
-    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Item>, DomainError>;
+    /// List items matching the filter, one keyset page at a time
+    async fn list(
+        &self,
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<Item>, DomainError>;
 
-    /// Count total items
-    async fn count(&self) -> Result<i64, DomainError>;
+    /// The variants of a product, by SKU
+    async fn list_by_product(&self, product_id: Uuid) -> Result<Vec<Item>, DomainError>;
+
+    /// Count items matching the filter
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError>;
 
     /// Check if SKU is already taken by another item
     async fn sku_exists(
@@ -32,4 +48,22 @@ pub trait ItemRepository: Send + Sync {
         sku: &str,
         exclude_item_id: Option<Uuid>,
     ) -> Result<bool, DomainError>;
+
+    /// Check if barcode is already taken by another item
+    async fn barcode_exists(
+        &self,
+        barcode: &str,
+        exclude_item_id: Option<Uuid>,
+    ) -> Result<bool, DomainError>;
+
+    /// Reject any quantity finer than its item's precision allows. Items that
+    /// don't exist are left for the caller's own checks to report.
+    async fn check_quantities(&self, quantities: &[(Uuid, Decimal)]) -> Result<(), DomainError> {
+        for (item_id, qty) in quantities {
+            if let Some(item) = self.find_by_id(*item_id).await? {
+                item.check_quantity(*qty)?;
+            }
+        }
+        Ok(())
+    }
 }
@@ -35,4 +35,22 @@ pub trait JobRepository: Send + Sync {
         status: &str,
         limit: i64,
     ) -> Result<Vec<Job>, DomainError>;
+
+    /// Mark up to `limit` runnable jobs of the given types as running, across all
+    /// tenants, and return them. Runnable means queued and due, or running with a
+    /// heartbeat older than `lease_seconds` (its worker died) and attempts left.
+    async fn claim_due_jobs(
+        &self,
+        job_types: &[String],
+        limit: i64,
+        lease_seconds: i64,
+    ) -> Result<Vec<Job>, DomainError>;
+
+    /// Finish running jobs whose worker stopped heartbeating: failed if it was
+    /// their last attempt, cancelled if cancellation had been requested
+    async fn fail_abandoned_jobs(&self, lease_seconds: i64) -> Result<u64, DomainError>;
+
+    /// Record that the worker running a job is still alive. Returns whether the
+    /// job should stop: cancellation was requested or it is no longer running.
+    async fn heartbeat(&self, id: Uuid) -> Result<bool, DomainError>;
 }
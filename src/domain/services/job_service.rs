@@ -1,6 +1,7 @@
 use crate::domain::entities::job::{CreateJobRequest, Job, JobError};
 use crate::shared::error::DomainError;
 use async_trait::async_trait;
+use chrono::Duration;
 use uuid::Uuid;
 
 #[async_trait]
@@ -54,4 +55,39 @@ pub trait JobService: Send + Sync {
         status: &str,
         limit: i64,
     ) -> Result<Vec<Job>, DomainError>;
+
+    /// Claim runnable jobs of the given types for a worker
+    async fn claim_due_jobs(
+        &self,
+        job_types: &[String],
+        limit: i64,
+        lease_seconds: i64,
+    ) -> Result<Vec<Job>, DomainError>;
+
+    /// Finish running jobs abandoned by their worker on their last attempt or
+    /// while being cancelled
+    async fn fail_abandoned_jobs(&self, lease_seconds: i64) -> Result<u64, DomainError>;
+
+    /// Record that the worker running a job is still alive; returns whether the
+    /// job should stop
+    async fn heartbeat(&self, job_id: &str) -> Result<bool, DomainError>;
+
+    /// Record a failed attempt, requeueing the job after `retry_delay` if it has
+    /// attempts left
+    async fn fail_job_attempt(
+        &self,
+        job_id: &str,
+        errors: Vec<JobError>,
+        retry_delay: Duration,
+    ) -> Result<(), DomainError>;
+
+    /// Cancel a tenant's job: queued jobs stop at once, running ones at their
+    /// handler's next cancellation check
+    async fn cancel_job(&self, tenant_id: Uuid, job_id: &str) -> Result<Job, DomainError>;
+
+    /// Queue a tenant's failed or cancelled job to run again
+    async fn retry_job(&self, tenant_id: Uuid, job_id: &str) -> Result<Job, DomainError>;
+
+    /// Record that a running job stopped because it was cancelled
+    async fn mark_job_cancelled(&self, job_id: &str) -> Result<(), DomainError>;
 }
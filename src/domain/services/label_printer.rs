@@ -0,0 +1,12 @@
+use crate::domain::entities::printing::Printer;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+
+/// Delivers rendered labels straight to a networked printer, so devices on
+/// the warehouse floor don't need print drivers of their own
+#[async_trait]
+pub trait LabelPrinter: Send + Sync {
+    /// Send raw printer language (ZPL) to the printer. An unreachable printer
+    /// is an `InfrastructureError`, which the print job retries.
+    async fn send(&self, printer: &Printer, payload: &[u8]) -> Result<(), DomainError>;
+}
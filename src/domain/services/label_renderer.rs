@@ -0,0 +1,60 @@
+use crate::shared::error::DomainError;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelFormat {
+    #[default]
+    Zpl,
+    Pdf,
+}
+
+impl LabelFormat {
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s.to_lowercase().as_str() {
+            "zpl" => Ok(LabelFormat::Zpl),
+            "pdf" => Ok(LabelFormat::Pdf),
+            _ => Err(DomainError::ValidationError(
+                format!("Invalid label format: {}. Must be one of: zpl, pdf", s).into(),
+            )),
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            LabelFormat::Zpl => "application/zpl",
+            LabelFormat::Pdf => "application/pdf",
+        }
+    }
+}
+
+/// Everything printed on one GS1-128 carton label
+#[derive(Debug, Clone, Serialize)]
+pub struct ShippingLabel {
+    pub sscc: String,
+    pub ship_from: Vec<String>,
+    pub ship_to: Vec<String>,
+    pub carrier: Option<String>,
+    pub tracking_number: Option<String>,
+    pub shipment_number: String,
+    /// The order or transfer number the shipment fulfils
+    pub reference: Option<String>,
+    pub carton_number: usize,
+    pub carton_count: usize,
+    pub weight_kg: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderedLabels {
+    pub content_type: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+pub trait LabelRenderer: Send + Sync {
+    /// Render the labels in order, one per page or ZPL format block
+    fn render(
+        &self,
+        labels: &[ShippingLabel],
+        format: LabelFormat,
+    ) -> Result<RenderedLabels, DomainError>;
+}
@@ -0,0 +1,17 @@
+use crate::domain::entities::location_capacity::{ItemFootprint, LocationStock};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait LocationCapacityRepository: Send + Sync {
+    /// Active locations with their capacity and the footprint of the stock
+    /// they hold; only the given ones when `location_ids` is set
+    async fn find_location_stock(
+        &self,
+        location_ids: Option<&[Uuid]>,
+    ) -> Result<Vec<LocationStock>, DomainError>;
+
+    /// The footprints of these items; unknown items are left out
+    async fn find_footprints(&self, item_ids: &[Uuid]) -> Result<Vec<ItemFootprint>, DomainError>;
+}
@@ -8,6 +8,9 @@ pub trait LocationRepository: Send + Sync {
     /// Find a location by its ID
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Location>, DomainError>;
 
+    /// Find the locations with the given IDs in one query; IDs with no location are left out
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Location>, DomainError>;
+
     /// Find a location by its code
     async fn find_by_code(&self, code: &str) -> Result<Option<Location>, DomainError>;
 
@@ -1,20 +1,69 @@
 // Domain services will be implemented here
+pub mod address_validator;
+pub mod adjustment_repository;
+pub mod allocation_repository;
+pub mod attachment_repository;
+pub mod barcode_service;
+pub mod blob_storage;
+pub mod connector;
+pub mod currency_service;
+pub mod customer_credit_repository;
+pub mod cycle_count_repository;
+pub mod document_renderer;
+pub mod document_settings_repository;
+pub mod edi_repository;
+pub mod edi_translator;
+pub mod email_sender;
+pub mod event_broadcaster;
+pub mod exchange_rate_provider;
+pub mod exchange_rate_repository;
 pub mod export_service;
+pub mod export_source;
+pub mod forecast_repository;
 pub mod idempotency_repository;
+pub mod inbound_shipment_repository;
+pub mod integration_repository;
+pub mod invitation_repository;
+pub mod invoice_repository;
+pub mod item_availability_repository;
+pub mod item_merge_repository;
 pub mod item_repository;
-pub mod job_processor;
 pub mod job_repository;
 pub mod job_service;
+pub mod label_printer;
+pub mod label_renderer;
+pub mod location_capacity_repository;
 pub mod location_repository;
+pub mod print_repository;
+pub mod product_repository;
 pub mod purchase_order_repository;
+pub mod putaway_rule_repository;
+pub mod quota_service;
+pub mod receiving_settings_repository;
 pub mod report_service;
+pub mod reservation_repository;
 pub mod return_repository;
 pub mod sales_order_repository;
+pub mod sandbox_echo_inbox;
+pub mod sandbox_seed_repository;
 pub mod search_projection;
 pub mod search_repository;
+pub mod session_repository;
+pub mod shipment_repository;
+pub mod snapshot_repository;
+pub mod status_history_repository;
 pub mod stock_repository;
+pub mod tenant_audit_repository;
 pub mod tenant_repository;
+pub mod tenant_snapshot_repository;
+pub mod time_zone_service;
+pub mod totp;
 pub mod transfer_repository;
+pub mod two_factor_repository;
 pub mod user_repository;
+pub mod vendor_return_repository;
 pub mod webhook_dispatcher;
 pub mod webhook_repository;
+pub mod webhook_retention_repository;
+pub mod webhook_signature;
+pub mod webhook_url_policy;
@@ -0,0 +1,35 @@
+use crate::domain::entities::printing::{PrintJob, PrintJobStatus, Printer};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait PrintRepository: Send + Sync {
+    /// Save a new printer; making it its location's default takes that from
+    /// whichever printer had it
+    async fn create_printer(&self, printer: &Printer) -> Result<(), DomainError>;
+    async fn find_printer(&self, id: Uuid) -> Result<Option<Printer>, DomainError>;
+    /// The location's active default printer
+    async fn find_default_printer(&self, location_id: Uuid)
+        -> Result<Option<Printer>, DomainError>;
+    async fn list_printers(&self, location_id: Option<Uuid>) -> Result<Vec<Printer>, DomainError>;
+    async fn update_printer(&self, printer: &Printer) -> Result<(), DomainError>;
+    /// Delete a printer along with its print jobs
+    async fn delete_printer(&self, id: Uuid) -> Result<(), DomainError>;
+
+    async fn create_print_job(&self, job: &PrintJob) -> Result<(), DomainError>;
+    async fn find_print_job(&self, id: Uuid) -> Result<Option<PrintJob>, DomainError>;
+    /// Newest first
+    async fn list_print_jobs(
+        &self,
+        printer_id: Option<Uuid>,
+        status: Option<PrintJobStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PrintJob>, DomainError>;
+    /// Save a print job's background job, status and attempt details
+    async fn update_print_job(&self, job: &PrintJob) -> Result<(), DomainError>;
+    /// Record the background job delivering a print job, leaving the rest of
+    /// it to that job, which may already be running
+    async fn set_print_job_job_id(&self, id: Uuid, job_id: &str) -> Result<(), DomainError>;
+}
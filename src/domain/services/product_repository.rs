@@ -0,0 +1,15 @@
+use crate::domain::entities::product::Product;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait ProductRepository: Send + Sync {
+    async fn create(&self, product: &Product) -> Result<(), DomainError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Product>, DomainError>;
+    async fn parent_sku_exists(&self, parent_sku: &str) -> Result<bool, DomainError>;
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Product>, DomainError>;
+    async fn update(&self, product: &Product) -> Result<(), DomainError>;
+    /// Delete the product, turning its variants into stand-alone items
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
+}
@@ -1,8 +1,15 @@
+use crate::domain::entities::list_filter::ListFilter;
 use crate::domain::entities::purchase_order::{
-    CreatePurchaseOrderRequest, PurchaseOrder, ReceivePurchaseOrderRequest,
+    CreatePurchaseOrderRequest, OverduePurchaseOrderLine, PurchaseOrder, PurchaseOrderReceipt,
+    ReceivePurchaseOrderRequest,
 };
+use crate::domain::entities::receiving::ReceivingSettings;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 #[async_trait]
@@ -25,22 +32,42 @@ pub trait PurchaseOrderRepository: Send + Sync {
     /// Delete a purchase order by ID
     async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
 
-    /// List purchase orders with pagination and optional filtering
+    /// List purchase orders matching the filter, one keyset page at a time
     async fn list(
         &self,
-        limit: i64,
-        offset: i64,
-        status_filter: Option<String>,
-    ) -> Result<Vec<PurchaseOrder>, DomainError>;
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<PurchaseOrder>, DomainError>;
 
-    /// Count total purchase orders
-    async fn count(&self, status_filter: Option<String>) -> Result<i64, DomainError>;
+    /// Count purchase orders matching the filter
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError>;
 
-    /// Receive items for a purchase order (update lines and create stock movements)
+    /// Receive items for a purchase order (update lines and create stock movements),
+    /// holding each line to the supplier's over-receipt tolerance
     async fn receive_purchase_order(
         &self,
         po_id: Uuid,
         request: &ReceivePurchaseOrderRequest,
+        tolerances: &ReceivingSettings,
         user_id: Uuid,
     ) -> Result<Vec<crate::domain::entities::inventory::StockMovement>, DomainError>;
+
+    /// Every receiving session against a purchase order, oldest first
+    async fn list_receipts(&self, po_id: Uuid) -> Result<Vec<PurchaseOrderReceipt>, DomainError>;
+
+    /// Quantity-weighted average cost of everything received for each item, in the
+    /// tenant's base currency. Items with nothing received yet are left out.
+    async fn average_received_costs(
+        &self,
+        item_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Decimal>, DomainError>;
+
+    /// Lines on open orders still short of their ordered quantity whose
+    /// promised (or else expected) date is before `as_of`, most overdue first.
+    /// `supplier_id` limits them to one supplier's orders.
+    async fn overdue_lines(
+        &self,
+        as_of: DateTime<Utc>,
+        supplier_id: Option<Uuid>,
+    ) -> Result<Vec<OverduePurchaseOrderLine>, DomainError>;
 }
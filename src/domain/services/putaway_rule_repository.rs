@@ -0,0 +1,16 @@
+use crate::domain::entities::putaway::{PutawayCandidate, PutawayRule};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait PutawayRuleRepository: Send + Sync {
+    async fn create(&self, rule: &PutawayRule) -> Result<(), DomainError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PutawayRule>, DomainError>;
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<PutawayRule>, DomainError>;
+    async fn update(&self, rule: &PutawayRule) -> Result<(), DomainError>;
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
+    /// Active rules matching the item (by id, category or catch-all) at active locations,
+    /// with the item's and the location's current on-hand quantity
+    async fn find_candidates(&self, item_id: Uuid) -> Result<Vec<PutawayCandidate>, DomainError>;
+}
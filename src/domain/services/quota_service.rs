@@ -0,0 +1,44 @@
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Tenant resources capped by the `max_*` limits in `tenant_quotas`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaResource {
+    Items,
+    Locations,
+    Webhooks,
+}
+
+impl QuotaResource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuotaResource::Items => "items",
+            QuotaResource::Locations => "locations",
+            QuotaResource::Webhooks => "webhooks",
+        }
+    }
+}
+
+/// Request rate overrides from `tenant_quotas`; `None` keeps the default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitOverrides {
+    pub requests_per_minute: Option<i64>,
+    pub requests_per_minute_per_api_key: Option<i64>,
+}
+
+#[async_trait]
+pub trait QuotaService: Send + Sync {
+    /// Count one more `resource` against the tenant's quota, or fail with
+    /// `QuotaExceeded` if the tenant is already at its limit
+    async fn reserve(&self, tenant_id: Uuid, resource: QuotaResource) -> Result<(), DomainError>;
+
+    /// Give back a reservation, after a failed creation or a hard delete
+    async fn release(&self, tenant_id: Uuid, resource: QuotaResource) -> Result<(), DomainError>;
+
+    /// The tenant's `max_api_calls_per_hour` allowance
+    async fn api_calls_per_hour(&self, tenant_id: Uuid) -> Result<i64, DomainError>;
+
+    /// The tenant's per-minute request limits, if set
+    async fn rate_limits(&self, tenant_id: Uuid) -> Result<RateLimitOverrides, DomainError>;
+}
@@ -0,0 +1,19 @@
+use crate::domain::entities::receiving::ReceivingSettings;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait ReceivingSettingsRepository: Send + Sync {
+    /// The current tenant's tolerances, zero where none have been set
+    async fn find(&self) -> Result<ReceivingSettings, DomainError>;
+    async fn save_default(&self, over_receipt_percent: Decimal) -> Result<(), DomainError>;
+    async fn save_supplier(
+        &self,
+        supplier_id: Uuid,
+        over_receipt_percent: Decimal,
+    ) -> Result<(), DomainError>;
+    /// Whether the supplier had a tolerance of its own to remove
+    async fn delete_supplier(&self, supplier_id: Uuid) -> Result<bool, DomainError>;
+}
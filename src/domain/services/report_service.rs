@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::domain::entities::{inventory::StockLevel, item::Item};
+use rust_decimal::Decimal;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LowStockReportItem {
@@ -13,14 +14,14 @@ pub struct LowStockReportItem {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StockValuationReportItem {
     pub item: Item,
-    pub valuation: f64,
+    pub valuation: Decimal,
 }
 
 #[async_trait]
 pub trait ReportService: Send + Sync {
     async fn generate_low_stock_report(
         &self,
-        threshold: i32,
+        threshold: Decimal,
         limit: i64,
         cursor: Option<String>,
     ) -> Result<LowStockReportResponse, String>;
@@ -0,0 +1,28 @@
+use crate::domain::entities::inventory::StockLevel;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// Soft reservations held against `stock_levels.quantity_reserved`
+#[async_trait]
+pub trait ReservationRepository: Send + Sync {
+    /// Reserve stock, failing with `BusinessLogicError` when available-to-promise is short
+    async fn reserve(
+        &self,
+        item_id: Uuid,
+        location_id: Uuid,
+        quantity: Decimal,
+    ) -> Result<StockLevel, DomainError>;
+    async fn release(
+        &self,
+        item_id: Uuid,
+        location_id: Uuid,
+        quantity: Decimal,
+    ) -> Result<StockLevel, DomainError>;
+    async fn get_available_to_promise(
+        &self,
+        item_id: Uuid,
+        location_id: Uuid,
+    ) -> Result<Decimal, DomainError>;
+}
@@ -1,11 +1,17 @@
 use crate::domain::entities::inventory::StockMovement;
-use crate::domain::entities::returns::{ProcessReturnRequest, Return, ReturnLine};
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::returns::{ProcessReturnRequest, Return, ReturnLine, ScrapTotal};
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 #[async_trait]
 pub trait ReturnRepository: Send + Sync {
+    /// Lines naming a sales order line are checked against it, with the
+    /// order lines locked so concurrent returns can't together exceed what
+    /// was shipped
     async fn create(&self, return_entity: &Return) -> Result<(), DomainError>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<(Return, Vec<ReturnLine>)>, DomainError>;
     async fn find_by_return_number(
@@ -16,8 +22,15 @@ pub trait ReturnRepository: Send + Sync {
     async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
     async fn list(
         &self,
-        limit: i64,
-        offset: i64,
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<(Return, Vec<ReturnLine>)>, DomainError>;
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError>;
+    /// Returns with a line against the sales order, oldest first. Fails with
+    /// NotFound when there's no such order.
+    async fn find_by_sales_order(
+        &self,
+        sales_order_id: Uuid,
     ) -> Result<Vec<(Return, Vec<ReturnLine>)>, DomainError>;
     async fn open_return(&self, id: Uuid) -> Result<(Return, Vec<ReturnLine>), DomainError>;
     async fn process_return(
@@ -26,4 +39,13 @@ pub trait ReturnRepository: Send + Sync {
         process_request: ProcessReturnRequest,
         created_by: Uuid,
     ) -> Result<(Return, Vec<ReturnLine>, Vec<StockMovement>), DomainError>;
+
+    /// Scrapped quantities per item over lines processed in `[from, to)`,
+    /// optionally at one location
+    async fn scrap_totals(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        location_id: Option<Uuid>,
+    ) -> Result<Vec<ScrapTotal>, DomainError>;
 }
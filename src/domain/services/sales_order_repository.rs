@@ -1,7 +1,11 @@
+use crate::domain::entities::list_filter::ListFilter;
 use crate::domain::entities::sales_order::{
-    SalesOrder, SalesOrderLine, ShipLineRequest, StockMovement,
+    SalesOrder, SalesOrderBackorder, SalesOrderLine, ShipLineRequest, ShippedSalesOrder,
+    StockMovement,
 };
+use crate::domain::entities::shipment::Shipment;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -20,18 +24,38 @@ pub trait SalesOrderRepository: Send + Sync {
     async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
     async fn list(
         &self,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<(SalesOrder, Vec<SalesOrderLine>)>, DomainError>;
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<(SalesOrder, Vec<SalesOrderLine>)>, DomainError>;
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError>;
+    /// Ship the lines in one transaction: the order and its lines, the stock
+    /// movements and levels, the shipment record and the outbox events
+    /// announcing it all commit together or not at all
     async fn ship_sales_order(
         &self,
         id: Uuid,
         shipped_lines: Vec<ShipLineRequest>,
+        allow_backorder: bool,
+        shipment: Shipment,
         created_by: Uuid,
-    ) -> Result<(SalesOrder, Vec<SalesOrderLine>, Vec<StockMovement>), DomainError>;
+    ) -> Result<ShippedSalesOrder, DomainError>;
+    /// Split the unshipped remainder into a new order, returning (original, backorder order, link)
+    async fn create_backorder(
+        &self,
+        id: Uuid,
+        so_number: String,
+        created_by: Uuid,
+    ) -> Result<(SalesOrder, SalesOrder, SalesOrderBackorder), DomainError>;
     async fn reserve_inventory(
         &self,
         id: Uuid,
         created_by: Uuid,
     ) -> Result<Vec<StockMovement>, DomainError>;
+    /// Cancel the order, releasing its reservations and dropping its allocations
+    async fn cancel_sales_order(
+        &self,
+        id: Uuid,
+        reason: Option<String>,
+        cancelled_by: Uuid,
+    ) -> Result<(SalesOrder, Vec<StockMovement>), DomainError>;
 }
@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Deliveries kept per sandbox; older ones are dropped first
+pub const DEFAULT_ECHO_INBOX_SIZE: usize = 50;
+
+/// A webhook delivery the echo receiver got
+#[derive(Debug, Clone, Serialize)]
+pub struct EchoDelivery {
+    pub id: Uuid,
+    pub received_at: DateTime<Utc>,
+    /// The `X-TWH-Signature` header, for checking a receiver's verification code against
+    pub signature: Option<String>,
+    pub body: serde_json::Value,
+}
+
+/// In-process inbox of the deliveries each sandbox's seeded webhook sent to the
+/// built-in echo receiver, so evaluators can see events arrive without hosting
+/// an endpoint of their own. Contents are lost on restart.
+#[derive(Clone)]
+pub struct SandboxEchoInbox {
+    capacity: usize,
+    deliveries: Arc<Mutex<HashMap<Uuid, VecDeque<EchoDelivery>>>>,
+}
+
+impl SandboxEchoInbox {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            deliveries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn record(&self, tenant_id: Uuid, delivery: EchoDelivery) {
+        let mut deliveries = self.deliveries.lock().unwrap_or_else(|e| e.into_inner());
+        let inbox = deliveries.entry(tenant_id).or_default();
+        if inbox.len() == self.capacity {
+            inbox.pop_front();
+        }
+        inbox.push_back(delivery);
+    }
+
+    /// Forget the tenant's deliveries, e.g. once its sandbox is reset
+    pub fn clear(&self, tenant_id: Uuid) {
+        let mut deliveries = self.deliveries.lock().unwrap_or_else(|e| e.into_inner());
+        deliveries.remove(&tenant_id);
+    }
+
+    /// The tenant's deliveries, newest first
+    pub fn list(&self, tenant_id: Uuid) -> Vec<EchoDelivery> {
+        let deliveries = self.deliveries.lock().unwrap_or_else(|e| e.into_inner());
+        deliveries
+            .get(&tenant_id)
+            .map(|inbox| inbox.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for SandboxEchoInbox {
+    fn default() -> Self {
+        Self::new(DEFAULT_ECHO_INBOX_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delivery(n: i64) -> EchoDelivery {
+        EchoDelivery {
+            id: Uuid::new_v4(),
+            received_at: Utc::now(),
+            signature: None,
+            body: serde_json::json!({ "n": n }),
+        }
+    }
+
+    #[test]
+    fn test_keeps_the_latest_deliveries_per_tenant() {
+        let inbox = SandboxEchoInbox::new(2);
+        let (tenant, other) = (Uuid::new_v4(), Uuid::new_v4());
+        for n in 1..=3 {
+            inbox.record(tenant, delivery(n));
+        }
+        inbox.record(other, delivery(9));
+
+        let bodies: Vec<_> = inbox
+            .list(tenant)
+            .into_iter()
+            .map(|d| d.body["n"].clone())
+            .collect();
+        assert_eq!(bodies, vec![3, 2]);
+        assert_eq!(inbox.list(other).len(), 1);
+    }
+}
@@ -0,0 +1,24 @@
+use crate::domain::entities::sandbox_seed::{SeedDataset, SeedRecord};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait SandboxSeedRepository: Send + Sync {
+    /// Write the dataset into the current tenant in one transaction, raising the
+    /// tenant's quotas to make room for it and indexing it for search
+    async fn seed(&self, dataset: &SeedDataset, record: &SeedRecord) -> Result<(), DomainError>;
+
+    /// Delete everything the current tenant owns apart from its users, then seed
+    /// it as `seed` does, all in one transaction. The quota headroom the previous
+    /// seed added is handed back first.
+    async fn reseed(&self, dataset: &SeedDataset, record: &SeedRecord) -> Result<(), DomainError>;
+
+    /// How the current tenant was last seeded
+    async fn find_record(&self) -> Result<Option<SeedRecord>, DomainError>;
+
+    /// Delete the current tenant's transactions: orders, stock and its movements,
+    /// shipments, returns, transfers, counts, jobs and webhook deliveries. Its
+    /// catalogue, locations, users, quotas and settings stay, as do webhooks other
+    /// than those delivering under `echo_url`. The seed record goes too.
+    async fn clear_transactions(&self, echo_url: &str) -> Result<(), DomainError>;
+}
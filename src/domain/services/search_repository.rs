@@ -1,5 +1,6 @@
 use crate::domain::entities::search::{
-    SearchIndex, SearchIndexRequest, SearchQuery, SearchResult, SearchResultItem,
+    SearchIndex, SearchIndexHealth, SearchIndexRequest, SearchQuery, SearchResult,
+    SearchResultItem, SuggestQuery, Suggestion,
 };
 use crate::shared::error::DomainError;
 use async_trait::async_trait;
@@ -16,9 +17,13 @@ pub trait SearchRepository: Send + Sync {
         entity_id: uuid::Uuid,
     ) -> Result<(), DomainError>;
 
-    /// Search documents using full-text search
+    /// Search documents using full-text search, plus trigram matching so
+    /// misspelt names and codes still turn up
     async fn search(&self, query: SearchQuery) -> Result<SearchResult, DomainError>;
 
+    /// Documents whose names or codes resemble the text, most similar first
+    async fn suggest(&self, query: SuggestQuery) -> Result<Vec<Suggestion>, DomainError>;
+
     /// Get a specific search document
     async fn get_document(
         &self,
@@ -26,9 +31,25 @@ pub trait SearchRepository: Send + Sync {
         entity_id: uuid::Uuid,
     ) -> Result<Option<SearchIndex>, DomainError>;
 
-    /// Rebuild search index for all entities (expensive operation)
+    /// Re-read items from their tables and bring their documents, and those of
+    /// their stock levels, up to date. Inactive or missing items are dropped.
+    async fn index_items(&self, item_ids: &[uuid::Uuid]) -> Result<u64, DomainError>;
+
+    /// Same as `index_items`, for locations
+    async fn index_locations(&self, location_ids: &[uuid::Uuid]) -> Result<u64, DomainError>;
+
+    /// Upsert every document, across tenants, that is missing or older than its
+    /// source rows and drop those whose source is gone. Returns documents touched.
+    async fn sync_stale_documents(&self) -> Result<u64, DomainError>;
+
+    /// Drop the current tenant's documents and index all its items, locations
+    /// and stock levels again (expensive operation)
     async fn rebuild_index(&self) -> Result<i64, DomainError>;
 
     /// Clean up orphaned search documents
     async fn cleanup_orphaned_documents(&self) -> Result<i64, DomainError>;
+
+    /// Whether the backend is up and how far the current tenant's documents
+    /// lag behind their source
+    async fn health(&self) -> Result<SearchIndexHealth, DomainError>;
 }
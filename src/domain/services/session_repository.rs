@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::session::Session;
+use crate::shared::error::DomainError;
+
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    /// Record a new login
+    async fn save(&self, session: &Session) -> Result<(), DomainError>;
+
+    /// Find a session by ID, whether or not it is still active
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Session>, DomainError>;
+
+    /// The user's sessions that are neither revoked nor expired, newest first
+    async fn list_active(&self, user_id: Uuid) -> Result<Vec<Session>, DomainError>;
+
+    /// Revoke one of the user's sessions. Returns `false` if the user has no
+    /// such active session.
+    async fn revoke(&self, user_id: Uuid, id: Uuid) -> Result<bool, DomainError>;
+
+    /// Revoke all of the user's active sessions, logging them out everywhere
+    async fn revoke_all(&self, user_id: Uuid) -> Result<(), DomainError>;
+
+    /// Lift the enroll-only restriction from the user's sessions once they
+    /// have confirmed two-factor login
+    async fn clear_two_factor_enrollment(&self, user_id: Uuid) -> Result<(), DomainError>;
+}
+
+#[cfg(test)]
+use mockall::mock;
+
+#[cfg(test)]
+mock! {
+    pub SessionRepository {}
+
+    #[async_trait]
+    impl SessionRepository for SessionRepository {
+        async fn save(&self, session: &Session) -> Result<(), DomainError>;
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Session>, DomainError>;
+        async fn list_active(&self, user_id: Uuid) -> Result<Vec<Session>, DomainError>;
+        async fn revoke(&self, user_id: Uuid, id: Uuid) -> Result<bool, DomainError>;
+        async fn revoke_all(&self, user_id: Uuid) -> Result<(), DomainError>;
+        async fn clear_two_factor_enrollment(&self, user_id: Uuid) -> Result<(), DomainError>;
+    }
+}
@@ -0,0 +1,30 @@
+use crate::domain::entities::gs1::SsccSequence;
+use crate::domain::entities::shipment::{Shipment, ShipmentCarton, ShipmentSourceType};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait ShipmentRepository: Send + Sync {
+    async fn create(&self, shipment: &Shipment) -> Result<(), DomainError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Shipment>, DomainError>;
+    async fn find_by_source(
+        &self,
+        source_type: &ShipmentSourceType,
+        source_id: Uuid,
+    ) -> Result<Vec<Shipment>, DomainError>;
+    async fn find_by_tracking_number(
+        &self,
+        tracking_number: &str,
+    ) -> Result<Vec<Shipment>, DomainError>;
+    async fn update_tracking(&self, shipment: &Shipment) -> Result<(), DomainError>;
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Shipment>, DomainError>;
+    async fn find_sscc_sequence(&self) -> Result<Option<SsccSequence>, DomainError>;
+    async fn save_sscc_sequence(&self, sequence: &SsccSequence) -> Result<(), DomainError>;
+    /// Open a carton per entry, allocating SSCCs from the tenant's sequence
+    async fn create_cartons(
+        &self,
+        shipment_id: Uuid,
+        package_ids: &[Option<Uuid>],
+    ) -> Result<Vec<ShipmentCarton>, DomainError>;
+}
@@ -0,0 +1,39 @@
+use crate::domain::entities::stock_snapshot::{StockQuantity, StockQuantityFilter};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[async_trait]
+pub trait SnapshotRepository: Send + Sync {
+    /// Copy the current stock levels of every tenant whose local clock has reached
+    /// `local_hour` at `taken_at` into a snapshot stamped `taken_at` and dated by
+    /// the tenant's calendar. At most one snapshot is kept per tenant per local
+    /// day; returns the rows written.
+    async fn create_snapshot(
+        &self,
+        taken_at: DateTime<Utc>,
+        local_hour: u32,
+    ) -> Result<u64, DomainError>;
+
+    /// Most recent snapshot taken strictly before `before`
+    async fn latest_snapshot_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>, DomainError>;
+
+    /// Quantities recorded by the snapshot taken at `taken_at`
+    async fn get_snapshot_quantities(
+        &self,
+        taken_at: DateTime<Utc>,
+        filter: &StockQuantityFilter,
+    ) -> Result<Vec<StockQuantity>, DomainError>;
+
+    /// Net movement per item and location with `after < created_at < before`,
+    /// or everything before `before` when `after` is `None`
+    async fn get_movement_totals(
+        &self,
+        after: Option<DateTime<Utc>>,
+        before: DateTime<Utc>,
+        filter: &StockQuantityFilter,
+    ) -> Result<Vec<StockQuantity>, DomainError>;
+}
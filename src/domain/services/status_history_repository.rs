@@ -0,0 +1,15 @@
+use crate::domain::entities::status_history::{StatusChange, StatusHistoryEntity};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait StatusHistoryRepository: Send + Sync {
+    /// The entity's status changes, oldest first; `None` if the current tenant
+    /// has no such entity
+    async fn find_history(
+        &self,
+        entity: StatusHistoryEntity,
+        entity_id: Uuid,
+    ) -> Result<Option<Vec<StatusChange>>, DomainError>;
+}
@@ -1,19 +1,35 @@
-use crate::domain::entities::inventory::{StockLevel, StockMovement};
+use crate::domain::entities::export::StockMovementExportFilter;
+use crate::domain::entities::inventory::{DeadStockItem, StockLevel, StockMovement};
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::pin::Pin;
+use tokio_stream::Stream;
 use uuid::Uuid;
 
-#[derive(Debug, Clone)]
-pub struct PaginatedStockLevels {
-    pub items: Vec<StockLevel>,
-    pub next_cursor: Option<String>,
-}
+/// Movements in `created_at` order, a batch at a time, for exports too large to buffer
+pub type StockMovementBatches =
+    Pin<Box<dyn Stream<Item = Result<Vec<StockMovement>, DomainError>> + Send>>;
 
 #[async_trait]
 pub trait StockRepository: Send + Sync {
     /// Record a new stock movement and update stock levels atomically
     async fn record_movement(&self, movement: &StockMovement) -> Result<(), DomainError>;
 
+    /// Record a batch of movements and update the stock levels they touch in one
+    /// transaction; either all of them apply or none do
+    async fn record_movements(&self, movements: &[StockMovement]) -> Result<(), DomainError>;
+
+    /// Record both movements of a status change atomically, returning the
+    /// resulting stock level
+    async fn record_status_change(
+        &self,
+        out_of_status: &StockMovement,
+        into_status: &StockMovement,
+    ) -> Result<StockLevel, DomainError>;
+
     /// Get stock level for a specific item and location
     async fn get_stock_level(
         &self,
@@ -30,36 +46,59 @@ pub trait StockRepository: Send + Sync {
         location_id: Uuid,
     ) -> Result<Vec<StockLevel>, DomainError>;
 
-    /// Get stock movements for an item with pagination
+    /// Get the stock levels of several items in one query, by item then location
+    async fn get_stock_levels_for_items(
+        &self,
+        item_ids: &[Uuid],
+    ) -> Result<Vec<StockLevel>, DomainError>;
+
+    /// Get the stock levels of several (item, location) pairs in one query;
+    /// pairs with no stock level are left out
+    async fn get_stock_levels_for_pairs(
+        &self,
+        pairs: &[(Uuid, Uuid)],
+    ) -> Result<Vec<StockLevel>, DomainError>;
+
+    /// Get the stock levels of several locations in one query, by location then item
+    async fn get_stock_levels_for_locations(
+        &self,
+        location_ids: &[Uuid],
+    ) -> Result<Vec<StockLevel>, DomainError>;
+
+    /// Get stock movements for an item with keyset pagination
     async fn get_item_movements(
         &self,
         item_id: Uuid,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<StockMovement>, DomainError>;
+        page: &PageRequest,
+    ) -> Result<Page<StockMovement>, DomainError>;
 
-    /// Get stock movements for a location with pagination
+    /// Get stock movements for a location with keyset pagination
     async fn get_location_movements(
         &self,
         location_id: Uuid,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<StockMovement>, DomainError>;
+        page: &PageRequest,
+    ) -> Result<Page<StockMovement>, DomainError>;
 
     /// Get stock movements for a specific item at a specific location
     async fn get_stock_movements(
         &self,
         item_id: Uuid,
         location_id: Uuid,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<StockMovement>, DomainError>;
+        page: &PageRequest,
+    ) -> Result<Page<StockMovement>, DomainError>;
+
+    /// Stream every movement matching the filter, oldest first, `batch_size` rows at a time
+    fn stream_movements(
+        &self,
+        filter: &StockMovementExportFilter,
+        batch_size: i64,
+    ) -> StockMovementBatches;
 
     /// Get a specific stock movement by ID
     async fn get_movement_by_id(&self, id: Uuid) -> Result<Option<StockMovement>, DomainError>;
 
     /// Get total quantity on hand for an item across all locations
-    async fn get_total_quantity_on_hand(&self, item_id: Uuid) -> Result<i32, DomainError>;
+    async fn get_total_quantity_on_hand(&self, item_id: Uuid) -> Result<Decimal, DomainError>;
 
     /// Initialize stock level for a new item/location combination
     async fn initialize_stock_level(
@@ -78,23 +117,29 @@ pub trait StockRepository: Send + Sync {
     /// Get stock levels below a threshold for low stock report
     async fn get_stock_levels_below_threshold(
         &self,
-        threshold: i32,
-        limit: i64,
-        cursor: Option<String>,
-    ) -> Result<PaginatedStockLevels, DomainError>;
+        threshold: Decimal,
+        page: &PageRequest,
+    ) -> Result<Page<StockLevel>, DomainError>;
 
-    /// Get stock levels by location with pagination
+    /// Get stock levels by location with keyset pagination
     async fn get_stock_levels_by_location(
         &self,
         location_id: Uuid,
-        limit: i64,
-        cursor: Option<String>,
-    ) -> Result<PaginatedStockLevels, DomainError>;
+        page: &PageRequest,
+    ) -> Result<Page<StockLevel>, DomainError>;
+
+    /// Items with stock on hand and no outbound movement since `since`,
+    /// highest value first. `location_id` limits both stock and movements to
+    /// one location.
+    async fn dead_stock(
+        &self,
+        since: DateTime<Utc>,
+        location_id: Option<Uuid>,
+    ) -> Result<Vec<DeadStockItem>, DomainError>;
 
-    /// Get all stock levels with pagination
+    /// Get all stock levels with keyset pagination
     async fn get_all_stock_levels(
         &self,
-        limit: i64,
-        cursor: Option<String>,
-    ) -> Result<PaginatedStockLevels, DomainError>;
+        page: &PageRequest,
+    ) -> Result<Page<StockLevel>, DomainError>;
 }
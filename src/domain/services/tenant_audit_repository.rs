@@ -0,0 +1,16 @@
+use crate::domain::entities::tenant_audit::TenantAuditEntry;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait TenantAuditRepository: Send + Sync {
+    async fn record(&self, entry: &TenantAuditEntry) -> Result<(), DomainError>;
+
+    /// The tenant's audit entries, newest first
+    async fn list_for_tenant(
+        &self,
+        tenant_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<TenantAuditEntry>, DomainError>;
+}
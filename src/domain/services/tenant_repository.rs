@@ -18,6 +18,9 @@ pub trait TenantRepository: Send + Sync {
     /// Update tenant status
     async fn update_tenant_status(&self, tenant_id: Uuid, status: &str) -> Result<(), DomainError>;
 
+    /// Save a tenant's name, type, tier, status and expiry
+    async fn update_tenant(&self, tenant: &Tenant) -> Result<(), DomainError>;
+
     /// Delete tenant (mark as deleting)
     async fn delete_tenant(&self, tenant_id: Uuid) -> Result<(), DomainError>;
 
@@ -32,6 +35,30 @@ pub trait TenantRepository: Send + Sync {
         &self,
         tenant_id: Uuid,
     ) -> Result<Option<crate::domain::entities::tenant::TenantTier>, DomainError>;
+
+    /// Get the currency the tenant reports in
+    async fn get_base_currency(&self, tenant_id: Uuid) -> Result<Option<String>, DomainError>;
+
+    /// Change the tenant's base currency. Refused, returning false, once any of its
+    /// orders is priced in another currency, since their rates point at the old one.
+    async fn set_base_currency(&self, tenant_id: Uuid, currency: &str)
+        -> Result<bool, DomainError>;
+
+    /// Get the IANA time zone the tenant's reports and snapshots follow
+    async fn get_timezone(&self, tenant_id: Uuid) -> Result<Option<String>, DomainError>;
+
+    /// Change the tenant's time zone
+    async fn set_timezone(&self, tenant_id: Uuid, timezone: &str) -> Result<(), DomainError>;
+
+    /// Whether the tenant requires its admins to log in with two factors
+    async fn get_admin_two_factor_required(&self, tenant_id: Uuid) -> Result<bool, DomainError>;
+
+    /// Require, or stop requiring, two-factor login for the tenant's admins
+    async fn set_admin_two_factor_required(
+        &self,
+        tenant_id: Uuid,
+        required: bool,
+    ) -> Result<(), DomainError>;
 }
 
 #[cfg(test)]
@@ -47,9 +74,16 @@ mock! {
         async fn get_tenant(&self, tenant_id: Uuid) -> Result<Option<Tenant>, DomainError>;
         async fn list_tenants(&self) -> Result<Vec<Tenant>, DomainError>;
         async fn update_tenant_status(&self, tenant_id: Uuid, status: &str) -> Result<(), DomainError>;
+        async fn update_tenant(&self, tenant: &Tenant) -> Result<(), DomainError>;
         async fn delete_tenant(&self, tenant_id: Uuid) -> Result<(), DomainError>;
         async fn get_expired_sandboxes(&self) -> Result<Vec<Tenant>, DomainError>;
         async fn permanently_delete_tenant(&self, tenant_id: Uuid) -> Result<(), DomainError>;
         async fn get_tenant_tier(&self, tenant_id: Uuid) -> Result<Option<crate::domain::entities::tenant::TenantTier>, DomainError>;
+        async fn get_base_currency(&self, tenant_id: Uuid) -> Result<Option<String>, DomainError>;
+        async fn set_base_currency(&self, tenant_id: Uuid, currency: &str) -> Result<bool, DomainError>;
+        async fn get_timezone(&self, tenant_id: Uuid) -> Result<Option<String>, DomainError>;
+        async fn set_timezone(&self, tenant_id: Uuid, timezone: &str) -> Result<(), DomainError>;
+        async fn get_admin_two_factor_required(&self, tenant_id: Uuid) -> Result<bool, DomainError>;
+        async fn set_admin_two_factor_required(&self, tenant_id: Uuid, required: bool) -> Result<(), DomainError>;
     }
 }
@@ -0,0 +1,24 @@
+use crate::domain::entities::tenant_snapshot::TenantSnapshot;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait TenantSnapshotRepository: Send + Sync {
+    /// Every row the current tenant owns in the snapshot tables, read in one
+    /// transaction so the tables agree with each other
+    async fn dump(&self) -> Result<TenantSnapshot, DomainError>;
+
+    /// Whether the current tenant owns any rows in the snapshot tables
+    async fn has_data(&self) -> Result<bool, DomainError>;
+
+    /// Insert a snapshot's rows into the current tenant in one transaction,
+    /// keeping their ids. `created_by` columns naming users this environment
+    /// doesn't have are set to `restored_by`. Fails with `Conflict` if the tenant
+    /// already owns data and `QuotaExceeded` if the rows don't fit its quotas.
+    async fn restore(
+        &self,
+        snapshot: &TenantSnapshot,
+        restored_by: Uuid,
+    ) -> Result<(), DomainError>;
+}
@@ -0,0 +1,43 @@
+use crate::domain::entities::time_zone::{local_date, parse_time_zone, DEFAULT_TIME_ZONE};
+use crate::domain::services::tenant_repository::TenantRepository;
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::current_tenant;
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use chrono_tz::Tz;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait TimeZoneService: Send + Sync {
+    /// The current tenant's time zone
+    async fn time_zone(&self) -> Result<Tz, DomainError>;
+
+    /// Today's date on the current tenant's calendar
+    async fn today(&self) -> Result<NaiveDate, DomainError> {
+        Ok(local_date(self.time_zone().await?, Utc::now()))
+    }
+}
+
+pub struct TimeZoneServiceImpl {
+    tenant_repository: Arc<dyn TenantRepository>,
+}
+
+impl TimeZoneServiceImpl {
+    pub fn new(tenant_repository: Arc<dyn TenantRepository>) -> Self {
+        Self { tenant_repository }
+    }
+}
+
+#[async_trait]
+impl TimeZoneService for TimeZoneServiceImpl {
+    async fn time_zone(&self) -> Result<Tz, DomainError> {
+        let tenant_id = current_tenant()
+            .ok_or_else(|| DomainError::ValidationError("No tenant is in scope".into()))?;
+        let name = self
+            .tenant_repository
+            .get_timezone(tenant_id)
+            .await?
+            .unwrap_or_else(|| DEFAULT_TIME_ZONE.to_string());
+        parse_time_zone(&name)
+    }
+}
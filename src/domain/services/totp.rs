@@ -0,0 +1,169 @@
+//! Time-based one-time passwords (RFC 6238) as authenticator apps compute them:
+//! HMAC-SHA1, six digits, a new code every 30 seconds.
+
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use uuid::Uuid;
+
+pub const DIGITS: u32 = 6;
+pub const PERIOD_SECS: i64 = 30;
+
+/// Codes from one step either side of the server's clock are accepted, so a
+/// phone that is a little fast or slow still works
+const SKEW_STEPS: i64 = 1;
+
+const SECRET_BYTES: usize = 20;
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// A new random shared secret, base32 encoded the way authenticator apps take it
+pub fn generate_secret() -> String {
+    let bytes: Vec<u8> = [Uuid::new_v4(), Uuid::new_v4()]
+        .iter()
+        .flat_map(|id| *id.as_bytes())
+        .take(SECRET_BYTES)
+        .collect();
+    base32_encode(&bytes)
+}
+
+/// `otpauth://` URI an authenticator app can import, usually from a QR code
+pub fn provisioning_uri(issuer: &str, account: &str, secret: &str) -> String {
+    format!(
+        "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={DIGITS}&period={PERIOD_SECS}",
+        issuer = percent_encode(issuer),
+        account = percent_encode(account),
+    )
+}
+
+/// The time step `code` belongs to at `unix_time`, if it is a valid code for
+/// `secret`. Callers remember the step so the same code can't be replayed.
+pub fn matching_step(secret: &str, code: &str, unix_time: i64) -> Option<i64> {
+    let key = base32_decode(secret)?;
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let current = unix_time.div_euclid(PERIOD_SECS);
+    (current - SKEW_STEPS..=current + SKEW_STEPS)
+        .find(|&step| constant_time_eq(code_at(&key, step).as_bytes(), code.as_bytes()))
+}
+
+fn code_at(key: &[u8], step: i64) -> String {
+    let mut mac =
+        <Hmac<Sha1> as KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(&step.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    // Dynamic truncation: four bytes at the offset named by the last nibble
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let value = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    format!(
+        "{:0width$}",
+        value % 10u32.pow(DIGITS),
+        width = DIGITS as usize
+    )
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    encoded
+}
+
+fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut decoded = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in text.chars().filter(|c| *c != '=' && !c.is_whitespace()) {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&symbol| symbol as char == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(decoded)
+}
+
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The SHA1 secret from RFC 6238's test vectors
+    fn rfc_secret() -> String {
+        base32_encode(b"12345678901234567890")
+    }
+
+    #[test]
+    fn test_codes_match_rfc_6238_vectors() {
+        let key = base32_decode(&rfc_secret()).unwrap();
+        assert_eq!(key, b"12345678901234567890");
+        assert_eq!(code_at(&key, 59 / PERIOD_SECS), "287082");
+        assert_eq!(code_at(&key, 1111111109 / PERIOD_SECS), "081804");
+        assert_eq!(code_at(&key, 2000000000 / PERIOD_SECS), "279037");
+    }
+
+    #[test]
+    fn test_accepts_one_step_of_drift() {
+        let secret = rfc_secret();
+        assert_eq!(matching_step(&secret, "287082", 59), Some(1));
+        assert_eq!(matching_step(&secret, "287082", 59 + PERIOD_SECS), Some(1));
+        assert_eq!(matching_step(&secret, "287082", 59 + 3 * PERIOD_SECS), None);
+        assert_eq!(matching_step(&secret, "28708", 59), None);
+        assert_eq!(matching_step(&secret, "abcdef", 59), None);
+    }
+
+    #[test]
+    fn test_generated_secret_round_trips() {
+        let secret = generate_secret();
+        assert_eq!(secret.len(), 32);
+        assert_eq!(base32_decode(&secret).unwrap().len(), SECRET_BYTES);
+        assert_ne!(secret, generate_secret());
+    }
+
+    #[test]
+    fn test_provisioning_uri_escapes_labels() {
+        assert_eq!(
+            provisioning_uri("The Warehouse Hub", "ana@example.com", "JBSWY3DP"),
+            "otpauth://totp/The%20Warehouse%20Hub:ana%40example.com?secret=JBSWY3DP&issuer=The%20Warehouse%20Hub&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+}
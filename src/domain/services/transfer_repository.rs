@@ -1,7 +1,10 @@
+use crate::domain::entities::inventory::StockMovement;
+use crate::domain::entities::list_filter::ListFilter;
 use crate::domain::entities::transfer::{
-    CreateTransferRequest, ReceiveTransferRequest, StockMovement, Transfer, TransferLine,
+    CreateTransferRequest, ReceiveTransferRequest, ShipTransferLineRequest, Transfer, TransferLine,
 };
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -20,18 +23,20 @@ pub trait TransferRepository: Send + Sync {
     async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
     async fn list(
         &self,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<(Transfer, Vec<TransferLine>)>, DomainError>;
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<(Transfer, Vec<TransferLine>)>, DomainError>;
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError>;
     async fn ship_transfer(
         &self,
         id: Uuid,
+        shipped_lines: Vec<ShipTransferLineRequest>,
         created_by: Uuid,
     ) -> Result<(Transfer, Vec<TransferLine>, Vec<StockMovement>), DomainError>;
     async fn receive_transfer(
         &self,
         id: Uuid,
-        received_lines: Vec<crate::domain::entities::transfer::ReceiveTransferLineRequest>,
+        request: ReceiveTransferRequest,
         created_by: Uuid,
     ) -> Result<(Transfer, Vec<TransferLine>, Vec<StockMovement>), DomainError>;
 }
@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::two_factor::TwoFactor;
+use crate::shared::error::DomainError;
+
+#[async_trait]
+pub trait TwoFactorRepository: Send + Sync {
+    /// The user's enrollment, pending or enabled
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Option<TwoFactor>, DomainError>;
+
+    /// Save an enrollment, replacing the user's previous one
+    async fn save(&self, two_factor: &TwoFactor) -> Result<(), DomainError>;
+
+    /// Remove the user's enrollment, turning two-factor login off
+    async fn delete(&self, user_id: Uuid) -> Result<(), DomainError>;
+}
+
+#[cfg(test)]
+use mockall::mock;
+
+#[cfg(test)]
+mock! {
+    pub TwoFactorRepository {}
+
+    #[async_trait]
+    impl TwoFactorRepository for TwoFactorRepository {
+        async fn find_by_user(&self, user_id: Uuid) -> Result<Option<TwoFactor>, DomainError>;
+        async fn save(&self, two_factor: &TwoFactor) -> Result<(), DomainError>;
+        async fn delete(&self, user_id: Uuid) -> Result<(), DomainError>;
+    }
+}
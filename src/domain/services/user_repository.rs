@@ -27,4 +27,7 @@ pub trait UserRepository: Send + Sync {
         email: &Email,
         exclude_user_id: Option<Uuid>,
     ) -> Result<bool, DomainError>;
+
+    /// List the users belonging to a tenant, oldest first
+    async fn list_by_tenant(&self, tenant_id: Uuid) -> Result<Vec<User>, DomainError>;
 }
@@ -0,0 +1,33 @@
+use crate::domain::entities::inventory::StockMovement;
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::vendor_return::{VendorCredit, VendorReturn};
+use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait VendorReturnRepository: Send + Sync {
+    /// Save a draft. Fails with `Conflict` if, together with the PO's other draft and
+    /// shipped returns, it would return more of a line than was received.
+    async fn create(&self, rtv: &VendorReturn) -> Result<(), DomainError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<VendorReturn>, DomainError>;
+    async fn find_credit(&self, rtv_id: Uuid) -> Result<Option<VendorCredit>, DomainError>;
+    async fn list(
+        &self,
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<VendorReturn>, DomainError>;
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError>;
+    /// Quantity of each PO line on draft or shipped returns
+    async fn returned_quantities(&self, po_id: Uuid)
+        -> Result<HashMap<Uuid, Decimal>, DomainError>;
+    /// Ship a draft: take the stock out of its location and record the expected credit
+    async fn ship(
+        &self,
+        id: Uuid,
+    ) -> Result<(VendorReturn, Vec<StockMovement>, VendorCredit), DomainError>;
+    async fn cancel(&self, id: Uuid) -> Result<VendorReturn, DomainError>;
+}
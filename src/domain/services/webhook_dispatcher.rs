@@ -1,12 +1,36 @@
 use crate::domain::entities::webhook::{Webhook, WebhookDelivery, WebhookEvent, WebhookEventType};
+use crate::domain::services::event_broadcaster::EventBroadcaster;
 use crate::domain::services::webhook_repository::WebhookRepository;
+use crate::domain::services::webhook_signature::{signature_header, SIGNATURE_HEADER};
+use crate::domain::services::webhook_url_policy::WebhookUrlPolicy;
 use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::{current_tenant, with_tenant};
 use async_trait::async_trait;
-use reqwest::{Client, StatusCode};
+use chrono::{Duration, Utc};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::{redirect, Client, StatusCode};
 use serde_json;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use tracing::error;
 use uuid::Uuid;
 
+/// How long a claimed outbox event is hidden from other publishers
+const OUTBOX_LEASE_SECONDS: i64 = 300;
+
+/// How long a failed outbox event waits before it is published again
+const OUTBOX_RETRY_DELAY_SECONDS: i64 = 60;
+
+/// Envelope every delivery's body is built from
+pub fn delivery_payload(event: &WebhookEvent) -> serde_json::Value {
+    serde_json::json!({
+        "id": event.id,
+        "event_type": event.event_type.as_str(),
+        "timestamp": event.created_at.to_rfc3339(),
+        "data": event.payload
+    })
+}
+
 #[async_trait]
 pub trait WebhookDispatcher: Send + Sync {
     /// Dispatch a webhook event to all subscribed webhooks
@@ -17,23 +41,73 @@ pub trait WebhookDispatcher: Send + Sync {
 
     /// Process pending deliveries (for background job)
     async fn process_pending_deliveries(&self) -> Result<(), DomainError>;
+
+    /// Dispatch events waiting in the outbox, removing each once it is
+    /// dispatched: the due ones, or with `ids` only those. An event whose
+    /// dispatch fails stays queued and is retried. Returns how many were claimed.
+    async fn publish_outbox(&self, ids: Option<&[Uuid]>, limit: i64) -> Result<usize, DomainError>;
+}
+
+/// Redirects followed per delivery, each checked against the URL policy
+const MAX_REDIRECTS: usize = 5;
+
+/// Resolves webhook hosts and refuses to connect when any address they
+/// resolve to is blocked by the URL policy, so a public name can't be pointed
+/// at an internal address after the webhook was registered
+struct PolicyResolver {
+    url_policy: Arc<WebhookUrlPolicy>,
+}
+
+impl Resolve for PolicyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let url_policy = Arc::clone(&self.url_policy);
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0)).await?.collect();
+            for addr in &addrs {
+                url_policy.check_address(host, addr.ip())?;
+            }
+            Ok::<Addrs, Box<dyn std::error::Error + Send + Sync>>(Box::new(addrs.into_iter()))
+        })
+    }
 }
 
 pub struct WebhookDispatcherImpl<R: WebhookRepository> {
     webhook_repository: Arc<R>,
+    event_broadcaster: Arc<EventBroadcaster>,
+    url_policy: Arc<WebhookUrlPolicy>,
     http_client: Client,
 }
 
 impl<R: WebhookRepository> WebhookDispatcherImpl<R> {
-    pub fn new(webhook_repository: Arc<R>) -> Self {
+    pub fn new(
+        webhook_repository: Arc<R>,
+        event_broadcaster: Arc<EventBroadcaster>,
+        url_policy: Arc<WebhookUrlPolicy>,
+    ) -> Self {
+        let redirect_policy = Arc::clone(&url_policy);
         let http_client = Client::builder()
             .timeout(std::time::Duration::from_secs(30))
             .user_agent("The-Warehouse-Hub-Webhook-Dispatcher/1.0")
+            .dns_resolver(Arc::new(PolicyResolver {
+                url_policy: Arc::clone(&url_policy),
+            }))
+            .redirect(redirect::Policy::custom(move |attempt| {
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    return attempt.error("too many redirects");
+                }
+                match redirect_policy.check_url(attempt.url().as_str()) {
+                    Ok(()) => attempt.follow(),
+                    Err(e) => attempt.error(e),
+                }
+            }))
             .build()
             .expect("Failed to create HTTP client");
 
         Self {
             webhook_repository,
+            event_broadcaster,
+            url_policy,
             http_client,
         }
     }
@@ -45,16 +119,22 @@ impl<R: WebhookRepository> WebhookDispatcherImpl<R> {
         event: &WebhookEvent,
         delivery: &WebhookDelivery,
     ) -> Result<(bool, Option<i32>, Option<String>, Option<String>), DomainError> {
-        // Create the webhook payload
-        let payload = serde_json::json!({
-            "id": event.id,
-            "event_type": event.event_type.as_str(),
-            "timestamp": event.created_at.to_rfc3339(),
-            "data": event.payload
-        });
+        // The policy may have tightened since the webhook was registered
+        if let Err(e) = self.url_policy.check_url(&webhook.url) {
+            return Ok((false, None, None, Some(policy_message(&e))));
+        }
 
-        // Create HMAC signature for verification
-        let signature = self.create_signature(&webhook.secret, &payload)?;
+        let payload = delivery_payload(event);
+
+        // Sign the exact bytes we send, with every secret active for this webhook
+        let body = serde_json::to_vec(&payload).map_err(|e| {
+            DomainError::ValidationError(format!("Failed to serialize payload: {}", e).into())
+        })?;
+        let signature = signature_header(
+            &webhook.signing_secrets(Utc::now()),
+            Utc::now().timestamp(),
+            &body,
+        )?;
 
         // Prepare the request
         let request = self
@@ -65,8 +145,8 @@ impl<R: WebhookRepository> WebhookDispatcherImpl<R> {
             .header("X-Webhook-ID", webhook.id.to_string())
             .header("X-Webhook-Event", event.event_type.as_str())
             .header("X-Webhook-Delivery", delivery.id.to_string())
-            .header("X-Webhook-Signature", signature)
-            .json(&payload);
+            .header(SIGNATURE_HEADER, signature)
+            .body(body);
 
         // Send the request
         match request.send().await {
@@ -87,7 +167,9 @@ impl<R: WebhookRepository> WebhookDispatcherImpl<R> {
             }
             Err(e) => {
                 // Handle network errors, timeouts, etc.
-                let error_message = if e.is_timeout() {
+                let error_message = if let Some(blocked) = policy_error(&e) {
+                    policy_message(blocked)
+                } else if e.is_timeout() {
                     "Request timed out".to_string()
                 } else if e.is_connect() {
                     "Connection failed".to_string()
@@ -100,48 +182,36 @@ impl<R: WebhookRepository> WebhookDispatcherImpl<R> {
         }
     }
 
-    /// Create HMAC signature for webhook verification
-    fn create_signature(
-        &self,
-        secret: &str,
-        payload: &serde_json::Value,
-    ) -> Result<String, DomainError> {
-        use hmac::digest::KeyInit;
-        use hmac::{Hmac, Mac};
-        use sha2::Sha256;
-
-        let payload_str = serde_json::to_string(payload).map_err(|e| {
-            DomainError::ValidationError(format!("Failed to serialize payload: {}", e))
-        })?;
-
-        let mut mac = <Hmac<Sha256> as KeyInit>::new_from_slice(secret.as_bytes())
-            .map_err(|e| DomainError::ValidationError(format!("Failed to create HMAC: {}", e)))?;
-
-        mac.update(payload_str.as_bytes());
-
-        let result = mac.finalize();
-        let signature = format!("sha256={}", hex::encode(result.into_bytes()));
-
-        Ok(signature)
-    }
-}
-
-#[async_trait]
-impl<R: WebhookRepository> WebhookDispatcher for WebhookDispatcherImpl<R> {
-    async fn dispatch_event(&self, event: &WebhookEvent) -> Result<(), DomainError> {
-        // Find all webhooks subscribed to this event type
-        let webhooks = self
+    /// Record a delivery of the event to each subscribed webhook and attempt it
+    async fn deliver(&self, event: &WebhookEvent) -> Result<(), DomainError> {
+        // Find all webhooks subscribed to this event type whose filter accepts it
+        let webhooks: Vec<Webhook> = self
             .webhook_repository
             .get_webhooks_for_event(&event.event_type)
-            .await?;
+            .await?
+            .into_iter()
+            .filter(|webhook| webhook.accepts(event))
+            .collect();
 
         if webhooks.is_empty() {
             return Ok(()); // No webhooks to dispatch to
         }
 
+        // Deliveries reference the event, and retries and held replays read it back
+        self.webhook_repository.create_event(event).await?;
+
         // Create deliveries for each webhook
         for webhook in &webhooks {
-            let delivery = WebhookDelivery::new(webhook.id, event.id);
+            let mut delivery = WebhookDelivery::new(webhook.id, event.id);
+            // A paused webhook keeps its deliveries for replay once it is resumed
+            if webhook.is_paused() {
+                delivery.hold();
+                self.webhook_repository.create_delivery(&delivery).await?;
+                continue;
+            }
+            // Keep the delivery worker off this row while we attempt it inline; if the
+            // attempt never gets recorded the worker picks it up once this passes
+            delivery.next_attempt_at = Some(Utc::now() + Duration::minutes(1));
 
             // Store the delivery in the database
             self.webhook_repository.create_delivery(&delivery).await?;
@@ -153,15 +223,61 @@ impl<R: WebhookRepository> WebhookDispatcher for WebhookDispatcherImpl<R> {
         Ok(())
     }
 
+    /// Dispatch one claimed outbox event and settle its row
+    async fn publish_outbox_event(&self, event: &WebhookEvent) -> Result<(), DomainError> {
+        match self.dispatch_event(event).await {
+            Ok(()) => self.webhook_repository.delete_outbox_event(event.id).await,
+            Err(e) => {
+                let retry_at = Utc::now() + Duration::seconds(OUTBOX_RETRY_DELAY_SECONDS);
+                self.webhook_repository
+                    .release_outbox_event(event.id, &e.to_string(), retry_at)
+                    .await
+            }
+        }
+    }
+}
+
+/// The URL policy's refusal behind a failed request, from DNS resolution or
+/// a redirect
+fn policy_error(error: &reqwest::Error) -> Option<&DomainError> {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error);
+    while let Some(current) = source {
+        if let Some(blocked) = current.downcast_ref::<DomainError>() {
+            return Some(blocked);
+        }
+        source = current.source();
+    }
+    None
+}
+
+fn policy_message(error: &DomainError) -> String {
+    match error {
+        DomainError::ValidationError(msg) => msg.to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[async_trait]
+impl<R: WebhookRepository> WebhookDispatcher for WebhookDispatcherImpl<R> {
+    async fn dispatch_event(&self, event: &WebhookEvent) -> Result<(), DomainError> {
+        // Live streams see every event, whether or not any webhook subscribes to it
+        self.event_broadcaster.publish(event);
+
+        // Use cases dispatch from a spawned task, which doesn't inherit the request's
+        // tenant; look the webhooks up under the tenant that raised the event
+        match event.tenant_id {
+            Some(tenant_id) if current_tenant().is_none() => {
+                with_tenant(tenant_id, self.deliver(event)).await
+            }
+            _ => self.deliver(event).await,
+        }
+    }
+
     async fn retry_delivery(&self, delivery_id: Uuid) -> Result<(), DomainError> {
         // Get the delivery
         let mut delivery = match self.webhook_repository.get_delivery(delivery_id).await? {
             Some(delivery) => delivery,
-            None => {
-                return Err(DomainError::ValidationError(
-                    "Delivery not found".to_string(),
-                ))
-            }
+            None => return Err(DomainError::ValidationError("Delivery not found".into())),
         };
 
         // Check if delivery should be retried
@@ -184,13 +300,14 @@ impl<R: WebhookRepository> WebhookDispatcher for WebhookDispatcherImpl<R> {
             }
         };
 
-        let event = match self
-            .webhook_repository
-            .get_recent_events(1, 0)
-            .await?
-            .into_iter()
-            .find(|e| e.id == delivery.event_id)
-        {
+        // Paused since this delivery was queued; hold it rather than send
+        if webhook.is_paused() {
+            delivery.hold();
+            self.webhook_repository.update_delivery(&delivery).await?;
+            return Ok(());
+        }
+
+        let event = match self.webhook_repository.get_event(delivery.event_id).await? {
             Some(event) => event,
             None => {
                 // Event not found, mark delivery as failed
@@ -225,8 +342,13 @@ impl<R: WebhookRepository> WebhookDispatcher for WebhookDispatcherImpl<R> {
         let pending_deliveries = self.webhook_repository.get_pending_deliveries(50).await?;
 
         for delivery in pending_deliveries {
-            // Retry each delivery
-            if let Err(e) = self.retry_delivery(delivery.id).await {
+            // Retry each delivery under the tenant that owns it
+            let retry = self.retry_delivery(delivery.id);
+            let result = match delivery.tenant_id {
+                Some(tenant_id) => with_tenant(tenant_id, retry).await,
+                None => retry.await,
+            };
+            if let Err(e) = result {
                 // Log error but continue processing other deliveries
                 eprintln!("Failed to retry delivery {}: {}", delivery.id, e);
             }
@@ -234,4 +356,26 @@ impl<R: WebhookRepository> WebhookDispatcher for WebhookDispatcherImpl<R> {
 
         Ok(())
     }
+
+    async fn publish_outbox(&self, ids: Option<&[Uuid]>, limit: i64) -> Result<usize, DomainError> {
+        let events = self
+            .webhook_repository
+            .claim_outbox_events(ids, limit, OUTBOX_LEASE_SECONDS)
+            .await?;
+
+        // In order, so a subscriber sees an order's events as they were raised
+        for event in &events {
+            let publish = self.publish_outbox_event(event);
+            let result = match event.tenant_id {
+                Some(tenant_id) => with_tenant(tenant_id, publish).await,
+                None => publish.await,
+            };
+            if let Err(e) = result {
+                // The claim lapses and the event is published again
+                error!("Failed to publish outbox event {}: {}", event.id, e);
+            }
+        }
+
+        Ok(events.len())
+    }
 }
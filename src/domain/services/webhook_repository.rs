@@ -1,6 +1,9 @@
-use crate::domain::entities::webhook::{Webhook, WebhookDelivery, WebhookEvent, WebhookEventType};
+use crate::domain::entities::webhook::{
+    Webhook, WebhookDelivery, WebhookEvent, WebhookEventType, WebhookStatus,
+};
 use crate::shared::error::DomainError;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -27,7 +30,7 @@ pub trait WebhookRepository: Send + Sync {
     /// Delete webhook
     async fn delete_webhook(&self, id: Uuid) -> Result<(), DomainError>;
 
-    /// Create a webhook event
+    /// Create a webhook event; an event that was already stored is left as it is
     async fn create_event(&self, event: &WebhookEvent) -> Result<(), DomainError>;
 
     /// Get recent events with pagination
@@ -55,6 +58,63 @@ pub trait WebhookRepository: Send + Sync {
     async fn get_pending_deliveries(&self, limit: i64)
         -> Result<Vec<WebhookDelivery>, DomainError>;
 
+    /// Claim due deliveries for this worker. Claimed rows have `next_attempt_at`
+    /// pushed out by `lease_seconds` so other workers skip them until the attempt
+    /// is recorded (or the lease runs out because this worker died).
+    async fn claim_pending_deliveries(
+        &self,
+        limit: i64,
+        lease_seconds: i64,
+    ) -> Result<Vec<WebhookDelivery>, DomainError>;
+
+    /// Claim outbox events that are due for publishing, across all tenants,
+    /// oldest first; with `ids`, only those events. Claimed rows are hidden
+    /// from other publishers for `lease_seconds` and have their attempt counted.
+    async fn claim_outbox_events(
+        &self,
+        ids: Option<&[Uuid]>,
+        limit: i64,
+        lease_seconds: i64,
+    ) -> Result<Vec<WebhookEvent>, DomainError>;
+
+    /// Remove a published event from the outbox
+    async fn delete_outbox_event(&self, id: Uuid) -> Result<(), DomainError>;
+
+    /// Record why publishing an outbox event failed and when to try it again
+    async fn release_outbox_event(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> Result<(), DomainError>;
+
+    /// Queue a resumed webhook's held deliveries for sending, oldest first.
+    /// Returns how many were released.
+    async fn release_held_deliveries(&self, webhook_id: Uuid) -> Result<u64, DomainError>;
+
+    /// Move deliveries held since before `created_before` to the DLQ, across all
+    /// tenants. Returns how many were moved.
+    async fn expire_held_deliveries(
+        &self,
+        created_before: DateTime<Utc>,
+    ) -> Result<u64, DomainError>;
+
+    /// Move every one of the tenant's webhooks in `from` to `to`, returning the
+    /// ids of those that changed
+    async fn set_tenant_webhook_status(
+        &self,
+        from: &[WebhookStatus],
+        to: WebhookStatus,
+    ) -> Result<Vec<Uuid>, DomainError>;
+
+    /// Dead-letter the outstanding (pending, failed or held) deliveries of the
+    /// given webhooks. Returns how many were moved.
+    async fn dead_letter_outstanding_deliveries(
+        &self,
+        webhook_ids: &[Uuid],
+        reason: &str,
+    ) -> Result<u64, DomainError>;
+
     /// Get deliveries in DLQ (Dead Letter Queue)
     async fn get_dlq_deliveries(
         &self,
@@ -74,9 +134,6 @@ pub trait WebhookRepository: Send + Sync {
     /// Count DLQ deliveries
     async fn count_dlq_deliveries(&self) -> Result<i64, DomainError>;
 
-    /// Clean up old events and deliveries (for maintenance)
-    async fn cleanup_old_data(&self, days_old: i32) -> Result<(), DomainError>;
-
     /// Get database pool for direct queries (used by admin use cases)
     fn get_pool(&self) -> &sqlx::PgPool;
 }
@@ -0,0 +1,41 @@
+use crate::domain::entities::webhook_retention::{
+    DeliveryCutoffs, ExpiredRecord, RetentionRun, WebhookRetentionSettings,
+};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[async_trait]
+pub trait WebhookRetentionRepository: Send + Sync {
+    /// The current tenant's settings, the defaults where none have been saved
+    async fn find_settings(&self) -> Result<WebhookRetentionSettings, DomainError>;
+    async fn save_settings(&self, settings: &WebhookRetentionSettings) -> Result<(), DomainError>;
+
+    /// Tenants, across all of them, whose scheduled cleanup is due. Claiming
+    /// pushes a tenant's next cleanup out a day.
+    async fn claim_due_tenants(&self, limit: i64) -> Result<Vec<Uuid>, DomainError>;
+
+    /// The oldest delivered or dead-lettered deliveries past their cutoff
+    async fn expired_deliveries(
+        &self,
+        cutoffs: DeliveryCutoffs,
+        limit: i64,
+    ) -> Result<Vec<ExpiredRecord>, DomainError>;
+
+    /// The oldest events created before `before` that no delivery refers to
+    async fn expired_events(
+        &self,
+        before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<ExpiredRecord>, DomainError>;
+
+    /// Delete deliveries that are still finished; returns how many went
+    async fn delete_deliveries(&self, ids: &[Uuid]) -> Result<u64, DomainError>;
+
+    /// Delete events that still have no deliveries; returns how many went
+    async fn delete_events(&self, ids: &[Uuid]) -> Result<u64, DomainError>;
+
+    async fn save_run(&self, run: &RetentionRun) -> Result<(), DomainError>;
+    async fn latest_run(&self) -> Result<Option<RetentionRun>, DomainError>;
+}
@@ -0,0 +1,117 @@
+use crate::shared::error::DomainError;
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+pub const SIGNATURE_HEADER: &str = "X-TWH-Signature";
+pub const SIGNATURE_SCHEME: &str = "v1";
+
+/// How far a signature's timestamp may drift from the receiver's clock before
+/// `verify_signature` rejects it as a possible replay
+pub const DEFAULT_TOLERANCE_SECONDS: i64 = 300;
+
+/// `X-TWH-Signature` value for a request body sent at `timestamp` (unix seconds).
+///
+/// The format is `t=<timestamp>,v1=<hex>[,v1=<hex>...]`, where each `v1` is the
+/// HMAC-SHA256 of `<timestamp>.<body>` under one of the webhook's active secrets.
+/// During a secret rotation both the new and the previous secret sign the request,
+/// so receivers can switch secrets at their own pace.
+pub fn signature_header(
+    secrets: &[&str],
+    timestamp: i64,
+    body: &[u8],
+) -> Result<String, DomainError> {
+    let mut header = format!("t={}", timestamp);
+    for secret in secrets {
+        let mac = signed_mac(secret, timestamp, body)?;
+        header.push_str(&format!(
+            ",{}={}",
+            SIGNATURE_SCHEME,
+            hex::encode(mac.finalize().into_bytes())
+        ));
+    }
+    Ok(header)
+}
+
+/// Check an `X-TWH-Signature` header against a body, the way a receiver should:
+/// the timestamp must be within `tolerance_seconds` of `now` and at least one `v1`
+/// signature must match `secret`. Comparison is constant-time.
+pub fn verify_signature(
+    header: &str,
+    secret: &str,
+    body: &[u8],
+    now: i64,
+    tolerance_seconds: i64,
+) -> Result<(), DomainError> {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some((SIGNATURE_SCHEME, value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+
+    let timestamp = timestamp.ok_or_else(|| {
+        DomainError::ValidationError("Signature header has no valid timestamp".into())
+    })?;
+    if (now - timestamp).abs() > tolerance_seconds {
+        return Err(DomainError::ValidationError(
+            "Signature timestamp is outside the allowed tolerance".into(),
+        ));
+    }
+
+    for signature in signatures {
+        let Ok(expected) = hex::decode(signature) else {
+            continue;
+        };
+        if signed_mac(secret, timestamp, body)?
+            .verify_slice(&expected)
+            .is_ok()
+        {
+            return Ok(());
+        }
+    }
+
+    Err(DomainError::ValidationError(
+        "No signature matches the payload".into(),
+    ))
+}
+
+fn signed_mac(secret: &str, timestamp: i64, body: &[u8]) -> Result<Hmac<Sha256>, DomainError> {
+    let mut mac = <Hmac<Sha256> as KeyInit>::new_from_slice(secret.as_bytes()).map_err(|e| {
+        DomainError::ValidationError(format!("Failed to create HMAC: {}", e).into())
+    })?;
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    Ok(mac)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_round_trip_with_rotation() {
+        let body = br#"{"id":"1","event_type":"STOCK_MOVEMENT"}"#;
+        let header = signature_header(&["new-secret", "old-secret"], 1_700_000_000, body).unwrap();
+        assert!(header.starts_with("t=1700000000,v1="));
+        assert_eq!(header.matches("v1=").count(), 2);
+
+        for secret in ["new-secret", "old-secret"] {
+            assert!(verify_signature(&header, secret, body, 1_700_000_060, 300).is_ok());
+        }
+        assert!(verify_signature(&header, "other-secret", body, 1_700_000_060, 300).is_err());
+        assert!(verify_signature(&header, "new-secret", b"{}", 1_700_000_060, 300).is_err());
+    }
+
+    #[test]
+    fn test_rejects_stale_or_malformed_header() {
+        let body = b"{}";
+        let header = signature_header(&["secret"], 1_700_000_000, body).unwrap();
+        assert!(verify_signature(&header, "secret", body, 1_700_001_000, 300).is_err());
+        assert!(verify_signature("v1=abcd", "secret", body, 1_700_000_000, 300).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use reqwest::Url;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Which hosts webhooks may be delivered to. By default anything that resolves
+/// to a public address is allowed and loopback, private, link-local (including
+/// cloud metadata at 169.254.169.254) and other reserved addresses are refused,
+/// so a webhook can't be pointed at the deployment's own network.
+#[derive(Debug, Clone, Default)]
+pub struct WebhookUrlPolicy {
+    /// Hosts delivered to even when they resolve to a non-public address
+    allowed_hosts: Vec<String>,
+    /// Hosts never delivered to
+    denied_hosts: Vec<String>,
+    /// Skip the address checks altogether, e.g. for local development
+    allow_private_networks: bool,
+}
+
+impl WebhookUrlPolicy {
+    /// Host entries are names (`hooks.example.com`), a leading `*.` for any
+    /// subdomain (`*.corp.example`) or IP addresses
+    pub fn new(
+        allowed_hosts: Vec<String>,
+        denied_hosts: Vec<String>,
+        allow_private_networks: bool,
+    ) -> Self {
+        let normalize = |hosts: Vec<String>| {
+            hosts
+                .into_iter()
+                .map(|host| host.trim().trim_end_matches('.').to_ascii_lowercase())
+                .filter(|host| !host.is_empty())
+                .collect()
+        };
+        Self {
+            allowed_hosts: normalize(allowed_hosts),
+            denied_hosts: normalize(denied_hosts),
+            allow_private_networks,
+        }
+    }
+
+    /// Also allow the host of `url`, for targets the deployment itself runs
+    /// such as the sandbox echo receiver
+    pub fn allow_url_host(mut self, url: &str) -> Self {
+        if let Some(host) = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(normalize_host))
+        {
+            self.allowed_hosts.push(host);
+        }
+        self
+    }
+
+    /// Check a webhook URL without resolving it: the scheme, the denylist, and
+    /// the address when the host is an IP literal. Names are checked again at
+    /// delivery, against the addresses they resolve to then.
+    pub fn check_url(&self, url: &str) -> Result<(), DomainError> {
+        let parsed = Url::parse(url).map_err(|_| {
+            DomainError::ValidationError(format!("Invalid webhook URL: {}", url).into())
+        })?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.webhook_url_scheme",
+                &[],
+            )));
+        }
+        let host = parsed
+            .host_str()
+            .map(normalize_host)
+            .ok_or_else(|| DomainError::ValidationError("Webhook URL has no host".into()))?;
+
+        if matches_any(&self.denied_hosts, &host) {
+            return Err(blocked(&host, "it is on the denylist"));
+        }
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return self.check_address(&host, ip);
+        }
+        if !self.allows_non_public(&host) && (host == "localhost" || host.ends_with(".localhost")) {
+            return Err(blocked(&host, "it is a local address"));
+        }
+        Ok(())
+    }
+
+    /// Check an address `host` resolved to
+    pub fn check_address(&self, host: &str, ip: IpAddr) -> Result<(), DomainError> {
+        let host = normalize_host(host);
+        if matches_any(&self.denied_hosts, &ip.to_string()) {
+            return Err(blocked(&host, "its address is on the denylist"));
+        }
+        if !is_public_address(ip) && !self.allows_non_public(&host) {
+            return Err(blocked(&host, &format!("{} is not a public address", ip)));
+        }
+        Ok(())
+    }
+
+    fn allows_non_public(&self, host: &str) -> bool {
+        self.allow_private_networks || matches_any(&self.allowed_hosts, host)
+    }
+}
+
+fn blocked(host: &str, reason: &str) -> DomainError {
+    DomainError::ValidationError(
+        format!("Webhook target {} is not allowed: {}", host, reason).into(),
+    )
+}
+
+/// Lowercase, without a trailing dot or the brackets around an IPv6 literal
+fn normalize_host(host: &str) -> String {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase()
+}
+
+fn matches_any(patterns: &[String], host: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| match pattern.strip_prefix("*.") {
+            Some(domain) => host
+                .strip_suffix(domain)
+                .is_some_and(|prefix| prefix.ends_with('.')),
+            None => pattern == host,
+        })
+}
+
+/// Whether `ip` is routable on the public internet
+pub fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match embedded_ipv4(ip) {
+            Some(embedded) => is_public_v4(embedded),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+/// The IPv4 address an IPv6 address carries and is delivered to: IPv4-mapped
+/// `::ffff:a.b.c.d`, the deprecated IPv4-compatible `::a.b.c.d` and 6to4
+/// `2002:aabb:ccdd::/48`
+fn embedded_ipv4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    if let Some(ipv4) = ip.to_ipv4() {
+        return Some(ipv4);
+    }
+    let [first, high, low, ..] = ip.segments();
+    (first == 0x2002).then(|| Ipv4Addr::from((u32::from(high) << 16) | u32::from(low)))
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        // Carrier-grade NAT, 100.64.0.0/10
+        || (a == 100 && (64..128).contains(&b))
+        // IETF protocol assignments, 192.0.0.0/24
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking, 198.18.0.0/15
+        || (a == 198 && (b == 18 || b == 19))
+        // Reserved, 240.0.0.0/4
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let [first, second, ..] = ip.segments();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // Unique local, fc00::/7
+        || (first & 0xfe00) == 0xfc00
+        // Link-local, fe80::/10
+        || (first & 0xffc0) == 0xfe80
+        // Documentation, 2001:db8::/32
+        || (first == 0x2001 && second == 0x0db8)
+        // NAT64, 64:ff9b::/96, and its local-use 64:ff9b:1::/48, which can
+        // reach private IPv4 addresses too
+        || (first == 0x0064 && second == 0xff9b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(text: &str) -> IpAddr {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_only_public_addresses_are_allowed_by_default() {
+        for blocked in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.10",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::127.0.0.1",
+            "::10.0.0.1",
+            "2002:a00:1::1",
+            "2002:7f00:1::",
+            "64:ff9b::a00:1",
+            "64:ff9b:1::a00:1",
+        ] {
+            assert!(
+                !is_public_address(ip(blocked)),
+                "{} should be blocked",
+                blocked
+            );
+        }
+        assert!(is_public_address(ip("93.184.216.34")));
+        assert!(is_public_address(ip("2606:2800:220:1:248:1893:25c8:1946")));
+        assert!(is_public_address(ip("::ffff:93.184.216.34")));
+        assert!(is_public_address(ip("2002:5db8:d822::1")));
+    }
+
+    #[test]
+    fn test_check_url_rejects_internal_literals_and_denied_hosts() {
+        let policy = WebhookUrlPolicy::new(vec![], vec!["*.evil.example".to_string()], false);
+        assert!(policy.check_url("https://hooks.example.com/in").is_ok());
+        assert!(policy
+            .check_url("http://169.254.169.254/latest/meta-data")
+            .is_err());
+        assert!(policy.check_url("http://[::1]:8080/").is_err());
+        assert!(policy.check_url("http://localhost:3000/").is_err());
+        assert!(policy.check_url("https://a.evil.example/").is_err());
+        assert!(policy.check_url("https://evil.example/").is_ok());
+        assert!(policy.check_url("ftp://example.com/").is_err());
+    }
+
+    #[test]
+    fn test_allowlisted_hosts_may_resolve_to_private_addresses() {
+        let policy = WebhookUrlPolicy::new(vec!["erp.internal".to_string()], vec![], false)
+            .allow_url_host("http://127.0.0.1:8080/sandbox/echo");
+        assert!(policy.check_address("erp.internal", ip("10.0.0.8")).is_ok());
+        assert!(policy
+            .check_address("other.internal", ip("10.0.0.8"))
+            .is_err());
+        assert!(policy
+            .check_url("http://127.0.0.1:8080/sandbox/echo/abc")
+            .is_ok());
+
+        let open = WebhookUrlPolicy::new(vec![], vec![], true);
+        assert!(open.check_url("http://localhost:3000/").is_ok());
+    }
+}
@@ -10,23 +10,17 @@ impl Email {
     /// Creates a new Email with validation
     pub fn new(value: String) -> Result<Self, DomainError> {
         if value.trim().is_empty() {
-            return Err(DomainError::ValidationError(
-                "Email cannot be empty".to_string(),
-            ));
+            return Err(DomainError::ValidationError("Email cannot be empty".into()));
         }
 
         if value.len() > 254 {
-            return Err(DomainError::ValidationError(
-                "Email is too long".to_string(),
-            ));
+            return Err(DomainError::ValidationError("Email is too long".into()));
         }
 
         // RFC 5322 compliant email regex (simplified)
         let email_regex = Regex::new(r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$").unwrap();
         if !email_regex.is_match(&value) {
-            return Err(DomainError::ValidationError(
-                "Invalid email format".to_string(),
-            ));
+            return Err(DomainError::ValidationError("Invalid email format".into()));
         }
 
         Ok(Self(value.to_lowercase()))
@@ -10,21 +10,19 @@ impl PasswordHash {
     pub fn new(password: &str) -> Result<Self, DomainError> {
         if password.len() < 8 {
             return Err(DomainError::ValidationError(
-                "Password must be at least 8 characters long".to_string(),
+                "Password must be at least 8 characters long".into(),
             ));
         }
 
         if password.len() > 128 {
-            return Err(DomainError::ValidationError(
-                "Password is too long".to_string(),
-            ));
+            return Err(DomainError::ValidationError("Password is too long".into()));
         }
 
         // Hash the password with bcrypt
         match hash(password, DEFAULT_COST) {
             Ok(hashed) => Ok(Self(hashed)),
             Err(_) => Err(DomainError::ValidationError(
-                "Failed to hash password".to_string(),
+                "Failed to hash password".into(),
             )),
         }
     }
@@ -39,7 +37,7 @@ impl PasswordHash {
         match verify(password, &self.0) {
             Ok(result) => Ok(result),
             Err(_) => Err(DomainError::ValidationError(
-                "Failed to verify password".to_string(),
+                "Failed to verify password".into(),
             )),
         }
     }
@@ -1 +1 @@
-// Infrastructure configuration will be implemented here
+pub mod app_config;
@@ -1,13 +1,32 @@
+use crate::application::use_cases::change_password::ChangePasswordRequest;
 use crate::application::use_cases::login::LoginRequest;
+use crate::application::use_cases::password_reset::{ForgotPasswordRequest, ResetPasswordRequest};
+use crate::domain::entities::session::SessionSummary;
+use crate::domain::entities::two_factor::{
+    TwoFactorCodeRequest, TwoFactorEnrollment, TwoFactorPolicy, TwoFactorStatus,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::shared::api_error::ApiError;
 use crate::shared::error::DomainError;
 use crate::AppState;
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::{header::USER_AGENT, HeaderMap, StatusCode},
+    Extension, Json,
+};
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use uuid::Uuid;
+
+/// Proxies in front of the server name the original client here
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
 
 #[derive(Debug, Deserialize)]
 pub struct LoginRequestDto {
     pub email: String,
     pub password: String,
+    /// Required once the user has two-factor login enabled
+    pub two_factor_code: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -16,67 +35,228 @@ pub struct LoginResponseDto {
     pub user_id: String,
     pub email: String,
     pub expires_at: i64,
-}
-
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub message: String,
+    pub session_id: String,
+    pub two_factor_enrollment_required: bool,
 }
 
 pub async fn login_handler(
     State(state): State<AppState>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: HeaderMap,
     Json(request): Json<LoginRequestDto>,
-) -> Result<Json<LoginResponseDto>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<LoginResponseDto>, ApiError> {
     // Convert DTO to domain request
     let login_request = LoginRequest {
         email: request.email,
         password: request.password,
+        ip_address: client_ip(&headers, connect_info.map(|Extension(info)| info.0)),
+        user_agent: headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        two_factor_code: request.two_factor_code,
     };
 
     // Execute the use case
-    match state.login_use_case.execute(login_request).await {
-        Ok(response) => {
-            let dto = LoginResponseDto {
-                token: response.token,
-                user_id: response.user_id,
-                email: response.email,
-                expires_at: response.expires_at,
-            };
-            Ok(Json(dto))
-        }
-        Err(DomainError::ValidationError(msg)) => {
-            let error_response = ErrorResponse {
-                error: "INVALID_CREDENTIALS".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::UNAUTHORIZED, Json(error_response)))
-        }
-        Err(e) => {
-            let error_response = ErrorResponse {
-                error: "INTERNAL_ERROR".to_string(),
-                message: format!("Authentication failed: {e}"),
-            };
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-        }
-    }
+    let response = state
+        .login_use_case
+        .execute(login_request)
+        .await
+        .map_err(|e| match e {
+            DomainError::ValidationError(msg) if msg == "Two-factor code required" => {
+                ApiError::unauthorized(msg).with_code("TWO_FACTOR_REQUIRED")
+            }
+            DomainError::ValidationError(msg) if msg == "Invalid two-factor code" => {
+                ApiError::unauthorized(msg).with_code("INVALID_TWO_FACTOR_CODE")
+            }
+            DomainError::ValidationError(msg) => {
+                ApiError::unauthorized(msg).with_code("INVALID_CREDENTIALS")
+            }
+            e => e.into(),
+        })?;
+    Ok(Json(LoginResponseDto {
+        token: response.token,
+        user_id: response.user_id,
+        email: response.email,
+        expires_at: response.expires_at,
+        session_id: response.session_id,
+        two_factor_enrollment_required: response.two_factor_enrollment_required,
+    }))
+}
+
+/// The client's address: the first hop of `X-Forwarded-For` when behind a
+/// proxy, else the peer the connection came from
+fn client_ip(headers: &HeaderMap, peer: Option<SocketAddr>) -> Option<String> {
+    headers
+        .get(FORWARDED_FOR_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .or_else(|| peer.map(|peer| peer.ip().to_string()))
+}
+
+/// Email a password reset token. Always accepted, whether or not the account exists.
+pub async fn forgot_password_handler(
+    State(state): State<AppState>,
+    Json(request): Json<ForgotPasswordRequest>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .password_reset_use_case
+        .forgot_password(request)
+        .await?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+pub async fn reset_password_handler(
+    State(state): State<AppState>,
+    Json(request): Json<ResetPasswordRequest>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .password_reset_use_case
+        .reset_password(request)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn change_password_handler(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<ChangePasswordRequest>,
+) -> Result<StatusCode, ApiError> {
+    let user_id = tenant_context.user_id.ok_or_else(|| {
+        ApiError::unauthorized("Changing a password requires a login token")
+            .with_code("LOGIN_REQUIRED")
+    })?;
+
+    state
+        .change_password_use_case
+        .execute(user_id, request)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The caller's active logins
+pub async fn list_sessions_handler(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+) -> Result<Json<Vec<SessionSummary>>, ApiError> {
+    let user_id = login_user(&tenant_context, "Listing sessions")?;
+    let sessions = state
+        .manage_sessions_use_case
+        .list(user_id, tenant_context.session_id)
+        .await?;
+    Ok(Json(sessions))
+}
+
+/// Revoke one of the caller's logins
+pub async fn revoke_session_handler(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(session_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let user_id = login_user(&tenant_context, "Revoking a session")?;
+    state
+        .manage_sessions_use_case
+        .revoke(user_id, session_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn two_factor_status_handler(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+) -> Result<Json<TwoFactorStatus>, ApiError> {
+    let user_id = login_user(&tenant_context, "Two-factor authentication")?;
+    let status = state.manage_two_factor_use_case.status(user_id).await?;
+    Ok(Json(status))
+}
+
+/// Start enrolling an authenticator app; the secret and recovery codes are
+/// only shown in this response
+pub async fn enroll_two_factor_handler(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+) -> Result<(StatusCode, Json<TwoFactorEnrollment>), ApiError> {
+    let user_id = login_user(&tenant_context, "Two-factor authentication")?;
+    let enrollment = state.manage_two_factor_use_case.enroll(user_id).await?;
+    Ok((StatusCode::CREATED, Json(enrollment)))
+}
+
+pub async fn confirm_two_factor_handler(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<TwoFactorCodeRequest>,
+) -> Result<Json<TwoFactorStatus>, ApiError> {
+    let user_id = login_user(&tenant_context, "Two-factor authentication")?;
+    let status = state
+        .manage_two_factor_use_case
+        .confirm(user_id, request)
+        .await?;
+    Ok(Json(status))
+}
+
+pub async fn disable_two_factor_handler(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<TwoFactorCodeRequest>,
+) -> Result<StatusCode, ApiError> {
+    let user_id = login_user(&tenant_context, "Two-factor authentication")?;
+    state
+        .manage_two_factor_use_case
+        .disable(user_id, request)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_two_factor_policy_handler(
+    State(state): State<AppState>,
+) -> Result<Json<TwoFactorPolicy>, ApiError> {
+    let policy = state.manage_two_factor_use_case.get_policy().await?;
+    Ok(Json(policy))
+}
+
+pub async fn update_two_factor_policy_handler(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<TwoFactorPolicy>,
+) -> Result<Json<TwoFactorPolicy>, ApiError> {
+    let user_id = login_user(&tenant_context, "Changing the two-factor policy")?;
+    let policy = state
+        .manage_two_factor_use_case
+        .update_policy(user_id, request)
+        .await?;
+    Ok(Json(policy))
+}
+
+fn login_user(tenant_context: &TenantContext, action: &str) -> Result<Uuid, ApiError> {
+    tenant_context.user_id.ok_or_else(|| {
+        ApiError::unauthorized(format!("{} requires a login token", action))
+            .with_code("LOGIN_REQUIRED")
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::api_error::ErrorResponse;
 
     #[tokio::test]
     async fn test_login_request_dto_conversion() {
         let dto = LoginRequestDto {
             email: "test@example.com".to_string(),
             password: "password".to_string(),
+            two_factor_code: None,
         };
 
         // Test that DTO can be converted to domain request
         let domain_request = crate::application::use_cases::login::LoginRequest {
             email: dto.email.clone(),
             password: dto.password.clone(),
+            ip_address: None,
+            user_agent: None,
+            two_factor_code: dto.two_factor_code.clone(),
         };
 
         assert_eq!(domain_request.email, "test@example.com");
@@ -90,6 +270,8 @@ mod tests {
             user_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
             email: "test@example.com".to_string(),
             expires_at: 1234567890,
+            session_id: "0b9c1f3e-6a55-4d1e-9b7a-2f0c8d4e5a61".to_string(),
+            two_factor_enrollment_required: false,
         };
 
         let dto = LoginResponseDto {
@@ -97,6 +279,8 @@ mod tests {
             user_id: domain_response.user_id.clone(),
             email: domain_response.email.clone(),
             expires_at: domain_response.expires_at,
+            session_id: domain_response.session_id.clone(),
+            two_factor_enrollment_required: domain_response.two_factor_enrollment_required,
         };
 
         assert_eq!(dto.token, "mock-jwt-token");
@@ -105,11 +289,29 @@ mod tests {
         assert_eq!(dto.expires_at, 1234567890);
     }
 
+    #[test]
+    fn test_client_ip_prefers_forwarded_for() {
+        let peer: SocketAddr = "10.0.0.5:51234".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(client_ip(&headers, Some(peer)).as_deref(), Some("10.0.0.5"));
+
+        headers.insert(
+            FORWARDED_FOR_HEADER,
+            "203.0.113.7, 10.0.0.1".parse().unwrap(),
+        );
+        assert_eq!(
+            client_ip(&headers, Some(peer)).as_deref(),
+            Some("203.0.113.7")
+        );
+        assert_eq!(client_ip(&HeaderMap::new(), None), None);
+    }
+
     #[tokio::test]
     async fn test_error_response_dto_structure() {
         let error_response = ErrorResponse {
             error: "INVALID_CREDENTIALS".to_string(),
             message: "Invalid credentials".to_string(),
+            trace_id: None,
         };
 
         assert_eq!(error_response.error, "INVALID_CREDENTIALS");
@@ -121,6 +323,7 @@ mod tests {
         let error_response = ErrorResponse {
             error: "INTERNAL_ERROR".to_string(),
             message: "Authentication failed: Database connection failed".to_string(),
+            trace_id: None,
         };
 
         assert_eq!(error_response.error, "INTERNAL_ERROR");
@@ -0,0 +1,26 @@
+use crate::domain::services::address_validator::AddressValidator;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::services::quota_service::QuotaService;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::AppState;
+use axum::extract::FromRef;
+use std::sync::Arc;
+
+/// What the item and location handlers work with, held as trait objects so
+/// handler tests can pass in-memory fakes instead of a database. The router
+/// extracts it from `AppState`.
+#[derive(Clone)]
+pub struct CatalogState {
+    pub item_repository: Arc<dyn ItemRepository>,
+    pub location_repository: Arc<dyn LocationRepository>,
+    pub webhook_dispatcher: Arc<dyn WebhookDispatcher>,
+    pub quota_service: Arc<dyn QuotaService>,
+    pub address_validator: Arc<dyn AddressValidator>,
+}
+
+impl FromRef<AppState> for CatalogState {
+    fn from_ref(state: &AppState) -> Self {
+        state.catalog.clone()
+    }
+}
@@ -1,19 +1,32 @@
 use crate::application::use_cases::{
     create_item::{CreateItemRequest, CreateItemUseCase},
     delete_item::{DeleteItemRequest, DeleteItemUseCase},
-    get_item::{GetItemRequest, GetItemUseCase},
+    get_item::{BatchGetItemsRequest, GetItemRequest, GetItemResponse, GetItemUseCase},
+    import_items::{ImportFileFormat, ImportItemsRequest, ImportItemsResponse},
     list_items::{ListItemsRequest, ListItemsUseCase},
     update_item::{UpdateItemRequest, UpdateItemUseCase},
 };
-use crate::infrastructure::repositories::postgres_item_repository::PostgresItemRepository;
+use crate::domain::entities::item_duplicate::{
+    DuplicateItemsReport, FindDuplicateItemsQuery, MergeItemsRequest, MergeItemsResponse,
+};
+use crate::domain::entities::list_filter::ListFilter;
+use crate::infrastructure::controllers::catalog_state::CatalogState;
+use crate::presentation::handlers::conditional::conditional_json;
+use crate::presentation::handlers::uploads::spool_body;
+use crate::shared::api_error::ApiError;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use crate::AppState;
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    http::{HeaderMap, StatusCode},
+    http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+    response::Response,
     Extension, Json,
 };
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -27,10 +40,11 @@ pub struct CreateItemRequestDto {
     pub category: Option<String>,
     pub unit: String,
     pub barcode: Option<String>,
-    pub cost_price: f64,
-    pub sale_price: Option<f64>,
-    pub reorder_point: Option<i32>,
-    pub reorder_qty: Option<i32>,
+    pub cost_price: Decimal,
+    pub sale_price: Option<Decimal>,
+    pub reorder_point: Option<Decimal>,
+    pub reorder_qty: Option<Decimal>,
+    pub quantity_precision: Option<u32>,
     pub weight: Option<f64>,
     pub dimensions: Option<serde_json::Value>,
     pub metadata: Option<serde_json::Value>,
@@ -42,7 +56,7 @@ pub struct CreateItemResponseDto {
     pub sku: String,
     pub name: String,
     pub unit: String,
-    pub cost_price: f64,
+    pub cost_price: Decimal,
     pub active: bool,
     pub created_at: String,
     pub updated_at: String,
@@ -57,16 +71,56 @@ pub struct GetItemResponseDto {
     pub category: Option<String>,
     pub unit: String,
     pub barcode: Option<String>,
-    pub cost_price: f64,
-    pub sale_price: Option<f64>,
-    pub reorder_point: Option<i32>,
-    pub reorder_qty: Option<i32>,
+    pub cost_price: Decimal,
+    pub sale_price: Option<Decimal>,
+    pub reorder_point: Option<Decimal>,
+    pub reorder_qty: Option<Decimal>,
+    pub quantity_precision: u32,
     pub weight: Option<f64>,
     pub dimensions: Option<serde_json::Value>,
     pub metadata: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant_attributes: Option<BTreeMap<String, String>>,
     pub active: bool,
     pub created_at: String,
     pub updated_at: String,
+    pub etag: String,
+}
+
+impl From<GetItemResponse> for GetItemResponseDto {
+    fn from(response: GetItemResponse) -> Self {
+        Self {
+            id: response.id.to_string(),
+            sku: response.sku,
+            name: response.name,
+            description: response.description,
+            category: response.category,
+            unit: response.unit,
+            barcode: response.barcode,
+            cost_price: response.cost_price,
+            sale_price: response.sale_price,
+            reorder_point: response.reorder_point,
+            reorder_qty: response.reorder_qty,
+            quantity_precision: response.quantity_precision,
+            weight: response.weight,
+            dimensions: response.dimensions,
+            metadata: response.metadata,
+            product_id: response.product_id.map(|id| id.to_string()),
+            variant_attributes: response.variant_attributes,
+            active: response.active,
+            created_at: response.created_at.to_rfc3339(),
+            updated_at: response.updated_at.to_rfc3339(),
+            etag: response.etag,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchGetItemsResponseDto {
+    pub items: Vec<GetItemResponseDto>,
+    pub missing: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -77,10 +131,11 @@ pub struct UpdateItemRequestDto {
     pub category: Option<String>,
     pub unit: Option<String>,
     pub barcode: Option<String>,
-    pub cost_price: Option<f64>,
-    pub sale_price: Option<f64>,
-    pub reorder_point: Option<i32>,
-    pub reorder_qty: Option<i32>,
+    pub cost_price: Option<Decimal>,
+    pub sale_price: Option<Decimal>,
+    pub reorder_point: Option<Decimal>,
+    pub reorder_qty: Option<Decimal>,
+    pub quantity_precision: Option<u32>,
     pub weight: Option<f64>,
     pub dimensions: Option<serde_json::Value>,
     pub metadata: Option<serde_json::Value>,
@@ -92,7 +147,7 @@ pub struct UpdateItemResponseDto {
     pub sku: String,
     pub name: String,
     pub unit: String,
-    pub cost_price: f64,
+    pub cost_price: Decimal,
     pub active: bool,
     pub updated_at: String,
     pub etag: String,
@@ -105,21 +160,15 @@ pub struct ItemSummaryDto {
     pub name: String,
     pub category: Option<String>,
     pub unit: String,
-    pub cost_price: f64,
-    pub sale_price: Option<f64>,
+    pub cost_price: Decimal,
+    pub sale_price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<String>,
     pub active: bool,
     pub created_at: String,
     pub updated_at: String,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ListItemsResponseDto {
-    pub items: Vec<ItemSummaryDto>,
-    pub total_count: i64,
-    pub limit: i64,
-    pub offset: i64,
-}
-
 #[derive(Debug, Serialize)]
 pub struct DeleteItemResponseDto {
     pub id: String,
@@ -127,48 +176,54 @@ pub struct DeleteItemResponseDto {
     pub updated_at: String,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub message: String,
-}
-
 // Query parameters for list endpoint
 #[derive(Debug, Deserialize)]
 pub struct ListItemsQuery {
     pub limit: Option<i64>,
-    pub offset: Option<i64>,
+    pub cursor: Option<String>,
 }
 
 // Handler functions
 
+/// The item use cases report some failures as validation errors; give those their
+/// proper status
+pub(crate) fn item_error(e: DomainError) -> ApiError {
+    match e {
+        DomainError::NotFound(msg) => ApiError::not_found(msg).with_code("ITEM_NOT_FOUND"),
+        DomainError::ValidationError(msg) if msg.contains("already deleted") => {
+            ApiError::conflict(msg).with_code("ITEM_ALREADY_DELETED")
+        }
+        DomainError::Conflict(msg) if msg.key() == Some("error.item_sku_exists") => {
+            ApiError::conflict(msg).with_code("DUPLICATE_SKU")
+        }
+        DomainError::Conflict(msg) if msg.key() == Some("error.item_barcode_exists") => {
+            ApiError::conflict(msg).with_code("DUPLICATE_BARCODE")
+        }
+        e => e.into(),
+    }
+}
+
+pub(crate) fn parse_item_id(id: &str) -> Result<Uuid, ApiError> {
+    Uuid::parse_str(id)
+        .map_err(|_| ApiError::bad_request("Invalid item ID format").with_code("INVALID_ID"))
+}
+
 pub async fn create_item_handler(
-    State(state): State<AppState>,
-    tenant_context: Option<
-        Extension<crate::infrastructure::middleware::tenant_middleware::TenantContext>,
+    State(state): State<CatalogState>,
+    Extension(tenant_context): Extension<
+        crate::infrastructure::middleware::tenant_middleware::TenantContext,
     >,
     Json(request): Json<CreateItemRequestDto>,
-) -> Result<(StatusCode, Json<CreateItemResponseDto>), (StatusCode, Json<ErrorResponse>)> {
-    // Extract tenant_id from extension or default to sandbox tenant
-    let tenant_id = tenant_context
-        .map(|ext| ext.tenant_id)
-        .unwrap_or_else(|| uuid::Uuid::parse_str("d60a7de9-1009-4606-aae9-ae6ffe5827aa").unwrap());
-
-    // Set tenant context on the connection pool
-    sqlx::query!("SELECT set_tenant_context($1)", tenant_id)
-        .execute(&*state.pool)
-        .await
-        .map_err(|e| {
-            let error_response = ErrorResponse {
-                error: "INTERNAL_ERROR".to_string(),
-                message: format!("Failed to set tenant context: {e}"),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
-        })?;
+) -> Result<(StatusCode, Json<CreateItemResponseDto>), ApiError> {
+    let tenant_id = tenant_context.tenant_id;
 
     // Initialize use case
-    let item_repository = Arc::new(PostgresItemRepository::new(Arc::clone(&state.pool)));
-    let use_case = CreateItemUseCase::new(item_repository); // Convert DTO to domain request
+    let item_repository = Arc::clone(&state.item_repository);
+    let use_case = CreateItemUseCase::new(
+        item_repository,
+        Arc::clone(&state.webhook_dispatcher),
+        Arc::clone(&state.quota_service),
+    ); // Convert DTO to domain request
     let domain_request = CreateItemRequest {
         sku: request.sku,
         name: request.name,
@@ -180,6 +235,7 @@ pub async fn create_item_handler(
         sale_price: request.sale_price,
         reorder_point: request.reorder_point,
         reorder_qty: request.reorder_qty,
+        quantity_precision: request.quantity_precision,
         weight: request.weight,
         dimensions: request
             .dimensions
@@ -188,122 +244,75 @@ pub async fn create_item_handler(
     };
 
     // Execute use case
-    match use_case.execute(domain_request, tenant_id).await {
-        Ok(response) => {
-            let dto = CreateItemResponseDto {
-                id: response.id.to_string(),
-                sku: response.sku,
-                name: response.name,
-                unit: response.unit,
-                cost_price: response.cost_price,
-                active: response.active,
-                created_at: response.created_at.to_rfc3339(),
-                updated_at: response.updated_at.to_rfc3339(),
-            };
-            Ok((StatusCode::CREATED, Json(dto)))
-        }
-        Err(DomainError::ValidationError(msg)) => {
-            let error_response = ErrorResponse {
-                error: "VALIDATION_ERROR".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::BAD_REQUEST, Json(error_response)))
-        }
-        Err(e) => {
-            let error_response = ErrorResponse {
-                error: "INTERNAL_ERROR".to_string(),
-                message: format!("Failed to create item: {e}"),
-            };
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-        }
-    }
+    let response = use_case
+        .execute(domain_request, tenant_id)
+        .await
+        .map_err(item_error)?;
+    let dto = CreateItemResponseDto {
+        id: response.id.to_string(),
+        sku: response.sku,
+        name: response.name,
+        unit: response.unit,
+        cost_price: response.cost_price,
+        active: response.active,
+        created_at: response.created_at.to_rfc3339(),
+        updated_at: response.updated_at.to_rfc3339(),
+    };
+    Ok((StatusCode::CREATED, Json(dto)))
 }
 
+/// Answers with 304 when the client's cached copy is still current
 pub async fn get_item_handler(
-    State(state): State<AppState>,
+    State(state): State<CatalogState>,
+    headers: HeaderMap,
     Path(id): Path<String>,
-) -> Result<Json<GetItemResponseDto>, (StatusCode, Json<ErrorResponse>)> {
-    // Parse UUID
-    let item_id = match Uuid::parse_str(&id) {
-        Ok(uuid) => uuid,
-        Err(_) => {
-            let error_response = ErrorResponse {
-                error: "INVALID_ID".to_string(),
-                message: "Invalid item ID format".to_string(),
-            };
-            return Err((StatusCode::BAD_REQUEST, Json(error_response)));
-        }
-    };
+) -> Result<Response, ApiError> {
+    let item_id = parse_item_id(&id)?;
 
     // Initialize use case
-    let item_repository = Arc::new(PostgresItemRepository::new(Arc::clone(&state.pool)));
+    let item_repository = Arc::clone(&state.item_repository);
     let use_case = GetItemUseCase::new(item_repository);
 
     // Execute use case
-    match use_case.execute(GetItemRequest { id: item_id }).await {
-        Ok(response) => {
-            let dto = GetItemResponseDto {
-                id: response.id.to_string(),
-                sku: response.sku,
-                name: response.name,
-                description: response.description,
-                category: response.category,
-                unit: response.unit,
-                barcode: response.barcode,
-                cost_price: response.cost_price,
-                sale_price: response.sale_price,
-                reorder_point: response.reorder_point,
-                reorder_qty: response.reorder_qty,
-                weight: response.weight,
-                dimensions: response.dimensions,
-                metadata: response.metadata,
-                active: response.active,
-                created_at: response.created_at.to_rfc3339(),
-                updated_at: response.updated_at.to_rfc3339(),
-            };
-            Ok(Json(dto))
-        }
-        Err(DomainError::ValidationError(msg)) if msg.contains("not found") => {
-            let error_response = ErrorResponse {
-                error: "ITEM_NOT_FOUND".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::NOT_FOUND, Json(error_response)))
-        }
-        Err(DomainError::ValidationError(msg)) => {
-            let error_response = ErrorResponse {
-                error: "VALIDATION_ERROR".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::BAD_REQUEST, Json(error_response)))
-        }
-        Err(e) => {
-            let error_response = ErrorResponse {
-                error: "INTERNAL_ERROR".to_string(),
-                message: format!("Failed to get item: {e}"),
-            };
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-        }
-    }
+    let response = use_case
+        .execute(GetItemRequest { id: item_id })
+        .await
+        .map_err(item_error)?;
+    let etag = response.etag.clone();
+    let last_modified = response.updated_at;
+    Ok(conditional_json(
+        &headers,
+        &etag,
+        last_modified,
+        GetItemResponseDto::from(response),
+    ))
+}
+
+/// Up to 500 items by ID in one call, in the order asked for; IDs with no
+/// item are listed under `missing`
+pub async fn batch_get_items_handler(
+    State(state): State<CatalogState>,
+    Json(request): Json<BatchGetItemsRequest>,
+) -> Result<Json<BatchGetItemsResponseDto>, ApiError> {
+    let use_case = GetItemUseCase::new(Arc::clone(&state.item_repository));
+    let response = use_case.execute_batch(request).await.map_err(item_error)?;
+    Ok(Json(BatchGetItemsResponseDto {
+        items: response
+            .items
+            .into_iter()
+            .map(GetItemResponseDto::from)
+            .collect(),
+        missing: response.missing.iter().map(Uuid::to_string).collect(),
+    }))
 }
 
 pub async fn update_item_handler(
-    State(state): State<AppState>,
+    State(state): State<CatalogState>,
     headers: HeaderMap,
     Path(id): Path<String>,
     Json(request): Json<UpdateItemRequestDto>,
-) -> Result<(StatusCode, Json<UpdateItemResponseDto>), (StatusCode, Json<ErrorResponse>)> {
-    // Parse UUID
-    let item_id = match Uuid::parse_str(&id) {
-        Ok(uuid) => uuid,
-        Err(_) => {
-            let error_response = ErrorResponse {
-                error: "INVALID_ID".to_string(),
-                message: "Invalid item ID format".to_string(),
-            };
-            return Err((StatusCode::BAD_REQUEST, Json(error_response)));
-        }
-    };
+) -> Result<(StatusCode, Json<UpdateItemResponseDto>), ApiError> {
+    let item_id = parse_item_id(&id)?;
 
     // Get If-Match header for optimistic concurrency
     let if_match_etag = headers
@@ -312,8 +321,8 @@ pub async fn update_item_handler(
         .map(|s| s.to_string());
 
     // Initialize use case
-    let item_repository = Arc::new(PostgresItemRepository::new(Arc::clone(&state.pool)));
-    let use_case = UpdateItemUseCase::new(item_repository);
+    let item_repository = Arc::clone(&state.item_repository);
+    let use_case = UpdateItemUseCase::new(item_repository, Arc::clone(&state.webhook_dispatcher));
 
     // Convert DTO to domain request
     let domain_request = UpdateItemRequest {
@@ -328,6 +337,7 @@ pub async fn update_item_handler(
         sale_price: request.sale_price,
         reorder_point: request.reorder_point,
         reorder_qty: request.reorder_qty,
+        quantity_precision: request.quantity_precision,
         weight: request.weight,
         dimensions: request.dimensions,
         metadata: request.metadata,
@@ -335,162 +345,415 @@ pub async fn update_item_handler(
     };
 
     // Execute use case
-    match use_case.execute(domain_request).await {
-        Ok(response) => {
-            let dto = UpdateItemResponseDto {
-                id: response.id.to_string(),
-                sku: response.sku,
-                name: response.name,
-                unit: response.unit,
-                cost_price: response.cost_price,
-                active: response.active,
-                updated_at: response.updated_at.to_rfc3339(),
-                etag: response.etag,
-            };
-            Ok((StatusCode::OK, Json(dto)))
-        }
-        Err(DomainError::ValidationError(msg)) if msg.contains("not found") => {
-            let error_response = ErrorResponse {
-                error: "ITEM_NOT_FOUND".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::NOT_FOUND, Json(error_response)))
-        }
-        Err(DomainError::ValidationError(msg))
-            if msg.contains("ETag") || msg.contains("concurrent") =>
-        {
-            let error_response = ErrorResponse {
-                error: "CONCURRENT_MODIFICATION".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::PRECONDITION_FAILED, Json(error_response)))
-        }
-        Err(DomainError::ValidationError(msg)) => {
-            let error_response = ErrorResponse {
-                error: "VALIDATION_ERROR".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::BAD_REQUEST, Json(error_response)))
-        }
-        Err(e) => {
-            let error_response = ErrorResponse {
-                error: "INTERNAL_ERROR".to_string(),
-                message: format!("Failed to update item: {e}"),
-            };
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-        }
-    }
+    let response = use_case.execute(domain_request).await.map_err(item_error)?;
+    let dto = UpdateItemResponseDto {
+        id: response.id.to_string(),
+        sku: response.sku,
+        name: response.name,
+        unit: response.unit,
+        cost_price: response.cost_price,
+        active: response.active,
+        updated_at: response.updated_at.to_rfc3339(),
+        etag: response.etag,
+    };
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+/// Edit an item with a JSON Merge Patch; `null` clears a field
+pub async fn patch_item_handler(
+    State(state): State<CatalogState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(patch): Json<serde_json::Value>,
+) -> Result<Json<UpdateItemResponseDto>, ApiError> {
+    let item_id = parse_item_id(&id)?;
+    let if_match_etag = headers
+        .get("if-match")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let use_case = UpdateItemUseCase::new(
+        Arc::clone(&state.item_repository),
+        Arc::clone(&state.webhook_dispatcher),
+    );
+    let response = use_case
+        .patch(item_id, patch, if_match_etag)
+        .await
+        .map_err(item_error)?;
+    Ok(Json(UpdateItemResponseDto {
+        id: response.id.to_string(),
+        sku: response.sku,
+        name: response.name,
+        unit: response.unit,
+        cost_price: response.cost_price,
+        active: response.active,
+        updated_at: response.updated_at.to_rfc3339(),
+        etag: response.etag,
+    }))
 }
 
 pub async fn list_items_handler(
-    State(state): State<AppState>,
+    State(state): State<CatalogState>,
     Query(query): Query<ListItemsQuery>,
-) -> Result<Json<ListItemsResponseDto>, (StatusCode, Json<ErrorResponse>)> {
+    Query(filter): Query<ListFilter>,
+) -> Result<Json<Page<ItemSummaryDto>>, ApiError> {
     // Initialize use case
-    let item_repository = Arc::new(PostgresItemRepository::new(Arc::clone(&state.pool)));
+    let item_repository = Arc::clone(&state.item_repository);
     let use_case = ListItemsUseCase::new(item_repository);
 
     // Execute use case
-    match use_case
+    let response = use_case
         .execute(ListItemsRequest {
-            limit: query.limit,
-            offset: query.offset,
+            page: PageRequest::new(query.limit, query.cursor),
+            filter,
         })
-        .await
-    {
-        Ok(response) => {
-            let items_dto = response
-                .items
-                .into_iter()
-                .map(|item| ItemSummaryDto {
-                    id: item.id.to_string(),
-                    sku: item.sku,
-                    name: item.name,
-                    category: item.category,
-                    unit: item.unit,
-                    cost_price: item.cost_price,
-                    sale_price: item.sale_price,
-                    active: item.active,
-                    created_at: item.created_at.to_rfc3339(),
-                    updated_at: item.updated_at.to_rfc3339(),
-                })
-                .collect();
-
-            let dto = ListItemsResponseDto {
-                items: items_dto,
-                total_count: response.total_count,
-                limit: response.limit,
-                offset: response.offset,
-            };
-            Ok(Json(dto))
-        }
-        Err(e) => {
-            let error_response = ErrorResponse {
-                error: "INTERNAL_ERROR".to_string(),
-                message: format!("Failed to list items: {e}"),
-            };
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-        }
-    }
+        .await?;
+    Ok(Json(response.map(|item| ItemSummaryDto {
+        id: item.id.to_string(),
+        sku: item.sku,
+        name: item.name,
+        category: item.category,
+        unit: item.unit,
+        cost_price: item.cost_price,
+        sale_price: item.sale_price,
+        product_id: item.product_id.map(|id| id.to_string()),
+        active: item.active,
+        created_at: item.created_at.to_rfc3339(),
+        updated_at: item.updated_at.to_rfc3339(),
+    })))
 }
 
 pub async fn delete_item_handler(
-    State(state): State<AppState>,
+    State(state): State<CatalogState>,
     Path(id): Path<String>,
-) -> Result<Json<DeleteItemResponseDto>, (StatusCode, Json<ErrorResponse>)> {
-    // Parse UUID
-    let item_id = match Uuid::parse_str(&id) {
-        Ok(uuid) => uuid,
-        Err(_) => {
-            let error_response = ErrorResponse {
-                error: "INVALID_ID".to_string(),
-                message: "Invalid item ID format".to_string(),
-            };
-            return Err((StatusCode::BAD_REQUEST, Json(error_response)));
-        }
-    };
+) -> Result<Json<DeleteItemResponseDto>, ApiError> {
+    let item_id = parse_item_id(&id)?;
 
     // Initialize use case
-    let item_repository = Arc::new(PostgresItemRepository::new(Arc::clone(&state.pool)));
-    let use_case = DeleteItemUseCase::new(item_repository);
+    let item_repository = Arc::clone(&state.item_repository);
+    let use_case = DeleteItemUseCase::new(item_repository, Arc::clone(&state.webhook_dispatcher));
 
     // Execute use case
-    match use_case.execute(DeleteItemRequest { id: item_id }).await {
-        Ok(response) => {
-            let dto = DeleteItemResponseDto {
-                id: response.id.to_string(),
-                active: response.active,
-                updated_at: response.updated_at.to_rfc3339(),
-            };
-            Ok(Json(dto))
-        }
-        Err(DomainError::ValidationError(msg)) if msg.contains("not found") => {
-            let error_response = ErrorResponse {
-                error: "ITEM_NOT_FOUND".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::NOT_FOUND, Json(error_response)))
-        }
-        Err(DomainError::ValidationError(msg)) if msg.contains("already deleted") => {
-            let error_response = ErrorResponse {
-                error: "ITEM_ALREADY_DELETED".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::CONFLICT, Json(error_response)))
-        }
-        Err(DomainError::ValidationError(msg)) => {
-            let error_response = ErrorResponse {
-                error: "VALIDATION_ERROR".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::BAD_REQUEST, Json(error_response)))
-        }
-        Err(e) => {
-            let error_response = ErrorResponse {
-                error: "INTERNAL_ERROR".to_string(),
-                message: format!("Failed to delete item: {e}"),
-            };
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+    let response = use_case
+        .execute(DeleteItemRequest { id: item_id })
+        .await
+        .map_err(item_error)?;
+    Ok(Json(DeleteItemResponseDto {
+        id: response.id.to_string(),
+        active: response.active,
+        updated_at: response.updated_at.to_rfc3339(),
+    }))
+}
+
+pub async fn import_items_handler(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<
+        crate::infrastructure::middleware::tenant_middleware::TenantContext,
+    >,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<(StatusCode, Json<ImportItemsResponse>), ApiError> {
+    let tenant_id = tenant_context.tenant_id;
+
+    let format = headers
+        .get(CONTENT_TYPE)
+        .and_then(|h| h.to_str().ok())
+        .and_then(ImportFileFormat::from_content_type)
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "UNSUPPORTED_MEDIA_TYPE",
+                "Content-Type must be text/csv or application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            )
+        })?;
+
+    // Written to disk as it arrives; the route's body limit caps the size
+    let request = ImportItemsRequest {
+        format,
+        file: spool_body(body).await?,
+    };
+
+    let response = state
+        .import_items_use_case
+        .execute(request, tenant_id)
+        .await?;
+    Ok((StatusCode::ACCEPTED, Json(response)))
+}
+
+/// Pairs of active items whose names or barcodes suggest they are the same product
+pub async fn find_duplicate_items_handler(
+    State(state): State<AppState>,
+    Query(query): Query<FindDuplicateItemsQuery>,
+) -> Result<Json<DuplicateItemsReport>, ApiError> {
+    let report = state.merge_items_use_case.find_duplicates(query).await?;
+    Ok(Json(report))
+}
+
+/// Fold the item named in the body into the one in the path
+pub async fn merge_items_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<MergeItemsRequest>,
+) -> Result<Json<MergeItemsResponse>, ApiError> {
+    let survivor_id = parse_item_id(&id)?;
+    let response = state
+        .merge_items_use_case
+        .merge(survivor_id, request)
+        .await
+        .map_err(item_error)?;
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::tenant::TenantTier;
+    use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+    use crate::test_support::fakes::CatalogFakes;
+    use axum::body::Body;
+    use axum::http::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+    use axum::http::{HeaderName, HeaderValue, Request};
+    use axum::routing::{get, post};
+    use axum::Router;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    fn router(fakes: &CatalogFakes) -> Router {
+        Router::new()
+            .route("/items", post(create_item_handler))
+            .route("/items/batch-get", post(batch_get_items_handler))
+            .route(
+                "/items/{id}",
+                get(get_item_handler)
+                    .patch(patch_item_handler)
+                    .delete(delete_item_handler),
+            )
+            .with_state(fakes.state())
+            .layer(Extension(TenantContext {
+                tenant_id: Uuid::new_v4(),
+                tier: TenantTier::Free,
+                user_id: None,
+                session_id: None,
+                two_factor_enrollment_required: false,
+            }))
+    }
+
+    async fn send(router: &Router, request: Request<Body>) -> (StatusCode, Value) {
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    fn create(body: Value) -> Request<Body> {
+        Request::post("/items")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_created_item_is_returned_by_get() {
+        let fakes = CatalogFakes::default();
+        let router = router(&fakes);
+        let widget = json!({ "sku": "W-1", "name": "Widget", "unit": "EA", "cost_price": 2.5 });
+
+        let (status, created) = send(&router, create(widget.clone())).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, item) = send(
+            &router,
+            Request::get(format!("/items/{}", created["id"].as_str().unwrap()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            (item["sku"].as_str(), item["name"].as_str()),
+            (Some("W-1"), Some("Widget"))
+        );
+
+        let (status, body) = send(&router, create(widget)).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"], "DUPLICATE_SKU");
+    }
+
+    #[tokio::test]
+    async fn test_get_item_answers_304_while_cached_copy_is_current() {
+        let router = router(&CatalogFakes::default());
+        let (_, created) = send(
+            &router,
+            create(json!({ "sku": "W-1", "name": "Widget", "unit": "EA", "cost_price": 1 })),
+        )
+        .await;
+        let uri = format!("/items/{}", created["id"].as_str().unwrap());
+
+        let response = router
+            .clone()
+            .oneshot(Request::get(&uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[ETAG].clone();
+        let last_modified = response.headers()[LAST_MODIFIED].clone();
+
+        let revalidate = |name: HeaderName, value: HeaderValue| {
+            let router = router.clone();
+            let request = Request::get(&uri).header(name, value).body(Body::empty());
+            async move { router.oneshot(request.unwrap()).await.unwrap() }
+        };
+        let response = revalidate(IF_NONE_MATCH, etag.clone()).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[ETAG], etag);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(body.is_empty());
+
+        let response = revalidate(IF_MODIFIED_SINCE, last_modified).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        let response = revalidate(IF_NONE_MATCH, HeaderValue::from_static("\"stale\"")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_batch_get_returns_items_in_request_order() {
+        let router = router(&CatalogFakes::default());
+        let mut ids = Vec::new();
+        for sku in ["W-1", "W-2"] {
+            let (_, created) = send(
+                &router,
+                create(json!({ "sku": sku, "name": "Widget", "unit": "EA", "cost_price": 1 })),
+            )
+            .await;
+            ids.push(created["id"].as_str().unwrap().to_string());
         }
+        let unknown = Uuid::new_v4().to_string();
+        let batch_get = |ids: Value| {
+            Request::post("/items/batch-get")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(json!({ "ids": ids }).to_string()))
+                .unwrap()
+        };
+
+        let (status, body) =
+            send(&router, batch_get(json!([ids[1], unknown, ids[0], ids[1]]))).await;
+        assert_eq!(status, StatusCode::OK);
+        let skus: Vec<_> = body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["sku"].as_str().unwrap())
+            .collect();
+        assert_eq!(skus, vec!["W-2", "W-1"]);
+        assert_eq!(body["missing"], json!([unknown]));
+
+        let too_many: Vec<_> = (0..501).map(|_| Uuid::new_v4().to_string()).collect();
+        let (status, _) = send(&router, batch_get(json!(too_many))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_merge_patch_clears_null_fields_and_checks_if_match() {
+        let router = router(&CatalogFakes::default());
+        let (_, created) = send(
+            &router,
+            create(json!({
+                "sku": "W-1", "name": "Widget", "unit": "EA", "cost_price": 1,
+                "barcode": "012345678905", "category": "Parts"
+            })),
+        )
+        .await;
+        let uri = format!("/items/{}", created["id"].as_str().unwrap());
+        let patch = |body: Value, if_match: &str| {
+            Request::patch(&uri)
+                .header(CONTENT_TYPE, "application/merge-patch+json")
+                .header("if-match", if_match)
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let (_, item) = send(&router, Request::get(&uri).body(Body::empty()).unwrap()).await;
+        let etag = item["etag"].as_str().unwrap().to_string();
+        let (status, patched) = send(
+            &router,
+            patch(json!({ "barcode": null, "name": "Blue widget" }), &etag),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_ne!(patched["etag"], item["etag"]);
+
+        let (_, item) = send(&router, Request::get(&uri).body(Body::empty()).unwrap()).await;
+        assert_eq!(item["barcode"], Value::Null);
+        assert_eq!(item["category"], "Parts");
+        assert_eq!(item["name"], "Blue widget");
+
+        let (status, _) = send(&router, patch(json!({ "category": null }), &etag)).await;
+        assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+        let etag = item["etag"].as_str().unwrap();
+        let (status, _) = send(&router, patch(json!({ "sku": null }), etag)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = send(&router, patch(json!({ "id": "other" }), etag)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_sku_or_barcode_is_a_conflict() {
+        let router = router(&CatalogFakes::default());
+        let item = |sku: &str, barcode: &str| {
+            json!({
+                "sku": sku, "name": "Widget", "unit": "EA", "cost_price": 1,
+                "barcode": barcode
+            })
+        };
+
+        let (status, _) = send(&router, create(item("W-1", "012345678905"))).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, body) = send(&router, create(item("W-2", "012345678905"))).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"], "DUPLICATE_BARCODE");
+        let (status, body) = send(&router, create(item("W-1", "4006381333931"))).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"], "DUPLICATE_SKU");
+    }
+
+    #[tokio::test]
+    async fn test_get_item_rejects_unknown_and_malformed_ids() {
+        let router = router(&CatalogFakes::default());
+        let get = |id: String| {
+            Request::get(format!("/items/{}", id))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let (status, _) = send(&router, get(Uuid::new_v4().to_string())).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, body) = send(&router, get("not-a-uuid".to_string())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "INVALID_ID");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_item_is_not_found_on_update_and_delete() {
+        let router = router(&CatalogFakes::default());
+        let uri = format!("/items/{}", Uuid::new_v4());
+
+        let (status, body) =
+            send(&router, Request::delete(&uri).body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"], "ITEM_NOT_FOUND");
+
+        let (status, body) = send(
+            &router,
+            Request::patch(&uri)
+                .header(CONTENT_TYPE, "application/merge-patch+json")
+                .body(Body::from(r#"{"name":"Renamed"}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"], "ITEM_NOT_FOUND");
     }
 }
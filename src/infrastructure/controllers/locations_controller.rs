@@ -5,13 +5,16 @@ use crate::application::use_cases::{
     list_locations::{ListLocationsRequest, ListLocationsUseCase},
     update_location::{UpdateLocationRequestDto, UpdateLocationUseCase},
 };
-use crate::infrastructure::repositories::postgres_location_repository::PostgresLocationRepository;
-use crate::shared::error::DomainError;
-use crate::AppState;
+use crate::domain::entities::location_capacity::LocationCapacity;
+use crate::infrastructure::controllers::catalog_state::CatalogState;
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::conditional::conditional_json;
+use crate::shared::api_error::ApiError;
 use axum::{
     extract::{Path, Query, State},
     http::{HeaderMap, StatusCode},
-    Json,
+    response::Response,
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -25,6 +28,7 @@ pub struct CreateLocationRequestDto {
     pub code: Option<String>,
     pub address: Option<serde_json::Value>,
     pub r#type: Option<String>,
+    pub capacity: Option<LocationCapacity>,
 }
 
 #[derive(Debug, Serialize)]
@@ -34,6 +38,7 @@ pub struct CreateLocationResponseDto {
     pub code: Option<String>,
     pub r#type: Option<String>,
     pub active: bool,
+    pub capacity: LocationCapacity,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -46,8 +51,10 @@ pub struct GetLocationResponseDto {
     pub address: Option<serde_json::Value>,
     pub r#type: Option<String>,
     pub active: bool,
+    pub capacity: LocationCapacity,
     pub created_at: String,
     pub updated_at: String,
+    pub etag: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -56,6 +63,7 @@ pub struct UpdateLocationRequestDtoApi {
     pub code: Option<String>,
     pub address: Option<serde_json::Value>,
     pub r#type: Option<String>,
+    pub capacity: Option<LocationCapacity>,
 }
 
 #[derive(Debug, Serialize)]
@@ -65,6 +73,7 @@ pub struct UpdateLocationResponseDto {
     pub code: Option<String>,
     pub r#type: Option<String>,
     pub active: bool,
+    pub capacity: LocationCapacity,
     pub updated_at: String,
     pub etag: String,
 }
@@ -76,12 +85,6 @@ pub struct DeleteLocationResponseDto {
     pub updated_at: String,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub message: String,
-}
-
 // Query parameters for list endpoint
 #[derive(Debug, Deserialize)]
 pub struct ListLocationsQuery {
@@ -91,13 +94,24 @@ pub struct ListLocationsQuery {
 
 // Handler functions
 
+fn parse_location_id(id: &str) -> Result<Uuid, ApiError> {
+    Uuid::parse_str(id)
+        .map_err(|_| ApiError::bad_request("Invalid location ID format").with_code("INVALID_ID"))
+}
+
 pub async fn create_location_handler(
-    State(state): State<AppState>,
+    State(state): State<CatalogState>,
+    Extension(tenant_context): Extension<TenantContext>,
     Json(request): Json<CreateLocationRequestDto>,
-) -> Result<(StatusCode, Json<CreateLocationResponseDto>), (StatusCode, Json<ErrorResponse>)> {
+) -> Result<(StatusCode, Json<CreateLocationResponseDto>), ApiError> {
     // Initialize use case
-    let location_repository = Arc::new(PostgresLocationRepository::new(Arc::clone(&state.pool)));
-    let use_case = CreateLocationUseCase::new(location_repository);
+    let location_repository = Arc::clone(&state.location_repository);
+    let use_case = CreateLocationUseCase::new(
+        location_repository,
+        Arc::clone(&state.webhook_dispatcher),
+        Arc::clone(&state.quota_service),
+        Arc::clone(&state.address_validator),
+    );
 
     // Convert DTO to domain request
     let domain_request = CreateLocationRequest {
@@ -105,114 +119,82 @@ pub async fn create_location_handler(
         code: request.code,
         address: request.address.and_then(|a| serde_json::from_value(a).ok()),
         r#type: request.r#type,
+        capacity: request.capacity,
     };
 
     // Execute use case
-    match use_case.execute(domain_request).await {
-        Ok(response) => {
-            let dto = CreateLocationResponseDto {
-                id: response.id.to_string(),
-                name: response.name,
-                code: response.code,
-                r#type: response.r#type,
-                active: response.active,
-                created_at: response.created_at.to_rfc3339(),
-                updated_at: response.updated_at.to_rfc3339(),
-            };
-            Ok((StatusCode::CREATED, Json(dto)))
-        }
-        Err(DomainError::ValidationError(msg)) => {
-            let error_response = ErrorResponse {
-                error: "VALIDATION_ERROR".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::BAD_REQUEST, Json(error_response)))
-        }
-        Err(e) => {
-            let error_response = ErrorResponse {
-                error: "INTERNAL_ERROR".to_string(),
-                message: format!("Failed to create location: {e}"),
-            };
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-        }
-    }
+    let response = use_case
+        .execute(domain_request, tenant_context.tenant_id)
+        .await?;
+    let dto = CreateLocationResponseDto {
+        id: response.id.to_string(),
+        name: response.name,
+        code: response.code,
+        r#type: response.r#type,
+        active: response.active,
+        capacity: response.capacity,
+        created_at: response.created_at.to_rfc3339(),
+        updated_at: response.updated_at.to_rfc3339(),
+    };
+    Ok((StatusCode::CREATED, Json(dto)))
 }
 
+/// Answers with 304 when the client's cached copy is still current
 pub async fn get_location_handler(
-    State(state): State<AppState>,
+    State(state): State<CatalogState>,
+    headers: HeaderMap,
     Path(id): Path<String>,
-) -> Result<Json<GetLocationResponseDto>, (StatusCode, Json<ErrorResponse>)> {
-    // Parse UUID
-    let location_id = Uuid::parse_str(&id).map_err(|_| {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "INVALID_ID".to_string(),
-                message: "Invalid location ID format".to_string(),
-            }),
-        )
-    })?;
+) -> Result<Response, ApiError> {
+    let location_id = parse_location_id(&id)?;
 
     // Initialize use case
-    let location_repository = Arc::new(PostgresLocationRepository::new(Arc::clone(&state.pool)));
+    let location_repository = Arc::clone(&state.location_repository);
     let use_case = GetLocationUseCase::new(location_repository);
 
     // Execute use case
-    match use_case
+    let response = use_case
         .execute(GetLocationRequest { id: location_id })
-        .await
-    {
-        Ok(response) => {
-            let dto = GetLocationResponseDto {
-                id: response.id.to_string(),
-                name: response.name,
-                code: response.code,
-                address: response
-                    .address
-                    .map(|a| serde_json::to_value(a).unwrap_or_default()),
-                r#type: response.r#type,
-                active: response.active,
-                created_at: response.created_at.to_rfc3339(),
-                updated_at: response.updated_at.to_rfc3339(),
-            };
-            Ok(Json(dto))
-        }
-        Err(DomainError::NotFound(msg)) => {
-            let error_response = ErrorResponse {
-                error: "NOT_FOUND".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::NOT_FOUND, Json(error_response)))
-        }
-        Err(e) => {
-            let error_response = ErrorResponse {
-                error: "INTERNAL_ERROR".to_string(),
-                message: format!("Failed to get location: {e}"),
-            };
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-        }
-    }
+        .await?;
+    let etag = response.etag.clone();
+    let last_modified = response.updated_at;
+    let dto = GetLocationResponseDto {
+        id: response.id.to_string(),
+        name: response.name,
+        code: response.code,
+        address: response
+            .address
+            .map(|a| serde_json::to_value(a).unwrap_or_default()),
+        r#type: response.r#type,
+        active: response.active,
+        capacity: response.capacity,
+        created_at: response.created_at.to_rfc3339(),
+        updated_at: response.updated_at.to_rfc3339(),
+        etag: response.etag,
+    };
+    Ok(conditional_json(&headers, &etag, last_modified, dto))
 }
 
 pub async fn update_location_handler(
-    State(state): State<AppState>,
+    State(state): State<CatalogState>,
+    headers: HeaderMap,
     Path(id): Path<String>,
     Json(request): Json<UpdateLocationRequestDtoApi>,
-) -> Result<(StatusCode, Json<UpdateLocationResponseDto>), (StatusCode, Json<ErrorResponse>)> {
-    // Parse UUID
-    let location_id = Uuid::parse_str(&id).map_err(|_| {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "INVALID_ID".to_string(),
-                message: "Invalid location ID format".to_string(),
-            }),
-        )
-    })?;
+) -> Result<(StatusCode, Json<UpdateLocationResponseDto>), ApiError> {
+    let location_id = parse_location_id(&id)?;
+
+    // Get If-Match header for optimistic concurrency
+    let if_match_etag = headers
+        .get("if-match")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
 
     // Initialize use case
-    let location_repository = Arc::new(PostgresLocationRepository::new(Arc::clone(&state.pool)));
-    let use_case = UpdateLocationUseCase::new(location_repository);
+    let location_repository = Arc::clone(&state.location_repository);
+    let use_case = UpdateLocationUseCase::new(
+        location_repository,
+        Arc::clone(&state.webhook_dispatcher),
+        Arc::clone(&state.address_validator),
+    );
 
     // Convert DTO to domain request
     let domain_request = UpdateLocationRequestDto {
@@ -220,130 +202,213 @@ pub async fn update_location_handler(
         code: request.code,
         address: request.address.and_then(|a| serde_json::from_value(a).ok()),
         r#type: request.r#type,
+        capacity: request.capacity,
     };
 
     // Execute use case
-    match use_case.execute(location_id, domain_request).await {
-        Ok(response) => {
-            let dto = UpdateLocationResponseDto {
-                id: response.id.to_string(),
-                name: response.name,
-                code: response.code,
-                r#type: response.r#type,
-                active: response.active,
-                updated_at: response.updated_at.to_rfc3339(),
-                etag: response.etag,
-            };
-            Ok((StatusCode::OK, Json(dto)))
-        }
-        Err(DomainError::ValidationError(msg)) => {
-            let error_response = ErrorResponse {
-                error: "VALIDATION_ERROR".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::BAD_REQUEST, Json(error_response)))
-        }
-        Err(DomainError::NotFound(msg)) => {
-            let error_response = ErrorResponse {
-                error: "NOT_FOUND".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::NOT_FOUND, Json(error_response)))
-        }
-        Err(e) => {
-            let error_response = ErrorResponse {
-                error: "INTERNAL_ERROR".to_string(),
-                message: format!("Failed to update location: {e}"),
-            };
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-        }
-    }
+    let response = use_case
+        .execute(location_id, domain_request, if_match_etag)
+        .await
+        .map_err(ApiError::from)?;
+    let dto = UpdateLocationResponseDto {
+        id: response.id.to_string(),
+        name: response.name,
+        code: response.code,
+        r#type: response.r#type,
+        active: response.active,
+        capacity: response.capacity,
+        updated_at: response.updated_at.to_rfc3339(),
+        etag: response.etag,
+    };
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+/// Edit a location with a JSON Merge Patch; `null` clears a field
+pub async fn patch_location_handler(
+    State(state): State<CatalogState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(patch): Json<serde_json::Value>,
+) -> Result<Json<UpdateLocationResponseDto>, ApiError> {
+    let location_id = parse_location_id(&id)?;
+    let if_match_etag = headers
+        .get("if-match")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let use_case = UpdateLocationUseCase::new(
+        Arc::clone(&state.location_repository),
+        Arc::clone(&state.webhook_dispatcher),
+        Arc::clone(&state.address_validator),
+    );
+    let response = use_case
+        .patch(location_id, patch, if_match_etag)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(UpdateLocationResponseDto {
+        id: response.id.to_string(),
+        name: response.name,
+        code: response.code,
+        r#type: response.r#type,
+        active: response.active,
+        capacity: response.capacity,
+        updated_at: response.updated_at.to_rfc3339(),
+        etag: response.etag,
+    }))
 }
 
 pub async fn delete_location_handler(
-    State(state): State<AppState>,
+    State(state): State<CatalogState>,
     Path(id): Path<String>,
-) -> Result<Json<DeleteLocationResponseDto>, (StatusCode, Json<ErrorResponse>)> {
-    // Parse UUID
-    let location_id = Uuid::parse_str(&id).map_err(|_| {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "INVALID_ID".to_string(),
-                message: "Invalid location ID format".to_string(),
-            }),
-        )
-    })?;
+) -> Result<Json<DeleteLocationResponseDto>, ApiError> {
+    let location_id = parse_location_id(&id)?;
 
     // Initialize use case
-    let location_repository = Arc::new(PostgresLocationRepository::new(Arc::clone(&state.pool)));
-    let use_case = DeleteLocationUseCase::new(location_repository);
+    let location_repository = Arc::clone(&state.location_repository);
+    let use_case =
+        DeleteLocationUseCase::new(location_repository, Arc::clone(&state.webhook_dispatcher));
 
     // Execute use case
-    match use_case
+    let response = use_case
         .execute(DeleteLocationRequest { id: location_id })
-        .await
-    {
-        Ok(response) => {
-            let dto = DeleteLocationResponseDto {
-                id: response.id.to_string(),
-                active: response.active,
-                updated_at: response.updated_at.to_rfc3339(),
-            };
-            Ok(Json(dto))
-        }
-        Err(DomainError::NotFound(msg)) => {
-            let error_response = ErrorResponse {
-                error: "NOT_FOUND".to_string(),
-                message: msg,
-            };
-            Err((StatusCode::NOT_FOUND, Json(error_response)))
-        }
-        Err(e) => {
-            let error_response = ErrorResponse {
-                error: "INTERNAL_ERROR".to_string(),
-                message: format!("Failed to delete location: {e}"),
-            };
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
-        }
-    }
+        .await?;
+    Ok(Json(DeleteLocationResponseDto {
+        id: response.id.to_string(),
+        active: response.active,
+        updated_at: response.updated_at.to_rfc3339(),
+    }))
 }
 
 pub async fn list_locations_handler(
-    State(state): State<AppState>,
+    State(state): State<CatalogState>,
     Query(query): Query<ListLocationsQuery>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     // Initialize use case
-    let location_repository = Arc::new(PostgresLocationRepository::new(Arc::clone(&state.pool)));
+    let location_repository = Arc::clone(&state.location_repository);
     let use_case = ListLocationsUseCase::new(location_repository);
 
     // Execute use case
-    match use_case
+    let response = use_case
         .execute(ListLocationsRequest {
             limit: query.limit,
             offset: query.offset,
         })
-        .await
-    {
-        Ok(response) => {
-            // Convert to the expected API format
-            let api_response = serde_json::json!({
-                "data": response.locations,
-                "meta": {
-                    "page": (response.offset / response.limit) + 1,
-                    "per_page": response.limit,
-                    "total": response.total_count,
-                    "total_pages": (response.total_count + response.limit - 1) / response.limit
-                }
-            });
-            Ok(Json(api_response))
-        }
-        Err(e) => {
-            let error_response = ErrorResponse {
-                error: "INTERNAL_ERROR".to_string(),
-                message: format!("Failed to list locations: {e}"),
-            };
-            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        .await?;
+
+    // Convert to the expected API format
+    Ok(Json(serde_json::json!({
+        "data": response.locations,
+        "meta": {
+            "page": (response.offset / response.limit) + 1,
+            "per_page": response.limit,
+            "total": response.total_count,
+            "total_pages": (response.total_count + response.limit - 1) / response.limit
         }
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::tenant::TenantTier;
+    use crate::domain::services::location_repository::LocationRepository;
+    use crate::test_support::fakes::CatalogFakes;
+    use axum::body::Body;
+    use axum::http::Request;
+    use axum::routing::{get, post};
+    use axum::Router;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_location_lifecycle_against_fakes() {
+        let fakes = CatalogFakes::default();
+        let router = Router::new()
+            .route("/locations", post(create_location_handler))
+            .route("/locations", get(list_locations_handler))
+            .route("/locations/{id}", get(get_location_handler))
+            .with_state(fakes.state())
+            .layer(Extension(TenantContext {
+                tenant_id: Uuid::new_v4(),
+                tier: TenantTier::Free,
+                user_id: None,
+                session_id: None,
+                two_factor_enrollment_required: false,
+            }));
+        let send = |request: Request<Body>| {
+            let router = router.clone();
+            async move {
+                let response = router.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                (
+                    status,
+                    serde_json::from_slice::<Value>(&body).unwrap_or(Value::Null),
+                )
+            }
+        };
+
+        let (status, created) = send(
+            Request::post("/locations")
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({
+                        "name": "Main",
+                        "code": "MAIN",
+                        "address": {
+                            "line1": "123 Industrial Blvd",
+                            "city": "Springfield",
+                            "region": "illinois",
+                            "postal_code": "62701",
+                            "country": "usa"
+                        }
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let id = created["id"].as_str().unwrap();
+        let (status, location) = send(
+            Request::get(format!("/locations/{}", id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(location["code"], "MAIN");
+        assert_eq!(location["address"]["region"], "IL");
+        assert_eq!(location["address"]["country"], "US");
+
+        let (status, _) = send(
+            Request::post("/locations")
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({
+                        "name": "Overflow",
+                        "address": { "city": "Springfield", "postal_code": "6270", "country": "US" }
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send(
+            Request::get(format!("/locations/{}", Uuid::new_v4()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(
+            LocationRepository::count(&*fakes.locations).await.unwrap(),
+            1
+        );
     }
 }
@@ -1,4 +1,5 @@
 // Infrastructure controllers will be implemented here
 pub mod auth_controller;
+pub mod catalog_state;
 pub mod items_controller;
 pub mod locations_controller;
@@ -1,18 +1,107 @@
-use crate::domain::entities::export::{CreateExportResponse, CreateStockCsvExportRequest};
+use crate::domain::entities::export::{
+    CreateExportResponse, CreateStockCsvExportRequest, ExportDownloadResponse, ReportExportRequest,
+};
+use crate::domain::entities::tenant_snapshot::TenantSnapshotImportResponse;
 use crate::domain::services::export_service::ExportService;
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::presentation::handlers::uploads::spool_body;
+use crate::shared::api_error::ApiError;
+use crate::shared::i18n::current_locale;
 use crate::AppState;
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
 
 /// Handler for creating a stock CSV export
 pub async fn create_stock_csv_export(
     State(state): State<AppState>,
     Json(request): Json<CreateStockCsvExportRequest>,
-) -> Result<Json<CreateExportResponse>, (StatusCode, String)> {
-    match state.export_service.create_stock_csv_export(request).await {
-        Ok(response) => Ok(Json(response)),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to create export: {}", e),
-        )),
+) -> Result<Json<CreateExportResponse>, ApiError> {
+    Ok(Json(
+        state
+            .export_service
+            .create_stock_csv_export(request)
+            .await?,
+    ))
+}
+
+/// Handler for queueing a report or collection export as CSV, XLSX or JSON
+pub async fn create_report_export(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(mut request): Json<ReportExportRequest>,
+) -> Result<(StatusCode, Json<CreateExportResponse>), ApiError> {
+    let tenant_id = tenant_context.tenant_id;
+    // Headers follow Accept-Language unless the request names a locale
+    if request.locale.is_none() {
+        request.locale = Some(current_locale().language().to_string());
     }
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(
+            state
+                .export_service
+                .create_report_export(tenant_id, request)
+                .await?,
+        ),
+    ))
+}
+
+/// Handler for signing a fresh download link for a finished export
+pub async fn get_export_download_url(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(job_id): Path<String>,
+) -> Result<Json<ExportDownloadResponse>, ApiError> {
+    let tenant_id = tenant_context.tenant_id;
+
+    Ok(Json(
+        state
+            .export_service
+            .get_download_url(tenant_id, &job_id)
+            .await?,
+    ))
+}
+
+/// Handler for queueing an export of everything the tenant owns as a zip archive
+pub async fn create_tenant_snapshot_export(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+) -> Result<(StatusCode, Json<CreateExportResponse>), ApiError> {
+    let tenant_id = tenant_context.tenant_id;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(
+            state
+                .tenant_snapshot_use_case
+                .request_export(tenant_id)
+                .await?,
+        ),
+    ))
+}
+
+/// Handler for queueing the restore of a snapshot archive into the tenant
+pub async fn import_tenant_snapshot(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    body: Body,
+) -> Result<(StatusCode, Json<TenantSnapshotImportResponse>), ApiError> {
+    let tenant_id = tenant_context.tenant_id;
+    let restored_by = acting_user(&tenant_context);
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(
+            state
+                .tenant_snapshot_use_case
+                .request_import(tenant_id, restored_by, spool_body(body).await?)
+                .await?,
+        ),
+    ))
 }
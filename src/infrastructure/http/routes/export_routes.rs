@@ -1,10 +1,33 @@
+use crate::infrastructure::config::app_config::BodyLimitsConfig;
 use crate::infrastructure::http::handlers::export_handlers;
+use crate::infrastructure::middleware::body_limit::body_limit;
 use crate::AppState;
-use axum::{routing::post, Router};
+use axum::{
+    routing::{get, post},
+    Router,
+};
 
-pub fn create_exports_router() -> Router<AppState> {
-    Router::new().route(
-        "/exports/stock_csv",
-        post(export_handlers::create_stock_csv_export),
-    )
+pub fn create_exports_router(body_limits: &BodyLimitsConfig) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/exports/stock_csv",
+            post(export_handlers::create_stock_csv_export),
+        )
+        .route(
+            "/exports/reports",
+            post(export_handlers::create_report_export),
+        )
+        .route(
+            "/exports/tenant-snapshot",
+            post(export_handlers::create_tenant_snapshot_export),
+        )
+        .route(
+            "/imports/tenant-snapshot",
+            post(export_handlers::import_tenant_snapshot)
+                .layer(body_limit(body_limits.import_bytes)),
+        )
+        .route(
+            "/exports/{job_id}/download",
+            get(export_handlers::get_export_download_url),
+        )
 }
@@ -0,0 +1,13 @@
+use axum::extract::DefaultBodyLimit;
+use tower_http::limit::RequestBodyLimitLayer;
+
+/// Layers capping request bodies on a route at `max_bytes`. `DefaultBodyLimit`
+/// is the cap the buffering extractors (`Json`, `Bytes`, `Multipart`) apply;
+/// `RequestBodyLimitLayer` also refuses an oversized `Content-Length` up front
+/// and cuts off handlers that stream the raw body.
+pub fn body_limit(max_bytes: usize) -> (DefaultBodyLimit, RequestBodyLimitLayer) {
+    (
+        DefaultBodyLimit::max(max_bytes),
+        RequestBodyLimitLayer::new(max_bytes),
+    )
+}
@@ -2,19 +2,61 @@ use std::sync::Arc;
 
 use axum::{
     body::Body,
-    extract::Request,
-    http::{HeaderMap, Method, StatusCode},
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use sha2::{Digest, Sha256};
+use tokio_stream::StreamExt;
+use tracing::warn;
+use uuid::Uuid;
 
 use crate::domain::entities::idempotency::{IdempotencyKey, IdempotencyKeyRequest};
 use crate::domain::services::idempotency_repository::IdempotencyRepository;
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::shared::api_error::ApiError;
 use crate::shared::error::DomainError;
+use crate::shared::spooled_file::SpooledFile;
 
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// Set on responses that were replayed from the store rather than handled again
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// State for `idempotency_middleware`
+pub struct Idempotency<R: IdempotencyRepository> {
+    repository: Arc<R>,
+    /// How long a key (and its stored response) is honoured
+    ttl_seconds: i64,
+    /// Bodies up to this size are hashed in memory; larger ones are spooled to disk
+    memory_body_bytes: usize,
+    /// Largest body read to hash, which no route group accepts more than
+    max_body_bytes: usize,
+}
+
+impl<R: IdempotencyRepository> Idempotency<R> {
+    pub fn new(
+        repository: Arc<R>,
+        ttl_seconds: i64,
+        memory_body_bytes: usize,
+        max_body_bytes: usize,
+    ) -> Self {
+        Self {
+            repository,
+            ttl_seconds,
+            memory_body_bytes,
+            max_body_bytes,
+        }
+    }
+}
+
+/// Honours an `Idempotency-Key` header on POST, PUT and PATCH requests: the first
+/// response for a key is stored and replayed to retries until the key expires.
+/// Keys are scoped to the tenant, must be reused with the same method, path and
+/// body, and are released again if the request fails with a server error so the
+/// client can retry it.
 pub async fn idempotency_middleware<R: IdempotencyRepository + 'static>(
-    idempotency_repo: Arc<R>,
+    State(idempotency): State<Arc<Idempotency<R>>>,
     request: Request,
     next: Next,
 ) -> Response {
@@ -27,143 +69,273 @@ pub async fn idempotency_middleware<R: IdempotencyRepository + 'static>(
     }
 
     // Extract idempotency key from header
-    let idempotency_key = match extract_idempotency_key(&request.headers()) {
-        Some(key) => key,
-        None => {
-            // No idempotency key provided, proceed normally
-            return next.run(request).await;
-        }
+    let Some(client_key) = extract_idempotency_key(request.headers()) else {
+        // No idempotency key provided, proceed normally
+        return next.run(request).await;
     };
+    let tenant_id = request
+        .extensions()
+        .get::<TenantContext>()
+        .map(|context| context.tenant_id);
+    let idempotency_key = scoped_key(tenant_id, &client_key);
 
-    // Check if key already exists
-    match idempotency_repo.get_key(&idempotency_key).await {
-        Ok(Some(existing_key)) => {
-            // Key exists, return cached response
-            return create_response_from_key(&existing_key);
-        }
-        Ok(None) => {
-            // Key doesn't exist, proceed with request
-        }
-        Err(DomainError::InfrastructureError(_)) => {
-            // Infrastructure error (Redis/PostgreSQL down), but we should still allow the request
-            // In production, you might want to add circuit breaker logic here
-            return next.run(request).await;
-        }
-        Err(e) => {
-            // Other errors, return error response
-            return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
-        }
-    }
-
-    // Clone request for processing
     let (parts, body) = request.into_parts();
 
     // Calculate request body hash
-    let body_bytes = match axum::body::to_bytes(body, usize::MAX).await {
-        Ok(bytes) => bytes,
-        Err(_) => {
-            return (StatusCode::BAD_REQUEST, "Invalid request body").into_response();
-        }
+    let (request_body_hash, body) = match hash_body(
+        body,
+        idempotency.memory_body_bytes,
+        idempotency.max_body_bytes,
+    )
+    .await
+    {
+        Ok(hashed) => hashed,
+        Err(e) => return e.into_response(),
     };
 
-    let body_hash = calculate_body_hash(&body_bytes);
-
-    // Create idempotency key record
     let key_request = IdempotencyKeyRequest {
+        tenant_id,
         idempotency_key: idempotency_key.clone(),
         request_path: parts.uri.path().to_string(),
         request_method: parts.method.to_string(),
-        request_body_hash: body_hash,
-        ttl_seconds: Some(86400), // 24 hours
+        request_body_hash,
+        ttl_seconds: Some(idempotency.ttl_seconds),
     };
 
     let key = match IdempotencyKey::new(key_request) {
         Ok(key) => key,
         Err(e) => {
-            return (StatusCode::BAD_REQUEST, e.to_string()).into_response();
+            return ApiError::from(e).into_response();
         }
     };
 
-    // Store the key
-    if let Err(e) = idempotency_repo.store_key(&key).await {
-        match e {
-            DomainError::Conflict(_) => {
-                // Key was created by another concurrent request, try to get it
-                match idempotency_repo.get_key(&idempotency_key).await {
-                    Ok(Some(existing_key)) => {
-                        return create_response_from_key(&existing_key);
-                    }
-                    _ => {
-                        // Still can't get it, return conflict
-                        return (StatusCode::CONFLICT, "Request already in progress")
-                            .into_response();
-                    }
+    // Claim the key; if it's already taken this is a retry
+    let repository = &idempotency.repository;
+    match repository.store_key(&key).await {
+        Ok(()) => {}
+        Err(DomainError::Conflict(_)) => {
+            return match repository.get_key(&idempotency_key).await {
+                Ok(Some(existing_key)) => replay(&existing_key, &key),
+                Ok(None) => {
+                    // Expired between the insert and the read
+                    ApiError::conflict("Request already in progress")
+                        .with_code("IDEMPOTENCY_KEY_IN_USE")
+                        .into_response()
                 }
-            }
-            DomainError::InfrastructureError(_) => {
-                // Infrastructure error, proceed without idempotency
-                return next
-                    .run(Request::from_parts(parts, Body::from(body_bytes)))
-                    .await;
-            }
-            _ => {
-                return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
-            }
+                Err(e) => ApiError::from(e).into_response(),
+            };
+        }
+        Err(DomainError::InfrastructureError(_)) => {
+            // Infrastructure error (Redis/PostgreSQL down), but we should still allow the request
+            // In production, you might want to add circuit breaker logic here
+            return next.run(Request::from_parts(parts, body)).await;
+        }
+        Err(e) => {
+            return ApiError::from(e).into_response();
         }
     }
 
     // Reconstruct request and proceed
-    let request = Request::from_parts(parts, Body::from(body_bytes));
+    let request = Request::from_parts(parts, body);
     let response = next.run(request).await;
 
-    // Store the response in the background
-    let idempotency_repo_clone = Arc::clone(&idempotency_repo);
-    let idempotency_key_clone = idempotency_key.clone();
-    let status = response.status().as_u16() as i32;
-    let body_string = if let Some(body) = extract_response_body(&response) {
-        Some(body)
-    } else {
-        None
-    };
+    // Server errors may not have done anything, so let the client try again
+    if response.status().is_server_error() {
+        if let Err(e) = repository.delete_key(&idempotency_key).await {
+            warn!(
+                "Failed to release idempotency key {}: {}",
+                idempotency_key, e
+            );
+        }
+        return response;
+    }
 
-    tokio::spawn(async move {
-        let _ = idempotency_repo_clone
-            .complete_key(&idempotency_key_clone, status, body_string)
-            .await;
-    });
+    // Store the response before returning it so an immediate retry sees it
+    let (parts, body) = response.into_parts();
+    let body_bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            let _ = repository.delete_key(&idempotency_key).await;
+            return ApiError::internal(e.to_string()).into_response();
+        }
+    };
+    let body_string = String::from_utf8(body_bytes.to_vec()).ok();
+    if let Err(e) = repository
+        .complete_key(&idempotency_key, parts.status.as_u16() as i32, body_string)
+        .await
+    {
+        warn!(
+            "Failed to store response for idempotency key {}: {}",
+            idempotency_key, e
+        );
+    }
 
-    response
+    Response::from_parts(parts, Body::from(body_bytes))
 }
 
 fn extract_idempotency_key(headers: &HeaderMap) -> Option<String> {
     headers
-        .get("idempotency-key")
+        .get(IDEMPOTENCY_KEY_HEADER)
         .and_then(|value| value.to_str().ok())
-        .map(|s| s.to_string())
+        .map(|s| s.trim().to_string())
 }
 
-fn calculate_body_hash(body: &[u8]) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(body);
-    format!("{:x}", hasher.finalize())
+/// Keys only have to be unique per tenant, but the store's are global
+fn scoped_key(tenant_id: Option<Uuid>, client_key: &str) -> String {
+    match tenant_id {
+        Some(tenant_id) => format!("{}:{}", tenant_id, client_key),
+        None => client_key.to_string(),
+    }
 }
 
-fn create_response_from_key(key: &IdempotencyKey) -> Response {
-    let status = match key.response_status {
-        Some(status) => {
-            StatusCode::from_u16(status as u16).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+/// SHA-256 of the request body, and the body to pass on. Bodies up to
+/// `memory_limit` are kept in memory; larger uploads such as imports are
+/// spooled to disk so sending a key with them doesn't buffer the file.
+async fn hash_body(
+    body: Body,
+    memory_limit: usize,
+    max_bytes: usize,
+) -> Result<(String, Body), ApiError> {
+    let mut hasher = Sha256::new();
+    let mut buffered = Vec::new();
+    let mut spooled: Option<SpooledFile> = None;
+    let mut len = 0usize;
+
+    let mut stream = body.into_data_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|_| ApiError::bad_request("Invalid request body"))?;
+        len += chunk.len();
+        if len > max_bytes {
+            return Err(ApiError::payload_too_large());
         }
-        None => StatusCode::ACCEPTED, // Request in progress
+        hasher.update(&chunk);
+
+        match spooled.as_mut() {
+            Some(file) => file.write(&chunk).await?,
+            None if len > memory_limit => {
+                let mut file = SpooledFile::create().await?;
+                file.write(&buffered).await?;
+                file.write(&chunk).await?;
+                buffered = Vec::new();
+                spooled = Some(file);
+            }
+            None => buffered.extend_from_slice(&chunk),
+        }
+    }
+
+    let body = match spooled {
+        Some(file) => file.finish().await?.into_body().await?,
+        None => Body::from(buffered),
     };
+    Ok((format!("{:x}", hasher.finalize()), body))
+}
 
-    let body = key.response_body.clone().unwrap_or_default();
+/// Answer a retry from the stored key. `request` is the key the retry would have
+/// created, used to reject a key reused for a different request.
+fn replay(stored: &IdempotencyKey, request: &IdempotencyKey) -> Response {
+    if stored.request_method != request.request_method
+        || stored.request_path != request.request_path
+        || stored.request_body_hash != request.request_body_hash
+    {
+        return ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "IDEMPOTENCY_KEY_REUSED",
+            "Idempotency-Key was already used for a different request",
+        )
+        .into_response();
+    }
+
+    let Some(status) = stored.response_status else {
+        return ApiError::conflict("A request with this Idempotency-Key is still being processed")
+            .with_code("IDEMPOTENCY_KEY_IN_USE")
+            .into_response();
+    };
+    let status = StatusCode::from_u16(status as u16).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
 
-    (status, body).into_response()
+    let body = stored.response_body.clone().unwrap_or_default();
+    // Handlers answer in JSON; anything else is replayed as plain text
+    let content_type = if serde_json::from_str::<serde::de::IgnoredAny>(&body).is_ok() {
+        "application/json"
+    } else {
+        "text/plain; charset=utf-8"
+    };
+
+    let mut response = (status, body).into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
 }
 
-fn extract_response_body(response: &Response) -> Option<String> {
-    // This is a simplified version. In a real implementation, you'd need to
-    // handle different response body types properly
-    // For now, we'll just return None to avoid complexity
-    None
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calculate_body_hash(body: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(body))
+    }
+
+    fn key(path: &str, body: &[u8]) -> IdempotencyKey {
+        let tenant_id = Uuid::new_v4();
+        IdempotencyKey::new(IdempotencyKeyRequest {
+            tenant_id: Some(tenant_id),
+            idempotency_key: scoped_key(Some(tenant_id), "abc"),
+            request_path: path.to_string(),
+            request_method: "POST".to_string(),
+            request_body_hash: calculate_body_hash(body),
+            ttl_seconds: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_replay_returns_stored_response() {
+        let mut stored = key("/transfers", b"{}");
+        stored
+            .complete(201, Some(r#"{"id":"1"}"#.to_string()))
+            .unwrap();
+
+        let response = replay(&stored, &key("/transfers", b"{}"));
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(response.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+    }
+
+    #[test]
+    fn test_replay_rejects_in_progress_and_mismatched_requests() {
+        let stored = key("/transfers", b"{}");
+
+        assert_eq!(
+            replay(&stored, &key("/transfers", b"{}")).status(),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            replay(&stored, &key("/transfers", b"{\"a\":1}")).status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            replay(&stored, &key("/sales-orders", b"{}")).status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[tokio::test]
+    async fn test_large_bodies_are_spooled_and_passed_on_intact() {
+        let data = vec![b'x'; 100];
+
+        for memory_limit in [1000, 10] {
+            let (hash, body) = hash_body(Body::from(data.clone()), memory_limit, 1000)
+                .await
+                .unwrap();
+            assert_eq!(hash, calculate_body_hash(&data));
+            let passed_on = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+            assert_eq!(passed_on.as_ref(), data.as_slice());
+        }
+
+        let error = hash_body(Body::from(data), 10, 50).await.unwrap_err();
+        assert_eq!(
+            error.into_response().status(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
 }
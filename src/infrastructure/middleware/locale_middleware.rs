@@ -0,0 +1,29 @@
+use axum::{
+    extract::Request,
+    http::{
+        header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE},
+        HeaderValue,
+    },
+    middleware::Next,
+    response::Response,
+};
+
+use crate::shared::i18n::{with_locale, Locale};
+
+/// Resolve the caller's `Accept-Language` to a supported locale for error
+/// messages and other user-facing text further in, and name it in the
+/// response's `Content-Language`
+pub async fn locale_middleware(request: Request, next: Next) -> Response {
+    let locale = request
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(Locale::from_accept_language)
+        .unwrap_or_default();
+
+    let mut response = with_locale(locale, next.run(request)).await;
+    response
+        .headers_mut()
+        .insert(CONTENT_LANGUAGE, HeaderValue::from_static(locale.tag()));
+    response
+}
@@ -1,4 +1,7 @@
 // Infrastructure middleware will be implemented here
+pub mod body_limit;
 pub mod idempotency;
+pub mod locale_middleware;
 pub mod rate_limit_middleware;
 pub mod tenant_middleware;
+pub mod usage_metering;
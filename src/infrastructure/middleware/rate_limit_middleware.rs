@@ -1,191 +1,436 @@
 use axum::{
-    extract::Request,
-    http::{HeaderMap, StatusCode},
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use redis::AsyncCommands;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
 
 use crate::domain::entities::tenant::TenantTier;
+use crate::domain::services::quota_service::QuotaService;
 use crate::infrastructure::middleware::tenant_middleware::TenantContext;
 use crate::infrastructure::observability::metrics::AppMetrics;
+use crate::shared::api_error::ApiError;
+use crate::shared::error::DomainError;
 
+/// Header carrying the caller's API key; each key gets its own bucket within
+/// the tenant's
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Length of the sliding window every bucket counts requests over
+const WINDOW_MS: i64 = 60_000;
+/// How long a tenant's resolved limits are cached before re-reading `tenant_quotas`
+const LIMIT_CACHE_TTL_SECS: i64 = 300;
+
+/// Sliding-window check over every bucket a request counts against. The request
+/// is admitted, and recorded in all of them, only if none is full. Returns
+/// `{admitted, index of the tightest bucket, its remaining, its reset in ms}`.
+const CHECK_SCRIPT: &str = r#"
+local now = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+local counts = {}
+local tightest = 1
+for i, key in ipairs(KEYS) do
+    redis.call('ZREMRANGEBYSCORE', key, '-inf', now - window)
+    counts[i] = redis.call('ZCARD', key)
+    if tonumber(ARGV[3 + i]) - counts[i] < tonumber(ARGV[3 + tightest]) - counts[tightest] then
+        tightest = i
+    end
+end
+local limit = tonumber(ARGV[3 + tightest])
+local admitted = counts[tightest] < limit
+if admitted then
+    for _, key in ipairs(KEYS) do
+        redis.call('ZADD', key, now, ARGV[3])
+        redis.call('PEXPIRE', key, window)
+    end
+    counts[tightest] = counts[tightest] + 1
+end
+local reset = now + window
+local oldest = redis.call('ZRANGE', KEYS[tightest], 0, 0, 'WITHSCORES')
+if oldest[2] then
+    reset = tonumber(oldest[2]) + window
+end
+return {admitted and 1 or 0, tightest - 1, limit - counts[tightest], reset}
+"#;
+
+/// Per-minute request limits for a tenant, after applying `tenant_quotas`
+/// overrides to the tier defaults
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimits {
+    pub per_tenant: i64,
+    pub per_api_key: i64,
+}
+
+/// One sliding-window bucket
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bucket {
+    key: String,
+    limit: i64,
+}
+
+/// Outcome of counting a request against its buckets
+#[derive(Debug, Clone, Copy)]
+struct Decision {
+    admitted: bool,
+    limit: i64,
+    remaining: i64,
+    /// Unix milliseconds when the tightest bucket frees a slot
+    reset_at_ms: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BucketStatus {
+    pub limit: i64,
+    pub used: i64,
+    pub remaining: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiKeyBucketStatus {
+    /// Hash prefix of the API key, never the key itself
+    pub key_id: String,
+    #[serde(flatten)]
+    pub bucket: BucketStatus,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RateLimitStatus {
+    pub tenant_id: Uuid,
+    pub window_secs: i64,
+    pub tenant: BucketStatus,
+    pub api_keys: Vec<ApiKeyBucketStatus>,
+}
+
+/// Sliding-window request limits in Redis. Requests count against their tenant's
+/// bucket and, when they carry `X-API-Key`, against that key's bucket too. Limits
+/// default to the tenant's tier and can be overridden per tenant in
+/// `tenant_quotas`. Requests without a tenant share a bucket per endpoint.
 #[derive(Clone)]
 pub struct RateLimitMiddleware {
     redis_client: redis::Client,
+    quota_service: Arc<dyn QuotaService>,
+    script: redis::Script,
 }
 
 impl RateLimitMiddleware {
-    pub fn new(redis_url: &str) -> Result<Self, redis::RedisError> {
-        let client = redis::Client::open(redis_url)?;
+    pub fn new(
+        redis_url: &str,
+        quota_service: Arc<dyn QuotaService>,
+    ) -> Result<Self, redis::RedisError> {
         Ok(Self {
-            redis_client: client,
+            redis_client: redis::Client::open(redis_url)?,
+            quota_service,
+            script: redis::Script::new(CHECK_SCRIPT),
         })
     }
 
-    pub async fn handle(self, headers: HeaderMap, request: Request, next: Next) -> Response {
-        // Extract tenant context from request extensions
-        let tenant_context = request.extensions().get::<TenantContext>();
+    /// The tenant's limits, from the Redis cache or `tenant_quotas`
+    pub async fn limits(
+        &self,
+        tenant_id: Uuid,
+        tier: &TenantTier,
+    ) -> Result<RateLimits, DomainError> {
+        let cache_key = limits_cache_key(tenant_id);
+        let mut conn = self.connection().await?;
+
+        let (per_tenant, per_api_key): (Option<i64>, Option<i64>) = conn
+            .hget(&cache_key, &["per_tenant", "per_api_key"])
+            .await
+            .map_err(redis_error)?;
+        if let (Some(per_tenant), Some(per_api_key)) = (per_tenant, per_api_key) {
+            return Ok(RateLimits {
+                per_tenant,
+                per_api_key,
+            });
+        }
 
-        let tenant_tier = match tenant_context {
-            Some(ctx) => ctx.tier.clone(),
-            None => {
-                // No tenant context found, default to FREE tier
-                TenantTier::Free
-            }
+        let overrides = self.quota_service.rate_limits(tenant_id).await?;
+        let per_tenant = overrides
+            .requests_per_minute
+            .unwrap_or(i64::from(tier.requests_per_minute()));
+        let limits = RateLimits {
+            per_tenant,
+            per_api_key: overrides
+                .requests_per_minute_per_api_key
+                .unwrap_or(per_tenant),
         };
 
-        // Get the endpoint path
-        let path = request.uri().path();
-
-        // Check rate limit
-        match self.check_rate_limit(&tenant_tier, path).await {
-            Ok((true, remaining, reset_time)) => {
-                // Rate limit passed, continue with request
-                let mut response = next.run(request).await;
-                // Add rate limit headers
-                response.headers_mut().insert(
-                    "X-RateLimit-Remaining",
-                    remaining.to_string().parse().unwrap(),
-                );
-                response
-                    .headers_mut()
-                    .insert("X-RateLimit-Reset", reset_time.to_string().parse().unwrap());
-                response
-            }
-            Ok((false, remaining, reset_time)) => {
-                // Rate limit exceeded
-                let mut response = (
-                    StatusCode::TOO_MANY_REQUESTS,
-                    "Rate limit exceeded. Please try again later.",
-                )
-                    .into_response();
-                response.headers_mut().insert(
-                    "Retry-After",
-                    "60".parse().unwrap(), // Retry after 60 seconds
-                );
-                response.headers_mut().insert(
-                    "X-RateLimit-Remaining",
-                    remaining.to_string().parse().unwrap(),
-                );
-                response
-                    .headers_mut()
-                    .insert("X-RateLimit-Reset", reset_time.to_string().parse().unwrap());
-                response
-            }
-            Err(_) => {
-                // Redis error, allow request to proceed (fail open)
-                next.run(request).await
-            }
-        }
+        let _: () = redis::pipe()
+            .atomic()
+            .hset_multiple(
+                &cache_key,
+                &[
+                    ("per_tenant", limits.per_tenant),
+                    ("per_api_key", limits.per_api_key),
+                ],
+            )
+            .ignore()
+            .expire(&cache_key, LIMIT_CACHE_TTL_SECS)
+            .ignore()
+            .query_async(&mut conn)
+            .await
+            .map_err(redis_error)?;
+        Ok(limits)
     }
 
-    async fn check_rate_limit(
+    /// Current usage of the tenant's bucket and of each API key bucket in use
+    pub async fn status(
         &self,
-        tenant_tier: &TenantTier,
-        endpoint: &str,
-    ) -> Result<(bool, i64, i64), redis::RedisError> {
-        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        tenant_id: Uuid,
+        tier: &TenantTier,
+    ) -> Result<RateLimitStatus, DomainError> {
+        let limits = self.limits(tenant_id, tier).await?;
+        let mut conn = self.connection().await?;
+        let window_start = chrono::Utc::now().timestamp_millis() - WINDOW_MS;
 
-        // Create a key for this tenant tier and endpoint
-        let key = format!("ratelimit:{:?}:{}", tenant_tier, endpoint);
+        let used: i64 = conn
+            .zcount(tenant_bucket_key(tenant_id), window_start, "+inf")
+            .await
+            .map_err(redis_error)?;
 
-        // Use Redis sorted set to track requests in a sliding window
-        let now = chrono::Utc::now().timestamp();
-        let window_start = now - 60; // 60 second window
+        let mut api_keys = Vec::new();
+        for key in self.api_key_buckets(tenant_id).await? {
+            let used: i64 = conn
+                .zcount(&key, window_start, "+inf")
+                .await
+                .map_err(redis_error)?;
+            let key_id = key.rsplit(':').next().unwrap_or_default().to_string();
+            api_keys.push(ApiKeyBucketStatus {
+                key_id,
+                bucket: bucket_status(limits.per_api_key, used),
+            });
+        }
+        api_keys.sort_by(|a, b| a.key_id.cmp(&b.key_id));
+
+        Ok(RateLimitStatus {
+            tenant_id,
+            window_secs: WINDOW_MS / 1000,
+            tenant: bucket_status(limits.per_tenant, used),
+            api_keys,
+        })
+    }
+
+    /// Empty the tenant's buckets and drop its cached limits, so the next request
+    /// starts a fresh window under the current `tenant_quotas`
+    pub async fn reset(&self, tenant_id: Uuid) -> Result<(), DomainError> {
+        let mut keys = self.api_key_buckets(tenant_id).await?;
+        keys.push(tenant_bucket_key(tenant_id));
+        keys.push(limits_cache_key(tenant_id));
+
+        let mut conn = self.connection().await?;
+        let _: () = conn.del(keys).await.map_err(redis_error)?;
+        Ok(())
+    }
 
-        // Remove old entries outside the window
+    /// Drop the tenant's cached limits after its quotas change
+    pub async fn invalidate_limits(&self, tenant_id: Uuid) -> Result<(), DomainError> {
+        let mut conn = self.connection().await?;
         let _: () = conn
-            .zrembyscore(&key, "-inf", &window_start.to_string())
-            .await?;
+            .del(limits_cache_key(tenant_id))
+            .await
+            .map_err(redis_error)?;
+        Ok(())
+    }
+
+    async fn check(&self, buckets: &[Bucket]) -> Result<Decision, DomainError> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let member = format!("{}:{}", now, Uuid::new_v4().simple());
 
-        // Count current requests in the window
-        let current_count: i64 = conn.zcount(&key, "-inf", "+inf").await?;
+        let mut invocation = self.script.prepare_invoke();
+        invocation.arg(now).arg(WINDOW_MS).arg(member);
+        for bucket in buckets {
+            invocation.key(&bucket.key).arg(bucket.limit);
+        }
 
-        // Check if limit exceeded based on tenant tier
-        let limit = tenant_tier.requests_per_minute() as i64;
-        let remaining = limit - current_count;
-        let reset_time = now + 60; // Reset in 60 seconds
+        let mut conn = self.connection().await?;
+        let (admitted, tightest, remaining, reset_at_ms): (i64, usize, i64, i64) = invocation
+            .invoke_async(&mut conn)
+            .await
+            .map_err(redis_error)?;
+
+        Ok(Decision {
+            admitted: admitted == 1,
+            limit: buckets[tightest].limit,
+            remaining: remaining.max(0),
+            reset_at_ms,
+        })
+    }
+
+    /// Buckets for a request, from its tenant, path and `X-API-Key`
+    async fn buckets(
+        &self,
+        tenant: Option<&TenantContext>,
+        path: &str,
+        api_key_id: Option<String>,
+    ) -> Result<Vec<Bucket>, DomainError> {
+        let Some(ctx) = tenant else {
+            return Ok(vec![Bucket {
+                key: format!("ratelimit:anonymous:{}", path),
+                limit: i64::from(TenantTier::Free.requests_per_minute()),
+            }]);
+        };
 
-        if current_count >= limit {
-            return Ok((false, remaining, reset_time));
+        let limits = self.limits(ctx.tenant_id, &ctx.tier).await?;
+        let mut buckets = vec![Bucket {
+            key: tenant_bucket_key(ctx.tenant_id),
+            limit: limits.per_tenant,
+        }];
+        if let Some(key_id) = api_key_id {
+            buckets.push(Bucket {
+                key: api_key_bucket_key(ctx.tenant_id, &key_id),
+                limit: limits.per_api_key,
+            });
         }
+        Ok(buckets)
+    }
 
-        // Add current request with unique member
-        let request_id = format!("{}:{}", now, uuid::Uuid::new_v4().simple());
-        let _: () = conn.zadd(&key, &request_id, now).await?;
+    async fn api_key_buckets(&self, tenant_id: Uuid) -> Result<Vec<String>, DomainError> {
+        let mut conn = self.connection().await?;
+        let mut iter: redis::AsyncIter<String> = conn
+            .scan_match(api_key_bucket_key(tenant_id, "*"))
+            .await
+            .map_err(redis_error)?;
 
-        // Set expiration on the key (cleanup after window + buffer)
-        let _: () = conn.expire(&key, 120).await?; // 2 minutes
+        let mut keys = Vec::new();
+        while let Some(key) = iter.next_item().await {
+            keys.push(key);
+        }
+        Ok(keys)
+    }
 
-        Ok((true, remaining - 1, reset_time)) // remaining - 1 because we just added one
+    async fn connection(&self) -> Result<redis::aio::MultiplexedConnection, DomainError> {
+        self.redis_client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(redis_error)
     }
 }
 
 pub async fn rate_limit_middleware(
-    state: axum::extract::State<Arc<RateLimitMiddleware>>,
-    headers: HeaderMap,
+    State(limiter): State<Arc<RateLimitMiddleware>>,
     request: Request,
     next: Next,
 ) -> Response {
-    // Extract tenant context from request extensions
-    let tenant_context = request.extensions().get::<TenantContext>();
-
-    let tenant_tier = match tenant_context {
-        Some(ctx) => ctx.tier.clone(),
-        None => {
-            // No tenant context found, default to FREE tier
-            TenantTier::Free
-        }
+    let tenant = request.extensions().get::<TenantContext>().cloned();
+    let path = request.uri().path().to_string();
+    let key_id = api_key_id(request.headers());
+    let decision = async {
+        let buckets = limiter.buckets(tenant.as_ref(), &path, key_id).await?;
+        limiter.check(&buckets).await
     };
 
-    // Get the endpoint path
-    let path = request.uri().path();
-
-    // Check rate limit
-    match state.check_rate_limit(&tenant_tier, path).await {
-        Ok((true, remaining, reset_time)) => {
-            // Rate limit passed, continue with request
-            let mut response = next.run(request).await;
-            // Add rate limit headers
-            response.headers_mut().insert(
-                "X-RateLimit-Remaining",
-                remaining.to_string().parse().unwrap(),
-            );
-            response
-                .headers_mut()
-                .insert("X-RateLimit-Reset", reset_time.to_string().parse().unwrap());
-            response
-        }
-        Ok((false, remaining, reset_time)) => {
-            // Rate limit exceeded - record metrics
-            if let Some(ctx) = tenant_context {
-                AppMetrics::get()
-                    .record_rate_limit_hit(&ctx.tenant_id.to_string(), &format!("{:?}", ctx.tier));
-            }
-
-            let mut response = (
-                StatusCode::TOO_MANY_REQUESTS,
-                "Rate limit exceeded. Please try again later.",
-            )
-                .into_response();
-            response.headers_mut().insert(
-                "Retry-After",
-                "60".parse().unwrap(), // Retry after 60 seconds
-            );
-            response.headers_mut().insert(
-                "X-RateLimit-Remaining",
-                remaining.to_string().parse().unwrap(),
-            );
-            response
-                .headers_mut()
-                .insert("X-RateLimit-Reset", reset_time.to_string().parse().unwrap());
-            response
-        }
-        Err(_) => {
+    let decision = match decision.await {
+        Ok(decision) => decision,
+        Err(e) => {
             // Redis error, allow request to proceed (fail open)
-            next.run(request).await
+            warn!(error = %e, "Failed to check rate limit");
+            return next.run(request).await;
+        }
+    };
+
+    let mut response = if decision.admitted {
+        next.run(request).await
+    } else {
+        if let Some(ctx) = &tenant {
+            AppMetrics::get()
+                .record_rate_limit_hit(&ctx.tenant_id.to_string(), &format!("{:?}", ctx.tier));
         }
+
+        let mut response = ApiError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "RATE_LIMITED",
+            "Rate limit exceeded. Please try again later.",
+        )
+        .into_response();
+        let now = chrono::Utc::now().timestamp_millis();
+        response.headers_mut().insert(
+            "Retry-After",
+            HeaderValue::from(retry_after_secs(now, decision.reset_at_ms)),
+        );
+        response
+    };
+
+    let headers = response.headers_mut();
+    headers.insert("X-RateLimit-Limit", HeaderValue::from(decision.limit));
+    headers.insert(
+        "X-RateLimit-Remaining",
+        HeaderValue::from(decision.remaining),
+    );
+    headers.insert(
+        "X-RateLimit-Reset",
+        HeaderValue::from((decision.reset_at_ms + 999) / 1000),
+    );
+    response
+}
+
+fn bucket_status(limit: i64, used: i64) -> BucketStatus {
+    BucketStatus {
+        limit,
+        used,
+        remaining: (limit - used).max(0),
+    }
+}
+
+/// Whole seconds until `reset_at_ms`, rounded up and at least one
+fn retry_after_secs(now_ms: i64, reset_at_ms: i64) -> i64 {
+    ((reset_at_ms - now_ms + 999) / 1000).max(1)
+}
+
+/// Stable identifier for the request's API key. The key is hashed so it never
+/// appears in Redis or in admin responses.
+fn api_key_id(headers: &HeaderMap) -> Option<String> {
+    let key = headers.get(API_KEY_HEADER)?.to_str().ok()?.trim();
+    if key.is_empty() {
+        return None;
+    }
+    Some(hex::encode(&Sha256::digest(key.as_bytes())[..8]))
+}
+
+fn tenant_bucket_key(tenant_id: Uuid) -> String {
+    format!("ratelimit:tenant:{}", tenant_id)
+}
+
+fn api_key_bucket_key(tenant_id: Uuid, key_id: &str) -> String {
+    format!("ratelimit:key:{}:{}", tenant_id, key_id)
+}
+
+fn limits_cache_key(tenant_id: Uuid) -> String {
+    format!("ratelimit:limits:{}", tenant_id)
+}
+
+fn redis_error(e: redis::RedisError) -> DomainError {
+    DomainError::InfrastructureError(format!("Redis error: {}", e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_id_hashes_the_key() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(api_key_id(&headers), None);
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("secret-key"));
+        let key_id = api_key_id(&headers).unwrap();
+        assert_eq!(key_id.len(), 16);
+        assert!(!key_id.contains("secret"));
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("   "));
+        assert_eq!(api_key_id(&headers), None);
+    }
+
+    #[test]
+    fn test_retry_after_rounds_up_to_whole_seconds() {
+        assert_eq!(retry_after_secs(10_000, 10_001), 1);
+        assert_eq!(retry_after_secs(10_000, 12_000), 2);
+        assert_eq!(retry_after_secs(10_000, 12_001), 3);
+        assert_eq!(retry_after_secs(10_000, 9_000), 1);
     }
 }
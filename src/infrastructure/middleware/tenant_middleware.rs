@@ -1,22 +1,76 @@
 use axum::{
     extract::Request,
-    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    http::{header::AUTHORIZATION, HeaderMap},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use jsonwebtoken::{decode, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::domain::entities::tenant::TenantTier;
+use crate::domain::services::session_repository::SessionRepository;
 use crate::domain::services::tenant_repository::TenantRepository;
+use crate::shared::api_error::ApiError;
+use crate::shared::tenant_scope::{with_actor, with_tenant};
+
+/// Header naming the tenant for callers that don't send a bearer token, honored only
+/// when `auth.allow_tenant_header` is set
+pub const TENANT_ID_HEADER: &str = "x-tenant-id";
+
+/// Routes served without a tenant: health checks, login (which issues the tenant's
+/// token), the password reset flow and invitation redemption (their tokens name the
+/// user), metrics scraping, and signed blob links, whose signature is the authorization
+const TENANT_EXEMPT_PATHS: &[&str] = &[
+    "/healthz",
+    "/auth/login",
+    "/auth/forgot-password",
+    "/auth/reset-password",
+    "/users/register",
+    "/metrics",
+];
+const TENANT_EXEMPT_PREFIXES: &[&str] = &["/blobs/", "/sandbox/echo/"];
+
+/// What a session that must enroll in two-factor login can still reach:
+/// enrollment itself, and its own sessions so it can log out
+const TWO_FACTOR_ENROLLMENT_PREFIXES: &[&str] = &["/auth/2fa", "/auth/sessions"];
 
 #[derive(Debug, Clone)]
 pub struct TenantContext {
     pub tenant_id: Uuid,
     pub tier: TenantTier,
+    /// The logged-in user, when the tenant came from a bearer token
+    pub user_id: Option<Uuid>,
+    /// The login session the bearer token belongs to
+    pub session_id: Option<Uuid>,
+    /// The session may only be used to enroll in two-factor login
+    pub two_factor_enrollment_required: bool,
+}
+
+impl TenantContext {
+    /// Refuse everything but two-factor enrollment to a session that must enroll
+    pub fn check_path(&self, path: &str) -> Result<(), ApiError> {
+        if self.two_factor_enrollment_required
+            && !TWO_FACTOR_ENROLLMENT_PREFIXES
+                .iter()
+                .any(|prefix| path.starts_with(prefix))
+        {
+            return Err(ApiError::forbidden(
+                "Your tenant requires two-factor authentication for admins; enroll at /auth/2fa/enroll",
+            )
+            .with_code("TWO_FACTOR_ENROLLMENT_REQUIRED"));
+        }
+        Ok(())
+    }
+}
+
+/// Who a login token names
+struct TokenIdentity {
+    tenant_id: Uuid,
+    user_id: Uuid,
+    session_id: Uuid,
+    two_factor_enrollment_required: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,110 +78,378 @@ struct Claims {
     sub: String,
     email: String,
     tenant_id: String,
+    sid: String,
     exp: usize,
     iat: usize,
 }
 
 #[derive(Clone)]
 pub struct TenantMiddleware {
-    pool: Arc<PgPool>,
     jwt_secret: String,
+    /// Accept `X-Tenant-ID` from callers without a bearer token
+    allow_tenant_header: bool,
     tenant_repository: Arc<dyn TenantRepository>,
+    session_repository: Arc<dyn SessionRepository>,
 }
 
 impl TenantMiddleware {
     pub fn new(
-        pool: Arc<PgPool>,
         jwt_secret: String,
+        allow_tenant_header: bool,
         tenant_repository: Arc<dyn TenantRepository>,
+        session_repository: Arc<dyn SessionRepository>,
     ) -> Self {
         Self {
-            pool,
             jwt_secret,
+            allow_tenant_header,
             tenant_repository,
+            session_repository,
         }
     }
 
     pub async fn handle(&self, headers: HeaderMap, mut request: Request, next: Next) -> Response {
-        // Extract tenant_id from JWT token or X-Tenant-ID header
-        let tenant_id = match self.extract_tenant_from_token(&headers).await {
-            Ok(Some(tenant_id)) => Some(tenant_id),
-            _ => {
-                // Check for X-Tenant-ID header (for testing/development)
-                if let Some(tenant_header) = headers.get("x-tenant-id") {
-                    if let Ok(tenant_str) = tenant_header.to_str() {
-                        uuid::Uuid::parse_str(tenant_str).ok()
-                    } else {
-                        None
-                    }
-                } else {
-                    // Default tenant for development
-                    Some(uuid::Uuid::parse_str("d60a7de9-1009-4606-aae9-ae6ffe5827aa").unwrap())
-                }
-            }
+        if is_tenant_exempt(request.uri().path()) {
+            return next.run(request).await;
+        }
+
+        let tenant_context = match self.resolve(&headers).await {
+            Ok(tenant_context) => tenant_context,
+            Err(e) => return e.into_response(),
         };
+        if let Err(e) = tenant_context.check_path(request.uri().path()) {
+            return e.into_response();
+        }
+        let tenant_id = tenant_context.tenant_id;
+        let user_id = tenant_context.user_id;
+
+        // Store tenant context in request extensions for use by other middleware and handlers
+        request.extensions_mut().insert(tenant_context);
 
-        if let Some(tenant_id) = tenant_id {
-            // Set tenant context in database session
-            if let Err(_) = sqlx::query("SELECT set_tenant_context($1)")
-                .bind(tenant_id)
-                .execute(&*self.pool)
-                .await
-            {
-                // If setting tenant context fails, return error
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Failed to set tenant context",
-                )
-                    .into_response();
-            }
-
-            // Look up tenant tier from database
-            let tier = match self.tenant_repository.get_tenant_tier(tenant_id).await {
-                Ok(Some(tier)) => tier,
-                _ => {
-                    // If tenant not found or error, default to FREE tier
-                    TenantTier::Free
+        match user_id {
+            Some(user_id) => with_tenant(tenant_id, with_actor(user_id, next.run(request))).await,
+            None => with_tenant(tenant_id, next.run(request)).await,
+        }
+    }
+
+    /// Resolve the tenant from the bearer token, or from `X-Tenant-ID` when no token is
+    /// sent and the header is allowed, and check that it exists
+    pub async fn resolve(&self, headers: &HeaderMap) -> Result<TenantContext, ApiError> {
+        let (tenant_id, user_id, session_id, two_factor_enrollment_required) =
+            match bearer_token(headers) {
+                Some(token) => {
+                    let identity = self.identity_from_token(token).await?;
+                    (
+                        identity.tenant_id,
+                        Some(identity.user_id),
+                        Some(identity.session_id),
+                        identity.two_factor_enrollment_required,
+                    )
+                }
+                None if self.allow_tenant_header => {
+                    (tenant_from_header(headers)?, None, None, false)
+                }
+                None => {
+                    return Err(ApiError::unauthorized("A bearer token is required")
+                        .with_code("TOKEN_REQUIRED"))
                 }
             };
 
-            let tenant_context = TenantContext { tenant_id, tier };
+        let tier = self
+            .tenant_repository
+            .get_tenant_tier(tenant_id)
+            .await?
+            .ok_or_else(|| ApiError::unauthorized("Unknown tenant").with_code("UNKNOWN_TENANT"))?;
+
+        Ok(TenantContext {
+            tenant_id,
+            tier,
+            user_id,
+            session_id,
+            two_factor_enrollment_required,
+        })
+    }
+
+    /// The tenant, user and session named by a login token, provided the session
+    /// hasn't been revoked
+    async fn identity_from_token(&self, token: &str) -> Result<TokenIdentity, ApiError> {
+        let invalid_token =
+            || ApiError::unauthorized("Invalid or expired token").with_code("INVALID_TOKEN");
+
+        let token_data = decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(self.jwt_secret.as_ref()),
+            &Validation::default(),
+        )
+        .map_err(|_| invalid_token())?;
 
-            // Store tenant context in request extensions for use by other middleware and handlers
-            request.extensions_mut().insert(tenant_context);
+        let tenant_id =
+            Uuid::parse_str(&token_data.claims.tenant_id).map_err(|_| invalid_token())?;
+        let user_id = Uuid::parse_str(&token_data.claims.sub).map_err(|_| invalid_token())?;
+        let session_id = Uuid::parse_str(&token_data.claims.sid).map_err(|_| invalid_token())?;
+
+        let session = self
+            .session_repository
+            .find_by_id(session_id)
+            .await?
+            .filter(|session| session.user_id == user_id && session.is_active())
+            .ok_or_else(invalid_token)?;
+
+        Ok(TokenIdentity {
+            tenant_id,
+            user_id,
+            session_id,
+            two_factor_enrollment_required: session.two_factor_enrollment_required,
+        })
+    }
+}
+
+fn is_tenant_exempt(path: &str) -> bool {
+    TENANT_EXEMPT_PATHS.contains(&path)
+        || TENANT_EXEMPT_PREFIXES
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+fn tenant_from_header(headers: &HeaderMap) -> Result<Uuid, ApiError> {
+    let value = headers.get(TENANT_ID_HEADER).ok_or_else(|| {
+        ApiError::unauthorized("A bearer token or X-Tenant-ID header is required")
+            .with_code("TENANT_REQUIRED")
+    })?;
+
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| Uuid::parse_str(value).ok())
+        .ok_or_else(|| {
+            ApiError::bad_request("X-Tenant-ID must be a UUID").with_code("INVALID_TENANT_ID")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::session::Session;
+    use crate::domain::services::session_repository::MockSessionRepository;
+    use crate::domain::services::tenant_repository::MockTenantRepository;
+    use axum::http::{HeaderValue, StatusCode};
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    const SECRET: &str = "test-secret";
+
+    /// A middleware that accepts `X-Tenant-ID` without a token
+    fn middleware(tier: Option<TenantTier>) -> TenantMiddleware {
+        TenantMiddleware {
+            allow_tenant_header: true,
+            ..with_sessions(tier, Vec::new())
         }
+    }
+
+    fn with_sessions(tier: Option<TenantTier>, sessions: Vec<Session>) -> TenantMiddleware {
+        let mut repository = MockTenantRepository::new();
+        repository
+            .expect_get_tenant_tier()
+            .returning(move |_| Ok(tier.clone()));
+        let mut session_repository = MockSessionRepository::new();
+        session_repository
+            .expect_find_by_id()
+            .returning(move |id| Ok(sessions.iter().find(|s| s.id == id).cloned()));
+        TenantMiddleware::new(
+            SECRET.to_string(),
+            false,
+            Arc::new(repository),
+            Arc::new(session_repository),
+        )
+    }
 
-        next.run(request).await
+    fn session(tenant_id: Uuid, user_id: Uuid) -> Session {
+        Session::new(user_id, tenant_id, None, None, chrono::Duration::hours(1))
     }
 
-    async fn extract_tenant_from_token(
-        &self,
-        headers: &HeaderMap,
-    ) -> Result<Option<uuid::Uuid>, Box<dyn std::error::Error + Send + Sync>> {
-        let auth_header = match headers.get(AUTHORIZATION) {
-            Some(header) => header.to_str()?,
-            None => return Ok(None),
+    fn token(session: &Session, secret: &str) -> String {
+        let claims = Claims {
+            sub: session.user_id.to_string(),
+            email: "user@example.com".to_string(),
+            tenant_id: session.tenant_id.to_string(),
+            sid: session.id.to_string(),
+            exp: (chrono::Utc::now().timestamp() + 3600) as usize,
+            iat: chrono::Utc::now().timestamp() as usize,
         };
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(secret.as_ref()),
+        )
+        .unwrap()
+    }
+
+    /// Status and error code the client would see
+    async fn rejection(error: ApiError) -> (StatusCode, String) {
+        let response = error.into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        (status, body["error"].as_str().unwrap().to_string())
+    }
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[tokio::test]
+    async fn test_resolves_tenant_from_token_claims() {
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let session = session(tenant_id, user_id);
+        let mut headers = headers(
+            "authorization",
+            &format!("Bearer {}", token(&session, SECRET)),
+        );
+        // The token wins over a header naming another tenant
+        headers.insert(
+            TENANT_ID_HEADER,
+            HeaderValue::from_str(&Uuid::new_v4().to_string()).unwrap(),
+        );
+
+        let session_id = session.id;
+        let context = with_sessions(Some(TenantTier::Growth), vec![session])
+            .resolve(&headers)
+            .await
+            .unwrap();
+        assert_eq!(context.tenant_id, tenant_id);
+        assert_eq!(context.user_id, Some(user_id));
+        assert_eq!(context.session_id, Some(session_id));
+        assert!(matches!(context.tier, TenantTier::Growth));
+    }
 
-        if !auth_header.starts_with("Bearer ") {
-            return Ok(None);
+    #[tokio::test]
+    async fn test_rejects_requests_without_a_resolvable_tenant() {
+        let middleware = middleware(Some(TenantTier::Free));
+
+        let missing = middleware.resolve(&HeaderMap::new()).await.unwrap_err();
+        assert_eq!(
+            rejection(missing).await,
+            (StatusCode::UNAUTHORIZED, "TENANT_REQUIRED".to_string())
+        );
+
+        let forged = headers(
+            "authorization",
+            &format!(
+                "Bearer {}",
+                token(&session(Uuid::new_v4(), Uuid::new_v4()), "other-secret")
+            ),
+        );
+        let forged = middleware.resolve(&forged).await.unwrap_err();
+        assert_eq!(
+            rejection(forged).await,
+            (StatusCode::UNAUTHORIZED, "INVALID_TOKEN".to_string())
+        );
+
+        let malformed = headers(TENANT_ID_HEADER, "not-a-uuid");
+        let malformed = middleware.resolve(&malformed).await.unwrap_err();
+        assert_eq!(
+            rejection(malformed).await,
+            (StatusCode::BAD_REQUEST, "INVALID_TENANT_ID".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tenant_header_requires_opting_in() {
+        let headers = headers(TENANT_ID_HEADER, &Uuid::new_v4().to_string());
+
+        let error = with_sessions(Some(TenantTier::Free), Vec::new())
+            .resolve(&headers)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            rejection(error).await,
+            (StatusCode::UNAUTHORIZED, "TOKEN_REQUIRED".to_string())
+        );
+        assert!(middleware(Some(TenantTier::Free))
+            .resolve(&headers)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_tokens_of_revoked_or_unknown_sessions() {
+        let mut revoked = session(Uuid::new_v4(), Uuid::new_v4());
+        revoked.revoked_at = Some(chrono::Utc::now());
+        let unknown = session(Uuid::new_v4(), Uuid::new_v4());
+        let middleware = with_sessions(Some(TenantTier::Free), vec![revoked.clone()]);
+
+        for session in [&revoked, &unknown] {
+            let headers = headers(
+                "authorization",
+                &format!("Bearer {}", token(session, SECRET)),
+            );
+            let error = middleware.resolve(&headers).await.unwrap_err();
+            assert_eq!(
+                rejection(error).await,
+                (StatusCode::UNAUTHORIZED, "INVALID_TOKEN".to_string())
+            );
         }
+    }
 
-        let token = &auth_header[7..]; // Remove "Bearer " prefix
+    #[tokio::test]
+    async fn test_sessions_that_must_enroll_only_reach_enrollment() {
+        let mut session = session(Uuid::new_v4(), Uuid::new_v4());
+        session.two_factor_enrollment_required = true;
+        let headers = headers(
+            "authorization",
+            &format!("Bearer {}", token(&session, SECRET)),
+        );
 
-        let token_data = decode::<Claims>(
-            token,
-            &DecodingKey::from_secret(self.jwt_secret.as_ref()),
-            &Validation::default(),
-        )?;
+        let context = with_sessions(Some(TenantTier::Free), vec![session])
+            .resolve(&headers)
+            .await
+            .unwrap();
+        assert!(context.check_path("/auth/2fa/enroll").is_ok());
+        assert!(context.check_path("/auth/sessions").is_ok());
+        assert_eq!(
+            rejection(context.check_path("/items").unwrap_err()).await,
+            (
+                StatusCode::FORBIDDEN,
+                "TWO_FACTOR_ENROLLMENT_REQUIRED".to_string()
+            )
+        );
+    }
 
-        let tenant_id = uuid::Uuid::parse_str(&token_data.claims.tenant_id)?;
-        Ok(Some(tenant_id))
+    #[tokio::test]
+    async fn test_rejects_unknown_tenant() {
+        let headers = headers(TENANT_ID_HEADER, &Uuid::new_v4().to_string());
+        let error = middleware(None).resolve(&headers).await.unwrap_err();
+        assert_eq!(
+            rejection(error).await,
+            (StatusCode::UNAUTHORIZED, "UNKNOWN_TENANT".to_string())
+        );
     }
-}
 
-pub async fn tenant_middleware(headers: HeaderMap, request: Request, next: Next) -> Response {
-    // This is a placeholder - in a real implementation, you'd get the middleware from app state
-    // For now, we'll skip tenant validation and just pass through
-    next.run(request).await
+    #[test]
+    fn test_only_public_routes_are_exempt() {
+        assert!(is_tenant_exempt("/healthz"));
+        assert!(is_tenant_exempt("/auth/login"));
+        assert!(is_tenant_exempt("/auth/reset-password"));
+        assert!(is_tenant_exempt("/users/register"));
+        assert!(is_tenant_exempt("/blobs/exports/stock.csv"));
+        assert!(is_tenant_exempt(
+            "/sandbox/echo/550e8400-e29b-41d4-a716-446655440001"
+        ));
+        assert!(!is_tenant_exempt("/sandbox/echo"));
+        assert!(!is_tenant_exempt("/items"));
+        assert!(!is_tenant_exempt("/users"));
+        assert!(!is_tenant_exempt("/auth/change-password"));
+        assert!(!is_tenant_exempt("/auth/login/extra"));
+    }
 }
@@ -0,0 +1,259 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use redis::AsyncCommands;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::domain::services::quota_service::QuotaService;
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::shared::api_error::ApiError;
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::with_tenant;
+
+/// Set of `usage:pending:*` hashes holding counts not yet written to Postgres
+const PENDING_SET: &str = "usage:pending";
+/// Hourly totals are kept for one extra hour, so each tenant has a ring of at most
+/// two live windows
+const HOURLY_TOTAL_TTL_SECS: i64 = 7200;
+/// How long a tenant's `max_api_calls_per_hour` is cached before re-reading it
+const LIMIT_CACHE_TTL_SECS: u64 = 300;
+
+/// Counts API calls per tenant and endpoint in Redis, rejects calls beyond the
+/// tenant's `max_api_calls_per_hour`, and periodically flushes the counts into
+/// `api_rate_limits` for billing
+pub struct UsageMetering {
+    redis_client: redis::Client,
+    pool: Arc<PgPool>,
+    quota_service: Arc<dyn QuotaService>,
+}
+
+impl UsageMetering {
+    pub fn new(
+        redis_url: &str,
+        pool: Arc<PgPool>,
+        quota_service: Arc<dyn QuotaService>,
+    ) -> Result<Self, redis::RedisError> {
+        Ok(Self {
+            redis_client: redis::Client::open(redis_url)?,
+            pool,
+            quota_service,
+        })
+    }
+
+    /// Count one call and return the tenant's total for the current hour
+    async fn record(&self, tenant_id: Uuid, endpoint: &str) -> Result<i64, DomainError> {
+        let hour = hour_start(chrono::Utc::now().timestamp());
+        let pending_key = pending_key(tenant_id, hour);
+        let total_key = format!("usage:total:{}:{}", tenant_id, hour);
+
+        let mut conn = self.connection().await?;
+        let (total,): (i64,) = redis::pipe()
+            .atomic()
+            .hincr(&pending_key, endpoint, 1)
+            .ignore()
+            .sadd(PENDING_SET, &pending_key)
+            .ignore()
+            .incr(&total_key, 1)
+            .expire(&total_key, HOURLY_TOTAL_TTL_SECS)
+            .ignore()
+            .query_async(&mut conn)
+            .await
+            .map_err(redis_error)?;
+        Ok(total)
+    }
+
+    async fn api_calls_per_hour(&self, tenant_id: Uuid) -> Result<i64, DomainError> {
+        let cache_key = format!("usage:limit:{}", tenant_id);
+        let mut conn = self.connection().await?;
+
+        let cached: Option<i64> = conn.get(&cache_key).await.map_err(redis_error)?;
+        if let Some(limit) = cached {
+            return Ok(limit);
+        }
+
+        let limit = self.quota_service.api_calls_per_hour(tenant_id).await?;
+        let _: () = conn
+            .set_ex(&cache_key, limit, LIMIT_CACHE_TTL_SECS)
+            .await
+            .map_err(redis_error)?;
+        Ok(limit)
+    }
+
+    /// Move pending counts from Redis into `api_rate_limits`. Counts whose write fails
+    /// are put back so the next flush retries them.
+    pub async fn flush(&self) -> Result<(), DomainError> {
+        let mut conn = self.connection().await?;
+        let pending_keys: Vec<String> = conn.smembers(PENDING_SET).await.map_err(redis_error)?;
+
+        for pending_key in pending_keys {
+            let Some((tenant_id, hour)) = parse_pending_key(&pending_key) else {
+                let _: () = conn
+                    .srem(PENDING_SET, &pending_key)
+                    .await
+                    .map_err(redis_error)?;
+                continue;
+            };
+
+            let (counts,): (HashMap<String, i64>,) = redis::pipe()
+                .atomic()
+                .hgetall(&pending_key)
+                .del(&pending_key)
+                .ignore()
+                .srem(PENDING_SET, &pending_key)
+                .ignore()
+                .query_async(&mut conn)
+                .await
+                .map_err(redis_error)?;
+
+            if let Err(e) = self.store_counts(tenant_id, hour, &counts).await {
+                let mut restore = redis::pipe();
+                for (endpoint, count) in &counts {
+                    restore.hincr(&pending_key, endpoint, *count).ignore();
+                }
+                restore.sadd(PENDING_SET, &pending_key).ignore();
+                let _: () = restore.query_async(&mut conn).await.map_err(redis_error)?;
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn store_counts(
+        &self,
+        tenant_id: Uuid,
+        hour: i64,
+        counts: &HashMap<String, i64>,
+    ) -> Result<(), DomainError> {
+        // api_rate_limits is under row-level security, so write as the tenant
+        with_tenant(tenant_id, async {
+            let mut tx = self.pool.begin().await?;
+            for (endpoint, count) in counts {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO api_rate_limits (tenant_id, endpoint, window_start, request_count)
+                    VALUES ($1, $2, to_timestamp($3), $4)
+                    ON CONFLICT (tenant_id, endpoint, window_start)
+                    DO UPDATE SET request_count = api_rate_limits.request_count + EXCLUDED.request_count
+                    "#,
+                    tenant_id,
+                    endpoint,
+                    hour as f64,
+                    *count as i32
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn connection(&self) -> Result<redis::aio::MultiplexedConnection, DomainError> {
+        self.redis_client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(redis_error)
+    }
+}
+
+pub async fn usage_metering_middleware(
+    State(metering): State<Arc<UsageMetering>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // Tenant-exempt routes aren't metered
+    let Some(tenant_id) = request
+        .extensions()
+        .get::<TenantContext>()
+        .map(|ctx| ctx.tenant_id)
+    else {
+        return next.run(request).await;
+    };
+
+    // Meter by route template so `/items/{id}` is one endpoint, not one per item
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let endpoint = format!("{} {}", request.method(), path);
+
+    let usage = async {
+        let calls = metering.record(tenant_id, &endpoint).await?;
+        let limit = metering.api_calls_per_hour(tenant_id).await?;
+        Ok::<_, DomainError>((calls, limit))
+    };
+
+    match usage.await {
+        Ok((calls, limit)) if calls > limit => {
+            let now = chrono::Utc::now().timestamp();
+            let retry_after = hour_start(now) + 3600 - now;
+            let mut response = ApiError::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                "QUOTA_EXCEEDED",
+                format!("Hourly API call quota of {} exceeded", limit),
+            )
+            .into_response();
+            response
+                .headers_mut()
+                .insert("Retry-After", HeaderValue::from(retry_after));
+            response
+        }
+        Ok(_) => next.run(request).await,
+        Err(e) => {
+            // Metering is best effort: a Redis outage shouldn't take the API down
+            warn!(error = %e, "Failed to meter API call");
+            next.run(request).await
+        }
+    }
+}
+
+/// Start of the hour containing `timestamp`
+fn hour_start(timestamp: i64) -> i64 {
+    timestamp - timestamp.rem_euclid(3600)
+}
+
+fn pending_key(tenant_id: Uuid, hour: i64) -> String {
+    format!("usage:pending:{}:{}", tenant_id, hour)
+}
+
+fn parse_pending_key(key: &str) -> Option<(Uuid, i64)> {
+    let (tenant_id, hour) = key.strip_prefix("usage:pending:")?.split_once(':')?;
+    Some((Uuid::parse_str(tenant_id).ok()?, hour.parse().ok()?))
+}
+
+fn redis_error(e: redis::RedisError) -> DomainError {
+    DomainError::InfrastructureError(format!("Redis error: {}", e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hour_start_truncates_to_the_hour() {
+        assert_eq!(hour_start(7200), 7200);
+        assert_eq!(hour_start(7200 + 3599), 7200);
+        assert_eq!(hour_start(7200 + 3600), 10800);
+    }
+
+    #[test]
+    fn test_pending_key_round_trips() {
+        let tenant_id = Uuid::new_v4();
+        assert_eq!(
+            parse_pending_key(&pending_key(tenant_id, 7200)),
+            Some((tenant_id, 7200))
+        );
+        assert_eq!(parse_pending_key("usage:pending:not-a-uuid:7200"), None);
+        assert_eq!(parse_pending_key("usage:total:whatever"), None);
+    }
+}
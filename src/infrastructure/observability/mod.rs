@@ -78,6 +78,12 @@ pub fn init_observability() -> Result<(), Box<dyn std::error::Error + Send + Syn
     Ok(())
 }
 
+/// Flush buffered spans to the OTLP exporter and stop the tracer provider. Call
+/// once, after the server and background workers have stopped.
+pub fn shutdown_observability() {
+    global::shutdown_tracer_provider();
+}
+
 /// Get the global Prometheus registry
 pub fn get_prometheus_registry() -> Arc<Registry> {
     Arc::clone(
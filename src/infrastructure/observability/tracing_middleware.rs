@@ -1,5 +1,6 @@
 use axum::{
     extract::{MatchedPath, Request},
+    http::HeaderValue,
     middleware::Next,
     response::Response,
 };
@@ -8,6 +9,25 @@ use opentelemetry::{global, KeyValue};
 use std::time::Instant;
 
 use crate::infrastructure::observability::metrics::AppMetrics;
+use crate::shared::trace_id::{current_trace_id, trace_id_or_new, with_trace_id, TRACE_ID_HEADER};
+
+/// Outermost middleware: gives every request a trace ID (the caller's `X-Trace-Id`
+/// if it sent a usable one), echoes it in the response and makes it available to
+/// error responses further in
+pub async fn trace_id_middleware(request: Request, next: Next) -> Response {
+    let trace_id = trace_id_or_new(
+        request
+            .headers()
+            .get(TRACE_ID_HEADER)
+            .and_then(|value| value.to_str().ok()),
+    );
+
+    let mut response = with_trace_id(trace_id.clone(), next.run(request)).await;
+    if let Ok(value) = HeaderValue::from_str(&trace_id) {
+        response.headers_mut().insert(TRACE_ID_HEADER, value);
+    }
+    response
+}
 
 /// Middleware that creates OpenTelemetry spans for HTTP requests
 pub async fn tracing_middleware(request: Request, next: Next) -> Response {
@@ -29,6 +49,7 @@ pub async fn tracing_middleware(request: Request, next: Next) -> Response {
         "http.method" = %method,
         "http.url" = %uri,
         "http.route" = matched_path,
+        "trace_id" = current_trace_id().as_deref().unwrap_or("-"),
         "network.transport" = "ip_tcp"
     );
 
@@ -73,6 +73,17 @@ impl<R: IdempotencyRepository, P: IdempotencyRepository> IdempotencyRepository
         }
     }
 
+    async fn delete_key(&self, idempotency_key: &str) -> Result<(), DomainError> {
+        // The key may have been stored in either, so clear both
+        let primary_result = self.primary.delete_key(idempotency_key).await;
+        let fallback_result = self.fallback.delete_key(idempotency_key).await;
+
+        match (primary_result, fallback_result) {
+            (Ok(()), _) | (_, Ok(())) => Ok(()),
+            (Err(e), _) => Err(e),
+        }
+    }
+
     async fn delete_expired_keys(&self) -> Result<i64, DomainError> {
         // Clean up both stores
         let primary_result = self.primary.delete_expired_keys().await;
@@ -0,0 +1,286 @@
+//! Timings for the order list queries against a real database, comparing the
+//! batched `list` with loading each row through `find_by_id`. They seed and then
+//! remove a throwaway tenant, so they only run on request:
+//!
+//! `cargo test --release list_benchmarks -- --ignored --nocapture --test-threads=1`
+//!
+//! with `DATABASE_URL` pointing at a migrated database.
+
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
+use crate::domain::services::return_repository::ReturnRepository;
+use crate::domain::services::transfer_repository::TransferRepository;
+use crate::infrastructure::config::app_config::DatabaseConfig;
+use crate::infrastructure::repositories::postgres_purchase_order_repository::PostgresPurchaseOrderRepository;
+use crate::infrastructure::repositories::postgres_return_repository::PostgresReturnRepository;
+use crate::infrastructure::repositories::postgres_transfer_repository::PostgresTransferRepository;
+use crate::infrastructure::repositories::tenant_pool::connect_tenant_pool;
+use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
+use crate::shared::tenant_scope::with_tenant;
+use sqlx::PgPool;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Orders per page, the list endpoints' default
+const PAGE_SIZE: usize = 50;
+const LINES_PER_ORDER: usize = 5;
+const ROUNDS: u32 = 20;
+
+/// A tenant holding one page of purchase orders, transfers and returns
+struct Fixture {
+    admin: PgPool,
+    pool: Arc<PgPool>,
+    tenant_id: Uuid,
+    user_id: Uuid,
+}
+
+impl Fixture {
+    async fn seed() -> Self {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let admin = PgPool::connect(&url).await.unwrap();
+        let pool = Arc::new(
+            connect_tenant_pool(&DatabaseConfig {
+                url,
+                ..DatabaseConfig::default()
+            })
+            .await
+            .unwrap(),
+        );
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        let statements = [
+            "INSERT INTO tenants (id, name, tenant_type, status, database_schema)
+             VALUES ($1, 'List benchmark', 'SANDBOX', 'ACTIVE', 'bench_' || replace($1::text, '-', ''))",
+            "INSERT INTO users (id, email, password_hash)
+             VALUES ($2, 'bench-' || $2::text || '@example.com', 'x')",
+            "INSERT INTO locations (name, tenant_id)
+             SELECT 'Bench ' || n, $1 FROM generate_series(1, 2) n",
+            "INSERT INTO items (sku, name, unit, cost_price, tenant_id)
+             SELECT 'BENCH-' || n, 'Bench item ' || n, 'each', 1, $1
+             FROM generate_series(1, $4) n",
+            "INSERT INTO purchase_orders (po_number, supplier_id, status, created_by, tenant_id)
+             SELECT 'BENCH-PO-' || n, gen_random_uuid(), 'OPEN', $2, $1
+             FROM generate_series(1, $3) n",
+            "INSERT INTO purchase_order_lines (po_id, item_id, qty_ordered, unit_cost, line_total, tenant_id)
+             SELECT po.id, i.id, 5, 1, 5, $1
+             FROM purchase_orders po CROSS JOIN items i
+             WHERE po.tenant_id = $1 AND i.tenant_id = $1",
+            "INSERT INTO transfers (transfer_number, from_location_id, to_location_id, status, created_by, tenant_id)
+             SELECT 'BENCH-TR-' || n, a.id, b.id, 'DRAFT', $2, $1
+             FROM generate_series(1, $3) n,
+                  (SELECT id FROM locations WHERE tenant_id = $1 ORDER BY name LIMIT 1) a,
+                  (SELECT id FROM locations WHERE tenant_id = $1 ORDER BY name DESC LIMIT 1) b",
+            "INSERT INTO transfer_lines (transfer_id, item_id, quantity, tenant_id)
+             SELECT t.id, i.id, 5, $1
+             FROM transfers t CROSS JOIN items i
+             WHERE t.tenant_id = $1 AND i.tenant_id = $1",
+            "INSERT INTO returns (return_number, location_id, created_by, tenant_id)
+             SELECT 'BENCH-RT-' || n, l.id, $2, $1
+             FROM generate_series(1, $3) n,
+                  (SELECT id FROM locations WHERE tenant_id = $1 LIMIT 1) l",
+            "INSERT INTO return_lines (return_id, item_id, quantity, tenant_id)
+             SELECT r.id, i.id, 5, $1
+             FROM returns r CROSS JOIN items i
+             WHERE r.tenant_id = $1 AND i.tenant_id = $1",
+        ];
+        for statement in statements {
+            sqlx::query(statement)
+                .bind(tenant_id)
+                .bind(user_id)
+                .bind(PAGE_SIZE as i32)
+                .bind(LINES_PER_ORDER as i32)
+                .execute(&admin)
+                .await
+                .unwrap();
+        }
+
+        Self {
+            admin,
+            pool,
+            tenant_id,
+            user_id,
+        }
+    }
+
+    async fn cleanup(self) {
+        let tables = [
+            "return_lines",
+            "returns",
+            "transfer_lines",
+            "transfers",
+            "purchase_order_lines",
+            "purchase_orders",
+            "items",
+            "locations",
+            "search_indexes",
+            "adjustment_reasons",
+            "tenant_quotas",
+        ];
+        for table in tables {
+            sqlx::query(&format!("DELETE FROM {} WHERE tenant_id = $1", table))
+                .bind(self.tenant_id)
+                .execute(&self.admin)
+                .await
+                .unwrap();
+        }
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(self.user_id)
+            .execute(&self.admin)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM tenants WHERE id = $1")
+            .bind(self.tenant_id)
+            .execute(&self.admin)
+            .await
+            .unwrap();
+    }
+
+    /// Mean time of `run` over `ROUNDS` rounds, after one warm-up round
+    async fn time<F, Fut>(&self, mut run: F) -> Duration
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = usize>,
+    {
+        with_tenant(self.tenant_id, async {
+            assert_eq!(run().await, PAGE_SIZE * LINES_PER_ORDER);
+            let started = Instant::now();
+            for _ in 0..ROUNDS {
+                run().await;
+            }
+            started.elapsed() / ROUNDS
+        })
+        .await
+    }
+
+    /// Rows of one page, fetched as the seeded tenant
+    async fn page<T>(&self, list: impl Future<Output = Result<Page<T>, DomainError>>) -> Vec<T> {
+        with_tenant(self.tenant_id, list).await.unwrap().data
+    }
+}
+
+fn first_page() -> PageRequest {
+    PageRequest::new(Some(PAGE_SIZE as i64), None)
+}
+
+/// `per_row_queries` is what loading one row through `find_by_id` costs
+fn report(resource: &str, batched: Duration, per_row: Duration, per_row_queries: usize) {
+    println!(
+        "{}: list {:?} (3 queries), find_by_id per row {:?} ({} queries), {:.1}x faster",
+        resource,
+        batched,
+        per_row,
+        1 + PAGE_SIZE * per_row_queries,
+        per_row.as_secs_f64() / batched.as_secs_f64()
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs a migrated database at DATABASE_URL"]
+async fn bench_purchase_order_list() {
+    let fixture = Fixture::seed().await;
+    let repository = PostgresPurchaseOrderRepository::new(Arc::clone(&fixture.pool));
+    let filter = ListFilter::default();
+
+    let batched = fixture
+        .time(|| async {
+            let page = repository.list(&filter, &first_page()).await.unwrap();
+            page.data.iter().map(|po| po.lines.len()).sum()
+        })
+        .await;
+    let ids: Vec<Uuid> = fixture
+        .page(repository.list(&filter, &first_page()))
+        .await
+        .iter()
+        .map(|po| po.id)
+        .collect();
+    let per_row = fixture
+        .time(|| async {
+            let mut lines = 0;
+            for id in &ids {
+                lines += repository
+                    .find_by_id(*id)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .lines
+                    .len();
+            }
+            lines
+        })
+        .await;
+
+    report("purchase orders", batched, per_row, 1);
+    fixture.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs a migrated database at DATABASE_URL"]
+async fn bench_transfer_list() {
+    let fixture = Fixture::seed().await;
+    let repository = PostgresTransferRepository::new(Arc::clone(&fixture.pool));
+    let filter = ListFilter::default();
+
+    let batched = fixture
+        .time(|| async {
+            let page = repository.list(&filter, &first_page()).await.unwrap();
+            page.data.iter().map(|(_, lines)| lines.len()).sum()
+        })
+        .await;
+    let ids: Vec<Uuid> = fixture
+        .page(repository.list(&filter, &first_page()))
+        .await
+        .iter()
+        .map(|(transfer, _)| transfer.id)
+        .collect();
+    let per_row = fixture
+        .time(|| async {
+            let mut lines = 0;
+            for id in &ids {
+                lines += repository.find_by_id(*id).await.unwrap().unwrap().1.len();
+            }
+            lines
+        })
+        .await;
+
+    // A transaction around the header and line queries
+    report("transfers", batched, per_row, 4);
+    fixture.cleanup().await;
+}
+
+#[tokio::test]
+#[ignore = "needs a migrated database at DATABASE_URL"]
+async fn bench_return_list() {
+    let fixture = Fixture::seed().await;
+    let repository = PostgresReturnRepository::new(Arc::clone(&fixture.pool));
+    let filter = ListFilter::default();
+
+    let batched = fixture
+        .time(|| async {
+            let page = repository.list(&filter, &first_page()).await.unwrap();
+            page.data.iter().map(|(_, lines)| lines.len()).sum()
+        })
+        .await;
+    let ids: Vec<Uuid> = fixture
+        .page(repository.list(&filter, &first_page()))
+        .await
+        .iter()
+        .map(|(return_entity, _)| return_entity.id)
+        .collect();
+    let per_row = fixture
+        .time(|| async {
+            let mut lines = 0;
+            for id in &ids {
+                lines += repository.find_by_id(*id).await.unwrap().unwrap().1.len();
+            }
+            lines
+        })
+        .await;
+
+    // A transaction around the header and line queries
+    report("returns", batched, per_row, 4);
+    fixture.cleanup().await;
+}
@@ -0,0 +1,364 @@
+use crate::domain::entities::list_filter::ListFilter;
+use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, Postgres, QueryBuilder, Row};
+use uuid::Uuid;
+
+/// Maps the shared list filters onto one table's columns. Filter expressions are
+/// compared with `$n IN (expr)`, so they may be a plain column or a subquery that
+/// yields several values (e.g. the SKUs on an order's lines).
+pub(crate) struct ListColumns {
+    pub resource: &'static str,
+    pub id: &'static str,
+    pub status: Option<&'static str>,
+    pub created_at: &'static str,
+    pub supplier_id: Option<&'static str>,
+    pub customer_id: Option<&'static str>,
+    pub sku: Option<&'static str>,
+    pub category: Option<&'static str>,
+    pub search: &'static [&'static str],
+    /// Public sort field name, the column it sorts on and that column's SQL type
+    pub sortable: &'static [(&'static str, &'static str, &'static str)],
+}
+
+impl ListColumns {
+    fn unsupported(&self, filter: &str) -> DomainError {
+        DomainError::ValidationError(
+            format!("Filter '{}' is not supported for {}", filter, self.resource).into(),
+        )
+    }
+
+    fn column<T>(
+        &self,
+        name: &str,
+        value: &Option<T>,
+        column: Option<&'static str>,
+    ) -> Result<Option<&'static str>, DomainError> {
+        match (value, column) {
+            (None, _) => Ok(None),
+            (Some(_), Some(column)) => Ok(Some(column)),
+            (Some(_), None) => Err(self.unsupported(name)),
+        }
+    }
+}
+
+/// Append ` AND ...` conditions for every filter that is set. The builder must
+/// already contain a `WHERE` clause.
+pub(crate) fn push_filters(
+    builder: &mut QueryBuilder<'_, Postgres>,
+    filter: &ListFilter,
+    columns: &ListColumns,
+) -> Result<(), DomainError> {
+    filter.validate()?;
+
+    let statuses = filter.statuses();
+    if !statuses.is_empty() {
+        let column = columns
+            .status
+            .ok_or_else(|| columns.unsupported("status"))?;
+        builder.push(format!(" AND {} = ANY(", column));
+        builder.push_bind(statuses);
+        builder.push(")");
+    }
+    if let Some(from) = filter.created_from {
+        builder.push(format!(" AND {} >= ", columns.created_at));
+        builder.push_bind(from);
+    }
+    if let Some(to) = filter.created_to {
+        builder.push(format!(" AND {} <= ", columns.created_at));
+        builder.push_bind(to);
+    }
+    if let Some(column) = columns.column("supplier_id", &filter.supplier_id, columns.supplier_id)? {
+        builder.push(" AND ");
+        builder.push_bind(filter.supplier_id);
+        builder.push(format!(" IN ({})", column));
+    }
+    if let Some(column) = columns.column("customer_id", &filter.customer_id, columns.customer_id)? {
+        builder.push(" AND ");
+        builder.push_bind(filter.customer_id);
+        builder.push(format!(" IN ({})", column));
+    }
+    if let Some(column) = columns.column("sku", &filter.sku, columns.sku)? {
+        builder.push(" AND ");
+        builder.push_bind(filter.sku.clone().map(|s| s.trim().to_string()));
+        builder.push(format!(" IN ({})", column));
+    }
+    if let Some(column) = columns.column("category", &filter.category, columns.category)? {
+        builder.push(" AND ");
+        builder.push_bind(filter.category.clone().map(|c| c.trim().to_string()));
+        builder.push(format!(" IN ({})", column));
+    }
+    if let Some(q) = filter.search_text() {
+        if columns.search.is_empty() {
+            return Err(columns.unsupported("q"));
+        }
+        let pattern = format!("%{}%", escape_like(q));
+        builder.push(" AND (");
+        for (i, column) in columns.search.iter().enumerate() {
+            if i > 0 {
+                builder.push(" OR ");
+            }
+            builder.push(format!("{} ILIKE ", column));
+            builder.push_bind(pattern.clone());
+        }
+        builder.push(")");
+    }
+
+    Ok(())
+}
+
+/// Keyset position: the sort a page was produced with, plus the last row's sort
+/// value (as text, `None` when NULL) and id
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ListCursor {
+    pub sort: String,
+    pub value: Option<String>,
+    pub id: Uuid,
+}
+
+/// One keyset-paginated list request against a table described by `ListColumns`.
+/// Rows are ordered by the requested sort (newest first by default) with the id as
+/// tie-breaker, so a cursor always identifies a unique position.
+pub(crate) struct ListQuery<'a> {
+    columns: &'a ListColumns,
+    filter: &'a ListFilter,
+    column: &'static str,
+    sql_type: &'static str,
+    descending: bool,
+    sort: String,
+    after: Option<ListCursor>,
+    limit: i64,
+}
+
+impl<'a> ListQuery<'a> {
+    pub fn new(
+        columns: &'a ListColumns,
+        filter: &'a ListFilter,
+        page: &PageRequest,
+    ) -> Result<Self, DomainError> {
+        let (field, column, sql_type, descending) = match filter.sort_order()? {
+            Some(sort) => {
+                let (field, column, sql_type) = columns
+                    .sortable
+                    .iter()
+                    .find(|(name, _, _)| *name == sort.field)
+                    .copied()
+                    .ok_or_else(|| {
+                        let allowed: Vec<&str> =
+                            columns.sortable.iter().map(|(name, _, _)| *name).collect();
+                        DomainError::ValidationError(
+                            format!(
+                                "Cannot sort {} by '{}'; expected one of: {}",
+                                columns.resource,
+                                sort.field,
+                                allowed.join(", ")
+                            )
+                            .into(),
+                        )
+                    })?;
+                (field, column, sql_type, sort.descending)
+            }
+            None => ("created_at", columns.created_at, "timestamptz", true),
+        };
+        let sort = format!("{}{}", if descending { "-" } else { "" }, field);
+
+        let after: Option<ListCursor> = page.after()?;
+        if after.as_ref().is_some_and(|cursor| cursor.sort != sort) {
+            return Err(DomainError::ValidationError(
+                "Cursor was issued for a different sort order".into(),
+            ));
+        }
+
+        Ok(Self {
+            columns,
+            filter,
+            column,
+            sql_type,
+            descending,
+            sort,
+            after,
+            limit: page.limit(),
+        })
+    }
+
+    /// Select-list entry exposing the sort value that `cursor_for` reads back
+    pub fn sort_key_column(&self) -> String {
+        format!("{}::text AS sort_key", self.column)
+    }
+
+    /// Append the filters, keyset condition, `ORDER BY` and `LIMIT` (one extra row
+    /// to detect a next page). The builder must already contain a `WHERE` clause.
+    pub fn push_tail(&self, builder: &mut QueryBuilder<'_, Postgres>) -> Result<(), DomainError> {
+        push_filters(builder, self.filter, self.columns)?;
+
+        let (cmp, direction) = if self.descending {
+            ("<", "DESC")
+        } else {
+            (">", "ASC")
+        };
+        if let Some(after) = &self.after {
+            match &after.value {
+                Some(value) => {
+                    builder.push(format!(" AND ({} {} CAST(", self.column, cmp));
+                    builder.push_bind(value.clone());
+                    builder.push(format!(
+                        " AS {}) OR ({} = CAST(",
+                        self.sql_type, self.column
+                    ));
+                    builder.push_bind(value.clone());
+                    builder.push(format!(
+                        " AS {}) AND {} {} ",
+                        self.sql_type, self.columns.id, cmp
+                    ));
+                    builder.push_bind(after.id);
+                    builder.push(format!(") OR {} IS NULL)", self.column));
+                }
+                None => {
+                    builder.push(format!(
+                        " AND ({} IS NULL AND {} {} ",
+                        self.column, self.columns.id, cmp
+                    ));
+                    builder.push_bind(after.id);
+                    builder.push(")");
+                }
+            }
+        }
+
+        builder.push(format!(
+            " ORDER BY {} {} NULLS LAST, {} {} LIMIT ",
+            self.column, direction, self.columns.id, direction
+        ));
+        builder.push_bind(self.limit + 1);
+        Ok(())
+    }
+
+    pub fn cursor_for(&self, row: &PgRow) -> Result<ListCursor, DomainError> {
+        Ok(ListCursor {
+            sort: self.sort.clone(),
+            value: row.try_get("sort_key")?,
+            id: row.try_get("id")?,
+        })
+    }
+
+    pub fn page<T>(&self, rows: Vec<(ListCursor, T)>) -> Page<T> {
+        Page::from_rows(rows, self.limit, |(cursor, _)| cursor.clone()).map(|(_, row)| row)
+    }
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::pagination::encode_cursor;
+
+    const COLUMNS: ListColumns = ListColumns {
+        resource: "purchase orders",
+        id: "id",
+        status: Some("status"),
+        created_at: "created_at",
+        supplier_id: Some("supplier_id"),
+        customer_id: None,
+        sku: None,
+        category: None,
+        search: &["po_number"],
+        sortable: &[
+            ("created_at", "created_at", "timestamptz"),
+            ("po_number", "po_number", "text"),
+            ("total_amount", "total_amount", "numeric"),
+        ],
+    };
+
+    #[test]
+    fn test_builds_filters_keyset_and_order() {
+        let filter = ListFilter {
+            status: Some("open,receiving".to_string()),
+            supplier_id: Some(Uuid::new_v4()),
+            q: Some("100%".to_string()),
+            sort: Some("po_number".to_string()),
+            ..Default::default()
+        };
+        let cursor = encode_cursor(&ListCursor {
+            sort: "po_number".to_string(),
+            value: Some("PO-7".to_string()),
+            id: Uuid::new_v4(),
+        });
+        let page = PageRequest::new(Some(10), Some(cursor));
+        let query = ListQuery::new(&COLUMNS, &filter, &page).unwrap();
+
+        let mut builder = QueryBuilder::new("SELECT id FROM purchase_orders WHERE TRUE");
+        query.push_tail(&mut builder).unwrap();
+
+        assert_eq!(
+            builder.sql(),
+            "SELECT id FROM purchase_orders WHERE TRUE AND status = ANY($1) AND $2 IN (supplier_id) \
+             AND (po_number ILIKE $3) AND (po_number > CAST($4 AS text) OR (po_number = CAST($5 AS text) \
+             AND id > $6) OR po_number IS NULL) ORDER BY po_number ASC NULLS LAST, id ASC LIMIT $7"
+        );
+        assert_eq!(escape_like("100%_"), "100\\%\\_");
+    }
+
+    #[test]
+    fn test_pages_across_numeric_sort_key_without_rounding() {
+        let filter = ListFilter {
+            sort: Some("-total_amount".to_string()),
+            ..Default::default()
+        };
+        let first = ListQuery::new(&COLUMNS, &filter, &PageRequest::new(Some(2), None)).unwrap();
+        assert_eq!(first.sort_key_column(), "total_amount::text AS sort_key");
+
+        // The next page starts after the last row's exact NUMERIC text, which a
+        // double precision cast would round before comparing
+        let cursor = encode_cursor(&ListCursor {
+            sort: "-total_amount".to_string(),
+            value: Some("1234567890.1234".to_string()),
+            id: Uuid::new_v4(),
+        });
+        let next =
+            ListQuery::new(&COLUMNS, &filter, &PageRequest::new(Some(2), Some(cursor))).unwrap();
+        let mut builder = QueryBuilder::new("SELECT id FROM purchase_orders WHERE TRUE");
+        next.push_tail(&mut builder).unwrap();
+
+        assert_eq!(
+            builder.sql(),
+            "SELECT id FROM purchase_orders WHERE TRUE AND (total_amount < CAST($1 AS numeric) \
+             OR (total_amount = CAST($2 AS numeric) AND id < $3) OR total_amount IS NULL) \
+             ORDER BY total_amount DESC NULLS LAST, id DESC LIMIT $4"
+        );
+    }
+
+    #[test]
+    fn test_rejects_unsupported_filter_sort_and_stale_cursor() {
+        let mut builder = QueryBuilder::new("SELECT id FROM purchase_orders WHERE TRUE");
+        let filter = ListFilter {
+            customer_id: Some(Uuid::new_v4()),
+            ..Default::default()
+        };
+        assert!(push_filters(&mut builder, &filter, &COLUMNS).is_err());
+
+        let page = PageRequest::default();
+        let filter = ListFilter {
+            sort: Some("-quantity".to_string()),
+            ..Default::default()
+        };
+        assert!(ListQuery::new(&COLUMNS, &filter, &page).is_err());
+
+        let cursor = encode_cursor(&ListCursor {
+            sort: "-created_at".to_string(),
+            value: None,
+            id: Uuid::new_v4(),
+        });
+        let filter = ListFilter {
+            sort: Some("po_number".to_string()),
+            ..Default::default()
+        };
+        let page = PageRequest::new(None, Some(cursor));
+        assert!(ListQuery::new(&COLUMNS, &filter, &page).is_err());
+    }
+}
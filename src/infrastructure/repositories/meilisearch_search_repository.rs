@@ -0,0 +1,534 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::search::{
+    SearchIndex, SearchIndexHealth, SearchIndexRequest, SearchIndexStatus, SearchQuery,
+    SearchResult, SearchResultItem, SuggestQuery, Suggestion,
+};
+use crate::domain::services::search_repository::SearchRepository;
+use crate::infrastructure::repositories::postgres_search_repository::{
+    DocumentChanges, PostgresSearchRepository, Scope,
+};
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::current_tenant;
+
+/// Documents sent per request
+const PUSH_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub struct MeilisearchConfig {
+    pub url: String,
+    pub api_key: Option<String>,
+    pub index: String,
+}
+
+/// A search document as Meilisearch stores it. Every tenant shares the index,
+/// so each search is filtered on `tenant_id`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct MeiliDocument {
+    /// `{entity_type}-{entity_id}`
+    id: String,
+    entity_type: String,
+    entity_id: Uuid,
+    tenant_id: Uuid,
+    item_id: Option<Uuid>,
+    location_id: Option<Uuid>,
+    label: Option<String>,
+    content: String,
+    metadata: Option<serde_json::Value>,
+}
+
+impl MeiliDocument {
+    fn new(
+        entity_type: &str,
+        entity_id: Uuid,
+        tenant_id: Uuid,
+        content: String,
+        metadata: Option<serde_json::Value>,
+    ) -> Self {
+        let field = |key: &str| {
+            metadata
+                .as_ref()
+                .and_then(|m| m.get(key))
+                .and_then(|v| v.as_str())
+        };
+        let id_field = |key: &str| field(key).and_then(|id| Uuid::parse_str(id).ok());
+        // Same label as the `search_indexes.label` column
+        let label = field("name").map(str::to_string).or_else(|| {
+            Some(format!(
+                "{} @ {}",
+                field("item_name")?,
+                field("location_name")?
+            ))
+        });
+        let (item_id, location_id) = match entity_type {
+            "item" => (Some(entity_id), None),
+            "location" => (None, Some(entity_id)),
+            _ => (id_field("item_id"), id_field("location_id")),
+        };
+
+        Self {
+            id: document_id(entity_type, entity_id),
+            entity_type: entity_type.to_string(),
+            entity_id,
+            tenant_id,
+            item_id,
+            location_id,
+            label,
+            content,
+            metadata,
+        }
+    }
+}
+
+fn document_id(entity_type: &str, entity_id: Uuid) -> String {
+    format!("{}-{}", entity_type, entity_id)
+}
+
+/// A string in a Meilisearch filter expression
+fn quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Filter on the tenant and, if any are given, the entity types
+fn search_filter(tenant_id: Uuid, entity_types: Option<&[String]>) -> Vec<String> {
+    let mut filter = vec![format!("tenant_id = {}", quoted(&tenant_id.to_string()))];
+    if let Some(types) = entity_types.filter(|types| !types.is_empty()) {
+        let types: Vec<String> = types.iter().map(|t| quoted(t)).collect();
+        filter.push(format!("entity_type IN [{}]", types.join(", ")));
+    }
+    filter
+}
+
+fn tenant() -> Result<Uuid, DomainError> {
+    current_tenant()
+        .ok_or_else(|| DomainError::InfrastructureError("Search needs a tenant in scope".into()))
+}
+
+fn meili_error(message: String) -> DomainError {
+    DomainError::InfrastructureError(message.into())
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResponse<T> {
+    hits: Vec<T>,
+    #[serde(default)]
+    estimated_total_hits: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResultHit {
+    entity_type: String,
+    entity_id: Uuid,
+    metadata: Option<serde_json::Value>,
+    #[serde(rename = "_rankingScore", default)]
+    ranking_score: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct SuggestionHit {
+    entity_type: String,
+    entity_id: Uuid,
+    label: Option<String>,
+    #[serde(rename = "_rankingScore", default)]
+    ranking_score: f32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexStats {
+    is_indexing: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskList {
+    total: Option<i64>,
+}
+
+/// Search served by Meilisearch. `search_indexes` stays the record of what is
+/// indexed: each refresh works out the changes there, sends them to
+/// Meilisearch, and only commits once Meilisearch has accepted them, so a
+/// failed push is simply found stale again on the next sync.
+pub struct MeilisearchSearchRepository {
+    pool: Arc<PgPool>,
+    ledger: PostgresSearchRepository,
+    client: reqwest::Client,
+    config: MeilisearchConfig,
+}
+
+impl MeilisearchSearchRepository {
+    pub fn new(pool: Arc<PgPool>, config: MeilisearchConfig) -> Self {
+        Self {
+            ledger: PostgresSearchRepository::new(Arc::clone(&pool)),
+            pool,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .expect("Meilisearch HTTP client configuration is valid"),
+            config: MeilisearchConfig {
+                url: config.url.trim_end_matches('/').to_string(),
+                ..config
+            },
+        }
+    }
+
+    /// Create the index if it's missing and apply the settings searches rely on
+    pub async fn ensure_index(&self) -> Result<(), DomainError> {
+        // Meilisearch accepts this even when the index exists; the task then fails harmlessly
+        self.send(self.client.post(self.url("/indexes")).json(&json!({
+            "uid": self.config.index,
+            "primaryKey": "id",
+        })))
+        .await?;
+        self.send(self.client.patch(self.index_url("/settings")).json(&json!({
+            "searchableAttributes": ["label", "content"],
+            "filterableAttributes": ["tenant_id", "entity_type", "item_id", "location_id"],
+        })))
+        .await?;
+        Ok(())
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.url, path)
+    }
+
+    fn index_url(&self, path: &str) -> String {
+        self.url(&format!("/indexes/{}{}", self.config.index, path))
+    }
+
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, DomainError> {
+        let request = match &self.config.api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        };
+        let response = request
+            .send()
+            .await
+            .map_err(|e| meili_error(format!("Meilisearch request failed: {}", e)))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(meili_error(format!(
+                "Meilisearch answered {}: {}",
+                status, body
+            )));
+        }
+        Ok(response)
+    }
+
+    async fn fetch<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T, DomainError> {
+        self.send(request)
+            .await?
+            .json()
+            .await
+            .map_err(|e| meili_error(format!("Unexpected Meilisearch response: {}", e)))
+    }
+
+    async fn search_index<T: DeserializeOwned>(
+        &self,
+        body: serde_json::Value,
+    ) -> Result<SearchResponse<T>, DomainError> {
+        self.fetch(self.client.post(self.index_url("/search")).json(&body))
+            .await
+    }
+
+    /// Send a refresh's changes; Meilisearch applies them in the order sent
+    async fn push(&self, changes: &DocumentChanges) -> Result<(), DomainError> {
+        let removed: Vec<String> = changes
+            .removed
+            .iter()
+            .map(|(entity_type, entity_id)| document_id(entity_type, *entity_id))
+            .collect();
+        for ids in removed.chunks(PUSH_BATCH_SIZE) {
+            self.send(
+                self.client
+                    .post(self.index_url("/documents/delete-batch"))
+                    .json(ids),
+            )
+            .await?;
+        }
+
+        let upserted: Vec<MeiliDocument> = changes
+            .upserted
+            .iter()
+            .map(|doc| {
+                let mut document = MeiliDocument::new(
+                    &doc.entity_type,
+                    doc.entity_id,
+                    doc.tenant_id,
+                    doc.content.clone(),
+                    Some(doc.metadata.clone()),
+                );
+                document.item_id = doc.item_id;
+                document.location_id = doc.location_id;
+                document
+            })
+            .collect();
+        for documents in upserted.chunks(PUSH_BATCH_SIZE) {
+            self.send(
+                self.client
+                    .post(self.index_url("/documents"))
+                    .json(documents),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn refresh(&self, scope: Scope<'_>) -> Result<u64, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        let changes = PostgresSearchRepository::refresh(&mut tx, scope).await?;
+        self.push(&changes).await?;
+        tx.commit().await?;
+        Ok(changes.len())
+    }
+
+    /// Documents Meilisearch holds for the tenant, writes still queued, and
+    /// whether it is busy applying them
+    async fn backend_state(
+        &self,
+        tenant_id: Uuid,
+    ) -> Result<(i64, Option<i64>, bool), DomainError> {
+        self.send(self.client.get(self.url("/health"))).await?;
+        let counted: SearchResponse<serde_json::Value> = self
+            .search_index(json!({
+                "q": "",
+                "filter": search_filter(tenant_id, None),
+                "limit": 0,
+            }))
+            .await?;
+        let stats: IndexStats = self
+            .fetch(self.client.get(self.index_url("/stats")))
+            .await?;
+        let tasks: TaskList = self
+            .fetch(self.client.get(self.url("/tasks")).query(&[
+                ("indexUids", self.config.index.as_str()),
+                ("statuses", "enqueued,processing"),
+                ("limit", "1"),
+            ]))
+            .await?;
+        Ok((
+            counted.estimated_total_hits.unwrap_or_default() as i64,
+            tasks.total,
+            stats.is_indexing,
+        ))
+    }
+}
+
+#[async_trait]
+impl SearchRepository for MeilisearchSearchRepository {
+    async fn index_document(&self, request: SearchIndexRequest) -> Result<(), DomainError> {
+        let tenant_id = tenant()?;
+        self.ledger.index_document(request.clone()).await?;
+        let document = MeiliDocument::new(
+            &request.entity_type,
+            request.entity_id,
+            tenant_id,
+            request.searchable_content,
+            request.metadata,
+        );
+        self.send(
+            self.client
+                .post(self.index_url("/documents"))
+                .json(&[document]),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn remove_document(&self, entity_type: &str, entity_id: Uuid) -> Result<(), DomainError> {
+        self.ledger.remove_document(entity_type, entity_id).await?;
+        self.send(self.client.delete(self.index_url(&format!(
+            "/documents/{}",
+            document_id(entity_type, entity_id)
+        ))))
+        .await?;
+        Ok(())
+    }
+
+    async fn search(&self, query: SearchQuery) -> Result<SearchResult, DomainError> {
+        let response: SearchResponse<ResultHit> = self
+            .search_index(json!({
+                "q": query.query,
+                "filter": search_filter(tenant()?, query.entity_types.as_deref()),
+                "limit": query.limit.unwrap_or(50).clamp(0, 1000),
+                "offset": query.offset.unwrap_or(0).max(0),
+                "attributesToRetrieve": ["entity_type", "entity_id", "metadata"],
+                "showRankingScore": true,
+            }))
+            .await?;
+
+        Ok(SearchResult {
+            total: response.estimated_total_hits.unwrap_or(response.hits.len()),
+            results: response
+                .hits
+                .into_iter()
+                .map(|hit| SearchResultItem {
+                    entity_type: hit.entity_type,
+                    entity_id: hit.entity_id,
+                    rank: hit.ranking_score,
+                    metadata: hit.metadata,
+                })
+                .collect(),
+            query: query.query,
+        })
+    }
+
+    async fn suggest(&self, query: SuggestQuery) -> Result<Vec<Suggestion>, DomainError> {
+        // Meilisearch matches prefixes and tolerates typos on its own
+        let response: SearchResponse<SuggestionHit> = self
+            .search_index(json!({
+                "q": query.text,
+                "filter": search_filter(tenant()?, query.entity_types.as_deref()),
+                "limit": query.limit,
+                "attributesToRetrieve": ["entity_type", "entity_id", "label"],
+                "showRankingScore": true,
+            }))
+            .await?;
+
+        Ok(response
+            .hits
+            .into_iter()
+            .filter_map(|hit| {
+                Some(Suggestion {
+                    entity_type: hit.entity_type,
+                    entity_id: hit.entity_id,
+                    text: hit.label?,
+                    similarity: hit.ranking_score,
+                })
+            })
+            .collect())
+    }
+
+    async fn get_document(
+        &self,
+        entity_type: &str,
+        entity_id: Uuid,
+    ) -> Result<Option<SearchIndex>, DomainError> {
+        self.ledger.get_document(entity_type, entity_id).await
+    }
+
+    async fn index_items(&self, item_ids: &[Uuid]) -> Result<u64, DomainError> {
+        self.refresh(Scope::Items(item_ids)).await
+    }
+
+    async fn index_locations(&self, location_ids: &[Uuid]) -> Result<u64, DomainError> {
+        self.refresh(Scope::Locations(location_ids)).await
+    }
+
+    async fn sync_stale_documents(&self) -> Result<u64, DomainError> {
+        self.refresh(Scope::All).await
+    }
+
+    async fn rebuild_index(&self) -> Result<i64, DomainError> {
+        let tenant_id = tenant()?;
+        let mut tx = self.pool.begin().await?;
+        PostgresSearchRepository::clear_tenant(&mut tx).await?;
+        let changes = PostgresSearchRepository::refresh(&mut tx, Scope::Tenant).await?;
+        self.send(
+            self.client
+                .post(self.index_url("/documents/delete"))
+                .json(&json!({ "filter": search_filter(tenant_id, None) })),
+        )
+        .await?;
+        self.push(&changes).await?;
+        tx.commit().await?;
+        Ok(changes.len() as i64)
+    }
+
+    async fn cleanup_orphaned_documents(&self) -> Result<i64, DomainError> {
+        // A sync drops documents whose source is gone from both stores
+        Ok(self.sync_stale_documents().await? as i64)
+    }
+
+    async fn health(&self) -> Result<SearchIndexHealth, DomainError> {
+        let mut health = self.ledger.health().await?;
+        health.backend = "meilisearch".to_string();
+        match self.backend_state(tenant()?).await {
+            Ok((document_count, pending_tasks, is_indexing)) => {
+                health.document_count = Some(document_count);
+                health.pending_tasks = pending_tasks;
+                health.status = if is_indexing || pending_tasks.unwrap_or(0) > 0 {
+                    SearchIndexStatus::Indexing
+                } else {
+                    SearchIndexStatus::Available
+                };
+            }
+            Err(e) => {
+                health.document_count = None;
+                health.status = SearchIndexStatus::Unavailable;
+                health.error = Some(e.to_string());
+            }
+        }
+        Ok(health)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_quotes_entity_types() {
+        let tenant_id = Uuid::nil();
+        assert_eq!(
+            search_filter(tenant_id, Some(&[])),
+            vec![format!("tenant_id = \"{}\"", tenant_id)]
+        );
+        let filter = search_filter(
+            tenant_id,
+            Some(&["item".to_string(), "x\" OR tenant_id EXISTS".to_string()]),
+        );
+        assert_eq!(
+            filter[1],
+            r#"entity_type IN ["item", "x\" OR tenant_id EXISTS"]"#
+        );
+    }
+
+    #[test]
+    fn test_stock_level_document_takes_ids_and_label_from_metadata() {
+        let item_id = Uuid::new_v4();
+        let location_id = Uuid::new_v4();
+        let entity_id = Uuid::new_v4();
+        let document = MeiliDocument::new(
+            "stock_level",
+            entity_id,
+            Uuid::nil(),
+            "widget main".to_string(),
+            Some(json!({
+                "item_id": item_id,
+                "item_name": "Widget",
+                "location_id": location_id,
+                "location_name": "Main",
+            })),
+        );
+        assert_eq!(document.id, format!("stock_level-{}", entity_id));
+        assert_eq!(document.item_id, Some(item_id));
+        assert_eq!(document.location_id, Some(location_id));
+        assert_eq!(document.label.as_deref(), Some("Widget @ Main"));
+
+        let item = MeiliDocument::new(
+            "item",
+            item_id,
+            Uuid::nil(),
+            "widget".to_string(),
+            Some(json!({ "name": "Widget" })),
+        );
+        assert_eq!(item.item_id, Some(item_id));
+        assert_eq!(item.label.as_deref(), Some("Widget"));
+    }
+}
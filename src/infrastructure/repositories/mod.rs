@@ -1,16 +1,53 @@
 // Infrastructure repositories will be implemented here
 pub mod composite_idempotency_repository;
+#[cfg(test)]
+mod list_benchmarks;
+pub mod list_filter_sql;
+pub mod meilisearch_search_repository;
+pub mod postgres_adjustment_repository;
+pub mod postgres_allocation_repository;
+pub mod postgres_attachment_repository;
+pub mod postgres_customer_credit_repository;
+pub mod postgres_cycle_count_repository;
+pub mod postgres_document_settings_repository;
+pub mod postgres_edi_repository;
+pub mod postgres_exchange_rate_repository;
+pub mod postgres_forecast_repository;
 pub mod postgres_idempotency_repository;
+pub mod postgres_inbound_shipment_repository;
+pub mod postgres_integration_repository;
+pub mod postgres_invitation_repository;
+pub mod postgres_invoice_repository;
+pub mod postgres_item_availability_repository;
+pub mod postgres_item_merge_repository;
 pub mod postgres_item_repository;
 pub mod postgres_job_repository;
+pub mod postgres_location_capacity_repository;
 pub mod postgres_location_repository;
+pub mod postgres_print_repository;
+pub mod postgres_product_repository;
 pub mod postgres_purchase_order_repository;
+pub mod postgres_putaway_rule_repository;
+pub mod postgres_receiving_settings_repository;
+pub mod postgres_reservation_repository;
 pub mod postgres_return_repository;
 pub mod postgres_sales_order_repository;
+pub mod postgres_sandbox_seed_repository;
 pub mod postgres_search_repository;
+pub mod postgres_session_repository;
+pub mod postgres_shipment_repository;
+pub mod postgres_snapshot_repository;
+pub mod postgres_status_history_repository;
 pub mod postgres_stock_repository;
+pub mod postgres_tenant_audit_repository;
 pub mod postgres_tenant_repository;
+pub mod postgres_tenant_snapshot_repository;
 pub mod postgres_transfer_repository;
+pub mod postgres_two_factor_repository;
 pub mod postgres_user_repository;
+pub mod postgres_vendor_return_repository;
 pub mod postgres_webhook_repository;
+pub mod postgres_webhook_retention_repository;
 pub mod redis_idempotency_repository;
+pub mod schema_migrations;
+pub mod tenant_pool;
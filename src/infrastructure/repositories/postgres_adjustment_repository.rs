@@ -0,0 +1,285 @@
+use crate::domain::entities::adjustment::{Adjustment, AdjustmentReason, AdjustmentStatus};
+use crate::domain::entities::inventory::StockMovement;
+use crate::domain::services::adjustment_repository::AdjustmentRepository;
+use crate::infrastructure::repositories::postgres_stock_repository::PostgresStockRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const REASON_COLUMNS: &str = "id, code, description, active, approval_quantity_threshold, approval_value_threshold, created_at, updated_at";
+
+const ADJUSTMENT_COLUMNS: &str = "id, item_id, location_id, qty_change, reason_code, note, value, status, movement_id, created_by, created_at, decided_by, decided_at, decision_note";
+
+pub struct PostgresAdjustmentRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresAdjustmentRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_reason(row: &PgRow) -> Result<AdjustmentReason, DomainError> {
+        Ok(AdjustmentReason {
+            id: row.try_get("id")?,
+            code: row.try_get("code")?,
+            description: row.try_get("description")?,
+            active: row.try_get("active")?,
+            approval_quantity_threshold: row.try_get("approval_quantity_threshold")?,
+            approval_value_threshold: row.try_get("approval_value_threshold")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    fn row_to_adjustment(row: &PgRow) -> Result<Adjustment, DomainError> {
+        Ok(Adjustment {
+            id: row.try_get("id")?,
+            item_id: row.try_get("item_id")?,
+            location_id: row.try_get("location_id")?,
+            qty_change: row.try_get("qty_change")?,
+            reason_code: row.try_get("reason_code")?,
+            note: row.try_get("note")?,
+            value: row.try_get("value")?,
+            status: AdjustmentStatus::from_str(row.try_get("status")?)?,
+            movement_id: row.try_get("movement_id")?,
+            created_by: row.try_get("created_by")?,
+            created_at: row.try_get("created_at")?,
+            decided_by: row.try_get("decided_by")?,
+            decided_at: row.try_get("decided_at")?,
+            decision_note: row.try_get("decision_note")?,
+        })
+    }
+}
+
+#[async_trait]
+impl AdjustmentRepository for PostgresAdjustmentRepository {
+    async fn create_reason(&self, reason: &AdjustmentReason) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO adjustment_reasons (id, code, description, active, approval_quantity_threshold,
+                                            approval_value_threshold, tenant_id, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, get_current_tenant_id(), $7, $8)
+            "#,
+        )
+        .bind(reason.id)
+        .bind(&reason.code)
+        .bind(&reason.description)
+        .bind(reason.active)
+        .bind(reason.approval_quantity_threshold)
+        .bind(reason.approval_value_threshold)
+        .bind(reason.created_at)
+        .bind(reason.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_reason_by_id(&self, id: Uuid) -> Result<Option<AdjustmentReason>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM adjustment_reasons WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+            REASON_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_reason).transpose()
+    }
+
+    async fn find_reason_by_code(
+        &self,
+        code: &str,
+    ) -> Result<Option<AdjustmentReason>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM adjustment_reasons WHERE code = $1 AND tenant_id = get_current_tenant_id()",
+            REASON_COLUMNS
+        ))
+        .bind(code)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_reason).transpose()
+    }
+
+    async fn list_reasons(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AdjustmentReason>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM adjustment_reasons
+            WHERE tenant_id = get_current_tenant_id()
+            ORDER BY code
+            LIMIT $1 OFFSET $2
+            "#,
+            REASON_COLUMNS
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_reason).collect()
+    }
+
+    async fn update_reason(&self, reason: &AdjustmentReason) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE adjustment_reasons
+            SET description = $2, active = $3, approval_quantity_threshold = $4,
+                approval_value_threshold = $5, updated_at = $6
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(reason.id)
+        .bind(&reason.description)
+        .bind(reason.active)
+        .bind(reason.approval_quantity_threshold)
+        .bind(reason.approval_value_threshold)
+        .bind(reason.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Adjustment reason {} not found", reason.id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn delete_reason(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "DELETE FROM adjustment_reasons WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+        )
+        .bind(id)
+        .execute(&*self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Adjustment reason {} not found", id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn create(
+        &self,
+        adjustment: &Adjustment,
+        movement: Option<&StockMovement>,
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        if let Some(movement) = movement {
+            PostgresStockRepository::record_movement_in_tx(&mut tx, movement).await?;
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO stock_adjustments (id, item_id, location_id, qty_change, reason_code, note, value,
+                                           status, movement_id, created_by, created_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, get_current_tenant_id())
+            "#,
+        )
+        .bind(adjustment.id)
+        .bind(adjustment.item_id)
+        .bind(adjustment.location_id)
+        .bind(adjustment.qty_change)
+        .bind(&adjustment.reason_code)
+        .bind(&adjustment.note)
+        .bind(adjustment.value)
+        .bind(adjustment.status.as_str())
+        .bind(adjustment.movement_id)
+        .bind(adjustment.created_by)
+        .bind(adjustment.created_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn decide(
+        &self,
+        adjustment: &Adjustment,
+        movement: Option<&StockMovement>,
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        if let Some(movement) = movement {
+            PostgresStockRepository::record_movement_in_tx(&mut tx, movement).await?;
+        }
+
+        let result = sqlx::query(
+            r#"
+            UPDATE stock_adjustments
+            SET status = $2, movement_id = $3, decided_by = $4, decided_at = $5, decision_note = $6
+            WHERE id = $1 AND status = 'PENDING_APPROVAL' AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(adjustment.id)
+        .bind(adjustment.status.as_str())
+        .bind(adjustment.movement_id)
+        .bind(adjustment.decided_by)
+        .bind(adjustment.decided_at)
+        .bind(&adjustment.decision_note)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::Conflict(
+                format!(
+                    "Adjustment {} is no longer awaiting approval",
+                    adjustment.id
+                )
+                .into(),
+            ));
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Adjustment>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM stock_adjustments WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+            ADJUSTMENT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_adjustment).transpose()
+    }
+
+    async fn list(
+        &self,
+        status: Option<AdjustmentStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Adjustment>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM stock_adjustments
+            WHERE tenant_id = get_current_tenant_id()
+              AND ($1::text IS NULL OR status = $1)
+            ORDER BY created_at DESC, id
+            LIMIT $2 OFFSET $3
+            "#,
+            ADJUSTMENT_COLUMNS
+        ))
+        .bind(status.map(|s| s.as_str()))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_adjustment).collect()
+    }
+}
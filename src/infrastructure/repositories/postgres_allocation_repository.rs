@@ -0,0 +1,165 @@
+use crate::domain::entities::allocation::{LocationStock, SalesOrderAllocation};
+use crate::domain::services::allocation_repository::AllocationRepository;
+use crate::infrastructure::repositories::postgres_item_availability_repository::PostgresItemAvailabilityRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct PostgresAllocationRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresAllocationRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    pub(crate) fn row_to_allocation(row: &PgRow) -> Result<SalesOrderAllocation, DomainError> {
+        Ok(SalesOrderAllocation {
+            id: row.try_get("id")?,
+            sales_order_id: row.try_get("sales_order_id")?,
+            so_line_id: row.try_get("so_line_id")?,
+            item_id: row.try_get("item_id")?,
+            location_id: row.try_get("location_id")?,
+            qty_allocated: row.try_get("qty_allocated")?,
+            qty_shipped: row.try_get("qty_shipped")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl AllocationRepository for PostgresAllocationRepository {
+    /// Reads the availability read model; only the order's own reservations
+    /// and allocations, which it may reuse, are looked up live
+    async fn find_available_stock(
+        &self,
+        sales_order_id: Uuid,
+        item_ids: &[Uuid],
+    ) -> Result<Vec<LocationStock>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT ia.item_id, ia.location_id, l.address,
+                   (ia.quantity_on_hand - ia.quantity_unavailable
+                    - GREATEST(ia.quantity_reserved - COALESCE((
+                        SELECT SUM(sol.qty - sol.qty_shipped)
+                        FROM sales_order_lines sol
+                        JOIN sales_orders so ON so.id = sol.so_id
+                        WHERE sol.so_id = $1
+                          AND so.tenant_id = get_current_tenant_id()
+                          AND sol.reserved = true
+                          AND sol.item_id = ia.item_id
+                          AND so.fulfillment_location_id = ia.location_id
+                    ), 0), 0)
+                    - (ia.quantity_allocated - COALESCE((
+                        SELECT SUM(a.qty_allocated - a.qty_shipped)
+                        FROM so_allocations a
+                        WHERE a.item_id = ia.item_id
+                          AND a.location_id = ia.location_id
+                          AND a.sales_order_id = $1
+                          AND a.tenant_id = get_current_tenant_id()
+                    ), 0))) AS available
+            FROM item_availability ia
+            JOIN locations l ON l.id = ia.location_id
+            WHERE ia.item_id = ANY($2) AND l.active = true
+              AND ia.tenant_id = get_current_tenant_id()
+            ORDER BY ia.item_id, ia.location_id
+            "#,
+        )
+        .bind(sales_order_id)
+        .bind(item_ids)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let address: Option<serde_json::Value> = row.try_get("address")?;
+                Ok(LocationStock {
+                    item_id: row.try_get("item_id")?,
+                    location_id: row.try_get("location_id")?,
+                    available: row.try_get("available")?,
+                    address: address.map(|a| serde_json::from_value(a).unwrap_or_default()),
+                })
+            })
+            .collect()
+    }
+
+    async fn find_by_sales_order(
+        &self,
+        sales_order_id: Uuid,
+    ) -> Result<Vec<SalesOrderAllocation>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, sales_order_id, so_line_id, item_id, location_id, qty_allocated, qty_shipped, created_at, updated_at
+            FROM so_allocations
+            WHERE sales_order_id = $1 AND tenant_id = get_current_tenant_id()
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(sales_order_id)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_allocation).collect()
+    }
+
+    async fn replace_allocations(
+        &self,
+        sales_order_id: Uuid,
+        allocations: &[SalesOrderAllocation],
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Shipped quantities are history; only the open part of existing allocations is released
+        let mut changed: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            "DELETE FROM so_allocations WHERE sales_order_id = $1 AND qty_shipped = 0 AND tenant_id = get_current_tenant_id() RETURNING item_id, location_id",
+        )
+        .bind(sales_order_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        changed.extend(
+            sqlx::query_as::<_, (Uuid, Uuid)>(
+                r#"
+                UPDATE so_allocations
+                SET qty_allocated = qty_shipped, updated_at = NOW()
+                WHERE sales_order_id = $1 AND qty_allocated > qty_shipped
+                  AND tenant_id = get_current_tenant_id()
+                RETURNING item_id, location_id
+                "#,
+            )
+            .bind(sales_order_id)
+            .fetch_all(&mut *tx)
+            .await?,
+        );
+
+        for allocation in allocations {
+            sqlx::query(
+                r#"
+                INSERT INTO so_allocations (id, sales_order_id, so_line_id, item_id, location_id, qty_allocated, qty_shipped, tenant_id, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, get_current_tenant_id(), $8, $9)
+                "#,
+            )
+            .bind(allocation.id)
+            .bind(allocation.sales_order_id)
+            .bind(allocation.so_line_id)
+            .bind(allocation.item_id)
+            .bind(allocation.location_id)
+            .bind(allocation.qty_allocated)
+            .bind(allocation.qty_shipped)
+            .bind(allocation.created_at)
+            .bind(allocation.updated_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        changed.extend(allocations.iter().map(|a| (a.item_id, a.location_id)));
+        PostgresItemAvailabilityRepository::refresh_in_tx(&mut tx, &changed).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use crate::domain::entities::attachment::ItemAttachment;
+use crate::domain::services::attachment_repository::AttachmentRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct PostgresAttachmentRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresAttachmentRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AttachmentRepository for PostgresAttachmentRepository {
+    async fn save(&self, attachment: &ItemAttachment) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO item_attachments
+                (id, item_id, file_name, content_type, size_bytes, storage_key, tenant_id, created_by, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, get_current_tenant_id(), $7, $8)
+            "#,
+            attachment.id,
+            attachment.item_id,
+            attachment.file_name,
+            attachment.content_type,
+            attachment.size_bytes,
+            attachment.storage_key,
+            attachment.created_by,
+            attachment.created_at
+        )
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ItemAttachment>, DomainError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, item_id, file_name, content_type, size_bytes, storage_key, created_by, created_at
+            FROM item_attachments
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+            id
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        Ok(row.map(|row| ItemAttachment {
+            id: row.id,
+            item_id: row.item_id,
+            file_name: row.file_name,
+            content_type: row.content_type,
+            size_bytes: row.size_bytes,
+            storage_key: row.storage_key,
+            created_by: row.created_by,
+            created_at: row.created_at,
+        }))
+    }
+
+    async fn list_for_item(&self, item_id: Uuid) -> Result<Vec<ItemAttachment>, DomainError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, item_id, file_name, content_type, size_bytes, storage_key, created_by, created_at
+            FROM item_attachments
+            WHERE item_id = $1 AND tenant_id = get_current_tenant_id()
+            ORDER BY created_at, id
+            "#,
+            item_id
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ItemAttachment {
+                id: row.id,
+                item_id: row.item_id,
+                file_name: row.file_name,
+                content_type: row.content_type,
+                size_bytes: row.size_bytes,
+                storage_key: row.storage_key,
+                created_by: row.created_by,
+                created_at: row.created_at,
+            })
+            .collect())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query!(
+            "DELETE FROM item_attachments WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+            id
+        )
+        .execute(&*self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Attachment {} not found", id).into(),
+            ));
+        }
+        Ok(())
+    }
+}
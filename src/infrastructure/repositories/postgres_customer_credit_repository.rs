@@ -0,0 +1,156 @@
+use crate::domain::entities::credit::{CreditEnforcement, CreditSettings, CustomerCreditLimit};
+use crate::domain::services::customer_credit_repository::CustomerCreditRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Row};
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct PostgresCustomerCreditRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresCustomerCreditRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CustomerCreditRepository for PostgresCustomerCreditRepository {
+    async fn find(&self) -> Result<CreditSettings, DomainError> {
+        let enforcement = self.find_enforcement().await?;
+        let customers = sqlx::query(
+            r#"
+            SELECT customer_id, credit_limit, updated_at
+            FROM customer_credit_limits
+            WHERE tenant_id = get_current_tenant_id()
+            ORDER BY customer_id
+            "#,
+        )
+        .fetch_all(&*self.pool)
+        .await?
+        .into_iter()
+        .map(|row| {
+            Ok(CustomerCreditLimit {
+                customer_id: row.try_get("customer_id")?,
+                credit_limit: row.try_get("credit_limit")?,
+                updated_at: row.try_get("updated_at")?,
+            })
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
+
+        Ok(CreditSettings {
+            enforcement,
+            customers,
+        })
+    }
+
+    async fn find_enforcement(&self) -> Result<CreditEnforcement, DomainError> {
+        let enforcement: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT enforcement
+            FROM credit_settings
+            WHERE tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
+        enforcement
+            .as_deref()
+            .map(CreditEnforcement::from_str)
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    async fn find_limit(&self, customer_id: Uuid) -> Result<Option<Decimal>, DomainError> {
+        Ok(sqlx::query_scalar(
+            r#"
+            SELECT credit_limit
+            FROM customer_credit_limits
+            WHERE customer_id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_optional(&*self.pool)
+        .await?)
+    }
+
+    async fn save_enforcement(&self, enforcement: CreditEnforcement) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO credit_settings (tenant_id, enforcement, updated_at)
+            VALUES (get_current_tenant_id(), $1, NOW())
+            ON CONFLICT (tenant_id) DO UPDATE
+            SET enforcement = EXCLUDED.enforcement,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(enforcement.as_str())
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn save_customer(
+        &self,
+        customer_id: Uuid,
+        credit_limit: Decimal,
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO customer_credit_limits (tenant_id, customer_id, credit_limit, updated_at)
+            VALUES (get_current_tenant_id(), $1, $2, NOW())
+            ON CONFLICT (tenant_id, customer_id) DO UPDATE
+            SET credit_limit = EXCLUDED.credit_limit,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(customer_id)
+        .bind(credit_limit)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_customer(&self, customer_id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM customer_credit_limits
+            WHERE customer_id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(customer_id)
+        .execute(&*self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn outstanding_balance(&self, customer_id: Uuid) -> Result<Decimal, DomainError> {
+        let balance: Option<Decimal> = sqlx::query_scalar(
+            r#"
+            SELECT ROUND(
+                COALESCE((
+                    SELECT SUM(ROUND((total_amount - amount_paid) * COALESCE(exchange_rate, 1), 2))
+                    FROM invoices
+                    WHERE customer_id = $1 AND status <> 'PAID'
+                      AND tenant_id = get_current_tenant_id()
+                ), 0)
+                - COALESCE((
+                    SELECT SUM(ROUND(p.credit_amount * COALESCE(i.exchange_rate, 1), 2))
+                    FROM payments p
+                    JOIN invoices i ON i.id = p.invoice_id
+                    WHERE p.customer_id = $1 AND p.credit_amount > 0
+                      AND p.tenant_id = get_current_tenant_id()
+                ), 0),
+            2)
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_one(&*self.pool)
+        .await?;
+        Ok(balance.unwrap_or_default())
+    }
+}
@@ -0,0 +1,352 @@
+use crate::domain::entities::cycle_count::{CycleCount, CycleCountLine, CycleCountStatus};
+use crate::domain::entities::inventory::StockMovement;
+use crate::domain::services::cycle_count_repository::CycleCountRepository;
+use crate::infrastructure::repositories::postgres_stock_repository::PostgresStockRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct PostgresCycleCountRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresCycleCountRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_line(row: &PgRow) -> Result<CycleCountLine, DomainError> {
+        Ok(CycleCountLine {
+            id: row.try_get("id")?,
+            cycle_count_id: row.try_get("cycle_count_id")?,
+            item_id: row.try_get("item_id")?,
+            expected_qty: row.try_get("expected_qty")?,
+            counted_qty: row.try_get("counted_qty")?,
+            counted_by: row.try_get("counted_by")?,
+            counted_at: row.try_get("counted_at")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    fn row_to_cycle_count(
+        row: &PgRow,
+        lines: Vec<CycleCountLine>,
+    ) -> Result<CycleCount, DomainError> {
+        let status: String = row.try_get("status")?;
+        Ok(CycleCount {
+            id: row.try_get("id")?,
+            count_number: row.try_get("count_number")?,
+            location_id: row.try_get("location_id")?,
+            status: CycleCountStatus::from_str(&status)?,
+            scheduled_for: row.try_get("scheduled_for")?,
+            notes: row.try_get("notes")?,
+            lines,
+            created_by: row.try_get("created_by")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            completed_at: row.try_get("completed_at")?,
+        })
+    }
+
+    async fn find_lines(&self, cycle_count_id: Uuid) -> Result<Vec<CycleCountLine>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, cycle_count_id, item_id, expected_qty, counted_qty, counted_by, counted_at, created_at, updated_at
+            FROM cycle_count_lines
+            WHERE cycle_count_id = $1
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(cycle_count_id)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_line).collect()
+    }
+
+    async fn update_with_executor(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        cycle_count: &CycleCount,
+    ) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE cycle_counts
+            SET status = $2, scheduled_for = $3, notes = $4, updated_at = $5, completed_at = $6
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(cycle_count.id)
+        .bind(cycle_count.status.as_str())
+        .bind(cycle_count.scheduled_for)
+        .bind(&cycle_count.notes)
+        .bind(cycle_count.updated_at)
+        .bind(cycle_count.completed_at)
+        .execute(&mut **tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Cycle count {} not found", cycle_count.id).into(),
+            ));
+        }
+
+        for line in &cycle_count.lines {
+            sqlx::query(
+                r#"
+                UPDATE cycle_count_lines
+                SET expected_qty = $2, counted_qty = $3, counted_by = $4, counted_at = $5, updated_at = $6
+                WHERE id = $1
+                "#,
+            )
+            .bind(line.id)
+            .bind(line.expected_qty)
+            .bind(line.counted_qty)
+            .bind(line.counted_by)
+            .bind(line.counted_at)
+            .bind(line.updated_at)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CycleCountRepository for PostgresCycleCountRepository {
+    async fn create(&self, cycle_count: &CycleCount) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO cycle_counts (id, count_number, location_id, status, scheduled_for, notes, tenant_id, created_by, created_at, updated_at, completed_at)
+            VALUES ($1, $2, $3, $4, $5, $6, get_current_tenant_id(), $7, $8, $9, $10)
+            "#,
+        )
+        .bind(cycle_count.id)
+        .bind(&cycle_count.count_number)
+        .bind(cycle_count.location_id)
+        .bind(cycle_count.status.as_str())
+        .bind(cycle_count.scheduled_for)
+        .bind(&cycle_count.notes)
+        .bind(cycle_count.created_by)
+        .bind(cycle_count.created_at)
+        .bind(cycle_count.updated_at)
+        .bind(cycle_count.completed_at)
+        .execute(&mut *tx)
+        .await?;
+
+        for line in &cycle_count.lines {
+            sqlx::query(
+                r#"
+                INSERT INTO cycle_count_lines (id, cycle_count_id, item_id, expected_qty, counted_qty, counted_by, counted_at, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                "#,
+            )
+            .bind(line.id)
+            .bind(line.cycle_count_id)
+            .bind(line.item_id)
+            .bind(line.expected_qty)
+            .bind(line.counted_qty)
+            .bind(line.counted_by)
+            .bind(line.counted_at)
+            .bind(line.created_at)
+            .bind(line.updated_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<CycleCount>, DomainError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, count_number, location_id, status, scheduled_for, notes, created_by, created_at, updated_at, completed_at
+            FROM cycle_counts
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        match row {
+            Some(row) => {
+                let lines = self.find_lines(id).await?;
+                Ok(Some(Self::row_to_cycle_count(&row, lines)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn update(&self, cycle_count: &CycleCount) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+        Self::update_with_executor(&mut tx, cycle_count).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        location_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<CycleCount>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, count_number, location_id, status, scheduled_for, notes, created_by, created_at, updated_at, completed_at
+            FROM cycle_counts
+            WHERE tenant_id = get_current_tenant_id() AND ($1::uuid IS NULL OR location_id = $1)
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(location_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        let mut cycle_counts = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let id: Uuid = row.try_get("id")?;
+            let lines = self.find_lines(id).await?;
+            cycle_counts.push(Self::row_to_cycle_count(row, lines)?);
+        }
+
+        Ok(cycle_counts)
+    }
+
+    async fn finalize(
+        &self,
+        cycle_count: &CycleCount,
+        stock_movements: &[StockMovement],
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        Self::update_with_executor(&mut tx, cycle_count).await?;
+
+        PostgresStockRepository::record_movements_in_tx(&mut tx, stock_movements).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::tenant_scope::with_tenant;
+    use crate::test_support::database::{TestDatabase, SEEDED_USER};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn test_finalize_moves_stock_levels_and_keeps_held_units() {
+        let db = TestDatabase::connect().await;
+        let admin = &db.admin;
+        let repository = PostgresCycleCountRepository::new(Arc::clone(&db.pool));
+        let user = Uuid::from_str(SEEDED_USER).unwrap();
+        let tenant_id = db.create_tenant("Cycle count levels").await;
+        let location_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO locations (name, code, tenant_id) VALUES ('Count aisle', 'COUNT', $1) RETURNING id",
+        )
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        let item_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO items (sku, name, unit, cost_price, tenant_id)
+             VALUES ('COUNT-1', 'Counted item', 'each', 5, $1) RETURNING id",
+        )
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        // Four of the ten on hand are quarantined
+        sqlx::query(
+            "INSERT INTO stock_levels (item_id, location_id, quantity_on_hand, quantity_quarantine, tenant_id)
+             VALUES ($1, $2, 10, 4, $3)",
+        )
+        .bind(item_id)
+        .bind(location_id)
+        .bind(tenant_id)
+        .execute(admin)
+        .await
+        .unwrap();
+
+        let count = |number: &str, expected: i64, counted: i64| {
+            let mut cycle_count =
+                CycleCount::new(number.to_string(), location_id, None, None, user).unwrap();
+            cycle_count
+                .add_line(item_id, Decimal::from(expected))
+                .unwrap();
+            cycle_count
+                .record_count(item_id, Decimal::from(counted), user)
+                .unwrap();
+            cycle_count
+        };
+        let finalize = |mut cycle_count: CycleCount| {
+            let repository = &repository;
+            with_tenant(tenant_id, async move {
+                repository.create(&cycle_count).await.unwrap();
+                let movements = cycle_count.finalize(user).unwrap();
+                repository
+                    .finalize(&cycle_count, &movements)
+                    .await
+                    .map(|_| cycle_count.id)
+            })
+        };
+        let on_hand = || async {
+            sqlx::query_scalar::<_, Decimal>(
+                "SELECT quantity_on_hand FROM stock_levels WHERE item_id = $1 AND location_id = $2",
+            )
+            .bind(item_id)
+            .bind(location_id)
+            .fetch_one(admin)
+            .await
+            .unwrap()
+        };
+
+        // Two more found than the system expected
+        let found = finalize(count("CC-LEVELS-1", 10, 12)).await.unwrap();
+        assert_eq!(on_hand().await, Decimal::from(12));
+        let last_movement: Option<Uuid> = sqlx::query_scalar(
+            "SELECT last_movement_id FROM stock_levels WHERE item_id = $1 AND location_id = $2",
+        )
+        .bind(item_id)
+        .bind(location_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        let movement_reference: Option<Uuid> =
+            sqlx::query_scalar("SELECT reference_id FROM stock_movements WHERE id = $1")
+                .bind(last_movement)
+                .fetch_one(admin)
+                .await
+                .unwrap();
+        assert_eq!(movement_reference, Some(found));
+
+        // Counting fewer than the quarantined units would write off held stock;
+        // the count stays open and the level untouched
+        let short = count("CC-LEVELS-2", 12, 3);
+        let short_id = short.id;
+        assert!(matches!(
+            finalize(short).await,
+            Err(DomainError::BusinessLogicError(_))
+        ));
+        assert_eq!(on_hand().await, Decimal::from(12));
+        let status: String = sqlx::query_scalar("SELECT status FROM cycle_counts WHERE id = $1")
+            .bind(short_id)
+            .fetch_one(admin)
+            .await
+            .unwrap();
+        assert_ne!(status, "COMPLETED");
+    }
+}
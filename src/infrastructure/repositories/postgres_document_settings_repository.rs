@@ -0,0 +1,88 @@
+use crate::domain::entities::document::DocumentSettings;
+use crate::domain::services::document_settings_repository::DocumentSettingsRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use serde_json::Value;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+
+pub struct PostgresDocumentSettingsRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresDocumentSettingsRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DocumentSettingsRepository for PostgresDocumentSettingsRepository {
+    async fn find(&self) -> Result<Option<DocumentSettings>, DomainError> {
+        let row = sqlx::query(
+            r#"
+            SELECT company_name, address, phone, email, tax_id, footer_text, logo_key, updated_at
+            FROM document_settings
+            WHERE tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.map(|row| {
+            let address: Option<Value> = row.try_get("address")?;
+            Ok(DocumentSettings {
+                company_name: row.try_get("company_name")?,
+                address: address.map(|a| serde_json::from_value(a).unwrap_or_default()),
+                phone: row.try_get("phone")?,
+                email: row.try_get("email")?,
+                tax_id: row.try_get("tax_id")?,
+                footer_text: row.try_get("footer_text")?,
+                logo_key: row.try_get("logo_key")?,
+                updated_at: row.try_get("updated_at")?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn save(&self, settings: &DocumentSettings) -> Result<(), DomainError> {
+        let address = settings
+            .address
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| {
+                DomainError::InfrastructureError(
+                    format!("Failed to serialize address: {}", e).into(),
+                )
+            })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO document_settings (tenant_id, company_name, address, phone, email, tax_id, footer_text, logo_key, updated_at)
+            VALUES (get_current_tenant_id(), $1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (tenant_id) DO UPDATE
+            SET company_name = EXCLUDED.company_name,
+                address = EXCLUDED.address,
+                phone = EXCLUDED.phone,
+                email = EXCLUDED.email,
+                tax_id = EXCLUDED.tax_id,
+                footer_text = EXCLUDED.footer_text,
+                logo_key = EXCLUDED.logo_key,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(&settings.company_name)
+        .bind(address)
+        .bind(&settings.phone)
+        .bind(&settings.email)
+        .bind(&settings.tax_id)
+        .bind(&settings.footer_text)
+        .bind(&settings.logo_key)
+        .bind(settings.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
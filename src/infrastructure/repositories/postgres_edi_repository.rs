@@ -0,0 +1,355 @@
+use crate::domain::entities::edi::{
+    EdiDirection, EdiDocument, EdiDocumentStatus, EdiDocumentType, EdiItemMatch, EdiReferenceType,
+    EdiSalesOrderLink, TradingPartner,
+};
+use crate::domain::services::edi_repository::EdiRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const PARTNER_COLUMNS: &str = "id, name, interchange_id_qualifier, interchange_id, application_id, our_interchange_id_qualifier, our_interchange_id, our_application_id, item_id_qualifier, item_match, customer_id, fulfillment_location_id, active, created_at, updated_at";
+
+const DOCUMENT_COLUMNS: &str = "id, partner_id, document_type, direction, status, control_number, reference_type, reference_ids, error, content, created_at";
+
+const LINK_COLUMNS: &str = "sales_order_id, partner_id, document_id, po_number, po_date";
+
+pub struct PostgresEdiRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresEdiRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_partner(row: &PgRow) -> Result<TradingPartner, DomainError> {
+        Ok(TradingPartner {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            interchange_id_qualifier: row.try_get("interchange_id_qualifier")?,
+            interchange_id: row.try_get("interchange_id")?,
+            application_id: row.try_get("application_id")?,
+            our_interchange_id_qualifier: row.try_get("our_interchange_id_qualifier")?,
+            our_interchange_id: row.try_get("our_interchange_id")?,
+            our_application_id: row.try_get("our_application_id")?,
+            item_id_qualifier: row.try_get("item_id_qualifier")?,
+            item_match: EdiItemMatch::from_str(row.try_get("item_match")?)?,
+            customer_id: row.try_get("customer_id")?,
+            fulfillment_location_id: row.try_get("fulfillment_location_id")?,
+            active: row.try_get("active")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    fn row_to_document(row: &PgRow) -> Result<EdiDocument, DomainError> {
+        let reference_type: Option<String> = row.try_get("reference_type")?;
+        Ok(EdiDocument {
+            id: row.try_get("id")?,
+            partner_id: row.try_get("partner_id")?,
+            document_type: EdiDocumentType::from_str(row.try_get("document_type")?)?,
+            direction: EdiDirection::from_str(row.try_get("direction")?)?,
+            status: EdiDocumentStatus::from_str(row.try_get("status")?)?,
+            control_number: row.try_get("control_number")?,
+            reference_type: reference_type
+                .as_deref()
+                .map(EdiReferenceType::from_str)
+                .transpose()?,
+            reference_ids: row.try_get("reference_ids")?,
+            error: row.try_get("error")?,
+            content: row.try_get("content")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+
+    fn row_to_link(row: &PgRow) -> Result<EdiSalesOrderLink, DomainError> {
+        Ok(EdiSalesOrderLink {
+            sales_order_id: row.try_get("sales_order_id")?,
+            partner_id: row.try_get("partner_id")?,
+            document_id: row.try_get("document_id")?,
+            po_number: row.try_get("po_number")?,
+            po_date: row.try_get("po_date")?,
+        })
+    }
+}
+
+#[async_trait]
+impl EdiRepository for PostgresEdiRepository {
+    async fn create_partner(&self, partner: &TradingPartner) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO edi_trading_partners (id, name, interchange_id_qualifier, interchange_id, application_id,
+                                              our_interchange_id_qualifier, our_interchange_id, our_application_id,
+                                              item_id_qualifier, item_match, customer_id, fulfillment_location_id,
+                                              active, tenant_id, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, get_current_tenant_id(), $14, $15)
+            "#,
+        )
+        .bind(partner.id)
+        .bind(&partner.name)
+        .bind(&partner.interchange_id_qualifier)
+        .bind(&partner.interchange_id)
+        .bind(&partner.application_id)
+        .bind(&partner.our_interchange_id_qualifier)
+        .bind(&partner.our_interchange_id)
+        .bind(&partner.our_application_id)
+        .bind(&partner.item_id_qualifier)
+        .bind(partner.item_match.as_str())
+        .bind(partner.customer_id)
+        .bind(partner.fulfillment_location_id)
+        .bind(partner.active)
+        .bind(partner.created_at)
+        .bind(partner.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_partner_by_id(&self, id: Uuid) -> Result<Option<TradingPartner>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM edi_trading_partners WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+            PARTNER_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_partner).transpose()
+    }
+
+    async fn find_partner_by_interchange_id(
+        &self,
+        interchange_id: &str,
+    ) -> Result<Option<TradingPartner>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM edi_trading_partners WHERE interchange_id = $1 AND tenant_id = get_current_tenant_id()",
+            PARTNER_COLUMNS
+        ))
+        .bind(interchange_id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_partner).transpose()
+    }
+
+    async fn list_partners(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<TradingPartner>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM edi_trading_partners
+            WHERE tenant_id = get_current_tenant_id()
+            ORDER BY name, id
+            LIMIT $1 OFFSET $2
+            "#,
+            PARTNER_COLUMNS
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_partner).collect()
+    }
+
+    async fn update_partner(&self, partner: &TradingPartner) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE edi_trading_partners
+            SET name = $2, application_id = $3, our_interchange_id_qualifier = $4, our_interchange_id = $5,
+                our_application_id = $6, item_id_qualifier = $7, item_match = $8, customer_id = $9,
+                fulfillment_location_id = $10, active = $11, updated_at = $12
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(partner.id)
+        .bind(&partner.name)
+        .bind(&partner.application_id)
+        .bind(&partner.our_interchange_id_qualifier)
+        .bind(&partner.our_interchange_id)
+        .bind(&partner.our_application_id)
+        .bind(&partner.item_id_qualifier)
+        .bind(partner.item_match.as_str())
+        .bind(partner.customer_id)
+        .bind(partner.fulfillment_location_id)
+        .bind(partner.active)
+        .bind(partner.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Trading partner {} not found", partner.id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn delete_partner(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "DELETE FROM edi_trading_partners WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+        )
+        .bind(id)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_foreign_key_violation() => {
+                DomainError::Conflict(format!(
+                    "Trading partner {} has sales orders raised from its documents; deactivate it instead",
+                    id
+                ).into())
+            }
+            e => e.into(),
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Trading partner {} not found", id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn next_control_number(&self, partner_id: Uuid) -> Result<i64, DomainError> {
+        // ISA13 is nine digits, so the number wraps back to 1
+        let control_number: i64 = sqlx::query_scalar(
+            r#"
+            UPDATE edi_trading_partners
+            SET control_number = control_number % 999999999 + 1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            RETURNING control_number
+            "#,
+        )
+        .bind(partner_id)
+        .fetch_optional(&*self.pool)
+        .await?
+        .ok_or_else(|| {
+            DomainError::NotFound(format!("Trading partner {} not found", partner_id).into())
+        })?;
+
+        Ok(control_number)
+    }
+
+    async fn create_document(&self, document: &EdiDocument) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO edi_documents (id, partner_id, document_type, direction, status, control_number,
+                                       reference_type, reference_ids, error, content, tenant_id, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, get_current_tenant_id(), $11)
+            "#,
+        )
+        .bind(document.id)
+        .bind(document.partner_id)
+        .bind(document.document_type.as_str())
+        .bind(document.direction.as_str())
+        .bind(document.status.as_str())
+        .bind(&document.control_number)
+        .bind(document.reference_type.map(|r| r.as_str()))
+        .bind(&document.reference_ids)
+        .bind(&document.error)
+        .bind(&document.content)
+        .bind(document.created_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_document(&self, id: Uuid) -> Result<Option<EdiDocument>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM edi_documents WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+            DOCUMENT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_document).transpose()
+    }
+
+    async fn list_documents(
+        &self,
+        partner_id: Option<Uuid>,
+        document_type: Option<EdiDocumentType>,
+        direction: Option<EdiDirection>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<EdiDocument>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM edi_documents
+            WHERE tenant_id = get_current_tenant_id()
+              AND ($1::uuid IS NULL OR partner_id = $1)
+              AND ($2::text IS NULL OR document_type = $2)
+              AND ($3::text IS NULL OR direction = $3)
+            ORDER BY created_at DESC, id
+            LIMIT $4 OFFSET $5
+            "#,
+            DOCUMENT_COLUMNS
+        ))
+        .bind(partner_id)
+        .bind(document_type.map(|t| t.as_str()))
+        .bind(direction.map(|d| d.as_str()))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_document).collect()
+    }
+
+    async fn create_sales_order_link(&self, link: &EdiSalesOrderLink) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO edi_sales_orders (sales_order_id, partner_id, document_id, po_number, po_date, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, get_current_tenant_id())
+            "#,
+        )
+        .bind(link.sales_order_id)
+        .bind(link.partner_id)
+        .bind(link.document_id)
+        .bind(&link.po_number)
+        .bind(link.po_date)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_sales_order_link(
+        &self,
+        sales_order_id: Uuid,
+    ) -> Result<Option<EdiSalesOrderLink>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM edi_sales_orders WHERE sales_order_id = $1 AND tenant_id = get_current_tenant_id()",
+            LINK_COLUMNS
+        ))
+        .bind(sales_order_id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_link).transpose()
+    }
+
+    async fn find_sales_order_link_by_po(
+        &self,
+        partner_id: Uuid,
+        po_number: &str,
+    ) -> Result<Option<EdiSalesOrderLink>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM edi_sales_orders WHERE partner_id = $1 AND po_number = $2 AND tenant_id = get_current_tenant_id()",
+            LINK_COLUMNS
+        ))
+        .bind(partner_id)
+        .bind(po_number)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_link).transpose()
+    }
+}
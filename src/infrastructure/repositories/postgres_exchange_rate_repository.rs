@@ -0,0 +1,78 @@
+use crate::domain::entities::currency::ExchangeRate;
+use crate::domain::services::exchange_rate_repository::ExchangeRateRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use std::sync::Arc;
+
+/// Rates are reference data shared by every tenant, so nothing here is tenant scoped
+pub struct PostgresExchangeRateRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresExchangeRateRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_rate(row: &PgRow) -> Result<ExchangeRate, DomainError> {
+        Ok(ExchangeRate {
+            base_currency: row.try_get("base_currency")?,
+            quote_currency: row.try_get("quote_currency")?,
+            rate: row.try_get("rate")?,
+            rate_date: row.try_get("rate_date")?,
+            source: row.try_get("source")?,
+            fetched_at: row.try_get("fetched_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl ExchangeRateRepository for PostgresExchangeRateRepository {
+    async fn save_rates(&self, rates: &[ExchangeRate]) -> Result<u64, DomainError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO exchange_rates (base_currency, quote_currency, rate_date, rate, source, fetched_at)
+            SELECT * FROM UNNEST($1::VARCHAR[], $2::VARCHAR[], $3::DATE[], $4::NUMERIC[], $5::VARCHAR[], $6::TIMESTAMPTZ[])
+            ON CONFLICT (base_currency, quote_currency, rate_date)
+            DO UPDATE SET rate = EXCLUDED.rate, source = EXCLUDED.source, fetched_at = EXCLUDED.fetched_at
+            "#,
+        )
+        .bind(rates.iter().map(|r| r.base_currency.clone()).collect::<Vec<_>>())
+        .bind(rates.iter().map(|r| r.quote_currency.clone()).collect::<Vec<_>>())
+        .bind(rates.iter().map(|r| r.rate_date).collect::<Vec<_>>())
+        .bind(rates.iter().map(|r| r.rate).collect::<Vec<_>>())
+        .bind(rates.iter().map(|r| r.source.clone()).collect::<Vec<_>>())
+        .bind(rates.iter().map(|r| r.fetched_at).collect::<Vec<_>>())
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn latest_rates(&self, on: NaiveDate) -> Result<Vec<ExchangeRate>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT ON (base_currency, quote_currency)
+                   base_currency, quote_currency, rate_date, rate, source, fetched_at
+            FROM exchange_rates
+            WHERE rate_date <= $1
+            ORDER BY base_currency, quote_currency, rate_date DESC
+            "#,
+        )
+        .bind(on)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_rate).collect()
+    }
+
+    async fn latest_rate_date(&self) -> Result<Option<NaiveDate>, DomainError> {
+        let date = sqlx::query_scalar("SELECT MAX(rate_date) FROM exchange_rates")
+            .fetch_one(&*self.pool)
+            .await?;
+
+        Ok(date)
+    }
+}
@@ -0,0 +1,147 @@
+use crate::domain::entities::forecast::{DemandForecast, ForecastMethod};
+use crate::domain::services::forecast_repository::ForecastRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct PostgresForecastRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresForecastRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+/// Day counts are never negative in the table, so the casts back are lossless
+fn days(row: &sqlx::postgres::PgRow, column: &str) -> Result<u32, DomainError> {
+    let value: i32 = row.try_get(column)?;
+    Ok(value as u32)
+}
+
+#[async_trait]
+impl ForecastRepository for PostgresForecastRepository {
+    async fn daily_demand(
+        &self,
+        item_ids: &[Uuid],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<HashMap<Uuid, Vec<(NaiveDate, Decimal)>>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            WITH zone AS (SELECT get_current_tenant_timezone() AS name)
+            SELECT item_id, (created_at AT TIME ZONE zone.name)::date AS day,
+                   SUM(-quantity)::numeric AS quantity
+            FROM stock_movements, zone
+            WHERE item_id = ANY($1) AND tenant_id = (SELECT get_current_tenant_id())
+              AND movement_type = 'outbound'
+              AND created_at >= ($2::date::timestamp AT TIME ZONE zone.name)
+              AND created_at < ($3::date::timestamp AT TIME ZONE zone.name)
+            GROUP BY item_id, day
+            ORDER BY item_id, day
+            "#,
+        )
+        .bind(item_ids)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        let mut demand: HashMap<Uuid, Vec<(NaiveDate, Decimal)>> = HashMap::new();
+        for row in rows {
+            demand
+                .entry(row.try_get("item_id")?)
+                .or_default()
+                .push((row.try_get("day")?, row.try_get("quantity")?));
+        }
+        Ok(demand)
+    }
+
+    async fn save(&self, forecast: &DemandForecast) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO demand_forecasts (id, item_id, method, history_days, window_days, alpha, history_total, daily_demand, horizon_days, forecast_qty, lead_time_days, safety_stock_days, reorder_point, computed_at, computed_on, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, ($14 AT TIME ZONE get_current_tenant_timezone())::date, get_current_tenant_id())
+            ON CONFLICT (tenant_id, item_id, method, computed_on) DO UPDATE SET
+                id = EXCLUDED.id,
+                history_days = EXCLUDED.history_days,
+                window_days = EXCLUDED.window_days,
+                alpha = EXCLUDED.alpha,
+                history_total = EXCLUDED.history_total,
+                daily_demand = EXCLUDED.daily_demand,
+                horizon_days = EXCLUDED.horizon_days,
+                forecast_qty = EXCLUDED.forecast_qty,
+                lead_time_days = EXCLUDED.lead_time_days,
+                safety_stock_days = EXCLUDED.safety_stock_days,
+                reorder_point = EXCLUDED.reorder_point,
+                computed_at = EXCLUDED.computed_at
+            "#,
+        )
+        .bind(forecast.id)
+        .bind(forecast.item_id)
+        .bind(forecast.method.as_str())
+        .bind(forecast.history_days as i32)
+        .bind(forecast.window_days.map(|d| d as i32))
+        .bind(forecast.alpha)
+        .bind(forecast.history_total)
+        .bind(forecast.daily_demand)
+        .bind(forecast.horizon_days as i32)
+        .bind(forecast.forecast_qty)
+        .bind(forecast.lead_time_days as i32)
+        .bind(forecast.safety_stock_days as i32)
+        .bind(forecast.reorder_point)
+        .bind(forecast.computed_at)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn list_by_item(
+        &self,
+        item_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<DemandForecast>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, item_id, method, history_days, window_days, alpha, history_total, daily_demand, horizon_days, forecast_qty, lead_time_days, safety_stock_days, reorder_point, computed_at
+            FROM demand_forecasts
+            WHERE item_id = $1 AND tenant_id = get_current_tenant_id()
+            ORDER BY computed_at DESC, id
+            LIMIT $2
+            "#,
+        )
+        .bind(item_id)
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let method: String = row.try_get("method")?;
+                let window_days: Option<i32> = row.try_get("window_days")?;
+                Ok(DemandForecast {
+                    id: row.try_get("id")?,
+                    item_id: row.try_get("item_id")?,
+                    method: ForecastMethod::from_str(&method)?,
+                    history_days: days(row, "history_days")?,
+                    window_days: window_days.map(|d| d as u32),
+                    alpha: row.try_get("alpha")?,
+                    history_total: row.try_get("history_total")?,
+                    daily_demand: row.try_get("daily_demand")?,
+                    horizon_days: days(row, "horizon_days")?,
+                    forecast_qty: row.try_get("forecast_qty")?,
+                    lead_time_days: days(row, "lead_time_days")?,
+                    safety_stock_days: days(row, "safety_stock_days")?,
+                    reorder_point: row.try_get("reorder_point")?,
+                    computed_at: row.try_get("computed_at")?,
+                })
+            })
+            .collect()
+    }
+}
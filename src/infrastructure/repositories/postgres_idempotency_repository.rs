@@ -24,14 +24,27 @@ impl IdempotencyRepository for PostgresIdempotencyRepository {
         let result = sqlx::query(
             r#"
             INSERT INTO idempotency_keys (
-                id, idempotency_key, request_path, request_method,
+                id, tenant_id, idempotency_key, request_path, request_method,
                 request_body_hash, response_status, response_body,
                 expires_at, created_at, updated_at
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-            ON CONFLICT (idempotency_key) DO NOTHING
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (idempotency_key) DO UPDATE SET
+                id = EXCLUDED.id,
+                tenant_id = EXCLUDED.tenant_id,
+                request_path = EXCLUDED.request_path,
+                request_method = EXCLUDED.request_method,
+                request_body_hash = EXCLUDED.request_body_hash,
+                response_status = NULL,
+                response_body = NULL,
+                expires_at = EXCLUDED.expires_at,
+                created_at = EXCLUDED.created_at,
+                updated_at = EXCLUDED.updated_at
+            -- An expired key that hasn't been cleaned up yet can be reused
+            WHERE idempotency_keys.expires_at <= NOW()
             "#,
         )
         .bind(key.id)
+        .bind(key.tenant_id)
         .bind(&key.idempotency_key)
         .bind(&key.request_path)
         .bind(&key.request_method)
@@ -44,12 +57,12 @@ impl IdempotencyRepository for PostgresIdempotencyRepository {
         .execute(&*self.pool)
         .await
         .map_err(|e| {
-            DomainError::ValidationError(format!("Failed to store idempotency key: {}", e))
+            DomainError::DatabaseError(format!("Failed to store idempotency key: {}", e).into())
         })?;
 
         if result.rows_affected() == 0 {
             return Err(DomainError::Conflict(
-                "Idempotency key already exists".to_string(),
+                "Idempotency key already exists".into(),
             ));
         }
 
@@ -59,7 +72,7 @@ impl IdempotencyRepository for PostgresIdempotencyRepository {
     async fn get_key(&self, idempotency_key: &str) -> Result<Option<IdempotencyKey>, DomainError> {
         let row = sqlx::query(
             r#"
-            SELECT id, idempotency_key, request_path, request_method,
+            SELECT id, tenant_id, idempotency_key, request_path, request_method,
                    request_body_hash, response_status, response_body,
                    expires_at, created_at, updated_at
             FROM idempotency_keys
@@ -70,7 +83,7 @@ impl IdempotencyRepository for PostgresIdempotencyRepository {
         .fetch_optional(&*self.pool)
         .await
         .map_err(|e| {
-            DomainError::ValidationError(format!("Failed to get idempotency key: {}", e))
+            DomainError::DatabaseError(format!("Failed to get idempotency key: {}", e).into())
         })?;
 
         match row {
@@ -78,34 +91,37 @@ impl IdempotencyRepository for PostgresIdempotencyRepository {
                 let key = IdempotencyKey {
                     id: row
                         .try_get("id")
-                        .map_err(|e| DomainError::ValidationError(e.to_string()))?,
+                        .map_err(|e| DomainError::ValidationError(e.to_string().into()))?,
+                    tenant_id: row
+                        .try_get("tenant_id")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     idempotency_key: row
                         .try_get("idempotency_key")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     request_path: row
                         .try_get("request_path")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     request_method: row
                         .try_get("request_method")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     request_body_hash: row
                         .try_get("request_body_hash")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     response_status: row
                         .try_get("response_status")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     response_body: row
                         .try_get("response_body")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     expires_at: row
                         .try_get("expires_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     created_at: row
                         .try_get("created_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     updated_at: row
                         .try_get("updated_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                 };
 
                 Ok(Some(key))
@@ -133,24 +149,38 @@ impl IdempotencyRepository for PostgresIdempotencyRepository {
         .execute(&*self.pool)
         .await
         .map_err(|e| {
-            DomainError::DatabaseError(format!("Failed to complete idempotency key: {}", e))
+            DomainError::DatabaseError(format!("Failed to complete idempotency key: {}", e).into())
         })?;
 
         if result.rows_affected() == 0 {
             return Err(DomainError::NotFound(
-                "Idempotency key not found or already completed".to_string(),
+                "Idempotency key not found or already completed".into(),
             ));
         }
 
         Ok(())
     }
 
+    async fn delete_key(&self, idempotency_key: &str) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE idempotency_key = $1")
+            .bind(idempotency_key)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(
+                    format!("Failed to delete idempotency key: {}", e).into(),
+                )
+            })?;
+
+        Ok(())
+    }
+
     async fn delete_expired_keys(&self) -> Result<i64, DomainError> {
         let result = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= NOW()")
             .execute(&*self.pool)
             .await
             .map_err(|e| {
-                DomainError::DatabaseError(format!("Failed to delete expired keys: {}", e))
+                DomainError::DatabaseError(format!("Failed to delete expired keys: {}", e).into())
             })?;
 
         Ok(result.rows_affected() as i64)
@@ -163,7 +193,7 @@ impl IdempotencyRepository for PostgresIdempotencyRepository {
         .bind(idempotency_key)
         .fetch_one(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(format!("Failed to check key existence: {}", e)))?;
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to check key existence: {}", e).into()))?;
 
         Ok(count.0 > 0)
     }
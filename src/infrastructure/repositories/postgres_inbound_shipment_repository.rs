@@ -0,0 +1,271 @@
+use crate::domain::entities::inbound_shipment::{
+    InboundCarton, InboundCartonLine, InboundShipment, InboundShipmentStatus,
+};
+use crate::domain::services::inbound_shipment_repository::InboundShipmentRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const SHIPMENT_COLUMNS: &str = "id, asn_number, po_id, supplier_id, status, expected_arrival, carrier, tracking_number, created_by, created_at, updated_at, closed_at";
+
+pub struct PostgresInboundShipmentRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresInboundShipmentRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Attach cartons and their lines to the shipment rows, two queries for
+    /// the lot
+    async fn hydrate(&self, rows: Vec<PgRow>) -> Result<Vec<InboundShipment>, DomainError> {
+        let ids = rows
+            .iter()
+            .map(|row| row.try_get("id"))
+            .collect::<Result<Vec<Uuid>, _>>()?;
+
+        let carton_rows = sqlx::query(
+            r#"
+            SELECT id, shipment_id, code, received_at, received_by
+            FROM inbound_cartons
+            WHERE shipment_id = ANY($1) AND tenant_id = (SELECT get_current_tenant_id())
+            ORDER BY code
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&*self.pool)
+        .await?;
+        let carton_ids = carton_rows
+            .iter()
+            .map(|row| row.try_get("id"))
+            .collect::<Result<Vec<Uuid>, _>>()?;
+
+        let mut lines: HashMap<Uuid, Vec<InboundCartonLine>> = HashMap::new();
+        for row in sqlx::query(
+            r#"
+            SELECT l.id, l.carton_id, l.po_line_id, l.item_id, l.qty_expected, l.qty_received
+            FROM inbound_carton_lines l
+            WHERE l.carton_id = ANY($1) AND l.tenant_id = (SELECT get_current_tenant_id())
+            -- Announced lines first, then anything found in the carton
+            ORDER BY l.qty_expected = 0, l.po_line_id
+            "#,
+        )
+        .bind(&carton_ids)
+        .fetch_all(&*self.pool)
+        .await?
+        {
+            lines
+                .entry(row.try_get("carton_id")?)
+                .or_default()
+                .push(InboundCartonLine {
+                    id: row.try_get("id")?,
+                    po_line_id: row.try_get("po_line_id")?,
+                    item_id: row.try_get("item_id")?,
+                    qty_expected: row.try_get("qty_expected")?,
+                    qty_received: row.try_get("qty_received")?,
+                });
+        }
+
+        let mut cartons: HashMap<Uuid, Vec<InboundCarton>> = HashMap::new();
+        for row in carton_rows {
+            let id: Uuid = row.try_get("id")?;
+            cartons
+                .entry(row.try_get("shipment_id")?)
+                .or_default()
+                .push(InboundCarton {
+                    id,
+                    code: row.try_get("code")?,
+                    lines: lines.remove(&id).unwrap_or_default(),
+                    received_at: row.try_get("received_at")?,
+                    received_by: row.try_get("received_by")?,
+                });
+        }
+
+        rows.iter()
+            .map(|row| {
+                let id: Uuid = row.try_get("id")?;
+                let status: String = row.try_get("status")?;
+                Ok(InboundShipment {
+                    id,
+                    asn_number: row.try_get("asn_number")?,
+                    po_id: row.try_get("po_id")?,
+                    supplier_id: row.try_get("supplier_id")?,
+                    status: InboundShipmentStatus::from_str(&status)?,
+                    expected_arrival: row.try_get("expected_arrival")?,
+                    carrier: row.try_get("carrier")?,
+                    tracking_number: row.try_get("tracking_number")?,
+                    cartons: cartons.remove(&id).unwrap_or_default(),
+                    created_by: row.try_get("created_by")?,
+                    created_at: row.try_get("created_at")?,
+                    updated_at: row.try_get("updated_at")?,
+                    closed_at: row.try_get("closed_at")?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl InboundShipmentRepository for PostgresInboundShipmentRepository {
+    async fn create(&self, shipment: &InboundShipment) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO inbound_shipments (id, asn_number, po_id, supplier_id, status, expected_arrival, carrier, tracking_number, created_by, created_at, updated_at, closed_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, get_current_tenant_id())
+            "#,
+        )
+        .bind(shipment.id)
+        .bind(&shipment.asn_number)
+        .bind(shipment.po_id)
+        .bind(shipment.supplier_id)
+        .bind(shipment.status.as_str())
+        .bind(shipment.expected_arrival)
+        .bind(&shipment.carrier)
+        .bind(&shipment.tracking_number)
+        .bind(shipment.created_by)
+        .bind(shipment.created_at)
+        .bind(shipment.updated_at)
+        .bind(shipment.closed_at)
+        .execute(&mut *tx)
+        .await?;
+
+        for carton in &shipment.cartons {
+            sqlx::query(
+                r#"
+                INSERT INTO inbound_cartons (id, shipment_id, code, received_at, received_by, tenant_id)
+                VALUES ($1, $2, $3, $4, $5, get_current_tenant_id())
+                "#,
+            )
+            .bind(carton.id)
+            .bind(shipment.id)
+            .bind(&carton.code)
+            .bind(carton.received_at)
+            .bind(carton.received_by)
+            .execute(&mut *tx)
+            .await?;
+
+            for line in &carton.lines {
+                sqlx::query(
+                    r#"
+                    INSERT INTO inbound_carton_lines (id, carton_id, po_line_id, item_id, qty_expected, qty_received, tenant_id)
+                    VALUES ($1, $2, $3, $4, $5, $6, get_current_tenant_id())
+                    "#,
+                )
+                .bind(line.id)
+                .bind(carton.id)
+                .bind(line.po_line_id)
+                .bind(line.item_id)
+                .bind(line.qty_expected)
+                .bind(line.qty_received)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<InboundShipment>, DomainError> {
+        let sql = format!(
+            "SELECT {} FROM inbound_shipments WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+            SHIPMENT_COLUMNS
+        );
+        let rows = sqlx::query(&sql).bind(id).fetch_all(&*self.pool).await?;
+        Ok(self.hydrate(rows).await?.pop())
+    }
+
+    async fn find_by_asn_number(
+        &self,
+        supplier_id: Uuid,
+        asn_number: &str,
+    ) -> Result<Option<InboundShipment>, DomainError> {
+        let sql = format!(
+            "SELECT {} FROM inbound_shipments WHERE supplier_id = $1 AND asn_number = $2 AND tenant_id = get_current_tenant_id()",
+            SHIPMENT_COLUMNS
+        );
+        let rows = sqlx::query(&sql)
+            .bind(supplier_id)
+            .bind(asn_number)
+            .fetch_all(&*self.pool)
+            .await?;
+        Ok(self.hydrate(rows).await?.pop())
+    }
+
+    async fn list_by_po(&self, po_id: Uuid) -> Result<Vec<InboundShipment>, DomainError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM inbound_shipments WHERE po_id = $1 AND tenant_id = get_current_tenant_id() ORDER BY created_at, id",
+            SHIPMENT_COLUMNS
+        ))
+        .bind(po_id)
+        .fetch_all(&*self.pool)
+        .await?;
+        self.hydrate(rows).await
+    }
+
+    async fn update(&self, shipment: &InboundShipment) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE inbound_shipments
+            SET status = $2, updated_at = $3, closed_at = $4
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(shipment.id)
+        .bind(shipment.status.as_str())
+        .bind(shipment.updated_at)
+        .bind(shipment.closed_at)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Inbound shipment {} not found", shipment.id).into(),
+            ));
+        }
+
+        for carton in &shipment.cartons {
+            sqlx::query(
+                r#"
+                UPDATE inbound_cartons
+                SET received_at = $2, received_by = $3
+                WHERE id = $1 AND tenant_id = get_current_tenant_id()
+                "#,
+            )
+            .bind(carton.id)
+            .bind(carton.received_at)
+            .bind(carton.received_by)
+            .execute(&mut *tx)
+            .await?;
+
+            for line in &carton.lines {
+                sqlx::query(
+                    r#"
+                    INSERT INTO inbound_carton_lines (id, carton_id, po_line_id, item_id, qty_expected, qty_received, tenant_id)
+                    VALUES ($1, $2, $3, $4, $5, $6, get_current_tenant_id())
+                    ON CONFLICT (id) DO UPDATE SET qty_received = EXCLUDED.qty_received
+                    "#,
+                )
+                .bind(line.id)
+                .bind(carton.id)
+                .bind(line.po_line_id)
+                .bind(line.item_id)
+                .bind(line.qty_expected)
+                .bind(line.qty_received)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
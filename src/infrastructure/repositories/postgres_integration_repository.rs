@@ -0,0 +1,390 @@
+use crate::domain::entities::integration::{
+    ConnectorProvider, IntegrationConnector, IntegrationOrderLink, SyncKind, SyncRun, SyncRunError,
+    SyncRunStatus, SyncTrigger,
+};
+use crate::domain::services::integration_repository::{IntegrationRepository, LocationStock};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const CONNECTOR_COLUMNS: &str = "id, provider, name, shop_domain, location_id, external_location_id, customer_id, sync_interval_minutes, active, access_token, token_scopes, token_obtained_at, oauth_state, orders_cursor, inventory_cursor, next_sync_at, created_by, created_at, updated_at";
+
+const SYNC_RUN_COLUMNS: &str = "id, connector_id, kind, trigger, status, records_processed, records_failed, errors, started_at, finished_at";
+
+pub struct PostgresIntegrationRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresIntegrationRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_connector(row: &PgRow) -> Result<IntegrationConnector, DomainError> {
+        Ok(IntegrationConnector {
+            id: row.try_get("id")?,
+            provider: ConnectorProvider::from_str(row.try_get("provider")?)?,
+            name: row.try_get("name")?,
+            shop_domain: row.try_get("shop_domain")?,
+            location_id: row.try_get("location_id")?,
+            external_location_id: row.try_get("external_location_id")?,
+            customer_id: row.try_get("customer_id")?,
+            sync_interval_minutes: row.try_get("sync_interval_minutes")?,
+            active: row.try_get("active")?,
+            access_token: row.try_get("access_token")?,
+            token_scopes: row.try_get("token_scopes")?,
+            token_obtained_at: row.try_get("token_obtained_at")?,
+            oauth_state: row.try_get("oauth_state")?,
+            orders_cursor: row.try_get("orders_cursor")?,
+            inventory_cursor: row.try_get("inventory_cursor")?,
+            next_sync_at: row.try_get("next_sync_at")?,
+            created_by: row.try_get("created_by")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    fn row_to_sync_run(row: &PgRow) -> Result<SyncRun, DomainError> {
+        let errors: serde_json::Value = row.try_get("errors")?;
+        let errors: Vec<SyncRunError> = serde_json::from_value(errors).map_err(|e| {
+            DomainError::DatabaseError(format!("Invalid sync run errors: {}", e).into())
+        })?;
+        Ok(SyncRun {
+            id: row.try_get("id")?,
+            connector_id: row.try_get("connector_id")?,
+            kind: SyncKind::from_str(row.try_get("kind")?)?,
+            trigger: SyncTrigger::from_str(row.try_get("trigger")?)?,
+            status: SyncRunStatus::from_str(row.try_get("status")?)?,
+            records_processed: row.try_get("records_processed")?,
+            records_failed: row.try_get("records_failed")?,
+            errors,
+            started_at: row.try_get("started_at")?,
+            finished_at: row.try_get("finished_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl IntegrationRepository for PostgresIntegrationRepository {
+    async fn create_connector(&self, connector: &IntegrationConnector) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO integration_connectors (id, provider, name, shop_domain, location_id, external_location_id,
+                                                customer_id, sync_interval_minutes, active, next_sync_at,
+                                                created_by, tenant_id, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, get_current_tenant_id(), $12, $13)
+            "#,
+        )
+        .bind(connector.id)
+        .bind(connector.provider.as_str())
+        .bind(&connector.name)
+        .bind(&connector.shop_domain)
+        .bind(connector.location_id)
+        .bind(&connector.external_location_id)
+        .bind(connector.customer_id)
+        .bind(connector.sync_interval_minutes)
+        .bind(connector.active)
+        .bind(connector.next_sync_at)
+        .bind(connector.created_by)
+        .bind(connector.created_at)
+        .bind(connector.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_connector(&self, id: Uuid) -> Result<Option<IntegrationConnector>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM integration_connectors WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+            CONNECTOR_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_connector).transpose()
+    }
+
+    async fn find_connector_by_shop(
+        &self,
+        provider: ConnectorProvider,
+        shop_domain: &str,
+    ) -> Result<Option<IntegrationConnector>, DomainError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM integration_connectors
+            WHERE provider = $1 AND shop_domain = $2 AND tenant_id = get_current_tenant_id()
+            "#,
+            CONNECTOR_COLUMNS
+        ))
+        .bind(provider.as_str())
+        .bind(shop_domain)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_connector).transpose()
+    }
+
+    async fn list_connectors(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<IntegrationConnector>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM integration_connectors
+            WHERE tenant_id = get_current_tenant_id()
+            ORDER BY name, id
+            LIMIT $1 OFFSET $2
+            "#,
+            CONNECTOR_COLUMNS
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_connector).collect()
+    }
+
+    async fn update_connector(&self, connector: &IntegrationConnector) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE integration_connectors
+            SET name = $2, location_id = $3, external_location_id = $4, customer_id = $5,
+                sync_interval_minutes = $6, active = $7, access_token = $8, token_scopes = $9,
+                token_obtained_at = $10, oauth_state = $11, inventory_cursor = $12,
+                next_sync_at = $13, updated_at = $14
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(connector.id)
+        .bind(&connector.name)
+        .bind(connector.location_id)
+        .bind(&connector.external_location_id)
+        .bind(connector.customer_id)
+        .bind(connector.sync_interval_minutes)
+        .bind(connector.active)
+        .bind(&connector.access_token)
+        .bind(&connector.token_scopes)
+        .bind(connector.token_obtained_at)
+        .bind(&connector.oauth_state)
+        .bind(connector.inventory_cursor)
+        .bind(connector.next_sync_at)
+        .bind(connector.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Integration connector {} not found", connector.id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn save_sync_state(&self, connector: &IntegrationConnector) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            UPDATE integration_connectors
+            SET orders_cursor = $2, inventory_cursor = $3, next_sync_at = $4
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(connector.id)
+        .bind(connector.orders_cursor)
+        .bind(connector.inventory_cursor)
+        .bind(connector.next_sync_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_connector(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "DELETE FROM integration_connectors WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+        )
+        .bind(id)
+        .execute(&*self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Connector {} not found", id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn claim_due_connectors(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<(Uuid, IntegrationConnector)>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            UPDATE integration_connectors
+            SET next_sync_at = NOW() + make_interval(mins => sync_interval_minutes)
+            WHERE id IN (
+                SELECT id
+                FROM integration_connectors
+                WHERE active AND access_token IS NOT NULL AND next_sync_at <= NOW()
+                ORDER BY next_sync_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING tenant_id, {}
+            "#,
+            CONNECTOR_COLUMNS
+        ))
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get("tenant_id")?, Self::row_to_connector(row)?)))
+            .collect()
+    }
+
+    async fn find_order_link(
+        &self,
+        connector_id: Uuid,
+        external_order_id: &str,
+    ) -> Result<Option<IntegrationOrderLink>, DomainError> {
+        let row = sqlx::query(
+            r#"
+            SELECT connector_id, external_order_id, sales_order_id, external_order_number
+            FROM integration_orders
+            WHERE connector_id = $1 AND external_order_id = $2 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(connector_id)
+        .bind(external_order_id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(IntegrationOrderLink {
+                connector_id: row.try_get("connector_id")?,
+                external_order_id: row.try_get("external_order_id")?,
+                sales_order_id: row.try_get("sales_order_id")?,
+                external_order_number: row.try_get("external_order_number")?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn create_order_link(&self, link: &IntegrationOrderLink) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO integration_orders (connector_id, external_order_id, sales_order_id,
+                                            external_order_number, tenant_id)
+            VALUES ($1, $2, $3, $4, get_current_tenant_id())
+            "#,
+        )
+        .bind(link.connector_id)
+        .bind(&link.external_order_id)
+        .bind(link.sales_order_id)
+        .bind(&link.external_order_number)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_location_stock(
+        &self,
+        location_id: Uuid,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LocationStock>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT i.sku,
+                   GREATEST(sl.quantity_on_hand - sl.quantity_quarantine - sl.quantity_damaged
+                            - sl.quantity_reserved, 0) AS available,
+                   sl.updated_at
+            FROM stock_levels sl
+            JOIN items i ON i.id = sl.item_id AND i.tenant_id = sl.tenant_id
+            WHERE sl.location_id = $1
+              AND sl.tenant_id = get_current_tenant_id()
+              AND ($2::timestamptz IS NULL OR sl.updated_at > $2)
+            ORDER BY sl.updated_at, i.sku
+            "#,
+        )
+        .bind(location_id)
+        .bind(since)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(LocationStock {
+                    sku: row.try_get("sku")?,
+                    available: row.try_get("available")?,
+                    updated_at: row.try_get("updated_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn create_sync_run(&self, run: &SyncRun) -> Result<(), DomainError> {
+        let errors = serde_json::to_value(&run.errors).map_err(|e| {
+            DomainError::InfrastructureError(
+                format!("Failed to serialize sync errors: {}", e).into(),
+            )
+        })?;
+        sqlx::query(
+            r#"
+            INSERT INTO integration_sync_runs (id, connector_id, kind, trigger, status, records_processed,
+                                               records_failed, errors, started_at, finished_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, get_current_tenant_id())
+            "#,
+        )
+        .bind(run.id)
+        .bind(run.connector_id)
+        .bind(run.kind.as_str())
+        .bind(run.trigger.as_str())
+        .bind(run.status.as_str())
+        .bind(run.records_processed)
+        .bind(run.records_failed)
+        .bind(errors)
+        .bind(run.started_at)
+        .bind(run.finished_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_sync_runs(
+        &self,
+        connector_id: Uuid,
+        kind: Option<SyncKind>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<SyncRun>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM integration_sync_runs
+            WHERE connector_id = $1
+              AND tenant_id = get_current_tenant_id()
+              AND ($2::text IS NULL OR kind = $2)
+            ORDER BY started_at DESC, id
+            LIMIT $3 OFFSET $4
+            "#,
+            SYNC_RUN_COLUMNS
+        ))
+        .bind(connector_id)
+        .bind(kind.map(|k| k.as_str()))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_sync_run).collect()
+    }
+}
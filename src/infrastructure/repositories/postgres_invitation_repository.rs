@@ -0,0 +1,95 @@
+use crate::domain::entities::invitation::Invitation;
+use crate::domain::entities::user::UserRole;
+use crate::domain::services::invitation_repository::InvitationRepository;
+use crate::domain::value_objects::email::Email;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+pub struct PostgresInvitationRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresInvitationRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl InvitationRepository for PostgresInvitationRepository {
+    async fn save(&self, invitation: &Invitation) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "DELETE FROM invitations WHERE tenant_id = $1 AND email = $2 AND accepted_at IS NULL",
+            invitation.tenant_id,
+            invitation.email.as_str()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO invitations (id, tenant_id, email, role, token_hash, expires_at, accepted_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            invitation.id,
+            invitation.tenant_id,
+            invitation.email.as_str(),
+            invitation.role.as_str(),
+            invitation.token_hash,
+            invitation.expires_at,
+            invitation.accepted_at,
+            invitation.created_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Invitation>, DomainError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, tenant_id, email, role, token_hash, expires_at, accepted_at, created_at
+            FROM invitations
+            WHERE token_hash = $1
+            "#,
+            token_hash
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(Invitation {
+                id: row.id,
+                tenant_id: row.tenant_id,
+                email: Email::new(row.email)?,
+                role: UserRole::from_str(&row.role)?,
+                token_hash: row.token_hash,
+                expires_at: row.expires_at,
+                accepted_at: row.accepted_at,
+                created_at: row.created_at,
+            })
+        })
+        .transpose()
+    }
+
+    async fn mark_accepted(&self, invitation: &Invitation) -> Result<bool, DomainError> {
+        let result = sqlx::query!(
+            "UPDATE invitations SET accepted_at = $2 WHERE id = $1 AND accepted_at IS NULL",
+            invitation.id,
+            invitation.accepted_at
+        )
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,325 @@
+use crate::domain::entities::invoice::{
+    Invoice, InvoiceStatus, Payment, PaymentMethod, RecordPaymentRequest,
+};
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::services::invoice_repository::InvoiceRepository;
+use crate::infrastructure::repositories::list_filter_sql::{push_filters, ListColumns, ListQuery};
+use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::{FromRow, PgPool, Postgres, QueryBuilder, Transaction};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const INVOICE_COLUMNS: &str = "id, invoice_number, so_id, customer_id, status, total_amount, \
+     amount_paid, currency, exchange_rate, issued_at, due_date, created_by, created_at, updated_at";
+
+const INVOICE_LIST_COLUMNS: ListColumns = ListColumns {
+    resource: "invoices",
+    id: "id",
+    status: Some("status"),
+    created_at: "created_at",
+    supplier_id: None,
+    customer_id: Some("customer_id"),
+    sku: None,
+    category: None,
+    search: &["invoice_number"],
+    sortable: &[
+        ("invoice_number", "invoice_number", "text"),
+        ("status", "status", "text"),
+        ("total_amount", "total_amount", "numeric"),
+        ("due_date", "due_date", "timestamptz"),
+        ("created_at", "created_at", "timestamptz"),
+        ("updated_at", "updated_at", "timestamptz"),
+    ],
+};
+
+pub struct PostgresInvoiceRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresInvoiceRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Load an invoice; `for_update` locks its row until the transaction ends
+    async fn find_with_tx(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        for_update: bool,
+    ) -> Result<Option<Invoice>, DomainError> {
+        let lock = if for_update { " FOR UPDATE" } else { "" };
+        let row = sqlx::query_as::<_, InvoiceRow>(&format!(
+            "SELECT {} FROM invoices WHERE id = $1 AND tenant_id = get_current_tenant_id(){}",
+            INVOICE_COLUMNS, lock
+        ))
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        row.map(InvoiceRow::into_invoice).transpose()
+    }
+}
+
+#[derive(FromRow)]
+struct InvoiceRow {
+    id: Uuid,
+    invoice_number: String,
+    so_id: Uuid,
+    customer_id: Option<Uuid>,
+    status: String,
+    total_amount: Decimal,
+    amount_paid: Decimal,
+    currency: Option<String>,
+    exchange_rate: Option<Decimal>,
+    issued_at: DateTime<Utc>,
+    due_date: DateTime<Utc>,
+    created_by: Uuid,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl InvoiceRow {
+    fn into_invoice(self) -> Result<Invoice, DomainError> {
+        Ok(Invoice {
+            id: self.id,
+            invoice_number: self.invoice_number,
+            so_id: self.so_id,
+            customer_id: self.customer_id,
+            status: InvoiceStatus::from_str(&self.status)?,
+            total_amount: self.total_amount,
+            amount_paid: self.amount_paid,
+            currency: self.currency,
+            exchange_rate: self.exchange_rate,
+            issued_at: self.issued_at,
+            due_date: self.due_date,
+            created_by: self.created_by,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        })
+    }
+}
+
+#[async_trait]
+impl InvoiceRepository for PostgresInvoiceRepository {
+    async fn create(&self, invoice: &Invoice) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Lock the order so it can't be invoiced twice or change status meanwhile
+        let so_status = sqlx::query_scalar!(
+            "SELECT status FROM sales_orders WHERE id = $1 AND tenant_id = get_current_tenant_id() FOR UPDATE",
+            invoice.so_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            DomainError::NotFound(format!("Sales order {} not found", invoice.so_id).into())
+        })?;
+        if so_status != "SHIPPED" {
+            return Err(DomainError::Conflict(
+                format!(
+                    "Sales order {} is {} and cannot be invoiced",
+                    invoice.so_id, so_status
+                )
+                .into(),
+            ));
+        }
+
+        let existing = sqlx::query_scalar!(
+            "SELECT invoice_number FROM invoices WHERE so_id = $1 AND tenant_id = get_current_tenant_id()",
+            invoice.so_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if let Some(invoice_number) = existing {
+            return Err(DomainError::Conflict(
+                format!(
+                    "Sales order {} is already invoiced as {}",
+                    invoice.so_id, invoice_number
+                )
+                .into(),
+            ));
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO invoices (id, invoice_number, so_id, customer_id, status, total_amount, amount_paid, currency, exchange_rate, issued_at, due_date, created_by, created_at, updated_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, get_current_tenant_id())
+            "#,
+            invoice.id,
+            invoice.invoice_number,
+            invoice.so_id,
+            invoice.customer_id,
+            invoice.status.as_str(),
+            invoice.total_amount,
+            invoice.amount_paid,
+            invoice.currency,
+            invoice.exchange_rate,
+            invoice.issued_at,
+            invoice.due_date,
+            invoice.created_by,
+            invoice.created_at,
+            invoice.updated_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE sales_orders
+            SET status = 'INVOICED', updated_at = $2
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+            invoice.so_id,
+            invoice.created_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Invoice>, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        self.find_with_tx(&mut tx, id, false).await
+    }
+
+    async fn payments(&self, invoice_id: Uuid) -> Result<Vec<Payment>, DomainError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, invoice_id, customer_id, amount, applied_amount, credit_amount, method,
+                   reference, paid_at, recorded_by, created_at
+            FROM payments
+            WHERE invoice_id = $1 AND tenant_id = get_current_tenant_id()
+            ORDER BY paid_at, created_at, id
+            "#,
+            invoice_id
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(Payment {
+                    id: row.id,
+                    invoice_id: row.invoice_id,
+                    customer_id: row.customer_id,
+                    amount: row.amount,
+                    applied_amount: row.applied_amount,
+                    credit_amount: row.credit_amount,
+                    method: PaymentMethod::from_str(&row.method)?,
+                    reference: row.reference,
+                    paid_at: row.paid_at,
+                    recorded_by: row.recorded_by,
+                    created_at: row.created_at,
+                })
+            })
+            .collect()
+    }
+
+    async fn list(
+        &self,
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<Invoice>, DomainError> {
+        let query = ListQuery::new(&INVOICE_LIST_COLUMNS, filter, page)?;
+        let mut builder = QueryBuilder::new(format!(
+            "SELECT {}, {} FROM invoices WHERE tenant_id = get_current_tenant_id()",
+            INVOICE_COLUMNS,
+            query.sort_key_column()
+        ));
+        query.push_tail(&mut builder)?;
+
+        let rows = builder.build().fetch_all(&*self.pool).await?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            let cursor = query.cursor_for(&row)?;
+            results.push((cursor, InvoiceRow::from_row(&row)?.into_invoice()?));
+        }
+
+        Ok(query.page(results))
+    }
+
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError> {
+        let mut builder = QueryBuilder::new(
+            "SELECT COUNT(*) FROM invoices WHERE tenant_id = get_current_tenant_id()",
+        );
+        push_filters(&mut builder, filter, &INVOICE_LIST_COLUMNS)?;
+
+        Ok(builder.build_query_scalar().fetch_one(&*self.pool).await?)
+    }
+
+    async fn record_payment(
+        &self,
+        invoice_id: Uuid,
+        request: &RecordPaymentRequest,
+        recorded_by: Uuid,
+    ) -> Result<(Invoice, Payment), DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut invoice = self
+            .find_with_tx(&mut tx, invoice_id, true)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Invoice {} not found", invoice_id).into())
+            })?;
+
+        let payment = invoice.record_payment(request, recorded_by)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO payments (id, invoice_id, customer_id, amount, applied_amount, credit_amount, method, reference, paid_at, recorded_by, created_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, get_current_tenant_id())
+            "#,
+            payment.id,
+            payment.invoice_id,
+            payment.customer_id,
+            payment.amount,
+            payment.applied_amount,
+            payment.credit_amount,
+            payment.method.as_str(),
+            payment.reference,
+            payment.paid_at,
+            payment.recorded_by,
+            payment.created_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE invoices
+            SET status = $2, amount_paid = $3, updated_at = $4
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+            invoice.id,
+            invoice.status.as_str(),
+            invoice.amount_paid,
+            invoice.updated_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok((invoice, payment))
+    }
+
+    async fn open_invoices(&self) -> Result<Vec<Invoice>, DomainError> {
+        let rows = sqlx::query_as::<_, InvoiceRow>(&format!(
+            "SELECT {} FROM invoices \
+             WHERE status <> 'PAID' AND tenant_id = get_current_tenant_id() \
+             ORDER BY due_date, id",
+            INVOICE_COLUMNS
+        ))
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.into_iter().map(InvoiceRow::into_invoice).collect()
+    }
+}
@@ -0,0 +1,257 @@
+use crate::domain::entities::item_availability::{
+    AvailabilityRebuild, ItemAvailability, LocationAvailability,
+};
+use crate::domain::services::item_availability_repository::ItemAvailabilityRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Purchase order statuses whose unreceived quantities count as on order
+const ON_ORDER_STATUSES: [&str; 3] = ["OPEN", "RECEIVING", "PARTIAL_RECEIVED"];
+
+/// Upserts the read model rows of the given stock levels from the levels and
+/// their open allocations; the caller binds the item and location arrays
+const REFRESH_AVAILABILITY_SQL: &str = r#"
+    INSERT INTO item_availability (
+        item_id, location_id, tenant_id, quantity_on_hand, quantity_unavailable,
+        quantity_reserved, quantity_allocated, quantity_in_transit, updated_at
+    )
+    SELECT sl.item_id, sl.location_id, sl.tenant_id, sl.quantity_on_hand,
+           sl.quantity_quarantine + sl.quantity_damaged, sl.quantity_reserved,
+           COALESCE((
+               SELECT SUM(a.qty_allocated - a.qty_shipped)
+               FROM so_allocations a
+               WHERE a.item_id = sl.item_id AND a.location_id = sl.location_id
+                 AND a.tenant_id = sl.tenant_id
+           ), 0),
+           sl.quantity_in_transit, NOW()
+    FROM stock_levels sl
+    WHERE (sl.item_id, sl.location_id) IN (SELECT * FROM UNNEST($1::uuid[], $2::uuid[]))
+      AND sl.tenant_id = get_current_tenant_id()
+    ORDER BY sl.item_id, sl.location_id
+    ON CONFLICT (item_id, location_id) DO UPDATE SET
+        quantity_on_hand = EXCLUDED.quantity_on_hand,
+        quantity_unavailable = EXCLUDED.quantity_unavailable,
+        quantity_reserved = EXCLUDED.quantity_reserved,
+        quantity_allocated = EXCLUDED.quantity_allocated,
+        quantity_in_transit = EXCLUDED.quantity_in_transit,
+        updated_at = EXCLUDED.updated_at
+"#;
+
+pub struct PostgresItemAvailabilityRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresItemAvailabilityRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Bring the read model rows of these stock levels up to date inside the
+    /// writer's transaction. The levels are locked in key order first, so the
+    /// recount sees every write committed ahead of it and two refreshes of
+    /// the same level can't overwrite each other with stale totals.
+    pub(crate) async fn refresh_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        keys: &[(Uuid, Uuid)],
+    ) -> Result<(), DomainError> {
+        let mut keys = keys.to_vec();
+        keys.sort();
+        keys.dedup();
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let (item_ids, location_ids): (Vec<Uuid>, Vec<Uuid>) = keys.into_iter().unzip();
+
+        sqlx::query(
+            r#"
+            SELECT 1 FROM stock_levels
+            WHERE (item_id, location_id) IN (SELECT * FROM UNNEST($1::uuid[], $2::uuid[]))
+              AND tenant_id = get_current_tenant_id()
+            ORDER BY item_id, location_id
+            FOR UPDATE
+            "#,
+        )
+        .bind(&item_ids)
+        .bind(&location_ids)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(REFRESH_AVAILABILITY_SQL)
+            .bind(&item_ids)
+            .bind(&location_ids)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// Recount what these items have on order inside the writer's
+    /// transaction, locking their rows first for the same reason as
+    /// [`Self::refresh_in_tx`]
+    pub(crate) async fn refresh_on_order_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        item_ids: &[Uuid],
+    ) -> Result<(), DomainError> {
+        let mut item_ids = item_ids.to_vec();
+        item_ids.sort();
+        item_ids.dedup();
+        if item_ids.is_empty() {
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO item_on_order (item_id, tenant_id)
+            SELECT i.item_id, get_current_tenant_id()
+            FROM UNNEST($1::uuid[]) AS i(item_id)
+            ORDER BY i.item_id
+            ON CONFLICT (item_id) DO NOTHING
+            "#,
+        )
+        .bind(&item_ids)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            SELECT 1 FROM item_on_order
+            WHERE item_id = ANY($1) AND tenant_id = get_current_tenant_id()
+            ORDER BY item_id
+            FOR UPDATE
+            "#,
+        )
+        .bind(&item_ids)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE item_on_order o
+            SET quantity_on_order = COALESCE((
+                    SELECT SUM(l.qty_ordered - l.qty_received)
+                    FROM purchase_order_lines l
+                    JOIN purchase_orders po ON po.id = l.po_id
+                    WHERE l.item_id = o.item_id AND po.status = ANY($2)
+                      AND l.tenant_id = get_current_tenant_id()
+                ), 0),
+                updated_at = NOW()
+            WHERE o.item_id = ANY($1) AND o.tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(&item_ids)
+        .bind(&ON_ORDER_STATUSES[..])
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ItemAvailabilityRepository for PostgresItemAvailabilityRepository {
+    async fn find_by_item(&self, item_id: Uuid) -> Result<ItemAvailability, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT location_id, quantity_on_hand, quantity_unavailable, quantity_reserved,
+                   quantity_allocated, quantity_in_transit, updated_at
+            FROM item_availability
+            WHERE item_id = $1 AND tenant_id = get_current_tenant_id()
+            ORDER BY location_id
+            "#,
+        )
+        .bind(item_id)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        let locations = rows
+            .iter()
+            .map(|row| {
+                let on_hand: Decimal = row.try_get("quantity_on_hand")?;
+                let unavailable: Decimal = row.try_get("quantity_unavailable")?;
+                let reserved: Decimal = row.try_get("quantity_reserved")?;
+                let allocated: Decimal = row.try_get("quantity_allocated")?;
+                Ok(LocationAvailability {
+                    location_id: row.try_get("location_id")?,
+                    quantity_on_hand: on_hand,
+                    quantity_unavailable: unavailable,
+                    quantity_reserved: reserved,
+                    quantity_allocated: allocated,
+                    quantity_in_transit: row.try_get("quantity_in_transit")?,
+                    quantity_available: LocationAvailability::available(
+                        on_hand,
+                        unavailable,
+                        reserved,
+                        allocated,
+                    ),
+                    updated_at: row.try_get("updated_at")?,
+                })
+            })
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        let on_order: Option<Decimal> = sqlx::query_scalar(
+            "SELECT quantity_on_order FROM item_on_order WHERE item_id = $1 AND tenant_id = get_current_tenant_id()",
+        )
+        .bind(item_id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        Ok(ItemAvailability::new(
+            item_id,
+            locations,
+            on_order.unwrap_or_default(),
+        ))
+    }
+
+    async fn rebuild(&self) -> Result<AvailabilityRebuild, DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM item_availability WHERE tenant_id = get_current_tenant_id()")
+            .execute(&mut *tx)
+            .await?;
+        let (item_ids, location_ids): (Vec<Uuid>, Vec<Uuid>) = sqlx::query(
+            "SELECT item_id, location_id FROM stock_levels WHERE tenant_id = get_current_tenant_id()",
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .iter()
+        .map(|row| Ok((row.try_get("item_id")?, row.try_get("location_id")?)))
+        .collect::<Result<Vec<(Uuid, Uuid)>, DomainError>>()?
+        .into_iter()
+        .unzip();
+        let locations = sqlx::query(REFRESH_AVAILABILITY_SQL)
+            .bind(&item_ids)
+            .bind(&location_ids)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        sqlx::query("DELETE FROM item_on_order WHERE tenant_id = get_current_tenant_id()")
+            .execute(&mut *tx)
+            .await?;
+        let on_order_items = sqlx::query(
+            r#"
+            INSERT INTO item_on_order (item_id, tenant_id, quantity_on_order, updated_at)
+            SELECT l.item_id, get_current_tenant_id(), SUM(l.qty_ordered - l.qty_received), NOW()
+            FROM purchase_order_lines l
+            JOIN purchase_orders po ON po.id = l.po_id
+            WHERE po.status = ANY($1) AND l.tenant_id = get_current_tenant_id()
+            GROUP BY l.item_id
+            ON CONFLICT (item_id) DO UPDATE SET
+                quantity_on_order = EXCLUDED.quantity_on_order,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(&ON_ORDER_STATUSES[..])
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        tx.commit().await?;
+        Ok(AvailabilityRebuild {
+            locations,
+            on_order_items,
+        })
+    }
+}
@@ -0,0 +1,374 @@
+use crate::domain::entities::item::Item;
+use crate::domain::entities::item_duplicate::{
+    DuplicateItemPair, DuplicateItemRef, DuplicateReason, ItemMergeCounts,
+};
+use crate::domain::services::item_merge_repository::ItemMergeRepository;
+use crate::infrastructure::repositories::postgres_item_availability_repository::PostgresItemAvailabilityRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::{FromRow, PgPool, Postgres, Transaction};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Lines of purchase, sales, transfer and return documents naming an item
+const ORDER_LINE_TABLES: &[&str] = &[
+    "purchase_order_lines",
+    "purchase_order_receipt_lines",
+    "sales_order_lines",
+    "so_allocations",
+    "transfer_lines",
+    "return_lines",
+    "vendor_return_lines",
+    "inbound_carton_lines",
+];
+
+/// Everything else that points at an item and carries over as it is
+const OTHER_ITEM_TABLES: &[&str] = &[
+    "stock_adjustments",
+    "cycle_count_lines",
+    "item_attachments",
+    "putaway_rules",
+];
+
+/// A barcode as compared for duplicates: alphanumerics only, uppercased,
+/// without leading zeros, so a UPC-A matches the EAN-13 it is printed as
+const NORMALIZED_BARCODE_SQL: &str =
+    "NULLIF(upper(ltrim(regexp_replace(COALESCE({}.barcode, ''), '[^0-9A-Za-z]', '', 'g'), '0')), '')";
+
+pub struct PostgresItemMergeRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresItemMergeRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Add the duplicate's rows of `table` to the survivor's rows with the same
+    /// `key` columns, then hand the rest over. Returns how many of the
+    /// duplicate's rows there were.
+    async fn combine_rows(
+        tx: &mut Transaction<'_, Postgres>,
+        table: &str,
+        key: &[&str],
+        quantities: &[&str],
+        duplicate_id: Uuid,
+        survivor_id: Uuid,
+    ) -> Result<u64, DomainError> {
+        let same_key = key
+            .iter()
+            .map(|column| format!("s.{column} = d.{column}"))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        let sums = quantities
+            .iter()
+            .map(|column| format!("{column} = s.{column} + d.{column}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        sqlx::query(&format!(
+            r#"
+            UPDATE {table} s SET {sums}
+            FROM {table} d
+            WHERE s.item_id = $2 AND d.item_id = $1 AND {same_key}
+              AND d.tenant_id = get_current_tenant_id() AND s.tenant_id = d.tenant_id
+            "#
+        ))
+        .bind(duplicate_id)
+        .bind(survivor_id)
+        .execute(&mut **tx)
+        .await?;
+
+        let combined = sqlx::query(&format!(
+            r#"
+            DELETE FROM {table} d
+            WHERE d.item_id = $1 AND d.tenant_id = get_current_tenant_id()
+              AND EXISTS (
+                  SELECT 1 FROM {table} s
+                  WHERE s.item_id = $2 AND {same_key} AND s.tenant_id = d.tenant_id
+              )
+            "#
+        ))
+        .bind(duplicate_id)
+        .bind(survivor_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+        let moved = Self::repoint(tx, table, duplicate_id, survivor_id).await?;
+        Ok(combined + moved)
+    }
+
+    /// Point the duplicate's rows of `table` at the survivor. Both items were
+    /// checked to be the current tenant's, so the item is filter enough.
+    async fn repoint(
+        tx: &mut Transaction<'_, Postgres>,
+        table: &str,
+        duplicate_id: Uuid,
+        survivor_id: Uuid,
+    ) -> Result<u64, DomainError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {table} SET item_id = $2 WHERE item_id = $1"
+        ))
+        .bind(duplicate_id)
+        .bind(survivor_id)
+        .execute(&mut **tx)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn save_item(tx: &mut Transaction<'_, Postgres>, item: &Item) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            UPDATE items SET barcode = $2, active = $3, updated_at = $4
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(item.id)
+        .bind(&item.barcode)
+        .bind(item.active)
+        .bind(item.updated_at)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
+
+#[derive(FromRow)]
+struct DuplicateRow {
+    item_id: Uuid,
+    item_sku: String,
+    item_name: String,
+    item_barcode: Option<String>,
+    duplicate_id: Uuid,
+    duplicate_sku: String,
+    duplicate_name: String,
+    duplicate_barcode: Option<String>,
+    name_similarity: f32,
+}
+
+#[async_trait]
+impl ItemMergeRepository for PostgresItemMergeRepository {
+    async fn find_duplicates(
+        &self,
+        min_similarity: f64,
+        limit: i64,
+    ) -> Result<Vec<DuplicateItemPair>, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        // Lets the name match below use the trigram index
+        sqlx::query("SELECT set_config('pg_trgm.similarity_threshold', $1::float8::text, true)")
+            .bind(min_similarity)
+            .execute(&mut *tx)
+            .await?;
+
+        let columns = r#"
+            a.id AS item_id, a.sku AS item_sku, a.name AS item_name, a.barcode AS item_barcode,
+            b.id AS duplicate_id, b.sku AS duplicate_sku, b.name AS duplicate_name,
+            b.barcode AS duplicate_barcode,
+            similarity(lower(a.name), lower(b.name)) AS name_similarity
+        "#;
+        let by_name = sqlx::query_as::<_, DuplicateRow>(&format!(
+            r#"
+            SELECT {columns}
+            FROM items a
+            JOIN items b ON lower(a.name) % lower(b.name) AND a.id < b.id
+            WHERE a.tenant_id = get_current_tenant_id() AND b.tenant_id = a.tenant_id
+              AND a.active AND b.active
+            ORDER BY name_similarity DESC, a.sku, b.sku
+            LIMIT $1
+            "#
+        ))
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let by_barcode = sqlx::query_as::<_, DuplicateRow>(&format!(
+            r#"
+            SELECT {columns}
+            FROM items a
+            JOIN items b ON {} = {} AND a.id < b.id
+            WHERE a.tenant_id = get_current_tenant_id() AND b.tenant_id = a.tenant_id
+              AND a.active AND b.active
+            ORDER BY a.sku, b.sku
+            LIMIT $1
+            "#,
+            NORMALIZED_BARCODE_SQL.replace("{}", "a"),
+            NORMALIZED_BARCODE_SQL.replace("{}", "b"),
+        ))
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        let mut pairs: BTreeMap<(Uuid, Uuid), DuplicateItemPair> = BTreeMap::new();
+        for (rows, reason) in [
+            (by_name, DuplicateReason::SimilarName),
+            (by_barcode, DuplicateReason::SameBarcode),
+        ] {
+            for row in rows {
+                pairs
+                    .entry((row.item_id, row.duplicate_id))
+                    .or_insert_with(|| DuplicateItemPair {
+                        item: DuplicateItemRef {
+                            id: row.item_id,
+                            sku: row.item_sku,
+                            name: row.item_name,
+                            barcode: row.item_barcode,
+                        },
+                        duplicate: DuplicateItemRef {
+                            id: row.duplicate_id,
+                            sku: row.duplicate_sku,
+                            name: row.duplicate_name,
+                            barcode: row.duplicate_barcode,
+                        },
+                        reasons: Vec::new(),
+                        name_similarity: f64::from(row.name_similarity),
+                    })
+                    .reasons
+                    .push(reason);
+            }
+        }
+
+        let mut pairs: Vec<DuplicateItemPair> = pairs.into_values().collect();
+        pairs.sort_by(|a, b| {
+            b.reasons
+                .len()
+                .cmp(&a.reasons.len())
+                .then(b.name_similarity.total_cmp(&a.name_similarity))
+                .then_with(|| a.item.sku.cmp(&b.item.sku))
+        });
+        pairs.truncate(limit.max(0) as usize);
+        Ok(pairs)
+    }
+
+    async fn merge(
+        &self,
+        duplicate: &Item,
+        survivor: &Item,
+    ) -> Result<ItemMergeCounts, DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Lock both items in ID order so concurrent merges of the pair can't deadlock
+        let mut ids = [duplicate.id, survivor.id];
+        ids.sort();
+        let locked: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM items
+            WHERE id = ANY($1) AND tenant_id = get_current_tenant_id()
+            ORDER BY id
+            FOR UPDATE
+            "#,
+        )
+        .bind(&ids[..])
+        .fetch_all(&mut *tx)
+        .await?;
+        if locked.len() != 2 {
+            return Err(DomainError::NotFound(
+                "One of the items to merge no longer exists".into(),
+            ));
+        }
+
+        // A count sheet holds each item once, so it can't hold both
+        let shared_count: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT cc.count_number
+            FROM cycle_count_lines d
+            JOIN cycle_count_lines s ON s.cycle_count_id = d.cycle_count_id
+            JOIN cycle_counts cc ON cc.id = d.cycle_count_id
+            WHERE d.item_id = $1 AND s.item_id = $2
+            LIMIT 1
+            "#,
+        )
+        .bind(duplicate.id)
+        .bind(survivor.id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if let Some(count_number) = shared_count {
+            return Err(DomainError::Conflict(
+                format!(
+                    "Cycle count {} counts both {} and {}, so they can't be merged",
+                    count_number, duplicate.sku, survivor.sku
+                )
+                .into(),
+            ));
+        }
+
+        let locations: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT location_id FROM stock_levels
+            WHERE item_id = ANY($1) AND tenant_id = get_current_tenant_id()
+            ORDER BY item_id, location_id
+            FOR UPDATE
+            "#,
+        )
+        .bind(&ids[..])
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut counts = ItemMergeCounts {
+            stock_levels: Self::combine_rows(
+                &mut tx,
+                "stock_levels",
+                &["location_id"],
+                &[
+                    "quantity_on_hand",
+                    "quantity_reserved",
+                    "quantity_quarantine",
+                    "quantity_damaged",
+                    "quantity_in_transit",
+                ],
+                duplicate.id,
+                survivor.id,
+            )
+            .await?,
+            stock_movements: Self::repoint(&mut tx, "stock_movements", duplicate.id, survivor.id)
+                .await?,
+            ..Default::default()
+        };
+        // Past snapshots keep answering "what was on hand then" for the survivor
+        counts.other_rows += Self::combine_rows(
+            &mut tx,
+            "stock_snapshots",
+            &["snapshot_date", "location_id"],
+            &["quantity_on_hand", "quantity_reserved"],
+            duplicate.id,
+            survivor.id,
+        )
+        .await?;
+        for table in ORDER_LINE_TABLES {
+            counts.order_lines += Self::repoint(&mut tx, table, duplicate.id, survivor.id).await?;
+        }
+        for table in OTHER_ITEM_TABLES {
+            counts.other_rows += Self::repoint(&mut tx, table, duplicate.id, survivor.id).await?;
+        }
+
+        // Forecasts are recomputed from the merged history on their next run,
+        // and the read model is rebuilt for the survivor below
+        sqlx::query("DELETE FROM demand_forecasts WHERE item_id = $1")
+            .bind(duplicate.id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM item_availability WHERE item_id = $1")
+            .bind(duplicate.id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM item_on_order WHERE item_id = $1")
+            .bind(duplicate.id)
+            .execute(&mut *tx)
+            .await?;
+        let keys: Vec<(Uuid, Uuid)> = locations
+            .into_iter()
+            .map(|location_id| (survivor.id, location_id))
+            .collect();
+        PostgresItemAvailabilityRepository::refresh_in_tx(&mut tx, &keys).await?;
+        PostgresItemAvailabilityRepository::refresh_on_order_in_tx(&mut tx, &[survivor.id]).await?;
+
+        // The duplicate gives up its barcode before the survivor can take it
+        Self::save_item(&mut tx, duplicate).await?;
+        Self::save_item(&mut tx, survivor).await?;
+
+        tx.commit().await?;
+        Ok(counts)
+    }
+}
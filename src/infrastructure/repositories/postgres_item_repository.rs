@@ -1,11 +1,36 @@
 use crate::domain::entities::item::Item;
+use crate::domain::entities::list_filter::ListFilter;
 use crate::domain::services::item_repository::ItemRepository;
+use crate::infrastructure::repositories::list_filter_sql::{push_filters, ListColumns, ListQuery};
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgPool, QueryBuilder, Row};
 use std::sync::Arc;
 use uuid::Uuid;
 
+const ITEM_LIST_COLUMNS: ListColumns = ListColumns {
+    resource: "items",
+    id: "id",
+    status: Some("CASE WHEN active THEN 'ACTIVE' ELSE 'INACTIVE' END"),
+    created_at: "created_at",
+    supplier_id: None,
+    customer_id: None,
+    sku: Some("sku"),
+    category: Some("category"),
+    search: &["sku", "name", "description", "barcode"],
+    sortable: &[
+        ("sku", "sku", "text"),
+        ("name", "name", "text"),
+        ("category", "category", "text"),
+        ("cost_price", "cost_price", "numeric"),
+        ("sale_price", "sale_price", "numeric"),
+        ("created_at", "created_at", "timestamptz"),
+        ("updated_at", "updated_at", "timestamptz"),
+    ],
+};
+
 pub struct PostgresItemRepository {
     pool: Arc<PgPool>,
 }
@@ -16,13 +41,29 @@ impl PostgresItemRepository {
     }
 }
 
+/// A save that lost a race with another writer of the same SKU or barcode
+/// fails on the unique index; report it as the same conflict the use cases do
+fn write_error(item: &Item, e: sqlx::Error) -> DomainError {
+    match e.as_database_error().and_then(|db| db.constraint()) {
+        Some("items_tenant_sku_key") => DomainError::Conflict(Message::localized(
+            "error.item_sku_exists",
+            &[("sku", &item.sku)],
+        )),
+        Some("idx_items_tenant_barcode") => DomainError::Conflict(Message::localized(
+            "error.item_barcode_exists",
+            &[("barcode", &item.barcode.as_deref().unwrap_or_default())],
+        )),
+        _ => DomainError::ValidationError(format!("Database error: {}", e).into()),
+    }
+}
+
 #[async_trait]
 impl ItemRepository for PostgresItemRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Item>, DomainError> {
-        let result = sqlx::query!("SELECT items.id, sku, name, description, category, unit, barcode, cost_price, sale_price, reorder_point, reorder_qty, weight, dimensions, metadata, items.tenant_id, active, created_at, updated_at FROM items WHERE items.id = $1 AND items.tenant_id = get_current_tenant_id()", id)
+        let result = sqlx::query!("SELECT items.id, sku, name, description, category, unit, barcode, cost_price, sale_price, reorder_point, reorder_qty, quantity_precision, weight, dimensions, metadata, product_id, variant_attributes, items.tenant_id, active, created_at, updated_at FROM items WHERE items.id = $1 AND items.tenant_id = get_current_tenant_id()", id)
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         match result {
             Some(row) => {
@@ -43,9 +84,14 @@ impl ItemRepository for PostgresItemRepository {
                     sale_price: row.sale_price,
                     reorder_point: row.reorder_point,
                     reorder_qty: row.reorder_qty,
+                    quantity_precision: row.quantity_precision as u32,
                     weight: row.weight,
                     dimensions,
                     metadata: row.metadata,
+                    product_id: row.product_id,
+                    variant_attributes: row
+                        .variant_attributes
+                        .map(|v| serde_json::from_value(v).unwrap_or_default()),
                     active: row.active,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
@@ -55,11 +101,49 @@ impl ItemRepository for PostgresItemRepository {
         }
     }
 
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Item>, DomainError> {
+        let rows = sqlx::query!("SELECT items.id, sku, name, description, category, unit, barcode, cost_price, sale_price, reorder_point, reorder_qty, quantity_precision, weight, dimensions, metadata, product_id, variant_attributes, items.tenant_id, active, created_at, updated_at FROM items WHERE items.id = ANY($1) AND items.tenant_id = get_current_tenant_id()", ids)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Item {
+                id: row.id,
+                tenant_id: row.tenant_id,
+                sku: row.sku,
+                name: row.name,
+                description: row.description,
+                category: row.category,
+                unit: row.unit,
+                barcode: row.barcode,
+                cost_price: row.cost_price,
+                sale_price: row.sale_price,
+                reorder_point: row.reorder_point,
+                reorder_qty: row.reorder_qty,
+                quantity_precision: row.quantity_precision as u32,
+                weight: row.weight,
+                dimensions: row
+                    .dimensions
+                    .map(|d| serde_json::from_value(d).unwrap_or_default()),
+                metadata: row.metadata,
+                product_id: row.product_id,
+                variant_attributes: row
+                    .variant_attributes
+                    .map(|v| serde_json::from_value(v).unwrap_or_default()),
+                active: row.active,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+            .collect())
+    }
+
     async fn find_by_sku(&self, sku: &str) -> Result<Option<Item>, DomainError> {
-        let result = sqlx::query!("SELECT items.id, sku, name, description, category, unit, barcode, cost_price, sale_price, reorder_point, reorder_qty, weight, dimensions, metadata, items.tenant_id, active, created_at, updated_at FROM items WHERE sku = $1 AND items.tenant_id = get_current_tenant_id()", sku)
+        let result = sqlx::query!("SELECT items.id, sku, name, description, category, unit, barcode, cost_price, sale_price, reorder_point, reorder_qty, quantity_precision, weight, dimensions, metadata, product_id, variant_attributes, items.tenant_id, active, created_at, updated_at FROM items WHERE sku = $1 AND items.tenant_id = get_current_tenant_id()", sku)
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         match result {
             Some(row) => {
@@ -80,9 +164,14 @@ impl ItemRepository for PostgresItemRepository {
                     sale_price: row.sale_price,
                     reorder_point: row.reorder_point,
                     reorder_qty: row.reorder_qty,
+                    quantity_precision: row.quantity_precision as u32,
                     weight: row.weight,
                     dimensions,
                     metadata: row.metadata,
+                    product_id: row.product_id,
+                    variant_attributes: row
+                        .variant_attributes
+                        .map(|v| serde_json::from_value(v).unwrap_or_default()),
                     active: row.active,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
@@ -92,31 +181,64 @@ impl ItemRepository for PostgresItemRepository {
         }
     }
 
-    async fn save(&self, item: &Item) -> Result<(), DomainError> {
-        // Get a connection from the pool
-        let mut conn = self.pool.acquire().await.map_err(|e| {
-            DomainError::ValidationError(format!("Failed to acquire connection: {}", e))
-        })?;
-
-        // Set tenant context on this connection
-        sqlx::query("SELECT set_tenant_context($1)")
-            .bind(item.tenant_id)
-            .execute(&mut *conn)
-            .await
-            .map_err(|e| {
-                DomainError::ValidationError(format!("Failed to set tenant context: {}", e))
-            })?;
+    async fn find_by_barcode(&self, barcode: &str) -> Result<Option<Item>, DomainError> {
+        let result = sqlx::query!("SELECT items.id, sku, name, description, category, unit, barcode, cost_price, sale_price, reorder_point, reorder_qty, quantity_precision, weight, dimensions, metadata, product_id, variant_attributes, items.tenant_id, active, created_at, updated_at FROM items WHERE barcode = $1 AND items.tenant_id = get_current_tenant_id()", barcode)
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
+        match result {
+            Some(row) => {
+                let dimensions = row
+                    .dimensions
+                    .map(|d| serde_json::from_value(d).unwrap_or_default());
+
+                Ok(Some(Item {
+                    id: row.id,
+                    tenant_id: row.tenant_id,
+                    sku: row.sku,
+                    name: row.name,
+                    description: row.description,
+                    category: row.category,
+                    unit: row.unit,
+                    barcode: row.barcode,
+                    cost_price: row.cost_price,
+                    sale_price: row.sale_price,
+                    reorder_point: row.reorder_point,
+                    reorder_qty: row.reorder_qty,
+                    quantity_precision: row.quantity_precision as u32,
+                    weight: row.weight,
+                    dimensions,
+                    metadata: row.metadata,
+                    product_id: row.product_id,
+                    variant_attributes: row
+                        .variant_attributes
+                        .map(|v| serde_json::from_value(v).unwrap_or_default()),
+                    active: row.active,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn save(&self, item: &Item) -> Result<(), DomainError> {
         let dimensions_json = item
             .dimensions
             .as_ref()
             .map(|d| serde_json::to_value(d).unwrap_or(serde_json::Value::Null));
+        let variant_attributes_json = item
+            .variant_attributes
+            .as_ref()
+            .map(|a| serde_json::to_value(a).unwrap_or(serde_json::Value::Null));
 
         sqlx::query!(
             r#"
             INSERT INTO items (id, sku, name, description, category, unit, barcode, cost_price, sale_price,
-                              reorder_point, reorder_qty, weight, dimensions, metadata, tenant_id, active, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
+                              reorder_point, reorder_qty, weight, dimensions, metadata, tenant_id, active, created_at, updated_at,
+                              product_id, variant_attributes, quantity_precision)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21)
             "#,
             item.id,
             item.sku,
@@ -135,11 +257,14 @@ impl ItemRepository for PostgresItemRepository {
             item.tenant_id,
             item.active,
             item.created_at,
-            item.updated_at
+            item.updated_at,
+            item.product_id,
+            variant_attributes_json,
+            item.quantity_precision as i16
         )
-        .execute(&mut *conn)
+        .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| write_error(item, e))?;
 
         Ok(())
     }
@@ -149,13 +274,18 @@ impl ItemRepository for PostgresItemRepository {
             .dimensions
             .as_ref()
             .map(|d| serde_json::to_value(d).unwrap_or(serde_json::Value::Null));
+        let variant_attributes_json = item
+            .variant_attributes
+            .as_ref()
+            .map(|a| serde_json::to_value(a).unwrap_or(serde_json::Value::Null));
 
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             UPDATE items
             SET sku = $2, name = $3, description = $4, category = $5, unit = $6, barcode = $7,
                 cost_price = $8, sale_price = $9, reorder_point = $10, reorder_qty = $11,
-                weight = $12, dimensions = $13, metadata = $14, active = $15, updated_at = $16
+                weight = $12, dimensions = $13, metadata = $14, active = $15, updated_at = $16,
+                product_id = $17, variant_attributes = $18, quantity_precision = $19
             WHERE id = $1 AND items.tenant_id = get_current_tenant_id()
             "#,
             item.id,
@@ -173,17 +303,26 @@ impl ItemRepository for PostgresItemRepository {
             dimensions_json,
             item.metadata,
             item.active,
-            item.updated_at
+            item.updated_at,
+            item.product_id,
+            variant_attributes_json,
+            item.quantity_precision as i16
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| write_error(item, e))?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(Message::localized(
+                "error.item_not_found",
+                &[("id", &item.id)],
+            )));
+        }
         Ok(())
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             DELETE FROM items WHERE id = $1 AND items.tenant_id = get_current_tenant_id()
             "#,
@@ -191,35 +330,86 @@ impl ItemRepository for PostgresItemRepository {
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(Message::localized(
+                "error.item_not_found",
+                &[("id", &id)],
+            )));
+        }
         Ok(())
     }
 
-    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Item>, DomainError> {
-        let rows = sqlx::query!(
+    async fn list(
+        &self,
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<Item>, DomainError> {
+        let query = ListQuery::new(&ITEM_LIST_COLUMNS, filter, page)?;
+        let mut builder = QueryBuilder::new(format!(
             r#"
             SELECT id, sku, name, description, category, unit, barcode, cost_price, sale_price,
-                   reorder_point, reorder_qty, weight, dimensions, metadata, items.tenant_id, active, created_at, updated_at
+                   reorder_point, reorder_qty, quantity_precision, weight, dimensions, metadata, product_id, variant_attributes, items.tenant_id, active, created_at, updated_at,
+                   {}
             FROM items
             WHERE items.tenant_id = get_current_tenant_id()
-            ORDER BY created_at DESC
-            LIMIT $1 OFFSET $2
             "#,
-            limit,
-            offset
-        )
+            query.sort_key_column()
+        ));
+        query.push_tail(&mut builder)?;
+
+        let rows =
+            builder.build().fetch_all(&*self.pool).await.map_err(|e| {
+                DomainError::ValidationError(format!("Database error: {}", e).into())
+            })?;
+
+        let items = rows
+            .iter()
+            .map(|row| {
+                let dimensions: Option<serde_json::Value> = row.try_get("dimensions")?;
+                let variant_attributes: Option<serde_json::Value> =
+                    row.try_get("variant_attributes")?;
+                let item = Item {
+                    id: row.try_get("id")?,
+                    tenant_id: row.try_get("tenant_id")?,
+                    sku: row.try_get("sku")?,
+                    name: row.try_get("name")?,
+                    description: row.try_get("description")?,
+                    category: row.try_get("category")?,
+                    unit: row.try_get("unit")?,
+                    barcode: row.try_get("barcode")?,
+                    cost_price: row.try_get("cost_price")?,
+                    sale_price: row.try_get("sale_price")?,
+                    reorder_point: row.try_get("reorder_point")?,
+                    reorder_qty: row.try_get("reorder_qty")?,
+                    quantity_precision: row.try_get::<i16, _>("quantity_precision")? as u32,
+                    weight: row.try_get("weight")?,
+                    dimensions: dimensions.map(|d| serde_json::from_value(d).unwrap_or_default()),
+                    metadata: row.try_get("metadata")?,
+                    product_id: row.try_get("product_id")?,
+                    variant_attributes: variant_attributes
+                        .map(|v| serde_json::from_value(v).unwrap_or_default()),
+                    active: row.try_get("active")?,
+                    created_at: row.try_get("created_at")?,
+                    updated_at: row.try_get("updated_at")?,
+                };
+                Ok((query.cursor_for(row)?, item))
+            })
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        Ok(query.page(items))
+    }
+
+    async fn list_by_product(&self, product_id: Uuid) -> Result<Vec<Item>, DomainError> {
+        let rows = sqlx::query!("SELECT items.id, sku, name, description, category, unit, barcode, cost_price, sale_price, reorder_point, reorder_qty, quantity_precision, weight, dimensions, metadata, product_id, variant_attributes, items.tenant_id, active, created_at, updated_at FROM items WHERE product_id = $1 AND items.tenant_id = get_current_tenant_id() ORDER BY sku", product_id)
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
-
-        let mut items = Vec::new();
-        for row in rows {
-            let dimensions = row
-                .dimensions
-                .map(|d| serde_json::from_value(d).unwrap_or_default());
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
-            items.push(Item {
+        Ok(rows
+            .into_iter()
+            .map(|row| Item {
                 id: row.id,
                 tenant_id: row.tenant_id,
                 sku: row.sku,
@@ -232,29 +422,36 @@ impl ItemRepository for PostgresItemRepository {
                 sale_price: row.sale_price,
                 reorder_point: row.reorder_point,
                 reorder_qty: row.reorder_qty,
+                quantity_precision: row.quantity_precision as u32,
                 weight: row.weight,
-                dimensions,
+                dimensions: row
+                    .dimensions
+                    .map(|d| serde_json::from_value(d).unwrap_or_default()),
                 metadata: row.metadata,
+                product_id: row.product_id,
+                variant_attributes: row
+                    .variant_attributes
+                    .map(|v| serde_json::from_value(v).unwrap_or_default()),
                 active: row.active,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
-            });
-        }
-
-        Ok(items)
+            })
+            .collect())
     }
 
-    async fn count(&self) -> Result<i64, DomainError> {
-        let count: Option<i64> = sqlx::query_scalar!(
-            r#"
-            SELECT COUNT(*) FROM items WHERE items.tenant_id = get_current_tenant_id()
-            "#
-        )
-        .fetch_one(&*self.pool)
-        .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {e}")))?;
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError> {
+        let mut builder = QueryBuilder::new(
+            "SELECT COUNT(*) FROM items WHERE items.tenant_id = get_current_tenant_id()",
+        );
+        push_filters(&mut builder, filter, &ITEM_LIST_COLUMNS)?;
 
-        Ok(count.unwrap_or(0))
+        let count: i64 = builder
+            .build_query_scalar()
+            .fetch_one(&*self.pool)
+            .await
+            .map_err(|e| DomainError::ValidationError(format!("Database error: {e}").into()))?;
+
+        Ok(count)
     }
 
     async fn sku_exists(
@@ -275,8 +472,31 @@ impl ItemRepository for PostgresItemRepository {
                 .fetch_one(&*self.pool)
                 .await
         }
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(count.unwrap_or(0) > 0)
     }
+
+    async fn barcode_exists(
+        &self,
+        barcode: &str,
+        exclude_item_id: Option<Uuid>,
+    ) -> Result<bool, DomainError> {
+        let exists = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM items
+                WHERE barcode = $1 AND items.tenant_id = get_current_tenant_id()
+                  AND ($2::uuid IS NULL OR id != $2)
+            ) AS "exists!"
+            "#,
+            barcode,
+            exclude_item_id
+        )
+        .fetch_one(&*self.pool)
+        .await
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
+
+        Ok(exists)
+    }
 }
@@ -22,6 +22,7 @@ impl JobRepository for PostgresJobRepository {
         let result = sqlx::query!(
             r#"
             SELECT id, job_id, tenant_id, type, status, progress, payload, result_url, errors,
+                   attempts, max_attempts, run_after, heartbeat_at, cancel_requested,
                    created_at, updated_at, started_at, completed_at
             FROM jobs
             WHERE job_id = $1
@@ -30,7 +31,7 @@ impl JobRepository for PostgresJobRepository {
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         match result {
             Some(row) => {
@@ -40,6 +41,7 @@ impl JobRepository for PostgresJobRepository {
                     "SUCCESS" => JobStatus::Success,
                     "FAILED" => JobStatus::Failed,
                     "PARTIAL_SUCCESS" => JobStatus::PartialSuccess,
+                    "CANCELLED" => JobStatus::Cancelled,
                     _ => JobStatus::Queued, // Default fallback
                 };
 
@@ -57,6 +59,11 @@ impl JobRepository for PostgresJobRepository {
                     payload: row.payload,
                     result_url: row.result_url,
                     errors,
+                    attempts: row.attempts,
+                    max_attempts: row.max_attempts,
+                    run_after: row.run_after,
+                    heartbeat_at: row.heartbeat_at,
+                    cancel_requested: row.cancel_requested,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                     started_at: row.started_at,
@@ -71,6 +78,7 @@ impl JobRepository for PostgresJobRepository {
         let result = sqlx::query!(
             r#"
             SELECT id, job_id, tenant_id, type, status, progress, payload, result_url, errors,
+                   attempts, max_attempts, run_after, heartbeat_at, cancel_requested,
                    created_at, updated_at, started_at, completed_at
             FROM jobs
             WHERE id = $1
@@ -79,7 +87,7 @@ impl JobRepository for PostgresJobRepository {
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         match result {
             Some(row) => {
@@ -89,6 +97,7 @@ impl JobRepository for PostgresJobRepository {
                     "SUCCESS" => JobStatus::Success,
                     "FAILED" => JobStatus::Failed,
                     "PARTIAL_SUCCESS" => JobStatus::PartialSuccess,
+                    "CANCELLED" => JobStatus::Cancelled,
                     _ => JobStatus::Queued,
                 };
 
@@ -106,6 +115,11 @@ impl JobRepository for PostgresJobRepository {
                     payload: row.payload,
                     result_url: row.result_url,
                     errors,
+                    attempts: row.attempts,
+                    max_attempts: row.max_attempts,
+                    run_after: row.run_after,
+                    heartbeat_at: row.heartbeat_at,
+                    cancel_requested: row.cancel_requested,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                     started_at: row.started_at,
@@ -126,8 +140,9 @@ impl JobRepository for PostgresJobRepository {
         sqlx::query!(
             r#"
             INSERT INTO jobs (id, job_id, tenant_id, type, status, progress, payload, result_url, errors,
-                             created_at, updated_at, started_at, completed_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                             created_at, updated_at, started_at, completed_at,
+                             attempts, max_attempts, run_after, heartbeat_at, cancel_requested)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
             "#,
             job.id,
             job.job_id,
@@ -141,11 +156,16 @@ impl JobRepository for PostgresJobRepository {
             job.created_at,
             job.updated_at,
             job.started_at,
-            job.completed_at
+            job.completed_at,
+            job.attempts,
+            job.max_attempts,
+            job.run_after,
+            job.heartbeat_at,
+            job.cancel_requested
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(())
     }
@@ -161,8 +181,10 @@ impl JobRepository for PostgresJobRepository {
             r#"
             UPDATE jobs
             SET status = $1, progress = $2, result_url = $3, errors = $4,
-                updated_at = $5, started_at = $6, completed_at = $7
-            WHERE id = $8
+                updated_at = $5, started_at = $6, completed_at = $7,
+                attempts = $8, max_attempts = $9, run_after = $10, heartbeat_at = $11,
+                cancel_requested = $12
+            WHERE id = $13
             "#,
             status_str,
             job.progress,
@@ -171,11 +193,16 @@ impl JobRepository for PostgresJobRepository {
             job.updated_at,
             job.started_at,
             job.completed_at,
+            job.attempts,
+            job.max_attempts,
+            job.run_after,
+            job.heartbeat_at,
+            job.cancel_requested,
             job.id
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(())
     }
@@ -189,6 +216,7 @@ impl JobRepository for PostgresJobRepository {
         let rows = sqlx::query!(
             r#"
             SELECT id, job_id, tenant_id, type, status, progress, payload, result_url, errors,
+                   attempts, max_attempts, run_after, heartbeat_at, cancel_requested,
                    created_at, updated_at, started_at, completed_at
             FROM jobs
             WHERE tenant_id = $1
@@ -201,7 +229,7 @@ impl JobRepository for PostgresJobRepository {
         )
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         let mut jobs = Vec::new();
         for row in rows {
@@ -211,6 +239,7 @@ impl JobRepository for PostgresJobRepository {
                 "SUCCESS" => JobStatus::Success,
                 "FAILED" => JobStatus::Failed,
                 "PARTIAL_SUCCESS" => JobStatus::PartialSuccess,
+                "CANCELLED" => JobStatus::Cancelled,
                 _ => JobStatus::Queued,
             };
 
@@ -228,6 +257,11 @@ impl JobRepository for PostgresJobRepository {
                 payload: row.payload,
                 result_url: row.result_url,
                 errors,
+                attempts: row.attempts,
+                max_attempts: row.max_attempts,
+                run_after: row.run_after,
+                heartbeat_at: row.heartbeat_at,
+                cancel_requested: row.cancel_requested,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
                 started_at: row.started_at,
@@ -249,7 +283,7 @@ impl JobRepository for PostgresJobRepository {
         )
         .fetch_one(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(result.count.unwrap_or(0))
     }
@@ -263,6 +297,7 @@ impl JobRepository for PostgresJobRepository {
         let rows = sqlx::query!(
             r#"
             SELECT id, job_id, tenant_id, type, status, progress, payload, result_url, errors,
+                   attempts, max_attempts, run_after, heartbeat_at, cancel_requested,
                    created_at, updated_at, started_at, completed_at
             FROM jobs
             WHERE tenant_id = $1 AND status = $2
@@ -275,7 +310,7 @@ impl JobRepository for PostgresJobRepository {
         )
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         let mut jobs = Vec::new();
         for row in rows {
@@ -285,6 +320,7 @@ impl JobRepository for PostgresJobRepository {
                 "SUCCESS" => JobStatus::Success,
                 "FAILED" => JobStatus::Failed,
                 "PARTIAL_SUCCESS" => JobStatus::PartialSuccess,
+                "CANCELLED" => JobStatus::Cancelled,
                 _ => JobStatus::Queued,
             };
 
@@ -302,6 +338,78 @@ impl JobRepository for PostgresJobRepository {
                 payload: row.payload,
                 result_url: row.result_url,
                 errors,
+                attempts: row.attempts,
+                max_attempts: row.max_attempts,
+                run_after: row.run_after,
+                heartbeat_at: row.heartbeat_at,
+                cancel_requested: row.cancel_requested,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                started_at: row.started_at,
+                completed_at: row.completed_at,
+            });
+        }
+
+        Ok(jobs)
+    }
+
+    async fn claim_due_jobs(
+        &self,
+        job_types: &[String],
+        limit: i64,
+        lease_seconds: i64,
+    ) -> Result<Vec<Job>, DomainError> {
+        let rows = sqlx::query!(
+            r#"
+            UPDATE jobs
+            SET status = 'RUNNING', attempts = attempts + 1, started_at = NOW(),
+                heartbeat_at = NOW(), updated_at = NOW()
+            WHERE id IN (
+                SELECT id
+                FROM jobs
+                WHERE type = ANY($1)
+                  AND ((status = 'QUEUED' AND run_after <= NOW())
+                       OR (status = 'RUNNING'
+                           AND heartbeat_at < NOW() - make_interval(secs => $3::float8)
+                           AND attempts < max_attempts
+                           AND NOT cancel_requested))
+                ORDER BY run_after ASC
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, job_id, tenant_id, type, status, progress, payload, result_url, errors,
+                      attempts, max_attempts, run_after, heartbeat_at, cancel_requested,
+                      created_at, updated_at, started_at, completed_at
+            "#,
+            job_types,
+            limit,
+            lease_seconds as f64
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to claim jobs: {}", e).into()))?;
+
+        let mut jobs = Vec::new();
+        for row in rows {
+            let errors = row
+                .errors
+                .map(|e| serde_json::from_value(e).unwrap_or_default());
+
+            jobs.push(Job {
+                id: row.id,
+                job_id: row.job_id,
+                tenant_id: row.tenant_id,
+                job_type: row.r#type,
+                status: JobStatus::Running,
+                progress: row.progress,
+                payload: row.payload,
+                result_url: row.result_url,
+                errors,
+                attempts: row.attempts,
+                max_attempts: row.max_attempts,
+                run_after: row.run_after,
+                heartbeat_at: row.heartbeat_at,
+                cancel_requested: row.cancel_requested,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
                 started_at: row.started_at,
@@ -311,4 +419,52 @@ impl JobRepository for PostgresJobRepository {
 
         Ok(jobs)
     }
+
+    async fn fail_abandoned_jobs(&self, lease_seconds: i64) -> Result<u64, DomainError> {
+        let errors = serde_json::to_value(vec![JobError {
+            row: None,
+            message: "Worker stopped responding while running the job".to_string(),
+        }])
+        .unwrap_or_default();
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE jobs
+            SET status = CASE WHEN cancel_requested THEN 'CANCELLED' ELSE 'FAILED' END,
+                errors = CASE WHEN cancel_requested THEN errors ELSE $2 END,
+                cancel_requested = FALSE, completed_at = NOW(), updated_at = NOW()
+            WHERE status = 'RUNNING'
+              AND heartbeat_at < NOW() - make_interval(secs => $1::float8)
+              AND (attempts >= max_attempts OR cancel_requested)
+            "#,
+            lease_seconds as f64,
+            errors
+        )
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to fail abandoned jobs: {}", e).into())
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn heartbeat(&self, id: Uuid) -> Result<bool, DomainError> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE jobs SET heartbeat_at = NOW()
+            WHERE id = $1 AND status = 'RUNNING'
+            RETURNING cancel_requested
+            "#,
+            id
+        )
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to record heartbeat: {}", e).into())
+        })?;
+
+        // A job that is no longer running has been cancelled or reclaimed elsewhere
+        Ok(row.is_none_or(|row| row.cancel_requested))
+    }
 }
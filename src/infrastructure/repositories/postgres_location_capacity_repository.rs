@@ -0,0 +1,117 @@
+use crate::domain::entities::item::ItemDimensions;
+use crate::domain::entities::location_capacity::{
+    CapacityEnforcement, ItemFootprint, LocationCapacity, LocationStock, StockFootprint,
+};
+use crate::domain::services::location_capacity_repository::LocationCapacityRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct PostgresLocationCapacityRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresLocationCapacityRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Reads the footprint of an item selected as `id, weight, dimensions, metadata`
+    fn row_to_footprint(row: &PgRow, id_column: &str) -> Result<ItemFootprint, DomainError> {
+        let dimensions: Option<serde_json::Value> = row.try_get("dimensions")?;
+        let dimensions = dimensions.and_then(|d| serde_json::from_value::<ItemDimensions>(d).ok());
+        let metadata: Option<serde_json::Value> = row.try_get("metadata")?;
+        Ok(ItemFootprint::from_parts(
+            row.try_get(id_column)?,
+            row.try_get("weight")?,
+            dimensions.as_ref(),
+            metadata.as_ref(),
+        ))
+    }
+}
+
+#[async_trait]
+impl LocationCapacityRepository for PostgresLocationCapacityRepository {
+    async fn find_location_stock(
+        &self,
+        location_ids: Option<&[Uuid]>,
+    ) -> Result<Vec<LocationStock>, DomainError> {
+        let locations = sqlx::query(
+            r#"
+            SELECT id, name, code, max_volume, max_weight, max_pallets, capacity_enforcement
+            FROM locations
+            WHERE active = true AND tenant_id = get_current_tenant_id()
+              AND ($1::uuid[] IS NULL OR id = ANY($1))
+            ORDER BY name, id
+            "#,
+        )
+        .bind(location_ids)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        let stock_rows = sqlx::query(
+            r#"
+            SELECT sl.location_id, sl.quantity_on_hand, i.id AS item_id, i.weight, i.dimensions, i.metadata
+            FROM stock_levels sl
+            JOIN items i ON i.id = sl.item_id
+            WHERE sl.quantity_on_hand > 0 AND sl.tenant_id = get_current_tenant_id()
+              AND ($1::uuid[] IS NULL OR sl.location_id = ANY($1))
+            "#,
+        )
+        .bind(location_ids)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        let mut stock: HashMap<Uuid, Vec<StockFootprint>> = HashMap::new();
+        for row in &stock_rows {
+            stock
+                .entry(row.try_get("location_id")?)
+                .or_default()
+                .push(StockFootprint {
+                    footprint: Self::row_to_footprint(row, "item_id")?,
+                    quantity: row.try_get("quantity_on_hand")?,
+                });
+        }
+
+        locations
+            .iter()
+            .map(|row| {
+                let id: Uuid = row.try_get("id")?;
+                let enforcement: String = row.try_get("capacity_enforcement")?;
+                Ok(LocationStock {
+                    location_id: id,
+                    name: row.try_get("name")?,
+                    code: row.try_get("code")?,
+                    capacity: LocationCapacity {
+                        max_volume: row.try_get("max_volume")?,
+                        max_weight: row.try_get("max_weight")?,
+                        max_pallets: row.try_get("max_pallets")?,
+                        enforcement: CapacityEnforcement::from_str(&enforcement)?,
+                    },
+                    stock: stock.remove(&id).unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
+    async fn find_footprints(&self, item_ids: &[Uuid]) -> Result<Vec<ItemFootprint>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, weight, dimensions, metadata
+            FROM items
+            WHERE id = ANY($1) AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(item_ids)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Self::row_to_footprint(row, "id"))
+            .collect()
+    }
+}
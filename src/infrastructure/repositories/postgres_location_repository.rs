@@ -1,4 +1,5 @@
 use crate::domain::entities::location::{Location, LocationAddress, LocationType};
+use crate::domain::entities::location_capacity::{CapacityEnforcement, LocationCapacity};
 use crate::domain::services::location_repository::LocationRepository;
 use crate::shared::error::DomainError;
 use async_trait::async_trait;
@@ -21,15 +22,16 @@ impl LocationRepository for PostgresLocationRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Location>, DomainError> {
         let result = sqlx::query!(
             r#"
-            SELECT id, name, code, address, type, active, created_at, updated_at
+            SELECT id, name, code, address, type, active, created_at, updated_at,
+                   max_volume, max_weight, max_pallets, capacity_enforcement
             FROM locations
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             id
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         match result {
             Some(row) => {
@@ -46,6 +48,12 @@ impl LocationRepository for PostgresLocationRepository {
                     address,
                     r#type,
                     active: row.active,
+                    capacity: LocationCapacity {
+                        max_volume: row.max_volume,
+                        max_weight: row.max_weight,
+                        max_pallets: row.max_pallets,
+                        enforcement: CapacityEnforcement::from_str(&row.capacity_enforcement)?,
+                    },
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                 }))
@@ -54,18 +62,57 @@ impl LocationRepository for PostgresLocationRepository {
         }
     }
 
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Location>, DomainError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, name, code, address, type, active, created_at, updated_at,
+                   max_volume, max_weight, max_pallets, capacity_enforcement
+            FROM locations
+            WHERE id = ANY($1) AND tenant_id = get_current_tenant_id()
+            "#,
+            ids
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(Location {
+                    id: row.id,
+                    name: row.name,
+                    code: row.code,
+                    address: row
+                        .address
+                        .map(|a| serde_json::from_value(a).unwrap_or_default()),
+                    r#type: row.r#type.map(|t| LocationType::from_str(&t)).transpose()?,
+                    active: row.active,
+                    capacity: LocationCapacity {
+                        max_volume: row.max_volume,
+                        max_weight: row.max_weight,
+                        max_pallets: row.max_pallets,
+                        enforcement: CapacityEnforcement::from_str(&row.capacity_enforcement)?,
+                    },
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                })
+            })
+            .collect()
+    }
+
     async fn find_by_code(&self, code: &str) -> Result<Option<Location>, DomainError> {
         let result = sqlx::query!(
             r#"
-            SELECT id, name, code, address, type, active, created_at, updated_at
+            SELECT id, name, code, address, type, active, created_at, updated_at,
+                   max_volume, max_weight, max_pallets, capacity_enforcement
             FROM locations
-            WHERE code = $1
+            WHERE code = $1 AND tenant_id = get_current_tenant_id()
             "#,
             code
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         match result {
             Some(row) => {
@@ -82,6 +129,12 @@ impl LocationRepository for PostgresLocationRepository {
                     address,
                     r#type,
                     active: row.active,
+                    capacity: LocationCapacity {
+                        max_volume: row.max_volume,
+                        max_weight: row.max_weight,
+                        max_pallets: row.max_pallets,
+                        enforcement: CapacityEnforcement::from_str(&row.capacity_enforcement)?,
+                    },
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                 }))
@@ -97,15 +150,16 @@ impl LocationRepository for PostgresLocationRepository {
             .map(|a| serde_json::to_value(a))
             .transpose()
             .map_err(|e| {
-                DomainError::ValidationError(format!("Failed to serialize address: {}", e))
+                DomainError::ValidationError(format!("Failed to serialize address: {}", e).into())
             })?;
 
         let type_str = location.r#type.as_ref().map(|t| t.as_str());
 
         sqlx::query!(
             r#"
-            INSERT INTO locations (id, name, code, address, type, active, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO locations (id, name, code, address, type, active, created_at, updated_at,
+                                   max_volume, max_weight, max_pallets, capacity_enforcement, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, get_current_tenant_id())
             "#,
             location.id,
             location.name,
@@ -114,11 +168,15 @@ impl LocationRepository for PostgresLocationRepository {
             type_str,
             location.active,
             location.created_at,
-            location.updated_at
+            location.updated_at,
+            location.capacity.max_volume,
+            location.capacity.max_weight,
+            location.capacity.max_pallets,
+            location.capacity.enforcement.as_str()
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(())
     }
@@ -130,16 +188,17 @@ impl LocationRepository for PostgresLocationRepository {
             .map(|a| serde_json::to_value(a))
             .transpose()
             .map_err(|e| {
-                DomainError::ValidationError(format!("Failed to serialize address: {}", e))
+                DomainError::ValidationError(format!("Failed to serialize address: {}", e).into())
             })?;
 
         let type_str = location.r#type.as_ref().map(|t| t.as_str());
 
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             UPDATE locations
-            SET name = $2, code = $3, address = $4, type = $5, active = $6, updated_at = $7
-            WHERE id = $1
+            SET name = $2, code = $3, address = $4, type = $5, active = $6, updated_at = $7,
+                max_volume = $8, max_weight = $9, max_pallets = $10, capacity_enforcement = $11
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             location.id,
             location.name,
@@ -147,35 +206,51 @@ impl LocationRepository for PostgresLocationRepository {
             address_json,
             type_str,
             location.active,
-            location.updated_at
+            location.updated_at,
+            location.capacity.max_volume,
+            location.capacity.max_weight,
+            location.capacity.max_pallets,
+            location.capacity.enforcement.as_str()
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Location with id {} not found", location.id).into(),
+            ));
+        }
         Ok(())
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             DELETE FROM locations
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             id
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Location with id {} not found", id).into(),
+            ));
+        }
         Ok(())
     }
 
     async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Location>, DomainError> {
         let rows = sqlx::query!(
             r#"
-            SELECT id, name, code, address, type, active, created_at, updated_at
+            SELECT id, name, code, address, type, active, created_at, updated_at,
+                   max_volume, max_weight, max_pallets, capacity_enforcement
             FROM locations
+            WHERE tenant_id = get_current_tenant_id()
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
             "#,
@@ -184,7 +259,7 @@ impl LocationRepository for PostgresLocationRepository {
         )
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         let mut locations = Vec::new();
         for row in rows {
@@ -201,6 +276,12 @@ impl LocationRepository for PostgresLocationRepository {
                 address,
                 r#type,
                 active: row.active,
+                capacity: LocationCapacity {
+                    max_volume: row.max_volume,
+                    max_weight: row.max_weight,
+                    max_pallets: row.max_pallets,
+                    enforcement: CapacityEnforcement::from_str(&row.capacity_enforcement)?,
+                },
                 created_at: row.created_at,
                 updated_at: row.updated_at,
             });
@@ -214,11 +295,12 @@ impl LocationRepository for PostgresLocationRepository {
             r#"
             SELECT COUNT(*) as count
             FROM locations
+            WHERE tenant_id = get_current_tenant_id()
             "#
         )
         .fetch_one(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(result.count.unwrap_or(0))
     }
@@ -239,7 +321,7 @@ impl LocationRepository for PostgresLocationRepository {
         )
         .fetch_one(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(result.count.unwrap_or(0) > 0)
     }
@@ -0,0 +1,300 @@
+use crate::domain::entities::printing::{PrintJob, PrintJobSource, PrintJobStatus, Printer};
+use crate::domain::services::print_repository::PrintRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Postgres, Row, Transaction};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const PRINTER_COLUMNS: &str =
+    "id, location_id, name, host, port, is_default, active, created_at, updated_at";
+
+const PRINT_JOB_COLUMNS: &str = "id, printer_id, job_id, source_type, source_id, content, copies, status, attempts, last_error, created_at, updated_at, printed_at";
+
+pub struct PostgresPrintRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresPrintRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_printer(row: &PgRow) -> Result<Printer, DomainError> {
+        Ok(Printer {
+            id: row.try_get("id")?,
+            location_id: row.try_get("location_id")?,
+            name: row.try_get("name")?,
+            host: row.try_get("host")?,
+            port: row.try_get("port")?,
+            is_default: row.try_get("is_default")?,
+            active: row.try_get("active")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    fn row_to_print_job(row: &PgRow) -> Result<PrintJob, DomainError> {
+        let source_type: String = row.try_get("source_type")?;
+        let status: String = row.try_get("status")?;
+        Ok(PrintJob {
+            id: row.try_get("id")?,
+            printer_id: row.try_get("printer_id")?,
+            job_id: row.try_get("job_id")?,
+            source_type: PrintJobSource::from_str(&source_type)?,
+            source_id: row.try_get("source_id")?,
+            content: row.try_get("content")?,
+            copies: row.try_get("copies")?,
+            status: PrintJobStatus::from_str(&status)?,
+            attempts: row.try_get("attempts")?,
+            last_error: row.try_get("last_error")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            printed_at: row.try_get("printed_at")?,
+        })
+    }
+
+    /// A location has at most one default printer; clear the flag on the
+    /// others before `printer` takes it
+    async fn take_default(
+        tx: &mut Transaction<'_, Postgres>,
+        printer: &Printer,
+    ) -> Result<(), DomainError> {
+        if printer.is_default {
+            sqlx::query(
+                r#"
+                UPDATE printers SET is_default = false, updated_at = NOW()
+                WHERE location_id = $1 AND id <> $2 AND is_default
+                  AND tenant_id = get_current_tenant_id()
+                "#,
+            )
+            .bind(printer.location_id)
+            .bind(printer.id)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PrintRepository for PostgresPrintRepository {
+    async fn create_printer(&self, printer: &Printer) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+        Self::take_default(&mut tx, printer).await?;
+        sqlx::query(
+            r#"
+            INSERT INTO printers (id, location_id, name, host, port, is_default, active, tenant_id, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, get_current_tenant_id(), $8, $9)
+            "#,
+        )
+        .bind(printer.id)
+        .bind(printer.location_id)
+        .bind(&printer.name)
+        .bind(&printer.host)
+        .bind(printer.port)
+        .bind(printer.is_default)
+        .bind(printer.active)
+        .bind(printer.created_at)
+        .bind(printer.updated_at)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_printer(&self, id: Uuid) -> Result<Option<Printer>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM printers WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+            PRINTER_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_printer).transpose()
+    }
+
+    async fn find_default_printer(
+        &self,
+        location_id: Uuid,
+    ) -> Result<Option<Printer>, DomainError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM printers
+            WHERE location_id = $1 AND is_default AND active
+              AND tenant_id = get_current_tenant_id()
+            "#,
+            PRINTER_COLUMNS
+        ))
+        .bind(location_id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_printer).transpose()
+    }
+
+    async fn list_printers(&self, location_id: Option<Uuid>) -> Result<Vec<Printer>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM printers
+            WHERE tenant_id = get_current_tenant_id()
+              AND ($1::uuid IS NULL OR location_id = $1)
+            ORDER BY name, id
+            "#,
+            PRINTER_COLUMNS
+        ))
+        .bind(location_id)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_printer).collect()
+    }
+
+    async fn update_printer(&self, printer: &Printer) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+        Self::take_default(&mut tx, printer).await?;
+        let result = sqlx::query(
+            r#"
+            UPDATE printers
+            SET location_id = $2, name = $3, host = $4, port = $5, is_default = $6,
+                active = $7, updated_at = $8
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(printer.id)
+        .bind(printer.location_id)
+        .bind(&printer.name)
+        .bind(&printer.host)
+        .bind(printer.port)
+        .bind(printer.is_default)
+        .bind(printer.active)
+        .bind(printer.updated_at)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Printer {} not found", printer.id).into(),
+            ));
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn delete_printer(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "DELETE FROM printers WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+        )
+        .bind(id)
+        .execute(&*self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Printer {} not found", id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn create_print_job(&self, job: &PrintJob) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO print_jobs (id, printer_id, job_id, source_type, source_id, content, copies,
+                                    status, attempts, last_error, tenant_id, created_at, updated_at, printed_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, get_current_tenant_id(), $11, $12, $13)
+            "#,
+        )
+        .bind(job.id)
+        .bind(job.printer_id)
+        .bind(&job.job_id)
+        .bind(job.source_type.as_str())
+        .bind(job.source_id)
+        .bind(&job.content)
+        .bind(job.copies)
+        .bind(job.status.as_str())
+        .bind(job.attempts)
+        .bind(&job.last_error)
+        .bind(job.created_at)
+        .bind(job.updated_at)
+        .bind(job.printed_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_print_job(&self, id: Uuid) -> Result<Option<PrintJob>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM print_jobs WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+            PRINT_JOB_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_print_job).transpose()
+    }
+
+    async fn list_print_jobs(
+        &self,
+        printer_id: Option<Uuid>,
+        status: Option<PrintJobStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PrintJob>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM print_jobs
+            WHERE tenant_id = get_current_tenant_id()
+              AND ($1::uuid IS NULL OR printer_id = $1)
+              AND ($2::text IS NULL OR status = $2)
+            ORDER BY created_at DESC, id
+            LIMIT $3 OFFSET $4
+            "#,
+            PRINT_JOB_COLUMNS
+        ))
+        .bind(printer_id)
+        .bind(status.map(|s| s.as_str()))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_print_job).collect()
+    }
+
+    async fn update_print_job(&self, job: &PrintJob) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            UPDATE print_jobs
+            SET job_id = $2, status = $3, attempts = $4, last_error = $5, updated_at = $6,
+                printed_at = $7
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(job.id)
+        .bind(&job.job_id)
+        .bind(job.status.as_str())
+        .bind(job.attempts)
+        .bind(&job.last_error)
+        .bind(job.updated_at)
+        .bind(job.printed_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn set_print_job_job_id(&self, id: Uuid, job_id: &str) -> Result<(), DomainError> {
+        sqlx::query(
+            "UPDATE print_jobs SET job_id = $2 WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+        )
+        .bind(id)
+        .bind(job_id)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use crate::domain::entities::product::Product;
+use crate::domain::services::product_repository::ProductRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const PRODUCT_COLUMNS: &str =
+    "p.id, p.tenant_id, p.parent_sku, p.name, p.description, p.category, p.unit, p.attributes, p.created_at, p.updated_at";
+
+pub struct PostgresProductRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresProductRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_product(row: &PgRow) -> Result<Product, DomainError> {
+        let attributes: serde_json::Value = row.try_get("attributes")?;
+        Ok(Product {
+            id: row.try_get("id")?,
+            tenant_id: row.try_get("tenant_id")?,
+            parent_sku: row.try_get("parent_sku")?,
+            name: row.try_get("name")?,
+            description: row.try_get("description")?,
+            category: row.try_get("category")?,
+            unit: row.try_get("unit")?,
+            attributes: serde_json::from_value(attributes).map_err(|e| {
+                DomainError::InfrastructureError(
+                    format!("Invalid product attributes: {}", e).into(),
+                )
+            })?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl ProductRepository for PostgresProductRepository {
+    async fn create(&self, product: &Product) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO products (id, tenant_id, parent_sku, name, description, category, unit, attributes, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(product.id)
+        .bind(product.tenant_id)
+        .bind(&product.parent_sku)
+        .bind(&product.name)
+        .bind(&product.description)
+        .bind(&product.category)
+        .bind(&product.unit)
+        .bind(serde_json::to_value(&product.attributes).unwrap_or_default())
+        .bind(product.created_at)
+        .bind(product.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Product>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM products p WHERE p.id = $1 AND p.tenant_id = get_current_tenant_id()",
+            PRODUCT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_product).transpose()
+    }
+
+    async fn parent_sku_exists(&self, parent_sku: &str) -> Result<bool, DomainError> {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM products WHERE parent_sku = $1 AND tenant_id = get_current_tenant_id())",
+        )
+        .bind(parent_sku)
+        .fetch_one(&*self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Product>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM products p
+            WHERE p.tenant_id = get_current_tenant_id()
+            ORDER BY p.parent_sku
+            LIMIT $1 OFFSET $2
+            "#,
+            PRODUCT_COLUMNS
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_product).collect()
+    }
+
+    async fn update(&self, product: &Product) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE products
+            SET name = $2, description = $3, category = $4, unit = $5, attributes = $6, updated_at = $7
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(product.id)
+        .bind(&product.name)
+        .bind(&product.description)
+        .bind(&product.category)
+        .bind(&product.unit)
+        .bind(serde_json::to_value(&product.attributes).unwrap_or_default())
+        .bind(product.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Product {} not found", product.id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            UPDATE items SET product_id = NULL, variant_attributes = NULL, updated_at = NOW()
+            WHERE product_id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        let result = sqlx::query(
+            "DELETE FROM products WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Product {} not found", id).into(),
+            ));
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
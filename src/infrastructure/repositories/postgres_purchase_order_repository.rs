@@ -1,23 +1,181 @@
-use crate::domain::entities::inventory::StockMovement;
+use crate::domain::entities::inventory::{StockMovement, StockStatus};
+use crate::domain::entities::list_filter::ListFilter;
 use crate::domain::entities::purchase_order::{
-    CreatePurchaseOrderRequest, PurchaseOrder, PurchaseOrderLine, PurchaseOrderStatus,
+    CreatePurchaseOrderRequest, OverduePurchaseOrderLine, PurchaseOrder, PurchaseOrderLine,
+    PurchaseOrderReceipt, PurchaseOrderReceiptLine, PurchaseOrderStatus,
     ReceivePurchaseOrderRequest,
 };
+use crate::domain::entities::receiving::ReceivingSettings;
 use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
+use crate::infrastructure::repositories::list_filter_sql::{push_filters, ListColumns, ListQuery};
+use crate::infrastructure::repositories::postgres_item_availability_repository::PostgresItemAvailabilityRepository;
+use crate::infrastructure::repositories::postgres_stock_repository::PostgresStockRepository;
 use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
+const PURCHASE_ORDER_LIST_COLUMNS: ListColumns = ListColumns {
+    resource: "purchase orders",
+    id: "id",
+    status: Some("status"),
+    created_at: "created_at",
+    supplier_id: Some("supplier_id"),
+    customer_id: None,
+    sku: Some(
+        "SELECT i.sku FROM purchase_order_lines l JOIN items i ON i.id = l.item_id WHERE l.po_id = purchase_orders.id",
+    ),
+    category: Some(
+        "SELECT i.category FROM purchase_order_lines l JOIN items i ON i.id = l.item_id WHERE l.po_id = purchase_orders.id",
+    ),
+    search: &["po_number"],
+    sortable: &[
+        ("po_number", "po_number", "text"),
+        ("status", "status", "text"),
+        ("expected_date", "expected_date", "timestamptz"),
+        ("total_amount", "total_amount", "numeric"),
+        ("created_at", "created_at", "timestamptz"),
+        ("updated_at", "updated_at", "timestamptz"),
+    ],
+};
+
 pub struct PostgresPurchaseOrderRepository {
     pool: Arc<PgPool>,
 }
 
+fn parse_status(status: &str) -> Result<PurchaseOrderStatus, DomainError> {
+    match status {
+        "DRAFT" => Ok(PurchaseOrderStatus::Draft),
+        "OPEN" => Ok(PurchaseOrderStatus::Open),
+        "RECEIVING" => Ok(PurchaseOrderStatus::Receiving),
+        "PARTIAL_RECEIVED" => Ok(PurchaseOrderStatus::PartialReceived),
+        "RECEIVED" => Ok(PurchaseOrderStatus::Received),
+        "CANCELLED" => Ok(PurchaseOrderStatus::Cancelled),
+        "CLOSED" => Ok(PurchaseOrderStatus::Closed),
+        _ => Err(DomainError::InfrastructureError("Invalid status".into())),
+    }
+}
+
 impl PostgresPurchaseOrderRepository {
     pub fn new(pool: Arc<PgPool>) -> Self {
         Self { pool }
     }
+
+    async fn save_receipt_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        receipt: &PurchaseOrderReceipt,
+    ) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO purchase_order_receipts (id, po_id, location_id, stock_status, received_by, received_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, get_current_tenant_id())
+            "#,
+            receipt.id,
+            receipt.po_id,
+            receipt.location_id,
+            receipt.stock_status.as_str(),
+            receipt.received_by,
+            receipt.received_at
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
+
+        for line in &receipt.lines {
+            sqlx::query!(
+                r#"
+                INSERT INTO purchase_order_receipt_lines (id, receipt_id, po_line_id, item_id, qty_received, tenant_id)
+                VALUES ($1, $2, $3, $4, $5, get_current_tenant_id())
+                "#,
+                line.id,
+                receipt.id,
+                line.po_line_id,
+                line.item_id,
+                line.qty_received
+            )
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
+        }
+        Ok(())
+    }
+
+    /// Load a page of purchase orders with their lines in two queries rather
+    /// than one round trip per order. The tenant is read once per query instead
+    /// of once per candidate row, as the id filter already narrows the rows.
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<HashMap<Uuid, PurchaseOrder>, DomainError> {
+        let headers = sqlx::query!(
+            r#"
+            SELECT id, po_number, supplier_id, status, expected_date, total_amount, currency,
+                   exchange_rate, created_by, created_at, updated_at
+            FROM purchase_orders
+            WHERE id = ANY($1) AND tenant_id = (SELECT get_current_tenant_id())
+            "#,
+            ids
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
+
+        let line_rows = sqlx::query!(
+            r#"
+            SELECT id, po_id, item_id, qty_ordered, qty_received, unit_cost, line_total,
+                   expected_date, promised_date, promised_at
+            FROM purchase_order_lines
+            WHERE po_id = ANY($1) AND tenant_id = (SELECT get_current_tenant_id())
+            ORDER BY created_at
+            "#,
+            ids
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
+
+        let mut lines: HashMap<Uuid, Vec<PurchaseOrderLine>> = HashMap::new();
+        for row in line_rows {
+            lines.entry(row.po_id).or_default().push(PurchaseOrderLine {
+                id: row.id,
+                po_id: row.po_id,
+                item_id: row.item_id,
+                qty_ordered: row.qty_ordered,
+                qty_received: row.qty_received,
+                unit_cost: row.unit_cost,
+                line_total: row.line_total,
+                expected_date: row.expected_date,
+                promised_date: row.promised_date,
+                promised_at: row.promised_at,
+            });
+        }
+
+        headers
+            .into_iter()
+            .map(|row| {
+                Ok((
+                    row.id,
+                    PurchaseOrder {
+                        id: row.id,
+                        po_number: row.po_number,
+                        supplier_id: row.supplier_id,
+                        status: parse_status(&row.status)?,
+                        expected_date: row.expected_date,
+                        total_amount: row.total_amount,
+                        currency: row.currency,
+                        exchange_rate: row.exchange_rate,
+                        lines: lines.remove(&row.id).unwrap_or_default(),
+                        created_by: row.created_by,
+                        created_at: row.created_at,
+                        updated_at: row.updated_at,
+                    },
+                ))
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -27,19 +185,21 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
             r#"
             SELECT
                 po.id, po.po_number, po.supplier_id, po.status, po.expected_date,
-                po.total_amount, po.created_by, po.created_at, po.updated_at,
+                po.total_amount, po.currency, po.exchange_rate, po.created_by,
+                po.created_at, po.updated_at,
                 pol.id as line_id, pol.item_id, pol.qty_ordered, pol.qty_received,
-                pol.unit_cost, pol.line_total
+                pol.unit_cost, pol.line_total, pol.expected_date as line_expected_date,
+                pol.promised_date, pol.promised_at
             FROM purchase_orders po
             LEFT JOIN purchase_order_lines pol ON po.id = pol.po_id
-            WHERE po.id = $1
+            WHERE po.id = $1 AND po.tenant_id = get_current_tenant_id()
             ORDER BY pol.created_at
             "#,
             id
         )
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
 
         if result.is_empty() {
             return Ok(None);
@@ -52,23 +212,13 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
         let status_str = result[0].status.as_str();
         let expected_date = result[0].expected_date;
         let total_amount = result[0].total_amount;
+        let currency = result[0].currency.clone();
+        let exchange_rate = result[0].exchange_rate;
         let created_by = result[0].created_by;
         let created_at = result[0].created_at;
         let updated_at = result[0].updated_at;
 
-        let status = match status_str {
-            "DRAFT" => PurchaseOrderStatus::Draft,
-            "OPEN" => PurchaseOrderStatus::Open,
-            "RECEIVING" => PurchaseOrderStatus::Receiving,
-            "PARTIAL_RECEIVED" => PurchaseOrderStatus::PartialReceived,
-            "RECEIVED" => PurchaseOrderStatus::Received,
-            "CANCELLED" => PurchaseOrderStatus::Cancelled,
-            _ => {
-                return Err(DomainError::InfrastructureError(
-                    "Invalid status".to_string(),
-                ))
-            }
-        };
+        let status = parse_status(status_str)?;
 
         let mut lines = Vec::new();
         for row in result {
@@ -80,6 +230,9 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
                 qty_received: row.qty_received,
                 unit_cost: row.unit_cost,
                 line_total: row.line_total,
+                expected_date: row.line_expected_date,
+                promised_date: row.promised_date,
+                promised_at: row.promised_at,
             });
         }
 
@@ -90,6 +243,8 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
             status,
             expected_date,
             total_amount,
+            currency,
+            exchange_rate,
             lines,
             created_by,
             created_at,
@@ -103,13 +258,13 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
     ) -> Result<Option<PurchaseOrder>, DomainError> {
         let result = sqlx::query!(
             r#"
-            SELECT id FROM purchase_orders WHERE po_number = $1
+            SELECT id FROM purchase_orders WHERE po_number = $1 AND tenant_id = get_current_tenant_id()
             "#,
             po_number
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
 
         match result {
             Some(row) => self.find_by_id(row.id).await,
@@ -125,17 +280,17 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
             PurchaseOrderStatus::PartialReceived => "PARTIAL_RECEIVED",
             PurchaseOrderStatus::Received => "RECEIVED",
             PurchaseOrderStatus::Cancelled => "CANCELLED",
+            PurchaseOrderStatus::Closed => "CLOSED",
         };
 
-        let mut tx =
-            self.pool.begin().await.map_err(|e| {
-                DomainError::InfrastructureError(format!("Transaction error: {}", e))
-            })?;
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            DomainError::InfrastructureError(format!("Transaction error: {}", e).into())
+        })?;
 
         sqlx::query!(
             r#"
-            INSERT INTO purchase_orders (id, po_number, supplier_id, status, expected_date, total_amount, created_by, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO purchase_orders (id, po_number, supplier_id, status, expected_date, total_amount, currency, exchange_rate, created_by, created_at, updated_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, get_current_tenant_id())
             "#,
             po.id,
             po.po_number,
@@ -143,19 +298,21 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
             status_str,
             po.expected_date,
             po.total_amount,
+            po.currency,
+            po.exchange_rate,
             po.created_by,
             po.created_at,
             po.updated_at
         )
         .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
 
         for line in &po.lines {
             sqlx::query!(
                 r#"
-                INSERT INTO purchase_order_lines (id, po_id, item_id, qty_ordered, qty_received, unit_cost, line_total, created_at, updated_at)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                INSERT INTO purchase_order_lines (id, po_id, item_id, qty_ordered, qty_received, unit_cost, line_total, expected_date, promised_date, promised_at, created_at, updated_at, tenant_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, get_current_tenant_id())
                 "#,
                 line.id,
                 line.po_id,
@@ -164,16 +321,22 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
                 line.qty_received,
                 line.unit_cost,
                 line.line_total,
+                line.expected_date,
+                line.promised_date,
+                line.promised_at,
                 po.created_at,
                 po.updated_at
             )
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e)))?;
+            .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
         }
 
+        let item_ids: Vec<Uuid> = po.lines.iter().map(|l| l.item_id).collect();
+        PostgresItemAvailabilityRepository::refresh_on_order_in_tx(&mut tx, &item_ids).await?;
+
         tx.commit().await.map_err(|e| {
-            DomainError::InfrastructureError(format!("Transaction commit error: {}", e))
+            DomainError::InfrastructureError(format!("Transaction commit error: {}", e).into())
         })?;
 
         Ok(())
@@ -187,18 +350,18 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
             PurchaseOrderStatus::PartialReceived => "PARTIAL_RECEIVED",
             PurchaseOrderStatus::Received => "RECEIVED",
             PurchaseOrderStatus::Cancelled => "CANCELLED",
+            PurchaseOrderStatus::Closed => "CLOSED",
         };
 
-        let mut tx =
-            self.pool.begin().await.map_err(|e| {
-                DomainError::InfrastructureError(format!("Transaction error: {}", e))
-            })?;
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            DomainError::InfrastructureError(format!("Transaction error: {}", e).into())
+        })?;
 
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             UPDATE purchase_orders
             SET status = $2, expected_date = $3, total_amount = $4, updated_at = $5
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             po.id,
             status_str,
@@ -208,115 +371,177 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
         )
         .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(Message::localized(
+                "error.purchase_order_not_found",
+                &[("id", &po.id)],
+            )));
+        }
+
+        // Drop lines removed from the order, then upsert the rest
+        let line_ids: Vec<Uuid> = po.lines.iter().map(|l| l.id).collect();
+        let mut item_ids: Vec<Uuid> = sqlx::query_scalar!(
+            r#"
+            DELETE FROM purchase_order_lines
+            WHERE po_id = $1 AND NOT (id = ANY($2)) AND tenant_id = get_current_tenant_id()
+            RETURNING item_id
+            "#,
+            po.id,
+            &line_ids
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
 
-        // Update lines
         for line in &po.lines {
             sqlx::query!(
                 r#"
-                UPDATE purchase_order_lines
-                SET qty_received = $2, updated_at = $3
-                WHERE id = $1
+                INSERT INTO purchase_order_lines (id, po_id, item_id, qty_ordered, qty_received, unit_cost, line_total, expected_date, promised_date, promised_at, created_at, updated_at, tenant_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11, get_current_tenant_id())
+                ON CONFLICT (id) DO UPDATE
+                SET qty_ordered = EXCLUDED.qty_ordered, qty_received = EXCLUDED.qty_received,
+                    unit_cost = EXCLUDED.unit_cost, line_total = EXCLUDED.line_total,
+                    expected_date = EXCLUDED.expected_date, promised_date = EXCLUDED.promised_date,
+                    promised_at = EXCLUDED.promised_at, updated_at = EXCLUDED.updated_at
+                WHERE purchase_order_lines.tenant_id = EXCLUDED.tenant_id
                 "#,
                 line.id,
+                po.id,
+                line.item_id,
+                line.qty_ordered,
                 line.qty_received,
+                line.unit_cost,
+                line.line_total,
+                line.expected_date,
+                line.promised_date,
+                line.promised_at,
                 po.updated_at
             )
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e)))?;
+            .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
         }
 
+        // Status and received quantities both decide what is still on order
+        item_ids.extend(po.lines.iter().map(|l| l.item_id));
+        PostgresItemAvailabilityRepository::refresh_on_order_in_tx(&mut tx, &item_ids).await?;
+
         tx.commit().await.map_err(|e| {
-            DomainError::InfrastructureError(format!("Transaction commit error: {}", e))
+            DomainError::InfrastructureError(format!("Transaction commit error: {}", e).into())
         })?;
 
         Ok(())
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        sqlx::query!(
+        let mut tx = self.pool.begin().await?;
+
+        // The lines go with the order, so note their items first
+        let item_ids: Vec<Uuid> = sqlx::query_scalar!(
+            r#"
+            SELECT item_id FROM purchase_order_lines WHERE po_id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+            id
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
+
+        let result = sqlx::query!(
             r#"
-            DELETE FROM purchase_orders WHERE id = $1
+            DELETE FROM purchase_orders WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             id
         )
-        .execute(&*self.pool)
+        .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(Message::localized(
+                "error.purchase_order_not_found",
+                &[("id", &id)],
+            )));
+        }
 
+        PostgresItemAvailabilityRepository::refresh_on_order_in_tx(&mut tx, &item_ids).await?;
+        tx.commit().await?;
         Ok(())
     }
 
     async fn list(
         &self,
-        limit: i64,
-        offset: i64,
-        status_filter: Option<String>,
-    ) -> Result<Vec<PurchaseOrder>, DomainError> {
-        let rows = if let Some(status) = &status_filter {
-            sqlx::query("SELECT id FROM purchase_orders WHERE status = $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3")
-                .bind(status)
-                .bind(limit)
-                .bind(offset)
-                .fetch_all(&*self.pool)
-                .await
-        } else {
-            sqlx::query("SELECT id FROM purchase_orders ORDER BY created_at DESC LIMIT $1 OFFSET $2")
-                .bind(limit)
-                .bind(offset)
-                .fetch_all(&*self.pool)
-                .await
-        }
-        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e)))?;
-
-        let mut pos = Vec::new();
-        for row in rows {
-            let id: Uuid = row.get("id");
-            if let Some(po) = self.find_by_id(id).await? {
-                pos.push(po);
-            }
-        }
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<PurchaseOrder>, DomainError> {
+        let query = ListQuery::new(&PURCHASE_ORDER_LIST_COLUMNS, filter, page)?;
+        let mut builder = QueryBuilder::new(format!(
+            "SELECT id, {} FROM purchase_orders WHERE tenant_id = get_current_tenant_id()",
+            query.sort_key_column()
+        ));
+        query.push_tail(&mut builder)?;
+
+        let rows = builder.build().fetch_all(&*self.pool).await.map_err(|e| {
+            DomainError::InfrastructureError(format!("Database error: {}", e).into())
+        })?;
 
-        Ok(pos)
+        let cursors = rows
+            .iter()
+            .map(|row| query.cursor_for(row))
+            .collect::<Result<Vec<_>, _>>()?;
+        let ids: Vec<Uuid> = cursors.iter().map(|cursor| cursor.id).collect();
+        let mut orders = self.find_by_ids(&ids).await?;
+
+        let results = cursors
+            .into_iter()
+            .filter_map(|cursor| {
+                let order = orders.remove(&cursor.id)?;
+                Some((cursor, order))
+            })
+            .collect();
+
+        Ok(query.page(results))
     }
 
-    async fn count(&self, status_filter: Option<String>) -> Result<i64, DomainError> {
-        let result = if let Some(status) = &status_filter {
-            sqlx::query("SELECT COUNT(*) as count FROM purchase_orders WHERE status = $1")
-                .bind(status)
-                .fetch_one(&*self.pool)
-                .await
-        } else {
-            sqlx::query("SELECT COUNT(*) as count FROM purchase_orders")
-                .fetch_one(&*self.pool)
-                .await
-        }
-        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e)))?;
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError> {
+        let mut builder = QueryBuilder::new(
+            "SELECT COUNT(*) FROM purchase_orders WHERE tenant_id = get_current_tenant_id()",
+        );
+        push_filters(&mut builder, filter, &PURCHASE_ORDER_LIST_COLUMNS)?;
 
-        Ok(result.get::<i64, _>("count"))
+        builder
+            .build_query_scalar()
+            .fetch_one(&*self.pool)
+            .await
+            .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))
     }
-
     async fn receive_purchase_order(
         &self,
         po_id: Uuid,
         request: &ReceivePurchaseOrderRequest,
+        tolerances: &ReceivingSettings,
         user_id: Uuid,
     ) -> Result<Vec<StockMovement>, DomainError> {
-        let mut tx =
-            self.pool.begin().await.map_err(|e| {
-                DomainError::InfrastructureError(format!("Transaction error: {}", e))
-            })?;
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            DomainError::InfrastructureError(format!("Transaction error: {}", e).into())
+        })?;
 
         // Get current PO
         let Some(mut po) = self.find_by_id(po_id).await? else {
-            return Err(DomainError::ValidationError(
-                "Purchase order not found".to_string(),
-            ));
+            return Err(DomainError::NotFound(Message::localized(
+                "error.purchase_order_not_found",
+                &[("id", &po_id)],
+            )));
         };
 
         // Receive the lines
-        po.receive_lines(request.received_lines.clone())?;
+        let receipt = PurchaseOrderReceipt::new(&po, request, user_id)?;
+        po.receive_lines(
+            request.received_lines.clone(),
+            tolerances.tolerance_for(po.supplier_id),
+        )?;
 
         // Update PO
         self.update(&po).await?;
@@ -324,12 +549,12 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
         // Create stock movements for received items
         let mut movements = Vec::new();
         for receive_req in &request.received_lines {
-            if receive_req.qty_received > 0 {
+            if receive_req.qty_received > Decimal::ZERO {
                 let line = po
                     .lines
                     .iter()
                     .find(|l| l.id == receive_req.po_line_id)
-                    .ok_or_else(|| DomainError::ValidationError("Line not found".to_string()))?;
+                    .ok_or_else(|| DomainError::ValidationError("Line not found".into()))?;
 
                 let movement = StockMovement::new(
                     line.item_id,
@@ -340,58 +565,158 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
                     Some(po_id),
                     Some(format!("PO-{}", po.po_number)),
                     Some(user_id),
-                )?;
-
-                // Save movement
-                sqlx::query!(
-                    r#"
-                    INSERT INTO stock_movements (id, item_id, location_id, quantity, movement_type, reference_type, reference_id, reason, created_by, created_at)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-                    "#,
-                    movement.id,
-                    movement.item_id,
-                    movement.location_id,
-                    movement.quantity,
-                    "inbound", // Map to database enum
-                    movement.reference_type.as_str(),
-                    movement.reference_id,
-                    movement.reason,
-                    movement.created_by,
-                    movement.created_at
-                )
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e)))?;
-
-                // Update stock levels
-                sqlx::query!(
-                    r#"
-                    INSERT INTO stock_levels (item_id, location_id, quantity_on_hand, last_movement_id, updated_at)
-                    VALUES ($1, $2, $3, $4, $5)
-                    ON CONFLICT (item_id, location_id)
-                    DO UPDATE SET
-                        quantity_on_hand = stock_levels.quantity_on_hand + EXCLUDED.quantity_on_hand,
-                        last_movement_id = EXCLUDED.last_movement_id,
-                        updated_at = EXCLUDED.updated_at
-                    "#,
-                    movement.item_id,
-                    movement.location_id,
-                    movement.quantity,
-                    movement.id,
-                    movement.created_at
-                )
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e)))?;
+                )?
+                .with_stock_status(request.stock_status);
 
                 movements.push(movement);
             }
         }
 
+        // Save movements and update stock levels
+        PostgresStockRepository::record_movements_in_tx(&mut tx, &movements).await?;
+
+        Self::save_receipt_in_tx(&mut tx, &receipt).await?;
+
         tx.commit().await.map_err(|e| {
-            DomainError::InfrastructureError(format!("Transaction commit error: {}", e))
+            DomainError::InfrastructureError(format!("Transaction commit error: {}", e).into())
         })?;
 
         Ok(movements)
     }
+
+    async fn list_receipts(&self, po_id: Uuid) -> Result<Vec<PurchaseOrderReceipt>, DomainError> {
+        let receipt_rows = sqlx::query!(
+            r#"
+            SELECT r.id, r.po_id, r.location_id, r.stock_status, r.received_by, r.received_at,
+                   u.email AS "received_by_email?"
+            FROM purchase_order_receipts r
+            LEFT JOIN users u ON u.id = r.received_by
+            WHERE r.po_id = $1 AND r.tenant_id = get_current_tenant_id()
+            ORDER BY r.received_at, r.created_at
+            "#,
+            po_id
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
+
+        let line_rows = sqlx::query!(
+            r#"
+            SELECT l.id, l.receipt_id, l.po_line_id, l.item_id, l.qty_received
+            FROM purchase_order_receipt_lines l
+            JOIN purchase_order_receipts r ON r.id = l.receipt_id
+            WHERE r.po_id = $1 AND l.tenant_id = get_current_tenant_id()
+            ORDER BY l.receipt_id
+            "#,
+            po_id
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
+
+        let mut lines: HashMap<Uuid, Vec<PurchaseOrderReceiptLine>> = HashMap::new();
+        for row in line_rows {
+            lines
+                .entry(row.receipt_id)
+                .or_default()
+                .push(PurchaseOrderReceiptLine {
+                    id: row.id,
+                    po_line_id: row.po_line_id,
+                    item_id: row.item_id,
+                    qty_received: row.qty_received,
+                });
+        }
+
+        receipt_rows
+            .into_iter()
+            .map(|row| {
+                Ok(PurchaseOrderReceipt {
+                    id: row.id,
+                    po_id: row.po_id,
+                    location_id: row.location_id,
+                    stock_status: StockStatus::from_str(&row.stock_status)?,
+                    received_by: row.received_by,
+                    received_by_email: row.received_by_email,
+                    received_at: row.received_at,
+                    lines: lines.remove(&row.id).unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
+    async fn average_received_costs(
+        &self,
+        item_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Decimal>, DomainError> {
+        let rows: Vec<(Uuid, Decimal)> = sqlx::query_as(
+            r#"
+            SELECT pol.item_id,
+                   ROUND(SUM(pol.qty_received * pol.unit_cost * COALESCE(po.exchange_rate, 1))
+                       / SUM(pol.qty_received), 4)
+            FROM purchase_order_lines pol
+            JOIN purchase_orders po ON po.id = pol.po_id
+            WHERE pol.item_id = ANY($1) AND pol.qty_received > 0
+              AND pol.tenant_id = get_current_tenant_id()
+            GROUP BY pol.item_id
+            "#,
+        )
+        .bind(item_ids)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::InfrastructureError(format!("Database error: {}", e).into()))?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    async fn overdue_lines(
+        &self,
+        as_of: DateTime<Utc>,
+        supplier_id: Option<Uuid>,
+    ) -> Result<Vec<OverduePurchaseOrderLine>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT po.id AS po_id, po.po_number, po.supplier_id,
+                   pol.id AS line_id, pol.item_id, i.sku, i.name,
+                   pol.qty_ordered, pol.qty_received, pol.expected_date, pol.promised_date,
+                   COALESCE(pol.promised_date, pol.expected_date) AS due_date
+            FROM purchase_order_lines pol
+            JOIN purchase_orders po ON po.id = pol.po_id
+            JOIN items i ON i.id = pol.item_id
+            WHERE pol.tenant_id = get_current_tenant_id()
+              AND po.status IN ('OPEN', 'RECEIVING', 'PARTIAL_RECEIVED')
+              AND pol.qty_received < pol.qty_ordered
+              AND COALESCE(pol.promised_date, pol.expected_date) < $1
+              AND ($2::uuid IS NULL OR po.supplier_id = $2)
+            ORDER BY due_date, po.po_number, i.sku
+            "#,
+        )
+        .bind(as_of)
+        .bind(supplier_id)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let qty_ordered: Decimal = row.try_get("qty_ordered")?;
+                let qty_received: Decimal = row.try_get("qty_received")?;
+                let due_date: DateTime<Utc> = row.try_get("due_date")?;
+                Ok(OverduePurchaseOrderLine {
+                    po_id: row.try_get("po_id")?,
+                    po_number: row.try_get("po_number")?,
+                    supplier_id: row.try_get("supplier_id")?,
+                    line_id: row.try_get("line_id")?,
+                    item_id: row.try_get("item_id")?,
+                    sku: row.try_get("sku")?,
+                    name: row.try_get("name")?,
+                    qty_ordered,
+                    qty_received,
+                    qty_outstanding: qty_ordered - qty_received,
+                    expected_date: row.try_get("expected_date")?,
+                    promised_date: row.try_get("promised_date")?,
+                    due_date,
+                    days_late: (as_of - due_date).num_days(),
+                })
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,172 @@
+use crate::domain::entities::putaway::{PutawayCandidate, PutawayRule};
+use crate::domain::services::putaway_rule_repository::PutawayRuleRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const PUTAWAY_RULE_COLUMNS: &str =
+    "r.id, r.name, r.item_id, r.category, r.location_id, r.priority, r.max_quantity, r.active, r.created_at, r.updated_at";
+
+pub struct PostgresPutawayRuleRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresPutawayRuleRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_rule(row: &PgRow) -> Result<PutawayRule, DomainError> {
+        Ok(PutawayRule {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            item_id: row.try_get("item_id")?,
+            category: row.try_get("category")?,
+            location_id: row.try_get("location_id")?,
+            priority: row.try_get("priority")?,
+            max_quantity: row.try_get("max_quantity")?,
+            active: row.try_get("active")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl PutawayRuleRepository for PostgresPutawayRuleRepository {
+    async fn create(&self, rule: &PutawayRule) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO putaway_rules (id, name, item_id, category, location_id, priority, max_quantity, active, tenant_id, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, get_current_tenant_id(), $9, $10)
+            "#,
+        )
+        .bind(rule.id)
+        .bind(&rule.name)
+        .bind(rule.item_id)
+        .bind(&rule.category)
+        .bind(rule.location_id)
+        .bind(rule.priority)
+        .bind(rule.max_quantity)
+        .bind(rule.active)
+        .bind(rule.created_at)
+        .bind(rule.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PutawayRule>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM putaway_rules r WHERE r.id = $1 AND r.tenant_id = get_current_tenant_id()",
+            PUTAWAY_RULE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_rule).transpose()
+    }
+
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<PutawayRule>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM putaway_rules r
+            WHERE r.tenant_id = get_current_tenant_id()
+            ORDER BY r.priority, r.name
+            LIMIT $1 OFFSET $2
+            "#,
+            PUTAWAY_RULE_COLUMNS
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_rule).collect()
+    }
+
+    async fn update(&self, rule: &PutawayRule) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE putaway_rules
+            SET name = $2, item_id = $3, category = $4, location_id = $5, priority = $6,
+                max_quantity = $7, active = $8, updated_at = $9
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(rule.id)
+        .bind(&rule.name)
+        .bind(rule.item_id)
+        .bind(&rule.category)
+        .bind(rule.location_id)
+        .bind(rule.priority)
+        .bind(rule.max_quantity)
+        .bind(rule.active)
+        .bind(rule.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Putaway rule {} not found", rule.id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "DELETE FROM putaway_rules WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+        )
+        .bind(id)
+        .execute(&*self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Putaway rule {} not found", id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn find_candidates(&self, item_id: Uuid) -> Result<Vec<PutawayCandidate>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {},
+                   COALESCE((SELECT sl.quantity_on_hand FROM stock_levels sl
+                             WHERE sl.item_id = i.id AND sl.location_id = r.location_id
+                               AND sl.tenant_id = r.tenant_id), 0) AS item_on_hand,
+                   COALESCE((SELECT SUM(sl.quantity_on_hand) FROM stock_levels sl
+                             WHERE sl.location_id = r.location_id
+                               AND sl.tenant_id = r.tenant_id), 0) AS location_on_hand
+            FROM putaway_rules r
+            JOIN items i ON i.id = $1
+            JOIN locations l ON l.id = r.location_id AND l.active = true
+            WHERE r.active = true AND r.tenant_id = get_current_tenant_id()
+              AND (r.item_id = i.id
+                   OR (r.item_id IS NULL AND LOWER(r.category) = LOWER(i.category))
+                   OR (r.item_id IS NULL AND r.category IS NULL))
+            "#,
+            PUTAWAY_RULE_COLUMNS
+        ))
+        .bind(item_id)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(PutawayCandidate {
+                    rule: Self::row_to_rule(row)?,
+                    item_on_hand: row.try_get("item_on_hand")?,
+                    location_on_hand: row.try_get("location_on_hand")?,
+                    capacity_room: None,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,108 @@
+use crate::domain::entities::receiving::{ReceivingSettings, SupplierTolerance};
+use crate::domain::services::receiving_settings_repository::ReceivingSettingsRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct PostgresReceivingSettingsRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresReceivingSettingsRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReceivingSettingsRepository for PostgresReceivingSettingsRepository {
+    async fn find(&self) -> Result<ReceivingSettings, DomainError> {
+        let over_receipt_percent: Option<Decimal> = sqlx::query_scalar(
+            r#"
+            SELECT over_receipt_percent
+            FROM receiving_settings
+            WHERE tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        let suppliers = sqlx::query(
+            r#"
+            SELECT supplier_id, over_receipt_percent, updated_at
+            FROM supplier_receiving_tolerances
+            WHERE tenant_id = get_current_tenant_id()
+            ORDER BY supplier_id
+            "#,
+        )
+        .fetch_all(&*self.pool)
+        .await?
+        .into_iter()
+        .map(|row| {
+            Ok(SupplierTolerance {
+                supplier_id: row.try_get("supplier_id")?,
+                over_receipt_percent: row.try_get("over_receipt_percent")?,
+                updated_at: row.try_get("updated_at")?,
+            })
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
+
+        Ok(ReceivingSettings {
+            over_receipt_percent: over_receipt_percent.unwrap_or_default(),
+            suppliers,
+        })
+    }
+
+    async fn save_default(&self, over_receipt_percent: Decimal) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO receiving_settings (tenant_id, over_receipt_percent, updated_at)
+            VALUES (get_current_tenant_id(), $1, NOW())
+            ON CONFLICT (tenant_id) DO UPDATE
+            SET over_receipt_percent = EXCLUDED.over_receipt_percent,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(over_receipt_percent)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn save_supplier(
+        &self,
+        supplier_id: Uuid,
+        over_receipt_percent: Decimal,
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO supplier_receiving_tolerances (tenant_id, supplier_id, over_receipt_percent, updated_at)
+            VALUES (get_current_tenant_id(), $1, $2, NOW())
+            ON CONFLICT (tenant_id, supplier_id) DO UPDATE
+            SET over_receipt_percent = EXCLUDED.over_receipt_percent,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(supplier_id)
+        .bind(over_receipt_percent)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_supplier(&self, supplier_id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM supplier_receiving_tolerances
+            WHERE supplier_id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(supplier_id)
+        .execute(&*self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,183 @@
+use crate::domain::entities::inventory::StockLevel;
+use crate::domain::services::reservation_repository::ReservationRepository;
+use crate::infrastructure::repositories::postgres_item_availability_repository::PostgresItemAvailabilityRepository;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use sqlx::{postgres::PgRow, PgPool, Postgres, Row, Transaction};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct PostgresReservationRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresReservationRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_stock_level(row: &PgRow) -> Result<StockLevel, DomainError> {
+        Ok(StockLevel {
+            item_id: row.try_get("item_id")?,
+            location_id: row.try_get("location_id")?,
+            quantity_on_hand: row.try_get("quantity_on_hand")?,
+            quantity_reserved: row.try_get("quantity_reserved")?,
+            quantity_quarantine: row.try_get("quantity_quarantine")?,
+            quantity_damaged: row.try_get("quantity_damaged")?,
+            quantity_in_transit: row.try_get("quantity_in_transit")?,
+            last_movement_id: row.try_get("last_movement_id")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    /// Reserve inside a caller's transaction so the reservation commits with the order change
+    pub(crate) async fn reserve_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        item_id: Uuid,
+        location_id: Uuid,
+        quantity: Decimal,
+    ) -> Result<StockLevel, DomainError> {
+        if quantity <= Decimal::ZERO {
+            return Err(DomainError::ValidationError(Message::localized(
+                "error.reservation_quantity_positive",
+                &[],
+            )));
+        }
+
+        let row = sqlx::query(
+            r#"
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
+            FROM stock_levels
+            WHERE item_id = $1 AND location_id = $2 AND tenant_id = get_current_tenant_id()
+            FOR UPDATE
+            "#,
+        )
+        .bind(item_id)
+        .bind(location_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        let available = match &row {
+            Some(row) => Self::row_to_stock_level(row)?.available_to_promise(),
+            None => Decimal::ZERO,
+        };
+
+        if quantity > available {
+            return Err(DomainError::BusinessLogicError(
+                format!(
+                    "Insufficient stock to reserve item {}: requested {}, available {}",
+                    item_id, quantity, available
+                )
+                .into(),
+            ));
+        }
+
+        let row = sqlx::query(
+            r#"
+            UPDATE stock_levels
+            SET quantity_reserved = quantity_reserved + $3, updated_at = NOW()
+            WHERE item_id = $1 AND location_id = $2 AND tenant_id = get_current_tenant_id()
+            RETURNING item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
+            "#,
+        )
+        .bind(item_id)
+        .bind(location_id)
+        .bind(quantity)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        PostgresItemAvailabilityRepository::refresh_in_tx(tx, &[(item_id, location_id)]).await?;
+        Self::row_to_stock_level(&row)
+    }
+
+    /// Release inside a caller's transaction; never drops the reserved quantity below zero
+    pub(crate) async fn release_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        item_id: Uuid,
+        location_id: Uuid,
+        quantity: Decimal,
+    ) -> Result<Option<StockLevel>, DomainError> {
+        let row = sqlx::query(
+            r#"
+            UPDATE stock_levels
+            SET quantity_reserved = GREATEST(quantity_reserved - $3, 0), updated_at = NOW()
+            WHERE item_id = $1 AND location_id = $2 AND tenant_id = get_current_tenant_id()
+            RETURNING item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
+            "#,
+        )
+        .bind(item_id)
+        .bind(location_id)
+        .bind(quantity)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        PostgresItemAvailabilityRepository::refresh_in_tx(tx, &[(item_id, location_id)]).await?;
+        row.as_ref().map(Self::row_to_stock_level).transpose()
+    }
+}
+
+#[async_trait]
+impl ReservationRepository for PostgresReservationRepository {
+    async fn reserve(
+        &self,
+        item_id: Uuid,
+        location_id: Uuid,
+        quantity: Decimal,
+    ) -> Result<StockLevel, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        let stock_level = Self::reserve_in_tx(&mut tx, item_id, location_id, quantity).await?;
+        tx.commit().await?;
+        Ok(stock_level)
+    }
+
+    async fn release(
+        &self,
+        item_id: Uuid,
+        location_id: Uuid,
+        quantity: Decimal,
+    ) -> Result<StockLevel, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        let stock_level = Self::release_in_tx(&mut tx, item_id, location_id, quantity)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(
+                    format!(
+                        "No stock level for item {} at location {}",
+                        item_id, location_id
+                    )
+                    .into(),
+                )
+            })?;
+        tx.commit().await?;
+        Ok(stock_level)
+    }
+
+    async fn get_available_to_promise(
+        &self,
+        item_id: Uuid,
+        location_id: Uuid,
+    ) -> Result<Decimal, DomainError> {
+        let row = sqlx::query(
+            r#"
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
+            FROM stock_levels
+            WHERE item_id = $1 AND location_id = $2 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(item_id)
+        .bind(location_id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Self::row_to_stock_level(&row)?.available_to_promise()),
+            None => Ok(Decimal::ZERO),
+        }
+    }
+}
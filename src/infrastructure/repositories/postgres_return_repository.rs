@@ -1,11 +1,43 @@
 use crate::domain::entities::inventory::StockMovement;
-use crate::domain::entities::returns::{ProcessReturnRequest, Return, ReturnLine, ReturnStatus};
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::returns::{
+    ProcessReturnRequest, Return, ReturnDisposition, ReturnLine, ReturnStatus, ReturnableLine,
+    ScrapTotal,
+};
 use crate::domain::services::return_repository::ReturnRepository;
+use crate::infrastructure::repositories::list_filter_sql::{push_filters, ListColumns, ListQuery};
+use crate::infrastructure::repositories::postgres_stock_repository::PostgresStockRepository;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
-use sqlx::{PgPool, Postgres, Transaction};
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+const RETURN_LIST_COLUMNS: ListColumns = ListColumns {
+    resource: "returns",
+    id: "id",
+    status: Some("status"),
+    created_at: "created_at",
+    supplier_id: None,
+    customer_id: Some("customer_id"),
+    sku: Some(
+        "SELECT i.sku FROM return_lines l JOIN items i ON i.id = l.item_id WHERE l.return_id = returns.id",
+    ),
+    category: Some(
+        "SELECT i.category FROM return_lines l JOIN items i ON i.id = l.item_id WHERE l.return_id = returns.id",
+    ),
+    search: &["return_number", "notes"],
+    sortable: &[
+        ("return_number", "return_number", "text"),
+        ("status", "status", "text"),
+        ("total_quantity", "total_quantity", "numeric"),
+        ("created_at", "created_at", "timestamptz"),
+        ("updated_at", "updated_at", "timestamptz"),
+    ],
+};
+
 pub struct PostgresReturnRepository {
     pool: std::sync::Arc<PgPool>,
 }
@@ -25,13 +57,13 @@ impl PostgresReturnRepository {
             r#"
             SELECT id, return_number, location_id, customer_id, status, total_quantity, notes, created_by, created_at, updated_at
             FROM returns
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             id
         )
         .fetch_optional(&mut **tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         let return_row = match return_row {
             Some(row) => row,
@@ -41,31 +73,41 @@ impl PostgresReturnRepository {
         // Get return lines
         let line_rows = sqlx::query!(
             r#"
-            SELECT id, return_id, item_id, quantity, quantity_received, unit_price, reason, created_at, updated_at
+            SELECT id, return_id, item_id, quantity, quantity_received, unit_price, reason, disposition,
+                   sales_order_id, so_line_id, created_at, updated_at
             FROM return_lines
-            WHERE return_id = $1
+            WHERE return_id = $1 AND tenant_id = get_current_tenant_id()
             ORDER BY created_at
             "#,
             id
         )
         .fetch_all(&mut **tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
-        let lines: Vec<ReturnLine> = line_rows
+        let lines = line_rows
             .into_iter()
-            .map(|row| ReturnLine {
-                id: row.id,
-                return_id: row.return_id,
-                item_id: row.item_id,
-                quantity: row.quantity,
-                quantity_received: row.quantity_received,
-                unit_price: row.unit_price,
-                reason: row.reason,
-                created_at: row.created_at,
-                updated_at: row.updated_at,
+            .map(|row| {
+                Ok(ReturnLine {
+                    id: row.id,
+                    return_id: row.return_id,
+                    item_id: row.item_id,
+                    quantity: row.quantity,
+                    quantity_received: row.quantity_received,
+                    unit_price: row.unit_price,
+                    reason: row.reason,
+                    disposition: row
+                        .disposition
+                        .as_deref()
+                        .map(ReturnDisposition::from_str)
+                        .transpose()?,
+                    sales_order_id: row.sales_order_id,
+                    so_line_id: row.so_line_id,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                })
             })
-            .collect();
+            .collect::<Result<Vec<_>, DomainError>>()?;
 
         let return_entity = Return {
             id: return_row.id,
@@ -73,7 +115,7 @@ impl PostgresReturnRepository {
             location_id: return_row.location_id,
             customer_id: return_row.customer_id,
             status: ReturnStatus::from_str(&return_row.status)
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
             total_quantity: return_row.total_quantity,
             notes: return_row.notes,
             lines: lines.clone(), // Populate the lines field
@@ -84,6 +126,131 @@ impl PostgresReturnRepository {
 
         Ok(Some((return_entity, lines)))
     }
+
+    /// Load a page of returns with their lines in two queries rather than two
+    /// per return
+    async fn find_by_ids(
+        &self,
+        ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, (Return, Vec<ReturnLine>)>, DomainError> {
+        let return_rows = sqlx::query!(
+            r#"
+            SELECT id, return_number, location_id, customer_id, status, total_quantity, notes, created_by, created_at, updated_at
+            FROM returns
+            WHERE id = ANY($1) AND tenant_id = (SELECT get_current_tenant_id())
+            "#,
+            ids
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        let line_rows = sqlx::query!(
+            r#"
+            SELECT id, return_id, item_id, quantity, quantity_received, unit_price, reason, disposition,
+                   sales_order_id, so_line_id, created_at, updated_at
+            FROM return_lines
+            WHERE return_id = ANY($1) AND tenant_id = (SELECT get_current_tenant_id())
+            ORDER BY created_at
+            "#,
+            ids
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        let mut lines: HashMap<Uuid, Vec<ReturnLine>> = HashMap::new();
+        for row in line_rows {
+            lines.entry(row.return_id).or_default().push(ReturnLine {
+                id: row.id,
+                return_id: row.return_id,
+                item_id: row.item_id,
+                quantity: row.quantity,
+                quantity_received: row.quantity_received,
+                unit_price: row.unit_price,
+                reason: row.reason,
+                disposition: row
+                    .disposition
+                    .as_deref()
+                    .map(ReturnDisposition::from_str)
+                    .transpose()?,
+                sales_order_id: row.sales_order_id,
+                so_line_id: row.so_line_id,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            });
+        }
+
+        return_rows
+            .into_iter()
+            .map(|row| {
+                let lines = lines.remove(&row.id).unwrap_or_default();
+                let return_entity = Return {
+                    id: row.id,
+                    return_number: row.return_number,
+                    location_id: row.location_id,
+                    customer_id: row.customer_id,
+                    status: ReturnStatus::from_str(&row.status)
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    total_quantity: row.total_quantity,
+                    notes: row.notes,
+                    lines: lines.clone(),
+                    created_by: row.created_by,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                };
+                Ok((row.id, (return_entity, lines)))
+            })
+            .collect()
+    }
+
+    /// The sales order lines `return_entity` returns against, locked until
+    /// the transaction ends, with what other returns already claim of them
+    async fn returnable_lines_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        return_entity: &Return,
+    ) -> Result<Vec<ReturnableLine>, DomainError> {
+        let so_line_ids: Vec<Uuid> = return_entity
+            .lines
+            .iter()
+            .filter_map(|line| line.so_line_id)
+            .collect();
+        if so_line_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT sol.id, sol.so_id, so.customer_id, sol.item_id, sol.qty_shipped,
+                   COALESCE((
+                       SELECT SUM(rl.quantity)
+                       FROM return_lines rl
+                       JOIN returns r ON r.id = rl.return_id
+                       WHERE rl.so_line_id = sol.id AND r.status <> 'CANCELLED'
+                   ), 0) AS "qty_returned!"
+            FROM sales_order_lines sol
+            JOIN sales_orders so ON so.id = sol.so_id
+            WHERE sol.id = ANY($1) AND sol.tenant_id = get_current_tenant_id()
+            FOR UPDATE OF sol
+            "#,
+            &so_line_ids
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ReturnableLine {
+                so_line_id: row.id,
+                sales_order_id: row.so_id,
+                customer_id: row.customer_id,
+                item_id: row.item_id,
+                qty_shipped: row.qty_shipped,
+                qty_returned: row.qty_returned,
+            })
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -93,13 +260,16 @@ impl ReturnRepository for PostgresReturnRepository {
             .pool
             .begin()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        let returnable = Self::returnable_lines_in_tx(&mut tx, return_entity).await?;
+        return_entity.check_sales_order_lines(&returnable)?;
 
         // Insert return
         sqlx::query!(
             r#"
-            INSERT INTO returns (id, return_number, location_id, customer_id, status, total_quantity, notes, created_by, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            INSERT INTO returns (id, return_number, location_id, customer_id, status, total_quantity, notes, created_by, created_at, updated_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, get_current_tenant_id())
             "#,
             return_entity.id,
             return_entity.return_number,
@@ -114,14 +284,14 @@ impl ReturnRepository for PostgresReturnRepository {
         )
         .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Insert return lines
         for line in &return_entity.lines {
             sqlx::query!(
                 r#"
-                INSERT INTO return_lines (id, return_id, item_id, quantity, quantity_received, unit_price, reason, created_at, updated_at)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                INSERT INTO return_lines (id, return_id, item_id, quantity, quantity_received, unit_price, reason, sales_order_id, so_line_id, created_at, updated_at, tenant_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, get_current_tenant_id())
                 "#,
                 line.id,
                 line.return_id,
@@ -130,17 +300,19 @@ impl ReturnRepository for PostgresReturnRepository {
                 line.quantity_received,
                 line.unit_price,
                 line.reason,
+                line.sales_order_id,
+                line.so_line_id,
                 line.created_at,
                 line.updated_at
             )
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
         }
 
         tx.commit()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         Ok(())
     }
@@ -150,7 +322,7 @@ impl ReturnRepository for PostgresReturnRepository {
             .pool
             .begin()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         self.find_by_id_with_tx(&mut tx, id).await
     }
@@ -161,13 +333,13 @@ impl ReturnRepository for PostgresReturnRepository {
     ) -> Result<Option<(Return, Vec<ReturnLine>)>, DomainError> {
         let return_row = sqlx::query!(
             r#"
-            SELECT id FROM returns WHERE return_number = $1
+            SELECT id FROM returns WHERE return_number = $1 AND tenant_id = get_current_tenant_id()
             "#,
             return_number
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         match return_row {
             Some(row) => self.find_by_id(row.id).await,
@@ -176,11 +348,11 @@ impl ReturnRepository for PostgresReturnRepository {
     }
 
     async fn update(&self, return_entity: &Return) -> Result<(), DomainError> {
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             UPDATE returns
             SET return_number = $2, location_id = $3, customer_id = $4, status = $5, total_quantity = $6, notes = $7, updated_at = $8
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             return_entity.id,
             return_entity.return_number,
@@ -193,51 +365,123 @@ impl ReturnRepository for PostgresReturnRepository {
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Return {} not found", return_entity.id).into(),
+            ));
+        }
         Ok(())
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
-            DELETE FROM returns WHERE id = $1
+            DELETE FROM returns WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             id
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Return {} not found", id).into(),
+            ));
+        }
         Ok(())
     }
 
     async fn list(
         &self,
-        limit: i64,
-        offset: i64,
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<(Return, Vec<ReturnLine>)>, DomainError> {
+        let query = ListQuery::new(&RETURN_LIST_COLUMNS, filter, page)?;
+        let mut builder = QueryBuilder::new(format!(
+            "SELECT id, {} FROM returns WHERE tenant_id = get_current_tenant_id()",
+            query.sort_key_column()
+        ));
+        query.push_tail(&mut builder)?;
+
+        let rows = builder
+            .build()
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        let cursors = rows
+            .iter()
+            .map(|row| query.cursor_for(row))
+            .collect::<Result<Vec<_>, _>>()?;
+        let ids: Vec<Uuid> = cursors.iter().map(|cursor| cursor.id).collect();
+        let mut returns = self.find_by_ids(&ids).await?;
+
+        let results = cursors
+            .into_iter()
+            .filter_map(|cursor| {
+                let return_entity = returns.remove(&cursor.id)?;
+                Some((cursor, return_entity))
+            })
+            .collect();
+
+        Ok(query.page(results))
+    }
+
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError> {
+        let mut builder = QueryBuilder::new(
+            "SELECT COUNT(*) FROM returns WHERE tenant_id = get_current_tenant_id()",
+        );
+        push_filters(&mut builder, filter, &RETURN_LIST_COLUMNS)?;
+
+        builder
+            .build_query_scalar()
+            .fetch_one(&*self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))
+    }
+
+    async fn find_by_sales_order(
+        &self,
+        sales_order_id: Uuid,
     ) -> Result<Vec<(Return, Vec<ReturnLine>)>, DomainError> {
-        let return_rows = sqlx::query!(
+        let exists = sqlx::query_scalar!(
             r#"
-            SELECT id FROM returns
-            ORDER BY created_at DESC
-            LIMIT $1 OFFSET $2
+            SELECT EXISTS (
+                SELECT 1 FROM sales_orders WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            ) AS "exists!"
             "#,
-            limit,
-            offset
+            sales_order_id
         )
-        .fetch_all(&*self.pool)
+        .fetch_one(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-
-        let mut results = Vec::new();
-        for row in return_rows {
-            if let Some(result) = self.find_by_id(row.id).await? {
-                results.push(result);
-            }
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+        if !exists {
+            return Err(DomainError::NotFound(
+                format!("Sales order {} not found", sales_order_id).into(),
+            ));
         }
 
-        Ok(results)
+        let ids: Vec<Uuid> = sqlx::query_scalar!(
+            r#"
+            SELECT r.id
+            FROM returns r
+            WHERE r.tenant_id = get_current_tenant_id()
+              AND EXISTS (
+                  SELECT 1 FROM return_lines l WHERE l.return_id = r.id AND l.sales_order_id = $1
+              )
+            ORDER BY r.created_at, r.id
+            "#,
+            sales_order_id
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        let mut returns = self.find_by_ids(&ids).await?;
+        Ok(ids.iter().filter_map(|id| returns.remove(id)).collect())
     }
 
     async fn open_return(&self, id: Uuid) -> Result<(Return, Vec<ReturnLine>), DomainError> {
@@ -245,13 +489,13 @@ impl ReturnRepository for PostgresReturnRepository {
             .pool
             .begin()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Get current return
         let (mut return_entity, lines) = self
             .find_by_id_with_tx(&mut tx, id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Return {} not found", id)))?;
+            .ok_or_else(|| DomainError::NotFound(format!("Return {} not found", id).into()))?;
 
         // Open the return (this validates status transition)
         return_entity.open()?;
@@ -261,7 +505,7 @@ impl ReturnRepository for PostgresReturnRepository {
             r#"
             UPDATE returns
             SET status = $2, updated_at = $3
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             return_entity.id,
             return_entity.status.as_str(),
@@ -269,11 +513,11 @@ impl ReturnRepository for PostgresReturnRepository {
         )
         .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         tx.commit()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         Ok((return_entity, lines))
     }
@@ -288,13 +532,13 @@ impl ReturnRepository for PostgresReturnRepository {
             .pool
             .begin()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Get current return
-        let (mut return_entity, mut lines) = self
+        let (mut return_entity, _) = self
             .find_by_id_with_tx(&mut tx, id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Return {} not found", id)))?;
+            .ok_or_else(|| DomainError::NotFound(format!("Return {} not found", id).into()))?;
 
         // Process the return (this validates and creates stock movements)
         let stock_movements = return_entity.process(process_request.lines.clone())?;
@@ -304,7 +548,7 @@ impl ReturnRepository for PostgresReturnRepository {
             r#"
             UPDATE returns
             SET status = $2, updated_at = $3
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             return_entity.id,
             return_entity.status.as_str(),
@@ -312,52 +556,76 @@ impl ReturnRepository for PostgresReturnRepository {
         )
         .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Update return lines with received quantities
         for line in &return_entity.lines {
             sqlx::query!(
                 r#"
                 UPDATE return_lines
-                SET quantity_received = $2, updated_at = $3
-                WHERE id = $1
+                SET quantity_received = $2, disposition = $3, updated_at = $4
+                WHERE id = $1 AND tenant_id = get_current_tenant_id()
                 "#,
                 line.id,
                 line.quantity_received,
+                line.disposition.as_ref().map(ReturnDisposition::as_str),
                 line.updated_at
             )
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
         }
 
-        // Insert stock movements
-        for movement in &stock_movements {
-            sqlx::query!(
-                r#"
-                INSERT INTO stock_movements (id, item_id, location_id, movement_type, quantity, reference_type, reference_id, reason, created_at, created_by)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-                "#,
-                movement.id,
-                movement.item_id,
-                movement.location_id,
-                movement.movement_type.as_str(),
-                movement.quantity,
-                movement.reference_type.as_str(),
-                movement.reference_id,
-                movement.reason,
-                movement.created_at,
-                movement.created_by
-            )
-            .execute(&mut *tx)
-            .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-        }
+        // Record stock movements into the statuses the dispositions call for
+        PostgresStockRepository::record_movements_in_tx(&mut tx, &stock_movements).await?;
 
         tx.commit()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
+        let lines = return_entity.lines.clone();
         Ok((return_entity, lines, stock_movements))
     }
+
+    async fn scrap_totals(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        location_id: Option<Uuid>,
+    ) -> Result<Vec<ScrapTotal>, DomainError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT l.item_id, i.sku, i.name,
+                   SUM(l.quantity_received) AS "quantity_scrapped!",
+                   ROUND(SUM(l.quantity_received * l.unit_price), 2) AS "value!",
+                   COUNT(DISTINCT l.return_id) AS "return_count!"
+            FROM return_lines l
+            JOIN returns r ON r.id = l.return_id
+            JOIN items i ON i.id = l.item_id
+            WHERE l.disposition = 'SCRAP' AND l.tenant_id = get_current_tenant_id()
+              AND ($1::timestamptz IS NULL OR l.updated_at >= $1)
+              AND ($2::timestamptz IS NULL OR l.updated_at < $2)
+              AND ($3::uuid IS NULL OR r.location_id = $3)
+            GROUP BY l.item_id, i.sku, i.name
+            ORDER BY 5 DESC, i.sku
+            "#,
+            from,
+            to,
+            location_id
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ScrapTotal {
+                item_id: row.item_id,
+                sku: row.sku,
+                name: row.name,
+                quantity_scrapped: row.quantity_scrapped,
+                value: row.value,
+                return_count: row.return_count,
+            })
+            .collect())
+    }
 }
@@ -1,14 +1,51 @@
+use crate::domain::entities::allocation::{split_shipment, SalesOrderAllocation};
+use crate::domain::entities::list_filter::ListFilter;
 use crate::domain::entities::sales_order::{
-    SalesOrder, SalesOrderLine, SalesOrderStatus, ShipLineRequest, StockMovement,
+    SalesOrder, SalesOrderBackorder, SalesOrderLine, SalesOrderStatus, ShipLineRequest,
+    ShippedSalesOrder, StockMovement,
 };
+use crate::domain::entities::shipment::Shipment;
 use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::infrastructure::repositories::list_filter_sql::{push_filters, ListColumns, ListQuery};
+use crate::infrastructure::repositories::postgres_allocation_repository::PostgresAllocationRepository;
+use crate::infrastructure::repositories::postgres_item_availability_repository::PostgresItemAvailabilityRepository;
+use crate::infrastructure::repositories::postgres_reservation_repository::PostgresReservationRepository;
+use crate::infrastructure::repositories::postgres_shipment_repository::PostgresShipmentRepository;
+use crate::infrastructure::repositories::postgres_stock_repository::PostgresStockRepository;
+use crate::infrastructure::repositories::postgres_webhook_repository::PostgresWebhookRepository;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{PgPool, QueryBuilder, Row};
 use uuid::Uuid;
 
+use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::sync::Arc;
 
+const SALES_ORDER_LIST_COLUMNS: ListColumns = ListColumns {
+    resource: "sales orders",
+    id: "id",
+    status: Some("status"),
+    created_at: "created_at",
+    supplier_id: None,
+    customer_id: Some("customer_id"),
+    sku: Some(
+        "SELECT i.sku FROM sales_order_lines l JOIN items i ON i.id = l.item_id WHERE l.so_id = sales_orders.id",
+    ),
+    category: Some(
+        "SELECT i.category FROM sales_order_lines l JOIN items i ON i.id = l.item_id WHERE l.so_id = sales_orders.id",
+    ),
+    search: &["so_number"],
+    sortable: &[
+        ("so_number", "so_number", "text"),
+        ("status", "status", "text"),
+        ("total_amount", "total_amount", "numeric"),
+        ("created_at", "created_at", "timestamptz"),
+        ("updated_at", "updated_at", "timestamptz"),
+    ],
+};
+
 pub struct PostgresSalesOrderRepository {
     pool: Arc<PgPool>,
 }
@@ -26,13 +63,13 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
             .pool
             .begin()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Insert sales order
         sqlx::query(
             r#"
-            INSERT INTO sales_orders (id, so_number, customer_id, status, total_amount, fulfillment_location_id, created_by, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO sales_orders (id, so_number, customer_id, status, total_amount, currency, exchange_rate, fulfillment_location_id, created_by, created_at, updated_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, get_current_tenant_id())
             "#,
         )
         .bind(sales_order.id)
@@ -40,26 +77,29 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
         .bind(sales_order.customer_id)
         .bind(sales_order.status.as_str())
         .bind(sales_order.total_amount)
+        .bind(&sales_order.currency)
+        .bind(sales_order.exchange_rate)
         .bind(sales_order.fulfillment_location_id)
         .bind(sales_order.created_by)
         .bind(sales_order.created_at)
         .bind(sales_order.updated_at)
         .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Insert sales order lines
         for line in &sales_order.lines {
             sqlx::query(
                 r#"
-                INSERT INTO sales_order_lines (id, so_id, item_id, qty, unit_price, tax, reserved, created_at, updated_at)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                INSERT INTO sales_order_lines (id, so_id, item_id, qty, qty_shipped, unit_price, tax, reserved, created_at, updated_at, tenant_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, get_current_tenant_id())
                 "#,
             )
             .bind(line.id)
             .bind(line.so_id)
             .bind(line.item_id)
             .bind(line.qty)
+            .bind(line.qty_shipped)
             .bind(line.unit_price)
             .bind(line.tax)
             .bind(line.reserved)
@@ -67,12 +107,12 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
             .bind(line.updated_at)
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
         }
 
         tx.commit()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
         Ok(())
     }
 
@@ -83,20 +123,21 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
         let row = sqlx::query(
             r#"
             SELECT
-                so.id, so.so_number, so.customer_id, so.status, so.total_amount, so.fulfillment_location_id,
-                so.created_by, so.created_at, so.updated_at,
-                sol.id as line_id, sol.item_id, sol.qty, sol.unit_price, sol.tax, sol.reserved,
+                so.id, so.so_number, so.customer_id, so.status, so.total_amount, so.currency, so.exchange_rate,
+                so.fulfillment_location_id,
+                so.cancellation_reason, so.cancelled_at, so.created_by, so.created_at, so.updated_at,
+                sol.id as line_id, sol.item_id, sol.qty, sol.qty_shipped, sol.unit_price, sol.tax, sol.reserved,
                 sol.created_at as line_created_at, sol.updated_at as line_updated_at
             FROM sales_orders so
             LEFT JOIN sales_order_lines sol ON so.id = sol.so_id
-            WHERE so.id = $1
+            WHERE so.id = $1 AND so.tenant_id = get_current_tenant_id()
             ORDER BY sol.created_at
             "#,
         )
         .bind(id)
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         if row.is_empty() {
             return Ok(None);
@@ -109,36 +150,48 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
             if sales_order.is_none() {
                 let status_str: String = r
                     .try_get("status")
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
                 let status = SalesOrderStatus::from_str(&status_str)?;
 
                 sales_order = Some(SalesOrder {
                     id: r
                         .try_get("id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     so_number: r
                         .try_get("so_number")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     customer_id: r
                         .try_get("customer_id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     status,
                     total_amount: r
                         .try_get("total_amount")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    currency: r
+                        .try_get("currency")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    exchange_rate: r
+                        .try_get("exchange_rate")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     fulfillment_location_id: r
                         .try_get("fulfillment_location_id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     lines: Vec::new(), // Will be set later
+                    cancellation_reason: r
+                        .try_get("cancellation_reason")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    cancelled_at: r
+                        .try_get("cancelled_at")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     created_by: r
                         .try_get("created_by")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     created_at: r
                         .try_get("created_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     updated_at: r
                         .try_get("updated_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                 });
             }
 
@@ -148,28 +201,31 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
                     id: line_id,
                     so_id: r
                         .try_get("id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     item_id: r
                         .try_get("item_id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     qty: r
                         .try_get("qty")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    qty_shipped: r
+                        .try_get("qty_shipped")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     unit_price: r
                         .try_get("unit_price")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     tax: r
                         .try_get("tax")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     reserved: r
                         .try_get("reserved")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     created_at: r
                         .try_get("line_created_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     updated_at: r
                         .try_get("line_updated_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                 };
                 lines.push(line);
             }
@@ -190,20 +246,21 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
         let row = sqlx::query(
             r#"
             SELECT
-                so.id, so.so_number, so.customer_id, so.status, so.total_amount, so.fulfillment_location_id,
-                so.created_by, so.created_at, so.updated_at,
-                sol.id as line_id, sol.item_id, sol.qty, sol.unit_price, sol.tax, sol.reserved,
+                so.id, so.so_number, so.customer_id, so.status, so.total_amount, so.currency, so.exchange_rate,
+                so.fulfillment_location_id,
+                so.cancellation_reason, so.cancelled_at, so.created_by, so.created_at, so.updated_at,
+                sol.id as line_id, sol.item_id, sol.qty, sol.qty_shipped, sol.unit_price, sol.tax, sol.reserved,
                 sol.created_at as line_created_at, sol.updated_at as line_updated_at
             FROM sales_orders so
             LEFT JOIN sales_order_lines sol ON so.id = sol.so_id
-            WHERE so.so_number = $1
+            WHERE so.so_number = $1 AND so.tenant_id = get_current_tenant_id()
             ORDER BY sol.created_at
             "#,
         )
         .bind(so_number)
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         if row.is_empty() {
             return Ok(None);
@@ -216,36 +273,48 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
             if sales_order.is_none() {
                 let status_str: String = r
                     .try_get("status")
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
                 let status = SalesOrderStatus::from_str(&status_str)?;
 
                 sales_order = Some(SalesOrder {
                     id: r
                         .try_get("id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     so_number: r
                         .try_get("so_number")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     customer_id: r
                         .try_get("customer_id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     status,
                     total_amount: r
                         .try_get("total_amount")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    currency: r
+                        .try_get("currency")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    exchange_rate: r
+                        .try_get("exchange_rate")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     fulfillment_location_id: r
                         .try_get("fulfillment_location_id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     lines: Vec::new(), // Will be set later
+                    cancellation_reason: r
+                        .try_get("cancellation_reason")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    cancelled_at: r
+                        .try_get("cancelled_at")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     created_by: r
                         .try_get("created_by")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     created_at: r
                         .try_get("created_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     updated_at: r
                         .try_get("updated_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                 });
             }
 
@@ -255,28 +324,31 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
                     id: line_id,
                     so_id: r
                         .try_get("id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     item_id: r
                         .try_get("item_id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     qty: r
                         .try_get("qty")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    qty_shipped: r
+                        .try_get("qty_shipped")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     unit_price: r
                         .try_get("unit_price")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     tax: r
                         .try_get("tax")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     reserved: r
                         .try_get("reserved")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     created_at: r
                         .try_get("line_created_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     updated_at: r
                         .try_get("line_updated_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                 };
                 lines.push(line);
             }
@@ -295,15 +367,16 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
             .pool
             .begin()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Update sales order
-        sqlx::query(
+        let result = sqlx::query(
             r#"
             UPDATE sales_orders
             SET so_number = $2, customer_id = $3, status = $4, total_amount = $5,
-                fulfillment_location_id = $6, updated_at = $7
-            WHERE id = $1
+                fulfillment_location_id = $6, cancellation_reason = $7, cancelled_at = $8,
+                updated_at = $9
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
         )
         .bind(sales_order.id)
@@ -312,30 +385,40 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
         .bind(sales_order.status.as_str())
         .bind(sales_order.total_amount)
         .bind(sales_order.fulfillment_location_id)
+        .bind(&sales_order.cancellation_reason)
+        .bind(sales_order.cancelled_at)
         .bind(sales_order.updated_at)
         .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Sales order {} not found", sales_order.id).into(),
+            ));
+        }
 
         // Delete existing lines and re-insert (simplified approach)
-        sqlx::query("DELETE FROM sales_order_lines WHERE so_id = $1")
+        sqlx::query("DELETE FROM sales_order_lines WHERE so_id = $1 AND tenant_id = get_current_tenant_id()",
+        )
             .bind(sales_order.id)
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Insert updated lines
         for line in &sales_order.lines {
             sqlx::query(
                 r#"
-                INSERT INTO sales_order_lines (id, so_id, item_id, qty, unit_price, tax, reserved, created_at, updated_at)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                INSERT INTO sales_order_lines (id, so_id, item_id, qty, qty_shipped, unit_price, tax, reserved, created_at, updated_at, tenant_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, get_current_tenant_id())
                 "#,
             )
             .bind(line.id)
             .bind(line.so_id)
             .bind(line.item_id)
             .bind(line.qty)
+            .bind(line.qty_shipped)
             .bind(line.unit_price)
             .bind(line.tax)
             .bind(line.reserved)
@@ -343,167 +426,323 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
             .bind(line.updated_at)
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
         }
 
         tx.commit()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
         Ok(())
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        sqlx::query("DELETE FROM sales_orders WHERE id = $1")
-            .bind(id)
-            .execute(&*self.pool)
-            .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let result = sqlx::query(
+            "DELETE FROM sales_orders WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+        )
+        .bind(id)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Sales order {} not found", id).into(),
+            ));
+        }
         Ok(())
     }
 
     async fn list(
         &self,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<(SalesOrder, Vec<SalesOrderLine>)>, DomainError> {
-        let rows = sqlx::query(
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<(SalesOrder, Vec<SalesOrderLine>)>, DomainError> {
+        let query = ListQuery::new(&SALES_ORDER_LIST_COLUMNS, filter, page)?;
+        let mut builder = QueryBuilder::new(format!(
+            "SELECT id, {} FROM sales_orders WHERE tenant_id = get_current_tenant_id()",
+            query.sort_key_column()
+        ));
+        query.push_tail(&mut builder)?;
+
+        let rows = builder
+            .build()
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            let cursor = query.cursor_for(&row)?;
+            if let Some(result) = self.find_by_id(cursor.id).await? {
+                results.push((cursor, result));
+            }
+        }
+
+        Ok(query.page(results))
+    }
+
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError> {
+        let mut builder = QueryBuilder::new(
+            "SELECT COUNT(*) FROM sales_orders WHERE tenant_id = get_current_tenant_id()",
+        );
+        push_filters(&mut builder, filter, &SALES_ORDER_LIST_COLUMNS)?;
+
+        builder
+            .build_query_scalar()
+            .fetch_one(&*self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))
+    }
+    async fn ship_sales_order(
+        &self,
+        id: Uuid,
+        shipped_lines: Vec<ShipLineRequest>,
+        allow_backorder: bool,
+        shipment: Shipment,
+        created_by: Uuid,
+    ) -> Result<ShippedSalesOrder, DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        // Get current sales order
+        let (mut sales_order, _) = self
+            .find_by_id_with_tx(&mut tx, id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Sales order {} not found", id).into()))?;
+
+        let mut allocations: Vec<SalesOrderAllocation> = sqlx::query(
             r#"
-            SELECT
-                so.id, so.so_number, so.customer_id, so.status, so.total_amount, so.fulfillment_location_id,
-                so.created_by, so.created_at, so.updated_at,
-                sol.id as line_id, sol.item_id, sol.qty, sol.unit_price, sol.tax, sol.reserved,
-                sol.created_at as line_created_at, sol.updated_at as line_updated_at
-            FROM sales_orders so
-            LEFT JOIN sales_order_lines sol ON so.id = sol.so_id
-            ORDER BY so.created_at DESC
-            LIMIT $1 OFFSET $2
+            SELECT id, sales_order_id, so_line_id, item_id, location_id, qty_allocated, qty_shipped, created_at, updated_at
+            FROM so_allocations
+            WHERE sales_order_id = $1 AND tenant_id = get_current_tenant_id()
+            ORDER BY created_at, id
+            FOR UPDATE
             "#,
         )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&*self.pool)
-        .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await?
+        .iter()
+        .map(PostgresAllocationRepository::row_to_allocation)
+        .collect::<Result<_, _>>()?;
 
-        let mut result = Vec::new();
-        let mut current_so: Option<(SalesOrder, Vec<SalesOrderLine>)> = None;
+        // Check availability at the fulfillment location; with backorders allowed,
+        // ship what is on hand and leave the rest open on the order
+        let mut shippable_lines = Vec::with_capacity(shipped_lines.len());
+        if !allocations.is_empty() {
+            // Allocated orders ship from each allocated location in turn
+            let mut on_hand: HashMap<(Uuid, Uuid), Decimal> = HashMap::new();
+            for allocation in allocations
+                .iter()
+                .filter(|a| a.remaining_qty() > Decimal::ZERO)
+            {
+                if on_hand.contains_key(&(allocation.item_id, allocation.location_id)) {
+                    continue;
+                }
+                let available = Self::available_to_ship(
+                    &mut tx,
+                    &sales_order,
+                    allocation.item_id,
+                    allocation.location_id,
+                )
+                .await?;
+                on_hand.insert((allocation.item_id, allocation.location_id), available);
+            }
+
+            for ship_request in shipped_lines {
+                let split = split_shipment(&mut allocations, &ship_request, &mut on_hand);
+                let allocated: Decimal = split.iter().map(|s| s.qty_shipped).sum();
+                if allocated < ship_request.qty_shipped && !allow_backorder {
+                    return Err(DomainError::BusinessLogicError(
+                        format!(
+                            "Insufficient allocated stock for line {}: requested {}, available {}",
+                            ship_request.so_line_id, ship_request.qty_shipped, allocated
+                        )
+                        .into(),
+                    ));
+                }
+                shippable_lines.extend(split);
+            }
 
-        for r in rows {
-            let so_id: Uuid = r
-                .try_get("id")
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            if shippable_lines.is_empty() {
+                return Err(DomainError::BusinessLogicError(
+                    "No stock available to ship any of the requested lines".into(),
+                ));
+            }
 
-            if current_so.as_ref().map_or(true, |(so, _)| so.id != so_id) {
-                // Save previous sales order if exists
-                if let Some(so_data) = current_so.take() {
-                    result.push(so_data);
+            for allocation in &allocations {
+                sqlx::query(
+                    "UPDATE so_allocations SET qty_shipped = $2, updated_at = $3 WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+                )
+                .bind(allocation.id)
+                .bind(allocation.qty_shipped)
+                .bind(allocation.updated_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+        } else if let Some(location_id) = sales_order.fulfillment_location_id {
+            for mut ship_request in shipped_lines {
+                let item_id = sales_order
+                    .lines
+                    .iter()
+                    .find(|l| l.id == ship_request.so_line_id)
+                    .map(|l| l.item_id)
+                    .ok_or_else(|| {
+                        DomainError::ValidationError(
+                            format!("Line {} not found", ship_request.so_line_id).into(),
+                        )
+                    })?;
+                if ship_request.location_id.is_some_and(|l| l != location_id) {
+                    return Err(DomainError::ValidationError(
+                        format!(
+                            "Sales order {} ships from its fulfillment location {}",
+                            sales_order.so_number, location_id
+                        )
+                        .into(),
+                    ));
                 }
 
-                // Start new sales order
-                let status_str: String = r
-                    .try_get("status")
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-                let status = SalesOrderStatus::from_str(&status_str)?;
+                let available =
+                    Self::available_to_ship(&mut tx, &sales_order, item_id, location_id).await?;
 
-                current_so = Some((
-                    SalesOrder {
-                        id: so_id,
-                        so_number: r
-                            .try_get("so_number")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                        customer_id: r
-                            .try_get("customer_id")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                        status,
-                        total_amount: r
-                            .try_get("total_amount")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                        fulfillment_location_id: r
-                            .try_get("fulfillment_location_id")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                        lines: Vec::new(),
-                        created_by: r
-                            .try_get("created_by")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                        created_at: r
-                            .try_get("created_at")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                        updated_at: r
-                            .try_get("updated_at")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                    },
-                    Vec::new(),
+                if ship_request.qty_shipped > available {
+                    if !allow_backorder {
+                        return Err(DomainError::BusinessLogicError(
+                            format!(
+                                "Insufficient stock for item {}: requested {}, available {}",
+                                item_id, ship_request.qty_shipped, available
+                            )
+                            .into(),
+                        ));
+                    }
+                    ship_request.qty_shipped = available.max(Decimal::ZERO);
+                }
+
+                if ship_request.qty_shipped > Decimal::ZERO {
+                    shippable_lines.push(ship_request);
+                }
+            }
+
+            if shippable_lines.is_empty() {
+                return Err(DomainError::BusinessLogicError(
+                    "No stock available to ship any of the requested lines".into(),
                 ));
             }
+        } else {
+            shippable_lines = shipped_lines;
+        }
 
-            // Add line if it exists
-            if let Ok(line_id) = r.try_get::<Uuid, _>("line_id") {
-                if let Some((_, lines)) = current_so.as_mut() {
-                    let line = SalesOrderLine {
-                        id: line_id,
-                        so_id,
-                        item_id: r
-                            .try_get("item_id")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                        qty: r
-                            .try_get("qty")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                        unit_price: r
-                            .try_get("unit_price")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                        tax: r
-                            .try_get("tax")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                        reserved: r
-                            .try_get("reserved")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                        created_at: r
-                            .try_get("line_created_at")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                        updated_at: r
-                            .try_get("line_updated_at")
-                            .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
-                    };
-                    lines.push(line);
-                }
+        // Ship the order (this validates and creates stock movements)
+        let reserved_before: Vec<(Uuid, Uuid, Decimal)> = match sales_order.fulfillment_location_id
+        {
+            Some(location_id) => sales_order
+                .lines
+                .iter()
+                .map(|l| {
+                    (
+                        l.item_id,
+                        location_id,
+                        sales_order.reserved_qty(l.item_id, location_id),
+                    )
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let stock_movements = sales_order.ship(shippable_lines)?;
+
+        // Shipped units no longer need to be held in reservation
+        let mut released = Vec::new();
+        for (item_id, location_id, before) in reserved_before {
+            let shipped = before - sales_order.reserved_qty(item_id, location_id);
+            if shipped > Decimal::ZERO && !released.contains(&item_id) {
+                PostgresReservationRepository::release_in_tx(
+                    &mut tx,
+                    item_id,
+                    location_id,
+                    shipped,
+                )
+                .await?;
+                released.push(item_id);
             }
         }
 
-        // Add the last sales order
-        if let Some(so_data) = current_so.take() {
-            result.push(so_data);
+        // Update sales order status
+        sqlx::query(
+            r#"
+            UPDATE sales_orders
+            SET status = $2, updated_at = $3
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(sales_order.id)
+        .bind(sales_order.status.as_str())
+        .bind(sales_order.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        // Update shipped quantities
+        for line in &sales_order.lines {
+            sqlx::query(
+                r#"
+                UPDATE sales_order_lines
+                SET qty_shipped = $2, reserved = $3, updated_at = $4
+                WHERE id = $1 AND tenant_id = get_current_tenant_id()
+                "#,
+            )
+            .bind(line.id)
+            .bind(line.qty_shipped)
+            .bind(line.reserved)
+            .bind(line.updated_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
         }
 
-        Ok(result)
+        // Record the shipped stock leaving the shipping locations
+        PostgresStockRepository::record_movements_in_tx(&mut tx, &stock_movements).await?;
+
+        // The shipment and the events announcing it commit with the stock they
+        // describe; the delivery worker publishes the events once they have
+        let shipped = ShippedSalesOrder::new(sales_order, stock_movements, shipment);
+        PostgresShipmentRepository::insert_in_tx(&mut tx, &shipped.shipment).await?;
+        PostgresWebhookRepository::enqueue_outbox_in_tx(&mut tx, &shipped.events).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        Ok(shipped)
     }
 
-    async fn ship_sales_order(
+    async fn create_backorder(
         &self,
         id: Uuid,
-        shipped_lines: Vec<ShipLineRequest>,
+        so_number: String,
         created_by: Uuid,
-    ) -> Result<(SalesOrder, Vec<SalesOrderLine>, Vec<StockMovement>), DomainError> {
+    ) -> Result<(SalesOrder, SalesOrder, SalesOrderBackorder), DomainError> {
         let mut tx = self
             .pool
             .begin()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
-        // Get current sales order
-        let (mut sales_order, lines) = self
+        let (mut sales_order, _) = self
             .find_by_id_with_tx(&mut tx, id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Sales order {} not found", id)))?;
+            .ok_or_else(|| DomainError::NotFound(format!("Sales order {} not found", id).into()))?;
 
-        // Ship the order (this validates and creates stock movements)
-        let stock_movements = sales_order.ship(shipped_lines)?;
+        let (backorder_so, backorder) = sales_order.create_backorder(so_number, created_by)?;
 
-        // Update sales order status
+        // Close out the original order
         sqlx::query(
             r#"
             UPDATE sales_orders
             SET status = $2, updated_at = $3
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
         )
         .bind(sales_order.id)
@@ -511,36 +750,88 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
         .bind(sales_order.updated_at)
         .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        for line in &sales_order.lines {
+            sqlx::query(
+                r#"
+                UPDATE sales_order_lines
+                SET reserved = $2, updated_at = $3
+                WHERE id = $1 AND tenant_id = get_current_tenant_id()
+                "#,
+            )
+            .bind(line.id)
+            .bind(line.reserved)
+            .bind(line.updated_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+        }
+
+        // Insert the follow-up order for the unshipped quantities
+        sqlx::query(
+            r#"
+            INSERT INTO sales_orders (id, so_number, customer_id, status, total_amount, currency, exchange_rate, fulfillment_location_id, created_by, created_at, updated_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, get_current_tenant_id())
+            "#,
+        )
+        .bind(backorder_so.id)
+        .bind(&backorder_so.so_number)
+        .bind(backorder_so.customer_id)
+        .bind(backorder_so.status.as_str())
+        .bind(backorder_so.total_amount)
+        .bind(&backorder_so.currency)
+        .bind(backorder_so.exchange_rate)
+        .bind(backorder_so.fulfillment_location_id)
+        .bind(backorder_so.created_by)
+        .bind(backorder_so.created_at)
+        .bind(backorder_so.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
-        // Insert stock movements
-        for movement in &stock_movements {
+        for line in &backorder_so.lines {
             sqlx::query(
                 r#"
-                INSERT INTO stock_movements (id, item_id, location_id, movement_type, quantity, reference_type, reference_id, reason, created_at, created_by)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                INSERT INTO sales_order_lines (id, so_id, item_id, qty, qty_shipped, unit_price, tax, reserved, created_at, updated_at, tenant_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, get_current_tenant_id())
                 "#,
             )
-            .bind(movement.id)
-            .bind(movement.item_id)
-            .bind(movement.location_id)
-            .bind(movement.movement_type.as_str())
-            .bind(movement.quantity)
-            .bind(movement.reference_type.as_str())
-            .bind(movement.reference_id)
-            .bind(&movement.reason)
-            .bind(movement.created_at)
-            .bind(movement.created_by)
+            .bind(line.id)
+            .bind(line.so_id)
+            .bind(line.item_id)
+            .bind(line.qty)
+            .bind(line.qty_shipped)
+            .bind(line.unit_price)
+            .bind(line.tax)
+            .bind(line.reserved)
+            .bind(line.created_at)
+            .bind(line.updated_at)
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
         }
 
+        sqlx::query(
+            r#"
+            INSERT INTO sales_order_backorders (id, original_so_id, backorder_so_id, created_by, created_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, get_current_tenant_id())
+            "#,
+        )
+        .bind(backorder.id)
+        .bind(backorder.original_so_id)
+        .bind(backorder.backorder_so_id)
+        .bind(backorder.created_by)
+        .bind(backorder.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
         tx.commit()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
-        Ok((sales_order, lines, stock_movements))
+        Ok((sales_order, backorder_so, backorder))
     }
 
     async fn reserve_inventory(
@@ -552,24 +843,38 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
             .pool
             .begin()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Get current sales order
         let (mut sales_order, _) = self
             .find_by_id_with_tx(&mut tx, id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Sales order {} not found", id)))?;
+            .ok_or_else(|| DomainError::NotFound(format!("Sales order {} not found", id).into()))?;
 
         // Reserve inventory
+        let to_reserve: Vec<(Uuid, Decimal)> = sales_order
+            .lines
+            .iter()
+            .filter(|l| !l.reserved && l.remaining_qty() > Decimal::ZERO)
+            .map(|l| (l.item_id, l.remaining_qty()))
+            .collect();
         let stock_movements = sales_order.reserve_inventory()?;
 
+        // Hold the quantities against available-to-promise; a shortfall rolls back the whole reservation
+        if let Some(location_id) = sales_order.fulfillment_location_id {
+            for (item_id, qty) in to_reserve {
+                PostgresReservationRepository::reserve_in_tx(&mut tx, item_id, location_id, qty)
+                    .await?;
+            }
+        }
+
         // Update line reservations
         for line in &sales_order.lines {
             sqlx::query(
                 r#"
                 UPDATE sales_order_lines
                 SET reserved = $2, updated_at = $3
-                WHERE id = $1
+                WHERE id = $1 AND tenant_id = get_current_tenant_id()
                 "#,
             )
             .bind(line.id)
@@ -577,41 +882,134 @@ impl SalesOrderRepository for PostgresSalesOrderRepository {
             .bind(line.updated_at)
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+        }
+
+        // Reservation entries carry no quantity; they only mark the levels they touch
+        PostgresStockRepository::record_movements_in_tx(&mut tx, &stock_movements).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        Ok(stock_movements)
+    }
+
+    async fn cancel_sales_order(
+        &self,
+        id: Uuid,
+        reason: Option<String>,
+        cancelled_by: Uuid,
+    ) -> Result<(SalesOrder, Vec<StockMovement>), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        let (mut sales_order, _) = self
+            .find_by_id_with_tx(&mut tx, id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Sales order {} not found", id).into()))?;
+
+        // Work out what the order holds before cancelling clears the reserved flags
+        let to_release: Vec<(Uuid, Decimal)> = sales_order
+            .lines
+            .iter()
+            .filter(|l| l.reserved && l.remaining_qty() > Decimal::ZERO)
+            .map(|l| (l.item_id, l.remaining_qty()))
+            .collect();
+        let stock_movements = sales_order.cancel(reason, cancelled_by)?;
+
+        if let Some(location_id) = sales_order.fulfillment_location_id {
+            for (item_id, qty) in to_release {
+                PostgresReservationRepository::release_in_tx(&mut tx, item_id, location_id, qty)
+                    .await?;
+            }
         }
 
-        // Insert stock movements (reservations)
-        for movement in &stock_movements {
+        let released_allocations: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            "DELETE FROM so_allocations WHERE sales_order_id = $1 AND tenant_id = get_current_tenant_id() RETURNING item_id, location_id",
+        )
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+        PostgresItemAvailabilityRepository::refresh_in_tx(&mut tx, &released_allocations).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE sales_orders
+            SET status = $2, cancellation_reason = $3, cancelled_at = $4, updated_at = $5
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(sales_order.id)
+        .bind(sales_order.status.as_str())
+        .bind(&sales_order.cancellation_reason)
+        .bind(sales_order.cancelled_at)
+        .bind(sales_order.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        for line in &sales_order.lines {
             sqlx::query(
                 r#"
-                INSERT INTO stock_movements (id, item_id, location_id, movement_type, quantity, reference_type, reference_id, reason, created_at, created_by)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                UPDATE sales_order_lines
+                SET reserved = $2, updated_at = $3
+                WHERE id = $1 AND tenant_id = get_current_tenant_id()
                 "#,
             )
-            .bind(movement.id)
-            .bind(movement.item_id)
-            .bind(movement.location_id)
-            .bind(movement.movement_type.as_str())
-            .bind(movement.quantity)
-            .bind(movement.reference_type.as_str())
-            .bind(movement.reference_id)
-            .bind(&movement.reason)
-            .bind(movement.created_at)
-            .bind(movement.created_by)
+            .bind(line.id)
+            .bind(line.reserved)
+            .bind(line.updated_at)
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
         }
 
+        PostgresStockRepository::record_movements_in_tx(&mut tx, &stock_movements).await?;
+
         tx.commit()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
-        Ok(stock_movements)
+        Ok((sales_order, stock_movements))
     }
 }
 
 impl PostgresSalesOrderRepository {
+    /// Stock free for this order to ship: available on hand, less what other orders have reserved
+    async fn available_to_ship(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        sales_order: &SalesOrder,
+        item_id: Uuid,
+        location_id: Uuid,
+    ) -> Result<Decimal, DomainError> {
+        let level: Option<(Decimal, Decimal)> = sqlx::query_as(
+            r#"
+            SELECT quantity_on_hand - quantity_quarantine - quantity_damaged, quantity_reserved
+            FROM stock_levels
+            WHERE item_id = $1 AND location_id = $2 AND tenant_id = get_current_tenant_id()
+            FOR UPDATE
+            "#,
+        )
+        .bind(item_id)
+        .bind(location_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(match level {
+            Some((available, reserved)) => {
+                let reserved_by_others =
+                    (reserved - sales_order.reserved_qty(item_id, location_id)).max(Decimal::ZERO);
+                (available - reserved_by_others).max(Decimal::ZERO)
+            }
+            None => Decimal::ZERO,
+        })
+    }
+
     async fn find_by_id_with_tx(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
@@ -620,20 +1018,21 @@ impl PostgresSalesOrderRepository {
         let row = sqlx::query(
             r#"
             SELECT
-                so.id, so.so_number, so.customer_id, so.status, so.total_amount, so.fulfillment_location_id,
-                so.created_by, so.created_at, so.updated_at,
-                sol.id as line_id, sol.item_id, sol.qty, sol.unit_price, sol.tax, sol.reserved,
+                so.id, so.so_number, so.customer_id, so.status, so.total_amount, so.currency, so.exchange_rate,
+                so.fulfillment_location_id,
+                so.cancellation_reason, so.cancelled_at, so.created_by, so.created_at, so.updated_at,
+                sol.id as line_id, sol.item_id, sol.qty, sol.qty_shipped, sol.unit_price, sol.tax, sol.reserved,
                 sol.created_at as line_created_at, sol.updated_at as line_updated_at
             FROM sales_orders so
             LEFT JOIN sales_order_lines sol ON so.id = sol.so_id
-            WHERE so.id = $1
+            WHERE so.id = $1 AND so.tenant_id = get_current_tenant_id()
             ORDER BY sol.created_at
             "#,
         )
         .bind(id)
         .fetch_all(&mut **tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         if row.is_empty() {
             return Ok(None);
@@ -646,36 +1045,48 @@ impl PostgresSalesOrderRepository {
             if sales_order.is_none() {
                 let status_str: String = r
                     .try_get("status")
-                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+                    .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
                 let status = SalesOrderStatus::from_str(&status_str)?;
 
                 sales_order = Some(SalesOrder {
                     id: r
                         .try_get("id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     so_number: r
                         .try_get("so_number")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     customer_id: r
                         .try_get("customer_id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     status,
                     total_amount: r
                         .try_get("total_amount")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    currency: r
+                        .try_get("currency")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    exchange_rate: r
+                        .try_get("exchange_rate")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     fulfillment_location_id: r
                         .try_get("fulfillment_location_id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     lines: Vec::new(), // Will be set later
+                    cancellation_reason: r
+                        .try_get("cancellation_reason")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    cancelled_at: r
+                        .try_get("cancelled_at")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     created_by: r
                         .try_get("created_by")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     created_at: r
                         .try_get("created_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     updated_at: r
                         .try_get("updated_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                 });
             }
 
@@ -685,28 +1096,31 @@ impl PostgresSalesOrderRepository {
                     id: line_id,
                     so_id: r
                         .try_get("id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     item_id: r
                         .try_get("item_id")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     qty: r
                         .try_get("qty")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
+                    qty_shipped: r
+                        .try_get("qty_shipped")
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     unit_price: r
                         .try_get("unit_price")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     tax: r
                         .try_get("tax")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     reserved: r
                         .try_get("reserved")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     created_at: r
                         .try_get("line_created_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                     updated_at: r
                         .try_get("line_updated_at")
-                        .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+                        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?,
                 };
                 lines.push(line);
             }
@@ -720,3 +1134,158 @@ impl PostgresSalesOrderRepository {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::shipment::ShipmentSourceType;
+    use crate::domain::services::shipment_repository::ShipmentRepository;
+    use crate::shared::tenant_scope::with_tenant;
+    use crate::test_support::database::{TestDatabase, SEEDED_USER};
+    use std::str::FromStr;
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn test_ship_sales_order_commits_shipment_and_events_with_the_stock() {
+        let db = TestDatabase::connect().await;
+        let (admin, pool) = (&db.admin, &db.pool);
+        let repository = PostgresSalesOrderRepository::new(Arc::clone(pool));
+        let user = Uuid::from_str(SEEDED_USER).unwrap();
+        let tenant_id = db.create_tenant("Ship saga").await;
+        let location_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO locations (name, code, tenant_id) VALUES ('Ship dock', 'SHIP', $1) RETURNING id",
+        )
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        let item_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO items (sku, name, unit, cost_price, tenant_id)
+             VALUES ('SHIP-1', 'Ship item', 'each', 5, $1) RETURNING id",
+        )
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO stock_levels (item_id, location_id, quantity_on_hand, tenant_id)
+             VALUES ($1, $2, 10, $3)",
+        )
+        .bind(item_id)
+        .bind(location_id)
+        .bind(tenant_id)
+        .execute(admin)
+        .await
+        .unwrap();
+        let order_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO sales_orders (so_number, status, fulfillment_location_id, created_by, tenant_id)
+             VALUES ('SO-SHIP-1', 'CONFIRMED', $1, $2, $3) RETURNING id",
+        )
+        .bind(location_id)
+        .bind(user)
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        let line_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO sales_order_lines (so_id, item_id, qty, unit_price, tenant_id)
+             VALUES ($1, $2, 5, 10, $3) RETURNING id",
+        )
+        .bind(order_id)
+        .bind(item_id)
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+
+        let shipment = || {
+            Shipment::new(
+                format!("SH-{}", Uuid::new_v4().simple()),
+                ShipmentSourceType::SalesOrder,
+                order_id,
+                None,
+                None,
+                None,
+                user,
+            )
+            .unwrap()
+        };
+        let ship = |shipment: Shipment| {
+            with_tenant(
+                tenant_id,
+                repository.ship_sales_order(
+                    order_id,
+                    vec![ShipLineRequest {
+                        so_line_id: line_id,
+                        qty_shipped: Decimal::from(3),
+                        location_id: None,
+                    }],
+                    false,
+                    shipment,
+                    user,
+                ),
+            )
+        };
+        let state = || async {
+            let status: String =
+                sqlx::query_scalar("SELECT status FROM sales_orders WHERE id = $1")
+                    .bind(order_id)
+                    .fetch_one(admin)
+                    .await
+                    .unwrap();
+            let on_hand: Decimal = sqlx::query_scalar(
+                "SELECT quantity_on_hand FROM stock_levels WHERE item_id = $1 AND location_id = $2",
+            )
+            .bind(item_id)
+            .bind(location_id)
+            .fetch_one(admin)
+            .await
+            .unwrap();
+            let events: Vec<String> = sqlx::query_scalar(
+                "SELECT event_type FROM event_outbox WHERE tenant_id = $1 ORDER BY created_at, id",
+            )
+            .bind(tenant_id)
+            .fetch_all(admin)
+            .await
+            .unwrap();
+            (status, on_hand, events)
+        };
+
+        // The shipment record fails last; nothing the ship did before it sticks
+        let mut duplicate = shipment();
+        with_tenant(tenant_id, async {
+            PostgresShipmentRepository::new(Arc::clone(pool))
+                .create(&shipment())
+                .await
+                .unwrap()
+        })
+        .await;
+        duplicate.id = sqlx::query_scalar("SELECT id FROM shipments WHERE tenant_id = $1")
+            .bind(tenant_id)
+            .fetch_one(admin)
+            .await
+            .unwrap();
+        assert!(ship(duplicate).await.is_err());
+        assert_eq!(
+            state().await,
+            ("CONFIRMED".to_string(), Decimal::from(10), vec![])
+        );
+
+        let shipped = ship(shipment()).await.unwrap();
+        assert_eq!(
+            shipped.sales_order.status,
+            SalesOrderStatus::PartiallyShipped
+        );
+        assert_eq!(
+            state().await,
+            (
+                "PARTIALLY_SHIPPED".to_string(),
+                Decimal::from(7),
+                vec![
+                    "SALES_ORDER_UPDATED".to_string(),
+                    "SHIPMENT_CREATED".to_string()
+                ]
+            )
+        );
+    }
+}
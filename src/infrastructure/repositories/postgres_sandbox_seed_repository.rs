@@ -0,0 +1,711 @@
+use crate::domain::entities::inventory::{StockLevel, StockMovement};
+use crate::domain::entities::item::Item;
+use crate::domain::entities::location::Location;
+use crate::domain::entities::sandbox_seed::{SeedDataset, SeedRecord, SeedSummary};
+use crate::domain::services::sandbox_seed_repository::SandboxSeedRepository;
+use crate::infrastructure::repositories::postgres_item_availability_repository::PostgresItemAvailabilityRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use serde_json::Value;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Tables holding a tenant's transactions, in an order that deletes rows before
+/// the rows they reference
+const TRANSACTION_TABLES: &[&str] = &[
+    "webhook_deliveries",
+    "webhook_events",
+    "event_outbox",
+    "entity_status_history",
+    "edi_sales_orders",
+    "edi_documents",
+    "integration_orders",
+    "integration_sync_runs",
+    "stock_adjustments",
+    "payments",
+    "invoices",
+    "vendor_credits",
+    "vendor_return_lines",
+    "vendor_returns",
+    "so_allocations",
+    "sales_order_backorders",
+    "shipment_cartons",
+    "shipments",
+    "sales_order_lines",
+    "sales_orders",
+    "return_lines",
+    "returns",
+    "transfer_lines",
+    "transfers",
+    "cycle_counts",
+    "inbound_carton_lines",
+    "inbound_cartons",
+    "inbound_shipments",
+    "purchase_order_receipt_lines",
+    "purchase_order_receipts",
+    "item_on_order",
+    "purchase_order_lines",
+    "purchase_orders",
+    "item_availability",
+    "stock_levels",
+    "stock_snapshots",
+    "demand_forecasts",
+    "stock_movements",
+    "jobs",
+    "idempotency_keys",
+];
+
+/// Everything else a reset clears once the transactions are gone; users,
+/// invitations, quotas and usage stats stay
+const CONFIGURATION_TABLES: &[&str] = &[
+    "webhooks",
+    "edi_trading_partners",
+    "integration_connectors",
+    "adjustment_reasons",
+    "putaway_rules",
+    "item_attachments",
+    "items",
+    "products",
+    "locations",
+    "search_indexes",
+    "document_settings",
+    "receiving_settings",
+    "supplier_receiving_tolerances",
+    "credit_settings",
+    "customer_credit_limits",
+    "sscc_sequences",
+    "sandbox_seeds",
+];
+
+/// Bulk-loads a sandbox's demo data: one statement per table, binding each
+/// column as an array and expanding it with UNNEST
+pub struct PostgresSandboxSeedRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresSandboxSeedRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    async fn delete_from(
+        tx: &mut Transaction<'_, Postgres>,
+        tables: &[&str],
+    ) -> Result<(), DomainError> {
+        for table in tables {
+            sqlx::query(&format!(
+                "DELETE FROM {} WHERE tenant_id = get_current_tenant_id()",
+                table
+            ))
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn find_record_in(
+        tx: &mut Transaction<'_, Postgres>,
+    ) -> Result<Option<SeedRecord>, DomainError> {
+        let row = sqlx::query(
+            "SELECT overrides, summary, seeded_at FROM sandbox_seeds WHERE tenant_id = get_current_tenant_id()",
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        row.map(|row| {
+            let invalid = |e: serde_json::Error| {
+                DomainError::InfrastructureError(
+                    format!("Invalid sandbox seed record: {}", e).into(),
+                )
+            };
+            Ok(SeedRecord {
+                overrides: serde_json::from_value(row.try_get("overrides")?).map_err(invalid)?,
+                summary: serde_json::from_value(row.try_get("summary")?).map_err(invalid)?,
+                seeded_at: row.try_get("seeded_at")?,
+            })
+        })
+        .transpose()
+    }
+
+    /// Undo what `make_room` did for the previous seed once its rows are gone;
+    /// with everything deleted the counters start again from zero
+    async fn give_back_room(
+        tx: &mut Transaction<'_, Postgres>,
+        previous: Option<&SeedSummary>,
+    ) -> Result<(), DomainError> {
+        let (items, locations, webhooks) = previous
+            .map(|s| (s.items as i32, s.locations as i32, s.webhooks as i32))
+            .unwrap_or_default();
+        sqlx::query(
+            r#"
+            UPDATE tenant_quotas
+            SET max_items = GREATEST(max_items - $1, 0), current_items = 0,
+                max_locations = GREATEST(max_locations - $2, 0), current_locations = 0,
+                max_webhooks = GREATEST(max_webhooks - $3, 0), current_webhooks = 0,
+                updated_at = NOW()
+            WHERE tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(items)
+        .bind(locations)
+        .bind(webhooks)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn save_record(
+        tx: &mut Transaction<'_, Postgres>,
+        record: &SeedRecord,
+    ) -> Result<(), DomainError> {
+        let to_json = |value: serde_json::Result<Value>| {
+            value.map_err(|e| {
+                DomainError::InfrastructureError(
+                    format!("Failed to serialize seed record: {}", e).into(),
+                )
+            })
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO sandbox_seeds (tenant_id, profile, overrides, summary, seeded_at)
+            VALUES (get_current_tenant_id(), $1, $2, $3, $4)
+            ON CONFLICT (tenant_id) DO UPDATE
+            SET profile = EXCLUDED.profile, overrides = EXCLUDED.overrides,
+                summary = EXCLUDED.summary, seeded_at = EXCLUDED.seeded_at
+            "#,
+        )
+        .bind(&record.summary.profile)
+        .bind(to_json(serde_json::to_value(&record.overrides))?)
+        .bind(to_json(serde_json::to_value(&record.summary))?)
+        .bind(record.seeded_at)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn write(
+        tx: &mut Transaction<'_, Postgres>,
+        dataset: &SeedDataset,
+        record: &SeedRecord,
+    ) -> Result<(), DomainError> {
+        Self::make_room(
+            tx,
+            dataset.items.len(),
+            dataset.locations.len(),
+            dataset.webhooks.len(),
+        )
+        .await?;
+        Self::insert_locations(tx, &dataset.locations).await?;
+        Self::insert_items(tx, &dataset.items).await?;
+        Self::insert_orders(tx, dataset).await?;
+        Self::insert_movements(tx, &dataset.movements).await?;
+        Self::insert_stock_levels(tx, &dataset.stock_levels).await?;
+        Self::refresh_availability(tx, dataset).await?;
+        Self::insert_webhooks(tx, dataset).await?;
+        Self::index_for_search(tx, &dataset.items, &dataset.locations).await?;
+        Self::save_record(tx, record).await
+    }
+
+    /// Seeded rows don't count against the sandbox's own allowance: the limits
+    /// grow by what was seeded, and the counters start at it
+    async fn make_room(
+        tx: &mut Transaction<'_, Postgres>,
+        items: usize,
+        locations: usize,
+        webhooks: usize,
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            UPDATE tenant_quotas
+            SET max_items = max_items + $1, current_items = current_items + $1,
+                max_locations = max_locations + $2, current_locations = current_locations + $2,
+                max_webhooks = max_webhooks + $3, current_webhooks = current_webhooks + $3,
+                updated_at = NOW()
+            WHERE tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(items as i32)
+        .bind(locations as i32)
+        .bind(webhooks as i32)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_locations(
+        tx: &mut Transaction<'_, Postgres>,
+        locations: &[Location],
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO locations (id, name, code, address, type, active, created_at, updated_at, tenant_id)
+            SELECT id, name, code, address, type, TRUE, created_at, created_at, get_current_tenant_id()
+            FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::jsonb[], $5::text[], $6::timestamptz[])
+                AS l(id, name, code, address, type, created_at)
+            "#,
+        )
+        .bind(locations.iter().map(|l| l.id).collect::<Vec<_>>())
+        .bind(locations.iter().map(|l| l.name.clone()).collect::<Vec<_>>())
+        .bind(locations.iter().map(|l| l.code.clone()).collect::<Vec<_>>())
+        .bind(
+            locations
+                .iter()
+                .map(|l| serde_json::to_value(&l.address).unwrap_or(Value::Null))
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            locations
+                .iter()
+                .map(|l| l.r#type.as_ref().map(|t| t.as_str().to_string()))
+                .collect::<Vec<_>>(),
+        )
+        .bind(locations.iter().map(|l| l.created_at).collect::<Vec<_>>())
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_items(
+        tx: &mut Transaction<'_, Postgres>,
+        items: &[Item],
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO items (id, sku, name, description, category, unit, barcode, cost_price, sale_price,
+                               reorder_point, reorder_qty, weight, active, created_at, updated_at, tenant_id)
+            SELECT id, sku, name, description, category, unit, barcode, cost_price, sale_price,
+                   reorder_point, reorder_qty, weight, TRUE, created_at, created_at, get_current_tenant_id()
+            FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::text[], $5::text[], $6::text[], $7::text[],
+                        $8::numeric[], $9::numeric[], $10::numeric[], $11::numeric[], $12::float8[], $13::timestamptz[])
+                AS i(id, sku, name, description, category, unit, barcode, cost_price, sale_price,
+                     reorder_point, reorder_qty, weight, created_at)
+            "#,
+        )
+        .bind(items.iter().map(|i| i.id).collect::<Vec<_>>())
+        .bind(items.iter().map(|i| i.sku.clone()).collect::<Vec<_>>())
+        .bind(items.iter().map(|i| i.name.clone()).collect::<Vec<_>>())
+        .bind(items.iter().map(|i| i.description.clone()).collect::<Vec<_>>())
+        .bind(items.iter().map(|i| i.category.clone()).collect::<Vec<_>>())
+        .bind(items.iter().map(|i| i.unit.clone()).collect::<Vec<_>>())
+        .bind(items.iter().map(|i| i.barcode.clone()).collect::<Vec<_>>())
+        .bind(items.iter().map(|i| i.cost_price).collect::<Vec<_>>())
+        .bind(items.iter().map(|i| i.sale_price).collect::<Vec<_>>())
+        .bind(items.iter().map(|i| i.reorder_point).collect::<Vec<_>>())
+        .bind(items.iter().map(|i| i.reorder_qty).collect::<Vec<_>>())
+        .bind(items.iter().map(|i| i.weight).collect::<Vec<_>>())
+        .bind(items.iter().map(|i| i.created_at).collect::<Vec<_>>())
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_orders(
+        tx: &mut Transaction<'_, Postgres>,
+        dataset: &SeedDataset,
+    ) -> Result<(), DomainError> {
+        let pos = &dataset.purchase_orders;
+        sqlx::query(
+            r#"
+            INSERT INTO purchase_orders (id, po_number, supplier_id, status, expected_date, total_amount,
+                                         created_by, created_at, updated_at, tenant_id)
+            SELECT id, po_number, supplier_id, status, expected_date, total_amount,
+                   created_by, created_at, updated_at, get_current_tenant_id()
+            FROM UNNEST($1::uuid[], $2::text[], $3::uuid[], $4::text[], $5::timestamptz[], $6::numeric[],
+                        $7::uuid[], $8::timestamptz[], $9::timestamptz[])
+                AS po(id, po_number, supplier_id, status, expected_date, total_amount,
+                      created_by, created_at, updated_at)
+            "#,
+        )
+        .bind(pos.iter().map(|po| po.id).collect::<Vec<_>>())
+        .bind(pos.iter().map(|po| po.po_number.clone()).collect::<Vec<_>>())
+        .bind(pos.iter().map(|po| po.supplier_id).collect::<Vec<_>>())
+        .bind(pos.iter().map(|po| po.status.to_string()).collect::<Vec<_>>())
+        .bind(pos.iter().map(|po| po.expected_date).collect::<Vec<_>>())
+        .bind(pos.iter().map(|po| po.total_amount).collect::<Vec<_>>())
+        .bind(pos.iter().map(|po| po.created_by).collect::<Vec<_>>())
+        .bind(pos.iter().map(|po| po.created_at).collect::<Vec<_>>())
+        .bind(pos.iter().map(|po| po.updated_at).collect::<Vec<_>>())
+        .execute(&mut **tx)
+        .await?;
+
+        let po_lines: Vec<_> = pos
+            .iter()
+            .flat_map(|po| po.lines.iter().map(move |line| (po, line)))
+            .collect();
+        sqlx::query(
+            r#"
+            INSERT INTO purchase_order_lines (id, po_id, item_id, qty_ordered, qty_received, unit_cost, line_total,
+                                              expected_date, created_at, updated_at, tenant_id)
+            SELECT id, po_id, item_id, qty_ordered, qty_received, unit_cost, line_total,
+                   expected_date, created_at, updated_at, get_current_tenant_id()
+            FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::numeric[], $5::numeric[], $6::numeric[], $7::numeric[],
+                        $8::timestamptz[], $9::timestamptz[], $10::timestamptz[])
+                AS l(id, po_id, item_id, qty_ordered, qty_received, unit_cost, line_total, expected_date,
+                     created_at, updated_at)
+            "#,
+        )
+        .bind(po_lines.iter().map(|(_, l)| l.id).collect::<Vec<_>>())
+        .bind(po_lines.iter().map(|(_, l)| l.po_id).collect::<Vec<_>>())
+        .bind(po_lines.iter().map(|(_, l)| l.item_id).collect::<Vec<_>>())
+        .bind(po_lines.iter().map(|(_, l)| l.qty_ordered).collect::<Vec<_>>())
+        .bind(po_lines.iter().map(|(_, l)| l.qty_received).collect::<Vec<_>>())
+        .bind(po_lines.iter().map(|(_, l)| l.unit_cost).collect::<Vec<_>>())
+        .bind(po_lines.iter().map(|(_, l)| l.line_total).collect::<Vec<_>>())
+        .bind(po_lines.iter().map(|(_, l)| l.expected_date).collect::<Vec<_>>())
+        .bind(po_lines.iter().map(|(po, _)| po.created_at).collect::<Vec<_>>())
+        .bind(po_lines.iter().map(|(po, _)| po.updated_at).collect::<Vec<_>>())
+        .execute(&mut **tx)
+        .await?;
+
+        let orders = &dataset.sales_orders;
+        sqlx::query(
+            r#"
+            INSERT INTO sales_orders (id, so_number, customer_id, status, total_amount, fulfillment_location_id,
+                                      created_by, created_at, updated_at, tenant_id)
+            SELECT id, so_number, customer_id, status, total_amount, fulfillment_location_id,
+                   created_by, created_at, updated_at, get_current_tenant_id()
+            FROM UNNEST($1::uuid[], $2::text[], $3::uuid[], $4::text[], $5::numeric[], $6::uuid[],
+                        $7::uuid[], $8::timestamptz[], $9::timestamptz[])
+                AS so(id, so_number, customer_id, status, total_amount, fulfillment_location_id,
+                      created_by, created_at, updated_at)
+            "#,
+        )
+        .bind(orders.iter().map(|so| so.id).collect::<Vec<_>>())
+        .bind(orders.iter().map(|so| so.so_number.clone()).collect::<Vec<_>>())
+        .bind(orders.iter().map(|so| so.customer_id).collect::<Vec<_>>())
+        .bind(
+            orders
+                .iter()
+                .map(|so| so.status.as_str().to_string())
+                .collect::<Vec<_>>(),
+        )
+        .bind(orders.iter().map(|so| so.total_amount).collect::<Vec<_>>())
+        .bind(
+            orders
+                .iter()
+                .map(|so| so.fulfillment_location_id)
+                .collect::<Vec<_>>(),
+        )
+        .bind(orders.iter().map(|so| so.created_by).collect::<Vec<_>>())
+        .bind(orders.iter().map(|so| so.created_at).collect::<Vec<_>>())
+        .bind(orders.iter().map(|so| so.updated_at).collect::<Vec<_>>())
+        .execute(&mut **tx)
+        .await?;
+
+        let so_lines: Vec<_> = orders.iter().flat_map(|so| so.lines.iter()).collect();
+        sqlx::query(
+            r#"
+            INSERT INTO sales_order_lines (id, so_id, item_id, qty, qty_shipped, unit_price, tax, reserved,
+                                           created_at, updated_at, tenant_id)
+            SELECT id, so_id, item_id, qty, qty_shipped, unit_price, tax, reserved,
+                   created_at, updated_at, get_current_tenant_id()
+            FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::numeric[], $5::numeric[], $6::numeric[], $7::numeric[],
+                        $8::bool[], $9::timestamptz[], $10::timestamptz[])
+                AS l(id, so_id, item_id, qty, qty_shipped, unit_price, tax, reserved, created_at, updated_at)
+            "#,
+        )
+        .bind(so_lines.iter().map(|l| l.id).collect::<Vec<_>>())
+        .bind(so_lines.iter().map(|l| l.so_id).collect::<Vec<_>>())
+        .bind(so_lines.iter().map(|l| l.item_id).collect::<Vec<_>>())
+        .bind(so_lines.iter().map(|l| l.qty).collect::<Vec<_>>())
+        .bind(so_lines.iter().map(|l| l.qty_shipped).collect::<Vec<_>>())
+        .bind(so_lines.iter().map(|l| l.unit_price).collect::<Vec<_>>())
+        .bind(so_lines.iter().map(|l| l.tax).collect::<Vec<_>>())
+        .bind(so_lines.iter().map(|l| l.reserved).collect::<Vec<_>>())
+        .bind(so_lines.iter().map(|l| l.created_at).collect::<Vec<_>>())
+        .bind(so_lines.iter().map(|l| l.updated_at).collect::<Vec<_>>())
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_movements(
+        tx: &mut Transaction<'_, Postgres>,
+        movements: &[StockMovement],
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO stock_movements (id, item_id, location_id, movement_type, quantity, reference_type,
+                                         reference_id, reason, created_at, created_by, stock_status, tenant_id)
+            SELECT id, item_id, location_id, movement_type, quantity, reference_type,
+                   reference_id, reason, created_at, created_by, 'AVAILABLE', get_current_tenant_id()
+            FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::text[], $5::numeric[], $6::text[],
+                        $7::uuid[], $8::text[], $9::timestamptz[], $10::uuid[])
+                AS m(id, item_id, location_id, movement_type, quantity, reference_type,
+                     reference_id, reason, created_at, created_by)
+            "#,
+        )
+        .bind(movements.iter().map(|m| m.id).collect::<Vec<_>>())
+        .bind(movements.iter().map(|m| m.item_id).collect::<Vec<_>>())
+        .bind(movements.iter().map(|m| m.location_id).collect::<Vec<_>>())
+        .bind(
+            movements
+                .iter()
+                .map(|m| m.movement_type.as_str().to_string())
+                .collect::<Vec<_>>(),
+        )
+        .bind(movements.iter().map(|m| m.quantity).collect::<Vec<_>>())
+        .bind(
+            movements
+                .iter()
+                .map(|m| m.reference_type.as_str().to_string())
+                .collect::<Vec<_>>(),
+        )
+        .bind(movements.iter().map(|m| m.reference_id).collect::<Vec<_>>())
+        .bind(movements.iter().map(|m| m.reason.clone()).collect::<Vec<_>>())
+        .bind(movements.iter().map(|m| m.created_at).collect::<Vec<_>>())
+        .bind(movements.iter().map(|m| m.created_by).collect::<Vec<_>>())
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_stock_levels(
+        tx: &mut Transaction<'_, Postgres>,
+        levels: &[StockLevel],
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO stock_levels (item_id, location_id, quantity_on_hand, quantity_reserved,
+                                      last_movement_id, updated_at, tenant_id)
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved,
+                   last_movement_id, updated_at, get_current_tenant_id()
+            FROM UNNEST($1::uuid[], $2::uuid[], $3::numeric[], $4::numeric[], $5::uuid[], $6::timestamptz[])
+                AS s(item_id, location_id, quantity_on_hand, quantity_reserved, last_movement_id, updated_at)
+            "#,
+        )
+        .bind(levels.iter().map(|s| s.item_id).collect::<Vec<_>>())
+        .bind(levels.iter().map(|s| s.location_id).collect::<Vec<_>>())
+        .bind(levels.iter().map(|s| s.quantity_on_hand).collect::<Vec<_>>())
+        .bind(levels.iter().map(|s| s.quantity_reserved).collect::<Vec<_>>())
+        .bind(levels.iter().map(|s| s.last_movement_id).collect::<Vec<_>>())
+        .bind(levels.iter().map(|s| s.updated_at).collect::<Vec<_>>())
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn refresh_availability(
+        tx: &mut Transaction<'_, Postgres>,
+        dataset: &SeedDataset,
+    ) -> Result<(), DomainError> {
+        let keys: Vec<(Uuid, Uuid)> = dataset
+            .stock_levels
+            .iter()
+            .map(|level| (level.item_id, level.location_id))
+            .collect();
+        PostgresItemAvailabilityRepository::refresh_in_tx(tx, &keys).await?;
+        let item_ids: Vec<Uuid> = dataset
+            .purchase_orders
+            .iter()
+            .flat_map(|po| po.lines.iter().map(|line| line.item_id))
+            .collect();
+        PostgresItemAvailabilityRepository::refresh_on_order_in_tx(tx, &item_ids).await
+    }
+
+    async fn insert_webhooks(
+        tx: &mut Transaction<'_, Postgres>,
+        dataset: &SeedDataset,
+    ) -> Result<(), DomainError> {
+        for webhook in &dataset.webhooks {
+            sqlx::query(
+                r#"
+                INSERT INTO webhooks (id, url, secret, events, status, created_by, created_at, updated_at, tenant_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $7, get_current_tenant_id())
+                "#,
+            )
+            .bind(webhook.id)
+            .bind(&webhook.url)
+            .bind(&webhook.secret)
+            .bind(
+                webhook
+                    .events
+                    .iter()
+                    .map(|e| e.as_str().to_string())
+                    .collect::<Vec<_>>(),
+            )
+            .bind(webhook.status.as_str())
+            .bind(webhook.created_by)
+            .bind(webhook.created_at)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Index the catalogue and locations the way the search projection would
+    /// once they saw their first stock movement
+    async fn index_for_search(
+        tx: &mut Transaction<'_, Postgres>,
+        items: &[Item],
+        locations: &[Location],
+    ) -> Result<(), DomainError> {
+        let mut entity_types = Vec::new();
+        let mut entity_ids = Vec::new();
+        let mut contents = Vec::new();
+        let mut metadata = Vec::new();
+        for item in items {
+            entity_types.push("item".to_string());
+            entity_ids.push(item.id);
+            contents.push(format!(
+                "{} {} {} {} {}",
+                item.name,
+                item.sku,
+                item.description.as_deref().unwrap_or(""),
+                item.category.as_deref().unwrap_or(""),
+                item.unit
+            ));
+            metadata.push(serde_json::json!({
+                "type": "item",
+                "sku": item.sku,
+                "name": item.name,
+                "category": item.category,
+                "unit": item.unit,
+                "active": item.active
+            }));
+        }
+        for location in locations {
+            entity_types.push("location".to_string());
+            entity_ids.push(location.id);
+            contents.push(format!(
+                "{} {} {} {}",
+                location.name,
+                location.code.as_deref().unwrap_or(""),
+                location
+                    .address
+                    .as_ref()
+                    .map(|address| address.lines().join(" "))
+                    .unwrap_or_default(),
+                location.r#type.as_ref().map(|t| t.as_str()).unwrap_or("")
+            ));
+            metadata.push(serde_json::json!({
+                "type": "location",
+                "name": location.name,
+                "code": location.code,
+                "location_type": location.r#type.as_ref().map(|t| t.as_str()),
+                "active": location.active
+            }));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO search_indexes (entity_type, entity_id, search_vector, metadata, updated_at, tenant_id)
+            SELECT entity_type, entity_id, to_tsvector('english', content), metadata, NOW(), get_current_tenant_id()
+            FROM UNNEST($1::text[], $2::uuid[], $3::text[], $4::jsonb[])
+                AS s(entity_type, entity_id, content, metadata)
+            "#,
+        )
+        .bind(entity_types)
+        .bind(entity_ids)
+        .bind(contents)
+        .bind(metadata)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Loading a load-test dataset, as the `seed` command does: the same inserts as
+/// a sandbox seed, but a batch per transaction so millions of rows can be
+/// written without one huge statement
+impl PostgresSandboxSeedRepository {
+    /// Write the catalogue and its search entries, raising the tenant's limits
+    /// to hold it
+    pub async fn load_catalogue(
+        &self,
+        locations: &[Location],
+        items: &[Item],
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+        Self::make_room(&mut tx, items.len(), locations.len(), 0).await?;
+        Self::insert_locations(&mut tx, locations).await?;
+        Self::insert_items(&mut tx, items).await?;
+        Self::index_for_search(&mut tx, items, locations).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn load_movements(&self, movements: &[StockMovement]) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+        Self::insert_movements(&mut tx, movements).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Write levels once every movement they name has been loaded, with the
+    /// item availability they add up to
+    pub async fn load_stock_levels(&self, levels: &[StockLevel]) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+        Self::insert_stock_levels(&mut tx, levels).await?;
+        let keys: Vec<(Uuid, Uuid)> = levels
+            .iter()
+            .map(|level| (level.item_id, level.location_id))
+            .collect();
+        PostgresItemAvailabilityRepository::refresh_in_tx(&mut tx, &keys).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SandboxSeedRepository for PostgresSandboxSeedRepository {
+    async fn seed(&self, dataset: &SeedDataset, record: &SeedRecord) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+        Self::write(&mut tx, dataset, record).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn reseed(&self, dataset: &SeedDataset, record: &SeedRecord) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+        let previous = Self::find_record_in(&mut tx).await?;
+        Self::delete_from(&mut tx, TRANSACTION_TABLES).await?;
+        Self::delete_from(&mut tx, CONFIGURATION_TABLES).await?;
+        Self::give_back_room(&mut tx, previous.as_ref().map(|r| &r.summary)).await?;
+        Self::write(&mut tx, dataset, record).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_record(&self) -> Result<Option<SeedRecord>, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        let record = Self::find_record_in(&mut tx).await?;
+        tx.commit().await?;
+        Ok(record)
+    }
+
+    async fn clear_transactions(&self, echo_url: &str) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+        Self::delete_from(&mut tx, TRANSACTION_TABLES).await?;
+        sqlx::query(
+            "DELETE FROM search_indexes WHERE entity_type = 'stock_level' AND tenant_id = get_current_tenant_id()",
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let echo_webhooks = sqlx::query(
+            "DELETE FROM webhooks WHERE starts_with(url, $1) AND tenant_id = get_current_tenant_id()",
+        )
+        .bind(echo_url)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        sqlx::query(
+            r#"
+            UPDATE tenant_quotas
+            SET current_webhooks = GREATEST(current_webhooks - $1, 0), updated_at = NOW()
+            WHERE tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(echo_webhooks as i32)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM sandbox_seeds WHERE tenant_id = get_current_tenant_id()")
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+}
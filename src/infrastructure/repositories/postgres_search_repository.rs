@@ -1,14 +1,123 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{PgConnection, PgPool, Postgres, Row, Transaction};
+use uuid::Uuid;
 
 use crate::domain::entities::search::{
-    SearchIndex, SearchIndexRequest, SearchQuery, SearchResult, SearchResultItem,
+    SearchIndex, SearchIndexHealth, SearchIndexRequest, SearchIndexStatus, SearchQuery,
+    SearchResult, SearchResultItem, SuggestQuery, Suggestion,
 };
 use crate::domain::services::search_repository::SearchRepository;
 use crate::shared::error::DomainError;
 
+/// Trigram word similarity a document needs to match a search, and a
+/// suggestion. Autocomplete input is partial, so it gets more leeway.
+const SEARCH_SIMILARITY_THRESHOLD: &str = "0.4";
+const SUGGEST_SIMILARITY_THRESHOLD: &str = "0.3";
+
+/// Every document the index should hold, across tenants: one per active item,
+/// active location and stock level of both. Metadata carries the source rows'
+/// `updated_at`, so an indexed document whose metadata differs is stale.
+const LIVE_DOCUMENTS: &str = r#"
+    SELECT 'item'::text AS entity_type, i.id AS entity_id, i.tenant_id,
+        i.id AS item_id, NULL::uuid AS location_id,
+        concat_ws(' ', i.name, i.sku, i.barcode, i.description, i.category, i.unit) AS content,
+        jsonb_build_object('type', 'item', 'sku', i.sku, 'name', i.name, 'category', i.category,
+            'unit', i.unit, 'active', i.active, 'source_updated_at', i.updated_at) AS metadata
+    FROM items i
+    WHERE i.active
+    UNION ALL
+    SELECT 'location', l.id, l.tenant_id, NULL, l.id,
+        concat_ws(' ', l.name, l.code, l.address->>'line1', l.address->>'city',
+            l.address->>'region', l.address->>'postal_code', l.address->>'country', l.type),
+        jsonb_build_object('type', 'location', 'name', l.name, 'code', l.code,
+            'location_type', l.type, 'active', l.active, 'source_updated_at', l.updated_at)
+    FROM locations l
+    WHERE l.active
+    UNION ALL
+    SELECT 'stock_level', md5(s.item_id::text || ':' || s.location_id::text)::uuid, s.tenant_id,
+        s.item_id, s.location_id,
+        concat_ws(' ', i.name, i.sku, l.name, l.code, i.category, 'stock level'),
+        jsonb_build_object('type', 'stock_level', 'item_id', i.id, 'item_sku', i.sku,
+            'item_name', i.name, 'location_id', l.id, 'location_name', l.name,
+            'location_code', l.code, 'quantity', s.quantity_on_hand,
+            'source_updated_at', GREATEST(s.updated_at, i.updated_at, l.updated_at))
+    FROM stock_levels s
+    JOIN items i ON i.id = s.item_id AND i.active
+    JOIN locations l ON l.id = s.location_id AND l.active
+"#;
+
+/// A row of `LIVE_DOCUMENTS`
+#[derive(Debug, Clone)]
+pub(crate) struct LiveDocument {
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub tenant_id: Uuid,
+    pub item_id: Option<Uuid>,
+    pub location_id: Option<Uuid>,
+    /// Searchable text
+    pub content: String,
+    pub metadata: serde_json::Value,
+}
+
+/// What a refresh wrote to, and dropped from, `search_indexes`
+#[derive(Debug, Default)]
+pub(crate) struct DocumentChanges {
+    pub upserted: Vec<LiveDocument>,
+    /// `(entity_type, entity_id)` of each document dropped
+    pub removed: Vec<(String, Uuid)>,
+}
+
+impl DocumentChanges {
+    pub fn len(&self) -> u64 {
+        (self.upserted.len() + self.removed.len()) as u64
+    }
+}
+
+/// Which documents a refresh covers. Ids bind as `$1`.
+pub(crate) enum Scope<'a> {
+    Items(&'a [Uuid]),
+    Locations(&'a [Uuid]),
+    Tenant,
+    All,
+}
+
+impl Scope<'_> {
+    fn ids(&self) -> &[Uuid] {
+        match self {
+            Scope::Items(ids) | Scope::Locations(ids) => ids,
+            Scope::Tenant | Scope::All => &[],
+        }
+    }
+
+    /// Filter on a `LIVE_DOCUMENTS` row `d`
+    fn live(&self) -> &'static str {
+        match self {
+            Scope::Items(_) => "d.tenant_id = get_current_tenant_id() AND d.item_id = ANY($1)",
+            Scope::Locations(_) => {
+                "d.tenant_id = get_current_tenant_id() AND d.location_id = ANY($1)"
+            }
+            Scope::Tenant => "d.tenant_id = get_current_tenant_id()",
+            Scope::All => "TRUE",
+        }
+    }
+
+    /// Filter on an indexed document `si`
+    fn indexed(&self) -> &'static str {
+        match self {
+            Scope::Items(_) => {
+                "si.tenant_id = get_current_tenant_id() AND ((si.entity_type = 'item' AND si.entity_id = ANY($1)) OR (si.entity_type = 'stock_level' AND si.metadata->>'item_id' = ANY($1::text[])))"
+            }
+            Scope::Locations(_) => {
+                "si.tenant_id = get_current_tenant_id() AND ((si.entity_type = 'location' AND si.entity_id = ANY($1)) OR (si.entity_type = 'stock_level' AND si.metadata->>'location_id' = ANY($1::text[])))"
+            }
+            Scope::Tenant => "si.tenant_id = get_current_tenant_id()",
+            Scope::All => "TRUE",
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct PostgresSearchRepository {
     pool: Arc<PgPool>,
@@ -18,6 +127,105 @@ impl PostgresSearchRepository {
     pub fn new(pool: Arc<PgPool>) -> Self {
         Self { pool }
     }
+
+    /// Transaction in which `<%` matches at `threshold` word similarity
+    async fn begin_fuzzy(
+        pool: &PgPool,
+        threshold: &str,
+    ) -> Result<Transaction<'static, Postgres>, DomainError> {
+        let mut tx = pool.begin().await?;
+        sqlx::query("SELECT set_config('pg_trgm.word_similarity_threshold', $1, true)")
+            .bind(threshold)
+            .execute(&mut *tx)
+            .await?;
+        Ok(tx)
+    }
+
+    /// Upsert the scope's stale documents and drop the ones whose source is
+    /// gone, returning what changed
+    pub(crate) async fn refresh(
+        conn: &mut PgConnection,
+        scope: Scope<'_>,
+    ) -> Result<DocumentChanges, DomainError> {
+        let upserted = sqlx::query(&format!(
+            r#"
+            WITH stale AS MATERIALIZED (
+                SELECT d.*
+                FROM ({LIVE_DOCUMENTS}) d
+                WHERE {}
+                  AND NOT EXISTS (
+                      SELECT 1 FROM search_indexes si
+                      WHERE si.entity_type = d.entity_type AND si.entity_id = d.entity_id
+                        AND si.metadata = d.metadata
+                  )
+            ), written AS (
+                INSERT INTO search_indexes (entity_type, entity_id, search_vector, metadata, updated_at, tenant_id)
+                SELECT entity_type, entity_id, to_tsvector('english', content), metadata, NOW(), tenant_id
+                FROM stale
+                ON CONFLICT (entity_type, entity_id)
+                DO UPDATE SET
+                    search_vector = EXCLUDED.search_vector,
+                    metadata = EXCLUDED.metadata,
+                    updated_at = NOW()
+            )
+            SELECT entity_type, entity_id, tenant_id, item_id, location_id, content, metadata
+            FROM stale
+            "#,
+            scope.live()
+        ))
+        .bind(scope.ids())
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .map(|row| {
+            Ok(LiveDocument {
+                entity_type: row.try_get("entity_type")?,
+                entity_id: row.try_get("entity_id")?,
+                tenant_id: row.try_get("tenant_id")?,
+                item_id: row.try_get("item_id")?,
+                location_id: row.try_get("location_id")?,
+                content: row.try_get("content")?,
+                metadata: row.try_get("metadata")?,
+            })
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
+
+        let removed = sqlx::query(&format!(
+            r#"
+            DELETE FROM search_indexes si
+            WHERE si.entity_type IN ('item', 'location', 'stock_level')
+              AND {}
+              AND NOT EXISTS (
+                  SELECT 1 FROM ({LIVE_DOCUMENTS}) d
+                  WHERE d.entity_type = si.entity_type AND d.entity_id = si.entity_id
+              )
+            RETURNING si.entity_type, si.entity_id
+            "#,
+            scope.indexed()
+        ))
+        .bind(scope.ids())
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .map(|row| Ok((row.try_get("entity_type")?, row.try_get("entity_id")?)))
+        .collect::<Result<Vec<_>, DomainError>>()?;
+
+        Ok(DocumentChanges { upserted, removed })
+    }
+
+    /// Drop the current tenant's item, location and stock level documents
+    pub(crate) async fn clear_tenant(conn: &mut PgConnection) -> Result<u64, DomainError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM search_indexes
+            WHERE entity_type IN ('item', 'location', 'stock_level')
+              AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
 }
 
 #[async_trait]
@@ -26,11 +234,11 @@ impl SearchRepository for PostgresSearchRepository {
         // Use PostgreSQL's to_tsvector function to create the search vector
         let result = sqlx::query(
             r#"
-            INSERT INTO search_indexes (entity_type, entity_id, search_vector, metadata, updated_at)
-            VALUES ($1, $2, to_tsvector('english', $3), $4, NOW())
+            INSERT INTO search_indexes (entity_type, entity_id, search_vector, metadata, updated_at, tenant_id)
+            VALUES ($1, $2, to_tsvector('english', $3), $4, NOW(), get_current_tenant_id())
             ON CONFLICT (entity_type, entity_id)
             DO UPDATE SET
-                search_vector = to_tsvector('english', EXCLUDED.search_vector),
+                search_vector = EXCLUDED.search_vector,
                 metadata = EXCLUDED.metadata,
                 updated_at = NOW()
             "#,
@@ -41,11 +249,11 @@ impl SearchRepository for PostgresSearchRepository {
         .bind(&request.metadata)
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Failed to index document: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Failed to index document: {}", e).into()))?;
 
         if result.rows_affected() == 0 {
             return Err(DomainError::ValidationError(
-                "Failed to index document".to_string(),
+                "Failed to index document".into(),
             ));
         }
 
@@ -57,13 +265,14 @@ impl SearchRepository for PostgresSearchRepository {
         entity_type: &str,
         entity_id: uuid::Uuid,
     ) -> Result<(), DomainError> {
-        sqlx::query("DELETE FROM search_indexes WHERE entity_type = $1 AND entity_id = $2")
+        sqlx::query("DELETE FROM search_indexes WHERE entity_type = $1 AND entity_id = $2 AND tenant_id = get_current_tenant_id()",
+        )
             .bind(entity_type)
             .bind(entity_id)
             .execute(&*self.pool)
             .await
             .map_err(|e| {
-                DomainError::ValidationError(format!("Failed to remove document: {}", e))
+                DomainError::ValidationError(format!("Failed to remove document: {}", e).into())
             })?;
 
         Ok(())
@@ -72,74 +281,64 @@ impl SearchRepository for PostgresSearchRepository {
     async fn search(&self, query: SearchQuery) -> Result<SearchResult, DomainError> {
         let limit = query.limit.unwrap_or(50).min(1000); // Max 1000 results
         let offset = query.offset.unwrap_or(0).max(0);
+        let entity_types = query
+            .entity_types
+            .as_deref()
+            .filter(|types| !types.is_empty());
+        let filter = r#"
+            si.tenant_id = get_current_tenant_id()
+            AND ($2::text[] IS NULL OR si.entity_type = ANY($2))
+            AND (si.search_vector @@ websearch_to_tsquery('english', $1) OR lower($1) <% si.search_text)
+        "#;
 
-        let query_builder = if let Some(entity_types) = &query.entity_types {
-            if !entity_types.is_empty() {
-                let sql = r#"
-                    SELECT entity_type, entity_id, ts_rank(search_vector, query) as rank, metadata
-                    FROM search_indexes, to_tsquery('english', $1) as query
-                    WHERE search_vector @@ query AND entity_type = ANY($2)
-                    ORDER BY rank DESC LIMIT $3 OFFSET $4
-                "#;
-                sqlx::query(sql)
-                    .bind(&query.query)
-                    .bind(entity_types.as_slice())
-                    .bind(limit)
-                    .bind(offset)
-            } else {
-                let sql = r#"
-                    SELECT entity_type, entity_id, ts_rank(search_vector, query) as rank, metadata
-                    FROM search_indexes, to_tsquery('english', $1) as query
-                    WHERE search_vector @@ query
-                    ORDER BY rank DESC LIMIT $2 OFFSET $3
-                "#;
-                sqlx::query(sql).bind(&query.query).bind(limit).bind(offset)
-            }
-        } else {
-            let sql = r#"
-                SELECT entity_type, entity_id, ts_rank(search_vector, query) as rank, metadata
-                FROM search_indexes, to_tsquery('english', $1) as query
-                WHERE search_vector @@ query
-                ORDER BY rank DESC LIMIT $2 OFFSET $3
-            "#;
-            sqlx::query(sql).bind(&query.query).bind(limit).bind(offset)
-        };
-
-        let rows = query_builder
-            .fetch_all(&*self.pool)
-            .await
-            .map_err(|e| DomainError::ValidationError(format!("Search failed: {e}")))?;
+        let mut tx = Self::begin_fuzzy(&self.pool, SEARCH_SIMILARITY_THRESHOLD).await?;
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT si.entity_type, si.entity_id, si.metadata,
+                ts_rank(si.search_vector, websearch_to_tsquery('english', $1))
+                    + word_similarity(lower($1), si.search_text) AS rank
+            FROM search_indexes si
+            WHERE {filter}
+            ORDER BY rank DESC, si.entity_id
+            LIMIT $3 OFFSET $4
+            "#
+        ))
+        .bind(&query.query)
+        .bind(entity_types)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ValidationError(format!("Search failed: {e}").into()))?;
 
         let mut results = Vec::new();
         for row in rows {
             results.push(SearchResultItem {
                 entity_type: row
                     .try_get("entity_type")
-                    .map_err(|e| DomainError::ValidationError(e.to_string()))?,
+                    .map_err(|e| DomainError::ValidationError(e.to_string().into()))?,
                 entity_id: row
                     .try_get("entity_id")
-                    .map_err(|e| DomainError::ValidationError(e.to_string()))?,
+                    .map_err(|e| DomainError::ValidationError(e.to_string().into()))?,
                 rank: row
                     .try_get("rank")
-                    .map_err(|e| DomainError::ValidationError(e.to_string()))?,
+                    .map_err(|e| DomainError::ValidationError(e.to_string().into()))?,
                 metadata: row
                     .try_get("metadata")
-                    .map_err(|e| DomainError::ValidationError(e.to_string()))?,
+                    .map_err(|e| DomainError::ValidationError(e.to_string().into()))?,
             });
         }
 
         // Get total count for pagination
-        let count_sql = r#"
-            SELECT COUNT(*) as total
-            FROM search_indexes, to_tsquery('english', $1) as query
-            WHERE search_vector @@ query
-        "#;
-
-        let total: i64 = sqlx::query_scalar(count_sql)
-            .bind(&query.query)
-            .fetch_one(&*self.pool)
-            .await
-            .map_err(|e| DomainError::ValidationError(format!("Count failed: {e}")))?;
+        let total: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM search_indexes si WHERE {filter}"
+        ))
+        .bind(&query.query)
+        .bind(entity_types)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ValidationError(format!("Count failed: {e}").into()))?;
+        tx.commit().await?;
 
         Ok(SearchResult {
             query: query.query,
@@ -148,6 +347,44 @@ impl SearchRepository for PostgresSearchRepository {
         })
     }
 
+    async fn suggest(&self, query: SuggestQuery) -> Result<Vec<Suggestion>, DomainError> {
+        let entity_types = query
+            .entity_types
+            .as_deref()
+            .filter(|types| !types.is_empty());
+
+        let mut tx = Self::begin_fuzzy(&self.pool, SUGGEST_SIMILARITY_THRESHOLD).await?;
+        let rows = sqlx::query(
+            r#"
+            SELECT entity_type, entity_id, label, word_similarity(lower($1), search_text) AS similarity
+            FROM search_indexes
+            WHERE tenant_id = get_current_tenant_id()
+              AND label IS NOT NULL
+              AND ($2::text[] IS NULL OR entity_type = ANY($2))
+              AND lower($1) <% search_text
+            ORDER BY similarity DESC, label, entity_id
+            LIMIT $3
+            "#,
+        )
+        .bind(&query.text)
+        .bind(entity_types)
+        .bind(query.limit)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(Suggestion {
+                    entity_type: row.try_get("entity_type")?,
+                    entity_id: row.try_get("entity_id")?,
+                    text: row.try_get("label")?,
+                    similarity: row.try_get("similarity")?,
+                })
+            })
+            .collect()
+    }
+
     async fn get_document(
         &self,
         entity_type: &str,
@@ -157,39 +394,39 @@ impl SearchRepository for PostgresSearchRepository {
             r#"
             SELECT id, entity_type, entity_id, search_vector::text, metadata, created_at, updated_at
             FROM search_indexes
-            WHERE entity_type = $1 AND entity_id = $2
+            WHERE entity_type = $1 AND entity_id = $2 AND tenant_id = get_current_tenant_id()
             "#,
         )
         .bind(entity_type)
         .bind(entity_id)
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Failed to get document: {e}")))?;
+        .map_err(|e| DomainError::ValidationError(format!("Failed to get document: {e}").into()))?;
 
         match row {
             Some(row) => {
                 let document = SearchIndex {
                     id: row
                         .try_get("id")
-                        .map_err(|e| DomainError::ValidationError(e.to_string()))?,
+                        .map_err(|e| DomainError::ValidationError(e.to_string().into()))?,
                     entity_type: row
                         .try_get("entity_type")
-                        .map_err(|e| DomainError::ValidationError(e.to_string()))?,
+                        .map_err(|e| DomainError::ValidationError(e.to_string().into()))?,
                     entity_id: row
                         .try_get("entity_id")
-                        .map_err(|e| DomainError::ValidationError(e.to_string()))?,
+                        .map_err(|e| DomainError::ValidationError(e.to_string().into()))?,
                     search_vector: row
                         .try_get("search_vector")
-                        .map_err(|e| DomainError::ValidationError(e.to_string()))?,
+                        .map_err(|e| DomainError::ValidationError(e.to_string().into()))?,
                     metadata: row
                         .try_get("metadata")
-                        .map_err(|e| DomainError::ValidationError(e.to_string()))?,
+                        .map_err(|e| DomainError::ValidationError(e.to_string().into()))?,
                     created_at: row
                         .try_get("created_at")
-                        .map_err(|e| DomainError::ValidationError(e.to_string()))?,
+                        .map_err(|e| DomainError::ValidationError(e.to_string().into()))?,
                     updated_at: row
                         .try_get("updated_at")
-                        .map_err(|e| DomainError::ValidationError(e.to_string()))?,
+                        .map_err(|e| DomainError::ValidationError(e.to_string().into()))?,
                 };
                 Ok(Some(document))
             }
@@ -197,19 +434,31 @@ impl SearchRepository for PostgresSearchRepository {
         }
     }
 
-    async fn rebuild_index(&self) -> Result<i64, DomainError> {
-        // This is a heavy operation that rebuilds the entire search index
-        // In a real implementation, you might want to do this in batches
-        let result = sqlx::query("TRUNCATE TABLE search_indexes")
-            .execute(&*self.pool)
-            .await
-            .map_err(|e| {
-                DomainError::ValidationError(format!("Failed to truncate search index: {e}"))
-            })?;
+    async fn index_items(&self, item_ids: &[Uuid]) -> Result<u64, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        Ok(Self::refresh(&mut conn, Scope::Items(item_ids))
+            .await?
+            .len())
+    }
+
+    async fn index_locations(&self, location_ids: &[Uuid]) -> Result<u64, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        Ok(Self::refresh(&mut conn, Scope::Locations(location_ids))
+            .await?
+            .len())
+    }
 
-        // Note: In a real implementation, you would then re-index all entities
-        // For now, we just return the number of deleted documents
-        Ok(result.rows_affected() as i64)
+    async fn sync_stale_documents(&self) -> Result<u64, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        Ok(Self::refresh(&mut conn, Scope::All).await?.len())
+    }
+
+    async fn rebuild_index(&self) -> Result<i64, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        Self::clear_tenant(&mut tx).await?;
+        let indexed = Self::refresh(&mut tx, Scope::Tenant).await?;
+        tx.commit().await?;
+        Ok(indexed.len() as i64)
     }
 
     async fn cleanup_orphaned_documents(&self) -> Result<i64, DomainError> {
@@ -224,7 +473,7 @@ impl SearchRepository for PostgresSearchRepository {
         .execute(&*self.pool)
         .await
         .map_err(|e| {
-            DomainError::ValidationError(format!("Failed to cleanup item documents: {e}"))
+            DomainError::ValidationError(format!("Failed to cleanup item documents: {e}").into())
         })?;
 
         // Remove search documents for locations that no longer exist
@@ -238,9 +487,140 @@ impl SearchRepository for PostgresSearchRepository {
         .execute(&*self.pool)
         .await
         .map_err(|e| {
-            DomainError::ValidationError(format!("Failed to cleanup location documents: {e}"))
+            DomainError::ValidationError(
+                format!("Failed to cleanup location documents: {e}").into(),
+            )
         })?;
 
         Ok(item_cleanup.rows_affected() as i64 + location_cleanup.rows_affected() as i64)
     }
+
+    async fn health(&self) -> Result<SearchIndexHealth, DomainError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM search_indexes WHERE tenant_id = get_current_tenant_id()) AS document_count,
+                (SELECT MAX(updated_at) FROM search_indexes WHERE tenant_id = get_current_tenant_id()) AS last_indexed_at,
+                COUNT(*) AS expected_document_count,
+                COUNT(*) FILTER (
+                    WHERE NOT EXISTS (
+                        SELECT 1 FROM search_indexes si
+                        WHERE si.entity_type = d.entity_type AND si.entity_id = d.entity_id
+                          AND si.metadata = d.metadata
+                    )
+                ) AS stale_document_count
+            FROM ({LIVE_DOCUMENTS}) d
+            WHERE d.tenant_id = get_current_tenant_id()
+            "#
+        ))
+        .fetch_one(&*self.pool)
+        .await?;
+
+        Ok(SearchIndexHealth {
+            backend: "postgres".to_string(),
+            status: SearchIndexStatus::Available,
+            document_count: Some(row.try_get("document_count")?),
+            expected_document_count: row.try_get("expected_document_count")?,
+            stale_document_count: row.try_get("stale_document_count")?,
+            pending_tasks: None,
+            last_indexed_at: row.try_get("last_indexed_at")?,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::tenant_scope::with_tenant;
+    use crate::test_support::database::TestDatabase;
+
+    async fn indexed_item(
+        db: &TestDatabase,
+        repository: &PostgresSearchRepository,
+        tenant_id: Uuid,
+        sku: &str,
+        name: &str,
+    ) -> Uuid {
+        let item_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO items (sku, name, unit, cost_price, tenant_id)
+             VALUES ($1, $2, 'each', 1, $3) RETURNING id",
+        )
+        .bind(sku)
+        .bind(name)
+        .bind(tenant_id)
+        .fetch_one(&db.admin)
+        .await
+        .unwrap();
+        with_tenant(tenant_id, repository.index_items(&[item_id]))
+            .await
+            .unwrap();
+        item_id
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn test_misspelt_search_and_suggestions_stay_within_the_tenant() {
+        let db = TestDatabase::connect().await;
+        let repository = PostgresSearchRepository::new(Arc::clone(&db.pool));
+        let tenant_id = db.create_tenant("Fuzzy search").await;
+        let other_tenant = db.create_tenant("Fuzzy search other").await;
+        let bolt = indexed_item(
+            &db,
+            &repository,
+            tenant_id,
+            "FZ-BOLT",
+            "Stainless Steel Bolt",
+        )
+        .await;
+        let washer = indexed_item(&db, &repository, tenant_id, "FZ-WASH", "Steel Washer").await;
+        let bracket = indexed_item(&db, &repository, tenant_id, "FZ-BRKT", "Steel Bracket").await;
+        indexed_item(&db, &repository, tenant_id, "FZ-NUT", "Hex Nut").await;
+        let other_bolt = indexed_item(
+            &db,
+            &repository,
+            other_tenant,
+            "FZ-BOLT-B",
+            "Stainless Steel Bolt",
+        )
+        .await;
+
+        let found = with_tenant(
+            tenant_id,
+            repository.search(SearchQuery {
+                query: "stainles bolt".to_string(),
+                entity_types: Some(vec!["item".to_string()]),
+                limit: None,
+                offset: None,
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(found.results.first().map(|r| r.entity_id), Some(bolt));
+        assert!(found.results.iter().all(|r| r.entity_id != other_bolt));
+
+        let suggest = |limit: i64| {
+            with_tenant(
+                tenant_id,
+                repository.suggest(SuggestQuery {
+                    text: "stel".to_string(),
+                    entity_types: None,
+                    limit,
+                }),
+            )
+        };
+        let mut suggested: Vec<Uuid> = suggest(10)
+            .await
+            .unwrap()
+            .iter()
+            .map(|s| s.entity_id)
+            .collect();
+        suggested.sort();
+        let mut steel = vec![bolt, washer, bracket];
+        steel.sort();
+        assert_eq!(suggested, steel);
+        let limited = suggest(2).await.unwrap();
+        assert_eq!(limited.len(), 2);
+        assert!(limited.iter().all(|s| steel.contains(&s.entity_id)));
+    }
 }
@@ -0,0 +1,144 @@
+use crate::domain::entities::session::Session;
+use crate::domain::services::session_repository::SessionRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct PostgresSessionRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresSessionRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for PostgresSessionRepository {
+    async fn save(&self, session: &Session) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO user_sessions (
+                id, user_id, tenant_id, ip_address, user_agent, issued_at, expires_at, revoked_at,
+                two_factor_enrollment_required
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            session.id,
+            session.user_id,
+            session.tenant_id,
+            session.ip_address,
+            session.user_agent,
+            session.issued_at,
+            session.expires_at,
+            session.revoked_at,
+            session.two_factor_enrollment_required
+        )
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Session>, DomainError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, user_id, tenant_id, ip_address, user_agent, issued_at, expires_at, revoked_at,
+                two_factor_enrollment_required
+            FROM user_sessions
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        Ok(row.map(|row| Session {
+            id: row.id,
+            user_id: row.user_id,
+            tenant_id: row.tenant_id,
+            ip_address: row.ip_address,
+            user_agent: row.user_agent,
+            issued_at: row.issued_at,
+            expires_at: row.expires_at,
+            revoked_at: row.revoked_at,
+            two_factor_enrollment_required: row.two_factor_enrollment_required,
+        }))
+    }
+
+    async fn list_active(&self, user_id: Uuid) -> Result<Vec<Session>, DomainError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, user_id, tenant_id, ip_address, user_agent, issued_at, expires_at, revoked_at,
+                two_factor_enrollment_required
+            FROM user_sessions
+            WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+            ORDER BY issued_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Session {
+                id: row.id,
+                user_id: row.user_id,
+                tenant_id: row.tenant_id,
+                ip_address: row.ip_address,
+                user_agent: row.user_agent,
+                issued_at: row.issued_at,
+                expires_at: row.expires_at,
+                revoked_at: row.revoked_at,
+                two_factor_enrollment_required: row.two_factor_enrollment_required,
+            })
+            .collect())
+    }
+
+    async fn revoke(&self, user_id: Uuid, id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE user_sessions SET revoked_at = NOW()
+            WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL AND expires_at > NOW()
+            "#,
+            id,
+            user_id
+        )
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn revoke_all(&self, user_id: Uuid) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            UPDATE user_sessions SET revoked_at = NOW()
+            WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+            "#,
+            user_id
+        )
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn clear_two_factor_enrollment(&self, user_id: Uuid) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            UPDATE user_sessions SET two_factor_enrollment_required = false
+            WHERE user_id = $1 AND two_factor_enrollment_required
+            "#,
+            user_id
+        )
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,369 @@
+use crate::domain::entities::gs1::SsccSequence;
+use crate::domain::entities::shipment::{
+    Shipment, ShipmentCarton, ShipmentPackage, ShipmentSourceType,
+};
+use crate::domain::services::shipment_repository::ShipmentRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Postgres, Row, Transaction};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const SHIPMENT_COLUMNS: &str = "id, shipment_number, source_type, source_id, carrier, tracking_number, total_weight_kg, shipped_at, created_by, created_at, updated_at";
+
+pub struct PostgresShipmentRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresShipmentRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_shipment(
+        row: &PgRow,
+        packages: Vec<ShipmentPackage>,
+        cartons: Vec<ShipmentCarton>,
+    ) -> Result<Shipment, DomainError> {
+        let source_type: String = row.try_get("source_type")?;
+        Ok(Shipment {
+            id: row.try_get("id")?,
+            shipment_number: row.try_get("shipment_number")?,
+            source_type: ShipmentSourceType::from_str(&source_type)?,
+            source_id: row.try_get("source_id")?,
+            carrier: row.try_get("carrier")?,
+            tracking_number: row.try_get("tracking_number")?,
+            packages,
+            cartons,
+            total_weight_kg: row.try_get("total_weight_kg")?,
+            shipped_at: row.try_get("shipped_at")?,
+            created_by: row.try_get("created_by")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    async fn find_packages(&self, shipment_id: Uuid) -> Result<Vec<ShipmentPackage>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, shipment_id, weight_kg, length_cm, width_cm, height_cm, tracking_number, created_at
+            FROM shipment_packages
+            WHERE shipment_id = $1
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(shipment_id)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ShipmentPackage {
+                    id: row.try_get("id")?,
+                    shipment_id: row.try_get("shipment_id")?,
+                    weight_kg: row.try_get("weight_kg")?,
+                    length_cm: row.try_get("length_cm")?,
+                    width_cm: row.try_get("width_cm")?,
+                    height_cm: row.try_get("height_cm")?,
+                    tracking_number: row.try_get("tracking_number")?,
+                    created_at: row.try_get("created_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn find_cartons(&self, shipment_id: Uuid) -> Result<Vec<ShipmentCarton>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, shipment_id, package_id, sscc, created_at
+            FROM shipment_cartons
+            WHERE shipment_id = $1
+            ORDER BY sscc
+            "#,
+        )
+        .bind(shipment_id)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ShipmentCarton {
+                    id: row.try_get("id")?,
+                    shipment_id: row.try_get("shipment_id")?,
+                    package_id: row.try_get("package_id")?,
+                    sscc: row.try_get("sscc")?,
+                    created_at: row.try_get("created_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn hydrate(&self, rows: Vec<PgRow>) -> Result<Vec<Shipment>, DomainError> {
+        let mut shipments = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let id: Uuid = row.try_get("id")?;
+            let packages = self.find_packages(id).await?;
+            let cartons = self.find_cartons(id).await?;
+            shipments.push(Self::row_to_shipment(row, packages, cartons)?);
+        }
+        Ok(shipments)
+    }
+
+    /// Insert the shipment and its packages as part of the caller's transaction
+    pub(crate) async fn insert_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        shipment: &Shipment,
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO shipments (id, shipment_number, source_type, source_id, carrier, tracking_number, total_weight_kg, shipped_at, tenant_id, created_by, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, get_current_tenant_id(), $9, $10, $11)
+            "#,
+        )
+        .bind(shipment.id)
+        .bind(&shipment.shipment_number)
+        .bind(shipment.source_type.as_str())
+        .bind(shipment.source_id)
+        .bind(&shipment.carrier)
+        .bind(&shipment.tracking_number)
+        .bind(shipment.total_weight_kg)
+        .bind(shipment.shipped_at)
+        .bind(shipment.created_by)
+        .bind(shipment.created_at)
+        .bind(shipment.updated_at)
+        .execute(&mut **tx)
+        .await?;
+
+        for package in &shipment.packages {
+            sqlx::query(
+                r#"
+                INSERT INTO shipment_packages (id, shipment_id, weight_kg, length_cm, width_cm, height_cm, tracking_number, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+            )
+            .bind(package.id)
+            .bind(package.shipment_id)
+            .bind(package.weight_kg)
+            .bind(package.length_cm)
+            .bind(package.width_cm)
+            .bind(package.height_cm)
+            .bind(&package.tracking_number)
+            .bind(package.created_at)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ShipmentRepository for PostgresShipmentRepository {
+    async fn create(&self, shipment: &Shipment) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+        Self::insert_in_tx(&mut tx, shipment).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Shipment>, DomainError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM shipments WHERE id = $1 AND tenant_id = get_current_tenant_id()",
+            SHIPMENT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(self.hydrate(vec![row]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_by_source(
+        &self,
+        source_type: &ShipmentSourceType,
+        source_id: Uuid,
+    ) -> Result<Vec<Shipment>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM shipments
+            WHERE source_type = $1 AND source_id = $2 AND tenant_id = get_current_tenant_id()
+            ORDER BY shipped_at
+            "#,
+            SHIPMENT_COLUMNS
+        ))
+        .bind(source_type.as_str())
+        .bind(source_id)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        self.hydrate(rows).await
+    }
+
+    async fn find_by_tracking_number(
+        &self,
+        tracking_number: &str,
+    ) -> Result<Vec<Shipment>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM shipments
+            WHERE tenant_id = get_current_tenant_id()
+              AND (tracking_number = $1
+                   OR id IN (SELECT shipment_id FROM shipment_packages WHERE tracking_number = $1))
+            ORDER BY shipped_at DESC
+            "#,
+            SHIPMENT_COLUMNS
+        ))
+        .bind(tracking_number)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        self.hydrate(rows).await
+    }
+
+    async fn update_tracking(&self, shipment: &Shipment) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE shipments
+            SET carrier = $2, tracking_number = $3, updated_at = $4
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(shipment.id)
+        .bind(&shipment.carrier)
+        .bind(&shipment.tracking_number)
+        .bind(shipment.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Shipment {} not found", shipment.id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Shipment>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM shipments
+            WHERE tenant_id = get_current_tenant_id()
+            ORDER BY shipped_at DESC
+            LIMIT $1 OFFSET $2
+            "#,
+            SHIPMENT_COLUMNS
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        self.hydrate(rows).await
+    }
+
+    async fn find_sscc_sequence(&self) -> Result<Option<SsccSequence>, DomainError> {
+        let row = sqlx::query(
+            r#"
+            SELECT company_prefix, extension_digit, next_serial, updated_at
+            FROM sscc_sequences
+            WHERE tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(SsccSequence {
+                company_prefix: row.try_get("company_prefix")?,
+                extension_digit: row.try_get("extension_digit")?,
+                next_serial: row.try_get("next_serial")?,
+                updated_at: row.try_get("updated_at")?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn save_sscc_sequence(&self, sequence: &SsccSequence) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO sscc_sequences (tenant_id, company_prefix, extension_digit, next_serial, updated_at)
+            VALUES (get_current_tenant_id(), $1, $2, $3, $4)
+            ON CONFLICT (tenant_id) DO UPDATE
+            SET company_prefix = EXCLUDED.company_prefix,
+                extension_digit = EXCLUDED.extension_digit,
+                next_serial = EXCLUDED.next_serial,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(&sequence.company_prefix)
+        .bind(sequence.extension_digit)
+        .bind(sequence.next_serial)
+        .bind(sequence.updated_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn create_cartons(
+        &self,
+        shipment_id: Uuid,
+        package_ids: &[Option<Uuid>],
+    ) -> Result<Vec<ShipmentCarton>, DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Claiming the serials under the row lock keeps concurrent requests from sharing an SSCC
+        let count = package_ids.len() as i64;
+        let row = sqlx::query(
+            r#"
+            UPDATE sscc_sequences
+            SET next_serial = next_serial + $1, updated_at = NOW()
+            WHERE tenant_id = get_current_tenant_id()
+            RETURNING company_prefix, extension_digit, next_serial - $1 AS first_serial, updated_at
+            "#,
+        )
+        .bind(count)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            DomainError::BusinessLogicError(
+                "No GS1 company prefix is configured; set one at PUT /admin/sscc-sequence"
+                    .to_string()
+                    .into(),
+            )
+        })?;
+
+        let first_serial: i64 = row.try_get("first_serial")?;
+        let sequence = SsccSequence {
+            company_prefix: row.try_get("company_prefix")?,
+            extension_digit: row.try_get("extension_digit")?,
+            next_serial: first_serial,
+            updated_at: row.try_get("updated_at")?,
+        };
+
+        let mut cartons = Vec::with_capacity(package_ids.len());
+        for (serial, package_id) in (first_serial..).zip(package_ids) {
+            let carton = ShipmentCarton::new(shipment_id, *package_id, sequence.sscc(serial)?);
+            sqlx::query(
+                r#"
+                INSERT INTO shipment_cartons (id, tenant_id, shipment_id, package_id, sscc, created_at)
+                VALUES ($1, get_current_tenant_id(), $2, $3, $4, $5)
+                "#,
+            )
+            .bind(carton.id)
+            .bind(carton.shipment_id)
+            .bind(carton.package_id)
+            .bind(&carton.sscc)
+            .bind(carton.created_at)
+            .execute(&mut *tx)
+            .await?;
+            cartons.push(carton);
+        }
+
+        tx.commit().await?;
+        Ok(cartons)
+    }
+}
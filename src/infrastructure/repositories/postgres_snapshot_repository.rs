@@ -0,0 +1,153 @@
+use crate::domain::entities::stock_snapshot::{StockQuantity, StockQuantityFilter};
+use crate::domain::services::snapshot_repository::SnapshotRepository;
+use crate::infrastructure::repositories::tenant_pool::begin_with_statement_timeout;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub struct PostgresSnapshotRepository {
+    pool: Arc<PgPool>,
+    /// Applied to the historical stock queries
+    statement_timeout: Option<Duration>,
+}
+
+impl PostgresSnapshotRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self {
+            pool,
+            statement_timeout: None,
+        }
+    }
+
+    pub fn with_statement_timeout(mut self, statement_timeout: Option<Duration>) -> Self {
+        self.statement_timeout = statement_timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl SnapshotRepository for PostgresSnapshotRepository {
+    async fn create_snapshot(
+        &self,
+        taken_at: DateTime<Utc>,
+        local_hour: u32,
+    ) -> Result<u64, DomainError> {
+        // Runs outside any request, so it covers every tenant rather than the current one.
+        // "Reached the hour" rather than "at the hour", so a DST jump over it can't skip a day.
+        let result = sqlx::query!(
+            r#"
+            WITH due AS (
+                SELECT id AS tenant_id, ($1::timestamptz AT TIME ZONE timezone)::date AS snapshot_date
+                FROM tenants
+                WHERE EXTRACT(HOUR FROM $1::timestamptz AT TIME ZONE timezone) >= $2
+            )
+            INSERT INTO stock_snapshots
+                (snapshot_date, taken_at, item_id, location_id, quantity_on_hand, quantity_reserved, tenant_id)
+            SELECT due.snapshot_date, $1, s.item_id, s.location_id,
+                   s.quantity_on_hand, s.quantity_reserved, s.tenant_id
+            FROM stock_levels s
+            JOIN due ON due.tenant_id = s.tenant_id
+            WHERE NOT EXISTS (
+                SELECT 1 FROM stock_snapshots taken
+                WHERE taken.tenant_id = due.tenant_id AND taken.snapshot_date = due.snapshot_date
+            )
+            ON CONFLICT (tenant_id, snapshot_date, item_id, location_id) DO NOTHING
+            "#,
+            taken_at,
+            local_hour as i32
+        )
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn latest_snapshot_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>, DomainError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT MAX(taken_at) AS taken_at
+            FROM stock_snapshots
+            WHERE tenant_id = get_current_tenant_id() AND taken_at < $1
+            "#,
+            before
+        )
+        .fetch_one(&*self.pool)
+        .await?;
+
+        Ok(row.taken_at)
+    }
+
+    async fn get_snapshot_quantities(
+        &self,
+        taken_at: DateTime<Utc>,
+        filter: &StockQuantityFilter,
+    ) -> Result<Vec<StockQuantity>, DomainError> {
+        let mut tx = begin_with_statement_timeout(&self.pool, self.statement_timeout).await?;
+        let rows = sqlx::query!(
+            r#"
+            SELECT item_id, location_id, quantity_on_hand
+            FROM stock_snapshots
+            WHERE tenant_id = get_current_tenant_id() AND taken_at = $1
+              AND ($2::uuid IS NULL OR item_id = $2)
+              AND ($3::uuid IS NULL OR location_id = $3)
+            "#,
+            taken_at,
+            filter.item_id,
+            filter.location_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| StockQuantity {
+                item_id: row.item_id,
+                location_id: row.location_id,
+                quantity_on_hand: row.quantity_on_hand,
+            })
+            .collect())
+    }
+
+    async fn get_movement_totals(
+        &self,
+        after: Option<DateTime<Utc>>,
+        before: DateTime<Utc>,
+        filter: &StockQuantityFilter,
+    ) -> Result<Vec<StockQuantity>, DomainError> {
+        let mut tx = begin_with_statement_timeout(&self.pool, self.statement_timeout).await?;
+        let rows = sqlx::query!(
+            r#"
+            SELECT item_id, location_id, SUM(quantity) AS "quantity_on_hand!"
+            FROM stock_movements
+            WHERE tenant_id = get_current_tenant_id()
+              AND stock_status <> 'IN_TRANSIT'
+              AND ($1::timestamptz IS NULL OR created_at > $1)
+              AND created_at < $2
+              AND ($3::uuid IS NULL OR item_id = $3)
+              AND ($4::uuid IS NULL OR location_id = $4)
+            GROUP BY item_id, location_id
+            "#,
+            after,
+            before,
+            filter.item_id,
+            filter.location_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| StockQuantity {
+                item_id: row.item_id,
+                location_id: row.location_id,
+                quantity_on_hand: row.quantity_on_hand,
+            })
+            .collect())
+    }
+}
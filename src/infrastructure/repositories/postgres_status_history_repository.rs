@@ -0,0 +1,167 @@
+use crate::domain::entities::status_history::{StatusChange, StatusHistoryEntity};
+use crate::domain::services::status_history_repository::StatusHistoryRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Reads the history the `record_status_change` trigger writes; nothing here
+/// writes it
+pub struct PostgresStatusHistoryRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresStatusHistoryRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl StatusHistoryRepository for PostgresStatusHistoryRepository {
+    async fn find_history(
+        &self,
+        entity: StatusHistoryEntity,
+        entity_id: Uuid,
+    ) -> Result<Option<Vec<StatusChange>>, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        let exists: bool = sqlx::query_scalar(&format!(
+            "SELECT EXISTS (SELECT 1 FROM {} WHERE id = $1 AND tenant_id = get_current_tenant_id())",
+            entity.table()
+        ))
+        .bind(entity_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !exists {
+            return Ok(None);
+        }
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, entity_type, entity_id, old_status, new_status, actor_id, metadata, changed_at
+            FROM entity_status_history
+            WHERE entity_type = $1 AND entity_id = $2 AND tenant_id = get_current_tenant_id()
+            ORDER BY changed_at, id
+            "#,
+        )
+        .bind(entity.as_str())
+        .bind(entity_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let entity_type: String = row.try_get("entity_type")?;
+                Ok(StatusChange {
+                    id: row.try_get("id")?,
+                    entity_type: StatusHistoryEntity::from_str(&entity_type)?,
+                    entity_id: row.try_get("entity_id")?,
+                    old_status: row.try_get("old_status")?,
+                    new_status: row.try_get("new_status")?,
+                    actor_id: row.try_get("actor_id")?,
+                    metadata: row.try_get("metadata")?,
+                    changed_at: row.try_get("changed_at")?,
+                })
+            })
+            .collect::<Result<Vec<_>, DomainError>>()
+            .map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::tenant_scope::{with_actor, with_tenant};
+    use crate::test_support::database::{TestDatabase, SEEDED_USER};
+    use std::str::FromStr;
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn test_transitions_are_recorded_with_their_actor() {
+        let db = TestDatabase::connect().await;
+        let pool: &PgPool = &db.pool;
+        let repository = PostgresStatusHistoryRepository::new(Arc::clone(&db.pool));
+        let actor = Uuid::from_str(SEEDED_USER).unwrap();
+        let tenant_id = db.create_tenant("Status history").await;
+
+        // Created and confirmed by the logged-in user, cancelled by a job
+        let order_id: Uuid = with_tenant(
+            tenant_id,
+            with_actor(actor, async {
+                let order_id: Uuid = sqlx::query_scalar(
+                    "INSERT INTO sales_orders (so_number, status, created_by, tenant_id)
+                     VALUES ('SO-HIST-1', 'DRAFT', $1, get_current_tenant_id()) RETURNING id",
+                )
+                .bind(actor)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+                sqlx::query("UPDATE sales_orders SET status = 'CONFIRMED' WHERE id = $1")
+                    .bind(order_id)
+                    .execute(pool)
+                    .await
+                    .unwrap();
+                // Rewriting the same status isn't a transition
+                sqlx::query("UPDATE sales_orders SET status = 'CONFIRMED', updated_at = NOW() WHERE id = $1")
+                    .bind(order_id)
+                    .execute(pool)
+                    .await
+                    .unwrap();
+                order_id
+            }),
+        )
+        .await;
+        with_tenant(tenant_id, async {
+            sqlx::query(
+                "UPDATE sales_orders SET status = 'CANCELLED', cancellation_reason = 'Duplicate' WHERE id = $1",
+            )
+            .bind(order_id)
+            .execute(pool)
+            .await
+            .unwrap();
+        })
+        .await;
+
+        let history = with_tenant(
+            tenant_id,
+            repository.find_history(StatusHistoryEntity::SalesOrder, order_id),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        let transitions: Vec<_> = history
+            .iter()
+            .map(|c| (c.old_status.as_deref(), c.new_status.as_str(), c.actor_id))
+            .collect();
+        assert_eq!(
+            transitions,
+            [
+                (None, "DRAFT", Some(actor)),
+                (Some("DRAFT"), "CONFIRMED", Some(actor)),
+                (Some("CONFIRMED"), "CANCELLED", None),
+            ]
+        );
+        assert_eq!(
+            history[2].metadata,
+            serde_json::json!({"cancellation_reason": "Duplicate"})
+        );
+
+        // Another tenant's order, or one of the wrong type, has no history to show
+        assert!(with_tenant(
+            Uuid::new_v4(),
+            repository.find_history(StatusHistoryEntity::SalesOrder, order_id)
+        )
+        .await
+        .unwrap()
+        .is_none());
+        assert!(with_tenant(
+            tenant_id,
+            repository.find_history(StatusHistoryEntity::PurchaseOrder, order_id)
+        )
+        .await
+        .unwrap()
+        .is_none());
+    }
+}
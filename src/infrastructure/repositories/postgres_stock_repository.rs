@@ -1,18 +1,41 @@
-use crate::domain::entities::inventory::{MovementType, ReferenceType, StockLevel, StockMovement};
-use crate::domain::services::stock_repository::StockRepository;
+use crate::domain::entities::export::StockMovementExportFilter;
+use crate::domain::entities::inventory::{
+    DeadStockItem, MovementType, ReferenceType, StockLevel, StockMovement, StockStatus,
+};
+use crate::domain::services::stock_repository::{StockMovementBatches, StockRepository};
+use crate::infrastructure::repositories::postgres_item_availability_repository::PostgresItemAvailabilityRepository;
+use crate::infrastructure::repositories::tenant_pool::begin_with_statement_timeout;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
+use crate::shared::tenant_scope::current_tenant;
 use async_trait::async_trait;
-use sqlx::{PgPool, Postgres, Transaction};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::{postgres::PgRow, PgPool, Postgres, Row, Transaction};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use uuid::Uuid;
 
 pub struct PostgresStockRepository {
     pool: Arc<PgPool>,
+    /// Applied to report and export queries
+    statement_timeout: Option<Duration>,
 }
 
 impl PostgresStockRepository {
     pub fn new(pool: Arc<PgPool>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            statement_timeout: None,
+        }
+    }
+
+    pub fn with_statement_timeout(mut self, statement_timeout: Option<Duration>) -> Self {
+        self.statement_timeout = statement_timeout;
+        self
     }
 
     /// Execute stock movement and level update in a single transaction
@@ -21,91 +44,412 @@ impl PostgresStockRepository {
         movement: &StockMovement,
     ) -> Result<(), DomainError> {
         let mut tx = self.pool.begin().await.map_err(|e| {
-            DomainError::ValidationError(format!("Failed to start transaction: {}", e))
+            DomainError::ValidationError(format!("Failed to start transaction: {}", e).into())
         })?;
 
-        // Insert the stock movement
-        sqlx::query!(
+        Self::record_movement_in_tx(&mut tx, movement).await?;
+
+        tx.commit().await.map_err(|e| {
+            DomainError::ValidationError(format!("Failed to commit transaction: {}", e).into())
+        })?;
+
+        Ok(())
+    }
+
+    /// Execute a batch of movements and their level updates in a single transaction
+    async fn execute_movements_transaction(
+        &self,
+        movements: &[StockMovement],
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            DomainError::ValidationError(format!("Failed to start transaction: {}", e).into())
+        })?;
+
+        Self::record_movements_in_tx(&mut tx, movements).await?;
+
+        tx.commit().await.map_err(|e| {
+            DomainError::ValidationError(format!("Failed to commit transaction: {}", e).into())
+        })?;
+
+        Ok(())
+    }
+
+    /// Record a movement inside a caller's transaction and apply it to the
+    /// location's stock level, both on hand and in the movement's status bucket.
+    /// Stock can only be taken from the status it is held in, and in-transit
+    /// movements leave the quantity on hand untouched. Every repository that
+    /// records movements goes through here, so no movement is written without
+    /// the level it changes.
+    pub(crate) async fn record_movement_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        movement: &StockMovement,
+    ) -> Result<StockLevel, DomainError> {
+        let mut levels = Self::record_movements_in_tx(tx, std::slice::from_ref(movement)).await?;
+        Ok(levels.remove(0))
+    }
+
+    /// Record movements inside a caller's transaction as `record_movement_in_tx`
+    /// does, in a fixed number of round trips however many there are: the
+    /// affected levels are locked together, the movements inserted in one
+    /// statement and each level updated once with the sum of its movements.
+    /// Movements apply in order, so a later one may take stock an earlier one
+    /// brought in. Returns the resulting level for each item and location, in
+    /// the order they first appear.
+    pub(crate) async fn record_movements_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        movements: &[StockMovement],
+    ) -> Result<Vec<StockLevel>, DomainError> {
+        if movements.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (item_ids, location_ids): (Vec<Uuid>, Vec<Uuid>) =
+            movements.iter().map(|m| (m.item_id, m.location_id)).unzip();
+        // Lock in key order so concurrent batches over the same levels can't deadlock
+        let rows = sqlx::query(
+            r#"
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved,
+                   quantity_quarantine, quantity_damaged, quantity_in_transit, last_movement_id, updated_at
+            FROM stock_levels
+            WHERE (item_id, location_id) IN (SELECT * FROM UNNEST($1::uuid[], $2::uuid[]))
+              AND tenant_id = get_current_tenant_id()
+            ORDER BY item_id, location_id
+            FOR UPDATE
+            "#,
+        )
+        .bind(&item_ids)
+        .bind(&location_ids)
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| DomainError::ValidationError(format!("Failed to check stock level: {}", e).into()))?;
+        let current = rows
+            .iter()
+            .map(Self::row_to_stock_level)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let changes = stock_level_changes(current, movements)?;
+
+        sqlx::query(
             r#"
             INSERT INTO stock_movements (
                 id, item_id, location_id, movement_type, quantity,
-                reference_type, reference_id, reason, created_at, created_by, tenant_id
+                reference_type, reference_id, reason, created_at, created_by, stock_status, tenant_id
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, get_current_tenant_id())
+            SELECT m.*, get_current_tenant_id()
+            FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::text[], $5::numeric[], $6::text[],
+                        $7::uuid[], $8::text[], $9::timestamptz[], $10::uuid[], $11::text[]) AS m
             "#,
-            movement.id,
-            movement.item_id,
-            movement.location_id,
-            movement.movement_type.as_str(),
-            movement.quantity,
-            movement.reference_type.as_str(),
-            movement.reference_id,
-            movement.reason,
-            movement.created_at,
-            movement.created_by
         )
-        .execute(&mut *tx)
+        .bind(movements.iter().map(|m| m.id).collect::<Vec<_>>())
+        .bind(&item_ids)
+        .bind(&location_ids)
+        .bind(
+            movements
+                .iter()
+                .map(|m| m.movement_type.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .bind(movements.iter().map(|m| m.quantity).collect::<Vec<_>>())
+        .bind(
+            movements
+                .iter()
+                .map(|m| m.reference_type.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .bind(movements.iter().map(|m| m.reference_id).collect::<Vec<_>>())
+        .bind(movements.iter().map(|m| m.reason.clone()).collect::<Vec<_>>())
+        .bind(movements.iter().map(|m| m.created_at).collect::<Vec<_>>())
+        .bind(movements.iter().map(|m| m.created_by).collect::<Vec<_>>())
+        .bind(
+            movements
+                .iter()
+                .map(|m| m.stock_status.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .execute(&mut **tx)
         .await
-        .map_err(|e| {
-            DomainError::ValidationError(format!("Failed to insert stock movement: {}", e))
+        .map_err(|e| match e {
+            // Movements carry the current tenant, which has to own the item and location
+            sqlx::Error::Database(db)
+                if db
+                    .constraint()
+                    .is_some_and(|constraint| constraint.ends_with("_tenant_fkey")) =>
+            {
+                DomainError::NotFound(
+                    "Stock movement names an item or location the tenant doesn't have".into(),
+                )
+            }
+            e => DomainError::ValidationError(format!("Failed to insert stock movement: {}", e).into()),
         })?;
 
-        // Update or insert stock level
-        sqlx::query!(
+        // Constraints are checked against the proposed row of an upsert too, so
+        // only upsert levels that didn't exist yet (and can't go negative)
+        let (existing, new): (Vec<&LevelChange>, Vec<&LevelChange>) =
+            changes.iter().partition(|change| change.exists);
+        for (changes, sql) in [
+            (
+                existing,
+                r#"
+                UPDATE stock_levels s
+                SET quantity_on_hand = s.quantity_on_hand + d.on_hand,
+                    quantity_quarantine = s.quantity_quarantine + d.quarantine,
+                    quantity_damaged = s.quantity_damaged + d.damaged,
+                    quantity_in_transit = s.quantity_in_transit + d.in_transit,
+                    last_movement_id = d.last_movement_id,
+                    updated_at = d.updated_at
+                FROM UNNEST($1::uuid[], $2::uuid[], $3::numeric[], $4::numeric[], $5::numeric[],
+                            $6::numeric[], $7::uuid[], $8::timestamptz[])
+                     AS d(item_id, location_id, on_hand, quarantine, damaged, in_transit,
+                          last_movement_id, updated_at)
+                WHERE s.item_id = d.item_id AND s.location_id = d.location_id
+                  AND s.tenant_id = get_current_tenant_id()
+                "#,
+            ),
+            (
+                new,
+                r#"
+                INSERT INTO stock_levels (
+                    item_id, location_id, quantity_on_hand, quantity_quarantine, quantity_damaged,
+                    quantity_in_transit, last_movement_id, updated_at, tenant_id
+                )
+                SELECT d.*, get_current_tenant_id()
+                FROM UNNEST($1::uuid[], $2::uuid[], $3::numeric[], $4::numeric[], $5::numeric[],
+                            $6::numeric[], $7::uuid[], $8::timestamptz[]) AS d
+                ON CONFLICT (item_id, location_id)
+                DO UPDATE SET
+                    quantity_on_hand = stock_levels.quantity_on_hand + EXCLUDED.quantity_on_hand,
+                    quantity_quarantine = stock_levels.quantity_quarantine + EXCLUDED.quantity_quarantine,
+                    quantity_damaged = stock_levels.quantity_damaged + EXCLUDED.quantity_damaged,
+                    quantity_in_transit = stock_levels.quantity_in_transit + EXCLUDED.quantity_in_transit,
+                    last_movement_id = EXCLUDED.last_movement_id,
+                    updated_at = EXCLUDED.updated_at
+                "#,
+            ),
+        ] {
+            if changes.is_empty() {
+                continue;
+            }
+            sqlx::query(sql)
+                .bind(changes.iter().map(|c| c.after.item_id).collect::<Vec<_>>())
+                .bind(
+                    changes
+                        .iter()
+                        .map(|c| c.after.location_id)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    changes
+                        .iter()
+                        .map(|c| c.after.quantity_on_hand - c.before.quantity_on_hand)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    changes
+                        .iter()
+                        .map(|c| c.after.quantity_quarantine - c.before.quantity_quarantine)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    changes
+                        .iter()
+                        .map(|c| c.after.quantity_damaged - c.before.quantity_damaged)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    changes
+                        .iter()
+                        .map(|c| c.after.quantity_in_transit - c.before.quantity_in_transit)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    changes
+                        .iter()
+                        .map(|c| c.after.last_movement_id)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(changes.iter().map(|c| c.moved_at).collect::<Vec<_>>())
+                .execute(&mut **tx)
+                .await
+                .map_err(|e| {
+                    DomainError::ValidationError(
+                        format!("Failed to update stock level: {}", e).into(),
+                    )
+                })?;
+        }
+
+        let keys: Vec<(Uuid, Uuid)> = changes
+            .iter()
+            .map(|change| (change.after.item_id, change.after.location_id))
+            .collect();
+        PostgresItemAvailabilityRepository::refresh_in_tx(tx, &keys).await?;
+
+        Ok(changes.into_iter().map(|change| change.after).collect())
+    }
+
+    fn row_to_stock_level(row: &PgRow) -> Result<StockLevel, DomainError> {
+        Ok(StockLevel {
+            item_id: row.try_get("item_id")?,
+            location_id: row.try_get("location_id")?,
+            quantity_on_hand: row.try_get("quantity_on_hand")?,
+            quantity_reserved: row.try_get("quantity_reserved")?,
+            quantity_quarantine: row.try_get("quantity_quarantine")?,
+            quantity_damaged: row.try_get("quantity_damaged")?,
+            quantity_in_transit: row.try_get("quantity_in_transit")?,
+            last_movement_id: row.try_get("last_movement_id")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    fn row_to_movement(row: &PgRow) -> Result<StockMovement, DomainError> {
+        Ok(StockMovement {
+            id: row.try_get("id")?,
+            item_id: row.try_get("item_id")?,
+            location_id: row.try_get("location_id")?,
+            movement_type: MovementType::from_str(row.try_get("movement_type")?)?,
+            quantity: row.try_get("quantity")?,
+            reference_type: ReferenceType::from_str(row.try_get("reference_type")?)?,
+            reference_id: row.try_get("reference_id")?,
+            reason: row.try_get("reason")?,
+            created_at: row.try_get("created_at")?,
+            created_by: row.try_get("created_by")?,
+            stock_status: StockStatus::from_str(row.try_get("stock_status")?)?,
+        })
+    }
+
+    /// Walk a server-side cursor so only one batch is held in memory at a time.
+    /// Stops early once the receiving end (the HTTP response) has gone away.
+    async fn send_movement_batches(
+        pool: &PgPool,
+        statement_timeout: Option<Duration>,
+        filter: &StockMovementExportFilter,
+        tenant_id: Option<Uuid>,
+        batch_size: i64,
+        sender: &mpsc::Sender<Result<Vec<StockMovement>, DomainError>>,
+    ) -> Result<(), DomainError> {
+        // Cursors only live inside a transaction; the timeout bounds each FETCH
+        let mut tx = begin_with_statement_timeout(pool, statement_timeout).await?;
+
+        // The response body is streamed after the request's tenant scope has ended,
+        // so pin the tenant on this connection for the transaction's lifetime
+        if let Some(tenant_id) = tenant_id {
+            sqlx::query("SELECT set_config('custom.tenant_id', $1::text, true)")
+                .bind(tenant_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        sqlx::query(
             r#"
-            INSERT INTO stock_levels (item_id, location_id, quantity_on_hand, last_movement_id, updated_at, tenant_id)
-            VALUES ($1, $2, $3, $4, $5, get_current_tenant_id())
-            ON CONFLICT (item_id, location_id)
-            DO UPDATE SET
-                quantity_on_hand = stock_levels.quantity_on_hand + EXCLUDED.quantity_on_hand,
-                last_movement_id = EXCLUDED.last_movement_id,
-                updated_at = EXCLUDED.updated_at
+            DECLARE movement_export NO SCROLL CURSOR FOR
+            SELECT id, item_id, location_id, movement_type, quantity,
+                   reference_type, reference_id, reason, created_at, created_by, stock_status
+            FROM stock_movements
+            WHERE tenant_id = get_current_tenant_id()
+              AND ($1::timestamptz IS NULL OR created_at >= $1)
+              AND ($2::timestamptz IS NULL OR created_at < $2)
+              AND ($3::uuid IS NULL OR item_id = $3)
+              AND ($4::uuid IS NULL OR location_id = $4)
+              AND ($5::text IS NULL OR movement_type = $5)
+            ORDER BY created_at, id
             "#,
-            movement.item_id,
-            movement.location_id,
-            movement.quantity,
-            movement.id,
-            movement.created_at
         )
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.item_id)
+        .bind(filter.location_id)
+        .bind(filter.movement_type.as_deref())
         .execute(&mut *tx)
-        .await
-        .map_err(|e| DomainError::ValidationError(format!("Failed to update stock level: {}", e)))?;
-
-        // Validate that stock level is not negative (except for adjustments)
-        if movement.movement_type != MovementType::Adjustment {
-            let stock_level = sqlx::query!(
-                r#"SELECT quantity_on_hand FROM stock_levels WHERE item_id = $1 AND location_id = $2"#,
-                movement.item_id,
-                movement.location_id
-            )
-            .fetch_one(&mut *tx)
-            .await
-            .map_err(|e| DomainError::ValidationError(format!("Failed to check stock level: {}", e)))?;
+        .await?;
 
-            if stock_level.quantity_on_hand < 0 {
-                tx.rollback().await.map_err(|e| {
-                    DomainError::ValidationError(format!("Failed to rollback transaction: {}", e))
-                })?;
-                return Err(DomainError::BusinessLogicError(
-                    "Stock level cannot go negative".to_string(),
-                ));
+        let fetch = format!("FETCH FORWARD {} FROM movement_export", batch_size.max(1));
+        loop {
+            let rows = sqlx::query(&fetch).fetch_all(&mut *tx).await?;
+            if rows.is_empty() {
+                break;
+            }
+            let movements = rows
+                .iter()
+                .map(Self::row_to_movement)
+                .collect::<Result<Vec<_>, _>>()?;
+            if sender.send(Ok(movements)).await.is_err() {
+                break;
             }
         }
 
-        tx.commit().await.map_err(|e| {
-            DomainError::ValidationError(format!("Failed to commit transaction: {}", e))
-        })?;
-
+        tx.rollback().await?;
         Ok(())
     }
 }
 
+/// One stock level's part in a batch of movements
+#[derive(Debug)]
+struct LevelChange {
+    before: StockLevel,
+    after: StockLevel,
+    /// Whether the level had a row before the batch
+    exists: bool,
+    /// When the level's last movement in the batch happened
+    moved_at: DateTime<Utc>,
+}
+
+/// Apply the movements in order to the levels they touch, starting from
+/// `current` or an empty level, one change per item and location in the order
+/// they first appear
+fn stock_level_changes(
+    current: Vec<StockLevel>,
+    movements: &[StockMovement],
+) -> Result<Vec<LevelChange>, DomainError> {
+    let mut current: HashMap<(Uuid, Uuid), StockLevel> = current
+        .into_iter()
+        .map(|level| ((level.item_id, level.location_id), level))
+        .collect();
+    let mut changes: Vec<LevelChange> = Vec::new();
+    let mut index: HashMap<(Uuid, Uuid), usize> = HashMap::new();
+    for movement in movements {
+        let key = (movement.item_id, movement.location_id);
+        let position = *index.entry(key).or_insert_with(|| {
+            let (before, exists) = match current.remove(&key) {
+                Some(level) => (level, true),
+                None => (StockLevel::new(key.0, key.1), false),
+            };
+            changes.push(LevelChange {
+                after: before.clone(),
+                before,
+                exists,
+                moved_at: movement.created_at,
+            });
+            changes.len() - 1
+        });
+        let change = &mut changes[position];
+        change.after.apply_movement(movement)?;
+        change.after.updated_at = movement.created_at;
+        change.moved_at = movement.created_at;
+    }
+    Ok(changes)
+}
+
 #[async_trait]
 impl StockRepository for PostgresStockRepository {
     async fn record_movement(&self, movement: &StockMovement) -> Result<(), DomainError> {
         self.execute_movement_transaction(movement).await
     }
 
+    async fn record_movements(&self, movements: &[StockMovement]) -> Result<(), DomainError> {
+        self.execute_movements_transaction(movements).await
+    }
+
+    async fn record_status_change(
+        &self,
+        out_of_status: &StockMovement,
+        into_status: &StockMovement,
+    ) -> Result<StockLevel, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        Self::record_movement_in_tx(&mut tx, out_of_status).await?;
+        let stock_level = Self::record_movement_in_tx(&mut tx, into_status).await?;
+        tx.commit().await?;
+        Ok(stock_level)
+    }
+
     async fn get_stock_level(
         &self,
         item_id: Uuid,
@@ -113,7 +457,8 @@ impl StockRepository for PostgresStockRepository {
     ) -> Result<Option<StockLevel>, DomainError> {
         let result = sqlx::query!(
             r#"
-            SELECT item_id, location_id, quantity_on_hand, last_movement_id, updated_at
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
             FROM stock_levels
             WHERE item_id = $1 AND location_id = $2 AND tenant_id = get_current_tenant_id()
             "#,
@@ -122,12 +467,16 @@ impl StockRepository for PostgresStockRepository {
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(result.map(|row| StockLevel {
             item_id: row.item_id,
             location_id: row.location_id,
             quantity_on_hand: row.quantity_on_hand,
+            quantity_reserved: row.quantity_reserved,
+            quantity_quarantine: row.quantity_quarantine,
+            quantity_damaged: row.quantity_damaged,
+            quantity_in_transit: row.quantity_in_transit,
             last_movement_id: row.last_movement_id,
             updated_at: row.updated_at,
         }))
@@ -136,7 +485,8 @@ impl StockRepository for PostgresStockRepository {
     async fn get_item_stock_levels(&self, item_id: Uuid) -> Result<Vec<StockLevel>, DomainError> {
         let results = sqlx::query!(
             r#"
-            SELECT item_id, location_id, quantity_on_hand, last_movement_id, updated_at
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
             FROM stock_levels
             WHERE item_id = $1 AND tenant_id = get_current_tenant_id()
             ORDER BY location_id
@@ -145,7 +495,7 @@ impl StockRepository for PostgresStockRepository {
         )
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(results
             .into_iter()
@@ -153,6 +503,10 @@ impl StockRepository for PostgresStockRepository {
                 item_id: row.item_id,
                 location_id: row.location_id,
                 quantity_on_hand: row.quantity_on_hand,
+                quantity_reserved: row.quantity_reserved,
+                quantity_quarantine: row.quantity_quarantine,
+                quantity_damaged: row.quantity_damaged,
+                quantity_in_transit: row.quantity_in_transit,
                 last_movement_id: row.last_movement_id,
                 updated_at: row.updated_at,
             })
@@ -165,7 +519,8 @@ impl StockRepository for PostgresStockRepository {
     ) -> Result<Vec<StockLevel>, DomainError> {
         let results = sqlx::query!(
             r#"
-            SELECT item_id, location_id, quantity_on_hand, last_movement_id, updated_at
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
             FROM stock_levels
             WHERE location_id = $1 AND tenant_id = get_current_tenant_id()
             ORDER BY item_id
@@ -174,7 +529,7 @@ impl StockRepository for PostgresStockRepository {
         )
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(results
             .into_iter()
@@ -182,34 +537,108 @@ impl StockRepository for PostgresStockRepository {
                 item_id: row.item_id,
                 location_id: row.location_id,
                 quantity_on_hand: row.quantity_on_hand,
+                quantity_reserved: row.quantity_reserved,
+                quantity_quarantine: row.quantity_quarantine,
+                quantity_damaged: row.quantity_damaged,
+                quantity_in_transit: row.quantity_in_transit,
                 last_movement_id: row.last_movement_id,
                 updated_at: row.updated_at,
             })
             .collect())
     }
 
+    async fn get_stock_levels_for_items(
+        &self,
+        item_ids: &[Uuid],
+    ) -> Result<Vec<StockLevel>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
+            FROM stock_levels
+            WHERE item_id = ANY($1) AND tenant_id = get_current_tenant_id()
+            ORDER BY item_id, location_id
+            "#,
+        )
+        .bind(item_ids)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
+
+        rows.iter().map(Self::row_to_stock_level).collect()
+    }
+
+    async fn get_stock_levels_for_pairs(
+        &self,
+        pairs: &[(Uuid, Uuid)],
+    ) -> Result<Vec<StockLevel>, DomainError> {
+        let (item_ids, location_ids): (Vec<Uuid>, Vec<Uuid>) = pairs.iter().copied().unzip();
+        let rows = sqlx::query(
+            r#"
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
+            FROM stock_levels
+            WHERE (item_id, location_id) = ANY(SELECT * FROM UNNEST($1::uuid[], $2::uuid[]))
+              AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(&item_ids)
+        .bind(&location_ids)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
+
+        rows.iter().map(Self::row_to_stock_level).collect()
+    }
+
+    async fn get_stock_levels_for_locations(
+        &self,
+        location_ids: &[Uuid],
+    ) -> Result<Vec<StockLevel>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
+            FROM stock_levels
+            WHERE location_id = ANY($1) AND tenant_id = get_current_tenant_id()
+            ORDER BY location_id, item_id
+            "#,
+        )
+        .bind(location_ids)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
+
+        rows.iter().map(Self::row_to_stock_level).collect()
+    }
+
     async fn get_item_movements(
         &self,
         item_id: Uuid,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<StockMovement>, DomainError> {
+        page: &PageRequest,
+    ) -> Result<Page<StockMovement>, DomainError> {
+        let limit = page.limit();
+        let after: Option<(DateTime<Utc>, Uuid)> = page.after()?;
+        let (after_created_at, after_id) = after.unzip();
+
         let results = sqlx::query!(
             r#"
             SELECT id, item_id, location_id, movement_type, quantity,
-                   reference_type, reference_id, reason, created_at, created_by
+                   reference_type, reference_id, reason, created_at, created_by, stock_status
             FROM stock_movements
             WHERE item_id = $1 AND tenant_id = get_current_tenant_id()
-            ORDER BY created_at DESC
-            LIMIT $2 OFFSET $3
+              AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
             item_id,
-            limit,
-            offset
+            after_created_at,
+            after_id,
+            limit + 1
         )
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         let mut movements = Vec::new();
         for row in results {
@@ -227,34 +656,40 @@ impl StockRepository for PostgresStockRepository {
                 reason: row.reason,
                 created_at: row.created_at,
                 created_by: row.created_by,
+                stock_status: StockStatus::from_str(&row.stock_status)?,
             });
         }
 
-        Ok(movements)
+        Ok(Page::from_rows(movements, limit, |m| (m.created_at, m.id)))
     }
 
     async fn get_location_movements(
         &self,
         location_id: Uuid,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<StockMovement>, DomainError> {
+        page: &PageRequest,
+    ) -> Result<Page<StockMovement>, DomainError> {
+        let limit = page.limit();
+        let after: Option<(DateTime<Utc>, Uuid)> = page.after()?;
+        let (after_created_at, after_id) = after.unzip();
+
         let results = sqlx::query!(
             r#"
             SELECT id, item_id, location_id, movement_type, quantity,
-                   reference_type, reference_id, reason, created_at, created_by
+                   reference_type, reference_id, reason, created_at, created_by, stock_status
             FROM stock_movements
             WHERE location_id = $1 AND tenant_id = get_current_tenant_id()
-            ORDER BY created_at DESC
-            LIMIT $2 OFFSET $3
+              AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
             location_id,
-            limit,
-            offset
+            after_created_at,
+            after_id,
+            limit + 1
         )
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         let mut movements = Vec::new();
         for row in results {
@@ -272,36 +707,42 @@ impl StockRepository for PostgresStockRepository {
                 reason: row.reason,
                 created_at: row.created_at,
                 created_by: row.created_by,
+                stock_status: StockStatus::from_str(&row.stock_status)?,
             });
         }
 
-        Ok(movements)
+        Ok(Page::from_rows(movements, limit, |m| (m.created_at, m.id)))
     }
 
     async fn get_stock_movements(
         &self,
         item_id: Uuid,
         location_id: Uuid,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<StockMovement>, DomainError> {
+        page: &PageRequest,
+    ) -> Result<Page<StockMovement>, DomainError> {
+        let limit = page.limit();
+        let after: Option<(DateTime<Utc>, Uuid)> = page.after()?;
+        let (after_created_at, after_id) = after.unzip();
+
         let results = sqlx::query!(
             r#"
             SELECT id, item_id, location_id, movement_type, quantity,
-                   reference_type, reference_id, reason, created_at, created_by
+                   reference_type, reference_id, reason, created_at, created_by, stock_status
             FROM stock_movements
             WHERE item_id = $1 AND location_id = $2 AND tenant_id = get_current_tenant_id()
-            ORDER BY created_at DESC
-            LIMIT $3 OFFSET $4
+              AND ($3::timestamptz IS NULL OR (created_at, id) < ($3, $4::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $5
             "#,
             item_id,
             location_id,
-            limit,
-            offset
+            after_created_at,
+            after_id,
+            limit + 1
         )
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         let mut movements = Vec::new();
         for row in results {
@@ -319,17 +760,48 @@ impl StockRepository for PostgresStockRepository {
                 reason: row.reason,
                 created_at: row.created_at,
                 created_by: row.created_by,
+                stock_status: StockStatus::from_str(&row.stock_status)?,
             });
         }
 
-        Ok(movements)
+        Ok(Page::from_rows(movements, limit, |m| (m.created_at, m.id)))
+    }
+
+    fn stream_movements(
+        &self,
+        filter: &StockMovementExportFilter,
+        batch_size: i64,
+    ) -> StockMovementBatches {
+        let pool = Arc::clone(&self.pool);
+        let statement_timeout = self.statement_timeout;
+        let filter = filter.clone();
+        let tenant_id = current_tenant();
+        // A couple of batches of buffer; the cursor only advances as the client reads
+        let (sender, receiver) = mpsc::channel(2);
+
+        tokio::spawn(async move {
+            if let Err(e) = Self::send_movement_batches(
+                &pool,
+                statement_timeout,
+                &filter,
+                tenant_id,
+                batch_size,
+                &sender,
+            )
+            .await
+            {
+                let _ = sender.send(Err(e)).await;
+            }
+        });
+
+        Box::pin(ReceiverStream::new(receiver))
     }
 
     async fn get_movement_by_id(&self, id: Uuid) -> Result<Option<StockMovement>, DomainError> {
         let result = sqlx::query!(
             r#"
             SELECT id, item_id, location_id, movement_type, quantity,
-                   reference_type, reference_id, reason, created_at, created_by
+                   reference_type, reference_id, reason, created_at, created_by, stock_status
             FROM stock_movements
             WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
@@ -337,7 +809,7 @@ impl StockRepository for PostgresStockRepository {
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         match result {
             Some(row) => {
@@ -355,13 +827,14 @@ impl StockRepository for PostgresStockRepository {
                     reason: row.reason,
                     created_at: row.created_at,
                     created_by: row.created_by,
+                    stock_status: StockStatus::from_str(&row.stock_status)?,
                 }))
             }
             None => Ok(None),
         }
     }
 
-    async fn get_total_quantity_on_hand(&self, item_id: Uuid) -> Result<i32, DomainError> {
+    async fn get_total_quantity_on_hand(&self, item_id: Uuid) -> Result<Decimal, DomainError> {
         let result = sqlx::query!(
             r#"
             SELECT COALESCE(SUM(quantity_on_hand), 0) as total
@@ -372,9 +845,9 @@ impl StockRepository for PostgresStockRepository {
         )
         .fetch_one(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
-        Ok(result.total.unwrap_or(0) as i32)
+        Ok(result.total.unwrap_or_default())
     }
 
     async fn initialize_stock_level(
@@ -382,6 +855,7 @@ impl StockRepository for PostgresStockRepository {
         item_id: Uuid,
         location_id: Uuid,
     ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
         sqlx::query!(
             r#"
             INSERT INTO stock_levels (item_id, location_id, quantity_on_hand, updated_at, tenant_id)
@@ -391,10 +865,13 @@ impl StockRepository for PostgresStockRepository {
             item_id,
             location_id
         )
-        .execute(&*self.pool)
+        .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
+        PostgresItemAvailabilityRepository::refresh_in_tx(&mut tx, &[(item_id, location_id)])
+            .await?;
+        tx.commit().await?;
         Ok(())
     }
 
@@ -415,37 +892,39 @@ impl StockRepository for PostgresStockRepository {
         )
         .fetch_one(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(result.exists.unwrap_or(false))
     }
 
     async fn get_stock_levels_below_threshold(
         &self,
-        threshold: i32,
-        limit: i64,
-        cursor: Option<String>,
-    ) -> Result<crate::domain::services::stock_repository::PaginatedStockLevels, DomainError> {
-        let offset = cursor
-            .as_ref()
-            .and_then(|c| c.parse::<i64>().ok())
-            .unwrap_or(0);
+        threshold: Decimal,
+        page: &PageRequest,
+    ) -> Result<Page<StockLevel>, DomainError> {
+        let limit = page.limit();
+        let after: Option<(Uuid, Uuid)> = page.after()?;
+        let (after_item_id, after_location_id) = after.unzip();
 
+        let mut tx = begin_with_statement_timeout(&self.pool, self.statement_timeout).await?;
         let results: Vec<_> = sqlx::query!(
             r#"
-            SELECT item_id, location_id, quantity_on_hand, last_movement_id, updated_at
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
             FROM stock_levels
-            WHERE quantity_on_hand <= $1 AND tenant_id = get_current_tenant_id()
+            WHERE quantity_on_hand - quantity_quarantine - quantity_damaged - quantity_reserved <= $1 AND tenant_id = get_current_tenant_id()
+              AND ($2::uuid IS NULL OR (item_id, location_id) > ($2, $3::uuid))
             ORDER BY item_id, location_id
-            LIMIT $2 OFFSET $3
+            LIMIT $4
             "#,
             threshold,
-            limit,
-            offset
+            after_item_id,
+            after_location_id,
+            limit + 1
         )
-        .fetch_all(&*self.pool)
+        .fetch_all(&mut *tx)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         let stock_levels: Vec<StockLevel> = results
             .into_iter()
@@ -453,51 +932,46 @@ impl StockRepository for PostgresStockRepository {
                 item_id: row.item_id,
                 location_id: row.location_id,
                 quantity_on_hand: row.quantity_on_hand,
+                quantity_reserved: row.quantity_reserved,
+                quantity_quarantine: row.quantity_quarantine,
+                quantity_damaged: row.quantity_damaged,
+                quantity_in_transit: row.quantity_in_transit,
                 last_movement_id: row.last_movement_id,
                 updated_at: row.updated_at,
             })
             .collect();
 
-        let next_cursor = if stock_levels.len() == limit as usize {
-            Some((offset + limit).to_string())
-        } else {
-            None
-        };
-
-        Ok(
-            crate::domain::services::stock_repository::PaginatedStockLevels {
-                items: stock_levels,
-                next_cursor,
-            },
-        )
+        Ok(Page::from_rows(stock_levels, limit, |level| {
+            (level.item_id, level.location_id)
+        }))
     }
 
     async fn get_stock_levels_by_location(
         &self,
         location_id: Uuid,
-        limit: i64,
-        cursor: Option<String>,
-    ) -> Result<crate::domain::services::stock_repository::PaginatedStockLevels, DomainError> {
-        let offset = cursor
-            .as_ref()
-            .and_then(|c| c.parse::<i64>().ok())
-            .unwrap_or(0);
+        page: &PageRequest,
+    ) -> Result<Page<StockLevel>, DomainError> {
+        let limit = page.limit();
+        let after: Option<Uuid> = page.after()?;
 
+        let mut tx = begin_with_statement_timeout(&self.pool, self.statement_timeout).await?;
         let results: Vec<_> = sqlx::query!(
             r#"
-            SELECT item_id, location_id, quantity_on_hand, last_movement_id, updated_at
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
             FROM stock_levels
             WHERE location_id = $1 AND tenant_id = get_current_tenant_id()
+              AND ($2::uuid IS NULL OR item_id > $2)
             ORDER BY item_id
-            LIMIT $2 OFFSET $3
+            LIMIT $3
             "#,
             location_id,
-            limit,
-            offset
+            after,
+            limit + 1
         )
-        .fetch_all(&*self.pool)
+        .fetch_all(&mut *tx)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         let stock_levels: Vec<StockLevel> = results
             .into_iter()
@@ -505,49 +979,102 @@ impl StockRepository for PostgresStockRepository {
                 item_id: row.item_id,
                 location_id: row.location_id,
                 quantity_on_hand: row.quantity_on_hand,
+                quantity_reserved: row.quantity_reserved,
+                quantity_quarantine: row.quantity_quarantine,
+                quantity_damaged: row.quantity_damaged,
+                quantity_in_transit: row.quantity_in_transit,
                 last_movement_id: row.last_movement_id,
                 updated_at: row.updated_at,
             })
             .collect();
 
-        let next_cursor = if stock_levels.len() == limit as usize {
-            Some((offset + limit).to_string())
-        } else {
-            None
-        };
+        Ok(Page::from_rows(stock_levels, limit, |level| level.item_id))
+    }
 
-        Ok(
-            crate::domain::services::stock_repository::PaginatedStockLevels {
-                items: stock_levels,
-                next_cursor,
-            },
+    async fn dead_stock(
+        &self,
+        since: DateTime<Utc>,
+        location_id: Option<Uuid>,
+    ) -> Result<Vec<DeadStockItem>, DomainError> {
+        let mut tx = begin_with_statement_timeout(&self.pool, self.statement_timeout).await?;
+        let rows = sqlx::query(
+            r#"
+            WITH on_hand AS (
+                SELECT item_id, SUM(quantity_on_hand)::numeric AS quantity_on_hand
+                FROM stock_levels
+                WHERE tenant_id = get_current_tenant_id()
+                  AND ($2::uuid IS NULL OR location_id = $2)
+                GROUP BY item_id
+                HAVING SUM(quantity_on_hand) > 0
+            ),
+            movements AS (
+                SELECT item_id,
+                       MAX(created_at) AS last_movement_at,
+                       MAX(created_at) FILTER (WHERE movement_type = 'outbound') AS last_outbound_at
+                FROM stock_movements
+                WHERE tenant_id = get_current_tenant_id()
+                  AND ($2::uuid IS NULL OR location_id = $2)
+                  AND item_id IN (SELECT item_id FROM on_hand)
+                GROUP BY item_id
+            )
+            SELECT i.id AS item_id, i.sku, i.name, o.quantity_on_hand, i.cost_price,
+                   ROUND(o.quantity_on_hand * i.cost_price, 2) AS value,
+                   m.last_movement_at, m.last_outbound_at
+            FROM on_hand o
+            JOIN items i ON i.id = o.item_id
+            LEFT JOIN movements m ON m.item_id = o.item_id
+            WHERE m.last_outbound_at IS NULL OR m.last_outbound_at < $1
+            ORDER BY value DESC, i.sku
+            "#,
         )
+        .bind(since)
+        .bind(location_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(DeadStockItem {
+                    item_id: row.try_get("item_id")?,
+                    sku: row.try_get("sku")?,
+                    name: row.try_get("name")?,
+                    quantity_on_hand: row.try_get("quantity_on_hand")?,
+                    unit_cost: row.try_get("cost_price")?,
+                    value: row.try_get("value")?,
+                    last_movement_at: row.try_get("last_movement_at")?,
+                    last_outbound_at: row.try_get("last_outbound_at")?,
+                })
+            })
+            .collect()
     }
 
     async fn get_all_stock_levels(
         &self,
-        limit: i64,
-        cursor: Option<String>,
-    ) -> Result<crate::domain::services::stock_repository::PaginatedStockLevels, DomainError> {
-        let offset = cursor
-            .as_ref()
-            .and_then(|c| c.parse::<i64>().ok())
-            .unwrap_or(0);
+        page: &PageRequest,
+    ) -> Result<Page<StockLevel>, DomainError> {
+        let limit = page.limit();
+        let after: Option<(Uuid, Uuid)> = page.after()?;
+        let (after_item_id, after_location_id) = after.unzip();
 
+        let mut tx = begin_with_statement_timeout(&self.pool, self.statement_timeout).await?;
         let results: Vec<_> = sqlx::query!(
             r#"
-            SELECT item_id, location_id, quantity_on_hand, last_movement_id, updated_at
+            SELECT item_id, location_id, quantity_on_hand, quantity_reserved, quantity_quarantine, quantity_damaged, quantity_in_transit,
+                   last_movement_id, updated_at
             FROM stock_levels
             WHERE tenant_id = get_current_tenant_id()
+              AND ($1::uuid IS NULL OR (item_id, location_id) > ($1, $2::uuid))
             ORDER BY item_id, location_id
-            LIMIT $1 OFFSET $2
+            LIMIT $3
             "#,
-            limit,
-            offset
+            after_item_id,
+            after_location_id,
+            limit + 1
         )
-        .fetch_all(&*self.pool)
+        .fetch_all(&mut *tx)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         let stock_levels: Vec<StockLevel> = results
             .into_iter()
@@ -555,22 +1082,219 @@ impl StockRepository for PostgresStockRepository {
                 item_id: row.item_id,
                 location_id: row.location_id,
                 quantity_on_hand: row.quantity_on_hand,
+                quantity_reserved: row.quantity_reserved,
+                quantity_quarantine: row.quantity_quarantine,
+                quantity_damaged: row.quantity_damaged,
+                quantity_in_transit: row.quantity_in_transit,
                 last_movement_id: row.last_movement_id,
                 updated_at: row.updated_at,
             })
             .collect();
 
-        let next_cursor = if stock_levels.len() == limit as usize {
-            Some((offset + limit).to_string())
+        Ok(Page::from_rows(stock_levels, limit, |level| {
+            (level.item_id, level.location_id)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movement(item_id: Uuid, location_id: Uuid, quantity: i64) -> StockMovement {
+        let movement_type = if quantity < 0 {
+            MovementType::Outbound
         } else {
-            None
+            MovementType::Inbound
+        };
+        StockMovement::new(
+            item_id,
+            location_id,
+            movement_type,
+            Decimal::from(quantity),
+            ReferenceType::SalesOrder,
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_level_changes_apply_in_order_and_sum_per_level() {
+        let (item, other_item, location) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut stocked = StockLevel::new(other_item, location);
+        stocked.quantity_on_hand = Decimal::from(10);
+        let movements = vec![
+            movement(item, location, 5),
+            movement(other_item, location, -4),
+            movement(item, location, -3),
+        ];
+
+        let changes = stock_level_changes(vec![stocked], &movements).unwrap();
+
+        assert_eq!(changes.len(), 2);
+        assert!(!changes[0].exists);
+        assert_eq!(changes[0].after.item_id, item);
+        assert_eq!(changes[0].after.quantity_on_hand, Decimal::from(2));
+        assert_eq!(changes[0].after.last_movement_id, Some(movements[2].id));
+        assert_eq!(changes[0].moved_at, movements[2].created_at);
+        assert!(changes[1].exists);
+        assert_eq!(
+            changes[1].after.quantity_on_hand - changes[1].before.quantity_on_hand,
+            Decimal::from(-4)
+        );
+
+        // Taking stock before it has been brought in fails the whole batch
+        let reversed = vec![movements[2].clone(), movements[0].clone()];
+        assert!(stock_level_changes(Vec::new(), &reversed).is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn test_stock_rows_carry_the_tenant_that_owns_the_item() {
+        use crate::shared::tenant_scope::with_tenant;
+        use crate::test_support::database::TestDatabase;
+
+        let db = TestDatabase::connect().await;
+        let admin = &db.admin;
+        let repository = PostgresStockRepository::new(Arc::clone(&db.pool));
+        let owner = db.create_tenant("Stock row owner").await;
+        let other = db.create_tenant("Stock row other").await;
+        let location_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO locations (name, code, tenant_id) VALUES ('Row dock', 'ROWS', $1) RETURNING id",
+        )
+        .bind(owner)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        let item_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO items (sku, name, unit, cost_price, tenant_id)
+             VALUES ('ROWS-1', 'Row item', 'each', 1, $1) RETURNING id",
+        )
+        .bind(owner)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+
+        with_tenant(
+            owner,
+            repository.record_movement(&movement(item_id, location_id, 5)),
+        )
+        .await
+        .unwrap();
+        // Another tenant can't move stock of an item it doesn't own
+        let result = with_tenant(
+            other,
+            repository.record_movement(&movement(item_id, location_id, 5)),
+        )
+        .await;
+        assert!(matches!(result, Err(DomainError::NotFound(_))));
+
+        let tenants: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT tenant_id FROM stock_movements WHERE item_id = $1
+             UNION ALL SELECT tenant_id FROM stock_levels WHERE item_id = $1",
+        )
+        .bind(item_id)
+        .fetch_all(admin)
+        .await
+        .unwrap();
+        assert_eq!(tenants, [owner, owner]);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn test_dead_stock_lists_stocked_items_without_recent_outbound_movement() {
+        use crate::shared::tenant_scope::with_tenant;
+        use crate::test_support::database::TestDatabase;
+
+        let db = TestDatabase::connect().await;
+        let admin = &db.admin;
+        let repository = PostgresStockRepository::new(Arc::clone(&db.pool));
+        let tenant_id = db.create_tenant("Dead stock").await;
+        let location_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO locations (name, code, tenant_id) VALUES ('Dead dock', 'DEAD', $1) RETURNING id",
+        )
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        let now = Utc::now();
+        // An item with `on_hand` units at `cost`, and a movement of each
+        // (type, quantity, days ago)
+        let stock = |sku: &'static str,
+                     cost: i64,
+                     on_hand: i64,
+                     moves: Vec<(&'static str, i64, i64)>| async move {
+            let item_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO items (sku, name, unit, cost_price, tenant_id)
+                 VALUES ($1, $1, 'each', $2, $3) RETURNING id",
+            )
+            .bind(sku)
+            .bind(Decimal::from(cost))
+            .bind(tenant_id)
+            .fetch_one(admin)
+            .await
+            .unwrap();
+            sqlx::query(
+                "INSERT INTO stock_levels (item_id, location_id, quantity_on_hand, tenant_id)
+                 VALUES ($1, $2, $3, $4)",
+            )
+            .bind(item_id)
+            .bind(location_id)
+            .bind(Decimal::from(on_hand))
+            .bind(tenant_id)
+            .execute(admin)
+            .await
+            .unwrap();
+            for (movement_type, quantity, days_ago) in moves {
+                sqlx::query(
+                    "INSERT INTO stock_movements (item_id, location_id, movement_type, quantity,
+                                                  reference_type, created_at, tenant_id)
+                     VALUES ($1, $2, $3, $4, 'adjustment', $5, $6)",
+                )
+                .bind(item_id)
+                .bind(location_id)
+                .bind(movement_type)
+                .bind(Decimal::from(quantity))
+                .bind(now - chrono::Duration::days(days_ago))
+                .bind(tenant_id)
+                .execute(admin)
+                .await
+                .unwrap();
+            }
         };
+        // Last shipped before the window, though restocked within it
+        stock(
+            "DEAD-STALE",
+            5,
+            10,
+            vec![("outbound", -2, 60), ("inbound", 3, 5)],
+        )
+        .await;
+        // Shipped within the window
+        stock("DEAD-MOVING", 5, 10, vec![("outbound", -2, 5)]).await;
+        // Never moved since it was counted in
+        stock("DEAD-NEVER", 1, 4, Vec::new()).await;
+        // Nothing left on hand to tie up value
+        stock("DEAD-EMPTY", 5, 0, vec![("outbound", -2, 60)]).await;
 
-        Ok(
-            crate::domain::services::stock_repository::PaginatedStockLevels {
-                items: stock_levels,
-                next_cursor,
-            },
+        let dead = with_tenant(
+            tenant_id,
+            repository.dead_stock(now - chrono::Duration::days(30), None),
         )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            dead.iter()
+                .map(|item| item.sku.as_str())
+                .collect::<Vec<_>>(),
+            ["DEAD-STALE", "DEAD-NEVER"]
+        );
+        assert_eq!(dead[0].value, Decimal::from(50));
+        assert!(dead[0].last_outbound_at.unwrap() < now - chrono::Duration::days(59));
+        assert!(dead[0].last_movement_at.unwrap() > now - chrono::Duration::days(6));
+        assert_eq!(dead[1].last_movement_at, None);
     }
 }
@@ -0,0 +1,72 @@
+use crate::domain::entities::tenant_audit::{TenantAuditAction, TenantAuditEntry};
+use crate::domain::services::tenant_audit_repository::TenantAuditRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct PostgresTenantAuditRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresTenantAuditRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TenantAuditRepository for PostgresTenantAuditRepository {
+    async fn record(&self, entry: &TenantAuditEntry) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO tenant_audit_log (id, tenant_id, action, actor_id, details, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(entry.id)
+        .bind(entry.tenant_id)
+        .bind(entry.action.as_str())
+        .bind(entry.actor_id)
+        .bind(&entry.details)
+        .bind(entry.created_at)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn list_for_tenant(
+        &self,
+        tenant_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<TenantAuditEntry>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, tenant_id, action, actor_id, details, created_at
+            FROM tenant_audit_log
+            WHERE tenant_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let action: String = row.try_get("action")?;
+                Ok(TenantAuditEntry {
+                    id: row.try_get("id")?,
+                    tenant_id: row.try_get("tenant_id")?,
+                    action: TenantAuditAction::from_str(&action)?,
+                    actor_id: row.try_get("actor_id")?,
+                    details: row.try_get("details")?,
+                    created_at: row.try_get("created_at")?,
+                })
+            })
+            .collect()
+    }
+}
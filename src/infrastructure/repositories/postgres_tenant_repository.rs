@@ -39,7 +39,7 @@ impl TenantRepository for PostgresTenantRepository {
         .bind(tenant.updated_at)
         .execute(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         Ok(())
     }
@@ -56,7 +56,7 @@ impl TenantRepository for PostgresTenantRepository {
         .bind(tenant_id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         if let Some(row) = row {
             let tenant_type = TenantType::from_str(row.try_get("tenant_type")?)?;
@@ -90,7 +90,7 @@ impl TenantRepository for PostgresTenantRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         let mut tenants = Vec::new();
         for row in rows {
@@ -119,7 +119,7 @@ impl TenantRepository for PostgresTenantRepository {
         // Validate status
         TenantStatus::from_str(status)?;
 
-        sqlx::query(
+        let result = sqlx::query(
             r#"
             UPDATE tenants SET status = $1, updated_at = NOW() WHERE id = $2
             "#,
@@ -128,14 +128,46 @@ impl TenantRepository for PostgresTenantRepository {
         .bind(tenant_id)
         .execute(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Tenant {} not found", tenant_id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn update_tenant(&self, tenant: &Tenant) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE tenants
+            SET name = $1, tenant_type = $2, tier = $3, status = $4, expires_at = $5, updated_at = $6
+            WHERE id = $7
+            "#,
+        )
+        .bind(&tenant.name)
+        .bind(tenant.tenant_type.as_str())
+        .bind(tenant.tier.as_str())
+        .bind(tenant.status.as_str())
+        .bind(tenant.expires_at)
+        .bind(tenant.updated_at)
+        .bind(tenant.id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Tenant {} not found", tenant.id).into(),
+            ));
+        }
         Ok(())
     }
 
     async fn delete_tenant(&self, tenant_id: Uuid) -> Result<(), DomainError> {
         // Mark as deleting rather than actually deleting
-        sqlx::query(
+        let result = sqlx::query(
             r#"
             UPDATE tenants SET status = 'DELETING', updated_at = NOW() WHERE id = $1
             "#,
@@ -143,8 +175,13 @@ impl TenantRepository for PostgresTenantRepository {
         .bind(tenant_id)
         .execute(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Tenant {} not found", tenant_id).into(),
+            ));
+        }
         Ok(())
     }
 
@@ -162,7 +199,7 @@ impl TenantRepository for PostgresTenantRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         let mut tenants = Vec::new();
         for row in rows {
@@ -194,7 +231,7 @@ impl TenantRepository for PostgresTenantRepository {
             .bind(tenant_id)
             .execute(&self.pool)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         Ok(())
     }
@@ -211,7 +248,7 @@ impl TenantRepository for PostgresTenantRepository {
         .bind(tenant_id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         if let Some(row) = row {
             let tier_str: String = row.try_get("tier")?;
@@ -221,4 +258,96 @@ impl TenantRepository for PostgresTenantRepository {
             Ok(None)
         }
     }
+
+    async fn get_base_currency(&self, tenant_id: Uuid) -> Result<Option<String>, DomainError> {
+        let currency = sqlx::query_scalar("SELECT base_currency FROM tenants WHERE id = $1")
+            .bind(tenant_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        Ok(currency)
+    }
+
+    async fn set_base_currency(
+        &self,
+        tenant_id: Uuid,
+        currency: &str,
+    ) -> Result<bool, DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE tenants SET base_currency = $2, updated_at = NOW()
+            WHERE id = $1
+              AND NOT EXISTS (SELECT 1 FROM purchase_orders WHERE tenant_id = $1 AND currency IS NOT NULL)
+              AND NOT EXISTS (SELECT 1 FROM sales_orders WHERE tenant_id = $1 AND currency IS NOT NULL)
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(currency)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_timezone(&self, tenant_id: Uuid) -> Result<Option<String>, DomainError> {
+        let timezone = sqlx::query_scalar("SELECT timezone FROM tenants WHERE id = $1")
+            .bind(tenant_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        Ok(timezone)
+    }
+
+    async fn set_timezone(&self, tenant_id: Uuid, timezone: &str) -> Result<(), DomainError> {
+        let result =
+            sqlx::query("UPDATE tenants SET timezone = $2, updated_at = NOW() WHERE id = $1")
+                .bind(tenant_id)
+                .bind(timezone)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Tenant {} not found", tenant_id).into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn get_admin_two_factor_required(&self, tenant_id: Uuid) -> Result<bool, DomainError> {
+        let required: Option<bool> =
+            sqlx::query_scalar("SELECT require_admin_two_factor FROM tenants WHERE id = $1")
+                .bind(tenant_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        Ok(required.unwrap_or(false))
+    }
+
+    async fn set_admin_two_factor_required(
+        &self,
+        tenant_id: Uuid,
+        required: bool,
+    ) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "UPDATE tenants SET require_admin_two_factor = $2, updated_at = NOW() WHERE id = $1",
+        )
+        .bind(tenant_id)
+        .bind(required)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Tenant {} not found", tenant_id).into(),
+            ));
+        }
+        Ok(())
+    }
 }
@@ -1,13 +1,42 @@
+use crate::domain::entities::inventory::StockMovement;
+use crate::domain::entities::list_filter::ListFilter;
 use crate::domain::entities::transfer::{
-    CreateTransferRequest, MovementType, ReceiveTransferRequest, ReferenceType, StockMovement,
-    Transfer, TransferLine, TransferStatus,
+    CreateTransferRequest, ReceiveTransferRequest, ShipTransferLineRequest, Transfer, TransferLine,
+    TransferStatus,
 };
 use crate::domain::services::transfer_repository::TransferRepository;
+use crate::infrastructure::repositories::list_filter_sql::{push_filters, ListColumns, ListQuery};
+use crate::infrastructure::repositories::postgres_stock_repository::PostgresStockRepository;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use async_trait::async_trait;
-use sqlx::{PgPool, Postgres, Transaction};
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+const TRANSFER_LIST_COLUMNS: ListColumns = ListColumns {
+    resource: "transfers",
+    id: "id",
+    status: Some("status"),
+    created_at: "created_at",
+    supplier_id: None,
+    customer_id: None,
+    sku: Some(
+        "SELECT i.sku FROM transfer_lines l JOIN items i ON i.id = l.item_id WHERE l.transfer_id = transfers.id",
+    ),
+    category: Some(
+        "SELECT i.category FROM transfer_lines l JOIN items i ON i.id = l.item_id WHERE l.transfer_id = transfers.id",
+    ),
+    search: &["transfer_number", "notes"],
+    sortable: &[
+        ("transfer_number", "transfer_number", "text"),
+        ("status", "status", "text"),
+        ("total_quantity", "total_quantity", "numeric"),
+        ("created_at", "created_at", "timestamptz"),
+        ("updated_at", "updated_at", "timestamptz"),
+    ],
+};
+
 pub struct PostgresTransferRepository {
     pool: std::sync::Arc<PgPool>,
 }
@@ -27,13 +56,13 @@ impl PostgresTransferRepository {
             r#"
             SELECT id, transfer_number, from_location_id, to_location_id, status, total_quantity, notes, created_by, created_at, updated_at
             FROM transfers
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             id
         )
         .fetch_optional(&mut **tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         let transfer_row = match transfer_row {
             Some(row) => row,
@@ -43,16 +72,17 @@ impl PostgresTransferRepository {
         // Get transfer lines
         let line_rows = sqlx::query!(
             r#"
-            SELECT id, transfer_id, item_id, quantity, quantity_received, created_at, updated_at
+            SELECT id, transfer_id, item_id, quantity, quantity_shipped, quantity_received, quantity_short,
+                   created_at, updated_at
             FROM transfer_lines
-            WHERE transfer_id = $1
+            WHERE transfer_id = $1 AND tenant_id = get_current_tenant_id()
             ORDER BY created_at
             "#,
             id
         )
         .fetch_all(&mut **tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         let lines: Vec<TransferLine> = line_rows
             .into_iter()
@@ -61,7 +91,9 @@ impl PostgresTransferRepository {
                 transfer_id: row.transfer_id,
                 item_id: row.item_id,
                 quantity: row.quantity,
+                quantity_shipped: row.quantity_shipped,
                 quantity_received: row.quantity_received,
+                quantity_short: row.quantity_short,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
             })
@@ -83,6 +115,78 @@ impl PostgresTransferRepository {
 
         Ok(Some((transfer, lines)))
     }
+
+    /// Load a page of transfers with their lines in two queries rather than
+    /// two per transfer
+    async fn find_by_ids(
+        &self,
+        ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, (Transfer, Vec<TransferLine>)>, DomainError> {
+        let transfer_rows = sqlx::query!(
+            r#"
+            SELECT id, transfer_number, from_location_id, to_location_id, status, total_quantity, notes, created_by, created_at, updated_at
+            FROM transfers
+            WHERE id = ANY($1) AND tenant_id = (SELECT get_current_tenant_id())
+            "#,
+            ids
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        let line_rows = sqlx::query!(
+            r#"
+            SELECT id, transfer_id, item_id, quantity, quantity_shipped, quantity_received, quantity_short,
+                   created_at, updated_at
+            FROM transfer_lines
+            WHERE transfer_id = ANY($1) AND tenant_id = (SELECT get_current_tenant_id())
+            ORDER BY created_at
+            "#,
+            ids
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
+
+        let mut lines: HashMap<Uuid, Vec<TransferLine>> = HashMap::new();
+        for row in line_rows {
+            lines
+                .entry(row.transfer_id)
+                .or_default()
+                .push(TransferLine {
+                    id: row.id,
+                    transfer_id: row.transfer_id,
+                    item_id: row.item_id,
+                    quantity: row.quantity,
+                    quantity_shipped: row.quantity_shipped,
+                    quantity_received: row.quantity_received,
+                    quantity_short: row.quantity_short,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                });
+        }
+
+        transfer_rows
+            .into_iter()
+            .map(|row| {
+                let lines = lines.remove(&row.id).unwrap_or_default();
+                let transfer = Transfer {
+                    id: row.id,
+                    transfer_number: row.transfer_number,
+                    from_location_id: row.from_location_id,
+                    to_location_id: row.to_location_id,
+                    status: TransferStatus::from_str(&row.status)?,
+                    total_quantity: row.total_quantity,
+                    notes: row.notes,
+                    created_by: row.created_by,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                    lines: lines.clone(),
+                };
+                Ok((row.id, (transfer, lines)))
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -92,13 +196,13 @@ impl TransferRepository for PostgresTransferRepository {
             .pool
             .begin()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Insert transfer
         sqlx::query!(
             r#"
-            INSERT INTO transfers (id, transfer_number, from_location_id, to_location_id, status, total_quantity, notes, created_by, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            INSERT INTO transfers (id, transfer_number, from_location_id, to_location_id, status, total_quantity, notes, created_by, created_at, updated_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, get_current_tenant_id())
             "#,
             transfer.id,
             transfer.transfer_number,
@@ -113,14 +217,14 @@ impl TransferRepository for PostgresTransferRepository {
         )
         .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Insert transfer lines
         for line in &transfer.lines {
             sqlx::query!(
                 r#"
-                INSERT INTO transfer_lines (id, transfer_id, item_id, quantity, quantity_received, created_at, updated_at)
-                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                INSERT INTO transfer_lines (id, transfer_id, item_id, quantity, quantity_received, created_at, updated_at, tenant_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, get_current_tenant_id())
                 "#,
                 line.id,
                 line.transfer_id,
@@ -132,12 +236,12 @@ impl TransferRepository for PostgresTransferRepository {
             )
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
         }
 
         tx.commit()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         Ok(())
     }
@@ -150,7 +254,7 @@ impl TransferRepository for PostgresTransferRepository {
             .pool
             .begin()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         self.find_by_id_with_tx(&mut tx, id).await
     }
@@ -161,13 +265,13 @@ impl TransferRepository for PostgresTransferRepository {
     ) -> Result<Option<(Transfer, Vec<TransferLine>)>, DomainError> {
         let transfer_row = sqlx::query!(
             r#"
-            SELECT id FROM transfers WHERE transfer_number = $1
+            SELECT id FROM transfers WHERE transfer_number = $1 AND tenant_id = get_current_tenant_id()
             "#,
             transfer_number
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         match transfer_row {
             Some(row) => self.find_by_id(row.id).await,
@@ -176,11 +280,11 @@ impl TransferRepository for PostgresTransferRepository {
     }
 
     async fn update(&self, transfer: &Transfer) -> Result<(), DomainError> {
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             UPDATE transfers
             SET status = $2, total_quantity = $3, notes = $4, updated_at = $5
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             transfer.id,
             transfer.status.as_str(),
@@ -190,79 +294,110 @@ impl TransferRepository for PostgresTransferRepository {
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Transfer {} not found", transfer.id).into(),
+            ));
+        }
         Ok(())
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
-            DELETE FROM transfers WHERE id = $1
+            DELETE FROM transfers WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             id
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Transfer {} not found", id).into(),
+            ));
+        }
         Ok(())
     }
 
     async fn list(
         &self,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<(Transfer, Vec<TransferLine>)>, DomainError> {
-        let transfer_rows = sqlx::query!(
-            r#"
-            SELECT id FROM transfers
-            ORDER BY created_at DESC
-            LIMIT $1 OFFSET $2
-            "#,
-            limit,
-            offset
-        )
-        .fetch_all(&*self.pool)
-        .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<(Transfer, Vec<TransferLine>)>, DomainError> {
+        let query = ListQuery::new(&TRANSFER_LIST_COLUMNS, filter, page)?;
+        let mut builder = QueryBuilder::new(format!(
+            "SELECT id, {} FROM transfers WHERE tenant_id = get_current_tenant_id()",
+            query.sort_key_column()
+        ));
+        query.push_tail(&mut builder)?;
+
+        let rows = builder
+            .build()
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
-        let mut results = Vec::new();
-        for row in transfer_rows {
-            if let Some(result) = self.find_by_id(row.id).await? {
-                results.push(result);
-            }
-        }
+        let cursors = rows
+            .iter()
+            .map(|row| query.cursor_for(row))
+            .collect::<Result<Vec<_>, _>>()?;
+        let ids: Vec<Uuid> = cursors.iter().map(|cursor| cursor.id).collect();
+        let mut transfers = self.find_by_ids(&ids).await?;
 
-        Ok(results)
+        let results = cursors
+            .into_iter()
+            .filter_map(|cursor| {
+                let transfer = transfers.remove(&cursor.id)?;
+                Some((cursor, transfer))
+            })
+            .collect();
+
+        Ok(query.page(results))
     }
 
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError> {
+        let mut builder = QueryBuilder::new(
+            "SELECT COUNT(*) FROM transfers WHERE tenant_id = get_current_tenant_id()",
+        );
+        push_filters(&mut builder, filter, &TRANSFER_LIST_COLUMNS)?;
+
+        builder
+            .build_query_scalar()
+            .fetch_one(&*self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))
+    }
     async fn ship_transfer(
         &self,
         id: Uuid,
+        shipped_lines: Vec<ShipTransferLineRequest>,
         created_by: Uuid,
     ) -> Result<(Transfer, Vec<TransferLine>, Vec<StockMovement>), DomainError> {
         let mut tx = self
             .pool
             .begin()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Get current transfer
-        let (mut transfer, lines) = self
+        let (mut transfer, _) = self
             .find_by_id_with_tx(&mut tx, id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Transfer {} not found", id)))?;
+            .ok_or_else(|| DomainError::NotFound(format!("Transfer {} not found", id).into()))?;
 
         // Ship the transfer (this validates and creates stock movements)
-        let stock_movements = transfer.ship()?;
+        let stock_movements = transfer.ship(shipped_lines, created_by)?;
 
         // Update transfer status
         sqlx::query!(
             r#"
             UPDATE transfers
             SET status = $2, updated_at = $3
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             transfer.id,
             transfer.status.as_str(),
@@ -270,65 +405,63 @@ impl TransferRepository for PostgresTransferRepository {
         )
         .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
-        // Insert stock movements
-        for movement in &stock_movements {
+        // Update shipped quantities
+        for line in &transfer.lines {
             sqlx::query!(
                 r#"
-                INSERT INTO stock_movements (id, item_id, location_id, movement_type, quantity, reference_type, reference_id, reason, created_at, created_by)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                UPDATE transfer_lines
+                SET quantity_shipped = $2, updated_at = $3
+                WHERE id = $1 AND tenant_id = get_current_tenant_id()
                 "#,
-                movement.id,
-                movement.item_id,
-                movement.location_id,
-                movement.movement_type.as_str(),
-                movement.quantity,
-                movement.reference_type.as_str(),
-                movement.reference_id,
-                movement.reason,
-                movement.created_at,
-                movement.created_by
+                line.id,
+                line.quantity_shipped,
+                line.updated_at
             )
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
         }
 
+        // Record stock movements and apply them to stock levels
+        PostgresStockRepository::record_movements_in_tx(&mut tx, &stock_movements).await?;
+
         tx.commit()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
+        let lines = transfer.lines.clone();
         Ok((transfer, lines, stock_movements))
     }
 
     async fn receive_transfer(
         &self,
         id: Uuid,
-        received_lines: Vec<crate::domain::entities::transfer::ReceiveTransferLineRequest>,
+        request: ReceiveTransferRequest,
         created_by: Uuid,
     ) -> Result<(Transfer, Vec<TransferLine>, Vec<StockMovement>), DomainError> {
         let mut tx = self
             .pool
             .begin()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Get current transfer
-        let (mut transfer, mut lines) = self
+        let (mut transfer, _) = self
             .find_by_id_with_tx(&mut tx, id)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Transfer {} not found", id)))?;
+            .ok_or_else(|| DomainError::NotFound(format!("Transfer {} not found", id).into()))?;
 
         // Receive the transfer (this validates and creates stock movements)
-        let stock_movements = transfer.receive(received_lines)?;
+        let stock_movements = transfer.receive(request, created_by)?;
 
         // Update transfer status
         sqlx::query!(
             r#"
             UPDATE transfers
             SET status = $2, updated_at = $3
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             transfer.id,
             transfer.status.as_str(),
@@ -336,54 +469,35 @@ impl TransferRepository for PostgresTransferRepository {
         )
         .execute(&mut *tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         // Update transfer lines
         for line in &transfer.lines {
             sqlx::query!(
                 r#"
                 UPDATE transfer_lines
-                SET quantity_received = $2, updated_at = $3
-                WHERE id = $1
+                SET quantity_received = $2, quantity_short = $3, updated_at = $4
+                WHERE id = $1 AND tenant_id = get_current_tenant_id()
                 "#,
                 line.id,
                 line.quantity_received,
+                line.quantity_short,
                 line.updated_at
             )
             .execute(&mut *tx)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
         }
 
-        // Insert stock movements
-        for movement in &stock_movements {
-            sqlx::query!(
-                r#"
-                INSERT INTO stock_movements (id, item_id, location_id, movement_type, quantity, reference_type, reference_id, reason, created_at, created_by)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-                "#,
-                movement.id,
-                movement.item_id,
-                movement.location_id,
-                movement.movement_type.as_str(),
-                movement.quantity,
-                movement.reference_type.as_str(),
-                movement.reference_id,
-                movement.reason,
-                movement.created_at,
-                movement.created_by
-            )
-            .execute(&mut *tx)
-            .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-        }
+        // Record stock movements and apply them to stock levels
+        PostgresStockRepository::record_movements_in_tx(&mut tx, &stock_movements).await?;
 
         // Get updated lines
         let updated_lines = transfer.lines.clone();
 
         tx.commit()
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(|e| DomainError::DatabaseError(e.to_string().into()))?;
 
         Ok((transfer, updated_lines, stock_movements))
     }
@@ -0,0 +1,75 @@
+use crate::domain::entities::two_factor::TwoFactor;
+use crate::domain::services::two_factor_repository::TwoFactorRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct PostgresTwoFactorRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresTwoFactorRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TwoFactorRepository for PostgresTwoFactorRepository {
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Option<TwoFactor>, DomainError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT user_id, secret, recovery_code_hashes, last_used_step, enabled_at, created_at
+            FROM user_two_factor
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        Ok(row.map(|row| TwoFactor {
+            user_id: row.user_id,
+            secret: row.secret,
+            recovery_code_hashes: row.recovery_code_hashes,
+            last_used_step: row.last_used_step,
+            enabled_at: row.enabled_at,
+            created_at: row.created_at,
+        }))
+    }
+
+    async fn save(&self, two_factor: &TwoFactor) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO user_two_factor (user_id, secret, recovery_code_hashes, last_used_step, enabled_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (user_id) DO UPDATE SET
+                secret = EXCLUDED.secret,
+                recovery_code_hashes = EXCLUDED.recovery_code_hashes,
+                last_used_step = EXCLUDED.last_used_step,
+                enabled_at = EXCLUDED.enabled_at,
+                created_at = EXCLUDED.created_at
+            "#,
+            two_factor.user_id,
+            two_factor.secret,
+            &two_factor.recovery_code_hashes,
+            two_factor.last_used_step,
+            two_factor.enabled_at,
+            two_factor.created_at
+        )
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: Uuid) -> Result<(), DomainError> {
+        sqlx::query!("DELETE FROM user_two_factor WHERE user_id = $1", user_id)
+            .execute(&*self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::domain::entities::user::User;
+use crate::domain::entities::user::{User, UserRole};
 use crate::domain::services::user_repository::UserRepository;
 use crate::domain::value_objects::{email::Email, password_hash::PasswordHash};
 use crate::shared::error::DomainError;
@@ -22,7 +22,7 @@ impl UserRepository for PostgresUserRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
         let result = sqlx::query!(
             r#"
-            SELECT id, email, password_hash, first_name, last_name, tenant_id, active, created_at, updated_at
+            SELECT id, email, password_hash, first_name, last_name, tenant_id, role, active, created_at, updated_at
             FROM users
             WHERE id = $1
             "#,
@@ -30,12 +30,12 @@ impl UserRepository for PostgresUserRepository {
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         match result {
             Some(row) => {
                 let email = Email::new(row.email).map_err(|_| {
-                    DomainError::ValidationError("Invalid email in database".to_string())
+                    DomainError::ValidationError("Invalid email in database".into())
                 })?;
                 let password_hash = PasswordHash::from_hash(row.password_hash);
 
@@ -46,8 +46,9 @@ impl UserRepository for PostgresUserRepository {
                     first_name: row.first_name.unwrap_or_default(),
                     last_name: row.last_name.unwrap_or_default(),
                     tenant_id: row.tenant_id.ok_or_else(|| {
-                        DomainError::ValidationError("User must have tenant_id".to_string())
+                        DomainError::ValidationError("User must have tenant_id".into())
                     })?,
+                    role: UserRole::from_str(&row.role)?,
                     active: row.active,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
@@ -60,7 +61,7 @@ impl UserRepository for PostgresUserRepository {
     async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError> {
         let result = sqlx::query!(
             r#"
-            SELECT id, email, password_hash, first_name, last_name, tenant_id, active, created_at, updated_at
+            SELECT id, email, password_hash, first_name, last_name, tenant_id, role, active, created_at, updated_at
             FROM users
             WHERE email = $1
             "#,
@@ -68,12 +69,12 @@ impl UserRepository for PostgresUserRepository {
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         match result {
             Some(row) => {
                 let email = Email::new(row.email).map_err(|_| {
-                    DomainError::ValidationError("Invalid email in database".to_string())
+                    DomainError::ValidationError("Invalid email in database".into())
                 })?;
                 let password_hash = PasswordHash::from_hash(row.password_hash);
 
@@ -84,8 +85,9 @@ impl UserRepository for PostgresUserRepository {
                     first_name: row.first_name.unwrap_or_default(),
                     last_name: row.last_name.unwrap_or_default(),
                     tenant_id: row.tenant_id.ok_or_else(|| {
-                        DomainError::ValidationError("User must have tenant_id".to_string())
+                        DomainError::ValidationError("User must have tenant_id".into())
                     })?,
+                    role: UserRole::from_str(&row.role)?,
                     active: row.active,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
@@ -98,8 +100,8 @@ impl UserRepository for PostgresUserRepository {
     async fn save(&self, user: &User) -> Result<(), DomainError> {
         sqlx::query!(
             r#"
-            INSERT INTO users (id, email, password_hash, first_name, last_name, tenant_id, active, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO users (id, email, password_hash, first_name, last_name, tenant_id, role, active, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             "#,
             user.id,
             user.email.as_str(),
@@ -107,22 +109,23 @@ impl UserRepository for PostgresUserRepository {
             user.first_name,
             user.last_name,
             user.tenant_id,
+            user.role.as_str(),
             user.active,
             user.created_at,
             user.updated_at
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(())
     }
 
     async fn update(&self, user: &User) -> Result<(), DomainError> {
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             UPDATE users
-            SET email = $2, password_hash = $3, first_name = $4, last_name = $5, active = $6, updated_at = $7
+            SET email = $2, password_hash = $3, first_name = $4, last_name = $5, role = $6, active = $7, updated_at = $8
             WHERE id = $1
             "#,
             user.id,
@@ -130,18 +133,24 @@ impl UserRepository for PostgresUserRepository {
             user.password_hash.as_str(),
             user.first_name,
             user.last_name,
+            user.role.as_str(),
             user.active,
             user.updated_at
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("User {} not found", user.id).into(),
+            ));
+        }
         Ok(())
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             DELETE FROM users WHERE id = $1
             "#,
@@ -149,8 +158,13 @@ impl UserRepository for PostgresUserRepository {
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("User {} not found", id).into(),
+            ));
+        }
         Ok(())
     }
 
@@ -175,8 +189,42 @@ impl UserRepository for PostgresUserRepository {
             .fetch_one(&*self.pool)
             .await
         }
-        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
 
         Ok(count.unwrap_or(0) > 0)
     }
+
+    async fn list_by_tenant(&self, tenant_id: Uuid) -> Result<Vec<User>, DomainError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, email, password_hash, first_name, last_name, role, active, created_at, updated_at
+            FROM users
+            WHERE tenant_id = $1
+            ORDER BY created_at, id
+            "#,
+            tenant_id
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::ValidationError(format!("Database error: {}", e).into()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(User {
+                    id: row.id,
+                    email: Email::new(row.email).map_err(|_| {
+                        DomainError::ValidationError("Invalid email in database".into())
+                    })?,
+                    password_hash: PasswordHash::from_hash(row.password_hash),
+                    first_name: row.first_name.unwrap_or_default(),
+                    last_name: row.last_name.unwrap_or_default(),
+                    tenant_id,
+                    role: UserRole::from_str(&row.role)?,
+                    active: row.active,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                })
+            })
+            .collect()
+    }
 }
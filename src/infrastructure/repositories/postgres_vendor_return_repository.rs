@@ -0,0 +1,374 @@
+use crate::domain::entities::inventory::StockMovement;
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::vendor_return::{
+    VendorCredit, VendorCreditStatus, VendorReturn, VendorReturnLine, VendorReturnStatus,
+};
+use crate::domain::services::vendor_return_repository::VendorReturnRepository;
+use crate::infrastructure::repositories::list_filter_sql::{push_filters, ListColumns, ListQuery};
+use crate::infrastructure::repositories::postgres_stock_repository::PostgresStockRepository;
+use crate::shared::error::DomainError;
+use crate::shared::i18n::Message;
+use crate::shared::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const VENDOR_RETURN_LIST_COLUMNS: ListColumns = ListColumns {
+    resource: "vendor returns",
+    id: "id",
+    status: Some("status"),
+    created_at: "created_at",
+    supplier_id: Some("supplier_id"),
+    customer_id: None,
+    sku: Some(
+        "SELECT i.sku FROM vendor_return_lines l JOIN items i ON i.id = l.item_id WHERE l.rtv_id = vendor_returns.id",
+    ),
+    category: Some(
+        "SELECT i.category FROM vendor_return_lines l JOIN items i ON i.id = l.item_id WHERE l.rtv_id = vendor_returns.id",
+    ),
+    search: &["rtv_number", "notes"],
+    sortable: &[
+        ("rtv_number", "rtv_number", "text"),
+        ("status", "status", "text"),
+        ("total_quantity", "total_quantity", "numeric"),
+        ("total_amount", "total_amount", "numeric"),
+        ("created_at", "created_at", "timestamptz"),
+        ("updated_at", "updated_at", "timestamptz"),
+    ],
+};
+
+pub struct PostgresVendorReturnRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresVendorReturnRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Load a vendor return; `for_update` locks its row until the transaction ends
+    async fn find_with_tx(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        for_update: bool,
+    ) -> Result<Option<VendorReturn>, DomainError> {
+        let lock = if for_update { " FOR UPDATE" } else { "" };
+        let row = sqlx::query_as::<_, VendorReturnRow>(&format!(
+            "SELECT id, rtv_number, po_id, supplier_id, location_id, status, total_quantity, \
+             total_amount, notes, shipped_at, created_by, created_at, updated_at \
+             FROM vendor_returns WHERE id = $1 AND tenant_id = get_current_tenant_id(){}",
+            lock
+        ))
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let lines = sqlx::query_as!(
+            VendorReturnLine,
+            r#"
+            SELECT id, rtv_id, po_line_id, item_id, quantity, unit_cost, reason, created_at
+            FROM vendor_return_lines
+            WHERE rtv_id = $1 AND tenant_id = get_current_tenant_id()
+            ORDER BY created_at, id
+            "#,
+            id
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(Some(row.into_vendor_return(lines)?))
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct VendorReturnRow {
+    id: Uuid,
+    rtv_number: String,
+    po_id: Uuid,
+    supplier_id: Uuid,
+    location_id: Uuid,
+    status: String,
+    total_quantity: Decimal,
+    total_amount: Decimal,
+    notes: Option<String>,
+    shipped_at: Option<chrono::DateTime<chrono::Utc>>,
+    created_by: Uuid,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl VendorReturnRow {
+    fn into_vendor_return(self, lines: Vec<VendorReturnLine>) -> Result<VendorReturn, DomainError> {
+        Ok(VendorReturn {
+            id: self.id,
+            rtv_number: self.rtv_number,
+            po_id: self.po_id,
+            supplier_id: self.supplier_id,
+            location_id: self.location_id,
+            status: VendorReturnStatus::from_str(&self.status)?,
+            total_quantity: self.total_quantity,
+            total_amount: self.total_amount,
+            notes: self.notes,
+            shipped_at: self.shipped_at,
+            created_by: self.created_by,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            lines,
+        })
+    }
+}
+
+async fn update_status(
+    tx: &mut Transaction<'_, Postgres>,
+    rtv: &VendorReturn,
+) -> Result<(), DomainError> {
+    sqlx::query!(
+        r#"
+        UPDATE vendor_returns
+        SET status = $2, shipped_at = $3, updated_at = $4
+        WHERE id = $1 AND tenant_id = get_current_tenant_id()
+        "#,
+        rtv.id,
+        rtv.status.as_str(),
+        rtv.shipped_at,
+        rtv.updated_at
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+#[async_trait]
+impl VendorReturnRepository for PostgresVendorReturnRepository {
+    async fn create(&self, rtv: &VendorReturn) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Serialize returns against the same PO so the received-quantity check
+        // below sees every other draft
+        sqlx::query!(
+            "SELECT id FROM purchase_orders WHERE id = $1 AND tenant_id = get_current_tenant_id() FOR UPDATE",
+            rtv.po_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| DomainError::NotFound(Message::localized("error.purchase_order_not_found", &[("id", &rtv.po_id)])))?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO vendor_returns (id, rtv_number, po_id, supplier_id, location_id, status, total_quantity, total_amount, notes, created_by, created_at, updated_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, get_current_tenant_id())
+            "#,
+            rtv.id,
+            rtv.rtv_number,
+            rtv.po_id,
+            rtv.supplier_id,
+            rtv.location_id,
+            rtv.status.as_str(),
+            rtv.total_quantity,
+            rtv.total_amount,
+            rtv.notes,
+            rtv.created_by,
+            rtv.created_at,
+            rtv.updated_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for line in &rtv.lines {
+            sqlx::query!(
+                r#"
+                INSERT INTO vendor_return_lines (id, rtv_id, po_line_id, item_id, quantity, unit_cost, reason, created_at, tenant_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, get_current_tenant_id())
+                "#,
+                line.id,
+                line.rtv_id,
+                line.po_line_id,
+                line.item_id,
+                line.quantity,
+                line.unit_cost,
+                line.reason,
+                line.created_at
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let over_returned = sqlx::query_scalar!(
+            r#"
+            SELECT pol.id
+            FROM purchase_order_lines pol
+            JOIN vendor_return_lines vrl ON vrl.po_line_id = pol.id
+            JOIN vendor_returns vr ON vr.id = vrl.rtv_id
+            WHERE pol.po_id = $1 AND vr.status <> 'CANCELLED'
+              AND vr.tenant_id = get_current_tenant_id()
+            GROUP BY pol.id, pol.qty_received
+            HAVING SUM(vrl.quantity) > pol.qty_received
+            LIMIT 1
+            "#,
+            rtv.po_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if let Some(po_line_id) = over_returned {
+            return Err(DomainError::Conflict(
+                format!(
+                    "PO line {} would be returned beyond its received quantity",
+                    po_line_id
+                )
+                .into(),
+            ));
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<VendorReturn>, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        self.find_with_tx(&mut tx, id, false).await
+    }
+
+    async fn find_credit(&self, rtv_id: Uuid) -> Result<Option<VendorCredit>, DomainError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, rtv_id, supplier_id, po_id, amount, status, created_at, updated_at
+            FROM vendor_credits
+            WHERE rtv_id = $1 AND tenant_id = get_current_tenant_id()
+            "#,
+            rtv_id
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(VendorCredit {
+                id: row.id,
+                rtv_id: row.rtv_id,
+                supplier_id: row.supplier_id,
+                po_id: row.po_id,
+                amount: row.amount,
+                status: VendorCreditStatus::from_str(&row.status)?,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+        })
+        .transpose()
+    }
+
+    async fn list(
+        &self,
+        filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<VendorReturn>, DomainError> {
+        let query = ListQuery::new(&VENDOR_RETURN_LIST_COLUMNS, filter, page)?;
+        let mut builder = QueryBuilder::new(format!(
+            "SELECT id, {} FROM vendor_returns WHERE tenant_id = get_current_tenant_id()",
+            query.sort_key_column()
+        ));
+        query.push_tail(&mut builder)?;
+
+        let rows = builder.build().fetch_all(&*self.pool).await?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            let cursor = query.cursor_for(&row)?;
+            if let Some(rtv) = self.find_by_id(cursor.id).await? {
+                results.push((cursor, rtv));
+            }
+        }
+
+        Ok(query.page(results))
+    }
+
+    async fn count(&self, filter: &ListFilter) -> Result<i64, DomainError> {
+        let mut builder = QueryBuilder::new(
+            "SELECT COUNT(*) FROM vendor_returns WHERE tenant_id = get_current_tenant_id()",
+        );
+        push_filters(&mut builder, filter, &VENDOR_RETURN_LIST_COLUMNS)?;
+
+        Ok(builder.build_query_scalar().fetch_one(&*self.pool).await?)
+    }
+
+    async fn returned_quantities(
+        &self,
+        po_id: Uuid,
+    ) -> Result<HashMap<Uuid, Decimal>, DomainError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT vrl.po_line_id, SUM(vrl.quantity) AS "quantity!"
+            FROM vendor_return_lines vrl
+            JOIN vendor_returns vr ON vr.id = vrl.rtv_id
+            WHERE vr.po_id = $1 AND vr.status <> 'CANCELLED'
+              AND vr.tenant_id = get_current_tenant_id()
+            GROUP BY vrl.po_line_id
+            "#,
+            po_id
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.po_line_id, row.quantity))
+            .collect())
+    }
+
+    async fn ship(
+        &self,
+        id: Uuid,
+    ) -> Result<(VendorReturn, Vec<StockMovement>, VendorCredit), DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut rtv = self.find_with_tx(&mut tx, id, true).await?.ok_or_else(|| {
+            DomainError::NotFound(format!("Vendor return {} not found", id).into())
+        })?;
+
+        let (movements, credit) = rtv.ship()?;
+        update_status(&mut tx, &rtv).await?;
+
+        // The units have to be on hand and not held in another status to ship them
+        PostgresStockRepository::record_movements_in_tx(&mut tx, &movements).await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO vendor_credits (id, rtv_id, supplier_id, po_id, amount, status, created_at, updated_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, get_current_tenant_id())
+            "#,
+            credit.id,
+            credit.rtv_id,
+            credit.supplier_id,
+            credit.po_id,
+            credit.amount,
+            credit.status.as_str(),
+            credit.created_at,
+            credit.updated_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok((rtv, movements, credit))
+    }
+
+    async fn cancel(&self, id: Uuid) -> Result<VendorReturn, DomainError> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut rtv = self.find_with_tx(&mut tx, id, true).await?.ok_or_else(|| {
+            DomainError::NotFound(format!("Vendor return {} not found", id).into())
+        })?;
+
+        rtv.cancel()?;
+        update_status(&mut tx, &rtv).await?;
+
+        tx.commit().await?;
+        Ok(rtv)
+    }
+}
@@ -1,10 +1,13 @@
 use crate::domain::entities::webhook::{
     DeliveryStatus, Webhook, WebhookDelivery, WebhookEvent, WebhookEventType, WebhookStatus,
+    MAX_DELIVERY_ATTEMPTS,
 };
+use crate::domain::entities::webhook_filter::WebhookFilter;
 use crate::domain::services::webhook_repository::WebhookRepository;
 use crate::shared::error::DomainError;
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -16,6 +19,52 @@ impl PostgresWebhookRepository {
     pub fn new(pool: Arc<PgPool>) -> Self {
         Self { pool }
     }
+
+    /// Write events to the outbox as part of the caller's transaction, for the
+    /// delivery worker to publish once it commits
+    pub(crate) async fn enqueue_outbox_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        events: &[WebhookEvent],
+    ) -> Result<(), DomainError> {
+        for event in events {
+            sqlx::query(
+                r#"
+                INSERT INTO event_outbox (id, tenant_id, event_type, payload, created_at)
+                VALUES ($1, get_current_tenant_id(), $2, $3, $4)
+                "#,
+            )
+            .bind(event.id)
+            .bind(event.event_type.as_str())
+            .bind(&event.payload)
+            .bind(event.created_at)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to write outbox event: {}", e).into())
+            })?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_filter(value: Option<serde_json::Value>) -> Result<Option<WebhookFilter>, DomainError> {
+    value
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|e| DomainError::DatabaseError(format!("Invalid webhook filter: {}", e).into()))
+}
+
+fn filter_json(webhook: &Webhook) -> Result<Option<serde_json::Value>, DomainError> {
+    webhook
+        .filter
+        .as_ref()
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(|e| {
+            DomainError::InfrastructureError(
+                format!("Failed to serialize webhook filter: {}", e).into(),
+            )
+        })
 }
 
 #[async_trait]
@@ -31,9 +80,10 @@ impl WebhookRepository for PostgresWebhookRepository {
             r#"
             INSERT INTO webhooks (
                 id, url, secret, events, status, created_by,
-                created_at, updated_at, last_delivery_at, failure_count
+                created_at, updated_at, last_delivery_at, failure_count,
+                previous_secret, previous_secret_expires_at, filter, tenant_id
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, get_current_tenant_id())
             "#,
             webhook.id,
             webhook.url,
@@ -44,11 +94,16 @@ impl WebhookRepository for PostgresWebhookRepository {
             webhook.created_at,
             webhook.updated_at,
             webhook.last_delivery_at,
-            webhook.failure_count
+            webhook.failure_count,
+            webhook.previous_secret,
+            webhook.previous_secret_expires_at,
+            filter_json(webhook)?
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(format!("Failed to create webhook: {}", e)))?;
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to create webhook: {}", e).into())
+        })?;
 
         Ok(())
     }
@@ -56,16 +111,17 @@ impl WebhookRepository for PostgresWebhookRepository {
     async fn get_webhook(&self, id: Uuid) -> Result<Option<Webhook>, DomainError> {
         let row = sqlx::query!(
             r#"
-            SELECT id, url, secret, events, status, created_by,
+            SELECT id, url, secret, previous_secret, previous_secret_expires_at,
+                   events, filter, status, created_by,
                    created_at, updated_at, last_delivery_at, failure_count
             FROM webhooks
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             id
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(format!("Failed to get webhook: {}", e)))?;
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to get webhook: {}", e).into()))?;
 
         match row {
             Some(row) => {
@@ -76,14 +132,17 @@ impl WebhookRepository for PostgresWebhookRepository {
                     .collect();
 
                 let status = WebhookStatus::from_str(&row.status).map_err(|e| {
-                    DomainError::DatabaseError(format!("Invalid webhook status: {}", e))
+                    DomainError::DatabaseError(format!("Invalid webhook status: {}", e).into())
                 })?;
 
                 Ok(Some(Webhook {
                     id: row.id,
                     url: row.url,
                     secret: row.secret,
+                    previous_secret: row.previous_secret,
+                    previous_secret_expires_at: row.previous_secret_expires_at,
                     events,
+                    filter: parse_filter(row.filter)?,
                     status,
                     created_by: row.created_by,
                     created_at: row.created_at,
@@ -99,17 +158,20 @@ impl WebhookRepository for PostgresWebhookRepository {
     async fn get_user_webhooks(&self, user_id: Uuid) -> Result<Vec<Webhook>, DomainError> {
         let rows = sqlx::query!(
             r#"
-            SELECT id, url, secret, events, status, created_by,
+            SELECT id, url, secret, previous_secret, previous_secret_expires_at,
+                   events, filter, status, created_by,
                    created_at, updated_at, last_delivery_at, failure_count
             FROM webhooks
-            WHERE created_by = $1
+            WHERE created_by = $1 AND tenant_id = get_current_tenant_id()
             ORDER BY created_at DESC
             "#,
             user_id
         )
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(format!("Failed to get user webhooks: {}", e)))?;
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to get user webhooks: {}", e).into())
+        })?;
 
         let mut webhooks = Vec::new();
         for row in rows {
@@ -120,14 +182,17 @@ impl WebhookRepository for PostgresWebhookRepository {
                 .collect();
 
             let status = WebhookStatus::from_str(&row.status).map_err(|e| {
-                DomainError::DatabaseError(format!("Invalid webhook status: {}", e))
+                DomainError::DatabaseError(format!("Invalid webhook status: {}", e).into())
             })?;
 
             webhooks.push(Webhook {
                 id: row.id,
                 url: row.url,
                 secret: row.secret,
+                previous_secret: row.previous_secret,
+                previous_secret_expires_at: row.previous_secret_expires_at,
                 events,
+                filter: parse_filter(row.filter)?,
                 status,
                 created_by: row.created_by,
                 created_at: row.created_at,
@@ -148,17 +213,19 @@ impl WebhookRepository for PostgresWebhookRepository {
 
         let rows = sqlx::query!(
             r#"
-            SELECT id, url, secret, events, status, created_by,
+            SELECT id, url, secret, previous_secret, previous_secret_expires_at,
+                   events, filter, status, created_by,
                    created_at, updated_at, last_delivery_at, failure_count
             FROM webhooks
-            WHERE status = 'ACTIVE' AND $1 = ANY(events)
+            WHERE status IN ('ACTIVE', 'PAUSED') AND $1 = ANY(events)
+              AND tenant_id = get_current_tenant_id()
             "#,
             event_str
         )
         .fetch_all(&*self.pool)
         .await
         .map_err(|e| {
-            DomainError::DatabaseError(format!("Failed to get webhooks for event: {}", e))
+            DomainError::DatabaseError(format!("Failed to get webhooks for event: {}", e).into())
         })?;
 
         let mut webhooks = Vec::new();
@@ -170,14 +237,17 @@ impl WebhookRepository for PostgresWebhookRepository {
                 .collect();
 
             let status = WebhookStatus::from_str(&row.status).map_err(|e| {
-                DomainError::DatabaseError(format!("Invalid webhook status: {}", e))
+                DomainError::DatabaseError(format!("Invalid webhook status: {}", e).into())
             })?;
 
             webhooks.push(Webhook {
                 id: row.id,
                 url: row.url,
                 secret: row.secret,
+                previous_secret: row.previous_secret,
+                previous_secret_expires_at: row.previous_secret_expires_at,
                 events,
+                filter: parse_filter(row.filter)?,
                 status,
                 created_by: row.created_by,
                 created_at: row.created_at,
@@ -197,12 +267,13 @@ impl WebhookRepository for PostgresWebhookRepository {
             .map(|e| e.as_str().to_string())
             .collect();
 
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             UPDATE webhooks
             SET url = $2, secret = $3, events = $4, status = $5,
-                updated_at = $6, last_delivery_at = $7, failure_count = $8
-            WHERE id = $1
+                updated_at = $6, last_delivery_at = $7, failure_count = $8,
+                previous_secret = $9, previous_secret_expires_at = $10, filter = $11
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             webhook.id,
             webhook.url,
@@ -211,34 +282,52 @@ impl WebhookRepository for PostgresWebhookRepository {
             webhook.status.as_str(),
             webhook.updated_at,
             webhook.last_delivery_at,
-            webhook.failure_count
+            webhook.failure_count,
+            webhook.previous_secret,
+            webhook.previous_secret_expires_at,
+            filter_json(webhook)?
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(format!("Failed to update webhook: {}", e)))?;
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to update webhook: {}", e).into())
+        })?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Webhook {} not found", webhook.id).into(),
+            ));
+        }
         Ok(())
     }
 
     async fn delete_webhook(&self, id: Uuid) -> Result<(), DomainError> {
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
-            DELETE FROM webhooks WHERE id = $1
+            DELETE FROM webhooks WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             id
         )
         .execute(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(format!("Failed to delete webhook: {}", e)))?;
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to delete webhook: {}", e).into())
+        })?;
 
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(
+                format!("Webhook {} not found", id).into(),
+            ));
+        }
         Ok(())
     }
 
     async fn create_event(&self, event: &WebhookEvent) -> Result<(), DomainError> {
         sqlx::query!(
             r#"
-            INSERT INTO webhook_events (id, event_type, payload, created_at)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO webhook_events (id, event_type, payload, created_at, tenant_id)
+            VALUES ($1, $2, $3, $4, get_current_tenant_id())
+            ON CONFLICT (id) DO NOTHING
             "#,
             event.id,
             event.event_type.as_str(),
@@ -248,7 +337,7 @@ impl WebhookRepository for PostgresWebhookRepository {
         .execute(&*self.pool)
         .await
         .map_err(|e| {
-            DomainError::DatabaseError(format!("Failed to create webhook event: {}", e))
+            DomainError::DatabaseError(format!("Failed to create webhook event: {}", e).into())
         })?;
 
         Ok(())
@@ -261,8 +350,9 @@ impl WebhookRepository for PostgresWebhookRepository {
     ) -> Result<Vec<WebhookEvent>, DomainError> {
         let rows = sqlx::query!(
             r#"
-            SELECT id, event_type, payload, created_at
+            SELECT id, event_type, payload, created_at, tenant_id
             FROM webhook_events
+            WHERE tenant_id = get_current_tenant_id()
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
             "#,
@@ -271,18 +361,22 @@ impl WebhookRepository for PostgresWebhookRepository {
         )
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(format!("Failed to get recent events: {}", e)))?;
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to get recent events: {}", e).into())
+        })?;
 
         let mut events = Vec::new();
         for row in rows {
-            let event_type = WebhookEventType::from_str(&row.event_type)
-                .map_err(|e| DomainError::DatabaseError(format!("Invalid event type: {}", e)))?;
+            let event_type = WebhookEventType::from_str(&row.event_type).map_err(|e| {
+                DomainError::DatabaseError(format!("Invalid event type: {}", e).into())
+            })?;
 
             events.push(WebhookEvent {
                 id: row.id,
                 event_type,
                 payload: row.payload,
                 created_at: row.created_at,
+                tenant_id: Some(row.tenant_id),
             });
         }
 
@@ -295,9 +389,9 @@ impl WebhookRepository for PostgresWebhookRepository {
             INSERT INTO webhook_deliveries (
                 id, webhook_id, event_id, status, attempt_count,
                 last_attempt_at, next_attempt_at, response_status,
-                response_body, error_message, created_at, updated_at
+                response_body, error_message, created_at, updated_at, tenant_id
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, get_current_tenant_id())
             "#,
             delivery.id,
             delivery.webhook_id,
@@ -315,7 +409,7 @@ impl WebhookRepository for PostgresWebhookRepository {
         .execute(&*self.pool)
         .await
         .map_err(|e| {
-            DomainError::DatabaseError(format!("Failed to create webhook delivery: {}", e))
+            DomainError::DatabaseError(format!("Failed to create webhook delivery: {}", e).into())
         })?;
 
         Ok(())
@@ -328,7 +422,7 @@ impl WebhookRepository for PostgresWebhookRepository {
             SET status = $2, attempt_count = $3, last_attempt_at = $4,
                 next_attempt_at = $5, response_status = $6,
                 response_body = $7, error_message = $8, updated_at = $9
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             delivery.id,
             delivery.status.as_str(),
@@ -343,7 +437,7 @@ impl WebhookRepository for PostgresWebhookRepository {
         .execute(&*self.pool)
         .await
         .map_err(|e| {
-            DomainError::DatabaseError(format!("Failed to update webhook delivery: {}", e))
+            DomainError::DatabaseError(format!("Failed to update webhook delivery: {}", e).into())
         })?;
 
         Ok(())
@@ -359,9 +453,9 @@ impl WebhookRepository for PostgresWebhookRepository {
             r#"
             SELECT id, webhook_id, event_id, status, attempt_count,
                    last_attempt_at, next_attempt_at, response_status,
-                   response_body, error_message, created_at, updated_at
+                   response_body, error_message, created_at, updated_at, tenant_id
             FROM webhook_deliveries
-            WHERE webhook_id = $1
+            WHERE webhook_id = $1 AND tenant_id = get_current_tenant_id()
             ORDER BY created_at DESC
             LIMIT $2 OFFSET $3
             "#,
@@ -372,13 +466,13 @@ impl WebhookRepository for PostgresWebhookRepository {
         .fetch_all(&*self.pool)
         .await
         .map_err(|e| {
-            DomainError::DatabaseError(format!("Failed to get webhook deliveries: {}", e))
+            DomainError::DatabaseError(format!("Failed to get webhook deliveries: {}", e).into())
         })?;
 
         let mut deliveries = Vec::new();
         for row in rows {
             let status = DeliveryStatus::from_str(&row.status).map_err(|e| {
-                DomainError::DatabaseError(format!("Invalid delivery status: {}", e))
+                DomainError::DatabaseError(format!("Invalid delivery status: {}", e).into())
             })?;
 
             deliveries.push(WebhookDelivery {
@@ -394,6 +488,7 @@ impl WebhookRepository for PostgresWebhookRepository {
                 error_message: row.error_message,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
+                tenant_id: Some(row.tenant_id),
             });
         }
 
@@ -408,26 +503,86 @@ impl WebhookRepository for PostgresWebhookRepository {
             r#"
             SELECT id, webhook_id, event_id, status, attempt_count,
                    last_attempt_at, next_attempt_at, response_status,
-                   response_body, error_message, created_at, updated_at
+                   response_body, error_message, created_at, updated_at, tenant_id
             FROM webhook_deliveries
             WHERE status IN ('PENDING', 'FAILED')
               AND next_attempt_at <= NOW()
-              AND attempt_count < 5
+              AND attempt_count < $2
             ORDER BY next_attempt_at ASC
             LIMIT $1
             "#,
-            limit
+            limit,
+            MAX_DELIVERY_ATTEMPTS
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to get pending deliveries: {}", e).into())
+        })?;
+
+        let mut deliveries = Vec::new();
+        for row in rows {
+            let status = DeliveryStatus::from_str(&row.status).map_err(|e| {
+                DomainError::DatabaseError(format!("Invalid delivery status: {}", e).into())
+            })?;
+
+            deliveries.push(WebhookDelivery {
+                id: row.id,
+                webhook_id: row.webhook_id,
+                event_id: row.event_id,
+                status,
+                attempt_count: row.attempt_count,
+                last_attempt_at: row.last_attempt_at,
+                next_attempt_at: row.next_attempt_at,
+                response_status: row.response_status,
+                response_body: row.response_body,
+                error_message: row.error_message,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                tenant_id: Some(row.tenant_id),
+            });
+        }
+
+        Ok(deliveries)
+    }
+
+    async fn claim_pending_deliveries(
+        &self,
+        limit: i64,
+        lease_seconds: i64,
+    ) -> Result<Vec<WebhookDelivery>, DomainError> {
+        let rows = sqlx::query!(
+            r#"
+            UPDATE webhook_deliveries
+            SET next_attempt_at = NOW() + make_interval(secs => $2::float8), updated_at = NOW()
+            WHERE id IN (
+                SELECT id
+                FROM webhook_deliveries
+                WHERE status IN ('PENDING', 'FAILED')
+                  AND next_attempt_at <= NOW()
+                  AND attempt_count < $3
+                ORDER BY next_attempt_at ASC
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, webhook_id, event_id, status, attempt_count,
+                      last_attempt_at, next_attempt_at, response_status,
+                      response_body, error_message, created_at, updated_at, tenant_id
+            "#,
+            limit,
+            lease_seconds as f64,
+            MAX_DELIVERY_ATTEMPTS
         )
         .fetch_all(&*self.pool)
         .await
         .map_err(|e| {
-            DomainError::DatabaseError(format!("Failed to get pending deliveries: {}", e))
+            DomainError::DatabaseError(format!("Failed to claim pending deliveries: {}", e).into())
         })?;
 
         let mut deliveries = Vec::new();
         for row in rows {
             let status = DeliveryStatus::from_str(&row.status).map_err(|e| {
-                DomainError::DatabaseError(format!("Invalid delivery status: {}", e))
+                DomainError::DatabaseError(format!("Invalid delivery status: {}", e).into())
             })?;
 
             deliveries.push(WebhookDelivery {
@@ -443,12 +598,180 @@ impl WebhookRepository for PostgresWebhookRepository {
                 error_message: row.error_message,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
+                tenant_id: Some(row.tenant_id),
             });
         }
 
         Ok(deliveries)
     }
 
+    async fn claim_outbox_events(
+        &self,
+        ids: Option<&[Uuid]>,
+        limit: i64,
+        lease_seconds: i64,
+    ) -> Result<Vec<WebhookEvent>, DomainError> {
+        let rows = sqlx::query!(
+            r#"
+            UPDATE event_outbox
+            SET available_at = NOW() + make_interval(secs => $3::float8),
+                attempt_count = attempt_count + 1
+            WHERE id IN (
+                SELECT id
+                FROM event_outbox
+                WHERE available_at <= NOW()
+                  AND ($1::uuid[] IS NULL OR id = ANY($1))
+                ORDER BY created_at, id
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, event_type, payload, created_at, tenant_id
+            "#,
+            ids as Option<&[Uuid]>,
+            limit,
+            lease_seconds as f64
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to claim outbox events: {}", e).into())
+        })?;
+
+        let mut events = Vec::with_capacity(rows.len());
+        for row in rows {
+            let event_type = WebhookEventType::from_str(&row.event_type).map_err(|e| {
+                DomainError::DatabaseError(format!("Invalid event type: {}", e).into())
+            })?;
+            events.push(WebhookEvent {
+                id: row.id,
+                event_type,
+                payload: row.payload,
+                created_at: row.created_at,
+                tenant_id: Some(row.tenant_id),
+            });
+        }
+        // The claim's RETURNING order isn't the subquery's
+        events.sort_by_key(|event| (event.created_at, event.id));
+
+        Ok(events)
+    }
+
+    async fn delete_outbox_event(&self, id: Uuid) -> Result<(), DomainError> {
+        sqlx::query!("DELETE FROM event_outbox WHERE id = $1", id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::DatabaseError(format!("Failed to delete outbox event: {}", e).into())
+            })?;
+        Ok(())
+    }
+
+    async fn release_outbox_event(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        sqlx::query!(
+            "UPDATE event_outbox SET available_at = $2, last_error = $3 WHERE id = $1",
+            id,
+            retry_at,
+            error
+        )
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to release outbox event: {}", e).into())
+        })?;
+        Ok(())
+    }
+
+    async fn release_held_deliveries(&self, webhook_id: Uuid) -> Result<u64, DomainError> {
+        // Due from when they were raised, so the worker replays them in order
+        let result = sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'PENDING', next_attempt_at = created_at, updated_at = NOW()
+            WHERE webhook_id = $1 AND status = 'HELD' AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(webhook_id)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to release held deliveries: {}", e).into())
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn expire_held_deliveries(
+        &self,
+        created_before: DateTime<Utc>,
+    ) -> Result<u64, DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'DLQ', error_message = 'Held past the pause replay window',
+                updated_at = NOW()
+            WHERE status = 'HELD' AND created_at < $1
+            "#,
+        )
+        .bind(created_before)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to expire held deliveries: {}", e).into())
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn set_tenant_webhook_status(
+        &self,
+        from: &[WebhookStatus],
+        to: WebhookStatus,
+    ) -> Result<Vec<Uuid>, DomainError> {
+        let from: Vec<&str> = from.iter().map(WebhookStatus::as_str).collect();
+        sqlx::query_scalar(
+            r#"
+            UPDATE webhooks
+            SET status = $2, updated_at = NOW()
+            WHERE status = ANY($1) AND tenant_id = get_current_tenant_id()
+            RETURNING id
+            "#,
+        )
+        .bind(&from)
+        .bind(to.as_str())
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to update webhooks: {}", e).into()))
+    }
+
+    async fn dead_letter_outstanding_deliveries(
+        &self,
+        webhook_ids: &[Uuid],
+        reason: &str,
+    ) -> Result<u64, DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'DLQ', error_message = $2, next_attempt_at = NULL, updated_at = NOW()
+            WHERE webhook_id = ANY($1) AND status IN ('PENDING', 'FAILED', 'HELD')
+              AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(webhook_ids)
+        .bind(reason)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to dead-letter deliveries: {}", e).into())
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
     async fn get_dlq_deliveries(
         &self,
         limit: i64,
@@ -458,9 +781,9 @@ impl WebhookRepository for PostgresWebhookRepository {
             r#"
             SELECT id, webhook_id, event_id, status, attempt_count,
                    last_attempt_at, next_attempt_at, response_status,
-                   response_body, error_message, created_at, updated_at
+                   response_body, error_message, created_at, updated_at, tenant_id
             FROM webhook_deliveries
-            WHERE status = 'DLQ'
+            WHERE status = 'DLQ' AND tenant_id = get_current_tenant_id()
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
             "#,
@@ -469,12 +792,14 @@ impl WebhookRepository for PostgresWebhookRepository {
         )
         .fetch_all(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(format!("Failed to get DLQ deliveries: {}", e)))?;
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to get DLQ deliveries: {}", e).into())
+        })?;
 
         let mut deliveries = Vec::new();
         for row in rows {
             let status = DeliveryStatus::from_str(&row.status).map_err(|e| {
-                DomainError::DatabaseError(format!("Invalid delivery status: {}", e))
+                DomainError::DatabaseError(format!("Invalid delivery status: {}", e).into())
             })?;
 
             deliveries.push(WebhookDelivery {
@@ -490,6 +815,7 @@ impl WebhookRepository for PostgresWebhookRepository {
                 error_message: row.error_message,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
+                tenant_id: Some(row.tenant_id),
             });
         }
 
@@ -501,20 +827,20 @@ impl WebhookRepository for PostgresWebhookRepository {
             r#"
             SELECT id, webhook_id, event_id, status, attempt_count,
                    last_attempt_at, next_attempt_at, response_status,
-                   response_body, error_message, created_at, updated_at
+                   response_body, error_message, created_at, updated_at, tenant_id
             FROM webhook_deliveries
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             id
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(format!("Failed to get delivery: {}", e)))?;
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to get delivery: {}", e).into()))?;
 
         match row {
             Some(row) => {
                 let status = DeliveryStatus::from_str(&row.status).map_err(|e| {
-                    DomainError::DatabaseError(format!("Invalid delivery status: {}", e))
+                    DomainError::DatabaseError(format!("Invalid delivery status: {}", e).into())
                 })?;
 
                 Ok(Some(WebhookDelivery {
@@ -530,6 +856,7 @@ impl WebhookRepository for PostgresWebhookRepository {
                     error_message: row.error_message,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
+                    tenant_id: Some(row.tenant_id),
                 }))
             }
             None => Ok(None),
@@ -539,20 +866,20 @@ impl WebhookRepository for PostgresWebhookRepository {
     async fn get_event(&self, id: Uuid) -> Result<Option<WebhookEvent>, DomainError> {
         let row = sqlx::query!(
             r#"
-            SELECT id, event_type, payload, created_at
+            SELECT id, event_type, payload, created_at, tenant_id
             FROM webhook_events
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             id
         )
         .fetch_optional(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(format!("Failed to get event: {}", e)))?;
+        .map_err(|e| DomainError::DatabaseError(format!("Failed to get event: {}", e).into()))?;
 
         match row {
             Some(row) => {
                 let event_type = WebhookEventType::from_str(&row.event_type).map_err(|e| {
-                    DomainError::DatabaseError(format!("Invalid event type: {}", e))
+                    DomainError::DatabaseError(format!("Invalid event type: {}", e).into())
                 })?;
 
                 Ok(Some(WebhookEvent {
@@ -560,6 +887,7 @@ impl WebhookRepository for PostgresWebhookRepository {
                     event_type,
                     payload: row.payload,
                     created_at: row.created_at,
+                    tenant_id: Some(row.tenant_id),
                 }))
             }
             None => Ok(None),
@@ -571,13 +899,15 @@ impl WebhookRepository for PostgresWebhookRepository {
             r#"
             SELECT COUNT(*) as count
             FROM webhook_deliveries
-            WHERE webhook_id = $1
+            WHERE webhook_id = $1 AND tenant_id = get_current_tenant_id()
             "#,
             webhook_id
         )
         .fetch_one(&*self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(format!("Failed to count deliveries: {}", e)))?;
+        .map_err(|e| {
+            DomainError::DatabaseError(format!("Failed to count deliveries: {}", e).into())
+        })?;
 
         Ok(row.count.unwrap_or(0))
     }
@@ -587,48 +917,18 @@ impl WebhookRepository for PostgresWebhookRepository {
             r#"
             SELECT COUNT(*) as count
             FROM webhook_deliveries
-            WHERE status = 'DLQ'
+            WHERE status = 'DLQ' AND tenant_id = get_current_tenant_id()
             "#,
         )
         .fetch_one(&*self.pool)
         .await
         .map_err(|e| {
-            DomainError::DatabaseError(format!("Failed to count DLQ deliveries: {}", e))
+            DomainError::DatabaseError(format!("Failed to count DLQ deliveries: {}", e).into())
         })?;
 
         Ok(row.count.unwrap_or(0))
     }
 
-    async fn cleanup_old_data(&self, days_old: i32) -> Result<(), DomainError> {
-        // Clean up old events (keep last 30 days)
-        sqlx::query!(
-            r#"
-            DELETE FROM webhook_events
-            WHERE created_at < NOW() - INTERVAL '1 day' * $1
-            "#,
-            days_old as f64
-        )
-        .execute(&*self.pool)
-        .await
-        .map_err(|e| DomainError::DatabaseError(format!("Failed to cleanup old events: {}", e)))?;
-
-        // Clean up old successful deliveries (keep last 7 days)
-        sqlx::query!(
-            r#"
-            DELETE FROM webhook_deliveries
-            WHERE status = 'SUCCESS' AND created_at < NOW() - INTERVAL '1 day' * $1
-            "#,
-            days_old as f64
-        )
-        .execute(&*self.pool)
-        .await
-        .map_err(|e| {
-            DomainError::DatabaseError(format!("Failed to cleanup old deliveries: {}", e))
-        })?;
-
-        Ok(())
-    }
-
     fn get_pool(&self) -> &sqlx::PgPool {
         &self.pool
     }
@@ -0,0 +1,243 @@
+use crate::domain::entities::webhook_retention::{
+    DeliveryCutoffs, ExpiredRecord, RetentionRun, RetentionRunStatus, RetentionTrigger,
+    WebhookRetentionSettings,
+};
+use crate::domain::services::webhook_retention_repository::WebhookRetentionRepository;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const RUN_COLUMNS: &str = "id, trigger, status, deliveries_pruned, events_pruned, archive_keys, \
+                           error, started_at, finished_at";
+
+pub struct PostgresWebhookRetentionRepository {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresWebhookRetentionRepository {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_record(row: &PgRow) -> Result<ExpiredRecord, DomainError> {
+        Ok(ExpiredRecord {
+            id: row.try_get("id")?,
+            record: row.try_get("record")?,
+        })
+    }
+
+    fn row_to_run(row: &PgRow) -> Result<RetentionRun, DomainError> {
+        let archive_keys: serde_json::Value = row.try_get("archive_keys")?;
+        Ok(RetentionRun {
+            id: row.try_get("id")?,
+            trigger: RetentionTrigger::from_str(row.try_get("trigger")?)?,
+            status: RetentionRunStatus::from_str(row.try_get("status")?)?,
+            deliveries_pruned: row.try_get("deliveries_pruned")?,
+            events_pruned: row.try_get("events_pruned")?,
+            archive_keys: serde_json::from_value(archive_keys).map_err(|e| {
+                DomainError::DatabaseError(
+                    format!("Invalid retention run archive keys: {}", e).into(),
+                )
+            })?,
+            error: row.try_get("error")?,
+            started_at: row.try_get("started_at")?,
+            finished_at: row.try_get("finished_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl WebhookRetentionRepository for PostgresWebhookRetentionRepository {
+    async fn find_settings(&self) -> Result<WebhookRetentionSettings, DomainError> {
+        let row = sqlx::query(
+            r#"
+            SELECT event_retention_days, delivery_retention_days, dlq_retention_days, archive_enabled
+            FROM webhook_retention_settings
+            WHERE tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(WebhookRetentionSettings {
+                event_retention_days: row.try_get("event_retention_days")?,
+                delivery_retention_days: row.try_get("delivery_retention_days")?,
+                dlq_retention_days: row.try_get("dlq_retention_days")?,
+                archive_enabled: row.try_get("archive_enabled")?,
+            }),
+            None => Ok(WebhookRetentionSettings::default()),
+        }
+    }
+
+    async fn save_settings(&self, settings: &WebhookRetentionSettings) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_retention_settings (tenant_id, event_retention_days, delivery_retention_days,
+                                                    dlq_retention_days, archive_enabled, updated_at)
+            VALUES (get_current_tenant_id(), $1, $2, $3, $4, NOW())
+            ON CONFLICT (tenant_id) DO UPDATE
+            SET event_retention_days = EXCLUDED.event_retention_days,
+                delivery_retention_days = EXCLUDED.delivery_retention_days,
+                dlq_retention_days = EXCLUDED.dlq_retention_days,
+                archive_enabled = EXCLUDED.archive_enabled,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(settings.event_retention_days)
+        .bind(settings.delivery_retention_days)
+        .bind(settings.dlq_retention_days)
+        .bind(settings.archive_enabled)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn claim_due_tenants(&self, limit: i64) -> Result<Vec<Uuid>, DomainError> {
+        // Tenants without settings are due straight away and get a row with the
+        // defaults. The conflict update re-checks next_run_at under the row lock,
+        // so an instance racing this one claims nothing.
+        let tenant_ids = sqlx::query_scalar(
+            r#"
+            INSERT INTO webhook_retention_settings (tenant_id, next_run_at)
+            SELECT t.id, NOW() + INTERVAL '1 day'
+            FROM tenants t
+            LEFT JOIN webhook_retention_settings s ON s.tenant_id = t.id
+            WHERE s.tenant_id IS NULL OR s.next_run_at <= NOW()
+            ORDER BY s.next_run_at NULLS FIRST
+            LIMIT $1
+            ON CONFLICT (tenant_id) DO UPDATE
+            SET next_run_at = EXCLUDED.next_run_at
+            WHERE webhook_retention_settings.next_run_at <= NOW()
+            RETURNING tenant_id
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .await?;
+        Ok(tenant_ids)
+    }
+
+    async fn expired_deliveries(
+        &self,
+        cutoffs: DeliveryCutoffs,
+        limit: i64,
+    ) -> Result<Vec<ExpiredRecord>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT d.id, to_jsonb(d) AS record
+            FROM webhook_deliveries d
+            WHERE d.tenant_id = get_current_tenant_id()
+              AND ((d.status = 'SUCCESS' AND d.created_at < $1)
+                OR (d.status = 'DLQ' AND d.created_at < $2))
+            ORDER BY d.created_at, d.id
+            LIMIT $3
+            "#,
+        )
+        .bind(cutoffs.delivered_before)
+        .bind(cutoffs.dead_lettered_before)
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_record).collect()
+    }
+
+    async fn expired_events(
+        &self,
+        before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<ExpiredRecord>, DomainError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT e.id, to_jsonb(e) AS record
+            FROM webhook_events e
+            WHERE e.tenant_id = get_current_tenant_id()
+              AND e.created_at < $1
+              AND NOT EXISTS (SELECT 1 FROM webhook_deliveries d WHERE d.event_id = e.id)
+            ORDER BY e.created_at, e.id
+            LIMIT $2
+            "#,
+        )
+        .bind(before)
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_record).collect()
+    }
+
+    async fn delete_deliveries(&self, ids: &[Uuid]) -> Result<u64, DomainError> {
+        // A dead-lettered delivery replayed since it was read is live again
+        let result = sqlx::query(
+            r#"
+            DELETE FROM webhook_deliveries
+            WHERE id = ANY($1)
+              AND status IN ('SUCCESS', 'DLQ')
+              AND tenant_id = get_current_tenant_id()
+            "#,
+        )
+        .bind(ids)
+        .execute(&*self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_events(&self, ids: &[Uuid]) -> Result<u64, DomainError> {
+        // Deliveries cascade with their event, so never take one that has any
+        let result = sqlx::query(
+            r#"
+            DELETE FROM webhook_events e
+            WHERE e.id = ANY($1)
+              AND e.tenant_id = get_current_tenant_id()
+              AND NOT EXISTS (SELECT 1 FROM webhook_deliveries d WHERE d.event_id = e.id)
+            "#,
+        )
+        .bind(ids)
+        .execute(&*self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn save_run(&self, run: &RetentionRun) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_retention_runs (id, trigger, status, deliveries_pruned, events_pruned,
+                                                archive_keys, error, started_at, finished_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, get_current_tenant_id())
+            "#,
+        )
+        .bind(run.id)
+        .bind(run.trigger.as_str())
+        .bind(run.status.as_str())
+        .bind(run.deliveries_pruned)
+        .bind(run.events_pruned)
+        .bind(serde_json::json!(run.archive_keys))
+        .bind(&run.error)
+        .bind(run.started_at)
+        .bind(run.finished_at)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn latest_run(&self) -> Result<Option<RetentionRun>, DomainError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM webhook_retention_runs
+            WHERE tenant_id = get_current_tenant_id()
+            ORDER BY started_at DESC, id
+            LIMIT 1
+            "#,
+            RUN_COLUMNS
+        ))
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_run).transpose()
+    }
+}
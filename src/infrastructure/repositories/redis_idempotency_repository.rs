@@ -15,8 +15,9 @@ pub struct RedisIdempotencyRepository {
 
 impl RedisIdempotencyRepository {
     pub fn new(redis_url: &str) -> Result<Self, DomainError> {
-        let client = RedisClient::open(redis_url)
-            .map_err(|e| DomainError::ValidationError(format!("Redis connection error: {}", e)))?;
+        let client = RedisClient::open(redis_url).map_err(|e| {
+            DomainError::ValidationError(format!("Redis connection error: {}", e).into())
+        })?;
 
         Ok(Self {
             client: Arc::new(client),
@@ -26,20 +27,21 @@ impl RedisIdempotencyRepository {
     async fn get_connection(&self) -> Result<redis::Connection, DomainError> {
         let client = Arc::clone(&self.client);
         tokio::task::spawn_blocking(move || {
-            client
-                .get_connection()
-                .map_err(|e| DomainError::ValidationError(format!("Redis connection error: {}", e)))
+            client.get_connection().map_err(|e| {
+                DomainError::ValidationError(format!("Redis connection error: {}", e).into())
+            })
         })
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Task join error: {}", e)))?
+        .map_err(|e| DomainError::ValidationError(format!("Task join error: {}", e).into()))?
     }
 }
 
 #[async_trait]
 impl IdempotencyRepository for RedisIdempotencyRepository {
     async fn store_key(&self, key: &IdempotencyKey) -> Result<(), DomainError> {
-        let key_data = serde_json::to_string(key)
-            .map_err(|e| DomainError::ValidationError(format!("Failed to serialize key: {}", e)))?;
+        let key_data = serde_json::to_string(key).map_err(|e| {
+            DomainError::ValidationError(format!("Failed to serialize key: {}", e).into())
+        })?;
 
         let key_clone = key.idempotency_key.clone();
         let key_data_clone = key_data.clone();
@@ -48,7 +50,7 @@ impl IdempotencyRepository for RedisIdempotencyRepository {
         let client = Arc::clone(&self.client);
         tokio::task::spawn_blocking(move || {
             let mut conn = client.get_connection().map_err(|e| {
-                DomainError::ValidationError(format!("Redis connection error: {}", e))
+                DomainError::ValidationError(format!("Redis connection error: {}", e).into())
             })?;
 
             let result: Result<(), redis::RedisError> = redis::cmd("SET")
@@ -62,13 +64,15 @@ impl IdempotencyRepository for RedisIdempotencyRepository {
             match result {
                 Ok(_) => Ok(()),
                 Err(e) if e.kind() == redis::ErrorKind::TypeError => Err(DomainError::Conflict(
-                    "Idempotency key already exists".to_string(),
+                    "Idempotency key already exists".into(),
+                )),
+                Err(e) => Err(DomainError::ValidationError(
+                    format!("Redis error: {}", e).into(),
                 )),
-                Err(e) => Err(DomainError::ValidationError(format!("Redis error: {}", e))),
             }
         })
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Task join error: {}", e)))?
+        .map_err(|e| DomainError::ValidationError(format!("Task join error: {}", e).into()))?
     }
 
     async fn get_key(&self, idempotency_key: &str) -> Result<Option<IdempotencyKey>, DomainError> {
@@ -77,23 +81,25 @@ impl IdempotencyRepository for RedisIdempotencyRepository {
 
         let result = tokio::task::spawn_blocking(move || {
             let mut conn = client.get_connection().map_err(|e| {
-                DomainError::ValidationError(format!("Redis connection error: {}", e))
+                DomainError::ValidationError(format!("Redis connection error: {}", e).into())
             })?;
 
             let key_data: Option<String> = conn
                 .get(&key)
-                .map_err(|e| DomainError::ValidationError(format!("Redis error: {}", e)))?;
+                .map_err(|e| DomainError::ValidationError(format!("Redis error: {}", e).into()))?;
 
             match key_data {
                 Some(data) => {
                     let key: IdempotencyKey = serde_json::from_str(&data).map_err(|e| {
-                        DomainError::ValidationError(format!("Failed to deserialize key: {}", e))
+                        DomainError::ValidationError(
+                            format!("Failed to deserialize key: {}", e).into(),
+                        )
                     })?;
 
                     if key.is_expired() {
                         // Key is expired, remove it
                         let _: () = conn.del(key.idempotency_key).map_err(|e| {
-                            DomainError::ValidationError(format!("Redis error: {}", e))
+                            DomainError::ValidationError(format!("Redis error: {}", e).into())
                         })?;
                         Ok(None)
                     } else {
@@ -104,7 +110,7 @@ impl IdempotencyRepository for RedisIdempotencyRepository {
             }
         })
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Task join error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Task join error: {}", e).into()))?;
 
         result
     }
@@ -121,17 +127,17 @@ impl IdempotencyRepository for RedisIdempotencyRepository {
 
         tokio::task::spawn_blocking(move || {
             let mut conn = client.get_connection().map_err(|e| {
-                DomainError::ValidationError(format!("Redis connection error: {}", e))
+                DomainError::ValidationError(format!("Redis connection error: {}", e).into())
             })?;
 
             // Get the current key
             let key_data: Option<String> = conn
                 .get(&key)
-                .map_err(|e| DomainError::ValidationError(format!("Redis error: {}", e)))?;
+                .map_err(|e| DomainError::ValidationError(format!("Redis error: {}", e).into()))?;
 
             if let Some(data) = key_data {
                 let mut key: IdempotencyKey = serde_json::from_str(&data).map_err(|e| {
-                    DomainError::ValidationError(format!("Failed to deserialize key: {}", e))
+                    DomainError::ValidationError(format!("Failed to deserialize key: {}", e).into())
                 })?;
 
                 // Complete the key
@@ -139,22 +145,39 @@ impl IdempotencyRepository for RedisIdempotencyRepository {
 
                 // Store the updated key
                 let updated_data = serde_json::to_string(&key).map_err(|e| {
-                    DomainError::ValidationError(format!("Failed to serialize key: {}", e))
+                    DomainError::ValidationError(format!("Failed to serialize key: {}", e).into())
                 })?;
 
-                let _: () = conn
-                    .set(key.idempotency_key, updated_data)
-                    .map_err(|e| DomainError::ValidationError(format!("Redis error: {}", e)))?;
+                let _: () = conn.set(key.idempotency_key, updated_data).map_err(|e| {
+                    DomainError::ValidationError(format!("Redis error: {}", e).into())
+                })?;
 
                 Ok(())
             } else {
-                Err(DomainError::NotFound(
-                    "Idempotency key not found".to_string(),
-                ))
+                Err(DomainError::NotFound("Idempotency key not found".into()))
             }
         })
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Task join error: {}", e)))?
+        .map_err(|e| DomainError::ValidationError(format!("Task join error: {}", e).into()))?
+    }
+
+    async fn delete_key(&self, idempotency_key: &str) -> Result<(), DomainError> {
+        let key = idempotency_key.to_string();
+        let client = Arc::clone(&self.client);
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = client.get_connection().map_err(|e| {
+                DomainError::ValidationError(format!("Redis connection error: {}", e).into())
+            })?;
+
+            let _: () = conn
+                .del(&key)
+                .map_err(|e| DomainError::ValidationError(format!("Redis error: {}", e).into()))?;
+
+            Ok(())
+        })
+        .await
+        .map_err(|e| DomainError::ValidationError(format!("Task join error: {}", e).into()))?
     }
 
     async fn delete_expired_keys(&self) -> Result<i64, DomainError> {
@@ -169,17 +192,17 @@ impl IdempotencyRepository for RedisIdempotencyRepository {
 
         let result = tokio::task::spawn_blocking(move || {
             let mut conn = client.get_connection().map_err(|e| {
-                DomainError::ValidationError(format!("Redis connection error: {}", e))
+                DomainError::ValidationError(format!("Redis connection error: {}", e).into())
             })?;
 
             let exists: bool = conn
                 .exists(&key)
-                .map_err(|e| DomainError::ValidationError(format!("Redis error: {}", e)))?;
+                .map_err(|e| DomainError::ValidationError(format!("Redis error: {}", e).into()))?;
 
             Ok(exists)
         })
         .await
-        .map_err(|e| DomainError::ValidationError(format!("Task join error: {}", e)))?;
+        .map_err(|e| DomainError::ValidationError(format!("Task join error: {}", e).into()))?;
 
         result
     }
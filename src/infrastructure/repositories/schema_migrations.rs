@@ -0,0 +1,161 @@
+use crate::shared::error::DomainError;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::migrate::{Migrate, Migrator};
+use sqlx::{PgConnection, PgPool, Row};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+/// Migrations under `migrations/`, embedded at compile time
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Last migration that databases set up by running the SQL scripts by hand
+/// already contain
+const BASELINE_VERSION: i64 = 3;
+
+#[derive(Debug, Serialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub installed_on: Option<DateTime<Utc>>,
+    /// The file changed after it was applied
+    pub checksum_mismatch: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MigrationsReport {
+    pub applied: Vec<MigrationStatus>,
+    pub pending: Vec<MigrationStatus>,
+}
+
+/// Apply pending migrations. The database's migration advisory lock is held
+/// throughout, so replicas starting together apply each migration once; the
+/// others wait and then find nothing left to do.
+pub async fn run_migrations(pool: &PgPool) -> Result<(), DomainError> {
+    let mut conn = pool.acquire().await?;
+    conn.lock().await.map_err(migrate_error)?;
+
+    let result = async {
+        baseline_unmanaged_database(&mut conn).await?;
+        MIGRATOR.run(&mut *conn).await.map_err(migrate_error)
+    }
+    .await;
+
+    conn.unlock().await.map_err(migrate_error)?;
+    result?;
+    info!("Database migrations are up to date");
+    Ok(())
+}
+
+/// Applied and pending migrations, in version order
+pub async fn migrations_report(pool: &PgPool) -> Result<MigrationsReport, DomainError> {
+    let mut conn = pool.acquire().await?;
+    let applied: HashMap<i64, (DateTime<Utc>, Vec<u8>)> = if is_managed(&mut conn).await? {
+        sqlx::query(
+            "SELECT version, installed_on, checksum FROM _sqlx_migrations WHERE success ORDER BY version",
+        )
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|row| {
+            (
+                row.get("version"),
+                (row.get("installed_on"), row.get("checksum")),
+            )
+        })
+        .collect()
+    } else {
+        HashMap::new()
+    };
+
+    let mut report = MigrationsReport {
+        applied: Vec::new(),
+        pending: Vec::new(),
+    };
+    for migration in MIGRATOR
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration())
+    {
+        let mut status = MigrationStatus {
+            version: migration.version,
+            description: migration.description.to_string(),
+            installed_on: None,
+            checksum_mismatch: false,
+        };
+        match applied.get(&migration.version) {
+            Some((installed_on, checksum)) => {
+                status.installed_on = Some(*installed_on);
+                status.checksum_mismatch = checksum.as_slice() != &*migration.checksum;
+                report.applied.push(status);
+            }
+            None => report.pending.push(status),
+        }
+    }
+    Ok(report)
+}
+
+/// Databases created from the SQL scripts before migrations were tracked already
+/// have the baseline schema but no migrations table. Record the baseline as
+/// applied so only the migrations after it run.
+async fn baseline_unmanaged_database(conn: &mut PgConnection) -> Result<(), DomainError> {
+    let has_schema: bool = sqlx::query_scalar("SELECT to_regclass('public.items') IS NOT NULL")
+        .fetch_one(&mut *conn)
+        .await?;
+    if is_managed(conn).await? || !has_schema {
+        return Ok(());
+    }
+
+    conn.ensure_migrations_table()
+        .await
+        .map_err(migrate_error)?;
+    let baseline: Vec<_> = MIGRATOR
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration() && m.version <= BASELINE_VERSION)
+        .collect();
+    for migration in &baseline {
+        sqlx::query(
+            "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+             VALUES ($1, $2, TRUE, $3, 0)",
+        )
+        .bind(migration.version)
+        .bind(&*migration.description)
+        .bind(&*migration.checksum)
+        .execute(&mut *conn)
+        .await?;
+    }
+    warn!(
+        "Database schema predates migration tracking; recorded {} baseline migrations as applied",
+        baseline.len()
+    );
+    Ok(())
+}
+
+async fn is_managed(conn: &mut PgConnection) -> Result<bool, DomainError> {
+    Ok(
+        sqlx::query_scalar("SELECT to_regclass('public._sqlx_migrations') IS NOT NULL")
+            .fetch_one(conn)
+            .await?,
+    )
+}
+
+fn migrate_error(e: sqlx::migrate::MigrateError) -> DomainError {
+    DomainError::DatabaseError(format!("Migration failed: {}", e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_baseline_migrations_are_embedded_in_order() {
+        let versions: Vec<i64> = MIGRATOR.iter().map(|m| m.version).collect();
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(
+            versions
+                .iter()
+                .filter(|version| **version <= BASELINE_VERSION)
+                .count(),
+            BASELINE_VERSION as usize
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use crate::infrastructure::config::app_config::DatabaseConfig;
+use crate::shared::tenant_scope::{current_actor, current_tenant};
+use sqlx::postgres::{PgConnection, PgPoolOptions};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::time::Duration;
+
+/// Connect a pool whose connections always carry the tenant of the task using them.
+///
+/// Repositories scope their queries with `get_current_tenant_id()`, which reads the
+/// `custom.tenant_id` setting of the connection. Pooled connections are shared
+/// between requests, so the setting is refreshed every time a connection is handed
+/// out rather than once per request on whichever connection happened to be free.
+/// Outside a tenant scope it is cleared, so tenant-filtered queries match nothing.
+/// The acting user is pinned the same way in `custom.actor_id`, for the triggers
+/// recording status history.
+pub async fn connect_tenant_pool(config: &DatabaseConfig) -> Result<PgPool, sqlx::Error> {
+    PgPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .acquire_timeout(Duration::from_secs(config.acquire_timeout_secs))
+        .after_connect(|conn, _meta| Box::pin(pin_current_tenant(conn)))
+        .before_acquire(|conn, _meta| {
+            Box::pin(async move {
+                pin_current_tenant(conn).await?;
+                Ok(true)
+            })
+        })
+        .connect(&config.url)
+        .await
+}
+
+/// Begin a transaction whose statements are cancelled once they run longer than
+/// `statement_timeout`, for report and export queries that could otherwise hold a
+/// connection indefinitely. `None` leaves the server's default in place.
+pub async fn begin_with_statement_timeout(
+    pool: &PgPool,
+    statement_timeout: Option<Duration>,
+) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    if let Some(timeout) = statement_timeout {
+        // SET takes no bind parameters; the value is a plain integer
+        sqlx::query(&format!(
+            "SET LOCAL statement_timeout = {}",
+            timeout.as_millis()
+        ))
+        .execute(&mut *tx)
+        .await?;
+    }
+    Ok(tx)
+}
+
+/// Point the connection's tenant and actor settings at the current task's
+async fn pin_current_tenant(conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+    let tenant_id = current_tenant()
+        .map(|tenant_id| tenant_id.to_string())
+        .unwrap_or_default();
+    let actor_id = current_actor()
+        .map(|actor_id| actor_id.to_string())
+        .unwrap_or_default();
+    sqlx::query("SELECT set_config('custom.tenant_id', $1, false), set_config('custom.actor_id', $2, false)")
+        .bind(tenant_id)
+        .bind(actor_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::services::item_repository::ItemRepository;
+    use crate::domain::services::location_repository::LocationRepository;
+    use crate::domain::services::sales_order_repository::SalesOrderRepository;
+    use crate::infrastructure::repositories::postgres_item_repository::PostgresItemRepository;
+    use crate::infrastructure::repositories::postgres_location_repository::PostgresLocationRepository;
+    use crate::infrastructure::repositories::postgres_sales_order_repository::PostgresSalesOrderRepository;
+    use crate::shared::error::DomainError;
+    use crate::shared::tenant_scope::with_tenant;
+    use crate::test_support::database::{TestDatabase, SEEDED_USER};
+    use sqlx::PgPool;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    /// One location, item and sales order owned by `tenant_id`
+    async fn seed(admin: &PgPool, tenant_id: Uuid, prefix: &str) -> (Uuid, Uuid, Uuid) {
+        let location_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO locations (name, code, tenant_id) VALUES ($1, $1, $2) RETURNING id",
+        )
+        .bind(format!("{}-LOC", prefix))
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        let item_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO items (sku, name, unit, cost_price, tenant_id)
+             VALUES ($1, $1, 'each', 5, $2) RETURNING id",
+        )
+        .bind(format!("{}-ITEM", prefix))
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        let order_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO sales_orders (so_number, status, created_by, tenant_id)
+             VALUES ($1, 'DRAFT', $2, $3) RETURNING id",
+        )
+        .bind(format!("{}-SO", prefix))
+        .bind(Uuid::from_str(SEEDED_USER).unwrap())
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        (location_id, item_id, order_id)
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn test_tenants_never_see_or_change_each_others_rows() {
+        let db = TestDatabase::connect().await;
+        let (admin, pool) = (&db.admin, &db.pool);
+        let locations = PostgresLocationRepository::new(Arc::clone(pool));
+        let items = PostgresItemRepository::new(Arc::clone(pool));
+        let sales_orders = PostgresSalesOrderRepository::new(Arc::clone(pool));
+        let tenant_a = db.create_tenant("Isolation A").await;
+        let tenant_b = db.create_tenant("Isolation B").await;
+        let (location_a, _, _) = seed(admin, tenant_a, "ISO-A").await;
+        let (location_b, item_b, order_b) = seed(admin, tenant_b, "ISO-B").await;
+
+        // Reads under A find only A's rows
+        let (location, item, order, listed) = with_tenant(tenant_a, async {
+            (
+                locations.find_by_id(location_b).await.unwrap(),
+                items.find_by_id(item_b).await.unwrap(),
+                sales_orders.find_by_id(order_b).await.unwrap(),
+                locations.list(100, 0).await.unwrap(),
+            )
+        })
+        .await;
+        assert!(location.is_none() && item.is_none() && order.is_none());
+        assert_eq!(
+            listed.iter().map(|l| l.id).collect::<Vec<_>>(),
+            vec![location_a]
+        );
+
+        // Writes under A to B's rows match nothing
+        let (mut location, (mut order, _)) = with_tenant(tenant_b, async {
+            (
+                locations.find_by_id(location_b).await.unwrap().unwrap(),
+                sales_orders.find_by_id(order_b).await.unwrap().unwrap(),
+            )
+        })
+        .await;
+        location.name = "Renamed by A".to_string();
+        order.so_number = "RENAMED-BY-A".to_string();
+        let results = with_tenant(tenant_a, async {
+            [
+                locations.update(&location).await,
+                sales_orders.update(&order).await,
+                locations.delete(location_b).await,
+                items.delete(item_b).await,
+                sales_orders.delete(order_b).await,
+            ]
+        })
+        .await;
+        for result in results {
+            assert!(matches!(result, Err(DomainError::NotFound(_))));
+        }
+
+        let (location_name, item_count, so_number): (String, i64, String) = sqlx::query_as(
+            "SELECT (SELECT name FROM locations WHERE id = $1),
+                    (SELECT COUNT(*) FROM items WHERE id = $2),
+                    (SELECT so_number FROM sales_orders WHERE id = $3)",
+        )
+        .bind(location_b)
+        .bind(item_b)
+        .bind(order_b)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        assert_eq!(location_name, "ISO-B-LOC");
+        assert_eq!(item_count, 1);
+        assert_eq!(so_number, "ISO-B-SO");
+    }
+}
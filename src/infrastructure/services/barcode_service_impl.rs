@@ -0,0 +1,215 @@
+use std::io::Cursor;
+
+use barcoders::sym::code128::Code128;
+use image::{GrayImage, ImageFormat, Luma};
+use qrcode::QrCode;
+
+use crate::domain::services::barcode_service::{
+    BarcodeFormat, BarcodeService, BarcodeSymbology, RenderedBarcode,
+};
+use crate::shared::error::DomainError;
+
+/// Code128 character-set B prefix understood by `barcoders`
+const CODE128_SET_B: char = '\u{0181}';
+
+/// Dark/light module grid shared by 1D and 2D symbologies before rasterising
+struct ModuleGrid {
+    width: usize,
+    height: usize,
+    dark: Vec<bool>,
+    quiet_zone: usize,
+}
+
+impl ModuleGrid {
+    fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.dark[y * self.width + x]
+    }
+}
+
+pub struct BarcodeServiceImpl {
+    module_px: u32,
+    bar_height: usize,
+}
+
+impl BarcodeServiceImpl {
+    pub fn new() -> Self {
+        Self {
+            module_px: 4,
+            bar_height: 20,
+        }
+    }
+
+    fn encode(&self, data: &str, symbology: BarcodeSymbology) -> Result<ModuleGrid, DomainError> {
+        if data.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Barcode data cannot be empty".into(),
+            ));
+        }
+
+        match symbology {
+            BarcodeSymbology::Code128 => {
+                if !data.chars().all(|c| c.is_ascii() && !c.is_ascii_control()) {
+                    return Err(DomainError::ValidationError(
+                        "Code128 labels support printable ASCII only".into(),
+                    ));
+                }
+                let bars = Code128::new(format!("{}{}", CODE128_SET_B, data))
+                    .map_err(|e| {
+                        DomainError::ValidationError(format!("Invalid Code128 data: {}", e).into())
+                    })?
+                    .encode();
+
+                // A linear barcode is the same row of bars repeated down the label
+                let dark = (0..self.bar_height)
+                    .flat_map(|_| bars.iter().map(|b| *b == 1))
+                    .collect();
+                Ok(ModuleGrid {
+                    width: bars.len(),
+                    height: self.bar_height,
+                    dark,
+                    quiet_zone: 10,
+                })
+            }
+            BarcodeSymbology::Qr => {
+                let code = QrCode::new(data.as_bytes()).map_err(|e| {
+                    DomainError::ValidationError(format!("Invalid QR data: {}", e).into())
+                })?;
+                let width = code.width();
+                let dark = code
+                    .to_colors()
+                    .into_iter()
+                    .map(|c| c == qrcode::Color::Dark)
+                    .collect();
+                Ok(ModuleGrid {
+                    width,
+                    height: width,
+                    dark,
+                    quiet_zone: 4,
+                })
+            }
+        }
+    }
+
+    fn to_svg(&self, grid: &ModuleGrid) -> Vec<u8> {
+        let total_w = grid.width + grid.quiet_zone * 2;
+        let total_h = grid.height + grid.quiet_zone * 2;
+        let mut svg = format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {w} {h}" width="{pw}" height="{ph}" shape-rendering="crispEdges"><rect width="{w}" height="{h}" fill="#fff"/><path fill="#000" d=""##,
+            w = total_w,
+            h = total_h,
+            pw = total_w as u32 * self.module_px,
+            ph = total_h as u32 * self.module_px,
+        );
+
+        // One path segment per horizontal run of dark modules keeps the output small
+        for y in 0..grid.height {
+            let mut x = 0;
+            while x < grid.width {
+                if !grid.is_dark(x, y) {
+                    x += 1;
+                    continue;
+                }
+                let start = x;
+                while x < grid.width && grid.is_dark(x, y) {
+                    x += 1;
+                }
+                svg.push_str(&format!(
+                    "M{} {}h{}v1h-{}z",
+                    start + grid.quiet_zone,
+                    y + grid.quiet_zone,
+                    x - start,
+                    x - start
+                ));
+            }
+        }
+
+        svg.push_str(r#""/></svg>"#);
+        svg.into_bytes()
+    }
+
+    fn to_png(&self, grid: &ModuleGrid) -> Result<Vec<u8>, DomainError> {
+        let px = self.module_px;
+        let total_w = (grid.width + grid.quiet_zone * 2) as u32 * px;
+        let total_h = (grid.height + grid.quiet_zone * 2) as u32 * px;
+
+        let image = GrayImage::from_fn(total_w, total_h, |x, y| {
+            let mx = (x / px) as usize;
+            let my = (y / px) as usize;
+            let inside = mx >= grid.quiet_zone
+                && my >= grid.quiet_zone
+                && mx < grid.width + grid.quiet_zone
+                && my < grid.height + grid.quiet_zone;
+            if inside && grid.is_dark(mx - grid.quiet_zone, my - grid.quiet_zone) {
+                Luma([0u8])
+            } else {
+                Luma([255u8])
+            }
+        });
+
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, ImageFormat::Png).map_err(|e| {
+            DomainError::InfrastructureError(format!("Failed to encode PNG: {}", e).into())
+        })?;
+        Ok(bytes.into_inner())
+    }
+}
+
+impl Default for BarcodeServiceImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BarcodeService for BarcodeServiceImpl {
+    fn render(
+        &self,
+        data: &str,
+        symbology: BarcodeSymbology,
+        format: BarcodeFormat,
+    ) -> Result<RenderedBarcode, DomainError> {
+        let grid = self.encode(data, symbology)?;
+        let bytes = match format {
+            BarcodeFormat::Svg => self.to_svg(&grid),
+            BarcodeFormat::Png => self.to_png(&grid)?,
+        };
+
+        Ok(RenderedBarcode {
+            content_type: format.content_type(),
+            bytes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_code128_svg() {
+        let rendered = BarcodeServiceImpl::new()
+            .render("SKU-001", BarcodeSymbology::Code128, BarcodeFormat::Svg)
+            .unwrap();
+
+        let svg = String::from_utf8(rendered.bytes).unwrap();
+        assert_eq!(rendered.content_type, "image/svg+xml");
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("M10 10h"));
+    }
+
+    #[test]
+    fn test_render_qr_png() {
+        let rendered = BarcodeServiceImpl::new()
+            .render("SKU-001", BarcodeSymbology::Qr, BarcodeFormat::Png)
+            .unwrap();
+
+        assert_eq!(rendered.content_type, "image/png");
+        assert_eq!(&rendered.bytes[..4], b"\x89PNG");
+    }
+
+    #[test]
+    fn test_code128_rejects_non_ascii() {
+        assert!(BarcodeServiceImpl::new()
+            .render("SKU-ñ", BarcodeSymbology::Code128, BarcodeFormat::Svg)
+            .is_err());
+    }
+}
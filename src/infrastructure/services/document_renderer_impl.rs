@@ -0,0 +1,367 @@
+use image::imageops::FilterType;
+use image::ImageFormat;
+use tracing::warn;
+
+use crate::domain::services::document_renderer::{BusinessDocument, DocumentRenderer};
+use crate::infrastructure::services::pdf_writer::{text_width, ImageId, PdfDocument, PdfPage};
+use crate::shared::error::DomainError;
+
+/// A4 portrait, in points
+const PAGE_WIDTH: f64 = 595.0;
+const PAGE_HEIGHT: f64 = 842.0;
+const MARGIN: f64 = 40.0;
+const CONTENT_WIDTH: f64 = PAGE_WIDTH - 2.0 * MARGIN;
+
+/// The logo is scaled to fit this box, keeping its aspect ratio
+const LOGO_MAX_WIDTH: f64 = 160.0;
+const LOGO_MAX_HEIGHT: f64 = 56.0;
+/// Larger logos are downsampled before embedding to keep documents small
+const LOGO_MAX_PIXELS: u32 = 640;
+
+const TEXT_SIZE: f64 = 9.0;
+const LINE_HEIGHT: f64 = 12.0;
+const ROW_HEIGHT: f64 = 16.0;
+const CELL_PADDING: f64 = 4.0;
+/// Space kept clear at the bottom of each page for the footer
+const FOOTER_HEIGHT: f64 = 40.0;
+
+pub struct DocumentRendererImpl;
+
+impl DocumentRendererImpl {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Decode a PNG logo to RGB over a white background; a logo that fails to
+    /// decode is left off rather than failing every document
+    fn embed_logo(pdf: &mut PdfDocument, png: &[u8]) -> Option<(ImageId, f64, f64)> {
+        let image = match image::load_from_memory_with_format(png, ImageFormat::Png) {
+            Ok(image) => image,
+            Err(e) => {
+                warn!("Skipping unreadable document logo: {}", e);
+                return None;
+            }
+        };
+        let image = if image.width() > LOGO_MAX_PIXELS || image.height() > LOGO_MAX_PIXELS {
+            image.resize(LOGO_MAX_PIXELS, LOGO_MAX_PIXELS, FilterType::Triangle)
+        } else {
+            image
+        };
+        let rgba = image.to_rgba8();
+        let rgb: Vec<u8> = rgba
+            .pixels()
+            .flat_map(|pixel| {
+                let [r, g, b, a] = pixel.0;
+                let blend = |c: u8| {
+                    ((u16::from(c) * u16::from(a) + 255 * (255 - u16::from(a))) / 255) as u8
+                };
+                [blend(r), blend(g), blend(b)]
+            })
+            .collect();
+        let id = pdf.add_image(rgba.width(), rgba.height(), &rgb);
+
+        let scale = (LOGO_MAX_WIDTH / f64::from(rgba.width()))
+            .min(LOGO_MAX_HEIGHT / f64::from(rgba.height()));
+        Some((
+            id,
+            f64::from(rgba.width()) * scale,
+            f64::from(rgba.height()) * scale,
+        ))
+    }
+
+    /// Letterhead, title block and parties; returns where the line table starts
+    fn first_page_header(
+        page: &mut PdfPage,
+        document: &BusinessDocument,
+        logo: Option<(ImageId, f64, f64)>,
+    ) -> f64 {
+        let right = PAGE_WIDTH - MARGIN;
+        let mut left_y = PAGE_HEIGHT - MARGIN;
+        if let Some((id, width, height)) = logo {
+            page.image(id, MARGIN, left_y - height, width, height);
+            left_y -= height + 8.0;
+        }
+        for (i, line) in document.letterhead.iter().enumerate() {
+            left_y -= LINE_HEIGHT;
+            if i == 0 {
+                page.bold_text(MARGIN, left_y, 11.0, line);
+            } else {
+                page.text(MARGIN, left_y, TEXT_SIZE, line);
+            }
+        }
+
+        let mut right_y = PAGE_HEIGHT - MARGIN - 18.0;
+        page.bold_text_right(right, right_y, 18.0, document.kind.title());
+        right_y -= 18.0;
+        page.bold_text_right(right, right_y, 11.0, &format!("No. {}", document.number));
+        let date = document.issued_at.format("%Y-%m-%d").to_string();
+        for (label, value) in std::iter::once(("Date", date.as_str())).chain(
+            document
+                .details
+                .iter()
+                .map(|(label, value)| (label.as_str(), value.as_str())),
+        ) {
+            right_y -= LINE_HEIGHT;
+            page.text_right(right, right_y, TEXT_SIZE, &format!("{}: {}", label, value));
+        }
+
+        let mut y = left_y.min(right_y) - 24.0;
+        if !document.parties.is_empty() {
+            let column_width = CONTENT_WIDTH / document.parties.len() as f64;
+            let mut bottom = y;
+            for (i, party) in document.parties.iter().enumerate() {
+                let x = MARGIN + column_width * i as f64;
+                let mut party_y = y;
+                page.bold_text(x, party_y, TEXT_SIZE, &party.heading);
+                for line in &party.lines {
+                    party_y -= LINE_HEIGHT;
+                    page.text(
+                        x,
+                        party_y,
+                        TEXT_SIZE,
+                        &fit(line, column_width - CELL_PADDING, TEXT_SIZE),
+                    );
+                }
+                bottom = bottom.min(party_y);
+            }
+            y = bottom - 24.0;
+        }
+        y
+    }
+
+    /// The document title and number on continuation pages
+    fn continuation_header(page: &mut PdfPage, document: &BusinessDocument) -> f64 {
+        let y = PAGE_HEIGHT - MARGIN - 12.0;
+        page.bold_text(
+            MARGIN,
+            y,
+            11.0,
+            &format!("{} {} (continued)", document.kind.title(), document.number),
+        );
+        y - 24.0
+    }
+
+    fn table_header(
+        page: &mut PdfPage,
+        columns: &[(f64, f64)],
+        document: &BusinessDocument,
+        y: f64,
+    ) {
+        page.shade(MARGIN, y - 4.0, CONTENT_WIDTH, ROW_HEIGHT, 0.9);
+        for ((x, width), column) in columns.iter().zip(&document.columns) {
+            let header = fit(&column.header, width - 2.0 * CELL_PADDING, TEXT_SIZE);
+            if column.numeric {
+                page.bold_text_right(x + width - CELL_PADDING, y, TEXT_SIZE, &header);
+            } else {
+                page.bold_text(x + CELL_PADDING, y, TEXT_SIZE, &header);
+            }
+        }
+    }
+
+    /// Footer text and page numbers, once the page count is known
+    fn footers(pages: &mut [PdfPage], footer: Option<&str>) {
+        let count = pages.len();
+        for (i, page) in pages.iter_mut().enumerate() {
+            page.rect(MARGIN, MARGIN + 14.0, CONTENT_WIDTH, 0.5);
+            if let Some(footer) = footer {
+                page.text(MARGIN, MARGIN, 8.0, &fit(footer, CONTENT_WIDTH - 80.0, 8.0));
+            }
+            page.text_right(
+                PAGE_WIDTH - MARGIN,
+                MARGIN,
+                8.0,
+                &format!("Page {} of {}", i + 1, count),
+            );
+        }
+    }
+}
+
+impl Default for DocumentRendererImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DocumentRenderer for DocumentRendererImpl {
+    fn render(&self, document: &BusinessDocument) -> Result<Vec<u8>, DomainError> {
+        if document.columns.is_empty() {
+            return Err(DomainError::ValidationError(
+                "A document needs at least one column".into(),
+            ));
+        }
+
+        let mut pdf = PdfDocument::new();
+        let logo = document
+            .logo
+            .as_deref()
+            .and_then(|png| Self::embed_logo(&mut pdf, png));
+
+        let total_weight: f64 = document.columns.iter().map(|c| c.weight.max(0.0)).sum();
+        let mut x = MARGIN;
+        let columns: Vec<(f64, f64)> = document
+            .columns
+            .iter()
+            .map(|column| {
+                let width = if total_weight > 0.0 {
+                    CONTENT_WIDTH * column.weight.max(0.0) / total_weight
+                } else {
+                    CONTENT_WIDTH / document.columns.len() as f64
+                };
+                let start = x;
+                x += width;
+                (start, width)
+            })
+            .collect();
+        let bottom = MARGIN + FOOTER_HEIGHT;
+
+        let mut pages = Vec::new();
+        let mut page = PdfPage::new(PAGE_WIDTH, PAGE_HEIGHT);
+        let mut y = Self::first_page_header(&mut page, document, logo);
+        Self::table_header(&mut page, &columns, document, y);
+        y -= ROW_HEIGHT;
+
+        for row in &document.rows {
+            if y < bottom {
+                pages.push(page);
+                page = PdfPage::new(PAGE_WIDTH, PAGE_HEIGHT);
+                y = Self::continuation_header(&mut page, document);
+                Self::table_header(&mut page, &columns, document, y);
+                y -= ROW_HEIGHT;
+            }
+            for ((x, width), (column, cell)) in columns.iter().zip(document.columns.iter().zip(row))
+            {
+                if cell.is_empty() {
+                    continue;
+                }
+                let cell = fit(cell, width - 2.0 * CELL_PADDING, TEXT_SIZE);
+                if column.numeric {
+                    page.text_right(x + width - CELL_PADDING, y, TEXT_SIZE, &cell);
+                } else {
+                    page.text(x + CELL_PADDING, y, TEXT_SIZE, &cell);
+                }
+            }
+            page.rect(MARGIN, y - 4.0, CONTENT_WIDTH, 0.25);
+            y -= ROW_HEIGHT;
+        }
+
+        if !document.totals.is_empty() {
+            if y - ROW_HEIGHT * (document.totals.len() as f64) < bottom {
+                pages.push(page);
+                page = PdfPage::new(PAGE_WIDTH, PAGE_HEIGHT);
+                y = Self::continuation_header(&mut page, document);
+            }
+            let right = PAGE_WIDTH - MARGIN - CELL_PADDING;
+            for (label, value) in &document.totals {
+                page.bold_text_right(right - 100.0, y, TEXT_SIZE, label);
+                page.bold_text_right(right, y, TEXT_SIZE, value);
+                y -= ROW_HEIGHT;
+            }
+        }
+        pages.push(page);
+
+        Self::footers(&mut pages, document.footer.as_deref());
+        for page in pages {
+            pdf.add_page(page);
+        }
+        Ok(pdf.to_bytes())
+    }
+}
+
+/// Cut `text` with an ellipsis so it fits in `width` points
+fn fit(text: &str, width: f64, size: f64) -> String {
+    if text_width(text, size) <= width {
+        return text.to_string();
+    }
+    let ellipsis_width = text_width("...", size);
+    let mut fitted = String::new();
+    let mut used = 0.0;
+    for ch in text.chars() {
+        let ch_width = text_width(ch.encode_utf8(&mut [0; 4]), size);
+        if used + ch_width + ellipsis_width > width {
+            break;
+        }
+        fitted.push(ch);
+        used += ch_width;
+    }
+    fitted.push_str("...");
+    fitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::document_renderer::{DocumentColumn, DocumentKind, DocumentParty};
+    use chrono::Utc;
+    use image::{ImageBuffer, Rgba};
+    use std::io::Cursor;
+
+    fn document(rows: usize) -> BusinessDocument {
+        BusinessDocument {
+            kind: DocumentKind::PurchaseOrder,
+            number: "PO-0001".to_string(),
+            issued_at: Utc::now(),
+            letterhead: vec!["Acme Supply".to_string(), "1 Dock Road".to_string()],
+            logo: None,
+            parties: vec![DocumentParty {
+                heading: "Supplier".to_string(),
+                lines: vec!["Widgets Inc".to_string()],
+            }],
+            details: vec![("Status".to_string(), "OPEN".to_string())],
+            columns: vec![
+                DocumentColumn::text("Item", 3.0),
+                DocumentColumn::numeric("Qty", 1.0),
+            ],
+            rows: (0..rows)
+                .map(|i| vec![format!("WIDGET-{:03}", i), "2".to_string()])
+                .collect(),
+            totals: vec![("Total".to_string(), "10.00".to_string())],
+            footer: Some("Net 30".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_long_tables_continue_on_new_pages() {
+        let renderer = DocumentRendererImpl::new();
+
+        let pdf = renderer.render(&document(3)).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Count 1"));
+        assert!(text.contains("(Purchase Order) Tj"));
+        assert!(text.contains("(WIDGET-002) Tj"));
+        assert!(text.contains("(Page 1 of 1) Tj"));
+
+        let pdf = renderer.render(&document(100)).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Count 3"));
+        assert!(text.contains("(Purchase Order PO-0001 \\(continued\\)) Tj"));
+        assert!(text.contains("(WIDGET-099) Tj"));
+        assert!(text.contains("(Page 3 of 3) Tj"));
+    }
+
+    #[test]
+    fn test_logo_is_embedded_and_bad_logos_are_skipped() {
+        let mut png = Vec::new();
+        ImageBuffer::from_pixel(4, 2, Rgba([10u8, 20, 30, 255]))
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        let mut with_logo = document(1);
+        with_logo.logo = Some(png);
+        let pdf = DocumentRendererImpl::new().render(&with_logo).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Width 4 /Height 2"));
+        assert!(text.contains("/Im0 Do"));
+
+        let mut bad_logo = document(1);
+        bad_logo.logo = Some(b"\x89PNG\r\n\x1a\nnot really".to_vec());
+        let pdf = DocumentRendererImpl::new().render(&bad_logo).unwrap();
+        assert!(!String::from_utf8_lossy(&pdf).contains("/Subtype /Image"));
+    }
+
+    #[test]
+    fn test_fit_truncates_with_ellipsis() {
+        assert_eq!(fit("Short", 100.0, 9.0), "Short");
+        let fitted = fit(&"W".repeat(50), 60.0, 9.0);
+        assert!(fitted.ends_with("..."));
+        assert!(text_width(&fitted, 9.0) <= 60.0);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::domain::services::exchange_rate_provider::ExchangeRateProvider;
+use crate::domain::services::exchange_rate_repository::ExchangeRateRepository;
+use crate::infrastructure::services::stock_snapshot_worker::next_run_after;
+use chrono::Utc;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+/// Fetches the day's exchange rates once a day, plus once at startup when the
+/// stored rates are older than today. Rates are keyed by currency pair and day,
+/// so running several instances only overwrites the same rows.
+pub struct ExchangeRateWorker<R: ExchangeRateRepository, P: ExchangeRateProvider> {
+    exchange_rate_repository: Arc<R>,
+    provider: Arc<P>,
+    base_currency: String,
+    hour_utc: u32,
+}
+
+impl<R: ExchangeRateRepository + 'static, P: ExchangeRateProvider + 'static>
+    ExchangeRateWorker<R, P>
+{
+    pub fn new(
+        exchange_rate_repository: Arc<R>,
+        provider: Arc<P>,
+        base_currency: String,
+        hour_utc: u32,
+    ) -> Self {
+        Self {
+            exchange_rate_repository,
+            provider,
+            base_currency,
+            hour_utc: hour_utc % 24,
+        }
+    }
+
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            "Starting exchange rate worker ({} rates daily at {:02}:00 UTC)",
+            self.base_currency, self.hour_utc
+        );
+
+        match self.exchange_rate_repository.latest_rate_date().await {
+            Ok(Some(date)) if date >= Utc::now().date_naive() => {}
+            Ok(_) => self.fetch().await,
+            Err(e) => warn!("Failed to check stored exchange rates: {}", e),
+        }
+
+        loop {
+            let now = Utc::now();
+            let wait = (next_run_after(now, self.hour_utc) - now)
+                .to_std()
+                .unwrap_or_default();
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(wait) => {}
+            }
+
+            self.fetch().await;
+        }
+        info!("Exchange rate worker stopped");
+    }
+
+    async fn fetch(&self) {
+        let rates = match self.provider.fetch_latest(&self.base_currency).await {
+            Ok(rates) => rates,
+            Err(e) => {
+                error!("Failed to fetch exchange rates: {}", e);
+                return;
+            }
+        };
+        match self.exchange_rate_repository.save_rates(&rates).await {
+            Ok(saved) => info!("Stored {} {} exchange rates", saved, self.base_currency),
+            Err(e) => error!("Failed to store exchange rates: {}", e),
+        }
+    }
+}
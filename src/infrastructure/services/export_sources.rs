@@ -0,0 +1,566 @@
+use crate::application::use_cases::get_dead_stock_report::GetDeadStockReportUseCase;
+use crate::application::use_cases::get_reorder_suggestions::GetReorderSuggestionsUseCase;
+use crate::application::use_cases::get_scrap_report::GetScrapReportUseCase;
+use crate::domain::entities::forecast::ForecastQuery;
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::time_zone::ReportBound;
+use crate::domain::services::export_source::{parse_params, ExportPage, ExportSource};
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::services::purchase_order_repository::PurchaseOrderRepository;
+use crate::domain::services::report_service::ReportService;
+use crate::domain::services::return_repository::ReturnRepository;
+use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::domain::services::stock_repository::StockRepository;
+use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest, MAX_PAGE_LIMIT};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn to_rows<T: Serialize>(items: &[T]) -> Result<Vec<Value>, DomainError> {
+    items
+        .iter()
+        .map(|item| {
+            serde_json::to_value(item).map_err(|e| {
+                DomainError::InfrastructureError(
+                    format!("Failed to serialize report row: {}", e).into(),
+                )
+            })
+        })
+        .collect()
+}
+
+fn keyset_page<T: Serialize>(page: Page<T>) -> Result<ExportPage, DomainError> {
+    Ok(ExportPage {
+        rows: to_rows(&page.data)?,
+        next_cursor: page.cursor.next_cursor.filter(|_| page.cursor.has_more),
+    })
+}
+
+/// Orders export one row each; their lines are summarised as a count
+fn order_row(mut row: Value) -> Value {
+    if let Some(fields) = row.as_object_mut() {
+        let line_count = fields
+            .remove("lines")
+            .and_then(|lines| lines.as_array().map(Vec::len))
+            .unwrap_or(0);
+        fields.insert("line_count".to_string(), line_count.into());
+    }
+    row
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NoParams {}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LowStockParams {
+    threshold: Option<Decimal>,
+}
+
+pub struct LowStockExportSource<R: ReportService> {
+    report_service: Arc<R>,
+}
+
+impl<R: ReportService> LowStockExportSource<R> {
+    pub fn new(report_service: Arc<R>) -> Self {
+        Self { report_service }
+    }
+}
+
+#[async_trait]
+impl<R: ReportService> ExportSource for LowStockExportSource<R> {
+    fn name(&self) -> &'static str {
+        "low_stock"
+    }
+
+    fn validate(&self, params: &Value) -> Result<(), DomainError> {
+        parse_params::<LowStockParams>(params).map(|_| ())
+    }
+
+    async fn fetch_page(
+        &self,
+        params: &Value,
+        cursor: Option<String>,
+    ) -> Result<ExportPage, DomainError> {
+        let params: LowStockParams = parse_params(params)?;
+        let page = self
+            .report_service
+            .generate_low_stock_report(
+                params.threshold.unwrap_or(Decimal::from(10)),
+                MAX_PAGE_LIMIT,
+                cursor,
+            )
+            .await
+            .map_err(|e| DomainError::InfrastructureError(e.into()))?;
+        Ok(ExportPage {
+            rows: to_rows(&page.items)?,
+            next_cursor: page.next_cursor,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StockValuationParams {
+    location_id: Option<Uuid>,
+    valuation_method: Option<String>,
+}
+
+impl StockValuationParams {
+    fn parse(params: &Value) -> Result<Self, DomainError> {
+        let params: Self = parse_params(params)?;
+        if let Some(method) = &params.valuation_method {
+            if !["FIFO", "LIFO", "AVG"].contains(&method.as_str()) {
+                return Err(DomainError::ValidationError(
+                    "Invalid valuation method. Must be FIFO, LIFO, or AVG".into(),
+                ));
+            }
+        }
+        Ok(params)
+    }
+}
+
+pub struct StockValuationExportSource<R: ReportService> {
+    report_service: Arc<R>,
+}
+
+impl<R: ReportService> StockValuationExportSource<R> {
+    pub fn new(report_service: Arc<R>) -> Self {
+        Self { report_service }
+    }
+}
+
+#[async_trait]
+impl<R: ReportService> ExportSource for StockValuationExportSource<R> {
+    fn name(&self) -> &'static str {
+        "stock_valuation"
+    }
+
+    fn validate(&self, params: &Value) -> Result<(), DomainError> {
+        StockValuationParams::parse(params).map(|_| ())
+    }
+
+    async fn fetch_page(
+        &self,
+        params: &Value,
+        cursor: Option<String>,
+    ) -> Result<ExportPage, DomainError> {
+        let params = StockValuationParams::parse(params)?;
+        let page = self
+            .report_service
+            .generate_stock_valuation_report(
+                params.location_id,
+                params
+                    .valuation_method
+                    .unwrap_or_else(|| "FIFO".to_string()),
+                MAX_PAGE_LIMIT,
+                cursor,
+            )
+            .await
+            .map_err(|e| DomainError::InfrastructureError(e.into()))?;
+        Ok(ExportPage {
+            rows: to_rows(&page.items)?,
+            next_cursor: page.next_cursor,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScrapParams {
+    from: Option<ReportBound>,
+    to: Option<ReportBound>,
+    location_id: Option<Uuid>,
+}
+
+pub struct ScrapExportSource<R: ReturnRepository> {
+    get_scrap_report_use_case: Arc<GetScrapReportUseCase<R>>,
+}
+
+impl<R: ReturnRepository> ScrapExportSource<R> {
+    pub fn new(get_scrap_report_use_case: Arc<GetScrapReportUseCase<R>>) -> Self {
+        Self {
+            get_scrap_report_use_case,
+        }
+    }
+}
+
+#[async_trait]
+impl<R: ReturnRepository> ExportSource for ScrapExportSource<R> {
+    fn name(&self) -> &'static str {
+        "scrap"
+    }
+
+    fn validate(&self, params: &Value) -> Result<(), DomainError> {
+        parse_params::<ScrapParams>(params).map(|_| ())
+    }
+
+    async fn fetch_page(
+        &self,
+        params: &Value,
+        _cursor: Option<String>,
+    ) -> Result<ExportPage, DomainError> {
+        let params: ScrapParams = parse_params(params)?;
+        let report = self
+            .get_scrap_report_use_case
+            .execute(params.from, params.to, params.location_id)
+            .await?;
+        Ok(ExportPage {
+            rows: to_rows(&report.items)?,
+            next_cursor: None,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DeadStockParams {
+    days: Option<i64>,
+    location_id: Option<Uuid>,
+}
+
+pub struct DeadStockExportSource<S: StockRepository> {
+    get_dead_stock_report_use_case: Arc<GetDeadStockReportUseCase<S>>,
+}
+
+impl<S: StockRepository> DeadStockExportSource<S> {
+    pub fn new(get_dead_stock_report_use_case: Arc<GetDeadStockReportUseCase<S>>) -> Self {
+        Self {
+            get_dead_stock_report_use_case,
+        }
+    }
+}
+
+#[async_trait]
+impl<S: StockRepository> ExportSource for DeadStockExportSource<S> {
+    fn name(&self) -> &'static str {
+        "dead_stock"
+    }
+
+    fn validate(&self, params: &Value) -> Result<(), DomainError> {
+        parse_params::<DeadStockParams>(params).map(|_| ())
+    }
+
+    async fn fetch_page(
+        &self,
+        params: &Value,
+        _cursor: Option<String>,
+    ) -> Result<ExportPage, DomainError> {
+        let params: DeadStockParams = parse_params(params)?;
+        let report = self
+            .get_dead_stock_report_use_case
+            .execute(params.days, params.location_id)
+            .await?;
+        Ok(ExportPage {
+            rows: to_rows(&report.items)?,
+            next_cursor: None,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReorderSuggestionsParams {
+    supplier_id: Option<Uuid>,
+    #[serde(default)]
+    dynamic_reorder_points: bool,
+}
+
+/// One row per suggested line, tagged with its supplier
+pub struct ReorderSuggestionsExportSource<T: ItemRepository, S: StockRepository> {
+    get_reorder_suggestions_use_case: Arc<GetReorderSuggestionsUseCase<T, S>>,
+}
+
+impl<T: ItemRepository, S: StockRepository> ReorderSuggestionsExportSource<T, S> {
+    pub fn new(get_reorder_suggestions_use_case: Arc<GetReorderSuggestionsUseCase<T, S>>) -> Self {
+        Self {
+            get_reorder_suggestions_use_case,
+        }
+    }
+}
+
+#[async_trait]
+impl<T: ItemRepository, S: StockRepository> ExportSource for ReorderSuggestionsExportSource<T, S> {
+    fn name(&self) -> &'static str {
+        "reorder_suggestions"
+    }
+
+    fn validate(&self, params: &Value) -> Result<(), DomainError> {
+        parse_params::<ReorderSuggestionsParams>(params).map(|_| ())
+    }
+
+    async fn fetch_page(
+        &self,
+        params: &Value,
+        _cursor: Option<String>,
+    ) -> Result<ExportPage, DomainError> {
+        let params: ReorderSuggestionsParams = parse_params(params)?;
+        let report = self
+            .get_reorder_suggestions_use_case
+            .execute(
+                params.supplier_id,
+                params
+                    .dynamic_reorder_points
+                    .then(ForecastQuery::default)
+                    .as_ref(),
+            )
+            .await?;
+        let mut rows = Vec::with_capacity(report.item_count);
+        for group in &report.suppliers {
+            for mut row in to_rows(&group.lines)? {
+                if let Some(fields) = row.as_object_mut() {
+                    fields.insert(
+                        "supplier_id".to_string(),
+                        serde_json::json!(group.supplier_id),
+                    );
+                    fields.insert(
+                        "supplier_name".to_string(),
+                        serde_json::json!(group.supplier_name),
+                    );
+                }
+                rows.push(row);
+            }
+        }
+        Ok(ExportPage {
+            rows,
+            next_cursor: None,
+        })
+    }
+}
+
+/// Accepts the same filters as `GET /items`
+pub struct ItemsExportSource<R: ItemRepository> {
+    item_repository: Arc<R>,
+}
+
+impl<R: ItemRepository> ItemsExportSource<R> {
+    pub fn new(item_repository: Arc<R>) -> Self {
+        Self { item_repository }
+    }
+}
+
+#[async_trait]
+impl<R: ItemRepository> ExportSource for ItemsExportSource<R> {
+    fn name(&self) -> &'static str {
+        "items"
+    }
+
+    fn validate(&self, params: &Value) -> Result<(), DomainError> {
+        parse_params::<ListFilter>(params).map(|_| ())
+    }
+
+    async fn fetch_page(
+        &self,
+        params: &Value,
+        cursor: Option<String>,
+    ) -> Result<ExportPage, DomainError> {
+        let filter: ListFilter = parse_params(params)?;
+        let page = self
+            .item_repository
+            .list(&filter, &PageRequest::new(Some(MAX_PAGE_LIMIT), cursor))
+            .await?;
+        keyset_page(page)
+    }
+}
+
+pub struct LocationsExportSource<R: LocationRepository> {
+    location_repository: Arc<R>,
+}
+
+impl<R: LocationRepository> LocationsExportSource<R> {
+    pub fn new(location_repository: Arc<R>) -> Self {
+        Self {
+            location_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl<R: LocationRepository> ExportSource for LocationsExportSource<R> {
+    fn name(&self) -> &'static str {
+        "locations"
+    }
+
+    fn validate(&self, params: &Value) -> Result<(), DomainError> {
+        parse_params::<NoParams>(params).map(|_| ())
+    }
+
+    async fn fetch_page(
+        &self,
+        params: &Value,
+        cursor: Option<String>,
+    ) -> Result<ExportPage, DomainError> {
+        parse_params::<NoParams>(params)?;
+        // Locations page by offset; the cursor carries it between pages
+        let offset = match cursor {
+            Some(cursor) => cursor.parse().map_err(|_| {
+                DomainError::ValidationError(format!("Invalid cursor '{}'", cursor).into())
+            })?,
+            None => 0,
+        };
+        let locations = self
+            .location_repository
+            .list(MAX_PAGE_LIMIT, offset)
+            .await?;
+        let next_cursor = (locations.len() as i64 == MAX_PAGE_LIMIT)
+            .then(|| (offset + MAX_PAGE_LIMIT).to_string());
+        Ok(ExportPage {
+            rows: to_rows(&locations)?,
+            next_cursor,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StockLevelsParams {
+    location_id: Option<Uuid>,
+}
+
+pub struct StockLevelsExportSource<S: StockRepository> {
+    stock_repository: Arc<S>,
+}
+
+impl<S: StockRepository> StockLevelsExportSource<S> {
+    pub fn new(stock_repository: Arc<S>) -> Self {
+        Self { stock_repository }
+    }
+}
+
+#[async_trait]
+impl<S: StockRepository> ExportSource for StockLevelsExportSource<S> {
+    fn name(&self) -> &'static str {
+        "stock_levels"
+    }
+
+    fn validate(&self, params: &Value) -> Result<(), DomainError> {
+        parse_params::<StockLevelsParams>(params).map(|_| ())
+    }
+
+    async fn fetch_page(
+        &self,
+        params: &Value,
+        cursor: Option<String>,
+    ) -> Result<ExportPage, DomainError> {
+        let params: StockLevelsParams = parse_params(params)?;
+        let page_request = PageRequest::new(Some(MAX_PAGE_LIMIT), cursor);
+        let page = match params.location_id {
+            Some(location_id) => {
+                self.stock_repository
+                    .get_stock_levels_by_location(location_id, &page_request)
+                    .await?
+            }
+            None => {
+                self.stock_repository
+                    .get_all_stock_levels(&page_request)
+                    .await?
+            }
+        };
+        keyset_page(page)
+    }
+}
+
+/// Accepts the same filters as `GET /sales_orders`
+pub struct SalesOrdersExportSource<R: SalesOrderRepository> {
+    sales_order_repository: Arc<R>,
+}
+
+impl<R: SalesOrderRepository> SalesOrdersExportSource<R> {
+    pub fn new(sales_order_repository: Arc<R>) -> Self {
+        Self {
+            sales_order_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl<R: SalesOrderRepository> ExportSource for SalesOrdersExportSource<R> {
+    fn name(&self) -> &'static str {
+        "sales_orders"
+    }
+
+    fn validate(&self, params: &Value) -> Result<(), DomainError> {
+        parse_params::<ListFilter>(params).map(|_| ())
+    }
+
+    async fn fetch_page(
+        &self,
+        params: &Value,
+        cursor: Option<String>,
+    ) -> Result<ExportPage, DomainError> {
+        let filter: ListFilter = parse_params(params)?;
+        let page = self
+            .sales_order_repository
+            .list(&filter, &PageRequest::new(Some(MAX_PAGE_LIMIT), cursor))
+            .await?;
+        let orders: Vec<_> = page.data.into_iter().map(|(order, _)| order).collect();
+        Ok(ExportPage {
+            rows: to_rows(&orders)?.into_iter().map(order_row).collect(),
+            next_cursor: page.cursor.next_cursor.filter(|_| page.cursor.has_more),
+        })
+    }
+}
+
+/// Accepts the same filters as `GET /purchase_orders`
+pub struct PurchaseOrdersExportSource<R: PurchaseOrderRepository> {
+    purchase_order_repository: Arc<R>,
+}
+
+impl<R: PurchaseOrderRepository> PurchaseOrdersExportSource<R> {
+    pub fn new(purchase_order_repository: Arc<R>) -> Self {
+        Self {
+            purchase_order_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl<R: PurchaseOrderRepository> ExportSource for PurchaseOrdersExportSource<R> {
+    fn name(&self) -> &'static str {
+        "purchase_orders"
+    }
+
+    fn validate(&self, params: &Value) -> Result<(), DomainError> {
+        parse_params::<ListFilter>(params).map(|_| ())
+    }
+
+    async fn fetch_page(
+        &self,
+        params: &Value,
+        cursor: Option<String>,
+    ) -> Result<ExportPage, DomainError> {
+        let filter: ListFilter = parse_params(params)?;
+        let page = self
+            .purchase_order_repository
+            .list(&filter, &PageRequest::new(Some(MAX_PAGE_LIMIT), cursor))
+            .await?;
+        let mut export = keyset_page(page)?;
+        export.rows = export.rows.into_iter().map(order_row).collect();
+        Ok(export)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_order_row_counts_lines() {
+        let row = order_row(json!({ "id": 1, "lines": [{}, {}] }));
+        assert_eq!(row, json!({ "id": 1, "line_count": 2 }));
+    }
+
+    #[test]
+    fn test_params_reject_unknown_fields() {
+        assert!(StockValuationParams::parse(&json!({ "valuation_method": "AVG" })).is_ok());
+        assert!(StockValuationParams::parse(&json!({ "valuation_method": "LOL" })).is_err());
+        assert!(parse_params::<NoParams>(&json!({ "limit": 5 })).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::domain::entities::address::{AddressIssue, AddressValidation};
+use crate::domain::entities::location::LocationAddress;
+use crate::domain::services::address_validator::AddressValidator;
+use crate::infrastructure::services::offline_address_validator::OfflineAddressValidator;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use std::env;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct AddressValidationConfig {
+    /// Endpoint that takes `{"address": {...}}` and answers with the normalized
+    /// address and any issues
+    pub api_url: String,
+    /// Sent as a bearer token when set
+    pub api_key: Option<String>,
+    pub timeout_secs: u64,
+}
+
+impl AddressValidationConfig {
+    /// Read `ADDRESS_VALIDATION_API_URL`, `ADDRESS_VALIDATION_API_KEY` and
+    /// `ADDRESS_VALIDATION_TIMEOUT_SECS`; `None` without a URL
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| {
+            env::var(name)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Some(Self {
+            api_url: var("ADDRESS_VALIDATION_API_URL")?,
+            api_key: var("ADDRESS_VALIDATION_API_KEY"),
+            timeout_secs: var("ADDRESS_VALIDATION_TIMEOUT_SECS")
+                .and_then(|secs| secs.parse().ok())
+                .filter(|secs| *secs > 0)
+                .unwrap_or(5),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderResponse {
+    address: LocationAddress,
+    #[serde(default)]
+    issues: Vec<AddressIssue>,
+}
+
+/// Validates addresses with an external provider. The offline heuristics tidy
+/// the address first and stand in whenever the provider can't be reached or
+/// answers with something unexpected, so saving a location never depends on it.
+pub struct HttpAddressValidator {
+    client: reqwest::Client,
+    api_url: String,
+    api_key: Option<String>,
+    fallback: OfflineAddressValidator,
+}
+
+impl HttpAddressValidator {
+    pub fn new(config: &AddressValidationConfig) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(config.timeout_secs))
+                .build()
+                .expect("Address validation HTTP client configuration is valid"),
+            api_url: config.api_url.clone(),
+            api_key: config.api_key.clone(),
+            fallback: OfflineAddressValidator,
+        }
+    }
+
+    async fn ask_provider(&self, address: &LocationAddress) -> Result<String, DomainError> {
+        let mut request = self
+            .client
+            .post(&self.api_url)
+            .json(&json!({ "address": address }));
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            DomainError::InfrastructureError(
+                format!("Address validation request failed: {}", e).into(),
+            )
+        })?;
+        if !response.status().is_success() {
+            return Err(DomainError::InfrastructureError(
+                format!("Address validation provider answered {}", response.status()).into(),
+            ));
+        }
+        response.text().await.map_err(|e| {
+            DomainError::InfrastructureError(
+                format!("Address validation response unreadable: {}", e).into(),
+            )
+        })
+    }
+
+    fn provider_name(&self) -> String {
+        reqwest::Url::parse(&self.api_url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| "address-provider".to_string())
+    }
+}
+
+/// Turn the provider's answer into a validation of `original`
+fn parse_response(
+    body: &str,
+    original: &LocationAddress,
+    provider: String,
+) -> Result<AddressValidation, DomainError> {
+    let response: ProviderResponse = serde_json::from_str(body).map_err(|e| {
+        DomainError::InfrastructureError(
+            format!("Unexpected address validation response: {}", e).into(),
+        )
+    })?;
+    Ok(AddressValidation::new(
+        original,
+        response.address,
+        response.issues,
+        provider,
+    ))
+}
+
+#[async_trait]
+impl AddressValidator for HttpAddressValidator {
+    async fn validate(&self, address: &LocationAddress) -> Result<AddressValidation, DomainError> {
+        let offline = self.fallback.validate(address).await?;
+        // Nothing a provider can fix about an unknown country or a malformed postal code
+        if !offline.valid {
+            return Ok(offline);
+        }
+
+        let checked = match self.ask_provider(&offline.address).await {
+            Ok(body) => parse_response(&body, address, self.provider_name()),
+            Err(e) => Err(e),
+        };
+        match checked {
+            Ok(validation) => Ok(validation),
+            Err(e) => {
+                tracing::warn!(
+                    "Address validation provider unavailable, using offline checks: {}",
+                    e
+                );
+                Ok(offline)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::address::AddressIssueSeverity;
+
+    #[test]
+    fn test_parse_response() {
+        let original = LocationAddress {
+            line1: Some("1600 wynkoop".to_string()),
+            city: Some("Denver".to_string()),
+            country: Some("US".to_string()),
+            ..Default::default()
+        };
+        let body = r#"{"address":{"line1":"1600 Wynkoop St","city":"Denver","region":"CO","postal_code":"80202","country":"US"},
+            "issues":[{"field":"line1","code":"SUITE_MISSING","severity":"WARNING","message":"Suite number may be missing"}]}"#;
+
+        let validation = parse_response(body, &original, "av.example.com".to_string()).unwrap();
+
+        assert!(validation.valid);
+        assert_eq!(validation.provider, "av.example.com");
+        assert_eq!(validation.address.region.as_deref(), Some("CO"));
+        assert_eq!(
+            validation.corrected_fields,
+            vec!["line1", "region", "postal_code"]
+        );
+        assert_eq!(validation.issues[0].severity, AddressIssueSeverity::Warning);
+        assert!(parse_response("<html>", &original, "x".to_string()).is_err());
+    }
+}
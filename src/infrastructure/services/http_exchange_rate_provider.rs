@@ -0,0 +1,160 @@
+use crate::domain::entities::currency::{normalize_currency, ExchangeRate};
+use crate::domain::services::exchange_rate_provider::ExchangeRateProvider;
+use crate::shared::error::DomainError;
+use crate::shared::money;
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use reqwest::Url;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct ExchangeRateConfig {
+    /// Frankfurter-compatible feed, e.g. the public ECB reference rates
+    pub api_url: String,
+    /// Currency the feed is asked to quote against; others are crossed through it
+    pub base_currency: String,
+    /// Hour of the day (UTC) rates are fetched, after the feed publishes
+    pub fetch_hour_utc: u32,
+}
+
+impl Default for ExchangeRateConfig {
+    fn default() -> Self {
+        Self {
+            api_url: "https://api.frankfurter.app".to_string(),
+            base_currency: "EUR".to_string(),
+            // The ECB publishes shortly after 16:00 CET
+            fetch_hour_utc: 16,
+        }
+    }
+}
+
+impl ExchangeRateConfig {
+    /// Read `EXCHANGE_RATE_API_URL`, `EXCHANGE_RATE_BASE_CURRENCY` and
+    /// `EXCHANGE_RATE_FETCH_HOUR_UTC`
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| {
+            env::var(name)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Self {
+            api_url: var("EXCHANGE_RATE_API_URL").unwrap_or(defaults.api_url),
+            base_currency: var("EXCHANGE_RATE_BASE_CURRENCY")
+                .and_then(|code| normalize_currency(&code).ok())
+                .unwrap_or(defaults.base_currency),
+            fetch_hour_utc: var("EXCHANGE_RATE_FETCH_HOUR_UTC")
+                .and_then(|hour| hour.parse().ok())
+                .filter(|hour| *hour < 24)
+                .unwrap_or(defaults.fetch_hour_utc),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LatestRates {
+    base: String,
+    date: NaiveDate,
+    rates: HashMap<String, Decimal>,
+}
+
+/// Fetches daily reference rates from a Frankfurter-style `/latest` endpoint
+pub struct HttpExchangeRateProvider {
+    client: reqwest::Client,
+    api_url: String,
+}
+
+impl HttpExchangeRateProvider {
+    pub fn new(config: &ExchangeRateConfig) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .expect("Exchange rate HTTP client configuration is valid"),
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+/// Turn a `/latest` response into rates, skipping codes that aren't ISO 4217
+fn parse_rates(body: &str, source: &str) -> Result<Vec<ExchangeRate>, DomainError> {
+    let latest: LatestRates = serde_json::from_str(body).map_err(|e| {
+        DomainError::InfrastructureError(format!("Unexpected exchange rate response: {}", e).into())
+    })?;
+    let base_currency = normalize_currency(&latest.base)?;
+    let fetched_at = Utc::now();
+
+    let mut rates: Vec<ExchangeRate> = latest
+        .rates
+        .into_iter()
+        .filter(|(_, rate)| *rate > Decimal::ZERO)
+        .filter_map(|(code, rate)| {
+            Some(ExchangeRate {
+                base_currency: base_currency.clone(),
+                quote_currency: normalize_currency(&code).ok()?,
+                rate: money::round_rate(rate),
+                rate_date: latest.date,
+                source: source.to_string(),
+                fetched_at,
+            })
+        })
+        .collect();
+    rates.sort_by(|a, b| a.quote_currency.cmp(&b.quote_currency));
+    Ok(rates)
+}
+
+#[async_trait]
+impl ExchangeRateProvider for HttpExchangeRateProvider {
+    async fn fetch_latest(&self, base_currency: &str) -> Result<Vec<ExchangeRate>, DomainError> {
+        let url = Url::parse_with_params(
+            &format!("{}/latest", self.api_url),
+            &[("from", base_currency)],
+        )
+        .map_err(|e| {
+            DomainError::InfrastructureError(format!("Invalid exchange rate URL: {}", e).into())
+        })?;
+        let source = url.host_str().unwrap_or("exchange-rate-feed").to_string();
+
+        let response = self.client.get(url).send().await.map_err(|e| {
+            DomainError::InfrastructureError(format!("Exchange rate request failed: {}", e).into())
+        })?;
+        if !response.status().is_success() {
+            return Err(DomainError::InfrastructureError(
+                format!("Exchange rate feed answered {}", response.status()).into(),
+            ));
+        }
+        let body = response.text().await.map_err(|e| {
+            DomainError::InfrastructureError(
+                format!("Exchange rate response unreadable: {}", e).into(),
+            )
+        })?;
+
+        parse_rates(&body, &source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rates() {
+        let body = r#"{"amount":1.0,"base":"EUR","date":"2026-10-14","rates":{"USD":1.0772,"GBP":0.8561,"XX1":2.0}}"#;
+        let rates = parse_rates(body, "api.frankfurter.app").unwrap();
+
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[0].quote_currency, "GBP");
+        assert_eq!(rates[1].base_currency, "EUR");
+        assert_eq!(rates[1].quote_currency, "USD");
+        assert_eq!(rates[1].rate, Decimal::new(10772, 4));
+        assert_eq!(
+            rates[1].rate_date,
+            NaiveDate::from_ymd_opt(2026, 10, 14).unwrap()
+        );
+    }
+}
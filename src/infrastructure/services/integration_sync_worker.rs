@@ -0,0 +1,118 @@
+use crate::application::use_cases::sync_connector::SyncConnectorUseCase;
+use crate::domain::entities::integration::{SyncRunStatus, SyncTrigger};
+use crate::domain::services::integration_repository::IntegrationRepository;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::sales_order_repository::SalesOrderRepository;
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::with_tenant;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+/// Connectors claimed per poll
+const BATCH_SIZE: i64 = 10;
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Runs connectors whose sync interval has elapsed. Claiming pushes a
+/// connector's next sync out, so several instances never sync it twice.
+pub struct IntegrationSyncWorker<
+    R: IntegrationRepository,
+    I: ItemRepository,
+    O: SalesOrderRepository,
+> {
+    integration_repository: Arc<R>,
+    sync_use_case: Arc<SyncConnectorUseCase<R, I, O>>,
+    poll_interval: Duration,
+}
+
+impl<R, I, O> IntegrationSyncWorker<R, I, O>
+where
+    R: IntegrationRepository + 'static,
+    I: ItemRepository + 'static,
+    O: SalesOrderRepository + 'static,
+{
+    pub fn new(
+        integration_repository: Arc<R>,
+        sync_use_case: Arc<SyncConnectorUseCase<R, I, O>>,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            integration_repository,
+            sync_use_case,
+            poll_interval,
+        }
+    }
+
+    /// Read the poll interval from `INTEGRATION_SYNC_POLL_INTERVAL_SECS`, defaulting to a minute
+    pub fn from_env(
+        integration_repository: Arc<R>,
+        sync_use_case: Arc<SyncConnectorUseCase<R, I, O>>,
+    ) -> Self {
+        let poll_interval = env::var("INTEGRATION_SYNC_POLL_INTERVAL_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_POLL_INTERVAL);
+        Self::new(integration_repository, sync_use_case, poll_interval)
+    }
+
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            "Starting integration sync worker (poll every {:?})",
+            self.poll_interval
+        );
+
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            // Keep going while full batches come back instead of waiting a whole interval
+            loop {
+                match self.poll_once().await {
+                    Ok(claimed) if claimed as i64 == BATCH_SIZE => continue,
+                    Ok(_) => break,
+                    Err(e) => {
+                        error!("Failed to claim due connectors: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+        info!("Integration sync worker stopped");
+    }
+
+    async fn poll_once(&self) -> Result<usize, DomainError> {
+        let due = self
+            .integration_repository
+            .claim_due_connectors(BATCH_SIZE)
+            .await?;
+        let claimed = due.len();
+        for (tenant_id, connector) in due {
+            let connector_id = connector.id;
+            let sync = self.sync_use_case.run(connector, SyncTrigger::Scheduled);
+            match with_tenant(tenant_id, sync).await {
+                Ok(runs) => {
+                    for run in runs
+                        .iter()
+                        .filter(|run| run.status != SyncRunStatus::Succeeded)
+                    {
+                        warn!(
+                            "Connector {} {} sync finished {}: {:?}",
+                            connector_id,
+                            run.kind.as_str(),
+                            run.status.as_str(),
+                            run.errors.first().map(|e| &e.message)
+                        );
+                    }
+                }
+                Err(e) => error!("Failed to sync connector {}: {}", connector_id, e),
+            }
+        }
+        Ok(claimed)
+    }
+}
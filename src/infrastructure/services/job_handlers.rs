@@ -0,0 +1,430 @@
+use crate::application::use_cases::import_items::ImportItemsUseCase;
+use crate::application::use_cases::item_availability::ItemAvailabilityUseCase;
+use crate::application::use_cases::manage_label_printing::ManageLabelPrintingUseCase;
+use crate::application::use_cases::search_use_case::SearchUseCase;
+use crate::application::use_cases::tenant_snapshot::TenantSnapshotUseCase;
+use crate::application::use_cases::webhook_retention::WebhookRetentionUseCase;
+use crate::domain::entities::export::{
+    export_object_key, ExportFormat, ExportTable, ReportExportRequest,
+};
+use crate::domain::entities::job::{Job, JobError};
+use crate::domain::entities::webhook_retention::WebhookRetentionJobPayload;
+use crate::domain::services::blob_storage::BlobStorage;
+use crate::domain::services::export_service::ExportService;
+use crate::domain::services::export_source::{ExportSource, ExportSourceRegistry};
+use crate::domain::services::item_availability_repository::ItemAvailabilityRepository;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::job_service::JobService;
+use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::services::print_repository::PrintRepository;
+use crate::domain::services::tenant_snapshot_repository::TenantSnapshotRepository;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::domain::services::webhook_retention_repository::WebhookRetentionRepository;
+use crate::infrastructure::services::job_worker::{JobContext, JobHandler, JobOutcome};
+use crate::infrastructure::services::table_export_writer::render_table;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub use crate::domain::entities::export::GENERATE_REPORT_JOB_TYPE;
+pub use crate::domain::entities::item_availability::REBUILD_ITEM_AVAILABILITY_JOB_TYPE;
+pub use crate::domain::entities::printing::PRINT_LABELS_JOB_TYPE;
+pub use crate::domain::entities::search::REBUILD_SEARCH_INDEX_JOB_TYPE;
+pub use crate::domain::entities::tenant_snapshot::{
+    TENANT_SNAPSHOT_EXPORT_JOB_TYPE, TENANT_SNAPSHOT_IMPORT_JOB_TYPE,
+};
+pub use crate::domain::entities::webhook_retention::WEBHOOK_RETENTION_JOB_TYPE;
+
+/// Bad input won't get better on a retry, so fail the job outright; anything
+/// else (storage or database trouble) fails just this attempt
+fn outcome_for_error(e: DomainError) -> Result<JobOutcome, JobError> {
+    let error = JobError {
+        row: None,
+        message: e.to_string(),
+    };
+    match e {
+        DomainError::ValidationError(_) | DomainError::NotFound(_) => Ok(JobOutcome::Failed {
+            errors: vec![error],
+        }),
+        _ => Err(error),
+    }
+}
+
+/// Writes a stock CSV export to blob storage
+pub struct StockCsvExportJobHandler<E: ExportService> {
+    export_service: Arc<E>,
+}
+
+impl<E: ExportService> StockCsvExportJobHandler<E> {
+    pub fn new(export_service: Arc<E>) -> Self {
+        Self { export_service }
+    }
+}
+
+#[async_trait]
+impl<E: ExportService> JobHandler for StockCsvExportJobHandler<E> {
+    async fn handle(&self, job: &Job, context: &JobContext) -> Result<JobOutcome, JobError> {
+        match self
+            .export_service
+            .run_stock_csv_export(job, context.cancellation())
+            .await
+        {
+            Ok(Some(result)) => Ok(JobOutcome::Success {
+                result_url: Some(result.download_url),
+            }),
+            Ok(None) => Ok(JobOutcome::Cancelled),
+            Err(e) => outcome_for_error(e),
+        }
+    }
+}
+
+/// Creates the items of an uploaded import file
+pub struct ImportItemsJobHandler<R, D, S>
+where
+    R: ItemRepository + 'static,
+    D: WebhookDispatcher + 'static,
+    S: JobService + 'static,
+{
+    import_items_use_case: Arc<ImportItemsUseCase<R, D, S>>,
+}
+
+impl<R, D, S> ImportItemsJobHandler<R, D, S>
+where
+    R: ItemRepository + 'static,
+    D: WebhookDispatcher + 'static,
+    S: JobService + 'static,
+{
+    pub fn new(import_items_use_case: Arc<ImportItemsUseCase<R, D, S>>) -> Self {
+        Self {
+            import_items_use_case,
+        }
+    }
+}
+
+#[async_trait]
+impl<R, D, S> JobHandler for ImportItemsJobHandler<R, D, S>
+where
+    R: ItemRepository + 'static,
+    D: WebhookDispatcher + 'static,
+    S: JobService + 'static,
+{
+    async fn handle(&self, job: &Job, context: &JobContext) -> Result<JobOutcome, JobError> {
+        let outcome = match self
+            .import_items_use_case
+            .run(job, context.cancellation())
+            .await
+        {
+            Ok(outcome) => outcome,
+            Err(e) => return outcome_for_error(e),
+        };
+
+        Ok(if outcome.cancelled {
+            JobOutcome::Cancelled
+        } else if outcome.errors.is_empty() {
+            JobOutcome::Success { result_url: None }
+        } else if outcome.created > 0 {
+            JobOutcome::PartialSuccess {
+                result_url: None,
+                errors: outcome.errors,
+            }
+        } else {
+            JobOutcome::Failed {
+                errors: outcome.errors,
+            }
+        })
+    }
+}
+
+/// Rebuilds the search index
+pub struct RebuildSearchIndexJobHandler<U: SearchUseCase> {
+    search_use_case: Arc<U>,
+}
+
+impl<U: SearchUseCase> RebuildSearchIndexJobHandler<U> {
+    pub fn new(search_use_case: Arc<U>) -> Self {
+        Self { search_use_case }
+    }
+}
+
+#[async_trait]
+impl<U: SearchUseCase> JobHandler for RebuildSearchIndexJobHandler<U> {
+    async fn handle(&self, _job: &Job, _context: &JobContext) -> Result<JobOutcome, JobError> {
+        match self.search_use_case.rebuild_indexes().await {
+            Ok(()) => Ok(JobOutcome::Success { result_url: None }),
+            Err(e) => outcome_for_error(e),
+        }
+    }
+}
+
+/// Recomputes the tenant's item availability read model
+pub struct RebuildItemAvailabilityJobHandler<I: ItemRepository, A: ItemAvailabilityRepository> {
+    availability_use_case: Arc<ItemAvailabilityUseCase<I, A>>,
+}
+
+impl<I: ItemRepository, A: ItemAvailabilityRepository> RebuildItemAvailabilityJobHandler<I, A> {
+    pub fn new(availability_use_case: Arc<ItemAvailabilityUseCase<I, A>>) -> Self {
+        Self {
+            availability_use_case,
+        }
+    }
+}
+
+#[async_trait]
+impl<I: ItemRepository, A: ItemAvailabilityRepository> JobHandler
+    for RebuildItemAvailabilityJobHandler<I, A>
+{
+    async fn handle(&self, _job: &Job, _context: &JobContext) -> Result<JobOutcome, JobError> {
+        match self.availability_use_case.rebuild().await {
+            Ok(_) => Ok(JobOutcome::Success { result_url: None }),
+            Err(e) => outcome_for_error(e),
+        }
+    }
+}
+
+/// Sends a queued print job to its printer; an unreachable printer is retried
+/// with backoff until the job runs out of attempts
+pub struct PrintLabelsJobHandler<P, L, S>
+where
+    P: PrintRepository + 'static,
+    L: LocationRepository + 'static,
+    S: JobService + 'static,
+{
+    printing_use_case: Arc<ManageLabelPrintingUseCase<P, L, S>>,
+}
+
+impl<P, L, S> PrintLabelsJobHandler<P, L, S>
+where
+    P: PrintRepository + 'static,
+    L: LocationRepository + 'static,
+    S: JobService + 'static,
+{
+    pub fn new(printing_use_case: Arc<ManageLabelPrintingUseCase<P, L, S>>) -> Self {
+        Self { printing_use_case }
+    }
+}
+
+#[async_trait]
+impl<P, L, S> JobHandler for PrintLabelsJobHandler<P, L, S>
+where
+    P: PrintRepository + 'static,
+    L: LocationRepository + 'static,
+    S: JobService + 'static,
+{
+    async fn handle(&self, job: &Job, _context: &JobContext) -> Result<JobOutcome, JobError> {
+        match self.printing_use_case.run(job).await {
+            Ok(_) => Ok(JobOutcome::Success { result_url: None }),
+            Err(e) => outcome_for_error(e),
+        }
+    }
+}
+
+/// Prunes the tenant's expired webhook deliveries and events
+pub struct WebhookRetentionJobHandler<R: WebhookRetentionRepository> {
+    retention_use_case: Arc<WebhookRetentionUseCase<R>>,
+}
+
+impl<R: WebhookRetentionRepository> WebhookRetentionJobHandler<R> {
+    pub fn new(retention_use_case: Arc<WebhookRetentionUseCase<R>>) -> Self {
+        Self { retention_use_case }
+    }
+}
+
+#[async_trait]
+impl<R: WebhookRetentionRepository> JobHandler for WebhookRetentionJobHandler<R> {
+    async fn handle(&self, job: &Job, _context: &JobContext) -> Result<JobOutcome, JobError> {
+        let payload: WebhookRetentionJobPayload =
+            match serde_json::from_value(job.payload.clone().unwrap_or_default()) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    return outcome_for_error(DomainError::ValidationError(
+                        format!("Invalid webhook retention payload: {}", e).into(),
+                    ))
+                }
+            };
+        match self.retention_use_case.run(payload.trigger).await {
+            Ok(_) => Ok(JobOutcome::Success { result_url: None }),
+            Err(e) => outcome_for_error(e),
+        }
+    }
+}
+
+/// Runs a full (unpaginated) report and stores it in blob storage as JSON,
+/// CSV or XLSX, laid out by the columns, locale and headers the job asks for
+pub struct GenerateReportJobHandler {
+    export_sources: Arc<ExportSourceRegistry>,
+    blob_storage: Arc<dyn BlobStorage>,
+    url_expiry: Duration,
+}
+
+impl GenerateReportJobHandler {
+    pub fn new(
+        export_sources: Arc<ExportSourceRegistry>,
+        blob_storage: Arc<dyn BlobStorage>,
+        url_expiry: Duration,
+    ) -> Self {
+        Self {
+            export_sources,
+            blob_storage,
+            url_expiry,
+        }
+    }
+
+    /// Every page of the report, with progress nudged after each page; `None`
+    /// if the job was cancelled part way through
+    async fn collect(
+        &self,
+        source: &dyn ExportSource,
+        params: &serde_json::Value,
+        context: &JobContext,
+    ) -> Result<Option<Vec<serde_json::Value>>, DomainError> {
+        let mut rows = Vec::new();
+        let mut cursor = None;
+        let mut pages = 0;
+        loop {
+            if context.is_cancelled() {
+                return Ok(None);
+            }
+            let page = source.fetch_page(params, cursor).await?;
+            rows.extend(page.rows);
+
+            // The total isn't known up front; creep towards 90% so the job visibly moves
+            pages += 1;
+            let _ = context.set_progress((90 - 90 / (pages + 1)).min(90)).await;
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(Some(rows)),
+            }
+        }
+    }
+
+    async fn export(&self, job: &Job, context: &JobContext) -> Result<Option<String>, DomainError> {
+        let request: ReportExportRequest =
+            serde_json::from_value(job.payload.clone().unwrap_or_default()).map_err(|e| {
+                DomainError::ValidationError(format!("Invalid report payload: {}", e).into())
+            })?;
+        request.validate()?;
+        let source = self.export_sources.get(&request.report)?;
+        let params = request.params();
+        source.validate(&params)?;
+
+        let Some(rows) = self.collect(source.as_ref(), &params, context).await? else {
+            return Ok(None);
+        };
+        let body = if request.format == ExportFormat::Json && request.columns.is_none() {
+            // Without a column selection JSON keeps the rows as the report returns them
+            serde_json::to_vec(&rows).map_err(|e| {
+                DomainError::InfrastructureError(
+                    format!("Failed to serialize report: {}", e).into(),
+                )
+            })?
+        } else {
+            render_table(&ExportTable::build(&rows, &request)?, request.format)?
+        };
+
+        let key = export_object_key(job.tenant_id, &job.job_id, &request.file_name());
+        self.blob_storage
+            .put(&key, request.format.content_type(), body)
+            .await?;
+        self.blob_storage
+            .signed_url(&key, self.url_expiry)
+            .await
+            .map(Some)
+    }
+}
+
+#[async_trait]
+impl JobHandler for GenerateReportJobHandler {
+    async fn handle(&self, job: &Job, context: &JobContext) -> Result<JobOutcome, JobError> {
+        match self.export(job, context).await {
+            Ok(Some(url)) => Ok(JobOutcome::Success {
+                result_url: Some(url),
+            }),
+            Ok(None) => Ok(JobOutcome::Cancelled),
+            Err(e) => outcome_for_error(e),
+        }
+    }
+}
+
+/// Writes a zip of everything the tenant owns to blob storage
+pub struct TenantSnapshotExportJobHandler<R, S>
+where
+    R: TenantSnapshotRepository + 'static,
+    S: JobService + 'static,
+{
+    tenant_snapshot_use_case: Arc<TenantSnapshotUseCase<R, S>>,
+}
+
+impl<R, S> TenantSnapshotExportJobHandler<R, S>
+where
+    R: TenantSnapshotRepository + 'static,
+    S: JobService + 'static,
+{
+    pub fn new(tenant_snapshot_use_case: Arc<TenantSnapshotUseCase<R, S>>) -> Self {
+        Self {
+            tenant_snapshot_use_case,
+        }
+    }
+}
+
+#[async_trait]
+impl<R, S> JobHandler for TenantSnapshotExportJobHandler<R, S>
+where
+    R: TenantSnapshotRepository + 'static,
+    S: JobService + 'static,
+{
+    async fn handle(&self, job: &Job, _context: &JobContext) -> Result<JobOutcome, JobError> {
+        match self.tenant_snapshot_use_case.run_export(job).await {
+            Ok(url) => Ok(JobOutcome::Success {
+                result_url: Some(url),
+            }),
+            Err(e) => outcome_for_error(e),
+        }
+    }
+}
+
+/// Restores an uploaded tenant snapshot
+pub struct TenantSnapshotImportJobHandler<R, S>
+where
+    R: TenantSnapshotRepository + 'static,
+    S: JobService + 'static,
+{
+    tenant_snapshot_use_case: Arc<TenantSnapshotUseCase<R, S>>,
+}
+
+impl<R, S> TenantSnapshotImportJobHandler<R, S>
+where
+    R: TenantSnapshotRepository + 'static,
+    S: JobService + 'static,
+{
+    pub fn new(tenant_snapshot_use_case: Arc<TenantSnapshotUseCase<R, S>>) -> Self {
+        Self {
+            tenant_snapshot_use_case,
+        }
+    }
+}
+
+#[async_trait]
+impl<R, S> JobHandler for TenantSnapshotImportJobHandler<R, S>
+where
+    R: TenantSnapshotRepository + 'static,
+    S: JobService + 'static,
+{
+    async fn handle(&self, job: &Job, _context: &JobContext) -> Result<JobOutcome, JobError> {
+        match self.tenant_snapshot_use_case.run_import(job).await {
+            Ok(()) => Ok(JobOutcome::Success { result_url: None }),
+            // The restore runs in one transaction, so a conflict or a full quota
+            // is as final as bad input
+            Err(e @ (DomainError::Conflict(_) | DomainError::QuotaExceeded(_))) => {
+                Ok(JobOutcome::Failed {
+                    errors: vec![JobError {
+                        row: None,
+                        message: e.to_string(),
+                    }],
+                })
+            }
+            Err(e) => outcome_for_error(e),
+        }
+    }
+}
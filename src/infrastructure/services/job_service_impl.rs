@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::Duration;
 use uuid::Uuid;
 
 use crate::domain::{
@@ -16,6 +17,22 @@ impl<T: JobRepository> JobServiceImpl<T> {
     pub fn new(job_repository: Arc<T>) -> Self {
         Self { job_repository }
     }
+
+    async fn find_tenant_job(
+        &self,
+        tenant_id: Uuid,
+        job_id: &str,
+    ) -> Result<Job, crate::shared::error::DomainError> {
+        self.job_repository
+            .find_by_job_id(job_id)
+            .await?
+            .filter(|job| job.tenant_id == tenant_id)
+            .ok_or_else(|| {
+                crate::shared::error::DomainError::NotFound(
+                    format!("Job {} not found", job_id).into(),
+                )
+            })
+    }
 }
 
 #[async_trait]
@@ -27,6 +44,9 @@ impl<T: JobRepository> JobService for JobServiceImpl<T> {
     ) -> Result<Job, crate::shared::error::DomainError> {
         // Create new job entity
         let mut job = Job::new(tenant_id, request.job_type, Some(request.payload))?;
+        if let Some(max_attempts) = request.max_attempts {
+            job.max_attempts = max_attempts.max(1);
+        }
 
         // Save to repository
         self.job_repository.save(&job).await?;
@@ -45,7 +65,7 @@ impl<T: JobRepository> JobService for JobServiceImpl<T> {
         if let Some(ref job) = job {
             if job.tenant_id != tenant_id {
                 return Err(crate::shared::error::DomainError::ValidationError(
-                    "Job not found".to_string(),
+                    "Job not found".into(),
                 ));
             }
         }
@@ -63,7 +83,7 @@ impl<T: JobRepository> JobService for JobServiceImpl<T> {
             .find_by_job_id(job_id)
             .await?
             .ok_or_else(|| {
-                crate::shared::error::DomainError::ValidationError("Job not found".to_string())
+                crate::shared::error::DomainError::ValidationError("Job not found".into())
             })?;
 
         job.update_progress(progress);
@@ -82,7 +102,7 @@ impl<T: JobRepository> JobService for JobServiceImpl<T> {
             .find_by_job_id(job_id)
             .await?
             .ok_or_else(|| {
-                crate::shared::error::DomainError::ValidationError("Job not found".to_string())
+                crate::shared::error::DomainError::ValidationError("Job not found".into())
             })?;
 
         job.complete_success(result_url);
@@ -101,7 +121,7 @@ impl<T: JobRepository> JobService for JobServiceImpl<T> {
             .find_by_job_id(job_id)
             .await?
             .ok_or_else(|| {
-                crate::shared::error::DomainError::ValidationError("Job not found".to_string())
+                crate::shared::error::DomainError::ValidationError("Job not found".into())
             })?;
 
         job.complete_failure(errors);
@@ -121,7 +141,7 @@ impl<T: JobRepository> JobService for JobServiceImpl<T> {
             .find_by_job_id(job_id)
             .await?
             .ok_or_else(|| {
-                crate::shared::error::DomainError::ValidationError("Job not found".to_string())
+                crate::shared::error::DomainError::ValidationError("Job not found".into())
             })?;
 
         job.complete_partial_success(result_url, errors);
@@ -139,7 +159,7 @@ impl<T: JobRepository> JobService for JobServiceImpl<T> {
             .find_by_job_id(job_id)
             .await?
             .ok_or_else(|| {
-                crate::shared::error::DomainError::ValidationError("Job not found".to_string())
+                crate::shared::error::DomainError::ValidationError("Job not found".into())
             })?;
 
         job.start();
@@ -158,4 +178,97 @@ impl<T: JobRepository> JobService for JobServiceImpl<T> {
             .find_by_status(tenant_id, status, limit)
             .await
     }
+
+    async fn claim_due_jobs(
+        &self,
+        job_types: &[String],
+        limit: i64,
+        lease_seconds: i64,
+    ) -> Result<Vec<Job>, crate::shared::error::DomainError> {
+        if job_types.is_empty() || limit <= 0 {
+            return Ok(Vec::new());
+        }
+        self.job_repository
+            .claim_due_jobs(job_types, limit, lease_seconds)
+            .await
+    }
+
+    async fn fail_abandoned_jobs(
+        &self,
+        lease_seconds: i64,
+    ) -> Result<u64, crate::shared::error::DomainError> {
+        self.job_repository.fail_abandoned_jobs(lease_seconds).await
+    }
+
+    async fn heartbeat(&self, job_id: &str) -> Result<bool, crate::shared::error::DomainError> {
+        let job = self
+            .job_repository
+            .find_by_job_id(job_id)
+            .await?
+            .ok_or_else(|| {
+                crate::shared::error::DomainError::ValidationError("Job not found".into())
+            })?;
+
+        self.job_repository.heartbeat(job.id).await
+    }
+
+    async fn fail_job_attempt(
+        &self,
+        job_id: &str,
+        errors: Vec<JobError>,
+        retry_delay: Duration,
+    ) -> Result<(), crate::shared::error::DomainError> {
+        let mut job = self
+            .job_repository
+            .find_by_job_id(job_id)
+            .await?
+            .ok_or_else(|| {
+                crate::shared::error::DomainError::ValidationError("Job not found".into())
+            })?;
+
+        job.fail_attempt(errors, retry_delay);
+        self.job_repository.update(&job).await?;
+
+        Ok(())
+    }
+
+    async fn cancel_job(
+        &self,
+        tenant_id: Uuid,
+        job_id: &str,
+    ) -> Result<Job, crate::shared::error::DomainError> {
+        let mut job = self.find_tenant_job(tenant_id, job_id).await?;
+        job.request_cancel()?;
+        self.job_repository.update(&job).await?;
+        Ok(job)
+    }
+
+    async fn retry_job(
+        &self,
+        tenant_id: Uuid,
+        job_id: &str,
+    ) -> Result<Job, crate::shared::error::DomainError> {
+        let mut job = self.find_tenant_job(tenant_id, job_id).await?;
+        job.retry()?;
+        self.job_repository.update(&job).await?;
+        Ok(job)
+    }
+
+    async fn mark_job_cancelled(
+        &self,
+        job_id: &str,
+    ) -> Result<(), crate::shared::error::DomainError> {
+        let mut job = self
+            .job_repository
+            .find_by_job_id(job_id)
+            .await?
+            .ok_or_else(|| {
+                crate::shared::error::DomainError::ValidationError("Job not found".into())
+            })?;
+
+        job.mark_cancelled();
+        self.job_repository.update(&job).await?;
+
+        Ok(())
+    }
 }
@@ -1,128 +1,374 @@
-use crate::domain::entities::job::{Job, JobError};
-use crate::domain::services::{job_processor::JobProcessor, job_service::JobService};
+use crate::domain::entities::job::{Job, JobCancellation, JobError};
+use crate::domain::services::job_service::JobService;
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::with_tenant;
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::env;
 use std::sync::Arc;
-use tokio::sync::mpsc;
-use tracing::{error, info};
-use uuid;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
 
-pub struct WorkerManager<S: JobService, P: JobProcessor> {
+/// How a handler finished a job. Returning `Err(JobError)` from a handler instead
+/// means the attempt failed and the job should be retried if it has attempts left.
+#[derive(Debug)]
+pub enum JobOutcome {
+    Success {
+        result_url: Option<String>,
+    },
+    /// Some of the work failed (e.g. rejected import rows); not retried
+    PartialSuccess {
+        result_url: Option<String>,
+        errors: Vec<JobError>,
+    },
+    /// The job can't succeed no matter how often it runs; not retried
+    Failed {
+        errors: Vec<JobError>,
+    },
+    /// The handler stopped early because the job was cancelled
+    Cancelled,
+}
+
+/// Runs the jobs of one `job_type`. Handlers run inside the job's tenant scope.
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    async fn handle(&self, job: &Job, context: &JobContext) -> Result<JobOutcome, JobError>;
+}
+
+/// Handed to a running handler so it can report progress and notice cancellation
+pub struct JobContext {
+    job_id: String,
+    job_service: Arc<dyn JobService>,
+    cancellation: JobCancellation,
+}
+
+impl JobContext {
+    /// Raised once the job has been cancelled; check it between batches and
+    /// return `JobOutcome::Cancelled`
+    pub fn cancellation(&self) -> &JobCancellation {
+        &self.cancellation
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Record progress (0-100); also counts as a heartbeat
+    pub async fn set_progress(&self, progress: i32) -> Result<(), DomainError> {
+        self.job_service
+            .update_job_progress(&self.job_id, progress)
+            .await
+    }
+}
+
+/// Maps each `job_type` to the handler that runs it. Only registered types are
+/// claimed, so jobs of unknown types stay queued rather than failing.
+#[derive(Default)]
+pub struct JobHandlerRegistry {
+    handlers: HashMap<String, Arc<dyn JobHandler>>,
+}
+
+impl JobHandlerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(mut self, job_type: &str, handler: Arc<dyn JobHandler>) -> Self {
+        self.handlers.insert(job_type.to_string(), handler);
+        self
+    }
+
+    pub fn get(&self, job_type: &str) -> Option<Arc<dyn JobHandler>> {
+        self.handlers.get(job_type).cloned()
+    }
+
+    pub fn job_types(&self) -> Vec<String> {
+        let mut job_types: Vec<String> = self.handlers.keys().cloned().collect();
+        job_types.sort();
+        job_types
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JobWorkerConfig {
+    /// Jobs run at the same time by this instance
+    pub concurrency: usize,
+    pub poll_interval: Duration,
+    /// A running job whose heartbeat is older than this is considered abandoned
+    /// and handed to another worker
+    pub lease: Duration,
+    /// Delay before the first retry; doubles with every further attempt
+    pub retry_base_delay: Duration,
+}
+
+impl Default for JobWorkerConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            poll_interval: Duration::from_secs(5),
+            lease: Duration::from_secs(120),
+            retry_base_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl JobWorkerConfig {
+    /// Read `JOB_WORKER_CONCURRENCY`, `JOB_WORKER_POLL_INTERVAL_SECS`,
+    /// `JOB_WORKER_LEASE_SECS` and `JOB_WORKER_RETRY_DELAY_SECS`, falling back to
+    /// the defaults for anything unset or invalid
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            concurrency: env_var("JOB_WORKER_CONCURRENCY").unwrap_or(defaults.concurrency),
+            poll_interval: env_var("JOB_WORKER_POLL_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.poll_interval),
+            lease: env_var("JOB_WORKER_LEASE_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.lease),
+            retry_base_delay: env_var("JOB_WORKER_RETRY_DELAY_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.retry_base_delay),
+        }
+    }
+
+    /// Backoff before retrying a job whose attempt number `attempt` just failed
+    pub fn retry_delay(&self, attempt: i32) -> Duration {
+        let exponent = attempt.saturating_sub(1).clamp(0, 16) as u32;
+        self.retry_base_delay
+            .saturating_mul(2u32.pow(exponent))
+            .min(Duration::from_secs(3600))
+    }
+}
+
+/// Upper bound on the time between heartbeats, and so on how long a running job
+/// takes to notice it has been cancelled
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+fn env_var<T: std::str::FromStr + PartialOrd + Default>(name: &str) -> Option<T> {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|value| *value > T::default())
+}
+
+/// Polls the jobs table for due jobs of the registered types and runs them,
+/// at most `concurrency` at a time. Running jobs send a heartbeat every few
+/// seconds, which also picks up cancellation requests; jobs whose worker died
+/// are reclaimed once the lease lapses.
+/// Claims use `FOR UPDATE SKIP LOCKED`, so several instances can share a database.
+pub struct JobWorker<S: JobService> {
     job_service: Arc<S>,
-    job_processor: Arc<P>,
-    job_queue: mpsc::Receiver<String>, // Receives job IDs
+    registry: Arc<JobHandlerRegistry>,
+    config: JobWorkerConfig,
+    slots: Arc<Semaphore>,
 }
 
-impl<S: JobService, P: JobProcessor> WorkerManager<S, P> {
-    pub fn new(
-        job_service: Arc<S>,
-        job_processor: Arc<P>,
-        job_queue: mpsc::Receiver<String>,
-    ) -> Self {
+impl<S: JobService + 'static> JobWorker<S> {
+    pub fn new(job_service: Arc<S>, registry: JobHandlerRegistry, config: JobWorkerConfig) -> Self {
+        let slots = Arc::new(Semaphore::new(config.concurrency.max(1)));
         Self {
             job_service,
-            job_processor,
-            job_queue,
+            registry: Arc::new(registry),
+            config,
+            slots,
         }
     }
 
-    pub async fn run(mut self) {
-        info!("Starting job worker manager");
-
-        while let Some(job_id) = self.job_queue.recv().await {
-            info!("Processing job: {}", job_id);
+    /// Poll until `shutdown` is cancelled, then wait for the running jobs to finish.
+    /// Jobs still running when the process exits are reclaimed once their lease lapses.
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            "Starting job worker for {:?} (concurrency {}, polling every {:?})",
+            self.registry.job_types(),
+            self.config.concurrency,
+            self.config.poll_interval
+        );
 
-            // For now, we'll process jobs one by one
-            // In a real implementation, you'd have multiple workers
-            if let Err(e) = self.process_single_job(&job_id).await {
-                error!("Failed to process job {}: {:?}", job_id, e);
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            if let Err(e) = self.poll_once().await {
+                error!("Failed to claim jobs: {}", e);
             }
         }
 
-        info!("Job worker manager stopped");
+        let running = self.config.concurrency.max(1) - self.slots.available_permits();
+        if running > 0 {
+            info!("Waiting for {} running jobs to finish", running);
+        }
+        let _ = self
+            .slots
+            .acquire_many(self.config.concurrency.max(1) as u32)
+            .await;
+        info!("Job worker stopped");
     }
 
-    async fn process_single_job(
-        &self,
-        job_id: &str,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // For simplicity, we'll process jobs for all tenants
-        // In a real implementation, you'd have tenant-specific workers
-        let tenant_id = uuid::Uuid::new_v4(); // This should be passed or retrieved properly
-        let queued_jobs = self
+    /// Claim as many jobs as there are free slots and start them. Returns how
+    /// many jobs were claimed.
+    pub async fn poll_once(&self) -> Result<usize, DomainError> {
+        let lease_seconds = self.config.lease.as_secs() as i64;
+        let abandoned = self.job_service.fail_abandoned_jobs(lease_seconds).await?;
+        if abandoned > 0 {
+            warn!("Failed {} jobs abandoned on their last attempt", abandoned);
+        }
+
+        let free = self.slots.available_permits();
+        if free == 0 {
+            return Ok(0);
+        }
+        let jobs = self
             .job_service
-            .find_by_status(tenant_id, "QUEUED", 10)
+            .claim_due_jobs(&self.registry.job_types(), free as i64, lease_seconds)
             .await?;
+        let claimed = jobs.len();
 
-        for job in queued_jobs {
-            if job.job_id == job_id {
-                // Start processing the job
-                self.job_service.start_job_processing(&job.job_id).await?;
-
-                // Process the job
-                match self.job_processor.process_job(&job).await {
-                    Ok(_) => {
-                        // Job completed successfully
-                        self.job_service
-                            .complete_job_success(&job.job_id, None)
-                            .await?;
-                        info!("Job {} completed successfully", job_id);
-                    }
-                    Err(e) => {
-                        // Job failed
-                        let errors = vec![e];
-                        self.job_service
-                            .complete_job_failure(&job.job_id, errors)
-                            .await?;
-                        error!("Job {} failed", job_id);
-                    }
-                }
-                break;
-            }
+        for job in jobs {
+            let permit = Arc::clone(&self.slots)
+                .acquire_owned()
+                .await
+                .expect("job worker semaphore is never closed");
+            let job_service = Arc::clone(&self.job_service);
+            let registry = Arc::clone(&self.registry);
+            let config = self.config.clone();
+            tokio::spawn(async move {
+                let _permit = permit;
+                run_job(job_service, registry, config, job).await;
+            });
         }
 
-        Ok(())
+        Ok(claimed)
     }
 }
 
-// Basic job processor implementation
-pub struct BasicJobProcessor;
+async fn run_job<S: JobService + 'static>(
+    job_service: Arc<S>,
+    registry: Arc<JobHandlerRegistry>,
+    config: JobWorkerConfig,
+    job: Job,
+) {
+    let job_id = job.job_id.clone();
+    let Some(handler) = registry.get(&job.job_type) else {
+        // Only registered types are claimed, so this means the registry changed
+        error!("No handler for job {} of type {}", job_id, job.job_type);
+        return;
+    };
+    info!(
+        "Running job {} ({}, attempt {}/{})",
+        job_id, job.job_type, job.attempts, job.max_attempts
+    );
 
-impl BasicJobProcessor {
-    pub fn new() -> Self {
-        Self
+    let cancellation = JobCancellation::new();
+    let heartbeat = {
+        let job_service = Arc::clone(&job_service);
+        let job_id = job_id.clone();
+        let cancellation = cancellation.clone();
+        // Often enough to keep the lease and to react to cancellation promptly
+        let every = (config.lease / 3).clamp(Duration::from_secs(1), HEARTBEAT_INTERVAL);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(every);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                match job_service.heartbeat(&job_id).await {
+                    Ok(true) => cancellation.cancel(),
+                    Ok(false) => {}
+                    Err(e) => warn!("Failed to record heartbeat for job {}: {}", job_id, e),
+                }
+            }
+        })
+    };
+
+    // Run the handler on its own task so a panic fails the attempt instead of
+    // leaving the job running until its lease lapses
+    let attempt = job.attempts;
+    let handler_task = {
+        let context = JobContext {
+            job_id: job_id.clone(),
+            job_service: Arc::clone(&job_service) as Arc<dyn JobService>,
+            cancellation,
+        };
+        tokio::spawn(
+            async move { with_tenant(job.tenant_id, handler.handle(&job, &context)).await },
+        )
+    };
+    let result = handler_task.await.unwrap_or_else(|e| {
+        Err(JobError {
+            row: None,
+            message: format!("Job handler panicked: {}", e),
+        })
+    });
+    heartbeat.abort();
+
+    let recorded = match result {
+        Ok(JobOutcome::Success { result_url }) => {
+            info!("Job {} completed successfully", job_id);
+            job_service.complete_job_success(&job_id, result_url).await
+        }
+        Ok(JobOutcome::PartialSuccess { result_url, errors }) => {
+            info!("Job {} completed with {} errors", job_id, errors.len());
+            job_service
+                .complete_job_partial_success(&job_id, result_url, errors)
+                .await
+        }
+        Ok(JobOutcome::Failed { errors }) => {
+            error!("Job {} failed", job_id);
+            job_service.complete_job_failure(&job_id, errors).await
+        }
+        Ok(JobOutcome::Cancelled) => {
+            info!("Job {} cancelled", job_id);
+            job_service.mark_job_cancelled(&job_id).await
+        }
+        Err(e) => {
+            warn!("Job {} attempt {} failed: {}", job_id, attempt, e.message);
+            let delay = chrono::Duration::from_std(config.retry_delay(attempt))
+                .unwrap_or(chrono::Duration::minutes(1));
+            job_service.fail_job_attempt(&job_id, vec![e], delay).await
+        }
+    };
+    if let Err(e) = recorded {
+        error!("Failed to record result of job {}: {}", job_id, e);
     }
 }
 
-#[async_trait]
-impl JobProcessor for BasicJobProcessor {
-    async fn process_job(&self, job: &Job) -> Result<(), JobError> {
-        info!("Processing job: {} of type: {}", job.job_id, job.job_type);
-
-        // Simulate job processing based on type
-        match job.job_type.as_str() {
-            "import_items" => {
-                // Simulate importing items
-                tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-                info!("Successfully imported items for job {}", job.job_id);
-            }
-            "export_stock" => {
-                // Simulate exporting stock
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-                info!("Successfully exported stock for job {}", job.job_id);
-            }
-            "process_data" => {
-                // Simulate processing data
-                tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                info!("Successfully processed data for job {}", job.job_id);
-            }
-            _ => {
-                return Err(JobError {
-                    row: None,
-                    message: format!("Unknown job type: {}", job.job_type),
-                });
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoopHandler;
+
+    #[async_trait]
+    impl JobHandler for NoopHandler {
+        async fn handle(&self, _job: &Job, _context: &JobContext) -> Result<JobOutcome, JobError> {
+            Ok(JobOutcome::Success { result_url: None })
         }
+    }
+
+    #[test]
+    fn test_registry_lists_registered_job_types() {
+        let registry = JobHandlerRegistry::new()
+            .register("stock_csv_export", Arc::new(NoopHandler))
+            .register("import_items", Arc::new(NoopHandler));
+
+        assert_eq!(
+            registry.job_types(),
+            vec!["import_items".to_string(), "stock_csv_export".to_string()]
+        );
+        assert!(registry.get("import_items").is_some());
+        assert!(registry.get("unknown").is_none());
+    }
 
-        Ok(())
+    #[test]
+    fn test_retry_delay_backs_off_exponentially() {
+        let config = JobWorkerConfig::default();
+        assert_eq!(config.retry_delay(1), Duration::from_secs(30));
+        assert_eq!(config.retry_delay(2), Duration::from_secs(60));
+        assert_eq!(config.retry_delay(3), Duration::from_secs(120));
+        assert_eq!(config.retry_delay(20), Duration::from_secs(3600));
     }
 }
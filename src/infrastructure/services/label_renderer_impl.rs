@@ -0,0 +1,227 @@
+use barcoders::sym::code128::Code128;
+
+use crate::domain::services::label_renderer::{
+    LabelFormat, LabelRenderer, RenderedLabels, ShippingLabel,
+};
+use crate::infrastructure::services::pdf_writer::{PdfDocument, PdfPage};
+use crate::shared::error::DomainError;
+
+/// Code128 character-set C and FNC1 prefixes understood by `barcoders`;
+/// FNC1 straight after the start code is what makes a symbol GS1-128
+const CODE128_SET_C: char = '\u{0106}';
+const CODE128_FNC1: char = '\u{0179}';
+
+/// 4x6in label, in points for PDF and 203dpi dots for ZPL
+const PDF_WIDTH: f64 = 288.0;
+const PDF_HEIGHT: f64 = 432.0;
+const ZPL_WIDTH: u32 = 812;
+const ZPL_HEIGHT: u32 = 1218;
+
+pub struct LabelRendererImpl;
+
+impl LabelRendererImpl {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn details(label: &ShippingLabel) -> Vec<String> {
+        let mut details = vec![format!("Shipment: {}", label.shipment_number)];
+        if let Some(reference) = &label.reference {
+            details.push(format!("Reference: {}", reference));
+        }
+        if let Some(carrier) = &label.carrier {
+            details.push(format!("Carrier: {}", carrier));
+        }
+        if let Some(tracking_number) = &label.tracking_number {
+            details.push(format!("Tracking: {}", tracking_number));
+        }
+        details.push(format!(
+            "Carton {} of {}",
+            label.carton_number, label.carton_count
+        ));
+        if let Some(weight_kg) = label.weight_kg {
+            details.push(format!("Weight: {:.2} kg", weight_kg));
+        }
+        details
+    }
+
+    fn to_zpl(&self, labels: &[ShippingLabel]) -> Vec<u8> {
+        let mut zpl = String::new();
+        for label in labels {
+            zpl.push_str(&format!("^XA\n^PW{}\n^LL{}\n", ZPL_WIDTH, ZPL_HEIGHT));
+            for (x, heading, lines) in [(30, "FROM", &label.ship_from), (420, "TO", &label.ship_to)]
+            {
+                zpl.push_str(&format!("^FO{},30^A0N,26,26^FD{}^FS\n", x, heading));
+                for (i, line) in lines.iter().enumerate() {
+                    zpl.push_str(&format!(
+                        "^FO{},{}^A0N,24,24^FD{}^FS\n",
+                        x,
+                        66 + i * 30,
+                        zpl_field(line)
+                    ));
+                }
+            }
+            zpl.push_str("^FO30,250^GB752,3,3^FS\n");
+            for (i, line) in Self::details(label).iter().enumerate() {
+                zpl.push_str(&format!(
+                    "^FO30,{}^A0N,30,30^FD{}^FS\n",
+                    280 + i * 42,
+                    zpl_field(line)
+                ));
+            }
+            zpl.push_str("^FO30,600^GB752,3,3^FS\n");
+            zpl.push_str("^FO30,630^A0N,30,30^FDSSCC^FS\n");
+            // Mode D prints GS1-128, taking the application identifier in parentheses
+            zpl.push_str(&format!(
+                "^FO60,680^BY3^BCN,300,N,N,N,D^FD(00){}^FS\n",
+                label.sscc
+            ));
+            zpl.push_str(&format!(
+                "^FO60,1010^A0N,44,44^FD(00) {}^FS\n^XZ\n",
+                label.sscc
+            ));
+        }
+        zpl.into_bytes()
+    }
+
+    fn to_pdf(&self, labels: &[ShippingLabel]) -> Result<Vec<u8>, DomainError> {
+        let mut document = PdfDocument::new();
+        for label in labels {
+            let mut page = PdfPage::new(PDF_WIDTH, PDF_HEIGHT);
+            for (x, heading, lines) in [
+                (18.0, "FROM", &label.ship_from),
+                (150.0, "TO", &label.ship_to),
+            ] {
+                page.bold_text(x, 410.0, 8.0, heading);
+                for (i, line) in lines.iter().enumerate() {
+                    page.text(x, 397.0 - i as f64 * 11.0, 8.0, line);
+                }
+            }
+            page.rect(18.0, 330.0, 252.0, 0.75);
+            for (i, line) in Self::details(label).iter().enumerate() {
+                page.text(18.0, 312.0 - i as f64 * 14.0, 10.0, line);
+            }
+            page.rect(18.0, 215.0, 252.0, 0.75);
+
+            page.bold_text(18.0, 198.0, 10.0, "SSCC");
+            let bars = gs1_128_bars(&label.sscc)?;
+            let module = 1.5;
+            let left = (PDF_WIDTH - bars.len() as f64 * module) / 2.0;
+            // One rectangle per run of dark modules
+            let mut x = 0;
+            while x < bars.len() {
+                if bars[x] == 0 {
+                    x += 1;
+                    continue;
+                }
+                let start = x;
+                while x < bars.len() && bars[x] == 1 {
+                    x += 1;
+                }
+                page.rect(
+                    left + start as f64 * module,
+                    70.0,
+                    (x - start) as f64 * module,
+                    110.0,
+                );
+            }
+            page.bold_text(left, 50.0, 13.0, &format!("(00) {}", label.sscc));
+            document.add_page(page);
+        }
+        Ok(document.to_bytes())
+    }
+}
+
+impl Default for LabelRendererImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LabelRenderer for LabelRendererImpl {
+    fn render(
+        &self,
+        labels: &[ShippingLabel],
+        format: LabelFormat,
+    ) -> Result<RenderedLabels, DomainError> {
+        if labels.is_empty() {
+            return Err(DomainError::ValidationError(
+                "There are no labels to render".into(),
+            ));
+        }
+
+        let bytes = match format {
+            LabelFormat::Zpl => self.to_zpl(labels),
+            LabelFormat::Pdf => self.to_pdf(labels)?,
+        };
+
+        Ok(RenderedLabels {
+            content_type: format.content_type(),
+            bytes,
+        })
+    }
+}
+
+/// Bar modules for the SSCC element string `(00)` + 18 digits
+fn gs1_128_bars(sscc: &str) -> Result<Vec<u8>, DomainError> {
+    Code128::new(format!("{}{}00{}", CODE128_SET_C, CODE128_FNC1, sscc))
+        .map(|code| code.encode())
+        .map_err(|e| DomainError::ValidationError(format!("Invalid SSCC {}: {}", sscc, e).into()))
+}
+
+/// ZPL treats `^` and `~` as command prefixes, and the default code page is ASCII
+fn zpl_field(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '^' | '~' => ' ',
+            c if c.is_ascii() && !c.is_ascii_control() => c,
+            _ => '?',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(carton_number: usize) -> ShippingLabel {
+        ShippingLabel {
+            sscc: "106141412345678908".to_string(),
+            ship_from: vec!["Main Warehouse".to_string()],
+            ship_to: vec!["Downtown Store".to_string(), "1 ^Main~ St".to_string()],
+            carrier: Some("UPS".to_string()),
+            tracking_number: None,
+            shipment_number: "SH-1".to_string(),
+            reference: Some("TR-1".to_string()),
+            carton_number,
+            carton_count: 2,
+            weight_kg: Some(4.5),
+        }
+    }
+
+    #[test]
+    fn test_zpl_has_one_gs1_128_block_per_label() {
+        let rendered = LabelRendererImpl::new()
+            .render(&[label(1), label(2)], LabelFormat::Zpl)
+            .unwrap();
+        let zpl = String::from_utf8(rendered.bytes).unwrap();
+
+        assert_eq!(zpl.matches("^XA").count(), 2);
+        assert!(zpl.contains("^BCN,300,N,N,N,D^FD(00)106141412345678908^FS"));
+        assert!(zpl.contains("^FD1  Main  St^FS"));
+        assert!(zpl.contains("^FDCarton 2 of 2^FS"));
+    }
+
+    #[test]
+    fn test_pdf_draws_the_gs1_128_symbol() {
+        let rendered = LabelRendererImpl::new()
+            .render(&[label(1)], LabelFormat::Pdf)
+            .unwrap();
+        assert_eq!(rendered.content_type, "application/pdf");
+        assert!(rendered.bytes.starts_with(b"%PDF"));
+
+        // Start C, FNC1, ten digit pairs, check character, then the stop pattern
+        assert_eq!(gs1_128_bars("106141412345678908").unwrap().len(), 156);
+    }
+}
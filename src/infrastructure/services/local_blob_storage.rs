@@ -0,0 +1,219 @@
+use crate::domain::services::blob_storage::{validate_key, BlobStorage};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Stores objects as files under a root directory; suited to development and
+/// single-node deployments. Signed URLs point at this API's `/blobs/{key}` route
+/// and carry an HMAC over the key and expiry made with `url_secret`.
+pub struct LocalBlobStorage {
+    root: PathBuf,
+    url_secret: Vec<u8>,
+}
+
+impl LocalBlobStorage {
+    pub fn new(root: impl Into<PathBuf>, url_secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            root: root.into(),
+            url_secret: url_secret.into(),
+        }
+    }
+
+    fn url_mac(&self, key: &str, expires: i64) -> Hmac<Sha256> {
+        let mut mac = <Hmac<Sha256> as KeyInit>::new_from_slice(&self.url_secret)
+            .expect("HMAC accepts keys of any length");
+        mac.update(format!("{}\n{}", key, expires).as_bytes());
+        mac
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf, DomainError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+
+    /// Path for `key` with its parent directories created
+    async fn writable_path(&self, key: &str) -> Result<PathBuf, DomainError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                DomainError::InfrastructureError(
+                    format!("Failed to create directory: {}", e).into(),
+                )
+            })?;
+        }
+        Ok(path)
+    }
+}
+
+#[async_trait]
+impl BlobStorage for LocalBlobStorage {
+    async fn put(&self, key: &str, _content_type: &str, data: Vec<u8>) -> Result<(), DomainError> {
+        let path = self.writable_path(key).await?;
+        tokio::fs::write(&path, data).await.map_err(|e| {
+            DomainError::InfrastructureError(format!("Failed to write {}: {}", key, e).into())
+        })
+    }
+
+    async fn put_file(
+        &self,
+        key: &str,
+        _content_type: &str,
+        source: &Path,
+    ) -> Result<(), DomainError> {
+        let path = self.writable_path(key).await?;
+        tokio::fs::copy(source, &path)
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                DomainError::InfrastructureError(format!("Failed to write {}: {}", key, e).into())
+            })
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, DomainError> {
+        let path = self.path_for(key)?;
+        tokio::fs::read(&path).await.map_err(|e| match e.kind() {
+            ErrorKind::NotFound => {
+                DomainError::NotFound(format!("Object {} not found", key).into())
+            }
+            _ => DomainError::InfrastructureError(format!("Failed to read {}: {}", key, e).into()),
+        })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), DomainError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(DomainError::InfrastructureError(
+                format!("Failed to delete {}: {}", key, e).into(),
+            )),
+        }
+    }
+
+    async fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, DomainError> {
+        validate_key(key)?;
+        let expires = Utc::now().timestamp() + expires_in.as_secs() as i64;
+        let signature = hex::encode(self.url_mac(key, expires).finalize().into_bytes());
+        Ok(format!(
+            "/blobs/{}?expires={}&signature={}",
+            key, expires, signature
+        ))
+    }
+
+    fn verify_signed_url(
+        &self,
+        key: &str,
+        expires: i64,
+        signature: &str,
+    ) -> Result<(), DomainError> {
+        validate_key(key)?;
+        let signature = hex::decode(signature)
+            .map_err(|_| DomainError::ValidationError("Malformed signature".into()))?;
+        self.url_mac(key, expires)
+            .verify_slice(&signature)
+            .map_err(|_| DomainError::ValidationError("Invalid signature".into()))?;
+        if expires < Utc::now().timestamp() {
+            return Err(DomainError::ValidationError(
+                "Download link has expired".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_round_trip_and_rejects_traversal() {
+        let root = std::env::temp_dir().join(format!("twh-blobs-{}", uuid::Uuid::new_v4()));
+        let storage = LocalBlobStorage::new(&root, "secret");
+
+        storage
+            .put("items/1/spec.pdf", "application/pdf", b"%PDF".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(storage.get("items/1/spec.pdf").await.unwrap(), b"%PDF");
+
+        storage.delete("items/1/spec.pdf").await.unwrap();
+        storage.delete("items/1/spec.pdf").await.unwrap();
+        assert!(matches!(
+            storage.get("items/1/spec.pdf").await,
+            Err(DomainError::NotFound(_))
+        ));
+
+        assert!(storage
+            .put("../escape", "text/plain", Vec::new())
+            .await
+            .is_err());
+        assert!(storage.get("/etc/passwd").await.is_err());
+
+        let _ = tokio::fs::remove_dir_all(&root).await;
+    }
+
+    #[tokio::test]
+    async fn test_put_file_copies_the_upload() {
+        let root = std::env::temp_dir().join(format!("twh-blobs-{}", uuid::Uuid::new_v4()));
+        let storage = LocalBlobStorage::new(&root, "secret");
+        let source = root.with_extension("csv");
+        tokio::fs::write(&source, b"sku,name\n").await.unwrap();
+
+        storage
+            .put_file("imports/items.csv", "text/csv", &source)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.get("imports/items.csv").await.unwrap(),
+            b"sku,name\n"
+        );
+
+        let _ = tokio::fs::remove_file(&source).await;
+        let _ = tokio::fs::remove_dir_all(&root).await;
+    }
+
+    #[tokio::test]
+    async fn test_signed_urls_verify_until_expiry() {
+        let storage = LocalBlobStorage::new(std::env::temp_dir(), "secret");
+        let url = storage
+            .signed_url("exports/stock.csv", Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        let (path, query) = url.split_once('?').unwrap();
+        assert_eq!(path, "/blobs/exports/stock.csv");
+        let params: std::collections::HashMap<_, _> = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .collect();
+        let expires: i64 = params["expires"].parse().unwrap();
+        let signature = params["signature"];
+
+        assert!(storage
+            .verify_signed_url("exports/stock.csv", expires, signature)
+            .is_ok());
+        assert!(storage
+            .verify_signed_url("exports/other.csv", expires, signature)
+            .is_err());
+        assert!(storage
+            .verify_signed_url("exports/stock.csv", expires + 1, signature)
+            .is_err());
+
+        let expired = Utc::now().timestamp() - 1;
+        let signature = hex::encode(
+            storage
+                .url_mac("exports/stock.csv", expired)
+                .finalize()
+                .into_bytes(),
+        );
+        assert!(storage
+            .verify_signed_url("exports/stock.csv", expired, &signature)
+            .is_err());
+    }
+}
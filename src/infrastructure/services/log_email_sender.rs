@@ -0,0 +1,22 @@
+use crate::domain::services::email_sender::{EmailMessage, EmailSender};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use tracing::info;
+
+/// Writes emails to the log instead of sending them, for development setups without
+/// an SMTP server. Messages can carry credentials such as reset links, so don't use
+/// this where logs are shared.
+pub struct LogEmailSender;
+
+#[async_trait]
+impl EmailSender for LogEmailSender {
+    async fn send(&self, message: &EmailMessage) -> Result<(), DomainError> {
+        info!(
+            to = %message.to,
+            subject = %message.subject,
+            body = %message.body,
+            "Email not sent: SMTP is not configured"
+        );
+        Ok(())
+    }
+}
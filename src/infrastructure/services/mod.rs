@@ -1,3 +1,27 @@
+pub mod barcode_service_impl;
+pub mod document_renderer_impl;
+pub mod exchange_rate_worker;
+pub mod export_sources;
+pub mod http_address_validator;
+pub mod http_exchange_rate_provider;
+pub mod integration_sync_worker;
+pub mod job_handlers;
 pub mod job_service_impl;
 pub mod job_worker;
+pub mod label_renderer_impl;
+pub mod local_blob_storage;
+pub mod log_email_sender;
+pub mod offline_address_validator;
+pub mod pdf_writer;
+pub mod postgres_quota_service;
 pub mod report_service_impl;
+pub mod s3_blob_storage;
+pub mod search_index_worker;
+pub mod shopify_connector;
+pub mod smtp_email_sender;
+pub mod stock_snapshot_worker;
+pub mod table_export_writer;
+pub mod tcp_label_printer;
+pub mod webhook_retention_worker;
+pub mod webhook_worker;
+pub mod x12_translator;
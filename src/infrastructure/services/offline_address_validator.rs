@@ -0,0 +1,17 @@
+use crate::domain::entities::address::AddressValidation;
+use crate::domain::entities::location::LocationAddress;
+use crate::domain::services::address_validator::AddressValidator;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+
+/// Validates addresses with built-in heuristics only; used when no external
+/// provider is configured, and as the fallback when the provider is down
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OfflineAddressValidator;
+
+#[async_trait]
+impl AddressValidator for OfflineAddressValidator {
+    async fn validate(&self, address: &LocationAddress) -> Result<AddressValidation, DomainError> {
+        Ok(AddressValidation::offline(address))
+    }
+}
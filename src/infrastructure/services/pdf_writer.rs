@@ -0,0 +1,297 @@
+//! Just enough PDF to lay out text, filled boxes and RGB images in the standard
+//! Helvetica fonts, which every viewer ships, so documents need no embedded fonts.
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::Write;
+
+/// One page; coordinates are points from the bottom-left corner
+pub struct PdfPage {
+    width: f64,
+    height: f64,
+    content: String,
+}
+
+impl PdfPage {
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            width,
+            height,
+            content: String::new(),
+        }
+    }
+
+    pub fn text(&mut self, x: f64, y: f64, size: f64, text: &str) {
+        self.write_text("F1", x, y, size, text);
+    }
+
+    pub fn bold_text(&mut self, x: f64, y: f64, size: f64, text: &str) {
+        self.write_text("F2", x, y, size, text);
+    }
+
+    /// Text ending at `right` rather than starting at it
+    pub fn text_right(&mut self, right: f64, y: f64, size: f64, text: &str) {
+        self.text(right - text_width(text, size), y, size, text);
+    }
+
+    pub fn bold_text_right(&mut self, right: f64, y: f64, size: f64, text: &str) {
+        self.bold_text(right - text_width(text, size), y, size, text);
+    }
+
+    /// A filled black rectangle; thin ones serve as rules
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        self.content.push_str(&format!(
+            "{:.2} {:.2} {:.2} {:.2} re f\n",
+            x, y, width, height
+        ));
+    }
+
+    /// A filled rectangle in a shade of grey, 0 black to 1 white
+    pub fn shade(&mut self, x: f64, y: f64, width: f64, height: f64, gray: f64) {
+        self.content.push_str(&format!(
+            "q {:.2} g {:.2} {:.2} {:.2} {:.2} re f Q\n",
+            gray, x, y, width, height
+        ));
+    }
+
+    /// Draw an image added to the document, scaled into the given box
+    pub fn image(&mut self, image: ImageId, x: f64, y: f64, width: f64, height: f64) {
+        self.content.push_str(&format!(
+            "q {:.2} 0 0 {:.2} {:.2} {:.2} cm /Im{} Do Q\n",
+            width, height, x, y, image.0
+        ));
+    }
+
+    fn write_text(&mut self, font: &str, x: f64, y: f64, size: f64, text: &str) {
+        self.content.push_str(&format!(
+            "BT /{} {:.1} Tf {:.2} {:.2} Td ({}) Tj ET\n",
+            font,
+            size,
+            x,
+            y,
+            escape(text)
+        ));
+    }
+}
+
+/// An image shared by every page of a document
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageId(usize);
+
+struct PdfImage {
+    width: u32,
+    height: u32,
+    /// Zlib-compressed 8-bit RGB samples
+    data: Vec<u8>,
+}
+
+#[derive(Default)]
+pub struct PdfDocument {
+    pages: Vec<PdfPage>,
+    images: Vec<PdfImage>,
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_page(&mut self, page: PdfPage) {
+        self.pages.push(page);
+    }
+
+    /// Add an image from 8-bit RGB samples, row by row from the top
+    pub fn add_image(&mut self, width: u32, height: u32, rgb: &[u8]) -> ImageId {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(rgb)
+            .expect("writing to a Vec cannot fail");
+        self.images.push(PdfImage {
+            width,
+            height,
+            data: encoder.finish().expect("writing to a Vec cannot fail"),
+        });
+        ImageId(self.images.len() - 1)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Objects 1-4 are the catalog, page tree and two fonts, followed by the
+        // images; each page then takes a page object and its content stream
+        let first_page_id = 5 + self.images.len();
+        let page_ids: Vec<usize> = (0..self.pages.len())
+            .map(|i| first_page_id + i * 2)
+            .collect();
+        let x_objects: String = (0..self.images.len())
+            .map(|i| format!(" /Im{} {} 0 R", i, 5 + i))
+            .collect();
+        let mut objects: Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                page_ids
+                    .iter()
+                    .map(|id| format!("{} 0 R", id))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                self.pages.len()
+            )
+            .into_bytes(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        ];
+        for image in &self.images {
+            let mut object = format!(
+                "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /FlateDecode /Length {} >>\nstream\n",
+                image.width,
+                image.height,
+                image.data.len()
+            )
+            .into_bytes();
+            object.extend_from_slice(&image.data);
+            object.extend_from_slice(b"\nendstream");
+            objects.push(object);
+        }
+        for (page, id) in self.pages.iter().zip(&page_ids) {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> /XObject <<{} >> >> /Contents {} 0 R >>",
+                    page.width,
+                    page.height,
+                    x_objects,
+                    id + 1
+                )
+                .into_bytes(),
+            );
+            objects.push(
+                format!(
+                    "<< /Length {} >>\nstream\n{}endstream",
+                    page.content.len(),
+                    page.content
+                )
+                .into_bytes(),
+            );
+        }
+
+        let mut pdf = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+            pdf.extend_from_slice(object);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = pdf.len();
+        let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            trailer.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        trailer.push_str(&format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        ));
+        pdf.extend_from_slice(trailer.as_bytes());
+        pdf
+    }
+}
+
+/// Helvetica advance widths, in thousandths of the font size, for ' ' through '~'
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Width of `text` set in Helvetica; bold runs a little wider but its digits match
+pub fn text_width(text: &str, size: f64) -> f64 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c as u32 {
+            code @ 0x20..=0x7e => u32::from(HELVETICA_WIDTHS[(code - 0x20) as usize]),
+            _ => 556,
+        })
+        .sum();
+    f64::from(units) * size / 1000.0
+}
+
+/// Escape a PDF string literal. WinAnsi matches Latin-1 from U+00A0 up, so
+/// accented letters print; anything beyond it becomes '?'
+fn escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\\' | '(' | ')' => format!("\\{}", c),
+            c if c.is_ascii() && !c.is_ascii_control() => c.to_string(),
+            c if ('\u{a0}'..='\u{ff}').contains(&c) => format!("\\{:03o}", c as u32),
+            _ => "?".to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_has_a_valid_cross_reference_table() {
+        let mut document = PdfDocument::new();
+        for _ in 0..2 {
+            let mut page = PdfPage::new(288.0, 432.0);
+            page.bold_text(18.0, 400.0, 10.0, "Ship (to)");
+            page.rect(18.0, 390.0, 252.0, 0.75);
+            document.add_page(page);
+        }
+
+        let bytes = document.to_bytes();
+        let pdf = String::from_utf8(bytes.clone()).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("/Count 2"));
+        assert!(pdf.contains("(Ship \\(to\\)) Tj"));
+
+        // startxref must point at the xref keyword, and each entry at its object
+        let startxref: usize = pdf
+            .rsplit("startxref\n")
+            .next()
+            .unwrap()
+            .lines()
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(pdf[startxref..].starts_with("xref"));
+        let font_entry = pdf[startxref..].lines().nth(5).unwrap();
+        let offset: usize = font_entry[..10].parse().unwrap();
+        assert!(pdf[offset..].starts_with("3 0 obj"));
+    }
+
+    #[test]
+    fn test_images_are_shared_by_every_page() {
+        let mut document = PdfDocument::new();
+        let logo = document.add_image(2, 1, &[255, 0, 0, 0, 0, 255]);
+        for _ in 0..2 {
+            let mut page = PdfPage::new(595.0, 842.0);
+            page.image(logo, 36.0, 780.0, 40.0, 20.0);
+            page.text(36.0, 760.0, 10.0, "Société €");
+            document.add_page(page);
+        }
+
+        let bytes = document.to_bytes();
+        let pdf = String::from_utf8_lossy(&bytes);
+        assert!(pdf.contains("/Width 2 /Height 1 /ColorSpace /DeviceRGB"));
+        assert_eq!(pdf.matches("/XObject << /Im0 5 0 R >>").count(), 2);
+        assert!(pdf.contains("/Im0 Do"));
+        assert!(pdf.contains("(Soci\\351t\\351 ?) Tj"));
+        // Pages follow the image object
+        assert!(pdf.contains("/Kids [6 0 R 8 0 R]"));
+    }
+
+    #[test]
+    fn test_text_width() {
+        assert_eq!(text_width("10.50", 10.0), 25.02);
+        assert!(text_width("W", 10.0) > text_width("i", 10.0));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::domain::services::quota_service::{QuotaResource, QuotaService, RateLimitOverrides};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Enforces quotas with the `current_*` counters in `tenant_quotas`. The limit check
+/// and the increment are one conditional `UPDATE`, so concurrent creations can't
+/// both take the last slot.
+pub struct PostgresQuotaService {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresQuotaService {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+/// `tenant_quotas.max_api_calls_per_hour` default, for tenants without a quota row
+const DEFAULT_API_CALLS_PER_HOUR: i64 = 10000;
+
+/// `(counter, limit)` columns of `tenant_quotas` for a resource
+fn quota_columns(resource: QuotaResource) -> (&'static str, &'static str) {
+    match resource {
+        QuotaResource::Items => ("current_items", "max_items"),
+        QuotaResource::Locations => ("current_locations", "max_locations"),
+        QuotaResource::Webhooks => ("current_webhooks", "max_webhooks"),
+    }
+}
+
+#[async_trait]
+impl QuotaService for PostgresQuotaService {
+    async fn reserve(&self, tenant_id: Uuid, resource: QuotaResource) -> Result<(), DomainError> {
+        let (current, max) = quota_columns(resource);
+        let mut tx = self.pool.begin().await?;
+
+        // Tenants created before quotas existed get the default limits, with the
+        // counters starting from what they already own
+        sqlx::query(
+            r#"
+            INSERT INTO tenant_quotas (tenant_id, current_items, current_locations, current_webhooks)
+            SELECT $1,
+                   (SELECT COUNT(*) FROM items WHERE tenant_id = $1),
+                   (SELECT COUNT(*) FROM locations WHERE tenant_id = $1),
+                   (SELECT COUNT(*) FROM webhooks WHERE tenant_id = $1)
+            ON CONFLICT (tenant_id) DO NOTHING
+            "#,
+        )
+        .bind(tenant_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let reserved = sqlx::query(&format!(
+            "UPDATE tenant_quotas SET {current} = {current} + 1, updated_at = NOW() \
+             WHERE tenant_id = $1 AND {current} < {max}"
+        ))
+        .bind(tenant_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if reserved == 0 {
+            return Err(DomainError::QuotaExceeded(
+                format!("Tenant has reached its {} quota", resource.as_str()).into(),
+            ));
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn release(&self, tenant_id: Uuid, resource: QuotaResource) -> Result<(), DomainError> {
+        let (current, _) = quota_columns(resource);
+        sqlx::query(&format!(
+            "UPDATE tenant_quotas SET {current} = GREATEST({current} - 1, 0), updated_at = NOW() \
+             WHERE tenant_id = $1"
+        ))
+        .bind(tenant_id)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn api_calls_per_hour(&self, tenant_id: Uuid) -> Result<i64, DomainError> {
+        let limit = sqlx::query_scalar!(
+            "SELECT max_api_calls_per_hour FROM tenant_quotas WHERE tenant_id = $1",
+            tenant_id
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
+        Ok(limit.map_or(DEFAULT_API_CALLS_PER_HOUR, i64::from))
+    }
+
+    async fn rate_limits(&self, tenant_id: Uuid) -> Result<RateLimitOverrides, DomainError> {
+        let row = sqlx::query!(
+            "SELECT max_requests_per_minute, max_requests_per_minute_per_api_key \
+             FROM tenant_quotas WHERE tenant_id = $1",
+            tenant_id
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
+        Ok(
+            row.map_or_else(RateLimitOverrides::default, |row| RateLimitOverrides {
+                requests_per_minute: row.max_requests_per_minute.map(i64::from),
+                requests_per_minute_per_api_key: row
+                    .max_requests_per_minute_per_api_key
+                    .map(i64::from),
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_each_resource_maps_to_its_counter_and_limit() {
+        assert_eq!(
+            quota_columns(QuotaResource::Items),
+            ("current_items", "max_items")
+        );
+        assert_eq!(
+            quota_columns(QuotaResource::Locations),
+            ("current_locations", "max_locations")
+        );
+        assert_eq!(
+            quota_columns(QuotaResource::Webhooks),
+            ("current_webhooks", "max_webhooks")
+        );
+    }
+}
@@ -1,12 +1,15 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use rust_decimal::Decimal;
 use uuid::Uuid;
 
 use crate::domain::{
     entities::{inventory::StockLevel, item::Item},
     services::{
         item_repository::ItemRepository,
+        purchase_order_repository::PurchaseOrderRepository,
         report_service::{
             LowStockReportItem, LowStockReportResponse, ReportService, StockValuationReportItem,
             StockValuationResponse,
@@ -14,17 +17,25 @@ use crate::domain::{
         stock_repository::StockRepository,
     },
 };
+use crate::shared::money::round_total;
+use crate::shared::pagination::PageRequest;
 
 pub struct ReportServiceImpl<T: ItemRepository, S: StockRepository> {
     item_repository: Arc<T>,
     stock_repository: Arc<S>,
+    purchase_order_repository: Arc<dyn PurchaseOrderRepository>,
 }
 
 impl<T: ItemRepository, S: StockRepository> ReportServiceImpl<T, S> {
-    pub fn new(item_repository: Arc<T>, stock_repository: Arc<S>) -> Self {
+    pub fn new(
+        item_repository: Arc<T>,
+        stock_repository: Arc<S>,
+        purchase_order_repository: Arc<dyn PurchaseOrderRepository>,
+    ) -> Self {
         Self {
             item_repository,
             stock_repository,
+            purchase_order_repository,
         }
     }
 }
@@ -33,20 +44,20 @@ impl<T: ItemRepository, S: StockRepository> ReportServiceImpl<T, S> {
 impl<T: ItemRepository, S: StockRepository> ReportService for ReportServiceImpl<T, S> {
     async fn generate_low_stock_report(
         &self,
-        threshold: i32,
+        threshold: Decimal,
         limit: i64,
         cursor: Option<String>,
     ) -> Result<LowStockReportResponse, String> {
         // Get all stock levels below threshold
         let stock_levels = self
             .stock_repository
-            .get_stock_levels_below_threshold(threshold, limit, cursor)
+            .get_stock_levels_below_threshold(threshold, &PageRequest::new(Some(limit), cursor))
             .await
             .map_err(|e| format!("Failed to get stock levels: {}", e))?;
 
         // Get corresponding items
         let mut items = Vec::new();
-        for stock_level in &stock_levels.items {
+        for stock_level in &stock_levels.data {
             if let Some(item) = self
                 .item_repository
                 .find_by_id(stock_level.item_id)
@@ -62,7 +73,7 @@ impl<T: ItemRepository, S: StockRepository> ReportService for ReportServiceImpl<
 
         Ok(LowStockReportResponse {
             items,
-            next_cursor: stock_levels.next_cursor,
+            next_cursor: stock_levels.cursor.next_cursor,
         })
     }
 
@@ -76,18 +87,29 @@ impl<T: ItemRepository, S: StockRepository> ReportService for ReportServiceImpl<
         // Get stock levels for the specified location (or all locations if none specified)
         let stock_levels = if let Some(location_id) = location_id {
             self.stock_repository
-                .get_stock_levels_by_location(location_id, limit, cursor)
+                .get_stock_levels_by_location(location_id, &PageRequest::new(Some(limit), cursor))
                 .await
         } else {
             self.stock_repository
-                .get_all_stock_levels(limit, cursor)
+                .get_all_stock_levels(&PageRequest::new(Some(limit), cursor))
                 .await
         }
         .map_err(|e| format!("Failed to get stock levels: {}", e))?;
 
+        // Average costs come from what was received, converted into the base currency
+        let average_costs = if valuation_method == "AVG" {
+            let item_ids: Vec<Uuid> = stock_levels.data.iter().map(|s| s.item_id).collect();
+            self.purchase_order_repository
+                .average_received_costs(&item_ids)
+                .await
+                .map_err(|e| format!("Failed to get received costs: {}", e))?
+        } else {
+            HashMap::new()
+        };
+
         // Calculate valuations
         let mut items = Vec::new();
-        for stock_level in &stock_levels.items {
+        for stock_level in &stock_levels.data {
             if let Some(item) = self
                 .item_repository
                 .find_by_id(stock_level.item_id)
@@ -95,7 +117,12 @@ impl<T: ItemRepository, S: StockRepository> ReportService for ReportServiceImpl<
                 .map_err(|e| format!("Failed to get item {}: {}", stock_level.item_id, e))?
             {
                 let valuation = self
-                    .calculate_item_valuation(&item, &stock_level, &valuation_method)
+                    .calculate_item_valuation(
+                        &item,
+                        stock_level,
+                        &valuation_method,
+                        average_costs.get(&item.id).copied(),
+                    )
                     .await?;
 
                 items.push(StockValuationReportItem { item, valuation });
@@ -104,7 +131,7 @@ impl<T: ItemRepository, S: StockRepository> ReportService for ReportServiceImpl<
 
         Ok(StockValuationResponse {
             items,
-            next_cursor: stock_levels.next_cursor,
+            next_cursor: stock_levels.cursor.next_cursor,
         })
     }
 }
@@ -115,19 +142,23 @@ impl<T: ItemRepository, S: StockRepository> ReportServiceImpl<T, S> {
         item: &Item,
         stock_level: &StockLevel,
         valuation_method: &str,
-    ) -> Result<f64, String> {
+        average_cost: Option<Decimal>,
+    ) -> Result<Decimal, String> {
+        let quantity = Decimal::from(stock_level.quantity_on_hand);
         match valuation_method {
             "FIFO" => {
                 // FIFO: Use the cost_price from the item
-                Ok(item.cost_price * stock_level.quantity_on_hand as f64)
+                Ok(round_total(item.cost_price * quantity))
             }
             "LIFO" => {
                 // LIFO: For simplicity, use current cost_price (would need movement history for true LIFO)
-                Ok(item.cost_price * stock_level.quantity_on_hand as f64)
+                Ok(round_total(item.cost_price * quantity))
             }
             "AVG" => {
-                // AVG: Use current cost_price (would need to calculate average from movements)
-                Ok(item.cost_price * stock_level.quantity_on_hand as f64)
+                // AVG: Average received cost, falling back to cost_price for items never received
+                Ok(round_total(
+                    average_cost.unwrap_or(item.cost_price) * quantity,
+                ))
             }
             _ => Err(format!(
                 "Unsupported valuation method: {}",
@@ -0,0 +1,112 @@
+use crate::domain::entities::webhook::WebhookEvent;
+use crate::domain::services::event_broadcaster::EventBroadcaster;
+use crate::domain::services::search_projection::{
+    affects_stock, ProjectionHandler, SearchProjectionHandler,
+};
+use crate::domain::services::search_repository::SearchRepository;
+use crate::shared::tenant_scope::with_tenant;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+const DEFAULT_SYNC_INTERVAL: Duration = Duration::from_secs(60);
+/// How long stock events are gathered before their documents are refreshed
+const STOCK_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Keeps the search index in step with the data it is built from. Item and
+/// location events reindex their entity straight away; stock events are
+/// batched into a sync of stale documents a second later. A full sync also
+/// runs on start and every interval, catching whatever happened without an
+/// event or while this instance wasn't listening.
+pub struct SearchIndexWorker<SR: SearchRepository + ?Sized> {
+    search_repository: Arc<SR>,
+    projection: SearchProjectionHandler<SR>,
+    events: broadcast::Receiver<Arc<WebhookEvent>>,
+    sync_interval: Duration,
+}
+
+impl<SR: SearchRepository + ?Sized + 'static> SearchIndexWorker<SR> {
+    /// Subscribes right away so no event published before `run` is missed
+    pub fn new(
+        search_repository: Arc<SR>,
+        event_broadcaster: &EventBroadcaster,
+        sync_interval: Duration,
+    ) -> Self {
+        Self {
+            projection: SearchProjectionHandler::new(Arc::clone(&search_repository)),
+            search_repository,
+            events: event_broadcaster.subscribe(),
+            sync_interval,
+        }
+    }
+
+    /// Read the full sync interval from `SEARCH_INDEX_SYNC_INTERVAL_SECS`, defaulting to a minute
+    pub fn from_env(search_repository: Arc<SR>, event_broadcaster: &EventBroadcaster) -> Self {
+        let sync_interval = env::var("SEARCH_INDEX_SYNC_INTERVAL_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_SYNC_INTERVAL);
+        Self::new(search_repository, event_broadcaster, sync_interval)
+    }
+
+    pub async fn run(mut self, shutdown: CancellationToken) {
+        info!(
+            "Starting search index worker (full sync every {:?})",
+            self.sync_interval
+        );
+
+        let mut sync = tokio::time::interval(self.sync_interval);
+        let mut flush = tokio::time::interval(STOCK_FLUSH_INTERVAL);
+        let mut stock_changed = false;
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                received = self.events.recv() => match received {
+                    Ok(event) if affects_stock(&event.event_type) => stock_changed = true,
+                    Ok(event) => self.project(&event).await,
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!("Search index worker missed {} events, resyncing", missed);
+                        stock_changed = true;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                _ = flush.tick(), if stock_changed => {
+                    stock_changed = false;
+                    self.sync().await;
+                }
+                _ = sync.tick() => {
+                    stock_changed = false;
+                    self.sync().await;
+                }
+            }
+        }
+        info!("Search index worker stopped");
+    }
+
+    async fn project(&self, event: &WebhookEvent) {
+        let Some(tenant_id) = event.tenant_id else {
+            return;
+        };
+        if let Err(e) = with_tenant(tenant_id, self.projection.handle_event(event)).await {
+            error!(
+                "Failed to index {} event {}: {}",
+                event.event_type.as_str(),
+                event.id,
+                e
+            );
+        }
+    }
+
+    async fn sync(&self) {
+        match self.search_repository.sync_stale_documents().await {
+            Ok(0) => {}
+            Ok(touched) => info!("Search index synced ({} documents)", touched),
+            Err(e) => error!("Failed to sync search index: {}", e),
+        }
+    }
+}
@@ -0,0 +1,536 @@
+use crate::domain::entities::integration::{ConnectorProvider, IntegrationConnector, SyncRunError};
+use crate::domain::services::connector::{
+    ChannelOrder, ChannelOrderLine, ChannelStockLevel, Connector, OAuthToken, StockPushResult,
+};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use reqwest::{header, StatusCode, Url};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::env;
+use std::time::Duration;
+
+/// Largest page the REST Admin API returns
+const ORDERS_PAGE_LIMIT: u32 = 250;
+/// Quantities set per `inventorySetQuantities` call
+const INVENTORY_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct ShopifyConfig {
+    /// Client ID and secret of the Shopify app; only needed for the OAuth flow
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    /// Where Shopify sends the merchant after they approve the app
+    pub redirect_uri: Option<String>,
+    pub scopes: String,
+    pub api_version: String,
+}
+
+impl Default for ShopifyConfig {
+    fn default() -> Self {
+        Self {
+            api_key: None,
+            api_secret: None,
+            redirect_uri: None,
+            scopes: "read_orders,read_products,write_inventory".to_string(),
+            api_version: "2024-07".to_string(),
+        }
+    }
+}
+
+impl ShopifyConfig {
+    /// Read `SHOPIFY_API_KEY`, `SHOPIFY_API_SECRET`, `SHOPIFY_REDIRECT_URI`,
+    /// `SHOPIFY_SCOPES` and `SHOPIFY_API_VERSION`. Without the app credentials
+    /// connectors can still use tokens stored directly, e.g. from a custom app.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| {
+            env::var(name)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Self {
+            api_key: var("SHOPIFY_API_KEY"),
+            api_secret: var("SHOPIFY_API_SECRET"),
+            redirect_uri: var("SHOPIFY_REDIRECT_URI"),
+            scopes: var("SHOPIFY_SCOPES").unwrap_or(defaults.scopes),
+            api_version: var("SHOPIFY_API_VERSION").unwrap_or(defaults.api_version),
+        }
+    }
+}
+
+/// Pulls paid orders through the REST Admin API and sets available quantities
+/// through the GraphQL Admin API, matching variants to items by SKU
+pub struct ShopifyConnector {
+    client: reqwest::Client,
+    config: ShopifyConfig,
+}
+
+impl ShopifyConnector {
+    pub fn new(config: ShopifyConfig) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                // Only shop URLs we built are followed; never let a redirect move us elsewhere
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .expect("Shopify HTTP client configuration is valid"),
+            config,
+        }
+    }
+
+    fn access_token<'a>(
+        &self,
+        connector: &'a IntegrationConnector,
+    ) -> Result<&'a str, DomainError> {
+        connector.access_token.as_deref().ok_or_else(|| {
+            DomainError::BusinessLogicError(
+                format!(
+                    "Connector {} has not been granted access to {}",
+                    connector.name, connector.shop_domain
+                )
+                .into(),
+            )
+        })
+    }
+
+    fn admin_url(&self, connector: &IntegrationConnector, path: &str) -> String {
+        format!(
+            "https://{}/admin/api/{}/{}",
+            connector.shop_domain, self.config.api_version, path
+        )
+    }
+
+    async fn graphql(
+        &self,
+        connector: &IntegrationConnector,
+        query: &str,
+        variables: Value,
+    ) -> Result<Value, DomainError> {
+        let response = self
+            .client
+            .post(self.admin_url(connector, "graphql.json"))
+            .header("X-Shopify-Access-Token", self.access_token(connector)?)
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .map_err(request_error)?;
+        let body: Value = ensure_success(response, &connector.shop_domain)
+            .await?
+            .json()
+            .await
+            .map_err(request_error)?;
+        if let Some(errors) = body.get("errors") {
+            return Err(DomainError::InfrastructureError(
+                format!("Shopify GraphQL error: {}", errors).into(),
+            ));
+        }
+        Ok(body["data"].clone())
+    }
+
+    /// The inventory item behind the variant with `sku`, if the store lists one
+    async fn inventory_item_id(
+        &self,
+        connector: &IntegrationConnector,
+        sku: &str,
+    ) -> Result<Option<String>, DomainError> {
+        let data = self
+            .graphql(
+                connector,
+                "query($query: String!) { productVariants(first: 1, query: $query) { nodes { inventoryItem { id } } } }",
+                json!({ "query": sku_search(sku) }),
+            )
+            .await?;
+        Ok(data["productVariants"]["nodes"][0]["inventoryItem"]["id"]
+            .as_str()
+            .map(str::to_string))
+    }
+}
+
+#[async_trait]
+impl Connector for ShopifyConnector {
+    fn provider(&self) -> ConnectorProvider {
+        ConnectorProvider::Shopify
+    }
+
+    fn authorize_url(
+        &self,
+        connector: &IntegrationConnector,
+        state: &str,
+    ) -> Result<String, DomainError> {
+        let (Some(api_key), Some(redirect_uri)) = (&self.config.api_key, &self.config.redirect_uri)
+        else {
+            return Err(DomainError::BusinessLogicError(
+                "Shopify OAuth needs SHOPIFY_API_KEY and SHOPIFY_REDIRECT_URI; store an access token instead"
+                    .to_string().into(),
+            ));
+        };
+        let url = Url::parse_with_params(
+            &format!("https://{}/admin/oauth/authorize", connector.shop_domain),
+            [
+                ("client_id", api_key.as_str()),
+                ("scope", self.config.scopes.as_str()),
+                ("redirect_uri", redirect_uri.as_str()),
+                ("state", state),
+            ],
+        )
+        .map_err(|e| DomainError::ValidationError(format!("Invalid shop domain: {}", e).into()))?;
+        Ok(url.to_string())
+    }
+
+    async fn exchange_code(
+        &self,
+        connector: &IntegrationConnector,
+        code: &str,
+    ) -> Result<OAuthToken, DomainError> {
+        let (Some(api_key), Some(api_secret)) = (&self.config.api_key, &self.config.api_secret)
+        else {
+            return Err(DomainError::BusinessLogicError(
+                "Shopify OAuth needs SHOPIFY_API_KEY and SHOPIFY_API_SECRET".into(),
+            ));
+        };
+
+        #[derive(Deserialize)]
+        struct AccessTokenResponse {
+            access_token: String,
+            scope: Option<String>,
+        }
+
+        let response = self
+            .client
+            .post(format!(
+                "https://{}/admin/oauth/access_token",
+                connector.shop_domain
+            ))
+            .json(&json!({
+                "client_id": api_key,
+                "client_secret": api_secret,
+                "code": code,
+            }))
+            .send()
+            .await
+            .map_err(request_error)?;
+        let token: AccessTokenResponse = ensure_success(response, &connector.shop_domain)
+            .await?
+            .json()
+            .await
+            .map_err(request_error)?;
+        Ok(OAuthToken {
+            access_token: token.access_token,
+            scopes: token.scope,
+        })
+    }
+
+    async fn fetch_paid_orders(
+        &self,
+        connector: &IntegrationConnector,
+        updated_since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<ChannelOrder>, DomainError> {
+        let mut url = Url::parse(&self.admin_url(connector, "orders.json")).map_err(|e| {
+            DomainError::ValidationError(format!("Invalid shop domain: {}", e).into())
+        })?;
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("status", "any")
+                .append_pair("financial_status", "paid")
+                .append_pair("limit", &ORDERS_PAGE_LIMIT.to_string())
+                .append_pair("fields", "id,name,updated_at,line_items");
+            if let Some(updated_since) = updated_since {
+                query.append_pair(
+                    "updated_at_min",
+                    &updated_since.to_rfc3339_opts(SecondsFormat::Secs, true),
+                );
+            }
+        }
+
+        let mut orders = Vec::new();
+        let mut next = Some(url);
+        while let Some(url) = next.take() {
+            // Page links come from the response; refuse any that leave the shop
+            if url.host_str() != Some(connector.shop_domain.as_str()) {
+                return Err(DomainError::InfrastructureError(
+                    format!(
+                        "Shopify pagination pointed away from {}",
+                        connector.shop_domain
+                    )
+                    .into(),
+                ));
+            }
+            let response = self
+                .client
+                .get(url)
+                .header("X-Shopify-Access-Token", self.access_token(connector)?)
+                .send()
+                .await
+                .map_err(request_error)?;
+            let response = ensure_success(response, &connector.shop_domain).await?;
+            next = response
+                .headers()
+                .get(header::LINK)
+                .and_then(|value| value.to_str().ok())
+                .and_then(next_page_url);
+            let page: Value = response.json().await.map_err(request_error)?;
+            orders.extend(parse_orders(&page)?);
+        }
+
+        orders.sort_by_key(|order| order.updated_at);
+        Ok(orders)
+    }
+
+    async fn push_stock_levels(
+        &self,
+        connector: &IntegrationConnector,
+        levels: &[ChannelStockLevel],
+    ) -> Result<StockPushResult, DomainError> {
+        let location_id = connector
+            .external_location_id
+            .as_deref()
+            .map(location_gid)
+            .ok_or_else(|| {
+                DomainError::BusinessLogicError(
+                    format!(
+                        "Connector {} needs an external_location_id to publish stock",
+                        connector.name
+                    )
+                    .into(),
+                )
+            })?;
+
+        let mut result = StockPushResult::default();
+        let mut quantities = Vec::new();
+        for level in levels {
+            match self.inventory_item_id(connector, &level.sku).await? {
+                Some(inventory_item_id) => {
+                    // Shopify counts whole units, so partial ones aren't offered
+                    let available = level.available.floor().to_i64().unwrap_or(0);
+                    quantities.push((level.sku.clone(), inventory_item_id, available))
+                }
+                None => result.errors.push(SyncRunError {
+                    reference: Some(level.sku.clone()),
+                    message: format!(
+                        "No variant with SKU {} in {}",
+                        level.sku, connector.shop_domain
+                    ),
+                }),
+            }
+        }
+
+        for batch in quantities.chunks(INVENTORY_BATCH_SIZE) {
+            let data = self
+                .graphql(
+                    connector,
+                    "mutation($input: InventorySetQuantitiesInput!) { inventorySetQuantities(input: $input) { userErrors { field message } } }",
+                    json!({
+                        "input": {
+                            "name": "available",
+                            "reason": "correction",
+                            "ignoreCompareQuantity": true,
+                            "quantities": batch
+                                .iter()
+                                .map(|(_, inventory_item_id, available)| json!({
+                                    "inventoryItemId": inventory_item_id,
+                                    "locationId": location_id,
+                                    "quantity": available,
+                                }))
+                                .collect::<Vec<_>>(),
+                        }
+                    }),
+                )
+                .await?;
+            let user_errors = data["inventorySetQuantities"]["userErrors"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            if user_errors.is_empty() {
+                result.updated += batch.len();
+            } else {
+                // Shopify applies a batch all or nothing
+                let message = user_errors
+                    .iter()
+                    .filter_map(|e| e["message"].as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                result
+                    .errors
+                    .extend(batch.iter().map(|(sku, _, _)| SyncRunError {
+                        reference: Some(sku.clone()),
+                        message: message.clone(),
+                    }));
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn request_error(error: reqwest::Error) -> DomainError {
+    DomainError::InfrastructureError(format!("Shopify request failed: {}", error).into())
+}
+
+async fn ensure_success(
+    response: reqwest::Response,
+    shop_domain: &str,
+) -> Result<reqwest::Response, DomainError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        return Err(DomainError::BusinessLogicError(
+            format!(
+                "{} rejected the connector's access token ({}); reconnect the store",
+                shop_domain, status
+            )
+            .into(),
+        ));
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(DomainError::InfrastructureError(
+        format!(
+            "{} returned {}: {}",
+            shop_domain,
+            status,
+            body.chars().take(500).collect::<String>()
+        )
+        .into(),
+    ))
+}
+
+/// The `rel="next"` target of a `Link` header
+fn next_page_url(link: &str) -> Option<Url> {
+    link.split(',').find_map(|part| {
+        let (target, params) = part.split_once(';')?;
+        if !params.split(';').any(|p| p.trim() == "rel=\"next\"") {
+            return None;
+        }
+        Url::parse(target.trim().trim_start_matches('<').trim_end_matches('>')).ok()
+    })
+}
+
+fn parse_orders(page: &Value) -> Result<Vec<ChannelOrder>, DomainError> {
+    #[derive(Deserialize)]
+    struct OrdersPage {
+        orders: Vec<ShopifyOrder>,
+    }
+    #[derive(Deserialize)]
+    struct ShopifyOrder {
+        id: u64,
+        name: String,
+        updated_at: DateTime<Utc>,
+        line_items: Vec<ShopifyLineItem>,
+    }
+    #[derive(Deserialize)]
+    struct ShopifyLineItem {
+        sku: Option<String>,
+        quantity: i32,
+        price: String,
+    }
+
+    let page: OrdersPage = serde_json::from_value(page.clone()).map_err(|e| {
+        DomainError::InfrastructureError(
+            format!("Unexpected Shopify orders response: {}", e).into(),
+        )
+    })?;
+    page.orders
+        .into_iter()
+        .map(|order| {
+            let lines = order
+                .line_items
+                .into_iter()
+                .map(|line| {
+                    Ok(ChannelOrderLine {
+                        sku: line
+                            .sku
+                            .map(|sku| sku.trim().to_string())
+                            .filter(|sku| !sku.is_empty()),
+                        quantity: Decimal::from(line.quantity),
+                        unit_price: line.price.parse().map_err(|_| {
+                            DomainError::InfrastructureError(
+                                format!(
+                                    "Order {} has an invalid price '{}'",
+                                    order.name, line.price
+                                )
+                                .into(),
+                            )
+                        })?,
+                    })
+                })
+                .collect::<Result<_, DomainError>>()?;
+            Ok(ChannelOrder {
+                external_id: order.id.to_string(),
+                order_number: order.name,
+                updated_at: order.updated_at,
+                lines,
+            })
+        })
+        .collect()
+}
+
+/// Shopify search syntax for an exact SKU
+fn sku_search(sku: &str) -> String {
+    format!("sku:\"{}\"", sku.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Accept either a numeric location ID or a GraphQL global ID
+fn location_gid(location_id: &str) -> String {
+    if location_id.starts_with("gid://") {
+        location_id.to_string()
+    } else {
+        format!("gid://shopify/Location/{}", location_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_page_url() {
+        let link = "<https://acme.myshopify.com/admin/api/2024-07/orders.json?page_info=abc&limit=250>; rel=\"previous\", \
+                    <https://acme.myshopify.com/admin/api/2024-07/orders.json?page_info=def&limit=250>; rel=\"next\"";
+        let next = next_page_url(link).unwrap();
+        assert_eq!(next.host_str(), Some("acme.myshopify.com"));
+        assert!(next.query().unwrap().contains("page_info=def"));
+
+        assert!(next_page_url("<https://acme.myshopify.com/x>; rel=\"previous\"").is_none());
+    }
+
+    #[test]
+    fn test_parse_orders() {
+        let page = json!({
+            "orders": [{
+                "id": 450789469,
+                "name": "#1001",
+                "updated_at": "2024-05-10T12:30:00-04:00",
+                "line_items": [
+                    { "sku": "WIDGET-001", "quantity": 2, "price": "19.99" },
+                    { "sku": "", "quantity": 1, "price": "5.00" }
+                ]
+            }]
+        });
+        let orders = parse_orders(&page).unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].external_id, "450789469");
+        assert_eq!(
+            orders[0].updated_at.to_rfc3339(),
+            "2024-05-10T16:30:00+00:00"
+        );
+        assert_eq!(orders[0].lines[0].sku.as_deref(), Some("WIDGET-001"));
+        assert_eq!(orders[0].lines[0].unit_price, Decimal::new(1999, 2));
+        assert_eq!(orders[0].lines[1].sku, None);
+    }
+
+    #[test]
+    fn test_search_and_location_ids() {
+        assert_eq!(sku_search("A\"B"), "sku:\"A\\\"B\"");
+        assert_eq!(location_gid("123"), "gid://shopify/Location/123");
+        assert_eq!(
+            location_gid("gid://shopify/Location/123"),
+            "gid://shopify/Location/123"
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use crate::domain::services::email_sender::{EmailMessage, EmailSender};
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use std::env;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+/// Upper bound on a whole SMTP conversation, so a stalled server can't hang a request
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How the connection to the SMTP server is secured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    /// Plain TCP, for local relays and test servers only
+    None,
+    /// Plain TCP upgraded with `STARTTLS` (usually port 587)
+    StartTls,
+    /// TLS from the first byte (usually port 465)
+    Tls,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// `From` address of every message
+    pub from: String,
+}
+
+impl SmtpConfig {
+    /// Read `SMTP_HOST` and `SMTP_FROM` (required), plus `SMTP_SECURITY`
+    /// (`starttls`, `tls` or `none`; default `starttls`), `SMTP_PORT` (default 587,
+    /// or 465 with `tls`) and `SMTP_USERNAME`/`SMTP_PASSWORD` for `AUTH PLAIN`
+    pub fn from_env() -> Result<Self, DomainError> {
+        let required = |name: &str| {
+            env::var(name).map_err(|_| {
+                DomainError::InfrastructureError(
+                    format!("{} must be set for SMTP email", name).into(),
+                )
+            })
+        };
+        let security = match env::var("SMTP_SECURITY").as_deref() {
+            Ok("none") => SmtpSecurity::None,
+            Ok("tls") => SmtpSecurity::Tls,
+            Ok("starttls") | Err(_) => SmtpSecurity::StartTls,
+            Ok(other) => {
+                return Err(DomainError::InfrastructureError(
+                    format!(
+                        "Invalid SMTP_SECURITY: {}. Must be one of: starttls, tls, none",
+                        other
+                    )
+                    .into(),
+                ))
+            }
+        };
+        let port = match env::var("SMTP_PORT") {
+            Ok(port) => port.parse().map_err(|_| {
+                DomainError::InfrastructureError("SMTP_PORT must be a port number".into())
+            })?,
+            Err(_) if security == SmtpSecurity::Tls => 465,
+            Err(_) => 587,
+        };
+
+        Ok(Self {
+            host: required("SMTP_HOST")?,
+            port,
+            security,
+            username: env::var("SMTP_USERNAME").ok(),
+            password: env::var("SMTP_PASSWORD").ok(),
+            from: required("SMTP_FROM")?,
+        })
+    }
+}
+
+/// Sends email through an SMTP server, opening one connection per message
+pub struct SmtpEmailSender {
+    config: SmtpConfig,
+}
+
+impl SmtpEmailSender {
+    pub fn new(config: SmtpConfig) -> Self {
+        Self { config }
+    }
+
+    async fn deliver(&self, message: &EmailMessage) -> Result<(), DomainError> {
+        let tcp = TcpStream::connect((self.config.host.as_str(), self.config.port))
+            .await
+            .map_err(smtp_error)?;
+
+        let stream: Box<dyn SmtpStream> = match self.config.security {
+            SmtpSecurity::Tls => Box::new(self.tls(tcp).await?),
+            SmtpSecurity::None | SmtpSecurity::StartTls => Box::new(tcp),
+        };
+        let mut session = SmtpSession::new(stream);
+        session.expect_reply(220).await?;
+        session.command("EHLO twh.local", 250).await?;
+
+        if self.config.security == SmtpSecurity::StartTls {
+            session.command("STARTTLS", 220).await?;
+            let tcp = session.into_tcp()?;
+            session = SmtpSession::new(Box::new(self.tls(tcp).await?));
+            session.command("EHLO twh.local", 250).await?;
+        }
+
+        if let (Some(username), Some(password)) = (&self.config.username, &self.config.password) {
+            let credentials = BASE64.encode(format!("\0{}\0{}", username, password));
+            session
+                .command(&format!("AUTH PLAIN {}", credentials), 235)
+                .await?;
+        }
+
+        session
+            .command(&format!("MAIL FROM:<{}>", self.config.from), 250)
+            .await?;
+        session
+            .command(&format!("RCPT TO:<{}>", message.to), 250)
+            .await?;
+        session.command("DATA", 354).await?;
+        session
+            .command(&format_message(&self.config.from, message), 250)
+            .await?;
+        session.command("QUIT", 221).await?;
+        Ok(())
+    }
+
+    async fn tls(
+        &self,
+        tcp: TcpStream,
+    ) -> Result<tokio_native_tls::TlsStream<TcpStream>, DomainError> {
+        let connector = tokio_native_tls::native_tls::TlsConnector::new().map_err(smtp_error)?;
+        tokio_native_tls::TlsConnector::from(connector)
+            .connect(&self.config.host, tcp)
+            .await
+            .map_err(smtp_error)
+    }
+}
+
+#[async_trait]
+impl EmailSender for SmtpEmailSender {
+    async fn send(&self, message: &EmailMessage) -> Result<(), DomainError> {
+        if [&message.to, &message.subject]
+            .iter()
+            .any(|field| field.contains(['\r', '\n']))
+        {
+            return Err(DomainError::ValidationError(
+                "Email recipient and subject must be a single line".into(),
+            ));
+        }
+
+        tokio::time::timeout(SMTP_TIMEOUT, self.deliver(message))
+            .await
+            .map_err(|_| DomainError::InfrastructureError("SMTP server timed out".into()))?
+    }
+}
+
+trait SmtpStream: AsyncRead + AsyncWrite + Unpin + Send {
+    /// The underlying TCP stream, if the connection isn't encrypted yet
+    fn into_tcp(self: Box<Self>) -> Option<TcpStream>;
+}
+
+impl SmtpStream for TcpStream {
+    fn into_tcp(self: Box<Self>) -> Option<TcpStream> {
+        Some(*self)
+    }
+}
+
+impl SmtpStream for tokio_native_tls::TlsStream<TcpStream> {
+    fn into_tcp(self: Box<Self>) -> Option<TcpStream> {
+        None
+    }
+}
+
+struct SmtpSession {
+    stream: BufReader<Box<dyn SmtpStream>>,
+}
+
+impl SmtpSession {
+    fn new(stream: Box<dyn SmtpStream>) -> Self {
+        Self {
+            stream: BufReader::new(stream),
+        }
+    }
+
+    async fn command(&mut self, line: &str, expected: u16) -> Result<(), DomainError> {
+        let stream = self.stream.get_mut();
+        stream
+            .write_all(line.as_bytes())
+            .await
+            .map_err(smtp_error)?;
+        stream.write_all(b"\r\n").await.map_err(smtp_error)?;
+        stream.flush().await.map_err(smtp_error)?;
+        self.expect_reply(expected).await
+    }
+
+    /// Read a (possibly multi-line) reply and check its status code
+    async fn expect_reply(&mut self, expected: u16) -> Result<(), DomainError> {
+        let mut reply = String::new();
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line).await.map_err(smtp_error)? == 0 {
+                return Err(DomainError::InfrastructureError(
+                    "SMTP server closed the connection".into(),
+                ));
+            }
+            reply.push_str(&line);
+            if is_last_reply_line(&line) {
+                break;
+            }
+        }
+
+        match reply.get(..3).and_then(|code| code.parse::<u16>().ok()) {
+            Some(code) if code == expected || (expected == 250 && code == 251) => Ok(()),
+            _ => Err(DomainError::InfrastructureError(
+                format!("Unexpected SMTP reply: {}", reply.trim_end()).into(),
+            )),
+        }
+    }
+
+    fn into_tcp(self) -> Result<TcpStream, DomainError> {
+        self.stream.into_inner().into_tcp().ok_or_else(|| {
+            DomainError::InfrastructureError("SMTP connection is already encrypted".into())
+        })
+    }
+}
+
+/// Reply lines are `<code>-<text>` except the last, which is `<code> <text>`
+fn is_last_reply_line(line: &str) -> bool {
+    line.as_bytes().get(3) != Some(&b'-')
+}
+
+/// Headers and body of `message` as `DATA` content, including the terminating `.`
+fn format_message(from: &str, message: &EmailMessage) -> String {
+    let subject = if message.subject.is_ascii() {
+        message.subject.clone()
+    } else {
+        format!("=?UTF-8?B?{}?=", BASE64.encode(&message.subject))
+    };
+
+    // Normalise line endings to CRLF and escape lines starting with '.'
+    let body = message
+        .body
+        .lines()
+        .map(|line| match line.strip_prefix('.') {
+            Some(_) => format!(".{}", line),
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\r\n");
+
+    format!(
+        "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n{}\r\n.",
+        from,
+        message.to,
+        subject,
+        chrono::Utc::now().to_rfc2822(),
+        body
+    )
+}
+
+fn smtp_error(e: impl std::fmt::Display) -> DomainError {
+    DomainError::InfrastructureError(format!("SMTP error: {}", e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(subject: &str, body: &str) -> EmailMessage {
+        EmailMessage {
+            to: "user@example.com".to_string(),
+            subject: subject.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_message_body_is_dot_stuffed_with_crlf_lines() {
+        let data = format_message(
+            "noreply@example.com",
+            &message("Hi", "line one\n.hidden\nend"),
+        );
+        assert!(data.starts_with("From: noreply@example.com\r\nTo: user@example.com\r\n"));
+        assert!(data.ends_with("\r\n\r\nline one\r\n..hidden\r\nend\r\n."));
+    }
+
+    #[test]
+    fn test_non_ascii_subject_is_encoded() {
+        let data = format_message("noreply@example.com", &message("Olá", "body"));
+        assert!(data.contains("Subject: =?UTF-8?B?T2zDoQ==?=\r\n"));
+    }
+
+    #[test]
+    fn test_multiline_replies_end_at_space_separator() {
+        assert!(!is_last_reply_line("250-smtp.example.com\r\n"));
+        assert!(is_last_reply_line("250 OK\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_header_injection() {
+        let sender = SmtpEmailSender::new(SmtpConfig {
+            host: "localhost".to_string(),
+            port: 25,
+            security: SmtpSecurity::None,
+            username: None,
+            password: None,
+            from: "noreply@example.com".to_string(),
+        });
+        let result = sender
+            .send(&message("Hi\r\nBcc: someone@example.com", "body"))
+            .await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::domain::services::snapshot_repository::SnapshotRepository;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use std::env;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+/// Hour of the day, on each tenant's own clock, the nightly snapshot is taken
+const DEFAULT_SNAPSHOT_HOUR: u32 = 0;
+
+/// Takes a stock level snapshot once a day per tenant, at the configured hour
+/// in the tenant's time zone. It wakes every hour since tenants' days start at
+/// different times. Snapshots are unique per tenant and day, so running several
+/// instances only writes one set of rows.
+pub struct StockSnapshotWorker<R: SnapshotRepository> {
+    snapshot_repository: Arc<R>,
+    hour: u32,
+}
+
+impl<R: SnapshotRepository + 'static> StockSnapshotWorker<R> {
+    pub fn new(snapshot_repository: Arc<R>, hour: u32) -> Self {
+        Self {
+            snapshot_repository,
+            hour: hour % 24,
+        }
+    }
+
+    /// Read the local snapshot hour from `STOCK_SNAPSHOT_HOUR` (or the older
+    /// `STOCK_SNAPSHOT_HOUR_UTC`), defaulting to midnight
+    pub fn from_env(snapshot_repository: Arc<R>) -> Self {
+        let hour = ["STOCK_SNAPSHOT_HOUR", "STOCK_SNAPSHOT_HOUR_UTC"]
+            .iter()
+            .find_map(|name| env::var(name).ok())
+            .and_then(|value| value.trim().parse().ok())
+            .filter(|hour| *hour < 24)
+            .unwrap_or(DEFAULT_SNAPSHOT_HOUR);
+        Self::new(snapshot_repository, hour)
+    }
+
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            "Starting stock snapshot worker (daily at {:02}:00 tenant time)",
+            self.hour
+        );
+
+        loop {
+            let now = Utc::now();
+            let wait = (next_hour_after(now) - now).to_std().unwrap_or_default();
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(wait) => {}
+            }
+
+            match self
+                .snapshot_repository
+                .create_snapshot(Utc::now(), self.hour)
+                .await
+            {
+                Ok(0) => {}
+                Ok(rows) => info!("Stock snapshot taken ({} levels)", rows),
+                Err(e) => error!("Failed to take stock snapshot: {}", e),
+            }
+        }
+        info!("Stock snapshot worker stopped");
+    }
+}
+
+/// Top of the next hour after `now`
+fn next_hour_after(now: DateTime<Utc>) -> DateTime<Utc> {
+    now.duration_trunc(Duration::hours(1))
+        .expect("an hour fits any timestamp")
+        + Duration::hours(1)
+}
+
+/// Next time after `now` the clock reads `hour_utc`:00 UTC
+pub(crate) fn next_run_after(now: DateTime<Utc>, hour_utc: u32) -> DateTime<Utc> {
+    let today = now
+        .date_naive()
+        .and_hms_opt(hour_utc, 0, 0)
+        .expect("hour is below 24")
+        .and_utc();
+    if today > now {
+        today
+    } else {
+        today + Duration::days(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_run_after() {
+        let now = DateTime::parse_from_rfc3339("2024-05-10T01:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            next_run_after(now, 2).to_rfc3339(),
+            "2024-05-10T02:00:00+00:00"
+        );
+        assert_eq!(
+            next_run_after(now, 0).to_rfc3339(),
+            "2024-05-11T00:00:00+00:00"
+        );
+        assert_eq!(
+            next_run_after(now, 1).to_rfc3339(),
+            "2024-05-11T01:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_next_hour_after() {
+        let now = DateTime::parse_from_rfc3339("2024-05-10T23:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            next_hour_after(now).to_rfc3339(),
+            "2024-05-11T00:00:00+00:00"
+        );
+    }
+}
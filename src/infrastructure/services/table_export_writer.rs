@@ -0,0 +1,229 @@
+use crate::domain::entities::export::{ExportFormat, ExportTable};
+use crate::shared::error::DomainError;
+use serde_json::Value;
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+/// Write `table` as a CSV file or an XLSX workbook with a single sheet
+pub fn render_table(table: &ExportTable, format: ExportFormat) -> Result<Vec<u8>, DomainError> {
+    match format {
+        ExportFormat::Csv => render_csv(table),
+        ExportFormat::Xlsx => render_xlsx(table),
+        ExportFormat::Json => {
+            let rows: Vec<serde_json::Map<String, Value>> = table
+                .rows
+                .iter()
+                .map(|row| {
+                    table
+                        .columns
+                        .iter()
+                        .map(|column| column.path.clone())
+                        .zip(row.iter().cloned())
+                        .collect()
+                })
+                .collect();
+            serde_json::to_vec(&rows).map_err(|e| {
+                DomainError::InfrastructureError(
+                    format!("Failed to serialize report: {}", e).into(),
+                )
+            })
+        }
+    }
+}
+
+/// Text of a cell; nested arrays are written as JSON
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn render_csv(table: &ExportTable) -> Result<Vec<u8>, DomainError> {
+    let to_error =
+        |e: csv::Error| DomainError::InfrastructureError(format!("CSV error: {}", e).into());
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(table.columns.iter().map(|column| column.header.as_str()))
+        .map_err(to_error)?;
+    for row in &table.rows {
+        writer
+            .write_record(row.iter().map(cell_text))
+            .map_err(to_error)?;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| DomainError::InfrastructureError(format!("CSV error: {}", e).into()))
+}
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/></Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+
+const WORKBOOK: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Report" sheetId="1" r:id="rId1"/></sheets></workbook>"#;
+
+const WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/></Relationships>"#;
+
+/// Style 1 is the bold header row
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><fonts count="2"><font><sz val="11"/><name val="Calibri"/></font><font><b/><sz val="11"/><name val="Calibri"/></font></fonts><fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills><borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders><cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs><cellXfs count="2"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/><xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1"/></cellXfs></styleSheet>"#;
+
+fn render_xlsx(table: &ExportTable) -> Result<Vec<u8>, DomainError> {
+    let mut sheet = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetViews><sheetView workbookViewId="0"><pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/></sheetView></sheetViews><sheetData>"#,
+    );
+    sheet.push_str(r#"<row r="1">"#);
+    for (index, column) in table.columns.iter().enumerate() {
+        sheet.push_str(&format!(
+            r#"<c r="{}1" s="1" t="inlineStr"><is><t>{}</t></is></c>"#,
+            column_name(index),
+            escape_xml(&column.header)
+        ));
+    }
+    sheet.push_str("</row>");
+    for (row_index, row) in table.rows.iter().enumerate() {
+        let row_number = row_index + 2;
+        sheet.push_str(&format!(r#"<row r="{}">"#, row_number));
+        for (index, value) in row.iter().enumerate() {
+            let reference = format!("{}{}", column_name(index), row_number);
+            match value {
+                Value::Null => {}
+                Value::Number(number) => {
+                    sheet.push_str(&format!(r#"<c r="{}"><v>{}</v></c>"#, reference, number))
+                }
+                Value::Bool(flag) => sheet.push_str(&format!(
+                    r#"<c r="{}" t="b"><v>{}</v></c>"#,
+                    reference,
+                    u8::from(*flag)
+                )),
+                other => sheet.push_str(&format!(
+                    r#"<c r="{}" t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#,
+                    reference,
+                    escape_xml(&cell_text(other))
+                )),
+            }
+        }
+        sheet.push_str("</row>");
+    }
+    sheet.push_str("</sheetData></worksheet>");
+
+    let to_error = |e: zip::result::ZipError| {
+        DomainError::InfrastructureError(format!("Failed to write workbook: {}", e).into())
+    };
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    for (name, content) in [
+        ("[Content_Types].xml", CONTENT_TYPES),
+        ("_rels/.rels", ROOT_RELS),
+        ("xl/workbook.xml", WORKBOOK),
+        ("xl/_rels/workbook.xml.rels", WORKBOOK_RELS),
+        ("xl/styles.xml", STYLES),
+        ("xl/worksheets/sheet1.xml", sheet.as_str()),
+    ] {
+        zip.start_file(name, options).map_err(to_error)?;
+        zip.write_all(content.as_bytes()).map_err(|e| {
+            DomainError::InfrastructureError(format!("Failed to write workbook: {}", e).into())
+        })?;
+    }
+    Ok(zip.finish().map_err(to_error)?.into_inner())
+}
+
+/// Spreadsheet column letters: A..Z, AA..
+fn column_name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).expect("column letters are ASCII")
+}
+
+/// Escape text for XML, dropping control characters XML 1.0 cannot carry
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' | '\n' | '\r' => escaped.push(ch),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::export::ExportColumn;
+    use calamine::{Data, Reader, Xlsx};
+    use serde_json::json;
+
+    fn table() -> ExportTable {
+        ExportTable {
+            columns: vec![
+                ExportColumn {
+                    path: "sku".to_string(),
+                    header: "SKU".to_string(),
+                },
+                ExportColumn {
+                    path: "quantity_on_hand".to_string(),
+                    header: "Existencias".to_string(),
+                },
+            ],
+            rows: vec![
+                vec![json!("A<&>1"), json!(12)],
+                vec![json!("B, \"2\""), Value::Null],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_column_name() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let csv = String::from_utf8(render_table(&table(), ExportFormat::Csv).unwrap()).unwrap();
+        assert_eq!(csv, "SKU,Existencias\nA<&>1,12\n\"B, \"\"2\"\"\",\n");
+    }
+
+    #[test]
+    fn test_render_xlsx_reads_back() {
+        let bytes = render_table(&table(), ExportFormat::Xlsx).unwrap();
+        let mut workbook: Xlsx<_> = Xlsx::new(Cursor::new(bytes)).unwrap();
+        let range = workbook.worksheet_range("Report").unwrap();
+        assert_eq!(
+            range.get_value((0, 1)),
+            Some(&Data::String("Existencias".to_string()))
+        );
+        assert_eq!(
+            range.get_value((1, 0)),
+            Some(&Data::String("A<&>1".to_string()))
+        );
+        assert_eq!(range.get_value((1, 1)), Some(&Data::Float(12.0)));
+        assert_eq!(
+            range.get_value((2, 0)),
+            Some(&Data::String("B, \"2\"".to_string()))
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::domain::entities::printing::Printer;
+use crate::domain::services::label_printer::LabelPrinter;
+use crate::shared::error::DomainError;
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+/// How long to wait for a printer to accept the connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Upper bound on sending a job, so a printer that stops reading (out of
+/// paper, head open) can't hold the worker
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Prints by writing ZPL to the printer's raw TCP port (9100 on Zebra
+/// printers), the way a print server's "raw" queue would
+#[derive(Debug, Default)]
+pub struct TcpLabelPrinter;
+
+impl TcpLabelPrinter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn unavailable(printer: &Printer, reason: impl std::fmt::Display) -> DomainError {
+    DomainError::InfrastructureError(
+        format!(
+            "Printer {} at {}:{} is unavailable: {}",
+            printer.name, printer.host, printer.port, reason
+        )
+        .into(),
+    )
+}
+
+#[async_trait]
+impl LabelPrinter for TcpLabelPrinter {
+    async fn send(&self, printer: &Printer, payload: &[u8]) -> Result<(), DomainError> {
+        let address = (printer.host.as_str(), printer.port as u16);
+        let mut stream = timeout(CONNECT_TIMEOUT, TcpStream::connect(address))
+            .await
+            .map_err(|_| unavailable(printer, "connection timed out"))?
+            .map_err(|e| unavailable(printer, e))?;
+
+        timeout(SEND_TIMEOUT, async {
+            stream.write_all(payload).await?;
+            stream.flush().await?;
+            stream.shutdown().await
+        })
+        .await
+        .map_err(|_| unavailable(printer, "send timed out"))?
+        .map_err(|e| unavailable(printer, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+    use uuid::Uuid;
+
+    fn printer(port: u16) -> Printer {
+        Printer {
+            id: Uuid::new_v4(),
+            location_id: Uuid::new_v4(),
+            name: "Dock 1".to_string(),
+            host: "127.0.0.1".to_string(),
+            port: i32::from(port),
+            is_default: true,
+            active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sends_payload_over_raw_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let received = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut data = Vec::new();
+            socket.read_to_end(&mut data).await.unwrap();
+            data
+        });
+
+        TcpLabelPrinter::new()
+            .send(&printer(port), b"^XA^FDHello^FS^XZ")
+            .await
+            .unwrap();
+        assert_eq!(received.await.unwrap(), b"^XA^FDHello^FS^XZ");
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_printer_is_infrastructure_error() {
+        // Bind then drop a listener so nothing is listening on its port
+        let port = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let result = TcpLabelPrinter::new().send(&printer(port), b"^XA^XZ").await;
+        assert!(matches!(result, Err(DomainError::InfrastructureError(_))));
+    }
+}
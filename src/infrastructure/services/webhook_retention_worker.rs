@@ -0,0 +1,101 @@
+use crate::domain::entities::job::CreateJobRequest;
+use crate::domain::entities::webhook_retention::{
+    RetentionTrigger, WebhookRetentionJobPayload, WEBHOOK_RETENTION_JOB_TYPE,
+};
+use crate::domain::services::job_service::JobService;
+use crate::domain::services::webhook_retention_repository::WebhookRetentionRepository;
+use crate::shared::error::DomainError;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+/// Tenants claimed per poll
+const BATCH_SIZE: i64 = 50;
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Queues a webhook retention job for each tenant once a day. Claiming pushes
+/// a tenant's next cleanup out a day, so several instances never queue it twice.
+pub struct WebhookRetentionWorker<R: WebhookRetentionRepository, S: JobService> {
+    retention_repository: Arc<R>,
+    job_service: Arc<S>,
+    poll_interval: Duration,
+}
+
+impl<R, S> WebhookRetentionWorker<R, S>
+where
+    R: WebhookRetentionRepository + 'static,
+    S: JobService + 'static,
+{
+    pub fn new(retention_repository: Arc<R>, job_service: Arc<S>, poll_interval: Duration) -> Self {
+        Self {
+            retention_repository,
+            job_service,
+            poll_interval,
+        }
+    }
+
+    /// Read the poll interval from `WEBHOOK_RETENTION_POLL_INTERVAL_SECS`, defaulting to an hour
+    pub fn from_env(retention_repository: Arc<R>, job_service: Arc<S>) -> Self {
+        let poll_interval = env::var("WEBHOOK_RETENTION_POLL_INTERVAL_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_POLL_INTERVAL);
+        Self::new(retention_repository, job_service, poll_interval)
+    }
+
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            "Starting webhook retention worker (poll every {:?})",
+            self.poll_interval
+        );
+
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            // Keep going while full batches come back instead of waiting a whole interval
+            loop {
+                match self.poll_once().await {
+                    Ok(claimed) if claimed as i64 == BATCH_SIZE => continue,
+                    Ok(_) => break,
+                    Err(e) => {
+                        error!("Failed to claim tenants due a webhook cleanup: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+        info!("Webhook retention worker stopped");
+    }
+
+    async fn poll_once(&self) -> Result<usize, DomainError> {
+        let tenant_ids = self
+            .retention_repository
+            .claim_due_tenants(BATCH_SIZE)
+            .await?;
+        let payload = serde_json::to_value(WebhookRetentionJobPayload {
+            trigger: RetentionTrigger::Scheduled,
+        })
+        .expect("retention payload serializes");
+        for &tenant_id in &tenant_ids {
+            let request = CreateJobRequest {
+                job_type: WEBHOOK_RETENTION_JOB_TYPE.to_string(),
+                payload: payload.clone(),
+                max_attempts: None,
+            };
+            if let Err(e) = self.job_service.enqueue_job(tenant_id, request).await {
+                error!(
+                    "Failed to queue webhook cleanup for tenant {}: {}",
+                    tenant_id, e
+                );
+            }
+        }
+        Ok(tenant_ids.len())
+    }
+}
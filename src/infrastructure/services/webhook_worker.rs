@@ -0,0 +1,209 @@
+use crate::domain::services::{
+    webhook_dispatcher::WebhookDispatcher, webhook_repository::WebhookRepository,
+};
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::with_tenant;
+use chrono::{DateTime, Utc};
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+#[derive(Debug, Clone)]
+pub struct WebhookWorkerConfig {
+    /// Deliveries sent at the same time
+    pub concurrency: usize,
+    /// Deliveries claimed per poll
+    pub batch_size: i64,
+    pub poll_interval: Duration,
+    /// How long a claimed delivery stays hidden from other workers
+    pub lease: Duration,
+    /// How long a paused webhook's deliveries are held for replay before they
+    /// are dead-lettered
+    pub pause_replay_window: Duration,
+}
+
+impl Default for WebhookWorkerConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            batch_size: 50,
+            poll_interval: Duration::from_secs(10),
+            lease: Duration::from_secs(120),
+            pause_replay_window: Duration::from_secs(72 * 3600),
+        }
+    }
+}
+
+impl WebhookWorkerConfig {
+    /// Read `WEBHOOK_WORKER_CONCURRENCY`, `WEBHOOK_WORKER_BATCH_SIZE`,
+    /// `WEBHOOK_WORKER_POLL_INTERVAL_SECS`, `WEBHOOK_WORKER_LEASE_SECS` and
+    /// `WEBHOOK_PAUSE_REPLAY_WINDOW_HOURS`, falling back to the defaults for anything unset or invalid
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            concurrency: env_var("WEBHOOK_WORKER_CONCURRENCY").unwrap_or(defaults.concurrency),
+            batch_size: env_var("WEBHOOK_WORKER_BATCH_SIZE").unwrap_or(defaults.batch_size),
+            poll_interval: env_var("WEBHOOK_WORKER_POLL_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.poll_interval),
+            lease: env_var("WEBHOOK_WORKER_LEASE_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.lease),
+            pause_replay_window: env_var("WEBHOOK_PAUSE_REPLAY_WINDOW_HOURS")
+                .map(|hours: u64| Duration::from_secs(hours * 3600))
+                .unwrap_or(defaults.pause_replay_window),
+        }
+    }
+}
+
+fn env_var<T: std::str::FromStr + PartialOrd + Default>(name: &str) -> Option<T> {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|value| *value > T::default())
+}
+
+/// Polls for deliveries that are due (new ones whose inline attempt never completed
+/// and failed ones whose backoff has elapsed) and sends them through the dispatcher,
+/// which records the attempt and schedules the next retry or moves it to the DLQ.
+/// Rows are claimed with `FOR UPDATE SKIP LOCKED`, so several instances can run
+/// against the same database. Each poll also dead-letters deliveries held for a
+/// paused webhook longer than the replay window, and first publishes the events
+/// waiting in the outbox, which creates their deliveries.
+pub struct WebhookDeliveryWorker<R: WebhookRepository, D: WebhookDispatcher> {
+    webhook_repository: Arc<R>,
+    webhook_dispatcher: Arc<D>,
+    config: WebhookWorkerConfig,
+}
+
+impl<R, D> WebhookDeliveryWorker<R, D>
+where
+    R: WebhookRepository + 'static,
+    D: WebhookDispatcher + 'static,
+{
+    pub fn new(
+        webhook_repository: Arc<R>,
+        webhook_dispatcher: Arc<D>,
+        config: WebhookWorkerConfig,
+    ) -> Self {
+        Self {
+            webhook_repository,
+            webhook_dispatcher,
+            config,
+        }
+    }
+
+    /// Poll until `shutdown` is cancelled. A batch already claimed is delivered
+    /// before the worker returns.
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            "Starting webhook delivery worker (concurrency {}, polling every {:?})",
+            self.config.concurrency, self.config.poll_interval
+        );
+
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            self.publish_outbox(&shutdown).await;
+            self.expire_held().await;
+            // Keep draining while full batches come back instead of waiting a whole interval
+            loop {
+                match self.poll_once().await {
+                    Ok(claimed)
+                        if claimed as i64 >= self.config.batch_size && !shutdown.is_cancelled() =>
+                    {
+                        continue
+                    }
+                    Ok(_) => break,
+                    Err(e) => {
+                        error!("Failed to claim webhook deliveries: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+        info!("Webhook delivery worker stopped");
+    }
+
+    async fn publish_outbox(&self, shutdown: &CancellationToken) {
+        loop {
+            match self
+                .webhook_dispatcher
+                .publish_outbox(None, self.config.batch_size)
+                .await
+            {
+                Ok(claimed)
+                    if claimed as i64 >= self.config.batch_size && !shutdown.is_cancelled() =>
+                {
+                    continue
+                }
+                Ok(_) => break,
+                Err(e) => {
+                    error!("Failed to claim outbox events: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn expire_held(&self) {
+        // An absurdly long window just means nothing expires
+        let cutoff = chrono::Duration::from_std(self.config.pause_replay_window)
+            .ok()
+            .and_then(|window| Utc::now().checked_sub_signed(window))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        match self.webhook_repository.expire_held_deliveries(cutoff).await {
+            Ok(0) => {}
+            Ok(expired) => info!(
+                "Moved {} held webhook deliveries past the replay window to the DLQ",
+                expired
+            ),
+            Err(e) => error!("Failed to expire held webhook deliveries: {}", e),
+        }
+    }
+
+    /// Claim one batch and deliver it. Returns how many deliveries were claimed.
+    pub async fn poll_once(&self) -> Result<usize, DomainError> {
+        let deliveries = self
+            .webhook_repository
+            .claim_pending_deliveries(self.config.batch_size, self.config.lease.as_secs() as i64)
+            .await?;
+        let claimed = deliveries.len();
+
+        let permits = Arc::new(Semaphore::new(self.config.concurrency.max(1)));
+        let mut tasks = JoinSet::new();
+        for delivery in deliveries {
+            let permit = Arc::clone(&permits)
+                .acquire_owned()
+                .await
+                .expect("delivery semaphore is never closed");
+            let dispatcher = Arc::clone(&self.webhook_dispatcher);
+            tasks.spawn(async move {
+                let _permit = permit;
+                // Deliveries are claimed across tenants; each is retried as its owner
+                let retry = dispatcher.retry_delivery(delivery.id);
+                let result = match delivery.tenant_id {
+                    Some(tenant_id) => with_tenant(tenant_id, retry).await,
+                    None => retry.await,
+                };
+                if let Err(e) = result {
+                    error!("Failed to deliver webhook delivery {}: {}", delivery.id, e);
+                }
+            });
+        }
+        while let Some(result) = tasks.join_next().await {
+            if let Err(e) = result {
+                error!("Webhook delivery task panicked: {}", e);
+            }
+        }
+
+        Ok(claimed)
+    }
+}
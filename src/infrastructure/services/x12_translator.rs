@@ -0,0 +1,434 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use crate::domain::services::edi_translator::{
+    EdiDocumentLine, EdiEnvelope, EdiInvoice, EdiPurchaseOrder, EdiPurchaseOrderInterchange,
+    EdiPurchaseOrderLine, EdiShipNotice, EdiTranslator,
+};
+use crate::shared::error::DomainError;
+use crate::shared::money::{extend, round_total};
+
+/// Delimiters used on everything we send; inbound ones are read from the ISA
+const ELEMENT_SEPARATOR: char = '*';
+const SEGMENT_TERMINATOR: char = '~';
+const SUB_ELEMENT_SEPARATOR: char = '>';
+
+/// The ISA segment is fixed width, terminator included
+const ISA_LENGTH: usize = 106;
+
+/// Reads and writes ANSI X12 004010 interchanges
+pub struct X12Translator;
+
+impl X12Translator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn segments(content: &str) -> Result<Vec<Vec<String>>, DomainError> {
+        let content = content.trim_start_matches('\u{feff}').trim_start();
+        let bytes = content.as_bytes();
+        if !content.starts_with("ISA")
+            || bytes.len() < ISA_LENGTH
+            || !bytes[..ISA_LENGTH].is_ascii()
+        {
+            return Err(invalid(
+                "content must start with a 106-character ISA segment",
+            ));
+        }
+
+        let element_separator = bytes[3] as char;
+        let segment_terminator = bytes[ISA_LENGTH - 1] as char;
+        Ok(content
+            .split(segment_terminator)
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                segment
+                    .split(element_separator)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .collect())
+    }
+
+    fn purchase_order_line(segment: &[String]) -> Result<EdiPurchaseOrderLine, DomainError> {
+        let quantity: Decimal = element(segment, 2)
+            .parse()
+            .map_err(|_| invalid("PO102 quantity must be numeric"))?;
+        if quantity <= Decimal::ZERO {
+            return Err(invalid("PO102 quantity must be positive"));
+        }
+        let unit_price = match element(segment, 4) {
+            "" => Decimal::ZERO,
+            price => price
+                .parse()
+                .map_err(|_| invalid("PO104 unit price must be numeric"))?,
+        };
+
+        // PO106 onwards are qualifier/ID pairs
+        let product_ids = segment
+            .get(6..)
+            .unwrap_or_default()
+            .chunks(2)
+            .filter(|pair| pair.len() == 2 && !pair[0].is_empty() && !pair[1].is_empty())
+            .map(|pair| (pair[0].trim().to_string(), pair[1].trim().to_string()))
+            .collect();
+
+        Ok(EdiPurchaseOrderLine {
+            line_number: Some(element(segment, 1).to_string()).filter(|n| !n.is_empty()),
+            quantity,
+            unit_price,
+            product_ids,
+        })
+    }
+
+    /// Wrap one transaction set in ST/SE, GS/GE and ISA/IEA
+    fn interchange(
+        envelope: &EdiEnvelope,
+        functional_id: &str,
+        transaction_set: &str,
+        body: Vec<String>,
+    ) -> String {
+        let date = envelope.created_at.format("%y%m%d");
+        let long_date = envelope.created_at.format("%Y%m%d");
+        let time = envelope.created_at.format("%H%M");
+        let control = format!("{:09}", envelope.control_number);
+
+        let mut segments = vec![
+            format!(
+                "ISA*00*{:10}*00*{:10}*{:2}*{:15}*{:2}*{:15}*{}*{}*U*00401*{}*0*P*{}",
+                "",
+                "",
+                envelope.sender_qualifier,
+                envelope.sender_id,
+                envelope.receiver_qualifier,
+                envelope.receiver_id,
+                date,
+                time,
+                control,
+                SUB_ELEMENT_SEPARATOR
+            ),
+            format!(
+                "GS*{}*{}*{}*{}*{}*{}*X*004010",
+                functional_id,
+                envelope.sender_application_id,
+                envelope.receiver_application_id,
+                long_date,
+                time,
+                envelope.control_number
+            ),
+            format!("ST*{}*0001", transaction_set),
+        ];
+        // SE01 counts the segments from ST to SE inclusive
+        let transaction_segments = body.len() + 2;
+        segments.extend(body);
+        segments.push(format!("SE*{}*0001", transaction_segments));
+        segments.push(format!("GE*1*{}", envelope.control_number));
+        segments.push(format!("IEA*1*{}", control));
+
+        let mut content = segments.join(&format!("{}\n", SEGMENT_TERMINATOR));
+        content.push(SEGMENT_TERMINATOR);
+        content.push('\n');
+        content
+    }
+}
+
+impl Default for X12Translator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EdiTranslator for X12Translator {
+    fn read_purchase_orders(
+        &self,
+        content: &str,
+    ) -> Result<EdiPurchaseOrderInterchange, DomainError> {
+        let segments = Self::segments(content)?;
+        let isa = &segments[0];
+
+        let mut purchase_orders = Vec::new();
+        let mut current: Option<EdiPurchaseOrder> = None;
+        for segment in &segments[1..] {
+            match segment[0].as_str() {
+                "ST" => {
+                    if element(segment, 1) != "850" {
+                        return Err(invalid(&format!(
+                            "only 850 purchase orders can be ingested, found transaction set {}",
+                            element(segment, 1)
+                        )));
+                    }
+                    current = Some(EdiPurchaseOrder {
+                        po_number: String::new(),
+                        po_date: None,
+                        lines: Vec::new(),
+                    });
+                }
+                "BEG" => {
+                    let order = current
+                        .as_mut()
+                        .ok_or_else(|| invalid("BEG outside ST/SE"))?;
+                    order.po_number = element(segment, 3).trim().to_string();
+                    order.po_date = NaiveDate::parse_from_str(element(segment, 5), "%Y%m%d").ok();
+                }
+                "PO1" => {
+                    let order = current
+                        .as_mut()
+                        .ok_or_else(|| invalid("PO1 outside ST/SE"))?;
+                    order.lines.push(Self::purchase_order_line(segment)?);
+                }
+                "SE" => {
+                    let order = current.take().ok_or_else(|| invalid("SE without ST"))?;
+                    if order.po_number.is_empty() {
+                        return Err(invalid("850 is missing its BEG03 purchase order number"));
+                    }
+                    if order.lines.is_empty() {
+                        return Err(invalid(&format!(
+                            "purchase order {} has no PO1 lines",
+                            order.po_number
+                        )));
+                    }
+                    purchase_orders.push(order);
+                }
+                _ => {}
+            }
+        }
+
+        if current.is_some() {
+            return Err(invalid("transaction set is missing its SE segment"));
+        }
+        if purchase_orders.is_empty() {
+            return Err(invalid("interchange contains no 850 transaction sets"));
+        }
+
+        Ok(EdiPurchaseOrderInterchange {
+            sender_id: element(isa, 6).trim().to_string(),
+            receiver_id: element(isa, 8).trim().to_string(),
+            control_number: element(isa, 13).trim().to_string(),
+            purchase_orders,
+        })
+    }
+
+    fn write_ship_notice(
+        &self,
+        envelope: &EdiEnvelope,
+        notice: &EdiShipNotice,
+    ) -> Result<String, DomainError> {
+        let shipped_date = notice.shipped_at.format("%Y%m%d");
+        let mut body = vec![
+            format!(
+                "BSN*00*{}*{}*{}",
+                text(&notice.shipment_number),
+                envelope.created_at.format("%Y%m%d"),
+                envelope.created_at.format("%H%M")
+            ),
+            "HL*1**S".to_string(),
+        ];
+        if let Some(carrier) = &notice.carrier {
+            body.push(format!("TD5**2*{}", text(carrier)));
+        }
+        if let Some(tracking_number) = &notice.tracking_number {
+            body.push(format!("REF*CN*{}", text(tracking_number)));
+        }
+        body.push(format!("DTM*011*{}", shipped_date));
+        body.push("HL*2*1*O".to_string());
+        body.push(format!("PRF*{}", text(&notice.po_number)));
+
+        // Packs and items both hang off the order level
+        let mut hl = 2;
+        for sscc in &notice.sscc_codes {
+            hl += 1;
+            body.push(format!("HL*{}*2*P", hl));
+            body.push(format!("MAN*GM*{}", sscc));
+        }
+        for line in &notice.lines {
+            hl += 1;
+            body.push(format!("HL*{}*2*I", hl));
+            body.push(format!(
+                "LIN*{}*{}*{}",
+                text(&line.line_number),
+                text(&line.product_qualifier),
+                text(&line.product_id)
+            ));
+            body.push(format!(
+                "SN1*{}*{}*EA",
+                text(&line.line_number),
+                line.quantity.normalize()
+            ));
+        }
+        body.push(format!("CTT*{}", hl));
+
+        Ok(Self::interchange(envelope, "SH", "856", body))
+    }
+
+    fn write_invoice(
+        &self,
+        envelope: &EdiEnvelope,
+        invoice: &EdiInvoice,
+    ) -> Result<String, DomainError> {
+        let mut body = vec![format!(
+            "BIG*{}*{}*{}*{}",
+            invoice.invoice_date.format("%Y%m%d"),
+            text(&invoice.invoice_number),
+            invoice
+                .po_date
+                .map(|d| d.format("%Y%m%d").to_string())
+                .unwrap_or_default(),
+            text(&invoice.po_number)
+        )];
+        body.extend(invoice.lines.iter().map(invoice_line));
+
+        // TDS01 carries the total with two implied decimal places
+        let total: Decimal = invoice
+            .lines
+            .iter()
+            .map(|l| extend(l.quantity, l.unit_price))
+            .sum();
+        body.push(format!("TDS*{}", (total * Decimal::ONE_HUNDRED).trunc()));
+        body.push(format!("CTT*{}", invoice.lines.len()));
+
+        Ok(Self::interchange(envelope, "IN", "810", body))
+    }
+}
+
+fn invoice_line(line: &EdiDocumentLine) -> String {
+    format!(
+        "IT1*{}*{}*EA*{}**{}*{}",
+        text(&line.line_number),
+        line.quantity.normalize(),
+        round_total(line.unit_price).normalize(),
+        text(&line.product_qualifier),
+        text(&line.product_id)
+    )
+}
+
+fn element(segment: &[String], index: usize) -> &str {
+    segment.get(index).map(String::as_str).unwrap_or("")
+}
+
+/// Free text must not contain the delimiters we write with
+fn text(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            ELEMENT_SEPARATOR | SEGMENT_TERMINATOR | SUB_ELEMENT_SEPARATOR => ' ',
+            c if c.is_ascii() && !c.is_ascii_control() => c,
+            _ => '?',
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn invalid(message: &str) -> DomainError {
+    DomainError::ValidationError(format!("Invalid X12 interchange: {}", message).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    const PURCHASE_ORDER: &str = "ISA*00*          *00*          *ZZ*ACMERETAIL     *ZZ*TWH            *261015*0930*U*00401*000000042*0*P*>~
+GS*PO*ACMERETAIL*TWH*20261015*0930*42*X*004010~
+ST*850*0001~
+BEG*00*SA*PO-7731**20261014~
+N1*ST*Acme Store 12~
+PO1*1*24*EA*3.5**VN*SKU-001*UP*012345678905~
+PO1*2*6*EA*12**VN*SKU-002~
+CTT*2~
+SE*7*0001~
+GE*1*42~
+IEA*1*000000042~";
+
+    fn envelope() -> EdiEnvelope {
+        EdiEnvelope {
+            sender_qualifier: "ZZ".to_string(),
+            sender_id: "TWH".to_string(),
+            sender_application_id: "TWH".to_string(),
+            receiver_qualifier: "ZZ".to_string(),
+            receiver_id: "ACMERETAIL".to_string(),
+            receiver_application_id: "ACMERETAIL".to_string(),
+            control_number: 7,
+            created_at: Utc.with_ymd_and_hms(2026, 10, 15, 16, 45, 0).unwrap(),
+        }
+    }
+
+    fn line() -> EdiDocumentLine {
+        EdiDocumentLine {
+            line_number: "1".to_string(),
+            product_qualifier: "VN".to_string(),
+            product_id: "SKU-001".to_string(),
+            quantity: Decimal::from(24),
+            unit_price: Decimal::new(35, 1),
+        }
+    }
+
+    #[test]
+    fn test_reads_850_purchase_orders() {
+        let interchange = X12Translator::new()
+            .read_purchase_orders(PURCHASE_ORDER)
+            .unwrap();
+
+        assert_eq!(interchange.sender_id, "ACMERETAIL");
+        assert_eq!(interchange.control_number, "000000042");
+        let order = &interchange.purchase_orders[0];
+        assert_eq!(order.po_number, "PO-7731");
+        assert_eq!(order.po_date, NaiveDate::from_ymd_opt(2026, 10, 14));
+        assert_eq!(order.lines.len(), 2);
+        assert_eq!(order.lines[0].quantity, Decimal::from(24));
+        assert_eq!(order.lines[0].product_id("UP"), Some("012345678905"));
+        assert_eq!(order.lines[1].product_id("VN"), Some("SKU-002"));
+    }
+
+    #[test]
+    fn test_rejects_other_transaction_sets() {
+        let content = PURCHASE_ORDER.replace("ST*850", "ST*810");
+        assert!(X12Translator::new().read_purchase_orders(&content).is_err());
+        assert!(X12Translator::new().read_purchase_orders("ST*850").is_err());
+    }
+
+    #[test]
+    fn test_ship_notice_envelope_and_counts() {
+        let notice = EdiShipNotice {
+            shipment_number: "SH-1".to_string(),
+            shipped_at: Utc.with_ymd_and_hms(2026, 10, 15, 12, 0, 0).unwrap(),
+            carrier: Some("UPSN".to_string()),
+            tracking_number: Some("1Z*999".to_string()),
+            po_number: "PO-7731".to_string(),
+            sscc_codes: vec!["106141412345678908".to_string()],
+            lines: vec![line()],
+        };
+        let content = X12Translator::new()
+            .write_ship_notice(&envelope(), &notice)
+            .unwrap();
+        let segments: Vec<&str> = content.lines().collect();
+
+        assert_eq!(segments[0].len(), ISA_LENGTH);
+        assert!(segments.contains(&"REF*CN*1Z 999~"));
+        assert!(segments.contains(&"MAN*GM*106141412345678908~"));
+        assert!(segments.contains(&"CTT*4~"));
+        let st = segments.iter().position(|s| s.starts_with("ST*")).unwrap();
+        let se = segments.iter().position(|s| s.starts_with("SE*")).unwrap();
+        assert_eq!(segments[se], format!("SE*{}*0001~", se - st + 1));
+        assert_eq!(segments.last(), Some(&"IEA*1*000000007~"));
+    }
+
+    #[test]
+    fn test_invoice_totals_lines() {
+        let invoice = EdiInvoice {
+            invoice_number: "SH-1".to_string(),
+            invoice_date: Utc.with_ymd_and_hms(2026, 10, 15, 12, 0, 0).unwrap(),
+            po_number: "PO-7731".to_string(),
+            po_date: NaiveDate::from_ymd_opt(2026, 10, 14),
+            lines: vec![line()],
+        };
+        let content = X12Translator::new()
+            .write_invoice(&envelope(), &invoice)
+            .unwrap();
+
+        assert!(content.contains("BIG*20261015*SH-1*20261014*PO-7731~"));
+        assert!(content.contains("IT1*1*24*EA*3.5**VN*SKU-001~"));
+        assert!(content.contains("TDS*8400~"));
+    }
+}
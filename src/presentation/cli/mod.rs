@@ -0,0 +1,20 @@
+//! Admin commands run with the server binary instead of serving, e.g.
+//! `The-Warehouse-Hub---TWH seed --tenant <id> --items 100000`. They read the
+//! same configuration as the server.
+
+pub mod seed;
+
+use crate::infrastructure::config::app_config::AppConfig;
+use crate::shared::error::DomainError;
+
+/// Run the command named by `args[0]`, if it names one. Returns `None` when the
+/// binary should start the server instead.
+pub async fn run(config: &AppConfig, args: &[String]) -> Option<Result<(), DomainError>> {
+    match args.first().map(String::as_str) {
+        Some("seed") => Some(match seed::SeedArgs::parse(&args[1..]) {
+            Ok(seed_args) => seed::run(config, seed_args).await,
+            Err(e) => Err(e),
+        }),
+        _ => None,
+    }
+}
@@ -0,0 +1,182 @@
+//! `seed`: bulk-load synthetic data into an existing tenant for load testing.
+//!
+//! ```text
+//! The-Warehouse-Hub---TWH seed --tenant <id> --items 100000 --movements 5000000
+//!     [--locations 10] [--history-days 365] [--seed 20240601] [--batch 10000] [--user <id>]
+//! ```
+//!
+//! The catalogue is the sandbox one at the requested size; the movements are
+//! receipts and shipments spread over the history, written a batch per
+//! transaction, and the stock levels they add up to are written last.
+
+use crate::domain::entities::sandbox_seed::{LoadDataset, LoadProfile, DEFAULT_SEED};
+use crate::infrastructure::config::app_config::AppConfig;
+use crate::infrastructure::repositories::postgres_sandbox_seed_repository::PostgresSandboxSeedRepository;
+use crate::infrastructure::repositories::tenant_pool::connect_tenant_pool;
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::with_tenant;
+use chrono::Utc;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
+use uuid::Uuid;
+
+const DEFAULT_BATCH_SIZE: usize = 10_000;
+
+#[derive(Debug, PartialEq)]
+pub struct SeedArgs {
+    pub tenant_id: Uuid,
+    /// Recorded as the creator of the movements; defaults to whoever created the tenant
+    pub user_id: Option<Uuid>,
+    pub profile: LoadProfile,
+    pub seed: u64,
+    pub batch_size: usize,
+}
+
+impl SeedArgs {
+    pub fn parse(args: &[String]) -> Result<Self, DomainError> {
+        fn value<T: FromStr>(flag: &str, value: Option<&String>) -> Result<T, DomainError> {
+            value.and_then(|value| value.parse().ok()).ok_or_else(|| {
+                DomainError::ValidationError(format!("{} needs a valid value", flag).into())
+            })
+        }
+
+        let mut tenant_id = None;
+        let mut user_id = None;
+        let mut profile = LoadProfile::default();
+        let mut seed = DEFAULT_SEED;
+        let mut batch_size = DEFAULT_BATCH_SIZE;
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            let next = args.next();
+            match flag.as_str() {
+                "--tenant" => tenant_id = Some(value(flag, next)?),
+                "--user" => user_id = Some(value(flag, next)?),
+                "--locations" => profile.locations = value(flag, next)?,
+                "--items" => profile.items = value(flag, next)?,
+                "--movements" => profile.movements = value(flag, next)?,
+                "--history-days" => profile.history_days = value(flag, next)?,
+                "--seed" => seed = value(flag, next)?,
+                "--batch" => batch_size = value(flag, next)?,
+                other => {
+                    return Err(DomainError::ValidationError(
+                        format!("Unknown seed option: {}", other).into(),
+                    ))
+                }
+            }
+        }
+        profile.validate()?;
+        if batch_size == 0 {
+            return Err(DomainError::ValidationError(
+                "--batch must be at least 1".into(),
+            ));
+        }
+
+        Ok(Self {
+            tenant_id: tenant_id
+                .ok_or_else(|| DomainError::ValidationError("--tenant is required".into()))?,
+            user_id,
+            profile,
+            seed,
+            batch_size,
+        })
+    }
+}
+
+pub async fn run(config: &AppConfig, args: SeedArgs) -> Result<(), DomainError> {
+    let pool = Arc::new(connect_tenant_pool(&config.database).await?);
+    let created_by: Option<Uuid> = match args.user_id {
+        Some(user_id) => Some(user_id),
+        None => sqlx::query_scalar("SELECT created_by FROM tenants WHERE id = $1")
+            .bind(args.tenant_id)
+            .fetch_optional(&*pool)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Tenant {}", args.tenant_id).into()))?,
+    };
+    let created_by = created_by.ok_or_else(|| {
+        DomainError::ValidationError(
+            "The tenant has no creator to record the movements against; pass --user".into(),
+        )
+    })?;
+
+    let repository = PostgresSandboxSeedRepository::new(Arc::clone(&pool));
+    with_tenant(args.tenant_id, async {
+        let started = Instant::now();
+        let mut dataset = LoadDataset::generate(
+            &args.profile,
+            args.seed,
+            args.tenant_id,
+            created_by,
+            Utc::now(),
+        );
+        repository
+            .load_catalogue(&dataset.locations, &dataset.items)
+            .await?;
+        println!(
+            "Seeded {} locations and {} items",
+            dataset.locations.len(),
+            dataset.items.len()
+        );
+
+        let mut loaded = 0;
+        loop {
+            let movements = dataset.next_movements(args.batch_size);
+            if movements.is_empty() {
+                break;
+            }
+            repository.load_movements(&movements).await?;
+            loaded += movements.len();
+            if (loaded / args.batch_size).is_multiple_of(50) || loaded == args.profile.movements {
+                println!(
+                    "Seeded {}/{} stock movements ({:.0?})",
+                    loaded,
+                    args.profile.movements,
+                    started.elapsed()
+                );
+            }
+        }
+
+        let levels = dataset.stock_levels();
+        for batch in levels.chunks(args.batch_size) {
+            repository.load_stock_levels(batch).await?;
+        }
+        println!(
+            "Seeded {} stock levels in {:.0?}",
+            levels.len(),
+            started.elapsed()
+        );
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_seed_args() {
+        let tenant_id = Uuid::new_v4();
+        let parsed = SeedArgs::parse(&args(&format!(
+            "--tenant {} --items 100000 --movements 5000000 --batch 5000",
+            tenant_id
+        )))
+        .unwrap();
+        assert_eq!(parsed.tenant_id, tenant_id);
+        assert_eq!(
+            (parsed.profile.items, parsed.profile.movements),
+            (100_000, 5_000_000)
+        );
+        assert_eq!(parsed.profile.locations, LoadProfile::default().locations);
+        assert_eq!((parsed.seed, parsed.batch_size), (DEFAULT_SEED, 5000));
+
+        assert!(SeedArgs::parse(&args("--items 10")).is_err());
+        assert!(SeedArgs::parse(&args(&format!("--tenant {} --items", tenant_id))).is_err());
+        assert!(SeedArgs::parse(&args(&format!("--tenant {} --items 0", tenant_id))).is_err());
+        assert!(SeedArgs::parse(&args(&format!("--tenant {} --size 3", tenant_id))).is_err());
+    }
+}
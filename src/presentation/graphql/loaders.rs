@@ -0,0 +1,154 @@
+use crate::domain::entities::inventory::StockLevel;
+use crate::domain::entities::item::Item;
+use crate::domain::entities::location::Location;
+use crate::domain::services::{
+    item_repository::ItemRepository, location_repository::LocationRepository,
+    stock_repository::StockRepository,
+};
+use crate::infrastructure::repositories::{
+    postgres_item_repository::PostgresItemRepository,
+    postgres_location_repository::PostgresLocationRepository,
+    postgres_stock_repository::PostgresStockRepository,
+};
+use crate::shared::error::DomainError;
+use crate::shared::tenant_scope::with_tenant;
+use async_graphql::dataloader::{DataLoader, HashMapCache, Loader};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// The objects that nested fields point at. Each loader collects the keys
+/// asked for while a level of the query resolves and fetches them in one
+/// repository query, and keeps what it found for the rest of the request.
+/// Lives for one request.
+pub struct Loaders {
+    pub items: DataLoader<ItemLoader, HashMapCache>,
+    pub locations: DataLoader<LocationLoader, HashMapCache>,
+    pub item_stock_levels: DataLoader<ItemStockLevelLoader, HashMapCache>,
+    pub location_stock_levels: DataLoader<LocationStockLevelLoader, HashMapCache>,
+}
+
+impl Loaders {
+    /// Loaders for a request of `tenant_id`. Batches run on their own tasks,
+    /// so they are put in the tenant's scope explicitly.
+    pub fn new(
+        tenant_id: Option<Uuid>,
+        item_repository: Arc<PostgresItemRepository>,
+        location_repository: Arc<PostgresLocationRepository>,
+        stock_repository: Arc<PostgresStockRepository>,
+    ) -> Self {
+        fn batched<T: Loader<Uuid>>(
+            tenant_id: Option<Uuid>,
+            loader: T,
+        ) -> DataLoader<T, HashMapCache> {
+            DataLoader::with_cache(
+                loader,
+                move |batch| match tenant_id {
+                    Some(tenant_id) => tokio::spawn(with_tenant(tenant_id, batch)),
+                    None => tokio::spawn(batch),
+                },
+                HashMapCache::default(),
+            )
+        }
+
+        Self {
+            items: batched(tenant_id, ItemLoader(item_repository)),
+            locations: batched(tenant_id, LocationLoader(location_repository)),
+            item_stock_levels: batched(
+                tenant_id,
+                ItemStockLevelLoader(Arc::clone(&stock_repository)),
+            ),
+            location_stock_levels: batched(tenant_id, LocationStockLevelLoader(stock_repository)),
+        }
+    }
+}
+
+pub struct ItemLoader(Arc<PostgresItemRepository>);
+
+impl Loader<Uuid> for ItemLoader {
+    type Value = Item;
+    type Error = DomainError;
+
+    async fn load(&self, ids: &[Uuid]) -> Result<HashMap<Uuid, Item>, DomainError> {
+        let items = self.0.find_by_ids(ids).await?;
+        Ok(items.into_iter().map(|item| (item.id, item)).collect())
+    }
+}
+
+pub struct LocationLoader(Arc<PostgresLocationRepository>);
+
+impl Loader<Uuid> for LocationLoader {
+    type Value = Location;
+    type Error = DomainError;
+
+    async fn load(&self, ids: &[Uuid]) -> Result<HashMap<Uuid, Location>, DomainError> {
+        let locations = self.0.find_by_ids(ids).await?;
+        Ok(locations
+            .into_iter()
+            .map(|location| (location.id, location))
+            .collect())
+    }
+}
+
+/// Stock levels by item; items without any are left out
+pub struct ItemStockLevelLoader(Arc<PostgresStockRepository>);
+
+impl Loader<Uuid> for ItemStockLevelLoader {
+    type Value = Vec<StockLevel>;
+    type Error = DomainError;
+
+    async fn load(&self, ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<StockLevel>>, DomainError> {
+        let levels = self.0.get_stock_levels_for_items(ids).await?;
+        Ok(group_by(levels, |level| level.item_id))
+    }
+}
+
+/// Stock levels by location; locations without any are left out
+pub struct LocationStockLevelLoader(Arc<PostgresStockRepository>);
+
+impl Loader<Uuid> for LocationStockLevelLoader {
+    type Value = Vec<StockLevel>;
+    type Error = DomainError;
+
+    async fn load(&self, ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<StockLevel>>, DomainError> {
+        let levels = self.0.get_stock_levels_for_locations(ids).await?;
+        Ok(group_by(levels, |level| level.location_id))
+    }
+}
+
+fn group_by(
+    levels: Vec<StockLevel>,
+    key: impl Fn(&StockLevel) -> Uuid,
+) -> HashMap<Uuid, Vec<StockLevel>> {
+    let mut groups: HashMap<Uuid, Vec<StockLevel>> = HashMap::new();
+    for level in levels {
+        groups.entry(key(&level)).or_default().push(level);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_group_by_collects_levels_per_key() {
+        let (item_a, item_b, location) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut levels = vec![
+            StockLevel::new(item_a, location),
+            StockLevel::new(item_b, location),
+            StockLevel::new(item_a, Uuid::new_v4()),
+        ];
+        levels[0].quantity_on_hand = Decimal::from(5);
+
+        let by_item = group_by(levels.clone(), |level| level.item_id);
+        assert_eq!(by_item.len(), 2);
+        assert_eq!(by_item[&item_a].len(), 2);
+        assert_eq!(by_item[&item_a][0].quantity_on_hand, Decimal::from(5));
+        assert_eq!(by_item[&item_b].len(), 1);
+
+        let by_location = group_by(levels, |level| level.location_id);
+        assert_eq!(by_location[&location].len(), 2);
+    }
+}
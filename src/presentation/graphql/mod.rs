@@ -0,0 +1,273 @@
+//! Read-only GraphQL API for dashboards, served at `/graphql` over the same
+//! repositories as the REST API. Nested objects go through per-request
+//! DataLoaders, so an order's lines' items' stock levels cost one batched
+//! query per level rather than one per parent.
+
+pub mod loaders;
+pub mod query;
+pub mod types;
+
+use crate::infrastructure::repositories::{
+    postgres_item_repository::PostgresItemRepository,
+    postgres_location_repository::PostgresLocationRepository,
+    postgres_purchase_order_repository::PostgresPurchaseOrderRepository,
+    postgres_sales_order_repository::PostgresSalesOrderRepository,
+    postgres_stock_repository::PostgresStockRepository,
+};
+use crate::shared::tenant_scope::current_tenant;
+use crate::AppState;
+use async_graphql::{EmptyMutation, EmptySubscription, Schema};
+pub use loaders::Loaders;
+pub use query::Query;
+use std::sync::{Arc, OnceLock};
+
+pub type WarehouseSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+/// How deeply a query may nest selections, counting its root fields
+const MAX_DEPTH: usize = 10;
+
+static SCHEMA: OnceLock<WarehouseSchema> = OnceLock::new();
+
+/// The schema served at `/graphql`
+pub fn schema() -> &'static WarehouseSchema {
+    SCHEMA.get_or_init(|| {
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .limit_depth(MAX_DEPTH)
+            .finish()
+    })
+}
+
+/// The repositories the root fields read from
+pub struct Repositories {
+    pub item_repository: Arc<PostgresItemRepository>,
+    pub location_repository: Arc<PostgresLocationRepository>,
+    pub stock_repository: Arc<PostgresStockRepository>,
+    pub sales_order_repository: Arc<PostgresSalesOrderRepository>,
+    pub purchase_order_repository: Arc<PostgresPurchaseOrderRepository>,
+}
+
+impl Repositories {
+    pub fn new(state: &AppState) -> Self {
+        Self {
+            item_repository: Arc::clone(&state.item_repository),
+            location_repository: Arc::clone(&state.location_repository),
+            stock_repository: Arc::clone(&state.stock_repository),
+            sales_order_repository: Arc::clone(&state.sales_order_repository),
+            purchase_order_repository: Arc::clone(&state.purchase_order_repository),
+        }
+    }
+}
+
+/// Run `request` for the current tenant, with loaders of its own
+pub async fn execute(
+    repositories: Repositories,
+    request: async_graphql::Request,
+) -> async_graphql::Response {
+    let loaders = Loaders::new(
+        current_tenant(),
+        Arc::clone(&repositories.item_repository),
+        Arc::clone(&repositories.location_repository),
+        Arc::clone(&repositories.stock_repository),
+    );
+    schema()
+        .execute(request.data(repositories).data(loaders))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::tenant_scope::with_tenant;
+    use crate::test_support::database::{TestDatabase, SEEDED_USER};
+    use serde_json::{json, Value};
+    use uuid::Uuid;
+
+    async fn run(repositories: Repositories, tenant_id: Uuid, query: &str) -> Value {
+        let response = with_tenant(tenant_id, execute(repositories, query.into())).await;
+        serde_json::to_value(response).unwrap()
+    }
+
+    #[test]
+    fn test_schema_is_introspectable() {
+        let sdl = schema().sdl();
+        for definition in [
+            "type SalesOrder {",
+            "lines: [SalesOrderLine!]!",
+            "stockLevels: [StockLevel!]!",
+            "customerId: ID",
+            "): SalesOrderPage!",
+            "type: String",
+            "scalar DateTime",
+        ] {
+            assert!(sdl.contains(definition), "{definition} not in\n{sdl}");
+        }
+    }
+
+    /// Checked before anything resolves, so no repositories are needed
+    #[tokio::test]
+    async fn test_queries_are_checked_against_the_schema() {
+        for (query, message) in [
+            (r#"{ item(id: "x") { price } }"#, r#"Unknown field "price""#),
+            (
+                "{ item { id } }",
+                r#"argument "id" of type "Query" is required but not provided"#,
+            ),
+            (r#"{ item(id: "x") }"#, "must have a selection of subfields"),
+            (
+                "{ item(id: \"x\") { stockLevels { item { stockLevels { item { stockLevels { item { stockLevels { item { stockLevels { item { id } } } } } } } } } } } }",
+                "nested too deep",
+            ),
+        ] {
+            let response = serde_json::to_value(schema().execute(query).await).unwrap();
+            assert_eq!(response["data"], Value::Null, "{query}");
+            let error = response["errors"][0]["message"].as_str().unwrap();
+            assert!(error.contains(message), "{query}: {error}");
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database at DATABASE_URL"]
+    async fn test_nested_fields_resolve_per_parent() {
+        let db = TestDatabase::connect().await;
+        let (admin, pool) = (&db.admin, &db.pool);
+        let tenant_id = db.create_tenant("GraphQL").await;
+        let location_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO locations (name, tenant_id) VALUES ('GraphQL dock', $1) RETURNING id",
+        )
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        let mut item_ids = Vec::new();
+        for (sku, on_hand) in [("GQL-A", 5), ("GQL-B", 3)] {
+            let item_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO items (sku, name, unit, cost_price, tenant_id)
+                 VALUES ($1, $1, 'each', 1, $2) RETURNING id",
+            )
+            .bind(sku)
+            .bind(tenant_id)
+            .fetch_one(admin)
+            .await
+            .unwrap();
+            sqlx::query(
+                "INSERT INTO stock_levels (item_id, location_id, quantity_on_hand, quantity_reserved, tenant_id)
+                 VALUES ($1, $2, $3, 1, $4)",
+            )
+            .bind(item_id)
+            .bind(location_id)
+            .bind(rust_decimal::Decimal::from(on_hand))
+            .bind(tenant_id)
+            .execute(admin)
+            .await
+            .unwrap();
+            item_ids.push(item_id);
+        }
+        let order_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO sales_orders (so_number, status, created_by, fulfillment_location_id, tenant_id)
+             VALUES ('SO-GQL-1', 'CONFIRMED', $1, $2, $3) RETURNING id",
+        )
+        .bind(Uuid::parse_str(SEEDED_USER).unwrap())
+        .bind(location_id)
+        .bind(tenant_id)
+        .fetch_one(admin)
+        .await
+        .unwrap();
+        // The first item twice, so its lines share one loaded item
+        for item_id in [item_ids[0], item_ids[1], item_ids[0]] {
+            sqlx::query(
+                "INSERT INTO sales_order_lines (so_id, item_id, qty, unit_price, tenant_id)
+                 VALUES ($1, $2, 2, 10, $3)",
+            )
+            .bind(order_id)
+            .bind(item_id)
+            .bind(tenant_id)
+            .execute(admin)
+            .await
+            .unwrap();
+        }
+
+        let repositories = || Repositories {
+            item_repository: Arc::new(PostgresItemRepository::new(Arc::clone(pool))),
+            location_repository: Arc::new(PostgresLocationRepository::new(Arc::clone(pool))),
+            stock_repository: Arc::new(PostgresStockRepository::new(Arc::clone(pool))),
+            sales_order_repository: Arc::new(PostgresSalesOrderRepository::new(Arc::clone(pool))),
+            purchase_order_repository: Arc::new(PostgresPurchaseOrderRepository::new(Arc::clone(
+                pool,
+            ))),
+        };
+        let response = run(
+            repositories(),
+            tenant_id,
+            &format!(
+                r#"{{
+                    order: salesOrder(id: "{order_id}") {{
+                        soNumber
+                        fulfillmentLocation {{ name }}
+                        lines {{
+                            qty
+                            item {{
+                                sku
+                                stockLevels {{ quantityOnHand availableToPromise location {{ name }} }}
+                            }}
+                        }}
+                    }}
+                    missing: item(id: "{missing}") {{ sku }}
+                    stockLevel(itemId: "{item}", locationId: "{location_id}") {{ __typename item {{ sku }} }}
+                }}"#,
+                missing = Uuid::new_v4(),
+                item = item_ids[1],
+            ),
+        )
+        .await;
+
+        let stock = |on_hand: f64| {
+            json!([{
+                "quantityOnHand": on_hand,
+                "availableToPromise": on_hand - 1.0,
+                "location": { "name": "GraphQL dock" },
+            }])
+        };
+        let mut lines = response["data"]["order"]["lines"]
+            .as_array()
+            .cloned()
+            .unwrap();
+        lines.sort_by_key(|line| line["item"]["sku"].as_str().unwrap().to_string());
+        assert_eq!(
+            lines,
+            [
+                json!({"qty": 2.0, "item": {"sku": "GQL-A", "stockLevels": stock(5.0)}}),
+                json!({"qty": 2.0, "item": {"sku": "GQL-A", "stockLevels": stock(5.0)}}),
+                json!({"qty": 2.0, "item": {"sku": "GQL-B", "stockLevels": stock(3.0)}}),
+            ]
+        );
+        assert_eq!(response["data"]["order"]["soNumber"], "SO-GQL-1");
+        assert_eq!(
+            response["data"]["order"]["fulfillmentLocation"]["name"],
+            "GraphQL dock"
+        );
+        assert_eq!(response["data"]["missing"], Value::Null);
+        assert_eq!(
+            response["data"]["stockLevel"],
+            json!({"__typename": "StockLevel", "item": {"sku": "GQL-B"}})
+        );
+        assert!(response.get("errors").is_none(), "{response}");
+
+        // A failing root field leaves the others resolved
+        let response = run(
+            repositories(),
+            tenant_id,
+            r#"{ salesOrder(id: "not-a-uuid") { id } locations { name } }"#,
+        )
+        .await;
+        assert_eq!(response["data"]["salesOrder"], Value::Null);
+        assert_eq!(
+            response["data"]["locations"],
+            json!([{"name": "GraphQL dock"}])
+        );
+        assert_eq!(response["errors"][0]["path"], json!(["salesOrder"]));
+        assert_eq!(
+            response["errors"][0]["extensions"]["code"],
+            "VALIDATION_ERROR"
+        );
+    }
+}
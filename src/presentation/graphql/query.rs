@@ -0,0 +1,226 @@
+use super::types::{
+    item, location, ItemNode, LocationNode, MovementNode, PageNode, PurchaseOrderNode,
+    SalesOrderNode, StockLevelNode,
+};
+use super::Repositories;
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::services::{
+    item_repository::ItemRepository, location_repository::LocationRepository,
+    purchase_order_repository::PurchaseOrderRepository,
+    sales_order_repository::SalesOrderRepository, stock_repository::StockRepository,
+};
+use crate::shared::api_error::ApiError;
+use crate::shared::pagination::PageRequest;
+use async_graphql::{Context, Object, Result, ID};
+use uuid::Uuid;
+
+fn repositories<'a>(ctx: &Context<'a>) -> &'a Repositories {
+    ctx.data_unchecked::<Repositories>()
+}
+
+fn parse_id(name: &str, id: &ID) -> Result<Uuid, ApiError> {
+    Uuid::parse_str(id.trim())
+        .map_err(|_| ApiError::bad_request(format!("{name} must be a UUID, got '{}'", id.as_str())))
+}
+
+fn parse_optional_id(name: &str, id: Option<ID>) -> Result<Option<Uuid>, ApiError> {
+    id.map(|id| parse_id(name, &id)).transpose()
+}
+
+/// Read-only queries for dashboards. Fields carry the same values as the REST
+/// API's JSON: decimals are numbers, timestamps RFC 3339 strings and statuses
+/// the REST status strings. Failures come back in `errors` with
+/// `extensions.code` set to the REST error code, e.g. `NOT_FOUND`.
+pub struct Query;
+
+#[Object]
+impl Query {
+    async fn item(&self, ctx: &Context<'_>, id: ID) -> Result<Option<ItemNode>> {
+        item(ctx, parse_id("id", &id)?).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn items(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i64>,
+        after: Option<String>,
+        sku: Option<String>,
+        category: Option<String>,
+        q: Option<String>,
+        sort: Option<String>,
+    ) -> Result<PageNode<ItemNode>> {
+        let filter = ListFilter {
+            sku,
+            category,
+            q,
+            sort,
+            ..ListFilter::default()
+        };
+        let page = repositories(ctx)
+            .item_repository
+            .list(&filter, &PageRequest::new(first, after))
+            .await
+            .map_err(ApiError::from)?;
+        Ok(PageNode::new(page, ItemNode))
+    }
+
+    async fn location(&self, ctx: &Context<'_>, id: ID) -> Result<Option<LocationNode>> {
+        location(ctx, parse_id("id", &id)?).await
+    }
+
+    async fn locations(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<Vec<LocationNode>> {
+        let locations = repositories(ctx)
+            .location_repository
+            .list(
+                limit.unwrap_or(50).clamp(1, 100),
+                offset.unwrap_or(0).max(0),
+            )
+            .await
+            .map_err(ApiError::from)?;
+        Ok(locations.into_iter().map(LocationNode).collect())
+    }
+
+    async fn stock_level(
+        &self,
+        ctx: &Context<'_>,
+        item_id: ID,
+        location_id: ID,
+    ) -> Result<Option<StockLevelNode>> {
+        let level = repositories(ctx)
+            .stock_repository
+            .get_stock_level(
+                parse_id("itemId", &item_id)?,
+                parse_id("locationId", &location_id)?,
+            )
+            .await
+            .map_err(ApiError::from)?;
+        Ok(level.map(StockLevelNode))
+    }
+
+    async fn sales_order(&self, ctx: &Context<'_>, id: ID) -> Result<Option<SalesOrderNode>> {
+        let order = repositories(ctx)
+            .sales_order_repository
+            .find_by_id(parse_id("id", &id)?)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(order.map(|(order, lines)| SalesOrderNode(order, lines)))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn sales_orders(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i64>,
+        after: Option<String>,
+        status: Option<String>,
+        customer_id: Option<ID>,
+        q: Option<String>,
+        sort: Option<String>,
+    ) -> Result<PageNode<SalesOrderNode>> {
+        let filter = ListFilter {
+            status,
+            customer_id: parse_optional_id("customerId", customer_id)?,
+            q,
+            sort,
+            ..ListFilter::default()
+        };
+        let page = repositories(ctx)
+            .sales_order_repository
+            .list(&filter, &PageRequest::new(first, after))
+            .await
+            .map_err(ApiError::from)?;
+        Ok(PageNode::new(page, |(order, lines)| {
+            SalesOrderNode(order, lines)
+        }))
+    }
+
+    async fn purchase_order(&self, ctx: &Context<'_>, id: ID) -> Result<Option<PurchaseOrderNode>> {
+        let order = repositories(ctx)
+            .purchase_order_repository
+            .find_by_id(parse_id("id", &id)?)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(order.map(PurchaseOrderNode))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn purchase_orders(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i64>,
+        after: Option<String>,
+        status: Option<String>,
+        supplier_id: Option<ID>,
+        q: Option<String>,
+        sort: Option<String>,
+    ) -> Result<PageNode<PurchaseOrderNode>> {
+        let filter = ListFilter {
+            status,
+            supplier_id: parse_optional_id("supplierId", supplier_id)?,
+            q,
+            sort,
+            ..ListFilter::default()
+        };
+        let page = repositories(ctx)
+            .purchase_order_repository
+            .list(&filter, &PageRequest::new(first, after))
+            .await
+            .map_err(ApiError::from)?;
+        Ok(PageNode::new(page, PurchaseOrderNode))
+    }
+
+    async fn movement(&self, ctx: &Context<'_>, id: ID) -> Result<Option<MovementNode>> {
+        let movement = repositories(ctx)
+            .stock_repository
+            .get_movement_by_id(parse_id("id", &id)?)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(movement.map(MovementNode))
+    }
+
+    /// Movements of an item, a location or both; one of `itemId` or
+    /// `locationId` is required
+    async fn movements(
+        &self,
+        ctx: &Context<'_>,
+        item_id: Option<ID>,
+        location_id: Option<ID>,
+        first: Option<i64>,
+        after: Option<String>,
+    ) -> Result<PageNode<MovementNode>> {
+        let stock_repository = &repositories(ctx).stock_repository;
+        let page_request = PageRequest::new(first, after);
+        let page = match (
+            parse_optional_id("itemId", item_id)?,
+            parse_optional_id("locationId", location_id)?,
+        ) {
+            (Some(item_id), Some(location_id)) => {
+                stock_repository
+                    .get_stock_movements(item_id, location_id, &page_request)
+                    .await
+            }
+            (Some(item_id), None) => {
+                stock_repository
+                    .get_item_movements(item_id, &page_request)
+                    .await
+            }
+            (None, Some(location_id)) => {
+                stock_repository
+                    .get_location_movements(location_id, &page_request)
+                    .await
+            }
+            (None, None) => {
+                return Err(
+                    ApiError::bad_request("movements needs an itemId or a locationId").into(),
+                )
+            }
+        };
+        Ok(PageNode::new(page.map_err(ApiError::from)?, MovementNode))
+    }
+}
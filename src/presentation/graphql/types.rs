@@ -0,0 +1,516 @@
+use super::loaders::Loaders;
+use crate::domain::entities::inventory::{StockLevel, StockMovement};
+use crate::domain::entities::item::Item;
+use crate::domain::entities::location::Location;
+use crate::domain::entities::purchase_order::{PurchaseOrder, PurchaseOrderLine};
+use crate::domain::entities::sales_order::{SalesOrder, SalesOrderLine};
+use crate::shared::api_error::ApiError;
+use crate::shared::pagination::Page;
+use async_graphql::{Context, Json, Object, OutputType, Result, SimpleObject, ID};
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Decimals are JSON numbers, as in the REST API
+fn float(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+/// An enum as the REST API spells it
+fn rest_name(value: &impl Serialize) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn loaders<'a>(ctx: &Context<'a>) -> &'a Loaders {
+    ctx.data_unchecked::<Loaders>()
+}
+
+pub(super) async fn item(ctx: &Context<'_>, id: Uuid) -> Result<Option<ItemNode>> {
+    let item = loaders(ctx)
+        .items
+        .load_one(id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(item.map(ItemNode))
+}
+
+pub(super) async fn location(ctx: &Context<'_>, id: Uuid) -> Result<Option<LocationNode>> {
+    let location = loaders(ctx)
+        .locations
+        .load_one(id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(location.map(LocationNode))
+}
+
+fn stock_levels(levels: Option<Vec<StockLevel>>) -> Vec<StockLevelNode> {
+    levels
+        .unwrap_or_default()
+        .into_iter()
+        .map(StockLevelNode)
+        .collect()
+}
+
+/// A page of a list field. Pass `nextCursor` as `after` to get the next one.
+#[derive(SimpleObject)]
+#[graphql(
+    concrete(name = "ItemPage", params(ItemNode)),
+    concrete(name = "MovementPage", params(MovementNode)),
+    concrete(name = "SalesOrderPage", params(SalesOrderNode)),
+    concrete(name = "PurchaseOrderPage", params(PurchaseOrderNode))
+)]
+pub struct PageNode<T: OutputType> {
+    pub nodes: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+impl<T: OutputType> PageNode<T> {
+    pub fn new<U>(page: Page<U>, node: impl FnMut(U) -> T) -> Self {
+        Self {
+            nodes: page.data.into_iter().map(node).collect(),
+            next_cursor: page.cursor.next_cursor,
+            has_more: page.cursor.has_more,
+        }
+    }
+}
+
+pub struct ItemNode(pub Item);
+
+#[Object(name = "Item")]
+impl ItemNode {
+    async fn id(&self) -> ID {
+        self.0.id.into()
+    }
+
+    async fn sku(&self) -> &str {
+        &self.0.sku
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn category(&self) -> Option<&str> {
+        self.0.category.as_deref()
+    }
+
+    async fn unit(&self) -> &str {
+        &self.0.unit
+    }
+
+    async fn barcode(&self) -> Option<&str> {
+        self.0.barcode.as_deref()
+    }
+
+    async fn cost_price(&self) -> f64 {
+        float(self.0.cost_price)
+    }
+
+    async fn sale_price(&self) -> Option<f64> {
+        self.0.sale_price.map(float)
+    }
+
+    async fn reorder_point(&self) -> Option<f64> {
+        self.0.reorder_point.map(float)
+    }
+
+    async fn reorder_qty(&self) -> Option<f64> {
+        self.0.reorder_qty.map(float)
+    }
+
+    async fn quantity_precision(&self) -> u32 {
+        self.0.quantity_precision
+    }
+
+    async fn weight(&self) -> Option<f64> {
+        self.0.weight
+    }
+
+    async fn metadata(&self) -> Option<Json<serde_json::Value>> {
+        self.0.metadata.clone().map(Json)
+    }
+
+    async fn product_id(&self) -> Option<ID> {
+        self.0.product_id.map(ID::from)
+    }
+
+    async fn active(&self) -> bool {
+        self.0.active
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn stock_levels(&self, ctx: &Context<'_>) -> Result<Vec<StockLevelNode>> {
+        let levels = loaders(ctx)
+            .item_stock_levels
+            .load_one(self.0.id)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(stock_levels(levels))
+    }
+}
+
+pub struct LocationNode(pub Location);
+
+#[Object(name = "Location")]
+impl LocationNode {
+    async fn id(&self) -> ID {
+        self.0.id.into()
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn code(&self) -> Option<&str> {
+        self.0.code.as_deref()
+    }
+
+    #[graphql(name = "type")]
+    async fn location_type(&self) -> Option<String> {
+        self.0.r#type.as_ref().map(rest_name)
+    }
+
+    async fn active(&self) -> bool {
+        self.0.active
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn stock_levels(&self, ctx: &Context<'_>) -> Result<Vec<StockLevelNode>> {
+        let levels = loaders(ctx)
+            .location_stock_levels
+            .load_one(self.0.id)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(stock_levels(levels))
+    }
+}
+
+pub struct StockLevelNode(pub StockLevel);
+
+#[Object(name = "StockLevel")]
+impl StockLevelNode {
+    async fn item_id(&self) -> ID {
+        self.0.item_id.into()
+    }
+
+    async fn location_id(&self) -> ID {
+        self.0.location_id.into()
+    }
+
+    async fn quantity_on_hand(&self) -> f64 {
+        float(self.0.quantity_on_hand)
+    }
+
+    async fn quantity_reserved(&self) -> f64 {
+        float(self.0.quantity_reserved)
+    }
+
+    async fn quantity_quarantine(&self) -> f64 {
+        float(self.0.quantity_quarantine)
+    }
+
+    async fn quantity_damaged(&self) -> f64 {
+        float(self.0.quantity_damaged)
+    }
+
+    async fn quantity_in_transit(&self) -> f64 {
+        float(self.0.quantity_in_transit)
+    }
+
+    async fn available_to_promise(&self) -> f64 {
+        float(self.0.available_to_promise())
+    }
+
+    async fn last_movement_id(&self) -> Option<ID> {
+        self.0.last_movement_id.map(ID::from)
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn item(&self, ctx: &Context<'_>) -> Result<Option<ItemNode>> {
+        item(ctx, self.0.item_id).await
+    }
+
+    async fn location(&self, ctx: &Context<'_>) -> Result<Option<LocationNode>> {
+        location(ctx, self.0.location_id).await
+    }
+}
+
+pub struct MovementNode(pub StockMovement);
+
+#[Object(name = "Movement")]
+impl MovementNode {
+    async fn id(&self) -> ID {
+        self.0.id.into()
+    }
+
+    async fn item_id(&self) -> ID {
+        self.0.item_id.into()
+    }
+
+    async fn location_id(&self) -> ID {
+        self.0.location_id.into()
+    }
+
+    async fn movement_type(&self) -> String {
+        rest_name(&self.0.movement_type)
+    }
+
+    async fn quantity(&self) -> f64 {
+        float(self.0.quantity)
+    }
+
+    async fn stock_status(&self) -> String {
+        rest_name(&self.0.stock_status)
+    }
+
+    async fn reference_type(&self) -> String {
+        rest_name(&self.0.reference_type)
+    }
+
+    async fn reference_id(&self) -> Option<ID> {
+        self.0.reference_id.map(ID::from)
+    }
+
+    async fn reason(&self) -> Option<&str> {
+        self.0.reason.as_deref()
+    }
+
+    async fn created_by(&self) -> Option<ID> {
+        self.0.created_by.map(ID::from)
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn item(&self, ctx: &Context<'_>) -> Result<Option<ItemNode>> {
+        item(ctx, self.0.item_id).await
+    }
+
+    async fn location(&self, ctx: &Context<'_>) -> Result<Option<LocationNode>> {
+        location(ctx, self.0.location_id).await
+    }
+}
+
+pub struct SalesOrderNode(pub SalesOrder, pub Vec<SalesOrderLine>);
+
+#[Object(name = "SalesOrder")]
+impl SalesOrderNode {
+    async fn id(&self) -> ID {
+        self.0.id.into()
+    }
+
+    async fn so_number(&self) -> &str {
+        &self.0.so_number
+    }
+
+    async fn customer_id(&self) -> Option<ID> {
+        self.0.customer_id.map(ID::from)
+    }
+
+    async fn status(&self) -> String {
+        rest_name(&self.0.status)
+    }
+
+    async fn total_amount(&self) -> f64 {
+        float(self.0.total_amount)
+    }
+
+    async fn currency(&self) -> Option<&str> {
+        self.0.currency.as_deref()
+    }
+
+    async fn exchange_rate(&self) -> Option<f64> {
+        self.0.exchange_rate.map(float)
+    }
+
+    async fn fulfillment_location_id(&self) -> Option<ID> {
+        self.0.fulfillment_location_id.map(ID::from)
+    }
+
+    async fn cancellation_reason(&self) -> Option<&str> {
+        self.0.cancellation_reason.as_deref()
+    }
+
+    async fn cancelled_at(&self) -> Option<DateTime<Utc>> {
+        self.0.cancelled_at
+    }
+
+    async fn created_by(&self) -> ID {
+        self.0.created_by.into()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn fulfillment_location(&self, ctx: &Context<'_>) -> Result<Option<LocationNode>> {
+        match self.0.fulfillment_location_id {
+            Some(id) => location(ctx, id).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn lines(&self) -> Vec<SalesOrderLineNode> {
+        self.1.iter().cloned().map(SalesOrderLineNode).collect()
+    }
+}
+
+pub struct SalesOrderLineNode(pub SalesOrderLine);
+
+#[Object(name = "SalesOrderLine")]
+impl SalesOrderLineNode {
+    async fn id(&self) -> ID {
+        self.0.id.into()
+    }
+
+    async fn item_id(&self) -> ID {
+        self.0.item_id.into()
+    }
+
+    async fn qty(&self) -> f64 {
+        float(self.0.qty)
+    }
+
+    async fn qty_shipped(&self) -> f64 {
+        float(self.0.qty_shipped)
+    }
+
+    async fn unit_price(&self) -> f64 {
+        float(self.0.unit_price)
+    }
+
+    async fn tax(&self) -> f64 {
+        float(self.0.tax)
+    }
+
+    async fn reserved(&self) -> bool {
+        self.0.reserved
+    }
+
+    async fn item(&self, ctx: &Context<'_>) -> Result<Option<ItemNode>> {
+        item(ctx, self.0.item_id).await
+    }
+}
+
+pub struct PurchaseOrderNode(pub PurchaseOrder);
+
+#[Object(name = "PurchaseOrder")]
+impl PurchaseOrderNode {
+    async fn id(&self) -> ID {
+        self.0.id.into()
+    }
+
+    async fn po_number(&self) -> &str {
+        &self.0.po_number
+    }
+
+    async fn supplier_id(&self) -> ID {
+        self.0.supplier_id.into()
+    }
+
+    async fn status(&self) -> String {
+        rest_name(&self.0.status)
+    }
+
+    async fn expected_date(&self) -> Option<DateTime<Utc>> {
+        self.0.expected_date
+    }
+
+    async fn total_amount(&self) -> f64 {
+        float(self.0.total_amount)
+    }
+
+    async fn currency(&self) -> Option<&str> {
+        self.0.currency.as_deref()
+    }
+
+    async fn exchange_rate(&self) -> Option<f64> {
+        self.0.exchange_rate.map(float)
+    }
+
+    async fn created_by(&self) -> ID {
+        self.0.created_by.into()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn lines(&self) -> Vec<PurchaseOrderLineNode> {
+        self.0
+            .lines
+            .iter()
+            .cloned()
+            .map(PurchaseOrderLineNode)
+            .collect()
+    }
+}
+
+pub struct PurchaseOrderLineNode(pub PurchaseOrderLine);
+
+#[Object(name = "PurchaseOrderLine")]
+impl PurchaseOrderLineNode {
+    async fn id(&self) -> ID {
+        self.0.id.into()
+    }
+
+    async fn item_id(&self) -> ID {
+        self.0.item_id.into()
+    }
+
+    async fn qty_ordered(&self) -> f64 {
+        float(self.0.qty_ordered)
+    }
+
+    async fn qty_received(&self) -> f64 {
+        float(self.0.qty_received)
+    }
+
+    async fn unit_cost(&self) -> f64 {
+        float(self.0.unit_cost)
+    }
+
+    async fn line_total(&self) -> f64 {
+        float(self.0.line_total)
+    }
+
+    async fn item(&self, ctx: &Context<'_>) -> Result<Option<ItemNode>> {
+        item(ctx, self.0.item_id).await
+    }
+}
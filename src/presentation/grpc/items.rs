@@ -0,0 +1,189 @@
+use super::proto;
+use super::proto::item_service_server;
+use super::server::{parse_decimal, parse_json, parse_optional_decimal, tenant_context, timestamp};
+use crate::application::use_cases::{
+    create_item::{CreateItemRequest, CreateItemResponse, CreateItemUseCase},
+    get_item::{GetItemRequest, GetItemResponse, GetItemUseCase},
+    list_items::{ItemSummary, ListItemsRequest, ListItemsUseCase},
+};
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcherImpl;
+use crate::infrastructure::controllers::items_controller::{item_error, parse_item_id};
+use crate::infrastructure::repositories::postgres_item_repository::PostgresItemRepository;
+use crate::infrastructure::repositories::postgres_webhook_repository::PostgresWebhookRepository;
+use crate::shared::api_error::ApiError;
+use crate::shared::pagination::PageRequest;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+/// `twh.v1.ItemService`, over the use cases behind `/items`
+#[derive(Clone)]
+pub struct ItemService {
+    get_item_use_case: Arc<GetItemUseCase<PostgresItemRepository>>,
+    list_items_use_case: Arc<ListItemsUseCase<PostgresItemRepository>>,
+    create_item_use_case: Arc<
+        CreateItemUseCase<PostgresItemRepository, WebhookDispatcherImpl<PostgresWebhookRepository>>,
+    >,
+}
+
+impl ItemService {
+    pub fn new(
+        get_item_use_case: Arc<GetItemUseCase<PostgresItemRepository>>,
+        list_items_use_case: Arc<ListItemsUseCase<PostgresItemRepository>>,
+        create_item_use_case: Arc<
+            CreateItemUseCase<
+                PostgresItemRepository,
+                WebhookDispatcherImpl<PostgresWebhookRepository>,
+            >,
+        >,
+    ) -> Self {
+        Self {
+            get_item_use_case,
+            list_items_use_case,
+            create_item_use_case,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl item_service_server::ItemService for ItemService {
+    async fn get_item(
+        &self,
+        request: Request<proto::GetItemRequest>,
+    ) -> Result<Response<proto::Item>, Status> {
+        let id = parse_item_id(&request.into_inner().id)?;
+        let item = self
+            .get_item_use_case
+            .execute(GetItemRequest { id })
+            .await
+            .map_err(item_error)?;
+        Ok(Response::new(item.into()))
+    }
+
+    async fn list_items(
+        &self,
+        request: Request<proto::ListItemsRequest>,
+    ) -> Result<Response<proto::ListItemsResponse>, Status> {
+        let request = request.into_inner();
+        let page = self
+            .list_items_use_case
+            .execute(ListItemsRequest {
+                page: PageRequest::new(request.limit, request.cursor),
+                filter: ListFilter {
+                    sku: request.sku,
+                    category: request.category,
+                    q: request.q,
+                    sort: request.sort,
+                    ..ListFilter::default()
+                },
+            })
+            .await
+            .map_err(ApiError::from)?;
+        Ok(Response::new(proto::ListItemsResponse {
+            items: page.data.into_iter().map(Into::into).collect(),
+            next_cursor: page.cursor.next_cursor,
+            has_more: page.cursor.has_more,
+            total_count: page.total_count,
+        }))
+    }
+
+    async fn create_item(
+        &self,
+        request: Request<proto::CreateItemRequest>,
+    ) -> Result<Response<proto::CreateItemResponse>, Status> {
+        let tenant_id = tenant_context(&request)?.tenant_id;
+        let request = request.into_inner();
+        let cost_price = parse_decimal("cost_price", &request.cost_price)?;
+        let request = CreateItemRequest {
+            sku: request.sku,
+            name: request.name,
+            description: request.description,
+            category: request.category,
+            unit: request.unit,
+            barcode: request.barcode,
+            cost_price,
+            sale_price: parse_optional_decimal("sale_price", request.sale_price.as_deref())?,
+            reorder_point: parse_optional_decimal(
+                "reorder_point",
+                request.reorder_point.as_deref(),
+            )?,
+            reorder_qty: parse_optional_decimal("reorder_qty", request.reorder_qty.as_deref())?,
+            quantity_precision: request.quantity_precision,
+            weight: request.weight,
+            // As over REST, dimensions that don't describe a box are dropped
+            dimensions: parse_json("dimensions", request.dimensions.as_deref())?
+                .and_then(|d| serde_json::from_value(d).ok()),
+            metadata: parse_json("metadata", request.metadata.as_deref())?,
+        };
+        let item = self
+            .create_item_use_case
+            .execute(request, tenant_id)
+            .await
+            .map_err(item_error)?;
+        Ok(Response::new(item.into()))
+    }
+}
+
+impl From<GetItemResponse> for proto::Item {
+    fn from(item: GetItemResponse) -> Self {
+        Self {
+            id: item.id.to_string(),
+            sku: item.sku,
+            name: item.name,
+            description: item.description,
+            category: item.category,
+            unit: item.unit,
+            barcode: item.barcode,
+            cost_price: item.cost_price.to_string(),
+            sale_price: item.sale_price.map(|p| p.to_string()),
+            reorder_point: item.reorder_point.map(|q| q.to_string()),
+            reorder_qty: item.reorder_qty.map(|q| q.to_string()),
+            quantity_precision: item.quantity_precision,
+            weight: item.weight,
+            dimensions: item.dimensions.map(|d| d.to_string()),
+            metadata: item.metadata.map(|m| m.to_string()),
+            product_id: item.product_id.map(|id| id.to_string()),
+            variant_attributes: item
+                .variant_attributes
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+            active: item.active,
+            created_at: timestamp(item.created_at),
+            updated_at: timestamp(item.updated_at),
+        }
+    }
+}
+
+impl From<ItemSummary> for proto::ItemSummary {
+    fn from(item: ItemSummary) -> Self {
+        Self {
+            id: item.id.to_string(),
+            sku: item.sku,
+            name: item.name,
+            category: item.category,
+            unit: item.unit,
+            cost_price: item.cost_price.to_string(),
+            sale_price: item.sale_price.map(|p| p.to_string()),
+            product_id: item.product_id.map(|id| id.to_string()),
+            active: item.active,
+            created_at: timestamp(item.created_at),
+            updated_at: timestamp(item.updated_at),
+        }
+    }
+}
+
+impl From<CreateItemResponse> for proto::CreateItemResponse {
+    fn from(item: CreateItemResponse) -> Self {
+        Self {
+            id: item.id.to_string(),
+            sku: item.sku,
+            name: item.name,
+            unit: item.unit,
+            cost_price: item.cost_price.to_string(),
+            active: item.active,
+            created_at: timestamp(item.created_at),
+            updated_at: timestamp(item.updated_at),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! gRPC API for internal services, served on its own port next to the REST
+//! API and backed by the same use cases. The contract is
+//! `proto/twh/v1/warehouse.proto`.
+
+pub mod items;
+pub mod orders;
+#[cfg(test)]
+mod parity_tests;
+pub mod proto;
+pub mod server;
+pub mod stock;
+
+use crate::application::use_cases::get_sales_order::GetSalesOrderUseCase;
+use crate::infrastructure::middleware::tenant_middleware::TenantMiddleware;
+use crate::infrastructure::repositories::postgres_sales_order_repository::PostgresSalesOrderRepository;
+use crate::AppState;
+use items::ItemService;
+use orders::OrderService;
+use proto::{
+    item_service_server::ItemServiceServer, order_service_server::OrderServiceServer,
+    stock_service_server::StockServiceServer,
+};
+use server::TenantScoped;
+use std::sync::Arc;
+use stock::StockService;
+use tonic::transport::server::Router;
+use tonic::transport::Server;
+
+/// The item, stock and order services over the application's use cases
+pub fn grpc_router(state: &AppState) -> Router {
+    services_router(
+        ItemService::new(
+            Arc::clone(&state.get_item_use_case),
+            Arc::clone(&state.list_items_use_case),
+            Arc::clone(&state.create_item_use_case),
+        ),
+        StockService::new(
+            Arc::clone(&state.get_stock_level_use_case),
+            Arc::clone(&state.list_item_stock_levels_use_case),
+        ),
+        OrderService::new(
+            Arc::clone(&state.get_purchase_order_use_case),
+            Arc::new(GetSalesOrderUseCase::new(
+                PostgresSalesOrderRepository::new(Arc::clone(&state.pool)),
+            )),
+            Arc::clone(&state.create_sales_order_use_case),
+        ),
+        Arc::clone(&state.tenant_middleware),
+    )
+}
+
+fn services_router(
+    items: ItemService,
+    stock: StockService,
+    orders: OrderService,
+    tenant_middleware: Arc<TenantMiddleware>,
+) -> Router {
+    Server::builder()
+        .add_service(TenantScoped::new(
+            ItemServiceServer::new(items),
+            Arc::clone(&tenant_middleware),
+        ))
+        .add_service(TenantScoped::new(
+            StockServiceServer::new(stock),
+            Arc::clone(&tenant_middleware),
+        ))
+        .add_service(TenantScoped::new(
+            OrderServiceServer::new(orders),
+            tenant_middleware,
+        ))
+}
@@ -0,0 +1,187 @@
+use super::proto;
+use super::proto::order_service_server;
+use super::server::{parse_decimal, parse_optional_uuid, parse_uuid, tenant_context, timestamp};
+use crate::application::use_cases::{
+    create_sales_order::{
+        CreateSalesOrderLineRequest, CreateSalesOrderRequest, CreateSalesOrderUseCase,
+    },
+    get_purchase_order::{GetPurchaseOrderResponse, GetPurchaseOrderUseCase},
+    get_sales_order::GetSalesOrderUseCase,
+};
+use crate::domain::entities::sales_order::{SalesOrder, SalesOrderLine};
+use crate::domain::services::webhook_dispatcher::WebhookDispatcherImpl;
+use crate::infrastructure::repositories::{
+    postgres_purchase_order_repository::PostgresPurchaseOrderRepository,
+    postgres_sales_order_repository::PostgresSalesOrderRepository,
+    postgres_webhook_repository::PostgresWebhookRepository,
+};
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
+use rust_decimal::prelude::ToPrimitive;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+/// `twh.v1.OrderService`, over the use cases behind `/purchase_orders` and
+/// `/sales_orders`
+#[derive(Clone)]
+pub struct OrderService {
+    get_purchase_order_use_case: Arc<GetPurchaseOrderUseCase<PostgresPurchaseOrderRepository>>,
+    get_sales_order_use_case: Arc<GetSalesOrderUseCase<PostgresSalesOrderRepository>>,
+    create_sales_order_use_case: Arc<
+        CreateSalesOrderUseCase<
+            PostgresSalesOrderRepository,
+            WebhookDispatcherImpl<PostgresWebhookRepository>,
+        >,
+    >,
+}
+
+impl OrderService {
+    pub fn new(
+        get_purchase_order_use_case: Arc<GetPurchaseOrderUseCase<PostgresPurchaseOrderRepository>>,
+        get_sales_order_use_case: Arc<GetSalesOrderUseCase<PostgresSalesOrderRepository>>,
+        create_sales_order_use_case: Arc<
+            CreateSalesOrderUseCase<
+                PostgresSalesOrderRepository,
+                WebhookDispatcherImpl<PostgresWebhookRepository>,
+            >,
+        >,
+    ) -> Self {
+        Self {
+            get_purchase_order_use_case,
+            get_sales_order_use_case,
+            create_sales_order_use_case,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl order_service_server::OrderService for OrderService {
+    async fn get_purchase_order(
+        &self,
+        request: Request<proto::GetPurchaseOrderRequest>,
+    ) -> Result<Response<proto::PurchaseOrder>, Status> {
+        let purchase_order = self
+            .get_purchase_order_use_case
+            .execute(parse_uuid("id", &request.get_ref().id)?)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(Response::new(purchase_order.into()))
+    }
+
+    async fn get_sales_order(
+        &self,
+        request: Request<proto::GetSalesOrderRequest>,
+    ) -> Result<Response<proto::SalesOrder>, Status> {
+        let response = self
+            .get_sales_order_use_case
+            .execute(parse_uuid("id", &request.get_ref().id)?)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(Response::new(sales_order(
+            response.sales_order,
+            response.lines,
+            response.etag,
+        )))
+    }
+
+    async fn create_sales_order(
+        &self,
+        request: Request<proto::CreateSalesOrderRequest>,
+    ) -> Result<Response<proto::SalesOrder>, Status> {
+        let created_by = acting_user(&tenant_context(&request)?);
+        let request = request.into_inner();
+        let lines = request
+            .lines
+            .iter()
+            .map(|line| {
+                Ok(CreateSalesOrderLineRequest {
+                    item_id: parse_uuid("lines.item_id", &line.item_id)?,
+                    qty: parse_decimal("lines.qty", &line.qty)?,
+                    unit_price: parse_decimal("lines.unit_price", &line.unit_price)?,
+                })
+            })
+            .collect::<Result<Vec<_>, ApiError>>()?;
+        let request = CreateSalesOrderRequest {
+            customer_id: parse_optional_uuid("customer_id", request.customer_id.as_deref())?,
+            lines,
+            should_reserve: request.should_reserve,
+            fulfillment_location_id: parse_optional_uuid(
+                "fulfillment_location_id",
+                request.fulfillment_location_id.as_deref(),
+            )?,
+            currency: request.currency,
+            override_credit_limit: false,
+        };
+        let response = self
+            .create_sales_order_use_case
+            .execute(request, created_by)
+            .await
+            .map_err(ApiError::from)?;
+        let mut order = response.sales_order;
+        let lines = std::mem::take(&mut order.lines);
+        let etag = order.etag();
+        Ok(Response::new(sales_order(order, lines, etag)))
+    }
+}
+
+impl From<GetPurchaseOrderResponse> for proto::PurchaseOrder {
+    fn from(po: GetPurchaseOrderResponse) -> Self {
+        Self {
+            id: po.id.to_string(),
+            po_number: po.po_number,
+            supplier_id: po.supplier_id.to_string(),
+            status: po.status,
+            expected_date: po.expected_date.map(timestamp),
+            total_amount: po.total_amount.to_string(),
+            currency: po.currency,
+            exchange_rate: po.exchange_rate.and_then(|rate| rate.to_f64()),
+            lines: po
+                .lines
+                .into_iter()
+                .map(|line| proto::PurchaseOrderLine {
+                    id: line.id.to_string(),
+                    item_id: line.item_id.to_string(),
+                    qty_ordered: line.qty_ordered.to_string(),
+                    qty_received: line.qty_received.to_string(),
+                    unit_cost: line.unit_cost.to_string(),
+                    line_total: line.line_total.to_string(),
+                })
+                .collect(),
+            created_by: po.created_by.to_string(),
+            created_at: timestamp(po.created_at),
+            updated_at: timestamp(po.updated_at),
+            etag: po.etag,
+        }
+    }
+}
+
+fn sales_order(order: SalesOrder, lines: Vec<SalesOrderLine>, etag: String) -> proto::SalesOrder {
+    proto::SalesOrder {
+        id: order.id.to_string(),
+        so_number: order.so_number,
+        customer_id: order.customer_id.map(|id| id.to_string()),
+        status: order.status.as_str().to_string(),
+        total_amount: order.total_amount.to_string(),
+        currency: order.currency,
+        exchange_rate: order.exchange_rate.and_then(|rate| rate.to_f64()),
+        fulfillment_location_id: order.fulfillment_location_id.map(|id| id.to_string()),
+        lines: lines
+            .into_iter()
+            .map(|line| proto::SalesOrderLine {
+                id: line.id.to_string(),
+                item_id: line.item_id.to_string(),
+                qty: line.qty.to_string(),
+                qty_shipped: line.qty_shipped.to_string(),
+                unit_price: line.unit_price.to_string(),
+                tax: line.tax.to_string(),
+                reserved: line.reserved,
+            })
+            .collect(),
+        cancellation_reason: order.cancellation_reason,
+        cancelled_at: order.cancelled_at.map(timestamp),
+        created_by: order.created_by.to_string(),
+        created_at: timestamp(order.created_at),
+        updated_at: timestamp(order.updated_at),
+        etag,
+    }
+}
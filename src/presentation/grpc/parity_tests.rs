@@ -0,0 +1,451 @@
+//! Calls the gRPC services over a real connection and checks they answer the
+//! way the REST endpoints over the same use cases do. They seed a throwaway
+//! tenant, so they only run on request:
+//!
+//! `cargo test grpc::parity_tests -- --ignored`
+//!
+//! with `DATABASE_URL` pointing at a migrated database.
+
+use super::items::ItemService;
+use super::orders::OrderService;
+use super::proto;
+use super::services_router;
+use super::stock::StockService;
+use crate::application::use_cases::{
+    create_item::CreateItemUseCase,
+    create_sales_order::CreateSalesOrderUseCase,
+    get_item::{GetItemRequest, GetItemUseCase},
+    get_purchase_order::GetPurchaseOrderUseCase,
+    get_sales_order::GetSalesOrderUseCase,
+    get_stock_level::GetStockLevelUseCase,
+    list_item_stock_levels::ListItemStockLevelsUseCase,
+    list_items::ListItemsUseCase,
+};
+use crate::domain::services::currency_service::{CurrencyService, CurrencyServiceImpl};
+use crate::domain::services::event_broadcaster::EventBroadcaster;
+use crate::domain::services::quota_service::QuotaService;
+use crate::domain::services::webhook_dispatcher::WebhookDispatcherImpl;
+use crate::domain::services::webhook_url_policy::WebhookUrlPolicy;
+use crate::infrastructure::middleware::tenant_middleware::{TenantMiddleware, TENANT_ID_HEADER};
+use crate::infrastructure::repositories::{
+    postgres_customer_credit_repository::PostgresCustomerCreditRepository,
+    postgres_exchange_rate_repository::PostgresExchangeRateRepository,
+    postgres_item_repository::PostgresItemRepository,
+    postgres_location_repository::PostgresLocationRepository,
+    postgres_purchase_order_repository::PostgresPurchaseOrderRepository,
+    postgres_sales_order_repository::PostgresSalesOrderRepository,
+    postgres_session_repository::PostgresSessionRepository,
+    postgres_stock_repository::PostgresStockRepository,
+    postgres_tenant_repository::PostgresTenantRepository,
+    postgres_user_repository::PostgresUserRepository,
+    postgres_webhook_repository::PostgresWebhookRepository,
+};
+use crate::infrastructure::services::postgres_quota_service::PostgresQuotaService;
+use crate::shared::tenant_scope::with_tenant;
+use crate::test_support::database::TestDatabase;
+use axum::http::uri::PathAndQuery;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+use tonic::codec::ProstCodec;
+use tonic::transport::server::TcpIncoming;
+use tonic::transport::Channel;
+use tonic::{Code, Status};
+use uuid::Uuid;
+
+/// A throwaway tenant with one location, and the gRPC server over its data
+struct Fixture {
+    /// Removes the tenant once the fixture is dropped
+    _db: TestDatabase,
+    tenant_id: Uuid,
+    location_id: Uuid,
+    get_item_use_case: Arc<GetItemUseCase<PostgresItemRepository>>,
+    channel: Channel,
+    shutdown: CancellationToken,
+}
+
+impl Fixture {
+    async fn start() -> Self {
+        let db = TestDatabase::connect().await;
+        let pool = Arc::clone(&db.pool);
+        let tenant_id = db.create_tenant("gRPC parity").await;
+        let location_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO locations (name, tenant_id) VALUES ('gRPC dock', $1) RETURNING id",
+        )
+        .bind(tenant_id)
+        .fetch_one(&db.admin)
+        .await
+        .unwrap();
+
+        let item_repository = Arc::new(PostgresItemRepository::new(Arc::clone(&pool)));
+        let stock_repository = Arc::new(PostgresStockRepository::new(Arc::clone(&pool)));
+        let location_repository = Arc::new(PostgresLocationRepository::new(Arc::clone(&pool)));
+        let tenant_repository = Arc::new(PostgresTenantRepository::new((*pool).clone()));
+        let webhook_dispatcher = Arc::new(WebhookDispatcherImpl::new(
+            Arc::new(PostgresWebhookRepository::new(Arc::clone(&pool))),
+            Arc::new(EventBroadcaster::default()),
+            Arc::new(WebhookUrlPolicy::default()),
+        ));
+        let quota_service: Arc<dyn QuotaService> =
+            Arc::new(PostgresQuotaService::new(Arc::clone(&pool)));
+        let currency_service: Arc<dyn CurrencyService> = Arc::new(CurrencyServiceImpl::new(
+            tenant_repository.clone(),
+            Arc::new(PostgresExchangeRateRepository::new(Arc::clone(&pool))),
+        ));
+        let get_item_use_case = Arc::new(GetItemUseCase::new(Arc::clone(&item_repository)));
+
+        let router = services_router(
+            ItemService::new(
+                Arc::clone(&get_item_use_case),
+                Arc::new(ListItemsUseCase::new(Arc::clone(&item_repository))),
+                Arc::new(CreateItemUseCase::new(
+                    Arc::clone(&item_repository),
+                    Arc::clone(&webhook_dispatcher),
+                    quota_service,
+                )),
+            ),
+            StockService::new(
+                Arc::new(GetStockLevelUseCase::new(
+                    Arc::clone(&stock_repository),
+                    Arc::clone(&item_repository),
+                    Arc::clone(&location_repository),
+                )),
+                Arc::new(ListItemStockLevelsUseCase::new(
+                    stock_repository,
+                    Arc::clone(&item_repository),
+                    location_repository,
+                )),
+            ),
+            OrderService::new(
+                Arc::new(GetPurchaseOrderUseCase::new(Arc::new(
+                    PostgresPurchaseOrderRepository::new(Arc::clone(&pool)),
+                ))),
+                Arc::new(GetSalesOrderUseCase::new(
+                    PostgresSalesOrderRepository::new(Arc::clone(&pool)),
+                )),
+                Arc::new(CreateSalesOrderUseCase::new(
+                    Arc::new(PostgresSalesOrderRepository::new(Arc::clone(&pool))),
+                    currency_service,
+                    item_repository,
+                    Arc::new(PostgresCustomerCreditRepository::new(Arc::clone(&pool))),
+                    Arc::new(PostgresUserRepository::new(Arc::clone(&pool))),
+                    webhook_dispatcher,
+                )),
+            ),
+            Arc::new(TenantMiddleware::new(
+                "parity-secret".to_string(),
+                true,
+                tenant_repository,
+                Arc::new(PostgresSessionRepository::new(Arc::clone(&pool))),
+            )),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+        let shutdown = CancellationToken::new();
+        tokio::spawn(
+            router.serve_with_incoming_shutdown(incoming, shutdown.clone().cancelled_owned()),
+        );
+        let channel = Channel::from_shared(format!("http://{}", addr))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+
+        Self {
+            _db: db,
+            tenant_id,
+            location_id,
+            get_item_use_case,
+            channel,
+            shutdown,
+        }
+    }
+
+    /// Call `path` as the fixture's tenant, or as no tenant at all
+    async fn call<Req, Res>(
+        &self,
+        path: &'static str,
+        message: Req,
+        as_tenant: bool,
+    ) -> Result<Res, Status>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Res: prost::Message + Default + Send + Sync + 'static,
+    {
+        let mut client = tonic::client::Grpc::new(self.channel.clone());
+        client.ready().await.unwrap();
+        let mut request = tonic::Request::new(message);
+        if as_tenant {
+            request.metadata_mut().insert(
+                TENANT_ID_HEADER,
+                self.tenant_id.to_string().parse().unwrap(),
+            );
+        }
+        client
+            .unary(
+                request,
+                PathAndQuery::from_static(path),
+                ProstCodec::<Req, Res>::default(),
+            )
+            .await
+            .map(tonic::Response::into_inner)
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+    }
+}
+
+/// REST error code a failed call carries
+fn error_code(status: &Status) -> &str {
+    status
+        .metadata()
+        .get("error-code")
+        .and_then(|code| code.to_str().ok())
+        .unwrap_or_default()
+}
+
+fn create_item_request(sku: &str) -> proto::CreateItemRequest {
+    proto::CreateItemRequest {
+        sku: sku.to_string(),
+        name: "gRPC widget".to_string(),
+        unit: "each".to_string(),
+        cost_price: "2.50".to_string(),
+        sale_price: Some("4.00".to_string()),
+        metadata: Some(r#"{"source":"grpc"}"#.to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs a migrated database at DATABASE_URL"]
+async fn test_items_match_rest() {
+    let fixture = Fixture::start().await;
+    let sku = format!("GRPC-{}", Uuid::new_v4().simple());
+
+    // Without a tenant the call is refused, as REST answers 401
+    let status = fixture
+        .call::<_, proto::ListItemsResponse>(
+            "/twh.v1.ItemService/ListItems",
+            proto::ListItemsRequest::default(),
+            false,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    let created: proto::CreateItemResponse = fixture
+        .call(
+            "/twh.v1.ItemService/CreateItem",
+            create_item_request(&sku),
+            true,
+        )
+        .await
+        .unwrap();
+    assert_eq!(created.sku, sku);
+    assert!(created.active);
+
+    // The same item the REST handler reads through the use case
+    let item: proto::Item = fixture
+        .call(
+            "/twh.v1.ItemService/GetItem",
+            proto::GetItemRequest {
+                id: created.id.clone(),
+            },
+            true,
+        )
+        .await
+        .unwrap();
+    let expected = with_tenant(
+        fixture.tenant_id,
+        fixture.get_item_use_case.execute(GetItemRequest {
+            id: Uuid::parse_str(&created.id).unwrap(),
+        }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        Decimal::from_str(&item.cost_price).unwrap(),
+        expected.cost_price
+    );
+    assert_eq!(item.sale_price, expected.sale_price.map(|p| p.to_string()));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(item.metadata.as_deref().unwrap()).unwrap(),
+        expected.metadata.unwrap()
+    );
+    assert_eq!(item.created_at, expected.created_at.to_rfc3339());
+
+    let page: proto::ListItemsResponse = fixture
+        .call(
+            "/twh.v1.ItemService/ListItems",
+            proto::ListItemsRequest {
+                sku: Some(sku.clone()),
+                ..Default::default()
+            },
+            true,
+        )
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].id, created.id);
+    assert!(!page.has_more);
+
+    // REST: 409 DUPLICATE_SKU, 404 ITEM_NOT_FOUND, 400 INVALID_ID
+    let duplicate = fixture
+        .call::<_, proto::CreateItemResponse>(
+            "/twh.v1.ItemService/CreateItem",
+            create_item_request(&sku),
+            true,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(duplicate.code(), Code::AlreadyExists);
+    assert_eq!(error_code(&duplicate), "DUPLICATE_SKU");
+
+    let missing = fixture
+        .call::<_, proto::Item>(
+            "/twh.v1.ItemService/GetItem",
+            proto::GetItemRequest {
+                id: Uuid::new_v4().to_string(),
+            },
+            true,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+    assert_eq!(error_code(&missing), "ITEM_NOT_FOUND");
+
+    let malformed = fixture
+        .call::<_, proto::Item>(
+            "/twh.v1.ItemService/GetItem",
+            proto::GetItemRequest {
+                id: "not-a-uuid".to_string(),
+            },
+            true,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(malformed.code(), Code::InvalidArgument);
+    assert_eq!(error_code(&malformed), "INVALID_ID");
+
+    let unknown = fixture
+        .call::<_, proto::Item>(
+            "/twh.v1.ItemService/DeleteItem",
+            proto::GetItemRequest { id: created.id },
+            true,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(unknown.code(), Code::Unimplemented);
+}
+
+#[tokio::test]
+#[ignore = "needs a migrated database at DATABASE_URL"]
+async fn test_stock_and_orders_match_rest() {
+    let fixture = Fixture::start().await;
+    let item: proto::CreateItemResponse = fixture
+        .call(
+            "/twh.v1.ItemService/CreateItem",
+            create_item_request(&format!("GRPC-{}", Uuid::new_v4().simple())),
+            true,
+        )
+        .await
+        .unwrap();
+
+    // Never stocked: REST answers null, gRPC leaves the level unset
+    let level: proto::GetStockLevelResponse = fixture
+        .call(
+            "/twh.v1.StockService/GetStockLevel",
+            proto::GetStockLevelRequest {
+                item_id: item.id.clone(),
+                location_id: fixture.location_id.to_string(),
+            },
+            true,
+        )
+        .await
+        .unwrap();
+    assert_eq!(level.stock_level, None);
+    let levels: proto::ListItemStockLevelsResponse = fixture
+        .call(
+            "/twh.v1.StockService/ListItemStockLevels",
+            proto::ListItemStockLevelsRequest {
+                item_id: item.id.clone(),
+            },
+            true,
+        )
+        .await
+        .unwrap();
+    assert!(levels.stock_levels.is_empty());
+
+    let created: proto::SalesOrder = fixture
+        .call(
+            "/twh.v1.OrderService/CreateSalesOrder",
+            proto::CreateSalesOrderRequest {
+                lines: vec![proto::CreateSalesOrderLine {
+                    item_id: item.id.clone(),
+                    qty: "3".to_string(),
+                    unit_price: "4.00".to_string(),
+                }],
+                should_reserve: Some(false),
+                ..Default::default()
+            },
+            true,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        Decimal::from_str(&created.total_amount).unwrap(),
+        Decimal::from(12)
+    );
+    let fetched: proto::SalesOrder = fixture
+        .call(
+            "/twh.v1.OrderService/GetSalesOrder",
+            proto::GetSalesOrderRequest {
+                id: created.id.clone(),
+            },
+            true,
+        )
+        .await
+        .unwrap();
+    assert_eq!(fetched.so_number, created.so_number);
+    assert_eq!(fetched.status, created.status);
+    assert_eq!(fetched.etag, created.etag);
+    assert_eq!(fetched.lines.len(), 1);
+    assert_eq!(fetched.lines[0].item_id, item.id);
+
+    // REST: 400 for a zero quantity, 404 for an unknown purchase order
+    let invalid = fixture
+        .call::<_, proto::SalesOrder>(
+            "/twh.v1.OrderService/CreateSalesOrder",
+            proto::CreateSalesOrderRequest {
+                lines: vec![proto::CreateSalesOrderLine {
+                    item_id: item.id,
+                    qty: "0".to_string(),
+                    unit_price: "4.00".to_string(),
+                }],
+                ..Default::default()
+            },
+            true,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(invalid.code(), Code::InvalidArgument);
+
+    let missing = fixture
+        .call::<_, proto::PurchaseOrder>(
+            "/twh.v1.OrderService/GetPurchaseOrder",
+            proto::GetPurchaseOrderRequest {
+                id: Uuid::new_v4().to_string(),
+            },
+            true,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+}
@@ -0,0 +1,4 @@
+//! Messages and server traits of `proto/twh/v1/warehouse.proto`, generated by
+//! `build.rs`
+
+tonic::include_proto!("twh.v1");
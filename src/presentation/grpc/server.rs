@@ -0,0 +1,174 @@
+use crate::infrastructure::middleware::tenant_middleware::{TenantContext, TenantMiddleware};
+use crate::shared::api_error::ApiError;
+use crate::shared::tenant_scope::with_tenant;
+use axum::http::{Request, Response};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tonic::body::BoxBody;
+use tonic::server::NamedService;
+use tonic::Status;
+use tower::Service;
+use uuid::Uuid;
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// Serves one of the generated services, scoping each call to the tenant named
+/// by its metadata the way the tenant middleware does for REST requests. The
+/// tenant's `TenantContext` is left in the request extensions, where
+/// `tenant_context` finds it.
+#[derive(Clone)]
+pub struct TenantScoped<S> {
+    inner: S,
+    tenant_middleware: Arc<TenantMiddleware>,
+}
+
+impl<S> TenantScoped<S> {
+    pub fn new(inner: S, tenant_middleware: Arc<TenantMiddleware>) -> Self {
+        Self {
+            inner,
+            tenant_middleware,
+        }
+    }
+}
+
+impl<S: NamedService> NamedService for TenantScoped<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+impl<S> Service<Request<BoxBody>> for TenantScoped<S>
+where
+    S: Service<Request<BoxBody>, Response = Response<BoxBody>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<Result<Self::Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<BoxBody>) -> Self::Future {
+        // Take the service that was polled ready and leave a fresh clone behind
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let tenant_middleware = Arc::clone(&self.tenant_middleware);
+        Box::pin(async move {
+            let tenant_context = match tenant_middleware.resolve(request.headers()).await {
+                Ok(tenant_context) => tenant_context,
+                Err(e) => return Ok(Status::from(e).into_http()),
+            };
+            if let Err(e) = tenant_context.check_path(request.uri().path()) {
+                return Ok(Status::from(e).into_http());
+            }
+            let tenant_id = tenant_context.tenant_id;
+            request.extensions_mut().insert(tenant_context);
+            with_tenant(tenant_id, inner.call(request)).await
+        })
+    }
+}
+
+/// The tenant `TenantScoped` resolved the call to
+pub fn tenant_context<T>(request: &tonic::Request<T>) -> Result<TenantContext, Status> {
+    request
+        .extensions()
+        .get::<TenantContext>()
+        .cloned()
+        .ok_or_else(|| Status::unauthenticated("Call is not scoped to a tenant"))
+}
+
+/// Request fields are checked the way REST checks a request body: a malformed
+/// one is a 400, which calls report as `INVALID_ARGUMENT`
+pub fn parse_uuid(field: &str, value: &str) -> Result<Uuid, ApiError> {
+    Uuid::parse_str(value.trim())
+        .map_err(|_| ApiError::bad_request(format!("{} must be a UUID, got '{}'", field, value)))
+}
+
+pub fn parse_optional_uuid(field: &str, value: Option<&str>) -> Result<Option<Uuid>, ApiError> {
+    value.map(|value| parse_uuid(field, value)).transpose()
+}
+
+pub fn parse_decimal(field: &str, value: &str) -> Result<Decimal, ApiError> {
+    Decimal::from_str(value.trim())
+        .map_err(|_| ApiError::bad_request(format!("{} must be a decimal, got '{}'", field, value)))
+}
+
+pub fn parse_optional_decimal(
+    field: &str,
+    value: Option<&str>,
+) -> Result<Option<Decimal>, ApiError> {
+    value.map(|value| parse_decimal(field, value)).transpose()
+}
+
+pub fn parse_json(field: &str, value: Option<&str>) -> Result<Option<serde_json::Value>, ApiError> {
+    value
+        .map(|value| {
+            serde_json::from_str(value)
+                .map_err(|e| ApiError::bad_request(format!("{} must be JSON: {}", field, e)))
+        })
+        .transpose()
+}
+
+pub fn timestamp(value: DateTime<Utc>) -> String {
+    value.to_rfc3339()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::tenant::TenantTier;
+
+    #[test]
+    fn test_parse_helpers_reject_malformed_fields() {
+        assert_eq!(
+            parse_decimal("qty", " 12.50 ").unwrap(),
+            Decimal::new(1250, 2)
+        );
+        assert_eq!(parse_optional_uuid("id", None).unwrap(), None);
+        assert_eq!(
+            parse_json("metadata", Some(r#"{"a":1}"#)).unwrap(),
+            Some(serde_json::json!({"a": 1}))
+        );
+
+        for error in [
+            parse_uuid("item_id", "ABC").unwrap_err(),
+            parse_decimal("qty", "ten").unwrap_err(),
+            parse_json("metadata", Some("{")).unwrap_err(),
+        ] {
+            let status = Status::from(error);
+            assert_eq!(status.code(), tonic::Code::InvalidArgument);
+            assert_eq!(
+                status.metadata().get("error-code").unwrap(),
+                "VALIDATION_ERROR"
+            );
+        }
+    }
+
+    #[test]
+    fn test_tenant_context_comes_from_request_extensions() {
+        let mut request = tonic::Request::new(());
+        assert_eq!(
+            tenant_context(&request).unwrap_err().code(),
+            tonic::Code::Unauthenticated
+        );
+
+        let tenant_id = Uuid::new_v4();
+        request.extensions_mut().insert(TenantContext {
+            tenant_id,
+            tier: TenantTier::Free,
+            user_id: None,
+            session_id: None,
+            two_factor_enrollment_required: false,
+        });
+        assert_eq!(tenant_context(&request).unwrap().tenant_id, tenant_id);
+    }
+}
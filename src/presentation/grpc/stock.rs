@@ -0,0 +1,114 @@
+use super::proto;
+use super::proto::stock_service_server;
+use super::server::{parse_uuid, timestamp};
+use crate::application::use_cases::{
+    get_stock_level::{GetStockLevelRequest, GetStockLevelUseCase},
+    list_item_stock_levels::{ListItemStockLevelsRequest, ListItemStockLevelsUseCase},
+};
+use crate::domain::entities::inventory::StockLevelResponse;
+use crate::infrastructure::repositories::{
+    postgres_item_repository::PostgresItemRepository,
+    postgres_location_repository::PostgresLocationRepository,
+    postgres_stock_repository::PostgresStockRepository,
+};
+use crate::shared::api_error::ApiError;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+/// `twh.v1.StockService`, over the use cases behind `/stock/{item_id}/{location_id}`
+/// and `/stock/items/{item_id}`
+#[derive(Clone)]
+pub struct StockService {
+    get_stock_level_use_case: Arc<
+        GetStockLevelUseCase<
+            PostgresStockRepository,
+            PostgresItemRepository,
+            PostgresLocationRepository,
+        >,
+    >,
+    list_item_stock_levels_use_case: Arc<
+        ListItemStockLevelsUseCase<
+            PostgresStockRepository,
+            PostgresItemRepository,
+            PostgresLocationRepository,
+        >,
+    >,
+}
+
+impl StockService {
+    pub fn new(
+        get_stock_level_use_case: Arc<
+            GetStockLevelUseCase<
+                PostgresStockRepository,
+                PostgresItemRepository,
+                PostgresLocationRepository,
+            >,
+        >,
+        list_item_stock_levels_use_case: Arc<
+            ListItemStockLevelsUseCase<
+                PostgresStockRepository,
+                PostgresItemRepository,
+                PostgresLocationRepository,
+            >,
+        >,
+    ) -> Self {
+        Self {
+            get_stock_level_use_case,
+            list_item_stock_levels_use_case,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl stock_service_server::StockService for StockService {
+    async fn get_stock_level(
+        &self,
+        request: Request<proto::GetStockLevelRequest>,
+    ) -> Result<Response<proto::GetStockLevelResponse>, Status> {
+        let request = request.into_inner();
+        let stock_level = self
+            .get_stock_level_use_case
+            .execute(GetStockLevelRequest {
+                item_id: parse_uuid("item_id", &request.item_id)?,
+                location_id: parse_uuid("location_id", &request.location_id)?,
+            })
+            .await
+            .map_err(ApiError::from)?;
+        Ok(Response::new(proto::GetStockLevelResponse {
+            stock_level: stock_level.map(Into::into),
+        }))
+    }
+
+    async fn list_item_stock_levels(
+        &self,
+        request: Request<proto::ListItemStockLevelsRequest>,
+    ) -> Result<Response<proto::ListItemStockLevelsResponse>, Status> {
+        let response = self
+            .list_item_stock_levels_use_case
+            .execute(ListItemStockLevelsRequest {
+                item_id: parse_uuid("item_id", &request.get_ref().item_id)?,
+            })
+            .await
+            .map_err(ApiError::from)?;
+        Ok(Response::new(proto::ListItemStockLevelsResponse {
+            stock_levels: response.stock_levels.into_iter().map(Into::into).collect(),
+        }))
+    }
+}
+
+impl From<StockLevelResponse> for proto::StockLevel {
+    fn from(level: StockLevelResponse) -> Self {
+        Self {
+            item_id: level.item_id.to_string(),
+            location_id: level.location_id.to_string(),
+            quantity_on_hand: level.quantity_on_hand.to_string(),
+            quantity_reserved: level.quantity_reserved.to_string(),
+            quantity_quarantine: level.quantity_quarantine.to_string(),
+            quantity_damaged: level.quantity_damaged.to_string(),
+            quantity_in_transit: level.quantity_in_transit.to_string(),
+            available_to_promise: level.available_to_promise.to_string(),
+            last_movement_id: level.last_movement_id.map(|id| id.to_string()),
+            updated_at: timestamp(level.updated_at),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use uuid::Uuid;
+
+/// The user a request acts as. Callers without a login token act as the
+/// seeded test user.
+pub(crate) fn acting_user(tenant_context: &TenantContext) -> Uuid {
+    tenant_context
+        .user_id
+        .unwrap_or_else(|| Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap())
+}
@@ -0,0 +1,15 @@
+use crate::domain::entities::address::AddressValidation;
+use crate::domain::entities::location::LocationAddress;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{extract::State, response::Json};
+
+/// Validate and normalize an address; problems come back as issues, not errors
+pub async fn validate_address(
+    State(state): State<AppState>,
+    Json(address): Json<LocationAddress>,
+) -> Result<Json<AddressValidation>, ApiError> {
+    Ok(Json(
+        state.validate_address_use_case.execute(address).await?,
+    ))
+}
@@ -0,0 +1,143 @@
+use crate::application::use_cases::adjust_stock::{
+    AdjustStockResponse, ListAdjustmentsQuery, ListAdjustmentsResponse,
+};
+use crate::application::use_cases::manage_adjustment_reasons::{
+    ListAdjustmentReasonsQuery, ListAdjustmentReasonsResponse,
+};
+use crate::domain::entities::adjustment::{
+    Adjustment, AdjustmentReason, AdjustmentStatus, CreateAdjustmentReasonRequest,
+    ReviewAdjustmentRequest, StockAdjustmentRequest, UpdateAdjustmentReasonRequest,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+
+/// Adjust stock against a reason code; 202 when the adjustment is held for approval
+pub async fn adjust_stock(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<StockAdjustmentRequest>,
+) -> Result<(StatusCode, Json<AdjustStockResponse>), ApiError> {
+    let response = state
+        .adjust_stock_use_case
+        .execute(request, acting_user(&tenant_context))
+        .await?;
+    let status = if response.adjustment.status == AdjustmentStatus::PendingApproval {
+        StatusCode::ACCEPTED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(response)))
+}
+
+pub async fn list_adjustments(
+    State(state): State<AppState>,
+    Query(query): Query<ListAdjustmentsQuery>,
+) -> Result<Json<ListAdjustmentsResponse>, ApiError> {
+    Ok(Json(state.adjust_stock_use_case.list(query).await?))
+}
+
+pub async fn get_adjustment(
+    State(state): State<AppState>,
+    Path(adjustment_id): Path<Uuid>,
+) -> Result<Json<Adjustment>, ApiError> {
+    Ok(Json(state.adjust_stock_use_case.get(adjustment_id).await?))
+}
+
+/// Apply an adjustment that was held for approval
+pub async fn approve_adjustment(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(adjustment_id): Path<Uuid>,
+    request: Option<Json<ReviewAdjustmentRequest>>,
+) -> Result<Json<AdjustStockResponse>, ApiError> {
+    let Json(request) = request.unwrap_or_default();
+    let response = state
+        .adjust_stock_use_case
+        .approve(adjustment_id, acting_user(&tenant_context), request.note)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Reject an adjustment that was held for approval, leaving stock untouched
+pub async fn reject_adjustment(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(adjustment_id): Path<Uuid>,
+    request: Option<Json<ReviewAdjustmentRequest>>,
+) -> Result<Json<Adjustment>, ApiError> {
+    let Json(request) = request.unwrap_or_default();
+    let adjustment = state
+        .adjust_stock_use_case
+        .reject(adjustment_id, acting_user(&tenant_context), request.note)
+        .await?;
+    Ok(Json(adjustment))
+}
+
+pub async fn create_adjustment_reason(
+    State(state): State<AppState>,
+    Json(request): Json<CreateAdjustmentReasonRequest>,
+) -> Result<(StatusCode, Json<AdjustmentReason>), ApiError> {
+    state
+        .manage_adjustment_reasons_use_case
+        .create(request)
+        .await
+        .map(|reason| (StatusCode::CREATED, Json(reason)))
+        .map_err(ApiError::from)
+}
+
+pub async fn list_adjustment_reasons(
+    State(state): State<AppState>,
+    Query(query): Query<ListAdjustmentReasonsQuery>,
+) -> Result<Json<ListAdjustmentReasonsResponse>, ApiError> {
+    state
+        .manage_adjustment_reasons_use_case
+        .list(query)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_adjustment_reason(
+    State(state): State<AppState>,
+    Path(reason_id): Path<Uuid>,
+) -> Result<Json<AdjustmentReason>, ApiError> {
+    state
+        .manage_adjustment_reasons_use_case
+        .get(reason_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn update_adjustment_reason(
+    State(state): State<AppState>,
+    Path(reason_id): Path<Uuid>,
+    Json(request): Json<UpdateAdjustmentReasonRequest>,
+) -> Result<Json<AdjustmentReason>, ApiError> {
+    state
+        .manage_adjustment_reasons_use_case
+        .update(reason_id, request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn delete_adjustment_reason(
+    State(state): State<AppState>,
+    Path(reason_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .manage_adjustment_reasons_use_case
+        .delete(reason_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(ApiError::from)
+}
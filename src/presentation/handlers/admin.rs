@@ -8,6 +8,14 @@ use sqlx::{PgPool, Row};
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::application::use_cases::manage_webhook_pause::{
+    DisableTenantWebhooksRequest, DisableTenantWebhooksResponse, ManageWebhookPauseUseCase,
+};
+use crate::domain::services::tenant_repository::TenantRepository;
+use crate::infrastructure::middleware::rate_limit_middleware::RateLimitStatus;
+use crate::infrastructure::repositories::schema_migrations::{migrations_report, MigrationsReport};
+use crate::shared::api_error::ApiError;
+use crate::shared::tenant_scope::with_tenant;
 use crate::AppState;
 
 #[derive(Serialize)]
@@ -41,6 +49,10 @@ pub struct TenantQuotasResponse {
     pub max_webhooks: i32,
     pub max_api_calls_per_hour: i32,
     pub max_storage_mb: i32,
+    /// `None` uses the tier's default
+    pub max_requests_per_minute: Option<i32>,
+    /// `None` uses the tenant's limit
+    pub max_requests_per_minute_per_api_key: Option<i32>,
     pub current_items: i32,
     pub current_locations: i32,
     pub current_webhooks: i32,
@@ -56,16 +68,14 @@ pub struct UpdateTenantQuotasRequest {
     pub max_webhooks: Option<i32>,
     pub max_api_calls_per_hour: Option<i32>,
     pub max_storage_mb: Option<i32>,
+    pub max_requests_per_minute: Option<i32>,
+    pub max_requests_per_minute_per_api_key: Option<i32>,
 }
 
 pub async fn admin_dashboard_handler(
     State(state): State<AppState>,
-) -> Result<Json<AdminDashboardResponse>, StatusCode> {
-    let tenants = state
-        .list_tenants_use_case
-        .execute()
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+) -> Result<Json<AdminDashboardResponse>, ApiError> {
+    let tenants = state.list_tenants_use_case.execute().await?;
 
     let active_sandboxes = tenants
         .iter()
@@ -80,8 +90,7 @@ pub async fn admin_dashboard_handler(
     // Get actual webhook delivery counts
     let total_webhook_deliveries = sqlx::query!("SELECT COUNT(*) as count FROM webhook_deliveries")
         .fetch_one(&*state.pool)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .await?
         .count
         .unwrap_or(0);
 
@@ -89,8 +98,7 @@ pub async fn admin_dashboard_handler(
         "SELECT COUNT(*) as count FROM webhook_deliveries WHERE status IN ('FAILED', 'TIMEOUT', 'DLQ')"
     )
     .fetch_one(&*state.pool)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .await?
     .count
     .unwrap_or(0);
 
@@ -105,12 +113,8 @@ pub async fn admin_dashboard_handler(
 
 pub async fn list_sandboxes_handler(
     State(state): State<AppState>,
-) -> Result<Json<ListSandboxesResponse>, StatusCode> {
-    let tenants = state
-        .list_tenants_use_case
-        .execute()
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+) -> Result<Json<ListSandboxesResponse>, ApiError> {
+    let tenants = state.list_tenants_use_case.execute().await?;
 
     let sandboxes = tenants
         .into_iter()
@@ -129,12 +133,8 @@ pub async fn list_sandboxes_handler(
 
 pub async fn cleanup_expired_sandboxes_handler(
     State(state): State<AppState>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    let cleaned_ids = state
-        .cleanup_expired_sandboxes_use_case
-        .execute()
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let cleaned_ids = state.cleanup_expired_sandboxes_use_case.execute().await?;
 
     Ok(Json(serde_json::json!({
         "message": format!("Cleaned up {} expired sandboxes", cleaned_ids.len()),
@@ -150,12 +150,11 @@ pub struct DlqDeliveryResponse {
 
 pub async fn list_dlq_deliveries_handler(
     State(state): State<AppState>,
-) -> Result<Json<DlqDeliveryResponse>, StatusCode> {
+) -> Result<Json<DlqDeliveryResponse>, ApiError> {
     let result = state
         .list_dlq_deliveries_use_case
         .execute(None, None)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     // Convert deliveries to JSON values for serialization
     let deliveries = result
@@ -197,12 +196,11 @@ pub struct ReplayDlqRequest {
 pub async fn replay_dlq_delivery_handler(
     State(state): State<AppState>,
     Json(request): Json<ReplayDlqRequest>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     let result = state
         .replay_dlq_delivery_use_case
         .execute(request.delivery_id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     Ok(Json(serde_json::json!({
         "success": result.success,
@@ -213,12 +211,8 @@ pub async fn replay_dlq_delivery_handler(
 
 pub async fn get_billing_metrics_handler(
     State(state): State<AppState>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    let metrics = state
-        .get_billing_metrics_use_case
-        .execute()
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let metrics = state.get_billing_metrics_use_case.execute().await?;
 
     Ok(Json(serde_json::json!({
         "total_api_calls": metrics.total_api_calls,
@@ -241,10 +235,17 @@ pub async fn get_billing_metrics_handler(
     })))
 }
 
+/// Applied and pending database migrations
+pub async fn get_migrations_handler(
+    State(state): State<AppState>,
+) -> Result<Json<MigrationsReport>, ApiError> {
+    Ok(Json(migrations_report(&state.pool).await?))
+}
+
 pub async fn get_tenant_quotas_handler(
     State(state): State<AppState>,
     Path(tenant_id): Path<Uuid>,
-) -> Result<Json<TenantQuotasResponse>, StatusCode> {
+) -> Result<Json<TenantQuotasResponse>, ApiError> {
     let quota = sqlx::query_as!(
         TenantQuotasResponse,
         r#"
@@ -255,6 +256,8 @@ pub async fn get_tenant_quotas_handler(
             max_webhooks,
             max_api_calls_per_hour,
             max_storage_mb,
+            max_requests_per_minute,
+            max_requests_per_minute_per_api_key,
             current_items,
             current_locations,
             current_webhooks,
@@ -267,20 +270,18 @@ pub async fn get_tenant_quotas_handler(
         tenant_id
     )
     .fetch_optional(&*state.pool)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .await?;
 
-    match quota {
-        Some(quota) => Ok(Json(quota)),
-        None => Err(StatusCode::NOT_FOUND),
-    }
+    quota
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("No quotas for tenant {}", tenant_id)))
 }
 
 pub async fn update_tenant_quotas_handler(
     State(state): State<AppState>,
     Path(tenant_id): Path<Uuid>,
     Json(request): Json<UpdateTenantQuotasRequest>,
-) -> Result<Json<TenantQuotasResponse>, StatusCode> {
+) -> Result<Json<TenantQuotasResponse>, ApiError> {
     // Build dynamic update query based on provided fields
     let mut update_fields = Vec::new();
     let mut param_count = 2; // tenant_id is $1, updated_at is $2
@@ -305,9 +306,20 @@ pub async fn update_tenant_quotas_handler(
         update_fields.push(format!("max_storage_mb = ${}", param_count));
         param_count += 1;
     }
+    if request.max_requests_per_minute.is_some() {
+        update_fields.push(format!("max_requests_per_minute = ${}", param_count));
+        param_count += 1;
+    }
+    if request.max_requests_per_minute_per_api_key.is_some() {
+        update_fields.push(format!(
+            "max_requests_per_minute_per_api_key = ${}",
+            param_count
+        ));
+        param_count += 1;
+    }
 
     if update_fields.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::bad_request("No quota fields to update"));
     }
 
     let update_query = format!(
@@ -334,11 +346,17 @@ pub async fn update_tenant_quotas_handler(
     if let Some(max_storage_mb) = request.max_storage_mb {
         query = query.bind(max_storage_mb);
     }
+    if let Some(max_requests_per_minute) = request.max_requests_per_minute {
+        query = query.bind(max_requests_per_minute);
+    }
+    if let Some(per_api_key) = request.max_requests_per_minute_per_api_key {
+        query = query.bind(per_api_key);
+    }
 
     let row = query
-        .fetch_one(&*state.pool)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .fetch_optional(&*state.pool)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("No quotas for tenant {}", tenant_id)))?;
 
     let response = TenantQuotasResponse {
         tenant_id: row.get("tenant_id"),
@@ -347,6 +365,8 @@ pub async fn update_tenant_quotas_handler(
         max_webhooks: row.get("max_webhooks"),
         max_api_calls_per_hour: row.get("max_api_calls_per_hour"),
         max_storage_mb: row.get("max_storage_mb"),
+        max_requests_per_minute: row.get("max_requests_per_minute"),
+        max_requests_per_minute_per_api_key: row.get("max_requests_per_minute_per_api_key"),
         current_items: row.get("current_items"),
         current_locations: row.get("current_locations"),
         current_webhooks: row.get("current_webhooks"),
@@ -355,5 +375,57 @@ pub async fn update_tenant_quotas_handler(
         updated_at: row.get("updated_at"),
     };
 
+    // Cached limits would otherwise hold the old values for a few minutes
+    if let Err(e) = state
+        .rate_limit_middleware
+        .invalidate_limits(tenant_id)
+        .await
+    {
+        tracing::warn!(error = %e, %tenant_id, "Failed to drop cached rate limits");
+    }
+
+    Ok(Json(response))
+}
+
+/// Usage of the tenant's rate limit buckets in the current window
+pub async fn get_tenant_rate_limit_handler(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<Uuid>,
+) -> Result<Json<RateLimitStatus>, ApiError> {
+    let tier = state
+        .tenant_repository
+        .get_tenant_tier(tenant_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Tenant {} not found", tenant_id)))?;
+    Ok(Json(
+        state.rate_limit_middleware.status(tenant_id, &tier).await?,
+    ))
+}
+
+/// Empty the tenant's rate limit buckets
+pub async fn reset_tenant_rate_limit_handler(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    state.rate_limit_middleware.reset(tenant_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Stop all of a tenant's webhooks during an incident. The body is optional;
+/// `{"pause": true}` pauses them so their deliveries can be replayed later.
+pub async fn disable_tenant_webhooks_handler(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<Uuid>,
+    request: Option<Json<DisableTenantWebhooksRequest>>,
+) -> Result<Json<DisableTenantWebhooksResponse>, ApiError> {
+    state
+        .tenant_repository
+        .get_tenant_tier(tenant_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Tenant {} not found", tenant_id)))?;
+
+    let use_case = ManageWebhookPauseUseCase::new(state.webhook_repository.clone());
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let response = with_tenant(tenant_id, use_case.disable_all(tenant_id, request)).await?;
     Ok(Json(response))
 }
@@ -0,0 +1,98 @@
+use crate::application::use_cases::manage_item_attachments::{
+    ItemAttachmentResponse, UploadAttachmentRequest,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::presentation::handlers::uploads::{multipart_error, spool_field};
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Multipart, Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+use uuid::Uuid;
+
+/// Upload a file as multipart/form-data; the file goes in the `file` field and
+/// is written to a temporary file as it arrives rather than held in memory
+pub async fn upload_item_attachment(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(item_id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<ItemAttachmentResponse>), ApiError> {
+    let mut upload = None;
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let file_name = field.file_name().unwrap_or("file").to_string();
+        let content_type = field
+            .content_type()
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        upload = Some(UploadAttachmentRequest {
+            file_name,
+            content_type,
+            file: spool_field(field).await?,
+        });
+        break;
+    }
+    let upload = upload.ok_or_else(|| ApiError::bad_request("Missing 'file' field".to_string()))?;
+
+    let created_by = acting_user(&tenant_context);
+
+    state
+        .manage_item_attachments_use_case
+        .upload(item_id, upload, created_by)
+        .await
+        .map(|attachment| (StatusCode::CREATED, Json(attachment)))
+        .map_err(ApiError::from)
+}
+
+pub async fn list_item_attachments(
+    State(state): State<AppState>,
+    Path(item_id): Path<Uuid>,
+) -> Result<Json<Vec<ItemAttachmentResponse>>, ApiError> {
+    state
+        .manage_item_attachments_use_case
+        .list(item_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn download_item_attachment(
+    State(state): State<AppState>,
+    Path((item_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, ApiError> {
+    let (attachment, data) = state
+        .manage_item_attachments_use_case
+        .download(item_id, attachment_id)
+        .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, attachment.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"{}\"", attachment.file_name),
+            ),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+pub async fn delete_item_attachment(
+    State(state): State<AppState>,
+    Path((item_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .manage_item_attachments_use_case
+        .delete(item_id, attachment_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(ApiError::from)
+}
@@ -0,0 +1,49 @@
+use crate::application::use_cases::enqueue_job::EnqueueJobRequest;
+use crate::domain::entities::item_availability::{
+    ItemAvailability, REBUILD_ITEM_AVAILABILITY_JOB_TYPE,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::jobs::EnqueueJobResponse;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use uuid::Uuid;
+
+/// An item's on hand, reserved, allocated, in transit and on order
+/// quantities, per location and in total
+pub async fn get_item_availability(
+    State(state): State<AppState>,
+    Path(item_id): Path<Uuid>,
+) -> Result<Json<ItemAvailability>, ApiError> {
+    let availability = state.item_availability_use_case.execute(item_id).await?;
+    Ok(Json(availability))
+}
+
+/// Queue a rebuild of the tenant's availability read model from the stock,
+/// allocation and purchase order tables; poll the returned job for completion
+pub async fn rebuild_item_availability(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+) -> Result<(StatusCode, Json<EnqueueJobResponse>), ApiError> {
+    let response = state
+        .enqueue_job_use_case
+        .execute(EnqueueJobRequest {
+            tenant_id: tenant_context.tenant_id,
+            job_type: REBUILD_ITEM_AVAILABILITY_JOB_TYPE.to_string(),
+            payload: serde_json::json!({}),
+        })
+        .await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(EnqueueJobResponse {
+            job_id: response.job_id,
+            status: response.status,
+            created_at: response.created_at,
+        }),
+    ))
+}
@@ -0,0 +1,41 @@
+use crate::application::use_cases::generate_item_barcode::ItemBarcodeQuery;
+use crate::application::use_cases::scan_lookup::ScanLookupResponse;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Json, Response},
+};
+use uuid::Uuid;
+
+/// Render a Code128 or QR label for an item as PNG or SVG
+pub async fn get_item_barcode(
+    State(state): State<AppState>,
+    Path(item_id): Path<Uuid>,
+    Query(query): Query<ItemBarcodeQuery>,
+) -> Result<Response, ApiError> {
+    let rendered = state
+        .generate_item_barcode_use_case
+        .execute(item_id, query)
+        .await?;
+
+    Ok((
+        [(header::CONTENT_TYPE, rendered.content_type)],
+        rendered.bytes,
+    )
+        .into_response())
+}
+
+/// Resolve a scanned barcode or SKU to an item with its stock levels
+pub async fn scan_code(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> Result<Json<ScanLookupResponse>, ApiError> {
+    state
+        .scan_lookup_use_case
+        .execute(code)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
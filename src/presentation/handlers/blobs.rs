@@ -0,0 +1,45 @@
+use crate::shared::api_error::ApiError;
+use crate::shared::error::DomainError;
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct SignedUrlQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
+/// Serve an object through a signed link issued by local blob storage
+pub async fn download_signed_blob(
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+    Query(query): Query<SignedUrlQuery>,
+) -> Result<Response, ApiError> {
+    state
+        .blob_storage
+        .verify_signed_url(&key, query.expires, &query.signature)
+        .map_err(|e| match e {
+            DomainError::NotFound(msg) => ApiError::not_found(msg),
+            e => ApiError::forbidden(e.to_string()).with_code("INVALID_SIGNATURE"),
+        })?;
+
+    let data = state.blob_storage.get(&key).await?;
+
+    let file_name = key.rsplit('/').next().unwrap_or("download");
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ),
+        ],
+        data,
+    )
+        .into_response())
+}
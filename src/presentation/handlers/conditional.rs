@@ -0,0 +1,120 @@
+//! Conditional GETs of single resources. Responses carry the entity's ETag and
+//! Last-Modified, and a client whose cached copy is still current gets an empty
+//! 304 Not Modified instead of the body.
+
+use axum::{
+    http::{
+        header::{CACHE_CONTROL, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Respond with `body`, or with 304 when the request's validators match
+/// `etag` and `last_modified`. Caches may keep the response but must check
+/// it is current before reusing it.
+pub fn conditional_json<T: Serialize>(
+    headers: &HeaderMap,
+    etag: &str,
+    last_modified: DateTime<Utc>,
+    body: T,
+) -> Response {
+    let validators = [
+        (ETAG, etag.to_string()),
+        (LAST_MODIFIED, http_date(last_modified)),
+        (CACHE_CONTROL, "private, no-cache".to_string()),
+    ];
+    if is_not_modified(headers, etag, last_modified) {
+        (StatusCode::NOT_MODIFIED, validators).into_response()
+    } else {
+        (validators, Json(body)).into_response()
+    }
+}
+
+/// Whether the client's copy is current. If-None-Match takes precedence over
+/// If-Modified-Since, which only has second precision.
+pub fn is_not_modified(headers: &HeaderMap, etag: &str, last_modified: DateTime<Utc>) -> bool {
+    if let Some(if_none_match) = headers.get(IF_NONE_MATCH) {
+        // GETs use the weak comparison, so a tag a proxy weakened when it
+        // compressed the response still matches
+        return if_none_match.to_str().is_ok_and(|tags| {
+            tags.split(',')
+                .map(str::trim)
+                .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+        });
+    }
+    headers
+        .get(IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .is_some_and(|since| last_modified.timestamp() <= since.timestamp())
+}
+
+/// `time` in the IMF-fixdate format HTTP uses
+pub fn http_date(time: DateTime<Utc>) -> String {
+    time.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn request(name: axum::http::HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_if_none_match_takes_precedence() {
+        let modified = DateTime::from_timestamp(1_700_000_000, 500_000_000).unwrap();
+        let etag = "\"abc\"";
+
+        assert!(is_not_modified(
+            &request(IF_NONE_MATCH, "\"old\", W/\"abc\""),
+            etag,
+            modified
+        ));
+        assert!(is_not_modified(
+            &request(IF_NONE_MATCH, "*"),
+            etag,
+            modified
+        ));
+        assert!(!is_not_modified(
+            &request(IF_NONE_MATCH, "\"old\""),
+            etag,
+            modified
+        ));
+        assert!(!is_not_modified(&HeaderMap::new(), etag, modified));
+
+        let mut both = request(IF_NONE_MATCH, "\"old\"");
+        both.insert(
+            IF_MODIFIED_SINCE,
+            HeaderValue::from_str(&http_date(modified)).unwrap(),
+        );
+        assert!(!is_not_modified(&both, etag, modified));
+    }
+
+    #[test]
+    fn test_if_modified_since_compares_whole_seconds() {
+        let modified = DateTime::from_timestamp(1_700_000_000, 500_000_000).unwrap();
+        assert_eq!(http_date(modified), "Tue, 14 Nov 2023 22:13:20 GMT");
+
+        let since = |time: DateTime<Utc>| request(IF_MODIFIED_SINCE, &http_date(time));
+        assert!(is_not_modified(&since(modified), "\"abc\"", modified));
+        assert!(!is_not_modified(
+            &since(modified - chrono::Duration::seconds(1)),
+            "\"abc\"",
+            modified
+        ));
+        assert!(!is_not_modified(
+            &request(IF_MODIFIED_SINCE, "yesterday"),
+            "\"abc\"",
+            modified
+        ));
+    }
+}
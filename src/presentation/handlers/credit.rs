@@ -0,0 +1,73 @@
+use crate::domain::entities::credit::{
+    CreditSettings, CustomerCreditStatus, SetCustomerCreditLimitRequest,
+    UpdateCreditSettingsRequest,
+};
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+
+pub async fn get_credit_settings(
+    State(state): State<AppState>,
+) -> Result<Json<CreditSettings>, ApiError> {
+    state
+        .manage_credit_settings_use_case
+        .get()
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// Choose whether orders past a customer's limit are refused or only flagged
+pub async fn update_credit_settings(
+    State(state): State<AppState>,
+    Json(request): Json<UpdateCreditSettingsRequest>,
+) -> Result<Json<CreditSettings>, ApiError> {
+    state
+        .manage_credit_settings_use_case
+        .update(request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn set_customer_credit_limit(
+    State(state): State<AppState>,
+    Path(customer_id): Path<Uuid>,
+    Json(request): Json<SetCustomerCreditLimitRequest>,
+) -> Result<Json<CreditSettings>, ApiError> {
+    state
+        .manage_credit_settings_use_case
+        .set_customer(customer_id, request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn delete_customer_credit_limit(
+    State(state): State<AppState>,
+    Path(customer_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .manage_credit_settings_use_case
+        .remove_customer(customer_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// A customer's limit, outstanding balance and the credit left
+pub async fn get_customer_credit(
+    State(state): State<AppState>,
+    Path(customer_id): Path<Uuid>,
+) -> Result<Json<CustomerCreditStatus>, ApiError> {
+    state
+        .manage_credit_settings_use_case
+        .customer_status(customer_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
@@ -0,0 +1,69 @@
+use crate::application::use_cases::manage_currency::{ExchangeRatesQuery, ExchangeRatesResponse};
+use crate::domain::entities::currency::{CurrencySettings, UpdateCurrencySettingsRequest};
+use crate::domain::entities::time_zone::{TimeZoneSettings, UpdateTimeZoneSettingsRequest};
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+
+pub async fn get_currency_settings(
+    State(state): State<AppState>,
+) -> Result<Json<CurrencySettings>, ApiError> {
+    state
+        .manage_currency_use_case
+        .get_settings()
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn update_currency_settings(
+    State(state): State<AppState>,
+    Json(request): Json<UpdateCurrencySettingsRequest>,
+) -> Result<Json<CurrencySettings>, ApiError> {
+    state
+        .manage_currency_use_case
+        .update_settings(request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// Exchange rates into the tenant's base currency, as of `?date=` or today
+pub async fn list_exchange_rates(
+    State(state): State<AppState>,
+    Query(query): Query<ExchangeRatesQuery>,
+) -> Result<Json<ExchangeRatesResponse>, ApiError> {
+    state
+        .manage_currency_use_case
+        .list_rates(query)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_time_zone_settings(
+    State(state): State<AppState>,
+) -> Result<Json<TimeZoneSettings>, ApiError> {
+    state
+        .manage_time_zone_use_case
+        .get_settings()
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// Set the IANA time zone report dates, snapshots and billing periods follow
+pub async fn update_time_zone_settings(
+    State(state): State<AppState>,
+    Json(request): Json<UpdateTimeZoneSettingsRequest>,
+) -> Result<Json<TimeZoneSettings>, ApiError> {
+    state
+        .manage_time_zone_use_case
+        .update_settings(request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
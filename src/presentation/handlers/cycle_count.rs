@@ -0,0 +1,102 @@
+use crate::application::use_cases::create_cycle_count::CreateCycleCountResponse;
+use crate::application::use_cases::finalize_cycle_count::FinalizeCycleCountResponse;
+use crate::application::use_cases::get_cycle_count::{
+    ListCycleCountsQuery, ListCycleCountsResponse,
+};
+use crate::application::use_cases::record_count::RecordCountResponse;
+use crate::domain::entities::cycle_count::{
+    CreateCycleCountRequest, CycleCount, RecordCountRequest,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use uuid::Uuid;
+
+pub async fn create_cycle_count(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<CreateCycleCountRequest>,
+) -> Result<(StatusCode, Json<CreateCycleCountResponse>), ApiError> {
+    let created_by = acting_user(&tenant_context);
+
+    state
+        .create_cycle_count_use_case
+        .execute(request, created_by)
+        .await
+        .map(|response| (StatusCode::CREATED, Json(response)))
+        .map_err(ApiError::from)
+}
+
+pub async fn list_cycle_counts(
+    State(state): State<AppState>,
+    Query(query): Query<ListCycleCountsQuery>,
+) -> Result<Json<ListCycleCountsResponse>, ApiError> {
+    state
+        .get_cycle_count_use_case
+        .list(query)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_cycle_count(
+    State(state): State<AppState>,
+    Path(cycle_count_id): Path<Uuid>,
+) -> Result<Json<CycleCount>, ApiError> {
+    state
+        .get_cycle_count_use_case
+        .execute(cycle_count_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn record_counts(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(cycle_count_id): Path<Uuid>,
+    Json(request): Json<RecordCountRequest>,
+) -> Result<Json<RecordCountResponse>, ApiError> {
+    let counted_by = acting_user(&tenant_context);
+
+    state
+        .record_count_use_case
+        .execute(cycle_count_id, request, counted_by)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn finalize_cycle_count(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(cycle_count_id): Path<Uuid>,
+) -> Result<Json<FinalizeCycleCountResponse>, ApiError> {
+    let finalized_by = acting_user(&tenant_context);
+
+    state
+        .finalize_cycle_count_use_case
+        .execute(cycle_count_id, finalized_by)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn cancel_cycle_count(
+    State(state): State<AppState>,
+    Path(cycle_count_id): Path<Uuid>,
+) -> Result<Json<CycleCount>, ApiError> {
+    state
+        .cancel_cycle_count_use_case
+        .execute(cycle_count_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
@@ -0,0 +1,129 @@
+use crate::application::use_cases::generate_order_documents::RenderedDocument;
+use crate::domain::entities::document::{DocumentSettings, UpdateDocumentSettingsRequest};
+use crate::presentation::handlers::uploads::multipart_error;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Multipart, Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use uuid::Uuid;
+
+fn pdf_response(document: RenderedDocument) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"{}\"", document.file_name),
+            ),
+        ],
+        document.bytes,
+    )
+        .into_response()
+}
+
+/// The purchase order as a PDF to send to the supplier
+pub async fn get_purchase_order_pdf(
+    State(state): State<AppState>,
+    Path(po_id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    state
+        .generate_order_documents_use_case
+        .purchase_order(po_id)
+        .await
+        .map(pdf_response)
+        .map_err(ApiError::from)
+}
+
+/// The sales order confirmation as a PDF
+pub async fn get_sales_order_pdf(
+    State(state): State<AppState>,
+    Path(so_id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    state
+        .generate_order_documents_use_case
+        .sales_order(so_id)
+        .await
+        .map(pdf_response)
+        .map_err(ApiError::from)
+}
+
+/// A packing slip doubling as the pick ticket for what is left to ship
+pub async fn get_packing_slip_pdf(
+    State(state): State<AppState>,
+    Path(so_id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    state
+        .generate_order_documents_use_case
+        .packing_slip(so_id)
+        .await
+        .map(pdf_response)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_document_settings(
+    State(state): State<AppState>,
+) -> Result<Json<DocumentSettings>, ApiError> {
+    state
+        .manage_document_settings_use_case
+        .get()
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn update_document_settings(
+    State(state): State<AppState>,
+    Json(request): Json<UpdateDocumentSettingsRequest>,
+) -> Result<Json<DocumentSettings>, ApiError> {
+    state
+        .manage_document_settings_use_case
+        .update(request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// Upload the logo as multipart/form-data; the PNG goes in the `file` field
+pub async fn upload_document_logo(
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<Json<DocumentSettings>, ApiError> {
+    let mut data = None;
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+        // Logos are capped at MAX_LOGO_BYTES, small enough to check in memory
+        let bytes = field.bytes().await.map_err(multipart_error)?;
+        data = Some(bytes.to_vec());
+        break;
+    }
+    let data = data.ok_or_else(|| ApiError::bad_request("Missing 'file' field".to_string()))?;
+
+    state
+        .manage_document_settings_use_case
+        .upload_logo(data)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_document_logo(State(state): State<AppState>) -> Result<Response, ApiError> {
+    let data = state
+        .manage_document_settings_use_case
+        .download_logo()
+        .await?;
+    Ok(([(header::CONTENT_TYPE, "image/png")], data).into_response())
+}
+
+pub async fn delete_document_logo(State(state): State<AppState>) -> Result<StatusCode, ApiError> {
+    state
+        .manage_document_settings_use_case
+        .delete_logo()
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(ApiError::from)
+}
@@ -0,0 +1,173 @@
+use crate::application::use_cases::exchange_edi_documents::{
+    IngestEdiDocumentQuery, ListEdiDocumentsResponse,
+};
+use crate::application::use_cases::manage_trading_partners::{
+    ListTradingPartnersQuery, ListTradingPartnersResponse,
+};
+use crate::domain::entities::edi::{
+    CreateTradingPartnerRequest, EdiDocument, ListEdiDocumentsQuery, TradingPartner,
+    UpdateTradingPartnerRequest,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use uuid::Uuid;
+
+pub async fn create_trading_partner(
+    State(state): State<AppState>,
+    Json(request): Json<CreateTradingPartnerRequest>,
+) -> Result<(StatusCode, Json<TradingPartner>), ApiError> {
+    state
+        .manage_trading_partners_use_case
+        .create(request)
+        .await
+        .map(|partner| (StatusCode::CREATED, Json(partner)))
+        .map_err(ApiError::from)
+}
+
+pub async fn list_trading_partners(
+    State(state): State<AppState>,
+    Query(query): Query<ListTradingPartnersQuery>,
+) -> Result<Json<ListTradingPartnersResponse>, ApiError> {
+    state
+        .manage_trading_partners_use_case
+        .list(query)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_trading_partner(
+    State(state): State<AppState>,
+    Path(partner_id): Path<Uuid>,
+) -> Result<Json<TradingPartner>, ApiError> {
+    state
+        .manage_trading_partners_use_case
+        .get(partner_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn update_trading_partner(
+    State(state): State<AppState>,
+    Path(partner_id): Path<Uuid>,
+    Json(request): Json<UpdateTradingPartnerRequest>,
+) -> Result<Json<TradingPartner>, ApiError> {
+    state
+        .manage_trading_partners_use_case
+        .update(partner_id, request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn delete_trading_partner(
+    State(state): State<AppState>,
+    Path(partner_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .manage_trading_partners_use_case
+        .delete(partner_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(ApiError::from)
+}
+
+/// Upload a raw X12 850 interchange; each purchase order becomes a draft sales order
+pub async fn upload_edi_document(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Query(query): Query<IngestEdiDocumentQuery>,
+    content: String,
+) -> Result<(StatusCode, Json<EdiDocument>), ApiError> {
+    let created_by = acting_user(&tenant_context);
+
+    state
+        .exchange_edi_documents_use_case
+        .ingest(content, query, created_by)
+        .await
+        .map(|document| (StatusCode::CREATED, Json(document)))
+        .map_err(ApiError::from)
+}
+
+pub async fn list_edi_documents(
+    State(state): State<AppState>,
+    Query(query): Query<ListEdiDocumentsQuery>,
+) -> Result<Json<ListEdiDocumentsResponse>, ApiError> {
+    state
+        .exchange_edi_documents_use_case
+        .list_documents(query)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_edi_document(
+    State(state): State<AppState>,
+    Path(document_id): Path<Uuid>,
+) -> Result<Json<EdiDocument>, ApiError> {
+    state
+        .exchange_edi_documents_use_case
+        .get_document(document_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// Download the X12 interchange exactly as it was received or sent
+pub async fn download_edi_document(
+    State(state): State<AppState>,
+    Path(document_id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    let document = state
+        .exchange_edi_documents_use_case
+        .get_document(document_id)
+        .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/edi-x12".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}-{}.edi\"",
+                    document.document_type.as_str(),
+                    document.id
+                ),
+            ),
+        ],
+        document.content,
+    )
+        .into_response())
+}
+
+pub async fn create_ship_notice(
+    State(state): State<AppState>,
+    Path(shipment_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<EdiDocument>), ApiError> {
+    state
+        .exchange_edi_documents_use_case
+        .ship_notice(shipment_id)
+        .await
+        .map(|document| (StatusCode::CREATED, Json(document)))
+        .map_err(ApiError::from)
+}
+
+pub async fn create_invoice(
+    State(state): State<AppState>,
+    Path(shipment_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<EdiDocument>), ApiError> {
+    state
+        .exchange_edi_documents_use_case
+        .invoice(shipment_id)
+        .await
+        .map(|document| (StatusCode::CREATED, Json(document)))
+        .map_err(ApiError::from)
+}
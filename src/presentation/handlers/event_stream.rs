@@ -0,0 +1,78 @@
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event, KeepAlive, Sse},
+    Extension,
+};
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_stream::{Stream, StreamExt};
+
+use crate::domain::entities::webhook::WebhookEventType;
+use crate::domain::services::webhook_dispatcher::delivery_payload;
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+
+/// Idle connections get a comment line this often so proxies don't close them
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Deserialize)]
+pub struct EventStreamQuery {
+    /// Comma-separated event types, e.g. `STOCK_MOVEMENT,SALES_ORDER_UPDATED`; all when omitted
+    pub types: Option<String>,
+}
+
+/// Server-sent events stream of the caller's tenant's inventory and order events.
+/// Each message's `event` field is the event type and its data is the same JSON
+/// envelope webhooks receive. A `lagged` message reports events dropped because the
+/// client fell behind.
+pub async fn stream_events(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Query(query): Query<EventStreamQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let tenant_id = tenant_context.tenant_id;
+
+    let types = match query.types.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(types) => Some(
+            types
+                .split(',')
+                .map(|t| WebhookEventType::from_str(t.trim()))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| ApiError::bad_request(e.to_string()))?,
+        ),
+    };
+
+    let stream =
+        BroadcastStream::new(state.event_broadcaster.subscribe()).filter_map(move |message| {
+            match message {
+                Ok(event) => {
+                    if event.tenant_id != Some(tenant_id) {
+                        return None;
+                    }
+                    if types
+                        .as_ref()
+                        .is_some_and(|types| !types.contains(&event.event_type))
+                    {
+                        return None;
+                    }
+                    Some(Ok(Event::default()
+                        .id(event.id.to_string())
+                        .event(event.event_type.as_str())
+                        .data(delivery_payload(&event).to_string())))
+                }
+                Err(BroadcastStreamRecvError::Lagged(missed)) => Some(Ok(Event::default()
+                    .event("lagged")
+                    .data(missed.to_string()))),
+            }
+        });
+
+    Ok(Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(HEARTBEAT_INTERVAL)
+            .text("heartbeat"),
+    ))
+}
@@ -0,0 +1,35 @@
+use crate::application::use_cases::forecast_demand::ForecastSnapshotsQuery;
+use crate::domain::entities::forecast::{DemandForecast, ForecastQuery};
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use uuid::Uuid;
+
+/// Forecast an item's demand from its outbound movements and store the result
+pub async fn get_item_forecast(
+    State(state): State<AppState>,
+    Path(item_id): Path<Uuid>,
+    Query(query): Query<ForecastQuery>,
+) -> Result<Json<DemandForecast>, ApiError> {
+    let forecast = state
+        .forecast_demand_use_case
+        .execute(item_id, query)
+        .await?;
+    Ok(Json(forecast))
+}
+
+/// Forecasts previously stored for an item, newest first
+pub async fn list_item_forecast_snapshots(
+    State(state): State<AppState>,
+    Path(item_id): Path<Uuid>,
+    Query(query): Query<ForecastSnapshotsQuery>,
+) -> Result<Json<Vec<DemandForecast>>, ApiError> {
+    let snapshots = state
+        .forecast_demand_use_case
+        .snapshots(item_id, query)
+        .await?;
+    Ok(Json(snapshots))
+}
@@ -0,0 +1,25 @@
+use crate::presentation::graphql::{execute, schema, Repositories};
+use crate::AppState;
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Json},
+};
+
+/// Run a query. A document that doesn't parse or doesn't fit the schema comes
+/// back with only `errors`; failures while resolving come back in `errors`
+/// next to whatever data could be resolved.
+pub async fn execute_graphql(
+    State(state): State<AppState>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(execute(Repositories::new(&state), request).await)
+}
+
+/// The schema in SDL, for client code generators and tooling
+pub async fn get_graphql_schema() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        schema().sdl(),
+    )
+}
@@ -0,0 +1,78 @@
+use crate::application::use_cases::manage_inbound_shipments::{
+    InboundShipmentResponse, ReceiveInboundCartonsRequest, ReceiveInboundCartonsResponse,
+};
+use crate::domain::entities::inbound_shipment::{CreateInboundShipmentRequest, InboundShipment};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::presentation::handlers::purchase_order::receive_error;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use uuid::Uuid;
+
+/// Record a supplier's advance shipping notice against a purchase order
+pub async fn create_inbound_shipment(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<CreateInboundShipmentRequest>,
+) -> Result<(StatusCode, Json<InboundShipmentResponse>), ApiError> {
+    let response = state
+        .manage_inbound_shipments_use_case
+        .create(request, acting_user(&tenant_context))
+        .await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn get_inbound_shipment(
+    State(state): State<AppState>,
+    Path(asn_id): Path<Uuid>,
+) -> Result<Json<InboundShipmentResponse>, ApiError> {
+    Ok(Json(
+        state.manage_inbound_shipments_use_case.get(asn_id).await?,
+    ))
+}
+
+pub async fn list_purchase_order_inbound_shipments(
+    State(state): State<AppState>,
+    Path(po_id): Path<Uuid>,
+) -> Result<Json<Vec<InboundShipment>>, ApiError> {
+    Ok(Json(
+        state
+            .manage_inbound_shipments_use_case
+            .list_by_po(po_id)
+            .await?,
+    ))
+}
+
+/// Check in scanned cartons, receiving their contents against the PO
+pub async fn receive_inbound_cartons(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(asn_id): Path<Uuid>,
+    Json(request): Json<ReceiveInboundCartonsRequest>,
+) -> Result<Json<ReceiveInboundCartonsResponse>, ApiError> {
+    state
+        .manage_inbound_shipments_use_case
+        .receive(asn_id, request, acting_user(&tenant_context))
+        .await
+        .map(Json)
+        .map_err(receive_error)
+}
+
+/// Close the shipment, reporting cartons that never arrived as missing
+pub async fn close_inbound_shipment(
+    State(state): State<AppState>,
+    Path(asn_id): Path<Uuid>,
+) -> Result<Json<InboundShipmentResponse>, ApiError> {
+    Ok(Json(
+        state
+            .manage_inbound_shipments_use_case
+            .close(asn_id)
+            .await?,
+    ))
+}
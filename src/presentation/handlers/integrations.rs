@@ -0,0 +1,147 @@
+use crate::application::use_cases::manage_connectors::{
+    AuthorizeConnectorResponse, ListConnectorsQuery, ListConnectorsResponse,
+};
+use crate::application::use_cases::sync_connector::SyncRunsResponse;
+use crate::domain::entities::integration::{
+    ConnectorCredentialsRequest, CreateConnectorRequest, IntegrationConnector, ListSyncRunsQuery,
+    OAuthCallbackRequest, UpdateConnectorRequest,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+
+pub async fn create_connector(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<CreateConnectorRequest>,
+) -> Result<(StatusCode, Json<IntegrationConnector>), ApiError> {
+    let created_by = acting_user(&tenant_context);
+
+    state
+        .manage_connectors_use_case
+        .create(request, created_by)
+        .await
+        .map(|connector| (StatusCode::CREATED, Json(connector)))
+        .map_err(ApiError::from)
+}
+
+pub async fn list_connectors(
+    State(state): State<AppState>,
+    Query(query): Query<ListConnectorsQuery>,
+) -> Result<Json<ListConnectorsResponse>, ApiError> {
+    state
+        .manage_connectors_use_case
+        .list(query)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_connector(
+    State(state): State<AppState>,
+    Path(connector_id): Path<Uuid>,
+) -> Result<Json<IntegrationConnector>, ApiError> {
+    state
+        .manage_connectors_use_case
+        .get(connector_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn update_connector(
+    State(state): State<AppState>,
+    Path(connector_id): Path<Uuid>,
+    Json(request): Json<UpdateConnectorRequest>,
+) -> Result<Json<IntegrationConnector>, ApiError> {
+    state
+        .manage_connectors_use_case
+        .update(connector_id, request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn delete_connector(
+    State(state): State<AppState>,
+    Path(connector_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .manage_connectors_use_case
+        .delete(connector_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(ApiError::from)
+}
+
+/// Store an access token issued outside the OAuth flow
+pub async fn set_connector_credentials(
+    State(state): State<AppState>,
+    Path(connector_id): Path<Uuid>,
+    Json(request): Json<ConnectorCredentialsRequest>,
+) -> Result<Json<IntegrationConnector>, ApiError> {
+    state
+        .manage_connectors_use_case
+        .set_credentials(connector_id, request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn authorize_connector(
+    State(state): State<AppState>,
+    Path(connector_id): Path<Uuid>,
+) -> Result<Json<AuthorizeConnectorResponse>, ApiError> {
+    state
+        .manage_connectors_use_case
+        .begin_oauth(connector_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// The client relays the `code` and `state` the channel redirected the merchant back with
+pub async fn complete_connector_authorization(
+    State(state): State<AppState>,
+    Path(connector_id): Path<Uuid>,
+    Json(request): Json<OAuthCallbackRequest>,
+) -> Result<Json<IntegrationConnector>, ApiError> {
+    state
+        .manage_connectors_use_case
+        .complete_oauth(connector_id, request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn sync_connector(
+    State(state): State<AppState>,
+    Path(connector_id): Path<Uuid>,
+) -> Result<Json<SyncRunsResponse>, ApiError> {
+    state
+        .sync_connector_use_case
+        .sync(connector_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn list_sync_runs(
+    State(state): State<AppState>,
+    Path(connector_id): Path<Uuid>,
+    Query(query): Query<ListSyncRunsQuery>,
+) -> Result<Json<SyncRunsResponse>, ApiError> {
+    state
+        .sync_connector_use_case
+        .list_runs(connector_id, query)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
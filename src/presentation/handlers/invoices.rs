@@ -0,0 +1,65 @@
+use crate::application::use_cases::manage_invoices::{InvoiceResponse, RecordPaymentResponse};
+use crate::domain::entities::invoice::{CreateInvoiceRequest, Invoice, RecordPaymentRequest};
+use crate::domain::entities::list_filter::ListFilter;
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
+use crate::shared::pagination::{Page, PageRequest};
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use uuid::Uuid;
+
+/// Invoice a shipped sales order, moving it to INVOICED
+pub async fn create_invoice(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(so_id): Path<Uuid>,
+    request: Option<Json<CreateInvoiceRequest>>,
+) -> Result<(StatusCode, Json<InvoiceResponse>), ApiError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let created_by = acting_user(&tenant_context);
+
+    let response = state
+        .manage_invoices_use_case
+        .create_for_sales_order(so_id, request, created_by)
+        .await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn list_invoices(
+    State(state): State<AppState>,
+    Query(page): Query<PageRequest>,
+    Query(filter): Query<ListFilter>,
+) -> Result<Json<Page<Invoice>>, ApiError> {
+    Ok(Json(
+        state.manage_invoices_use_case.list(&filter, page).await?,
+    ))
+}
+
+pub async fn get_invoice(
+    State(state): State<AppState>,
+    Path(invoice_id): Path<Uuid>,
+) -> Result<Json<InvoiceResponse>, ApiError> {
+    Ok(Json(state.manage_invoices_use_case.get(invoice_id).await?))
+}
+
+/// Record a customer payment against an invoice
+pub async fn record_payment(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(invoice_id): Path<Uuid>,
+    Json(request): Json<RecordPaymentRequest>,
+) -> Result<(StatusCode, Json<RecordPaymentResponse>), ApiError> {
+    let recorded_by = acting_user(&tenant_context);
+
+    let response = state
+        .manage_invoices_use_case
+        .record_payment(invoice_id, request, recorded_by)
+        .await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
@@ -2,22 +2,20 @@ use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::Json,
+    Extension,
 };
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 
 use crate::application::use_cases::{
+    cancel_job::CancelJobRequest,
     enqueue_job::{EnqueueJobRequest, EnqueueJobUseCase},
-    get_job_status::{GetJobStatusRequest, GetJobStatusUseCase},
+    get_job_status::{GetJobStatusRequest, GetJobStatusResponse, GetJobStatusUseCase},
+    retry_job::RetryJobRequest,
 };
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::shared::api_error::ApiError;
 use crate::AppState;
 
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub message: String,
-}
-
 #[derive(Debug, Deserialize)]
 pub struct EnqueueJobPayload {
     #[serde(rename = "type")]
@@ -39,6 +37,9 @@ pub struct JobStatusResponse {
     pub r#type: String,
     pub status: String,
     pub progress: i32,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub cancel_requested: bool,
     pub result_url: Option<String>,
     pub errors: Option<Vec<serde_json::Value>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -47,85 +48,98 @@ pub struct JobStatusResponse {
     pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+impl From<GetJobStatusResponse> for JobStatusResponse {
+    fn from(response: GetJobStatusResponse) -> Self {
+        let errors = response.errors.map(|e| {
+            e.into_iter()
+                .map(|error| serde_json::to_value(error).unwrap_or_default())
+                .collect()
+        });
+
+        Self {
+            job_id: response.job_id,
+            r#type: response.job_type,
+            status: response.status,
+            progress: response.progress,
+            attempts: response.attempts,
+            max_attempts: response.max_attempts,
+            cancel_requested: response.cancel_requested,
+            result_url: response.result_url,
+            errors,
+            created_at: response.created_at,
+            updated_at: response.updated_at,
+            started_at: response.started_at,
+            completed_at: response.completed_at,
+        }
+    }
+}
+
 /// Enqueue a new async job
 pub async fn enqueue_job(
     State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
     Json(payload): Json<EnqueueJobPayload>,
-) -> Result<(StatusCode, Json<EnqueueJobResponse>), (StatusCode, Json<ErrorResponse>)> {
-    // For now, use a hardcoded tenant ID - tenant isolation will be added later
-    let tenant_id = uuid::Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
-    match state
+) -> Result<(StatusCode, Json<EnqueueJobResponse>), ApiError> {
+    let tenant_id = tenant_context.tenant_id;
+    let response = state
         .enqueue_job_use_case
         .execute(EnqueueJobRequest {
             tenant_id,
             job_type: payload.r#type,
             payload: payload.payload,
         })
-        .await
-    {
-        Ok(response) => Ok((
-            StatusCode::ACCEPTED,
-            Json(EnqueueJobResponse {
-                job_id: response.job_id,
-                status: response.status,
-                created_at: response.created_at,
-            }),
-        )),
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "Bad Request".to_string(),
-                message: e.to_string(),
-            }),
-        )),
-    }
+        .await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(EnqueueJobResponse {
+            job_id: response.job_id,
+            status: response.status,
+            created_at: response.created_at,
+        }),
+    ))
 }
 
 /// Get job status by job ID
 pub async fn get_job_status(
     State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
     Path(job_id): Path<String>,
-) -> Result<Json<JobStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // For now, use a hardcoded tenant ID - tenant isolation will be added later
-    let tenant_id = uuid::Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
-    match state
+) -> Result<Json<JobStatusResponse>, ApiError> {
+    let tenant_id = tenant_context.tenant_id;
+    state
         .get_job_status_use_case
         .execute(GetJobStatusRequest { tenant_id, job_id })
+        .await?
+        .map(|response| Json(response.into()))
+        .ok_or_else(|| ApiError::not_found("Job not found"))
+}
+
+/// Cancel a queued or running job
+pub async fn cancel_job(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(job_id): Path<String>,
+) -> Result<Json<JobStatusResponse>, ApiError> {
+    let tenant_id = tenant_context.tenant_id;
+    state
+        .cancel_job_use_case
+        .execute(CancelJobRequest { tenant_id, job_id })
         .await
-    {
-        Ok(Some(response)) => {
-            let errors = response.errors.map(|e| {
-                e.into_iter()
-                    .map(|error| serde_json::to_value(error).unwrap_or_default())
-                    .collect()
-            });
+        .map(|response| Json(response.into()))
+        .map_err(ApiError::from)
+}
 
-            Ok(Json(JobStatusResponse {
-                job_id: response.job_id,
-                r#type: response.job_type,
-                status: response.status,
-                progress: response.progress,
-                result_url: response.result_url,
-                errors,
-                created_at: response.created_at,
-                updated_at: response.updated_at,
-                started_at: response.started_at,
-                completed_at: response.completed_at,
-            }))
-        }
-        Ok(None) => Err((
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                error: "Not Found".to_string(),
-                message: "Job not found".to_string(),
-            }),
-        )),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "Internal Server Error".to_string(),
-                message: e.to_string(),
-            }),
-        )),
-    }
+/// Re-queue a failed or cancelled job
+pub async fn retry_job(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(job_id): Path<String>,
+) -> Result<(StatusCode, Json<JobStatusResponse>), ApiError> {
+    let tenant_id = tenant_context.tenant_id;
+    state
+        .retry_job_use_case
+        .execute(RetryJobRequest { tenant_id, job_id })
+        .await
+        .map(|response| (StatusCode::ACCEPTED, Json(response.into())))
+        .map_err(ApiError::from)
 }
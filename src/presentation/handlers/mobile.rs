@@ -0,0 +1,52 @@
+use crate::application::use_cases::mobile_scanning::{
+    ScanCountRequest, ScanCountResponse, ScanPickRequest, ScanPickResponse, ScanReceiveRequest,
+    ScanReceiveResponse,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::presentation::handlers::purchase_order::receive_error;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{extract::State, response::Json, Extension};
+
+/// Receive a scanned item against a purchase order number
+pub async fn scan_receive(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<ScanReceiveRequest>,
+) -> Result<Json<ScanReceiveResponse>, ApiError> {
+    state
+        .scan_to_receive_use_case
+        .execute(request, acting_user(&tenant_context))
+        .await
+        .map(Json)
+        .map_err(receive_error)
+}
+
+/// Pick a scanned item from a scanned bin for a sales order
+pub async fn scan_pick(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<ScanPickRequest>,
+) -> Result<Json<ScanPickResponse>, ApiError> {
+    state
+        .scan_to_pick_use_case
+        .execute(request, acting_user(&tenant_context))
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// Record a scanned count for an item at a scanned location
+pub async fn scan_count(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<ScanCountRequest>,
+) -> Result<Json<ScanCountResponse>, ApiError> {
+    state
+        .scan_to_count_use_case
+        .execute(request, acting_user(&tenant_context))
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
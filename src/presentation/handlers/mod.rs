@@ -1,13 +1,43 @@
 // Presentation layer handlers
+pub mod actor;
+pub mod addresses;
+pub mod adjustments;
 pub mod admin;
+pub mod attachments;
+pub mod availability;
+pub mod barcode;
+pub mod blobs;
+pub mod conditional;
+pub mod credit;
+pub mod currency;
+pub mod cycle_count;
+pub mod documents;
+pub mod edi;
+pub mod event_stream;
+pub mod forecast;
+pub mod graphql;
+pub mod inbound_shipments;
+pub mod integrations;
+pub mod invoices;
 pub mod jobs;
+pub mod mobile;
+pub mod printing;
+pub mod products;
 pub mod purchase_order;
+pub mod putaway;
+pub mod receiving;
 pub mod reports;
 pub mod returns;
 pub mod sales_order;
 pub mod search;
+pub mod shipment;
+pub mod status_history;
 pub mod stock;
 pub mod tenant;
 pub mod transfer;
+pub mod uploads;
+pub mod users;
+pub mod vendor_returns;
 pub mod webhook;
 pub mod webhook_deliveries;
+pub mod webhook_retention;
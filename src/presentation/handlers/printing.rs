@@ -0,0 +1,147 @@
+use crate::application::use_cases::generate_shipment_labels::ShipmentLabelsQuery;
+use crate::application::use_cases::manage_label_printing::{
+    CreatePrintJobRequest, ListPrintJobsQuery, ListPrintJobsResponse, ListPrintersQuery,
+    ListPrintersResponse,
+};
+use crate::domain::entities::printing::{
+    CreatePrinterRequest, PrintJob, Printer, UpdatePrinterRequest,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use uuid::Uuid;
+
+pub async fn create_printer(
+    State(state): State<AppState>,
+    Json(request): Json<CreatePrinterRequest>,
+) -> Result<(StatusCode, Json<Printer>), ApiError> {
+    state
+        .manage_label_printing_use_case
+        .create_printer(request)
+        .await
+        .map(|printer| (StatusCode::CREATED, Json(printer)))
+        .map_err(ApiError::from)
+}
+
+pub async fn list_printers(
+    State(state): State<AppState>,
+    Query(query): Query<ListPrintersQuery>,
+) -> Result<Json<ListPrintersResponse>, ApiError> {
+    state
+        .manage_label_printing_use_case
+        .list_printers(query)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_printer(
+    State(state): State<AppState>,
+    Path(printer_id): Path<Uuid>,
+) -> Result<Json<Printer>, ApiError> {
+    state
+        .manage_label_printing_use_case
+        .get_printer(printer_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn update_printer(
+    State(state): State<AppState>,
+    Path(printer_id): Path<Uuid>,
+    Json(request): Json<UpdatePrinterRequest>,
+) -> Result<Json<Printer>, ApiError> {
+    state
+        .manage_label_printing_use_case
+        .update_printer(printer_id, request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn delete_printer(
+    State(state): State<AppState>,
+    Path(printer_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .manage_label_printing_use_case
+        .delete_printer(printer_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(ApiError::from)
+}
+
+/// Queue labels for a printer; the job is sent in the background
+pub async fn create_print_job(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<CreatePrintJobRequest>,
+) -> Result<(StatusCode, Json<PrintJob>), ApiError> {
+    let shipment_labels = match request.shipment_id {
+        Some(shipment_id) => {
+            let rendered = state
+                .generate_shipment_labels_use_case
+                .execute(
+                    shipment_id,
+                    ShipmentLabelsQuery {
+                        format: Some("zpl".to_string()),
+                    },
+                )
+                .await?;
+            Some(String::from_utf8_lossy(&rendered.bytes).into_owned())
+        }
+        None => None,
+    };
+
+    state
+        .manage_label_printing_use_case
+        .create_print_job(tenant_context.tenant_id, request, shipment_labels)
+        .await
+        .map(|print_job| (StatusCode::ACCEPTED, Json(print_job)))
+        .map_err(ApiError::from)
+}
+
+pub async fn list_print_jobs(
+    State(state): State<AppState>,
+    Query(query): Query<ListPrintJobsQuery>,
+) -> Result<Json<ListPrintJobsResponse>, ApiError> {
+    state
+        .manage_label_printing_use_case
+        .list_print_jobs(query)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_print_job(
+    State(state): State<AppState>,
+    Path(print_job_id): Path<Uuid>,
+) -> Result<Json<PrintJob>, ApiError> {
+    state
+        .manage_label_printing_use_case
+        .get_print_job(print_job_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// Queue a failed print job again
+pub async fn retry_print_job(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(print_job_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<PrintJob>), ApiError> {
+    state
+        .manage_label_printing_use_case
+        .retry_print_job(tenant_context.tenant_id, print_job_id)
+        .await
+        .map(|print_job| (StatusCode::ACCEPTED, Json(print_job)))
+        .map_err(ApiError::from)
+}
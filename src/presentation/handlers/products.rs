@@ -0,0 +1,91 @@
+use crate::application::use_cases::manage_products::{
+    GenerateVariantsResponse, ListProductsQuery, ListProductsResponse, ProductResponse,
+};
+use crate::domain::entities::product::{
+    CreateProductRequest, GenerateVariantsRequest, UpdateProductRequest,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use uuid::Uuid;
+
+pub async fn create_product(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<CreateProductRequest>,
+) -> Result<(StatusCode, Json<ProductResponse>), ApiError> {
+    state
+        .manage_products_use_case
+        .create(request, tenant_context.tenant_id)
+        .await
+        .map(|product| (StatusCode::CREATED, Json(product)))
+        .map_err(ApiError::from)
+}
+
+pub async fn list_products(
+    State(state): State<AppState>,
+    Query(query): Query<ListProductsQuery>,
+) -> Result<Json<ListProductsResponse>, ApiError> {
+    state
+        .manage_products_use_case
+        .list(query)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_product(
+    State(state): State<AppState>,
+    Path(product_id): Path<Uuid>,
+) -> Result<Json<ProductResponse>, ApiError> {
+    state
+        .manage_products_use_case
+        .get(product_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn update_product(
+    State(state): State<AppState>,
+    Path(product_id): Path<Uuid>,
+    Json(request): Json<UpdateProductRequest>,
+) -> Result<Json<ProductResponse>, ApiError> {
+    state
+        .manage_products_use_case
+        .update(product_id, request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn delete_product(
+    State(state): State<AppState>,
+    Path(product_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .manage_products_use_case
+        .delete(product_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(ApiError::from)
+}
+
+pub async fn generate_variants(
+    State(state): State<AppState>,
+    Path(product_id): Path<Uuid>,
+    Json(request): Json<GenerateVariantsRequest>,
+) -> Result<Json<GenerateVariantsResponse>, ApiError> {
+    state
+        .manage_products_use_case
+        .generate_variants(product_id, request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
@@ -1,36 +1,46 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
     response::Json,
+    Extension,
 };
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
+use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::application::use_cases::{
+    cancel_purchase_order::CancelPurchaseOrderRequest,
+    close_purchase_order::ClosePurchaseOrderRequest,
     create_purchase_order::{
         CreatePurchaseOrderResponse, CreatePurchaseOrderUseCase, CreatePurchaseOrderUseCaseRequest,
     },
+    edit_purchase_order_lines::EditPurchaseOrderLinesUseCase,
     get_purchase_order::{GetPurchaseOrderResponse, GetPurchaseOrderUseCase},
     receive_purchase_order::{
         ReceivePurchaseOrderResponse, ReceivePurchaseOrderUseCase,
         ReceivePurchaseOrderUseCaseRequest,
     },
 };
-use crate::domain::entities::purchase_order::{CreatePurchaseOrderLine, ReceiveLine};
+use crate::domain::entities::inventory::StockStatus;
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::purchase_order::{
+    CreatePurchaseOrderLine, PromisePurchaseOrderLineRequest, PurchaseOrderReceipt, ReceiveLine,
+    UpdatePurchaseOrderLineRequest,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use crate::AppState;
 
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub message: String,
-}
-
 #[derive(Debug, Deserialize)]
 pub struct CreatePurchaseOrderRequest {
     pub supplier_id: Uuid,
     pub expected_date: Option<chrono::DateTime<chrono::Utc>>,
     pub lines: Vec<CreatePurchaseOrderLine>,
+    /// ISO 4217 code the supplier prices in; defaults to the tenant's base currency
+    pub currency: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -38,95 +48,220 @@ pub struct ReceivePurchaseOrderRequest {
     pub received_lines: Vec<ReceiveLine>,
     pub receive_date: Option<chrono::DateTime<chrono::Utc>>,
     pub destination_location_id: Uuid,
+    /// Defaults to AVAILABLE; QUARANTINE holds the goods for inspection
+    #[serde(default)]
+    pub stock_status: StockStatus,
 }
 
 /// Create a new purchase order
 pub async fn create_purchase_order(
     State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
     Json(request): Json<CreatePurchaseOrderRequest>,
-) -> Result<(StatusCode, Json<CreatePurchaseOrderResponse>), (StatusCode, Json<ErrorResponse>)> {
+) -> Result<(StatusCode, Json<CreatePurchaseOrderResponse>), ApiError> {
     let use_case_request = CreatePurchaseOrderUseCaseRequest {
         supplier_id: request.supplier_id,
         expected_date: request.expected_date,
         lines: request.lines,
+        currency: request.currency,
     };
 
-    // TODO: Get user ID from authentication context
-    let created_by = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(); // Use existing test user
+    let created_by = acting_user(&tenant_context);
 
-    match state
+    let response = state
         .create_purchase_order_use_case
         .execute(use_case_request, created_by)
-        .await
-    {
-        Ok(response) => Ok((StatusCode::CREATED, Json(response))),
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "PurchaseOrderError".to_string(),
-                message: e.to_string(),
-            }),
-        )),
-    }
+        .await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// List purchase orders matching the query filters
+pub async fn list_purchase_orders(
+    State(state): State<AppState>,
+    Query(page): Query<PageRequest>,
+    Query(filter): Query<ListFilter>,
+) -> Result<Json<Page<GetPurchaseOrderResponse>>, ApiError> {
+    Ok(Json(
+        state
+            .get_purchase_order_use_case
+            .list(&filter, page)
+            .await?,
+    ))
 }
 
 /// Get a purchase order by ID
 pub async fn get_purchase_order(
     State(state): State<AppState>,
     Path(po_id): Path<Uuid>,
-) -> Result<Json<GetPurchaseOrderResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match state.get_purchase_order_use_case.execute(po_id).await {
-        Ok(response) => Ok(Json(response)),
-        Err(e) => {
-            if e.to_string().contains("not found") {
-                Err((
-                    StatusCode::NOT_FOUND,
-                    Json(ErrorResponse {
-                        error: "NotFound".to_string(),
-                        message: e.to_string(),
-                    }),
-                ))
-            } else {
-                Err((
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ErrorResponse {
-                        error: "PurchaseOrderError".to_string(),
-                        message: e.to_string(),
-                    }),
-                ))
-            }
-        }
-    }
+) -> Result<Json<GetPurchaseOrderResponse>, ApiError> {
+    Ok(Json(
+        state.get_purchase_order_use_case.execute(po_id).await?,
+    ))
+}
+
+/// List the receiving sessions recorded against a purchase order
+pub async fn list_purchase_order_receipts(
+    State(state): State<AppState>,
+    Path(po_id): Path<Uuid>,
+) -> Result<Json<Vec<PurchaseOrderReceipt>>, ApiError> {
+    Ok(Json(
+        state.get_purchase_order_use_case.receipts(po_id).await?,
+    ))
 }
 
 /// Receive items for a purchase order
 pub async fn receive_purchase_order(
     State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
     Path(po_id): Path<Uuid>,
     Json(request): Json<ReceivePurchaseOrderRequest>,
-) -> Result<Json<ReceivePurchaseOrderResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<ReceivePurchaseOrderResponse>, ApiError> {
     let use_case_request = ReceivePurchaseOrderUseCaseRequest {
         po_id,
         received_lines: request.received_lines,
         receive_date: request.receive_date,
         destination_location_id: request.destination_location_id,
+        stock_status: request.stock_status,
     };
 
-    // TODO: Get user ID from authentication context
-    let received_by = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(); // Use existing test user
+    let received_by = acting_user(&tenant_context);
 
-    match state
+    let response = state
         .receive_purchase_order_use_case
         .execute(use_case_request, received_by)
         .await
-    {
-        Ok(response) => Ok(Json(response)),
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "ReceiveError".to_string(),
-                message: e.to_string(),
-            }),
-        )),
+        .map_err(receive_error)?;
+    Ok(Json(response))
+}
+
+/// Receiving past the supplier's over-receipt tolerance gets its own code
+pub(crate) fn receive_error(e: DomainError) -> ApiError {
+    match e {
+        DomainError::ValidationError(msg) if msg.key() == Some("error.over_receipt_tolerance") => {
+            ApiError::bad_request(msg).with_code("OVER_RECEIPT_TOLERANCE_EXCEEDED")
+        }
+        e => e.into(),
+    }
+}
+
+/// Cancel and close report an order in the wrong status as a validation error
+fn status_change_error(e: DomainError) -> ApiError {
+    match e {
+        DomainError::ValidationError(msg) => ApiError::conflict(msg).with_code("INVALID_STATUS"),
+        e => e.into(),
     }
 }
+
+/// Cancel a purchase order that has not received any goods
+pub async fn cancel_purchase_order(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(po_id): Path<Uuid>,
+    headers: HeaderMap,
+    request: Option<Json<CancelPurchaseOrderRequest>>,
+) -> Result<Json<GetPurchaseOrderResponse>, ApiError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+
+    let cancelled_by = acting_user(&tenant_context);
+
+    state
+        .cancel_purchase_order_use_case
+        .execute(po_id, request, cancelled_by, if_match(&headers))
+        .await
+        .map(Json)
+        .map_err(status_change_error)
+}
+
+/// Close a partially received purchase order so no further receipts are accepted
+pub async fn close_purchase_order(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(po_id): Path<Uuid>,
+    headers: HeaderMap,
+    request: Option<Json<ClosePurchaseOrderRequest>>,
+) -> Result<Json<GetPurchaseOrderResponse>, ApiError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+
+    let closed_by = acting_user(&tenant_context);
+
+    state
+        .close_purchase_order_use_case
+        .execute(po_id, request, closed_by, if_match(&headers))
+        .await
+        .map(Json)
+        .map_err(status_change_error)
+}
+
+fn if_match(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("if-match")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string())
+}
+
+/// Add a line to a draft purchase order
+pub async fn add_purchase_order_line(
+    State(state): State<AppState>,
+    Path(po_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(request): Json<CreatePurchaseOrderLine>,
+) -> Result<(StatusCode, Json<GetPurchaseOrderResponse>), ApiError> {
+    EditPurchaseOrderLinesUseCase::new(
+        Arc::clone(&state.purchase_order_repository),
+        state.item_repository.clone(),
+    )
+    .add_line(po_id, request, if_match(&headers))
+    .await
+    .map(|response| (StatusCode::CREATED, Json(response)))
+    .map_err(ApiError::from)
+}
+
+/// Change quantity or cost on a draft purchase order line
+pub async fn update_purchase_order_line(
+    State(state): State<AppState>,
+    Path((po_id, line_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    Json(request): Json<UpdatePurchaseOrderLineRequest>,
+) -> Result<Json<GetPurchaseOrderResponse>, ApiError> {
+    EditPurchaseOrderLinesUseCase::new(
+        Arc::clone(&state.purchase_order_repository),
+        state.item_repository.clone(),
+    )
+    .update_line(po_id, line_id, request, if_match(&headers))
+    .await
+    .map(Json)
+    .map_err(ApiError::from)
+}
+
+/// Remove a line from a draft purchase order
+pub async fn remove_purchase_order_line(
+    State(state): State<AppState>,
+    Path((po_id, line_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+) -> Result<Json<GetPurchaseOrderResponse>, ApiError> {
+    EditPurchaseOrderLinesUseCase::new(
+        Arc::clone(&state.purchase_order_repository),
+        state.item_repository.clone(),
+    )
+    .remove_line(po_id, line_id, if_match(&headers))
+    .await
+    .map(Json)
+    .map_err(ApiError::from)
+}
+
+/// Record the date the supplier has confirmed for a line on an open purchase order
+pub async fn promise_purchase_order_line(
+    State(state): State<AppState>,
+    Path((po_id, line_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    Json(request): Json<PromisePurchaseOrderLineRequest>,
+) -> Result<Json<GetPurchaseOrderResponse>, ApiError> {
+    EditPurchaseOrderLinesUseCase::new(
+        Arc::clone(&state.purchase_order_repository),
+        state.item_repository.clone(),
+    )
+    .promise_line(po_id, line_id, request, if_match(&headers))
+    .await
+    .map(Json)
+    .map_err(ApiError::from)
+}
@@ -0,0 +1,75 @@
+use crate::application::use_cases::manage_putaway_rules::{
+    ListPutawayRulesQuery, ListPutawayRulesResponse,
+};
+use crate::domain::entities::putaway::{
+    CreatePutawayRuleRequest, PutawayRule, UpdatePutawayRuleRequest,
+};
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+
+pub async fn create_putaway_rule(
+    State(state): State<AppState>,
+    Json(request): Json<CreatePutawayRuleRequest>,
+) -> Result<(StatusCode, Json<PutawayRule>), ApiError> {
+    state
+        .manage_putaway_rules_use_case
+        .create(request)
+        .await
+        .map(|rule| (StatusCode::CREATED, Json(rule)))
+        .map_err(ApiError::from)
+}
+
+pub async fn list_putaway_rules(
+    State(state): State<AppState>,
+    Query(query): Query<ListPutawayRulesQuery>,
+) -> Result<Json<ListPutawayRulesResponse>, ApiError> {
+    state
+        .manage_putaway_rules_use_case
+        .list(query)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_putaway_rule(
+    State(state): State<AppState>,
+    Path(rule_id): Path<Uuid>,
+) -> Result<Json<PutawayRule>, ApiError> {
+    state
+        .manage_putaway_rules_use_case
+        .get(rule_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn update_putaway_rule(
+    State(state): State<AppState>,
+    Path(rule_id): Path<Uuid>,
+    Json(request): Json<UpdatePutawayRuleRequest>,
+) -> Result<Json<PutawayRule>, ApiError> {
+    state
+        .manage_putaway_rules_use_case
+        .update(rule_id, request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn delete_putaway_rule(
+    State(state): State<AppState>,
+    Path(rule_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .manage_putaway_rules_use_case
+        .delete(rule_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(ApiError::from)
+}
@@ -0,0 +1,59 @@
+use crate::domain::entities::receiving::{
+    ReceivingSettings, SetSupplierToleranceRequest, UpdateReceivingSettingsRequest,
+};
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+
+pub async fn get_receiving_settings(
+    State(state): State<AppState>,
+) -> Result<Json<ReceivingSettings>, ApiError> {
+    state
+        .manage_receiving_settings_use_case
+        .get()
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn update_receiving_settings(
+    State(state): State<AppState>,
+    Json(request): Json<UpdateReceivingSettingsRequest>,
+) -> Result<Json<ReceivingSettings>, ApiError> {
+    state
+        .manage_receiving_settings_use_case
+        .update(request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// Give one supplier a tolerance other than the tenant's
+pub async fn set_supplier_tolerance(
+    State(state): State<AppState>,
+    Path(supplier_id): Path<Uuid>,
+    Json(request): Json<SetSupplierToleranceRequest>,
+) -> Result<Json<ReceivingSettings>, ApiError> {
+    state
+        .manage_receiving_settings_use_case
+        .set_supplier(supplier_id, request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn delete_supplier_tolerance(
+    State(state): State<AppState>,
+    Path(supplier_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .manage_receiving_settings_use_case
+        .remove_supplier(supplier_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -2,25 +2,36 @@ use axum::{
     extract::{Query, State},
     http::StatusCode,
     response::Json,
+    Extension,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::application::use_cases::{
+    create_reorder_purchase_orders::{
+        CreateReorderPurchaseOrdersRequest, CreateReorderPurchaseOrdersResponse,
+    },
+    get_dead_stock_report::DeadStockReportResponse,
+    get_location_utilization_report::LocationUtilizationReportResponse,
     get_low_stock_report::GetLowStockReportRequest,
+    get_overdue_purchase_orders_report::OverduePurchaseOrdersReportResponse,
+    get_receivables_aging_report::ReceivablesAgingReportResponse,
+    get_reorder_suggestions::ReorderSuggestionsResponse,
+    get_scrap_report::ScrapReportResponse,
     get_stock_valuation_report::GetStockValuationReportRequest,
 };
+use crate::domain::entities::forecast::ForecastQuery;
+use crate::domain::entities::time_zone::ReportBound;
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
+use crate::shared::pagination::Page;
 use crate::AppState;
-
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub message: String,
-}
+use rust_decimal::Decimal;
 
 #[derive(Debug, Deserialize)]
 pub struct LowStockQuery {
-    pub threshold: Option<i32>,
+    pub threshold: Option<Decimal>,
     pub limit: Option<i64>,
     pub cursor: Option<String>,
 }
@@ -33,16 +44,45 @@ pub struct StockValuationQuery {
     pub cursor: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct CursorMeta {
-    pub next_cursor: Option<String>,
-    pub has_more: bool,
+#[derive(Debug, Deserialize)]
+pub struct ReorderSuggestionsQuery {
+    pub supplier_id: Option<Uuid>,
+    /// Reorder at forecast demand rather than each item's fixed reorder point
+    #[serde(default)]
+    pub dynamic_reorder_points: bool,
+    pub forecast_method: Option<String>,
+    pub safety_stock_days: Option<u32>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct LowStockReportResponse {
-    pub data: Vec<LowStockItem>,
-    pub cursor: Option<CursorMeta>,
+#[derive(Debug, Deserialize)]
+pub struct ScrapReportQuery {
+    /// An instant, or a date read in the tenant's time zone
+    pub from: Option<ReportBound>,
+    /// An instant (exclusive), or a date whose whole day is included
+    pub to: Option<ReportBound>,
+    pub location_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeadStockQuery {
+    pub days: Option<i64>,
+    pub location_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OverduePurchaseOrdersQuery {
+    pub as_of: Option<chrono::DateTime<chrono::Utc>>,
+    pub supplier_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReceivablesAgingQuery {
+    pub as_of: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LocationUtilizationQuery {
+    pub location_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize)]
@@ -51,26 +91,20 @@ pub struct LowStockItem {
     pub stock: serde_json::Value,
 }
 
-#[derive(Debug, Serialize)]
-pub struct StockValuationReportResponse {
-    pub data: Vec<StockValuationItem>,
-    pub cursor: Option<CursorMeta>,
-}
-
 #[derive(Debug, Serialize)]
 pub struct StockValuationItem {
     pub item: serde_json::Value,
-    pub valuation: f64,
+    pub valuation: Decimal,
 }
 
 /// Get low stock report
 pub async fn get_low_stock_report(
     State(state): State<AppState>,
     Query(query): Query<LowStockQuery>,
-) -> Result<Json<LowStockReportResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let threshold = query.threshold.unwrap_or(10); // Default threshold of 10
+) -> Result<Json<Page<LowStockItem>>, ApiError> {
+    let threshold = query.threshold.unwrap_or(Decimal::from(10)); // Default threshold of 10
 
-    match state
+    let response = state
         .get_low_stock_report_use_case
         .execute(GetLowStockReportRequest {
             threshold,
@@ -78,56 +112,35 @@ pub async fn get_low_stock_report(
             cursor: query.cursor,
         })
         .await
-    {
-        Ok(response) => {
-            let cursor_meta = response.next_cursor.map(|cursor| CursorMeta {
-                next_cursor: Some(cursor),
-                has_more: true,
-            });
-
-            let data = response
-                .items
-                .into_iter()
-                .map(|item| LowStockItem {
-                    item: serde_json::to_value(&item.item).unwrap_or_default(),
-                    stock: serde_json::to_value(&item.stock).unwrap_or_default(),
-                })
-                .collect();
-
-            Ok(Json(LowStockReportResponse {
-                data,
-                cursor: cursor_meta,
-            }))
-        }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "ReportGenerationError".to_string(),
-                message: e.to_string(),
-            }),
-        )),
-    }
+        .map_err(ApiError::internal)?;
+    let data = response
+        .items
+        .into_iter()
+        .map(|item| LowStockItem {
+            item: serde_json::to_value(&item.item).unwrap_or_default(),
+            stock: serde_json::to_value(&item.stock).unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(Json(Page::new(data, response.next_cursor)))
 }
 
 /// Get stock valuation report
 pub async fn get_stock_valuation_report(
     State(state): State<AppState>,
     Query(query): Query<StockValuationQuery>,
-) -> Result<Json<StockValuationReportResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<Page<StockValuationItem>>, ApiError> {
     let valuation_method = query.valuation_method.unwrap_or_else(|| "FIFO".to_string());
 
     // Validate valuation method
     if !["FIFO", "LIFO", "AVG"].contains(&valuation_method.as_str()) {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "InvalidValuationMethod".to_string(),
-                message: "Valuation method must be one of: FIFO, LIFO, AVG".to_string(),
-            }),
-        ));
+        return Err(
+            ApiError::bad_request("Valuation method must be one of: FIFO, LIFO, AVG")
+                .with_code("INVALID_VALUATION_METHOD"),
+        );
     }
 
-    match state
+    let response = state
         .get_stock_valuation_report_use_case
         .execute(GetStockValuationReportRequest {
             location_id: query.location_id,
@@ -136,33 +149,107 @@ pub async fn get_stock_valuation_report(
             cursor: query.cursor,
         })
         .await
-    {
-        Ok(response) => {
-            let cursor_meta = response.next_cursor.map(|cursor| CursorMeta {
-                next_cursor: Some(cursor),
-                has_more: true,
-            });
-
-            let data = response
-                .items
-                .into_iter()
-                .map(|item| StockValuationItem {
-                    item: serde_json::to_value(&item.item).unwrap_or_default(),
-                    valuation: item.valuation,
-                })
-                .collect();
-
-            Ok(Json(StockValuationReportResponse {
-                data,
-                cursor: cursor_meta,
-            }))
-        }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "ReportGenerationError".to_string(),
-                message: e.to_string(),
-            }),
-        )),
-    }
+        .map_err(ApiError::internal)?;
+    let data = response
+        .items
+        .into_iter()
+        .map(|item| StockValuationItem {
+            item: serde_json::to_value(&item.item).unwrap_or_default(),
+            valuation: item.valuation,
+        })
+        .collect();
+
+    Ok(Json(Page::new(data, response.next_cursor)))
+}
+
+/// Get reorder suggestions grouped by supplier
+pub async fn get_reorder_suggestions(
+    State(state): State<AppState>,
+    Query(query): Query<ReorderSuggestionsQuery>,
+) -> Result<Json<ReorderSuggestionsResponse>, ApiError> {
+    let forecast = query.dynamic_reorder_points.then(|| ForecastQuery {
+        method: query.forecast_method,
+        safety_stock_days: query.safety_stock_days,
+        ..ForecastQuery::default()
+    });
+    let response = state
+        .get_reorder_suggestions_use_case
+        .execute(query.supplier_id, forecast.as_ref())
+        .await?;
+    Ok(Json(response))
+}
+
+/// Returned stock scrapped on processing, per item
+pub async fn get_scrap_report(
+    State(state): State<AppState>,
+    Query(query): Query<ScrapReportQuery>,
+) -> Result<Json<ScrapReportResponse>, ApiError> {
+    let response = state
+        .get_scrap_report_use_case
+        .execute(query.from, query.to, query.location_id)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Items with stock on hand but no outbound movements in the last `days`
+pub async fn get_dead_stock_report(
+    State(state): State<AppState>,
+    Query(query): Query<DeadStockQuery>,
+) -> Result<Json<DeadStockReportResponse>, ApiError> {
+    let response = state
+        .get_dead_stock_report_use_case
+        .execute(query.days, query.location_id)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Purchase order lines still awaited past their promised or expected date
+pub async fn get_overdue_purchase_orders_report(
+    State(state): State<AppState>,
+    Query(query): Query<OverduePurchaseOrdersQuery>,
+) -> Result<Json<OverduePurchaseOrdersReportResponse>, ApiError> {
+    let response = state
+        .get_overdue_purchase_orders_report_use_case
+        .execute(query.as_of, query.supplier_id)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Unpaid invoice balances per customer, bucketed by days past due
+pub async fn get_receivables_aging_report(
+    State(state): State<AppState>,
+    Query(query): Query<ReceivablesAgingQuery>,
+) -> Result<Json<ReceivablesAgingReportResponse>, ApiError> {
+    let response = state
+        .get_receivables_aging_report_use_case
+        .execute(query.as_of)
+        .await?;
+    Ok(Json(response))
+}
+
+/// How much of its volume, weight and pallet capacity each active location uses
+pub async fn get_location_utilization_report(
+    State(state): State<AppState>,
+    Query(query): Query<LocationUtilizationQuery>,
+) -> Result<Json<LocationUtilizationReportResponse>, ApiError> {
+    let response = state
+        .get_location_utilization_report_use_case
+        .execute(query.location_id)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Draft one purchase order per supplier from the current reorder suggestions
+pub async fn create_reorder_purchase_orders(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<CreateReorderPurchaseOrdersRequest>,
+) -> Result<(StatusCode, Json<CreateReorderPurchaseOrdersResponse>), ApiError> {
+    let created_by = acting_user(&tenant_context);
+
+    let response = state
+        .create_reorder_purchase_orders_use_case
+        .execute(request, created_by)
+        .await?;
+    Ok((StatusCode::CREATED, Json(response)))
 }
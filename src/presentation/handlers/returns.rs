@@ -1,117 +1,91 @@
 use crate::application::use_cases::create_return::{CreateReturnResponse, CreateReturnUseCase};
-use crate::application::use_cases::get_return::{GetReturnResponse, GetReturnUseCase};
+use crate::application::use_cases::get_return::{
+    GetReturnResponse, GetReturnUseCase, SalesOrderReturnsResponse,
+};
 use crate::application::use_cases::process_return::{ProcessReturnResponse, ProcessReturnUseCase};
+use crate::domain::entities::list_filter::ListFilter;
 use crate::domain::entities::returns::ProcessReturnRequest;
 use crate::domain::services::return_repository::ReturnRepository;
 use crate::infrastructure::repositories::postgres_return_repository::PostgresReturnRepository;
-use crate::shared::error::DomainError;
+use crate::shared::api_error::ApiError;
+use crate::shared::pagination::{Page, PageRequest};
 use crate::AppState;
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
     response::Json,
 };
-use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
 pub async fn create_return(
     State(state): State<AppState>,
     Json(request): Json<crate::domain::entities::returns::CreateReturnRequest>,
-) -> Result<Json<CreateReturnResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<CreateReturnResponse>, ApiError> {
     // TODO: Get user ID from authentication context
     let created_by = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(); // Use existing test user
 
-    match state
+    let response = state
         .create_return_use_case
         .execute(request, created_by)
-        .await
-    {
-        Ok(response) => Ok(Json(response)),
-        Err(DomainError::ValidationError(msg)) => {
-            Err((StatusCode::BAD_REQUEST, Json(json!({ "error": msg }))))
-        }
-        Err(e) => {
-            eprintln!("Error creating return: {:?}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": "Internal server error" })),
-            ))
-        }
-    }
+        .await?;
+    Ok(Json(response))
+}
+
+pub async fn list_returns(
+    State(state): State<AppState>,
+    Query(page): Query<PageRequest>,
+    Query(filter): Query<ListFilter>,
+) -> Result<Json<Page<GetReturnResponse>>, ApiError> {
+    let repo = Arc::new(PostgresReturnRepository::new(Arc::clone(&state.pool)));
+    let use_case = GetReturnUseCase::new(repo);
+
+    let response = use_case.list(&filter, page).await?;
+    Ok(Json(response))
 }
 
 pub async fn get_return(
     State(state): State<AppState>,
     Path(return_id): Path<Uuid>,
-) -> Result<Json<GetReturnResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<GetReturnResponse>, ApiError> {
     let repo = Arc::new(PostgresReturnRepository::new(Arc::clone(&state.pool)));
     let use_case = GetReturnUseCase::new(repo);
 
-    match use_case.execute(return_id).await {
-        Ok(response) => Ok(Json(response)),
-        Err(DomainError::NotFound(msg)) => {
-            Err((StatusCode::NOT_FOUND, Json(json!({ "error": msg }))))
-        }
-        Err(e) => {
-            eprintln!("Error getting return: {:?}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": "Internal server error" })),
-            ))
-        }
-    }
+    let response = use_case.execute(return_id).await?;
+    Ok(Json(response))
+}
+
+pub async fn get_sales_order_returns(
+    State(state): State<AppState>,
+    Path(so_id): Path<Uuid>,
+) -> Result<Json<SalesOrderReturnsResponse>, ApiError> {
+    let response = state.get_return_use_case.for_sales_order(so_id).await?;
+    Ok(Json(response))
 }
 
 pub async fn process_return(
     State(state): State<AppState>,
     Path(return_id): Path<Uuid>,
     Json(request): Json<ProcessReturnRequest>,
-) -> Result<Json<ProcessReturnResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<ProcessReturnResponse>, ApiError> {
     let repo = Arc::new(PostgresReturnRepository::new(Arc::clone(&state.pool)));
     let use_case = ProcessReturnUseCase::new(repo);
 
     // TODO: Get user ID from authentication context
     let processed_by = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(); // Use existing test user
 
-    match use_case.execute(return_id, request, processed_by).await {
-        Ok(response) => Ok(Json(response)),
-        Err(DomainError::ValidationError(msg)) => {
-            Err((StatusCode::BAD_REQUEST, Json(json!({ "error": msg }))))
-        }
-        Err(DomainError::NotFound(msg)) => {
-            Err((StatusCode::NOT_FOUND, Json(json!({ "error": msg }))))
-        }
-        Err(e) => {
-            eprintln!("Error processing return: {:?}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": "Internal server error" })),
-            ))
-        }
-    }
+    let response = use_case.execute(return_id, request, processed_by).await?;
+    Ok(Json(response))
 }
 
 pub async fn open_return(
     State(state): State<AppState>,
     Path(return_id): Path<Uuid>,
-) -> Result<Json<GetReturnResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<GetReturnResponse>, ApiError> {
     let repo = Arc::new(PostgresReturnRepository::new(Arc::clone(&state.pool)));
 
-    match repo.open_return(return_id).await {
-        Ok((return_entity, lines)) => Ok(Json(GetReturnResponse {
-            return_entity,
-            lines,
-        })),
-        Err(DomainError::NotFound(msg)) => {
-            Err((StatusCode::NOT_FOUND, Json(json!({ "error": msg }))))
-        }
-        Err(DomainError::ValidationError(msg)) => {
-            Err((StatusCode::BAD_REQUEST, Json(json!({ "error": msg }))))
-        }
-        Err(_) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": "Internal server error" })),
-        )),
-    }
+    let (return_entity, lines) = repo.open_return(return_id).await?;
+    Ok(Json(GetReturnResponse {
+        return_entity,
+        lines,
+    }))
 }
@@ -1,91 +1,216 @@
 use crate::application::use_cases::{
-    create_sales_order::{CreateSalesOrderRequest, CreateSalesOrderResponse},
+    cancel_sales_order::{CancelSalesOrderRequest, CancelSalesOrderResponse},
+    create_backorder::CreateBackorderResponse,
+    create_sales_order::{
+        CreateSalesOrderLineRequest, CreateSalesOrderRequest, CreateSalesOrderResponse,
+    },
+    edit_sales_order_lines::EditSalesOrderLinesUseCase,
     get_sales_order::{GetSalesOrderUseCase, SalesOrderWithLines},
+    get_sales_order_allocations::GetSalesOrderAllocationsResponse,
     ship_sales_order::{ShipSalesOrderRequest, ShipSalesOrderResponse},
 };
+use crate::domain::entities::allocation::{AllocateSalesOrderRequest, AllocationPlan};
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::sales_order::UpdateSalesOrderLineRequest;
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
 use crate::infrastructure::repositories::postgres_sales_order_repository::PostgresSalesOrderRepository;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
 use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
 use crate::AppState;
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
     response::Json,
+    Extension,
 };
-use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
 pub async fn create_sales_order(
     State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
     Json(request): Json<CreateSalesOrderRequest>,
-) -> Result<Json<CreateSalesOrderResponse>, (StatusCode, Json<serde_json::Value>)> {
-    // TODO: Get user ID from authentication context
-    let created_by = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(); // Use existing test user
+) -> Result<Json<CreateSalesOrderResponse>, ApiError> {
+    // Overriding a credit limit is down to who is asking, so it needs a login
+    if request.override_credit_limit && tenant_context.user_id.is_none() {
+        return Err(
+            ApiError::unauthorized("Log in to override a credit limit").with_code("LOGIN_REQUIRED")
+        );
+    }
+    let created_by = acting_user(&tenant_context);
 
-    match state
+    let response = state
         .create_sales_order_use_case
         .execute(request, created_by)
-        .await
-    {
-        Ok(response) => Ok(Json(response)),
-        Err(DomainError::ValidationError(msg)) => {
-            Err((StatusCode::BAD_REQUEST, Json(json!({ "error": msg }))))
-        }
-        Err(e) => {
-            eprintln!("Error creating sales order: {:?}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": "Internal server error" })),
-            ))
-        }
-    }
+        .await?;
+    Ok(Json(response))
+}
+
+pub async fn list_sales_orders(
+    State(state): State<AppState>,
+    Query(page): Query<PageRequest>,
+    Query(filter): Query<ListFilter>,
+) -> Result<Json<Page<SalesOrderWithLines>>, ApiError> {
+    let repo = PostgresSalesOrderRepository::new(Arc::clone(&state.pool));
+    let use_case = GetSalesOrderUseCase::new(repo);
+
+    let response = use_case.list(&filter, page).await?;
+    Ok(Json(response))
 }
 
 pub async fn get_sales_order(
     State(state): State<AppState>,
     Path(so_id): Path<Uuid>,
-) -> Result<Json<SalesOrderWithLines>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<SalesOrderWithLines>, ApiError> {
     let repo = PostgresSalesOrderRepository::new(Arc::clone(&state.pool));
     let use_case = GetSalesOrderUseCase::new(repo);
 
-    match use_case.execute(so_id).await {
-        Ok(response) => Ok(Json(response)),
-        Err(DomainError::NotFound(msg)) => {
-            Err((StatusCode::NOT_FOUND, Json(json!({ "error": msg }))))
-        }
-        Err(e) => {
-            eprintln!("Error getting sales order: {:?}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": "Internal server error" })),
-            ))
-        }
-    }
+    let response = use_case.execute(so_id).await?;
+    Ok(Json(response))
+}
+
+pub async fn get_sales_order_by_number(
+    State(state): State<AppState>,
+    Path(so_number): Path<String>,
+) -> Result<Json<SalesOrderWithLines>, ApiError> {
+    let repo = PostgresSalesOrderRepository::new(Arc::clone(&state.pool));
+    let use_case = GetSalesOrderUseCase::new(repo);
+
+    let response = use_case.execute_by_number(&so_number).await?;
+    Ok(Json(response))
 }
 
 pub async fn ship_sales_order(
     State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
     Path(so_id): Path<Uuid>,
     Json(request): Json<ShipSalesOrderRequest>,
-) -> Result<Json<ShipSalesOrderResponse>, (StatusCode, Json<serde_json::Value>)> {
-    // TODO: Get user ID from authentication context
-    let created_by = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(); // Use existing test user
+) -> Result<Json<ShipSalesOrderResponse>, ApiError> {
+    let created_by = acting_user(&tenant_context);
 
-    match state
+    let response = state
         .ship_sales_order_use_case
         .execute(so_id, request, created_by)
+        .await?;
+    Ok(Json(response))
+}
+
+pub async fn cancel_sales_order(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(so_id): Path<Uuid>,
+    headers: HeaderMap,
+    request: Option<Json<CancelSalesOrderRequest>>,
+) -> Result<Json<CancelSalesOrderResponse>, ApiError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+
+    let cancelled_by = acting_user(&tenant_context);
+
+    state
+        .cancel_sales_order_use_case
+        .execute(so_id, request, cancelled_by, if_match(&headers))
         .await
-    {
-        Ok(response) => Ok(Json(response)),
-        Err(DomainError::ValidationError(msg)) | Err(DomainError::NotFound(msg)) => {
-            Err((StatusCode::BAD_REQUEST, Json(json!({ "error": msg }))))
-        }
-        Err(e) => {
-            eprintln!("Error shipping sales order: {:?}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": "Internal server error" })),
-            ))
-        }
-    }
+        .map(Json)
+        .map_err(|e| match e {
+            // An order in the wrong status is reported as a validation error
+            DomainError::ValidationError(msg) => {
+                ApiError::conflict(msg).with_code("INVALID_STATUS")
+            }
+            e => e.into(),
+        })
+}
+
+pub async fn create_backorder(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(so_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<CreateBackorderResponse>), ApiError> {
+    let created_by = acting_user(&tenant_context);
+
+    let response = state
+        .create_backorder_use_case
+        .execute(so_id, created_by)
+        .await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn allocate_sales_order(
+    State(state): State<AppState>,
+    Path(so_id): Path<Uuid>,
+    request: Option<Json<AllocateSalesOrderRequest>>,
+) -> Result<Json<AllocationPlan>, ApiError> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+
+    let response = state
+        .allocate_sales_order_use_case
+        .execute(so_id, request)
+        .await?;
+    Ok(Json(response))
+}
+
+pub async fn get_sales_order_allocations(
+    State(state): State<AppState>,
+    Path(so_id): Path<Uuid>,
+) -> Result<Json<GetSalesOrderAllocationsResponse>, ApiError> {
+    let response = state
+        .get_sales_order_allocations_use_case
+        .execute(so_id)
+        .await?;
+    Ok(Json(response))
+}
+
+fn if_match(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("if-match")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string())
+}
+
+pub async fn add_sales_order_line(
+    State(state): State<AppState>,
+    Path(so_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(request): Json<CreateSalesOrderLineRequest>,
+) -> Result<(StatusCode, Json<SalesOrderWithLines>), ApiError> {
+    EditSalesOrderLinesUseCase::new(
+        Arc::clone(&state.sales_order_repository),
+        state.item_repository.clone(),
+    )
+    .add_line(so_id, request, if_match(&headers))
+    .await
+    .map(|response| (StatusCode::CREATED, Json(response)))
+    .map_err(ApiError::from)
+}
+
+pub async fn update_sales_order_line(
+    State(state): State<AppState>,
+    Path((so_id, line_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    Json(request): Json<UpdateSalesOrderLineRequest>,
+) -> Result<Json<SalesOrderWithLines>, ApiError> {
+    EditSalesOrderLinesUseCase::new(
+        Arc::clone(&state.sales_order_repository),
+        state.item_repository.clone(),
+    )
+    .update_line(so_id, line_id, request, if_match(&headers))
+    .await
+    .map(Json)
+    .map_err(ApiError::from)
+}
+
+pub async fn remove_sales_order_line(
+    State(state): State<AppState>,
+    Path((so_id, line_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+) -> Result<Json<SalesOrderWithLines>, ApiError> {
+    EditSalesOrderLinesUseCase::new(
+        Arc::clone(&state.sales_order_repository),
+        state.item_repository.clone(),
+    )
+    .remove_line(so_id, line_id, if_match(&headers))
+    .await
+    .map(Json)
+    .map_err(ApiError::from)
 }
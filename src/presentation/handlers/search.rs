@@ -2,17 +2,18 @@ use axum::{
     extract::{Query, State},
     http::StatusCode,
     response::Json,
+    Extension,
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub message: String,
-}
-
+use crate::application::use_cases::enqueue_job::EnqueueJobRequest;
 use crate::application::use_cases::search_use_case::SearchUseCase;
-use crate::domain::entities::search::SearchQuery;
+use crate::domain::entities::search::{
+    SearchIndexHealth, SearchQuery, SuggestQuery, Suggestion, REBUILD_SEARCH_INDEX_JOB_TYPE,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::jobs::EnqueueJobResponse;
+use crate::shared::api_error::ApiError;
 use crate::AppState;
 
 #[derive(Debug, Deserialize)]
@@ -54,6 +55,19 @@ pub struct SuggestionsResponse {
     pub prefix: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SuggestParams {
+    q: String,
+    entity_types: Option<String>, // comma-separated list
+    limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SuggestResponse {
+    pub query: String,
+    pub suggestions: Vec<Suggestion>,
+}
+
 // Convert domain SearchResultItem to API ApiSearchResultItem
 impl From<&crate::domain::entities::search::SearchResultItem> for ApiSearchResultItem {
     fn from(item: &crate::domain::entities::search::SearchResultItem) -> Self {
@@ -86,22 +100,11 @@ impl From<SearchParams> for SearchQuery {
 pub async fn search_all(
     State(state): State<AppState>,
     Query(params): Query<SearchParams>,
-) -> Result<Json<SearchResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<SearchResponse>, ApiError> {
     let start_time = std::time::Instant::now();
 
     let query: SearchQuery = params.into();
-    let result = match state.search_use_case.search(query).await {
-        Ok(result) => result,
-        Err(e) => {
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "SearchError".to_string(),
-                    message: e.to_string(),
-                }),
-            ))
-        }
-    };
+    let result = state.search_use_case.search(query).await?;
 
     let took_ms = start_time.elapsed().as_millis() as u64;
 
@@ -123,22 +126,11 @@ pub async fn search_all(
 pub async fn search_items(
     State(state): State<AppState>,
     Query(params): Query<SearchParams>,
-) -> Result<Json<SearchResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<SearchResponse>, ApiError> {
     let start_time = std::time::Instant::now();
 
     let query: SearchQuery = params.into();
-    let result = match state.search_use_case.search_items(query).await {
-        Ok(result) => result,
-        Err(e) => {
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "SearchError".to_string(),
-                    message: e.to_string(),
-                }),
-            ))
-        }
-    };
+    let result = state.search_use_case.search_items(query).await?;
 
     let took_ms = start_time.elapsed().as_millis() as u64;
 
@@ -160,22 +152,11 @@ pub async fn search_items(
 pub async fn search_locations(
     State(state): State<AppState>,
     Query(params): Query<SearchParams>,
-) -> Result<Json<SearchResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<SearchResponse>, ApiError> {
     let start_time = std::time::Instant::now();
 
     let query: SearchQuery = params.into();
-    let result = match state.search_use_case.search_locations(query).await {
-        Ok(result) => result,
-        Err(e) => {
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "SearchError".to_string(),
-                    message: e.to_string(),
-                }),
-            ))
-        }
-    };
+    let result = state.search_use_case.search_locations(query).await?;
 
     let took_ms = start_time.elapsed().as_millis() as u64;
 
@@ -197,22 +178,11 @@ pub async fn search_locations(
 pub async fn search_stock_levels(
     State(state): State<AppState>,
     Query(params): Query<SearchParams>,
-) -> Result<Json<SearchResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<SearchResponse>, ApiError> {
     let start_time = std::time::Instant::now();
 
     let query: SearchQuery = params.into();
-    let result = match state.search_use_case.search_stock_levels(query).await {
-        Ok(result) => result,
-        Err(e) => {
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "SearchError".to_string(),
-                    message: e.to_string(),
-                }),
-            ))
-        }
-    };
+    let result = state.search_use_case.search_stock_levels(query).await?;
 
     let took_ms = start_time.elapsed().as_millis() as u64;
 
@@ -234,24 +204,12 @@ pub async fn search_stock_levels(
 pub async fn get_search_suggestions(
     State(state): State<AppState>,
     Query(params): Query<SuggestionsParams>,
-) -> Result<Json<SuggestionsResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<SuggestionsResponse>, ApiError> {
     let limit = params.limit.unwrap_or(10).min(50); // Cap at 50 suggestions
-    let suggestions = match state
+    let suggestions = state
         .search_use_case
         .get_search_suggestions(params.prefix.clone(), limit)
-        .await
-    {
-        Ok(suggestions) => suggestions,
-        Err(e) => {
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "SearchError".to_string(),
-                    message: e.to_string(),
-                }),
-            ))
-        }
-    };
+        .await?;
 
     let response = SuggestionsResponse {
         suggestions,
@@ -261,18 +219,69 @@ pub async fn get_search_suggestions(
     Ok(Json(response))
 }
 
+/// Autocomplete: entities whose names or codes resemble what has been typed,
+/// misspellings included, most similar first
+pub async fn suggest(
+    State(state): State<AppState>,
+    Query(params): Query<SuggestParams>,
+) -> Result<Json<SuggestResponse>, ApiError> {
+    let entity_types = params.entity_types.map(|types| {
+        types
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    });
+    let suggestions = state
+        .search_use_case
+        .suggest(SuggestQuery {
+            text: params.q.clone(),
+            entity_types,
+            limit: params.limit.unwrap_or(0),
+        })
+        .await?;
+
+    Ok(Json(SuggestResponse {
+        query: params.q,
+        suggestions,
+    }))
+}
+
 /// Rebuild search indexes (admin endpoint)
-pub async fn rebuild_search_indexes(
+pub async fn rebuild_search_indexes(State(state): State<AppState>) -> Result<StatusCode, ApiError> {
+    state.search_use_case.rebuild_indexes().await?;
+    Ok(StatusCode::OK)
+}
+
+/// Queue a rebuild of the tenant's search index from the item, location and
+/// stock tables; poll the returned job for completion
+pub async fn reindex_search(
     State(state): State<AppState>,
-) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
-    match state.search_use_case.rebuild_indexes().await {
-        Ok(_) => Ok(StatusCode::OK),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "RebuildError".to_string(),
-                message: e.to_string(),
-            }),
-        )),
-    }
+    Extension(tenant_context): Extension<TenantContext>,
+) -> Result<(StatusCode, Json<EnqueueJobResponse>), ApiError> {
+    let response = state
+        .enqueue_job_use_case
+        .execute(EnqueueJobRequest {
+            tenant_id: tenant_context.tenant_id,
+            job_type: REBUILD_SEARCH_INDEX_JOB_TYPE.to_string(),
+            payload: serde_json::json!({}),
+        })
+        .await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(EnqueueJobResponse {
+            job_id: response.job_id,
+            status: response.status,
+            created_at: response.created_at,
+        }),
+    ))
+}
+
+/// Report how the active search backend is doing for the tenant: whether it
+/// is reachable, how many documents it holds against how many it should, and
+/// how many are waiting to be refreshed
+pub async fn search_index_health(
+    State(state): State<AppState>,
+) -> Result<Json<SearchIndexHealth>, ApiError> {
+    Ok(Json(state.search_use_case.health().await?))
 }
@@ -0,0 +1,107 @@
+use crate::application::use_cases::generate_shipment_labels::ShipmentLabelsQuery;
+use crate::application::use_cases::get_shipment::{ListShipmentsQuery, ListShipmentsResponse};
+use crate::domain::entities::gs1::{SsccSequence, UpdateSsccSequenceRequest};
+use crate::domain::entities::shipment::{
+    CreateShipmentCartonsRequest, Shipment, UpdateShipmentTrackingRequest,
+};
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use uuid::Uuid;
+
+pub async fn list_shipments(
+    State(state): State<AppState>,
+    Query(query): Query<ListShipmentsQuery>,
+) -> Result<Json<ListShipmentsResponse>, ApiError> {
+    state
+        .get_shipment_use_case
+        .list(query)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn get_shipment(
+    State(state): State<AppState>,
+    Path(shipment_id): Path<Uuid>,
+) -> Result<Json<Shipment>, ApiError> {
+    state
+        .get_shipment_use_case
+        .execute(shipment_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn update_shipment_tracking(
+    State(state): State<AppState>,
+    Path(shipment_id): Path<Uuid>,
+    Json(request): Json<UpdateShipmentTrackingRequest>,
+) -> Result<Json<Shipment>, ApiError> {
+    state
+        .update_shipment_tracking_use_case
+        .execute(shipment_id, request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// Open SSCC-numbered cartons on a shipment
+pub async fn create_shipment_cartons(
+    State(state): State<AppState>,
+    Path(shipment_id): Path<Uuid>,
+    request: Option<Json<CreateShipmentCartonsRequest>>,
+) -> Result<(StatusCode, Json<Shipment>), ApiError> {
+    let Json(request) = request.unwrap_or_default();
+    state
+        .generate_shipment_labels_use_case
+        .add_cartons(shipment_id, request)
+        .await
+        .map(|shipment| (StatusCode::CREATED, Json(shipment)))
+        .map_err(ApiError::from)
+}
+
+/// Render GS1-128 carton labels for a shipment as ZPL or PDF
+pub async fn get_shipment_labels(
+    State(state): State<AppState>,
+    Path(shipment_id): Path<Uuid>,
+    Query(query): Query<ShipmentLabelsQuery>,
+) -> Result<Response, ApiError> {
+    let rendered = state
+        .generate_shipment_labels_use_case
+        .execute(shipment_id, query)
+        .await?;
+
+    Ok((
+        [(header::CONTENT_TYPE, rendered.content_type)],
+        rendered.bytes,
+    )
+        .into_response())
+}
+
+pub async fn get_sscc_sequence(
+    State(state): State<AppState>,
+) -> Result<Json<SsccSequence>, ApiError> {
+    state
+        .manage_sscc_sequence_use_case
+        .get()
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn update_sscc_sequence(
+    State(state): State<AppState>,
+    Json(request): Json<UpdateSsccSequenceRequest>,
+) -> Result<Json<SsccSequence>, ApiError> {
+    state
+        .manage_sscc_sequence_use_case
+        .update(request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
@@ -0,0 +1,52 @@
+use crate::application::use_cases::get_status_history::GetStatusHistoryUseCase;
+use crate::domain::entities::status_history::{StatusHistoryEntity, StatusHistoryResponse};
+use crate::infrastructure::repositories::postgres_status_history_repository::PostgresStatusHistoryRepository;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+async fn status_history(
+    state: &AppState,
+    entity: StatusHistoryEntity,
+    entity_id: Uuid,
+) -> Result<Json<StatusHistoryResponse>, ApiError> {
+    let repo = Arc::new(PostgresStatusHistoryRepository::new(Arc::clone(
+        &state.pool,
+    )));
+    let use_case = GetStatusHistoryUseCase::new(repo);
+
+    Ok(Json(use_case.execute(entity, entity_id).await?))
+}
+
+pub async fn get_purchase_order_history(
+    State(state): State<AppState>,
+    Path(po_id): Path<Uuid>,
+) -> Result<Json<StatusHistoryResponse>, ApiError> {
+    status_history(&state, StatusHistoryEntity::PurchaseOrder, po_id).await
+}
+
+pub async fn get_sales_order_history(
+    State(state): State<AppState>,
+    Path(so_id): Path<Uuid>,
+) -> Result<Json<StatusHistoryResponse>, ApiError> {
+    status_history(&state, StatusHistoryEntity::SalesOrder, so_id).await
+}
+
+pub async fn get_transfer_history(
+    State(state): State<AppState>,
+    Path(transfer_id): Path<Uuid>,
+) -> Result<Json<StatusHistoryResponse>, ApiError> {
+    status_history(&state, StatusHistoryEntity::Transfer, transfer_id).await
+}
+
+pub async fn get_return_history(
+    State(state): State<AppState>,
+    Path(return_id): Path<Uuid>,
+) -> Result<Json<StatusHistoryResponse>, ApiError> {
+    status_history(&state, StatusHistoryEntity::Return, return_id).await
+}
@@ -1,22 +1,38 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    body::Body,
+    extract::{Extension, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::application::use_cases::{
-    adjust_stock::AdjustStockResponse, get_stock_level::GetStockLevelRequest,
-    list_item_stock_levels::ListItemStockLevelsRequest,
+    change_stock_status::ChangeStockStatusResponse,
+    get_stock_level::{
+        BatchGetStockLevelsRequest, BatchGetStockLevelsResponse, GetStockLevelRequest,
+    },
+    get_stock_levels_as_of::GetStockLevelsAsOfRequest,
+    list_item_stock_levels::{ListItemStockLevelsRequest, ListItemStockLevelsResponse},
+    reserve_stock::AvailableToPromiseResponse,
 };
-use crate::domain::entities::inventory::StockAdjustmentRequest;
+use crate::domain::entities::export::StockMovementExportFilter;
+use crate::domain::entities::inventory::{
+    ChangeStockStatusRequest, StockLevel, StockLevelResponse, StockMovementResponse,
+    StockReservationRequest,
+};
+use crate::domain::entities::stock_snapshot::HistoricalStockLevels;
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
+use crate::shared::pagination::{Page, PageRequest};
 use crate::AppState;
 
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub message: String,
+#[derive(Debug, Deserialize)]
+pub struct StockLevelsQuery {
+    pub location_id: Option<Uuid>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,132 +40,151 @@ pub struct StockMovementsQuery {
     pub item_id: Option<Uuid>,
     pub location_id: Option<Uuid>,
     pub limit: Option<i64>,
-    pub offset: Option<i64>,
+    pub cursor: Option<String>,
 }
 
 /// Get stock level for a specific item at a specific location
 pub async fn get_stock_level(
     State(state): State<AppState>,
     Path((item_id, location_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<Option<serde_json::Value>>, (StatusCode, Json<ErrorResponse>)> {
-    match state
+) -> Result<Json<Option<StockLevelResponse>>, ApiError> {
+    let stock_level = state
         .get_stock_level_use_case
         .execute(GetStockLevelRequest {
             item_id,
             location_id,
         })
-        .await
-    {
-        Ok(Some(stock_level)) => Ok(Json(Some(serde_json::to_value(stock_level).map_err(
-            |e| {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ErrorResponse {
-                        error: "SerializationError".to_string(),
-                        message: e.to_string(),
-                    }),
-                )
-            },
-        )?))),
-        Ok(None) => Ok(Json(None)),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "StockError".to_string(),
-                message: e.to_string(),
-            }),
-        )),
-    }
+        .await?;
+    Ok(Json(stock_level))
+}
+
+/// Stock levels for up to 500 (item, location) pairs in one call, in the
+/// order asked for; pairs with no stock level are listed under `missing`
+pub async fn batch_get_stock_levels(
+    State(state): State<AppState>,
+    Json(request): Json<BatchGetStockLevelsRequest>,
+) -> Result<Json<BatchGetStockLevelsResponse>, ApiError> {
+    let response = state
+        .get_stock_level_use_case
+        .execute_batch(request)
+        .await?;
+    Ok(Json(response))
 }
 
 /// Get all stock levels for a specific item across all locations
 pub async fn get_item_stock_levels(
     State(state): State<AppState>,
     Path(item_id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    match state
+) -> Result<Json<ListItemStockLevelsResponse>, ApiError> {
+    let response = state
         .list_item_stock_levels_use_case
         .execute(ListItemStockLevelsRequest { item_id })
-        .await
-    {
-        Ok(response) => Ok(Json(serde_json::to_value(response).map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "SerializationError".to_string(),
-                    message: e.to_string(),
-                }),
-            )
-        })?)),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "StockError".to_string(),
-                message: e.to_string(),
-            }),
-        )),
-    }
+        .await?;
+    Ok(Json(response))
+}
+
+/// List stock levels, optionally at one location, with on-hand, held and
+/// in-transit quantities
+pub async fn list_stock_levels(
+    State(state): State<AppState>,
+    Query(query): Query<StockLevelsQuery>,
+) -> Result<Json<Page<StockLevel>>, ApiError> {
+    let levels = state
+        .list_stock_levels_use_case
+        .execute(
+            query.location_id,
+            PageRequest::new(query.limit, query.cursor),
+        )
+        .await?;
+    Ok(Json(levels))
 }
 
-/// Get stock movements with optional filtering
+/// Get stock movements with optional filtering, newest first
 pub async fn get_stock_movements(
     State(state): State<AppState>,
     Query(query): Query<StockMovementsQuery>,
-) -> Result<Json<Vec<serde_json::Value>>, (StatusCode, Json<ErrorResponse>)> {
-    let limit = query.limit.unwrap_or(50).min(1000);
-    let offset = query.offset.unwrap_or(0).max(0);
-
-    match state
+) -> Result<Json<Page<StockMovementResponse>>, ApiError> {
+    let movements = state
         .get_stock_movements_use_case
-        .execute(query.item_id, query.location_id, limit, offset)
-        .await
-    {
-        Ok(movements) => {
-            let json_movements = movements
-                .into_iter()
-                .map(|m| serde_json::to_value(m))
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(|e| {
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(ErrorResponse {
-                            error: "SerializationError".to_string(),
-                            message: e.to_string(),
-                        }),
-                    )
-                })?;
-            Ok(Json(json_movements))
-        }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "StockError".to_string(),
-                message: e.to_string(),
-            }),
-        )),
-    }
+        .execute(
+            query.item_id,
+            query.location_id,
+            PageRequest::new(query.limit, query.cursor),
+        )
+        .await?;
+    Ok(Json(movements))
+}
+
+/// Stream stock movements matching the filter as CSV, oldest first
+pub async fn export_stock_movements(
+    State(state): State<AppState>,
+    Query(filter): Query<StockMovementExportFilter>,
+) -> Result<Response, ApiError> {
+    let chunks = state.export_stock_movements_use_case.execute(filter)?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"stock_movements.csv\"",
+            ),
+        ],
+        Body::from_stream(chunks),
+    )
+        .into_response())
+}
+
+/// Reconstruct stock levels as they stood at the end of a past day
+pub async fn get_stock_levels_as_of(
+    State(state): State<AppState>,
+    Query(request): Query<GetStockLevelsAsOfRequest>,
+) -> Result<Json<HistoricalStockLevels>, ApiError> {
+    let levels = state
+        .get_stock_levels_as_of_use_case
+        .execute(request)
+        .await?;
+    Ok(Json(levels))
 }
 
-/// Adjust stock level (requires authentication)
-pub async fn adjust_stock(
+/// Move stock between AVAILABLE, QUARANTINE and DAMAGED at a location
+pub async fn change_stock_status(
     State(state): State<AppState>,
-    Json(request): Json<StockAdjustmentRequest>,
-) -> Result<Json<AdjustStockResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // TODO: Get user ID from authentication context
-    let created_by = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(); // Use existing test user
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<ChangeStockStatusRequest>,
+) -> Result<(StatusCode, Json<ChangeStockStatusResponse>), ApiError> {
+    let created_by = acting_user(&tenant_context);
 
-    match state
-        .adjust_stock_use_case
+    let response = state
+        .change_stock_status_use_case
         .execute(request, created_by)
-        .await
-    {
-        Ok(response) => Ok(Json(response)),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "StockError".to_string(),
-                message: e.to_string(),
-            }),
-        )),
-    }
+        .await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// Place a soft reservation against available-to-promise stock
+pub async fn reserve_stock(
+    State(state): State<AppState>,
+    Json(request): Json<StockReservationRequest>,
+) -> Result<Json<StockLevel>, ApiError> {
+    Ok(Json(state.reserve_stock_use_case.reserve(request).await?))
+}
+
+/// Release a previously placed soft reservation
+pub async fn release_stock(
+    State(state): State<AppState>,
+    Json(request): Json<StockReservationRequest>,
+) -> Result<Json<StockLevel>, ApiError> {
+    Ok(Json(state.reserve_stock_use_case.release(request).await?))
+}
+
+/// Get available-to-promise (on hand less reserved) for an item at a location
+pub async fn get_available_to_promise(
+    State(state): State<AppState>,
+    Path((item_id, location_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<AvailableToPromiseResponse>, ApiError> {
+    let response = state
+        .reserve_stock_use_case
+        .available_to_promise(item_id, location_id)
+        .await?;
+    Ok(Json(response))
 }
@@ -1,7 +1,9 @@
 use axum::{
+    body::Bytes,
     extract::{Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::Json,
+    Extension,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -13,9 +15,15 @@ use crate::application::use_cases::{
     list_tenants::ListTenantsUseCase,
 };
 use crate::domain::entities::tenant::{
-    CreateSandboxTenantResponse, Tenant, TenantTier, TenantType,
+    CreateSandboxTenantRequest, CreateSandboxTenantResponse, PromoteSandboxRequest, Tenant,
+    TenantTier, TenantType,
 };
-use crate::shared::error::DomainError;
+use crate::domain::entities::tenant_audit::TenantAuditEntry;
+use crate::domain::services::sandbox_echo_inbox::EchoDelivery;
+use crate::domain::services::webhook_signature::SIGNATURE_HEADER;
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
 use crate::AppState;
 
 #[derive(Deserialize)]
@@ -65,15 +73,14 @@ pub struct CleanupResponse {
 pub async fn create_tenant(
     State(state): State<AppState>,
     Json(request): Json<CreateTenantRequest>,
-) -> Result<Json<TenantResponse>, (StatusCode, String)> {
+) -> Result<Json<TenantResponse>, ApiError> {
     // Parse tenant type
     let tenant_type = match request.tenant_type.as_str() {
         "SANDBOX" => TenantType::Sandbox,
         "PRODUCTION" => TenantType::Production,
         _ => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                "Invalid tenant type. Must be 'SANDBOX' or 'PRODUCTION'".to_string(),
+            return Err(ApiError::bad_request(
+                "Invalid tenant type. Must be 'SANDBOX' or 'PRODUCTION'",
             ))
         }
     };
@@ -82,9 +89,8 @@ pub async fn create_tenant(
     let tier = match TenantTier::from_str(&request.tier) {
         Ok(t) => t,
         Err(_) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                "Invalid tenant tier. Must be one of: FREE, DEVELOPER, STARTUP, GROWTH, SCALE, ENTERPRISE".to_string(),
+            return Err(ApiError::bad_request(
+                "Invalid tenant tier. Must be one of: FREE, DEVELOPER, STARTUP, GROWTH, SCALE, ENTERPRISE",
             ))
         }
     };
@@ -93,106 +99,188 @@ pub async fn create_tenant(
     // For now, use None (system-created tenant)
     let created_by = None; // Placeholder - should come from auth
 
-    match state
+    let tenant = state
         .create_tenant_use_case
         .execute(request.name, tenant_type, tier, created_by)
-        .await
-    {
-        Ok(tenant) => Ok(Json(tenant.into())),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to create tenant: {}", e),
-        )),
-    }
+        .await?;
+    Ok(Json(tenant.into()))
 }
 
+/// Create a sandbox seeded with demo data. The optional body picks a seed
+/// profile and overrides its counts, e.g. `{"profile": "large", "history_days": 30}`.
 pub async fn create_sandbox_tenant(
     State(state): State<AppState>,
-) -> Result<Json<CreateSandboxTenantResponse>, (StatusCode, String)> {
-    // TODO: Get user ID from authentication context
-    // For now, use None (system-created tenant)
-    let created_by = None; // Placeholder - should come from auth
+    Extension(tenant_context): Extension<TenantContext>,
+    request: Option<Json<CreateSandboxTenantRequest>>,
+) -> Result<Json<CreateSandboxTenantResponse>, ApiError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let created_by = acting_user(&tenant_context);
 
-    match state
+    let sandbox = state
         .create_sandbox_tenant_use_case
-        .execute(created_by)
-        .await
-    {
-        Ok(tenant) => {
-            let response = CreateSandboxTenantResponse {
-                tenant_id: tenant.id,
-                status: tenant.status.as_str().to_string(),
-                expires_at: tenant.expires_at.unwrap_or_default(),
-                message: "Sandbox tenant created successfully with sample data".to_string(),
-            };
-            Ok(Json(response))
-        }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to create sandbox tenant: {}", e),
-        )),
-    }
+        .execute(request, created_by)
+        .await?;
+    Ok(Json(CreateSandboxTenantResponse {
+        tenant_id: sandbox.tenant.id,
+        status: sandbox.tenant.status.as_str().to_string(),
+        expires_at: sandbox.tenant.expires_at.unwrap_or_default(),
+        message: format!(
+            "Sandbox tenant created with the {} sample dataset",
+            sandbox.seeded.profile
+        ),
+        seeded: sandbox.seeded,
+        echo_url: sandbox.echo_url,
+        webhook_secret: sandbox.webhook_secret,
+    }))
+}
+
+/// The logged-in user; managing a tenant as a whole needs a login token rather
+/// than just an `X-Tenant-ID`
+fn logged_in_user(tenant_context: &TenantContext) -> Result<Uuid, ApiError> {
+    tenant_context.user_id.ok_or_else(|| {
+        ApiError::unauthorized("Log in to manage tenants").with_code("LOGIN_REQUIRED")
+    })
+}
+
+/// Wipe a sandbox and seed it again. The optional body is the one sandbox
+/// creation takes; without it the sandbox's previous profile is replayed.
+pub async fn reset_sandbox(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(tenant_id): Path<Uuid>,
+    request: Option<Json<CreateSandboxTenantRequest>>,
+) -> Result<Json<CreateSandboxTenantResponse>, ApiError> {
+    let actor_id = logged_in_user(&tenant_context)?;
+    let sandbox = state
+        .manage_sandbox_use_case
+        .reset(tenant_id, request.map(|Json(r)| r), actor_id)
+        .await?;
+    state.sandbox_echo_inbox.clear(tenant_id);
+
+    Ok(Json(CreateSandboxTenantResponse {
+        tenant_id: sandbox.tenant.id,
+        status: sandbox.tenant.status.as_str().to_string(),
+        expires_at: sandbox.tenant.expires_at.unwrap_or_default(),
+        message: format!(
+            "Sandbox tenant reset with the {} sample dataset",
+            sandbox.seeded.profile
+        ),
+        seeded: sandbox.seeded,
+        echo_url: sandbox.echo_url,
+        webhook_secret: sandbox.webhook_secret,
+    }))
+}
+
+/// Convert a sandbox into a production tenant, keeping its users, quotas,
+/// webhooks and settings but not its demo transactions
+pub async fn promote_sandbox(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(tenant_id): Path<Uuid>,
+    request: Option<Json<PromoteSandboxRequest>>,
+) -> Result<Json<TenantResponse>, ApiError> {
+    let actor_id = logged_in_user(&tenant_context)?;
+    let tenant = state
+        .manage_sandbox_use_case
+        .promote(
+            tenant_id,
+            request.map(|Json(r)| r).unwrap_or_default(),
+            actor_id,
+        )
+        .await?;
+    state.sandbox_echo_inbox.clear(tenant_id);
+    Ok(Json(tenant.into()))
+}
+
+/// Resets, promotions and other administrative actions taken on the tenant
+pub async fn list_tenant_audit_log(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(tenant_id): Path<Uuid>,
+) -> Result<Json<Vec<TenantAuditEntry>>, ApiError> {
+    let actor_id = logged_in_user(&tenant_context)?;
+    let entries = state
+        .manage_sandbox_use_case
+        .audit_log(tenant_id, actor_id)
+        .await?;
+    Ok(Json(entries))
+}
+
+/// Built-in webhook receiver that sandbox webhooks deliver to: keeps the
+/// delivery for `GET /sandbox/echo` and echoes it back. Unauthenticated, like
+/// any webhook endpoint, so it only accepts sandbox tenants.
+pub async fn receive_sandbox_echo(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<EchoDelivery>, ApiError> {
+    state
+        .get_tenant_use_case
+        .execute(tenant_id)
+        .await?
+        .filter(|tenant| tenant.tenant_type == TenantType::Sandbox)
+        .ok_or_else(|| ApiError::not_found(format!("Sandbox {} not found", tenant_id)))?;
+
+    let delivery = EchoDelivery {
+        id: Uuid::new_v4(),
+        received_at: chrono::Utc::now(),
+        signature: headers
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        body: serde_json::from_slice(&body).unwrap_or_else(|_| {
+            serde_json::Value::String(String::from_utf8_lossy(&body).into_owned())
+        }),
+    };
+    state.sandbox_echo_inbox.record(tenant_id, delivery.clone());
+    Ok(Json(delivery))
+}
+
+/// Webhook deliveries the echo receiver got for the current sandbox, newest first
+pub async fn list_sandbox_echo_deliveries(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+) -> Json<Vec<EchoDelivery>> {
+    Json(state.sandbox_echo_inbox.list(tenant_context.tenant_id))
 }
 
 pub async fn get_tenant(
     State(state): State<AppState>,
     Path(tenant_id): Path<Uuid>,
-) -> Result<Json<TenantResponse>, (StatusCode, String)> {
-    match state.get_tenant_use_case.execute(tenant_id).await {
-        Ok(Some(tenant)) => Ok(Json(tenant.into())),
-        Ok(None) => Err((
-            StatusCode::NOT_FOUND,
-            format!("Tenant {} not found", tenant_id),
-        )),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to get tenant: {}", e),
-        )),
-    }
+) -> Result<Json<TenantResponse>, ApiError> {
+    state
+        .get_tenant_use_case
+        .execute(tenant_id)
+        .await?
+        .map(|tenant| Json(tenant.into()))
+        .ok_or_else(|| ApiError::not_found(format!("Tenant {} not found", tenant_id)))
 }
 
 pub async fn list_tenants(
     State(state): State<AppState>,
-) -> Result<Json<Vec<TenantResponse>>, (StatusCode, String)> {
-    match state.list_tenants_use_case.execute().await {
-        Ok(tenants) => {
-            let responses = tenants.into_iter().map(TenantResponse::from).collect();
-            Ok(Json(responses))
-        }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to list tenants: {}", e),
-        )),
-    }
+) -> Result<Json<Vec<TenantResponse>>, ApiError> {
+    let tenants = state.list_tenants_use_case.execute().await?;
+    Ok(Json(
+        tenants.into_iter().map(TenantResponse::from).collect(),
+    ))
 }
 
 pub async fn delete_tenant(
     State(state): State<AppState>,
     Path(tenant_id): Path<Uuid>,
-) -> Result<StatusCode, (StatusCode, String)> {
-    match state.delete_tenant_use_case.execute(tenant_id).await {
-        Ok(()) => Ok(StatusCode::NO_CONTENT),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to delete tenant: {}", e),
-        )),
-    }
+) -> Result<StatusCode, ApiError> {
+    state.delete_tenant_use_case.execute(tenant_id).await?;
+    Ok(StatusCode::NO_CONTENT)
 }
 
 pub async fn cleanup_expired_sandboxes(
     State(state): State<AppState>,
-) -> Result<Json<CleanupResponse>, (StatusCode, String)> {
-    match state.cleanup_expired_sandboxes_use_case.execute().await {
-        Ok(cleaned_ids) => {
-            let count = cleaned_ids.len();
-            Ok(Json(CleanupResponse {
-                cleaned_tenant_ids: cleaned_ids,
-                count,
-            }))
-        }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to cleanup expired sandboxes: {}", e),
-        )),
-    }
+) -> Result<Json<CleanupResponse>, ApiError> {
+    let cleaned_ids = state.cleanup_expired_sandboxes_use_case.execute().await?;
+    let count = cleaned_ids.len();
+    Ok(Json(CleanupResponse {
+        cleaned_tenant_ids: cleaned_ids,
+        count,
+    }))
 }
@@ -0,0 +1,58 @@
+//! Reading uploads into temporary files chunk by chunk. The route group's body
+//! limit cuts a stream off once it is passed, which is reported as 413.
+
+use crate::shared::api_error::ApiError;
+use crate::shared::spooled_file::SpooledFile;
+use axum::{
+    body::Body,
+    extract::multipart::{Field, MultipartError},
+    http::StatusCode,
+};
+use http_body_util::LengthLimitError;
+use tokio_stream::StreamExt;
+
+/// Write a request body to a temporary file as it arrives
+pub async fn spool_body(body: Body) -> Result<SpooledFile, ApiError> {
+    let mut spooled = SpooledFile::create().await?;
+    let mut stream = body.into_data_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| {
+            if exceeds_limit(&e) {
+                ApiError::payload_too_large()
+            } else {
+                ApiError::bad_request(format!("Failed to read upload: {}", e))
+            }
+        })?;
+        spooled.write(&chunk).await?;
+    }
+    Ok(spooled.finish().await?)
+}
+
+/// Write a multipart field to a temporary file as it arrives
+pub async fn spool_field(mut field: Field<'_>) -> Result<SpooledFile, ApiError> {
+    let mut spooled = SpooledFile::create().await?;
+    while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+        spooled.write(&chunk).await?;
+    }
+    Ok(spooled.finish().await?)
+}
+
+/// A malformed multipart body is the client's mistake, as is one over the limit
+pub fn multipart_error(error: MultipartError) -> ApiError {
+    if error.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        ApiError::payload_too_large()
+    } else {
+        ApiError::bad_request(format!("Invalid multipart body: {}", error.body_text()))
+    }
+}
+
+fn exceeds_limit(error: &axum::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error);
+    while let Some(current) = source {
+        if current.is::<LengthLimitError>() {
+            return true;
+        }
+        source = current.source();
+    }
+    false
+}
@@ -0,0 +1,115 @@
+use crate::application::use_cases::{
+    manage_tenant_users::{
+        ChangeUserRoleRequest, InviteUserRequest, InviteUserResponse, ListTenantUsersResponse,
+        TenantUserResponse,
+    },
+    register_user::RegisterUserRequest,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use uuid::Uuid;
+
+pub async fn list_users(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+) -> Result<Json<ListTenantUsersResponse>, ApiError> {
+    state
+        .manage_tenant_users_use_case
+        .list(tenant_context.tenant_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn invite_user(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<InviteUserRequest>,
+) -> Result<(StatusCode, Json<InviteUserResponse>), ApiError> {
+    state
+        .manage_tenant_users_use_case
+        .invite(
+            tenant_context.tenant_id,
+            acting_user(&tenant_context),
+            request,
+        )
+        .await
+        .map(|response| (StatusCode::CREATED, Json(response)))
+        .map_err(ApiError::from)
+}
+
+/// Redeem an invitation token. Served without a tenant: the invitation names it.
+pub async fn register_user(
+    State(state): State<AppState>,
+    Json(request): Json<RegisterUserRequest>,
+) -> Result<(StatusCode, Json<TenantUserResponse>), ApiError> {
+    state
+        .register_user_use_case
+        .execute(request)
+        .await
+        .map(|user| (StatusCode::CREATED, Json(user)))
+        .map_err(ApiError::from)
+}
+
+pub async fn activate_user(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<TenantUserResponse>, ApiError> {
+    state
+        .manage_tenant_users_use_case
+        .set_active(
+            tenant_context.tenant_id,
+            acting_user(&tenant_context),
+            user_id,
+            true,
+        )
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn deactivate_user(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<TenantUserResponse>, ApiError> {
+    state
+        .manage_tenant_users_use_case
+        .set_active(
+            tenant_context.tenant_id,
+            acting_user(&tenant_context),
+            user_id,
+            false,
+        )
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn change_user_role(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<ChangeUserRoleRequest>,
+) -> Result<Json<TenantUserResponse>, ApiError> {
+    state
+        .manage_tenant_users_use_case
+        .change_role(
+            tenant_context.tenant_id,
+            acting_user(&tenant_context),
+            user_id,
+            request,
+        )
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
@@ -0,0 +1,73 @@
+use crate::application::use_cases::manage_vendor_returns::{
+    ShipVendorReturnResponse, VendorReturnResponse,
+};
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::vendor_return::{CreateVendorReturnRequest, VendorReturn};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
+use crate::shared::pagination::{Page, PageRequest};
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    Extension,
+};
+use uuid::Uuid;
+
+/// Draft a return-to-vendor against a purchase order
+pub async fn create_vendor_return(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Json(request): Json<CreateVendorReturnRequest>,
+) -> Result<(StatusCode, Json<VendorReturnResponse>), ApiError> {
+    let created_by = acting_user(&tenant_context);
+
+    let response = state
+        .manage_vendor_returns_use_case
+        .create(request, created_by)
+        .await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn list_vendor_returns(
+    State(state): State<AppState>,
+    Query(page): Query<PageRequest>,
+    Query(filter): Query<ListFilter>,
+) -> Result<Json<Page<VendorReturn>>, ApiError> {
+    Ok(Json(
+        state
+            .manage_vendor_returns_use_case
+            .list(&filter, page)
+            .await?,
+    ))
+}
+
+pub async fn get_vendor_return(
+    State(state): State<AppState>,
+    Path(rtv_id): Path<Uuid>,
+) -> Result<Json<VendorReturnResponse>, ApiError> {
+    Ok(Json(
+        state.manage_vendor_returns_use_case.get(rtv_id).await?,
+    ))
+}
+
+/// Ship a draft return, taking the stock out of its location
+pub async fn ship_vendor_return(
+    State(state): State<AppState>,
+    Path(rtv_id): Path<Uuid>,
+) -> Result<Json<ShipVendorReturnResponse>, ApiError> {
+    Ok(Json(
+        state.manage_vendor_returns_use_case.ship(rtv_id).await?,
+    ))
+}
+
+pub async fn cancel_vendor_return(
+    State(state): State<AppState>,
+    Path(rtv_id): Path<Uuid>,
+) -> Result<Json<VendorReturnResponse>, ApiError> {
+    Ok(Json(
+        state.manage_vendor_returns_use_case.cancel(rtv_id).await?,
+    ))
+}
@@ -2,141 +2,188 @@ use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::Json,
+    Extension,
 };
-use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::application::use_cases::{
     delete_webhook::DeleteWebhookUseCase,
+    manage_webhook_filter::{ManageWebhookFilterUseCase, SetWebhookFilterRequest},
+    manage_webhook_pause::ManageWebhookPauseUseCase,
     register_webhook::{RegisterWebhookRequest, RegisterWebhookUseCase},
     update_webhook::{UpdateWebhookRequest, UpdateWebhookUseCase},
 };
 use crate::domain::services::webhook_repository::WebhookRepository;
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::actor::acting_user;
+use crate::shared::api_error::ApiError;
+use crate::shared::error::DomainError;
 use crate::AppState;
 
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub message: String,
-}
-
 /// Register a new webhook
 pub async fn register_webhook(
     State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
     Json(request): Json<RegisterWebhookRequest>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    // For now, use the user ID from login - authentication middleware will be added later
-    let user_id = uuid::Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
-
-    let use_case = RegisterWebhookUseCase::new(state.webhook_repository.clone());
-
-    match use_case.execute(request, user_id).await {
-        Ok(response) => Ok(Json(serde_json::to_value(response).map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "SerializationError".to_string(),
-                    message: e.to_string(),
-                }),
-            )
-        })?)),
-        Err(e) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "ValidationError".to_string(),
-                message: e.to_string(),
-            }),
-        )),
-    }
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let user_id = acting_user(&tenant_context);
+
+    let use_case = RegisterWebhookUseCase::new(
+        state.webhook_repository.clone(),
+        state.quota_service.clone(),
+        state.webhook_url_policy.clone(),
+    );
+
+    let response = use_case
+        .execute(request, user_id, tenant_context.tenant_id)
+        .await
+        .map_err(webhook_error)?;
+    Ok(Json(
+        serde_json::to_value(response).map_err(|e| ApiError::internal(e.to_string()))?,
+    ))
 }
 
 /// Update an existing webhook
 pub async fn update_webhook(
     State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
     Path(webhook_id): Path<Uuid>,
     Json(request): Json<UpdateWebhookRequest>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    // For now, use a hardcoded user ID - authentication will be added later
-    let user_id = Uuid::new_v4();
-
-    let use_case = UpdateWebhookUseCase::new(state.webhook_repository.clone());
-
-    match use_case.execute(webhook_id, request, user_id).await {
-        Ok(response) => Ok(Json(serde_json::to_value(response).map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "SerializationError".to_string(),
-                    message: e.to_string(),
-                }),
-            )
-        })?)),
-        Err(e) => {
-            let status_code = match e {
-                crate::shared::error::DomainError::NotFound(_) => StatusCode::NOT_FOUND,
-                _ => StatusCode::BAD_REQUEST,
-            };
-            Err((
-                status_code,
-                Json(ErrorResponse {
-                    error: "ValidationError".to_string(),
-                    message: e.to_string(),
-                }),
-            ))
-        }
-    }
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let user_id = acting_user(&tenant_context);
+
+    let use_case = UpdateWebhookUseCase::new(
+        state.webhook_repository.clone(),
+        state.webhook_url_policy.clone(),
+    );
+
+    let response = use_case
+        .execute(webhook_id, request, user_id)
+        .await
+        .map_err(webhook_error)?;
+    Ok(Json(
+        serde_json::to_value(response).map_err(|e| ApiError::internal(e.to_string()))?,
+    ))
 }
 
 /// Delete a webhook
 pub async fn delete_webhook(
     State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
     Path(webhook_id): Path<Uuid>,
-) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
-    // For now, use a hardcoded user ID - authentication will be added later
-    let user_id = Uuid::new_v4();
-
-    let use_case = DeleteWebhookUseCase::new(state.webhook_repository.clone());
-
-    match use_case.execute(webhook_id, user_id).await {
-        Ok(_) => Ok(StatusCode::NO_CONTENT),
-        Err(e) => {
-            let status_code = match e {
-                crate::shared::error::DomainError::NotFound(_) => StatusCode::NOT_FOUND,
-                _ => StatusCode::BAD_REQUEST,
-            };
-            Err((
-                status_code,
-                Json(ErrorResponse {
-                    error: "ValidationError".to_string(),
-                    message: e.to_string(),
-                }),
-            ))
-        }
-    }
+) -> Result<StatusCode, ApiError> {
+    let user_id = acting_user(&tenant_context);
+
+    let use_case = DeleteWebhookUseCase::new(
+        state.webhook_repository.clone(),
+        state.quota_service.clone(),
+    );
+
+    use_case
+        .execute(webhook_id, user_id, tenant_context.tenant_id)
+        .await
+        .map_err(webhook_error)?;
+    Ok(StatusCode::NO_CONTENT)
 }
 
 /// Get user's webhooks
 pub async fn get_user_webhooks(
     State(state): State<AppState>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    // For now, use a hardcoded user ID - authentication will be added later
-    let user_id = Uuid::new_v4();
-    match state.webhook_repository.get_user_webhooks(user_id).await {
-        Ok(webhooks) => Ok(Json(serde_json::to_value(webhooks).map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "SerializationError".to_string(),
-                    message: e.to_string(),
-                }),
-            )
-        })?)),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "DatabaseError".to_string(),
-                message: e.to_string(),
-            }),
-        )),
+    Extension(tenant_context): Extension<TenantContext>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let user_id = acting_user(&tenant_context);
+    let webhooks = state.webhook_repository.get_user_webhooks(user_id).await?;
+    Ok(Json(
+        serde_json::to_value(webhooks).map_err(|e| ApiError::internal(e.to_string()))?,
+    ))
+}
+
+/// Get a webhook's payload filter
+pub async fn get_webhook_filter(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let user_id = acting_user(&tenant_context);
+
+    let use_case = ManageWebhookFilterUseCase::new(state.webhook_repository.clone());
+    let response = use_case
+        .get(webhook_id, user_id)
+        .await
+        .map_err(webhook_error)?;
+    Ok(Json(serde_json::json!(response)))
+}
+
+/// Set or replace a webhook's payload filter
+pub async fn set_webhook_filter(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(webhook_id): Path<Uuid>,
+    Json(request): Json<SetWebhookFilterRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let user_id = acting_user(&tenant_context);
+
+    let use_case = ManageWebhookFilterUseCase::new(state.webhook_repository.clone());
+    let response = use_case
+        .set(webhook_id, request, user_id)
+        .await
+        .map_err(webhook_error)?;
+    Ok(Json(serde_json::json!(response)))
+}
+
+/// Remove a webhook's payload filter so it receives every subscribed event again
+pub async fn delete_webhook_filter(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let user_id = acting_user(&tenant_context);
+
+    let use_case = ManageWebhookFilterUseCase::new(state.webhook_repository.clone());
+    use_case
+        .clear(webhook_id, user_id)
+        .await
+        .map_err(webhook_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Stop sending a webhook's deliveries, holding them until it is resumed
+pub async fn pause_webhook(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let user_id = acting_user(&tenant_context);
+
+    let use_case = ManageWebhookPauseUseCase::new(state.webhook_repository.clone());
+    let response = use_case
+        .pause(webhook_id, user_id)
+        .await
+        .map_err(webhook_error)?;
+    Ok(Json(serde_json::json!(response)))
+}
+
+/// Reactivate a paused webhook and replay the deliveries held while it was paused
+pub async fn resume_webhook(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let user_id = acting_user(&tenant_context);
+
+    let use_case = ManageWebhookPauseUseCase::new(state.webhook_repository.clone());
+    let response = use_case
+        .resume(webhook_id, user_id)
+        .await
+        .map_err(webhook_error)?;
+    Ok(Json(serde_json::json!(response)))
+}
+
+/// The webhook use cases report a webhook owned by someone else as a business
+/// rule violation
+pub(crate) fn webhook_error(e: DomainError) -> ApiError {
+    match e {
+        DomainError::BusinessLogicError(msg) => ApiError::forbidden(msg),
+        e => e.into(),
     }
 }
@@ -0,0 +1,73 @@
+use crate::application::use_cases::enqueue_job::EnqueueJobRequest;
+use crate::domain::entities::webhook_retention::{
+    RetentionRun, RetentionTrigger, UpdateWebhookRetentionRequest, WebhookRetentionJobPayload,
+    WebhookRetentionSettings, WEBHOOK_RETENTION_JOB_TYPE,
+};
+use crate::infrastructure::middleware::tenant_middleware::TenantContext;
+use crate::presentation::handlers::jobs::EnqueueJobResponse;
+use crate::shared::api_error::ApiError;
+use crate::AppState;
+use axum::{extract::State, http::StatusCode, response::Json, Extension};
+
+pub async fn get_webhook_retention(
+    State(state): State<AppState>,
+) -> Result<Json<WebhookRetentionSettings>, ApiError> {
+    state
+        .webhook_retention_use_case
+        .get_settings()
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+pub async fn update_webhook_retention(
+    State(state): State<AppState>,
+    Json(request): Json<UpdateWebhookRetentionRequest>,
+) -> Result<Json<WebhookRetentionSettings>, ApiError> {
+    state
+        .webhook_retention_use_case
+        .update_settings(request)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// Queue a cleanup of the tenant's expired webhook history now rather than at
+/// its daily run; poll the returned job for completion
+pub async fn run_webhook_retention(
+    State(state): State<AppState>,
+    Extension(tenant_context): Extension<TenantContext>,
+) -> Result<(StatusCode, Json<EnqueueJobResponse>), ApiError> {
+    let payload = serde_json::to_value(WebhookRetentionJobPayload {
+        trigger: RetentionTrigger::Manual,
+    })
+    .expect("retention payload serializes");
+    let response = state
+        .enqueue_job_use_case
+        .execute(EnqueueJobRequest {
+            tenant_id: tenant_context.tenant_id,
+            job_type: WEBHOOK_RETENTION_JOB_TYPE.to_string(),
+            payload,
+        })
+        .await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(EnqueueJobResponse {
+            job_id: response.job_id,
+            status: response.status,
+            created_at: response.created_at,
+        }),
+    ))
+}
+
+/// What the tenant's most recent cleanup pruned and where it archived it
+pub async fn get_latest_webhook_retention_run(
+    State(state): State<AppState>,
+) -> Result<Json<RetentionRun>, ApiError> {
+    state
+        .webhook_retention_use_case
+        .latest_run()
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
@@ -1,3 +1,6 @@
 // Presentation layer
+pub mod cli;
+pub mod graphql;
+pub mod grpc;
 pub mod handlers;
 pub mod routes;
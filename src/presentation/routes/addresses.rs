@@ -0,0 +1,11 @@
+use crate::presentation::handlers::addresses::validate_address;
+use axum::{routing::post, Router};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn address_routes() -> Router<AppState> {
+    Router::new()
+        .route("/addresses/validate", post(validate_address))
+        .layer(CorsLayer::permissive())
+}
@@ -0,0 +1,32 @@
+use crate::presentation::handlers::adjustments::{
+    adjust_stock, approve_adjustment, create_adjustment_reason, delete_adjustment_reason,
+    get_adjustment, get_adjustment_reason, list_adjustment_reasons, list_adjustments,
+    reject_adjustment, update_adjustment_reason,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn adjustment_routes() -> Router<AppState> {
+    Router::new()
+        .route("/stock/adjust", post(adjust_stock))
+        .route("/adjustments", post(adjust_stock).get(list_adjustments))
+        .route("/adjustments/{id}", get(get_adjustment))
+        .route("/adjustments/{id}/approve", post(approve_adjustment))
+        .route("/adjustments/{id}/reject", post(reject_adjustment))
+        .route(
+            "/admin/adjustment-reasons",
+            post(create_adjustment_reason).get(list_adjustment_reasons),
+        )
+        .route(
+            "/admin/adjustment-reasons/{reasonId}",
+            get(get_adjustment_reason)
+                .put(update_adjustment_reason)
+                .delete(delete_adjustment_reason),
+        )
+        .layer(CorsLayer::permissive())
+}
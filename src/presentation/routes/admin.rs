@@ -1,12 +1,13 @@
 use axum::{
-    routing::{get, post, put},
+    routing::{delete, get, post, put},
     Router,
 };
 
 use crate::presentation::handlers::admin::{
-    admin_dashboard_handler, cleanup_expired_sandboxes_handler, get_billing_metrics_handler,
-    get_tenant_quotas_handler, list_dlq_deliveries_handler, list_sandboxes_handler,
-    replay_dlq_delivery_handler, update_tenant_quotas_handler,
+    admin_dashboard_handler, cleanup_expired_sandboxes_handler, disable_tenant_webhooks_handler,
+    get_billing_metrics_handler, get_migrations_handler, get_tenant_quotas_handler,
+    get_tenant_rate_limit_handler, list_dlq_deliveries_handler, list_sandboxes_handler,
+    replay_dlq_delivery_handler, reset_tenant_rate_limit_handler, update_tenant_quotas_handler,
 };
 use crate::AppState;
 
@@ -21,6 +22,7 @@ pub fn create_admin_router() -> Router<AppState> {
         .route("/admin/dlq", get(list_dlq_deliveries_handler))
         .route("/admin/dlq/replay", post(replay_dlq_delivery_handler))
         .route("/admin/billing", get(get_billing_metrics_handler))
+        .route("/admin/migrations", get(get_migrations_handler))
         .route(
             "/admin/tenants/{tenant_id}/quotas",
             get(get_tenant_quotas_handler),
@@ -29,4 +31,16 @@ pub fn create_admin_router() -> Router<AppState> {
             "/admin/tenants/{tenant_id}/quotas",
             put(update_tenant_quotas_handler),
         )
+        .route(
+            "/admin/tenants/{tenant_id}/rate-limit",
+            get(get_tenant_rate_limit_handler),
+        )
+        .route(
+            "/admin/tenants/{tenant_id}/rate-limit",
+            delete(reset_tenant_rate_limit_handler),
+        )
+        .route(
+            "/admin/tenants/{tenant_id}/webhooks/disable",
+            post(disable_tenant_webhooks_handler),
+        )
 }
@@ -0,0 +1,31 @@
+use crate::infrastructure::config::app_config::BodyLimitsConfig;
+use crate::infrastructure::middleware::body_limit::body_limit;
+use crate::presentation::handlers::attachments::{
+    delete_item_attachment, download_item_attachment, list_item_attachments, upload_item_attachment,
+};
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn attachment_routes(body_limits: &BodyLimitsConfig) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/items/{id}/attachments",
+            post(upload_item_attachment)
+                .layer(body_limit(body_limits.upload_bytes))
+                .get(list_item_attachments),
+        )
+        .route(
+            "/items/{id}/attachments/{attachment_id}",
+            delete(delete_item_attachment),
+        )
+        .route(
+            "/items/{id}/attachments/{attachment_id}/content",
+            get(download_item_attachment),
+        )
+        .layer(CorsLayer::permissive())
+}
@@ -0,0 +1,20 @@
+use crate::presentation::handlers::availability::{
+    get_item_availability, rebuild_item_availability,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn availability_routes() -> Router<AppState> {
+    Router::new()
+        .route("/items/{id}/availability", get(get_item_availability))
+        .route(
+            "/items/availability/rebuild",
+            post(rebuild_item_availability),
+        )
+        .layer(CorsLayer::permissive())
+}
@@ -0,0 +1,12 @@
+use crate::presentation::handlers::barcode::{get_item_barcode, scan_code};
+use axum::{routing::get, Router};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn barcode_routes() -> Router<AppState> {
+    Router::new()
+        .route("/items/{id}/barcode", get(get_item_barcode))
+        .route("/scan/{code}", get(scan_code))
+        .layer(CorsLayer::permissive())
+}
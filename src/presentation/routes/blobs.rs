@@ -0,0 +1,11 @@
+use crate::presentation::handlers::blobs::download_signed_blob;
+use axum::{routing::get, Router};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn blob_routes() -> Router<AppState> {
+    Router::new()
+        .route("/blobs/{*key}", get(download_signed_blob))
+        .layer(CorsLayer::permissive())
+}
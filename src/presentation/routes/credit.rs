@@ -0,0 +1,25 @@
+use crate::presentation::handlers::credit::{
+    delete_customer_credit_limit, get_credit_settings, get_customer_credit,
+    set_customer_credit_limit, update_credit_settings,
+};
+use axum::{
+    routing::{get, put},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn credit_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/admin/credit-settings",
+            get(get_credit_settings).put(update_credit_settings),
+        )
+        .route(
+            "/admin/credit-settings/customers/{customerId}",
+            put(set_customer_credit_limit).delete(delete_customer_credit_limit),
+        )
+        .route("/customers/{customerId}/credit", get(get_customer_credit))
+        .layer(CorsLayer::permissive())
+}
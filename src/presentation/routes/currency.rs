@@ -0,0 +1,22 @@
+use crate::presentation::handlers::currency::{
+    get_currency_settings, get_time_zone_settings, list_exchange_rates, update_currency_settings,
+    update_time_zone_settings,
+};
+use axum::{routing::get, Router};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn currency_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/admin/currency-settings",
+            get(get_currency_settings).put(update_currency_settings),
+        )
+        .route(
+            "/admin/timezone-settings",
+            get(get_time_zone_settings).put(update_time_zone_settings),
+        )
+        .route("/exchange-rates", get(list_exchange_rates))
+        .layer(CorsLayer::permissive())
+}
@@ -0,0 +1,30 @@
+use crate::presentation::handlers::cycle_count::{
+    cancel_cycle_count, create_cycle_count, finalize_cycle_count, get_cycle_count,
+    list_cycle_counts, record_counts,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn cycle_count_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/cycle-counts",
+            post(create_cycle_count).get(list_cycle_counts),
+        )
+        .route("/cycle-counts/{cycleCountId}", get(get_cycle_count))
+        .route("/cycle-counts/{cycleCountId}/counts", post(record_counts))
+        .route(
+            "/cycle-counts/{cycleCountId}/finalize",
+            post(finalize_cycle_count),
+        )
+        .route(
+            "/cycle-counts/{cycleCountId}/cancel",
+            post(cancel_cycle_count),
+        )
+        .layer(CorsLayer::permissive())
+}
@@ -0,0 +1,35 @@
+use crate::infrastructure::config::app_config::BodyLimitsConfig;
+use crate::infrastructure::middleware::body_limit::body_limit;
+use crate::presentation::handlers::documents::{
+    delete_document_logo, get_document_logo, get_document_settings, get_packing_slip_pdf,
+    get_purchase_order_pdf, get_sales_order_pdf, update_document_settings, upload_document_logo,
+};
+use axum::{
+    routing::{get, put},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn document_routes(body_limits: &BodyLimitsConfig) -> Router<AppState> {
+    Router::new()
+        .route("/purchase_orders/{poId}/pdf", get(get_purchase_order_pdf))
+        .route("/sales_orders/{soId}/pdf", get(get_sales_order_pdf))
+        .route(
+            "/sales_orders/{soId}/packing-slip",
+            get(get_packing_slip_pdf),
+        )
+        .route(
+            "/admin/document-settings",
+            get(get_document_settings).put(update_document_settings),
+        )
+        .route(
+            "/admin/document-settings/logo",
+            put(upload_document_logo)
+                .layer(body_limit(body_limits.upload_bytes))
+                .get(get_document_logo)
+                .delete(delete_document_logo),
+        )
+        .layer(CorsLayer::permissive())
+}
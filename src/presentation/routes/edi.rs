@@ -0,0 +1,41 @@
+use crate::presentation::handlers::edi::{
+    create_invoice, create_ship_notice, create_trading_partner, delete_trading_partner,
+    download_edi_document, get_edi_document, get_trading_partner, list_edi_documents,
+    list_trading_partners, update_trading_partner, upload_edi_document,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn edi_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/edi/partners",
+            post(create_trading_partner).get(list_trading_partners),
+        )
+        .route(
+            "/edi/partners/{partnerId}",
+            get(get_trading_partner)
+                .put(update_trading_partner)
+                .delete(delete_trading_partner),
+        )
+        .route(
+            "/edi/documents",
+            post(upload_edi_document).get(list_edi_documents),
+        )
+        .route("/edi/documents/{documentId}", get(get_edi_document))
+        .route(
+            "/edi/documents/{documentId}/content",
+            get(download_edi_document),
+        )
+        .route(
+            "/edi/shipments/{shipmentId}/ship-notice",
+            post(create_ship_notice),
+        )
+        .route("/edi/shipments/{shipmentId}/invoice", post(create_invoice))
+        .layer(CorsLayer::permissive())
+}
@@ -0,0 +1,8 @@
+use crate::presentation::handlers::event_stream::stream_events;
+use axum::{routing::get, Router};
+
+use crate::AppState;
+
+pub fn event_stream_routes() -> Router<AppState> {
+    Router::new().route("/events/stream", get(stream_events))
+}
@@ -0,0 +1,15 @@
+use crate::presentation::handlers::forecast::{get_item_forecast, list_item_forecast_snapshots};
+use axum::{routing::get, Router};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn forecast_routes() -> Router<AppState> {
+    Router::new()
+        .route("/items/{id}/forecast", get(get_item_forecast))
+        .route(
+            "/items/{id}/forecast/snapshots",
+            get(list_item_forecast_snapshots),
+        )
+        .layer(CorsLayer::permissive())
+}
@@ -0,0 +1,11 @@
+use crate::presentation::handlers::graphql::{execute_graphql, get_graphql_schema};
+use axum::{routing::get, Router};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn graphql_routes() -> Router<AppState> {
+    Router::new()
+        .route("/graphql", get(get_graphql_schema).post(execute_graphql))
+        .layer(CorsLayer::permissive())
+}
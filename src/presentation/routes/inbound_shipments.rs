@@ -0,0 +1,24 @@
+use crate::presentation::handlers::inbound_shipments::{
+    close_inbound_shipment, create_inbound_shipment, get_inbound_shipment,
+    list_purchase_order_inbound_shipments, receive_inbound_cartons,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn inbound_shipment_routes() -> Router<AppState> {
+    Router::new()
+        .route("/asns", post(create_inbound_shipment))
+        .route("/asns/{asnId}", get(get_inbound_shipment))
+        .route("/asns/{asnId}/receive", post(receive_inbound_cartons))
+        .route("/asns/{asnId}/close", post(close_inbound_shipment))
+        .route(
+            "/purchase_orders/{poId}/asns",
+            get(list_purchase_order_inbound_shipments),
+        )
+        .layer(CorsLayer::permissive())
+}
@@ -0,0 +1,47 @@
+use crate::presentation::handlers::integrations::{
+    authorize_connector, complete_connector_authorization, create_connector, delete_connector,
+    get_connector, list_connectors, list_sync_runs, set_connector_credentials, sync_connector,
+    update_connector,
+};
+use axum::{
+    routing::{get, post, put},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn integration_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/integrations/connectors",
+            post(create_connector).get(list_connectors),
+        )
+        .route(
+            "/integrations/connectors/{connectorId}",
+            get(get_connector)
+                .put(update_connector)
+                .delete(delete_connector),
+        )
+        .route(
+            "/integrations/connectors/{connectorId}/credentials",
+            put(set_connector_credentials),
+        )
+        .route(
+            "/integrations/connectors/{connectorId}/oauth/authorize",
+            post(authorize_connector),
+        )
+        .route(
+            "/integrations/connectors/{connectorId}/oauth/callback",
+            post(complete_connector_authorization),
+        )
+        .route(
+            "/integrations/connectors/{connectorId}/sync",
+            post(sync_connector),
+        )
+        .route(
+            "/integrations/connectors/{connectorId}/runs",
+            get(list_sync_runs),
+        )
+        .layer(CorsLayer::permissive())
+}
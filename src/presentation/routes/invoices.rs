@@ -0,0 +1,19 @@
+use crate::presentation::handlers::invoices::{
+    create_invoice, get_invoice, list_invoices, record_payment,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn invoice_routes() -> Router<AppState> {
+    Router::new()
+        .route("/sales_orders/{soId}/invoice", post(create_invoice))
+        .route("/invoices", get(list_invoices))
+        .route("/invoices/{invoiceId}", get(get_invoice))
+        .route("/invoices/{invoiceId}/payments", post(record_payment))
+        .layer(CorsLayer::permissive())
+}
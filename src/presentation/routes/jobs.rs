@@ -1,4 +1,4 @@
-use crate::presentation::handlers::jobs::{enqueue_job, get_job_status};
+use crate::presentation::handlers::jobs::{cancel_job, enqueue_job, get_job_status, retry_job};
 use crate::AppState;
 use axum::{
     routing::{get, post},
@@ -9,4 +9,6 @@ pub fn create_jobs_routes() -> Router<AppState> {
     Router::new()
         .route("/jobs", post(enqueue_job))
         .route("/jobs/{jobId}", get(get_job_status))
+        .route("/jobs/{jobId}/cancel", post(cancel_job))
+        .route("/jobs/{jobId}/retry", post(retry_job))
 }
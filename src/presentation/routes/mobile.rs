@@ -0,0 +1,15 @@
+use crate::presentation::handlers::mobile::{scan_count, scan_pick, scan_receive};
+use axum::{routing::post, Router};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+/// Coarse-grained endpoints for RF scanners: each validates the scan and
+/// records the movement in a single round trip
+pub fn mobile_routes() -> Router<AppState> {
+    Router::new()
+        .route("/mobile/v1/receive", post(scan_receive))
+        .route("/mobile/v1/pick", post(scan_pick))
+        .route("/mobile/v1/count", post(scan_count))
+        .layer(CorsLayer::permissive())
+}
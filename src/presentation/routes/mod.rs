@@ -1,25 +1,77 @@
 // Presentation layer routes
+pub mod addresses;
+pub mod adjustments;
 pub mod admin;
+pub mod attachments;
+pub mod availability;
+pub mod barcode;
+pub mod blobs;
+pub mod credit;
+pub mod currency;
+pub mod cycle_count;
+pub mod documents;
+pub mod edi;
+pub mod event_stream;
+pub mod forecast;
+pub mod graphql;
+pub mod inbound_shipments;
+pub mod integrations;
+pub mod invoices;
 pub mod jobs;
 pub mod metrics;
+pub mod mobile;
+pub mod printing;
+pub mod products;
 pub mod purchase_order;
+pub mod putaway;
+pub mod receiving;
 pub mod reports;
 pub mod returns;
 pub mod sales_order;
 pub mod search;
+pub mod shipment;
 pub mod stock;
 pub mod tenant;
 pub mod transfer;
+pub mod users;
+pub mod vendor_returns;
 pub mod webhook;
+pub mod webhook_retention;
 
+pub use addresses::address_routes;
+pub use adjustments::adjustment_routes;
 pub use admin::create_admin_router;
+pub use attachments::attachment_routes;
+pub use availability::availability_routes;
+pub use barcode::barcode_routes;
+pub use blobs::blob_routes;
+pub use credit::credit_routes;
+pub use currency::currency_routes;
+pub use cycle_count::cycle_count_routes;
+pub use documents::document_routes;
+pub use edi::edi_routes;
+pub use event_stream::event_stream_routes;
+pub use forecast::forecast_routes;
+pub use graphql::graphql_routes;
+pub use inbound_shipments::inbound_shipment_routes;
+pub use integrations::integration_routes;
+pub use invoices::invoice_routes;
 pub use jobs::create_jobs_routes;
 pub use metrics::create_metrics_router;
+pub use mobile::mobile_routes;
+pub use printing::printing_routes;
+pub use products::product_routes;
 pub use purchase_order::create_purchase_order_routes;
+pub use putaway::putaway_routes;
+pub use receiving::receiving_routes;
 pub use reports::create_reports_routes;
 pub use returns::return_routes;
 pub use sales_order::sales_order_routes;
+pub use shipment::shipment_routes;
 pub use stock::create_stock_routes;
 pub use tenant::tenant_routes;
 pub use transfer::transfer_routes;
+pub use users::user_routes;
+pub use vendor_returns::vendor_return_routes;
 pub use webhook::create_webhook_routes;
+pub use webhook_retention::webhook_retention_routes;
@@ -0,0 +1,24 @@
+use crate::presentation::handlers::printing::{
+    create_print_job, create_printer, delete_printer, get_print_job, get_printer, list_print_jobs,
+    list_printers, retry_print_job, update_printer,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn printing_routes() -> Router<AppState> {
+    Router::new()
+        .route("/printers", post(create_printer).get(list_printers))
+        .route(
+            "/printers/{printerId}",
+            get(get_printer).put(update_printer).delete(delete_printer),
+        )
+        .route("/print-jobs", post(create_print_job).get(list_print_jobs))
+        .route("/print-jobs/{printJobId}", get(get_print_job))
+        .route("/print-jobs/{printJobId}/retry", post(retry_print_job))
+        .layer(CorsLayer::permissive())
+}
@@ -0,0 +1,24 @@
+use crate::presentation::handlers::products::{
+    create_product, delete_product, generate_variants, get_product, list_products, update_product,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn product_routes() -> Router<AppState> {
+    Router::new()
+        .route("/products", post(create_product).get(list_products))
+        .route(
+            "/products/{productId}",
+            get(get_product).put(update_product).delete(delete_product),
+        )
+        .route(
+            "/products/{productId}/variants/generate",
+            post(generate_variants),
+        )
+        .layer(CorsLayer::permissive())
+}
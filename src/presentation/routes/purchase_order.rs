@@ -1,22 +1,54 @@
 use axum::{
-    routing::{get, post},
+    routing::{get, patch, post, put},
     Router,
 };
 use tower_http::cors::CorsLayer;
 
 use crate::presentation::handlers::purchase_order::{
-    create_purchase_order, get_purchase_order, receive_purchase_order,
+    add_purchase_order_line, cancel_purchase_order, close_purchase_order, create_purchase_order,
+    get_purchase_order, list_purchase_order_receipts, list_purchase_orders,
+    promise_purchase_order_line, receive_purchase_order, remove_purchase_order_line,
+    update_purchase_order_line,
 };
+use crate::presentation::handlers::status_history::get_purchase_order_history;
 use crate::AppState;
 
 /// Create purchase order-related routes
 pub fn create_purchase_order_routes() -> Router<AppState> {
     Router::new()
-        .route("/purchase_orders", post(create_purchase_order))
+        .route(
+            "/purchase_orders",
+            get(list_purchase_orders).post(create_purchase_order),
+        )
         .route("/purchase_orders/{poId}", get(get_purchase_order))
         .route(
             "/purchase_orders/{poId}/receive",
             post(receive_purchase_order),
         )
+        .route(
+            "/purchase_orders/{poId}/receipts",
+            get(list_purchase_order_receipts),
+        )
+        .route(
+            "/purchase_orders/{poId}/cancel",
+            post(cancel_purchase_order),
+        )
+        .route("/purchase_orders/{poId}/close", post(close_purchase_order))
+        .route(
+            "/purchase_orders/{poId}/history",
+            get(get_purchase_order_history),
+        )
+        .route(
+            "/purchase_orders/{poId}/lines",
+            post(add_purchase_order_line),
+        )
+        .route(
+            "/purchase_orders/{poId}/lines/{lineId}",
+            put(update_purchase_order_line).delete(remove_purchase_order_line),
+        )
+        .route(
+            "/purchase_orders/{poId}/lines/{lineId}/promise",
+            patch(promise_purchase_order_line),
+        )
         .layer(CorsLayer::permissive())
 }
@@ -0,0 +1,26 @@
+use crate::presentation::handlers::putaway::{
+    create_putaway_rule, delete_putaway_rule, get_putaway_rule, list_putaway_rules,
+    update_putaway_rule,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn putaway_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/putaway-rules",
+            post(create_putaway_rule).get(list_putaway_rules),
+        )
+        .route(
+            "/putaway-rules/{ruleId}",
+            get(get_putaway_rule)
+                .put(update_putaway_rule)
+                .delete(delete_putaway_rule),
+        )
+        .layer(CorsLayer::permissive())
+}
@@ -0,0 +1,24 @@
+use crate::presentation::handlers::receiving::{
+    delete_supplier_tolerance, get_receiving_settings, set_supplier_tolerance,
+    update_receiving_settings,
+};
+use axum::{
+    routing::{get, put},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn receiving_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/admin/receiving-settings",
+            get(get_receiving_settings).put(update_receiving_settings),
+        )
+        .route(
+            "/admin/receiving-settings/suppliers/{supplierId}",
+            put(set_supplier_tolerance).delete(delete_supplier_tolerance),
+        )
+        .layer(CorsLayer::permissive())
+}
@@ -1,10 +1,55 @@
-use crate::presentation::handlers::reports::{get_low_stock_report, get_stock_valuation_report};
+use crate::presentation::handlers::reports::{
+    create_reorder_purchase_orders, get_dead_stock_report, get_location_utilization_report,
+    get_low_stock_report, get_overdue_purchase_orders_report, get_receivables_aging_report,
+    get_reorder_suggestions, get_scrap_report, get_stock_valuation_report,
+};
 use crate::AppState;
-use axum::{routing::get, Router};
-use std::sync::Arc;
+use axum::{
+    http::{header::CACHE_CONTROL, HeaderValue},
+    response::Response,
+    routing::{get, post},
+    Router,
+};
+use tower_http::set_header::SetResponseHeaderLayer;
+
+/// Reports are aggregates over many rows, so a client may reuse one for a
+/// minute rather than recompute it on every screen refresh
+const REPORT_CACHE_CONTROL: &str = "private, max-age=60";
 
 pub fn create_reports_routes() -> Router<AppState> {
-    Router::new()
+    let reports = Router::new()
         .route("/reports/low_stock", get(get_low_stock_report))
         .route("/reports/stock_valuation", get(get_stock_valuation_report))
+        .route("/reports/reorder-suggestions", get(get_reorder_suggestions))
+        .route("/reports/scrap", get(get_scrap_report))
+        .route("/reports/dead-stock", get(get_dead_stock_report))
+        .route(
+            "/reports/overdue-purchase-orders",
+            get(get_overdue_purchase_orders_report),
+        )
+        .route(
+            "/reports/receivables-aging",
+            get(get_receivables_aging_report),
+        )
+        .route(
+            "/reports/location-utilization",
+            get(get_location_utilization_report),
+        )
+        .layer(SetResponseHeaderLayer::if_not_present(
+            CACHE_CONTROL,
+            |response: &Response| {
+                // Errors aren't cached, so a retry isn't served the failure
+                response
+                    .status()
+                    .is_success()
+                    .then(|| HeaderValue::from_static(REPORT_CACHE_CONTROL))
+            },
+        ));
+
+    Router::new()
+        .route(
+            "/reports/reorder-suggestions/create-pos",
+            post(create_reorder_purchase_orders),
+        )
+        .merge(reports)
 }
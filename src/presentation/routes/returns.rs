@@ -1,6 +1,7 @@
 use crate::presentation::handlers::returns::{
-    create_return, get_return, open_return, process_return,
+    create_return, get_return, list_returns, open_return, process_return,
 };
+use crate::presentation::handlers::status_history::get_return_history;
 use axum::{
     routing::{get, post},
     Router,
@@ -11,9 +12,10 @@ use crate::AppState;
 
 pub fn return_routes() -> Router<AppState> {
     Router::new()
-        .route("/returns", post(create_return))
+        .route("/returns", get(list_returns).post(create_return))
         .route("/returns/{returnId}", get(get_return))
         .route("/returns/{returnId}/open", post(open_return))
         .route("/returns/{returnId}/process", post(process_return))
+        .route("/returns/{returnId}/history", get(get_return_history))
         .layer(CorsLayer::permissive())
 }
@@ -1,18 +1,42 @@
 use axum::{
-    routing::{get, post},
+    routing::{get, post, put},
     Router,
 };
 use tower_http::cors::CorsLayer;
 
+use crate::presentation::handlers::returns::get_sales_order_returns;
 use crate::presentation::handlers::sales_order::{
-    create_sales_order, get_sales_order, ship_sales_order,
+    add_sales_order_line, allocate_sales_order, cancel_sales_order, create_backorder,
+    create_sales_order, get_sales_order, get_sales_order_allocations, get_sales_order_by_number,
+    list_sales_orders, remove_sales_order_line, ship_sales_order, update_sales_order_line,
 };
+use crate::presentation::handlers::status_history::get_sales_order_history;
 use crate::AppState;
 
 pub fn sales_order_routes() -> Router<AppState> {
     Router::new()
-        .route("/sales_orders", post(create_sales_order))
+        .route(
+            "/sales_orders",
+            get(list_sales_orders).post(create_sales_order),
+        )
         .route("/sales_orders/{soId}", get(get_sales_order))
+        .route(
+            "/sales_orders/number/{soNumber}",
+            get(get_sales_order_by_number),
+        )
         .route("/sales_orders/{soId}/ship", post(ship_sales_order))
+        .route("/sales_orders/{soId}/backorder", post(create_backorder))
+        .route("/sales_orders/{soId}/cancel", post(cancel_sales_order))
+        .route("/sales_orders/{soId}/history", get(get_sales_order_history))
+        .route("/sales_orders/{soId}/returns", get(get_sales_order_returns))
+        .route("/sales_orders/{soId}/lines", post(add_sales_order_line))
+        .route(
+            "/sales_orders/{soId}/lines/{lineId}",
+            put(update_sales_order_line).delete(remove_sales_order_line),
+        )
+        .route(
+            "/sales_orders/{soId}/allocations",
+            post(allocate_sales_order).get(get_sales_order_allocations),
+        )
         .layer(CorsLayer::permissive())
 }
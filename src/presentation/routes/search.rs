@@ -7,8 +7,8 @@ use std::sync::Arc;
 use crate::application::use_cases::search_use_case::{SearchUseCase, SearchUseCaseImpl};
 use crate::infrastructure::repositories::postgres_search_repository::PostgresSearchRepository;
 use crate::presentation::handlers::search::{
-    get_search_suggestions, rebuild_search_indexes, search_all, search_items, search_locations,
-    search_stock_levels,
+    get_search_suggestions, rebuild_search_indexes, reindex_search, search_all,
+    search_index_health, search_items, search_locations, search_stock_levels, suggest,
 };
 use crate::AppState;
 
@@ -20,5 +20,8 @@ pub fn create_search_routes() -> Router<AppState> {
         .route("/search/locations", get(search_locations))
         .route("/search/stock-levels", get(search_stock_levels))
         .route("/search/suggestions", get(get_search_suggestions))
+        .route("/search/suggest", get(suggest))
+        .route("/search/reindex", post(reindex_search))
         .route("/admin/search/rebuild", post(rebuild_search_indexes))
+        .route("/admin/search/health", get(search_index_health))
 }
@@ -0,0 +1,31 @@
+use crate::presentation::handlers::shipment::{
+    create_shipment_cartons, get_shipment, get_shipment_labels, get_sscc_sequence, list_shipments,
+    update_shipment_tracking, update_sscc_sequence,
+};
+use axum::{
+    routing::{get, post, put},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn shipment_routes() -> Router<AppState> {
+    Router::new()
+        .route("/shipments", get(list_shipments))
+        .route("/shipments/{shipmentId}", get(get_shipment))
+        .route(
+            "/shipments/{shipmentId}/tracking",
+            put(update_shipment_tracking),
+        )
+        .route(
+            "/shipments/{shipmentId}/cartons",
+            post(create_shipment_cartons),
+        )
+        .route("/shipments/{shipmentId}/labels", get(get_shipment_labels))
+        .route(
+            "/admin/sscc-sequence",
+            get(get_sscc_sequence).put(update_sscc_sequence),
+        )
+        .layer(CorsLayer::permissive())
+}
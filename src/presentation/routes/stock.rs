@@ -5,7 +5,9 @@ use axum::{
 use tower_http::cors::CorsLayer;
 
 use crate::presentation::handlers::stock::{
-    adjust_stock, get_item_stock_levels, get_stock_level, get_stock_movements,
+    batch_get_stock_levels, change_stock_status, export_stock_movements, get_available_to_promise,
+    get_item_stock_levels, get_stock_level, get_stock_levels_as_of, get_stock_movements,
+    list_stock_levels, release_stock, reserve_stock,
 };
 use crate::AppState;
 
@@ -13,9 +15,18 @@ use crate::AppState;
 pub fn create_stock_routes() -> Router<AppState> {
     Router::new()
         .route("/stock/{item_id}/{location_id}", get(get_stock_level))
+        .route(
+            "/stock/{item_id}/{location_id}/atp",
+            get(get_available_to_promise),
+        )
+        .route("/stock/reservations", post(reserve_stock))
+        .route("/stock/reservations/release", post(release_stock))
         .route("/stock/items/{item_id}", get(get_item_stock_levels))
+        .route("/stock/levels", get(list_stock_levels))
+        .route("/stock/levels/batch-get", post(batch_get_stock_levels))
+        .route("/stock/levels/as-of", get(get_stock_levels_as_of))
         .route("/stock/movements", get(get_stock_movements))
-        .route("/stock/adjust", post(adjust_stock))
-        .route("/adjustments", post(adjust_stock))
+        .route("/stock/movements/export", get(export_stock_movements))
+        .route("/stock/status-changes", post(change_stock_status))
         .layer(CorsLayer::permissive())
 }
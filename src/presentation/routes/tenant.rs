@@ -1,6 +1,7 @@
 use crate::presentation::handlers::tenant::{
     cleanup_expired_sandboxes, create_sandbox_tenant, create_tenant, delete_tenant, get_tenant,
-    list_tenants,
+    list_sandbox_echo_deliveries, list_tenant_audit_log, list_tenants, promote_sandbox,
+    receive_sandbox_echo, reset_sandbox,
 };
 use crate::AppState;
 use axum::{
@@ -16,4 +17,9 @@ pub fn tenant_routes() -> Router<AppState> {
         .route("/tenants/cleanup", post(cleanup_expired_sandboxes))
         .route("/tenants/{tenant_id}", get(get_tenant))
         .route("/tenants/{tenant_id}", delete(delete_tenant))
+        .route("/tenants/{tenant_id}/sandbox/reset", post(reset_sandbox))
+        .route("/tenants/{tenant_id}/promote", post(promote_sandbox))
+        .route("/tenants/{tenant_id}/audit-log", get(list_tenant_audit_log))
+        .route("/sandbox/echo", get(list_sandbox_echo_deliveries))
+        .route("/sandbox/echo/{tenant_id}", post(receive_sandbox_echo))
 }
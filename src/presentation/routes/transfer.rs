@@ -1,5 +1,6 @@
+use crate::presentation::handlers::status_history::get_transfer_history;
 use crate::presentation::handlers::transfer::{
-    create_transfer, get_transfer, receive_transfer, ship_transfer,
+    create_transfer, get_transfer, list_transfers, receive_transfer, ship_transfer,
 };
 use axum::{
     routing::{get, post},
@@ -11,9 +12,10 @@ use crate::AppState;
 
 pub fn transfer_routes() -> Router<AppState> {
     Router::new()
-        .route("/transfers", post(create_transfer))
+        .route("/transfers", get(list_transfers).post(create_transfer))
         .route("/transfers/{transferId}", get(get_transfer))
         .route("/transfers/{transferId}/ship", post(ship_transfer))
         .route("/transfers/{transferId}/receive", post(receive_transfer))
+        .route("/transfers/{transferId}/history", get(get_transfer_history))
         .layer(CorsLayer::permissive())
 }
@@ -0,0 +1,21 @@
+use crate::presentation::handlers::users::{
+    activate_user, change_user_role, deactivate_user, invite_user, list_users, register_user,
+};
+use axum::{
+    routing::{get, post, put},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn user_routes() -> Router<AppState> {
+    Router::new()
+        .route("/users", get(list_users))
+        .route("/users/invitations", post(invite_user))
+        .route("/users/register", post(register_user))
+        .route("/users/{userId}/activate", post(activate_user))
+        .route("/users/{userId}/deactivate", post(deactivate_user))
+        .route("/users/{userId}/role", put(change_user_role))
+        .layer(CorsLayer::permissive())
+}
@@ -0,0 +1,20 @@
+use crate::presentation::handlers::vendor_returns::{
+    cancel_vendor_return, create_vendor_return, get_vendor_return, list_vendor_returns,
+    ship_vendor_return,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::AppState;
+
+pub fn vendor_return_routes() -> Router<AppState> {
+    Router::new()
+        .route("/rtv", get(list_vendor_returns).post(create_vendor_return))
+        .route("/rtv/{rtvId}", get(get_vendor_return))
+        .route("/rtv/{rtvId}/ship", post(ship_vendor_return))
+        .route("/rtv/{rtvId}/cancel", post(cancel_vendor_return))
+        .layer(CorsLayer::permissive())
+}
@@ -5,10 +5,12 @@ use axum::{
 use tower_http::cors::CorsLayer;
 
 use crate::presentation::handlers::webhook::{
-    delete_webhook, get_user_webhooks, register_webhook, update_webhook,
+    delete_webhook, delete_webhook_filter, get_user_webhooks, get_webhook_filter, pause_webhook,
+    register_webhook, resume_webhook, set_webhook_filter, update_webhook,
 };
 use crate::presentation::handlers::webhook_deliveries::{
     get_webhook_deliveries, get_webhook_delivery_details, retry_webhook_delivery, test_webhook,
+    verify_webhook_sample,
 };
 use crate::AppState;
 
@@ -27,7 +29,19 @@ pub fn create_webhook_routes() -> Router<AppState> {
             "/webhooks/deliveries/{delivery_id}",
             get(get_webhook_delivery_details),
         )
+        .route(
+            "/webhooks/{webhook_id}/filter",
+            get(get_webhook_filter)
+                .put(set_webhook_filter)
+                .delete(delete_webhook_filter),
+        )
+        .route("/webhooks/{webhook_id}/pause", post(pause_webhook))
+        .route("/webhooks/{webhook_id}/resume", post(resume_webhook))
         .route("/webhooks/{webhook_id}/test", post(test_webhook))
+        .route(
+            "/webhooks/{webhook_id}/verify-sample",
+            post(verify_webhook_sample),
+        )
         .route(
             "/webhooks/deliveries/{delivery_id}/retry",
             post(retry_webhook_delivery),
@@ -0,0 +1,23 @@
+use crate::presentation::handlers::webhook_retention::{
+    get_latest_webhook_retention_run, get_webhook_retention, run_webhook_retention,
+    update_webhook_retention,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::AppState;
+
+pub fn webhook_retention_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/admin/webhook-retention",
+            get(get_webhook_retention).put(update_webhook_retention),
+        )
+        .route("/admin/webhook-retention/run", post(run_webhook_retention))
+        .route(
+            "/admin/webhook-retention/runs/latest",
+            get(get_latest_webhook_retention_run),
+        )
+}
@@ -0,0 +1,378 @@
+use crate::shared::error::DomainError;
+use crate::shared::i18n::{current_locale, t, Message};
+use crate::shared::trace_id::current_trace_id;
+use async_graphql::ErrorExtensions;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Body of every error response. `error` is a stable, machine-readable code
+/// (e.g. `NOT_FOUND`); `message` is for humans and may change.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: String,
+    pub message: String,
+    /// Quote this when reporting a problem; it ties the response to the server logs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+}
+
+/// Error returned by HTTP handlers. Converts from `DomainError`, so handlers can
+/// use `?` on use case results.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: Cow<'static, str>,
+    message: Message,
+}
+
+impl ApiError {
+    pub fn new(
+        status: StatusCode,
+        code: impl Into<Cow<'static, str>>,
+        message: impl Into<Message>,
+    ) -> Self {
+        Self {
+            status,
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn bad_request(message: impl Into<Message>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "VALIDATION_ERROR", message)
+    }
+
+    pub fn unauthorized(message: impl Into<Message>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "UNAUTHORIZED", message)
+    }
+
+    pub fn forbidden(message: impl Into<Message>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, "FORBIDDEN", message)
+    }
+
+    pub fn not_found(message: impl Into<Message>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, "NOT_FOUND", message)
+    }
+
+    pub fn conflict(message: impl Into<Message>) -> Self {
+        Self::new(StatusCode::CONFLICT, "CONFLICT", message)
+    }
+
+    /// The resource changed since the client read it (`If-Match` mismatch)
+    pub fn precondition_failed(message: impl Into<Message>) -> Self {
+        Self::new(
+            StatusCode::PRECONDITION_FAILED,
+            "CONCURRENT_MODIFICATION",
+            message,
+        )
+    }
+
+    /// The body is over the route's size limit
+    pub fn payload_too_large() -> Self {
+        Self::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "PAYLOAD_TOO_LARGE",
+            "Request body is too large",
+        )
+    }
+
+    pub fn internal(message: impl Into<Message>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR", message)
+    }
+
+    /// A more specific code than the default for the status, e.g. `ITEM_NOT_FOUND`
+    pub fn with_code(mut self, code: impl Into<Cow<'static, str>>) -> Self {
+        self.code = code.into();
+        self
+    }
+}
+
+impl From<DomainError> for ApiError {
+    fn from(error: DomainError) -> Self {
+        match error {
+            DomainError::ValidationError(msg) => Self::bad_request(msg),
+            DomainError::BusinessLogicError(msg) => {
+                Self::new(StatusCode::CONFLICT, "BUSINESS_RULE_VIOLATION", msg)
+            }
+            DomainError::NotFound(msg) => Self::not_found(msg),
+            DomainError::Conflict(msg) => Self::conflict(msg),
+            DomainError::InfrastructureError(msg) => {
+                Self::new(StatusCode::SERVICE_UNAVAILABLE, "INFRASTRUCTURE_ERROR", msg)
+            }
+            DomainError::DatabaseError(msg) => {
+                Self::new(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", msg)
+            }
+            DomainError::QuotaExceeded(msg) => {
+                Self::new(StatusCode::FORBIDDEN, "QUOTA_EXCEEDED", msg)
+            }
+            DomainError::Forbidden(msg) => Self::forbidden(msg),
+            DomainError::PreconditionFailed(msg) => Self::precondition_failed(msg),
+        }
+    }
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(error: sqlx::Error) -> Self {
+        DomainError::from(error).into()
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let trace_id = current_trace_id();
+        // Server-side details stay in the logs; the client gets the trace ID
+        let message = if self.status.is_server_error() {
+            tracing::error!(
+                trace_id = trace_id.as_deref().unwrap_or("-"),
+                code = %self.code,
+                "{}",
+                self.message
+            );
+            t("error.internal", &[])
+        } else {
+            self.message.localize(current_locale())
+        };
+
+        let body = ErrorResponse {
+            error: self.code.into_owned(),
+            message,
+            trace_id,
+        };
+        (self.status, Json(body)).into_response()
+    }
+}
+
+/// The same failure reported over gRPC, under the status code closest to the
+/// HTTP status. The error code travels in the `error-code` metadata.
+impl From<ApiError> for tonic::Status {
+    fn from(error: ApiError) -> Self {
+        use tonic::Code;
+
+        let code = match (error.status, error.code.as_ref()) {
+            (_, "QUOTA_EXCEEDED") => Code::ResourceExhausted,
+            (StatusCode::BAD_REQUEST, _) => Code::InvalidArgument,
+            (StatusCode::UNAUTHORIZED, _) => Code::Unauthenticated,
+            (StatusCode::FORBIDDEN, _) => Code::PermissionDenied,
+            (StatusCode::NOT_FOUND, _) => Code::NotFound,
+            (StatusCode::CONFLICT, "CONFLICT" | "DUPLICATE_SKU" | "DUPLICATE_BARCODE") => {
+                Code::AlreadyExists
+            }
+            (StatusCode::CONFLICT, _) | (StatusCode::PRECONDITION_FAILED, _) => {
+                Code::FailedPrecondition
+            }
+            (StatusCode::TOO_MANY_REQUESTS, _) => Code::ResourceExhausted,
+            (StatusCode::SERVICE_UNAVAILABLE, _) => Code::Unavailable,
+            (status, _) if status.is_server_error() => Code::Internal,
+            _ => Code::Unknown,
+        };
+        let message = if error.status.is_server_error() {
+            tracing::error!(code = %error.code, "{}", error.message);
+            "An internal error occurred".to_string()
+        } else {
+            error.message.into()
+        };
+
+        let mut status = tonic::Status::new(code, message);
+        if let Ok(value) = error.code.parse() {
+            status.metadata_mut().insert("error-code", value);
+        }
+        status
+    }
+}
+
+/// A failed GraphQL field, with the error code in `extensions.code`
+impl From<ApiError> for async_graphql::Error {
+    fn from(error: ApiError) -> Self {
+        let trace_id = current_trace_id();
+        let message = if error.status.is_server_error() {
+            tracing::error!(
+                trace_id = trace_id.as_deref().unwrap_or("-"),
+                code = %error.code,
+                "{}",
+                error.message
+            );
+            "An internal error occurred".to_string()
+        } else {
+            error.message.into()
+        };
+
+        async_graphql::Error::new(message).extend_with(|_, extensions| {
+            extensions.set("code", error.code.as_ref());
+            if let Some(trace_id) = trace_id {
+                extensions.set("traceId", trace_id);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::i18n::{with_locale, Locale};
+    use crate::shared::trace_id::with_trace_id;
+
+    #[test]
+    fn test_domain_errors_map_to_status_and_code() {
+        let cases = [
+            (
+                DomainError::ValidationError("x".into()),
+                StatusCode::BAD_REQUEST,
+                "VALIDATION_ERROR",
+            ),
+            (
+                DomainError::BusinessLogicError("x".into()),
+                StatusCode::CONFLICT,
+                "BUSINESS_RULE_VIOLATION",
+            ),
+            (
+                DomainError::NotFound("x".into()),
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+            ),
+            (
+                DomainError::Conflict("x".into()),
+                StatusCode::CONFLICT,
+                "CONFLICT",
+            ),
+            (
+                DomainError::InfrastructureError("x".into()),
+                StatusCode::SERVICE_UNAVAILABLE,
+                "INFRASTRUCTURE_ERROR",
+            ),
+            (
+                DomainError::DatabaseError("x".into()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+            ),
+            (
+                DomainError::QuotaExceeded("x".into()),
+                StatusCode::FORBIDDEN,
+                "QUOTA_EXCEEDED",
+            ),
+            (
+                DomainError::Forbidden("x".into()),
+                StatusCode::FORBIDDEN,
+                "FORBIDDEN",
+            ),
+            (
+                DomainError::PreconditionFailed("x".into()),
+                StatusCode::PRECONDITION_FAILED,
+                "CONCURRENT_MODIFICATION",
+            ),
+        ];
+        for (error, status, code) in cases {
+            let api_error = ApiError::from(error);
+            assert_eq!(api_error.status, status);
+            assert_eq!(api_error.code, code);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_response_carries_trace_id_and_hides_server_details() {
+        let response = with_trace_id("trace-1".to_string(), async {
+            ApiError::from(DomainError::DatabaseError("relation missing".into())).into_response()
+        })
+        .await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: ErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.error, "DATABASE_ERROR");
+        assert_eq!(body.message, "An internal error occurred");
+        assert_eq!(body.trace_id.as_deref(), Some("trace-1"));
+    }
+
+    #[tokio::test]
+    async fn test_response_message_follows_request_locale() {
+        let response = with_locale(Locale::PtBr, async {
+            ApiError::from(DomainError::ValidationError(Message::localized(
+                "error.sku_empty",
+                &[],
+            )))
+            .into_response()
+        })
+        .await;
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: ErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.message, "O SKU não pode ficar vazio");
+    }
+
+    #[test]
+    fn test_grpc_status_matches_http_status() {
+        let cases = [
+            (
+                DomainError::ValidationError("x".into()),
+                tonic::Code::InvalidArgument,
+            ),
+            (
+                DomainError::BusinessLogicError("x".into()),
+                tonic::Code::FailedPrecondition,
+            ),
+            (DomainError::NotFound("x".into()), tonic::Code::NotFound),
+            (
+                DomainError::Conflict("x".into()),
+                tonic::Code::AlreadyExists,
+            ),
+            (
+                DomainError::InfrastructureError("x".into()),
+                tonic::Code::Unavailable,
+            ),
+            (
+                DomainError::QuotaExceeded("x".into()),
+                tonic::Code::ResourceExhausted,
+            ),
+            (
+                DomainError::Forbidden("x".into()),
+                tonic::Code::PermissionDenied,
+            ),
+            (
+                DomainError::PreconditionFailed("x".into()),
+                tonic::Code::FailedPrecondition,
+            ),
+        ];
+        for (error, code) in cases {
+            assert_eq!(tonic::Status::from(ApiError::from(error)).code(), code);
+        }
+
+        let status = tonic::Status::from(ApiError::from(DomainError::DatabaseError(
+            "relation missing".into(),
+        )));
+        assert_eq!(status.code(), tonic::Code::Internal);
+        assert_eq!(status.message(), "An internal error occurred");
+        assert_eq!(
+            status.metadata().get("error-code").unwrap(),
+            "DATABASE_ERROR"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_graphql_error_carries_code() {
+        let error = async_graphql::Error::from(ApiError::not_found("Item not found"));
+        assert_eq!(error.message, "Item not found");
+        assert_eq!(
+            serde_json::to_value(error.extensions).unwrap(),
+            serde_json::json!({ "code": "NOT_FOUND" })
+        );
+
+        let error = with_trace_id("trace-1".to_string(), async {
+            async_graphql::Error::from(ApiError::from(DomainError::DatabaseError(
+                "relation missing".into(),
+            )))
+        })
+        .await;
+        assert_eq!(error.message, "An internal error occurred");
+        let extensions = serde_json::to_value(error.extensions).unwrap();
+        assert_eq!(extensions["code"], "DATABASE_ERROR");
+        assert_eq!(extensions["traceId"], "trace-1");
+    }
+}
@@ -0,0 +1,75 @@
+//! Fetching many resources by ID in one request, for integrations that would
+//! otherwise look them up one at a time
+
+use crate::shared::error::DomainError;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Most IDs one batch-get request may name
+pub const MAX_BATCH_GET: usize = 500;
+
+/// The requested keys without repeats, in the order first asked for
+pub fn unique_keys<K: Eq + Hash + Copy>(keys: Vec<K>) -> Result<Vec<K>, DomainError> {
+    if keys.len() > MAX_BATCH_GET {
+        return Err(DomainError::ValidationError(
+            format!(
+                "At most {} IDs can be fetched at once, got {}",
+                MAX_BATCH_GET,
+                keys.len()
+            )
+            .into(),
+        ));
+    }
+    let mut seen = HashSet::new();
+    Ok(keys.into_iter().filter(|key| seen.insert(*key)).collect())
+}
+
+/// Put what was found in the order of `keys`, and list the keys nothing was
+/// found for
+pub fn in_request_order<K: Eq + Hash + Copy, T>(
+    keys: &[K],
+    found: Vec<T>,
+    key_of: impl Fn(&T) -> K,
+) -> (Vec<T>, Vec<K>) {
+    let mut found: HashMap<K, T> = found
+        .into_iter()
+        .map(|value| (key_of(&value), value))
+        .collect();
+    let mut missing = Vec::new();
+    let results = keys
+        .iter()
+        .filter_map(|key| {
+            let value = found.remove(key);
+            if value.is_none() {
+                missing.push(*key);
+            }
+            value
+        })
+        .collect();
+    (results, missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_keys_keeps_first_order_and_caps_the_batch() {
+        assert_eq!(unique_keys(vec![3, 1, 3, 2, 1]).unwrap(), vec![3, 1, 2]);
+        assert!(unique_keys(vec![0; MAX_BATCH_GET]).is_ok());
+        assert!(matches!(
+            unique_keys(vec![0; MAX_BATCH_GET + 1]),
+            Err(DomainError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_results_follow_request_order_with_missing_keys_listed() {
+        let (results, missing) =
+            in_request_order(&[3, 1, 4, 2], vec![(1, "a"), (2, "b"), (3, "c")], |found| {
+                found.0
+            });
+        assert_eq!(results, vec![(3, "c"), (1, "a"), (2, "b")]);
+        assert_eq!(missing, vec![4]);
+    }
+}
@@ -1,13 +1,19 @@
+use crate::shared::i18n::Message;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DomainError {
-    ValidationError(String),
-    BusinessLogicError(String),
-    NotFound(String),
-    Conflict(String),
-    InfrastructureError(String),
-    DatabaseError(String),
+    ValidationError(Message),
+    BusinessLogicError(Message),
+    NotFound(Message),
+    Conflict(Message),
+    InfrastructureError(Message),
+    DatabaseError(Message),
+    QuotaExceeded(Message),
+    /// The caller is known but may not take this action
+    Forbidden(Message),
+    /// The entity changed since the caller read it (a stale If-Match)
+    PreconditionFailed(Message),
 }
 
 impl std::fmt::Display for DomainError {
@@ -19,6 +25,9 @@ impl std::fmt::Display for DomainError {
             DomainError::Conflict(msg) => write!(f, "Conflict: {msg}"),
             DomainError::InfrastructureError(msg) => write!(f, "Infrastructure error: {msg}"),
             DomainError::DatabaseError(msg) => write!(f, "Database error: {msg}"),
+            DomainError::QuotaExceeded(msg) => write!(f, "Quota exceeded: {msg}"),
+            DomainError::Forbidden(msg) => write!(f, "Forbidden: {msg}"),
+            DomainError::PreconditionFailed(msg) => write!(f, "Precondition failed: {msg}"),
         }
     }
 }
@@ -27,6 +36,6 @@ impl std::error::Error for DomainError {}
 
 impl From<sqlx::Error> for DomainError {
     fn from(error: sqlx::Error) -> Self {
-        DomainError::DatabaseError(error.to_string())
+        DomainError::DatabaseError(error.to_string().into())
     }
 }
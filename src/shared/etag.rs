@@ -0,0 +1,57 @@
+use crate::shared::error::DomainError;
+use chrono::{DateTime, Utc};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use uuid::Uuid;
+
+/// Strong entity tag derived from an entity's id and last modification time. The
+/// time is taken at microsecond precision, as stored by Postgres, so the tag returned
+/// after a write matches the one computed when the entity is read back.
+pub fn entity_etag(id: Uuid, updated_at: DateTime<Utc>) -> String {
+    let mut hasher = DefaultHasher::new();
+    id.hash(&mut hasher);
+    updated_at.timestamp_micros().hash(&mut hasher);
+    format!("\"{:x}\"", hasher.finish())
+}
+
+/// Reject the write when the caller's If-Match no longer matches the current entity tag
+pub fn check_if_match(if_match: Option<&str>, current_etag: &str) -> Result<(), DomainError> {
+    match if_match {
+        Some(expected) if expected != "*" && expected != current_etag => {
+            Err(DomainError::PreconditionFailed(
+                "ETag mismatch: resource has been modified by another request".into(),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_if_match() {
+        let now = Utc::now();
+        let id = Uuid::new_v4();
+        let etag = entity_etag(id, now);
+
+        assert_eq!(etag, entity_etag(id, now));
+        assert!(check_if_match(Some(&etag), &etag).is_ok());
+        assert!(check_if_match(Some("*"), &etag).is_ok());
+        assert!(check_if_match(None, &etag).is_ok());
+        assert!(matches!(
+            check_if_match(Some("\"stale\""), &etag),
+            Err(DomainError::PreconditionFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_etag_ignores_sub_microsecond_precision() {
+        let id = Uuid::new_v4();
+        let written = DateTime::from_timestamp(1_700_000_000, 123_456_789).unwrap();
+        let stored = DateTime::from_timestamp(1_700_000_000, 123_456_000).unwrap();
+
+        assert_eq!(entity_etag(id, written), entity_etag(id, stored));
+    }
+}
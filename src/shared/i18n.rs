@@ -0,0 +1,306 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::OnceLock;
+
+tokio::task_local! {
+    static CURRENT_LOCALE: Locale;
+}
+
+/// Languages the API answers in. Catalogs live in `locales/<tag>.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    PtBr,
+    Es,
+}
+
+impl Locale {
+    /// BCP 47 tag, as sent back in `Content-Language`
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::PtBr => "pt-BR",
+            Locale::Es => "es",
+        }
+    }
+
+    /// Bare language code, as used by report export locales
+    pub fn language(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::PtBr => "pt",
+            Locale::Es => "es",
+        }
+    }
+
+    /// Any regional variant of a supported language maps to it (`pt-PT` → pt-BR)
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Locale::En),
+            "pt" => Some(Locale::PtBr),
+            "es" => Some(Locale::Es),
+            _ => None,
+        }
+    }
+
+    /// Pick the supported language the client prefers most from an
+    /// `Accept-Language` header, falling back to English
+    pub fn from_accept_language(header: &str) -> Self {
+        let mut best: Option<(f32, Locale)> = None;
+        for entry in header.split(',') {
+            let mut parts = entry.split(';');
+            let Some(locale) = parts.next().and_then(Locale::from_tag) else {
+                continue;
+            };
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if quality > 0.0 && best.is_none_or(|(q, _)| quality > q) {
+                best = Some((quality, locale));
+            }
+        }
+        best.map(|(_, locale)| locale).unwrap_or_default()
+    }
+
+    fn catalog(&self) -> &'static HashMap<String, String> {
+        static EN: OnceLock<HashMap<String, String>> = OnceLock::new();
+        static PT_BR: OnceLock<HashMap<String, String>> = OnceLock::new();
+        static ES: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+        let (cell, source) = match self {
+            Locale::En => (&EN, include_str!("../../locales/en.json")),
+            Locale::PtBr => (&PT_BR, include_str!("../../locales/pt-BR.json")),
+            Locale::Es => (&ES, include_str!("../../locales/es.json")),
+        };
+        cell.get_or_init(|| {
+            serde_json::from_str(source)
+                .unwrap_or_else(|e| panic!("locales/{}.json is invalid: {}", self.tag(), e))
+        })
+    }
+}
+
+/// Run `future` with `locale` as the request's language
+pub async fn with_locale<F: Future>(locale: Locale, future: F) -> F::Output {
+    CURRENT_LOCALE.scope(locale, future).await
+}
+
+/// Language of the request being handled, English outside a request
+pub fn current_locale() -> Locale {
+    CURRENT_LOCALE
+        .try_with(|locale| *locale)
+        .unwrap_or_default()
+}
+
+/// Render catalog entry `key` in `locale`, filling `{name}` placeholders from
+/// `args`. Keys missing from a catalog fall back to English, then to the key.
+pub fn translate(locale: Locale, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let template = locale
+        .catalog()
+        .get(key)
+        .or_else(|| Locale::En.catalog().get(key))
+        .map(String::as_str)
+        .unwrap_or(key);
+
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+}
+
+/// `translate` in the current request's language
+pub fn t(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    translate(current_locale(), key, args)
+}
+
+/// Text for the client: English, and for failures the catalog has an entry
+/// for, the key and arguments to render it again in the request's language.
+/// Anything else is passed through as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    text: String,
+    key: Option<&'static str>,
+    args: Vec<(&'static str, String)>,
+}
+
+impl Message {
+    /// Catalog entry `key`, with its `{name}` placeholders filled from `args`
+    pub fn localized(key: &'static str, args: &[(&'static str, &dyn Display)]) -> Self {
+        let text = translate(Locale::En, key, args);
+        let args = args
+            .iter()
+            .map(|(name, value)| (*name, value.to_string()))
+            .collect();
+        Self {
+            text,
+            key: Some(key),
+            args,
+        }
+    }
+
+    /// The message in `locale`; English when it has no catalog entry
+    pub fn localize(&self, locale: Locale) -> String {
+        match self.key {
+            Some(key) if locale != Locale::En => {
+                let args: Vec<(&str, &dyn Display)> = self
+                    .args
+                    .iter()
+                    .map(|(name, value)| (*name, value as &dyn Display))
+                    .collect();
+                translate(locale, key, &args)
+            }
+            _ => self.text.clone(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The catalog key the message was built from, for telling errors apart
+    /// without matching their wording
+    pub fn key(&self) -> Option<&'static str> {
+        self.key
+    }
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            key: None,
+            args: Vec::new(),
+        }
+    }
+}
+
+impl From<&str> for Message {
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+impl From<Message> for String {
+    fn from(message: Message) -> Self {
+        message.text
+    }
+}
+
+/// Serialized as its English text; a deserialized message has no catalog key
+impl Serialize for Message {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+impl<'de> Deserialize<'de> for Message {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Message::from)
+    }
+}
+
+impl std::ops::Deref for Message {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl PartialEq<str> for Message {
+    fn eq(&self, other: &str) -> bool {
+        self.text == other
+    }
+}
+
+impl PartialEq<&str> for Message {
+    fn eq(&self, other: &&str) -> bool {
+        self.text == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accept_language_picks_highest_quality_supported_locale() {
+        assert_eq!(Locale::from_accept_language("pt-BR,pt;q=0.9"), Locale::PtBr);
+        assert_eq!(
+            Locale::from_accept_language("fr-FR, es;q=0.8, en;q=0.5"),
+            Locale::Es
+        );
+        assert_eq!(Locale::from_accept_language("de, en;q=0"), Locale::En);
+        assert_eq!(Locale::from_accept_language("*"), Locale::En);
+        assert_eq!(Locale::from_accept_language(""), Locale::En);
+    }
+
+    #[test]
+    fn test_catalogs_cover_every_english_key() {
+        for locale in [Locale::PtBr, Locale::Es] {
+            for key in Locale::En.catalog().keys() {
+                assert!(
+                    locale.catalog().contains_key(key),
+                    "{} is missing {}",
+                    locale.tag(),
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate_fills_placeholders_and_falls_back() {
+        assert_eq!(
+            translate(Locale::Es, "webhook.test.failed", &[("error", &"timeout")]),
+            "Falló la entrega del webhook de prueba: timeout"
+        );
+        assert_eq!(translate(Locale::PtBr, "no.such.key", &[]), "no.such.key");
+    }
+
+    #[test]
+    fn test_message_renders_its_key_in_each_locale() {
+        let message = Message::localized("error.item_sku_exists", &[("sku", &"ABC-1")]);
+        assert_eq!(message.to_string(), "Item with SKU 'ABC-1' already exists");
+        assert_eq!(message.key(), Some("error.item_sku_exists"));
+        assert_eq!(
+            message.localize(Locale::PtBr),
+            "Já existe um item com o SKU 'ABC-1'"
+        );
+        assert_eq!(
+            Message::localized(
+                "error.field_between",
+                &[("field", &"quantity"), ("min", &1), ("max", &5)]
+            )
+            .localize(Locale::Es),
+            "quantity debe estar entre 1 y 5"
+        );
+        assert_eq!(
+            Message::from("Something without a catalog entry").localize(Locale::PtBr),
+            "Something without a catalog entry"
+        );
+        assert_eq!(
+            Message::from("Something without a catalog entry").key(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_locale_is_scoped_to_future() {
+        assert_eq!(
+            with_locale(Locale::Es, async { current_locale() }).await,
+            Locale::Es
+        );
+        assert_eq!(current_locale(), Locale::En);
+    }
+}
@@ -0,0 +1,110 @@
+//! JSON Merge Patch (RFC 7396): a partial document whose members replace the
+//! target's, where `null` removes a member and objects are merged recursively.
+
+use crate::shared::error::DomainError;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+/// Merge `patch` into `target` in place
+pub fn merge(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    if let Value::Object(target) = target {
+        for (name, value) in patch {
+            if value.is_null() {
+                target.remove(name);
+            } else {
+                merge(target.entry(name.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+/// `current` with `patch` applied. The patch must be an object, and the
+/// result must still be a valid `T`, so a required field can't be removed.
+pub fn apply<T: Serialize + DeserializeOwned>(
+    current: &T,
+    patch: &Value,
+) -> Result<T, DomainError> {
+    if !patch.is_object() {
+        return Err(DomainError::ValidationError(
+            "A merge patch must be a JSON object".into(),
+        ));
+    }
+    let mut document = serde_json::to_value(current).map_err(|e| {
+        DomainError::InfrastructureError(format!("Failed to serialize document: {}", e).into())
+    })?;
+    merge(&mut document, patch);
+    serde_json::from_value(document)
+        .map_err(|e| DomainError::ValidationError(format!("Invalid merge patch: {}", e).into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[test]
+    fn test_rfc_7396_example() {
+        let mut target = json!({
+            "title": "Goodbye!",
+            "author": { "givenName": "John", "familyName": "Doe" },
+            "tags": ["example", "sample"],
+            "content": "This will be unchanged"
+        });
+        merge(
+            &mut target,
+            &json!({
+                "title": "Hello!",
+                "phoneNumber": "+01-555-1234",
+                "author": { "familyName": null },
+                "tags": ["example"]
+            }),
+        );
+        assert_eq!(
+            target,
+            json!({
+                "title": "Hello!",
+                "author": { "givenName": "John" },
+                "tags": ["example"],
+                "content": "This will be unchanged",
+                "phoneNumber": "+01-555-1234"
+            })
+        );
+
+        let mut scalar = json!("text");
+        merge(&mut scalar, &json!({ "a": { "b": null, "c": 1 } }));
+        assert_eq!(scalar, json!({ "a": { "c": 1 } }));
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Details {
+        name: String,
+        note: Option<String>,
+    }
+
+    #[test]
+    fn test_apply_clears_optional_fields_and_rejects_invalid_results() {
+        let current = Details {
+            name: "Widget".to_string(),
+            note: Some("fragile".to_string()),
+        };
+        assert_eq!(
+            apply(&current, &json!({ "note": null })).unwrap(),
+            Details {
+                name: "Widget".to_string(),
+                note: None
+            }
+        );
+        assert!(apply(&current, &json!({ "name": null })).is_err());
+        assert!(apply(&current, &json!({ "id": "x" })).is_err());
+        assert!(apply(&current, &json!(["name"])).is_err());
+    }
+}
@@ -1 +1,12 @@
+pub mod api_error;
+pub mod batch_get;
 pub mod error;
+pub mod etag;
+pub mod i18n;
+pub mod merge_patch;
+pub mod money;
+pub mod pagination;
+pub mod quantity;
+pub mod spooled_file;
+pub mod tenant_scope;
+pub mod trace_id;
@@ -0,0 +1,91 @@
+use crate::shared::error::DomainError;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// Decimal places unit prices and costs are kept to, matching the NUMERIC(19, 4)
+/// columns they're stored in
+pub const UNIT_SCALE: u32 = 4;
+
+/// Decimal places line totals, order totals and valuations are rounded to
+pub const TOTAL_SCALE: u32 = 2;
+
+/// Decimal places exchange rates are kept to, matching the NUMERIC(20, 10)
+/// columns they're stored in
+pub const RATE_SCALE: u32 = 10;
+
+/// Round a unit price or cost to the stored precision, halves away from zero
+pub fn round_unit(amount: Decimal) -> Decimal {
+    amount.round_dp_with_strategy(UNIT_SCALE, RoundingStrategy::MidpointAwayFromZero)
+}
+
+/// Round a total to cents, halves away from zero
+pub fn round_total(amount: Decimal) -> Decimal {
+    amount.round_dp_with_strategy(TOTAL_SCALE, RoundingStrategy::MidpointAwayFromZero)
+}
+
+/// Round an exchange rate to the stored precision, halves away from zero
+pub fn round_rate(rate: Decimal) -> Decimal {
+    rate.round_dp_with_strategy(RATE_SCALE, RoundingStrategy::MidpointAwayFromZero)
+}
+
+/// `qty` units at `unit` each, rounded to cents. Order totals are the sum of
+/// these, so they always add up to the lines shown on the order.
+pub fn extend(qty: Decimal, unit: Decimal) -> Decimal {
+    round_total(qty * unit)
+}
+
+/// Convert an amount at an exchange rate, rounded to cents. Every currency
+/// conversion goes through here; a rate that isn't positive is refused.
+pub fn convert(amount: Decimal, rate: Decimal) -> Result<Decimal, DomainError> {
+    if rate <= Decimal::ZERO {
+        return Err(DomainError::ValidationError(
+            format!("Invalid exchange rate {}: rates must be positive", rate).into(),
+        ));
+    }
+    Ok(round_total(amount * rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounding_policy() {
+        assert_eq!(round_unit(Decimal::new(123455, 5)), Decimal::new(12346, 4));
+        assert_eq!(round_total(Decimal::new(1005, 3)), Decimal::new(101, 2));
+        assert_eq!(round_total(Decimal::new(-1005, 3)), Decimal::new(-101, 2));
+        assert_eq!(
+            extend(Decimal::new(3, 0), Decimal::new(3333, 4)),
+            Decimal::new(100, 2)
+        );
+        // Fractional quantities extend the same way: 2.5 m at 1.99
+        assert_eq!(
+            extend(Decimal::new(25, 1), Decimal::new(199, 2)),
+            Decimal::new(498, 2)
+        );
+    }
+
+    #[test]
+    fn test_convert_rounds_half_away_from_zero_and_refuses_bad_rates() {
+        assert_eq!(
+            convert(Decimal::new(1000, 2), Decimal::new(11, 1)).unwrap(),
+            Decimal::new(1100, 2)
+        );
+        // 0.125 rounds up, where banker's rounding would give 0.12
+        assert_eq!(
+            convert(Decimal::new(25, 2), Decimal::new(5, 1)).unwrap(),
+            Decimal::new(13, 2)
+        );
+        assert!(convert(Decimal::ONE, Decimal::ZERO).is_err());
+        assert!(convert(Decimal::ONE, Decimal::new(-1, 0)).is_err());
+    }
+
+    #[test]
+    fn test_large_sums_are_exact() {
+        // 100,000 lines of 0.10 sum to exactly 10,000.00, where f64 drifts
+        let total: Decimal = (0..100_000)
+            .map(|_| extend(Decimal::ONE, Decimal::new(10, 2)))
+            .sum();
+        assert_eq!(total, Decimal::new(1_000_000, 2));
+        assert_ne!((0..100_000).map(|_| 0.1_f64).sum::<f64>(), 10_000.0);
+    }
+}
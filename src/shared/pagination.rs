@@ -0,0 +1,125 @@
+use crate::shared::error::DomainError;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+pub const DEFAULT_PAGE_LIMIT: i64 = 50;
+pub const MAX_PAGE_LIMIT: i64 = 1000;
+
+/// `limit`/`cursor` query parameters accepted by every paginated collection
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PageRequest {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+impl PageRequest {
+    pub fn new(limit: Option<i64>, cursor: Option<String>) -> Self {
+        Self { limit, cursor }
+    }
+
+    pub fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .clamp(1, MAX_PAGE_LIMIT)
+    }
+
+    /// Decode the cursor into the repository's keyset type
+    pub fn after<K: DeserializeOwned>(&self) -> Result<Option<K>, DomainError> {
+        match self.cursor.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(cursor) => decode_cursor(cursor).map(Some),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CursorMeta {
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+/// Response envelope shared by all cursor-paginated collections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    pub cursor: CursorMeta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<i64>,
+}
+
+impl<T> Page<T> {
+    pub fn new(data: Vec<T>, next_cursor: Option<String>) -> Self {
+        Self {
+            data,
+            cursor: CursorMeta {
+                has_more: next_cursor.is_some(),
+                next_cursor,
+            },
+            total_count: None,
+        }
+    }
+
+    /// Build a page from a query that fetched `limit + 1` rows. The extra row is
+    /// dropped; it only tells us there is a next page, whose cursor is the key of
+    /// the last row kept.
+    pub fn from_rows<K: Serialize>(mut rows: Vec<T>, limit: i64, key: impl Fn(&T) -> K) -> Self {
+        let limit = limit.max(0) as usize;
+        let next_cursor = if rows.len() > limit {
+            rows.truncate(limit);
+            rows.last().map(|row| encode_cursor(&key(row)))
+        } else {
+            None
+        };
+        Self::new(rows, next_cursor)
+    }
+
+    pub fn with_total_count(mut self, total_count: i64) -> Self {
+        self.total_count = Some(total_count);
+        self
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            data: self.data.into_iter().map(f).collect(),
+            cursor: self.cursor,
+            total_count: self.total_count,
+        }
+    }
+}
+
+/// Cursors are the hex-encoded JSON of the last row's sort key. Clients should treat
+/// them as opaque; the encoding only needs to round-trip through a query string.
+pub fn encode_cursor<K: Serialize>(key: &K) -> String {
+    hex::encode(serde_json::to_vec(key).unwrap_or_default())
+}
+
+pub fn decode_cursor<K: DeserializeOwned>(cursor: &str) -> Result<K, DomainError> {
+    hex::decode(cursor)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or_else(|| DomainError::ValidationError("Invalid pagination cursor".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_rows_trims_lookahead_row() {
+        let page = Page::from_rows(vec![1, 2, 3], 2, |n| *n);
+        assert_eq!(page.data, vec![1, 2]);
+        assert!(page.cursor.has_more);
+        let next: i32 = decode_cursor(page.cursor.next_cursor.as_deref().unwrap()).unwrap();
+        assert_eq!(next, 2);
+
+        let last = Page::from_rows(vec![3], 2, |n| *n);
+        assert!(!last.cursor.has_more);
+        assert!(last.cursor.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_rejects_malformed_cursor() {
+        let request = PageRequest::new(None, Some("not-a-cursor".to_string()));
+        assert!(request.after::<i32>().is_err());
+        assert_eq!(PageRequest::default().limit(), DEFAULT_PAGE_LIMIT);
+    }
+}
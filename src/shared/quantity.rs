@@ -0,0 +1,58 @@
+use rust_decimal::Decimal;
+
+use crate::shared::error::DomainError;
+
+/// Most decimal places any quantity can have, matching the NUMERIC(19, 4)
+/// columns quantities are stored in
+pub const MAX_PRECISION: u32 = 4;
+
+/// Decimal places `qty` actually uses, ignoring trailing zeros
+pub fn decimal_places(qty: Decimal) -> u32 {
+    qty.normalize().scale()
+}
+
+/// Reject a quantity with more decimal places than the item allows, so a
+/// pallet of screws can't be counted in tenths and cable can't go below a
+/// millimetre when the item says metres to three places.
+pub fn check_precision(
+    qty: Decimal,
+    precision: u32,
+    sku: &str,
+    unit: &str,
+) -> Result<(), DomainError> {
+    if decimal_places(qty) <= precision {
+        return Ok(());
+    }
+    Err(DomainError::ValidationError(if precision == 0 {
+        format!(
+            "Quantity {} of {} must be a whole number of {}",
+            qty.normalize(),
+            sku,
+            unit
+        )
+        .into()
+    } else {
+        format!(
+            "Quantity {} of {} allows at most {} decimal places of {}",
+            qty.normalize(),
+            sku,
+            precision,
+            unit
+        )
+        .into()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_precision() {
+        assert!(check_precision(Decimal::new(12, 0), 0, "BOLT", "each").is_ok());
+        assert!(check_precision(Decimal::new(1200, 2), 0, "BOLT", "each").is_ok());
+        assert!(check_precision(Decimal::new(125, 1), 0, "BOLT", "each").is_err());
+        assert!(check_precision(Decimal::new(12125, 3), 3, "CABLE", "m").is_ok());
+        assert!(check_precision(Decimal::new(121255, 4), 3, "CABLE", "m").is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use crate::shared::error::DomainError;
+use axum::body::Body;
+use std::path::{Path, PathBuf};
+use tokio::fs::File;
+use tokio::io::AsyncWriteExt;
+use tokio_stream::StreamExt;
+use tokio_util::io::ReaderStream;
+use uuid::Uuid;
+
+/// An upload written to a temporary file as it arrives, so large files and
+/// imports aren't held in memory. The file is removed when this is dropped.
+#[derive(Debug)]
+pub struct SpooledFile {
+    path: PathBuf,
+    file: File,
+    len: u64,
+}
+
+impl SpooledFile {
+    pub async fn create() -> Result<Self, DomainError> {
+        let path = std::env::temp_dir().join(format!("twh-upload-{}", Uuid::new_v4()));
+        let file = File::create(&path).await.map_err(|e| {
+            DomainError::InfrastructureError(format!("Failed to create upload file: {}", e).into())
+        })?;
+        Ok(Self { path, file, len: 0 })
+    }
+
+    /// Append the next chunk of the upload
+    pub async fn write(&mut self, chunk: &[u8]) -> Result<(), DomainError> {
+        self.file.write_all(chunk).await.map_err(|e| {
+            DomainError::InfrastructureError(format!("Failed to write upload file: {}", e).into())
+        })?;
+        self.len += chunk.len() as u64;
+        Ok(())
+    }
+
+    /// Flush what has been written, once the upload is complete
+    pub async fn finish(mut self) -> Result<Self, DomainError> {
+        self.file.flush().await.map_err(|e| {
+            DomainError::InfrastructureError(format!("Failed to write upload file: {}", e).into())
+        })?;
+        Ok(self)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Bytes written so far
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Read the upload from the start, for parsers that take a reader
+    pub fn open(&self) -> Result<std::fs::File, DomainError> {
+        std::fs::File::open(&self.path).map_err(|e| {
+            DomainError::InfrastructureError(format!("Failed to read upload file: {}", e).into())
+        })
+    }
+
+    /// The upload as a request body read back from disk, for middleware that
+    /// had to look at it before the handler
+    pub async fn into_body(self) -> Result<Body, DomainError> {
+        let file = File::open(&self.path).await.map_err(|e| {
+            DomainError::InfrastructureError(format!("Failed to read upload file: {}", e).into())
+        })?;
+        // The stream holds on to `self` so the file is removed only once the
+        // body has been read or dropped
+        let stream = ReaderStream::new(file).map(move |chunk| {
+            let _spooled = &self;
+            chunk
+        });
+        Ok(Body::from_stream(stream))
+    }
+}
+
+impl Drop for SpooledFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[tokio::test]
+    async fn test_spooled_file_is_removed_when_dropped() {
+        let mut spooled = SpooledFile::create().await.unwrap();
+        spooled.write(b"sku,name\n").await.unwrap();
+        spooled.write(b"A-1,Widget\n").await.unwrap();
+        let spooled = spooled.finish().await.unwrap();
+        assert_eq!(spooled.len(), 20);
+
+        let mut content = String::new();
+        spooled
+            .open()
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "sku,name\nA-1,Widget\n");
+
+        let path = spooled.path().to_path_buf();
+        drop(spooled);
+        assert!(!path.exists());
+    }
+}
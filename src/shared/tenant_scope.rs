@@ -0,0 +1,57 @@
+use std::future::Future;
+use uuid::Uuid;
+
+tokio::task_local! {
+    static CURRENT_TENANT: Uuid;
+    static CURRENT_ACTOR: Uuid;
+}
+
+/// Run `future` with `tenant_id` as the request's tenant. The tenant middleware wraps
+/// each request in this so code without access to the request (e.g. event
+/// construction inside use cases) can still attribute work to a tenant.
+pub async fn with_tenant<F: Future>(tenant_id: Uuid, future: F) -> F::Output {
+    CURRENT_TENANT.scope(tenant_id, future).await
+}
+
+/// Tenant of the request being handled, if any. Tasks started with `tokio::spawn`
+/// don't inherit it, so read it before spawning.
+pub fn current_tenant() -> Option<Uuid> {
+    CURRENT_TENANT.try_with(|tenant_id| *tenant_id).ok()
+}
+
+/// Run `future` on behalf of the logged-in user `actor_id`. Database
+/// connections carry it, so triggers can attribute the rows they write.
+pub async fn with_actor<F: Future>(actor_id: Uuid, future: F) -> F::Output {
+    CURRENT_ACTOR.scope(actor_id, future).await
+}
+
+/// The logged-in user the current task acts for, if any. Like the tenant it
+/// isn't inherited by spawned tasks.
+pub fn current_actor() -> Option<Uuid> {
+    CURRENT_ACTOR.try_with(|actor_id| *actor_id).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tenant_is_scoped_to_future() {
+        let tenant_id = Uuid::new_v4();
+        assert_eq!(
+            with_tenant(tenant_id, async { current_tenant() }).await,
+            Some(tenant_id)
+        );
+        assert_eq!(current_tenant(), None);
+    }
+
+    #[tokio::test]
+    async fn test_actor_is_scoped_to_future() {
+        let actor_id = Uuid::new_v4();
+        assert_eq!(
+            with_actor(actor_id, async { current_actor() }).await,
+            Some(actor_id)
+        );
+        assert_eq!(current_actor(), None);
+    }
+}
@@ -0,0 +1,56 @@
+use std::future::Future;
+use uuid::Uuid;
+
+tokio::task_local! {
+    static CURRENT_TRACE_ID: String;
+}
+
+pub const TRACE_ID_HEADER: &str = "x-trace-id";
+
+/// Run `future` with `trace_id` as the request's trace ID, so error responses and
+/// logs can quote it without the request being threaded through
+pub async fn with_trace_id<F: Future>(trace_id: String, future: F) -> F::Output {
+    CURRENT_TRACE_ID.scope(trace_id, future).await
+}
+
+/// Trace ID of the request being handled, if any
+pub fn current_trace_id() -> Option<String> {
+    CURRENT_TRACE_ID.try_with(|trace_id| trace_id.clone()).ok()
+}
+
+/// Use the caller's trace ID if it looks sane (so it can be correlated across
+/// services), otherwise start a new one
+pub fn trace_id_or_new(incoming: Option<&str>) -> String {
+    incoming
+        .map(str::trim)
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= 128
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().simple().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_trace_id_is_scoped_to_future() {
+        assert_eq!(
+            with_trace_id("abc".to_string(), async { current_trace_id() }).await,
+            Some("abc".to_string())
+        );
+        assert_eq!(current_trace_id(), None);
+    }
+
+    #[test]
+    fn test_incoming_trace_id_is_validated() {
+        assert_eq!(trace_id_or_new(Some("req-123_a")), "req-123_a");
+        assert_eq!(trace_id_or_new(Some("bad id\n")).len(), 32);
+        assert_eq!(trace_id_or_new(None).len(), 32);
+    }
+}
@@ -0,0 +1,156 @@
+//! Throwaway tenants in the migrated database at `DATABASE_URL`, for tests that
+//! exercise SQL directly. Such tests are marked
+//! `#[ignore = "needs a migrated database at DATABASE_URL"]` and run with
+//! `cargo test <name> -- --ignored`:
+//!
+//! ```ignore
+//! let db = TestDatabase::connect().await;
+//! let tenant_id = db.create_tenant("Cycle counts").await;
+//! ```
+
+use crate::infrastructure::config::app_config::DatabaseConfig;
+use crate::infrastructure::repositories::tenant_pool::connect_tenant_pool;
+use sqlx::PgPool;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// The user the migrations seed, for rows that need a creator
+pub const SEEDED_USER: &str = "550e8400-e29b-41d4-a716-446655440000";
+
+/// Connections to the test database, and the tenants created through them.
+/// Dropping it removes the tenants and everything they own, whether or not
+/// the test's assertions passed.
+pub struct TestDatabase {
+    /// Connected outside any tenant, for arranging data and checking it
+    pub admin: PgPool,
+    /// Tenant-scoped pool, as the repositories use it
+    pub pool: Arc<PgPool>,
+    url: String,
+    tenants: Mutex<Vec<Uuid>>,
+}
+
+impl TestDatabase {
+    pub async fn connect() -> Self {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let admin = PgPool::connect(&url)
+            .await
+            .expect("Failed to connect to test database");
+        let pool = Arc::new(
+            connect_tenant_pool(&DatabaseConfig {
+                url: url.clone(),
+                ..DatabaseConfig::default()
+            })
+            .await
+            .expect("Failed to connect to test database"),
+        );
+        Self {
+            admin,
+            pool,
+            url,
+            tenants: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// A new sandbox tenant, removed again when `self` is dropped
+    pub async fn create_tenant(&self, name: &str) -> Uuid {
+        let tenant_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO tenants (id, name, tenant_type, status, database_schema)
+             VALUES ($1, $2, 'SANDBOX', 'ACTIVE', 'test_' || replace($1::text, '-', ''))",
+        )
+        .bind(tenant_id)
+        .bind(name)
+        .execute(&self.admin)
+        .await
+        .expect("Failed to create test tenant");
+        self.tenants.lock().unwrap().push(tenant_id);
+        tenant_id
+    }
+
+    /// Delete every row `tenant_id` owns, keeping the tenant itself
+    pub async fn clear_tenant(&self, tenant_id: Uuid) {
+        delete_tenant_rows(&self.admin, &[tenant_id])
+            .await
+            .expect("Failed to clear test tenant");
+    }
+}
+
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        let tenants = std::mem::take(&mut *self.tenants.lock().unwrap());
+        if tenants.is_empty() {
+            return;
+        }
+        // Drop can't await, and the test's runtime may be the single-threaded
+        // one, so the cleanup runs on a thread with a runtime of its own
+        let url = self.url.clone();
+        let cleanup = {
+            let tenants = tenants.clone();
+            std::thread::spawn(move || {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?
+                    .block_on(async {
+                        let admin = PgPool::connect(&url).await?;
+                        delete_tenant_rows(&admin, &tenants).await?;
+                        sqlx::query("DELETE FROM tenants WHERE id = ANY($1)")
+                            .bind(&tenants)
+                            .execute(&admin)
+                            .await?;
+                        Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+                    })
+            })
+            .join()
+        };
+        let message = match cleanup {
+            Ok(Ok(())) => return,
+            Ok(Err(error)) => format!("Failed to remove test tenants {tenants:?}: {error}"),
+            Err(_) => format!("Failed to remove test tenants {tenants:?}"),
+        };
+        // A second panic while the test is already failing would abort
+        if std::thread::panicking() {
+            eprintln!("{message}");
+        } else {
+            panic!("{message}");
+        }
+    }
+}
+
+/// Delete the rows of `tenants` from every table with a `tenant_id`. A table
+/// other rows still point at can't be emptied yet, so it is retried after the
+/// rest until a round deletes nothing new.
+async fn delete_tenant_rows(admin: &PgPool, tenants: &[Uuid]) -> Result<(), sqlx::Error> {
+    let mut tables: Vec<String> = sqlx::query_scalar(
+        "SELECT c.table_name::text
+         FROM information_schema.columns c
+         JOIN information_schema.tables t USING (table_schema, table_name)
+         WHERE c.table_schema = 'public' AND c.column_name = 'tenant_id'
+           AND t.table_type = 'BASE TABLE'",
+    )
+    .fetch_all(admin)
+    .await?;
+
+    while !tables.is_empty() {
+        let mut referenced = Vec::new();
+        let mut last_error = None;
+        for table in &tables {
+            let deleted = sqlx::query(&format!("DELETE FROM {table} WHERE tenant_id = ANY($1)"))
+                .bind(tenants)
+                .execute(admin)
+                .await;
+            match deleted {
+                Ok(_) => {}
+                Err(sqlx::Error::Database(error)) if error.is_foreign_key_violation() => {
+                    referenced.push(table.clone());
+                    last_error = Some(sqlx::Error::Database(error));
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        if referenced.len() == tables.len() {
+            return Err(last_error.expect("a table failed to empty"));
+        }
+        tables = referenced;
+    }
+    Ok(())
+}
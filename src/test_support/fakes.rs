@@ -0,0 +1,296 @@
+//! In-memory stand-ins for the catalogue's repositories and services, so
+//! handlers can be tested without a database:
+//!
+//! ```ignore
+//! let fakes = CatalogFakes::default();
+//! let router = Router::new()
+//!     .route("/items/{id}", get(get_item_handler))
+//!     .with_state(fakes.state());
+//! ```
+
+use crate::domain::entities::item::Item;
+use crate::domain::entities::list_filter::ListFilter;
+use crate::domain::entities::location::Location;
+use crate::domain::entities::webhook::WebhookEvent;
+use crate::domain::services::item_repository::ItemRepository;
+use crate::domain::services::location_repository::LocationRepository;
+use crate::domain::services::quota_service::{QuotaResource, QuotaService, RateLimitOverrides};
+use crate::domain::services::webhook_dispatcher::WebhookDispatcher;
+use crate::infrastructure::controllers::catalog_state::CatalogState;
+use crate::infrastructure::services::offline_address_validator::OfflineAddressValidator;
+use crate::shared::error::DomainError;
+use crate::shared::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Items kept in a map, listed in ID order. List filters are not applied.
+#[derive(Default)]
+pub struct InMemoryItemRepository {
+    items: Mutex<BTreeMap<Uuid, Item>>,
+}
+
+impl InMemoryItemRepository {
+    fn find(&self, matches: impl Fn(&Item) -> bool) -> Option<Item> {
+        self.items
+            .lock()
+            .unwrap()
+            .values()
+            .find(|item| matches(item))
+            .cloned()
+    }
+}
+
+#[async_trait]
+impl ItemRepository for InMemoryItemRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Item>, DomainError> {
+        Ok(self.items.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Item>, DomainError> {
+        let items = self.items.lock().unwrap();
+        Ok(ids.iter().filter_map(|id| items.get(id).cloned()).collect())
+    }
+
+    async fn find_by_sku(&self, sku: &str) -> Result<Option<Item>, DomainError> {
+        Ok(self.find(|item| item.sku == sku))
+    }
+
+    async fn find_by_barcode(&self, barcode: &str) -> Result<Option<Item>, DomainError> {
+        Ok(self.find(|item| item.barcode.as_deref() == Some(barcode)))
+    }
+
+    async fn save(&self, item: &Item) -> Result<(), DomainError> {
+        self.items.lock().unwrap().insert(item.id, item.clone());
+        Ok(())
+    }
+
+    async fn update(&self, item: &Item) -> Result<(), DomainError> {
+        match self.items.lock().unwrap().get_mut(&item.id) {
+            Some(existing) => {
+                *existing = item.clone();
+                Ok(())
+            }
+            None => Err(DomainError::NotFound(format!("Item {}", item.id).into())),
+        }
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        match self.items.lock().unwrap().remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(DomainError::NotFound(format!("Item {}", id).into())),
+        }
+    }
+
+    async fn list(
+        &self,
+        _filter: &ListFilter,
+        page: &PageRequest,
+    ) -> Result<Page<Item>, DomainError> {
+        let after: Option<Uuid> = page.after()?;
+        let rows = self
+            .items
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|item| after.is_none_or(|after| item.id > after))
+            .take(page.limit() as usize + 1)
+            .cloned()
+            .collect();
+        Ok(Page::from_rows(rows, page.limit(), |item| item.id))
+    }
+
+    async fn list_by_product(&self, product_id: Uuid) -> Result<Vec<Item>, DomainError> {
+        Ok(self
+            .items
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|item| item.product_id == Some(product_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn count(&self, _filter: &ListFilter) -> Result<i64, DomainError> {
+        Ok(self.items.lock().unwrap().len() as i64)
+    }
+
+    async fn sku_exists(
+        &self,
+        sku: &str,
+        exclude_item_id: Option<Uuid>,
+    ) -> Result<bool, DomainError> {
+        Ok(self
+            .find(|item| item.sku == sku && Some(item.id) != exclude_item_id)
+            .is_some())
+    }
+
+    async fn barcode_exists(
+        &self,
+        barcode: &str,
+        exclude_item_id: Option<Uuid>,
+    ) -> Result<bool, DomainError> {
+        Ok(self
+            .find(|item| {
+                item.barcode.as_deref() == Some(barcode) && Some(item.id) != exclude_item_id
+            })
+            .is_some())
+    }
+}
+
+/// Locations kept in a map, listed in ID order
+#[derive(Default)]
+pub struct InMemoryLocationRepository {
+    locations: Mutex<BTreeMap<Uuid, Location>>,
+}
+
+#[async_trait]
+impl LocationRepository for InMemoryLocationRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Location>, DomainError> {
+        Ok(self.locations.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Location>, DomainError> {
+        let locations = self.locations.lock().unwrap();
+        Ok(ids
+            .iter()
+            .filter_map(|id| locations.get(id).cloned())
+            .collect())
+    }
+
+    async fn find_by_code(&self, code: &str) -> Result<Option<Location>, DomainError> {
+        Ok(self
+            .locations
+            .lock()
+            .unwrap()
+            .values()
+            .find(|location| location.code.as_deref() == Some(code))
+            .cloned())
+    }
+
+    async fn save(&self, location: &Location) -> Result<(), DomainError> {
+        self.locations
+            .lock()
+            .unwrap()
+            .insert(location.id, location.clone());
+        Ok(())
+    }
+
+    async fn update(&self, location: &Location) -> Result<(), DomainError> {
+        match self.locations.lock().unwrap().get_mut(&location.id) {
+            Some(existing) => {
+                *existing = location.clone();
+                Ok(())
+            }
+            None => Err(DomainError::NotFound(
+                format!("Location {}", location.id).into(),
+            )),
+        }
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        match self.locations.lock().unwrap().remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(DomainError::NotFound(format!("Location {}", id).into())),
+        }
+    }
+
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Location>, DomainError> {
+        Ok(self
+            .locations
+            .lock()
+            .unwrap()
+            .values()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn count(&self) -> Result<i64, DomainError> {
+        Ok(self.locations.lock().unwrap().len() as i64)
+    }
+
+    async fn code_exists(
+        &self,
+        code: &str,
+        exclude_location_id: Option<Uuid>,
+    ) -> Result<bool, DomainError> {
+        Ok(self.locations.lock().unwrap().values().any(|location| {
+            location.code.as_deref() == Some(code) && Some(location.id) != exclude_location_id
+        }))
+    }
+}
+
+/// Records the events it is asked to dispatch instead of delivering them
+#[derive(Default)]
+pub struct RecordingWebhookDispatcher {
+    pub events: Mutex<Vec<WebhookEvent>>,
+}
+
+#[async_trait]
+impl WebhookDispatcher for RecordingWebhookDispatcher {
+    async fn dispatch_event(&self, event: &WebhookEvent) -> Result<(), DomainError> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+
+    async fn retry_delivery(&self, _delivery_id: Uuid) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn process_pending_deliveries(&self) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn publish_outbox(
+        &self,
+        _ids: Option<&[Uuid]>,
+        _limit: i64,
+    ) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+}
+
+/// A quota that never runs out
+pub struct UnlimitedQuotaService;
+
+#[async_trait]
+impl QuotaService for UnlimitedQuotaService {
+    async fn reserve(&self, _tenant_id: Uuid, _resource: QuotaResource) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn release(&self, _tenant_id: Uuid, _resource: QuotaResource) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn api_calls_per_hour(&self, _tenant_id: Uuid) -> Result<i64, DomainError> {
+        Ok(i64::MAX)
+    }
+
+    async fn rate_limits(&self, _tenant_id: Uuid) -> Result<RateLimitOverrides, DomainError> {
+        Ok(RateLimitOverrides::default())
+    }
+}
+
+/// The fakes behind a [`CatalogState`], kept so tests can arrange and inspect them
+#[derive(Default)]
+pub struct CatalogFakes {
+    pub items: Arc<InMemoryItemRepository>,
+    pub locations: Arc<InMemoryLocationRepository>,
+    pub webhooks: Arc<RecordingWebhookDispatcher>,
+}
+
+impl CatalogFakes {
+    pub fn state(&self) -> CatalogState {
+        CatalogState {
+            item_repository: self.items.clone(),
+            location_repository: self.locations.clone(),
+            webhook_dispatcher: self.webhooks.clone(),
+            quota_service: Arc::new(UnlimitedQuotaService),
+            address_validator: Arc::new(OfflineAddressValidator),
+        }
+    }
+}
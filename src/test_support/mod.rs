@@ -0,0 +1,301 @@
+//! End-to-end harness: the whole application, as `AppBuilder` wires it, over a
+//! throwaway Postgres and Redis started with testcontainers.
+//!
+//! Tests built on it need a Docker daemon, so they are marked ignored and run
+//! with `cargo test -- --ignored`:
+//!
+//! ```ignore
+//! let app = TestApp::builder().build().await;
+//! let item = app.post("/items", json!({ ... })).await.expect_status(StatusCode::CREATED);
+//! ```
+
+pub mod database;
+pub mod fakes;
+
+use crate::bootstrap::{App, AppBuilder};
+use crate::infrastructure::config::app_config::AppConfig;
+use crate::infrastructure::repositories::tenant_pool::connect_tenant_pool;
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode};
+use axum::Router;
+use serde_json::Value;
+use sqlx::PgPool;
+use std::sync::Arc;
+use testcontainers_modules::postgres::Postgres;
+use testcontainers_modules::redis::Redis;
+use testcontainers_modules::testcontainers::runners::AsyncRunner;
+use testcontainers_modules::testcontainers::ContainerAsync;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+/// Settings for a [`TestApp`] before its containers are started
+pub struct TestAppBuilder {
+    config: AppConfig,
+}
+
+impl TestAppBuilder {
+    /// Adjust the configuration the app is built with. The database, Redis
+    /// and blob storage settings are filled in by `build`.
+    pub fn configure(mut self, configure: impl FnOnce(&mut AppConfig)) -> Self {
+        configure(&mut self.config);
+        self
+    }
+
+    /// Start Postgres and Redis, migrate the database, create the tenant and
+    /// build the app over them
+    pub async fn build(self) -> TestApp {
+        let postgres = Postgres::default()
+            .start()
+            .await
+            .expect("Failed to start Postgres container");
+        let redis = Redis::default()
+            .start()
+            .await
+            .expect("Failed to start Redis container");
+
+        let mut config = self.config;
+        config.database.url = format!(
+            "postgres://postgres:postgres@{}:{}/postgres",
+            postgres.get_host().await.expect("Postgres host"),
+            postgres
+                .get_host_port_ipv4(5432)
+                .await
+                .expect("Postgres port")
+        );
+        config.redis.url = format!(
+            "redis://{}:{}",
+            redis.get_host().await.expect("Redis host"),
+            redis.get_host_port_ipv4(6379).await.expect("Redis port")
+        );
+        config.storage.path = std::env::temp_dir()
+            .join(format!("twh-test-blobs-{}", Uuid::new_v4()))
+            .to_string_lossy()
+            .into_owned();
+
+        config.database.run_migrations = true;
+        let config = Arc::new(config);
+        let App {
+            router,
+            shutdown,
+            background,
+            ..
+        } = AppBuilder::new(Arc::clone(&config))
+            .build()
+            .await
+            .expect("Failed to build the app");
+
+        let pool = Arc::new(
+            connect_tenant_pool(&config.database)
+                .await
+                .expect("Failed to connect to test database"),
+        );
+        let tenant_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO tenants (id, name, tenant_type, status, database_schema)
+             VALUES ($1, 'Test tenant', 'SANDBOX', 'ACTIVE', 'test_' || replace($1::text, '-', ''))",
+        )
+        .bind(tenant_id)
+        .execute(&*pool)
+        .await
+        .expect("Failed to create test tenant");
+
+        TestApp {
+            router,
+            pool,
+            tenant_id,
+            shutdown,
+            _background: background,
+            _postgres: postgres,
+            _redis: redis,
+        }
+    }
+}
+
+/// The application over its own containers, called in-process as one tenant.
+/// Dropping it stops the background workers and removes the containers.
+pub struct TestApp {
+    router: Router,
+    /// The app's pool, for arranging data or checking it directly
+    pub pool: Arc<PgPool>,
+    /// Tenant every request is made as
+    pub tenant_id: Uuid,
+    shutdown: CancellationToken,
+    _background: JoinSet<()>,
+    _postgres: ContainerAsync<Postgres>,
+    _redis: ContainerAsync<Redis>,
+}
+
+impl TestApp {
+    pub fn builder() -> TestAppBuilder {
+        let mut config = AppConfig::default();
+        // Requests name the test tenant in X-Tenant-ID rather than logging in
+        config.auth.allow_tenant_header = true;
+        TestAppBuilder { config }
+    }
+
+    /// Send a request as the test tenant, with `body` as JSON if given
+    pub async fn request(&self, method: Method, path: &str, body: Option<Value>) -> TestResponse {
+        let builder = Request::builder()
+            .method(method)
+            .uri(path)
+            .header("X-Tenant-ID", self.tenant_id.to_string());
+        let request = match body {
+            Some(body) => builder
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .expect("Invalid test request");
+
+        let response = self
+            .router
+            .clone()
+            .oneshot(request)
+            .await
+            .expect("Router is infallible");
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read response body");
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()))
+        };
+
+        TestResponse {
+            status,
+            body,
+            path: path.to_string(),
+        }
+    }
+
+    pub async fn get(&self, path: &str) -> TestResponse {
+        self.request(Method::GET, path, None).await
+    }
+
+    pub async fn post(&self, path: &str, body: Value) -> TestResponse {
+        self.request(Method::POST, path, Some(body)).await
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+    }
+}
+
+/// A response with its body read as JSON (or a JSON string when it isn't)
+#[derive(Debug)]
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub body: Value,
+    path: String,
+}
+
+impl TestResponse {
+    /// The body, after asserting the status
+    pub fn expect_status(self, status: StatusCode) -> Value {
+        assert_eq!(
+            self.status, status,
+            "unexpected status from {}: {}",
+            self.path, self.body
+        );
+        self.body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn id(value: &Value) -> String {
+        value.as_str().expect("id should be a string").to_string()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Docker for testcontainers"]
+    async fn test_received_stock_is_shipped_against_a_sales_order() {
+        let app = TestApp::builder().build().await;
+
+        let item = app
+            .post(
+                "/items",
+                json!({ "sku": "E2E-001", "name": "Widget", "unit": "EA", "cost_price": 4.0 }),
+            )
+            .await
+            .expect_status(StatusCode::CREATED);
+        let item_id = id(&item["id"]);
+        let location = app
+            .post("/locations", json!({ "name": "Main", "code": "MAIN" }))
+            .await
+            .expect_status(StatusCode::CREATED);
+        let location_id = id(&location["id"]);
+
+        let po = app
+            .post(
+                "/purchase_orders",
+                json!({
+                    "supplier_id": Uuid::new_v4(),
+                    "lines": [{ "item_id": item_id, "qty_ordered": 10, "unit_cost": 4.0 }],
+                }),
+            )
+            .await
+            .expect_status(StatusCode::CREATED);
+        app.post(
+            &format!("/purchase_orders/{}/receive", id(&po["id"])),
+            json!({
+                "received_lines": [{ "po_line_id": po["lines"][0]["id"], "qty_received": 10 }],
+                "destination_location_id": location_id,
+            }),
+        )
+        .await
+        .expect_status(StatusCode::OK);
+
+        let so = app
+            .post(
+                "/sales_orders",
+                json!({
+                    "lines": [{ "item_id": item_id, "qty": 3, "unit_price": 9.5 }],
+                    "fulfillment_location_id": location_id,
+                }),
+            )
+            .await
+            .expect_status(StatusCode::OK);
+        let so = &so["sales_order"];
+        let shipped = app
+            .post(
+                &format!("/sales_orders/{}/ship", id(&so["id"])),
+                json!({
+                    "lines": [{
+                        "so_line_id": so["lines"][0]["id"],
+                        "qty_shipped": 3,
+                        "location_id": location_id,
+                    }],
+                }),
+            )
+            .await
+            .expect_status(StatusCode::OK);
+        assert_eq!(shipped["sales_order"]["status"], "Shipped");
+
+        let level = app
+            .get(&format!("/stock/{item_id}/{location_id}"))
+            .await
+            .expect_status(StatusCode::OK);
+        assert_eq!(level["quantity_on_hand"].as_f64(), Some(7.0));
+
+        let movements: Vec<String> = sqlx::query_scalar(
+            "SELECT movement_type FROM stock_movements
+             WHERE item_id = $1::uuid AND quantity <> 0 ORDER BY created_at",
+        )
+        .bind(&item_id)
+        .fetch_all(&*app.pool)
+        .await
+        .unwrap();
+        assert_eq!(movements, ["inbound", "outbound"]);
+    }
+}